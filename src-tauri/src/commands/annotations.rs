@@ -0,0 +1,118 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::annotations::{Bookmark, PresetNotes};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tauri::State;
+
+fn running_simulation_type(manager: &SimulationManager) -> Result<&'static str, Diagnostic> {
+    manager
+        .current_simulation
+        .as_ref()
+        .map(|simulation| simulation.type_name())
+        .ok_or_else(|| Diagnostic::not_running("No simulation running"))
+}
+
+/// Notes on the running simulation's presets, keyed by preset name
+#[tauri::command]
+pub async fn get_preset_notes(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<BTreeMap<String, String>, Diagnostic> {
+    let simulation_type = running_simulation_type(&*manager.lock().await)?;
+    let notes = PresetNotes::load(simulation_type).map_err(Diagnostic::from)?;
+    Ok(notes.notes().clone())
+}
+
+/// Attach a note to a preset of the running simulation, or remove it when
+/// `note` is empty
+#[tauri::command]
+pub async fn set_preset_note(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    preset_name: String,
+    note: String,
+) -> Result<String, Diagnostic> {
+    let simulation_type = running_simulation_type(&*manager.lock().await)?;
+    let mut notes = PresetNotes::load(simulation_type).map_err(Diagnostic::from)?;
+    notes.set(&preset_name, &note);
+    notes.save(simulation_type).map_err(|e| {
+        Diagnostic::context(format!("Failed to save note for '{}'", preset_name), e)
+    })?;
+    Ok(format!("Note for '{}' saved", preset_name))
+}
+
+/// Bookmark the running simulation's settings, camera and color scheme
+#[tauri::command]
+pub async fn save_bookmark(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+    note: String,
+) -> Result<Bookmark, Diagnostic> {
+    let configuration = manager
+        .lock()
+        .await
+        .shared_configuration()
+        .map_err(Diagnostic::from)?;
+    let bookmark = Bookmark::new(&name, &note, configuration).map_err(Diagnostic::from)?;
+    let path = bookmark
+        .save()
+        .map_err(|e| Diagnostic::context(format!("Failed to save bookmark '{}'", name), e))?;
+    tracing::info!("Saved bookmark to {}", path.display());
+    Ok(bookmark)
+}
+
+#[tauri::command]
+pub async fn get_bookmarks(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Vec<Bookmark>, Diagnostic> {
+    let simulation_type = running_simulation_type(&*manager.lock().await)?;
+    Ok(Bookmark::list(simulation_type))
+}
+
+#[tauri::command]
+pub async fn apply_bookmark(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    name: String,
+) -> Result<String, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    let bookmark =
+        Bookmark::load(running_simulation_type(&sim_manager)?, &name).map_err(Diagnostic::from)?;
+    sim_manager
+        .apply_shared_configuration(&bookmark.configuration, &device, &queue)
+        .map_err(|e| {
+            tracing::error!("Failed to apply bookmark '{}': {}", name, e);
+            Diagnostic::context(format!("Failed to apply bookmark '{}'", name), e)
+        })?;
+    Ok(format!("Bookmark '{}' applied", name))
+}
+
+#[tauri::command]
+pub async fn update_bookmark_note(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+    note: String,
+) -> Result<Bookmark, Diagnostic> {
+    let simulation_type = running_simulation_type(&*manager.lock().await)?;
+    let mut bookmark = Bookmark::load(simulation_type, &name).map_err(Diagnostic::from)?;
+    bookmark.note = note;
+    bookmark
+        .save()
+        .map_err(|e| Diagnostic::context(format!("Failed to save bookmark '{}'", name), e))?;
+    Ok(bookmark)
+}
+
+#[tauri::command]
+pub async fn delete_bookmark(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+) -> Result<String, Diagnostic> {
+    let simulation_type = running_simulation_type(&*manager.lock().await)?;
+    Bookmark::delete(simulation_type, &name)
+        .map_err(|e| Diagnostic::context(format!("Failed to delete bookmark '{}'", name), e))?;
+    Ok(format!("Bookmark '{}' deleted", name))
+}
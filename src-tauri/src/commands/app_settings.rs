@@ -1,7 +1,16 @@
+use crate::error::{Diagnostic, ErrorCode};
+use crate::simulation::SimulationManager;
+use crate::simulation::keymap::{KeyAction, Keymap};
+use crate::simulation::overlay::{OverlayMode, select_alpha_mode};
+use crate::simulation::watchdog::WatchdogConfig;
+use crate::simulations::shared::{RewindConfig, gpu_budget};
+use crate::simulations::traits::Simulation;
 use dirs::home_dir;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tauri::Manager;
 use toml;
 use wgpu;
@@ -33,12 +42,36 @@ impl Into<u32> for TextureFiltering {
     }
 }
 
+/// Output color space for the display surface
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    #[default]
+    Srgb,
+    DisplayP3,
+}
+
+/// Background shader shown behind the main menu
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum MainMenuBackground {
+    #[default]
+    FbmSwirl,
+    Aurora,
+    Starfield,
+    LavaLamp,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     // Display Settings
     pub default_fps_limit: u32,
     pub default_fps_limit_enabled: bool,
     pub texture_filtering: TextureFiltering,
+    // Takes effect on next launch, since every pipeline is built for the surface format
+    #[serde(default)]
+    pub color_space: ColorSpace,
+    // Internal resolution relative to the window's physical pixels
+    #[serde(default = "default_render_scale")]
+    pub render_scale: f32,
 
     // Window Settings
     pub window_width: u32,
@@ -53,6 +86,61 @@ pub struct AppSettings {
 
     // Camera Settings
     pub default_camera_sensitivity: f32,
+
+    // Main Menu Settings
+    #[serde(default)]
+    pub main_menu_background: MainMenuBackground,
+    // Pick the main menu color scheme from the local time of day
+    #[serde(default)]
+    pub main_menu_time_of_day_themes: bool,
+
+    // Rewind Settings
+    #[serde(default)]
+    pub rewind_enabled: bool,
+    #[serde(default = "default_rewind_duration_seconds")]
+    pub rewind_duration_seconds: f32,
+    #[serde(default = "default_rewind_snapshot_interval_seconds")]
+    pub rewind_snapshot_interval_seconds: f32,
+
+    // GPU Settings
+    // Memory budget for large GPU allocations, 0 picks one for the adapter
+    #[serde(default)]
+    pub gpu_memory_budget_mb: u32,
+
+    // Watchdog Settings
+    #[serde(default = "default_watchdog_enabled")]
+    pub watchdog_enabled: bool,
+    // Reset the simulation, or clamp the settings that broke it, instead of only warning
+    #[serde(default)]
+    pub watchdog_auto_recover: bool,
+
+    // Keyboard Settings
+    #[serde(default)]
+    pub keybindings: Keymap,
+
+    // Simulation Settings
+    // Preset each simulation starts with, by simulation type, in place of its built-in defaults
+    #[serde(default)]
+    pub default_presets: BTreeMap<String, String>,
+    // Color script run over each simulation type's color scheme
+    #[serde(default)]
+    pub color_scripts: BTreeMap<String, String>,
+}
+
+fn default_render_scale() -> f32 {
+    1.0
+}
+
+fn default_rewind_duration_seconds() -> f32 {
+    10.0
+}
+
+fn default_rewind_snapshot_interval_seconds() -> f32 {
+    0.5
+}
+
+fn default_watchdog_enabled() -> bool {
+    true
 }
 
 impl AppSettings {
@@ -65,6 +153,15 @@ impl AppSettings {
             .map_err(|e| format!("Failed to read settings file: {}", e))?;
         toml::from_str(&content).map_err(|e| format!("Failed to parse settings file: {}", e))
     }
+
+    pub(crate) fn save_to_file(&self) -> Result<(), String> {
+        fs::create_dir_all(get_settings_dir())
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        fs::write(get_settings_path(), content)
+            .map_err(|e| format!("Failed to save settings: {}", e))
+    }
 }
 
 impl Default for AppSettings {
@@ -74,6 +171,8 @@ impl Default for AppSettings {
             default_fps_limit: 60,
             default_fps_limit_enabled: false,
             texture_filtering: TextureFiltering::Linear,
+            color_space: ColorSpace::Srgb,
+            render_scale: default_render_scale(),
 
             // Window Settings
             window_width: 1200,
@@ -88,6 +187,29 @@ impl Default for AppSettings {
 
             // Camera Settings
             default_camera_sensitivity: 1.0,
+
+            // Main Menu Settings
+            main_menu_background: MainMenuBackground::FbmSwirl,
+            main_menu_time_of_day_themes: false,
+
+            // Rewind Settings
+            rewind_enabled: false,
+            rewind_duration_seconds: default_rewind_duration_seconds(),
+            rewind_snapshot_interval_seconds: default_rewind_snapshot_interval_seconds(),
+
+            // GPU Settings
+            gpu_memory_budget_mb: 0,
+
+            // Watchdog Settings
+            watchdog_enabled: default_watchdog_enabled(),
+            watchdog_auto_recover: false,
+
+            // Keyboard Settings
+            keybindings: Keymap::default(),
+
+            // Simulation Settings
+            default_presets: BTreeMap::new(),
+            color_scripts: BTreeMap::new(),
         }
     }
 }
@@ -103,7 +225,7 @@ pub(crate) fn get_settings_dir() -> PathBuf {
 }
 
 #[tauri::command]
-pub async fn get_app_settings() -> Result<AppSettings, String> {
+pub async fn get_app_settings() -> Result<AppSettings, Diagnostic> {
     let settings_path = get_settings_path();
 
     if !settings_path.exists() {
@@ -127,23 +249,71 @@ pub async fn get_app_settings() -> Result<AppSettings, String> {
 }
 
 #[tauri::command]
-pub async fn save_app_settings(settings: AppSettings) -> Result<String, String> {
+pub async fn save_app_settings(
+    settings: AppSettings,
+    manager: tauri::State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: tauri::State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<String, Diagnostic> {
     let settings_dir = get_settings_dir();
     let settings_path = get_settings_path();
 
     // Create settings directory if it doesn't exist
     if !settings_dir.exists() {
         if let Err(e) = fs::create_dir_all(&settings_dir) {
-            return Err(format!("Failed to create settings directory: {}", e));
+            return Err(Diagnostic::context(
+                "Failed to create settings directory",
+                e,
+            ));
         }
     }
 
     // Serialize settings to TOML
     let toml_content = match toml::to_string_pretty(&settings) {
         Ok(content) => content,
-        Err(e) => return Err(format!("Failed to serialize settings: {}", e)),
+        Err(e) => {
+            return Err(Diagnostic::context(
+                "Failed to serialize settings",
+                e.to_string(),
+            ));
+        }
+    };
+
+    // The main menu is always alive behind the settings screen, so update it in place
+    let (device, queue, surface_config) = {
+        let mut gpu_ctx = gpu_context.lock().await;
+        let device = gpu_ctx.device.clone();
+        let queue = gpu_ctx.queue.clone();
+        let main_menu_settings = serde_json::json!({
+            "background": settings.main_menu_background,
+            "time_of_day_themes": settings.main_menu_time_of_day_themes,
+        });
+        if let Err(e) = gpu_ctx
+            .main_menu
+            .apply_settings(main_menu_settings, &device, &queue)
+        {
+            tracing::warn!("Failed to apply main menu settings: {}", e);
+        }
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (device, queue, surface_config)
     };
 
+    {
+        let mut sim_manager = manager.lock().await;
+        sim_manager
+            .set_render_scale(settings.render_scale, &device, &queue, &surface_config)
+            .map_err(|e| Diagnostic::context("Failed to set render scale", e))?;
+        sim_manager
+            .rewind
+            .set_config(RewindConfig::from_app_settings(&settings));
+        sim_manager.keymap = settings.keybindings.clone();
+        sim_manager.default_presets = settings.default_presets.clone();
+        sim_manager.color_scripts = settings.color_scripts.clone();
+        sim_manager
+            .watchdog
+            .set_config(WatchdogConfig::from_app_settings(&settings));
+    }
+    gpu_budget::set_budget_mb(settings.gpu_memory_budget_mb);
+
     // Write to file
     match fs::write(&settings_path, toml_content) {
         Ok(_) => {
@@ -152,19 +322,53 @@ pub async fn save_app_settings(settings: AppSettings) -> Result<String, String>
         }
         Err(e) => {
             tracing::error!("Failed to save settings: {}", e);
-            Err(format!("Failed to save settings: {}", e))
+            Err(Diagnostic::context("Failed to save settings", e))
+        }
+    }
+}
+
+/// Start `simulation_type` with `preset_name` from now on, or with its
+/// built-in defaults again when `preset_name` is `None`
+#[tauri::command]
+pub async fn set_default_preset(
+    manager: tauri::State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    simulation_type: String,
+    preset_name: Option<String>,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let mut settings = AppSettings::load_from_file()?;
+    match preset_name {
+        Some(preset_name) => {
+            if !sim_manager
+                .get_presets_for_simulation_type(&simulation_type)
+                .contains(&preset_name)
+            {
+                return Err(Diagnostic::new(
+                    ErrorCode::PresetNotFound,
+                    format!("{} has no preset named '{}'", simulation_type, preset_name),
+                ));
+            }
+            settings
+                .default_presets
+                .insert(simulation_type.clone(), preset_name);
+        }
+        None => {
+            settings.default_presets.remove(&simulation_type);
         }
     }
+    settings.save_to_file()?;
+    sim_manager.default_presets = settings.default_presets;
+    Ok(format!("Default preset for {} updated", simulation_type))
 }
 
 #[tauri::command]
-pub async fn reset_app_settings() -> Result<String, String> {
+pub async fn reset_app_settings() -> Result<String, Diagnostic> {
     let settings_path = get_settings_path();
 
     // If settings file exists, delete it
     if settings_path.exists() {
         if let Err(e) = fs::remove_file(&settings_path) {
-            return Err(format!("Failed to delete settings file: {}", e));
+            return Err(Diagnostic::context("Failed to delete settings file", e));
         }
     }
 
@@ -173,13 +377,16 @@ pub async fn reset_app_settings() -> Result<String, String> {
 }
 
 #[tauri::command]
-pub async fn get_settings_file_path() -> Result<String, String> {
+pub async fn get_settings_file_path() -> Result<String, Diagnostic> {
     let path = get_settings_path();
     Ok(path.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-pub async fn set_webview_zoom(app: tauri::AppHandle, zoom_factor: f64) -> Result<String, String> {
+pub async fn set_webview_zoom(
+    app: tauri::AppHandle,
+    zoom_factor: f64,
+) -> Result<String, Diagnostic> {
     // Get the main window
     let window = app
         .get_webview_window("main")
@@ -188,14 +395,14 @@ pub async fn set_webview_zoom(app: tauri::AppHandle, zoom_factor: f64) -> Result
     // Set the webview zoom factor
     window
         .set_zoom(zoom_factor)
-        .map_err(|e| format!("Failed to set zoom factor: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to set zoom factor", e))?;
 
     tracing::debug!("Webview zoom factor set to: {}", zoom_factor);
     Ok("Zoom factor set successfully".to_string())
 }
 
 #[tauri::command]
-pub async fn apply_window_settings(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn apply_window_settings(app: tauri::AppHandle) -> Result<String, Diagnostic> {
     // Load current settings
     let settings = get_app_settings().await?;
 
@@ -210,7 +417,7 @@ pub async fn apply_window_settings(app: tauri::AppHandle) -> Result<String, Stri
             width: settings.window_width as f64,
             height: settings.window_height as f64,
         }))
-        .map_err(|e| format!("Failed to set window size: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to set window size", e))?;
 
     tracing::debug!(
         "Window size applied: {}x{}",
@@ -221,7 +428,7 @@ pub async fn apply_window_settings(app: tauri::AppHandle) -> Result<String, Stri
 }
 
 #[tauri::command]
-pub async fn apply_window_settings_on_startup(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn apply_window_settings_on_startup(app: tauri::AppHandle) -> Result<String, Diagnostic> {
     // Load current settings
     let settings = get_app_settings().await?;
 
@@ -234,14 +441,14 @@ pub async fn apply_window_settings_on_startup(app: tauri::AppHandle) -> Result<S
     if settings.window_maximized {
         window
             .maximize()
-            .map_err(|e| format!("Failed to maximize window: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to maximize window", e))?;
     } else {
         window
             .set_size(tauri::Size::Logical(tauri::LogicalSize {
                 width: settings.window_width as f64,
                 height: settings.window_height as f64,
             }))
-            .map_err(|e| format!("Failed to set window size: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to set window size", e))?;
     }
 
     tracing::debug!(
@@ -254,7 +461,9 @@ pub async fn apply_window_settings_on_startup(app: tauri::AppHandle) -> Result<S
 }
 
 #[tauri::command]
-pub async fn get_current_window_size(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
+pub async fn get_current_window_size(
+    app: tauri::AppHandle,
+) -> Result<serde_json::Value, Diagnostic> {
     // Get the main window
     let window = app
         .get_webview_window("main")
@@ -263,12 +472,12 @@ pub async fn get_current_window_size(app: tauri::AppHandle) -> Result<serde_json
     // Get current window size in logical pixels
     let size = window
         .inner_size()
-        .map_err(|e| format!("Failed to get window size: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to get window size", e))?;
 
     // Convert physical pixels to logical pixels
     let scale_factor = window
         .scale_factor()
-        .map_err(|e| format!("Failed to get scale factor: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to get scale factor", e))?;
 
     let logical_width = (size.width as f64 / scale_factor) as u32;
     let logical_height = (size.height as f64 / scale_factor) as u32;
@@ -276,7 +485,7 @@ pub async fn get_current_window_size(app: tauri::AppHandle) -> Result<serde_json
     // Get current maximized state
     let is_maximized = window
         .is_maximized()
-        .map_err(|e| format!("Failed to get maximized state: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to get maximized state", e))?;
 
     let result = serde_json::json!({
         "width": logical_width,
@@ -294,3 +503,83 @@ pub async fn get_current_window_size(app: tauri::AppHandle) -> Result<serde_json
     );
     Ok(result)
 }
+
+/// Float the window over the desktop: keyed transparency, optionally
+/// click-through and on top of other windows
+#[tauri::command]
+pub async fn set_overlay_mode(
+    app: tauri::AppHandle,
+    manager: tauri::State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: tauri::State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    overlay: OverlayMode,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+    apply_overlay_mode(&app, &mut sim_manager, &gpu_ctx, overlay).await?;
+    Ok("Overlay mode updated".to_string())
+}
+
+#[tauri::command]
+pub async fn get_overlay_mode(
+    manager: tauri::State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<OverlayMode, Diagnostic> {
+    Ok(manager.lock().await.overlay)
+}
+
+/// Switch the surface's alpha mode, the master bus and the window over to
+/// `overlay`
+pub(crate) async fn apply_overlay_mode(
+    app: &tauri::AppHandle,
+    sim_manager: &mut SimulationManager,
+    gpu_ctx: &crate::GpuContext,
+    overlay: OverlayMode,
+) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+
+    // Click-through windows only hear the keyboard, so there has to be a
+    // shortcut to get out again
+    if overlay.enabled
+        && overlay.click_through
+        && !sim_manager
+            .keymap
+            .bindings()
+            .contains_key(&KeyAction::ToggleOverlay)
+    {
+        return Err(
+            "Bind a shortcut to ToggleOverlay before making the window click-through".into(),
+        );
+    }
+
+    let alpha_mode = if overlay.enabled {
+        Some(
+            select_alpha_mode(&gpu_ctx.alpha_modes)
+                .ok_or("This display doesn't support transparent windows")?,
+        )
+    } else {
+        None
+    };
+
+    {
+        let mut config = gpu_ctx.surface_config.lock().await;
+        config.alpha_mode = alpha_mode.unwrap_or(gpu_ctx.alpha_modes[0]);
+        gpu_ctx.surface.configure(&gpu_ctx.device, &config);
+        sim_manager
+            .set_overlay_mode(overlay, alpha_mode, &gpu_ctx.device, &config)
+            .map_err(|e| format!("Failed to set overlay mode: {}", e))?;
+    }
+
+    window
+        .set_decorations(!overlay.enabled)
+        .map_err(|e| format!("Failed to set window decorations: {}", e))?;
+    window
+        .set_always_on_top(overlay.enabled && overlay.always_on_top)
+        .map_err(|e| format!("Failed to set window always on top: {}", e))?;
+    window
+        .set_ignore_cursor_events(overlay.enabled && overlay.click_through)
+        .map_err(|e| format!("Failed to set click-through: {}", e))?;
+
+    tracing::debug!("Overlay mode set to {:?}", overlay);
+    Ok(())
+}
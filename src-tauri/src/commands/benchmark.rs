@@ -0,0 +1,52 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::benchmark::{self, BenchmarkOptions, BenchmarkReport};
+use std::sync::Arc;
+use tauri::State;
+
+/// Run the standard benchmark scenes and score them, or the simulations,
+/// frame count, resolution and particle count in `options`. Takes several
+/// seconds, during which nothing else renders.
+#[tauri::command]
+pub async fn run_benchmark(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    options: Option<BenchmarkOptions>,
+) -> Result<BenchmarkReport, Diagnostic> {
+    let options = options.unwrap_or_default();
+    let (device, queue, adapter_info, surface_format) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_format = gpu_ctx.surface_config.lock().await.format;
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            gpu_ctx.adapter_info.clone(),
+            surface_format,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    // A running simulation would share the GPU with the scenes and skew their times
+    if sim_manager.current_simulation.is_some() {
+        return Err(Diagnostic::unsupported(
+            "Stop the running simulation before benchmarking",
+        ));
+    }
+    // Previews hold GPU memory the scenes could use
+    sim_manager.previews.clear();
+
+    benchmark::run(
+        &device,
+        &queue,
+        surface_format,
+        &adapter_info,
+        &sim_manager.color_scheme_manager,
+        &sim_manager.app_settings,
+        &options,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Benchmark failed: {}", e);
+        Diagnostic::context("Benchmark failed", e)
+    })
+}
@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
 use std::sync::Arc;
 use tauri::State;
@@ -7,7 +8,7 @@ pub async fn pan_camera(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     delta_x: f32,
     delta_y: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     sim_manager.pan_camera(delta_x, delta_y);
@@ -18,7 +19,7 @@ pub async fn pan_camera(
 pub async fn zoom_camera(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     delta: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     sim_manager.zoom_camera(delta);
@@ -31,7 +32,7 @@ pub async fn zoom_camera_to_cursor(
     delta: f32,
     cursor_x: f32,
     cursor_y: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     sim_manager.zoom_camera_to_cursor(delta, cursor_x, cursor_y);
@@ -41,7 +42,7 @@ pub async fn zoom_camera_to_cursor(
 #[tauri::command]
 pub async fn reset_camera(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     sim_manager.reset_camera();
@@ -51,13 +52,13 @@ pub async fn reset_camera(
 #[tauri::command]
 pub async fn get_camera_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     let sim_manager = manager.lock().await;
 
     if let Some(camera_state) = sim_manager.get_camera_state() {
         Ok(camera_state)
     } else {
-        Err("No camera state available".to_string())
+        Err(Diagnostic::from("No camera state available"))
     }
 }
 
@@ -65,7 +66,7 @@ pub async fn get_camera_state(
 pub async fn set_camera_smoothing(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     smoothing_factor: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     sim_manager.set_camera_smoothing(smoothing_factor);
@@ -76,7 +77,7 @@ pub async fn set_camera_smoothing(
 pub async fn set_camera_sensitivity(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     sensitivity: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     sim_manager.set_camera_sensitivity(sensitivity);
@@ -0,0 +1,18 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::catalog::{self, Catalog};
+use std::sync::Arc;
+use tauri::State;
+
+/// Display names and descriptions of every simulation and its presets in
+/// `locale` (English when omitted), falling back to English for anything
+/// not translated yet
+#[tauri::command]
+pub async fn get_catalog(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    locale: Option<String>,
+) -> Result<Catalog, Diagnostic> {
+    let locale = locale.unwrap_or_else(|| catalog::DEFAULT_LOCALE.to_string());
+    let sim_manager = manager.lock().await;
+    Ok(catalog::build_catalog(&locale, &sim_manager.preset_manager))
+}
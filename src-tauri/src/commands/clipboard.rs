@@ -0,0 +1,73 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Copy the current frame to the system clipboard as an image
+#[tauri::command]
+pub async fn copy_frame_to_clipboard(
+    app: AppHandle,
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<String, Diagnostic> {
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let frame = {
+        let mut sim_manager = manager.lock().await;
+        sim_manager
+            .capture_frame(&device, &queue, &surface_config)
+            .and_then(|capture| Ok(capture.read_rgba(&device, &queue)?))
+            .map_err(|e| Diagnostic::context("Failed to capture frame", e))?
+    };
+
+    let (width, height) = frame.dimensions();
+    app.clipboard()
+        .write_image(&tauri::image::Image::new(frame.as_raw(), width, height))
+        .map_err(|e| Diagnostic::context("Failed to copy frame to clipboard", e.to_string()))?;
+
+    tracing::info!("Copied {}x{} frame to clipboard", width, height);
+    Ok("Frame copied to clipboard".to_string())
+}
+
+/// Seed the current simulation with the image on the system clipboard
+#[tauri::command]
+pub async fn paste_clipboard_image(
+    app: AppHandle,
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<String, Diagnostic> {
+    let clipboard_image = app.clipboard().read_image().map_err(|e| {
+        Diagnostic::invalid_input(format!("Clipboard does not contain an image: {}", e))
+    })?;
+    let image = image::RgbaImage::from_raw(
+        clipboard_image.width(),
+        clipboard_image.height(),
+        clipboard_image.rgba().to_vec(),
+    )
+    .map(image::DynamicImage::ImageRgba8)
+    .ok_or_else(|| "Clipboard image has unexpected size".to_string())?;
+
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .seed_from_image(&device, &queue, image)
+        .map_err(|e| {
+            tracing::error!("Failed to seed simulation from clipboard: {}", e);
+            Diagnostic::context("Failed to use clipboard image", e)
+        })?;
+
+    Ok("Clipboard image applied".to_string())
+}
@@ -1,6 +1,10 @@
 use crate::SimulationType;
+use crate::commands::AppSettings;
+use crate::error::Diagnostic;
+use crate::simulation::color_cycle::ColorCycle;
 use crate::simulation::manager::SimulationManager;
 use crate::simulations::shared::color_scheme::ColorScheme;
+use crate::simulations::shared::color_space::linear_to_srgb_u8;
 use std::sync::Arc;
 use tauri::State;
 
@@ -9,7 +13,7 @@ pub async fn apply_color_scheme_by_name(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     color_scheme_name: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -27,9 +31,9 @@ pub async fn apply_color_scheme_by_name(
                 color_scheme_name,
                 e
             );
-            Err(format!(
-                "Failed to apply color scheme '{}': {}",
-                color_scheme_name, e
+            Err(Diagnostic::context(
+                format!("Failed to apply color scheme '{}'", color_scheme_name),
+                e,
             ))
         }
     }
@@ -40,13 +44,13 @@ pub async fn apply_color_scheme(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     color_scheme_data: Vec<u8>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
     let color_scheme =
         ColorScheme::from_bytes("custom_color_scheme".to_string(), &color_scheme_data)
-            .map_err(|e| format!("Failed to create color scheme from data: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to create color scheme from data", e))?;
 
     match sim_manager.apply_custom_color_scheme(&color_scheme, &gpu_ctx.device, &gpu_ctx.queue) {
         Ok(_) => {
@@ -55,7 +59,10 @@ pub async fn apply_color_scheme(
         }
         Err(e) => {
             tracing::error!("Failed to apply custom color scheme: {}", e);
-            Err(format!("Failed to apply custom color scheme: {}", e))
+            Err(Diagnostic::context(
+                "Failed to apply custom color scheme",
+                e,
+            ))
         }
     }
 }
@@ -64,7 +71,7 @@ pub async fn apply_color_scheme(
 pub async fn toggle_color_scheme_reversed(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -75,7 +82,7 @@ pub async fn toggle_color_scheme_reversed(
         }
         Err(e) => {
             tracing::error!("Failed to reverse color scheme: {}", e);
-            Err(format!("Failed to reverse color scheme: {}", e))
+            Err(Diagnostic::context("Failed to reverse color scheme", e))
         }
     }
 }
@@ -85,12 +92,12 @@ pub async fn save_custom_color_scheme(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     name: String,
     color_scheme_data: Vec<u8>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let sim_manager = manager.lock().await;
 
     // Create ColorSchemeData from the byte data
     let lut_data = ColorScheme::from_bytes(name.clone(), &color_scheme_data)
-        .map_err(|e| format!("Failed to create color scheme data: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to create color scheme data", e))?;
 
     match sim_manager
         .color_scheme_manager
@@ -102,9 +109,9 @@ pub async fn save_custom_color_scheme(
         }
         Err(e) => {
             tracing::error!("Failed to save custom color scheme '{}': {}", name, e);
-            Err(format!(
-                "Failed to save custom color scheme '{}': {}",
-                name, e
+            Err(Diagnostic::context(
+                format!("Failed to save custom color scheme '{}'", name),
+                e,
             ))
         }
     }
@@ -115,12 +122,12 @@ pub async fn update_gradient_preview(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     color_scheme_data: Vec<u8>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
     let lut_data = ColorScheme::from_bytes("gradient_preview".to_string(), &color_scheme_data)
-        .map_err(|e| format!("Failed to create color scheme data: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to create color scheme data", e))?;
 
     // Apply the preview color scheme to any running simulation
     match sim_manager.apply_custom_color_scheme(&lut_data, &gpu_ctx.device, &gpu_ctx.queue) {
@@ -130,15 +137,55 @@ pub async fn update_gradient_preview(
         }
         Err(e) => {
             tracing::error!("Failed to update gradient preview: {}", e);
-            Err(format!("Failed to update gradient preview: {}", e))
+            Err(Diagnostic::context("Failed to update gradient preview", e))
         }
     }
 }
 
+/// Run a color script over the running simulation's color scheme, or stop
+/// running one when `source` is `None`. The script is kept for that
+/// simulation type in the app settings.
+#[tauri::command]
+pub async fn set_color_script(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    source: Option<String>,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+    let simulation_type = sim_manager
+        .current_simulation
+        .as_ref()
+        .map(|simulation| simulation.type_name().to_string())
+        .ok_or("No simulation running")?;
+
+    sim_manager
+        .set_color_script(source, &gpu_ctx.device, &gpu_ctx.queue)
+        .map_err(|e| Diagnostic::context("Failed to set color script", e))?;
+
+    let mut settings = AppSettings::load_from_file()?;
+    settings.color_scripts = sim_manager.color_scripts.clone();
+    settings.save_to_file()?;
+    Ok(format!("Color script for {} updated", simulation_type))
+}
+
+/// The color script the running simulation type uses, if any
+#[tauri::command]
+pub async fn get_color_script(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Option<String>, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(sim_manager
+        .current_simulation
+        .as_ref()
+        .and_then(|simulation| sim_manager.color_scripts.get(simulation.type_name()))
+        .cloned())
+}
+
 #[tauri::command]
 pub async fn get_available_color_schemes(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, Diagnostic> {
     let sim_manager = manager.lock().await;
     Ok(sim_manager.get_available_color_schemes())
 }
@@ -146,7 +193,7 @@ pub async fn get_available_color_schemes(
 #[tauri::command]
 pub async fn get_current_color_scheme_colors(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<Vec<u8>>, String> {
+) -> Result<Vec<Vec<u8>>, Diagnostic> {
     let sim_manager = manager.lock().await;
 
     if let Some(SimulationType::ParticleLife(simulation)) = &sim_manager.current_simulation {
@@ -154,35 +201,53 @@ pub async fn get_current_color_scheme_colors(
         let mut colors = Vec::with_capacity(species_colors.len());
 
         // Convert from linear RGB (GPU space) to sRGB for UI display
-        fn linear_to_srgb(linear: f32) -> f32 {
-            if linear <= 0.003_130_8 {
-                linear * 12.92
-            } else {
-                1.055 * linear.powf(1.0 / 2.4) - 0.055
-            }
-        }
-
         for &[r_lin, g_lin, b_lin, _a] in species_colors {
-            let r = (linear_to_srgb(r_lin).clamp(0.0, 1.0) * 255.0).round() as u8;
-            let g = (linear_to_srgb(g_lin).clamp(0.0, 1.0) * 255.0).round() as u8;
-            let b = (linear_to_srgb(b_lin).clamp(0.0, 1.0) * 255.0).round() as u8;
-            colors.push(vec![r, g, b]);
+            colors.push(vec![
+                linear_to_srgb_u8(r_lin),
+                linear_to_srgb_u8(g_lin),
+                linear_to_srgb_u8(b_lin),
+            ]);
         }
 
         Ok(colors)
     } else {
-        Err("No particle life simulation running".to_string())
+        Err(Diagnostic::not_running(
+            "No particle life simulation running",
+        ))
     }
 }
 
 #[tauri::command]
 pub async fn get_species_colors(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<[f32; 4]>, String> {
+) -> Result<Vec<[f32; 4]>, Diagnostic> {
     let sim_manager = manager.lock().await;
     if let Some(SimulationType::ParticleLife(simulation)) = &sim_manager.current_simulation {
         Ok(simulation.state.species_colors.clone())
     } else {
-        Err("No particle life simulation running".to_string())
+        Err(Diagnostic::not_running(
+            "No particle life simulation running",
+        ))
     }
 }
+
+/// Animate the running simulation's color scheme by rotating it over time
+#[tauri::command]
+pub async fn set_color_cycle(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    cycle: ColorCycle,
+) -> Result<ColorCycle, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .set_color_cycle(cycle)
+        .map_err(|e| Diagnostic::context("Failed to set color cycle", e))?;
+    Ok(*sim_manager.color_cycler.cycle())
+}
+
+#[tauri::command]
+pub async fn get_color_cycle(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<ColorCycle, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(*sim_manager.color_cycler.cycle())
+}
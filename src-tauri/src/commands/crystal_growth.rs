@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
 use crate::simulations::traits::{Simulation, SimulationType};
 use std::sync::Arc;
@@ -8,7 +9,7 @@ pub async fn start_crystal_growth_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_crystal_growth_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -45,7 +46,7 @@ pub async fn start_crystal_growth_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -54,7 +55,7 @@ pub async fn start_crystal_growth_simulation(
 pub async fn randomize_crystal_growth_settings(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("randomize_crystal_growth_settings called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -62,11 +63,13 @@ pub async fn randomize_crystal_growth_settings(
     if let Some(SimulationType::CrystalGrowth(simulation)) = &mut sim_manager.current_simulation {
         simulation
             .randomize_settings(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to randomize settings: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to randomize settings", e))?;
         tracing::info!("Crystal Growth settings randomized");
         Ok("Crystal Growth settings randomized successfully".to_string())
     } else {
-        Err("This command is only available for Crystal Growth simulation".to_string())
+        Err(Diagnostic::unsupported(
+            "This command is only available for Crystal Growth simulation",
+        ))
     }
 }
 
@@ -74,7 +77,7 @@ pub async fn randomize_crystal_growth_settings(
 pub async fn reset_crystal_growth_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("reset_crystal_growth_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -82,10 +85,12 @@ pub async fn reset_crystal_growth_simulation(
     if let Some(SimulationType::CrystalGrowth(simulation)) = &mut sim_manager.current_simulation {
         simulation
             .reset_runtime_state(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to reset simulation: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to reset simulation", e))?;
         tracing::info!("Crystal Growth simulation reset");
         Ok("Crystal Growth simulation reset successfully".to_string())
     } else {
-        Err("This command is only available for Crystal Growth simulation".to_string())
+        Err(Diagnostic::unsupported(
+            "This command is only available for Crystal Growth simulation",
+        ))
     }
 }
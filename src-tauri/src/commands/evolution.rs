@@ -0,0 +1,97 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::evolution::{EvolutionConfig, Generation};
+use std::sync::Arc;
+use tauri::State;
+
+/// Start evolving the running simulation's settings. The first generation
+/// holds the current settings and mutations of them, each with a preview.
+#[tauri::command]
+pub async fn evolve_init(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    config: Option<EvolutionConfig>,
+) -> Result<Generation, Diagnostic> {
+    let (device, queue, adapter_info, surface_format) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_format = gpu_ctx.surface_config.lock().await.format;
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            gpu_ctx.adapter_info.clone(),
+            surface_format,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .start_evolution(
+            config.unwrap_or_default(),
+            &device,
+            &queue,
+            surface_format,
+            &adapter_info,
+        )
+        .await
+        .cloned()
+        .map_err(|e| Diagnostic::context("Failed to start evolution", e))
+}
+
+/// Pick the variants of the current generation to breed from
+#[tauri::command]
+pub async fn evolve_select(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    indices: Vec<usize>,
+) -> Result<Generation, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let evolution = sim_manager.evolution_mut().map_err(Diagnostic::from)?;
+    evolution.select(indices).map_err(Diagnostic::from)?;
+    Ok(evolution.generation().clone())
+}
+
+/// Breed the selected variants into the next generation
+#[tauri::command]
+pub async fn evolve_next(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<Generation, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    let evolution = sim_manager.evolution_mut().map_err(Diagnostic::from)?;
+    evolution
+        .next_generation(&device, &queue)
+        .map_err(|e| Diagnostic::context("Failed to breed the next generation", e))?;
+    Ok(evolution.generation().clone())
+}
+
+/// Give the running simulation the settings of a variant
+#[tauri::command]
+pub async fn evolve_apply(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    index: usize,
+) -> Result<String, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .apply_evolution_variant(index, &device, &queue)
+        .map_err(|e| Diagnostic::context(format!("Failed to apply variant {}", index), e))?;
+    Ok(format!("Variant {} applied", index))
+}
+
+/// End the evolution and free its preview simulation
+#[tauri::command]
+pub async fn evolve_stop(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<String, Diagnostic> {
+    manager.lock().await.evolution = None;
+    Ok("Evolution stopped".to_string())
+}
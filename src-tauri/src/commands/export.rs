@@ -0,0 +1,88 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::recording::{RecordingConfig, RecordingSummary};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+
+/// Export the current frame as linear EXR or 16-bit PNG, chosen by the file
+/// extension. These carry values beyond 0-1 only from a wide-gamut surface;
+/// otherwise they hold the 8-bit frame. Returns the path of the JSON metadata
+/// written next to the image.
+#[tauri::command]
+pub async fn export_frame_linear(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    path: String,
+) -> Result<String, Diagnostic> {
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    crate::simulation::frame_export::export_frame_linear(
+        &mut sim_manager,
+        &device,
+        &queue,
+        &surface_config,
+        Path::new(&path),
+    )
+    .map(|metadata_path| metadata_path.to_string_lossy().into_owned())
+    .map_err(|e| {
+        tracing::error!("Failed to export frame to {}: {}", path, e);
+        Diagnostic::context("Failed to export frame", e)
+    })
+}
+
+/// Start recording the running simulation at `width` x `height` and `fps`,
+/// whatever the window size. `path` ending in `.mp4` or `.webm` records video
+/// through ffmpeg; any other path is a directory for a PNG sequence.
+#[tauri::command]
+pub async fn start_recording(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    path: String,
+    width: u32,
+    height: u32,
+    fps: f32,
+) -> Result<(), Diagnostic> {
+    let config = RecordingConfig {
+        path: path.into(),
+        width,
+        height,
+        fps,
+    };
+    manager
+        .lock()
+        .await
+        .start_recording(config)
+        .map_err(|e| Diagnostic::context("Failed to start recording", e))
+}
+
+#[tauri::command]
+pub async fn stop_recording(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<RecordingSummary, Diagnostic> {
+    manager
+        .lock()
+        .await
+        .stop_recording()
+        .map_err(|e| Diagnostic::context("Failed to finish recording", e))
+}
+
+/// The recording in progress and how many frames it has so far
+#[tauri::command]
+pub async fn get_recording_status(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Option<(RecordingConfig, u64)>, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(sim_manager
+        .recording
+        .as_ref()
+        .map(|recording| (recording.config().clone(), recording.frames())))
+}
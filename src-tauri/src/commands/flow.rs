@@ -1,4 +1,7 @@
+use crate::error::{Diagnostic, ErrorCode};
 use crate::simulation::SimulationManager;
+use crate::simulations::flow::baked_field::FlowField;
+use crate::simulations::flow::emitters::Emitter;
 use serde_json::Value;
 use std::sync::Arc;
 use tauri::State;
@@ -7,7 +10,7 @@ use tauri::State;
 pub async fn kill_all_particles(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("kill_all_particles called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -15,7 +18,7 @@ pub async fn kill_all_particles(
     let simulation = sim_manager.flow_simulation_mut()?;
     simulation
         .kill_all_particles(&gpu_ctx.device, &gpu_ctx.queue)
-        .map_err(|e| format!("Failed to kill particles: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to kill particles", e))?;
     tracing::info!("All particles killed successfully");
     Ok("All particles killed successfully".to_string())
 }
@@ -26,7 +29,7 @@ pub async fn update_post_processing_state(
     effect_name: String,
     enabled: bool,
     params: Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "update_post_processing_state called: {} = {}",
         effect_name,
@@ -52,14 +55,17 @@ pub async fn update_post_processing_state(
             );
             Ok("Post processing state updated successfully".to_string())
         }
-        _ => Err(format!("Unknown post processing effect: {}", effect_name)),
+        _ => Err(Diagnostic::invalid_input(format!(
+            "Unknown post processing effect: {}",
+            effect_name
+        ))),
     }
 }
 
 #[tauri::command]
 pub async fn get_post_processing_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Value, String> {
+) -> Result<Value, Diagnostic> {
     tracing::debug!("get_post_processing_state called");
     let sim_manager = manager.lock().await;
 
@@ -86,7 +92,7 @@ pub async fn draw_antialiased_shape(
     intensity: f32,
     antialiasing_width: f32,
     rotation: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "draw_antialiased_shape called at ({}, {}) with size {}",
         center_x,
@@ -96,7 +102,9 @@ pub async fn draw_antialiased_shape(
 
     // Validate color array
     if color.len() != 4 {
-        return Err("Color must be an RGBA array with 4 values".to_string());
+        return Err(Diagnostic::invalid_input(
+            "Color must be an RGBA array with 4 values",
+        ));
     }
 
     let color_array = [color[0], color[1], color[2], color[3]];
@@ -118,7 +126,7 @@ pub async fn draw_antialiased_shape(
             antialiasing_width,
             rotation,
         )
-        .map_err(|e| format!("Failed to draw shape: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to draw shape", e))?;
 
     tracing::info!("Antialiased shape drawn successfully");
     Ok("Shape drawn successfully".to_string())
@@ -129,7 +137,7 @@ pub async fn set_flow_vector_field_type(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     vector_field_type: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -137,12 +145,26 @@ pub async fn set_flow_vector_field_type(
     sim.settings.vector_field_type = match vector_field_type.as_str() {
         "Noise" | "noise" => crate::simulations::flow::settings::VectorFieldType::Noise,
         "Image" | "image" => crate::simulations::flow::settings::VectorFieldType::Image,
-        _ => return Err("Invalid vector field type. Must be 'Noise' or 'Image'".to_string()),
+        "Baked" | "baked" => {
+            let name = sim
+                .settings
+                .baked_field
+                .clone()
+                .ok_or("No baked flow field has been loaded")?;
+            sim.load_baked_field(&gpu_ctx.queue, &name)
+                .map_err(|e| Diagnostic::context("Failed to load baked flow field", e))?;
+            crate::simulations::flow::settings::VectorFieldType::Baked
+        }
+        _ => {
+            return Err(Diagnostic::invalid_input(
+                "Invalid vector field type. Must be 'Noise', 'Image' or 'Baked'",
+            ));
+        }
     };
 
     // Regenerate flow vectors to apply the new mode
     sim.regenerate_flow_vectors(&gpu_ctx.device, &gpu_ctx.queue)
-        .map_err(|e| format!("Failed to regenerate flow vectors: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to regenerate flow vectors", e))?;
 
     Ok("Vector field type updated successfully".to_string())
 }
@@ -152,7 +174,7 @@ pub async fn set_flow_image_fit_mode(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     fit_mode: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -163,9 +185,9 @@ pub async fn set_flow_image_fit_mode(
     {
         Ok(m) => m,
         Err(_) => {
-            return Err(
-                "Invalid fit mode. Must be 'Stretch', 'Center', 'Fit H', or 'Fit V'".to_string(),
-            );
+            return Err(Diagnostic::invalid_input(
+                "Invalid fit mode. Must be 'Stretch', 'Center', 'Fit H', or 'Fit V'",
+            ));
         }
     };
 
@@ -175,9 +197,9 @@ pub async fn set_flow_image_fit_mode(
     {
         tracing::info!("Reprocessing image with new fit mode");
         sim.reprocess_vector_field_image_with_current_fit_mode(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to reprocess image: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to reprocess image", e))?;
         sim.regenerate_flow_vectors(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to regenerate flow vectors: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to regenerate flow vectors", e))?;
         tracing::info!("Image reprocessed and vectors regenerated");
     } else {
         tracing::warn!(
@@ -195,7 +217,7 @@ pub async fn set_flow_image_mirror_horizontal(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     mirror: bool,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -207,9 +229,9 @@ pub async fn set_flow_image_mirror_horizontal(
         && sim.vector_field_image_original.is_some()
     {
         sim.reprocess_vector_field_image_with_current_fit_mode(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to reprocess image: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to reprocess image", e))?;
         sim.regenerate_flow_vectors(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to regenerate flow vectors: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to regenerate flow vectors", e))?;
     }
 
     Ok("Image mirror horizontal setting updated successfully".to_string())
@@ -220,7 +242,7 @@ pub async fn set_flow_image_invert_tone(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     invert: bool,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -232,9 +254,9 @@ pub async fn set_flow_image_invert_tone(
         && sim.vector_field_image_original.is_some()
     {
         sim.reprocess_vector_field_image_with_current_fit_mode(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to reprocess image: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to reprocess image", e))?;
         sim.regenerate_flow_vectors(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to regenerate flow vectors: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to regenerate flow vectors", e))?;
     }
 
     Ok("Image invert tone setting updated successfully".to_string())
@@ -245,7 +267,7 @@ pub async fn set_flow_image_mirror_vertical(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     mirror: bool,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -257,9 +279,9 @@ pub async fn set_flow_image_mirror_vertical(
         && sim.vector_field_image_original.is_some()
     {
         sim.reprocess_vector_field_image_with_current_fit_mode(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to reprocess image: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to reprocess image", e))?;
         sim.regenerate_flow_vectors(&gpu_ctx.device, &gpu_ctx.queue)
-            .map_err(|e| format!("Failed to regenerate flow vectors: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to regenerate flow vectors", e))?;
     }
 
     Ok("Image mirror vertical setting updated successfully".to_string())
@@ -270,21 +292,21 @@ pub async fn load_flow_vector_field_image(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     image_path: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
     let sim = sim_manager.flow_simulation_mut()?;
     // Load the image
     sim.load_vector_field_image_from_path(&gpu_ctx.device, &gpu_ctx.queue, &image_path)
-        .map_err(|e| format!("Failed to load vector field image: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to load vector field image", e))?;
 
     // Switch to image mode if not already
     sim.settings.vector_field_type = crate::simulations::flow::settings::VectorFieldType::Image;
 
     // Regenerate flow vectors to apply the image
     sim.regenerate_flow_vectors(&gpu_ctx.device, &gpu_ctx.queue)
-        .map_err(|e| format!("Failed to regenerate flow vectors: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to regenerate flow vectors", e))?;
 
     Ok("Vector field image loaded and applied successfully".to_string())
 }
@@ -295,25 +317,25 @@ pub async fn load_flow_vector_field_image_bytes(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     data: Vec<u8>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
     let sim = sim_manager.flow_simulation_mut()?;
     // Decode image from memory
     let img = image::load_from_memory(&data)
-        .map_err(|e| format!("Failed to decode image bytes: {}", e))?;
+        .map_err(|e| Diagnostic::invalid_input(format!("Failed to decode image bytes: {}", e)))?;
 
     // Load image into GPU resources
     sim.load_vector_field_image_from_data(&gpu_ctx.device, &gpu_ctx.queue, img)
-        .map_err(|e| format!("Failed to load vector field image: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to load vector field image", e))?;
 
     // Ensure we are in Image mode
     sim.settings.vector_field_type = crate::simulations::flow::settings::VectorFieldType::Image;
 
     // Regenerate flow vectors
     sim.regenerate_flow_vectors(&gpu_ctx.device, &gpu_ctx.queue)
-        .map_err(|e| format!("Failed to regenerate flow vectors: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to regenerate flow vectors", e))?;
 
     Ok("Vector field image loaded from bytes and applied successfully".to_string())
 }
@@ -321,22 +343,25 @@ pub async fn load_flow_vector_field_image_bytes(
 #[tauri::command]
 pub async fn start_flow_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let sim = sim_manager.flow_simulation_mut()?;
     let devices = sim.get_available_webcam_devices();
     if devices.is_empty() {
-        return Err("No webcam devices available".to_string());
+        return Err(Diagnostic::new(
+            ErrorCode::WebcamUnavailable,
+            "No webcam devices available",
+        ));
     }
     sim.start_webcam_capture(devices[0])
-        .map_err(|e| e.to_string())?;
+        .map_err(Diagnostic::from)?;
     Ok("Flow webcam started".to_string())
 }
 
 #[tauri::command]
 pub async fn stop_flow_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let sim = sim_manager.flow_simulation_mut()?;
     sim.stop_webcam_capture();
@@ -346,8 +371,104 @@ pub async fn stop_flow_webcam_capture(
 #[tauri::command]
 pub async fn get_available_flow_webcam_devices(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<i32>, String> {
+) -> Result<Vec<i32>, Diagnostic> {
     let sim_manager = manager.lock().await;
     let sim = sim_manager.flow_simulation()?;
     Ok(sim.get_available_webcam_devices())
 }
+
+/// Emitters of the running Flow simulation
+#[tauri::command]
+pub async fn get_flow_emitters(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Vec<Emitter>, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    let sim = sim_manager.flow_simulation()?;
+    Ok(sim.settings.emitters.clone())
+}
+
+#[tauri::command]
+pub async fn add_flow_emitter(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    emitter: Emitter,
+) -> Result<Vec<Emitter>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let sim = sim_manager.flow_simulation_mut()?;
+    sim.add_emitter(emitter)
+        .map_err(|e| Diagnostic::context("Failed to add emitter", e))?;
+    Ok(sim.settings.emitters.clone())
+}
+
+/// Replace the emitter called `name`; `emitter` may carry a new name
+#[tauri::command]
+pub async fn update_flow_emitter(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+    emitter: Emitter,
+) -> Result<Vec<Emitter>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let sim = sim_manager.flow_simulation_mut()?;
+    sim.update_emitter(&name, emitter)
+        .map_err(|e| Diagnostic::context("Failed to update emitter", e))?;
+    Ok(sim.settings.emitters.clone())
+}
+
+#[tauri::command]
+pub async fn remove_flow_emitter(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+) -> Result<Vec<Emitter>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let sim = sim_manager.flow_simulation_mut()?;
+    sim.remove_emitter(&name)
+        .map_err(|e| Diagnostic::context("Failed to remove emitter", e))?;
+    Ok(sim.settings.emitters.clone())
+}
+
+/// Save the current flow vectors as a baked field, returning the file path
+#[tauri::command]
+pub async fn bake_flow_field(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    name: String,
+) -> Result<String, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+
+    let sim = sim_manager.flow_simulation()?;
+    let field = sim
+        .bake_flow_field(&gpu_ctx.device, &gpu_ctx.queue)
+        .map_err(|e| Diagnostic::context("Failed to bake flow field", e))?;
+    let path = field
+        .save(&name)
+        .map_err(|e| Diagnostic::context("Failed to save flow field", e))?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+pub async fn list_baked_flow_fields() -> Result<Vec<String>, Diagnostic> {
+    Ok(FlowField::list())
+}
+
+/// Replace the generated flow field with a baked one
+#[tauri::command]
+pub async fn load_baked_flow_field(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    name: String,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+
+    let sim = sim_manager.flow_simulation_mut()?;
+    sim.load_baked_field(&gpu_ctx.queue, &name)
+        .map_err(|e| Diagnostic::context("Failed to load baked flow field", e))?;
+
+    Ok("Baked flow field loaded successfully".to_string())
+}
+
+#[tauri::command]
+pub async fn delete_baked_flow_field(name: String) -> Result<Vec<String>, Diagnostic> {
+    FlowField::delete(&name).map_err(|e| Diagnostic::context("Failed to delete flow field", e))?;
+    Ok(FlowField::list())
+}
@@ -0,0 +1,92 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::gallery::{GalleryEntry, GalleryItem};
+use crate::simulation::settings_codec::SharedConfiguration;
+use std::sync::Arc;
+use tauri::State;
+
+/// Save the current frame to the gallery together with the settings, camera
+/// and color scheme it was rendered with
+#[tauri::command]
+pub async fn capture_to_gallery(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<GalleryEntry, Diagnostic> {
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    let entry =
+        GalleryEntry::capture(&mut sim_manager, &device, &queue, &surface_config).map_err(|e| {
+            tracing::error!("Failed to capture to gallery: {}", e);
+            Diagnostic::context("Failed to capture to gallery", e)
+        })?;
+    tracing::info!("Captured gallery entry {}", entry.id);
+    Ok(entry)
+}
+
+/// All gallery entries, newest first
+#[tauri::command]
+pub async fn get_gallery() -> Result<Vec<GalleryItem>, Diagnostic> {
+    Ok(GalleryEntry::list())
+}
+
+/// Open a gallery image in the system's default image viewer
+#[tauri::command]
+pub async fn open_gallery_image(id: String) -> Result<(), Diagnostic> {
+    let path = GalleryEntry::image_path(&id).map_err(Diagnostic::from)?;
+    tauri_plugin_opener::open_path(&path, None::<&str>).map_err(|e| {
+        Diagnostic::context(format!("Failed to open {}", path.display()), e.to_string())
+    })
+}
+
+#[tauri::command]
+pub async fn delete_gallery_image(id: String) -> Result<String, Diagnostic> {
+    GalleryEntry::delete(&id)
+        .map_err(|e| Diagnostic::context(format!("Failed to delete gallery entry '{}'", id), e))?;
+    Ok(format!("Gallery entry '{}' deleted", id))
+}
+
+/// Restore the configuration a gallery image was captured with. If it is for
+/// the running simulation it is applied straight away, otherwise it is kept
+/// as the pending shared configuration for the frontend to apply once it has
+/// started the right simulation.
+#[tauri::command]
+pub async fn restore_gallery_settings(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    id: String,
+) -> Result<SharedConfiguration, Diagnostic> {
+    let config = GalleryEntry::load(&id)
+        .map_err(Diagnostic::from)?
+        .configuration;
+
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    let is_running = sim_manager
+        .current_simulation
+        .as_ref()
+        .is_some_and(|simulation| simulation.type_name() == config.simulation_type);
+    if is_running {
+        sim_manager
+            .apply_shared_configuration(&config, &device, &queue)
+            .map_err(|e| {
+                Diagnostic::context(format!("Failed to restore settings from '{}'", id), e)
+            })?;
+    } else {
+        sim_manager.pending_shared_configuration = Some(config.clone());
+    }
+
+    Ok(config)
+}
@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
 use crate::simulations::traits::SimulationType;
 use std::sync::Arc;
@@ -8,7 +9,7 @@ pub async fn set_gradient_display_mode(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     mode: u32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("set_gradient_display_mode called with mode: {}", mode);
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -25,6 +26,8 @@ pub async fn set_gradient_display_mode(
         tracing::info!("Gradient display mode set to: {}", mode_name);
         Ok(format!("Gradient display mode set to: {}", mode_name))
     } else {
-        Err("This command is only available for Gradient simulation".to_string())
+        Err(Diagnostic::unsupported(
+            "This command is only available for Gradient simulation",
+        ))
     }
 }
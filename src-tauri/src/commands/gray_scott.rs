@@ -1,3 +1,4 @@
+use crate::error::{Diagnostic, ErrorCode};
 use crate::simulation::SimulationManager;
 use serde_json::Value;
 use std::sync::Arc;
@@ -9,7 +10,7 @@ pub async fn update_gray_scott_post_processing_state(
     effect_name: String,
     enabled: bool,
     params: Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "update_gray_scott_post_processing_state called: {} = {}",
         effect_name,
@@ -29,7 +30,10 @@ pub async fn update_gray_scott_post_processing_state(
             }
             Ok("Post processing state updated".to_string())
         }
-        _ => Err(format!("Unknown effect: {}", effect_name)),
+        _ => Err(Diagnostic::invalid_input(format!(
+            "Unknown effect: {}",
+            effect_name
+        ))),
     }
 }
 
@@ -39,7 +43,7 @@ pub async fn load_gray_scott_nutrient_image(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     image_path: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::info!(
         "load_gray_scott_nutrient_image called with path: {}",
         image_path
@@ -57,10 +61,27 @@ pub async fn load_gray_scott_nutrient_image(
     Ok("Gray-Scott nutrient image loaded".to_string())
 }
 
+/// Seed the reaction from an image, such as an exported slime mold trail map
+#[tauri::command]
+pub async fn seed_gray_scott_from_image(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    image_path: String,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu = gpu_context.lock().await;
+
+    let sim = sim_manager.gray_scott_simulation_mut()?;
+    let image = image::open(&image_path)
+        .map_err(|e| Diagnostic::invalid_input(format!("Failed to open seed image: {}", e)))?;
+    sim.seed_from_image(&gpu.queue, &image);
+    Ok("Gray-Scott seeded from image".to_string())
+}
+
 #[tauri::command]
 pub async fn get_gray_scott_post_processing_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     let sim_manager = manager.lock().await;
     let simulation = sim_manager.gray_scott_simulation()?;
 
@@ -76,25 +97,28 @@ pub async fn get_gray_scott_post_processing_state(
 #[tauri::command]
 pub async fn start_gray_scott_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let sim = sim_manager.gray_scott_simulation_mut()?;
 
     // Reuse device enumeration from SM webcam module
     let devices = crate::simulations::shared::webcam::WebcamCapture::get_available_devices();
     if devices.is_empty() {
-        return Err("No webcam devices available".to_string());
+        return Err(Diagnostic::new(
+            ErrorCode::WebcamUnavailable,
+            "No webcam devices available",
+        ));
     }
     let device_index = devices[0];
     sim.start_webcam_capture(device_index)
-        .map_err(|e| e.to_string())?;
+        .map_err(Diagnostic::from)?;
     Ok("Gray-Scott webcam started".to_string())
 }
 
 #[tauri::command]
 pub async fn stop_gray_scott_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let sim = sim_manager.gray_scott_simulation_mut()?;
     sim.stop_webcam_capture();
@@ -104,6 +128,6 @@ pub async fn stop_gray_scott_webcam_capture(
 #[tauri::command]
 pub async fn get_available_gray_scott_webcam_devices(
     _manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<i32>, String> {
+) -> Result<Vec<i32>, Diagnostic> {
     Ok(crate::simulations::shared::WebcamCapture::get_available_devices())
 }
@@ -1,4 +1,8 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
+use crate::simulations::shared::{
+    CursorForceField, CursorMode, EnvironmentField, GlobalForce, StrengthCurve,
+};
 use std::sync::Arc;
 use tauri::State;
 
@@ -9,7 +13,7 @@ pub async fn handle_mouse_interaction(
     x: f32,
     y: f32,
     mouse_button: u32, // 0 = left, 1 = middle, 2 = right
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -18,7 +22,7 @@ pub async fn handle_mouse_interaction(
         Ok(_) => Ok("Mouse interaction handled successfully".to_string()),
         Err(e) => {
             tracing::error!("Failed to handle mouse interaction: {}", e);
-            Err(format!("Failed to handle mouse interaction: {}", e))
+            Err(Diagnostic::context("Failed to handle mouse interaction", e))
         }
     }
 }
@@ -30,7 +34,7 @@ pub async fn handle_mouse_interaction_screen(
     screen_x: f32,
     screen_y: f32,
     mouse_button: u32, // 0 = left, 1 = middle, 2 = right
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
     sim_manager
@@ -41,7 +45,7 @@ pub async fn handle_mouse_interaction_screen(
             &gpu_ctx.device,
             &gpu_ctx.queue,
         )
-        .map_err(|e| e.to_string())?;
+        .map_err(Diagnostic::from)?;
     Ok("Mouse interaction handled".to_string())
 }
 
@@ -50,12 +54,12 @@ pub async fn handle_mouse_release(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     mouse_button: u32, // 0 = left, 1 = middle, 2 = right
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
     sim_manager
         .handle_mouse_release(mouse_button, &gpu_ctx.queue)
-        .map_err(|e| e.to_string())?;
+        .map_err(Diagnostic::from)?;
     Ok("Mouse release handled".to_string())
 }
 
@@ -65,7 +69,7 @@ pub async fn update_cursor_position_screen(
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     _screen_x: f32,
     _screen_y: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let _sim_manager = manager.lock().await;
     let _gpu_ctx = gpu_context.lock().await;
     // Currently, cursor position is handled through mouse interaction commands
@@ -76,7 +80,7 @@ pub async fn update_cursor_position_screen(
 pub async fn seed_random_noise(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -84,7 +88,7 @@ pub async fn seed_random_noise(
         Ok(_) => Ok("Random noise seeded successfully".to_string()),
         Err(e) => {
             tracing::error!("Failed to seed random noise: {}", e);
-            Err(format!("Failed to seed random noise: {}", e))
+            Err(Diagnostic::context("Failed to seed random noise", e))
         }
     }
 }
@@ -94,7 +98,7 @@ pub async fn update_cursor_size(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     size: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -102,7 +106,7 @@ pub async fn update_cursor_size(
         Ok(_) => Ok("Cursor size updated successfully".to_string()),
         Err(e) => {
             tracing::error!("Failed to update cursor size: {}", e);
-            Err(format!("Failed to update cursor size: {}", e))
+            Err(Diagnostic::context("Failed to update cursor size", e))
         }
     }
 }
@@ -112,7 +116,7 @@ pub async fn update_cursor_strength(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     strength: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -120,7 +124,68 @@ pub async fn update_cursor_strength(
         Ok(_) => Ok("Cursor strength updated successfully".to_string()),
         Err(e) => {
             tracing::error!("Failed to update cursor strength: {}", e);
-            Err(format!("Failed to update cursor strength: {}", e))
+            Err(Diagnostic::context("Failed to update cursor strength", e))
         }
     }
 }
+
+/// Switch the cursor between radial, vortex, push and turbulence force
+/// fields. `curve`, when given, becomes that mode's strength curve.
+#[tauri::command]
+pub async fn set_cursor_mode(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    mode: CursorMode,
+    curve: Option<StrengthCurve>,
+) -> Result<CursorForceField, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    sim_manager.set_cursor_mode(mode, curve).map_err(|e| {
+        tracing::error!("Failed to set cursor mode: {}", e);
+        Diagnostic::context("Failed to set cursor mode", e)
+    })
+}
+
+#[tauri::command]
+pub async fn get_global_force(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<GlobalForce, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    sim_manager.global_force().map_err(Diagnostic::from)
+}
+
+/// Set the wind, gusts, point gravity and LFOs of the running simulation
+#[tauri::command]
+pub async fn set_global_force(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    force: GlobalForce,
+) -> Result<GlobalForce, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    sim_manager.set_global_force(force).map_err(|e| {
+        tracing::error!("Failed to set global force: {}", e);
+        Diagnostic::context("Failed to set global force", e)
+    })
+}
+
+#[tauri::command]
+pub async fn get_environment_field(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<EnvironmentField, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    sim_manager.environment_field().map_err(Diagnostic::from)
+}
+
+/// Set the radial, linear, noise or image field particles drift along
+#[tauri::command]
+pub async fn set_environment_field(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    field: EnvironmentField,
+) -> Result<EnvironmentField, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+    sim_manager
+        .set_environment_field(field, &gpu_ctx.queue)
+        .map_err(|e| {
+            tracing::error!("Failed to set environment field: {}", e);
+            Diagnostic::context("Failed to set environment field", e)
+        })
+}
@@ -0,0 +1,137 @@
+use crate::commands::AppSettings;
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::keymap::{KeyAction, Keymap};
+use crate::simulation::overlay::OverlayMode;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+
+#[tauri::command]
+pub async fn get_keybindings(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Keymap, Diagnostic> {
+    Ok(manager.lock().await.keymap.clone())
+}
+
+/// Bind `action` to `shortcut` (empty to unbind) and save it to the app
+/// settings. Returns the updated keymap, since another action may have lost
+/// the shortcut.
+#[tauri::command]
+pub async fn set_keybinding(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    action: KeyAction,
+    shortcut: String,
+) -> Result<Keymap, Diagnostic> {
+    let keymap = {
+        let mut sim_manager = manager.lock().await;
+        if let Some(displaced) = sim_manager
+            .keymap
+            .set(action, &shortcut)
+            .map_err(Diagnostic::from)?
+        {
+            tracing::info!("{:?} is no longer bound to {}", displaced, shortcut);
+        }
+        sim_manager.keymap.clone()
+    };
+
+    let mut settings = AppSettings::load_from_file().unwrap_or_default();
+    settings.keybindings = keymap.clone();
+    settings.save_to_file()?;
+    Ok(keymap)
+}
+
+/// Run the action bound to `shortcut`, if any. The simulation views forward
+/// the key presses of bound shortcuts here, so there is one place that
+/// decides what a key does. The action is returned and emitted as
+/// `keybinding-action` for the UI to follow along.
+#[tauri::command]
+pub async fn handle_key_press(
+    app: AppHandle,
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    shortcut: String,
+) -> Result<Option<KeyAction>, Diagnostic> {
+    let Some(action) = manager.lock().await.keymap.action_for(&shortcut) else {
+        return Ok(None);
+    };
+    tracing::debug!("{} triggered {:?}", shortcut, action);
+
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    match action {
+        KeyAction::ToggleGui => sim_manager.toggle_gui(),
+        // The view that forwarded the key follows along with the returned
+        // action, which is how it learns the simulation resumed
+        KeyAction::TogglePause => {
+            if sim_manager.is_paused() {
+                sim_manager.resume();
+            } else {
+                sim_manager.pause();
+            }
+        }
+        KeyAction::Step => sim_manager.step_once(),
+        KeyAction::Screenshot => {
+            let path = screenshot_path(&sim_manager);
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)
+                    .map_err(|e| Diagnostic::context("Failed to create screenshot directory", e))?;
+            }
+            crate::simulation::frame_export::export_frame_linear(
+                &mut sim_manager,
+                &device,
+                &queue,
+                &surface_config,
+                &path,
+            )
+            .map_err(|e| Diagnostic::context("Failed to save screenshot", e))?;
+        }
+        KeyAction::NextPreset => {
+            if let Some(preset) = sim_manager
+                .apply_next_preset(&device, &queue)
+                .map_err(|e| Diagnostic::context("Failed to apply next preset", e))?
+            {
+                tracing::info!("Switched to preset {}", preset);
+            }
+        }
+        KeyAction::ResetCamera => sim_manager.reset_camera(),
+        KeyAction::ToggleOverlay => {
+            let overlay = OverlayMode {
+                enabled: !sim_manager.overlay.enabled,
+                ..sim_manager.overlay
+            };
+            let gpu_ctx = gpu_context.lock().await;
+            crate::commands::apply_overlay_mode(&app, &mut sim_manager, &gpu_ctx, overlay).await?;
+        }
+    }
+
+    if let Err(e) = app.emit("keybinding-action", action) {
+        tracing::warn!("Failed to emit keybinding-action event: {}", e);
+    }
+    Ok(Some(action))
+}
+
+/// `~/Pictures/Vizza/<simulation>-<time>.png`
+fn screenshot_path(manager: &SimulationManager) -> PathBuf {
+    let simulation_type = manager
+        .current_simulation
+        .as_ref()
+        .map_or("frame", |simulation| simulation.type_name());
+    dirs::picture_dir()
+        .unwrap_or_else(crate::commands::app_settings::get_settings_dir)
+        .join("Vizza")
+        .join(format!(
+            "{}-{}.png",
+            simulation_type,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+}
@@ -0,0 +1,20 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use std::sync::Arc;
+use tauri::State;
+
+/// Lens an image in place of the starfield
+#[tauri::command]
+pub async fn load_lensing_image(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    image_path: String,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+
+    let sim = sim_manager.lensing_simulation_mut()?;
+    sim.load_image_from_path(&gpu_ctx.device, &gpu_ctx.queue, &image_path)
+        .map_err(|e| Diagnostic::context("Failed to load lensing image", e))?;
+    Ok("Lensing image loaded successfully".to_string())
+}
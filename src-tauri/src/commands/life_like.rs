@@ -0,0 +1,21 @@
+use crate::error::Diagnostic;
+use crate::simulations::life_like::rule::{LifeRule, Neighborhood};
+
+/// Parse a rule as it's typed into the rule editor without touching the
+/// running simulation. Returns the rule in its canonical notation with what
+/// it was read as, or the reason it doesn't parse.
+#[tauri::command]
+pub async fn check_life_like_rule(rule: String) -> Result<serde_json::Value, Diagnostic> {
+    let rule: LifeRule = rule.parse()?;
+    Ok(serde_json::json!({
+        "rule": rule.to_string(),
+        "states": rule.states(),
+        "radius": rule.radius(),
+        "neighborhood": match rule.neighborhood() {
+            Neighborhood::Moore => "Moore",
+            Neighborhood::VonNeumann => "VonNeumann",
+        },
+        "include_center": rule.include_center(),
+        "neighbors": rule.neighborhood().size(rule.radius()),
+    }))
+}
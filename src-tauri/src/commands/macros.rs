@@ -0,0 +1,85 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::macros::{self, Macro};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+pub async fn start_macro_recording(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+) -> Result<(), Diagnostic> {
+    manager
+        .lock()
+        .await
+        .macro_recorder
+        .start(&name)
+        .map_err(|e| Diagnostic::context("Failed to start recording", e))?;
+    tracing::info!("Recording macro '{}'", name);
+    Ok(())
+}
+
+/// Finish recording and save the macro, returning what was recorded
+#[tauri::command]
+pub async fn stop_macro_recording(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Macro, Diagnostic> {
+    let recorded = manager
+        .lock()
+        .await
+        .macro_recorder
+        .stop()
+        .ok_or_else(|| "No macro is being recorded".to_string())?;
+    let path = recorded
+        .save()
+        .map_err(|e| Diagnostic::context("Failed to save macro", e))?;
+    tracing::info!(
+        "Saved macro '{}' with {} steps to {}",
+        recorded.name,
+        recorded.steps.len(),
+        path.display()
+    );
+    Ok(recorded)
+}
+
+#[tauri::command]
+pub async fn get_macros() -> Result<Vec<String>, Diagnostic> {
+    Ok(Macro::list())
+}
+
+#[tauri::command]
+pub async fn delete_macro(name: String) -> Result<(), Diagnostic> {
+    Macro::delete(&name)
+        .map_err(|e| Diagnostic::context(format!("Failed to delete macro '{}'", name), e))
+}
+
+/// Start replaying a saved macro, replacing any macro already playing
+#[tauri::command]
+pub async fn play_macro(
+    app: AppHandle,
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+) -> Result<(), Diagnostic> {
+    let recorded = Macro::load(&name).map_err(Diagnostic::from)?;
+    tracing::info!(
+        "Playing macro '{}' ({:.1}s)",
+        recorded.name,
+        recorded.duration_seconds()
+    );
+
+    let playback = tauri::async_runtime::spawn(macros::play(app, recorded));
+    if let Some(previous) = manager.lock().await.macro_playback.replace(playback) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_macro_playback(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<(), Diagnostic> {
+    if let Some(playback) = manager.lock().await.macro_playback.take() {
+        playback.abort();
+    }
+    Ok(())
+}
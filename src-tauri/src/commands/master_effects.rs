@@ -0,0 +1,37 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::master_effects::MasterEffects;
+use std::sync::Arc;
+use tauri::State;
+
+/// Set the grading, bloom and grain applied over the whole composited frame
+#[tauri::command]
+pub async fn set_master_effects(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    effects: MasterEffects,
+) -> Result<MasterEffects, Diagnostic> {
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .set_master_effects(effects, &device, &queue, &surface_config)
+        .map_err(|e| Diagnostic::context("Failed to set master effects", e))?;
+    Ok(sim_manager.master_bus.effects().clone())
+}
+
+#[tauri::command]
+pub async fn get_master_effects(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<MasterEffects, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(sim_manager.master_bus.effects().clone())
+}
@@ -1,40 +1,84 @@
+pub mod annotations;
 pub mod app_settings;
+pub mod benchmark;
 pub mod camera;
+pub mod catalog;
+pub mod clipboard;
 pub mod colors_schemes;
+pub mod evolution;
+pub mod export;
 pub mod flow;
+pub mod gallery;
 pub mod gradient;
 pub mod gray_scott;
 pub mod interaction;
+pub mod keymap;
+pub mod lensing;
+pub mod life_like;
+pub mod macros;
+pub mod master_effects;
 pub mod moire;
+pub mod panes;
 pub mod particle_life;
 pub mod pellets;
 pub mod presets;
+pub mod preview_stream;
+pub mod previews;
 pub mod primordial_particles;
 pub mod rendering;
+pub mod replays;
 pub mod reset;
+pub mod rewind;
+pub mod scenes;
 pub mod settings;
+pub mod sharing;
 pub mod simulation;
 pub mod slime_mold;
+pub mod snapshot;
+pub mod stippling;
 pub mod utility;
 pub mod voronoi_ca;
+pub mod workspaces;
 
 // Re-export all command functions for easy access
+pub use annotations::*;
 pub use app_settings::*;
+pub use benchmark::*;
 pub use camera::*;
+pub use catalog::*;
+pub use clipboard::*;
 pub use colors_schemes::*;
+pub use evolution::*;
+pub use export::*;
 pub use flow::*;
+pub use gallery::*;
 pub use gradient::*;
 pub use gray_scott::*;
 pub use interaction::*;
+pub use keymap::*;
+pub use lensing::*;
+pub use life_like::*;
+pub use macros::*;
+pub use master_effects::*;
 pub use moire::*;
+pub use panes::*;
 pub use particle_life::*;
 pub use pellets::*;
 pub use presets::*;
+pub use preview_stream::*;
+pub use previews::*;
 pub use primordial_particles::*;
 pub use rendering::*;
+pub use replays::*;
 pub use reset::*;
+pub use rewind::*;
+pub use scenes::*;
 pub use settings::*;
+pub use sharing::*;
 pub use simulation::*;
 pub use slime_mold::*;
+pub use snapshot::*;
+pub use stippling::*;
 pub use utility::*;
 pub use voronoi_ca::*;
+pub use workspaces::*;
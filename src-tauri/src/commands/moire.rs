@@ -1,3 +1,4 @@
+use crate::error::{Diagnostic, ErrorCode};
 use crate::simulation::SimulationManager;
 use crate::simulations::traits::Simulation;
 use std::sync::Arc;
@@ -8,7 +9,7 @@ pub async fn start_moire_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_moire_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -45,7 +46,7 @@ pub async fn start_moire_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -54,7 +55,7 @@ pub async fn start_moire_simulation(
 pub async fn randomize_moire_settings(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("randomize_moire_settings called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -62,7 +63,7 @@ pub async fn randomize_moire_settings(
     let simulation = sim_manager.moire_simulation_mut()?;
     simulation
         .randomize_settings(&gpu_ctx.device, &gpu_ctx.queue)
-        .map_err(|e| format!("Failed to randomize settings: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to randomize settings", e))?;
     tracing::info!("Moiré settings randomized");
     Ok("Moiré settings randomized successfully".to_string())
 }
@@ -74,31 +75,34 @@ pub async fn load_moire_image(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     image_path: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
     let sim = sim_manager.moire_simulation_mut()?;
     sim.load_image_from_path(&gpu_ctx.device, &gpu_ctx.queue, &image_path)
-        .map_err(|e| format!("Failed to load moiré image: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to load moiré image", e))?;
     Ok("Moiré image loaded successfully".to_string())
 }
 
 #[tauri::command]
 pub async fn start_moire_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let sim = sim_manager.moire_simulation_mut()?;
 
     let devices = crate::simulations::shared::webcam::WebcamCapture::get_available_devices();
     if devices.is_empty() {
-        return Err("No webcam devices available".to_string());
+        return Err(Diagnostic::new(
+            ErrorCode::WebcamUnavailable,
+            "No webcam devices available",
+        ));
     }
 
     let device_index = devices[0];
     sim.start_webcam_capture(device_index)
-        .map_err(|e| format!("Failed to start webcam capture: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to start webcam capture", e))?;
 
     Ok("Moiré webcam capture started".to_string())
 }
@@ -106,7 +110,7 @@ pub async fn start_moire_webcam_capture(
 #[tauri::command]
 pub async fn stop_moire_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let sim = sim_manager.moire_simulation_mut()?;
 
@@ -117,6 +121,6 @@ pub async fn stop_moire_webcam_capture(
 #[tauri::command]
 pub async fn get_available_moire_webcam_devices(
     _manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<i32>, String> {
+) -> Result<Vec<i32>, Diagnostic> {
     Ok(crate::simulations::shared::webcam::WebcamCapture::get_available_devices())
 }
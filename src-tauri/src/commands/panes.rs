@@ -0,0 +1,138 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::panes::{BlendMode, PaneBlend, PaneInfo, PaneLayout};
+use std::sync::Arc;
+use tauri::State;
+
+/// Open another simulation in a new pane next to the running one
+#[tauri::command]
+pub async fn add_simulation_pane(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    simulation_type: String,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let (device, queue, surface_config, adapter_info) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+            gpu_ctx.adapter_info.clone(),
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .add_pane(
+            &simulation_type,
+            &device,
+            &queue,
+            &surface_config,
+            &adapter_info,
+        )
+        .await
+        .map_err(|e| Diagnostic::context(format!("Failed to add {} pane", simulation_type), e))
+}
+
+#[tauri::command]
+pub async fn remove_simulation_pane(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    pane_id: u32,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .remove_pane(pane_id, &device, &queue)
+        .map_err(|e| Diagnostic::context("Failed to remove pane", e))
+}
+
+/// Direct settings, presets and input at the given pane
+#[tauri::command]
+pub async fn focus_simulation_pane(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    pane_id: u32,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .focus_pane(pane_id)
+        .map_err(|e| Diagnostic::context("Failed to focus pane", e))
+}
+
+#[tauri::command]
+pub async fn set_simulation_pane_layout(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    layout: PaneLayout,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .set_pane_layout(layout, &device, &queue)
+        .map_err(|e| Diagnostic::context("Failed to change pane layout", e))
+}
+
+#[tauri::command]
+pub async fn get_simulation_panes(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(sim_manager.panes.info())
+}
+
+/// Make every pane follow the focused pane's pan and zoom
+#[tauri::command]
+pub async fn set_pane_cameras_linked(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    linked: bool,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    Ok(sim_manager.set_pane_cameras_linked(linked))
+}
+
+/// Set how a pane blends over the panes before it when they're layered
+#[tauri::command]
+pub async fn set_pane_blend_mode(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    pane_id: u32,
+    mode: BlendMode,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let blend = pane_blend(&sim_manager, pane_id)?;
+    sim_manager
+        .set_pane_blend(pane_id, PaneBlend { mode, ..blend })
+        .map_err(|e| Diagnostic::context("Failed to set pane blend mode", e))
+}
+
+/// Fade a layered pane in or out, from 0 to 1
+#[tauri::command]
+pub async fn set_pane_opacity(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    pane_id: u32,
+    opacity: f32,
+) -> Result<Vec<PaneInfo>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let blend = pane_blend(&sim_manager, pane_id)?;
+    sim_manager
+        .set_pane_blend(pane_id, PaneBlend { opacity, ..blend })
+        .map_err(|e| Diagnostic::context("Failed to set pane opacity", e))
+}
+
+fn pane_blend(sim_manager: &SimulationManager, pane_id: u32) -> Result<PaneBlend, Diagnostic> {
+    sim_manager
+        .panes
+        .info()
+        .into_iter()
+        .find(|pane| pane.id == pane_id)
+        .map(|pane| pane.blend)
+        .ok_or_else(|| Diagnostic::invalid_input(format!("No pane with id {}", pane_id)))
+}
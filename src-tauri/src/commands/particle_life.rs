@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
 use bytemuck;
 use serde_json::Value;
@@ -10,7 +11,7 @@ pub async fn scale_force_matrix(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     scale_factor: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "scale_force_matrix called with scale_factor: {}",
         scale_factor
@@ -45,7 +46,7 @@ pub async fn scale_force_matrix(
 pub async fn flip_force_matrix_horizontal(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("flip_force_matrix_horizontal called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -77,7 +78,7 @@ pub async fn flip_force_matrix_horizontal(
 pub async fn flip_force_matrix_vertical(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("flip_force_matrix_vertical called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -109,7 +110,7 @@ pub async fn flip_force_matrix_vertical(
 pub async fn rotate_force_matrix_clockwise(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("rotate_force_matrix_clockwise called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -141,7 +142,7 @@ pub async fn rotate_force_matrix_clockwise(
 pub async fn rotate_force_matrix_counterclockwise(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("rotate_force_matrix_counterclockwise called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -173,7 +174,7 @@ pub async fn rotate_force_matrix_counterclockwise(
 pub async fn shift_force_matrix_left(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("shift_force_matrix_left called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -205,7 +206,7 @@ pub async fn shift_force_matrix_left(
 pub async fn shift_force_matrix_right(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("shift_force_matrix_right called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -237,7 +238,7 @@ pub async fn shift_force_matrix_right(
 pub async fn shift_force_matrix_up(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("shift_force_matrix_up called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -269,7 +270,7 @@ pub async fn shift_force_matrix_up(
 pub async fn shift_force_matrix_down(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("shift_force_matrix_down called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -301,7 +302,7 @@ pub async fn shift_force_matrix_down(
 pub async fn zero_force_matrix(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("zero_force_matrix called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -333,7 +334,7 @@ pub async fn zero_force_matrix(
 pub async fn flip_force_matrix_sign(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("flip_force_matrix_sign called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -367,7 +368,7 @@ pub async fn update_particle_life_post_processing_state(
     effect_name: String,
     enabled: bool,
     params: Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "update_particle_life_post_processing_state called: {} = {}",
         effect_name,
@@ -393,14 +394,17 @@ pub async fn update_particle_life_post_processing_state(
             );
             Ok("Post processing state updated successfully".to_string())
         }
-        _ => Err(format!("Unknown post processing effect: {}", effect_name)),
+        _ => Err(Diagnostic::invalid_input(format!(
+            "Unknown post processing effect: {}",
+            effect_name
+        ))),
     }
 }
 
 #[tauri::command]
 pub async fn get_particle_life_post_processing_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     tracing::debug!("get_particle_life_post_processing_state called");
     let sim_manager = manager.lock().await;
 
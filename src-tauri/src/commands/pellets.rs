@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
 use serde_json::Value;
 use std::sync::Arc;
@@ -9,7 +10,7 @@ pub async fn update_pellets_post_processing_state(
     effect_name: String,
     enabled: bool,
     params: Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "update_pellets_post_processing_state called: {} = {}",
         effect_name,
@@ -29,7 +30,10 @@ pub async fn update_pellets_post_processing_state(
             }
             Ok("Post processing state updated".to_string())
         }
-        _ => Err(format!("Unknown effect: {}", effect_name)),
+        _ => Err(Diagnostic::invalid_input(format!(
+            "Unknown effect: {}",
+            effect_name
+        ))),
     }
 }
 
@@ -38,7 +42,7 @@ pub async fn update_pellets_trails_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     enabled: bool,
     fade: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let simulation = sim_manager.pellets_simulation_mut()?;
     simulation.state.trails_enabled = enabled;
@@ -49,7 +53,7 @@ pub async fn update_pellets_trails_state(
 #[tauri::command]
 pub async fn get_pellets_post_processing_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     let sim_manager = manager.lock().await;
 
     let simulation = sim_manager.pellets_simulation()?;
@@ -1,11 +1,15 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
+use crate::simulation::preset_migration::PresetWarning;
+use crate::simulation::similarity::SimilarPreset;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tauri::State;
 
 #[tauri::command]
 pub async fn get_available_presets(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, Diagnostic> {
     let sim_manager = manager.lock().await;
     Ok(sim_manager.get_available_presets())
 }
@@ -14,28 +18,61 @@ pub async fn get_available_presets(
 pub async fn get_presets_for_simulation_type(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     simulation_type: String,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, Diagnostic> {
     let sim_manager = manager.lock().await;
     Ok(sim_manager.get_presets_for_simulation_type(&simulation_type))
 }
 
+/// User presets that didn't load cleanly, by simulation: settings that were
+/// migrated, dropped or filled in from the defaults, and files that failed
+#[tauri::command]
+pub async fn get_preset_warnings(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<BTreeMap<String, Vec<PresetWarning>>, Diagnostic> {
+    Ok(manager.lock().await.preset_manager.load_warnings())
+}
+
+/// Presets of the running simulation closest to `settings`, or to its
+/// current settings when none are given
+#[tauri::command]
+pub async fn find_similar_presets(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    settings: Option<serde_json::Value>,
+    limit: Option<usize>,
+) -> Result<Vec<SimilarPreset>, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    sim_manager
+        .find_similar_presets(settings.as_ref(), limit.unwrap_or(5))
+        .map_err(|e| Diagnostic::context("Failed to find similar presets", e))
+}
+
+/// Apply a preset, easing into it over `transition_seconds` when given
 #[tauri::command]
 pub async fn apply_preset(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     preset_name: String,
-) -> Result<String, String> {
+    transition_seconds: Option<f32>,
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
-    match sim_manager.apply_preset(&preset_name, &gpu_ctx.device, &gpu_ctx.queue) {
+    match sim_manager.apply_preset_over(
+        &preset_name,
+        transition_seconds.unwrap_or(0.0),
+        &gpu_ctx.device,
+        &gpu_ctx.queue,
+    ) {
         Ok(_) => {
             tracing::info!("Preset '{}' applied successfully", preset_name);
             Ok(format!("Preset '{}' applied successfully", preset_name))
         }
         Err(e) => {
             tracing::error!("Failed to apply preset '{}': {}", preset_name, e);
-            Err(format!("Failed to apply preset '{}': {}", preset_name, e))
+            Err(Diagnostic::context(
+                format!("Failed to apply preset '{}'", preset_name),
+                e,
+            ))
         }
     }
 }
@@ -44,7 +81,7 @@ pub async fn apply_preset(
 pub async fn save_preset(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     preset_name: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     // Get current settings (not state) for saving
@@ -56,11 +93,16 @@ pub async fn save_preset(
             }
             Err(e) => {
                 tracing::error!("Failed to save preset '{}': {}", preset_name, e);
-                Err(format!("Failed to save preset '{}': {}", preset_name, e))
+                Err(Diagnostic::context(
+                    format!("Failed to save preset '{}'", preset_name),
+                    e,
+                ))
             }
         }
     } else {
-        Err("No simulation running to save preset from".to_string())
+        Err(Diagnostic::not_running(
+            "No simulation running to save preset from",
+        ))
     }
 }
 
@@ -68,7 +110,7 @@ pub async fn save_preset(
 pub async fn delete_preset(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     preset_name: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
 
     match sim_manager.delete_preset(&preset_name) {
@@ -78,7 +120,10 @@ pub async fn delete_preset(
         }
         Err(e) => {
             tracing::error!("Failed to delete preset '{}': {}", preset_name, e);
-            Err(format!("Failed to delete preset '{}': {}", preset_name, e))
+            Err(Diagnostic::context(
+                format!("Failed to delete preset '{}'", preset_name),
+                e,
+            ))
         }
     }
 }
@@ -0,0 +1,27 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::preview_stream::PreviewStream;
+use std::sync::Arc;
+use tauri::State;
+
+/// Start sending `preview-frame` events with the running simulation shrunk by
+/// `scale`, `fps` times a second. Replaces any stream already running.
+#[tauri::command]
+pub async fn subscribe_preview_stream(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    scale: f32,
+    fps: f32,
+) -> Result<(), Diagnostic> {
+    let stream = PreviewStream::new(scale, fps)
+        .map_err(|e| Diagnostic::context("Failed to start preview stream", e))?;
+    manager.lock().await.preview_stream = Some(stream);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unsubscribe_preview_stream(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<(), Diagnostic> {
+    manager.lock().await.preview_stream = None;
+    Ok(())
+}
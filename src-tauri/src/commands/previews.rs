@@ -0,0 +1,51 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get a small PNG preview of a simulation as a data URL, for the main menu
+#[tauri::command]
+pub async fn get_simulation_preview(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    simulation_type: String,
+) -> Result<String, Diagnostic> {
+    let (device, queue, adapter_info, surface_format) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_format = gpu_ctx.surface_config.lock().await.format;
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            gpu_ctx.adapter_info.clone(),
+            surface_format,
+        )
+    };
+
+    let mut guard = manager.lock().await;
+    let sim_manager = &mut *guard;
+
+    // Previews compete with the running simulation for the GPU, so only serve them from the menu
+    if sim_manager.current_simulation.is_some() {
+        return Err(Diagnostic::unsupported(
+            "Previews are unavailable while a simulation is running",
+        ));
+    }
+
+    sim_manager
+        .previews
+        .get_preview(
+            &simulation_type,
+            &device,
+            &queue,
+            surface_format,
+            &adapter_info,
+            &sim_manager.preset_manager,
+            &sim_manager.color_scheme_manager,
+            &sim_manager.app_settings,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to render {} preview: {}", simulation_type, e);
+            Diagnostic::context("Failed to render preview", e)
+        })
+}
@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
 use serde_json::Value;
 use std::sync::Arc;
@@ -8,7 +9,7 @@ pub async fn start_primordial_particles_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_primordial_particles_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -45,7 +46,7 @@ pub async fn start_primordial_particles_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -56,7 +57,7 @@ pub async fn update_primordial_particles_post_processing_state(
     effect_name: String,
     enabled: bool,
     params: Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "update_primordial_particles_post_processing called: {} = {}",
         effect_name,
@@ -82,14 +83,17 @@ pub async fn update_primordial_particles_post_processing_state(
             );
             Ok("Post processing state updated successfully".to_string())
         }
-        _ => Err(format!("Unknown post processing effect: {}", effect_name)),
+        _ => Err(Diagnostic::invalid_input(format!(
+            "Unknown post processing effect: {}",
+            effect_name
+        ))),
     }
 }
 
 #[tauri::command]
 pub async fn get_primordial_particles_post_processing_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     tracing::debug!("get_primordial_particles_post_processing_state called");
     let sim_manager = manager.lock().await;
 
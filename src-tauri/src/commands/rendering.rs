@@ -1,13 +1,16 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
+use crate::simulation::canvas::Canvas;
+use crate::simulation::supersampling::Supersampling;
 use crate::simulations::traits::Simulation;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 
 #[tauri::command]
 pub async fn render_frame(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<(), String> {
+) -> Result<(), Diagnostic> {
     let sim_manager = manager.lock().await;
     let mut gpu_ctx = gpu_context.lock().await;
 
@@ -37,7 +40,10 @@ pub async fn render_frame(
         }
         Err(e) => {
             tracing::error!("Failed to render main menu background: {}", e);
-            Err(format!("Failed to render main menu background: {}", e))
+            Err(Diagnostic::context(
+                "Failed to render main menu background",
+                e,
+            ))
         }
     }
 }
@@ -46,7 +52,7 @@ pub async fn render_frame(
 pub async fn render_single_frame(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<(), String> {
+) -> Result<(), Diagnostic> {
     let sim_manager = manager.lock().await;
     let mut gpu_ctx = gpu_context.lock().await;
 
@@ -76,7 +82,10 @@ pub async fn render_single_frame(
         }
         Err(e) => {
             tracing::error!("Failed to render main menu background: {}", e);
-            Err(format!("Failed to render main menu background: {}", e))
+            Err(Diagnostic::context(
+                "Failed to render main menu background",
+                e,
+            ))
         }
     }
 }
@@ -87,13 +96,39 @@ pub async fn handle_window_resize(
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     width: u32,
     height: u32,
-) -> Result<(), String> {
+) -> Result<(), Diagnostic> {
+    resize_surface_and_simulation(&manager, &gpu_context, width, height).await
+}
+
+/// Moving the window to a monitor with another scale factor changes its
+/// physical size without the webview necessarily noticing, so the surface and
+/// the simulation's textures are rebuilt for the new size straight away
+pub async fn handle_scale_factor_change(
+    app: AppHandle,
+    scale_factor: f64,
+    width: u32,
+    height: u32,
+) {
+    let manager = app.state::<Arc<tokio::sync::Mutex<SimulationManager>>>();
+    let gpu_context = app.state::<Arc<tokio::sync::Mutex<crate::GpuContext>>>();
+    tracing::debug!("Scale factor changed to {}", scale_factor);
+    if let Err(e) = resize_surface_and_simulation(&manager, &gpu_context, width, height).await {
+        tracing::warn!("Failed to follow scale factor change: {}", e);
+    }
+}
+
+async fn resize_surface_and_simulation(
+    manager: &tokio::sync::Mutex<SimulationManager>,
+    gpu_context: &tokio::sync::Mutex<crate::GpuContext>,
+    width: u32,
+    height: u32,
+) -> Result<(), Diagnostic> {
     // Avoid holding both locks concurrently to prevent deadlocks during rapid resize
     let (device, queue, surface_config) = {
         let gpu_ctx = gpu_context.lock().await;
         if let Err(e) = gpu_ctx.resize_surface(width, height).await {
             tracing::error!("Failed to resize surface: {}", e);
-            return Err(format!("Failed to resize surface: {}", e));
+            return Err(Diagnostic::context("Failed to resize surface", e));
         }
         let device = gpu_ctx.device.clone();
         let queue = gpu_ctx.queue.clone();
@@ -104,9 +139,72 @@ pub async fn handle_window_resize(
     let mut sim_manager = manager.lock().await;
     if let Err(e) = sim_manager.handle_resize(&device, &queue, &surface_config) {
         tracing::error!("Failed to handle simulation resize: {}", e);
-        return Err(format!("Failed to handle simulation resize: {}", e));
+        return Err(Diagnostic::context("Failed to handle simulation resize", e));
     }
 
     tracing::trace!("Window resized to {}x{}", width, height);
     Ok(())
 }
+
+/// Render to a fixed size canvas, letterboxed into the window, so captures come
+/// out at exactly that size. `None` goes back to following the window.
+#[tauri::command]
+pub async fn set_canvas(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    canvas: Option<Canvas>,
+) -> Result<(), Diagnostic> {
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .set_canvas(canvas, &device, &queue, &surface_config)
+        .map_err(|e| Diagnostic::context("Failed to set canvas", e))
+}
+
+#[tauri::command]
+pub async fn get_canvas(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Option<Canvas>, Diagnostic> {
+    Ok(manager.lock().await.master_bus.canvas())
+}
+
+/// Turn progressive supersampling of the paused view on or off
+#[tauri::command]
+pub async fn set_supersampling(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    supersampling: Supersampling,
+) -> Result<(), Diagnostic> {
+    let (device, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (gpu_ctx.device.clone(), surface_config)
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .set_supersampling(supersampling, &device, &surface_config)
+        .map_err(|e| Diagnostic::context("Failed to set supersampling", e))
+}
+
+/// The supersampling settings, and how many samples of the paused view have
+/// been averaged so far
+#[tauri::command]
+pub async fn get_supersampling(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<serde_json::Value, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(serde_json::json!({
+        "settings": sim_manager.supersampler.config(),
+        "samples": sim_manager.master_bus.accumulated_samples(),
+    }))
+}
@@ -0,0 +1,112 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::replay::{Replay, ReplayInfo, ReplayProgress};
+use std::sync::Arc;
+use tauri::State;
+
+/// Start the running simulation over from its seed and record input from
+/// there
+#[tauri::command]
+pub async fn start_replay_recording(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    name: String,
+) -> Result<(), Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    manager
+        .lock()
+        .await
+        .start_replay_recording(&name, &device, &queue)
+        .map_err(|e| Diagnostic::context("Failed to start recording a replay", e))?;
+    tracing::info!("Recording replay '{}'", name);
+    Ok(())
+}
+
+/// Finish recording and save the replay
+#[tauri::command]
+pub async fn stop_replay_recording(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<ReplayInfo, Diagnostic> {
+    let replay = manager
+        .lock()
+        .await
+        .replay_recorder
+        .stop()
+        .ok_or_else(|| "No replay is being recorded".to_string())?;
+    let path = replay
+        .save()
+        .map_err(|e| Diagnostic::context("Failed to save replay", e))?;
+    let info = replay.info();
+    tracing::info!(
+        "Saved replay '{}' with {} frames to {}",
+        info.name,
+        info.frames,
+        path.display()
+    );
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn get_replays() -> Result<Vec<String>, Diagnostic> {
+    Ok(Replay::list())
+}
+
+#[tauri::command]
+pub async fn delete_replay(name: String) -> Result<(), Diagnostic> {
+    Replay::delete(&name)
+        .map_err(|e| Diagnostic::context(format!("Failed to delete replay '{}'", name), e))
+}
+
+/// Bring back a saved replay's start and play it frame by frame
+#[tauri::command]
+pub async fn play_replay(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    name: String,
+) -> Result<ReplayInfo, Diagnostic> {
+    let replay = Replay::load(&name).map_err(Diagnostic::from)?;
+    let info = replay.info();
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    manager
+        .lock()
+        .await
+        .play_replay(replay, &device, &queue)
+        .map_err(|e| Diagnostic::context(format!("Failed to play replay '{}'", name), e))?;
+    tracing::info!(
+        "Playing replay '{}' ({} frames, {:.1}s)",
+        info.name,
+        info.frames,
+        info.duration_seconds
+    );
+    Ok(info)
+}
+
+/// Stop the replay playing, handing the mouse back
+#[tauri::command]
+pub async fn stop_replay_playback(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<(), Diagnostic> {
+    manager.lock().await.replay_player = None;
+    Ok(())
+}
+
+/// How far the playing replay is, or `None` when none is playing
+#[tauri::command]
+pub async fn get_replay_progress(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Option<ReplayProgress>, Diagnostic> {
+    Ok(manager
+        .lock()
+        .await
+        .replay_player
+        .as_ref()
+        .map(|player| player.progress()))
+}
@@ -1,4 +1,6 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
+use crate::simulation::seeds::Seed;
 use std::sync::Arc;
 use tauri::State;
 
@@ -6,12 +8,12 @@ use tauri::State;
 pub async fn reset_trails(
     sim_manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<(), String> {
+) -> Result<(), Diagnostic> {
     let mut sim_manager = sim_manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
     match sim_manager.reset_trails(&gpu_ctx.device, &gpu_ctx.queue) {
         Ok(_) => Ok(()),
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -19,12 +21,12 @@ pub async fn reset_trails(
 pub async fn reset_agents(
     sim_manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<(), String> {
+) -> Result<(), Diagnostic> {
     let mut sim_manager = sim_manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
     match sim_manager.reset_agents(&gpu_ctx.device, &gpu_ctx.queue) {
         Ok(_) => Ok(()),
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -32,7 +34,7 @@ pub async fn reset_agents(
 pub async fn reset_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -49,7 +51,7 @@ pub async fn reset_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to reset simulation: {}", e);
-            Err(format!("Failed to reset simulation: {}", e))
+            Err(Diagnostic::context("Failed to reset simulation", e))
         }
     }
 }
@@ -58,7 +60,7 @@ pub async fn reset_simulation(
 pub async fn reset_runtime_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("reset_runtime_state called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -76,7 +78,7 @@ pub async fn reset_runtime_state(
         }
         Err(e) => {
             tracing::error!("Failed to reset runtime state: {}", e);
-            Err(format!("Failed to reset runtime state: {}", e))
+            Err(Diagnostic::context("Failed to reset runtime state", e))
         }
     }
 }
@@ -85,7 +87,7 @@ pub async fn reset_runtime_state(
 pub async fn reset_graphics_resources(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::info!("Resetting graphics resources for main menu");
 
     let mut sim_manager = manager.lock().await;
@@ -107,3 +109,18 @@ pub async fn reset_graphics_resources(
     tracing::info!("Graphics resources reset successfully");
     Ok("Graphics resources reset successfully".to_string())
 }
+
+/// Grow the current simulation's world again from `seed`, a number or any
+/// name, and return the seed it became
+#[tauri::command]
+pub async fn reseed(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    seed: String,
+) -> Result<Seed, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+    sim_manager
+        .reseed(&seed, &gpu_ctx.device, &gpu_ctx.queue)
+        .map_err(|e| Diagnostic::context("Failed to reseed", e))
+}
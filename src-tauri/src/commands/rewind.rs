@@ -0,0 +1,50 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulations::shared::RewindHistory;
+use std::sync::Arc;
+use tauri::State;
+
+/// Jump the running simulation back by up to `seconds` of recorded history
+#[tauri::command]
+pub async fn rewind_simulation(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    seconds: f32,
+) -> Result<RewindHistory, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .rewind(&device, &queue, seconds)
+        .map_err(|e| Diagnostic::context("Failed to rewind", e))
+}
+
+/// Pause and show one snapshot of the rewind history, 0 being the oldest
+#[tauri::command]
+pub async fn scrub_rewind(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    index: usize,
+) -> Result<RewindHistory, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .scrub_rewind(&device, &queue, index)
+        .map_err(|e| Diagnostic::context("Failed to scrub rewind history", e))
+}
+
+/// The recorded snapshots and the GPU memory they use
+#[tauri::command]
+pub async fn get_rewind_history(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<RewindHistory, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(sim_manager.rewind.history())
+}
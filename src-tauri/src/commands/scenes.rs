@@ -0,0 +1,55 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::scene_manager::{Scene, scene_path};
+use crate::simulation::workspace::WorkspaceInfo;
+use std::sync::Arc;
+use tauri::State;
+
+/// Save the running simulation's workspace and exact state to a scene file
+#[tauri::command]
+pub async fn save_scene(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    path: String,
+) -> Result<WorkspaceInfo, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let path = scene_path(&path);
+    let sim_manager = manager.lock().await;
+    let scene = Scene::capture(&sim_manager, &device, &queue, &path)
+        .and_then(|scene| scene.save(&path).map(|_| scene))
+        .map_err(|e| Diagnostic::context(format!("Failed to save scene {}", path.display()), e))?;
+    tracing::info!("Saved scene {}", path.display());
+    Ok(scene.info())
+}
+
+/// Restore a scene file into the running simulation, which has to be the one
+/// it was saved from
+#[tauri::command]
+pub async fn load_scene(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    path: String,
+) -> Result<WorkspaceInfo, Diagnostic> {
+    let path = scene_path(&path);
+    let scene = Scene::load(&path).map_err(Diagnostic::from)?;
+
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    scene
+        .restore(&mut sim_manager, &device, &queue, &surface_config)
+        .map_err(|e| Diagnostic::context(format!("Failed to load scene {}", path.display()), e))?;
+    Ok(scene.info())
+}
@@ -1,15 +1,30 @@
 use crate::GpuContext;
+use crate::error::{AppError, Diagnostic, SimulationError};
 use crate::simulation::SimulationManager;
+use crate::simulation::audio_reactive::AudioReactiveConfig;
+use crate::simulation::autopilot::AutopilotConfig;
+use crate::simulations::shared::audio::AudioBands;
+use crate::simulations::shared::{BackgroundLayer, RandomizeOptions, ValidationError};
+use crate::simulations::traits::Simulation;
+use serde::Serialize;
 use std::sync::Arc;
 use tauri::State;
 
+/// Returned by [`update_simulation_setting`] when a value can't be applied
+#[derive(Debug, Serialize)]
+pub struct SettingUpdateError {
+    pub message: String,
+    /// Why the value was rejected, when it failed the simulation's setting rules
+    pub validation: Option<ValidationError>,
+}
+
 #[tauri::command]
 pub async fn update_simulation_setting(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
     setting_name: String,
     value: serde_json::Value,
-) -> Result<String, String> {
+) -> Result<String, SettingUpdateError> {
     tracing::debug!(
         "update_simulation_setting called with settingName: '{}', value: {:?}",
         setting_name,
@@ -25,16 +40,27 @@ pub async fn update_simulation_setting(
         &gpu_ctx.device,
         &gpu_ctx.queue,
     ) {
+        Ok(applied) if applied != value => {
+            tracing::debug!("Setting '{}' clamped to {:?}", setting_name, applied);
+            Ok(format!("Setting '{}' clamped to {}", setting_name, applied))
+        }
         Ok(_) => {
             tracing::debug!("Setting '{}' updated to {:?}", setting_name, value);
             Ok(format!("Setting '{}' updated successfully", setting_name))
         }
+        Err(AppError::Simulation(SimulationError::Validation(e))) => {
+            tracing::warn!("Rejected value for setting '{}': {}", setting_name, e);
+            Err(SettingUpdateError {
+                message: e.to_string(),
+                validation: Some(e),
+            })
+        }
         Err(e) => {
             tracing::error!("Failed to update setting '{}': {}", setting_name, e);
-            Err(format!(
-                "Failed to update setting '{}': {}",
-                setting_name, e
-            ))
+            Err(SettingUpdateError {
+                message: format!("Failed to update setting '{}': {}", setting_name, e),
+                validation: None,
+            })
         }
     }
 }
@@ -42,12 +68,12 @@ pub async fn update_simulation_setting(
 #[tauri::command]
 pub async fn get_current_settings(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     let sim_manager = manager.lock().await;
 
     match sim_manager.get_current_settings() {
         Some(settings) => Ok(settings),
-        None => Err("No simulation running".to_string()),
+        None => Err(Diagnostic::not_running("No simulation running")),
     }
 }
 
@@ -57,7 +83,7 @@ pub async fn update_simulation_state(
     gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
     state_name: String,
     value: serde_json::Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "update_simulation_state called with stateName: '{}', value: {:?}",
         state_name,
@@ -74,7 +100,10 @@ pub async fn update_simulation_state(
         }
         Err(e) => {
             tracing::error!("Failed to update state '{}': {}", state_name, e);
-            Err(format!("Failed to update state '{}': {}", state_name, e))
+            Err(Diagnostic::context(
+                format!("Failed to update state '{}'", state_name),
+                e,
+            ))
         }
     }
 }
@@ -82,31 +111,135 @@ pub async fn update_simulation_state(
 #[tauri::command]
 pub async fn get_current_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     let sim_manager = manager.lock().await;
 
     match sim_manager.get_current_state() {
         Some(state) => Ok(state),
-        None => Err("No simulation running".to_string()),
+        None => Err(Diagnostic::not_running("No simulation running")),
     }
 }
 
+/// Randomize the running simulation's settings. Without `options` every
+/// setting is rolled again.
 #[tauri::command]
 pub async fn randomize_settings(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
-) -> Result<String, String> {
+    options: Option<RandomizeOptions>,
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
-    match sim_manager.randomize_settings(&gpu_ctx.device, &gpu_ctx.queue) {
+    match sim_manager.randomize_settings(
+        options.unwrap_or_default(),
+        &gpu_ctx.device,
+        &gpu_ctx.queue,
+    ) {
         Ok(_) => {
             tracing::debug!("Settings randomized successfully");
             Ok("Settings randomized successfully".to_string())
         }
         Err(e) => {
             tracing::error!("Failed to randomize settings: {}", e);
-            Err(format!("Failed to randomize settings: {}", e))
+            Err(Diagnostic::context("Failed to randomize settings", e))
         }
     }
 }
+
+/// Set the image drawn behind the running simulation
+#[tauri::command]
+pub async fn set_background_layer(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    layer: BackgroundLayer,
+) -> Result<(), Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .set_background_layer(layer)
+        .map_err(|e| Diagnostic::context("Failed to set background layer", e))
+}
+
+#[tauri::command]
+pub async fn get_background_layer(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Option<BackgroundLayer>, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    Ok(sim_manager
+        .simulation()
+        .and_then(|simulation| simulation.background_layer())
+        .cloned())
+}
+
+/// Let settings of the running simulation drift slowly on their own. The
+/// settings panel should reload the settings now and then while it's on.
+#[tauri::command]
+pub async fn set_autopilot(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    config: AutopilotConfig,
+) -> Result<AutopilotConfig, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .set_autopilot(config)
+        .map_err(|e| Diagnostic::context("Failed to set autopilot", e))?;
+    Ok(sim_manager.autopilot.config().clone())
+}
+
+#[tauri::command]
+pub async fn get_autopilot(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<AutopilotConfig, Diagnostic> {
+    Ok(manager.lock().await.autopilot.config().clone())
+}
+
+/// Route bands of live audio to settings of the running simulation. The
+/// frontend captures the audio and sends it with [`push_audio_samples`].
+#[tauri::command]
+pub async fn set_audio_reactive(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
+    config: AudioReactiveConfig,
+) -> Result<AudioReactiveConfig, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+    sim_manager
+        .set_audio_reactive(config, &gpu_ctx.device, &gpu_ctx.queue)
+        .map_err(|e| Diagnostic::context("Failed to set audio reactivity", e))?;
+    Ok(sim_manager.audio_reactive.config().clone())
+}
+
+#[tauri::command]
+pub async fn get_audio_reactive(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<AudioReactiveConfig, Diagnostic> {
+    Ok(manager.lock().await.audio_reactive.config().clone())
+}
+
+/// Mono samples in -1 to 1, as captured since the last call
+#[tauri::command]
+pub async fn push_audio_samples(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    samples: Vec<f32>,
+    sample_rate: u32,
+) -> Result<(), Diagnostic> {
+    if !(8000..=192000).contains(&sample_rate) {
+        return Err(Diagnostic::invalid_input(format!(
+            "Unsupported sample rate {}",
+            sample_rate
+        )));
+    }
+    manager
+        .lock()
+        .await
+        .audio_reactive
+        .push_samples(&samples, sample_rate);
+    Ok(())
+}
+
+/// Band energies as of the last frame, for level meters. They only update
+/// while audio reactivity is on.
+#[tauri::command]
+pub async fn get_audio_bands(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<AudioBands, Diagnostic> {
+    Ok(manager.lock().await.audio_reactive.bands())
+}
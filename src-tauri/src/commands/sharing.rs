@@ -0,0 +1,95 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::settings_codec::SharedConfiguration;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get a vizza:// link that reopens the running simulation with its current settings and camera
+#[tauri::command]
+pub async fn get_share_link(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<String, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    sim_manager
+        .shared_configuration()
+        .and_then(|config| config.to_deep_link())
+        .map_err(|e| Diagnostic::context("Failed to create share link", e))
+}
+
+/// Encode the running simulation's settings, color scheme and camera as a share code
+#[tauri::command]
+pub async fn export_share_code(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<String, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    sim_manager
+        .shared_configuration()
+        .and_then(|config| config.to_share_code())
+        .map_err(|e| Diagnostic::context("Failed to create share code", e))
+}
+
+/// Decode a share code. If it is for the running simulation it is applied
+/// straight away, otherwise it is kept as the pending shared configuration
+/// for the frontend to apply once it has started the right simulation.
+#[tauri::command]
+pub async fn import_share_code(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    code: String,
+) -> Result<SharedConfiguration, Diagnostic> {
+    let config = SharedConfiguration::from_share_code(&code).map_err(Diagnostic::from)?;
+
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    let is_running = sim_manager
+        .current_simulation
+        .as_ref()
+        .is_some_and(|simulation| simulation.type_name() == config.simulation_type);
+    if is_running {
+        sim_manager
+            .apply_shared_configuration(&config, &device, &queue)
+            .map_err(|e| Diagnostic::context("Failed to apply share code", e))?;
+    } else {
+        sim_manager.pending_shared_configuration = Some(config.clone());
+    }
+
+    Ok(config)
+}
+
+/// Take the configuration from the last opened deep link or share code, if it hasn't been taken yet
+#[tauri::command]
+pub async fn take_pending_shared_configuration(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Option<SharedConfiguration>, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    Ok(sim_manager.pending_shared_configuration.take())
+}
+
+/// Apply a shared configuration to the running simulation
+#[tauri::command]
+pub async fn apply_shared_configuration(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    config: SharedConfiguration,
+) -> Result<String, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .apply_shared_configuration(&config, &device, &queue)
+        .map_err(|e| {
+            tracing::error!("Failed to apply shared configuration: {}", e);
+            Diagnostic::context("Failed to apply shared configuration", e)
+        })?;
+    Ok(format!(
+        "Applied shared {} configuration",
+        config.simulation_type
+    ))
+}
@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::{simulation::SimulationManager, simulations::shared::BackgroundColorMode};
 use std::sync::Arc;
 use tauri::{Emitter, State};
@@ -7,7 +8,7 @@ pub async fn start_slime_mold_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_slime_mold_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -44,7 +45,7 @@ pub async fn start_slime_mold_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -54,7 +55,7 @@ pub async fn start_particle_life_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_particle_life_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -91,7 +92,7 @@ pub async fn start_particle_life_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -101,7 +102,7 @@ pub async fn start_gray_scott_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_gray_scott_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -138,7 +139,7 @@ pub async fn start_gray_scott_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -148,7 +149,7 @@ pub async fn start_flow_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_flow_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -185,7 +186,7 @@ pub async fn start_flow_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -195,7 +196,7 @@ pub async fn start_pellets_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_pellets_simulation called");
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -232,7 +233,7 @@ pub async fn start_pellets_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -243,7 +244,7 @@ pub async fn start_simulation(
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
     app: tauri::AppHandle,
     simulation_type: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("start_simulation called with type: {}", simulation_type);
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -283,7 +284,7 @@ pub async fn start_simulation(
         }
         Err(e) => {
             tracing::error!("Failed to start simulation: {}", e);
-            Err(format!("Failed to start simulation: {}", e))
+            Err(Diagnostic::context("Failed to start simulation", e))
         }
     }
 }
@@ -291,7 +292,7 @@ pub async fn start_simulation(
 #[tauri::command]
 pub async fn pause_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("pause_simulation called");
     let sim_manager = manager.lock().await;
     sim_manager.pause();
@@ -302,7 +303,7 @@ pub async fn pause_simulation(
 pub async fn resume_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("resume_simulation called");
     let sim_manager = manager.lock().await;
 
@@ -317,14 +318,14 @@ pub async fn resume_simulation(
 
         Ok("Simulation resumed".to_string())
     } else {
-        Err("No simulation to resume".to_string())
+        Err(Diagnostic::not_running("No simulation to resume"))
     }
 }
 
 #[tauri::command]
 pub async fn step_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("step_simulation called");
     let sim_manager = manager.lock().await;
     if sim_manager.is_running() {
@@ -332,14 +333,28 @@ pub async fn step_simulation(
         sim_manager.step_once();
         Ok("Simulation stepped one frame".to_string())
     } else {
-        Err("No simulation to step".to_string())
+        Err(Diagnostic::not_running("No simulation to step"))
     }
 }
 
+#[tauri::command]
+pub async fn fast_forward(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    steps: u32,
+) -> Result<u32, Diagnostic> {
+    tracing::debug!("fast_forward called with {} steps", steps);
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+    sim_manager
+        .fast_forward(steps, &gpu_ctx.device, &gpu_ctx.queue)
+        .map_err(|e| Diagnostic::context("Failed to fast-forward", e))
+}
+
 #[tauri::command]
 pub async fn destroy_simulation(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("destroy_simulation called");
     let mut sim_manager = manager.lock().await;
     sim_manager.stop_simulation();
@@ -350,7 +365,7 @@ pub async fn destroy_simulation(
 #[tauri::command]
 pub async fn get_simulation_status(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let sim_manager = manager.lock().await;
     Ok(sim_manager.get_status())
 }
@@ -359,7 +374,7 @@ pub async fn get_simulation_status(
 pub async fn clear_trail_texture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!("clear_trail_texture called");
     let sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
@@ -417,11 +432,11 @@ pub async fn clear_trail_texture(
                 tracing::debug!("Trail texture cleared successfully for Primordial Particles");
                 Ok("Trail texture cleared".to_string())
             }
-            _ => Err(
-                "Clear trail texture is only available for Particle Life simulations".to_string(),
-            ),
+            _ => Err(Diagnostic::unsupported(
+                "Clear trail texture is only available for Particle Life simulations",
+            )),
         }
     } else {
-        Err("No simulation running".to_string())
+        Err(Diagnostic::not_running("No simulation running"))
     }
 }
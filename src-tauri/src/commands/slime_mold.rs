@@ -1,4 +1,5 @@
 use crate::GpuContext;
+use crate::error::{Diagnostic, ErrorCode};
 use crate::simulation::SimulationManager;
 use serde_json::Value;
 use std::sync::Arc;
@@ -10,7 +11,7 @@ pub async fn update_slime_mold_post_processing_state(
     effect_name: String,
     enabled: bool,
     params: Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     tracing::debug!(
         "update_slime_mold_post_processing_state called: {} = {}",
         effect_name,
@@ -30,14 +31,17 @@ pub async fn update_slime_mold_post_processing_state(
             }
             Ok("Post processing state updated".to_string())
         }
-        _ => Err(format!("Unknown effect: {}", effect_name)),
+        _ => Err(Diagnostic::invalid_input(format!(
+            "Unknown effect: {}",
+            effect_name
+        ))),
     }
 }
 
 #[tauri::command]
 pub async fn get_slime_mold_post_processing_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     let sim_manager = manager.lock().await;
     let simulation = sim_manager.slime_mold_simulation()?;
 
@@ -56,7 +60,7 @@ pub async fn update_agent_count(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
     count: u32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
 
@@ -70,17 +74,17 @@ pub async fn update_agent_count(
             .await
         {
             Ok(_) => Ok(format!("Agent count updated to {}", count)),
-            Err(e) => Err(format!("Failed to update agent count: {}", e)),
+            Err(e) => Err(Diagnostic::context("Failed to update agent count", e)),
         }
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
 #[tauri::command]
 pub async fn get_current_agent_count(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Option<u32>, String> {
+) -> Result<Option<u32>, Diagnostic> {
     let sim_manager = manager.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(simulation)) =
         &sim_manager.current_simulation
@@ -96,17 +100,17 @@ pub async fn load_slime_mold_mask_image(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
     image_path: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu = gpu_context.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
         &mut sim_manager.current_simulation
     {
         sim.load_mask_image_from_path(&gpu.device, &gpu.queue, &image_path)
-            .map_err(|e| e.to_string())?;
+            .map_err(Diagnostic::from)?;
         Ok("Mask image loaded".to_string())
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
@@ -114,7 +118,7 @@ pub async fn load_slime_mold_mask_image(
 pub async fn set_slime_mold_mask_image_fit_mode(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     fit_mode: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
         &mut sim_manager.current_simulation
@@ -126,7 +130,7 @@ pub async fn set_slime_mold_mask_image_fit_mode(
         sim.reprocess_mask_image_with_current_fit_mode();
         Ok("Mask image fit mode set and image reprocessed".to_string())
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
@@ -135,17 +139,17 @@ pub async fn load_slime_mold_position_image(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
     image_path: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu = gpu_context.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
         &mut sim_manager.current_simulation
     {
         sim.load_position_image_from_path(&gpu.device, &gpu.queue, &image_path)
-            .map_err(|e| e.to_string())?;
+            .map_err(Diagnostic::from)?;
         Ok("Position image loaded".to_string())
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
@@ -153,7 +157,7 @@ pub async fn load_slime_mold_position_image(
 pub async fn set_slime_mold_position_image_fit_mode(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     fit_mode: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
         &mut sim_manager.current_simulation
@@ -165,36 +169,39 @@ pub async fn set_slime_mold_position_image_fit_mode(
         sim.reprocess_position_image_with_current_fit_mode();
         Ok("Position image fit mode set and image reprocessed".to_string())
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
 #[tauri::command]
 pub async fn start_slime_mold_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
         &mut sim_manager.current_simulation
     {
         let available_devices = sim.get_available_webcam_devices();
         if available_devices.is_empty() {
-            return Err("No webcam devices available".to_string());
+            return Err(Diagnostic::new(
+                ErrorCode::WebcamUnavailable,
+                "No webcam devices available",
+            ));
         }
         let device_index = available_devices[0];
         match sim.start_webcam_capture(device_index) {
             Ok(_) => Ok("Webcam capture started".to_string()),
-            Err(e) => Err(format!("Failed to start webcam capture: {}", e)),
+            Err(e) => Err(Diagnostic::context("Failed to start webcam capture", e)),
         }
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
 #[tauri::command]
 pub async fn stop_slime_mold_webcam_capture(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
         &mut sim_manager.current_simulation
@@ -202,21 +209,21 @@ pub async fn stop_slime_mold_webcam_capture(
         sim.stop_webcam_capture();
         Ok("Webcam capture stopped".to_string())
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
 #[tauri::command]
 pub async fn get_available_webcam_devices(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<Vec<i32>, String> {
+) -> Result<Vec<i32>, Diagnostic> {
     let sim_manager = manager.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
         &sim_manager.current_simulation
     {
         Ok(sim.get_available_webcam_devices())
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
 
@@ -225,7 +232,7 @@ pub async fn update_slime_mold_background_mode(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
     background_mode: String,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let gpu_ctx = gpu_context.lock().await;
     if let Some(crate::simulations::traits::SimulationType::SlimeMold(sim)) =
@@ -234,11 +241,56 @@ pub async fn update_slime_mold_background_mode(
         sim.settings.background_mode = match background_mode.as_str() {
             "black" => crate::simulations::slime_mold::settings::BackgroundMode::Black,
             "white" => crate::simulations::slime_mold::settings::BackgroundMode::White,
-            _ => return Err(format!("Invalid background mode: {}", background_mode)),
+            _ => {
+                return Err(Diagnostic::invalid_input(format!(
+                    "Invalid background mode: {}",
+                    background_mode
+                )));
+            }
         };
         sim.update_background_params(&gpu_ctx.queue);
         Ok(format!("Background mode updated to: {}", background_mode))
     } else {
-        Err("No slime mold simulation running".to_string())
+        Err(Diagnostic::not_running("No slime mold simulation running"))
     }
 }
+
+/// Save the trail map as a 16-bit grayscale image, PNG or TIFF by extension
+#[tauri::command]
+pub async fn export_slime_mold_trail_map(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
+    path: String,
+) -> Result<String, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    let gpu = gpu_context.lock().await;
+
+    let sim = sim_manager.slime_mold_simulation()?;
+    let image = sim
+        .export_trail_map(&gpu.device, &gpu.queue)
+        .map_err(|e| Diagnostic::context("Failed to read trail map", e))?;
+    image.save(&path).map_err(|e| {
+        Diagnostic::context(
+            format!("Failed to save trail map to {}", path),
+            e.to_string(),
+        )
+    })?;
+    Ok(path)
+}
+
+/// Replace the trail map with an image, stretched to the grid
+#[tauri::command]
+pub async fn import_slime_mold_trail_map(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<GpuContext>>>,
+    image_path: String,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu = gpu_context.lock().await;
+
+    let sim = sim_manager.slime_mold_simulation_mut()?;
+    let image = image::open(&image_path)
+        .map_err(|e| Diagnostic::invalid_input(format!("Failed to open trail map image: {}", e)))?;
+    sim.import_trail_map(&gpu.queue, &image);
+    Ok("Trail map imported".to_string())
+}
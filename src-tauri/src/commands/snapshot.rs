@@ -0,0 +1,40 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulations::shared::SimulationSnapshot;
+use std::sync::Arc;
+use tauri::State;
+
+/// Read the running simulation's exact state back from the GPU
+#[tauri::command]
+pub async fn get_simulation_snapshot(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<SimulationSnapshot, Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let sim_manager = manager.lock().await;
+    sim_manager
+        .simulation_snapshot(&device, &queue)
+        .map_err(|e| Diagnostic::context("Failed to snapshot simulation", e))
+}
+
+/// Restore a state taken by `get_simulation_snapshot`
+#[tauri::command]
+pub async fn load_simulation_snapshot(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    snapshot: SimulationSnapshot,
+) -> Result<(), Diagnostic> {
+    let (device, queue) = {
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let mut sim_manager = manager.lock().await;
+    sim_manager
+        .load_simulation_snapshot(&snapshot, &device, &queue)
+        .map_err(|e| Diagnostic::context("Failed to load simulation snapshot", e))
+}
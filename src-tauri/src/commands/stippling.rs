@@ -0,0 +1,34 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use std::sync::Arc;
+use tauri::State;
+
+/// Stipple an image in place of the sphere
+#[tauri::command]
+pub async fn load_stippling_image(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    image_path: String,
+) -> Result<String, Diagnostic> {
+    let mut sim_manager = manager.lock().await;
+    let gpu_ctx = gpu_context.lock().await;
+
+    let sim = sim_manager.stippling_simulation_mut()?;
+    sim.load_image_from_path(&gpu_ctx.device, &gpu_ctx.queue, &image_path)
+        .map_err(|e| Diagnostic::context("Failed to load stippling image", e))?;
+    Ok("Stippling image loaded successfully".to_string())
+}
+
+/// Save the dots as an SVG of circles
+#[tauri::command]
+pub async fn export_stippling_svg(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    path: String,
+) -> Result<String, Diagnostic> {
+    let sim_manager = manager.lock().await;
+
+    let sim = sim_manager.stippling_simulation()?;
+    std::fs::write(&path, sim.export_svg())
+        .map_err(|e| Diagnostic::context(format!("Failed to save stipples to {}", path), e))?;
+    Ok(path)
+}
@@ -1,25 +1,74 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
+use crate::simulation::gpu_report::{GpuCapabilities, GpuReport};
+use crate::simulations::shared::gpu_budget;
 use std::sync::Arc;
 use tauri::{Manager, State};
 
 #[tauri::command]
-pub async fn get_app_version() -> Result<String, String> {
+pub async fn get_app_version() -> Result<String, Diagnostic> {
     Ok(env!("CARGO_PKG_VERSION").to_string())
 }
 
 #[tauri::command]
 pub async fn check_gpu_context_ready(
     gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
-) -> Result<bool, String> {
+) -> Result<bool, Diagnostic> {
     let _gpu_ctx = gpu_context.lock().await;
     // If we can lock the GPU context, it's ready
     Ok(true)
 }
 
+/// Report the surface format in use and the color space it presents in, which
+/// can differ from the `color_space` app setting when wide gamut is unavailable
+#[tauri::command]
+pub async fn get_display_color_space(
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<serde_json::Value, Diagnostic> {
+    let gpu_ctx = gpu_context.lock().await;
+    let format = gpu_ctx.surface_config.lock().await.format;
+    Ok(serde_json::json!({
+        "surface_format": format!("{:?}", format),
+        "color_space": crate::simulations::shared::color_space::active_color_space(format),
+    }))
+}
+
+/// How much of the GPU memory budget the tracked allocations are using
+#[tauri::command]
+pub async fn get_gpu_memory_usage() -> Result<gpu_budget::GpuMemoryUsage, Diagnostic> {
+    Ok(gpu_budget::usage())
+}
+
+/// The adapter, its limits and features, and which optional features Vizza
+/// can use on it
+#[tauri::command]
+pub async fn get_gpu_report(
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<GpuReport, Diagnostic> {
+    let gpu_ctx = gpu_context.lock().await;
+    let surface_format = gpu_ctx.surface_config.lock().await.format;
+    let adapter = &gpu_ctx.adapter;
+    Ok(GpuReport::new(&GpuCapabilities {
+        adapter_info: &gpu_ctx.adapter_info,
+        adapter_features: adapter.features(),
+        adapter_limits: adapter.limits(),
+        device_limits: gpu_ctx.device.limits(),
+        surface_format,
+        surface_format_features: adapter.get_texture_format_features(surface_format),
+        surface_formats: gpu_ctx.surface.get_capabilities(adapter).formats,
+    }))
+}
+
+/// The errors commands returned lately, oldest first, for bug reports
+#[tauri::command]
+pub async fn get_recent_errors() -> Result<Vec<Diagnostic>, Diagnostic> {
+    Ok(crate::error::recent_errors())
+}
+
 #[tauri::command]
 pub async fn toggle_gui(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     sim_manager.toggle_gui();
 
@@ -36,7 +85,7 @@ pub async fn toggle_gui(
 #[tauri::command]
 pub async fn get_gui_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<bool, String> {
+) -> Result<bool, Diagnostic> {
     let sim_manager = manager.lock().await;
     Ok(sim_manager.is_gui_visible())
 }
@@ -46,7 +95,7 @@ pub async fn set_fps_limit(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     enabled: bool,
     limit: u32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let sim_manager = manager.lock().await;
     sim_manager.set_fps_limit(enabled, limit);
 
@@ -60,7 +109,7 @@ pub async fn set_fps_limit(
 }
 
 #[tauri::command]
-pub async fn toggle_fullscreen(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn toggle_fullscreen(app: tauri::AppHandle) -> Result<String, Diagnostic> {
     // Get the main window
     let window = app
         .get_webview_window("main")
@@ -69,18 +118,18 @@ pub async fn toggle_fullscreen(app: tauri::AppHandle) -> Result<String, String>
     // Check current fullscreen state
     let is_fullscreen = window
         .is_fullscreen()
-        .map_err(|e| format!("Failed to get fullscreen state: {}", e))?;
+        .map_err(|e| Diagnostic::context("Failed to get fullscreen state", e))?;
 
     // Toggle fullscreen state
     if is_fullscreen {
         window
             .set_fullscreen(false)
-            .map_err(|e| format!("Failed to exit fullscreen: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to exit fullscreen", e))?;
         tracing::debug!("Exited fullscreen mode");
     } else {
         window
             .set_fullscreen(true)
-            .map_err(|e| format!("Failed to enter fullscreen: {}", e))?;
+            .map_err(|e| Diagnostic::context("Failed to enter fullscreen", e))?;
         tracing::debug!("Entered fullscreen mode");
     }
 
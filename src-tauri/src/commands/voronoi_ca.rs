@@ -1,3 +1,4 @@
+use crate::error::Diagnostic;
 use crate::simulation::SimulationManager;
 use std::sync::Arc;
 use tauri::State;
@@ -8,7 +9,7 @@ pub async fn update_voronoi_ca_post_processing_state(
     effect_name: String,
     enabled: bool,
     params: serde_json::Value,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let simulation = sim_manager.voronoi_ca_simulation_mut()?;
     match effect_name.as_str() {
@@ -22,14 +23,14 @@ pub async fn update_voronoi_ca_post_processing_state(
             }
             Ok("Post processing state updated".to_string())
         }
-        _ => Err("Unknown post-processing effect".to_string()),
+        _ => Err(Diagnostic::invalid_input("Unknown post-processing effect")),
     }
 }
 
 #[tauri::command]
 pub async fn get_voronoi_ca_post_processing_state(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, Diagnostic> {
     let sim_manager = manager.lock().await;
     let simulation = sim_manager.voronoi_ca_simulation()?;
     Ok(serde_json::json!({
@@ -45,7 +46,7 @@ pub async fn get_voronoi_ca_post_processing_state(
 pub async fn update_voronoi_ca_border_width(
     manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
     border_width: f32,
-) -> Result<String, String> {
+) -> Result<String, Diagnostic> {
     let mut sim_manager = manager.lock().await;
     let simulation = sim_manager.voronoi_ca_simulation_mut()?;
     simulation.border_width = border_width.clamp(0.0, 1000.0);
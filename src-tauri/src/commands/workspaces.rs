@@ -0,0 +1,115 @@
+use crate::error::Diagnostic;
+use crate::simulation::SimulationManager;
+use crate::simulation::workspace::{Workspace, WorkspaceInfo};
+use std::sync::Arc;
+use tauri::State;
+
+/// Save the running simulation's settings, camera, color scheme, master
+/// effects, palette cycling and canvas as a named workspace
+#[tauri::command]
+pub async fn save_workspace(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    name: String,
+) -> Result<Workspace, Diagnostic> {
+    let sim_manager = manager.lock().await;
+    let workspace = Workspace::capture(&sim_manager, &name)
+        .and_then(|workspace| workspace.save().map(|_| workspace))
+        .map_err(|e| Diagnostic::context(format!("Failed to save workspace '{}'", name), e))?;
+    tracing::info!("Saved workspace {}", name);
+    Ok(workspace)
+}
+
+/// Restore a workspace. If it is for another simulation than the running one,
+/// its configuration is left pending for the frontend to apply once it has
+/// started the right simulation, as with gallery entries.
+#[tauri::command]
+pub async fn load_workspace(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+    name: String,
+) -> Result<Workspace, Diagnostic> {
+    let workspace = Workspace::load(&name).map_err(Diagnostic::from)?;
+
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    workspace
+        .restore(&mut sim_manager, &device, &queue, &surface_config)
+        .map_err(|e| Diagnostic::context(format!("Failed to load workspace '{}'", name), e))?;
+    Ok(workspace)
+}
+
+/// Saved workspaces, by name
+#[tauri::command]
+pub async fn list_workspaces() -> Result<Vec<WorkspaceInfo>, Diagnostic> {
+    Ok(Workspace::list())
+}
+
+#[tauri::command]
+pub async fn delete_workspace(name: String) -> Result<String, Diagnostic> {
+    Workspace::delete(&name)
+        .map_err(|e| Diagnostic::context(format!("Failed to delete workspace '{}'", name), e))?;
+    Ok(format!("Workspace '{}' deleted", name))
+}
+
+/// When the last session didn't exit cleanly, what its autosave holds
+#[tauri::command]
+pub async fn get_crash_recovery(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<Option<WorkspaceInfo>, Diagnostic> {
+    Ok(manager
+        .lock()
+        .await
+        .crash_recovery
+        .as_ref()
+        .map(|workspace| WorkspaceInfo {
+            name: workspace.name.clone(),
+            saved_at: workspace.saved_at.clone(),
+            simulation_type: workspace.configuration.simulation_type.clone(),
+        }))
+}
+
+/// Bring back the autosave of a session that crashed, the way a workspace is
+/// loaded
+#[tauri::command]
+pub async fn restore_last_session_after_crash(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+    gpu_context: State<'_, Arc<tokio::sync::Mutex<crate::GpuContext>>>,
+) -> Result<Workspace, Diagnostic> {
+    let (device, queue, surface_config) = {
+        let gpu_ctx = gpu_context.lock().await;
+        let surface_config = gpu_ctx.surface_config.lock().await.clone();
+        (
+            gpu_ctx.device.clone(),
+            gpu_ctx.queue.clone(),
+            surface_config,
+        )
+    };
+
+    let mut sim_manager = manager.lock().await;
+    let workspace = sim_manager
+        .crash_recovery
+        .take()
+        .ok_or_else(|| "There is no crashed session to restore".to_string())?;
+    workspace
+        .restore(&mut sim_manager, &device, &queue, &surface_config)
+        .map_err(|e| Diagnostic::context("Failed to restore the last session", e))?;
+    tracing::info!("Restored the session autosaved at {}", workspace.saved_at);
+    Ok(workspace)
+}
+
+#[tauri::command]
+pub async fn discard_crash_recovery(
+    manager: State<'_, Arc<tokio::sync::Mutex<SimulationManager>>>,
+) -> Result<(), Diagnostic> {
+    manager.lock().await.crash_recovery = None;
+    Ok(())
+}
@@ -1,5 +1,8 @@
+use serde::Serialize;
+use std::collections::VecDeque;
 use std::io;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 /// Main error type for the application
 #[derive(Debug, thiserror::Error)]
@@ -44,6 +47,9 @@ pub enum SimulationError {
         message: String,
     },
 
+    #[error(transparent)]
+    Validation(#[from] crate::simulations::shared::ValidationError),
+
     #[error("Simulation not running")]
     NotRunning,
 
@@ -259,6 +265,12 @@ impl From<String> for AppError {
     }
 }
 
+impl From<tauri::Error> for AppError {
+    fn from(e: tauri::Error) -> Self {
+        AppError::Window(e.to_string())
+    }
+}
+
 impl From<&str> for AppError {
     fn from(s: &str) -> Self {
         AppError::Unknown(s.to_string())
@@ -388,3 +400,259 @@ impl ColorSchemeError {
         }
     }
 }
+
+impl GpuError {
+    /// Sort an error caught in a wgpu error scope by the call that raised it
+    pub fn from_wgpu(error: wgpu::Error) -> Self {
+        match &error {
+            wgpu::Error::Validation { description, .. } => {
+                if description.contains("create_shader_module") {
+                    GpuError::ShaderCompilationFailed(description.clone())
+                } else if description.contains("_pipeline") {
+                    GpuError::PipelineCreationFailed(description.clone())
+                } else {
+                    GpuError::Wgpu(error)
+                }
+            }
+            _ => GpuError::Wgpu(error),
+        }
+    }
+}
+
+/// Errors the frontend can tell apart, each with what the user can do about
+/// it. Codes are stable, so messages can be reworded freely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    GpuLimitExceeded,
+    ShaderCompileFailed,
+    GpuFailure,
+    PresetParseError,
+    PresetNotFound,
+    ColorSchemeFailed,
+    InvalidInput,
+    NotRunning,
+    Unsupported,
+    WebcamUnavailable,
+    FileAccess,
+    Unknown,
+}
+
+impl ErrorCode {
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            ErrorCode::GpuLimitExceeded => Some(
+                "Lower the resolution, render scale, particle count or number of panes, \
+                 or close other apps using the GPU",
+            ),
+            ErrorCode::ShaderCompileFailed => Some(
+                "The graphics driver rejected one of this simulation's shaders. \
+                 Updating the driver usually fixes it; if not, please report it with your GPU model",
+            ),
+            ErrorCode::GpuFailure => {
+                Some("Restart Vizza, and update the graphics driver if it keeps happening")
+            }
+            ErrorCode::PresetParseError => {
+                Some("The preset file isn't valid TOML. Fix or delete it in the presets folder")
+            }
+            ErrorCode::PresetNotFound => {
+                Some("It may have been renamed or deleted outside Vizza, pick another preset")
+            }
+            ErrorCode::ColorSchemeFailed => {
+                Some("Check the file in the color schemes folder, or pick another color scheme")
+            }
+            ErrorCode::InvalidInput => Some("Check the value against what the control allows"),
+            ErrorCode::NotRunning => Some("Start a simulation first"),
+            ErrorCode::WebcamUnavailable => Some(
+                "Connect a camera, and check that Vizza may use it in the system privacy settings",
+            ),
+            ErrorCode::FileAccess => {
+                Some("Check that the folder exists and Vizza is allowed to write to it")
+            }
+            ErrorCode::Unsupported | ErrorCode::Unknown => None,
+        }
+    }
+}
+
+impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Simulation(e) => e.code(),
+            AppError::Gpu(e) => e.code(),
+            AppError::Command(CommandError::NotSupported(_)) => ErrorCode::Unsupported,
+            AppError::Command(CommandError::InvalidParameters(_))
+            | AppError::Command(CommandError::ValidationFailed(_)) => ErrorCode::InvalidInput,
+            AppError::Command(_) => ErrorCode::Unknown,
+            AppError::Preset(e) => e.code(),
+            AppError::ColorScheme(_) => ErrorCode::ColorSchemeFailed,
+            AppError::Io(_) => ErrorCode::FileAccess,
+            AppError::Serialization(_) => ErrorCode::InvalidInput,
+            AppError::Unknown(_) | AppError::Window(_) => ErrorCode::Unknown,
+        }
+    }
+}
+
+impl SimulationError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SimulationError::Gpu(_) => ErrorCode::GpuFailure,
+            SimulationError::BufferTooLarge { .. } => ErrorCode::GpuLimitExceeded,
+            SimulationError::InvalidSetting { .. }
+            | SimulationError::Validation(_)
+            | SimulationError::InvalidParameter(_)
+            | SimulationError::UnknownType(_)
+            | SimulationError::Serialization(_) => ErrorCode::InvalidInput,
+            SimulationError::NotRunning => ErrorCode::NotRunning,
+            SimulationError::UnsupportedOperation => ErrorCode::Unsupported,
+            SimulationError::LutError(_) => ErrorCode::ColorSchemeFailed,
+            _ => ErrorCode::Unknown,
+        }
+    }
+}
+
+impl GpuError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            GpuError::Wgpu(wgpu::Error::OutOfMemory { .. }) => ErrorCode::GpuLimitExceeded,
+            GpuError::ShaderCompilationFailed(_) | GpuError::PipelineCreationFailed(_) => {
+                ErrorCode::ShaderCompileFailed
+            }
+            _ => ErrorCode::GpuFailure,
+        }
+    }
+}
+
+impl PresetError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            PresetError::NotFound(_) => ErrorCode::PresetNotFound,
+            PresetError::DeserializationFailed(_)
+            | PresetError::FormatError(_)
+            | PresetError::CompatibilityError(_) => ErrorCode::PresetParseError,
+            PresetError::FileError { .. }
+            | PresetError::DirectoryError(_)
+            | PresetError::SavingFailed(_)
+            | PresetError::DeletionFailed(_) => ErrorCode::FileAccess,
+            PresetError::AlreadyExists(_) | PresetError::ValidationFailed(_) => {
+                ErrorCode::InvalidInput
+            }
+            _ => ErrorCode::Unknown,
+        }
+    }
+}
+
+/// How many diagnostics [`recent_errors`] keeps
+const RECENT_ERRORS: usize = 50;
+
+static RECENT: Mutex<VecDeque<Diagnostic>> = Mutex::new(VecDeque::new());
+
+/// The error commands return to the frontend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub code: ErrorCode,
+    pub message: String,
+    pub hint: Option<&'static str>,
+    pub at: String,
+}
+
+impl Diagnostic {
+    /// Diagnostics are only made to be returned from commands, so each one
+    /// is also kept for [`recent_errors`]
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        let diagnostic = Self {
+            code,
+            message: message.into(),
+            hint: code.hint(),
+            at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        };
+        let mut recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() == RECENT_ERRORS {
+            recent.pop_front();
+        }
+        recent.push_back(diagnostic.clone());
+        diagnostic
+    }
+
+    /// `error`, described as what was being done when it happened
+    pub fn context(context: impl std::fmt::Display, error: impl Into<AppError>) -> Self {
+        let error = error.into();
+        Self::new(error.code(), format!("{}: {}", context, error))
+    }
+
+    pub fn not_running(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotRunning, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Unsupported, message)
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl<E: Into<AppError>> From<E> for Diagnostic {
+    fn from(error: E) -> Self {
+        match error.into() {
+            // Plain messages don't need the "Unknown error" label
+            AppError::Unknown(message) => Self::new(ErrorCode::Unknown, message),
+            error => Self::new(error.code(), error.to_string()),
+        }
+    }
+}
+
+/// The errors commands returned lately, oldest first
+pub fn recent_errors() -> Vec<Diagnostic> {
+    let recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+    recent.iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_are_sorted_into_the_catalog() {
+        let too_large = AppError::from(SimulationError::BufferTooLarge {
+            requested: 1 << 32,
+            max_available: 1 << 28,
+        });
+        assert_eq!(too_large.code(), ErrorCode::GpuLimitExceeded);
+        assert!(ErrorCode::GpuLimitExceeded.hint().is_some());
+
+        let parse = AppError::from(PresetError::DeserializationFailed("line 3".to_string()));
+        let diagnostic = Diagnostic::context("Failed to import 'Coral'", parse);
+        assert_eq!(diagnostic.code, ErrorCode::PresetParseError);
+        assert!(diagnostic.message.starts_with("Failed to import 'Coral': "));
+        assert_eq!(diagnostic.hint, ErrorCode::PresetParseError.hint());
+    }
+
+    #[test]
+    fn plain_messages_keep_their_wording() {
+        let diagnostic = Diagnostic::from("Window not found".to_string());
+        assert_eq!(diagnostic.code, ErrorCode::Unknown);
+        assert_eq!(diagnostic.message, "Window not found");
+    }
+
+    #[test]
+    fn only_the_latest_errors_are_kept() {
+        for n in 0..RECENT_ERRORS + 5 {
+            Diagnostic::invalid_input(format!("recent error test {}", n));
+        }
+        let recent = recent_errors();
+        assert!(recent.len() <= RECENT_ERRORS);
+        let kept = |n: usize| {
+            let message = format!("recent error test {}", n);
+            recent.iter().any(|diagnostic| diagnostic.message == message)
+        };
+        assert!(kept(RECENT_ERRORS + 4));
+        assert!(!kept(0));
+    }
+}
@@ -15,15 +15,20 @@ mod simulation;
 mod simulations;
 
 use simulation::SimulationManager;
+use simulation::kiosk;
+use simulation::launch::{self, LaunchArgs};
 
 /// Unified GPU context managed by Tauri with surface
 pub struct GpuContext {
     pub device: Arc<Device>,
     pub queue: Arc<Queue>,
     pub instance: Instance,
+    pub adapter: wgpu::Adapter,
     pub adapter_info: wgpu::AdapterInfo,
     pub surface: Surface<'static>,
     pub surface_config: Arc<tokio::sync::Mutex<SurfaceConfiguration>>,
+    // Surface alpha modes, the first being the one the surface started with
+    pub alpha_modes: Vec<wgpu::CompositeAlphaMode>,
     pub main_menu: SimulationType,
 }
 
@@ -56,18 +61,37 @@ impl GpuContext {
         // Get adapter info
         let adapter_info = adapter.get_info();
         tracing::debug!("Using adapter: {:?}", adapter_info);
+        crate::simulations::shared::gpu_budget::configure(
+            &adapter_info,
+            app_settings.gpu_memory_budget_mb,
+        );
 
-        // Request device and queue with increased buffer size limit
+        // Request large buffers, but only as large as the adapter supports, and
+        // drop to downlevel limits on hardware that can't meet the defaults
+        let adapter_limits = adapter.limits();
+        let base_limits = if wgpu::Limits::default().check_limits(&adapter_limits) {
+            wgpu::Limits::default()
+        } else {
+            tracing::warn!("Adapter doesn't meet the default limits, using downlevel limits");
+            wgpu::Limits::downlevel_defaults()
+        };
         let limits = wgpu::Limits {
-            max_buffer_size: 2_147_483_647,                 // 2 gigabytes - 1 byte
-            max_storage_buffer_binding_size: 2_147_483_647, // 2 gigabyte binding size - 1 byte
-            ..Default::default()
+            // 2 gigabytes - 1 byte
+            max_buffer_size: 2_147_483_647.min(adapter_limits.max_buffer_size),
+            max_storage_buffer_binding_size: 2_147_483_647
+                .min(adapter_limits.max_storage_buffer_binding_size),
+            ..base_limits.using_resolution(adapter_limits.clone())
         };
+        let gpu_tier =
+            crate::simulations::shared::gpu_tier::GpuTier::detect(&adapter_info, &limits);
+        tracing::info!("GPU tier: {:?}", gpu_tier);
 
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor {
                 label: Some("Main GPU Device"),
-                required_features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
+                required_features: (wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
+                    | wgpu::Features::TIMESTAMP_QUERY)
+                    & adapter.features(),
                 required_limits: limits,
                 memory_hints: wgpu::MemoryHints::Performance,
                 ..Default::default()
@@ -82,12 +106,11 @@ impl GpuContext {
         let surface_caps = surface.get_capabilities(&adapter);
 
         // Choose appropriate surface format
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .find(|f| f.is_srgb())
-            .copied()
-            .unwrap_or(surface_caps.formats[0]);
+        let surface_format = crate::simulations::shared::color_space::select_surface_format(
+            &surface_caps.formats,
+            app_settings.color_space,
+        );
+        tracing::info!("Using surface format {:?}", surface_format);
 
         let surface_config = SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -127,9 +150,11 @@ impl GpuContext {
             device: device_arc,
             queue: queue_arc,
             instance,
+            adapter,
             adapter_info,
             surface,
             surface_config: Arc::new(tokio::sync::Mutex::new(surface_config)),
+            alpha_modes: surface_caps.alpha_modes,
             main_menu,
         })
     }
@@ -158,17 +183,33 @@ fn main() {
     // Initialize tracing
     tracing_subscriber::fmt::init();
 
+    let launch_args = match LaunchArgs::parse(std::env::args().skip(1)) {
+        Ok(launch_args) if launch_args.help => {
+            println!("{}", launch::USAGE);
+            return;
+        }
+        Ok(launch_args) => launch_args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, launch::USAGE);
+            std::process::exit(2);
+        }
+    };
+
     // Load app settings from file
     let app_settings =
         Arc::new(AppSettings::load_from_file().expect("Failed to load app settings"));
 
+    let mut sim_manager = SimulationManager::new(app_settings.clone());
+    launch_args.configure(&mut sim_manager);
+    let kiosk_enabled = launch_args.kiosk;
+
     let app_settings_clone = app_settings.clone();
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
-        .manage(Arc::new(tokio::sync::Mutex::new(SimulationManager::new(
-            app_settings,
-        ))))
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .manage(Arc::new(tokio::sync::Mutex::new(sim_manager)))
         .setup(move |app| {
             let window = app.get_webview_window("main").unwrap();
 
@@ -184,6 +225,11 @@ fn main() {
             })) {
                 tracing::warn!("Failed to set window size on startup: {}", e);
             }
+            if launch_args.fullscreen
+                && let Err(e) = window.set_fullscreen(true)
+            {
+                tracing::warn!("Failed to enter fullscreen on startup: {}", e);
+            }
 
             // Initialize GPU context
             let gpu_context = tauri::async_runtime::block_on(async {
@@ -194,156 +240,336 @@ fn main() {
 
             app.manage(Arc::new(tokio::sync::Mutex::new(gpu_context)));
 
+            // Lifecycle events need a handle to emit through
+            let manager = app.state::<Arc<tokio::sync::Mutex<SimulationManager>>>();
+            tauri::async_runtime::block_on(async {
+                let mut sim_manager = manager.lock().await;
+                sim_manager.events.attach(app.handle().clone());
+                sim_manager.crash_recovery = simulation::autosave::leftover();
+            });
+
+            simulation::deep_link::init(app);
+
+            tauri::async_runtime::spawn(simulation::watch_folder::watch(app.handle().clone()));
+            tauri::async_runtime::spawn(simulation::autosave::run(app.handle().clone()));
+
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            // Simulation commands
-            commands::start_simulation,
-            commands::start_slime_mold_simulation,
-            commands::start_gray_scott_simulation,
-            commands::start_particle_life_simulation,
-            commands::start_flow_simulation,
-            commands::start_pellets_simulation,
-            commands::pause_simulation,
-            commands::resume_simulation,
-            commands::step_simulation,
-            commands::destroy_simulation,
-            commands::get_simulation_status,
-            commands::scale_force_matrix,
-            commands::flip_force_matrix_horizontal,
-            commands::flip_force_matrix_vertical,
-            commands::rotate_force_matrix_clockwise,
-            commands::rotate_force_matrix_counterclockwise,
-            commands::shift_force_matrix_left,
-            commands::shift_force_matrix_right,
-            commands::shift_force_matrix_up,
-            commands::shift_force_matrix_down,
-            commands::zero_force_matrix,
-            commands::flip_force_matrix_sign,
-            commands::clear_trail_texture,
-            commands::kill_all_particles,
-            commands::draw_antialiased_shape,            // Flow
-            commands::update_post_processing_state,      // Flow
-            commands::get_post_processing_state,         // Flow
-            commands::set_flow_vector_field_type,        // Flow
-            commands::set_flow_image_fit_mode,           // Flow
-            commands::set_flow_image_mirror_horizontal,  // Flow
-            commands::set_flow_image_mirror_vertical,    // Flow
-            commands::set_flow_image_invert_tone,        // Flow
-            commands::load_flow_vector_field_image,      // Flow
-            commands::start_flow_webcam_capture,         // Flow webcam
-            commands::stop_flow_webcam_capture,          // Flow webcam
-            commands::get_available_flow_webcam_devices, // Flow webcam
-            commands::update_particle_life_post_processing_state, // Particle Life
-            commands::get_particle_life_post_processing_state, // Particle Life
-            commands::update_gray_scott_post_processing_state, // Gray Scott
-            commands::get_gray_scott_post_processing_state, // Gray Scott
-            commands::load_gray_scott_nutrient_image,    // Gray Scott
-            commands::start_gray_scott_webcam_capture,   // Gray Scott webcam
-            commands::stop_gray_scott_webcam_capture,    // Gray Scott webcam
-            commands::get_available_gray_scott_webcam_devices, // Gray Scott webcam
-            commands::update_slime_mold_post_processing_state, // Slime Mold
-            commands::get_slime_mold_post_processing_state, // Slime Mold
-            commands::update_pellets_post_processing_state, // Pellets
-            commands::get_pellets_post_processing_state, // Pellets
-            commands::update_pellets_trails_state,       // Pellets trails
-            commands::update_voronoi_ca_post_processing_state, // Voronoi CA
-            commands::get_voronoi_ca_post_processing_state, // Voronoi CA
-            commands::update_voronoi_ca_border_width,    // Voronoi CA
-            commands::start_moire_simulation,            // Moiré
-            commands::randomize_moire_settings,          // Moiré
-            commands::load_moire_image,                  // Moiré image
-            commands::start_moire_webcam_capture,        // Moiré webcam
-            commands::stop_moire_webcam_capture,         // Moiré webcam
-            commands::get_available_moire_webcam_devices, // Moiré webcam
-            commands::start_primordial_particles_simulation, // Primordial Particles
-            commands::update_primordial_particles_post_processing_state, // Primordial Particles
-            commands::get_primordial_particles_post_processing_state, // Primordial Particles
-            // Rendering commands
-            commands::render_frame,
-            commands::render_single_frame,
-            commands::handle_window_resize,
-            // Preset commands
-            commands::get_available_presets,
-            commands::get_presets_for_simulation_type,
-            commands::apply_preset,
-            commands::save_preset,
-            commands::delete_preset,
-            // Color scheme commands
-            commands::apply_color_scheme_by_name,
-            commands::apply_color_scheme,
-            commands::toggle_color_scheme_reversed,
-            commands::save_custom_color_scheme,
-            commands::update_gradient_preview,
-            commands::get_available_color_schemes,
-            commands::get_current_color_scheme_colors,
-            commands::get_species_colors,
-            // Camera commands
-            commands::pan_camera,
-            commands::zoom_camera,
-            commands::zoom_camera_to_cursor,
-            commands::reset_camera,
-            commands::get_camera_state,
-            commands::set_camera_smoothing,
-            commands::set_camera_sensitivity,
-            // Settings commands
-            commands::update_simulation_setting,
-            commands::update_simulation_state,
-            commands::get_current_settings,
-            commands::get_current_state,
-            commands::randomize_settings,
-            // Slime mold specific commands
-            commands::update_agent_count,
-            commands::get_current_agent_count,
-            commands::load_slime_mold_mask_image,
-            commands::set_slime_mold_mask_image_fit_mode,
-            commands::load_slime_mold_position_image,
-            commands::set_slime_mold_position_image_fit_mode,
-            commands::start_slime_mold_webcam_capture,
-            commands::stop_slime_mold_webcam_capture,
-            commands::update_slime_mold_background_mode,
-            commands::get_available_webcam_devices,
-            // Interaction commands
-            commands::handle_mouse_interaction,
-            commands::handle_mouse_interaction_screen,
-            commands::handle_mouse_release,
-            commands::update_cursor_position_screen,
-            commands::seed_random_noise,
-            commands::update_cursor_size,
-            commands::update_cursor_strength,
-            // Gradient commands
-            commands::set_gradient_display_mode,
-            // Utility commands
-            commands::check_gpu_context_ready,
-            commands::toggle_gui,
-            commands::get_gui_state,
-            commands::set_fps_limit,
-            commands::toggle_fullscreen,
-            commands::get_app_version,
-            // Flow image commands
-            commands::load_flow_vector_field_image,
-            commands::load_flow_vector_field_image_bytes,
-            commands::set_flow_vector_field_type,
-            commands::set_flow_image_fit_mode,
-            commands::set_flow_image_mirror_horizontal,
-            commands::set_flow_image_mirror_vertical,
-            commands::set_flow_image_invert_tone,
-            // Reset commands
-            commands::reset_trails,
-            commands::reset_agents,
-            commands::reset_simulation,
-            commands::reset_runtime_state,
-            commands::reset_graphics_resources,
-            // App settings commands
-            commands::get_app_settings,
-            commands::save_app_settings,
-            commands::reset_app_settings,
-            commands::get_settings_file_path,
-            commands::set_webview_zoom,
-            commands::apply_window_settings,
-            commands::apply_window_settings_on_startup,
-            commands::get_current_window_size,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                tauri::async_runtime::spawn(simulation::file_drop::handle_file_drop(
+                    window.app_handle().clone(),
+                    paths.clone(),
+                ));
+            }
+            tauri::WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+                ..
+            } => {
+                tauri::async_runtime::spawn(commands::handle_scale_factor_change(
+                    window.app_handle().clone(),
+                    *scale_factor,
+                    new_inner_size.width,
+                    new_inner_size.height,
+                ));
+            }
+            _ => {}
+        })
+        .invoke_handler(kiosk::gate(
+            kiosk_enabled,
+            tauri::generate_handler![
+                // Simulation commands
+                commands::start_simulation,
+                commands::start_slime_mold_simulation,
+                commands::start_gray_scott_simulation,
+                commands::start_particle_life_simulation,
+                commands::start_flow_simulation,
+                commands::start_pellets_simulation,
+                commands::pause_simulation,
+                commands::resume_simulation,
+                commands::step_simulation,
+                commands::fast_forward,
+                commands::destroy_simulation,
+                commands::get_simulation_status,
+                commands::scale_force_matrix,
+                commands::flip_force_matrix_horizontal,
+                commands::flip_force_matrix_vertical,
+                commands::rotate_force_matrix_clockwise,
+                commands::rotate_force_matrix_counterclockwise,
+                commands::shift_force_matrix_left,
+                commands::shift_force_matrix_right,
+                commands::shift_force_matrix_up,
+                commands::shift_force_matrix_down,
+                commands::zero_force_matrix,
+                commands::flip_force_matrix_sign,
+                commands::clear_trail_texture,
+                commands::kill_all_particles,
+                commands::draw_antialiased_shape,            // Flow
+                commands::update_post_processing_state,      // Flow
+                commands::get_post_processing_state,         // Flow
+                commands::set_flow_vector_field_type,        // Flow
+                commands::set_flow_image_fit_mode,           // Flow
+                commands::set_flow_image_mirror_horizontal,  // Flow
+                commands::set_flow_image_mirror_vertical,    // Flow
+                commands::set_flow_image_invert_tone,        // Flow
+                commands::load_flow_vector_field_image,      // Flow
+                commands::start_flow_webcam_capture,         // Flow webcam
+                commands::stop_flow_webcam_capture,          // Flow webcam
+                commands::get_available_flow_webcam_devices, // Flow webcam
+                commands::update_particle_life_post_processing_state, // Particle Life
+                commands::get_particle_life_post_processing_state, // Particle Life
+                commands::update_gray_scott_post_processing_state, // Gray Scott
+                commands::get_gray_scott_post_processing_state, // Gray Scott
+                commands::load_gray_scott_nutrient_image,    // Gray Scott
+                commands::seed_gray_scott_from_image,        // Gray Scott
+                commands::start_gray_scott_webcam_capture,   // Gray Scott webcam
+                commands::stop_gray_scott_webcam_capture,    // Gray Scott webcam
+                commands::get_available_gray_scott_webcam_devices, // Gray Scott webcam
+                commands::update_slime_mold_post_processing_state, // Slime Mold
+                commands::get_slime_mold_post_processing_state, // Slime Mold
+                commands::update_pellets_post_processing_state, // Pellets
+                commands::get_pellets_post_processing_state, // Pellets
+                commands::update_pellets_trails_state,       // Pellets trails
+                commands::update_voronoi_ca_post_processing_state, // Voronoi CA
+                commands::get_voronoi_ca_post_processing_state, // Voronoi CA
+                commands::update_voronoi_ca_border_width,    // Voronoi CA
+                commands::start_moire_simulation,            // Moiré
+                commands::randomize_moire_settings,          // Moiré
+                commands::load_moire_image,                  // Moiré image
+                commands::start_moire_webcam_capture,        // Moiré webcam
+                commands::stop_moire_webcam_capture,         // Moiré webcam
+                commands::get_available_moire_webcam_devices, // Moiré webcam
+                commands::start_primordial_particles_simulation, // Primordial Particles
+                commands::update_primordial_particles_post_processing_state, // Primordial Particles
+                commands::get_primordial_particles_post_processing_state, // Primordial Particles
+                commands::check_life_like_rule,              // Life-like rule editor
+                commands::load_lensing_image,                // Gravitational Lensing image
+                commands::load_stippling_image,              // Stippling image
+                commands::export_stippling_svg,              // Stippling SVG export
+                // Rendering commands
+                commands::render_frame,
+                commands::render_single_frame,
+                commands::handle_window_resize,
+                commands::set_canvas,
+                commands::get_canvas,
+                commands::set_supersampling,
+                commands::get_supersampling,
+                // Preview commands
+                commands::get_simulation_preview,
+                commands::subscribe_preview_stream,
+                commands::unsubscribe_preview_stream,
+                // Export commands
+                commands::export_frame_linear,
+                commands::start_recording,
+                commands::stop_recording,
+                commands::get_recording_status,
+                // Gallery commands
+                commands::capture_to_gallery,
+                commands::get_gallery,
+                commands::open_gallery_image,
+                commands::delete_gallery_image,
+                commands::restore_gallery_settings,
+                // Workspace commands
+                commands::save_workspace,
+                commands::load_workspace,
+                commands::list_workspaces,
+                commands::get_crash_recovery,
+                commands::restore_last_session_after_crash,
+                commands::discard_crash_recovery,
+                commands::delete_workspace,
+                // Scene commands
+                commands::save_scene,
+                commands::load_scene,
+                // Evolution commands
+                commands::evolve_init,
+                commands::evolve_select,
+                commands::evolve_next,
+                commands::evolve_apply,
+                commands::evolve_stop,
+                // Clipboard commands
+                commands::copy_frame_to_clipboard,
+                commands::paste_clipboard_image,
+                // Sharing commands
+                commands::get_share_link,
+                commands::export_share_code,
+                commands::import_share_code,
+                commands::take_pending_shared_configuration,
+                commands::apply_shared_configuration,
+                // Pane commands
+                commands::add_simulation_pane,
+                commands::remove_simulation_pane,
+                commands::focus_simulation_pane,
+                commands::set_simulation_pane_layout,
+                commands::set_pane_cameras_linked,
+                commands::set_pane_blend_mode,
+                commands::set_pane_opacity,
+                commands::get_simulation_panes,
+                // Rewind commands
+                commands::rewind_simulation,
+                commands::scrub_rewind,
+                commands::get_rewind_history,
+                commands::get_simulation_snapshot,
+                commands::load_simulation_snapshot,
+                // Master effects commands
+                commands::set_master_effects,
+                commands::get_master_effects,
+                // Keymap commands
+                commands::get_keybindings,
+                commands::set_keybinding,
+                commands::handle_key_press,
+                // Macro commands
+                commands::start_macro_recording,
+                commands::stop_macro_recording,
+                commands::get_macros,
+                commands::delete_macro,
+                commands::play_macro,
+                commands::stop_macro_playback,
+                // Replay commands
+                commands::start_replay_recording,
+                commands::stop_replay_recording,
+                commands::get_replays,
+                commands::delete_replay,
+                commands::play_replay,
+                commands::stop_replay_playback,
+                commands::get_replay_progress,
+                // Preset commands
+                commands::get_available_presets,
+                commands::get_presets_for_simulation_type,
+                commands::get_preset_warnings,
+                commands::find_similar_presets,
+                commands::apply_preset,
+                commands::save_preset,
+                commands::delete_preset,
+                // Catalog commands
+                commands::get_catalog,
+                // Note and bookmark commands
+                commands::get_preset_notes,
+                commands::set_preset_note,
+                commands::save_bookmark,
+                commands::get_bookmarks,
+                commands::apply_bookmark,
+                commands::update_bookmark_note,
+                commands::delete_bookmark,
+                // Color scheme commands
+                commands::apply_color_scheme_by_name,
+                commands::apply_color_scheme,
+                commands::toggle_color_scheme_reversed,
+                commands::save_custom_color_scheme,
+                commands::update_gradient_preview,
+                commands::set_color_script,
+                commands::get_color_script,
+                commands::get_available_color_schemes,
+                commands::get_current_color_scheme_colors,
+                commands::set_color_cycle,
+                commands::get_color_cycle,
+                commands::get_species_colors,
+                // Camera commands
+                commands::pan_camera,
+                commands::zoom_camera,
+                commands::zoom_camera_to_cursor,
+                commands::reset_camera,
+                commands::get_camera_state,
+                commands::set_camera_smoothing,
+                commands::set_camera_sensitivity,
+                // Settings commands
+                commands::update_simulation_setting,
+                commands::update_simulation_state,
+                commands::get_current_settings,
+                commands::get_current_state,
+                commands::randomize_settings,
+                commands::set_autopilot,
+                commands::get_autopilot,
+                commands::set_audio_reactive,
+                commands::get_audio_reactive,
+                commands::push_audio_samples,
+                commands::get_audio_bands,
+                commands::set_background_layer,
+                commands::get_background_layer,
+                // Slime mold specific commands
+                commands::update_agent_count,
+                commands::get_current_agent_count,
+                commands::load_slime_mold_mask_image,
+                commands::set_slime_mold_mask_image_fit_mode,
+                commands::load_slime_mold_position_image,
+                commands::set_slime_mold_position_image_fit_mode,
+                commands::start_slime_mold_webcam_capture,
+                commands::stop_slime_mold_webcam_capture,
+                commands::update_slime_mold_background_mode,
+                commands::export_slime_mold_trail_map,
+                commands::import_slime_mold_trail_map,
+                commands::get_available_webcam_devices,
+                // Interaction commands
+                commands::handle_mouse_interaction,
+                commands::handle_mouse_interaction_screen,
+                commands::handle_mouse_release,
+                commands::update_cursor_position_screen,
+                commands::seed_random_noise,
+                commands::update_cursor_size,
+                commands::update_cursor_strength,
+                commands::set_cursor_mode,
+                commands::get_global_force,
+                commands::set_global_force,
+                commands::get_environment_field,
+                commands::set_environment_field,
+                // Gradient commands
+                commands::set_gradient_display_mode,
+                // Utility commands
+                commands::check_gpu_context_ready,
+                commands::toggle_gui,
+                commands::get_gui_state,
+                commands::set_fps_limit,
+                commands::toggle_fullscreen,
+                commands::get_app_version,
+                commands::get_display_color_space,
+                commands::get_gpu_memory_usage,
+                commands::get_recent_errors,
+                commands::get_gpu_report,
+                commands::run_benchmark,
+                // Flow image commands
+                commands::load_flow_vector_field_image,
+                commands::load_flow_vector_field_image_bytes,
+                commands::set_flow_vector_field_type,
+                commands::set_flow_image_fit_mode,
+                commands::set_flow_image_mirror_horizontal,
+                commands::set_flow_image_mirror_vertical,
+                commands::set_flow_image_invert_tone,
+                // Flow emitter commands
+                commands::get_flow_emitters,
+                commands::add_flow_emitter,
+                commands::update_flow_emitter,
+                commands::remove_flow_emitter,
+                // Flow baked field commands
+                commands::bake_flow_field,
+                commands::list_baked_flow_fields,
+                commands::load_baked_flow_field,
+                commands::delete_baked_flow_field,
+                // Reset commands
+                commands::reset_trails,
+                commands::reset_agents,
+                commands::reset_simulation,
+                commands::reset_runtime_state,
+                commands::reset_graphics_resources,
+                commands::reseed,
+                // App settings commands
+                commands::get_app_settings,
+                commands::save_app_settings,
+                commands::reset_app_settings,
+                commands::set_default_preset,
+                commands::get_settings_file_path,
+                commands::set_webview_zoom,
+                commands::set_overlay_mode,
+                commands::get_overlay_mode,
+                commands::apply_window_settings,
+                commands::apply_window_settings_on_startup,
+                commands::get_current_window_size,
+            ],
+        ))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                simulation::autosave::clear();
+            }
+        });
 }
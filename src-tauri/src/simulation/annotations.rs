@@ -0,0 +1,190 @@
+//! User notes on presets, and bookmarks of annotated configurations.
+//!
+//! Both live in the simulation's folder in the settings directory, next to
+//! its `presets`: notes in `preset_notes.toml`, keyed by preset name, and one
+//! TOML file per bookmark under `bookmarks`. A bookmark is a
+//! [`SharedConfiguration`] (settings, camera and color scheme) with a note
+//! about why it was worth keeping.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::preset_manager::sanitize_filename;
+use super::settings_codec::SharedConfiguration;
+use crate::commands::get_settings_dir;
+use crate::error::{AppError, AppResult, SimulationError};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PresetNotes {
+    notes: BTreeMap<String, String>,
+}
+
+impl PresetNotes {
+    /// The notes for `simulation_type`, empty if none were written yet
+    pub fn load(simulation_type: &str) -> AppResult<Self> {
+        let path = preset_notes_path(simulation_type);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| {
+                AppError::Unknown(format!("Failed to parse {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, simulation_type: &str) -> AppResult<()> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize preset notes: {}", e)))?;
+        let path = preset_notes_path(simulation_type);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn notes(&self) -> &BTreeMap<String, String> {
+        &self.notes
+    }
+
+    /// Attach `note` to `preset`, or remove its note when `note` is blank
+    pub fn set(&mut self, preset: &str, note: &str) {
+        if note.trim().is_empty() {
+            self.notes.remove(preset);
+        } else {
+            self.notes.insert(preset.to_string(), note.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    #[serde(default)]
+    pub note: String,
+    /// RFC 3339, UTC
+    pub created_at: String,
+    pub configuration: SharedConfiguration,
+}
+
+impl Bookmark {
+    pub fn new(name: &str, note: &str, configuration: SharedConfiguration) -> AppResult<Self> {
+        if name.trim().is_empty() {
+            return Err(SimulationError::InvalidParameter(
+                "Bookmark name cannot be empty".to_string(),
+            )
+            .into());
+        }
+        Ok(Self {
+            name: name.to_string(),
+            note: note.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            configuration,
+        })
+    }
+
+    pub fn save(&self) -> AppResult<PathBuf> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize bookmark: {}", e)))?;
+        let simulation_type = &self.configuration.simulation_type;
+        std::fs::create_dir_all(bookmarks_dir(simulation_type))?;
+        let path = bookmark_path(simulation_type, &self.name);
+        std::fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    pub fn load(simulation_type: &str, name: &str) -> AppResult<Self> {
+        let path = bookmark_path(simulation_type, name);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            SimulationError::InvalidParameter(format!("Failed to read bookmark '{}': {}", name, e))
+        })?;
+        toml::from_str(&content)
+            .map_err(|e| AppError::Unknown(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+
+    pub fn delete(simulation_type: &str, name: &str) -> AppResult<()> {
+        std::fs::remove_file(bookmark_path(simulation_type, name))?;
+        Ok(())
+    }
+
+    /// Bookmarks of `simulation_type`, oldest first. Files that don't parse
+    /// are skipped.
+    pub fn list(simulation_type: &str) -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(bookmarks_dir(simulation_type)) else {
+            return vec![];
+        };
+        let mut bookmarks: Vec<Self> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("toml"))
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(&path).ok()?;
+                match toml::from_str::<Self>(&content) {
+                    Ok(bookmark) => Some(bookmark),
+                    Err(e) => {
+                        tracing::warn!("Skipping bookmark {}: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        bookmarks.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        bookmarks
+    }
+}
+
+fn preset_notes_path(simulation_type: &str) -> PathBuf {
+    get_settings_dir()
+        .join(simulation_type)
+        .join("preset_notes.toml")
+}
+
+fn bookmarks_dir(simulation_type: &str) -> PathBuf {
+    get_settings_dir().join(simulation_type).join("bookmarks")
+}
+
+fn bookmark_path(simulation_type: &str, name: &str) -> PathBuf {
+    bookmarks_dir(simulation_type).join(format!("{}.toml", sanitize_filename(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::settings_codec::CameraView;
+
+    #[test]
+    fn blank_notes_are_removed() {
+        let mut notes = PresetNotes::default();
+        notes.set("Default", "Stable loops after a minute");
+        assert_eq!(notes.notes().len(), 1);
+        notes.set("Default", "  ");
+        assert!(notes.notes().is_empty());
+    }
+
+    #[test]
+    fn bookmarks_round_trip_through_toml() {
+        let bookmark = Bookmark::new(
+            "Spirals",
+            "Turn rate just below the point where spirals break up",
+            SharedConfiguration {
+                simulation_type: "slime_mold".to_string(),
+                preset: Some("Default".to_string()),
+                settings: Some(serde_json::json!({
+                    "agent_turn_rate": 0.5,
+                    "agent_possible_starting_headings": [0.0, 360.0],
+                })),
+                camera: Some(CameraView {
+                    position: [0.25, -0.5],
+                    zoom: 2.0,
+                }),
+                color_scheme: None,
+            },
+        )
+        .unwrap();
+        let content = toml::to_string_pretty(&bookmark).unwrap();
+        assert_eq!(toml::from_str::<Bookmark>(&content).unwrap(), bookmark);
+        assert!(Bookmark::new(" ", "", bookmark.configuration.clone()).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+//! Settings driven by live audio.
+//!
+//! Each route ties one setting of the running simulation to a band of the
+//! [`AudioAnalyzer`]: the setting sits at the value it had when the route
+//! started while the band is silent, and moves away from it by `depth` times
+//! the band's 0-1 energy, in shares of the range the setting rules allow.
+//! A negative depth moves it down instead. Like the autopilot, only settings
+//! with a range can be routed, and turning audio off puts every routed
+//! setting back.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::audio::{AudioAnalyzer, AudioBand, AudioBands};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+
+/// Seconds between setting updates
+const STEP_INTERVAL: f32 = 1.0 / 30.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioRoute {
+    pub setting: String,
+    pub band: AudioBand,
+    /// Furthest the band moves the setting, as a share of its range, -1 to 1
+    pub depth: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioReactiveConfig {
+    pub enabled: bool,
+    /// Multiplies the normalized bands before they're cut to 0-1
+    pub gain: f32,
+    /// Seconds a band takes to fall back after a peak
+    pub release: f32,
+    pub routes: Vec<AudioRoute>,
+}
+
+impl Default for AudioReactiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            gain: 1.0,
+            release: 0.3,
+            routes: Vec::new(),
+        }
+    }
+}
+
+impl AudioReactiveConfig {
+    pub fn validate(&self, validator: &SettingValidator) -> SimulationResult<()> {
+        if !(self.gain > 0.0 && self.gain <= 10.0) {
+            return Err(SimulationError::InvalidParameter(
+                "Audio gain must be above 0 and at most 10".to_string(),
+            ));
+        }
+        if !(0.0..=5.0).contains(&self.release) {
+            return Err(SimulationError::InvalidParameter(
+                "Audio release must be from 0 to 5 seconds".to_string(),
+            ));
+        }
+        if let Some(route) = self
+            .routes
+            .iter()
+            .find(|route| !(-1.0..=1.0).contains(&route.depth))
+        {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Audio depth for '{}' must be from -1 to 1",
+                route.setting
+            )));
+        }
+        if let Some(route) = self
+            .routes
+            .iter()
+            .find(|route| !matches!(validator.rule(&route.setting), Some(Rule::Range { .. })))
+        {
+            return Err(SimulationError::InvalidParameter(format!(
+                "'{}' has no range for audio to move it in",
+                route.setting
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Modulation {
+    route: AudioRoute,
+    home: f64,
+    min: f64,
+    max: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct AudioReactive {
+    config: AudioReactiveConfig,
+    analyzer: AudioAnalyzer,
+    since_step: f32,
+    // Taken from the settings on the first step, and after rehoming
+    modulations: Option<Vec<Modulation>>,
+}
+
+impl AudioReactive {
+    pub fn config(&self) -> &AudioReactiveConfig {
+        &self.config
+    }
+
+    pub fn bands(&self) -> AudioBands {
+        self.analyzer.bands()
+    }
+
+    pub fn push_samples(&mut self, samples: &[f32], sample_rate: u32) {
+        self.analyzer.push_samples(samples, sample_rate);
+    }
+
+    /// The config must have been validated against the running simulation's
+    /// rules. Returns the settings to put back from the routes it replaces.
+    pub fn set_config(&mut self, config: AudioReactiveConfig) -> Vec<(String, f64)> {
+        let released = self.release();
+        self.config = config;
+        released
+    }
+
+    /// Start from the current settings on the next step, as after a preset
+    /// or another simulation was loaded
+    pub fn rehome(&mut self) {
+        self.modulations = None;
+        self.since_step = 0.0;
+    }
+
+    /// Move on by `delta_time` seconds. Returns the settings to update when
+    /// a step is due. `settings` is only called to find where the routes
+    /// start.
+    pub fn advance(
+        &mut self,
+        delta_time: f32,
+        settings: impl FnOnce() -> Value,
+        validator: &SettingValidator,
+    ) -> Vec<(String, f64)> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        self.since_step += delta_time;
+        if self.since_step < STEP_INTERVAL {
+            return Vec::new();
+        }
+        let step = self.since_step;
+        self.since_step = 0.0;
+
+        let bands = self
+            .analyzer
+            .analyze(step, self.config.gain, self.config.release);
+        let config = &self.config;
+        self.modulations
+            .get_or_insert_with(|| start_modulations(config, &settings(), validator))
+            .iter()
+            .map(|modulation| {
+                let energy = modulation.route.band.of(&bands) as f64;
+                let offset = modulation.route.depth * energy * (modulation.max - modulation.min);
+                let value = (modulation.home + offset).clamp(modulation.min, modulation.max);
+                (modulation.route.setting.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Stop modulating. Returns the settings to put back where they started.
+    fn release(&mut self) -> Vec<(String, f64)> {
+        let released = self
+            .modulations
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|modulation| (modulation.route.setting, modulation.home))
+            .collect();
+        self.rehome();
+        released
+    }
+}
+
+fn start_modulations(
+    config: &AudioReactiveConfig,
+    settings: &Value,
+    validator: &SettingValidator,
+) -> Vec<Modulation> {
+    config
+        .routes
+        .iter()
+        .filter_map(|route| {
+            let Some(Rule::Range { min, max }) = validator.rule(&route.setting) else {
+                return None;
+            };
+            let home = settings.get(&route.setting)?.as_f64()?.clamp(min, max);
+            Some(Modulation {
+                route: route.clone(),
+                home,
+                min,
+                max,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const RULES: SettingValidator = SettingValidator::new(
+        &[
+            ("feed_rate", Rule::Range { min: 0.0, max: 0.1 }),
+            ("count", Rule::Count { min: 1, max: 8 }),
+        ],
+        &[],
+    );
+
+    fn bass_tone() -> Vec<f32> {
+        (0..4096)
+            .map(|n| 0.5 * (std::f32::consts::TAU * 80.0 * n as f32 / 48000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn a_loud_band_moves_its_setting_and_silence_leaves_it_home() {
+        let mut audio = AudioReactive::default();
+        audio.set_config(AudioReactiveConfig {
+            enabled: true,
+            release: 0.0,
+            routes: vec![AudioRoute {
+                setting: "feed_rate".to_string(),
+                band: AudioBand::Bass,
+                depth: 0.5,
+            }],
+            ..Default::default()
+        });
+        let settings = || json!({ "feed_rate": 0.02, "count": 4 });
+
+        let quiet = audio.advance(STEP_INTERVAL, settings, &RULES);
+        assert_eq!(quiet, vec![("feed_rate".to_string(), 0.02)]);
+
+        audio.push_samples(&bass_tone(), 48000);
+        let loud = audio.advance(STEP_INTERVAL, settings, &RULES);
+        assert!((loud[0].1 - 0.07).abs() < 1e-3, "{:?}", loud);
+
+        let released = audio.set_config(AudioReactiveConfig::default());
+        assert_eq!(released, vec![("feed_rate".to_string(), 0.02)]);
+        assert!(audio.advance(STEP_INTERVAL, settings, &RULES).is_empty());
+    }
+
+    #[test]
+    fn routes_need_a_range_and_a_depth_within_one() {
+        let route = |setting: &str, depth| AudioReactiveConfig {
+            routes: vec![AudioRoute {
+                setting: setting.to_string(),
+                band: AudioBand::Treble,
+                depth,
+            }],
+            ..Default::default()
+        };
+        assert!(route("feed_rate", -1.0).validate(&RULES).is_ok());
+        assert!(route("feed_rate", 1.5).validate(&RULES).is_err());
+        assert!(route("count", 0.5).validate(&RULES).is_err());
+        assert!(route("speed", 0.5).validate(&RULES).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Autopilot: settings that drift on their own, for ambient displays.
+//!
+//! Each drifting setting wanders in a band around the value it had when the
+//! drift started, with a velocity that itself takes a small random step every
+//! tick, so the changes are smooth rather than jittery. Bands are a share of
+//! the range the simulation's setting rules allow, cut to fit inside it, and
+//! only settings with such a range drift. When the health watchdog reports a
+//! problem, every setting goes back to where it started.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+
+/// Seconds between setting updates
+const STEP_INTERVAL: f32 = 0.1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutopilotConfig {
+    pub enabled: bool,
+    /// Fastest a setting moves, in widths of its band per second
+    pub drift_speed: f64,
+    /// Width of the band each setting drifts in, as a share of its range
+    pub band: f64,
+    /// Settings to drift. Empty drifts every setting with a range.
+    pub parameters: Vec<String>,
+}
+
+impl Default for AutopilotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            drift_speed: 0.05,
+            band: 0.2,
+            parameters: Vec::new(),
+        }
+    }
+}
+
+impl AutopilotConfig {
+    pub fn validate(&self, validator: &SettingValidator) -> SimulationResult<()> {
+        if !(self.drift_speed > 0.0 && self.drift_speed <= 1.0) {
+            return Err(SimulationError::InvalidParameter(
+                "Autopilot drift speed must be above 0 and at most 1".to_string(),
+            ));
+        }
+        if !(self.band > 0.0 && self.band <= 1.0) {
+            return Err(SimulationError::InvalidParameter(
+                "Autopilot band must be above 0 and at most 1".to_string(),
+            ));
+        }
+        if let Some(parameter) = self
+            .parameters
+            .iter()
+            .find(|parameter| !matches!(validator.rule(parameter), Some(Rule::Range { .. })))
+        {
+            return Err(SimulationError::InvalidParameter(format!(
+                "'{}' has no range to drift in",
+                parameter
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Drift {
+    setting: String,
+    home: f64,
+    min: f64,
+    max: f64,
+    value: f64,
+    // In band widths per second
+    velocity: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct Autopilot {
+    config: AutopilotConfig,
+    since_step: f32,
+    // Taken from the settings on the first step, and after rehoming
+    drifts: Option<Vec<Drift>>,
+}
+
+impl Autopilot {
+    pub fn config(&self) -> &AutopilotConfig {
+        &self.config
+    }
+
+    /// The config must have been validated against the running simulation's
+    /// rules
+    pub fn set_config(&mut self, config: AutopilotConfig) {
+        self.config = config;
+        self.rehome();
+    }
+
+    /// Start drifting from the current settings on the next step, as after
+    /// a preset or another simulation was loaded
+    pub fn rehome(&mut self) {
+        self.drifts = None;
+        self.since_step = 0.0;
+    }
+
+    /// Move on by `delta_time` seconds. Returns the settings to update when
+    /// a step is due. `settings` is only called to find where the drift
+    /// starts.
+    pub fn advance(
+        &mut self,
+        delta_time: f32,
+        settings: impl FnOnce() -> Value,
+        validator: &SettingValidator,
+        rng: &mut impl Rng,
+    ) -> Vec<(String, f64)> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        self.since_step += delta_time;
+        if self.since_step < STEP_INTERVAL {
+            return Vec::new();
+        }
+        let step = self.since_step as f64;
+        self.since_step = 0.0;
+
+        let config = &self.config;
+        let drifts = self
+            .drifts
+            .get_or_insert_with(|| start_drifts(config, &settings(), validator));
+        drifts
+            .iter_mut()
+            .map(|drift| {
+                let width = drift.max - drift.min;
+                let speed = config.drift_speed;
+                drift.velocity = (drift.velocity + rng.random_range(-1.0..=1.0) * speed * step)
+                    .clamp(-speed, speed);
+                drift.value += drift.velocity * width * step;
+                // Bounce off the edges of the band
+                if drift.value > drift.max {
+                    drift.value = 2.0 * drift.max - drift.value;
+                    drift.velocity = -drift.velocity;
+                } else if drift.value < drift.min {
+                    drift.value = 2.0 * drift.min - drift.value;
+                    drift.velocity = -drift.velocity;
+                }
+                drift.value = drift.value.clamp(drift.min, drift.max);
+                (drift.setting.clone(), drift.value)
+            })
+            .collect()
+    }
+
+    /// Put every drifting setting back where it started. Returns the
+    /// settings to update.
+    pub fn retreat(&mut self) -> Vec<(String, f64)> {
+        let Some(drifts) = &mut self.drifts else {
+            return Vec::new();
+        };
+        drifts
+            .iter_mut()
+            .map(|drift| {
+                drift.value = drift.home;
+                drift.velocity = 0.0;
+                (drift.setting.clone(), drift.home)
+            })
+            .collect()
+    }
+}
+
+fn start_drifts(
+    config: &AutopilotConfig,
+    settings: &Value,
+    validator: &SettingValidator,
+) -> Vec<Drift> {
+    let Value::Object(fields) = settings else {
+        return Vec::new();
+    };
+    fields
+        .iter()
+        .filter(|(setting, _)| config.parameters.is_empty() || config.parameters.contains(setting))
+        .filter_map(|(setting, value)| {
+            let Some(Rule::Range { min, max }) = validator.rule(setting) else {
+                return None;
+            };
+            let home = value.as_f64()?.clamp(min, max);
+            let half_band = (max - min) * config.band / 2.0;
+            Some(Drift {
+                setting: setting.clone(),
+                home,
+                min: (home - half_band).max(min),
+                max: (home + half_band).min(max),
+                value: home,
+                velocity: 0.0,
+            })
+        })
+        .filter(|drift| drift.max > drift.min)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use serde_json::json;
+
+    const RULES: SettingValidator = SettingValidator::new(
+        &[
+            ("feed_rate", Rule::Range { min: 0.0, max: 0.1 }),
+            ("kill_rate", Rule::Range { min: 0.0, max: 0.1 }),
+            ("count", Rule::Count { min: 1, max: 8 }),
+        ],
+        &[],
+    );
+
+    fn settings() -> Value {
+        json!({ "feed_rate": 0.1, "kill_rate": 0.05, "count": 4, "speed": 1.0 })
+    }
+
+    #[test]
+    fn drifts_inside_the_band() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut autopilot = Autopilot::default();
+        autopilot.set_config(AutopilotConfig {
+            enabled: true,
+            drift_speed: 1.0,
+            ..Default::default()
+        });
+        let mut moved = false;
+        for _ in 0..1000 {
+            let updates = autopilot.advance(STEP_INTERVAL, settings, &RULES, &mut rng);
+            // Counts and settings without a range stay put
+            assert_eq!(updates.len(), 2);
+            for (setting, value) in updates {
+                match setting.as_str() {
+                    // Cut to the top of the range
+                    "feed_rate" => assert!((0.0899..=0.1).contains(&value)),
+                    "kill_rate" => {
+                        assert!((0.0399..=0.0601).contains(&value));
+                        moved |= value != 0.05;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+        assert!(moved);
+
+        let home = autopilot.retreat();
+        assert!(home.contains(&("kill_rate".to_string(), 0.05)));
+    }
+
+    #[test]
+    fn only_listed_settings_with_a_range_drift() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let config = AutopilotConfig {
+            enabled: true,
+            parameters: vec!["kill_rate".to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate(&RULES).is_ok());
+        let mut autopilot = Autopilot::default();
+        autopilot.set_config(config);
+        let updates = autopilot.advance(STEP_INTERVAL, settings, &RULES, &mut rng);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0, "kill_rate");
+
+        for parameter in ["count", "speed"] {
+            let config = AutopilotConfig {
+                parameters: vec![parameter.to_string()],
+                ..Default::default()
+            };
+            assert!(config.validate(&RULES).is_err());
+        }
+    }
+}
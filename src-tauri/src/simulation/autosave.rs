@@ -0,0 +1,139 @@
+//! Crash recovery for the running simulation's look.
+//!
+//! While a simulation runs, everything a [`Workspace`] holds is written to
+//! `recovery.json` in the settings directory every [`AUTOSAVE_INTERVAL`],
+//! whenever it changed since the last write. A clean exit deletes the file,
+//! so one found at startup was left by a session that crashed, hung the GPU
+//! or was killed, and the frontend can offer to bring it back.
+//!
+//! Each write goes to a temporary file renamed over the old one, so a crash in
+//! the middle of writing leaves the previous autosave whole.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::SimulationManager;
+use super::workspace::Workspace;
+use crate::commands::get_settings_dir;
+use crate::error::{AppError, AppResult};
+
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Workspace name the autosave is captured under
+const SESSION_NAME: &str = "Last session";
+
+fn recovery_path() -> PathBuf {
+    get_settings_dir().join("recovery.json")
+}
+
+/// The autosave the last session left behind, if it didn't exit cleanly.
+/// The file stays until this session exits cleanly, in case it
+/// crashes too before saving anything of its own.
+pub fn leftover() -> Option<Workspace> {
+    let path = recovery_path();
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(workspace) => {
+            tracing::info!("Found a session to recover in {}", path.display());
+            Some(workspace)
+        }
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn save(workspace: &Workspace) -> AppResult<()> {
+    let path = recovery_path();
+    std::fs::create_dir_all(get_settings_dir())?;
+    let content = serde_json::to_vec_pretty(workspace)?;
+    let partial = path.with_extension("json.partial");
+    std::fs::write(&partial, content)?;
+    std::fs::rename(&partial, &path)?;
+    Ok(())
+}
+
+/// Called on a clean exit, so the next start doesn't offer a recovery
+pub fn clear() {
+    let path = recovery_path();
+    if let Err(e) = std::fs::remove_file(&path)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove {}: {}", path.display(), e);
+    }
+}
+
+/// Whether two captures differ in anything but when they were taken
+fn changed(last: Option<&Workspace>, current: &Workspace) -> bool {
+    last.is_none_or(|last| {
+        let last = Workspace {
+            saved_at: current.saved_at.clone(),
+            ..last.clone()
+        };
+        last != *current
+    })
+}
+
+/// Autosave for as long as the app runs
+pub async fn run(app: AppHandle) {
+    let manager = app
+        .state::<Arc<tokio::sync::Mutex<SimulationManager>>>()
+        .inner()
+        .clone();
+    let mut last: Option<Workspace> = None;
+    let mut interval = tokio::time::interval(AUTOSAVE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+
+        let sim_manager = manager.lock().await;
+        if sim_manager.current_simulation.is_none() {
+            continue;
+        }
+        let saved = Workspace::capture(&sim_manager, SESSION_NAME).and_then(|workspace| {
+            if changed(last.as_ref(), &workspace) {
+                save(&workspace)?;
+                last = Some(workspace);
+            }
+            Ok::<_, AppError>(())
+        });
+        if let Err(e) = saved {
+            tracing::warn!("Autosave failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::settings_codec::SharedConfiguration;
+
+    fn capture(saved_at: &str, feed_rate: f64) -> Workspace {
+        Workspace {
+            name: SESSION_NAME.to_string(),
+            saved_at: saved_at.to_string(),
+            configuration: SharedConfiguration {
+                simulation_type: "gray_scott".to_string(),
+                preset: None,
+                settings: Some(serde_json::json!({ "feed_rate": feed_rate })),
+                camera: None,
+                color_scheme: None,
+            },
+            master_effects: Default::default(),
+            color_cycle: Default::default(),
+            canvas: None,
+        }
+    }
+
+    #[test]
+    fn only_changes_are_saved_again() {
+        let first = capture("2025-03-09T14:05:07Z", 0.055);
+        assert!(changed(None, &first));
+        let later = |feed_rate| capture("2025-03-09T14:05:37Z", feed_rate);
+        assert!(!changed(Some(&first), &later(0.055)));
+        assert!(changed(Some(&first), &later(0.06)));
+    }
+}
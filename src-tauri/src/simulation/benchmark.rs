@@ -0,0 +1,576 @@
+//! A standard benchmark for comparing hardware and settings.
+//!
+//! Each scene is one simulation started offscreen at a fixed resolution from
+//! its default settings and a fixed seed, warmed up, then run for a fixed
+//! number of frames at a fixed time step, so every machine renders the same
+//! work. Frame times come from GPU timestamps written either side of each
+//! frame when the device has them, and from the wall clock with the GPU
+//! drained after every frame when it doesn't.
+//!
+//! Simulations rendering through a render graph also get a GPU time for
+//! each of its passes (compute, trails, blit, infinite render and so on),
+//! for finding which one a regression or a slower GPU is in.
+//!
+//! A scene scores 1000 when its frames average [`REFERENCE_FRAME_MS`], and
+//! proportionally more or less otherwise. The overall score is the geometric
+//! mean of the scene scores, so no one simulation dominates it. Scores are
+//! only comparable between standard reports of the same
+//! [`BENCHMARK_VERSION`]. [`BenchmarkOptions`] can pick other simulations,
+//! frame counts, resolutions and particle counts, for profiling one
+//! simulation rather than scoring the machine.
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use wgpu::{Device, Queue};
+
+use super::previews::create_preview_simulation;
+use crate::commands::AppSettings;
+use crate::error::{AppResult, SimulationError, SimulationResult};
+use crate::simulations::shared::gpu_tier::GpuTier;
+use crate::simulations::shared::gpu_timer::pair_durations;
+use crate::simulations::shared::render_graph::NodeKind;
+use crate::simulations::shared::{ColorSchemeManager, FrameCapture, GpuTimer};
+use crate::simulations::traits::{Simulation, SimulationType};
+
+/// Raise whenever a change to the scenes or their settings would move scores
+pub const BENCHMARK_VERSION: u32 = 2;
+
+pub const BENCHMARK_WIDTH: u32 = 1920;
+pub const BENCHMARK_HEIGHT: u32 = 1080;
+
+/// Simulations run by the benchmark, in order
+pub const BENCHMARK_SCENES: &[&str] = &[
+    "slime_mold",
+    "gray_scott",
+    "particle_life",
+    "flow",
+    "pellets",
+];
+
+/// Frames simulated before timing starts, so start-up work isn't counted
+const WARM_UP_FRAMES: u32 = 60;
+const MEASURED_FRAMES: u32 = 300;
+const MAX_MEASURED_FRAMES: u32 = 100_000;
+const FRAME_DELTA_TIME: f32 = 1.0 / 60.0;
+const SEED: u32 = 1;
+
+/// Simulations without a seed whose start has no randomness in it. Any
+/// other scene has to grow from [`SEED`].
+const STARTS_THE_SAME: &[&str] = &["gray_scott"];
+
+/// Fixed rather than taken from the GPU tier, so every machine moves the
+/// same number of agents
+const SLIME_MOLD_AGENT_COUNT: usize = 1_000_000;
+
+/// Frame time a scene scores 1000 at, 60 fps
+pub const REFERENCE_FRAME_MS: f64 = 1000.0 / 60.0;
+
+/// What to run instead of the standard benchmark. Anything left out keeps
+/// its standard value.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct BenchmarkOptions {
+    pub simulations: Option<Vec<String>>,
+    pub frames: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Particles, agents or boids, for the simulations that have a count
+    pub particle_count: Option<u32>,
+}
+
+impl BenchmarkOptions {
+    /// Whether this is the standard benchmark, whose scores compare
+    pub fn is_standard(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The setting holding how many things `simulation_type` moves, besides
+/// Slime Mold whose agent count is fixed when it is created
+fn particle_count_setting(simulation_type: &str) -> Option<&'static str> {
+    match simulation_type {
+        "particle_life" | "pellets" | "primordial_particles" => Some("particle_count"),
+        "crowd" | "slime_mold_3d" => Some("agent_count"),
+        "boids" => Some("boid_count"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameTiming {
+    GpuTimestamps,
+    CpuWallClock,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FrameStats {
+    pub average_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub worst_ms: f64,
+}
+
+impl FrameStats {
+    /// Summarize frame times in milliseconds. Percentiles are by nearest
+    /// rank, so each one is a frame time that was actually measured.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self {
+                average_ms: 0.0,
+                p50_ms: 0.0,
+                p95_ms: 0.0,
+                p99_ms: 0.0,
+                worst_ms: 0.0,
+            };
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+            sorted[rank.clamp(1, sorted.len()) - 1]
+        };
+        Self {
+            average_ms: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p50_ms: percentile(50.0),
+            p95_ms: percentile(95.0),
+            p99_ms: percentile(99.0),
+            worst_ms: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// GPU time of one render graph pass over the measured frames it ran in
+#[derive(Debug, Clone, Serialize)]
+pub struct PassResult {
+    pub name: &'static str,
+    pub kind: NodeKind,
+    pub timings: FrameStats,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SceneResult {
+    pub simulation_type: String,
+    /// The count the scene ran with, when one was asked for and it has one
+    pub particle_count: Option<u32>,
+    pub frames: FrameStats,
+    /// Empty without a render graph or GPU timestamps
+    pub passes: Vec<PassResult>,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkReport {
+    pub version: u32,
+    /// Run with the standard options, so its scores compare with others
+    pub standard: bool,
+    pub adapter: String,
+    pub backend: String,
+    pub tier: GpuTier,
+    pub width: u32,
+    pub height: u32,
+    pub measured_frames: u32,
+    pub timing: FrameTiming,
+    pub scenes: Vec<SceneResult>,
+    pub score: u32,
+    /// RFC 3339
+    pub finished_at: String,
+    /// SVG data URL summing up the report, for sharing
+    pub infographic: String,
+}
+
+fn scene_score(average_ms: f64) -> f64 {
+    if average_ms > 0.0 {
+        1000.0 * REFERENCE_FRAME_MS / average_ms
+    } else {
+        0.0
+    }
+}
+
+/// Geometric mean of the scene scores
+fn overall_score(scenes: &[f64]) -> f64 {
+    if scenes.is_empty() || scenes.iter().any(|score| *score <= 0.0) {
+        return 0.0;
+    }
+    let log_sum: f64 = scenes.iter().map(|score| score.ln()).sum();
+    (log_sum / scenes.len() as f64).exp()
+}
+
+async fn create_scene(
+    simulation_type: &str,
+    particle_count: Option<u32>,
+    device: &Arc<Device>,
+    queue: &Arc<Queue>,
+    surface_config: &wgpu::SurfaceConfiguration,
+    adapter_info: &wgpu::AdapterInfo,
+    color_scheme_manager: &ColorSchemeManager,
+    app_settings: &AppSettings,
+) -> AppResult<SimulationType> {
+    let mut simulation = if simulation_type == "slime_mold" {
+        SimulationType::SlimeMold(Box::new(
+            crate::simulations::slime_mold::SlimeMoldModel::new(
+                device,
+                queue,
+                surface_config,
+                adapter_info,
+                particle_count.map_or(SLIME_MOLD_AGENT_COUNT, |count| count as usize),
+                crate::simulations::slime_mold::settings::Settings::default(),
+                app_settings,
+                color_scheme_manager,
+            )?,
+        ))
+    } else {
+        create_preview_simulation(
+            simulation_type,
+            device,
+            queue,
+            surface_config,
+            adapter_info,
+            color_scheme_manager,
+            app_settings,
+        )
+        .await?
+    };
+    if let Some(count) = particle_count
+        && let Some(setting) = particle_count_setting(simulation_type)
+    {
+        simulation.update_setting(setting, serde_json::Value::from(count), device, queue)?;
+    }
+    // Scores only compare when every run of a scene does the same work
+    match simulation.seed_setting() {
+        Some(setting) => {
+            simulation.update_setting(setting, serde_json::Value::from(SEED), device, queue)?;
+            simulation.reset_runtime_state(device, queue)?;
+        }
+        None if !STARTS_THE_SAME.contains(&simulation_type) => {
+            return Err(SimulationError::InvalidParameter(format!(
+                "{} starts differently every time, so it can't be benchmarked",
+                simulation_type
+            ))
+            .into());
+        }
+        None => {}
+    }
+    Ok(simulation)
+}
+
+/// Frame times in milliseconds of the measured frames, and the times of
+/// the render graph passes within them when the GPU writes timestamps
+fn measure(
+    simulation: &mut SimulationType,
+    capture: &FrameCapture,
+    frames: u32,
+    device: &Arc<Device>,
+    queue: &Arc<Queue>,
+) -> AppResult<(Vec<f64>, Vec<PassResult>)> {
+    for _ in 0..WARM_UP_FRAMES {
+        simulation.render_frame(device, queue, &capture.view, FRAME_DELTA_TIME)?;
+    }
+    device
+        .poll(wgpu::wgt::PollType::Wait)
+        .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+
+    let Some(mut frame_timer) =
+        GpuTimer::new(device, queue, frames * 2, "Benchmark Frame Timestamps")
+    else {
+        let mut frame_times = Vec::with_capacity(frames as usize);
+        for _ in 0..frames {
+            let started = Instant::now();
+            simulation.render_frame(device, queue, &capture.view, FRAME_DELTA_TIME)?;
+            device
+                .poll(wgpu::wgt::PollType::Wait)
+                .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+            frame_times.push(started.elapsed().as_secs_f64() * 1000.0);
+        }
+        return Ok((frame_times, Vec::new()));
+    };
+
+    if let Some(graph) = simulation.render_graph_mut()
+        && let Some(pass_timer) = GpuTimer::new(
+            device,
+            queue,
+            wgpu::QUERY_SET_MAX_QUERIES,
+            "Benchmark Pass Timestamps",
+        )
+    {
+        graph.start_profiling(pass_timer);
+    }
+    for _ in 0..frames {
+        // Timestamps are read back between frames, so the wait isn't timed
+        if frame_timer.remaining() < 2 {
+            frame_timer.read_back(device, queue)?;
+        }
+        if let Some(graph) = simulation.render_graph_mut() {
+            graph.make_room_for_frame(device, queue)?;
+        }
+        frame_timer.mark(device, queue)?;
+        simulation.render_frame(device, queue, &capture.view, FRAME_DELTA_TIME)?;
+        frame_timer.mark(device, queue)?;
+    }
+
+    let passes = match simulation.render_graph_mut() {
+        Some(graph) => graph
+            .finish_profiling(device, queue)?
+            .into_iter()
+            .map(|pass| PassResult {
+                name: pass.name,
+                kind: pass.kind,
+                timings: FrameStats::from_samples(&pass.durations_ms),
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    let frame_times = pair_durations(&frame_timer.finish(device, queue)?);
+    Ok((frame_times, passes))
+}
+
+/// Run every scene in turn. Nothing else should be using the GPU meanwhile,
+/// or its work is counted against the scenes.
+pub async fn run(
+    device: &Arc<Device>,
+    queue: &Arc<Queue>,
+    surface_format: wgpu::TextureFormat,
+    adapter_info: &wgpu::AdapterInfo,
+    color_scheme_manager: &ColorSchemeManager,
+    app_settings: &AppSettings,
+    options: &BenchmarkOptions,
+) -> AppResult<BenchmarkReport> {
+    let simulation_types: Vec<String> = match &options.simulations {
+        Some(simulations) => simulations.clone(),
+        None => BENCHMARK_SCENES.iter().map(|s| s.to_string()).collect(),
+    };
+    let frames = options.frames.unwrap_or(MEASURED_FRAMES);
+    let width = options.width.unwrap_or(BENCHMARK_WIDTH);
+    let height = options.height.unwrap_or(BENCHMARK_HEIGHT);
+    let max_dimension = device.limits().max_texture_dimension_2d;
+    if simulation_types.is_empty() {
+        return Err(
+            SimulationError::InvalidParameter("No simulations to benchmark".to_string()).into(),
+        );
+    }
+    if !(1..=MAX_MEASURED_FRAMES).contains(&frames) {
+        return Err(SimulationError::InvalidParameter(format!(
+            "Benchmark frames must be between 1 and {}",
+            MAX_MEASURED_FRAMES
+        ))
+        .into());
+    }
+    if !(1..=max_dimension).contains(&width) || !(1..=max_dimension).contains(&height) {
+        return Err(SimulationError::InvalidParameter(format!(
+            "Benchmark resolution must be between 1x1 and {}x{}",
+            max_dimension, max_dimension
+        ))
+        .into());
+    }
+
+    let capture = FrameCapture::new(device, width, height, surface_format, "Benchmark")?;
+    let surface_config = capture.surface_config();
+    let timing = if device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+        FrameTiming::GpuTimestamps
+    } else {
+        FrameTiming::CpuWallClock
+    };
+
+    let mut scenes = Vec::with_capacity(simulation_types.len());
+    let mut scores = Vec::with_capacity(simulation_types.len());
+    for simulation_type in &simulation_types {
+        let particle_count = options.particle_count.filter(|_| {
+            simulation_type == "slime_mold" || particle_count_setting(simulation_type).is_some()
+        });
+        let mut simulation = create_scene(
+            simulation_type,
+            particle_count,
+            device,
+            queue,
+            &surface_config,
+            adapter_info,
+            color_scheme_manager,
+            app_settings,
+        )
+        .await?;
+        let (frame_times, passes) = measure(&mut simulation, &capture, frames, device, queue)?;
+        drop(simulation);
+
+        let frame_stats = FrameStats::from_samples(&frame_times);
+        let score = scene_score(frame_stats.average_ms);
+        tracing::info!(
+            "Benchmarked {}: {:.2} ms average, {:.2} ms p95, score {:.0}",
+            simulation_type,
+            frame_stats.average_ms,
+            frame_stats.p95_ms,
+            score
+        );
+        scores.push(score);
+        scenes.push(SceneResult {
+            simulation_type: simulation_type.to_string(),
+            particle_count,
+            frames: frame_stats,
+            passes,
+            score: score.round() as u32,
+        });
+    }
+
+    let mut report = BenchmarkReport {
+        version: BENCHMARK_VERSION,
+        standard: options.is_standard(),
+        adapter: adapter_info.name.clone(),
+        backend: adapter_info.backend.to_string(),
+        tier: GpuTier::detect(adapter_info, &device.limits()),
+        width,
+        height,
+        measured_frames: frames,
+        timing,
+        scenes,
+        score: overall_score(&scores).round() as u32,
+        finished_at: chrono::Local::now().to_rfc3339(),
+        infographic: String::new(),
+    };
+    report.infographic = format!(
+        "data:image/svg+xml;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(infographic(&report))
+    );
+    Ok(report)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A card with the overall score and a bar for each scene
+fn infographic(report: &BenchmarkReport) -> String {
+    const WIDTH: u32 = 640;
+    const ROW_HEIGHT: u32 = 44;
+    const BAR_LEFT: u32 = 170;
+    const BAR_WIDTH: f64 = 300.0;
+    let height = 170 + ROW_HEIGHT * report.scenes.len() as u32;
+    // Bars share a scale, with the best scene at full width
+    let best = report
+        .scenes
+        .iter()
+        .map(|scene| scene.score)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" \
+         font-family=\"sans-serif\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#14141c\"/>\n\
+         <text x=\"24\" y=\"44\" font-size=\"22\" fill=\"#ffffff\">Vizza benchmark v{}</text>\n\
+         <text x=\"24\" y=\"70\" font-size=\"14\" fill=\"#a0a0b0\">{} ({}), {}x{}</text>\n\
+         <text x=\"{}\" y=\"64\" font-size=\"44\" fill=\"#7fd4ff\" text-anchor=\"end\">{}</text>\n",
+        report.version,
+        escape_xml(&report.adapter),
+        escape_xml(&report.backend),
+        report.width,
+        report.height,
+        WIDTH - 24,
+        report.score,
+    );
+    for (row, scene) in report.scenes.iter().enumerate() {
+        let y = 110 + ROW_HEIGHT * row as u32;
+        let bar = (scene.score as f64 / best * BAR_WIDTH).max(1.0);
+        svg.push_str(&format!(
+            "<text x=\"24\" y=\"{}\" font-size=\"15\" fill=\"#ffffff\">{}</text>\n\
+             <rect x=\"{BAR_LEFT}\" y=\"{}\" width=\"{:.1}\" height=\"18\" rx=\"3\" fill=\"#3d8bfd\"/>\n\
+             <text x=\"{}\" y=\"{}\" font-size=\"13\" fill=\"#a0a0b0\">{} · {:.1} ms avg · {:.1} ms p95</text>\n",
+            y + 14,
+            escape_xml(&scene.simulation_type),
+            y,
+            bar,
+            BAR_LEFT,
+            y + 34,
+            scene.score,
+            scene.frames.average_ms,
+            scene.frames.p95_ms,
+        ));
+    }
+    let timing = match report.timing {
+        FrameTiming::GpuTimestamps => "GPU timestamps",
+        FrameTiming::CpuWallClock => "CPU wall clock",
+    };
+    svg.push_str(&format!(
+        "<text x=\"24\" y=\"{}\" font-size=\"12\" fill=\"#70707c\">{} frames per scene, timed by {}, {}</text>\n</svg>\n",
+        height - 20,
+        report.measured_frames,
+        timing,
+        escape_xml(&report.finished_at),
+    ));
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_are_measured_frame_times() {
+        let samples: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        let stats = FrameStats::from_samples(&samples);
+        assert_eq!(stats.average_ms, 50.5);
+        assert_eq!(stats.p50_ms, 50.0);
+        assert_eq!(stats.p95_ms, 95.0);
+        assert_eq!(stats.p99_ms, 99.0);
+        assert_eq!(stats.worst_ms, 100.0);
+
+        let one = FrameStats::from_samples(&[4.0]);
+        assert_eq!((one.p50_ms, one.p99_ms), (4.0, 4.0));
+        assert_eq!(FrameStats::from_samples(&[]).worst_ms, 0.0);
+    }
+
+    #[test]
+    fn scores_are_relative_to_sixty_fps() {
+        assert!((scene_score(REFERENCE_FRAME_MS) - 1000.0).abs() < 1e-9);
+        assert!((scene_score(REFERENCE_FRAME_MS * 2.0) - 500.0).abs() < 1e-9);
+        // A geometric mean, so halving one scene costs as much as doubling
+        // another gains
+        assert!((overall_score(&[500.0, 2000.0]) - 1000.0).abs() < 1e-9);
+        assert_eq!(overall_score(&[1000.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn options_left_out_keep_the_standard_run() {
+        let options: BenchmarkOptions = serde_json::from_str("{}").unwrap();
+        assert!(options.is_standard());
+        let options: BenchmarkOptions =
+            serde_json::from_str(r#"{"simulations": ["pellets"], "particle_count": 20000}"#)
+                .unwrap();
+        assert!(!options.is_standard());
+        assert_eq!(particle_count_setting("pellets"), Some("particle_count"));
+        assert_eq!(particle_count_setting("gray_scott"), None);
+    }
+
+    #[test]
+    fn the_infographic_escapes_the_adapter_name() {
+        let report = BenchmarkReport {
+            version: BENCHMARK_VERSION,
+            standard: true,
+            adapter: "Radeon <R9> & Co".to_string(),
+            backend: "vulkan".to_string(),
+            tier: GpuTier::Full,
+            width: BENCHMARK_WIDTH,
+            height: BENCHMARK_HEIGHT,
+            measured_frames: MEASURED_FRAMES,
+            timing: FrameTiming::GpuTimestamps,
+            scenes: vec![SceneResult {
+                simulation_type: "gray_scott".to_string(),
+                particle_count: None,
+                frames: FrameStats::from_samples(&[8.0]),
+                passes: Vec::new(),
+                score: 2083,
+            }],
+            score: 2083,
+            finished_at: "2025-03-09T14:05:07+00:00".to_string(),
+            infographic: String::new(),
+        };
+        let svg = infographic(&report);
+        assert!(svg.contains("Radeon &lt;R9&gt; &amp; Co"));
+        assert!(svg.contains(">2083<"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,92 @@
+//! A fixed output canvas, independent of the window.
+//!
+//! With a canvas set, simulations render at the canvas resolution and the
+//! master bus letterboxes it into the window, so a 9:16 canvas shows as a
+//! pillarboxed column on a landscape window. Screenshots, the gallery and the
+//! clipboard get the canvas itself, framed exactly as it was set up.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SimulationError, SimulationResult};
+
+/// Smallest canvas side, in pixels
+pub const MIN_CANVAS_SIZE: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Canvas {
+    /// `max_size` is the device's largest texture side
+    pub fn validate(&self, max_size: u32) -> SimulationResult<()> {
+        let range = MIN_CANVAS_SIZE..=max_size;
+        if !range.contains(&self.width) || !range.contains(&self.height) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Canvas sides must be between {} and {} pixels",
+                MIN_CANVAS_SIZE, max_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Share of an `output` sized frame the canvas covers on each axis when
+    /// fitted inside it
+    pub fn letterbox(&self, output: [u32; 2]) -> [f32; 2] {
+        let aspect = self.width as f32 / self.height as f32;
+        let output_aspect = output[0] as f32 / output[1].max(1) as f32;
+        if aspect > output_aspect {
+            [1.0, output_aspect / aspect]
+        } else {
+            [aspect / output_aspect, 1.0]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_inside_the_output() {
+        let portrait = Canvas {
+            width: 1080,
+            height: 1920,
+        };
+        let [width, height] = portrait.letterbox([1920, 1080]);
+        assert_eq!(height, 1.0);
+        assert!((width - 0.316).abs() < 0.001);
+
+        let wide = Canvas {
+            width: 2000,
+            height: 500,
+        };
+        assert_eq!(wide.letterbox([1000, 1000]), [1.0, 0.25]);
+    }
+
+    #[test]
+    fn sides_must_fit_the_device() {
+        let canvas = Canvas {
+            width: 4096,
+            height: 8,
+        };
+        assert!(canvas.validate(8192).is_err());
+        assert!(
+            Canvas {
+                height: 4096,
+                ..canvas
+            }
+            .validate(2048)
+            .is_err()
+        );
+        assert!(
+            Canvas {
+                height: 16,
+                ..canvas
+            }
+            .validate(8192)
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Translated names and descriptions of the simulations and their presets.
+//!
+//! English text ships in `locales/en.toml`. Translations are TOML files of
+//! the same layout, either bundled next to it or dropped into the `locales`
+//! folder in the settings directory, where they also override bundled text.
+//! A lookup for `pt-BR` tries `pt-BR`, then `pt`, then English, field by
+//! field, so a translation can start out covering only a few entries.
+
+use include_dir::{Dir, include_dir};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::preset_manager::SimulationPresetManager;
+use super::previews::PREVIEWABLE_SIMULATIONS;
+use crate::commands::get_settings_dir;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+static LOCALE_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/simulation/locales");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Catalog {
+    /// The most specific locale any text was found for
+    pub locale: String,
+    pub simulations: Vec<SimulationEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationEntry {
+    pub id: String,
+    pub display_name: String,
+    pub description: String,
+    pub presets: Vec<PresetEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetEntry {
+    /// The name presets are applied and saved by
+    pub name: String,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub built_in: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Translations {
+    #[serde(default)]
+    simulations: HashMap<String, SimulationText>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SimulationText {
+    display_name: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    presets: HashMap<String, PresetText>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PresetText {
+    display_name: Option<String>,
+    description: Option<String>,
+}
+
+/// Translations for one requested locale, most specific first
+struct Localizer {
+    locale: String,
+    layers: Vec<Translations>,
+}
+
+impl Localizer {
+    fn load(requested: &str) -> Self {
+        let mut locale = None;
+        let mut layers = Vec::new();
+        for candidate in fallback_chain(requested) {
+            let found = load_translations(&candidate);
+            if !found.is_empty() {
+                locale.get_or_insert(candidate);
+            }
+            layers.extend(found);
+        }
+        Self {
+            locale: locale.unwrap_or_else(|| DEFAULT_LOCALE.to_string()),
+            layers,
+        }
+    }
+
+    fn simulation_text(
+        &self,
+        id: &str,
+        field: impl Fn(&SimulationText) -> Option<&String>,
+    ) -> Option<String> {
+        self.layers
+            .iter()
+            .filter_map(|layer| layer.simulations.get(id))
+            .find_map(field)
+            .cloned()
+    }
+
+    fn preset_text(
+        &self,
+        id: &str,
+        preset: &str,
+        field: impl Fn(&PresetText) -> Option<&String>,
+    ) -> Option<String> {
+        self.layers
+            .iter()
+            .filter_map(|layer| layer.simulations.get(id)?.presets.get(preset))
+            .find_map(field)
+            .cloned()
+    }
+
+    fn catalog(&self, presets: &SimulationPresetManager) -> Catalog {
+        let simulations = PREVIEWABLE_SIMULATIONS
+            .iter()
+            .map(|&id| SimulationEntry {
+                id: id.to_string(),
+                display_name: self
+                    .simulation_text(id, |text| text.display_name.as_ref())
+                    .unwrap_or_else(|| id.to_string()),
+                description: self
+                    .simulation_text(id, |text| text.description.as_ref())
+                    .unwrap_or_default(),
+                presets: presets
+                    .get_manager(id)
+                    .map(|manager| {
+                        manager
+                            .get_preset_names()
+                            .into_iter()
+                            .map(|name| PresetEntry {
+                                display_name: self
+                                    .preset_text(id, &name, |text| text.display_name.as_ref())
+                                    .unwrap_or_else(|| name.clone()),
+                                description: self
+                                    .preset_text(id, &name, |text| text.description.as_ref()),
+                                built_in: manager.is_built_in_preset(&name),
+                                name,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        Catalog {
+            locale: self.locale.clone(),
+            simulations,
+        }
+    }
+}
+
+/// Build the catalog for `locale`, e.g. `de` or `pt-BR`
+pub fn build_catalog(locale: &str, presets: &SimulationPresetManager) -> Catalog {
+    Localizer::load(locale).catalog(presets)
+}
+
+/// `pt-BR` gives `pt-BR`, `pt`, `en`
+fn fallback_chain(locale: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut push = |candidate: &str| {
+        // Locales end up in file names
+        let valid = !candidate.is_empty()
+            && candidate
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if valid && !chain.iter().any(|known: &String| known == candidate) {
+            chain.push(candidate.to_string());
+        }
+    };
+    let locale = locale.trim().replace('_', "-");
+    push(&locale);
+    if let Some((language, _)) = locale.split_once('-') {
+        push(language);
+    }
+    push(DEFAULT_LOCALE);
+    chain
+}
+
+/// The user's file for `locale` first, then the bundled one
+fn load_translations(locale: &str) -> Vec<Translations> {
+    let file_name = format!("{}.toml", locale);
+    let mut found = Vec::new();
+
+    let user_file = user_locales_dir().join(&file_name);
+    if let Ok(content) = std::fs::read_to_string(&user_file) {
+        match toml::from_str(&content) {
+            Ok(translations) => found.push(translations),
+            Err(e) => tracing::warn!("Ignoring {}: {}", user_file.display(), e),
+        }
+    }
+
+    if let Some(content) = LOCALE_DIR
+        .get_file(&file_name)
+        .and_then(|file| file.contents_utf8())
+    {
+        match toml::from_str(content) {
+            Ok(translations) => found.push(translations),
+            Err(e) => tracing::error!("Bundled locale {} is invalid: {}", locale, e),
+        }
+    }
+    found
+}
+
+fn user_locales_dir() -> PathBuf {
+    get_settings_dir().join("locales")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locales_fall_back_to_language_then_english() {
+        assert_eq!(fallback_chain("pt_BR"), ["pt-BR", "pt", "en"]);
+        assert_eq!(fallback_chain("de"), ["de", "en"]);
+        assert_eq!(fallback_chain("en-US"), ["en-US", "en"]);
+        assert_eq!(fallback_chain("../secrets"), ["en"]);
+    }
+
+    #[test]
+    fn missing_translations_use_the_next_layer() {
+        let german: Translations = toml::from_str(
+            r#"
+            [simulations.slime_mold]
+            display_name = "Schleimpilz"
+            "#,
+        )
+        .unwrap();
+        let bundled = LOCALE_DIR
+            .get_file("en.toml")
+            .and_then(|file| file.contents_utf8())
+            .unwrap();
+        let localizer = Localizer {
+            locale: "de".to_string(),
+            layers: vec![german, toml::from_str(bundled).unwrap()],
+        };
+
+        let name = localizer.simulation_text("slime_mold", |text| text.display_name.as_ref());
+        assert_eq!(name.as_deref(), Some("Schleimpilz"));
+        let description = localizer.simulation_text("slime_mold", |text| text.description.as_ref());
+        assert_eq!(
+            description.as_deref(),
+            Some("Agent collaboration simulation")
+        );
+        // Every simulation on the main menu has English text
+        for id in PREVIEWABLE_SIMULATIONS {
+            assert!(
+                localizer
+                    .simulation_text(id, |text| text.display_name.as_ref())
+                    .is_some()
+            );
+        }
+    }
+}
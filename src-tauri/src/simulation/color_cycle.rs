@@ -0,0 +1,155 @@
+//! Palette cycling of the running simulation's color scheme.
+//!
+//! Every frame the scheme is rotated a little further, so its bands of color
+//! flow through the image while the simulation itself stays put. The rotation
+//! is applied to the scheme before it's uploaded, which leaves each
+//! simulation's own LUT lookup untouched.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LutResult, SimulationError, SimulationResult};
+use crate::simulations::shared::{ColorScheme, ColorSchemeManager};
+
+const LUT_SIZE: f32 = 256.0;
+
+/// Fastest cycle, four trips through the scheme per second
+pub const MAX_SPEED: f32 = 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CycleDirection {
+    /// Colors move toward the low end of the scheme
+    #[default]
+    Forward,
+    Backward,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorCycle {
+    pub enabled: bool,
+    /// Color scheme entries per second
+    pub speed: f32,
+    pub direction: CycleDirection,
+}
+
+impl Default for ColorCycle {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            speed: 32.0,
+            direction: CycleDirection::Forward,
+        }
+    }
+}
+
+impl ColorCycle {
+    pub fn validate(&self) -> SimulationResult<()> {
+        if !(0.0..=MAX_SPEED).contains(&self.speed) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Color cycle speed must be between 0 and {}",
+                MAX_SPEED
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ColorCycler {
+    cycle: ColorCycle,
+    // Position in the scheme, in entries
+    position: f32,
+    // Rotation the simulation's scheme was last uploaded with
+    offset: usize,
+    // Unrotated scheme, reloaded when the simulation switches schemes
+    source: Option<ColorScheme>,
+}
+
+impl ColorCycler {
+    pub fn cycle(&self) -> &ColorCycle {
+        &self.cycle
+    }
+
+    /// Turning cycling off winds the scheme back to its start on the next
+    /// frame
+    pub fn set_cycle(&mut self, cycle: ColorCycle) -> SimulationResult<()> {
+        cycle.validate()?;
+        self.cycle = cycle;
+        if !cycle.enabled {
+            self.position = 0.0;
+        }
+        // Picks up edits to custom schemes
+        self.source = None;
+        Ok(())
+    }
+
+    /// Move the cycle on by `delta_time` seconds. Returns the new rotation
+    /// when it has moved to another entry.
+    pub fn advance(&mut self, delta_time: f32) -> Option<usize> {
+        if self.cycle.enabled {
+            let step = match self.cycle.direction {
+                CycleDirection::Forward => self.cycle.speed,
+                CycleDirection::Backward => -self.cycle.speed,
+            } * delta_time;
+            self.position = (self.position + step).rem_euclid(LUT_SIZE);
+        }
+        let offset = self.position as usize % LUT_SIZE as usize;
+        (offset != self.offset).then(|| {
+            self.offset = offset;
+            offset
+        })
+    }
+
+    /// The named scheme, unrotated
+    pub fn source(&mut self, manager: &ColorSchemeManager, name: &str) -> LutResult<ColorScheme> {
+        match &self.source {
+            Some(source) if source.name == name => Ok(source.clone()),
+            _ => {
+                let source = manager.get(name)?;
+                self.source = Some(source.clone());
+                Ok(source)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_only_new_entries() {
+        let mut cycler = ColorCycler::default();
+        cycler
+            .set_cycle(ColorCycle {
+                enabled: true,
+                speed: 10.0,
+                direction: CycleDirection::Backward,
+            })
+            .unwrap();
+
+        assert_eq!(cycler.advance(0.05), Some(255));
+        assert_eq!(cycler.advance(0.01), None);
+        assert_eq!(cycler.advance(0.2), Some(253));
+
+        cycler
+            .set_cycle(ColorCycle {
+                enabled: false,
+                ..*cycler.cycle()
+            })
+            .unwrap();
+        assert_eq!(cycler.advance(1.0), Some(0));
+        assert_eq!(cycler.advance(1.0), None);
+    }
+
+    #[test]
+    fn rejects_runaway_speeds() {
+        let cycle = ColorCycle {
+            speed: f32::NAN,
+            ..Default::default()
+        };
+        assert!(cycle.validate().is_err());
+        assert!(ColorCycle::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,80 @@
+//! Routing for `vizza://` links.
+//!
+//! The frontend owns navigation between simulations, so a link is decoded
+//! here, parked on the simulation manager and announced with an event. The
+//! frontend starts the named simulation and then applies the configuration.
+//! Links that launch the app arrive before the frontend is listening, which is
+//! why the configuration is also kept until the frontend takes it.
+
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use super::SimulationManager;
+use super::kiosk;
+use super::settings_codec::SharedConfiguration;
+
+/// Emitted with the decoded [`SharedConfiguration`] when a link is opened
+pub const DEEP_LINK_OPENED_EVENT: &str = "deep-link-opened";
+
+/// Hook up link handling, including any link the app was launched with
+pub fn init(app: &tauri::App) {
+    let deep_link = app.deep_link();
+
+    // Linux and Windows dev builds only know about the scheme once registered at runtime
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    if let Err(e) = deep_link.register_all() {
+        tracing::warn!("Failed to register deep link schemes: {}", e);
+    }
+
+    let app_handle = app.handle().clone();
+    deep_link.on_open_url(move |event| {
+        tauri::async_runtime::spawn(handle_deep_links(app_handle.clone(), event.urls()));
+    });
+
+    match deep_link.get_current() {
+        Ok(Some(urls)) => {
+            tauri::async_runtime::spawn(handle_deep_links(app.handle().clone(), urls));
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read startup deep link: {}", e),
+    }
+}
+
+async fn handle_deep_links(app: AppHandle, urls: Vec<tauri::Url>) {
+    // Only one configuration can be shown, so the last link that decodes wins
+    let Some(config) = urls
+        .iter()
+        .rev()
+        .find_map(|url| match SharedConfiguration::from_deep_link(url) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                tracing::warn!("Ignoring deep link {}: {}", url, e);
+                None
+            }
+        })
+    else {
+        return;
+    };
+
+    {
+        let manager = app.state::<Arc<tokio::sync::Mutex<SimulationManager>>>();
+        let mut sim_manager = manager.lock().await;
+        if kiosk::refuses(sim_manager.kiosk.as_ref(), "deep link") {
+            return;
+        }
+        tracing::info!("Opening {} from deep link", config.simulation_type);
+        sim_manager.pending_shared_configuration = Some(config.clone());
+    }
+
+    if let Some(Err(e)) = app
+        .get_webview_window("main")
+        .map(|window| window.set_focus())
+    {
+        tracing::warn!("Failed to focus window for deep link: {}", e);
+    }
+
+    if let Err(e) = app.emit(DEEP_LINK_OPENED_EVENT, &config) {
+        tracing::error!("Failed to emit {} event: {}", DEEP_LINK_OPENED_EVENT, e);
+    }
+}
@@ -0,0 +1,114 @@
+//! Lifecycle events of the running simulation, pushed to the frontend.
+//!
+//! The simulation manager publishes a [`SimulationEvent`] whenever something
+//! happens that the UI or an external integration would otherwise have to
+//! poll `get_simulation_status` for. Every event goes out under the single
+//! [`SIMULATION_EVENT`] name, tagged with its `type`.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Emitted with a [`SimulationEvent`] payload
+pub const SIMULATION_EVENT: &str = "simulation-event";
+
+/// Used when no FPS limit is set
+const DEFAULT_TARGET_FPS: u32 = 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum SimulationEvent {
+    Started {
+        simulation_type: String,
+    },
+    Paused,
+    Resumed,
+    Destroyed {
+        simulation_type: String,
+    },
+    PresetApplied {
+        simulation_type: String,
+        preset: String,
+    },
+    ColorSchemeChanged {
+        color_scheme: String,
+        reversed: bool,
+    },
+    Error {
+        message: String,
+    },
+    /// The frame rate fell below half of the target
+    FpsDropped {
+        fps: u32,
+        target_fps: u32,
+    },
+    /// The frame rate is back above three quarters of the target
+    FpsRecovered {
+        fps: u32,
+        target_fps: u32,
+    },
+    /// A replay played its last frame
+    ReplayFinished {
+        name: String,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct EventBus {
+    // Attached once the app is set up, events before that are dropped
+    app: Option<AppHandle>,
+    fps_low: bool,
+}
+
+impl EventBus {
+    pub fn attach(&mut self, app: AppHandle) {
+        self.app = Some(app);
+    }
+
+    pub fn publish(&self, event: SimulationEvent) {
+        let Some(app) = &self.app else {
+            tracing::debug!("Dropping {:?}, no app attached yet", event);
+            return;
+        };
+        if let Err(e) = app.emit(SIMULATION_EVENT, &event) {
+            tracing::warn!("Failed to emit {:?}: {}", event, e);
+        }
+    }
+
+    /// Publish an FPS alert when `fps` crosses the thresholds for `fps_limit`
+    /// (`None` without a limit). The gap between them keeps a frame rate
+    /// hovering around one from raising an alert every second.
+    pub fn report_fps(&mut self, fps: u32, fps_limit: Option<u32>) {
+        let target_fps = fps_limit.unwrap_or(DEFAULT_TARGET_FPS);
+        if !self.fps_low && fps < target_fps / 2 {
+            self.fps_low = true;
+            self.publish(SimulationEvent::FpsDropped { fps, target_fps });
+        } else if self.fps_low && fps >= target_fps * 3 / 4 {
+            self.fps_low = false;
+            self.publish(SimulationEvent::FpsRecovered { fps, target_fps });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fps_alerts_need_to_cross_both_thresholds() {
+        let mut events = EventBus::default();
+        events.report_fps(40, None);
+        assert!(!events.fps_low);
+        events.report_fps(29, None);
+        assert!(events.fps_low);
+        // Above half but below three quarters is still low
+        events.report_fps(40, None);
+        assert!(events.fps_low);
+        events.report_fps(45, None);
+        assert!(!events.fps_low);
+
+        events.report_fps(20, Some(30));
+        assert!(!events.fps_low);
+        events.report_fps(14, Some(30));
+        assert!(events.fps_low);
+    }
+}
@@ -0,0 +1,434 @@
+//! Interactive evolution of simulation settings.
+//!
+//! A generation is a handful of variants of the running simulation's
+//! settings, each shown as a small preview. The user picks the ones they like
+//! and the next generation is bred from them: the picks carry over as they
+//! are, and the rest of the population are crossovers of the picks nudged
+//! toward fresh random settings. Mutations move toward what the simulation's
+//! own randomizer produces, so they stay in the ranges it considers sensible,
+//! and every variant is checked against the simulation's setting rules.
+//!
+//! Previews render one after another on a single low-resolution instance of
+//! the simulation, restarted for each variant.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::{Device, Queue};
+
+use super::previews::{
+    PREVIEW_HEIGHT, PREVIEW_WIDTH, PREVIEWABLE_SIMULATIONS, create_preview_simulation,
+    encode_png_data_url,
+};
+use crate::commands::AppSettings;
+use crate::error::{AppResult, SimulationError};
+use crate::simulations::shared::validation::SettingValidator;
+use crate::simulations::shared::{ColorScheme, ColorSchemeManager, FrameCapture};
+use crate::simulations::traits::{Simulation, SimulationType};
+
+pub const MIN_POPULATION: usize = 2;
+pub const MAX_POPULATION: usize = 16;
+/// Frames simulated from a fresh start before a variant's preview is taken
+const PREVIEW_FRAMES: u32 = 90;
+const FRAME_DELTA_TIME: f32 = 1.0 / 60.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvolutionConfig {
+    /// Variants in each generation
+    pub population: usize,
+    /// Chance of each setting mutating, 0-1
+    pub mutation_rate: f64,
+    /// How far a mutating number moves toward a random value, 0-1
+    pub mutation_strength: f64,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            population: 8,
+            mutation_rate: 0.3,
+            mutation_strength: 0.5,
+        }
+    }
+}
+
+impl EvolutionConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(MIN_POPULATION..=MAX_POPULATION).contains(&self.population) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Evolution needs between {} and {} variants per generation",
+                MIN_POPULATION, MAX_POPULATION
+            ))
+            .into());
+        }
+        if !(0.0..=1.0).contains(&self.mutation_rate)
+            || !(0.0..=1.0).contains(&self.mutation_strength)
+        {
+            return Err(SimulationError::InvalidParameter(
+                "Mutation rate and strength must be between 0 and 1".to_string(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Variant {
+    pub settings: Value,
+    /// PNG data URL
+    pub preview: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Generation {
+    pub number: u32,
+    pub variants: Vec<Variant>,
+    /// Indices of the variants picked for breeding so far
+    pub selected: Vec<usize>,
+}
+
+pub struct Evolution {
+    simulation: SimulationType,
+    capture: FrameCapture,
+    config: EvolutionConfig,
+    generation: Generation,
+}
+
+impl Evolution {
+    /// Start from `settings` of a running `simulation_type`. The first
+    /// generation holds the settings themselves and mutations of them.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        simulation_type: &str,
+        settings: Value,
+        color_scheme: Option<&ColorScheme>,
+        config: EvolutionConfig,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_format: wgpu::TextureFormat,
+        adapter_info: &wgpu::AdapterInfo,
+        color_scheme_manager: &ColorSchemeManager,
+        app_settings: &AppSettings,
+    ) -> AppResult<Self> {
+        config.validate()?;
+        if !PREVIEWABLE_SIMULATIONS.contains(&simulation_type) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "{} can't be evolved",
+                simulation_type
+            ))
+            .into());
+        }
+
+        let capture = FrameCapture::new(
+            device,
+            PREVIEW_WIDTH,
+            PREVIEW_HEIGHT,
+            surface_format,
+            "Evolution Preview",
+        )?;
+        let mut simulation = create_preview_simulation(
+            simulation_type,
+            device,
+            queue,
+            &capture.surface_config(),
+            adapter_info,
+            color_scheme_manager,
+            app_settings,
+        )
+        .await?;
+        if let Some(color_scheme) = color_scheme {
+            simulation.update_color_scheme(color_scheme, device, queue)?;
+        }
+
+        let mut evolution = Self {
+            simulation,
+            capture,
+            config,
+            generation: Generation {
+                number: 0,
+                variants: Vec::new(),
+                selected: Vec::new(),
+            },
+        };
+        let mut population = vec![settings.clone()];
+        while population.len() < config.population {
+            let child = evolution.offspring(&[&settings], device, queue)?;
+            population.push(child);
+        }
+        evolution.render_generation(population, device, queue)?;
+        Ok(evolution)
+    }
+
+    pub fn generation(&self) -> &Generation {
+        &self.generation
+    }
+
+    pub fn variant(&self, index: usize) -> AppResult<&Variant> {
+        self.generation.variants.get(index).ok_or_else(|| {
+            SimulationError::InvalidParameter(format!("There is no variant {}", index)).into()
+        })
+    }
+
+    /// Pick the variants to breed the next generation from
+    pub fn select(&mut self, indices: Vec<usize>) -> AppResult<()> {
+        for &index in &indices {
+            self.variant(index)?;
+        }
+        let mut selected = indices;
+        selected.sort_unstable();
+        selected.dedup();
+        self.generation.selected = selected;
+        Ok(())
+    }
+
+    /// Breed the selected variants into the next generation
+    pub fn next_generation(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> AppResult<()> {
+        if self.generation.selected.is_empty() {
+            return Err(SimulationError::InvalidParameter(
+                "Select at least one variant to breed from".to_string(),
+            )
+            .into());
+        }
+        let parents: Vec<Value> = self
+            .generation
+            .selected
+            .iter()
+            .map(|&index| self.generation.variants[index].settings.clone())
+            .collect();
+        let parent_refs: Vec<&Value> = parents.iter().collect();
+
+        let mut population: Vec<Value> = parents
+            .iter()
+            .take(self.config.population - 1)
+            .cloned()
+            .collect();
+        while population.len() < self.config.population {
+            let child = self.offspring(&parent_refs, device, queue)?;
+            population.push(child);
+        }
+        self.render_generation(population, device, queue)?;
+        self.generation.number += 1;
+        Ok(())
+    }
+
+    /// A crossover of `parents`, mutated toward a fresh randomization and
+    /// held to the simulation's setting rules
+    fn offspring(
+        &mut self,
+        parents: &[&Value],
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<Value> {
+        self.simulation.randomize_settings(device, queue)?;
+        let target = self.simulation.get_settings();
+        let mut rng = rand::rng();
+        let mut child = crossover(parents, &mut rng);
+        mutate(
+            &mut child,
+            &target,
+            self.config.mutation_rate,
+            self.config.mutation_strength,
+            &mut rng,
+        );
+        Ok(conform(
+            child,
+            parents[0],
+            self.simulation.setting_validator(),
+        ))
+    }
+
+    fn render_generation(
+        &mut self,
+        population: Vec<Value>,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        let mut variants = Vec::with_capacity(population.len());
+        for settings in population {
+            self.simulation
+                .apply_settings(settings.clone(), device, queue)?;
+            self.simulation.reset_runtime_state(device, queue)?;
+            for _ in 0..PREVIEW_FRAMES {
+                self.simulation.render_frame(
+                    device,
+                    queue,
+                    &self.capture.view,
+                    FRAME_DELTA_TIME,
+                )?;
+            }
+            let image = self.capture.read_rgba(device, queue)?;
+            variants.push(Variant {
+                // Settings as the simulation took them, so applying the variant
+                // gives what the preview showed
+                settings: self.simulation.get_settings(),
+                preview: encode_png_data_url(&image)?,
+            });
+        }
+        self.generation.variants = variants;
+        self.generation.selected.clear();
+        Ok(())
+    }
+}
+
+/// Take each setting from one of the parents at random. Objects and
+/// same-length arrays are mixed setting by setting.
+fn crossover(parents: &[&Value], rng: &mut impl Rng) -> Value {
+    let first = parents[0];
+    match first {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, _)| {
+                    let candidates: Vec<&Value> = parents
+                        .iter()
+                        .filter_map(|parent| parent.get(key))
+                        .collect();
+                    (key.clone(), crossover(&candidates, rng))
+                })
+                .collect(),
+        ),
+        Value::Array(items)
+            if parents.iter().all(|parent| {
+                parent
+                    .as_array()
+                    .is_some_and(|other| other.len() == items.len())
+            }) =>
+        {
+            Value::Array(
+                (0..items.len())
+                    .map(|index| {
+                        let candidates: Vec<&Value> =
+                            parents.iter().map(|parent| &parent[index]).collect();
+                        crossover(&candidates, rng)
+                    })
+                    .collect(),
+            )
+        }
+        _ => parents[rng.random_range(0..parents.len())].clone(),
+    }
+}
+
+/// Move some of the settings toward `target`. Numbers travel part of the way
+/// there, anything else is swapped for the target's value outright.
+fn mutate(settings: &mut Value, target: &Value, rate: f64, strength: f64, rng: &mut impl Rng) {
+    match (&mut *settings, target) {
+        (Value::Object(fields), Value::Object(target_fields)) => {
+            for (key, value) in fields.iter_mut() {
+                if let Some(target_value) = target_fields.get(key) {
+                    mutate(value, target_value, rate, strength, rng);
+                }
+            }
+        }
+        (Value::Array(items), Value::Array(target_items)) if items.len() == target_items.len() => {
+            for (item, target_item) in items.iter_mut().zip(target_items) {
+                mutate(item, target_item, rate, strength, rng);
+            }
+        }
+        (Value::Number(number), Value::Number(target_number)) => {
+            if !rng.random_bool(rate) {
+                return;
+            }
+            let (Some(from), Some(to)) = (number.as_f64(), target_number.as_f64()) else {
+                return;
+            };
+            let moved = from + (to - from) * strength * rng.random_range(0.0..=1.0);
+            // Counts and indices have to stay whole
+            *settings = if number.is_f64() {
+                Value::from(moved)
+            } else if number.is_u64() {
+                Value::from(moved.round().max(0.0) as u64)
+            } else {
+                Value::from(moved.round() as i64)
+            };
+        }
+        (value, target_value) => {
+            if value != target_value && rng.random_bool(rate) {
+                *value = target_value.clone();
+            }
+        }
+    }
+}
+
+/// Hold each setting to the simulation's rules, clamping numbers and falling
+/// back to `fallback`'s value for anything the rules reject
+fn conform(settings: Value, fallback: &Value, validator: &SettingValidator) -> Value {
+    let Value::Object(fields) = &settings else {
+        return settings;
+    };
+    let conformed = fields
+        .iter()
+        .map(|(key, value)| {
+            let value = match validator.validate(key, value.clone(), || settings.clone()) {
+                Ok(validated) => validated.value,
+                Err(_) => fallback.get(key).cloned().unwrap_or_else(|| value.clone()),
+            };
+            (key.clone(), value)
+        })
+        .collect();
+    Value::Object(conformed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulations::shared::validation::Rule;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use serde_json::json;
+
+    #[test]
+    fn children_only_inherit_from_their_parents() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let a = json!({ "speed": 1.0, "shape": "Circle", "weights": [1, 2, 3] });
+        let b = json!({ "speed": 2.0, "shape": "Square", "weights": [4, 5, 6] });
+        for _ in 0..20 {
+            let child = crossover(&[&a, &b], &mut rng);
+            assert!(child["speed"] == a["speed"] || child["speed"] == b["speed"]);
+            assert!(child["shape"] == a["shape"] || child["shape"] == b["shape"]);
+            for index in 0..3 {
+                let weight = &child["weights"][index];
+                assert!(*weight == a["weights"][index] || *weight == b["weights"][index]);
+            }
+        }
+    }
+
+    #[test]
+    fn mutations_move_toward_the_target_and_keep_counts_whole() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let target = json!({ "speed": 10.0, "count": 100, "shape": "Square" });
+
+        let mut unchanged = json!({ "speed": 0.0, "count": 10, "shape": "Circle" });
+        mutate(&mut unchanged, &target, 0.0, 1.0, &mut rng);
+        assert_eq!(
+            unchanged,
+            json!({ "speed": 0.0, "count": 10, "shape": "Circle" })
+        );
+
+        let mut mutated = unchanged.clone();
+        mutate(&mut mutated, &target, 1.0, 1.0, &mut rng);
+        let speed = mutated["speed"].as_f64().unwrap();
+        assert!((0.0..=10.0).contains(&speed));
+        assert!((10..=100).contains(&mutated["count"].as_u64().unwrap()));
+        assert_eq!(mutated["shape"], json!("Square"));
+    }
+
+    #[test]
+    fn rejected_values_fall_back_to_the_parent() {
+        const RULES: SettingValidator = SettingValidator::new(
+            &[
+                ("strength", Rule::Range { min: 0.0, max: 1.0 }),
+                ("count", Rule::Count { min: 2, max: 8 }),
+            ],
+            &[],
+        );
+        let parent = json!({ "strength": 0.5, "count": 4 });
+        let child = json!({ "strength": 3.0, "count": 20 });
+        assert_eq!(
+            conform(child, &parent, &RULES),
+            json!({ "strength": 1.0, "count": 4 })
+        );
+    }
+}
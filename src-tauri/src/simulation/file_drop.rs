@@ -0,0 +1,215 @@
+//! Handling for files dropped onto the window.
+//!
+//! Each dropped file is classified by extension and handed to the existing
+//! import path for its kind: preset TOML goes to the preset manager of the
+//! simulation it belongs to, `.cube` files and gradient strips become custom
+//! color schemes, and any other image seeds the running simulation. The result
+//! of every file is reported to the frontend as an event.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use wgpu::{Device, Queue};
+
+use super::SimulationManager;
+use super::kiosk;
+use crate::error::{AppError, AppResult, ColorSchemeError};
+use crate::simulations::shared::ColorScheme;
+
+/// Emitted once per dropped file with a [`FileDropResult`]
+pub const FILE_DROPPED_EVENT: &str = "file-dropped";
+
+/// Images at least this many times wider than tall (or taller than wide) are
+/// treated as gradient strips rather than seed images
+const GRADIENT_STRIP_ASPECT: u32 = 8;
+
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "bmp", "gif", "tga", "tif", "tiff", "webp",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DroppedFileKind {
+    Preset,
+    CubeLut,
+    Image,
+}
+
+impl DroppedFileKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "toml" => Some(DroppedFileKind::Preset),
+            "cube" => Some(DroppedFileKind::CubeLut),
+            ext if IMAGE_EXTENSIONS.contains(&ext) => Some(DroppedFileKind::Image),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum FileDropResult {
+    PresetImported {
+        path: String,
+        simulation_type: String,
+        preset_name: String,
+    },
+    ColorSchemeImported {
+        path: String,
+        color_scheme_name: String,
+    },
+    SimulationSeeded {
+        path: String,
+        simulation_type: String,
+    },
+    Failed {
+        path: String,
+        message: String,
+    },
+}
+
+/// Import every dropped file and tell the frontend how each one went
+pub async fn handle_file_drop(app: AppHandle, paths: Vec<PathBuf>) {
+    let (device, queue) = {
+        let gpu_context = app.state::<Arc<tokio::sync::Mutex<crate::GpuContext>>>();
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+    let manager = app.state::<Arc<tokio::sync::Mutex<SimulationManager>>>();
+    if kiosk::refuses(manager.lock().await.kiosk.as_ref(), "dropped file") {
+        return;
+    }
+
+    for path in paths {
+        let result = {
+            let mut sim_manager = manager.lock().await;
+            import_dropped_file(&mut sim_manager, &device, &queue, &path)
+        }
+        .unwrap_or_else(|e| {
+            tracing::warn!("Could not import dropped file {}: {}", path.display(), e);
+            FileDropResult::Failed {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            }
+        });
+
+        if let Err(e) = app.emit(FILE_DROPPED_EVENT, &result) {
+            tracing::error!("Failed to emit {} event: {}", FILE_DROPPED_EVENT, e);
+        }
+    }
+}
+
+pub fn import_dropped_file(
+    manager: &mut SimulationManager,
+    device: &Arc<Device>,
+    queue: &Arc<Queue>,
+    path: &Path,
+) -> AppResult<FileDropResult> {
+    let kind = DroppedFileKind::from_path(path)
+        .ok_or_else(|| AppError::Unknown(format!("Unsupported file type: {}", path.display())))?;
+    let display_path = path.display().to_string();
+
+    match kind {
+        DroppedFileKind::Preset => {
+            let running = manager
+                .current_simulation
+                .as_ref()
+                .map(|simulation| simulation.type_name());
+            let (simulation_type, preset_name) =
+                manager.preset_manager.import_preset_file(path, running)?;
+            Ok(FileDropResult::PresetImported {
+                path: display_path,
+                simulation_type,
+                preset_name,
+            })
+        }
+        DroppedFileKind::CubeLut => {
+            let content = std::fs::read_to_string(path)?;
+            let color_scheme = ColorScheme::from_cube(file_stem(path), &content)?;
+            save_color_scheme(manager, color_scheme, display_path)
+        }
+        DroppedFileKind::Image => {
+            let image = image::open(path)
+                .map_err(|e| AppError::Unknown(format!("Failed to open image: {}", e)))?;
+            if is_gradient_strip(image.width(), image.height()) {
+                let color_scheme = ColorScheme::from_gradient_image(file_stem(path), &image);
+                return save_color_scheme(manager, color_scheme, display_path);
+            }
+
+            manager.seed_from_image(device, queue, image)?;
+            let simulation_type = manager
+                .current_simulation
+                .as_ref()
+                .map(|simulation| simulation.type_name().to_string())
+                .unwrap_or_default();
+            Ok(FileDropResult::SimulationSeeded {
+                path: display_path,
+                simulation_type,
+            })
+        }
+    }
+}
+
+fn save_color_scheme(
+    manager: &SimulationManager,
+    color_scheme: ColorScheme,
+    path: String,
+) -> AppResult<FileDropResult> {
+    if manager
+        .color_scheme_manager
+        .all_color_schemes()
+        .contains(&color_scheme.name)
+    {
+        return Err(ColorSchemeError::ValidationFailed(format!(
+            "A color scheme named '{}' already exists",
+            color_scheme.name
+        ))
+        .into());
+    }
+
+    manager
+        .color_scheme_manager
+        .save_custom(&color_scheme.name, &color_scheme)?;
+    tracing::info!("Imported color scheme '{}'", color_scheme.name);
+    Ok(FileDropResult::ColorSchemeImported {
+        path,
+        color_scheme_name: color_scheme.name,
+    })
+}
+
+fn is_gradient_strip(width: u32, height: u32) -> bool {
+    width >= height.saturating_mul(GRADIENT_STRIP_ASPECT)
+        || height >= width.saturating_mul(GRADIENT_STRIP_ASPECT)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Imported")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_are_classified_by_extension() {
+        let kind = |name: &str| DroppedFileKind::from_path(Path::new(name));
+        assert_eq!(kind("Wavy.toml"), Some(DroppedFileKind::Preset));
+        assert_eq!(kind("film.CUBE"), Some(DroppedFileKind::CubeLut));
+        assert_eq!(kind("photo.jpeg"), Some(DroppedFileKind::Image));
+        assert_eq!(kind("notes.txt"), None);
+        assert_eq!(kind("no_extension"), None);
+    }
+
+    #[test]
+    fn only_elongated_images_are_gradients() {
+        assert!(is_gradient_strip(256, 16));
+        assert!(is_gradient_strip(8, 200));
+        assert!(!is_gradient_strip(1920, 1080));
+        assert!(!is_gradient_strip(64, 64));
+    }
+}
@@ -0,0 +1,180 @@
+//! Linear frame export.
+//!
+//! Frames are written either as OpenEXR, holding the display texture's values
+//! as linear floating point, or as 16-bit sRGB PNG for tools that don't read
+//! EXR. Neither adds range the frame didn't have: a wide-gamut half-float
+//! surface keeps its values outside 0-1, but on the usual 8-bit sRGB surface
+//! the export is the 8-bit frame converted to linear, with no more precision
+//! than a PNG screenshot. Simulation and preset information goes into a JSON
+//! sidecar next to the image so the frame can be traced back to the settings
+//! that made it.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::SimulationManager;
+use super::seeds::Seed;
+use crate::error::{AppError, AppResult, SimulationError};
+use crate::simulations::shared::color_space::linear_to_srgb;
+use crate::simulations::traits::Simulation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinearFormat {
+    Exr,
+    Png16,
+}
+
+impl LinearFormat {
+    /// Pick the output format from a file's extension
+    pub fn from_path(path: &Path) -> AppResult<Self> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("exr") => Ok(LinearFormat::Exr),
+            Some("png") => Ok(LinearFormat::Png16),
+            _ => Err(SimulationError::InvalidParameter(format!(
+                "Unsupported frame export extension for {}, expected .exr or .png",
+                path.display()
+            ))
+            .into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameMetadata {
+    pub simulation_type: String,
+    pub preset: Option<String>,
+    /// For simulations whose worlds grow from a seed
+    pub seed: Option<Seed>,
+    pub settings: serde_json::Value,
+    pub width: u32,
+    pub height: u32,
+    pub surface_format: String,
+    pub exported_at: String,
+    pub app_version: String,
+}
+
+/// Render the current frame and write it to `path`, returning the path of the
+/// metadata sidecar.
+pub fn export_frame_linear(
+    manager: &mut SimulationManager,
+    device: &std::sync::Arc<wgpu::Device>,
+    queue: &std::sync::Arc<wgpu::Queue>,
+    surface_config: &wgpu::SurfaceConfiguration,
+    path: &Path,
+) -> AppResult<PathBuf> {
+    let format = LinearFormat::from_path(path)?;
+
+    let capture = manager.capture_frame(device, queue, surface_config)?;
+    let image = capture.read_linear(device, queue)?;
+
+    let simulation = manager
+        .current_simulation
+        .as_ref()
+        .ok_or(SimulationError::NotRunning)?;
+    let metadata = FrameMetadata {
+        simulation_type: simulation.type_name().to_string(),
+        preset: manager.current_preset.clone(),
+        seed: manager.simulation_seed(),
+        settings: simulation.get_settings(),
+        width: image.width(),
+        height: image.height(),
+        surface_format: format!("{:?}", surface_config.format),
+        exported_at: chrono::Local::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+
+    match format {
+        LinearFormat::Exr => image::DynamicImage::ImageRgba32F(image)
+            .save_with_format(path, image::ImageFormat::OpenExr),
+        LinearFormat::Png16 => {
+            encode_png16(&image)?.save_with_format(path, image::ImageFormat::Png)
+        }
+    }
+    .map_err(|e| AppError::Unknown(format!("Failed to write {}: {}", path.display(), e)))?;
+
+    let metadata_path = metadata_path(path);
+    std::fs::write(&metadata_path, serde_json::to_string_pretty(&metadata)?)?;
+
+    tracing::info!(
+        "Exported {} frame to {}",
+        metadata.simulation_type,
+        path.display()
+    );
+    Ok(metadata_path)
+}
+
+/// PNG has no linear float storage, so values are clamped and sRGB encoded
+fn encode_png16(
+    image: &image::Rgba32FImage,
+) -> AppResult<image::ImageBuffer<image::Rgba<u16>, Vec<u16>>> {
+    let pixels = image
+        .as_raw()
+        .iter()
+        .enumerate()
+        .map(|(channel, &value)| {
+            let value = if channel % 4 == 3 {
+                value
+            } else {
+                linear_to_srgb(value)
+            };
+            (value.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+        })
+        .collect();
+    image::ImageBuffer::from_raw(image.width(), image.height(), pixels).ok_or_else(|| {
+        SimulationError::InvalidParameter("Frame has unexpected size for a 16-bit PNG".to_string())
+            .into()
+    })
+}
+
+/// `frame.exr` -> `frame.exr.json`
+fn metadata_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".json");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_is_chosen_by_extension() {
+        assert_eq!(
+            LinearFormat::from_path(Path::new("frame.EXR")).unwrap(),
+            LinearFormat::Exr
+        );
+        assert_eq!(
+            LinearFormat::from_path(Path::new("frame.png")).unwrap(),
+            LinearFormat::Png16
+        );
+        assert!(LinearFormat::from_path(Path::new("frame.jpg")).is_err());
+        assert!(LinearFormat::from_path(Path::new("frame")).is_err());
+    }
+
+    #[test]
+    fn png16_encodes_srgb_and_clamps() {
+        let image =
+            image::Rgba32FImage::from_raw(2, 1, vec![0.0, 1.0, 4.0, 0.5, 0.5, -1.0, 0.0, 1.0])
+                .unwrap();
+        let encoded = encode_png16(&image).unwrap().into_raw();
+        assert_eq!(encoded[0], 0);
+        assert_eq!(encoded[1], u16::MAX);
+        assert_eq!(encoded[2], u16::MAX);
+        // Alpha stays linear
+        assert_eq!(encoded[3], (0.5 * u16::MAX as f32).round() as u16);
+        assert!(encoded[4] > u16::MAX / 2);
+        assert_eq!(encoded[5], 0);
+    }
+
+    #[test]
+    fn metadata_sits_next_to_image() {
+        assert_eq!(
+            metadata_path(Path::new("/tmp/frame.exr")),
+            PathBuf::from("/tmp/frame.exr.json")
+        );
+    }
+}
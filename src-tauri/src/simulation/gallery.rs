@@ -0,0 +1,214 @@
+//! Captured frames kept together with the configuration that produced them.
+//!
+//! The gallery lives in the `gallery` folder of the settings directory. Each
+//! capture is an 8-bit PNG, a small thumbnail for listing, and a TOML file
+//! with the [`SharedConfiguration`] taken at the same moment, all named by
+//! the entry's id. Restoring an entry applies that configuration again.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use wgpu::{Device, Queue, SurfaceConfiguration};
+
+use super::SimulationManager;
+use super::previews::encode_png_data_url;
+use super::settings_codec::SharedConfiguration;
+use crate::commands::get_settings_dir;
+use crate::error::{AppError, AppResult, SimulationError};
+
+/// Longest side of the thumbnails shown in the gallery list
+const THUMBNAIL_SIZE: u32 = 256;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GalleryEntry {
+    /// `<simulation>-<local time>`, also the base name of the entry's files
+    pub id: String,
+    /// RFC 3339, UTC
+    pub created_at: String,
+    pub width: u32,
+    pub height: u32,
+    pub configuration: SharedConfiguration,
+}
+
+/// A gallery entry as listed for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct GalleryItem {
+    #[serde(flatten)]
+    pub entry: GalleryEntry,
+    pub image_path: String,
+    /// PNG data URL, `None` if the thumbnail is missing
+    pub thumbnail: Option<String>,
+}
+
+impl GalleryEntry {
+    /// Capture the current frame and configuration into the gallery
+    pub fn capture(
+        manager: &mut SimulationManager,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<Self> {
+        let configuration = manager.shared_configuration()?;
+        let image = manager
+            .capture_frame(device, queue, surface_config)?
+            .read_rgba(device, queue)?;
+
+        let entry = Self {
+            id: new_id(
+                &configuration.simulation_type,
+                chrono::Local::now().naive_local(),
+            ),
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            width: image.width(),
+            height: image.height(),
+            configuration,
+        };
+
+        std::fs::create_dir_all(gallery_dir())?;
+        let save_png = |image: &image::RgbaImage, path: PathBuf| {
+            image.save(&path).map_err(|e| {
+                AppError::Unknown(format!("Failed to write {}: {}", path.display(), e))
+            })
+        };
+        save_png(&image, entry_path(&entry.id, "png")?)?;
+        let (width, height) = thumbnail_size(entry.width, entry.height);
+        save_png(
+            &image::imageops::thumbnail(&image, width, height),
+            entry_path(&entry.id, "thumb.png")?,
+        )?;
+
+        let content = toml::to_string_pretty(&entry)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize gallery entry: {}", e)))?;
+        std::fs::write(entry_path(&entry.id, "toml")?, content)?;
+        Ok(entry)
+    }
+
+    pub fn load(id: &str) -> AppResult<Self> {
+        let path = entry_path(id, "toml")?;
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            SimulationError::InvalidParameter(format!(
+                "Failed to read gallery entry '{}': {}",
+                id, e
+            ))
+        })?;
+        toml::from_str(&content)
+            .map_err(|e| AppError::Unknown(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+
+    /// Remove the entry's image, thumbnail and configuration
+    pub fn delete(id: &str) -> AppResult<()> {
+        for extension in ["png", "thumb.png", "toml"] {
+            match std::fs::remove_file(entry_path(id, extension)?) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn image_path(id: &str) -> AppResult<PathBuf> {
+        entry_path(id, "png")
+    }
+
+    /// Every entry in the gallery, newest first. Entries that don't parse are
+    /// skipped.
+    pub fn list() -> Vec<GalleryItem> {
+        let Ok(files) = std::fs::read_dir(gallery_dir()) else {
+            return vec![];
+        };
+        let mut entries: Vec<Self> = files
+            .filter_map(|file| file.ok())
+            .map(|file| file.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("toml"))
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(&path).ok()?;
+                match toml::from_str::<Self>(&content) {
+                    Ok(entry) => Some(entry),
+                    Err(e) => {
+                        tracing::warn!("Skipping gallery entry {}: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let image_path = entry_path(&entry.id, "png").ok()?;
+                let thumbnail = entry_path(&entry.id, "thumb.png")
+                    .ok()
+                    .and_then(|path| image::open(path).ok())
+                    .and_then(|thumbnail| encode_png_data_url(&thumbnail.to_rgba8()).ok());
+                Some(GalleryItem {
+                    image_path: image_path.to_string_lossy().into_owned(),
+                    thumbnail,
+                    entry,
+                })
+            })
+            .collect()
+    }
+}
+
+fn new_id(simulation_type: &str, time: chrono::NaiveDateTime) -> String {
+    format!("{}-{}", simulation_type, time.format("%Y%m%d-%H%M%S-%3f"))
+}
+
+fn thumbnail_size(width: u32, height: u32) -> (u32, u32) {
+    let scale = THUMBNAIL_SIZE as f32 / width.max(height).max(1) as f32;
+    if scale >= 1.0 {
+        return (width, height);
+    }
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+fn gallery_dir() -> PathBuf {
+    get_settings_dir().join("gallery")
+}
+
+/// Ids come back from the frontend, so only names `new_id` can produce are
+/// turned into paths
+fn entry_path(id: &str, extension: &str) -> AppResult<PathBuf> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(SimulationError::InvalidParameter(format!(
+            "Invalid gallery entry id '{}'",
+            id
+        ))
+        .into());
+    }
+    Ok(gallery_dir().join(format!("{}.{}", id, extension)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_valid_entry_names() {
+        let time = chrono::NaiveDate::from_ymd_opt(2025, 3, 9)
+            .unwrap()
+            .and_hms_milli_opt(14, 5, 7, 42)
+            .unwrap();
+        let id = new_id("particle_life", time);
+        assert_eq!(id, "particle_life-20250309-140507-042");
+        assert!(entry_path(&id, "png").is_ok());
+        assert!(entry_path("../presets", "toml").is_err());
+        assert!(entry_path("", "toml").is_err());
+    }
+
+    #[test]
+    fn thumbnails_keep_the_aspect_ratio() {
+        assert_eq!(thumbnail_size(1920, 1080), (256, 144));
+        assert_eq!(thumbnail_size(1080, 1920), (144, 256));
+        assert_eq!(thumbnail_size(200, 100), (200, 100));
+    }
+}
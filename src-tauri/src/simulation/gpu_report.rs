@@ -0,0 +1,247 @@
+//! What the GPU can do, in one place, for users working out why a
+//! simulation runs reduced or won't start at a high particle count.
+//!
+//! The report lists the adapter, the limits Vizza asked the device for next
+//! to what the adapter would allow, every adapter feature and surface format,
+//! and whether each optional feature Vizza makes use of is available.
+
+use serde::Serialize;
+
+use crate::simulations::shared::gpu_budget::{self, GpuMemoryUsage};
+use crate::simulations::shared::gpu_tier::{GpuTier, REDUCED_STORAGE_BUFFER_SIZE};
+
+const MIB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdapterReport {
+    pub name: String,
+    pub vendor: u32,
+    pub device: u32,
+    pub device_type: String,
+    pub backend: String,
+    pub driver: String,
+    pub driver_info: String,
+}
+
+/// The limits that bound how large a simulation can get
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LimitsReport {
+    pub max_texture_dimension_2d: u32,
+    pub max_texture_dimension_3d: u32,
+    pub max_buffer_size: u64,
+    pub max_storage_buffer_binding_size: u32,
+    pub max_uniform_buffer_binding_size: u32,
+    pub max_storage_buffers_per_shader_stage: u32,
+    pub max_bind_groups: u32,
+    pub max_compute_workgroup_size_x: u32,
+    pub max_compute_invocations_per_workgroup: u32,
+    pub max_compute_workgroups_per_dimension: u32,
+}
+
+impl From<&wgpu::Limits> for LimitsReport {
+    fn from(limits: &wgpu::Limits) -> Self {
+        Self {
+            max_texture_dimension_2d: limits.max_texture_dimension_2d,
+            max_texture_dimension_3d: limits.max_texture_dimension_3d,
+            max_buffer_size: limits.max_buffer_size,
+            max_storage_buffer_binding_size: limits.max_storage_buffer_binding_size,
+            max_uniform_buffer_binding_size: limits.max_uniform_buffer_binding_size,
+            max_storage_buffers_per_shader_stage: limits.max_storage_buffers_per_shader_stage,
+            max_bind_groups: limits.max_bind_groups,
+            max_compute_workgroup_size_x: limits.max_compute_workgroup_size_x,
+            max_compute_invocations_per_workgroup: limits.max_compute_invocations_per_workgroup,
+            max_compute_workgroups_per_dimension: limits.max_compute_workgroups_per_dimension,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OptionalFeature {
+    pub name: &'static str,
+    pub available: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuReport {
+    pub adapter: AdapterReport,
+    pub tier: GpuTier,
+    /// What the device was created with, which simulations are held to
+    pub limits: LimitsReport,
+    /// What the adapter would allow
+    pub adapter_limits: LimitsReport,
+    pub features: Vec<String>,
+    pub surface_format: String,
+    pub surface_formats: Vec<String>,
+    pub optional_features: Vec<OptionalFeature>,
+    pub memory: GpuMemoryUsage,
+}
+
+/// Everything the report is built from, gathered from the live adapter and
+/// device
+pub struct GpuCapabilities<'a> {
+    pub adapter_info: &'a wgpu::AdapterInfo,
+    pub adapter_features: wgpu::Features,
+    pub adapter_limits: wgpu::Limits,
+    pub device_limits: wgpu::Limits,
+    pub surface_format: wgpu::TextureFormat,
+    /// Of the surface format, for multisampling
+    pub surface_format_features: wgpu::TextureFormatFeatures,
+    pub surface_formats: Vec<wgpu::TextureFormat>,
+}
+
+impl GpuReport {
+    pub fn new(capabilities: &GpuCapabilities) -> Self {
+        let info = capabilities.adapter_info;
+        let tier = GpuTier::detect(info, &capabilities.device_limits);
+        Self {
+            adapter: AdapterReport {
+                name: info.name.clone(),
+                vendor: info.vendor,
+                device: info.device,
+                device_type: format!("{:?}", info.device_type),
+                backend: info.backend.to_string(),
+                driver: info.driver.clone(),
+                driver_info: info.driver_info.clone(),
+            },
+            tier,
+            limits: LimitsReport::from(&capabilities.device_limits),
+            adapter_limits: LimitsReport::from(&capabilities.adapter_limits),
+            features: capabilities
+                .adapter_features
+                .iter_names()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            surface_format: format!("{:?}", capabilities.surface_format),
+            surface_formats: capabilities
+                .surface_formats
+                .iter()
+                .map(|format| format!("{:?}", format))
+                .collect(),
+            optional_features: optional_features(capabilities, tier),
+            memory: gpu_budget::usage(),
+        }
+    }
+}
+
+fn optional_features(capabilities: &GpuCapabilities, tier: GpuTier) -> Vec<OptionalFeature> {
+    let features = capabilities.adapter_features;
+    let storage_binding = capabilities.device_limits.max_storage_buffer_binding_size as u64;
+    let msaa_supported = capabilities
+        .surface_format_features
+        .flags
+        .sample_count_supported(4);
+
+    vec![
+        OptionalFeature {
+            name: "MSAA 4x",
+            available: msaa_supported && tier.msaa_sample_count() == 4,
+            detail: match (msaa_supported, tier) {
+                (false, _) => format!(
+                    "{:?} can't be multisampled 4x on this adapter",
+                    capabilities.surface_format
+                ),
+                (true, GpuTier::Reduced) => {
+                    "Supported, but off because the GPU runs the reduced tier".to_string()
+                }
+                (true, GpuTier::Full) => "Particles are drawn antialiased".to_string(),
+            },
+        },
+        OptionalFeature {
+            name: "f16 shaders",
+            available: features.contains(wgpu::Features::SHADER_F16),
+            detail: "Half precision arithmetic in shaders".to_string(),
+        },
+        OptionalFeature {
+            name: "Timestamp queries",
+            available: features.contains(wgpu::Features::TIMESTAMP_QUERY),
+            detail: "GPU timings for profiling".to_string(),
+        },
+        OptionalFeature {
+            name: "Large buffers",
+            available: storage_binding >= REDUCED_STORAGE_BUFFER_SIZE as u64,
+            detail: format!(
+                "Up to {} MiB per storage buffer, which caps particle and agent counts",
+                storage_binding / MIB
+            ),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(
+        adapter_info: &wgpu::AdapterInfo,
+        limits: wgpu::Limits,
+        flags: wgpu::TextureFormatFeatureFlags,
+    ) -> GpuCapabilities<'_> {
+        GpuCapabilities {
+            adapter_info,
+            adapter_features: wgpu::Features::TIMESTAMP_QUERY,
+            adapter_limits: limits.clone(),
+            device_limits: limits,
+            surface_format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            surface_format_features: wgpu::TextureFormatFeatures {
+                allowed_usages: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                flags,
+            },
+            surface_formats: vec![wgpu::TextureFormat::Bgra8UnormSrgb],
+        }
+    }
+
+    fn adapter(device_type: wgpu::DeviceType) -> wgpu::AdapterInfo {
+        wgpu::AdapterInfo {
+            name: "Test Adapter".to_string(),
+            vendor: 0,
+            device: 0,
+            device_type,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend: wgpu::Backend::Vulkan,
+        }
+    }
+
+    fn available(report: &GpuReport, name: &str) -> bool {
+        report
+            .optional_features
+            .iter()
+            .find(|feature| feature.name == name)
+            .is_some_and(|feature| feature.available)
+    }
+
+    #[test]
+    fn a_full_gpu_has_everything_it_supports() {
+        let info = adapter(wgpu::DeviceType::DiscreteGpu);
+        let limits = wgpu::Limits {
+            max_storage_buffer_binding_size: 2_147_483_647,
+            ..wgpu::Limits::default()
+        };
+        let report = GpuReport::new(&capabilities(
+            &info,
+            limits,
+            wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X4,
+        ));
+        assert_eq!(report.tier, GpuTier::Full);
+        assert!(available(&report, "MSAA 4x"));
+        assert!(available(&report, "Timestamp queries"));
+        assert!(available(&report, "Large buffers"));
+        assert!(!available(&report, "f16 shaders"));
+        assert_eq!(report.features, vec!["TIMESTAMP_QUERY"]);
+    }
+
+    #[test]
+    fn downlevel_limits_explain_the_reduced_tier() {
+        let info = adapter(wgpu::DeviceType::IntegratedGpu);
+        let report = GpuReport::new(&capabilities(
+            &info,
+            wgpu::Limits::downlevel_defaults(),
+            wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X4,
+        ));
+        assert_eq!(report.tier, GpuTier::Reduced);
+        assert!(!available(&report, "MSAA 4x"));
+        assert!(!available(&report, "Large buffers"));
+        assert_eq!(report.limits.max_texture_dimension_2d, 2048);
+    }
+}
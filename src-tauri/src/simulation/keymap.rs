@@ -0,0 +1,181 @@
+//! Keyboard shortcuts for the actions that work in every simulation.
+//!
+//! Shortcuts are stored as normalized strings like `Ctrl+Shift+s` or `Space`,
+//! so the frontend can forward whatever key combination it sees and the
+//! keymap decides what it means. The bindings are kept in the app settings
+//! and can be remapped at runtime with `set_keybinding`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::error::{AppResult, SimulationError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum KeyAction {
+    ToggleGui,
+    TogglePause,
+    Step,
+    Screenshot,
+    NextPreset,
+    ResetCamera,
+    ToggleOverlay,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 7] = [
+        KeyAction::ToggleGui,
+        KeyAction::TogglePause,
+        KeyAction::Step,
+        KeyAction::Screenshot,
+        KeyAction::NextPreset,
+        KeyAction::ResetCamera,
+        KeyAction::ToggleOverlay,
+    ];
+
+    fn default_shortcut(self) -> &'static str {
+        match self {
+            KeyAction::ToggleGui => "/",
+            KeyAction::TogglePause => "Space",
+            KeyAction::Step => ".",
+            KeyAction::Screenshot => "F12",
+            KeyAction::NextPreset => "n",
+            KeyAction::ResetCamera => "c",
+            KeyAction::ToggleOverlay => "Ctrl+Shift+o",
+        }
+    }
+}
+
+/// Modifiers in the order they appear in a normalized shortcut
+const MODIFIERS: [&str; 4] = ["Ctrl", "Alt", "Shift", "Meta"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Keymap {
+    bindings: BTreeMap<KeyAction, String>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: KeyAction::ALL
+                .iter()
+                .map(|&action| (action, action.default_shortcut().to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn bindings(&self) -> &BTreeMap<KeyAction, String> {
+        &self.bindings
+    }
+
+    /// The action bound to `shortcut`, which doesn't need to be normalized
+    pub fn action_for(&self, shortcut: &str) -> Option<KeyAction> {
+        let shortcut = normalize_shortcut(shortcut).ok()?;
+        self.bindings
+            .iter()
+            .find(|(_, bound)| **bound == shortcut)
+            .map(|(&action, _)| action)
+    }
+
+    /// Bind `action` to `shortcut`, or unbind it when `shortcut` is empty.
+    /// An action that already used the shortcut loses it, and is returned.
+    pub fn set(&mut self, action: KeyAction, shortcut: &str) -> AppResult<Option<KeyAction>> {
+        if shortcut.is_empty() {
+            self.bindings.remove(&action);
+            return Ok(None);
+        }
+
+        let shortcut = normalize_shortcut(shortcut)?;
+        let displaced = self.action_for(&shortcut).filter(|&bound| bound != action);
+        if let Some(displaced) = displaced {
+            self.bindings.remove(&displaced);
+        }
+        self.bindings.insert(action, shortcut);
+        Ok(displaced)
+    }
+}
+
+/// Put modifiers in a fixed order and lowercase single characters, so
+/// `shift+ctrl+S` and `Ctrl+Shift+s` are the same shortcut
+pub fn normalize_shortcut(shortcut: &str) -> AppResult<String> {
+    let invalid =
+        || SimulationError::InvalidParameter(format!("Invalid keyboard shortcut '{}'", shortcut));
+
+    // A trailing "+" is the plus key itself, as in "Ctrl++"
+    let (modifier_part, key) = match shortcut.strip_suffix("++") {
+        Some(rest) => (rest, "+"),
+        None => match shortcut.rsplit_once('+') {
+            Some((rest, key)) => (rest, key),
+            None => ("", shortcut),
+        },
+    };
+
+    let mut modifiers = [false; MODIFIERS.len()];
+    for modifier in modifier_part.split('+').filter(|part| !part.is_empty()) {
+        let index = match modifier.trim().to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => 0,
+            "alt" | "option" => 1,
+            "shift" => 2,
+            "meta" | "cmd" | "command" | "super" => 3,
+            _ => return Err(invalid().into()),
+        };
+        modifiers[index] = true;
+    }
+
+    let key = match key {
+        " " => "Space".to_string(),
+        key if key.chars().count() == 1 => key.to_lowercase(),
+        key => {
+            let key = key.trim();
+            let mut chars = key.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => return Err(invalid().into()),
+            }
+        }
+    };
+
+    let mut parts: Vec<&str> = MODIFIERS
+        .iter()
+        .zip(modifiers)
+        .filter(|(_, held)| *held)
+        .map(|(name, _)| *name)
+        .collect();
+    parts.push(&key);
+    Ok(parts.join("+"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcuts_are_normalized() {
+        assert_eq!(normalize_shortcut("shift+ctrl+S").unwrap(), "Ctrl+Shift+s");
+        assert_eq!(normalize_shortcut(" ").unwrap(), "Space");
+        assert_eq!(normalize_shortcut("space").unwrap(), "Space");
+        assert_eq!(normalize_shortcut("Ctrl++").unwrap(), "Ctrl++");
+        assert_eq!(normalize_shortcut("cmd+f12").unwrap(), "Meta+F12");
+        assert_eq!(normalize_shortcut("ArrowUp").unwrap(), "Arrowup");
+        assert!(normalize_shortcut("Hyper+a").is_err());
+    }
+
+    #[test]
+    fn rebinding_takes_the_shortcut_from_other_actions() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.action_for("Space"), Some(KeyAction::TogglePause));
+
+        let displaced = keymap.set(KeyAction::Step, "space").unwrap();
+        assert_eq!(displaced, Some(KeyAction::TogglePause));
+        assert_eq!(keymap.action_for(" "), Some(KeyAction::Step));
+        assert!(!keymap.bindings().contains_key(&KeyAction::TogglePause));
+
+        keymap.set(KeyAction::Step, "").unwrap();
+        assert_eq!(keymap.action_for("Space"), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Kiosk mode for public installations.
+//!
+//! Started with `--kiosk`, the app runs fullscreen with the GUI hidden and
+//! visitors can only look and poke: every command outside a short list is
+//! refused before it reaches its handler, so a stray click, a key press or a
+//! script in the webview can't switch simulations, change settings or leave
+//! fullscreen. Dropped files and `vizza://` links, which don't arrive as
+//! commands, are turned away too. Reading state, the render loop's own calls
+//! and mouse interaction still go through. A simulation that fails to render is started
+//! again from scratch instead of being left on a broken frame.
+
+use tauri::Runtime;
+use tauri::ipc::Invoke;
+
+/// Commands that change something but stay available, besides starting the
+/// kiosk's simulation and the read-only `get_`, `check_` and `take_` ones
+const ALLOWED_COMMANDS: &[&str] = &[
+    "resume_simulation",
+    "render_frame",
+    "render_single_frame",
+    "handle_window_resize",
+    "handle_mouse_interaction",
+    "handle_mouse_interaction_screen",
+    "handle_mouse_release",
+    "update_cursor_position_screen",
+    "pan_camera",
+    "zoom_camera",
+    "zoom_camera_to_cursor",
+    "reset_camera",
+    "subscribe_preview_stream",
+    "unsubscribe_preview_stream",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Kiosk {
+    /// The only simulation that may be started, or any when `None`
+    pub simulation: Option<String>,
+}
+
+impl Kiosk {
+    pub fn allows_command(command: &str) -> bool {
+        let read_only = ["get_", "check_", "take_"]
+            .iter()
+            .any(|prefix| command.starts_with(prefix));
+        // Which simulation is checked when it actually starts
+        let starts_simulation = command.starts_with("start_") && command.ends_with("simulation");
+        read_only || starts_simulation || ALLOWED_COMMANDS.contains(&command)
+    }
+
+    pub fn allows_simulation(&self, simulation_type: &str) -> bool {
+        self.simulation
+            .as_deref()
+            .is_none_or(|simulation| simulation == simulation_type)
+    }
+}
+
+/// Wrap the app's command handler so that, with kiosk mode on, commands it
+/// doesn't allow are rejected without running
+pub fn gate<R: Runtime>(
+    enabled: bool,
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        if enabled && !Kiosk::allows_command(invoke.message.command()) {
+            tracing::debug!("Refused {} in kiosk mode", invoke.message.command());
+            invoke.resolver.reject("Not available in kiosk mode");
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+/// Whether to turn away something that would change the show without going
+/// through a command, like a dropped file or an opened link, which [`gate`]
+/// never sees. Logs what was refused.
+pub fn refuses(kiosk: Option<&Kiosk>, what: &str) -> bool {
+    if kiosk.is_some() {
+        tracing::debug!("Refused {} in kiosk mode", what);
+    }
+    kiosk.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_looking_and_interacting_are_allowed() {
+        for command in [
+            "get_current_settings",
+            "take_pending_shared_configuration",
+            "start_simulation",
+            "start_moire_simulation",
+            "render_frame",
+            "handle_mouse_interaction",
+        ] {
+            assert!(Kiosk::allows_command(command), "{}", command);
+        }
+        for command in [
+            "update_simulation_setting",
+            "apply_preset",
+            "save_app_settings",
+            "toggle_gui",
+            "toggle_fullscreen",
+            "destroy_simulation",
+            "handle_key_press",
+            "start_recording",
+        ] {
+            assert!(!Kiosk::allows_command(command), "{}", command);
+        }
+    }
+
+    #[test]
+    fn dropped_files_and_deep_links_are_refused() {
+        let kiosk = Kiosk::default();
+        assert!(refuses(Some(&kiosk), "dropped file"));
+        assert!(refuses(Some(&kiosk), "deep link"));
+        assert!(!refuses(None, "dropped file"));
+        assert!(!refuses(None, "deep link"));
+    }
+
+    #[test]
+    fn a_kiosk_can_be_held_to_one_simulation() {
+        let kiosk = Kiosk {
+            simulation: Some("gray_scott".to_string()),
+        };
+        assert!(kiosk.allows_simulation("gray_scott"));
+        assert!(!kiosk.allows_simulation("slime_mold"));
+        assert!(Kiosk::default().allows_simulation("slime_mold"));
+    }
+}
@@ -0,0 +1,196 @@
+//! Command-line arguments for launching straight into a simulation.
+//!
+//! `vizza --sim gray_scott --preset Mitosis --fullscreen --fps 60` is read
+//! before the window is shown. The window and frame rate are set up directly;
+//! the simulation and preset take the same route as a deep link, parked on the
+//! simulation manager for the frontend to start once it has loaded. Anything
+//! that isn't a flag is left alone, since deep links reach the app as
+//! arguments on Linux and Windows. `--kiosk` adds the lockdown described in
+//! [`super::kiosk`].
+
+use super::SimulationManager;
+use super::kiosk::Kiosk;
+use super::previews::PREVIEWABLE_SIMULATIONS;
+use super::settings_codec::SharedConfiguration;
+use super::watchdog::WatchdogConfig;
+
+pub const USAGE: &str = "\
+Usage: vizza [OPTIONS]
+
+Options:
+  --sim <SIMULATION>  Start this simulation, e.g. gray_scott
+  --preset <PRESET>   Apply this preset to it, e.g. Mitosis
+  --fullscreen        Start in fullscreen
+  --fps <FPS>         Limit the frame rate
+  --kiosk             Lock the app down for a public installation: fullscreen,
+                      no GUI and no changes, with the simulation restarted if
+                      it fails
+  -h, --help          Show this message";
+
+pub const MAX_FPS: u32 = 1000;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchArgs {
+    pub simulation: Option<String>,
+    pub preset: Option<String>,
+    pub fullscreen: bool,
+    pub fps: Option<u32>,
+    pub kiosk: bool,
+    pub help: bool,
+}
+
+impl LaunchArgs {
+    /// Parse the arguments after the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut launch = LaunchArgs::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Both `--sim gray_scott` and `--sim=gray_scott`
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} needs a value", flag))
+            };
+            match flag.as_str() {
+                "--sim" => {
+                    let simulation = value()?;
+                    if !PREVIEWABLE_SIMULATIONS.contains(&simulation.as_str()) {
+                        return Err(format!(
+                            "Unknown simulation '{}', expected one of: {}",
+                            simulation,
+                            PREVIEWABLE_SIMULATIONS.join(", ")
+                        ));
+                    }
+                    launch.simulation = Some(simulation);
+                }
+                "--preset" => launch.preset = Some(value()?),
+                "--fps" => {
+                    let text = value()?;
+                    let fps = text
+                        .parse()
+                        .ok()
+                        .filter(|fps| (1..=MAX_FPS).contains(fps))
+                        .ok_or_else(|| {
+                            format!("--fps must be a number from 1 to {}, got {}", MAX_FPS, text)
+                        })?;
+                    launch.fps = Some(fps);
+                }
+                "--fullscreen" => launch.fullscreen = true,
+                "--kiosk" => {
+                    launch.kiosk = true;
+                    launch.fullscreen = true;
+                }
+                "-h" | "--help" => launch.help = true,
+                _ if flag.starts_with('-') => return Err(format!("Unknown option {}", flag)),
+                _ => {}
+            }
+        }
+        if launch.preset.is_some() && launch.simulation.is_none() {
+            return Err("--preset needs --sim to say which simulation it's for".to_string());
+        }
+        Ok(launch)
+    }
+
+    /// The simulation and preset to start, in the form the frontend already
+    /// takes from deep links
+    pub fn configuration(&self) -> Option<SharedConfiguration> {
+        Some(SharedConfiguration {
+            simulation_type: self.simulation.clone()?,
+            preset: self.preset.clone(),
+            settings: None,
+            camera: None,
+            color_scheme: None,
+        })
+    }
+
+    /// Set up `manager` before the window is shown
+    pub fn configure(&self, manager: &mut SimulationManager) {
+        manager.pending_shared_configuration = self.configuration();
+        if let Some(fps) = self.fps {
+            manager.set_fps_limit(true, fps);
+        }
+        if !self.kiosk {
+            return;
+        }
+        manager.kiosk = Some(Kiosk {
+            simulation: self.simulation.clone(),
+        });
+        // The frontend can't apply presets in kiosk mode, so the manager
+        // applies it every time the simulation starts, restarts included
+        if let (Some(simulation), Some(preset)) = (&self.simulation, &self.preset) {
+            manager
+                .default_presets
+                .insert(simulation.clone(), preset.clone());
+            if let Some(configuration) = &mut manager.pending_shared_configuration {
+                configuration.preset = None;
+            }
+        }
+        manager.watchdog.set_config(WatchdogConfig {
+            enabled: true,
+            auto_recover: true,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<LaunchArgs, String> {
+        LaunchArgs::parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn all_options_are_read() {
+        let launch = parse("--sim gray_scott --preset=Mitosis --fullscreen --fps 60").unwrap();
+        assert_eq!(
+            launch,
+            LaunchArgs {
+                simulation: Some("gray_scott".to_string()),
+                preset: Some("Mitosis".to_string()),
+                fullscreen: true,
+                fps: Some(60),
+                kiosk: false,
+                help: false,
+            }
+        );
+        let configuration = launch.configuration().unwrap();
+        assert_eq!(configuration.simulation_type, "gray_scott");
+        assert_eq!(configuration.preset.as_deref(), Some("Mitosis"));
+    }
+
+    #[test]
+    fn no_arguments_launch_as_usual() {
+        let launch = parse("").unwrap();
+        assert_eq!(launch, LaunchArgs::default());
+        assert!(launch.configuration().is_none());
+        // Deep links arrive as plain arguments
+        assert_eq!(
+            parse("vizza://open?code=VZ1:abc").unwrap(),
+            LaunchArgs::default()
+        );
+    }
+
+    #[test]
+    fn kiosk_mode_is_always_fullscreen() {
+        let launch = parse("--kiosk --sim gray_scott").unwrap();
+        assert!(launch.kiosk && launch.fullscreen);
+    }
+
+    #[test]
+    fn bad_arguments_are_reported() {
+        assert!(parse("--sim").is_err());
+        assert!(parse("--sim not_a_simulation").is_err());
+        assert!(parse("--preset Mitosis").is_err());
+        assert!(parse("--fps 0").is_err());
+        assert!(parse("--fps fast").is_err());
+        assert!(parse("--windowed").is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! Recorded sequences of setting changes, preset applies and camera moves.
+//!
+//! While recording, the simulation manager notes every action it performs
+//! together with the time since recording started. The finished macro is
+//! saved as TOML next to the app settings, and playing it back repeats the
+//! same actions on the same schedule against whatever simulation is running.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use wgpu::{Device, Queue};
+
+use super::SimulationManager;
+use super::preset_manager::sanitize_filename;
+use crate::commands::get_settings_dir;
+use crate::error::{AppError, AppResult, SimulationError};
+
+/// Emitted with the macro name once playback reaches the last step
+pub const MACRO_FINISHED_EVENT: &str = "macro-playback-finished";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum MacroAction {
+    UpdateSetting {
+        name: String,
+        value: serde_json::Value,
+    },
+    ApplyPreset {
+        name: String,
+    },
+    PanCamera {
+        delta_x: f32,
+        delta_y: f32,
+    },
+    ZoomCamera {
+        delta: f32,
+    },
+    ZoomCameraToCursor {
+        delta: f32,
+        cursor_x: f32,
+        cursor_y: f32,
+    },
+    ResetCamera,
+}
+
+impl MacroAction {
+    pub fn apply(
+        &self,
+        manager: &mut SimulationManager,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        match self {
+            MacroAction::UpdateSetting { name, value } => {
+                manager.update_setting(name, value.clone(), device, queue)?;
+            }
+            MacroAction::ApplyPreset { name } => manager.apply_preset(name, device, queue)?,
+            MacroAction::PanCamera { delta_x, delta_y } => manager.pan_camera(*delta_x, *delta_y),
+            MacroAction::ZoomCamera { delta } => manager.zoom_camera(*delta),
+            MacroAction::ZoomCameraToCursor {
+                delta,
+                cursor_x,
+                cursor_y,
+            } => manager.zoom_camera_to_cursor(*delta, *cursor_x, *cursor_y),
+            MacroAction::ResetCamera => manager.reset_camera(),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroStep {
+    /// Seconds since the start of the macro
+    pub at_seconds: f32,
+    pub action: MacroAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Macro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+impl Macro {
+    pub fn duration_seconds(&self) -> f32 {
+        self.steps.last().map_or(0.0, |step| step.at_seconds)
+    }
+
+    pub fn save(&self) -> AppResult<PathBuf> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize macro: {}", e)))?;
+        std::fs::create_dir_all(macros_dir())?;
+        let path = macro_path(&self.name);
+        std::fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    pub fn load(name: &str) -> AppResult<Self> {
+        let path = macro_path(name);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            SimulationError::InvalidParameter(format!("Failed to read macro '{}': {}", name, e))
+        })?;
+        toml::from_str(&content)
+            .map_err(|e| AppError::Unknown(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+
+    pub fn delete(name: &str) -> AppResult<()> {
+        std::fs::remove_file(macro_path(name))?;
+        Ok(())
+    }
+
+    /// Names of the saved macros, sorted
+    pub fn list() -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(macros_dir()) else {
+            return vec![];
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("toml"))
+            .filter_map(|path| {
+                // The file name is sanitized, the name inside is what the user typed
+                let content = std::fs::read_to_string(&path).ok()?;
+                toml::from_str::<Macro>(&content)
+                    .map(|recorded| recorded.name)
+                    .ok()
+            })
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MacroRecorder {
+    recording: Option<Recording>,
+}
+
+#[derive(Debug)]
+struct Recording {
+    name: String,
+    started: Instant,
+    steps: Vec<MacroStep>,
+}
+
+impl MacroRecorder {
+    pub fn start(&mut self, name: &str) -> AppResult<()> {
+        if name.trim().is_empty() {
+            return Err(SimulationError::InvalidParameter(
+                "Macro name cannot be empty".to_string(),
+            )
+            .into());
+        }
+        self.recording = Some(Recording {
+            name: name.to_string(),
+            started: Instant::now(),
+            steps: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// The recorded macro, or `None` if nothing was being recorded
+    pub fn stop(&mut self) -> Option<Macro> {
+        self.recording.take().map(|recording| Macro {
+            name: recording.name,
+            steps: recording.steps,
+        })
+    }
+
+    pub fn record(&mut self, action: MacroAction) {
+        if let Some(recording) = &mut self.recording {
+            recording.steps.push(MacroStep {
+                at_seconds: recording.started.elapsed().as_secs_f32(),
+                action,
+            });
+        }
+    }
+}
+
+/// Run the steps of `recorded` at their recorded times. Steps that fail are
+/// logged and skipped, so one missing preset doesn't end the performance.
+pub async fn play(app: AppHandle, recorded: Macro) {
+    let started = tokio::time::Instant::now();
+    for step in &recorded.steps {
+        let at = Duration::try_from_secs_f32(step.at_seconds).unwrap_or_default();
+        tokio::time::sleep_until(started + at).await;
+
+        let (device, queue) = {
+            let gpu_context = app.state::<Arc<tokio::sync::Mutex<crate::GpuContext>>>();
+            let gpu_ctx = gpu_context.lock().await;
+            (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+        };
+        let manager = app.state::<Arc<tokio::sync::Mutex<SimulationManager>>>();
+        if let Err(e) = step
+            .action
+            .apply(&mut *manager.lock().await, &device, &queue)
+        {
+            tracing::warn!(
+                "Macro '{}' step at {:.2}s failed: {}",
+                recorded.name,
+                step.at_seconds,
+                e
+            );
+        }
+    }
+
+    if let Err(e) = app.emit(MACRO_FINISHED_EVENT, &recorded.name) {
+        tracing::warn!("Failed to emit {} event: {}", MACRO_FINISHED_EVENT, e);
+    }
+}
+
+fn macros_dir() -> PathBuf {
+    get_settings_dir().join("macros")
+}
+
+fn macro_path(name: &str) -> PathBuf {
+    macros_dir().join(format!("{}.toml", sanitize_filename(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorder_only_keeps_steps_while_recording() {
+        let mut recorder = MacroRecorder::default();
+        recorder.record(MacroAction::ResetCamera);
+        assert!(recorder.stop().is_none());
+
+        recorder.start("Sweep").unwrap();
+        recorder.record(MacroAction::ZoomCamera { delta: 0.5 });
+        recorder.record(MacroAction::ResetCamera);
+        let recorded = recorder.stop().unwrap();
+        assert_eq!(recorded.name, "Sweep");
+        assert_eq!(recorded.steps.len(), 2);
+        assert!(recorded.steps[0].at_seconds <= recorded.steps[1].at_seconds);
+        assert!(recorder.stop().is_none());
+    }
+
+    #[test]
+    fn macros_round_trip_through_toml() {
+        let recorded = Macro {
+            name: "Drift".to_string(),
+            steps: vec![
+                MacroStep {
+                    at_seconds: 0.0,
+                    action: MacroAction::ApplyPreset {
+                        name: "Default".to_string(),
+                    },
+                },
+                MacroStep {
+                    at_seconds: 1.5,
+                    action: MacroAction::UpdateSetting {
+                        name: "decay_rate".to_string(),
+                        value: serde_json::json!(0.25),
+                    },
+                },
+                MacroStep {
+                    at_seconds: 2.0,
+                    action: MacroAction::ResetCamera,
+                },
+            ],
+        };
+        let content = toml::to_string_pretty(&recorded).unwrap();
+        assert_eq!(toml::from_str::<Macro>(&content).unwrap(), recorded);
+        assert_eq!(recorded.duration_seconds(), 2.0);
+    }
+}
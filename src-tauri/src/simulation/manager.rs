@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::time::{Duration, Instant};
@@ -5,8 +6,36 @@ use tauri::{AppHandle, Emitter, Manager};
 use wgpu::{Device, Queue, SurfaceConfiguration};
 
 use crate::commands::AppSettings;
-use crate::error::{AppError, AppResult, ColorSchemeError};
+use crate::error::{AppError, AppResult, ColorSchemeError, GpuError, LutResult, SimulationError};
+use crate::simulation::annotations::PresetNotes;
+use crate::simulation::audio_reactive::{AudioReactive, AudioReactiveConfig};
+use crate::simulation::autopilot::{Autopilot, AutopilotConfig};
+use crate::simulation::canvas::Canvas;
+use crate::simulation::color_cycle::{ColorCycle, ColorCycler};
+use crate::simulation::events::{EventBus, SimulationEvent};
+use crate::simulation::evolution::{Evolution, EvolutionConfig, Generation};
+use crate::simulation::keymap::Keymap;
+use crate::simulation::kiosk::Kiosk;
+use crate::simulation::macros::{MacroAction, MacroRecorder};
+use crate::simulation::master_effects::{
+    MAX_RENDER_SCALE, MIN_RENDER_SCALE, MasterBus, MasterEffects,
+};
+use crate::simulation::overlay::OverlayMode;
+use crate::simulation::panes::{PaneBlend, PaneInfo, PaneLayout, Panes};
 use crate::simulation::preset_manager::SimulationPresetManager;
+use crate::simulation::preview_stream::{PreviewFrame, PreviewStream};
+use crate::simulation::previews::SimulationPreviews;
+use crate::simulation::recording::{Recording, RecordingConfig, RecordingSummary};
+use crate::simulation::replay::{Replay, ReplayInput, ReplayPlayer, ReplayRecorder};
+use crate::simulation::seeds::Seed;
+use crate::simulation::settings_codec::{
+    CameraView, SharedColorScheme, SharedConfiguration, merge_settings,
+};
+use crate::simulation::similarity::{self, SimilarPreset};
+use crate::simulation::supersampling::{self, Supersampler, Supersampling, ViewFingerprint};
+use crate::simulation::transition::{MAX_TRANSITION_SECONDS, SettingTransition};
+use crate::simulation::watchdog::{HEALTH_WARNING_EVENT, Watchdog, WatchdogConfig};
+use crate::simulation::workspace::Workspace;
 use crate::simulations::gray_scott::{GrayScottModel, settings::Settings as GrayScottSettings};
 use crate::simulations::particle_life::{
     ParticleLifeModel, settings::Settings as ParticleLifeSettings,
@@ -14,14 +43,25 @@ use crate::simulations::particle_life::{
 use crate::simulations::primordial_particles::{
     PrimordialParticlesModel, settings::Settings as PrimordialParticlesSettings,
 };
-use crate::simulations::shared::{BackgroundColorMode, ColorScheme};
 use crate::simulations::shared::{
-    ColorSchemeManager, SimulationColorSchemeManager, coordinates::ScreenCoords,
+    BackgroundColorMode, BackgroundLayer, ColorScheme, ColorScript, CursorForceField, CursorMode,
+    EnvironmentField, FrameCapture, GlobalForce, RandomizeOptions, RewindBuffer, RewindConfig,
+    RewindHistory, SimulationSnapshot, StrengthCurve, gpu_budget, gpu_tier::GpuTier, randomize,
+    snapshot,
+};
+use crate::simulations::shared::{
+    ColorSchemeManager, SimulationColorSchemeManager, camera::Camera, coordinates::ScreenCoords,
 };
 use crate::simulations::slime_mold::{SlimeMoldModel, settings::Settings as SlimeMoldSettings};
 use crate::simulations::traits::{Simulation, SimulationType};
 use crate::simulations::voronoi_ca::simulation::VoronoiCASimulation;
 
+/// Most steps one fast-forward runs
+const MAX_FAST_FORWARD_STEPS: u32 = 100_000;
+
+/// Steps a fast-forward hands the GPU before waiting for it to finish them
+const FAST_FORWARD_BATCH: u32 = 64;
+
 pub struct SimulationManager {
     pub current_simulation: Option<SimulationType>,
     pub preset_manager: SimulationPresetManager,
@@ -35,6 +75,59 @@ pub struct SimulationManager {
     // When paused, render-loop will update the simulation for this many frames then return to paused rendering
     pub step_frames_pending: Arc<AtomicU32>,
     pub app_settings: Arc<AppSettings>,
+    // Main menu thumbnails, dropped once a real simulation starts
+    pub previews: SimulationPreviews,
+    // Last preset applied to the current simulation, for export metadata
+    pub current_preset: Option<String>,
+    // Last seed the current world was grown from with `reseed`, for its name
+    pub current_seed: Option<Seed>,
+    // Configuration from a deep link, waiting for the frontend to start its simulation
+    pub pending_shared_configuration: Option<SharedConfiguration>,
+    // Autosave left by the last session if it didn't exit cleanly
+    pub crash_recovery: Option<Workspace>,
+    // Recent GPU snapshots of the running simulation, when rewinding is enabled
+    pub rewind: RewindBuffer,
+    // Extra simulations shown next to the current one, which is the focused pane
+    pub panes: Panes,
+    // Post effects over the whole composited frame
+    pub master_bus: MasterBus,
+    // Periodic thumbnails of the running simulation for the webview
+    pub preview_stream: Option<PreviewStream>,
+    // Video or PNG sequence being recorded from the running simulation
+    pub recording: Option<Recording>,
+    // Keyboard shortcuts, kept in sync with the app settings
+    pub keymap: Keymap,
+    // Preset to start each simulation type with, kept in sync with the app settings
+    pub default_presets: BTreeMap<String, String>,
+    // Color script run over each simulation type's color scheme, kept in sync with the app settings
+    pub color_scripts: BTreeMap<String, String>,
+    // Notes the actions below while a macro is being recorded
+    pub macro_recorder: MacroRecorder,
+    pub macro_playback: Option<tauri::async_runtime::JoinHandle<()>>,
+    // Notes input frame by frame while a replay is being recorded
+    pub replay_recorder: ReplayRecorder,
+    // Replay being played back, which stands in for live mouse input
+    pub replay_player: Option<ReplayPlayer>,
+    // Looks for NaNs and other broken states while the simulation updates
+    pub watchdog: Watchdog,
+    // Lifecycle events for the frontend
+    pub events: EventBus,
+    // Palette cycling of the running simulation's color scheme
+    pub color_cycler: ColorCycler,
+    // Transparent, click-through window settings
+    pub overlay: OverlayMode,
+    // Progressive supersampling of the paused view
+    pub supersampler: Supersampler,
+    // Interactive evolution of the running simulation's settings
+    pub evolution: Option<Evolution>,
+    // Slow random drift of the running simulation's settings
+    pub autopilot: Autopilot,
+    // Settings moved by bands of live audio
+    pub audio_reactive: AudioReactive,
+    // Settings easing toward the last preset applied with a transition
+    pub transition: Option<SettingTransition>,
+    // Lockdown for public installations, set from the command line
+    pub kiosk: Option<Kiosk>,
 }
 
 impl SimulationManager {
@@ -42,6 +135,17 @@ impl SimulationManager {
         // Simulations start paused to prevent race conditions between initialization
         // and render loop startup. They are automatically unpaused after successful
         // initialization to ensure all GPU resources and state are ready.
+        let rewind = RewindBuffer::new(RewindConfig::from_app_settings(&app_settings));
+        let keymap = app_settings.keybindings.clone();
+        let default_presets = app_settings.default_presets.clone();
+        let color_scripts = app_settings.color_scripts.clone();
+        let watchdog = Watchdog::new(WatchdogConfig::from_app_settings(&app_settings));
+        let mut master_bus = MasterBus::new();
+        master_bus.set_render_scale(
+            app_settings
+                .render_scale
+                .clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE),
+        );
         Self {
             current_simulation: None,
             preset_manager: SimulationPresetManager::new(),
@@ -53,6 +157,33 @@ impl SimulationManager {
             is_paused: Arc::new(AtomicBool::new(true)), // Start paused to avoid race condition
             step_frames_pending: Arc::new(AtomicU32::new(0)),
             app_settings,
+            previews: SimulationPreviews::new(),
+            current_preset: None,
+            current_seed: None,
+            pending_shared_configuration: None,
+            crash_recovery: None,
+            rewind,
+            panes: Panes::new(),
+            master_bus,
+            preview_stream: None,
+            recording: None,
+            keymap,
+            default_presets,
+            color_scripts,
+            macro_recorder: MacroRecorder::default(),
+            macro_playback: None,
+            replay_recorder: ReplayRecorder::default(),
+            replay_player: None,
+            watchdog,
+            events: EventBus::default(),
+            color_cycler: ColorCycler::default(),
+            overlay: OverlayMode::default(),
+            supersampler: Supersampler::default(),
+            evolution: None,
+            autopilot: Autopilot::default(),
+            audio_reactive: AudioReactive::default(),
+            transition: None,
+            kiosk: None,
         }
     }
 
@@ -182,6 +313,39 @@ impl SimulationManager {
         }
     }
 
+    /// Get mutable reference to Gravitational Lensing simulation if it's the current simulation
+    pub fn lensing_simulation_mut(
+        &mut self,
+    ) -> Result<&mut crate::simulations::lensing::LensingModel, String> {
+        match &mut self.current_simulation {
+            Some(SimulationType::Lensing(sim)) => Ok(sim),
+            Some(_) => Err("No Gravitational Lensing simulation running".to_string()),
+            None => Err("No simulation running".to_string()),
+        }
+    }
+
+    /// Get immutable reference to Stippling simulation if it's the current simulation
+    pub fn stippling_simulation(
+        &self,
+    ) -> Result<&crate::simulations::stippling::StipplingModel, String> {
+        match &self.current_simulation {
+            Some(SimulationType::Stippling(sim)) => Ok(sim),
+            Some(_) => Err("No Stippling simulation running".to_string()),
+            None => Err("No simulation running".to_string()),
+        }
+    }
+
+    /// Get mutable reference to Stippling simulation if it's the current simulation
+    pub fn stippling_simulation_mut(
+        &mut self,
+    ) -> Result<&mut crate::simulations::stippling::StipplingModel, String> {
+        match &mut self.current_simulation {
+            Some(SimulationType::Stippling(sim)) => Ok(sim),
+            Some(_) => Err("No Stippling simulation running".to_string()),
+            None => Err("No simulation running".to_string()),
+        }
+    }
+
     /// Get immutable reference to Voronoi CA simulation if it's the current simulation
     pub fn voronoi_ca_simulation(
         &self,
@@ -232,16 +396,46 @@ impl SimulationManager {
         surface_config: &SurfaceConfiguration,
         adapter_info: &wgpu::AdapterInfo,
     ) -> AppResult<()> {
-        match simulation_type.as_str() {
+        if let Some(kiosk) = &self.kiosk
+            && !kiosk.allows_simulation(&simulation_type)
+        {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Kiosk mode can't start {}",
+                simulation_type
+            ))
+            .into());
+        }
+        if let Some(previous) = &self.current_simulation {
+            self.events.publish(SimulationEvent::Destroyed {
+                simulation_type: previous.type_name().to_string(),
+            });
+        }
+        // Previews are only shown on the main menu, free their GPU memory
+        self.previews.clear();
+        self.on_focus_changed();
+        self.panes.clear();
+
+        // Simulations size their textures for the scene, not the surface
+        self.master_bus.resize(device, surface_config);
+        let surface_config = &self.master_bus.scene_config(surface_config);
+
+        // Catch shader, pipeline and allocation failures instead of leaving
+        // them to wgpu's uncaptured error handler, which panics. Error scopes
+        // belong to the thread, so nothing is awaited until they're popped.
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let started: AppResult<()> = match simulation_type.as_str() {
             "slime_mold" => {
                 // Initialize slime mold simulation
                 let settings = SlimeMoldSettings::default();
+                let agent_count =
+                    GpuTier::detect(adapter_info, &device.limits()).default_count(10_000_000);
                 let simulation = SlimeMoldModel::new(
                     device,
                     queue,
                     surface_config,
                     adapter_info,
-                    10_000_000,
+                    agent_count,
                     settings,
                     &self.app_settings,
                     &self.color_scheme_manager,
@@ -250,7 +444,7 @@ impl SimulationManager {
                 self.current_simulation = Some(SimulationType::SlimeMold(Box::new(simulation)));
 
                 // Automatically unpause after successful initialization
-                self.resume();
+                self.set_paused(false);
 
                 Ok(())
             }
@@ -267,9 +461,12 @@ impl SimulationManager {
                     GrayScottSettings::default()
                 };
 
-                // Use full surface resolution
-                let sim_width = surface_config.width.max(256);
-                let sim_height = surface_config.height.max(256);
+                // Use full surface resolution unless the grid resolution is fixed
+                let (grid_width, grid_height) = settings
+                    .grid_resolution
+                    .resolve(surface_config.width, surface_config.height);
+                let sim_width = grid_width.max(256);
+                let sim_height = grid_height.max(256);
 
                 let simulation = GrayScottModel::new(
                     device,
@@ -286,7 +483,7 @@ impl SimulationManager {
                 self.current_simulation = Some(SimulationType::GrayScott(Box::new(simulation)));
 
                 // Automatically unpause after successful initialization
-                self.resume();
+                self.set_paused(false);
 
                 Ok(())
             }
@@ -312,11 +509,12 @@ impl SimulationManager {
                     self.preset_manager
                         .apply_preset(simulation, "Default", device, queue)
                         .map_err(|e| format!("Failed to apply Default preset: {}", e))?;
+                    self.current_preset = Some("Default".to_string());
                     tracing::info!("Applied Default preset to Particle Life simulation");
                 }
 
                 // Automatically unpause after successful initialization
-                self.resume();
+                self.set_paused(false);
 
                 Ok(())
             }
@@ -334,7 +532,7 @@ impl SimulationManager {
                 .map_err(|e| format!("Failed to initialize Flow simulation: {}", e))?;
 
                 self.current_simulation = Some(SimulationType::Flow(Box::new(simulation)));
-                self.resume();
+                self.set_paused(false);
                 Ok(())
             }
             "pellets" => {
@@ -351,7 +549,7 @@ impl SimulationManager {
                 .map_err(|e| format!("Failed to initialize Pellets simulation: {}", e))?;
 
                 self.current_simulation = Some(SimulationType::Pellets(Box::new(simulation)));
-                self.resume();
+                self.set_paused(false);
                 Ok(())
             }
             "gradient" => {
@@ -364,7 +562,7 @@ impl SimulationManager {
                 );
 
                 self.current_simulation = Some(SimulationType::Gradient(Box::new(simulation)));
-                self.resume();
+                self.set_paused(false);
                 Ok(())
             }
             "voronoi_ca" => {
@@ -376,7 +574,7 @@ impl SimulationManager {
                         })?;
 
                 self.current_simulation = Some(SimulationType::VoronoiCA(Box::new(simulation)));
-                self.resume();
+                self.set_paused(false);
                 Ok(())
             }
             "moire" => {
@@ -393,7 +591,7 @@ impl SimulationManager {
                 .map_err(|e| format!("Failed to initialize Moiré simulation: {}", e))?;
 
                 self.current_simulation = Some(SimulationType::Moire(Box::new(simulation)));
-                self.resume();
+                self.set_paused(false);
                 Ok(())
             }
             "primordial_particles" => {
@@ -414,427 +612,2828 @@ impl SimulationManager {
 
                 self.current_simulation =
                     Some(SimulationType::PrimordialParticles(Box::new(simulation)));
-                self.resume();
+                self.set_paused(false);
+                Ok(())
+            }
+            "turmites" => {
+                let settings = crate::simulations::turmites::settings::Settings::default();
+                let simulation = crate::simulations::turmites::TurmitesModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Turmites simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Turmites(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "life_like" => {
+                let settings = crate::simulations::life_like::settings::Settings::default();
+                let simulation = crate::simulations::life_like::LifeLikeModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Life-like simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::LifeLike(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "lensing" => {
+                let settings = crate::simulations::lensing::settings::Settings::default();
+                let simulation = crate::simulations::lensing::LensingModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| {
+                    format!(
+                        "Failed to initialize Gravitational Lensing simulation: {}",
+                        e
+                    )
+                })?;
+
+                self.current_simulation = Some(SimulationType::Lensing(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "magnetic_pendulum" => {
+                let settings = crate::simulations::magnetic_pendulum::settings::Settings::default();
+                let simulation = crate::simulations::magnetic_pendulum::MagneticPendulumModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Magnetic Pendulum simulation: {}", e))?;
+
+                self.current_simulation =
+                    Some(SimulationType::MagneticPendulum(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "percolation" => {
+                let settings = crate::simulations::percolation::settings::Settings::default();
+                let simulation = crate::simulations::percolation::PercolationModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Percolation simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Percolation(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "crowd" => {
+                let settings = crate::simulations::crowd::settings::Settings::default();
+                let simulation = crate::simulations::crowd::CrowdModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Crowd simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Crowd(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "phyllotaxis" => {
+                let settings = crate::simulations::phyllotaxis::settings::Settings::default();
+                let simulation = crate::simulations::phyllotaxis::PhyllotaxisModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Phyllotaxis simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Phyllotaxis(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "harmonograph" => {
+                let settings = crate::simulations::harmonograph::settings::Settings::default();
+                let simulation = crate::simulations::harmonograph::HarmonographModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Harmonograph simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Harmonograph(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "slime_mold_3d" => {
+                let settings = crate::simulations::slime_mold_3d::settings::Settings::default();
+                let simulation = crate::simulations::slime_mold_3d::SlimeMold3dModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize 3D Slime Mold simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::SlimeMold3d(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "chemotaxis" => {
+                let settings = crate::simulations::chemotaxis::settings::Settings::default();
+                let simulation = crate::simulations::chemotaxis::ChemotaxisModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Chemotaxis simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Chemotaxis(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "softbody" => {
+                let settings = crate::simulations::softbody::settings::Settings::default();
+                let simulation = crate::simulations::softbody::SoftbodyModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Soft Body simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Softbody(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "eikonal" => {
+                let settings = crate::simulations::eikonal::settings::Settings::default();
+                let simulation = crate::simulations::eikonal::EikonalModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Eikonal simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Eikonal(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "quasicrystal" => {
+                let settings = crate::simulations::quasicrystal::settings::Settings::default();
+                let simulation = crate::simulations::quasicrystal::QuasicrystalModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Quasicrystal simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Quasicrystal(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "stippling" => {
+                let settings = crate::simulations::stippling::settings::Settings::default();
+                let simulation = crate::simulations::stippling::StipplingModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Stippling simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Stippling(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "vortex" => {
+                let settings = crate::simulations::vortex::settings::Settings::default();
+                let simulation = crate::simulations::vortex::VortexModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Vortex Smoke simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Vortex(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "boids" => {
+                let settings = crate::simulations::boids::settings::Settings::default();
+                let simulation = crate::simulations::boids::BoidsModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Boids simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Boids(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "dla" => {
+                let settings = crate::simulations::dla::settings::Settings::default();
+                let simulation = crate::simulations::dla::DlaModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize DLA simulation: {}", e))?;
+
+                self.current_simulation = Some(SimulationType::Dla(Box::new(simulation)));
+                self.set_paused(false);
+                Ok(())
+            }
+            "double_pendulum" => {
+                let settings = crate::simulations::double_pendulum::settings::Settings::default();
+                let simulation = crate::simulations::double_pendulum::DoublePendulumModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    &self.app_settings,
+                    &self.color_scheme_manager,
+                )
+                .map_err(|e| format!("Failed to initialize Double Pendulum simulation: {}", e))?;
+
+                self.current_simulation =
+                    Some(SimulationType::DoublePendulum(Box::new(simulation)));
+                self.set_paused(false);
                 Ok(())
             }
 
             _ => Err("Unknown simulation type".into()),
+        };
+        let validation_error = device.pop_error_scope();
+        let out_of_memory_error = device.pop_error_scope();
+        started?;
+        let gpu_error = match validation_error.await {
+            Some(error) => Some(error),
+            None => out_of_memory_error.await,
+        };
+        if let Some(error) = gpu_error {
+            self.current_simulation = None;
+            return Err(GpuError::from_wgpu(error).into());
         }
-    }
 
-    pub fn stop_simulation(&mut self) {
-        self.current_simulation = None;
+        self.events.publish(SimulationEvent::Started {
+            simulation_type: simulation_type.clone(),
+        });
+        self.apply_default_preset(&simulation_type, device, queue);
+        if let Err(e) = self.apply_color_script(device, queue) {
+            tracing::warn!("Failed to apply color script to {}: {}", simulation_type, e);
+        }
+        if self.kiosk.is_some() && self.is_gui_visible() {
+            self.toggle_gui();
+        }
+        Ok(())
     }
 
-    pub fn render(
+    /// In kiosk mode, start the simulation that failed again from scratch.
+    /// The render loop only asks once per run of failed frames, so a
+    /// simulation that can't start cleanly isn't rebuilt on every frame.
+    async fn restart_in_kiosk(
         &mut self,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
-        surface_view: &wgpu::TextureView,
-        delta_time: f32,
-    ) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            simulation.render_frame(device, queue, surface_view, delta_time)?;
+        surface_config: &SurfaceConfiguration,
+        adapter_info: &wgpu::AdapterInfo,
+    ) {
+        if self.kiosk.is_none() {
+            return;
+        }
+        let Some(simulation_type) = self
+            .current_simulation
+            .as_ref()
+            .map(|simulation| simulation.type_name().to_string())
+        else {
+            return;
+        };
+        tracing::warn!("Restarting {} after a failed frame", simulation_type);
+        if let Err(e) = self
+            .start_simulation(simulation_type, device, queue, surface_config, adapter_info)
+            .await
+        {
+            tracing::error!("Failed to restart simulation in kiosk mode: {}", e);
         }
-        Ok(())
     }
 
-    pub fn render_paused(
+    /// Apply the preset the user picked to start `simulation_type` with, if
+    /// any. A preset that has since been deleted leaves the simulation as it
+    /// started rather than failing the start.
+    fn apply_default_preset(
         &mut self,
+        simulation_type: &str,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
-        surface_view: &wgpu::TextureView,
-    ) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            // Render the current frame without updating simulation state
-            simulation.render_frame_paused(device, queue, surface_view)?;
+    ) {
+        let Some(preset) = self.default_presets.get(simulation_type).cloned() else {
+            return;
+        };
+        let Some(simulation) = &mut self.current_simulation else {
+            return;
+        };
+        if let Err(e) = self
+            .preset_manager
+            .apply_preset(simulation, &preset, device, queue)
+        {
+            tracing::warn!(
+                "Failed to apply default preset '{}' to {}: {}",
+                preset,
+                simulation_type,
+                e
+            );
+            return;
         }
-        Ok(())
+        self.current_preset = Some(preset.clone());
+        self.events.publish(SimulationEvent::PresetApplied {
+            simulation_type: simulation_type.to_string(),
+            preset,
+        });
     }
 
-    pub fn handle_resize(
+    pub fn stop_simulation(&mut self) {
+        if let Some(simulation) = self.current_simulation.take() {
+            self.events.publish(SimulationEvent::Destroyed {
+                simulation_type: simulation.type_name().to_string(),
+            });
+        }
+        self.current_preset = None;
+        self.rewind.clear();
+        self.panes.clear();
+        self.evolution = None;
+        self.transition = None;
+        self.replay_player = None;
+        if let Some(recording) = self.recording.take()
+            && let Err(e) = recording.finish()
+        {
+            tracing::warn!("Recording ended with an error: {}", e);
+        }
+    }
+
+    /// Render the current simulation into an offscreen capture at surface
+    /// size, or canvas size with a canvas set, without advancing it
+    pub fn capture_frame(
         &mut self,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
-        new_config: &SurfaceConfiguration,
-    ) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            simulation.resize(device, queue, new_config)?;
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<FrameCapture> {
+        let simulation = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        let [width, height] = self.master_bus.frame_size(surface_config);
+        let capture = FrameCapture::new(
+            device,
+            width,
+            height,
+            surface_config.format,
+            "Current Frame",
+        )?;
+        match self.master_bus.scene_view() {
+            Some(scene_view) => {
+                simulation.render_frame_paused(device, queue, scene_view)?;
+                self.master_bus.apply(device, queue, &capture.view);
+            }
+            None => simulation.render_frame_paused(device, queue, &capture.view)?,
         }
-        Ok(())
+        Ok(capture)
     }
 
-    pub fn handle_mouse_interaction(
+    /// Feed an image into whichever image input the current simulation has
+    /// and switch the simulation over to using it
+    pub fn seed_from_image(
         &mut self,
-        world_x: f32,
-        world_y: f32,
-        mouse_button: u32,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
+        image: image::DynamicImage,
     ) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            simulation.handle_mouse_interaction(world_x, world_y, mouse_button, device, queue)?;
-        }
-        Ok(())
+        match self.current_simulation.as_mut() {
+            Some(SimulationType::SlimeMold(simulation)) => {
+                simulation.load_position_image_from_data(device, queue, image)?;
+                simulation.update_setting(
+                    "position_generator",
+                    serde_json::json!("Image"),
+                    device,
+                    queue,
+                )?;
+                simulation.reset_agents(device, queue)?;
+            }
+            Some(SimulationType::GrayScott(simulation)) => {
+                simulation.load_nutrient_image_from_data(queue, image)?;
+                simulation.update_state(
+                    "mask_pattern",
+                    serde_json::json!("Image"),
+                    device,
+                    queue,
+                )?;
+            }
+            Some(SimulationType::Flow(simulation)) => {
+                simulation.load_vector_field_image_from_data(device, queue, image)?;
+                simulation.settings.vector_field_type =
+                    crate::simulations::flow::settings::VectorFieldType::Image;
+                simulation.regenerate_flow_vectors(device, queue)?;
+            }
+            Some(SimulationType::Moire(simulation)) => {
+                simulation.load_image_from_data(device, queue, image)?;
+                simulation.update_setting(
+                    "image_mode_enabled",
+                    serde_json::json!(true),
+                    device,
+                    queue,
+                )?;
+            }
+            Some(SimulationType::Lensing(simulation)) => {
+                simulation.load_image_from_data(device, queue, image)?;
+            }
+            Some(SimulationType::Stippling(simulation)) => {
+                simulation.load_image_from_data(device, queue, image)?;
+            }
+            Some(_) => return Err(SimulationError::UnsupportedOperation.into()),
+            None => return Err(SimulationError::NotRunning.into()),
+        }
+        Ok(())
     }
 
-    /// Handle mouse interaction using screen coordinates (physical pixels)
-    pub fn handle_mouse_interaction_screen_coords(
+    pub fn render(
         &mut self,
-        screen_x: f32,
-        screen_y: f32,
-        mouse_button: u32,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
+        surface_view: &wgpu::TextureView,
+        delta_time: f32,
     ) -> AppResult<()> {
+        // A replay steps by the frames it recorded, not the ones going by now
+        let delta_time = self.advance_replay(device, queue).unwrap_or(delta_time);
+        if let Err(e) = self.advance_color_cycle(delta_time, device, queue) {
+            tracing::warn!("Stopping color cycling: {}", e);
+            let cycle = ColorCycle {
+                enabled: false,
+                ..*self.color_cycler.cycle()
+            };
+            self.color_cycler.set_cycle(cycle)?;
+        }
+        self.sync_background_layer(device, queue, surface_view);
+        self.supersampler.clear();
+        self.master_bus.reset_accumulation();
+        let mut unhealthy = false;
         if let Some(simulation) = &mut self.current_simulation {
-            match simulation {
-                SimulationType::GrayScott(simulation) => {
-                    let camera = &simulation.camera;
-                    let screen = ScreenCoords::new(screen_x, screen_y);
-                    let world = camera.screen_to_world(screen);
-
-                    // Convert world coordinates [-1,1] to texture coordinates [0,1]
-                    // Gray-Scott simulation expects texture coordinates in [0,1] range
-                    // World space is [-1, 1] where (-1, -1) is bottom-left and (1, 1) is top-right
-                    // Texture space is [0, 1] where (0, 0) is top-left and (1, 1) is bottom-right
-                    let texture_x = (world.x + 1.0) * 0.5;
-                    let texture_y = (1.0 - world.y) * 0.5; // Flip Y axis to match texture coordinates
-
-                    simulation.handle_mouse_interaction(
-                        texture_x,
-                        texture_y,
-                        mouse_button,
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::ParticleLife(simulation) => {
-                    let camera = &simulation.camera;
-                    let screen = ScreenCoords::new(screen_x, screen_y);
-                    let world = camera.screen_to_world(screen);
-
-                    // Particles now live in [-1,1] world space, so use world coordinates directly
-                    let particle_x = world.x;
-                    let particle_y = world.y;
-
-                    simulation.handle_mouse_interaction(
-                        particle_x,
-                        particle_y,
-                        mouse_button,
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::Pellets(simulation) => {
-                    let camera = &simulation.camera;
-                    let screen = ScreenCoords::new(screen_x, screen_y);
-                    let world = camera.screen_to_world(screen);
-
-                    // Pellets particles also live in [-1,1] world space
-                    simulation.handle_mouse_interaction(
-                        world.x,
-                        world.y,
-                        mouse_button,
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::SlimeMold(simulation) => {
-                    let camera = &simulation.camera;
-                    let screen = ScreenCoords::new(screen_x, screen_y);
-                    let world = camera.screen_to_world(screen);
-                    let world_x = world.x;
-                    let world_y = world.y;
-                    simulation.handle_mouse_interaction(
-                        world_x,
-                        world_y,
-                        mouse_button,
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::Flow(simulation) => {
-                    let camera = &simulation.camera;
-                    let screen = ScreenCoords::new(screen_x, screen_y);
-                    let world = camera.screen_to_world(screen);
-                    let world_x = world.x;
-                    let world_y = world.y;
-                    simulation.handle_mouse_interaction(
-                        world_x,
-                        world_y,
-                        mouse_button,
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::VoronoiCA(simulation) => {
-                    let camera = &simulation.camera;
-                    let screen = ScreenCoords::new(screen_x, screen_y);
-                    let world = camera.screen_to_world(screen);
-                    simulation.handle_mouse_interaction(
-                        world.x,
-                        world.y,
-                        mouse_button,
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::PrimordialParticles(simulation) => {
-                    let camera = &simulation.camera;
-                    let screen = ScreenCoords::new(screen_x, screen_y);
-                    let world = camera.screen_to_world(screen);
-                    simulation.handle_mouse_interaction(
-                        world.x,
-                        world.y,
-                        mouse_button,
-                        device,
-                        queue,
-                    )?;
-                }
-
-                _ => (),
+            let target = self.master_bus.scene_view().unwrap_or(surface_view);
+            if self.panes.is_active() {
+                self.panes
+                    .render(device, queue, target, simulation, Some(delta_time))?;
+            } else {
+                simulation.render_frame(device, queue, target, delta_time)?;
+            }
+            self.master_bus.apply(device, queue, surface_view);
+            if self.rewind.tick(delta_time) {
+                self.rewind
+                    .record(device, queue, &simulation.rewind_resources());
             }
+            unhealthy = self.watchdog.update(simulation, device, queue, delta_time);
+        }
+        self.replay_recorder.end_frame(delta_time);
+        if let Err(e) = self.advance_autopilot(delta_time, unhealthy, device, queue) {
+            tracing::warn!("Stopping the autopilot: {}", e);
+            self.autopilot.set_config(AutopilotConfig {
+                enabled: false,
+                ..self.autopilot.config().clone()
+            });
+        }
+        if let Err(e) = self.advance_audio_reactive(delta_time, device, queue) {
+            tracing::warn!("Stopping audio reactivity: {}", e);
+            self.audio_reactive.set_config(AudioReactiveConfig {
+                enabled: false,
+                ..self.audio_reactive.config().clone()
+            });
+        }
+        if let Err(e) = self.advance_transition(delta_time, device, queue) {
+            tracing::warn!("Stopping the preset transition: {}", e);
+            self.transition = None;
         }
         Ok(())
     }
 
-    /// Handle mouse release events
-    pub fn handle_mouse_release(&mut self, mouse_button: u32, queue: &Arc<Queue>) -> AppResult<()> {
+    pub fn render_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &wgpu::TextureView,
+    ) -> AppResult<()> {
+        self.sync_background_layer(device, queue, surface_view);
+        if self.supersampler.config().enabled && !self.panes.is_active() {
+            return self.render_supersampled(device, queue, surface_view);
+        }
         if let Some(simulation) = &mut self.current_simulation {
-            match simulation {
-                SimulationType::GrayScott(simulation) => {
-                    simulation.handle_mouse_release(mouse_button, queue)?;
-                }
-                SimulationType::ParticleLife(simulation) => {
-                    simulation.handle_mouse_release(mouse_button, queue)?;
-                }
-                SimulationType::SlimeMold(simulation) => {
-                    simulation.handle_mouse_release(mouse_button, queue)?;
-                }
-                SimulationType::Flow(simulation) => {
-                    simulation.handle_mouse_release(mouse_button, queue)?;
-                }
-                SimulationType::Pellets(simulation) => {
-                    simulation.handle_mouse_release(mouse_button, queue)?;
-                }
-                SimulationType::VoronoiCA(simulation) => {
-                    simulation.handle_mouse_release(mouse_button, queue)?;
-                }
-                SimulationType::PrimordialParticles(simulation) => {
-                    simulation.handle_mouse_release(mouse_button, queue)?;
-                }
+            // Render the current frame without updating simulation state
+            let target = self.master_bus.scene_view().unwrap_or(surface_view);
+            if self.panes.is_active() {
+                self.panes.render(device, queue, target, simulation, None)?;
+            } else {
+                simulation.render_frame_paused(device, queue, target)?;
+            }
+            self.master_bus.apply(device, queue, surface_view);
+        }
+        Ok(())
+    }
 
-                _ => (),
+    /// Paused rendering that adds a jittered sample of the view to the
+    /// master bus's average each frame, until there are enough
+    fn render_supersampled(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &wgpu::TextureView,
+    ) -> AppResult<()> {
+        let Some(simulation) = &mut self.current_simulation else {
+            return Ok(());
+        };
+        let Some(camera) = simulation.camera() else {
+            // Nothing to jitter
+            let target = self.master_bus.scene_view().unwrap_or(surface_view);
+            simulation.render_frame_paused(device, queue, target)?;
+            self.master_bus.apply(device, queue, surface_view);
+            return Ok(());
+        };
+        let [x, y] = camera.position;
+        let [target_x, target_y] = camera.get_target_position();
+        let view = ViewFingerprint {
+            settings: simulation.get_settings(),
+            camera: [
+                x,
+                y,
+                camera.zoom,
+                target_x,
+                target_y,
+                camera.get_target_zoom(),
+            ],
+        };
+        if self.supersampler.view_changed(view) {
+            self.master_bus.reset_accumulation();
+        }
+
+        let samples = self.master_bus.accumulated_samples();
+        if samples < self.supersampler.config().samples {
+            let Some(scene_view) = self.master_bus.scene_view() else {
+                return Ok(());
+            };
+            if let Some(camera) = simulation.camera_mut() {
+                camera.set_jitter(supersampling::jitter(samples));
             }
+            let rendered = simulation.render_frame_paused(device, queue, scene_view);
+            if let Some(camera) = simulation.camera_mut() {
+                camera.set_jitter([0.0, 0.0]);
+            }
+            rendered?;
+            self.master_bus.accumulate(device, queue);
         }
+        self.master_bus.apply(device, queue, surface_view);
         Ok(())
     }
 
-    pub fn is_running(&self) -> bool {
-        self.current_simulation.is_some()
+    /// Turn progressive supersampling of the paused view on or off
+    pub fn set_supersampling(
+        &mut self,
+        supersampling: Supersampling,
+        device: &Arc<Device>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        self.supersampler.set_config(supersampling)?;
+        self.master_bus
+            .set_supersampling(supersampling.enabled, device, surface_config);
+        Ok(())
     }
 
-    pub fn pause(&self) {
-        self.is_paused.store(true, Ordering::Relaxed);
+    /// Change the image drawn behind the running simulation. It's part of
+    /// the simulation's settings, so presets saved afterwards keep it.
+    pub fn set_background_layer(&mut self, layer: BackgroundLayer) -> AppResult<()> {
+        layer.validate()?;
+        if layer.is_visible() {
+            image::image_dimensions(&layer.image_path).map_err(|e| {
+                SimulationError::InvalidParameter(format!(
+                    "Failed to read background image {}: {}",
+                    layer.image_path, e
+                ))
+            })?;
+        }
+        let simulation = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        let type_name = simulation.type_name();
+        let current = simulation.background_layer_mut().ok_or_else(|| {
+            SimulationError::InvalidParameter(format!("{} has no background layer", type_name))
+        })?;
+        *current = layer;
+        Ok(())
     }
 
-    pub fn resume(&self) {
-        self.is_paused.store(false, Ordering::Relaxed);
+    /// Point the master bus at the running simulation's background layer
+    /// when it changed, whether through the command, a preset or a new
+    /// simulation
+    fn sync_background_layer(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        frame: &wgpu::TextureView,
+    ) {
+        let layer = self
+            .current_simulation
+            .as_ref()
+            .and_then(|simulation| simulation.background_layer())
+            .filter(|layer| layer.is_visible());
+        if layer == self.master_bus.background_layer() {
+            return;
+        }
+        let layer = layer.cloned();
+        if let Err(e) = self
+            .master_bus
+            .set_background_layer(layer, device, queue, frame.texture())
+        {
+            tracing::warn!("Failed to show the background layer: {}", e);
+        }
     }
 
-    /// Request a single simulation update while remaining in paused mode
-    pub fn step_once(&self) {
-        self.step_frames_pending.fetch_add(1, Ordering::Relaxed);
+    /// Parameters are checked against the running simulation's rules
+    pub fn set_autopilot(&mut self, config: AutopilotConfig) -> AppResult<()> {
+        if let Some(simulation) = &self.current_simulation {
+            config.validate(simulation.setting_validator())?;
+        }
+        self.autopilot.set_config(config);
+        Ok(())
     }
 
-    pub fn get_status(&self) -> String {
-        if self.current_simulation.is_some() {
-            "Simulation Running"
+    /// Drift the autopilot's settings on, or send them back to where they
+    /// started when the watchdog has just found a problem
+    fn advance_autopilot(
+        &mut self,
+        delta_time: f32,
+        unhealthy: bool,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        let Some(simulation) = &mut self.current_simulation else {
+            return Ok(());
+        };
+        let retreating = unhealthy && self.autopilot.config().enabled;
+        let updates = if retreating {
+            tracing::info!("Autopilot is returning to its starting settings");
+            self.autopilot.retreat()
         } else {
-            "No Simulation Running"
+            self.autopilot.advance(
+                delta_time,
+                || simulation.get_settings(),
+                simulation.setting_validator(),
+                &mut rand::rng(),
+            )
+        };
+        for (setting, value) in updates {
+            simulation.update_setting(&setting, serde_json::Value::from(value), device, queue)?;
         }
-        .to_string()
+        if retreating {
+            simulation.reset_runtime_state(device, queue)?;
+        }
+        Ok(())
     }
 
-    pub fn update_setting(
+    /// Routes are checked against the running simulation's rules. Settings
+    /// the old routes moved are put back where they started.
+    pub fn set_audio_reactive(
         &mut self,
-        setting_name: &str,
-        value: serde_json::Value,
+        config: AudioReactiveConfig,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
-        tracing::debug!(
-            "SimulationManager::update_setting called with setting_name: '{}', value: {:?}",
-            setting_name,
-            value
-        );
-
+        if let Some(simulation) = &self.current_simulation {
+            config.validate(simulation.setting_validator())?;
+        }
+        let released = self.audio_reactive.set_config(config);
         if let Some(simulation) = &mut self.current_simulation {
-            tracing::debug!("Calling simulation.update_setting for current simulation");
-            simulation.update_setting(setting_name, value.clone(), device, queue)?;
-            tracing::debug!("Simulation update_setting completed successfully");
-        } else {
-            tracing::warn!("No simulation running, cannot update setting");
+            for (setting, value) in released {
+                simulation.update_setting(
+                    &setting,
+                    serde_json::Value::from(value),
+                    device,
+                    queue,
+                )?;
+            }
         }
         Ok(())
     }
 
-    pub fn update_state(
+    /// Move the audio routed settings to follow the latest samples
+    fn advance_audio_reactive(
         &mut self,
-        state_name: &str,
-        value: serde_json::Value,
+        delta_time: f32,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
-        tracing::debug!(
-            "SimulationManager::update_state called with state_name: '{}', value: {:?}",
-            state_name,
-            value
+        let Some(simulation) = &mut self.current_simulation else {
+            return Ok(());
+        };
+        let updates = self.audio_reactive.advance(
+            delta_time,
+            || simulation.get_settings(),
+            simulation.setting_validator(),
         );
+        for (setting, value) in updates {
+            simulation.update_setting(&setting, serde_json::Value::from(value), device, queue)?;
+        }
+        Ok(())
+    }
 
-        if let Some(simulation) = &mut self.current_simulation {
-            tracing::debug!("Calling simulation.update_state for current simulation");
-            simulation.update_state(state_name, value.clone(), device, queue)?;
-            tracing::debug!("Simulation update_state completed successfully");
-        } else {
-            tracing::warn!("No simulation running, cannot update state");
+    /// Ease the settings on toward the preset being transitioned to
+    fn advance_transition(
+        &mut self,
+        delta_time: f32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        let (Some(simulation), Some(transition)) =
+            (&mut self.current_simulation, &mut self.transition)
+        else {
+            return Ok(());
+        };
+        for (setting, value) in transition.advance(delta_time) {
+            simulation.update_setting(&setting, serde_json::Value::from(value), device, queue)?;
+        }
+        if transition.is_finished() {
+            self.transition = None;
+            // Drift from where the preset left the settings, not from partway there
+            self.autopilot.rehome();
+            self.audio_reactive.rehome();
         }
         Ok(())
     }
 
-    // Preset management methods
-    pub fn get_available_presets(&self) -> Vec<String> {
-        if let Some(simulation) = &self.current_simulation {
-            let presets = self.preset_manager.get_available_presets(simulation);
-            tracing::info!("Available presets for simulation: {:?}", presets);
-            presets
-        } else {
-            tracing::warn!("No simulation running, returning empty preset list");
-            vec![]
+    /// Note `action` for the macro and the replay being recorded, if any
+    fn record_action(&mut self, action: MacroAction) {
+        self.replay_recorder.record(action.clone().into());
+        self.macro_recorder.record(action);
+    }
+
+    /// Start recording a replay. The simulation starts over from its seed,
+    /// which is where the replay will start too.
+    pub fn start_replay_recording(
+        &mut self,
+        name: &str,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        let simulation = self
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?;
+        if !simulation.supports_replay() {
+            return Err(SimulationError::InvalidParameter(format!(
+                "{} can't be replayed exactly, so it can't be recorded",
+                simulation.type_name()
+            ))
+            .into());
         }
+        // Their changes aren't input, so playback couldn't make them again
+        if self.autopilot.config().enabled || self.audio_reactive.config().enabled {
+            return Err(SimulationError::InvalidParameter(
+                "Turn off the autopilot and audio reactivity before recording a replay".to_string(),
+            )
+            .into());
+        }
+        let configuration = self.shared_configuration()?;
+        self.replay_recorder.start(name, configuration)?;
+        if let Err(e) = self.reset_runtime_state(device, queue) {
+            self.replay_recorder.stop();
+            return Err(e);
+        }
+        Ok(())
     }
 
-    pub fn get_presets_for_simulation_type(&self, simulation_type: &str) -> Vec<String> {
-        if let Some(manager) = self.preset_manager.get_manager(simulation_type) {
-            let presets = manager.get_preset_names();
-            tracing::info!("{} presets: {:?}", simulation_type, presets);
-            presets
-        } else {
-            tracing::warn!(
-                "No preset manager was created for simulation type: {}",
-                simulation_type
-            );
-            vec![]
+    /// Play `replay` from its start, replacing any replay already playing
+    pub fn play_replay(
+        &mut self,
+        replay: Replay,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        self.replay_player = None;
+        self.transition = None;
+        self.replay_player = Some(ReplayPlayer::start(replay, self, device, queue)?);
+        Ok(())
+    }
+
+    /// Apply the input due before the next replayed frame and return how
+    /// long that frame is, or `None` when no replay is playing
+    fn advance_replay(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> Option<f32> {
+        let player = self.replay_player.as_mut()?;
+        let Some((inputs, delta_time)) = player.next_frame() else {
+            let player = self.replay_player.take()?;
+            tracing::info!("Replay '{}' finished", player.name());
+            self.events.publish(SimulationEvent::ReplayFinished {
+                name: player.name().to_string(),
+            });
+            return None;
+        };
+        for input in &inputs {
+            if let Err(e) = self.apply_replay_input(input, device, queue) {
+                tracing::warn!("Replay input {:?} failed: {}", input, e);
+            }
         }
+        Some(delta_time)
     }
 
-    pub fn apply_preset(
+    fn apply_replay_input(
         &mut self,
-        preset_name: &str,
+        input: &ReplayInput,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            self.preset_manager
-                .apply_preset(simulation, preset_name, device, queue)
-                .map_err(AppError::Preset)?;
-            simulation.reset_runtime_state(device, queue)?;
+        match input {
+            ReplayInput::Action(action) => action.apply(self, device, queue),
+            ReplayInput::MousePress {
+                screen_x,
+                screen_y,
+                mouse_button,
+            } => self.press_mouse(*screen_x, *screen_y, *mouse_button, device, queue),
+            ReplayInput::WorldMousePress {
+                world_x,
+                world_y,
+                mouse_button,
+            } => self.press_mouse_world(*world_x, *world_y, *mouse_button, device, queue),
+            ReplayInput::MouseRelease { mouse_button } => self.release_mouse(*mouse_button, queue),
         }
-        Ok(())
     }
 
-    pub fn save_preset(
+    pub fn set_color_cycle(&mut self, cycle: ColorCycle) -> AppResult<()> {
+        Ok(self.color_cycler.set_cycle(cycle)?)
+    }
+
+    /// Upload the running simulation's color scheme rotated to where the
+    /// color cycle has moved, if it moved this frame
+    fn advance_color_cycle(
         &mut self,
-        preset_name: &str,
-        settings: &serde_json::Value,
+        delta_time: f32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
     ) -> AppResult<()> {
-        if let Some(simulation) = &self.current_simulation {
-            self.preset_manager
-                .save_preset(simulation, preset_name, settings)?;
+        let Some(offset) = self.color_cycler.advance(delta_time) else {
+            return Ok(());
+        };
+        let Some((name, reversed)) = self.current_color_scheme() else {
+            return Ok(());
+        };
+        let script = self.color_script()?;
+        let Some(simulation) = &mut self.current_simulation else {
+            return Ok(());
+        };
+        let mut scheme = self
+            .color_cycler
+            .source(&self.color_scheme_manager, &name)?;
+        // These reverse the scheme themselves while uploading it, which turns
+        // the rotation around
+        let reverses_on_upload = reverses_color_scheme_on_upload(simulation);
+        match (reversed, reverses_on_upload) {
+            (true, true) => scheme.rotate(256 - offset),
+            (true, false) => {
+                scheme.reverse();
+                scheme.rotate(offset);
+            }
+            (false, _) => scheme.rotate(offset),
         }
+        if let Some(script) = script {
+            scheme = scripted_upload(&script, scheme, reversed && reverses_on_upload)?;
+        }
+        simulation.update_color_scheme(&scheme, device, queue)?;
         Ok(())
     }
 
-    pub fn delete_preset(&mut self, preset_name: &str) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            self.preset_manager.delete_preset(simulation, preset_name)?;
+    /// Render a preview stream frame if one is due. The stream is stopped if
+    /// rendering it fails.
+    pub fn stream_preview_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<Option<PreviewFrame>> {
+        let Some(mut stream) = self.preview_stream.take().filter(|stream| stream.is_due()) else {
+            return Ok(None);
+        };
+        let frame = stream
+            .frame_view(device, surface_config)
+            .and_then(|frame_view| self.render_paused(device, queue, &frame_view))
+            .and_then(|_| stream.encode(device, queue))?;
+        // A stream that failed is dropped rather than retried on every frame
+        self.preview_stream = Some(stream);
+        Ok(Some(frame))
+    }
+
+    /// Start recording the running simulation, replacing any recording that
+    /// was already going
+    pub fn start_recording(&mut self, config: RecordingConfig) -> AppResult<()> {
+        if self.current_simulation.is_none() {
+            return Err(SimulationError::NotRunning.into());
         }
+        if let Some(recording) = self.recording.take() {
+            recording.finish()?;
+        }
+        self.recording = Some(Recording::start(config)?);
         Ok(())
     }
 
-    pub fn get_current_settings(&self) -> Option<serde_json::Value> {
-        self.current_simulation
-            .as_ref()
-            .map(|simulation| simulation.get_settings())
+    /// Stop recording and wait for the last frames to be written
+    pub fn stop_recording(&mut self) -> AppResult<RecordingSummary> {
+        let recording = self.recording.take().ok_or_else(|| {
+            SimulationError::InvalidParameter("Nothing is being recorded".to_string())
+        })?;
+        recording.finish()
     }
 
-    pub fn get_current_state(&self) -> Option<serde_json::Value> {
-        self.current_simulation
+    /// Record a frame if one is due. The recording is stopped if rendering or
+    /// writing it fails.
+    pub fn record_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        if !self
+            .recording
             .as_ref()
-            .map(|simulation| simulation.get_state())
+            .is_some_and(|recording| recording.is_due())
+        {
+            return Ok(());
+        }
+        let Some(mut recording) = self.recording.take() else {
+            return Ok(());
+        };
+        recording
+            .frame_view(device, surface_config)
+            .and_then(|frame_view| self.render_paused(device, queue, &frame_view))
+            .and_then(|_| recording.write_frame(device, queue))?;
+        self.recording = Some(recording);
+        Ok(())
     }
 
-    pub fn toggle_gui(&mut self) {
+    pub fn handle_resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        self.master_bus.resize(device, new_config);
+        let scene_config = self.master_bus.scene_config(new_config);
         if let Some(simulation) = &mut self.current_simulation {
-            simulation.toggle_gui();
+            if self.panes.is_active() {
+                self.panes
+                    .resize(device, queue, &scene_config, simulation)?;
+            } else {
+                simulation.resize(device, queue, &scene_config)?;
+            }
         }
+        Ok(())
     }
 
-    pub fn is_gui_visible(&self) -> bool {
-        if let Some(simulation) = &self.current_simulation {
-            simulation.is_gui_visible()
+    /// Render at `scale` times the window's physical resolution, rebuilding
+    /// the running simulation's textures for the new size
+    pub fn set_render_scale(
+        &mut self,
+        scale: f32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        if !(MIN_RENDER_SCALE..=MAX_RENDER_SCALE).contains(&scale) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Render scale must be between {} and {}",
+                MIN_RENDER_SCALE, MAX_RENDER_SCALE
+            ))
+            .into());
+        }
+        if scale == self.master_bus.render_scale() {
+            return Ok(());
+        }
+        self.master_bus.set_render_scale(scale);
+        self.handle_resize(device, queue, surface_config)
+    }
+
+    /// Render to a fixed size canvas letterboxed into the window, or follow
+    /// the window again with `None`
+    pub fn set_canvas(
+        &mut self,
+        canvas: Option<Canvas>,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        if let Some(canvas) = &canvas {
+            canvas.validate(device.limits().max_texture_dimension_2d)?;
+        }
+        if canvas == self.master_bus.canvas() {
+            return Ok(());
+        }
+        self.master_bus.set_canvas(canvas);
+        self.handle_resize(device, queue, surface_config)
+    }
+
+    /// Switch overlay mode. `alpha_mode` is the surface's new alpha mode,
+    /// which the master bus keys the frame's alpha for; the window itself is
+    /// up to the caller.
+    pub fn set_overlay_mode(
+        &mut self,
+        overlay: OverlayMode,
+        alpha_mode: Option<wgpu::CompositeAlphaMode>,
+        device: &Arc<Device>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        overlay.validate()?;
+        let alpha_mode = alpha_mode.filter(|_| overlay.enabled);
+        self.master_bus
+            .set_overlay(alpha_mode, overlay.key_threshold, device, surface_config);
+        self.overlay = overlay;
+        Ok(())
+    }
+
+    /// Replace the master post effects applied over the composited frame
+    pub fn set_master_effects(
+        &mut self,
+        effects: MasterEffects,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        let lut = if effects.grading.enabled {
+            Some(
+                self.color_scheme_manager
+                    .get(&effects.grading.color_scheme)?,
+            )
         } else {
-            false
+            None
+        };
+        self.master_bus
+            .set_effects(effects, lut.as_ref(), device, queue, surface_config)
+    }
+
+    /// Open another simulation next to the running one. The first extra pane
+    /// splits the surface, the running simulation stays focused.
+    pub async fn add_pane(
+        &mut self,
+        simulation_type: &str,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        adapter_info: &wgpu::AdapterInfo,
+    ) -> AppResult<Vec<PaneInfo>> {
+        if self.current_simulation.is_none() {
+            return Err(SimulationError::NotRunning.into());
         }
+
+        let surface_config = &self.master_bus.scene_config(surface_config);
+        let (layout, pane_config) = self.panes.next_pane_config(surface_config)?;
+        let simulation = SimulationType::new(
+            simulation_type,
+            device,
+            queue,
+            &pane_config,
+            adapter_info,
+            &self.color_scheme_manager,
+            &self.app_settings,
+        )
+        .await
+        .map_err(|e| SimulationError::InitializationFailed(e.to_string()))?;
+
+        let focused = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        self.panes
+            .add(device, queue, surface_config, layout, simulation, focused)?;
+        Ok(self.panes.info())
     }
 
-    // Color scheme management methods
-    pub fn get_available_color_schemes(&self) -> Vec<String> {
-        self.simulation_color_scheme_manager
-            .get_available_color_schemes(&self.color_scheme_manager)
+    pub fn remove_pane(
+        &mut self,
+        pane_id: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<Vec<PaneInfo>> {
+        if self
+            .panes
+            .remove(device, queue, pane_id, &mut self.current_simulation)?
+        {
+            self.on_focus_changed();
+        }
+        Ok(self.panes.info())
     }
 
-    pub fn apply_color_scheme(
+    /// Make a pane the target of simulation commands and input
+    pub fn focus_pane(&mut self, pane_id: u32) -> AppResult<Vec<PaneInfo>> {
+        if self.panes.focus(pane_id, &mut self.current_simulation)? {
+            self.on_focus_changed();
+        }
+        Ok(self.panes.info())
+    }
+
+    /// Forget what belonged to the previously focused simulation, whether a
+    /// new one started or another pane took focus
+    fn on_focus_changed(&mut self) {
+        self.current_preset = None;
+        self.current_seed = None;
+        self.watchdog.reset();
+        self.rewind.clear();
+        self.evolution = None;
+        self.autopilot.rehome();
+        self.audio_reactive.rehome();
+        self.transition = None;
+    }
+
+    pub fn set_pane_cameras_linked(&mut self, linked: bool) -> Vec<PaneInfo> {
+        self.panes
+            .set_linked_cameras(linked, self.current_simulation.as_ref());
+        self.panes.info()
+    }
+
+    pub fn set_pane_blend(&mut self, pane_id: u32, blend: PaneBlend) -> AppResult<Vec<PaneInfo>> {
+        self.panes.set_blend(pane_id, blend)?;
+        Ok(self.panes.info())
+    }
+
+    pub fn set_pane_layout(
         &mut self,
-        color_scheme_name: &str,
+        layout: PaneLayout,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<Vec<PaneInfo>> {
+        self.panes
+            .set_layout(device, queue, layout, self.current_simulation.as_mut())?;
+        Ok(self.panes.info())
+    }
+
+    /// Live mouse input in world coordinates, ignored while a replay plays
+    pub fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        if self.replay_player.is_some() {
+            return Ok(());
+        }
+        self.replay_recorder.record(ReplayInput::WorldMousePress {
+            world_x,
+            world_y,
+            mouse_button,
+        });
+        self.press_mouse_world(world_x, world_y, mouse_button, device, queue)
+    }
+
+    fn press_mouse_world(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        if let Some(simulation) = &mut self.current_simulation {
+            simulation.handle_mouse_interaction(world_x, world_y, mouse_button, device, queue)?;
+        }
+        Ok(())
+    }
+
+    /// Handle mouse interaction using screen coordinates (physical pixels),
+    /// ignored while a replay plays
+    pub fn handle_mouse_interaction_screen_coords(
+        &mut self,
+        screen_x: f32,
+        screen_y: f32,
+        mouse_button: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        if self.replay_player.is_some() {
+            return Ok(());
+        }
+        self.replay_recorder.record(ReplayInput::MousePress {
+            screen_x,
+            screen_y,
+            mouse_button,
+        });
+        self.press_mouse(screen_x, screen_y, mouse_button, device, queue)
+    }
+
+    fn press_mouse(
+        &mut self,
+        screen_x: f32,
+        screen_y: f32,
+        mouse_button: u32,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
+        // Cameras work in scene pixels
+        let [screen_x, screen_y] = self.master_bus.scene_position([screen_x, screen_y]);
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
-                SimulationType::SlimeMold(simulation) => {
-                    // For slime mold, load the color scheme data and apply it directly
-                    let mut color_scheme_data = self
-                        .color_scheme_manager
-                        .get(color_scheme_name)
-                        .map_err(|e| {
-                            AppError::ColorScheme(ColorSchemeError::load_failed(
-                                color_scheme_name,
-                                &e.to_string(),
-                            ))
-                        })?;
+                SimulationType::GrayScott(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
 
-                    if simulation.color_scheme_reversed {
-                        color_scheme_data.reverse();
-                    }
+                    // Convert world coordinates [-1,1] to texture coordinates [0,1]
+                    // Gray-Scott simulation expects texture coordinates in [0,1] range
+                    // World space is [-1, 1] where (-1, -1) is bottom-left and (1, 1) is top-right
+                    // Texture space is [0, 1] where (0, 0) is top-left and (1, 1) is bottom-right
+                    let texture_x = (world.x + 1.0) * 0.5;
+                    let texture_y = (1.0 - world.y) * 0.5; // Flip Y axis to match texture coordinates
 
-                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
-                    simulation.current_color_scheme = color_scheme_name.to_string();
+                    simulation.handle_mouse_interaction(
+                        texture_x,
+                        texture_y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::ParticleLife(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
 
-                    tracing::info!(
-                        "Color scheme '{}' applied to slime mold simulation",
-                        color_scheme_name
-                    );
+                    // Particles now live in [-1,1] world space, so use world coordinates directly
+                    let particle_x = world.x;
+                    let particle_y = world.y;
+
+                    simulation.handle_mouse_interaction(
+                        particle_x,
+                        particle_y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
                 }
-                SimulationType::GrayScott(simulation) => {
-                    // For Gray-Scott, load the color scheme data and apply it to the renderer
-                    let mut color_scheme_data = self
+                SimulationType::Pellets(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+
+                    // Pellets particles also live in [-1,1] world space
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::SlimeMold(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    let world_x = world.x;
+                    let world_y = world.y;
+                    simulation.handle_mouse_interaction(
+                        world_x,
+                        world_y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Flow(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    let world_x = world.x;
+                    let world_y = world.y;
+                    simulation.handle_mouse_interaction(
+                        world_x,
+                        world_y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::VoronoiCA(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::PrimordialParticles(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Turmites(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::LifeLike(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Lensing(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::MagneticPendulum(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Percolation(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Crowd(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Phyllotaxis(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Harmonograph(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::SlimeMold3d(simulation) => {
+                    // There's no plane to map onto, so the volume casts its
+                    // own ray from the cursor's normalized device coordinates
+                    let camera = &simulation.camera;
+                    let ndc_x = screen_x / camera.viewport_width.max(1.0) * 2.0 - 1.0;
+                    let ndc_y = 1.0 - screen_y / camera.viewport_height.max(1.0) * 2.0;
+                    simulation.handle_mouse_interaction(
+                        ndc_x,
+                        ndc_y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Chemotaxis(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Softbody(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Eikonal(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Stippling(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Vortex(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Boids(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Dla(simulation) => {
+                    let camera = &simulation.camera;
+                    let screen = ScreenCoords::new(screen_x, screen_y);
+                    let world = camera.screen_to_world(screen);
+                    simulation.handle_mouse_interaction(
+                        world.x,
+                        world.y,
+                        mouse_button,
+                        device,
+                        queue,
+                    )?;
+                }
+
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle mouse release events, ignored while a replay plays
+    pub fn handle_mouse_release(&mut self, mouse_button: u32, queue: &Arc<Queue>) -> AppResult<()> {
+        if self.replay_player.is_some() {
+            return Ok(());
+        }
+        self.replay_recorder
+            .record(ReplayInput::MouseRelease { mouse_button });
+        self.release_mouse(mouse_button, queue)
+    }
+
+    fn release_mouse(&mut self, mouse_button: u32, queue: &Arc<Queue>) -> AppResult<()> {
+        if let Some(simulation) = &mut self.current_simulation {
+            match simulation {
+                SimulationType::GrayScott(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::ParticleLife(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::SlimeMold(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::Flow(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::Pellets(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::VoronoiCA(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::PrimordialParticles(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::Lensing(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::MagneticPendulum(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::Vortex(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::Boids(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+                SimulationType::Dla(simulation) => {
+                    simulation.handle_mouse_release(mouse_button, queue)?;
+                }
+
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.current_simulation.is_some()
+    }
+
+    pub fn pause(&self) {
+        if self.set_paused(true) {
+            self.events.publish(SimulationEvent::Paused);
+        }
+    }
+
+    pub fn resume(&self) {
+        if self.set_paused(false) {
+            self.events.publish(SimulationEvent::Resumed);
+        }
+    }
+
+    /// Returns whether this changed the paused state
+    fn set_paused(&self, paused: bool) -> bool {
+        self.is_paused.swap(paused, Ordering::Relaxed) != paused
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.load(Ordering::Relaxed)
+    }
+
+    /// Request a single simulation update while remaining in paused mode
+    pub fn step_once(&self) {
+        self.step_frames_pending.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Run `steps` updates of the current simulation without drawing, as
+    /// fast as the GPU gets through them, and return how many were run.
+    /// The GPU is waited on between batches so a long jump never queues
+    /// more work than the driver will sit through.
+    pub fn fast_forward(
+        &mut self,
+        steps: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<u32> {
+        let simulation = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        let steps = steps.min(MAX_FAST_FORWARD_STEPS);
+        let mut taken = 0;
+        while taken < steps {
+            let batch = (steps - taken).min(FAST_FORWARD_BATCH);
+            simulation.fast_forward(batch, device, queue)?;
+            device
+                .poll(wgpu::wgt::PollType::Wait)
+                .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+            taken += batch;
+        }
+        tracing::info!("Fast-forwarded {} steps", taken);
+        Ok(taken)
+    }
+
+    pub fn get_status(&self) -> String {
+        if self.current_simulation.is_some() {
+            "Simulation Running"
+        } else {
+            "No Simulation Running"
+        }
+        .to_string()
+    }
+
+    /// Validate `value` against the simulation's setting rules and apply it.
+    /// Returns the value that was applied, which differs from `value` when it
+    /// had to be clamped.
+    pub fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: serde_json::Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<serde_json::Value> {
+        tracing::debug!(
+            "SimulationManager::update_setting called with setting_name: '{}', value: {:?}",
+            setting_name,
+            value
+        );
+
+        let Some(simulation) = &mut self.current_simulation else {
+            tracing::warn!("No simulation running, cannot update setting");
+            return Ok(value);
+        };
+
+        let validated = simulation
+            .setting_validator()
+            .validate(setting_name, value, || simulation.get_settings())
+            .map_err(SimulationError::from)?;
+        if validated.clamped {
+            tracing::debug!("Clamped '{}' to {}", setting_name, validated.value);
+        }
+
+        tracing::debug!("Calling simulation.update_setting for current simulation");
+        simulation.update_setting(setting_name, validated.value.clone(), device, queue)?;
+        tracing::debug!("Simulation update_setting completed successfully");
+
+        self.record_action(MacroAction::UpdateSetting {
+            name: setting_name.to_string(),
+            value: validated.value.clone(),
+        });
+        Ok(validated.value)
+    }
+
+    pub fn update_state(
+        &mut self,
+        state_name: &str,
+        value: serde_json::Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        tracing::debug!(
+            "SimulationManager::update_state called with state_name: '{}', value: {:?}",
+            state_name,
+            value
+        );
+
+        if let Some(simulation) = &mut self.current_simulation {
+            tracing::debug!("Calling simulation.update_state for current simulation");
+            simulation.update_state(state_name, value.clone(), device, queue)?;
+            tracing::debug!("Simulation update_state completed successfully");
+        } else {
+            tracing::warn!("No simulation running, cannot update state");
+        }
+        Ok(())
+    }
+
+    // Preset management methods
+    pub fn get_available_presets(&self) -> Vec<String> {
+        if let Some(simulation) = &self.current_simulation {
+            let presets = self.preset_manager.get_available_presets(simulation);
+            tracing::info!("Available presets for simulation: {:?}", presets);
+            presets
+        } else {
+            tracing::warn!("No simulation running, returning empty preset list");
+            vec![]
+        }
+    }
+
+    /// The running simulation's presets nearest to `settings`, or to its
+    /// current settings, closest first
+    pub fn find_similar_presets(
+        &self,
+        settings: Option<&serde_json::Value>,
+        limit: usize,
+    ) -> AppResult<Vec<SimilarPreset>> {
+        let simulation = self
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?;
+        let Some(presets) = self.preset_manager.get_manager(simulation.type_name()) else {
+            return Ok(vec![]);
+        };
+        let current = match settings {
+            Some(settings) => settings.clone(),
+            None => simulation.get_settings(),
+        };
+        Ok(similarity::nearest_presets(
+            &current,
+            presets,
+            simulation.setting_validator(),
+            limit,
+        ))
+    }
+
+    pub fn get_presets_for_simulation_type(&self, simulation_type: &str) -> Vec<String> {
+        if let Some(manager) = self.preset_manager.get_manager(simulation_type) {
+            let presets = manager.get_preset_names();
+            tracing::info!("{} presets: {:?}", simulation_type, presets);
+            presets
+        } else {
+            tracing::warn!(
+                "No preset manager was created for simulation type: {}",
+                simulation_type
+            );
+            vec![]
+        }
+    }
+
+    pub fn apply_preset(
+        &mut self,
+        preset_name: &str,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        self.apply_preset_over(preset_name, 0.0, device, queue)
+    }
+
+    /// Apply a preset, easing its ranged settings in over `seconds` rather
+    /// than snapping to them. The simulation carries on from where it is
+    /// while they change; with no transition it starts over, as usual.
+    pub fn apply_preset_over(
+        &mut self,
+        preset_name: &str,
+        seconds: f32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        if !(0.0..=MAX_TRANSITION_SECONDS).contains(&seconds) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Preset transitions must be between 0 and {} seconds",
+                MAX_TRANSITION_SECONDS
+            ))
+            .into());
+        }
+        self.transition = None;
+        if let Some(simulation) = &mut self.current_simulation {
+            let before = simulation.get_settings();
+            self.preset_manager
+                .apply_preset(simulation, preset_name, device, queue)
+                .map_err(AppError::Preset)?;
+            let transition = SettingTransition::new(
+                &before,
+                &simulation.get_settings(),
+                simulation.setting_validator(),
+                seconds,
+            );
+            match transition {
+                Some(mut transition) => {
+                    // Back to where the glide starts
+                    for (setting, value) in transition.advance(0.0) {
+                        simulation.update_setting(
+                            &setting,
+                            serde_json::Value::from(value),
+                            device,
+                            queue,
+                        )?;
+                    }
+                    self.transition = Some(transition);
+                }
+                None => simulation.reset_runtime_state(device, queue)?,
+            }
+            self.autopilot.rehome();
+            self.audio_reactive.rehome();
+            self.current_preset = Some(preset_name.to_string());
+            self.events.publish(SimulationEvent::PresetApplied {
+                simulation_type: simulation.type_name().to_string(),
+                preset: preset_name.to_string(),
+            });
+            self.record_action(MacroAction::ApplyPreset {
+                name: preset_name.to_string(),
+            });
+        }
+        self.apply_color_script(device, queue)?;
+        Ok(())
+    }
+
+    /// Grow the current simulation's world again from `seed`, a number or a
+    /// name to hash into one
+    pub fn reseed(
+        &mut self,
+        seed: &str,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<Seed> {
+        let simulation = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        let setting = simulation.seed_setting().ok_or_else(|| {
+            SimulationError::InvalidParameter(format!(
+                "{} doesn't grow from a seed",
+                simulation.type_name()
+            ))
+        })?;
+        let seed = Seed::parse(seed);
+        self.transition = None;
+        simulation.update_setting(setting, serde_json::Value::from(seed.value), device, queue)?;
+        simulation.reset_runtime_state(device, queue)?;
+        self.current_seed = Some(seed.clone());
+        Ok(seed)
+    }
+
+    /// The seed the current world grew from, with its name when it was
+    /// reseeded by one and hasn't changed since
+    pub fn simulation_seed(&self) -> Option<Seed> {
+        let simulation = self.current_simulation.as_ref()?;
+        let value = simulation
+            .get_settings()
+            .get(simulation.seed_setting()?)?
+            .as_u64()? as u32;
+        Some(match &self.current_seed {
+            Some(seed) if seed.value == value => seed.clone(),
+            _ => Seed { name: None, value },
+        })
+    }
+
+    /// Apply the preset after the current one, wrapping around, and return its name
+    pub fn apply_next_preset(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<Option<String>> {
+        let presets = self.get_available_presets();
+        let next_index = self
+            .current_preset
+            .as_ref()
+            .and_then(|current| presets.iter().position(|name| name == current))
+            .map_or(0, |index| (index + 1) % presets.len());
+        let Some(next) = presets.get(next_index).cloned() else {
+            return Ok(None);
+        };
+        self.apply_preset(&next, device, queue)?;
+        Ok(Some(next))
+    }
+
+    pub fn save_preset(
+        &mut self,
+        preset_name: &str,
+        settings: &serde_json::Value,
+    ) -> AppResult<()> {
+        if let Some(simulation) = &self.current_simulation {
+            self.preset_manager
+                .save_preset(simulation, preset_name, settings)?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_preset(&mut self, preset_name: &str) -> AppResult<()> {
+        if let Some(simulation) = &mut self.current_simulation {
+            self.preset_manager.delete_preset(simulation, preset_name)?;
+            // A note shouldn't resurface on a new preset of the same name
+            if let Err(e) = forget_preset_note(simulation.type_name(), preset_name) {
+                tracing::warn!("Failed to remove note for '{}': {}", preset_name, e);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_current_settings(&self) -> Option<serde_json::Value> {
+        self.current_simulation
+            .as_ref()
+            .map(|simulation| simulation.get_settings())
+    }
+
+    pub fn get_current_state(&self) -> Option<serde_json::Value> {
+        self.current_simulation
+            .as_ref()
+            .map(|simulation| simulation.get_state())
+    }
+
+    pub fn toggle_gui(&mut self) {
+        if let Some(simulation) = &mut self.current_simulation {
+            simulation.toggle_gui();
+        }
+    }
+
+    pub fn is_gui_visible(&self) -> bool {
+        if let Some(simulation) = &self.current_simulation {
+            simulation.is_gui_visible()
+        } else {
+            false
+        }
+    }
+
+    // Color scheme management methods
+    pub fn get_available_color_schemes(&self) -> Vec<String> {
+        self.simulation_color_scheme_manager
+            .get_available_color_schemes(&self.color_scheme_manager)
+    }
+
+    pub fn apply_color_scheme(
+        &mut self,
+        color_scheme_name: &str,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        if let Some(simulation) = &mut self.current_simulation {
+            match simulation {
+                SimulationType::SlimeMold(simulation) => {
+                    // For slime mold, load the color scheme data and apply it directly
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.current_color_scheme = color_scheme_name.to_string();
+
+                    tracing::info!(
+                        "Color scheme '{}' applied to slime mold simulation",
+                        color_scheme_name
+                    );
+                }
+                SimulationType::GrayScott(simulation) => {
+                    // For Gray-Scott, load the color scheme data and apply it to the renderer
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.current_color_scheme = color_scheme_name.to_string();
+                }
+                SimulationType::ParticleLife(simulation) => {
+                    // For particle life, use the existing update_state method
+                    simulation.update_state(
+                        "color_scheme",
+                        serde_json::json!(color_scheme_name),
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Flow(simulation) => {
+                    // For Flow, use the existing update_state method
+                    simulation.update_state(
+                        "currentLut",
+                        serde_json::json!(color_scheme_name),
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Pellets(simulation) => {
+                    // For Pellets, use the existing update_state method
+                    simulation.update_state(
+                        "currentLut",
+                        serde_json::json!(color_scheme_name),
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::MainMenu(_) => {
+                    // Main menu doesn't support color scheme changes
+                    tracing::warn!("Color scheme changes not supported for main menu simulation");
+                }
+                SimulationType::Gradient(simulation) => {
+                    // For gradient simulation, load the color scheme data and apply it directly
+                    let color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                }
+                SimulationType::VoronoiCA(simulation) => {
+                    // Load color scheme data and write directly into the VCA color scheme buffer
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    let data_u32 = color_scheme_data.to_u32_buffer();
+                    queue.write_buffer(&simulation.lut_buffer, 0, bytemuck::cast_slice(&data_u32));
+                    simulation.current_color_scheme = color_scheme_name.to_string();
+                }
+                SimulationType::Moire(simulation) => {
+                    // For Moiré, load the color scheme data and apply it directly
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.current_color_scheme = color_scheme_name.to_string();
+                }
+                SimulationType::PrimordialParticles(simulation) => {
+                    // For Primordial Particles, use the existing update_state method
+                    simulation.update_state(
+                        "color_scheme",
+                        serde_json::json!(color_scheme_name),
+                        device,
+                        queue,
+                    )?;
+                }
+                SimulationType::Turmites(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::LifeLike(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Lensing(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::MagneticPendulum(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Percolation(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Crowd(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Phyllotaxis(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Harmonograph(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::SlimeMold3d(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Chemotaxis(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Softbody(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Eikonal(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Quasicrystal(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Stippling(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Vortex(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Boids(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::Dla(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+                SimulationType::DoublePendulum(simulation) => {
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    simulation.state.color_scheme_name = color_scheme_name.to_string();
+                }
+            }
+        }
+        self.apply_color_script(device, queue)?;
+        self.publish_color_scheme_changed();
+        Ok(())
+    }
+
+    pub fn reverse_current_color_scheme(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        if let Some(simulation) = &mut self.current_simulation {
+            match simulation {
+                SimulationType::SlimeMold(simulation) => {
+                    // Toggle the reversed flag and reload the color scheme
+                    simulation.color_scheme_reversed = !simulation.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.current_color_scheme)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.current_color_scheme,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for slime mold simulation");
+                }
+                SimulationType::GrayScott(simulation) => {
+                    // Toggle the reversed flag and reload the color scheme
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.current_color_scheme)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.current_color_scheme,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    // Don't apply reversal here - update_color_scheme handles it internally
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Gray-Scott simulation");
+                }
+                SimulationType::ParticleLife(simulation) => {
+                    // For particle life, we need to update the color scheme with reversed flag
+                    let current_reversed = simulation.state.color_scheme_reversed;
+                    simulation.state.color_scheme_reversed = !current_reversed;
+
+                    // Get the current color scheme and apply it with the new reversal state
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.current_color_scheme)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.current_color_scheme,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                }
+                SimulationType::Flow(simulation) => {
+                    let current_reversed = simulation.state.color_scheme_reversed;
+                    simulation.update_state(
+                        "color_scheme_reversed",
+                        serde_json::json!(!current_reversed),
+                        device,
+                        queue,
+                    )?;
+                    tracing::info!("Color scheme reversed for Flow simulation");
+                }
+                SimulationType::Pellets(simulation) => {
+                    let current_reversed = simulation.state.color_scheme_reversed;
+                    simulation.update_state(
+                        "color_scheme_reversed",
+                        serde_json::json!(!current_reversed),
+                        device,
+                        queue,
+                    )?;
+                    tracing::info!("LUT reversed for Pellets simulation");
+                }
+                SimulationType::MainMenu(_) => {
+                    // Main menu doesn't support LUT changes
+                    tracing::warn!("LUT reversal not supported for main menu simulation");
+                }
+                SimulationType::VoronoiCA(simulation) => {
+                    let current_reversed = simulation.color_scheme_reversed;
+                    simulation.color_scheme_reversed = !current_reversed;
+                    // Update GPU buffer to reflect reversal using the app's color scheme manager
+                    if let Ok(lut) = self
                         .color_scheme_manager
-                        .get(color_scheme_name)
+                        .get(&simulation.current_color_scheme)
+                    {
+                        let mut data = lut.to_u32_buffer();
+                        if simulation.color_scheme_reversed {
+                            // reverse planar color scheme in-place
+                            data[0..256].reverse();
+                            data[256..512].reverse();
+                            data[512..768].reverse();
+                        }
+                        queue.write_buffer(&simulation.lut_buffer, 0, bytemuck::cast_slice(&data));
+                    }
+                    tracing::info!("Color scheme reversed for Voronoi CA simulation");
+                }
+                SimulationType::Gradient(_simulation) => {
+                    tracing::warn!("Color scheme reversal not supported for Gradient simulation");
+                }
+                SimulationType::Moire(simulation) => {
+                    // Toggle the reversed flag and reload the color scheme
+                    simulation.color_scheme_reversed = !simulation.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.current_color_scheme)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                color_scheme_name,
+                                &simulation.current_color_scheme,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("LUT reversed for Moiré simulation");
+                }
+                SimulationType::PrimordialParticles(simulation) => {
+                    // For Primordial Particles, use the existing update_state method
+                    let current_reversed = simulation.state.color_scheme_reversed;
+                    simulation.update_state(
+                        "color_scheme_reversed",
+                        serde_json::json!(!current_reversed),
+                        device,
+                        queue,
+                    )?;
+                    tracing::info!("Color scheme reversed for Primordial Particles simulation");
+                }
+                SimulationType::Turmites(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Turmites simulation");
+                }
+                SimulationType::LifeLike(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Life-like simulation");
+                }
+                SimulationType::Lensing(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Gravitational Lensing simulation");
+                }
+                SimulationType::MagneticPendulum(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Magnetic Pendulum simulation");
+                }
+                SimulationType::Percolation(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Percolation simulation");
+                }
+                SimulationType::Crowd(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Crowd simulation");
+                }
+                SimulationType::Phyllotaxis(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Phyllotaxis simulation");
+                }
+                SimulationType::Harmonograph(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Harmonograph simulation");
+                }
+                SimulationType::SlimeMold3d(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for 3D Slime Mold simulation");
+                }
+                SimulationType::Chemotaxis(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
+                                &e.to_string(),
+                            ))
+                        })?;
+
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Chemotaxis simulation");
+                }
+                SimulationType::Softbody(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
+                        .color_scheme_manager
+                        .get(&simulation.state.color_scheme_name)
+                        .map_err(|e| {
+                            AppError::ColorScheme(ColorSchemeError::load_failed(
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
@@ -844,163 +3443,117 @@ impl SimulationManager {
                     }
 
                     simulation.update_color_scheme(&color_scheme_data, device, queue)?;
-                    simulation.state.current_color_scheme = color_scheme_name.to_string();
-                }
-                SimulationType::ParticleLife(simulation) => {
-                    // For particle life, use the existing update_state method
-                    simulation.update_state(
-                        "color_scheme",
-                        serde_json::json!(color_scheme_name),
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::Flow(simulation) => {
-                    // For Flow, use the existing update_state method
-                    simulation.update_state(
-                        "currentLut",
-                        serde_json::json!(color_scheme_name),
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::Pellets(simulation) => {
-                    // For Pellets, use the existing update_state method
-                    simulation.update_state(
-                        "currentLut",
-                        serde_json::json!(color_scheme_name),
-                        device,
-                        queue,
-                    )?;
-                }
-                SimulationType::MainMenu(_) => {
-                    // Main menu doesn't support color scheme changes
-                    tracing::warn!("Color scheme changes not supported for main menu simulation");
+                    tracing::info!("Color scheme reversed for Soft Body simulation");
                 }
-                SimulationType::Gradient(simulation) => {
-                    // For gradient simulation, load the color scheme data and apply it directly
-                    let color_scheme_data = self
+                SimulationType::Eikonal(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
+                    let mut color_scheme_data = self
                         .color_scheme_manager
-                        .get(color_scheme_name)
+                        .get(&simulation.state.color_scheme_name)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                color_scheme_name,
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
 
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
                     simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Eikonal simulation");
                 }
-                SimulationType::VoronoiCA(simulation) => {
-                    // Load color scheme data and write directly into the VCA color scheme buffer
+                SimulationType::Quasicrystal(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
                     let mut color_scheme_data = self
                         .color_scheme_manager
-                        .get(color_scheme_name)
+                        .get(&simulation.state.color_scheme_name)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                color_scheme_name,
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
 
-                    if simulation.color_scheme_reversed {
+                    if simulation.state.color_scheme_reversed {
                         color_scheme_data.reverse();
                     }
 
-                    let data_u32 = color_scheme_data.to_u32_buffer();
-                    queue.write_buffer(&simulation.lut_buffer, 0, bytemuck::cast_slice(&data_u32));
-                    simulation.current_color_scheme = color_scheme_name.to_string();
+                    simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for Quasicrystal simulation");
                 }
-                SimulationType::Moire(simulation) => {
-                    // For Moiré, load the color scheme data and apply it directly
+                SimulationType::Stippling(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
                     let mut color_scheme_data = self
                         .color_scheme_manager
-                        .get(color_scheme_name)
+                        .get(&simulation.state.color_scheme_name)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                color_scheme_name,
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
 
-                    if simulation.color_scheme_reversed {
+                    if simulation.state.color_scheme_reversed {
                         color_scheme_data.reverse();
                     }
 
                     simulation.update_color_scheme(&color_scheme_data, device, queue)?;
-                    simulation.current_color_scheme = color_scheme_name.to_string();
-                }
-                SimulationType::PrimordialParticles(simulation) => {
-                    // For Primordial Particles, use the existing update_state method
-                    simulation.update_state(
-                        "color_scheme",
-                        serde_json::json!(color_scheme_name),
-                        device,
-                        queue,
-                    )?;
+                    tracing::info!("Color scheme reversed for Stippling simulation");
                 }
-            }
-        }
-        Ok(())
-    }
-
-    pub fn reverse_current_color_scheme(
-        &mut self,
-        device: &Arc<Device>,
-        queue: &Arc<Queue>,
-    ) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            match simulation {
-                SimulationType::SlimeMold(simulation) => {
-                    // Toggle the reversed flag and reload the color scheme
-                    simulation.color_scheme_reversed = !simulation.color_scheme_reversed;
+                SimulationType::Vortex(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
                     let mut color_scheme_data = self
                         .color_scheme_manager
-                        .get(&simulation.current_color_scheme)
+                        .get(&simulation.state.color_scheme_name)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                &simulation.current_color_scheme,
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
 
-                    if simulation.color_scheme_reversed {
+                    if simulation.state.color_scheme_reversed {
                         color_scheme_data.reverse();
                     }
 
                     simulation.update_color_scheme(&color_scheme_data, device, queue)?;
-                    tracing::info!("Color scheme reversed for slime mold simulation");
+                    tracing::info!("Color scheme reversed for Vortex Smoke simulation");
                 }
-                SimulationType::GrayScott(simulation) => {
-                    // Toggle the reversed flag and reload the color scheme
+                SimulationType::Boids(simulation) => {
                     simulation.state.color_scheme_reversed =
                         !simulation.state.color_scheme_reversed;
-                    let color_scheme_data = self
+                    let mut color_scheme_data = self
                         .color_scheme_manager
-                        .get(&simulation.state.current_color_scheme)
+                        .get(&simulation.state.color_scheme_name)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                &simulation.state.current_color_scheme,
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
 
-                    // Don't apply reversal here - update_color_scheme handles it internally
+                    if simulation.state.color_scheme_reversed {
+                        color_scheme_data.reverse();
+                    }
+
                     simulation.update_color_scheme(&color_scheme_data, device, queue)?;
-                    tracing::info!("Color scheme reversed for Gray-Scott simulation");
+                    tracing::info!("Color scheme reversed for Boids simulation");
                 }
-                SimulationType::ParticleLife(simulation) => {
-                    // For particle life, we need to update the color scheme with reversed flag
-                    let current_reversed = simulation.state.color_scheme_reversed;
-                    simulation.state.color_scheme_reversed = !current_reversed;
-
-                    // Get the current color scheme and apply it with the new reversal state
+                SimulationType::Dla(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
                     let mut color_scheme_data = self
                         .color_scheme_manager
-                        .get(&simulation.state.current_color_scheme)
+                        .get(&simulation.state.color_scheme_name)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                &simulation.state.current_color_scheme,
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
@@ -1010,86 +3563,32 @@ impl SimulationManager {
                     }
 
                     simulation.update_color_scheme(&color_scheme_data, device, queue)?;
+                    tracing::info!("Color scheme reversed for DLA simulation");
                 }
-                SimulationType::Flow(simulation) => {
-                    let current_reversed = simulation.state.color_scheme_reversed;
-                    simulation.update_state(
-                        "color_scheme_reversed",
-                        serde_json::json!(!current_reversed),
-                        device,
-                        queue,
-                    )?;
-                    tracing::info!("Color scheme reversed for Flow simulation");
-                }
-                SimulationType::Pellets(simulation) => {
-                    let current_reversed = simulation.state.color_scheme_reversed;
-                    simulation.update_state(
-                        "color_scheme_reversed",
-                        serde_json::json!(!current_reversed),
-                        device,
-                        queue,
-                    )?;
-                    tracing::info!("LUT reversed for Pellets simulation");
-                }
-                SimulationType::MainMenu(_) => {
-                    // Main menu doesn't support LUT changes
-                    tracing::warn!("LUT reversal not supported for main menu simulation");
-                }
-                SimulationType::VoronoiCA(simulation) => {
-                    let current_reversed = simulation.color_scheme_reversed;
-                    simulation.color_scheme_reversed = !current_reversed;
-                    // Update GPU buffer to reflect reversal using the app's color scheme manager
-                    if let Ok(lut) = self
-                        .color_scheme_manager
-                        .get(&simulation.current_color_scheme)
-                    {
-                        let mut data = lut.to_u32_buffer();
-                        if simulation.color_scheme_reversed {
-                            // reverse planar color scheme in-place
-                            data[0..256].reverse();
-                            data[256..512].reverse();
-                            data[512..768].reverse();
-                        }
-                        queue.write_buffer(&simulation.lut_buffer, 0, bytemuck::cast_slice(&data));
-                    }
-                    tracing::info!("Color scheme reversed for Voronoi CA simulation");
-                }
-                SimulationType::Gradient(_simulation) => {
-                    tracing::warn!("Color scheme reversal not supported for Gradient simulation");
-                }
-                SimulationType::Moire(simulation) => {
-                    // Toggle the reversed flag and reload the color scheme
-                    simulation.color_scheme_reversed = !simulation.color_scheme_reversed;
+                SimulationType::DoublePendulum(simulation) => {
+                    simulation.state.color_scheme_reversed =
+                        !simulation.state.color_scheme_reversed;
                     let mut color_scheme_data = self
                         .color_scheme_manager
-                        .get(&simulation.current_color_scheme)
+                        .get(&simulation.state.color_scheme_name)
                         .map_err(|e| {
                             AppError::ColorScheme(ColorSchemeError::load_failed(
-                                &simulation.current_color_scheme,
+                                &simulation.state.color_scheme_name,
                                 &e.to_string(),
                             ))
                         })?;
 
-                    if simulation.color_scheme_reversed {
+                    if simulation.state.color_scheme_reversed {
                         color_scheme_data.reverse();
                     }
 
                     simulation.update_color_scheme(&color_scheme_data, device, queue)?;
-                    tracing::info!("LUT reversed for Moiré simulation");
-                }
-                SimulationType::PrimordialParticles(simulation) => {
-                    // For Primordial Particles, use the existing update_state method
-                    let current_reversed = simulation.state.color_scheme_reversed;
-                    simulation.update_state(
-                        "color_scheme_reversed",
-                        serde_json::json!(!current_reversed),
-                        device,
-                        queue,
-                    )?;
-                    tracing::info!("Color scheme reversed for Primordial Particles simulation");
+                    tracing::info!("Color scheme reversed for Double Pendulum simulation");
                 }
             }
         }
+        self.apply_color_script(device, queue)?;
+        self.publish_color_scheme_changed();
         Ok(())
     }
 
@@ -1104,9 +3603,87 @@ impl SimulationManager {
             simulation.update_color_scheme(color_scheme, device, queue)?;
             tracing::info!("Custom color scheme applied to simulation");
         }
+        self.publish_color_scheme_changed();
+        Ok(())
+    }
+
+    /// Run `source` over the running simulation's color scheme from now on,
+    /// or go back to the plain scheme when it's `None`. A script that doesn't
+    /// compile is rejected and the current one kept.
+    pub fn set_color_script(
+        &mut self,
+        source: Option<String>,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        let Some((color_scheme, _)) = self.current_color_scheme() else {
+            return Err(SimulationError::InvalidParameter(
+                "No simulation with a color scheme is running".to_string(),
+            )
+            .into());
+        };
+        let simulation_type = self
+            .current_simulation
+            .as_ref()
+            .map_or("", |simulation| simulation.type_name())
+            .to_string();
+        match source {
+            Some(source) => {
+                ColorScript::compile(&source)?;
+                self.color_scripts.insert(simulation_type, source);
+            }
+            None => {
+                self.color_scripts.remove(&simulation_type);
+            }
+        }
+        // Uploads the plain scheme and then runs the script over it
+        self.apply_color_scheme(&color_scheme, device, queue)
+    }
+
+    /// The running simulation type's color script, compiled
+    fn color_script(&self) -> AppResult<Option<ColorScript>> {
+        let Some(simulation) = &self.current_simulation else {
+            return Ok(None);
+        };
+        let Some(source) = self.color_scripts.get(simulation.type_name()) else {
+            return Ok(None);
+        };
+        Ok(Some(ColorScript::compile(source)?))
+    }
+
+    /// Upload the running simulation's color scheme with its color script run
+    /// over it. Does nothing when it has no script.
+    fn apply_color_script(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> AppResult<()> {
+        let Some(script) = self.color_script()? else {
+            return Ok(());
+        };
+        let Some((name, reversed)) = self.current_color_scheme() else {
+            return Ok(());
+        };
+        let Some(simulation) = &mut self.current_simulation else {
+            return Ok(());
+        };
+        let mut scheme = self.color_scheme_manager.get(&name).map_err(|e| {
+            AppError::ColorScheme(ColorSchemeError::load_failed(&name, &e.to_string()))
+        })?;
+        let reverses_on_upload = reverses_color_scheme_on_upload(simulation);
+        if reversed && !reverses_on_upload {
+            scheme.reverse();
+        }
+        let scheme = scripted_upload(&script, scheme, reversed && reverses_on_upload)?;
+        simulation.update_color_scheme(&scheme, device, queue)?;
         Ok(())
     }
 
+    fn publish_color_scheme_changed(&self) {
+        if let Some((color_scheme, reversed)) = self.current_color_scheme() {
+            self.events.publish(SimulationEvent::ColorSchemeChanged {
+                color_scheme,
+                reversed,
+            });
+        }
+    }
+
     // Render loop management
     pub fn start_render_loop(
         &self,
@@ -1126,6 +3703,7 @@ impl SimulationManager {
             let mut frame_count = 0u32;
             let mut last_fps_update = Instant::now();
             let mut last_frame_time = Instant::now();
+            let mut render_failing = false;
 
             while render_loop_running.load(Ordering::Relaxed) {
                 let frame_start = Instant::now();
@@ -1157,23 +3735,59 @@ impl SimulationManager {
                                     }
                                 }
 
-                                let render_result = if do_update {
-                                    sim_manager.render(
-                                        &gpu_ctx.device,
-                                        &gpu_ctx.queue,
-                                        &view,
-                                        delta_time,
-                                    )
-                                } else {
-                                    sim_manager.render_paused(
-                                        &gpu_ctx.device,
-                                        &gpu_ctx.queue,
-                                        &view,
-                                    )
+                                // Scoped so the error isn't held over the restart's awaits
+                                let first_failure = {
+                                    let render_result = if do_update {
+                                        sim_manager.render(
+                                            &gpu_ctx.device,
+                                            &gpu_ctx.queue,
+                                            &view,
+                                            delta_time,
+                                        )
+                                    } else {
+                                        sim_manager.render_paused(
+                                            &gpu_ctx.device,
+                                            &gpu_ctx.queue,
+                                            &view,
+                                        )
+                                    };
+
+                                    match render_result {
+                                        Ok(()) => {
+                                            output.present();
+                                            render_failing = false;
+                                            false
+                                        }
+                                        Err(e) => {
+                                            // Once per failure, not every frame it lasts
+                                            let first_failure = !render_failing;
+                                            if first_failure {
+                                                tracing::error!("Failed to render frame: {}", e);
+                                                sim_manager.events.publish(
+                                                    SimulationEvent::Error {
+                                                        message: format!(
+                                                            "Failed to render frame: {}",
+                                                            e
+                                                        ),
+                                                    },
+                                                );
+                                            }
+                                            render_failing = true;
+                                            first_failure
+                                        }
+                                    }
                                 };
-
-                                if render_result.is_ok() {
-                                    output.present();
+                                if first_failure {
+                                    let surface_config =
+                                        gpu_ctx.surface_config.lock().await.clone();
+                                    sim_manager
+                                        .restart_in_kiosk(
+                                            &gpu_ctx.device,
+                                            &gpu_ctx.queue,
+                                            &surface_config,
+                                            &gpu_ctx.adapter_info,
+                                        )
+                                        .await;
                                 }
                             }
                             Err(e) => {
@@ -1233,12 +3847,59 @@ impl SimulationManager {
                                 }
                             }
                         }
+
+                        for warning in sim_manager.watchdog.take_warnings() {
+                            if let Err(e) = app_handle.emit(HEALTH_WARNING_EVENT, &warning) {
+                                tracing::warn!("Failed to emit health warning: {}", e);
+                            }
+                        }
+
+                        if sim_manager.preview_stream.is_some() {
+                            let surface_config = gpu_ctx.surface_config.lock().await.clone();
+                            match sim_manager.stream_preview_frame(
+                                &gpu_ctx.device,
+                                &gpu_ctx.queue,
+                                &surface_config,
+                            ) {
+                                Ok(Some(frame)) => {
+                                    if let Err(e) = app_handle.emit("preview-frame", frame) {
+                                        tracing::warn!("Failed to emit preview frame: {}", e);
+                                    }
+                                }
+                                Ok(None) => {}
+                                Err(e) => {
+                                    tracing::warn!("Preview stream stopped: {}", e);
+                                }
+                            }
+                        }
+
+                        if sim_manager.recording.is_some() {
+                            let surface_config = gpu_ctx.surface_config.lock().await.clone();
+                            if let Err(e) = sim_manager.record_frame(
+                                &gpu_ctx.device,
+                                &gpu_ctx.queue,
+                                &surface_config,
+                            ) {
+                                tracing::warn!("Recording stopped: {}", e);
+                                if let Err(e) = app_handle.emit("recording-stopped", e.to_string())
+                                {
+                                    tracing::warn!("Failed to emit recording stopped: {}", e);
+                                }
+                            }
+                        }
                     } else {
                         // Stop the render loop if simulation is no longer running
                         break;
                     }
                 }
 
+                // Let the frontend know when something had to be allocated smaller than asked
+                for warning in gpu_budget::take_warnings() {
+                    if let Err(e) = app_handle.emit("gpu-memory-warning", &warning) {
+                        tracing::warn!("Failed to emit GPU memory warning: {}", e);
+                    }
+                }
+
                 // Update last frame time for next iteration
                 last_frame_time = frame_start;
                 frame_count += 1;
@@ -1251,6 +3912,10 @@ impl SimulationManager {
                     if let Err(e) = app_handle.emit("fps-update", fps) {
                         tracing::warn!("Failed to emit FPS update: {}", e);
                     }
+                    let limit = fps_limit_enabled
+                        .load(Ordering::Relaxed)
+                        .then(|| fps_limit.load(Ordering::Relaxed));
+                    manager.lock().await.events.report_fps(fps, limit);
 
                     frame_count = 0;
                     last_fps_update = Instant::now();
@@ -1284,8 +3949,11 @@ impl SimulationManager {
 
     // Reset methods
     pub fn reset_trails(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            simulation.reset_runtime_state(device, queue)?;
+        match &mut self.current_simulation {
+            // Its runtime state includes the agents, which should stay put
+            Some(SimulationType::SlimeMold(sim)) => sim.reset_trails(queue),
+            Some(simulation) => simulation.reset_runtime_state(device, queue)?,
+            None => {}
         }
         Ok(())
     }
@@ -1294,8 +3962,8 @@ impl SimulationManager {
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::SlimeMold(sim) => {
-                    // For slime mold, call the specific reset_agents method that repositions agents randomly
-                    sim.reset_agents(device, queue)
+                    // For slime mold, reposition the agents from a fresh random seed
+                    sim.randomize_agents(device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 _ => {
@@ -1308,6 +3976,7 @@ impl SimulationManager {
     }
 
     pub fn reset_simulation(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> AppResult<()> {
+        self.rewind.clear();
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::GrayScott(sim) => {
@@ -1322,31 +3991,223 @@ impl SimulationManager {
                 }
             }
         }
-        Ok(())
+        Ok(())
+    }
+
+    pub fn reset_runtime_state(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        self.rewind.clear();
+        if let Some(simulation) = &mut self.current_simulation {
+            simulation.reset_runtime_state(device, queue)?;
+        }
+        Ok(())
+    }
+
+    /// Jump back `seconds` of simulated time, as far as the history reaches
+    pub fn rewind(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        seconds: f32,
+    ) -> AppResult<RewindHistory> {
+        let simulation = self
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?;
+        self.rewind
+            .rewind(device, queue, seconds, &simulation.rewind_resources())?;
+        Ok(self.rewind.history())
+    }
+
+    /// Pause and show snapshot `index` of the history, 0 being the oldest
+    pub fn scrub_rewind(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        index: usize,
+    ) -> AppResult<RewindHistory> {
+        let simulation = self
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?;
+        self.pause();
+        self.rewind
+            .restore(device, queue, index, &simulation.rewind_resources())?;
+        Ok(self.rewind.history())
+    }
+
+    /// Read the running simulation's settings, camera and live state back
+    /// from the GPU
+    pub fn simulation_snapshot(
+        &self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<SimulationSnapshot> {
+        let simulation = self
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?;
+        let resources = simulation.rewind_resources();
+        if resources.is_empty() {
+            return Err(SimulationError::UnsupportedOperation.into());
+        }
+        Ok(SimulationSnapshot {
+            version: snapshot::SNAPSHOT_VERSION,
+            simulation_type: simulation.type_name().to_string(),
+            settings: simulation.get_settings(),
+            camera: self.camera_view(),
+            resources: snapshot::read_resources(device, queue, &resources)?,
+        })
     }
 
-    pub fn reset_runtime_state(
+    /// Put the running simulation back into the state `snapshot` was taken
+    /// in. The simulation must be the same type and, for state sized by the
+    /// window, the window the same size.
+    pub fn load_simulation_snapshot(
         &mut self,
+        snapshot: &SimulationSnapshot,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
-        if let Some(simulation) = &mut self.current_simulation {
-            simulation.reset_runtime_state(device, queue)?;
+        if snapshot.version > snapshot::SNAPSHOT_VERSION {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Snapshot version {} is newer than this build reads",
+                snapshot.version
+            ))
+            .into());
+        }
+        let simulation = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        if simulation.type_name() != snapshot.simulation_type {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Snapshot is of {}, but {} is running",
+                snapshot.simulation_type,
+                simulation.type_name()
+            ))
+            .into());
+        }
+
+        self.transition = None;
+        simulation.apply_settings(snapshot.settings.clone(), device, queue)?;
+        snapshot::write_resources(queue, &simulation.rewind_resources(), &snapshot.resources)?;
+        self.autopilot.rehome();
+        self.audio_reactive.rehome();
+        self.rewind.clear();
+        if let Some(view) = snapshot.camera {
+            self.set_camera_view(view);
         }
         Ok(())
     }
 
+    /// Randomize the running simulation's settings, all of them or only as
+    /// much as `options` asks for
     pub fn randomize_settings(
         &mut self,
+        options: RandomizeOptions,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
+        self.transition = None;
         if let Some(simulation) = &mut self.current_simulation {
-            simulation.randomize_settings(device, queue)?;
+            if options.is_full() {
+                simulation.randomize_settings(device, queue)?;
+            } else {
+                let current = simulation.get_settings();
+                simulation.randomize_settings(device, queue)?;
+                let rolled = simulation.get_settings();
+                let settings = randomize::randomized(
+                    &current,
+                    &rolled,
+                    options,
+                    simulation.setting_categories(),
+                    simulation.seed_setting(),
+                    &mut rand::rng(),
+                );
+                simulation.apply_settings(settings, device, queue)?;
+            }
+            self.autopilot.rehome();
+            self.audio_reactive.rehome();
         }
         Ok(())
     }
 
+    /// Start evolving the running simulation's settings, replacing any
+    /// evolution already under way
+    pub async fn start_evolution(
+        &mut self,
+        config: EvolutionConfig,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_format: wgpu::TextureFormat,
+        adapter_info: &wgpu::AdapterInfo,
+    ) -> AppResult<&Generation> {
+        let simulation = self
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?;
+        // Previews show the variants in the colors they'll be seen in
+        let color_scheme = match self.current_color_scheme() {
+            Some((name, reversed)) => {
+                let mut color_scheme = self.color_scheme_manager.get(&name)?;
+                if reversed {
+                    color_scheme.reverse();
+                }
+                Some(color_scheme)
+            }
+            None => None,
+        };
+
+        self.evolution = None;
+        let evolution = Evolution::new(
+            simulation.type_name(),
+            simulation.get_settings(),
+            color_scheme.as_ref(),
+            config,
+            device,
+            queue,
+            surface_format,
+            adapter_info,
+            &self.color_scheme_manager,
+            &self.app_settings,
+        )
+        .await?;
+        Ok(self.evolution.insert(evolution).generation())
+    }
+
+    pub fn evolution_mut(&mut self) -> AppResult<&mut Evolution> {
+        self.evolution.as_mut().ok_or_else(|| {
+            SimulationError::InvalidParameter("No evolution is running".to_string()).into()
+        })
+    }
+
+    /// Give the running simulation the settings of one of the current
+    /// generation's variants
+    pub fn apply_evolution_variant(
+        &mut self,
+        index: usize,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        let variant_settings = self.evolution_mut()?.variant(index)?.settings.clone();
+        let simulation = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        let mut settings = simulation.get_settings();
+        merge_settings(&mut settings, &variant_settings);
+        simulation.apply_settings(settings, device, queue)?;
+        simulation.reset_runtime_state(device, queue)?;
+        self.autopilot.rehome();
+        self.audio_reactive.rehome();
+        self.current_preset = None;
+        Ok(())
+    }
+
     // Note: seed_random_noise is Gray-Scott and CSA specific functionality
     pub fn seed_random_noise(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> AppResult<()> {
         if let Some(simulation) = &mut self.current_simulation {
@@ -1369,10 +4230,11 @@ impl SimulationManager {
 
     // Camera control methods
     pub fn pan_camera(&mut self, delta_x: f32, delta_y: f32) {
+        self.record_action(MacroAction::PanCamera { delta_x, delta_y });
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::SlimeMold(simulation) => simulation.pan_camera(delta_x, delta_y),
-                SimulationType::GrayScott(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::GrayScott(simulation) => simulation.pan_camera(delta_x, delta_y),
                 SimulationType::ParticleLife(simulation) => simulation.camera.pan(delta_x, delta_y),
                 SimulationType::Flow(simulation) => simulation.pan_camera(delta_x, delta_y),
                 SimulationType::Pellets(simulation) => simulation.pan_camera(delta_x, delta_y),
@@ -1382,16 +4244,41 @@ impl SimulationManager {
                 SimulationType::PrimordialParticles(simulation) => {
                     simulation.pan_camera(delta_x, delta_y)
                 }
+                SimulationType::Turmites(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::LifeLike(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Lensing(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::MagneticPendulum(simulation) => {
+                    simulation.camera.pan(delta_x, delta_y)
+                }
+                SimulationType::Percolation(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Crowd(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Phyllotaxis(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Harmonograph(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::SlimeMold3d(simulation) => {
+                    simulation.camera.orbit(delta_x, delta_y)
+                }
+                SimulationType::Chemotaxis(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Softbody(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Eikonal(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Quasicrystal(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Stippling(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Vortex(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Boids(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::Dla(simulation) => simulation.camera.pan(delta_x, delta_y),
+                SimulationType::DoublePendulum(simulation) => {
+                    simulation.camera.pan(delta_x, delta_y)
+                }
                 _ => {}
             }
         }
     }
 
     pub fn zoom_camera(&mut self, delta: f32) {
+        self.record_action(MacroAction::ZoomCamera { delta });
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::SlimeMold(simulation) => simulation.zoom_camera(delta),
-                SimulationType::GrayScott(simulation) => simulation.camera.zoom(delta),
+                SimulationType::GrayScott(simulation) => simulation.zoom_camera(delta),
                 SimulationType::ParticleLife(simulation) => simulation.camera.zoom(delta),
                 SimulationType::Flow(simulation) => simulation.camera.zoom(delta),
                 SimulationType::Pellets(simulation) => simulation.camera.zoom(delta),
@@ -1399,19 +4286,43 @@ impl SimulationManager {
                 SimulationType::VoronoiCA(simulation) => simulation.camera.zoom(delta),
                 SimulationType::Moire(simulation) => simulation.zoom_camera(delta),
                 SimulationType::PrimordialParticles(simulation) => simulation.zoom_camera(delta),
+                SimulationType::Turmites(simulation) => simulation.camera.zoom(delta),
+                SimulationType::LifeLike(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Lensing(simulation) => simulation.camera.zoom(delta),
+                SimulationType::MagneticPendulum(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Percolation(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Crowd(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Phyllotaxis(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Harmonograph(simulation) => simulation.camera.zoom(delta),
+                SimulationType::SlimeMold3d(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Chemotaxis(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Softbody(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Eikonal(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Quasicrystal(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Stippling(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Vortex(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Boids(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Dla(simulation) => simulation.camera.zoom(delta),
+                SimulationType::DoublePendulum(simulation) => simulation.camera.zoom(delta),
                 _ => {}
             }
         }
     }
 
     pub fn zoom_camera_to_cursor(&mut self, delta: f32, cursor_x: f32, cursor_y: f32) {
+        self.record_action(MacroAction::ZoomCameraToCursor {
+            delta,
+            cursor_x,
+            cursor_y,
+        });
+        let [cursor_x, cursor_y] = self.master_bus.scene_position([cursor_x, cursor_y]);
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::SlimeMold(simulation) => {
                     simulation.zoom_camera_to_cursor(delta, cursor_x, cursor_y)
                 }
                 SimulationType::GrayScott(simulation) => {
-                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                    simulation.zoom_camera_to_cursor(delta, cursor_x, cursor_y)
                 }
                 SimulationType::ParticleLife(simulation) => {
                     simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
@@ -1432,16 +4343,69 @@ impl SimulationManager {
                 SimulationType::PrimordialParticles(simulation) => {
                     simulation.zoom_camera_to_cursor(delta, cursor_x, cursor_y)
                 }
+                SimulationType::Turmites(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::LifeLike(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Lensing(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::MagneticPendulum(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Percolation(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Crowd(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Phyllotaxis(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Harmonograph(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::SlimeMold3d(simulation) => simulation.camera.zoom(delta),
+                SimulationType::Chemotaxis(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Softbody(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Eikonal(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Quasicrystal(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Stippling(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Vortex(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Boids(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::Dla(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
+                SimulationType::DoublePendulum(simulation) => {
+                    simulation.camera.zoom_to_cursor(delta, cursor_x, cursor_y)
+                }
                 _ => {}
             }
         }
     }
 
     pub fn reset_camera(&mut self) {
+        self.record_action(MacroAction::ResetCamera);
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::SlimeMold(simulation) => simulation.reset_camera(),
-                SimulationType::GrayScott(simulation) => simulation.camera.reset(),
+                SimulationType::GrayScott(simulation) => simulation.reset_camera(),
                 SimulationType::ParticleLife(simulation) => simulation.camera.reset(),
                 SimulationType::Flow(simulation) => simulation.camera.reset(),
                 SimulationType::Pellets(simulation) => simulation.camera.reset(),
@@ -1449,6 +4413,24 @@ impl SimulationManager {
                 SimulationType::VoronoiCA(simulation) => simulation.camera.reset(),
                 SimulationType::Moire(simulation) => simulation.reset_camera(),
                 SimulationType::PrimordialParticles(simulation) => simulation.reset_camera(),
+                SimulationType::Turmites(simulation) => simulation.camera.reset(),
+                SimulationType::LifeLike(simulation) => simulation.camera.reset(),
+                SimulationType::Lensing(simulation) => simulation.camera.reset(),
+                SimulationType::MagneticPendulum(simulation) => simulation.camera.reset(),
+                SimulationType::Percolation(simulation) => simulation.camera.reset(),
+                SimulationType::Crowd(simulation) => simulation.camera.reset(),
+                SimulationType::Phyllotaxis(simulation) => simulation.camera.reset(),
+                SimulationType::Harmonograph(simulation) => simulation.camera.reset(),
+                SimulationType::SlimeMold3d(simulation) => simulation.camera.reset(),
+                SimulationType::Chemotaxis(simulation) => simulation.camera.reset(),
+                SimulationType::Softbody(simulation) => simulation.camera.reset(),
+                SimulationType::Eikonal(simulation) => simulation.camera.reset(),
+                SimulationType::Quasicrystal(simulation) => simulation.camera.reset(),
+                SimulationType::Stippling(simulation) => simulation.camera.reset(),
+                SimulationType::Vortex(simulation) => simulation.camera.reset(),
+                SimulationType::Boids(simulation) => simulation.camera.reset(),
+                SimulationType::Dla(simulation) => simulation.camera.reset(),
+                SimulationType::DoublePendulum(simulation) => simulation.camera.reset(),
                 _ => {}
             }
         }
@@ -1467,6 +4449,24 @@ impl SimulationManager {
                 SimulationType::PrimordialParticles(simulation) => {
                     Some(simulation.get_camera_state())
                 }
+                SimulationType::Turmites(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::LifeLike(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Lensing(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::MagneticPendulum(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Percolation(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Crowd(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Phyllotaxis(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Harmonograph(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::SlimeMold3d(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Chemotaxis(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Softbody(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Eikonal(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Quasicrystal(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Stippling(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Vortex(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Boids(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::Dla(simulation) => Some(simulation.camera.get_state()),
+                SimulationType::DoublePendulum(simulation) => Some(simulation.camera.get_state()),
                 _ => Some(serde_json::json!({})), // No camera for other simulations
             }
         } else {
@@ -1482,7 +4482,10 @@ impl SimulationManager {
                     simulation.camera.set_smoothing_factor(smoothing_factor)
                 }
                 SimulationType::GrayScott(simulation) => {
-                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                    simulation.camera.set_smoothing_factor(smoothing_factor);
+                    simulation
+                        .orbit_camera
+                        .set_smoothing_factor(smoothing_factor);
                 }
                 SimulationType::ParticleLife(simulation) => {
                     simulation.camera.set_smoothing_factor(smoothing_factor)
@@ -1500,11 +4503,301 @@ impl SimulationManager {
                 SimulationType::PrimordialParticles(simulation) => {
                     simulation.camera.set_smoothing_factor(smoothing_factor)
                 }
+                SimulationType::Turmites(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::LifeLike(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Lensing(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::MagneticPendulum(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Percolation(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Crowd(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Phyllotaxis(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Harmonograph(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::SlimeMold3d(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Chemotaxis(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Softbody(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Eikonal(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Quasicrystal(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Stippling(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Vortex(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Boids(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::Dla(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
+                SimulationType::DoublePendulum(simulation) => {
+                    simulation.camera.set_smoothing_factor(smoothing_factor)
+                }
                 _ => {} // No camera for other simulations
             }
         }
     }
 
+    fn active_camera(&self) -> Option<&Camera> {
+        self.current_simulation.as_ref()?.camera()
+    }
+
+    fn active_camera_mut(&mut self) -> Option<&mut Camera> {
+        self.current_simulation.as_mut()?.camera_mut()
+    }
+
+    /// Where the active simulation's camera is heading, ignoring smoothing
+    pub fn camera_view(&self) -> Option<CameraView> {
+        self.active_camera().map(|camera| CameraView {
+            position: camera.get_target_position(),
+            zoom: camera.get_target_zoom(),
+        })
+    }
+
+    /// Move the active simulation's camera without smoothing
+    pub fn set_camera_view(&mut self, view: CameraView) {
+        if let Some(camera) = self.active_camera_mut() {
+            camera.set_view(view.position, view.zoom);
+        }
+    }
+
+    /// Capture the running simulation's settings and camera for sharing
+    pub fn shared_configuration(&self) -> AppResult<SharedConfiguration> {
+        let simulation = self
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?;
+        let color_scheme = match self.current_color_scheme() {
+            Some((name, reversed)) => {
+                // Built-in schemes ship with every copy, only custom ones need their colors
+                let is_custom = self
+                    .color_scheme_manager
+                    .all_custom_luts()
+                    .is_ok_and(|custom| custom.contains(&name));
+                let data = if is_custom {
+                    Some(SharedColorScheme::embed(
+                        &self.color_scheme_manager.get_custom(&name)?,
+                    ))
+                } else {
+                    None
+                };
+                Some(SharedColorScheme {
+                    name,
+                    reversed,
+                    data,
+                })
+            }
+            None => None,
+        };
+
+        Ok(SharedConfiguration {
+            simulation_type: simulation.type_name().to_string(),
+            preset: self.current_preset.clone(),
+            settings: Some(simulation.get_settings()),
+            camera: self.camera_view(),
+            color_scheme,
+        })
+    }
+
+    /// Name and reversal of the active simulation's color scheme
+    pub fn current_color_scheme(&self) -> Option<(String, bool)> {
+        match self.current_simulation.as_ref()? {
+            SimulationType::SlimeMold(simulation) => Some((
+                simulation.current_color_scheme.clone(),
+                simulation.color_scheme_reversed,
+            )),
+            SimulationType::GrayScott(simulation) => Some((
+                simulation.state.current_color_scheme.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::ParticleLife(simulation) => Some((
+                simulation.state.current_color_scheme.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Flow(simulation) => Some((
+                simulation.state.current_color_scheme.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Pellets(simulation) => Some((
+                simulation.state.current_color_scheme.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::VoronoiCA(simulation) => Some((
+                simulation.current_color_scheme.clone(),
+                simulation.color_scheme_reversed,
+            )),
+            SimulationType::Moire(simulation) => Some((
+                simulation.current_color_scheme.clone(),
+                simulation.color_scheme_reversed,
+            )),
+            SimulationType::PrimordialParticles(simulation) => Some((
+                simulation.state.current_color_scheme.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Turmites(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::LifeLike(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Lensing(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::MagneticPendulum(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Percolation(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Crowd(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Phyllotaxis(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Harmonograph(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::SlimeMold3d(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Chemotaxis(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Softbody(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Eikonal(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Quasicrystal(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Stippling(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Vortex(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Boids(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::Dla(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::DoublePendulum(simulation) => Some((
+                simulation.state.color_scheme_name.clone(),
+                simulation.state.color_scheme_reversed,
+            )),
+            SimulationType::MainMenu(_) | SimulationType::Gradient(_) => None,
+        }
+    }
+
+    /// Apply a shared configuration to the running simulation, which must be
+    /// of the configuration's type. Settings take precedence over the preset.
+    pub fn apply_shared_configuration(
+        &mut self,
+        config: &SharedConfiguration,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<()> {
+        let simulation = self
+            .current_simulation
+            .as_mut()
+            .ok_or(SimulationError::NotRunning)?;
+        if simulation.type_name() != config.simulation_type {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Configuration is for {}, but {} is running",
+                config.simulation_type,
+                simulation.type_name()
+            ))
+            .into());
+        }
+
+        if let Some(shared_settings) = &config.settings {
+            let mut settings = simulation.get_settings();
+            merge_settings(&mut settings, shared_settings);
+            simulation.apply_settings(settings, device, queue)?;
+            simulation.reset_runtime_state(device, queue)?;
+            self.autopilot.rehome();
+            self.audio_reactive.rehome();
+            self.current_preset = config.preset.clone();
+        } else if let Some(preset) = &config.preset {
+            self.apply_preset(preset, device, queue)?;
+        }
+
+        if let Some(shared_scheme) = &config.color_scheme {
+            let available = self
+                .color_scheme_manager
+                .all_color_schemes()
+                .contains(&shared_scheme.name);
+            if !available {
+                match shared_scheme.embedded_color_scheme() {
+                    Some(color_scheme) => self
+                        .color_scheme_manager
+                        .save_custom(&shared_scheme.name, &color_scheme?)?,
+                    None => {
+                        return Err(ColorSchemeError::NotFound(shared_scheme.name.clone()).into());
+                    }
+                }
+            }
+
+            self.apply_color_scheme(&shared_scheme.name, device, queue)?;
+            if self
+                .current_color_scheme()
+                .is_some_and(|(_, reversed)| reversed != shared_scheme.reversed)
+            {
+                self.reverse_current_color_scheme(device, queue)?;
+            }
+        }
+
+        if let Some(view) = config.camera {
+            self.set_camera_view(view);
+        }
+        Ok(())
+    }
+
     /// Set the camera sensitivity for the active simulation
     pub fn set_camera_sensitivity(&mut self, sensitivity: f32) {
         if let Some(simulation) = &mut self.current_simulation {
@@ -1513,7 +4806,8 @@ impl SimulationManager {
                     simulation.camera.set_sensitivity(sensitivity)
                 }
                 SimulationType::GrayScott(simulation) => {
-                    simulation.camera.set_sensitivity(sensitivity)
+                    simulation.camera.set_sensitivity(sensitivity);
+                    simulation.orbit_camera.set_sensitivity(sensitivity);
                 }
                 SimulationType::ParticleLife(simulation) => {
                     simulation.camera.set_sensitivity(sensitivity)
@@ -1529,11 +4823,77 @@ impl SimulationManager {
                 SimulationType::PrimordialParticles(simulation) => {
                     simulation.camera.set_sensitivity(sensitivity)
                 }
+                SimulationType::Turmites(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::LifeLike(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Lensing(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::MagneticPendulum(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Percolation(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Crowd(simulation) => simulation.camera.set_sensitivity(sensitivity),
+                SimulationType::Phyllotaxis(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Harmonograph(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::SlimeMold3d(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Chemotaxis(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Softbody(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Eikonal(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Quasicrystal(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Stippling(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Vortex(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
+                SimulationType::Boids(simulation) => simulation.camera.set_sensitivity(sensitivity),
+                SimulationType::Dla(simulation) => simulation.camera.set_sensitivity(sensitivity),
+                SimulationType::DoublePendulum(simulation) => {
+                    simulation.camera.set_sensitivity(sensitivity)
+                }
                 _ => {} // No camera for other simulations
             }
         }
     }
 
+    /// Cursor updates go straight to the simulation rather than through
+    /// [`Self::update_setting`], so they're validated here
+    fn validate_cursor_setting(
+        &self,
+        setting_name: &str,
+        value: f32,
+    ) -> AppResult<serde_json::Value> {
+        let value = serde_json::Value::from(value as f64);
+        let Some(simulation) = &self.current_simulation else {
+            return Ok(value);
+        };
+        let validated = simulation
+            .setting_validator()
+            .validate(setting_name, value, || simulation.get_settings())
+            .map_err(SimulationError::from)?;
+        Ok(validated.value)
+    }
+
     /// Update cursor size for the active simulation
     pub fn update_cursor_size(
         &mut self,
@@ -1541,77 +4901,36 @@ impl SimulationManager {
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
+        let value = self.validate_cursor_setting("cursor_size", size)?;
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::SlimeMold(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_size",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(size as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_size", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::ParticleLife(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_size",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(size as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_size", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::Pellets(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_size",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(size as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_size", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::Flow(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_size",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(size as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_size", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::VoronoiCA(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_size",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(size as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_size", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::PrimordialParticles(simulation) => {
-                    simulation.update_state(
-                        "cursor_size",
-                        serde_json::Value::Number(
-                            serde_json::Number::from_f64(size as f64).unwrap(),
-                        ),
-                        device,
-                        queue,
-                    )?;
+                    simulation.update_state("cursor_size", value.clone(), device, queue)?;
                 }
                 _ => {
                     return Err(AppError::Simulation(
@@ -1638,77 +4957,36 @@ impl SimulationManager {
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> AppResult<()> {
+        let value = self.validate_cursor_setting("cursor_strength", strength)?;
         if let Some(simulation) = &mut self.current_simulation {
             match simulation {
                 SimulationType::SlimeMold(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_strength",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(strength as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_strength", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::ParticleLife(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_strength",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(strength as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_strength", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::Pellets(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_strength",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(strength as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_strength", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::Flow(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_strength",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(strength as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_strength", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::VoronoiCA(simulation) => {
                     simulation
-                        .update_setting(
-                            "cursor_strength",
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(strength as f64).unwrap(),
-                            ),
-                            device,
-                            queue,
-                        )
+                        .update_setting("cursor_strength", value.clone(), device, queue)
                         .map_err(AppError::Simulation)?;
                 }
                 SimulationType::PrimordialParticles(simulation) => {
-                    simulation.update_state(
-                        "cursor_strength",
-                        serde_json::Value::Number(
-                            serde_json::Number::from_f64(strength as f64).unwrap(),
-                        ),
-                        device,
-                        queue,
-                    )?;
+                    simulation.update_state("cursor_strength", value.clone(), device, queue)?;
                 }
                 _ => {
                     return Err(AppError::Simulation(
@@ -1727,4 +5005,150 @@ impl SimulationManager {
         }
         Ok(())
     }
+
+    /// Change the shape of the cursor's force field in the particle
+    /// simulations, and the strength curve of `mode` if `curve` is given
+    pub fn set_cursor_mode(
+        &mut self,
+        mode: CursorMode,
+        curve: Option<StrengthCurve>,
+    ) -> AppResult<CursorForceField> {
+        let cursor_force = match &mut self.current_simulation {
+            Some(SimulationType::ParticleLife(simulation)) => &mut simulation.state.cursor_force,
+            Some(SimulationType::Pellets(simulation)) => &mut simulation.state.cursor_force,
+            Some(SimulationType::PrimordialParticles(simulation)) => {
+                &mut simulation.state.cursor_force
+            }
+            Some(_) => {
+                return Err(SimulationError::InvalidParameter(
+                    "Cursor modes not supported for this simulation type".to_string(),
+                )
+                .into());
+            }
+            None => return Err(SimulationError::NotRunning.into()),
+        };
+        cursor_force.set_mode(mode, curve);
+        Ok(cursor_force.clone())
+    }
+
+    /// Wind and gravity of the running particle simulation
+    pub fn global_force(&self) -> AppResult<GlobalForce> {
+        match &self.current_simulation {
+            Some(SimulationType::ParticleLife(simulation)) => {
+                Ok(simulation.state.global_force.clone())
+            }
+            Some(SimulationType::Pellets(simulation)) => Ok(simulation.state.global_force.clone()),
+            Some(SimulationType::PrimordialParticles(simulation)) => {
+                Ok(simulation.state.global_force.clone())
+            }
+            Some(SimulationType::Flow(simulation)) => Ok(simulation.state.global_force.clone()),
+            Some(_) => Err(SimulationError::InvalidParameter(
+                "Global forces not supported for this simulation type".to_string(),
+            )
+            .into()),
+            None => Err(SimulationError::NotRunning.into()),
+        }
+    }
+
+    pub fn set_global_force(&mut self, force: GlobalForce) -> AppResult<GlobalForce> {
+        force.validate()?;
+        let global_force = match &mut self.current_simulation {
+            Some(SimulationType::ParticleLife(simulation)) => &mut simulation.state.global_force,
+            Some(SimulationType::Pellets(simulation)) => &mut simulation.state.global_force,
+            Some(SimulationType::PrimordialParticles(simulation)) => {
+                &mut simulation.state.global_force
+            }
+            Some(SimulationType::Flow(simulation)) => &mut simulation.state.global_force,
+            Some(_) => {
+                return Err(SimulationError::InvalidParameter(
+                    "Global forces not supported for this simulation type".to_string(),
+                )
+                .into());
+            }
+            None => return Err(SimulationError::NotRunning.into()),
+        };
+        global_force.set(force);
+        Ok(global_force.clone())
+    }
+
+    /// Bias field of the running particle simulation
+    pub fn environment_field(&self) -> AppResult<EnvironmentField> {
+        match &self.current_simulation {
+            Some(SimulationType::PrimordialParticles(simulation)) => {
+                Ok(simulation.state.environment_field.clone())
+            }
+            Some(SimulationType::Flow(simulation)) => {
+                Ok(simulation.state.environment_field.clone())
+            }
+            Some(_) => Err(SimulationError::InvalidParameter(
+                "Environment fields not supported for this simulation type".to_string(),
+            )
+            .into()),
+            None => Err(SimulationError::NotRunning.into()),
+        }
+    }
+
+    pub fn set_environment_field(
+        &mut self,
+        field: EnvironmentField,
+        queue: &Arc<Queue>,
+    ) -> AppResult<EnvironmentField> {
+        field.validate()?;
+        match &mut self.current_simulation {
+            Some(SimulationType::PrimordialParticles(simulation)) => {
+                simulation.set_environment_field(queue, field.clone())?
+            }
+            Some(SimulationType::Flow(simulation)) => {
+                simulation.set_environment_field(queue, field.clone())?
+            }
+            Some(_) => {
+                return Err(SimulationError::InvalidParameter(
+                    "Environment fields not supported for this simulation type".to_string(),
+                )
+                .into());
+            }
+            None => return Err(SimulationError::NotRunning.into()),
+        }
+        Ok(field)
+    }
+}
+
+fn forget_preset_note(simulation_type: &str, preset_name: &str) -> AppResult<()> {
+    let mut notes = PresetNotes::load(simulation_type)?;
+    if notes.notes().contains_key(preset_name) {
+        notes.set(preset_name, "");
+        notes.save(simulation_type)?;
+    }
+    Ok(())
+}
+
+/// Whether `simulation` reverses the color scheme it's given while uploading
+/// it, when its reversed flag is set
+fn reverses_color_scheme_on_upload(simulation: &SimulationType) -> bool {
+    matches!(
+        simulation,
+        SimulationType::GrayScott(_)
+            | SimulationType::ParticleLife(_)
+            | SimulationType::VoronoiCA(_)
+            | SimulationType::Moire(_)
+            | SimulationType::PrimordialParticles(_)
+    )
+}
+
+/// `upload` with `script` run over the colors it ends up showing. A `flipped`
+/// upload is reversed by the simulation, so the script sees it the right way
+/// round and its result is flipped back.
+fn scripted_upload(
+    script: &ColorScript,
+    mut upload: ColorScheme,
+    flipped: bool,
+) -> LutResult<ColorScheme> {
+    if flipped {
+        upload.reverse();
+    }
+    let mut scheme = script.apply(&upload)?;
+    if flipped {
+        scheme.reverse();
+    }
+    Ok(scheme)
 }
@@ -0,0 +1,1002 @@
+//! Post effects applied to the final composited frame.
+//!
+//! Per-simulation post processing runs inside each simulation. The master bus
+//! runs once after that, over whatever ended up on screen (a single simulation
+//! or the whole pane grid), so every pane gets the same grading. While any
+//! master effect is on, simulations render into an offscreen scene texture and
+//! the bus draws that texture onto the surface.
+//!
+//! The bus also draws the focused simulation's
+//! [`BackgroundLayer`](crate::simulations::shared::BackgroundLayer) under
+//! the scene, before the effects, so it needs the scene texture for that too.
+//! So does [overlay mode](super::overlay), which keys the frame's alpha from
+//! its brightness on the way out, and a render scale other than 1 or a fixed
+//! [`Canvas`], where the scene is rendered at its own resolution and the bus
+//! scales it to the surface, and [supersampling](super::supersampling), which
+//! averages paused frames in an accumulation texture beside the scene.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use wgpu::util::DeviceExt;
+use wgpu::{CompositeAlphaMode, Device, Queue, SurfaceConfiguration};
+
+use super::canvas::Canvas;
+use crate::error::{AppResult, SimulationError};
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, GpuReservation};
+
+const MASTER_EFFECTS_SHADER: &str = concat!(
+    include_str!("../simulations/shared/color.wgsl"),
+    include_str!("master_effects.wgsl")
+);
+
+/// Averaged frames are kept at higher precision than the scene, so many
+/// samples don't band
+const ACCUMULATION_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Range of the render scale, the scene's resolution relative to the
+/// surface's
+pub const MIN_RENDER_SCALE: f32 = 0.25;
+pub const MAX_RENDER_SCALE: f32 = 2.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorGrading {
+    pub enabled: bool,
+    /// Color scheme used as per-channel tone curves
+    pub color_scheme: String,
+    /// Blend between the ungraded (0) and fully graded (1) image
+    pub strength: f32,
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color_scheme: "MATPLOTLIB_viridis".to_string(),
+            strength: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bloom {
+    pub enabled: bool,
+    /// Linear brightness above which pixels glow
+    pub threshold: f32,
+    pub intensity: f32,
+    /// Glow radius in pixels
+    pub radius: f32,
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.7,
+            intensity: 0.8,
+            radius: 16.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilmGrain {
+    pub enabled: bool,
+    pub amount: f32,
+}
+
+impl Default for FilmGrain {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            amount: 0.05,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MasterEffects {
+    pub grading: ColorGrading,
+    pub bloom: Bloom,
+    pub grain: FilmGrain,
+}
+
+impl MasterEffects {
+    pub fn is_active(&self) -> bool {
+        self.grading.enabled || self.bloom.enabled || self.grain.enabled
+    }
+
+    pub fn validate(&self) -> AppResult<()> {
+        let checks = [
+            ("grading strength", self.grading.strength, 0.0, 1.0),
+            ("bloom threshold", self.bloom.threshold, 0.0, 10.0),
+            ("bloom intensity", self.bloom.intensity, 0.0, 10.0),
+            ("bloom radius", self.bloom.radius, 0.0, 256.0),
+            ("grain amount", self.grain.amount, 0.0, 1.0),
+        ];
+        for (name, value, min, max) in checks {
+            if !(min..=max).contains(&value) {
+                return Err(SimulationError::InvalidParameter(format!(
+                    "Master {} must be between {} and {}, got {}",
+                    name, min, max, value
+                ))
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    fn params(&self, width: u32, height: u32, time: f32) -> MasterParams {
+        MasterParams {
+            resolution: [width as f32, height as f32],
+            time,
+            bloom_threshold: self.bloom.threshold,
+            bloom_intensity: if self.bloom.enabled {
+                self.bloom.intensity
+            } else {
+                0.0
+            },
+            bloom_radius: self.bloom.radius,
+            grading_strength: if self.grading.enabled {
+                self.grading.strength
+            } else {
+                0.0
+            },
+            grain_amount: if self.grain.enabled {
+                self.grain.amount
+            } else {
+                0.0
+            },
+            background_extent: [1.0, 1.0],
+            background_blend: 0,
+            background_tile: 0,
+            background_opacity: 0.0,
+            overlay_alpha: 0,
+            overlay_key: 1.0,
+            _pad0: 0.0,
+            letterbox: [1.0, 1.0],
+            _pad1: 0.0,
+            _pad2: 0.0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct MasterParams {
+    resolution: [f32; 2],
+    time: f32,
+    bloom_threshold: f32,
+    bloom_intensity: f32,
+    bloom_radius: f32,
+    grading_strength: f32,
+    grain_amount: f32,
+    // Share of the frame the background image covers
+    background_extent: [f32; 2],
+    // 0 = no background, otherwise a BackgroundBlend
+    background_blend: u32,
+    background_tile: u32,
+    background_opacity: f32,
+    // 0 = opaque, 1 = premultiplied, 2 = postmultiplied
+    overlay_alpha: u32,
+    // Brightness keyed to full alpha
+    overlay_key: f32,
+    _pad0: f32,
+    // Share of the output the scene covers, less than 1 on one axis with a canvas
+    letterbox: [f32; 2],
+    _pad1: f32,
+    _pad2: f32,
+}
+
+/// A background layer's image, uploaded
+struct BackgroundImage {
+    layer: BackgroundLayer,
+    view: wgpu::TextureView,
+    size: [f32; 2],
+    _memory: GpuReservation,
+}
+
+impl BackgroundImage {
+    fn load(layer: BackgroundLayer, device: &Device, queue: &Queue) -> AppResult<Self> {
+        let image = image::open(&layer.image_path)
+            .map_err(|e| {
+                SimulationError::InvalidParameter(format!(
+                    "Failed to open background image {}: {}",
+                    layer.image_path, e
+                ))
+            })?
+            .to_rgba8();
+        let texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Master Background Texture"),
+                size: wgpu::Extent3d {
+                    width: image.width(),
+                    height: image.height(),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &image,
+        );
+        Ok(Self {
+            layer,
+            view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            size: [image.width() as f32, image.height() as f32],
+            _memory: GpuReservation::for_texture(&texture),
+        })
+    }
+}
+
+struct MasterBusResources {
+    pipeline: wgpu::RenderPipeline,
+    // Blends the scene into the accumulation texture
+    accumulate_pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    background_sampler: wgpu::Sampler,
+    params_buffer: wgpu::Buffer,
+    lut_buffer: wgpu::Buffer,
+    // Bound while there's no background image
+    empty_background: wgpu::TextureView,
+    scene_view: wgpu::TextureView,
+    scene_memory: GpuReservation,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+}
+
+impl MasterBusResources {
+    fn new(device: &Device, width: u32, height: u32, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Master Effects Shader"),
+            source: wgpu::ShaderSource::Wgsl(MASTER_EFFECTS_SHADER.into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Master Effects Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Master Effects Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Master Effects Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // Each sample is weighed in through the blend constant, 1 / samples so far
+        let accumulate_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Master Accumulate Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_accumulate"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: ACCUMULATION_FORMAT,
+                    blend: Some(wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::Constant,
+                            dst_factor: wgpu::BlendFactor::OneMinusConstant,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::Constant,
+                            dst_factor: wgpu::BlendFactor::OneMinusConstant,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                    }),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Master Effects Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let background_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Master Background Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Master Effects Params Buffer"),
+            size: std::mem::size_of::<MasterParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Master Effects LUT Buffer"),
+            contents: bytemuck::cast_slice(&identity_lut()),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let empty_background = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("Master Empty Background Texture"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let (scene_view, scene_memory) = Self::create_scene(device, width, height, format);
+
+        Self {
+            pipeline,
+            accumulate_pipeline,
+            bind_group_layout,
+            sampler,
+            background_sampler,
+            params_buffer,
+            lut_buffer,
+            empty_background,
+            scene_view,
+            scene_memory,
+            width,
+            height,
+            format,
+        }
+    }
+
+    fn create_scene(
+        device: &Device,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    ) -> (wgpu::TextureView, GpuReservation) {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Master Effects Scene Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        (view, GpuReservation::for_texture(&texture))
+    }
+
+    /// Bindings for drawing `source`, the scene or its accumulated samples
+    fn bind_group(
+        &self,
+        device: &Device,
+        source: &wgpu::TextureView,
+        background: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Master Effects Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.lut_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(background),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::Sampler(&self.background_sampler),
+                },
+            ],
+        })
+    }
+
+    fn resize(&mut self, device: &Device, width: u32, height: u32) {
+        let (scene_view, scene_memory) = Self::create_scene(device, width, height, self.format);
+        self.scene_view = scene_view;
+        self.scene_memory = scene_memory;
+        self.width = width;
+        self.height = height;
+    }
+}
+
+pub struct MasterBus {
+    effects: MasterEffects,
+    resources: Option<MasterBusResources>,
+    started: Instant,
+    // Layer last asked for, kept even when its image failed to load
+    background_layer: Option<BackgroundLayer>,
+    background: Option<BackgroundImage>,
+    // Surface alpha mode and key threshold while the window is an overlay
+    overlay: Option<(CompositeAlphaMode, f32)>,
+    // Scene resolution relative to the surface
+    render_scale: f32,
+    // Fixed scene resolution, letterboxed into the surface
+    canvas: Option<Canvas>,
+    // Size of the surface the bus was last resized for
+    output_size: [u32; 2],
+    // Keep the scene texture around for supersampling paused frames
+    supersampling: bool,
+    accumulation: Option<Accumulation>,
+}
+
+/// Paused frames averaged together
+struct Accumulation {
+    view: wgpu::TextureView,
+    samples: u32,
+    _memory: GpuReservation,
+}
+
+impl Default for MasterBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MasterBus {
+    pub fn new() -> Self {
+        Self {
+            effects: MasterEffects::default(),
+            resources: None,
+            started: Instant::now(),
+            background_layer: None,
+            background: None,
+            overlay: None,
+            render_scale: 1.0,
+            canvas: None,
+            output_size: [0, 0],
+            supersampling: false,
+            accumulation: None,
+        }
+    }
+
+    pub fn effects(&self) -> &MasterEffects {
+        &self.effects
+    }
+
+    /// Replace the effect settings. `lut` is the grading color scheme, when
+    /// grading is enabled.
+    pub fn set_effects(
+        &mut self,
+        effects: MasterEffects,
+        lut: Option<&ColorScheme>,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        effects.validate()?;
+        self.effects = effects;
+
+        self.resize(device, surface_config);
+        if let Some(resources) = &self.resources {
+            let lut_data = lut.map_or_else(identity_lut, ColorScheme::to_u32_buffer);
+            queue.write_buffer(&resources.lut_buffer, 0, bytemuck::cast_slice(&lut_data));
+        }
+        Ok(())
+    }
+
+    fn is_needed(&self) -> bool {
+        self.effects.is_active()
+            || self.background.is_some()
+            || self.overlay.is_some()
+            || self.render_scale != 1.0
+            || self.canvas.is_some()
+            || self.supersampling
+    }
+
+    /// Keep the scene texture for [`accumulate`](Self::accumulate) even with
+    /// nothing else drawn through the bus
+    pub fn set_supersampling(
+        &mut self,
+        enabled: bool,
+        device: &Arc<Device>,
+        surface_config: &SurfaceConfiguration,
+    ) {
+        self.supersampling = enabled;
+        if !enabled {
+            self.accumulation = None;
+        }
+        self.resize(device, surface_config);
+    }
+
+    /// Samples averaged since the last reset
+    pub fn accumulated_samples(&self) -> u32 {
+        self.accumulation
+            .as_ref()
+            .map_or(0, |accumulation| accumulation.samples)
+    }
+
+    /// Start averaging over, from the next sample on
+    pub fn reset_accumulation(&mut self) {
+        if let Some(accumulation) = &mut self.accumulation {
+            accumulation.samples = 0;
+        }
+    }
+
+    /// Average the scene as it is now into the accumulated samples. Until
+    /// the next reset, [`apply`](Self::apply) draws the average instead of
+    /// the scene.
+    pub fn accumulate(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) {
+        let Some(resources) = &self.resources else {
+            return;
+        };
+        let accumulation = self.accumulation.get_or_insert_with(|| {
+            let (view, memory) = MasterBusResources::create_scene(
+                device,
+                resources.width,
+                resources.height,
+                ACCUMULATION_FORMAT,
+            );
+            Accumulation {
+                view,
+                samples: 0,
+                _memory: memory,
+            }
+        });
+        let bind_group =
+            resources.bind_group(device, &resources.scene_view, &resources.empty_background);
+        let weight = 1.0 / (accumulation.samples + 1) as f64;
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Master Accumulate Encoder"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Master Accumulate Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &accumulation.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&resources.accumulate_pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.set_blend_constant(wgpu::Color {
+                r: weight,
+                g: weight,
+                b: weight,
+                a: weight,
+            });
+            render_pass.draw(0..3, 0..1);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+        accumulation.samples += 1;
+    }
+
+    pub fn canvas(&self) -> Option<Canvas> {
+        self.canvas
+    }
+
+    /// Render the scene at a fixed size from the next
+    /// [`resize`](Self::resize) on, or follow the surface again with `None`
+    pub fn set_canvas(&mut self, canvas: Option<Canvas>) {
+        self.canvas = canvas;
+    }
+
+    /// Size of a captured frame: the canvas if there is one, otherwise the
+    /// surface
+    pub fn frame_size(&self, surface_config: &SurfaceConfiguration) -> [u32; 2] {
+        self.canvas
+            .map_or([surface_config.width, surface_config.height], |canvas| {
+                [canvas.width, canvas.height]
+            })
+    }
+
+    /// Where a point on the surface, in physical pixels, lands in the scene
+    pub fn scene_position(&self, point: [f32; 2]) -> [f32; 2] {
+        let [width, height] = self.output_size;
+        if width == 0 || height == 0 {
+            return point;
+        }
+        let scene = self.scene_size(width, height);
+        let letterbox = self
+            .canvas
+            .map_or([1.0, 1.0], |canvas| canvas.letterbox(self.output_size));
+        let output = [width as f32, height as f32];
+        std::array::from_fn(|i| {
+            ((point[i] / output[i] - 0.5) / letterbox[i] + 0.5) * scene[i] as f32
+        })
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Render the scene at `scale` times the surface's resolution from the
+    /// next [`resize`](Self::resize) on
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+    }
+
+    /// The surface configuration simulations should size themselves for,
+    /// which differs from the surface's with a render scale
+    pub fn scene_config(&self, surface_config: &SurfaceConfiguration) -> SurfaceConfiguration {
+        let [width, height] = self.scene_size(surface_config.width, surface_config.height);
+        SurfaceConfiguration {
+            width,
+            height,
+            ..surface_config.clone()
+        }
+    }
+
+    fn scene_size(&self, width: u32, height: u32) -> [u32; 2] {
+        if let Some(canvas) = self.canvas {
+            return [canvas.width, canvas.height];
+        }
+        let scale = |size: u32| ((size as f32 * self.render_scale).round() as u32).max(1);
+        [scale(width), scale(height)]
+    }
+
+    /// Key the frame's alpha for a surface in `alpha_mode`, or go back to
+    /// opaque frames with `None`
+    pub fn set_overlay(
+        &mut self,
+        alpha_mode: Option<CompositeAlphaMode>,
+        key_threshold: f32,
+        device: &Arc<Device>,
+        surface_config: &SurfaceConfiguration,
+    ) {
+        self.overlay = alpha_mode.map(|mode| (mode, key_threshold));
+        self.resize(device, surface_config);
+    }
+
+    pub fn background_layer(&self) -> Option<&BackgroundLayer> {
+        self.background_layer.as_ref()
+    }
+
+    /// Show `layer` under the scene, or nothing. The image is only read again
+    /// when its path changes. Turning the bus on for a background alone sizes
+    /// the scene to `frame`, the texture the bus will draw into.
+    pub fn set_background_layer(
+        &mut self,
+        layer: Option<BackgroundLayer>,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        frame: &wgpu::Texture,
+    ) -> AppResult<()> {
+        self.background_layer = layer.clone();
+        let previous = self.background.take();
+        self.background = match (layer, previous) {
+            (None, _) => None,
+            (Some(layer), Some(mut background))
+                if background.layer.image_path == layer.image_path =>
+            {
+                background.layer = layer;
+                Some(background)
+            }
+            (Some(layer), _) => {
+                layer.validate()?;
+                Some(BackgroundImage::load(layer, device, queue)?)
+            }
+        };
+
+        if !self.is_needed() {
+            self.resources = None;
+        } else if self.resources.is_none() {
+            let [width, height] = self.scene_size(frame.width(), frame.height());
+            self.resources = Some(MasterBusResources::new(
+                device,
+                width,
+                height,
+                frame.format(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Match the scene texture to the surface, creating it the first time
+    /// the bus is needed and freeing it once nothing is drawn through the bus
+    pub fn resize(&mut self, device: &Arc<Device>, surface_config: &SurfaceConfiguration) {
+        self.output_size = [surface_config.width, surface_config.height];
+        if !self.is_needed() {
+            self.resources = None;
+            return;
+        }
+        let [width, height] = self.scene_size(surface_config.width, surface_config.height);
+        match &mut self.resources {
+            Some(resources) if resources.format == surface_config.format => {
+                if (resources.width, resources.height) != (width, height) {
+                    resources.resize(device, width, height);
+                    self.accumulation = None;
+                }
+            }
+            _ => {
+                self.accumulation = None;
+                self.resources = Some(MasterBusResources::new(
+                    device,
+                    width,
+                    height,
+                    surface_config.format,
+                ));
+            }
+        }
+    }
+
+    /// Where simulations should render while master effects are on
+    pub fn scene_view(&self) -> Option<&wgpu::TextureView> {
+        self.resources
+            .as_ref()
+            .map(|resources| &resources.scene_view)
+    }
+
+    /// Draw the scene texture with the effect chain into `output`
+    pub fn apply(&self, device: &Arc<Device>, queue: &Arc<Queue>, output: &wgpu::TextureView) {
+        let Some(resources) = &self.resources else {
+            return;
+        };
+
+        let mut params = self.effects.params(
+            resources.width,
+            resources.height,
+            self.started.elapsed().as_secs_f32(),
+        );
+        if let Some(background) = &self.background {
+            let frame = [resources.width as f32, resources.height as f32];
+            params.background_extent = background.layer.extent(background.size, frame);
+            params.background_blend = background.layer.blend.into();
+            params.background_tile = background.layer.tile as u32;
+            params.background_opacity = background.layer.opacity;
+        }
+        if let Some((alpha_mode, key_threshold)) = self.overlay {
+            params.overlay_alpha = match alpha_mode {
+                CompositeAlphaMode::PostMultiplied => 2,
+                _ => 1,
+            };
+            params.overlay_key = key_threshold;
+        }
+        if let Some(canvas) = self.canvas {
+            let output = output.texture();
+            params.letterbox = canvas.letterbox([output.width(), output.height()]);
+        }
+        queue.write_buffer(&resources.params_buffer, 0, bytemuck::bytes_of(&params));
+        let background_view = self
+            .background
+            .as_ref()
+            .map_or(&resources.empty_background, |background| &background.view);
+        let source = match &self.accumulation {
+            Some(accumulation) if accumulation.samples > 0 => &accumulation.view,
+            _ => &resources.scene_view,
+        };
+        let bind_group = resources.bind_group(device, source, background_view);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Master Effects Encoder"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Master Effects Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: output,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(if self.overlay.is_some() {
+                            wgpu::Color::TRANSPARENT
+                        } else {
+                            wgpu::Color::BLACK
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&resources.pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+}
+
+/// LUT that maps every channel to itself
+fn identity_lut() -> Vec<u32> {
+    (0..3).flat_map(|_| 0..256u32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_effects_contribute_nothing() {
+        let effects = MasterEffects::default();
+        assert!(!effects.is_active());
+        let params = effects.params(640, 480, 0.0);
+        assert_eq!(params.bloom_intensity, 0.0);
+        assert_eq!(params.grading_strength, 0.0);
+        assert_eq!(params.grain_amount, 0.0);
+        assert_eq!(params.background_blend, 0);
+        assert_eq!(std::mem::size_of::<MasterParams>(), 80);
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        let mut effects = MasterEffects::default();
+        assert!(effects.validate().is_ok());
+        effects.grain.amount = 2.0;
+        assert!(effects.validate().is_err());
+    }
+
+    #[test]
+    fn partial_settings_use_defaults() {
+        let effects: MasterEffects =
+            serde_json::from_value(serde_json::json!({ "bloom": { "enabled": true } })).unwrap();
+        assert!(effects.bloom.enabled);
+        assert_eq!(effects.bloom.radius, Bloom::default().radius);
+        assert!(!effects.grain.enabled);
+    }
+
+    #[test]
+    fn identity_lut_covers_each_channel() {
+        let lut = identity_lut();
+        assert_eq!(lut.len(), 768);
+        assert_eq!(lut[255], 255);
+        assert_eq!(lut[256], 0);
+        assert_eq!(lut[767], 255);
+    }
+
+    #[test]
+    fn render_scale_needs_the_bus() {
+        let mut bus = MasterBus::new();
+        assert!(!bus.is_needed());
+        bus.set_render_scale(0.5);
+        assert!(bus.is_needed());
+        assert_eq!(bus.scene_size(1919, 1080), [960, 540]);
+        bus.set_render_scale(MIN_RENDER_SCALE);
+        assert_eq!(bus.scene_size(2, 2), [1, 1]);
+    }
+
+    #[test]
+    fn pointer_lands_in_the_letterboxed_canvas() {
+        let mut bus = MasterBus::new();
+        bus.output_size = [2000, 1000];
+        bus.set_render_scale(2.0);
+        assert_eq!(bus.scene_position([500.0, 250.0]), [1000.0, 500.0]);
+
+        // Square canvas centered in a 2:1 window, 500 pixels in from each side
+        bus.set_canvas(Some(Canvas {
+            width: 100,
+            height: 100,
+        }));
+        assert_eq!(bus.scene_position([1000.0, 500.0]), [50.0, 50.0]);
+        assert_eq!(bus.scene_position([500.0, 0.0]), [0.0, 0.0]);
+    }
+}
@@ -0,0 +1,162 @@
+// Final post chain over the composited frame: the background layer under
+// the scene, bloom, then grading through a color scheme LUT, then film grain.
+// Color helpers come from color.wgsl.
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+struct MasterParams {
+    resolution: vec2<f32>,
+    time: f32,
+    bloom_threshold: f32,
+    bloom_intensity: f32,
+    bloom_radius: f32,
+    grading_strength: f32,
+    grain_amount: f32,
+    background_extent: vec2<f32>,
+    background_blend: u32, // 0=none, 1=behind, 2=screen, 3=add, 4=multiply
+    background_tile: u32,
+    background_opacity: f32,
+    overlay_alpha: u32, // 0=opaque, 1=premultiplied, 2=postmultiplied
+    overlay_key: f32,
+    _pad0: f32,
+    letterbox: vec2<f32>, // share of the output the scene covers
+    _pad1: f32,
+    _pad2: f32,
+}
+
+@group(0) @binding(0) var scene_texture: texture_2d<f32>;
+@group(0) @binding(1) var scene_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: MasterParams;
+@group(0) @binding(3) var<storage, read> lut_data: array<u32>;
+@group(0) @binding(4) var background_texture: texture_2d<f32>;
+@group(0) @binding(5) var background_sampler: sampler;
+
+const BLOOM_TAPS: u32 = 24u;
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0)
+    );
+    var uvs = array<vec2<f32>, 3>(
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(2.0, 1.0),
+        vec2<f32>(0.0, -1.0)
+    );
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
+    out.uv = uvs[vertex_index];
+    return out;
+}
+
+// Bright parts of the scene gathered over a disc, golden angle spiral taps
+fn bloom(uv: vec2<f32>) -> vec3<f32> {
+    var sum = vec3<f32>(0.0);
+    for (var i = 0u; i < BLOOM_TAPS; i++) {
+        let radius = sqrt((f32(i) + 0.5) / f32(BLOOM_TAPS)) * params.bloom_radius;
+        let angle = f32(i) * GOLDEN_ANGLE;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * radius / params.resolution;
+        let sample = textureSampleLevel(scene_texture, scene_sampler, uv + offset, 0.0).rgb;
+        sum += max(sample - vec3<f32>(params.bloom_threshold), vec3<f32>(0.0));
+    }
+    return sum / f32(BLOOM_TAPS);
+}
+
+// Each channel is looked up in its own LUT curve, on sRGB values like the LUT bytes
+fn grade(srgb: vec3<f32>) -> vec3<f32> {
+    let index = vec3<u32>(clamp(srgb, vec3<f32>(0.0), vec3<f32>(1.0)) * 255.0 + 0.5);
+    return vec3<f32>(
+        f32(lut_data[index.r]),
+        f32(lut_data[index.g + 256u]),
+        f32(lut_data[index.b + 512u])
+    ) / 255.0;
+}
+
+// The scene over the background image. Simulations blend onto a cleared
+// target, so the scene's color is already weighted by its alpha.
+fn composite_background(uv: vec2<f32>, scene: vec4<f32>) -> vec3<f32> {
+    let image_uv = (uv - 0.5) / params.background_extent + 0.5;
+    let inside = all(image_uv >= vec2<f32>(0.0)) && all(image_uv <= vec2<f32>(1.0));
+    if (params.background_blend == 0u || (params.background_tile == 0u && !inside)) {
+        return scene.rgb;
+    }
+    let sample = textureSampleLevel(background_texture, background_sampler, image_uv, 0.0);
+    let background = sample.rgb * sample.a * params.background_opacity;
+    switch (params.background_blend) {
+        case 2u: {
+            return 1.0 - (1.0 - scene.rgb) * (1.0 - background);
+        }
+        case 3u: {
+            return scene.rgb + background;
+        }
+        case 4u: {
+            return scene.rgb * mix(vec3<f32>(1.0), sample.rgb, sample.a * params.background_opacity);
+        }
+        default: {
+            return scene.rgb + background * (1.0 - scene.a);
+        }
+    }
+}
+
+// Overlays fade the frame out where it's dark. With the key at or below 1
+// the alpha never drops under the brightest channel, so the color doubles
+// as its premultiplied self.
+fn with_overlay_alpha(color: vec3<f32>) -> vec4<f32> {
+    if (params.overlay_alpha == 0u) {
+        return vec4<f32>(color, 1.0);
+    }
+    let brightest = max(color.r, max(color.g, color.b));
+    let alpha = clamp(brightest / params.overlay_key, 0.0, 1.0);
+    if (params.overlay_alpha == 2u) {
+        return vec4<f32>(color / max(alpha, 1e-4), alpha);
+    }
+    return vec4<f32>(color, alpha);
+}
+
+fn hash(p: vec2<f32>) -> f32 {
+    let q = fract(p * vec2<f32>(123.34, 456.21));
+    let r = q + dot(q, q + 45.32);
+    return fract(r.x * r.y);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    // A canvas is fitted inside the output, with black bars around it
+    let uv = (input.uv - 0.5) / params.letterbox + 0.5;
+    if (any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0))) {
+        return with_overlay_alpha(vec3<f32>(0.0));
+    }
+
+    let scene = textureSampleLevel(scene_texture, scene_sampler, uv, 0.0);
+    var color = composite_background(uv, scene);
+
+    if (params.bloom_intensity > 0.0) {
+        color += bloom(uv) * params.bloom_intensity;
+    }
+
+    var srgb = linear_to_srgb_rgb(max(color, vec3<f32>(0.0)));
+    if (params.grading_strength > 0.0) {
+        srgb = mix(srgb, grade(srgb), params.grading_strength);
+    }
+
+    if (params.grain_amount > 0.0) {
+        let pixel = floor(uv * params.resolution);
+        let noise = hash(pixel + fract(params.time * 0.618) * 1000.0) - 0.5;
+        srgb += vec3<f32>(noise * params.grain_amount);
+    }
+
+    return with_overlay_alpha(srgb_to_linear_rgb(max(srgb, vec3<f32>(0.0))));
+}
+
+// One supersampling sample, blended into the accumulation texture with the
+// pipeline's blend constant as its weight
+@fragment
+fn fs_accumulate(input: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSampleLevel(scene_texture, scene_sampler, input.uv, 0.0);
+}
@@ -1,4 +1,40 @@
+pub mod annotations;
+pub mod audio_reactive;
+pub mod autopilot;
+pub mod autosave;
+pub mod benchmark;
+pub mod canvas;
+pub mod catalog;
+pub mod color_cycle;
+pub mod deep_link;
+pub mod events;
+pub mod evolution;
+pub mod file_drop;
+pub mod frame_export;
+pub mod gallery;
+pub mod gpu_report;
+pub mod keymap;
+pub mod kiosk;
+pub mod launch;
+pub mod macros;
 pub mod manager;
+pub mod master_effects;
+pub mod overlay;
+pub mod panes;
 pub mod preset_manager;
+pub mod preset_migration;
+pub mod preview_stream;
+pub mod previews;
+pub mod recording;
+pub mod replay;
+pub mod scene_manager;
+pub mod seeds;
+pub mod settings_codec;
+pub mod similarity;
+pub mod supersampling;
+pub mod transition;
+pub mod watch_folder;
+pub mod watchdog;
+pub mod workspace;
 
 pub use manager::SimulationManager;
@@ -0,0 +1,95 @@
+//! Overlay mode, for floating a simulation over the desktop or other apps.
+//!
+//! The window is already created transparent; overlay mode switches the
+//! surface to an alpha compositing mode and has the master bus key the
+//! frame's alpha from its brightness. Simulations clear to their own opaque
+//! backgrounds, so keying the finished frame is what lets the dark parts of
+//! every simulation show what's behind the window. With `click_through` the
+//! window also stops taking mouse input, and the keyboard shortcut for
+//! [`KeyAction::ToggleOverlay`](super::keymap::KeyAction::ToggleOverlay) is
+//! the way back.
+
+use serde::{Deserialize, Serialize};
+use wgpu::CompositeAlphaMode;
+
+use crate::error::{SimulationError, SimulationResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayMode {
+    pub enabled: bool,
+    /// Let mouse input through to whatever is under the window
+    pub click_through: bool,
+    pub always_on_top: bool,
+    /// Brightness at which the frame becomes fully opaque, 0-1. Anything
+    /// darker fades out toward black.
+    pub key_threshold: f32,
+}
+
+impl Default for OverlayMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            click_through: false,
+            always_on_top: true,
+            key_threshold: 0.25,
+        }
+    }
+}
+
+impl OverlayMode {
+    pub fn validate(&self) -> SimulationResult<()> {
+        if !(self.key_threshold > 0.0 && self.key_threshold <= 1.0) {
+            return Err(SimulationError::InvalidParameter(
+                "Overlay key threshold must be above 0 and at most 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The surface alpha mode to use for overlays, preferring premultiplied
+/// alpha. `None` when the surface can only be opaque.
+pub fn select_alpha_mode(supported: &[CompositeAlphaMode]) -> Option<CompositeAlphaMode> {
+    [
+        CompositeAlphaMode::PreMultiplied,
+        CompositeAlphaMode::PostMultiplied,
+    ]
+    .into_iter()
+    .find(|mode| supported.contains(mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_premultiplied_alpha() {
+        let supported = [
+            CompositeAlphaMode::Opaque,
+            CompositeAlphaMode::PostMultiplied,
+            CompositeAlphaMode::PreMultiplied,
+        ];
+        assert_eq!(
+            select_alpha_mode(&supported),
+            Some(CompositeAlphaMode::PreMultiplied)
+        );
+        assert_eq!(
+            select_alpha_mode(&supported[..2]),
+            Some(CompositeAlphaMode::PostMultiplied)
+        );
+        assert_eq!(select_alpha_mode(&[CompositeAlphaMode::Opaque]), None);
+    }
+
+    #[test]
+    fn key_threshold_must_leave_something_opaque() {
+        for key_threshold in [0.0, 1.5, f32::NAN] {
+            let overlay = OverlayMode {
+                key_threshold,
+                ..Default::default()
+            };
+            assert!(overlay.validate().is_err());
+        }
+        assert!(OverlayMode::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,869 @@
+//! Several simulations running side by side in a grid, or stacked as layers.
+//!
+//! The focused pane's simulation stays in `SimulationManager::current_simulation`,
+//! so every existing command keeps acting on whatever the user last focused.
+//! The other panes hold their simulations here. In pane mode each simulation
+//! renders into an offscreen texture the size of its viewport, and those
+//! textures are then drawn into their cells on the surface.
+//!
+//! Every pane has its own camera. With linked cameras the unfocused panes
+//! follow the focused pane's pan and zoom, which makes it easy to compare two
+//! parameterizations of the same simulation region by region.
+//!
+//! In the layered layout every pane covers the whole surface instead, and the
+//! panes are blended over one another in order, the first at the bottom. Each
+//! layer has a [`BlendMode`] and an opacity, so slime mold trails can be added
+//! over a Gray-Scott pattern, or one simulation can multiply another.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use wgpu::{Device, Queue, SurfaceConfiguration};
+
+use crate::error::{AppResult, SimulationError};
+use crate::simulations::shared::GpuReservation;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::traits::{Simulation, SimulationType};
+
+const PANES_SHADER: &str = include_str!("panes.wgsl");
+
+/// Pixels left dark between neighbouring panes
+const PANE_GAP: u32 = 2;
+
+/// Most simulations that can be stacked in the layered layout
+const MAX_LAYERS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PaneLayout {
+    #[default]
+    #[serde(rename = "1x1")]
+    Single,
+    #[serde(rename = "2x1")]
+    TwoByOne,
+    #[serde(rename = "2x2")]
+    TwoByTwo,
+    /// Every pane fills the surface, blended over the ones before it
+    #[serde(rename = "layered")]
+    Layered,
+}
+
+impl PaneLayout {
+    const GRIDS: [PaneLayout; 3] = [
+        PaneLayout::Single,
+        PaneLayout::TwoByOne,
+        PaneLayout::TwoByTwo,
+    ];
+
+    /// Columns and rows of the grid
+    pub fn grid(self) -> (u32, u32) {
+        match self {
+            PaneLayout::Single => (1, 1),
+            PaneLayout::TwoByOne => (2, 1),
+            PaneLayout::TwoByTwo => (2, 2),
+            PaneLayout::Layered => (1, 1),
+        }
+    }
+
+    pub fn capacity(self) -> usize {
+        if self == PaneLayout::Layered {
+            return MAX_LAYERS;
+        }
+        let (columns, rows) = self.grid();
+        (columns * rows) as usize
+    }
+
+    /// The smallest grid with room for `count` panes
+    pub fn fitting(count: usize) -> Option<Self> {
+        Self::GRIDS
+            .into_iter()
+            .find(|layout| layout.capacity() >= count)
+    }
+
+    /// Region of a `width` x `height` surface covered by pane `slot`, filling
+    /// rows left to right
+    pub fn viewport(self, slot: usize, width: u32, height: u32) -> PaneViewport {
+        // Layers all share the one cell
+        let slot = if self == PaneLayout::Layered { 0 } else { slot };
+        let (columns, rows) = self.grid();
+        let column = slot as u32 % columns;
+        let row = slot as u32 / columns;
+
+        let span = |index: u32, count: u32, extent: u32| {
+            let mut start = index * extent / count;
+            let mut end = (index + 1) * extent / count;
+            if index > 0 {
+                start += PANE_GAP / 2;
+            }
+            if index + 1 < count {
+                end -= PANE_GAP / 2;
+            }
+            (start, end.saturating_sub(start).max(1))
+        };
+        let (x, width) = span(column, columns, width);
+        let (y, height) = span(row, rows, height);
+        PaneViewport {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// How a layer combines with the layers below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlendMode {
+    /// Covers what's below, fading it out as opacity rises
+    #[default]
+    Alpha,
+    /// Adds its light, so dark areas leave the layers below untouched
+    Add,
+    /// Darkens, so white areas leave the layers below untouched
+    Multiply,
+    /// Lightens like add, without blowing out past white
+    Screen,
+}
+
+impl BlendMode {
+    const ALL: [BlendMode; 4] = [
+        BlendMode::Alpha,
+        BlendMode::Add,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+    ];
+
+    fn fragment_entry_point(self) -> &'static str {
+        match self {
+            BlendMode::Multiply => "fs_multiply_layer",
+            _ => "fs_layer",
+        }
+    }
+
+    /// Color blending applied to the `fs_layer` output, which is
+    /// premultiplied by opacity, or to `fs_multiply_layer`'s lerp toward white
+    fn color_blend(self) -> wgpu::BlendComponent {
+        let (src_factor, dst_factor) = match self {
+            BlendMode::Alpha => (wgpu::BlendFactor::One, wgpu::BlendFactor::OneMinusSrcAlpha),
+            BlendMode::Add => (wgpu::BlendFactor::One, wgpu::BlendFactor::One),
+            BlendMode::Multiply => (wgpu::BlendFactor::Zero, wgpu::BlendFactor::Src),
+            BlendMode::Screen => (wgpu::BlendFactor::One, wgpu::BlendFactor::OneMinusSrc),
+        };
+        wgpu::BlendComponent {
+            src_factor,
+            dst_factor,
+            operation: wgpu::BlendOperation::Add,
+        }
+    }
+}
+
+/// A pane's blending in the layered layout
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PaneBlend {
+    pub mode: BlendMode,
+    /// 0 hides the layer, 1 applies it fully
+    pub opacity: f32,
+}
+
+impl Default for PaneBlend {
+    fn default() -> Self {
+        Self {
+            mode: BlendMode::Alpha,
+            opacity: 1.0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct LayerParams {
+    opacity: f32,
+    _padding: [f32; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PaneViewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaneInfo {
+    pub id: u32,
+    pub simulation_type: String,
+    pub focused: bool,
+    pub viewport: PaneViewport,
+    /// Whether this pane's camera follows the focused pane
+    pub camera_linked: bool,
+    /// Only used in the layered layout
+    pub blend: PaneBlend,
+}
+
+struct PaneCompositor {
+    pipeline: wgpu::RenderPipeline,
+    /// Indexed by [`BlendMode`]
+    layer_pipelines: Vec<wgpu::RenderPipeline>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+}
+
+impl PaneCompositor {
+    fn new(device: &Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Pane Composite Shader"),
+            source: wgpu::ShaderSource::Wgsl(PANES_SHADER.into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Pane Composite Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Pane Composite Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let create_pipeline = |label: &str, entry_point: &str, blend: Option<wgpu::BlendState>| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        let pipeline = create_pipeline("Pane Composite Pipeline", "fs_main", None);
+        let layer_pipelines = BlendMode::ALL
+            .into_iter()
+            .map(|mode| {
+                create_pipeline(
+                    "Pane Layer Pipeline",
+                    mode.fragment_entry_point(),
+                    Some(wgpu::BlendState {
+                        color: mode.color_blend(),
+                        // The surface stays opaque
+                        alpha: wgpu::BlendComponent::OVER,
+                    }),
+                )
+            })
+            .collect();
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Pane Composite Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            layer_pipelines,
+            bind_group_layout,
+            sampler,
+        }
+    }
+}
+
+/// Offscreen texture a pane's simulation renders into
+struct PaneTarget {
+    view: wgpu::TextureView,
+    params: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    _memory: GpuReservation,
+}
+
+impl PaneTarget {
+    fn new(
+        device: &Device,
+        compositor: &PaneCompositor,
+        viewport: PaneViewport,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Pane Texture"),
+            size: wgpu::Extent3d {
+                width: viewport.width,
+                height: viewport.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Pane Layer Params"),
+            size: std::mem::size_of::<LayerParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Pane Composite Bind Group"),
+            layout: &compositor.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&compositor.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: params.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            view,
+            params,
+            bind_group,
+            _memory: GpuReservation::for_texture(&texture),
+        }
+    }
+}
+
+struct Pane {
+    id: u32,
+    simulation_type: &'static str,
+    /// `None` for the focused pane, whose simulation is the manager's current one
+    simulation: Option<SimulationType>,
+    target: Option<PaneTarget>,
+    blend: PaneBlend,
+}
+
+#[derive(Default)]
+pub struct Panes {
+    layout: PaneLayout,
+    panes: Vec<Pane>,
+    focused: usize,
+    next_id: u32,
+    linked_cameras: bool,
+    surface_config: Option<SurfaceConfiguration>,
+    compositor: Option<PaneCompositor>,
+}
+
+impl Panes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether more than one simulation is on screen
+    pub fn is_active(&self) -> bool {
+        !self.panes.is_empty()
+    }
+
+    pub fn info(&self) -> Vec<PaneInfo> {
+        let (width, height) = self
+            .surface_config
+            .as_ref()
+            .map_or((1, 1), |config| (config.width, config.height));
+        self.panes
+            .iter()
+            .enumerate()
+            .map(|(slot, pane)| PaneInfo {
+                id: pane.id,
+                simulation_type: pane.simulation_type.to_string(),
+                focused: slot == self.focused,
+                viewport: self.layout.viewport(slot, width, height),
+                camera_linked: self.linked_cameras,
+                blend: pane.blend,
+            })
+            .collect()
+    }
+
+    /// Layout and surface configuration for a pane about to be added to a surface
+    /// of `surface_config`'s size
+    pub fn next_pane_config(
+        &self,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<(PaneLayout, SurfaceConfiguration)> {
+        // Starting pane mode turns the running simulation into the first pane
+        let count = self.panes.len().max(1);
+        let layout = if self.layout.capacity() > count {
+            self.layout
+        } else {
+            PaneLayout::fitting(count + 1).ok_or_else(|| {
+                SimulationError::InvalidParameter(format!(
+                    "At most {} panes are supported in a grid, and {} as layers",
+                    PaneLayout::TwoByTwo.capacity(),
+                    PaneLayout::Layered.capacity()
+                ))
+            })?
+        };
+        let viewport = layout.viewport(count, surface_config.width, surface_config.height);
+        Ok((layout, pane_surface_config(surface_config, viewport)))
+    }
+
+    /// Add a pane showing `simulation` next to the focused one
+    pub fn add(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        layout: PaneLayout,
+        simulation: SimulationType,
+        focused_simulation: &mut SimulationType,
+    ) -> AppResult<()> {
+        if self.panes.is_empty() {
+            self.surface_config = Some(surface_config.clone());
+            self.focused = 0;
+            self.push(focused_simulation.type_name(), None);
+        }
+        self.push(simulation.type_name(), Some(simulation));
+        self.layout = layout;
+        self.relayout(device, queue, focused_simulation)
+    }
+
+    fn push(&mut self, simulation_type: &'static str, simulation: Option<SimulationType>) {
+        self.next_id += 1;
+        self.panes.push(Pane {
+            id: self.next_id,
+            simulation_type,
+            simulation,
+            target: None,
+            blend: PaneBlend::default(),
+        });
+    }
+
+    fn index_of(&self, pane_id: u32) -> AppResult<usize> {
+        self.panes
+            .iter()
+            .position(|pane| pane.id == pane_id)
+            .ok_or_else(|| {
+                SimulationError::InvalidParameter(format!("No pane with id {}", pane_id)).into()
+            })
+    }
+
+    /// Close a pane, returning whether focus moved to another simulation.
+    /// Leaving a single pane ends pane mode and gives the remaining
+    /// simulation the whole surface again.
+    pub fn remove(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        pane_id: u32,
+        current_simulation: &mut Option<SimulationType>,
+    ) -> AppResult<bool> {
+        let index = self.index_of(pane_id)?;
+        let focus_changed = index == self.focused;
+        if focus_changed {
+            self.panes.remove(index);
+            self.focused = index.min(self.panes.len().saturating_sub(1));
+            *current_simulation = self
+                .panes
+                .get_mut(self.focused)
+                .and_then(|pane| pane.simulation.take());
+        } else {
+            self.panes.remove(index);
+            if index < self.focused {
+                self.focused -= 1;
+            }
+        }
+
+        let Some(simulation) = current_simulation.as_mut() else {
+            self.clear();
+            return Ok(focus_changed);
+        };
+
+        if self.panes.len() <= 1 {
+            let surface_config = self.surface_config.clone();
+            self.clear();
+            if let Some(surface_config) = surface_config {
+                simulation.resize(device, queue, &surface_config)?;
+            }
+            return Ok(focus_changed);
+        }
+        self.relayout(device, queue, simulation)?;
+        Ok(focus_changed)
+    }
+
+    /// Swap the focused simulation, returning whether focus changed
+    pub fn focus(
+        &mut self,
+        pane_id: u32,
+        current_simulation: &mut Option<SimulationType>,
+    ) -> AppResult<bool> {
+        let index = self.index_of(pane_id)?;
+        if index == self.focused {
+            return Ok(false);
+        }
+        self.panes[self.focused].simulation = current_simulation.take();
+        *current_simulation = self.panes[index].simulation.take();
+        self.focused = index;
+        Ok(true)
+    }
+
+    pub fn set_layout(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        layout: PaneLayout,
+        focused_simulation: Option<&mut SimulationType>,
+    ) -> AppResult<()> {
+        if layout.capacity() < self.panes.len() {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Layout {:?} has room for {} panes, {} are open",
+                layout,
+                layout.capacity(),
+                self.panes.len()
+            ))
+            .into());
+        }
+        self.layout = layout;
+        match focused_simulation {
+            Some(simulation) if self.is_active() => self.relayout(device, queue, simulation),
+            _ => Ok(()),
+        }
+    }
+
+    /// Set how a pane blends over the ones before it in the layered layout
+    pub fn set_blend(&mut self, pane_id: u32, blend: PaneBlend) -> AppResult<()> {
+        if !(0.0..=1.0).contains(&blend.opacity) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Layer opacity must be from 0 to 1, got {}",
+                blend.opacity
+            ))
+            .into());
+        }
+        let index = self.index_of(pane_id)?;
+        self.panes[index].blend = blend;
+        Ok(())
+    }
+
+    pub fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+        focused_simulation: &mut SimulationType,
+    ) -> AppResult<()> {
+        self.surface_config = Some(new_config.clone());
+        self.relayout(device, queue, focused_simulation)
+    }
+
+    /// Link or unlink the pane cameras. Linking snaps every pane to the
+    /// focused pane's view.
+    pub fn set_linked_cameras(
+        &mut self,
+        linked: bool,
+        focused_simulation: Option<&SimulationType>,
+    ) {
+        self.linked_cameras = linked;
+        let leader = focused_simulation
+            .and_then(SimulationType::camera)
+            .filter(|_| linked);
+        if let Some(leader) = leader {
+            for camera in self.unfocused_cameras() {
+                camera.set_view(leader.position, leader.zoom);
+                camera.follow(leader);
+            }
+        }
+    }
+
+    fn unfocused_cameras(&mut self) -> impl Iterator<Item = &mut Camera> {
+        self.panes
+            .iter_mut()
+            .filter_map(|pane| pane.simulation.as_mut()?.camera_mut())
+    }
+
+    /// Drop every unfocused pane and leave pane mode
+    pub fn clear(&mut self) {
+        self.panes.clear();
+        self.focused = 0;
+    }
+
+    /// Recreate the pane textures and resize every simulation to its viewport
+    fn relayout(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        focused_simulation: &mut SimulationType,
+    ) -> AppResult<()> {
+        let Some(surface_config) = self.surface_config.as_ref() else {
+            return Ok(());
+        };
+        let compositor = self
+            .compositor
+            .get_or_insert_with(|| PaneCompositor::new(device, surface_config.format));
+
+        for (slot, pane) in self.panes.iter_mut().enumerate() {
+            let viewport = self
+                .layout
+                .viewport(slot, surface_config.width, surface_config.height);
+            pane.target = Some(PaneTarget::new(
+                device,
+                compositor,
+                viewport,
+                surface_config.format,
+            ));
+
+            let pane_config = pane_surface_config(surface_config, viewport);
+            let simulation = if slot == self.focused {
+                &mut *focused_simulation
+            } else {
+                pane.simulation
+                    .as_mut()
+                    .expect("unfocused panes own their simulation")
+            };
+            simulation.resize(device, queue, &pane_config)?;
+        }
+        Ok(())
+    }
+
+    /// Render every pane and draw them into `surface_view`. Without a
+    /// `delta_time` the simulations are drawn without advancing.
+    pub fn render(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &wgpu::TextureView,
+        focused_simulation: &mut SimulationType,
+        delta_time: Option<f32>,
+    ) -> AppResult<()> {
+        let (Some(surface_config), Some(compositor)) = (&self.surface_config, &self.compositor)
+        else {
+            return Ok(());
+        };
+
+        let leader = focused_simulation.camera().filter(|_| self.linked_cameras);
+        if let Some(leader) = leader {
+            for pane in self.panes.iter_mut() {
+                if let Some(camera) = pane
+                    .simulation
+                    .as_mut()
+                    .and_then(SimulationType::camera_mut)
+                {
+                    camera.follow(leader);
+                }
+            }
+        }
+
+        for (slot, pane) in self.panes.iter_mut().enumerate() {
+            let Some(target) = &pane.target else {
+                continue;
+            };
+            let simulation = if slot == self.focused {
+                &mut *focused_simulation
+            } else {
+                pane.simulation
+                    .as_mut()
+                    .expect("unfocused panes own their simulation")
+            };
+            match delta_time {
+                Some(delta_time) => {
+                    simulation.render_frame(device, queue, &target.view, delta_time)?
+                }
+                None => simulation.render_frame_paused(device, queue, &target.view)?,
+            }
+        }
+
+        let layered = self.layout == PaneLayout::Layered;
+        if layered {
+            for pane in &self.panes {
+                if let Some(target) = &pane.target {
+                    let params = LayerParams {
+                        opacity: pane.blend.opacity,
+                        _padding: [0.0; 3],
+                    };
+                    queue.write_buffer(&target.params, 0, bytemuck::bytes_of(&params));
+                }
+            }
+        }
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Pane Composite Encoder"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Pane Composite Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&compositor.pipeline);
+            for (slot, pane) in self.panes.iter().enumerate() {
+                let Some(target) = &pane.target else {
+                    continue;
+                };
+                if layered {
+                    render_pass.set_pipeline(&compositor.layer_pipelines[pane.blend.mode as usize]);
+                }
+                let viewport =
+                    self.layout
+                        .viewport(slot, surface_config.width, surface_config.height);
+                render_pass.set_viewport(
+                    viewport.x as f32,
+                    viewport.y as f32,
+                    viewport.width as f32,
+                    viewport.height as f32,
+                    0.0,
+                    1.0,
+                );
+                render_pass.set_bind_group(0, &target.bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+        Ok(())
+    }
+}
+
+fn pane_surface_config(
+    surface_config: &SurfaceConfiguration,
+    viewport: PaneViewport,
+) -> SurfaceConfiguration {
+    SurfaceConfiguration {
+        width: viewport.width,
+        height: viewport.height,
+        ..surface_config.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smallest_fitting_layout_is_chosen() {
+        assert_eq!(PaneLayout::fitting(1), Some(PaneLayout::Single));
+        assert_eq!(PaneLayout::fitting(2), Some(PaneLayout::TwoByOne));
+        assert_eq!(PaneLayout::fitting(3), Some(PaneLayout::TwoByTwo));
+        assert_eq!(PaneLayout::fitting(5), None);
+    }
+
+    #[test]
+    fn viewports_tile_the_surface_with_gaps() {
+        let layout = PaneLayout::TwoByTwo;
+        let top_left = layout.viewport(0, 1000, 600);
+        let top_right = layout.viewport(1, 1000, 600);
+        let bottom_left = layout.viewport(2, 1000, 600);
+
+        assert_eq!(
+            top_left,
+            PaneViewport {
+                x: 0,
+                y: 0,
+                width: 499,
+                height: 299
+            }
+        );
+        assert_eq!(top_right.x, 501);
+        assert_eq!(top_right.x + top_right.width, 1000);
+        assert_eq!(bottom_left.y, 301);
+        assert_eq!(bottom_left.y + bottom_left.height, 600);
+    }
+
+    #[test]
+    fn single_layout_covers_everything() {
+        assert_eq!(
+            PaneLayout::Single.viewport(0, 1920, 1080),
+            PaneViewport {
+                x: 0,
+                y: 0,
+                width: 1920,
+                height: 1080
+            }
+        );
+    }
+
+    #[test]
+    fn layers_each_cover_the_surface() {
+        assert_eq!(PaneLayout::Layered.capacity(), MAX_LAYERS);
+        for slot in 0..MAX_LAYERS {
+            assert_eq!(
+                PaneLayout::Layered.viewport(slot, 1920, 1080),
+                PaneLayout::Single.viewport(0, 1920, 1080)
+            );
+        }
+        // Adding panes only ever picks a grid
+        assert_ne!(PaneLayout::fitting(4), Some(PaneLayout::Layered));
+    }
+
+    #[test]
+    fn blend_modes_index_their_pipelines() {
+        for (index, mode) in BlendMode::ALL.into_iter().enumerate() {
+            assert_eq!(mode as usize, index);
+        }
+        let blend: PaneBlend = serde_json::from_str(r#"{"mode":"screen","opacity":0.5}"#).unwrap();
+        assert_eq!(blend.mode, BlendMode::Screen);
+    }
+
+    #[test]
+    fn layouts_use_grid_names() {
+        assert_eq!(
+            serde_json::to_string(&PaneLayout::TwoByOne).unwrap(),
+            "\"2x1\""
+        );
+        assert_eq!(
+            serde_json::from_str::<PaneLayout>("\"2x2\"").unwrap(),
+            PaneLayout::TwoByTwo
+        );
+    }
+}
@@ -0,0 +1,57 @@
+// Draws one pane's offscreen texture into its viewport on the surface, or
+// blends it over the layers below it in the layered layout
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0) var pane_texture: texture_2d<f32>;
+@group(0) @binding(1) var pane_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Full-screen triangle, clipped to the viewport set for the pane
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0)
+    );
+    var uvs = array<vec2<f32>, 3>(
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(2.0, 1.0),
+        vec2<f32>(0.0, -1.0)
+    );
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
+    out.uv = uvs[vertex_index];
+    return out;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(pane_texture, pane_sampler, input.uv);
+}
+
+struct LayerParams {
+    opacity: f32,
+}
+
+@group(0) @binding(2) var<uniform> layer: LayerParams;
+
+// A layer premultiplied by its opacity, for alpha, add and screen blending
+@fragment
+fn fs_layer(input: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(pane_texture, pane_sampler, input.uv);
+    let coverage = color.a * layer.opacity;
+    return vec4<f32>(color.rgb * coverage, coverage);
+}
+
+// A layer faded toward white by its opacity, so multiplying leaves more of
+// what's below as it fades out
+@fragment
+fn fs_multiply_layer(input: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(pane_texture, pane_sampler, input.uv);
+    let coverage = color.a * layer.opacity;
+    return vec4<f32>(mix(vec3<f32>(1.0), color.rgb, coverage), 1.0);
+}
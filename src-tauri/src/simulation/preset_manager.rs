@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use wgpu::Device;
 use wgpu::Queue;
@@ -8,6 +8,7 @@ use wgpu::Queue;
 use crate::commands::get_settings_dir;
 use crate::error::PresetError;
 use crate::error::PresetResult;
+use crate::simulation::preset_migration::{self, PresetSchema, PresetWarning};
 use serde::{Deserialize, Serialize};
 use toml;
 
@@ -30,11 +31,13 @@ pub struct PresetManager<Settings> {
     presets: Vec<Preset<Settings>>,
     user_presets_dir: PathBuf,
     built_in_preset_names: Vec<String>,
+    // From the last time the user presets were loaded
+    load_warnings: Vec<PresetWarning>,
 }
 
 impl<Settings> PresetManager<Settings>
 where
-    Settings: Clone + Serialize + for<'de> Deserialize<'de> + Default,
+    Settings: Clone + Serialize + for<'de> Deserialize<'de> + Default + PresetSchema,
 {
     pub fn new(simulation_name: String) -> Self {
         let user_presets_dir = get_user_presets_dir(&simulation_name);
@@ -42,11 +45,12 @@ where
             presets: vec![],
             user_presets_dir,
             built_in_preset_names: vec![],
+            load_warnings: vec![],
         };
 
         // Create the user presets directory if it doesn't exist
         if let Err(e) = fs::create_dir_all(&manager.user_presets_dir) {
-            eprintln!("Warning: Could not create user presets directory: {}", e);
+            tracing::warn!("Could not create user presets directory: {}", e);
         }
 
         manager
@@ -69,6 +73,12 @@ where
         self.built_in_preset_names = self.presets.iter().map(|p| p.name.clone()).collect();
     }
 
+    pub fn is_built_in_preset(&self, name: &str) -> bool {
+        self.built_in_preset_names
+            .iter()
+            .any(|built_in| built_in == name)
+    }
+
     /// Save a preset to a TOML file in the user's Documents folder
     pub fn save_user_preset(&self, name: &str, settings: &Settings) -> PresetResult<()> {
         let preset = Preset {
@@ -76,8 +86,7 @@ where
             settings: settings.clone(),
         };
 
-        let toml_content = toml::to_string_pretty(&preset)
-            .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+        let toml_content = preset_migration::to_toml(&preset)?;
         let path = self
             .user_presets_dir
             .join(format!("{}.toml", sanitize_filename(name)));
@@ -89,8 +98,14 @@ where
         Ok(())
     }
 
-    /// Load user presets from TOML files in the user's Documents folder
+    /// Load user presets from TOML files in the user's Documents folder,
+    /// replacing any loaded before so edited and deleted files are picked up
     pub fn load_user_presets(&mut self) -> PresetResult<()> {
+        let built_in_preset_names = &self.built_in_preset_names;
+        self.presets
+            .retain(|p| built_in_preset_names.contains(&p.name));
+        self.load_warnings.clear();
+
         if !self.user_presets_dir.exists() {
             return Ok(());
         }
@@ -108,16 +123,31 @@ where
             let path = entry.path();
 
             if path.extension().and_then(|s| s.to_str()) == Some("toml") {
-                match self.load_preset_from_file(&path) {
-                    Ok(preset) => {
+                let (preset_name, messages) = match self.load_preset_from_file(&path) {
+                    Ok((preset, messages)) => {
+                        let preset_name = preset.name.clone();
                         // Check if this preset name already exists (avoid duplicates)
                         if !self.presets.iter().any(|p| p.name == preset.name) {
                             self.presets.push(preset);
                         }
+                        (preset_name, messages)
                     }
                     Err(e) => {
-                        eprintln!("Warning: Could not load preset from {:?}: {}", path, e);
+                        let preset_name = path
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .unwrap_or_default()
+                            .to_string();
+                        (preset_name, vec![format!("Could not be loaded: {}", e)])
                     }
+                };
+                if !messages.is_empty() {
+                    tracing::warn!("Preset {}: {}", path.display(), messages.join("; "));
+                    self.load_warnings.push(PresetWarning {
+                        preset: preset_name,
+                        path: path.display().to_string(),
+                        messages,
+                    });
                 }
             }
         }
@@ -125,44 +155,24 @@ where
         Ok(())
     }
 
-    /// Load a single preset from a TOML file
-    fn load_preset_from_file(&self, path: &PathBuf) -> PresetResult<Preset<Settings>> {
+    /// Load a single preset from a TOML file, migrated to the current
+    /// version. Also returns what didn't carry over cleanly.
+    fn load_preset_from_file(
+        &self,
+        path: &PathBuf,
+    ) -> PresetResult<(Preset<Settings>, Vec<String>)> {
         let content = fs::read_to_string(path).map_err(|e| PresetError::FileError {
             path: path.clone(),
             error: e.to_string(),
         })?;
+        self.parse_preset(&content)
+    }
 
-        // First try to deserialize directly
-        match toml::from_str::<Preset<Settings>>(&content) {
-            Ok(preset) => Ok(preset),
-            Err(_) => {
-                // If direct deserialization fails, try to merge with defaults
-                let default_settings = Settings::default();
-                let default_preset = Preset {
-                    name: "".to_string(),
-                    settings: default_settings,
-                };
-
-                // Parse as a generic TOML value to handle partial data
-                let _toml_value: toml::Value = toml::from_str(&content)
-                    .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
-
-                // Convert to a partial preset structure
-                let partial_preset: Preset<toml::Value> = toml::from_str(&content)
-                    .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
-
-                // Merge the partial settings with defaults
-                let merged_settings = self.merge_settings_with_defaults(
-                    &partial_preset.settings,
-                    &default_preset.settings,
-                )?;
-
-                Ok(Preset {
-                    name: partial_preset.name,
-                    settings: merged_settings,
-                })
-            }
-        }
+    fn parse_preset(&self, content: &str) -> PresetResult<(Preset<Settings>, Vec<String>)> {
+        let upgraded = preset_migration::upgrade::<Settings>(content)?;
+        let settings =
+            self.merge_settings_with_defaults(&upgraded.settings, &Settings::default())?;
+        Ok((Preset::new(upgraded.name, settings), upgraded.messages))
     }
 
     /// Merge partial settings with default settings, filling in missing fields
@@ -236,11 +246,25 @@ where
     pub fn get_preset_settings(&self, name: &str) -> Option<&Settings> {
         self.get_preset(name).map(|p| &p.settings)
     }
+
+    /// Save preset TOML from elsewhere as a user preset, returning its name.
+    ///
+    /// Unlike loading from the presets directory, missing fields are not filled
+    /// in from defaults, so this fails for presets of another simulation type.
+    pub fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        let (preset, _) = self.parse_preset(content)?;
+        self.save_user_preset(&preset.name, &preset.settings)?;
+        Ok(preset.name)
+    }
+
+    pub fn load_warnings(&self) -> &[PresetWarning] {
+        &self.load_warnings
+    }
 }
 
 impl<Settings> Default for PresetManager<Settings>
 where
-    Settings: Clone + Serialize + for<'de> Deserialize<'de> + Default,
+    Settings: Clone + Serialize + for<'de> Deserialize<'de> + Default + PresetSchema,
 {
     fn default() -> Self {
         Self::new("default".to_string())
@@ -253,7 +277,7 @@ fn get_user_presets_dir(simulation_name: &str) -> PathBuf {
 }
 
 /// Sanitize filename to be safe for filesystem
-fn sanitize_filename(name: &str) -> String {
+pub(crate) fn sanitize_filename(name: &str) -> String {
     name.chars()
         .map(|c| match c {
             '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | ' ' => '_',
@@ -272,12 +296,42 @@ pub type FlowPresetManager = PresetManager<crate::simulations::flow::settings::S
 pub type MoirePresetManager = PresetManager<crate::simulations::moire::settings::Settings>;
 pub type PrimordialParticlesPresetManager =
     PresetManager<crate::simulations::primordial_particles::settings::Settings>;
+pub type TurmitesPresetManager = PresetManager<crate::simulations::turmites::settings::Settings>;
+pub type LifeLikePresetManager = PresetManager<crate::simulations::life_like::settings::Settings>;
+pub type LensingPresetManager = PresetManager<crate::simulations::lensing::settings::Settings>;
+pub type MagneticPendulumPresetManager =
+    PresetManager<crate::simulations::magnetic_pendulum::settings::Settings>;
+pub type PercolationPresetManager =
+    PresetManager<crate::simulations::percolation::settings::Settings>;
+pub type SoftbodyPresetManager = PresetManager<crate::simulations::softbody::settings::Settings>;
+pub type ChemotaxisPresetManager =
+    PresetManager<crate::simulations::chemotaxis::settings::Settings>;
+pub type CrowdPresetManager = PresetManager<crate::simulations::crowd::settings::Settings>;
+pub type PhyllotaxisPresetManager =
+    PresetManager<crate::simulations::phyllotaxis::settings::Settings>;
+pub type HarmonographPresetManager =
+    PresetManager<crate::simulations::harmonograph::settings::Settings>;
+pub type SlimeMold3dPresetManager =
+    PresetManager<crate::simulations::slime_mold_3d::settings::Settings>;
+pub type EikonalPresetManager = PresetManager<crate::simulations::eikonal::settings::Settings>;
+pub type QuasicrystalPresetManager =
+    PresetManager<crate::simulations::quasicrystal::settings::Settings>;
+pub type StipplingPresetManager = PresetManager<crate::simulations::stippling::settings::Settings>;
+pub type VortexPresetManager = PresetManager<crate::simulations::vortex::settings::Settings>;
+pub type BoidsPresetManager = PresetManager<crate::simulations::boids::settings::Settings>;
+pub type DlaPresetManager = PresetManager<crate::simulations::dla::settings::Settings>;
+pub type DoublePendulumPresetManager =
+    PresetManager<crate::simulations::double_pendulum::settings::Settings>;
 
 // Trait for unified preset manager operations
 pub trait AnyPresetManager {
     fn get_preset_names(&self) -> Vec<String>;
     fn delete_user_preset(&mut self, name: &str) -> PresetResult<()>;
     fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()>;
+    fn import_user_preset(&self, content: &str) -> PresetResult<String>;
+    fn is_built_in_preset(&self, name: &str) -> bool;
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value>;
+    fn load_warnings(&self) -> &[PresetWarning];
 }
 
 // Implement the trait for each specific preset manager type
@@ -296,6 +350,22 @@ impl AnyPresetManager for SlimeMoldPresetManager {
                 .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
         self.save_user_preset(name, &typed_settings)
     }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
 }
 
 impl AnyPresetManager for GrayScottPresetManager {
@@ -313,6 +383,22 @@ impl AnyPresetManager for GrayScottPresetManager {
                 .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
         self.save_user_preset(name, &typed_settings)
     }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
 }
 
 impl AnyPresetManager for ParticleLifePresetManager {
@@ -330,6 +416,22 @@ impl AnyPresetManager for ParticleLifePresetManager {
                 .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
         self.save_user_preset(name, &typed_settings)
     }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
 }
 
 impl AnyPresetManager for PelletsPresetManager {
@@ -347,6 +449,22 @@ impl AnyPresetManager for PelletsPresetManager {
                 .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
         self.save_user_preset(name, &typed_settings)
     }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
 }
 
 impl AnyPresetManager for FlowPresetManager {
@@ -364,6 +482,22 @@ impl AnyPresetManager for FlowPresetManager {
                 .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
         self.save_user_preset(name, &typed_settings)
     }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
 }
 
 impl AnyPresetManager for MoirePresetManager {
@@ -381,6 +515,22 @@ impl AnyPresetManager for MoirePresetManager {
                 .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
         self.save_user_preset(name, &typed_settings)
     }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
 }
 
 impl AnyPresetManager for PrimordialParticlesPresetManager {
@@ -398,196 +548,1162 @@ impl AnyPresetManager for PrimordialParticlesPresetManager {
                 .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
         self.save_user_preset(name, &typed_settings)
     }
-}
 
-// Enum to hold different types of preset managers
-pub enum PresetManagerType {
-    SlimeMold(SlimeMoldPresetManager),
-    GrayScott(GrayScottPresetManager),
-    ParticleLife(ParticleLifePresetManager),
-    Pellets(PelletsPresetManager),
-    Flow(FlowPresetManager),
-    Moire(MoirePresetManager),
-    PrimordialParticles(PrimordialParticlesPresetManager),
-}
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
 
-impl PresetManagerType {
-    fn as_any_preset_manager(&self) -> &dyn AnyPresetManager {
-        match self {
-            PresetManagerType::SlimeMold(manager) => manager,
-            PresetManagerType::GrayScott(manager) => manager,
-            PresetManagerType::ParticleLife(manager) => manager,
-            PresetManagerType::Pellets(manager) => manager,
-            PresetManagerType::Flow(manager) => manager,
-            PresetManagerType::Moire(manager) => manager,
-            PresetManagerType::PrimordialParticles(manager) => manager,
-        }
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
     }
 
-    fn as_any_preset_manager_mut(&mut self) -> &mut dyn AnyPresetManager {
-        match self {
-            PresetManagerType::SlimeMold(manager) => manager,
-            PresetManagerType::GrayScott(manager) => manager,
-            PresetManagerType::ParticleLife(manager) => manager,
-            PresetManagerType::Pellets(manager) => manager,
-            PresetManagerType::Flow(manager) => manager,
-            PresetManagerType::Moire(manager) => manager,
-            PresetManagerType::PrimordialParticles(manager) => manager,
-        }
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
     }
 
-    fn get_preset_settings_for_simulation(
-        &self,
-        preset_name: &str,
-        simulation: &mut SimulationType,
-        device: &Arc<Device>,
-        queue: &Arc<Queue>,
-    ) -> PresetResult<()> {
-        match (self, simulation) {
-            (PresetManagerType::SlimeMold(manager), SimulationType::SlimeMold(sim)) => {
-                if let Some(settings) = manager.get_preset_settings(preset_name) {
-                    let settings_json = serde_json::to_value(settings)
-                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
-                    sim.apply_settings(settings_json, device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    sim.reset_runtime_state(device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    tracing::info!("Applied slime mold preset '{}'", preset_name);
-                    Ok(())
-                } else {
-                    Err(format!("Preset '{}' not found for Slime Mold", preset_name).into())
-                }
-            }
-            (PresetManagerType::GrayScott(manager), SimulationType::GrayScott(sim)) => {
-                if let Some(settings) = manager.get_preset_settings(preset_name) {
-                    let settings_json = serde_json::to_value(settings)
-                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
-                    sim.apply_settings(settings_json, device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    sim.reset_runtime_state(device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    tracing::info!("Applied Gray-Scott preset '{}'", preset_name);
-                    Ok(())
-                } else {
-                    Err(format!("Preset '{}' not found for Gray-Scott", preset_name).into())
-                }
-            }
-            (PresetManagerType::ParticleLife(manager), SimulationType::ParticleLife(sim)) => {
-                if let Some(settings) = manager.get_preset_settings(preset_name) {
-                    let settings_json = serde_json::to_value(settings)
-                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
-                    sim.apply_settings(settings_json, device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    sim.reset_runtime_state(device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    tracing::info!("Applied Particle Life preset '{}'", preset_name);
-                    Ok(())
-                } else {
-                    Err(format!("Preset '{}' not found for Particle Life", preset_name).into())
-                }
-            }
-            (PresetManagerType::Pellets(manager), SimulationType::Pellets(sim)) => {
-                if let Some(settings) = manager.get_preset_settings(preset_name) {
-                    let settings_json = serde_json::to_value(settings)
-                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
-                    sim.apply_settings(settings_json, device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    sim.reset_runtime_state(device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    tracing::info!("Applied Pellets preset '{}'", preset_name);
-                    Ok(())
-                } else {
-                    Err(format!("Preset '{}' not found for Pellets", preset_name).into())
-                }
-            }
-            (PresetManagerType::Flow(manager), SimulationType::Flow(sim)) => {
-                if let Some(settings) = manager.get_preset_settings(preset_name) {
-                    let settings_json = serde_json::to_value(settings)
-                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
-                    sim.apply_settings(settings_json, device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    sim.reset_runtime_state(device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    tracing::info!("Applied Flow preset '{}'", preset_name);
-                    Ok(())
-                } else {
-                    Err(format!("Preset '{}' not found for Flow", preset_name).into())
-                }
-            }
-            (PresetManagerType::Moire(manager), SimulationType::Moire(sim)) => {
-                if let Some(settings) = manager.get_preset_settings(preset_name) {
-                    let settings_json = serde_json::to_value(settings)
-                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
-                    sim.apply_settings(settings_json, device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    sim.reset_runtime_state(device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    tracing::info!("Applied Moiré preset '{}'", preset_name);
-                    Ok(())
-                } else {
-                    Err(format!("Preset '{}' not found for Moiré", preset_name).into())
-                }
-            }
-            (
-                PresetManagerType::PrimordialParticles(manager),
-                SimulationType::PrimordialParticles(sim),
-            ) => {
-                if let Some(settings) = manager.get_preset_settings(preset_name) {
-                    let settings_json = serde_json::to_value(settings)
-                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
-                    sim.apply_settings(settings_json, device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    sim.reset_runtime_state(device, queue)
-                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
-                    tracing::info!("Applied Primordial Particles preset '{}'", preset_name);
-                    Ok(())
-                } else {
-                    Err(format!(
-                        "Preset '{}' not found for Primordial Particles",
-                        preset_name
-                    )
-                    .into())
-                }
-            }
-            (_, SimulationType::MainMenu(_)) => Err("Main menu does not support presets".into()),
-            (_, SimulationType::Gradient(_)) => Err("Gradient does not support presets".into()),
-            _ => Err("Simulation type does not match preset manager type".into()),
-        }
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
     }
 }
 
-// Wrapper struct to hold multiple preset managers using HashMap
-pub struct SimulationPresetManager {
-    managers: HashMap<String, PresetManagerType>,
-}
+impl AnyPresetManager for TurmitesPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
 
-impl SimulationPresetManager {
-    pub fn new() -> Self {
-        let mut slime_mold_preset_manager = SlimeMoldPresetManager::new("slime_mold".to_string());
-        let mut gray_scott_preset_manager = GrayScottPresetManager::new("gray_scott".to_string());
-        let mut particle_life_preset_manager =
-            ParticleLifePresetManager::new("particle_life".to_string());
-        let mut pellets_preset_manager = PelletsPresetManager::new("pellets".to_string());
-        let mut flow_preset_manager = FlowPresetManager::new("flow".to_string());
-        let mut moire_preset_manager = MoirePresetManager::new("moire".to_string());
-        let mut primordial_particles_preset_manager =
-            PrimordialParticlesPresetManager::new("primordial_particles".to_string());
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
 
-        crate::simulations::slime_mold::init_presets(&mut slime_mold_preset_manager);
-        crate::simulations::gray_scott::init_presets(&mut gray_scott_preset_manager);
-        crate::simulations::particle_life::init_presets(&mut particle_life_preset_manager);
-        crate::simulations::pellets::init_presets(&mut pellets_preset_manager);
-        crate::simulations::flow::init_presets(&mut flow_preset_manager);
-        crate::simulations::moire::init_presets(&mut moire_preset_manager);
-        crate::simulations::primordial_particles::init_presets(
-            &mut primordial_particles_preset_manager,
-        );
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::turmites::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
 
-        let mut managers = HashMap::new();
-        managers.insert(
-            "slime_mold".to_string(),
-            PresetManagerType::SlimeMold(slime_mold_preset_manager),
-        );
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for LifeLikePresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::life_like::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for LensingPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::lensing::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for MagneticPendulumPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::magnetic_pendulum::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for PercolationPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::percolation::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for SoftbodyPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::softbody::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for ChemotaxisPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::chemotaxis::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for CrowdPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::crowd::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for PhyllotaxisPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::phyllotaxis::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for HarmonographPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::harmonograph::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for SlimeMold3dPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::slime_mold_3d::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for EikonalPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::eikonal::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for QuasicrystalPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::quasicrystal::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for StipplingPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::stippling::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for VortexPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::vortex::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for BoidsPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::boids::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for DlaPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::dla::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+impl AnyPresetManager for DoublePendulumPresetManager {
+    fn get_preset_names(&self) -> Vec<String> {
+        self.get_preset_names()
+    }
+
+    fn delete_user_preset(&mut self, name: &str) -> PresetResult<()> {
+        self.delete_user_preset(name)
+    }
+
+    fn save_user_preset_json(&self, name: &str, settings: &serde_json::Value) -> PresetResult<()> {
+        let typed_settings: crate::simulations::double_pendulum::settings::Settings =
+            serde_json::from_value(settings.clone())
+                .map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+        self.save_user_preset(name, &typed_settings)
+    }
+
+    fn import_user_preset(&self, content: &str) -> PresetResult<String> {
+        self.import_user_preset(content)
+    }
+
+    fn is_built_in_preset(&self, name: &str) -> bool {
+        self.is_built_in_preset(name)
+    }
+
+    fn preset_settings_json(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.get_preset_settings(name)?).ok()
+    }
+
+    fn load_warnings(&self) -> &[PresetWarning] {
+        self.load_warnings()
+    }
+}
+
+// Enum to hold different types of preset managers
+pub enum PresetManagerType {
+    SlimeMold(SlimeMoldPresetManager),
+    GrayScott(GrayScottPresetManager),
+    ParticleLife(ParticleLifePresetManager),
+    Pellets(PelletsPresetManager),
+    Flow(FlowPresetManager),
+    Moire(MoirePresetManager),
+    PrimordialParticles(PrimordialParticlesPresetManager),
+    Turmites(TurmitesPresetManager),
+    LifeLike(LifeLikePresetManager),
+    Lensing(LensingPresetManager),
+    MagneticPendulum(MagneticPendulumPresetManager),
+    Percolation(PercolationPresetManager),
+    Softbody(SoftbodyPresetManager),
+    Chemotaxis(ChemotaxisPresetManager),
+    Crowd(CrowdPresetManager),
+    Phyllotaxis(PhyllotaxisPresetManager),
+    Harmonograph(HarmonographPresetManager),
+    SlimeMold3d(SlimeMold3dPresetManager),
+    Eikonal(EikonalPresetManager),
+    Quasicrystal(QuasicrystalPresetManager),
+    Stippling(StipplingPresetManager),
+    Vortex(VortexPresetManager),
+    Boids(BoidsPresetManager),
+    Dla(DlaPresetManager),
+    DoublePendulum(DoublePendulumPresetManager),
+}
+
+impl PresetManagerType {
+    fn as_any_preset_manager(&self) -> &dyn AnyPresetManager {
+        match self {
+            PresetManagerType::SlimeMold(manager) => manager,
+            PresetManagerType::GrayScott(manager) => manager,
+            PresetManagerType::ParticleLife(manager) => manager,
+            PresetManagerType::Pellets(manager) => manager,
+            PresetManagerType::Flow(manager) => manager,
+            PresetManagerType::Moire(manager) => manager,
+            PresetManagerType::PrimordialParticles(manager) => manager,
+            PresetManagerType::Turmites(manager) => manager,
+            PresetManagerType::LifeLike(manager) => manager,
+            PresetManagerType::Lensing(manager) => manager,
+            PresetManagerType::MagneticPendulum(manager) => manager,
+            PresetManagerType::Percolation(manager) => manager,
+            PresetManagerType::Crowd(manager) => manager,
+            PresetManagerType::Phyllotaxis(manager) => manager,
+            PresetManagerType::Harmonograph(manager) => manager,
+            PresetManagerType::SlimeMold3d(manager) => manager,
+            PresetManagerType::Chemotaxis(manager) => manager,
+            PresetManagerType::Softbody(manager) => manager,
+            PresetManagerType::Eikonal(manager) => manager,
+            PresetManagerType::Quasicrystal(manager) => manager,
+            PresetManagerType::Stippling(manager) => manager,
+            PresetManagerType::Vortex(manager) => manager,
+            PresetManagerType::Boids(manager) => manager,
+            PresetManagerType::Dla(manager) => manager,
+            PresetManagerType::DoublePendulum(manager) => manager,
+        }
+    }
+
+    fn as_any_preset_manager_mut(&mut self) -> &mut dyn AnyPresetManager {
+        match self {
+            PresetManagerType::SlimeMold(manager) => manager,
+            PresetManagerType::GrayScott(manager) => manager,
+            PresetManagerType::ParticleLife(manager) => manager,
+            PresetManagerType::Pellets(manager) => manager,
+            PresetManagerType::Flow(manager) => manager,
+            PresetManagerType::Moire(manager) => manager,
+            PresetManagerType::PrimordialParticles(manager) => manager,
+            PresetManagerType::Turmites(manager) => manager,
+            PresetManagerType::LifeLike(manager) => manager,
+            PresetManagerType::Lensing(manager) => manager,
+            PresetManagerType::MagneticPendulum(manager) => manager,
+            PresetManagerType::Percolation(manager) => manager,
+            PresetManagerType::Crowd(manager) => manager,
+            PresetManagerType::Phyllotaxis(manager) => manager,
+            PresetManagerType::Harmonograph(manager) => manager,
+            PresetManagerType::SlimeMold3d(manager) => manager,
+            PresetManagerType::Chemotaxis(manager) => manager,
+            PresetManagerType::Softbody(manager) => manager,
+            PresetManagerType::Eikonal(manager) => manager,
+            PresetManagerType::Quasicrystal(manager) => manager,
+            PresetManagerType::Stippling(manager) => manager,
+            PresetManagerType::Vortex(manager) => manager,
+            PresetManagerType::Boids(manager) => manager,
+            PresetManagerType::Dla(manager) => manager,
+            PresetManagerType::DoublePendulum(manager) => manager,
+        }
+    }
+
+    fn get_preset_settings_for_simulation(
+        &self,
+        preset_name: &str,
+        simulation: &mut SimulationType,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> PresetResult<()> {
+        match (self, simulation) {
+            (PresetManagerType::SlimeMold(manager), SimulationType::SlimeMold(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied slime mold preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Slime Mold", preset_name).into())
+                }
+            }
+            (PresetManagerType::GrayScott(manager), SimulationType::GrayScott(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Gray-Scott preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Gray-Scott", preset_name).into())
+                }
+            }
+            (PresetManagerType::ParticleLife(manager), SimulationType::ParticleLife(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Particle Life preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Particle Life", preset_name).into())
+                }
+            }
+            (PresetManagerType::Pellets(manager), SimulationType::Pellets(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Pellets preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Pellets", preset_name).into())
+                }
+            }
+            (PresetManagerType::Flow(manager), SimulationType::Flow(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Flow preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Flow", preset_name).into())
+                }
+            }
+            (PresetManagerType::Moire(manager), SimulationType::Moire(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Moiré preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Moiré", preset_name).into())
+                }
+            }
+            (
+                PresetManagerType::PrimordialParticles(manager),
+                SimulationType::PrimordialParticles(sim),
+            ) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Primordial Particles preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Preset '{}' not found for Primordial Particles",
+                        preset_name
+                    )
+                    .into())
+                }
+            }
+            (PresetManagerType::Turmites(manager), SimulationType::Turmites(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Turmites preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Turmites", preset_name).into())
+                }
+            }
+            (PresetManagerType::LifeLike(manager), SimulationType::LifeLike(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Life-like preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Life-like", preset_name).into())
+                }
+            }
+            (PresetManagerType::Lensing(manager), SimulationType::Lensing(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Gravitational Lensing preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Preset '{}' not found for Gravitational Lensing",
+                        preset_name
+                    )
+                    .into())
+                }
+            }
+            (
+                PresetManagerType::MagneticPendulum(manager),
+                SimulationType::MagneticPendulum(sim),
+            ) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Magnetic Pendulum preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Magnetic Pendulum", preset_name).into())
+                }
+            }
+            (PresetManagerType::Percolation(manager), SimulationType::Percolation(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Percolation preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Percolation", preset_name).into())
+                }
+            }
+            (PresetManagerType::Crowd(manager), SimulationType::Crowd(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Crowd preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Crowd", preset_name).into())
+                }
+            }
+            (PresetManagerType::Phyllotaxis(manager), SimulationType::Phyllotaxis(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Phyllotaxis preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Phyllotaxis", preset_name).into())
+                }
+            }
+            (PresetManagerType::Harmonograph(manager), SimulationType::Harmonograph(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Harmonograph preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Harmonograph", preset_name).into())
+                }
+            }
+            (PresetManagerType::SlimeMold3d(manager), SimulationType::SlimeMold3d(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied 3D Slime Mold preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for 3D Slime Mold", preset_name).into())
+                }
+            }
+            (PresetManagerType::Chemotaxis(manager), SimulationType::Chemotaxis(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Chemotaxis preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Chemotaxis", preset_name).into())
+                }
+            }
+            (PresetManagerType::Softbody(manager), SimulationType::Softbody(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Soft Body preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Soft Body", preset_name).into())
+                }
+            }
+            (PresetManagerType::Eikonal(manager), SimulationType::Eikonal(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Eikonal preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Eikonal", preset_name).into())
+                }
+            }
+            (PresetManagerType::Quasicrystal(manager), SimulationType::Quasicrystal(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Quasicrystal preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Quasicrystal", preset_name).into())
+                }
+            }
+            (PresetManagerType::Stippling(manager), SimulationType::Stippling(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Stippling preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Stippling", preset_name).into())
+                }
+            }
+            (PresetManagerType::Vortex(manager), SimulationType::Vortex(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Vortex Smoke preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Vortex Smoke", preset_name).into())
+                }
+            }
+            (PresetManagerType::Boids(manager), SimulationType::Boids(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Boids preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Boids", preset_name).into())
+                }
+            }
+            (PresetManagerType::Dla(manager), SimulationType::Dla(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied DLA preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for DLA", preset_name).into())
+                }
+            }
+            (PresetManagerType::DoublePendulum(manager), SimulationType::DoublePendulum(sim)) => {
+                if let Some(settings) = manager.get_preset_settings(preset_name) {
+                    let settings_json = serde_json::to_value(settings)
+                        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+                    sim.apply_settings(settings_json, device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    sim.reset_runtime_state(device, queue)
+                        .map_err(|e| PresetError::SimulationError(e.to_string()))?;
+                    tracing::info!("Applied Double Pendulum preset '{}'", preset_name);
+                    Ok(())
+                } else {
+                    Err(format!("Preset '{}' not found for Double Pendulum", preset_name).into())
+                }
+            }
+            (_, SimulationType::MainMenu(_)) => Err("Main menu does not support presets".into()),
+            (_, SimulationType::Gradient(_)) => Err("Gradient does not support presets".into()),
+            _ => Err("Simulation type does not match preset manager type".into()),
+        }
+    }
+}
+
+// Wrapper struct to hold multiple preset managers using HashMap
+pub struct SimulationPresetManager {
+    managers: HashMap<String, PresetManagerType>,
+}
+
+impl SimulationPresetManager {
+    pub fn new() -> Self {
+        let mut slime_mold_preset_manager = SlimeMoldPresetManager::new("slime_mold".to_string());
+        let mut gray_scott_preset_manager = GrayScottPresetManager::new("gray_scott".to_string());
+        let mut particle_life_preset_manager =
+            ParticleLifePresetManager::new("particle_life".to_string());
+        let mut pellets_preset_manager = PelletsPresetManager::new("pellets".to_string());
+        let mut flow_preset_manager = FlowPresetManager::new("flow".to_string());
+        let mut moire_preset_manager = MoirePresetManager::new("moire".to_string());
+        let mut primordial_particles_preset_manager =
+            PrimordialParticlesPresetManager::new("primordial_particles".to_string());
+        let mut turmites_preset_manager = TurmitesPresetManager::new("turmites".to_string());
+        let mut life_like_preset_manager = LifeLikePresetManager::new("life_like".to_string());
+        let mut lensing_preset_manager = LensingPresetManager::new("lensing".to_string());
+        let mut magnetic_pendulum_preset_manager =
+            MagneticPendulumPresetManager::new("magnetic_pendulum".to_string());
+        let mut percolation_preset_manager =
+            PercolationPresetManager::new("percolation".to_string());
+        let mut softbody_preset_manager = SoftbodyPresetManager::new("softbody".to_string());
+        let mut chemotaxis_preset_manager = ChemotaxisPresetManager::new("chemotaxis".to_string());
+        let mut crowd_preset_manager = CrowdPresetManager::new("crowd".to_string());
+        let mut phyllotaxis_preset_manager =
+            PhyllotaxisPresetManager::new("phyllotaxis".to_string());
+        let mut harmonograph_preset_manager =
+            HarmonographPresetManager::new("harmonograph".to_string());
+        let mut slime_mold_3d_preset_manager =
+            SlimeMold3dPresetManager::new("slime_mold_3d".to_string());
+        let mut eikonal_preset_manager = EikonalPresetManager::new("eikonal".to_string());
+        let mut quasicrystal_preset_manager =
+            QuasicrystalPresetManager::new("quasicrystal".to_string());
+        let mut stippling_preset_manager = StipplingPresetManager::new("stippling".to_string());
+        let mut vortex_preset_manager = VortexPresetManager::new("vortex".to_string());
+        let mut boids_preset_manager = BoidsPresetManager::new("boids".to_string());
+        let mut dla_preset_manager = DlaPresetManager::new("dla".to_string());
+        let mut double_pendulum_preset_manager =
+            DoublePendulumPresetManager::new("double_pendulum".to_string());
+
+        crate::simulations::slime_mold::init_presets(&mut slime_mold_preset_manager);
+        crate::simulations::gray_scott::init_presets(&mut gray_scott_preset_manager);
+        crate::simulations::particle_life::init_presets(&mut particle_life_preset_manager);
+        crate::simulations::pellets::init_presets(&mut pellets_preset_manager);
+        crate::simulations::flow::init_presets(&mut flow_preset_manager);
+        crate::simulations::moire::init_presets(&mut moire_preset_manager);
+        crate::simulations::primordial_particles::init_presets(
+            &mut primordial_particles_preset_manager,
+        );
+        crate::simulations::turmites::init_presets(&mut turmites_preset_manager);
+        crate::simulations::life_like::init_presets(&mut life_like_preset_manager);
+        crate::simulations::lensing::init_presets(&mut lensing_preset_manager);
+        crate::simulations::magnetic_pendulum::init_presets(&mut magnetic_pendulum_preset_manager);
+        crate::simulations::percolation::init_presets(&mut percolation_preset_manager);
+        crate::simulations::softbody::init_presets(&mut softbody_preset_manager);
+        crate::simulations::chemotaxis::init_presets(&mut chemotaxis_preset_manager);
+        crate::simulations::crowd::init_presets(&mut crowd_preset_manager);
+        crate::simulations::phyllotaxis::init_presets(&mut phyllotaxis_preset_manager);
+        crate::simulations::harmonograph::init_presets(&mut harmonograph_preset_manager);
+        crate::simulations::slime_mold_3d::init_presets(&mut slime_mold_3d_preset_manager);
+        crate::simulations::eikonal::init_presets(&mut eikonal_preset_manager);
+        crate::simulations::quasicrystal::init_presets(&mut quasicrystal_preset_manager);
+        crate::simulations::stippling::init_presets(&mut stippling_preset_manager);
+        crate::simulations::vortex::init_presets(&mut vortex_preset_manager);
+        crate::simulations::boids::init_presets(&mut boids_preset_manager);
+        crate::simulations::dla::init_presets(&mut dla_preset_manager);
+        crate::simulations::double_pendulum::init_presets(&mut double_pendulum_preset_manager);
+
+        let mut managers = HashMap::new();
+        managers.insert(
+            "slime_mold".to_string(),
+            PresetManagerType::SlimeMold(slime_mold_preset_manager),
+        );
         managers.insert(
             "gray_scott".to_string(),
             PresetManagerType::GrayScott(gray_scott_preset_manager),
@@ -612,23 +1728,84 @@ impl SimulationPresetManager {
             "primordial_particles".to_string(),
             PresetManagerType::PrimordialParticles(primordial_particles_preset_manager),
         );
+        managers.insert(
+            "turmites".to_string(),
+            PresetManagerType::Turmites(turmites_preset_manager),
+        );
+        managers.insert(
+            "life_like".to_string(),
+            PresetManagerType::LifeLike(life_like_preset_manager),
+        );
+        managers.insert(
+            "lensing".to_string(),
+            PresetManagerType::Lensing(lensing_preset_manager),
+        );
+        managers.insert(
+            "magnetic_pendulum".to_string(),
+            PresetManagerType::MagneticPendulum(magnetic_pendulum_preset_manager),
+        );
+        managers.insert(
+            "percolation".to_string(),
+            PresetManagerType::Percolation(percolation_preset_manager),
+        );
+        managers.insert(
+            "softbody".to_string(),
+            PresetManagerType::Softbody(softbody_preset_manager),
+        );
+        managers.insert(
+            "chemotaxis".to_string(),
+            PresetManagerType::Chemotaxis(chemotaxis_preset_manager),
+        );
+        managers.insert(
+            "crowd".to_string(),
+            PresetManagerType::Crowd(crowd_preset_manager),
+        );
+        managers.insert(
+            "phyllotaxis".to_string(),
+            PresetManagerType::Phyllotaxis(phyllotaxis_preset_manager),
+        );
+        managers.insert(
+            "harmonograph".to_string(),
+            PresetManagerType::Harmonograph(harmonograph_preset_manager),
+        );
+        managers.insert(
+            "slime_mold_3d".to_string(),
+            PresetManagerType::SlimeMold3d(slime_mold_3d_preset_manager),
+        );
+        managers.insert(
+            "eikonal".to_string(),
+            PresetManagerType::Eikonal(eikonal_preset_manager),
+        );
+        managers.insert(
+            "quasicrystal".to_string(),
+            PresetManagerType::Quasicrystal(quasicrystal_preset_manager),
+        );
+        managers.insert(
+            "stippling".to_string(),
+            PresetManagerType::Stippling(stippling_preset_manager),
+        );
+        managers.insert(
+            "vortex".to_string(),
+            PresetManagerType::Vortex(vortex_preset_manager),
+        );
+        managers.insert(
+            "boids".to_string(),
+            PresetManagerType::Boids(boids_preset_manager),
+        );
+        managers.insert(
+            "dla".to_string(),
+            PresetManagerType::Dla(dla_preset_manager),
+        );
+        managers.insert(
+            "double_pendulum".to_string(),
+            PresetManagerType::DoublePendulum(double_pendulum_preset_manager),
+        );
 
         Self { managers }
     }
 
     fn get_simulation_type_name(simulation_type: &SimulationType) -> &'static str {
-        match simulation_type {
-            SimulationType::SlimeMold(_) => "slime_mold",
-            SimulationType::GrayScott(_) => "gray_scott",
-            SimulationType::ParticleLife(_) => "particle_life",
-            SimulationType::Pellets(_) => "pellets",
-            SimulationType::Flow(_) => "flow",
-            SimulationType::MainMenu(_) => "main_menu",
-            SimulationType::Gradient(_) => "gradient",
-            SimulationType::Moire(_) => "moire",
-            SimulationType::VoronoiCA(_) => "voronoi_ca",
-            SimulationType::PrimordialParticles(_) => "primordial_particles",
-        }
+        simulation_type.type_name()
     }
 
     pub fn get_available_presets(&self, simulation_type: &SimulationType) -> Vec<String> {
@@ -719,6 +1896,60 @@ impl SimulationPresetManager {
                 PresetManagerType::PrimordialParticles(preset_manager) => {
                     preset_manager.load_user_presets()?;
                 }
+                PresetManagerType::Turmites(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::LifeLike(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Lensing(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::MagneticPendulum(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Percolation(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Crowd(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Phyllotaxis(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Harmonograph(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::SlimeMold3d(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Chemotaxis(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Softbody(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Eikonal(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Quasicrystal(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Stippling(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Vortex(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Boids(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::Dla(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
+                PresetManagerType::DoublePendulum(preset_manager) => {
+                    preset_manager.load_user_presets()?;
+                }
             }
             tracing::info!("Reloaded user presets for {}", sim_name);
             Ok(())
@@ -749,6 +1980,60 @@ impl SimulationPresetManager {
         }
     }
 
+    /// Problems found in user preset files the last time they were loaded,
+    /// by simulation. Simulations whose presets all loaded cleanly are left
+    /// out.
+    pub fn load_warnings(&self) -> BTreeMap<String, Vec<PresetWarning>> {
+        self.managers
+            .iter()
+            .map(|(sim_name, manager)| {
+                let warnings = manager.as_any_preset_manager().load_warnings().to_vec();
+                (sim_name.clone(), warnings)
+            })
+            .filter(|(_, warnings)| !warnings.is_empty())
+            .collect()
+    }
+
+    /// Every simulation's user presets folder, with the simulation's name
+    pub fn user_preset_dirs(&self) -> Vec<(String, PathBuf)> {
+        self.managers
+            .keys()
+            .map(|sim_name| (sim_name.clone(), get_user_presets_dir(sim_name)))
+            .collect()
+    }
+
+    /// Import a preset file into whichever simulation's presets it belongs to,
+    /// trying `preferred_sim` first. Returns the simulation and preset names.
+    pub fn import_preset_file(
+        &mut self,
+        path: &Path,
+        preferred_sim: Option<&str>,
+    ) -> PresetResult<(String, String)> {
+        let content = fs::read_to_string(path).map_err(|e| PresetError::FileError {
+            path: path.to_path_buf(),
+            error: e.to_string(),
+        })?;
+
+        let mut candidates: Vec<String> = self.managers.keys().cloned().collect();
+        candidates.sort_by_key(|name| (Some(name.as_str()) != preferred_sim, name.clone()));
+
+        for sim_name in candidates {
+            let imported = self.managers[&sim_name]
+                .as_any_preset_manager()
+                .import_user_preset(&content);
+            if let Ok(preset_name) = imported {
+                self.reload_user_presets(&sim_name)?;
+                tracing::info!("Imported {} preset '{}'", sim_name, preset_name);
+                return Ok((sim_name, preset_name));
+            }
+        }
+
+        Err(PresetError::CompatibilityError(format!(
+            "{} is not a preset for any simulation",
+            path.display()
+        )))
+    }
+
     // Getter methods for accessing the specific preset managers
     pub fn get_manager(&self, sim_name: &str) -> Option<&dyn AnyPresetManager> {
         self.managers
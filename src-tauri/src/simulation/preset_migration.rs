@@ -0,0 +1,212 @@
+//! Versioned preset files.
+//!
+//! Preset TOML carries a top-level `version`, the preset schema version of
+//! the simulation it was saved from. Files written before versioning count
+//! as version 1. On load the settings table is walked up one version at a
+//! time through the simulation's [`PresetSchema::migrate`], so a settings
+//! struct can rename or reshape a field without old presets breaking.
+//!
+//! Whatever the migrations don't account for is still loaded the way it
+//! always was, with missing settings filled in from the defaults and unknown
+//! ones dropped, but each of those is now noted so the user can be told
+//! which presets lost what.
+
+use serde::{Deserialize, Serialize};
+
+use super::preset_manager::Preset;
+use crate::error::{PresetError, PresetResult};
+
+/// Version of presets saved before the field existed
+pub const UNVERSIONED: u32 = 1;
+
+/// How a simulation's settings have changed shape across releases
+pub trait PresetSchema {
+    /// Raise by one, with a matching step in [`PresetSchema::migrate`],
+    /// whenever old presets need rewriting to load into the settings
+    const PRESET_VERSION: u32 = UNVERSIONED;
+
+    /// Rewrite settings saved at `version` into the layout of
+    /// `version + 1`. Returns a note for each change worth telling the user
+    /// about.
+    fn migrate(_version: u32, _settings: &mut toml::Table) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// What was noticed while loading one preset file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PresetWarning {
+    pub preset: String,
+    pub path: String,
+    pub messages: Vec<String>,
+}
+
+/// A preset's settings, migrated to the current version but not yet merged
+/// with the defaults
+#[derive(Debug)]
+pub struct UpgradedPreset {
+    pub name: String,
+    pub settings: toml::Value,
+    pub messages: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct VersionedPreset<'a, Settings> {
+    version: u32,
+    name: &'a str,
+    settings: &'a Settings,
+}
+
+/// The TOML a preset is saved as, stamped with the current version
+pub fn to_toml<Settings: Serialize + PresetSchema>(
+    preset: &Preset<Settings>,
+) -> PresetResult<String> {
+    toml::to_string_pretty(&VersionedPreset {
+        version: Settings::PRESET_VERSION,
+        name: &preset.name,
+        settings: &preset.settings,
+    })
+    .map_err(|e| PresetError::SerializationFailed(e.to_string()))
+}
+
+/// Read a preset file's TOML and bring its settings up to the current
+/// version
+pub fn upgrade<Settings>(content: &str) -> PresetResult<UpgradedPreset>
+where
+    Settings: Serialize + for<'de> Deserialize<'de> + Default + PresetSchema,
+{
+    let mut preset: toml::Table =
+        toml::from_str(content).map_err(|e| PresetError::DeserializationFailed(e.to_string()))?;
+    let name = match preset.remove("name") {
+        Some(toml::Value::String(name)) => name,
+        _ => return Err(PresetError::FormatError("Preset has no name".to_string())),
+    };
+    let version = match preset.remove("version") {
+        None => UNVERSIONED,
+        Some(toml::Value::Integer(version)) if version >= 1 => version as u32,
+        Some(version) => {
+            return Err(PresetError::FormatError(format!(
+                "Preset version {} is not a version number",
+                version
+            )));
+        }
+    };
+    let mut settings = match preset.remove("settings") {
+        Some(toml::Value::Table(settings)) => settings,
+        _ => {
+            return Err(PresetError::FormatError(format!(
+                "Preset '{}' has no settings",
+                name
+            )));
+        }
+    };
+
+    let mut messages = Vec::new();
+    if version > Settings::PRESET_VERSION {
+        messages.push(format!(
+            "Saved by a newer version of Vizza (preset version {}, this one reads up to {}), \
+             so some settings may not load",
+            version,
+            Settings::PRESET_VERSION
+        ));
+    }
+    for from in version..Settings::PRESET_VERSION {
+        messages.extend(Settings::migrate(from, &mut settings));
+    }
+
+    let defaults = toml::Table::try_from(Settings::default())
+        .map_err(|e| PresetError::SerializationFailed(e.to_string()))?;
+    let mut dropped: Vec<&String> = settings
+        .keys()
+        .filter(|key| !defaults.contains_key(*key))
+        .collect();
+    dropped.sort();
+    messages.extend(
+        dropped
+            .into_iter()
+            .map(|key| format!("Unknown setting '{}' was ignored", key)),
+    );
+    let mut defaulted: Vec<&String> = defaults
+        .keys()
+        .filter(|key| !settings.contains_key(*key))
+        .collect();
+    defaulted.sort();
+    messages.extend(
+        defaulted
+            .into_iter()
+            .map(|key| format!("'{}' was missing and uses its default", key)),
+    );
+
+    Ok(UpgradedPreset {
+        name,
+        settings: toml::Value::Table(settings),
+        messages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Settings {
+        feed_rate: f32,
+        kill_rate: f32,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Self {
+                feed_rate: 0.055,
+                kill_rate: 0.062,
+            }
+        }
+    }
+
+    impl PresetSchema for Settings {
+        const PRESET_VERSION: u32 = 2;
+
+        fn migrate(version: u32, settings: &mut toml::Table) -> Vec<String> {
+            match version {
+                1 => match settings.remove("feed") {
+                    Some(feed) => {
+                        settings.insert("feed_rate".to_string(), feed);
+                        vec!["Renamed 'feed' to 'feed_rate'".to_string()]
+                    }
+                    None => Vec::new(),
+                },
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn old_presets_are_migrated_and_what_they_lost_is_noted() {
+        let old = "name = \"Coral\"\n[settings]\nfeed = 0.03\nspeed = 2.0\n";
+        let upgraded = upgrade::<Settings>(old).unwrap();
+        assert_eq!(upgraded.name, "Coral");
+        assert_eq!(upgraded.settings["feed_rate"].as_float(), Some(0.03));
+        assert_eq!(
+            upgraded.messages,
+            vec![
+                "Renamed 'feed' to 'feed_rate'",
+                "Unknown setting 'speed' was ignored",
+                "'kill_rate' was missing and uses its default",
+            ]
+        );
+    }
+
+    #[test]
+    fn saved_presets_round_trip_at_the_current_version() {
+        let preset = Preset::new("Coral".to_string(), Settings::default());
+        let content = to_toml(&preset).unwrap();
+        assert!(content.starts_with("version = 2\n"));
+        let upgraded = upgrade::<Settings>(&content).unwrap();
+        assert!(upgraded.messages.is_empty());
+
+        let newer = content.replace("version = 2", "version = 3");
+        let upgraded = upgrade::<Settings>(&newer).unwrap();
+        assert!(upgraded.messages[0].contains("newer version"));
+    }
+}
@@ -0,0 +1,380 @@
+//! Small, periodic snapshots of the running simulation for the webview.
+//!
+//! The UI sometimes wants a live thumbnail of what is on screen (preset
+//! browser, pane picker) without a second surface. While subscribed, the
+//! render loop re-renders the current frame into an offscreen texture every
+//! `1 / fps` seconds, the GPU shrinks it by `scale`, and the result is read
+//! back and sent as a JPEG data URL in a `preview-frame` event.
+
+use base64::Engine;
+use serde::Serialize;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use wgpu::{Device, Queue, SurfaceConfiguration};
+
+use crate::error::{AppError, AppResult, SimulationError};
+use crate::simulations::shared::FrameCapture;
+
+const PREVIEW_STREAM_SHADER: &str = include_str!("preview_stream.wgsl");
+
+const MAX_FPS: f32 = 30.0;
+const JPEG_QUALITY: u8 = 75;
+
+/// Payload of the `preview-frame` event
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewFrame {
+    pub image: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Full-size render target and the shrunken capture it is drawn into
+struct StreamTargets {
+    frame_view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+    capture: FrameCapture,
+    frame_size: (u32, u32),
+    format: wgpu::TextureFormat,
+}
+
+/// Draws a frame into a texture of another size. Recordings also use it to
+/// scale frames up.
+pub(super) struct Downsampler {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    pub(super) format: wgpu::TextureFormat,
+}
+
+impl Downsampler {
+    pub(super) fn new(device: &Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Preview Stream Shader"),
+            source: wgpu::ShaderSource::Wgsl(PREVIEW_STREAM_SHADER.into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Preview Stream Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Preview Stream Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Preview Stream Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Preview Stream Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+            format,
+        }
+    }
+
+    /// Bind `frame_view`, a full-size frame texture, as the source to scale
+    pub(super) fn bind_group(
+        &self,
+        device: &Device,
+        frame_view: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Preview Stream Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(frame_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        })
+    }
+
+    /// Scale the frame bound in `bind_group` into `target`, or into the
+    /// `[x, y, width, height]` part of it given by `viewport` with the rest
+    /// left black
+    pub(super) fn draw(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        bind_group: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+        viewport: Option<[f32; 4]>,
+    ) {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Preview Stream Encoder"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Preview Stream Downsample Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, bind_group, &[]);
+            if let Some([x, y, width, height]) = viewport {
+                render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+            }
+            render_pass.draw(0..3, 0..1);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+}
+
+pub struct PreviewStream {
+    scale: f32,
+    interval: Duration,
+    last_frame: Option<Instant>,
+    downsampler: Option<Downsampler>,
+    targets: Option<StreamTargets>,
+}
+
+impl PreviewStream {
+    pub fn new(scale: f32, fps: f32) -> AppResult<Self> {
+        if !(scale > 0.0 && scale <= 1.0) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Preview scale must be in (0, 1], got {}",
+                scale
+            ))
+            .into());
+        }
+        if !(fps > 0.0 && fps <= MAX_FPS) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Preview fps must be in (0, {}], got {}",
+                MAX_FPS, fps
+            ))
+            .into());
+        }
+        Ok(Self {
+            scale,
+            interval: Duration::from_secs_f32(1.0 / fps),
+            last_frame: None,
+            downsampler: None,
+            targets: None,
+        })
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.last_frame
+            .is_none_or(|last_frame| last_frame.elapsed() >= self.interval)
+    }
+
+    /// Where the full-size frame should be rendered, sized to the surface
+    pub fn frame_view(
+        &mut self,
+        device: &Arc<Device>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<wgpu::TextureView> {
+        let frame_size = (surface_config.width.max(1), surface_config.height.max(1));
+        let format = surface_config.format;
+
+        if self
+            .downsampler
+            .as_ref()
+            .is_some_and(|downsampler| downsampler.format != format)
+        {
+            self.downsampler = None;
+        }
+        let downsampler = self
+            .downsampler
+            .get_or_insert_with(|| Downsampler::new(device, format));
+
+        let targets = match self.targets.take() {
+            Some(targets) if targets.frame_size == frame_size && targets.format == format => {
+                targets
+            }
+            _ => Self::create_targets(
+                device,
+                downsampler,
+                frame_size,
+                preview_size(frame_size, self.scale),
+                format,
+            )?,
+        };
+        let frame_view = targets.frame_view.clone();
+        self.targets = Some(targets);
+        Ok(frame_view)
+    }
+
+    /// Shrink the frame rendered into `frame_view` and encode it
+    pub fn encode(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> AppResult<PreviewFrame> {
+        let (Some(downsampler), Some(targets)) = (&self.downsampler, &self.targets) else {
+            return Err(SimulationError::NotRunning.into());
+        };
+
+        downsampler.draw(
+            device,
+            queue,
+            &targets.bind_group,
+            &targets.capture.view,
+            None,
+        );
+
+        let image = targets.capture.read_rgba(device, queue)?;
+        self.last_frame = Some(Instant::now());
+        Ok(PreviewFrame {
+            width: image.width(),
+            height: image.height(),
+            image: encode_jpeg_data_url(image)?,
+        })
+    }
+
+    fn create_targets(
+        device: &Arc<Device>,
+        downsampler: &Downsampler,
+        frame_size: (u32, u32),
+        (preview_width, preview_height): (u32, u32),
+        format: wgpu::TextureFormat,
+    ) -> AppResult<StreamTargets> {
+        let frame_view = frame_view(device, frame_size, format);
+
+        let bind_group = downsampler.bind_group(device, &frame_view);
+
+        let capture = FrameCapture::new(
+            device,
+            preview_width,
+            preview_height,
+            format,
+            "Preview Stream",
+        )?;
+
+        Ok(StreamTargets {
+            frame_view,
+            bind_group,
+            capture,
+            frame_size,
+            format,
+        })
+    }
+}
+
+/// A full-size texture for the simulation to render into and the scaler to
+/// sample
+pub(super) fn frame_view(
+    device: &Device,
+    frame_size: (u32, u32),
+    format: wgpu::TextureFormat,
+) -> wgpu::TextureView {
+    let frame_texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Preview Stream Frame Texture"),
+        size: wgpu::Extent3d {
+            width: frame_size.0,
+            height: frame_size.1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    frame_texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn preview_size((width, height): (u32, u32), scale: f32) -> (u32, u32) {
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+fn encode_jpeg_data_url(image: image::RgbaImage) -> AppResult<String> {
+    // JPEG has no alpha channel
+    let rgb = image::DynamicImage::ImageRgba8(image).into_rgb8();
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut Cursor::new(&mut jpeg), JPEG_QUALITY)
+        .encode_image(&rgb)
+        .map_err(|e| AppError::Unknown(format!("Failed to encode preview frame: {}", e)))?;
+    Ok(format!(
+        "data:image/jpeg;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(jpeg)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_size_scales_and_never_collapses() {
+        assert_eq!(preview_size((1920, 1080), 0.25), (480, 270));
+        assert_eq!(preview_size((3, 2), 0.1), (1, 1));
+    }
+
+    #[test]
+    fn out_of_range_parameters_are_rejected() {
+        assert!(PreviewStream::new(0.25, 5.0).is_ok());
+        assert!(PreviewStream::new(0.0, 5.0).is_err());
+        assert!(PreviewStream::new(1.5, 5.0).is_err());
+        assert!(PreviewStream::new(0.25, 0.0).is_err());
+        assert!(PreviewStream::new(0.25, 120.0).is_err());
+    }
+}
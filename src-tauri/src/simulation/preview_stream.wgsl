@@ -0,0 +1,39 @@
+// Shrinks the full-size frame into the preview stream's small capture texture
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0) var frame_texture: texture_2d<f32>;
+@group(0) @binding(1) var frame_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0)
+    );
+    var uvs = array<vec2<f32>, 3>(
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(2.0, 1.0),
+        vec2<f32>(0.0, -1.0)
+    );
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
+    out.uv = uvs[vertex_index];
+    return out;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    // Four bilinear taps spread over the output pixel, so thin features
+    // don't flicker in and out between frames
+    let spread = fwidth(input.uv) * 0.25;
+    var color = textureSample(frame_texture, frame_sampler, input.uv + vec2<f32>(-spread.x, -spread.y));
+    color += textureSample(frame_texture, frame_sampler, input.uv + vec2<f32>(spread.x, -spread.y));
+    color += textureSample(frame_texture, frame_sampler, input.uv + vec2<f32>(-spread.x, spread.y));
+    color += textureSample(frame_texture, frame_sampler, input.uv + vec2<f32>(spread.x, spread.y));
+    return vec4<f32>((color * 0.25).rgb, 1.0);
+}
@@ -0,0 +1,232 @@
+//! Small offscreen previews of each simulation for the main menu.
+//!
+//! Each preview keeps its own low-resolution simulation instance alive so that
+//! repeated requests show the simulation evolving. Rendering is throttled: a
+//! preview is only advanced and re-encoded once its refresh interval elapses,
+//! otherwise the cached image is returned as-is.
+
+use base64::Engine;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use wgpu::{Device, Queue};
+
+use crate::commands::AppSettings;
+use crate::error::{AppError, AppResult, SimulationError};
+use crate::simulation::preset_manager::SimulationPresetManager;
+use crate::simulations::shared::{ColorSchemeManager, FrameCapture};
+use crate::simulations::traits::{Simulation, SimulationType};
+
+pub const PREVIEW_WIDTH: u32 = 256;
+pub const PREVIEW_HEIGHT: u32 = 144;
+
+/// Slime mold normally runs with millions of agents; a thumbnail needs far fewer
+const PREVIEW_SLIME_MOLD_AGENT_COUNT: usize = 100_000;
+/// Frames simulated when a preview is first created so it doesn't start blank
+const WARM_UP_FRAMES: u32 = 60;
+/// Frames simulated on each refresh
+const FRAMES_PER_REFRESH: u32 = 10;
+const FRAME_DELTA_TIME: f32 = 1.0 / 60.0;
+
+/// Simulations that can be previewed from the main menu
+pub const PREVIEWABLE_SIMULATIONS: &[&str] = &[
+    "slime_mold",
+    "gray_scott",
+    "particle_life",
+    "flow",
+    "pellets",
+    "gradient",
+    "voronoi_ca",
+    "moire",
+    "primordial_particles",
+    "turmites",
+    "life_like",
+    "lensing",
+    "magnetic_pendulum",
+    "percolation",
+    "softbody",
+    "chemotaxis",
+    "crowd",
+    "phyllotaxis",
+    "harmonograph",
+    "slime_mold_3d",
+    "eikonal",
+    "quasicrystal",
+    "stippling",
+    "vortex",
+    "boids",
+    "dla",
+    "double_pendulum",
+];
+
+struct SimulationPreview {
+    simulation: SimulationType,
+    capture: FrameCapture,
+    encoded: String,
+    last_rendered: Instant,
+}
+
+pub struct SimulationPreviews {
+    previews: HashMap<String, SimulationPreview>,
+    pub refresh_interval: Duration,
+}
+
+impl Default for SimulationPreviews {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulationPreviews {
+    pub fn new() -> Self {
+        Self {
+            previews: HashMap::new(),
+            refresh_interval: Duration::from_millis(500),
+        }
+    }
+
+    /// Drop all preview simulations and free their GPU resources
+    pub fn clear(&mut self) {
+        self.previews.clear();
+    }
+
+    /// Get a PNG data URL showing the given simulation, creating or refreshing
+    /// the preview as needed.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_preview(
+        &mut self,
+        simulation_type: &str,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_format: wgpu::TextureFormat,
+        adapter_info: &wgpu::AdapterInfo,
+        preset_manager: &SimulationPresetManager,
+        color_scheme_manager: &ColorSchemeManager,
+        app_settings: &AppSettings,
+    ) -> AppResult<String> {
+        if !PREVIEWABLE_SIMULATIONS.contains(&simulation_type) {
+            return Err(SimulationError::unknown_type(simulation_type).into());
+        }
+
+        if let Some(preview) = self.previews.get_mut(simulation_type) {
+            if preview.last_rendered.elapsed() >= self.refresh_interval {
+                preview.advance(device, queue, FRAMES_PER_REFRESH)?;
+            }
+            return Ok(preview.encoded.clone());
+        }
+
+        let capture = FrameCapture::new(
+            device,
+            PREVIEW_WIDTH,
+            PREVIEW_HEIGHT,
+            surface_format,
+            "Simulation Preview",
+        )?;
+        let surface_config = capture.surface_config();
+
+        let mut simulation = create_preview_simulation(
+            simulation_type,
+            device,
+            queue,
+            &surface_config,
+            adapter_info,
+            color_scheme_manager,
+            app_settings,
+        )
+        .await?;
+
+        if let Some(preset_name) = representative_preset(preset_manager, simulation_type) {
+            preset_manager.apply_preset(&mut simulation, &preset_name, device, queue)?;
+            simulation.reset_runtime_state(device, queue)?;
+        }
+
+        let mut preview = SimulationPreview {
+            simulation,
+            capture,
+            encoded: String::new(),
+            last_rendered: Instant::now(),
+        };
+        preview.advance(device, queue, WARM_UP_FRAMES)?;
+
+        let encoded = preview.encoded.clone();
+        self.previews.insert(simulation_type.to_string(), preview);
+        Ok(encoded)
+    }
+}
+
+impl SimulationPreview {
+    fn advance(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, frames: u32) -> AppResult<()> {
+        for _ in 0..frames {
+            self.simulation
+                .render_frame(device, queue, &self.capture.view, FRAME_DELTA_TIME)?;
+        }
+        let image = self.capture.read_rgba(device, queue)?;
+        self.encoded = encode_png_data_url(&image)?;
+        self.last_rendered = Instant::now();
+        Ok(())
+    }
+}
+
+/// A simulation sized for a preview capture, with fewer slime mold agents
+/// than a full-size one
+pub(super) async fn create_preview_simulation(
+    simulation_type: &str,
+    device: &Arc<Device>,
+    queue: &Arc<Queue>,
+    surface_config: &wgpu::SurfaceConfiguration,
+    adapter_info: &wgpu::AdapterInfo,
+    color_scheme_manager: &ColorSchemeManager,
+    app_settings: &AppSettings,
+) -> AppResult<SimulationType> {
+    if simulation_type == "slime_mold" {
+        let simulation = crate::simulations::slime_mold::SlimeMoldModel::new(
+            device,
+            queue,
+            surface_config,
+            adapter_info,
+            PREVIEW_SLIME_MOLD_AGENT_COUNT,
+            crate::simulations::slime_mold::settings::Settings::default(),
+            app_settings,
+            color_scheme_manager,
+        )?;
+        return Ok(SimulationType::SlimeMold(Box::new(simulation)));
+    }
+    SimulationType::new(
+        simulation_type,
+        device,
+        queue,
+        surface_config,
+        adapter_info,
+        color_scheme_manager,
+        app_settings,
+    )
+    .await
+    .map_err(|e| AppError::Simulation(SimulationError::InitializationFailed(e.to_string())))
+}
+
+/// Prefer the "Default" preset, falling back to the first one available
+fn representative_preset(
+    preset_manager: &SimulationPresetManager,
+    simulation_type: &str,
+) -> Option<String> {
+    let names = preset_manager
+        .get_manager(simulation_type)?
+        .get_preset_names();
+    names
+        .iter()
+        .find(|name| name.as_str() == "Default")
+        .or_else(|| names.first())
+        .cloned()
+}
+
+pub(super) fn encode_png_data_url(image: &image::RgbaImage) -> AppResult<String> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| AppError::Unknown(format!("Failed to encode preview: {}", e)))?;
+    Ok(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png)
+    ))
+}
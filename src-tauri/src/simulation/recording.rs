@@ -0,0 +1,477 @@
+//! Recording the running simulation to video or a numbered PNG sequence.
+//!
+//! While a recording runs, the render loop re-renders the current frame into
+//! an offscreen texture `fps` times a second, scales it to the recording's
+//! own resolution and reads it back. If the window has a different shape than
+//! the recording, the frame is letterboxed rather than stretched. Frames go to
+//! a writer thread so encoding doesn't hold up the render loop: PNGs are
+//! written with the `image` crate, MP4 and WebM are piped as raw RGBA into an
+//! `ffmpeg` found on the PATH.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, SyncSender};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use wgpu::{Device, Queue, SurfaceConfiguration};
+
+use super::preview_stream::{Downsampler, frame_view};
+use crate::error::{AppError, AppResult, SimulationError};
+use crate::simulations::shared::FrameCapture;
+
+pub const MIN_SIZE: u32 = 16;
+pub const MAX_SIZE: u32 = 7680;
+pub const MAX_FPS: f32 = 120.0;
+
+/// Frames read back but not yet written. Past this the render loop waits for
+/// the writer instead of dropping frames.
+const QUEUED_FRAMES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordingFormat {
+    /// `frame_000000.png`, `frame_000001.png`, ... in a directory
+    PngSequence,
+    Mp4,
+    WebM,
+}
+
+impl RecordingFormat {
+    /// Video for `.mp4` and `.webm` paths, a PNG sequence for anything else
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("mp4") => RecordingFormat::Mp4,
+            Some("webm") => RecordingFormat::WebM,
+            _ => RecordingFormat::PngSequence,
+        }
+    }
+
+    fn is_video(&self) -> bool {
+        !matches!(self, RecordingFormat::PngSequence)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingConfig {
+    /// The video file, or the directory the PNG sequence goes in
+    pub path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f32,
+}
+
+impl RecordingConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        for (name, size) in [("width", self.width), ("height", self.height)] {
+            if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+                return Err(SimulationError::InvalidParameter(format!(
+                    "Recording {} must be between {} and {}, got {}",
+                    name, MIN_SIZE, MAX_SIZE, size
+                ))
+                .into());
+            }
+            // Video is stored with half-resolution color, which needs even sizes
+            if RecordingFormat::from_path(&self.path).is_video() && size % 2 != 0 {
+                return Err(SimulationError::InvalidParameter(format!(
+                    "Video {} must be even, got {}",
+                    name, size
+                ))
+                .into());
+            }
+        }
+        if !(self.fps > 0.0 && self.fps <= MAX_FPS) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Recording fps must be in (0, {}], got {}",
+                MAX_FPS, self.fps
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// What a finished recording produced
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingSummary {
+    pub path: PathBuf,
+    pub format: RecordingFormat,
+    pub frames: u64,
+}
+
+/// Full-size render target, rebuilt when the window changes size
+struct RecordingTargets {
+    frame_view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+    frame_size: (u32, u32),
+}
+
+pub struct Recording {
+    config: RecordingConfig,
+    format: RecordingFormat,
+    interval: Duration,
+    last_frame: Option<Instant>,
+    frames: u64,
+    scaler: Option<Downsampler>,
+    capture: Option<FrameCapture>,
+    targets: Option<RecordingTargets>,
+    writer: FrameWriter,
+}
+
+impl Recording {
+    /// Check `config` and start the writer. Fails straight away when the
+    /// output can't be created or, for video, `ffmpeg` can't be started.
+    pub fn start(config: RecordingConfig) -> AppResult<Self> {
+        config.validate()?;
+        let format = RecordingFormat::from_path(&config.path);
+        let writer = FrameWriter::start(&config, format)?;
+        Ok(Self {
+            interval: Duration::from_secs_f32(1.0 / config.fps),
+            config,
+            format,
+            last_frame: None,
+            frames: 0,
+            scaler: None,
+            capture: None,
+            targets: None,
+            writer,
+        })
+    }
+
+    pub fn config(&self) -> &RecordingConfig {
+        &self.config
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.last_frame
+            .is_none_or(|last_frame| last_frame.elapsed() >= self.interval)
+    }
+
+    /// Where the full-size frame should be rendered, sized to the surface
+    pub fn frame_view(
+        &mut self,
+        device: &Arc<Device>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<wgpu::TextureView> {
+        let frame_size = (surface_config.width.max(1), surface_config.height.max(1));
+        let format = surface_config.format;
+
+        if self
+            .scaler
+            .as_ref()
+            .is_some_and(|scaler| scaler.format != format)
+        {
+            self.scaler = None;
+            self.capture = None;
+            self.targets = None;
+        }
+        let scaler = self
+            .scaler
+            .get_or_insert_with(|| Downsampler::new(device, format));
+        if self.capture.is_none() {
+            self.capture = Some(FrameCapture::new(
+                device,
+                self.config.width,
+                self.config.height,
+                format,
+                "Recording",
+            )?);
+        }
+
+        let targets = match self.targets.take() {
+            Some(targets) if targets.frame_size == frame_size => targets,
+            _ => {
+                let frame_view = frame_view(device, frame_size, format);
+                RecordingTargets {
+                    bind_group: scaler.bind_group(device, &frame_view),
+                    frame_view,
+                    frame_size,
+                }
+            }
+        };
+        let frame_view = targets.frame_view.clone();
+        self.targets = Some(targets);
+        Ok(frame_view)
+    }
+
+    /// Scale the frame rendered into `frame_view` and hand it to the writer
+    pub fn write_frame(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> AppResult<()> {
+        let (Some(scaler), Some(capture), Some(targets)) =
+            (&self.scaler, &self.capture, &self.targets)
+        else {
+            return Err(SimulationError::NotRunning.into());
+        };
+        let viewport = letterbox(targets.frame_size, (self.config.width, self.config.height));
+        scaler.draw(
+            device,
+            queue,
+            &targets.bind_group,
+            &capture.view,
+            Some(viewport),
+        );
+        let image = capture.read_rgba(device, queue)?;
+        self.writer.send(image)?;
+        self.last_frame = Some(Instant::now());
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Wait for the writer to finish everything it was given
+    pub fn finish(self) -> AppResult<RecordingSummary> {
+        self.writer.finish()?;
+        tracing::info!(
+            "Recorded {} frames to {}",
+            self.frames,
+            self.config.path.display()
+        );
+        Ok(RecordingSummary {
+            path: self.config.path,
+            format: self.format,
+            frames: self.frames,
+        })
+    }
+}
+
+/// The `[x, y, width, height]` of the largest area of `output` with the shape
+/// of `frame`, centered, so the frame isn't stretched
+fn letterbox(frame: (u32, u32), output: (u32, u32)) -> [f32; 4] {
+    let (frame_width, frame_height) = (frame.0 as f32, frame.1 as f32);
+    let (output_width, output_height) = (output.0 as f32, output.1 as f32);
+    let scale = (output_width / frame_width).min(output_height / frame_height);
+    let (width, height) = (frame_width * scale, frame_height * scale);
+    [
+        ((output_width - width) / 2.0).round(),
+        ((output_height - height) / 2.0).round(),
+        width.round(),
+        height.round(),
+    ]
+}
+
+/// `ffmpeg` reading raw RGBA frames from its stdin
+fn ffmpeg_command(config: &RecordingConfig, format: RecordingFormat) -> Command {
+    let mut command = Command::new("ffmpeg");
+    command
+        .args([
+            "-y",
+            "-loglevel",
+            "error",
+            "-f",
+            "rawvideo",
+            "-pix_fmt",
+            "rgba",
+        ])
+        .arg("-s")
+        .arg(format!("{}x{}", config.width, config.height))
+        .arg("-r")
+        .arg(config.fps.to_string())
+        .args(["-i", "-"]);
+    match format {
+        RecordingFormat::WebM => command.args(["-c:v", "libvpx-vp9", "-b:v", "0", "-crf", "30"]),
+        _ => command.args(["-c:v", "libx264", "-crf", "18"]),
+    };
+    command
+        .args(["-pix_fmt", "yuv420p"])
+        .arg(&config.path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null());
+    command
+}
+
+/// Writes frames on its own thread, in the order they were sent
+struct FrameWriter {
+    sender: Option<SyncSender<image::RgbaImage>>,
+    // Errors are sent back as text, since `AppError` can't cross threads
+    thread: Option<JoinHandle<Result<(), String>>>,
+}
+
+impl FrameWriter {
+    fn start(config: &RecordingConfig, format: RecordingFormat) -> AppResult<Self> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(QUEUED_FRAMES);
+        let thread = match format {
+            RecordingFormat::PngSequence => {
+                std::fs::create_dir_all(&config.path)?;
+                let directory = config.path.clone();
+                std::thread::spawn(move || {
+                    write_png_sequence(&directory, receiver).map_err(|e| e.to_string())
+                })
+            }
+            RecordingFormat::Mp4 | RecordingFormat::WebM => {
+                let ffmpeg = ffmpeg_command(config, format).spawn().map_err(|e| {
+                    AppError::Unknown(format!(
+                        "Failed to start ffmpeg, which video recording needs on the PATH: {}",
+                        e
+                    ))
+                })?;
+                std::thread::spawn(move || write_video(ffmpeg, receiver).map_err(|e| e.to_string()))
+            }
+        };
+        Ok(Self {
+            sender: Some(sender),
+            thread: Some(thread),
+        })
+    }
+
+    fn send(&self, image: image::RgbaImage) -> AppResult<()> {
+        let sent = self
+            .sender
+            .as_ref()
+            .is_some_and(|sender| sender.send(image).is_ok());
+        if sent {
+            return Ok(());
+        }
+        // The writer only hangs up when it has failed
+        Err(AppError::Unknown(
+            "The recording's writer stopped".to_string(),
+        ))
+    }
+
+    fn finish(mut self) -> AppResult<()> {
+        self.close()
+    }
+
+    fn close(&mut self) -> AppResult<()> {
+        self.sender = None;
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result.map_err(AppError::Unknown),
+            Some(Err(_)) => Err(AppError::Unknown(
+                "The recording's writer panicked".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for FrameWriter {
+    /// A recording dropped without finishing still gets its files closed
+    fn drop(&mut self) {
+        if let Err(e) = self.close() {
+            tracing::warn!("Recording ended with an error: {}", e);
+        }
+    }
+}
+
+fn write_png_sequence(directory: &Path, frames: Receiver<image::RgbaImage>) -> AppResult<()> {
+    for (index, frame) in frames.into_iter().enumerate() {
+        let path = directory.join(format!("frame_{:06}.png", index));
+        frame
+            .save_with_format(&path, image::ImageFormat::Png)
+            .map_err(|e| AppError::Unknown(format!("Failed to write {}: {}", path.display(), e)))?;
+    }
+    Ok(())
+}
+
+fn write_video(mut ffmpeg: Child, frames: Receiver<image::RgbaImage>) -> AppResult<()> {
+    let mut stdin = ffmpeg
+        .stdin
+        .take()
+        .ok_or_else(|| AppError::Unknown("ffmpeg has no stdin".to_string()))?;
+    let written = frames
+        .into_iter()
+        .try_for_each(|frame| stdin.write_all(frame.as_raw()));
+    // ffmpeg only finishes the file once its input is closed
+    drop(stdin);
+    let status = ffmpeg.wait()?;
+    if !status.success() {
+        return Err(AppError::Unknown(format!(
+            "ffmpeg failed to encode the recording ({})",
+            status
+        )));
+    }
+    written?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(path: &str, width: u32, height: u32, fps: f32) -> RecordingConfig {
+        RecordingConfig {
+            path: PathBuf::from(path),
+            width,
+            height,
+            fps,
+        }
+    }
+
+    #[test]
+    fn format_is_chosen_by_extension() {
+        assert_eq!(
+            RecordingFormat::from_path(Path::new("clip.MP4")),
+            RecordingFormat::Mp4
+        );
+        assert_eq!(
+            RecordingFormat::from_path(Path::new("clip.webm")),
+            RecordingFormat::WebM
+        );
+        assert_eq!(
+            RecordingFormat::from_path(Path::new("/tmp/frames")),
+            RecordingFormat::PngSequence
+        );
+    }
+
+    #[test]
+    fn out_of_range_configs_are_rejected() {
+        assert!(config("clip.mp4", 1920, 1080, 60.0).validate().is_ok());
+        assert!(config("frames", 1921, 1081, 24.0).validate().is_ok());
+        // Odd sizes only work for PNGs
+        assert!(config("clip.mp4", 1921, 1080, 60.0).validate().is_err());
+        assert!(config("frames", 8, 1080, 60.0).validate().is_err());
+        assert!(
+            config("frames", 1920, MAX_SIZE + 2, 60.0)
+                .validate()
+                .is_err()
+        );
+        assert!(config("frames", 1920, 1080, 0.0).validate().is_err());
+        assert!(config("frames", 1920, 1080, 240.0).validate().is_err());
+    }
+
+    #[test]
+    fn letterbox_keeps_the_frame_shape() {
+        // A wide window in a square recording gets bars above and below
+        assert_eq!(letterbox((200, 100), (100, 100)), [0.0, 25.0, 100.0, 50.0]);
+        // A tall one gets bars at the sides
+        assert_eq!(letterbox((100, 200), (100, 100)), [25.0, 0.0, 50.0, 100.0]);
+        assert_eq!(
+            letterbox((1280, 720), (1920, 1080)),
+            [0.0, 0.0, 1920.0, 1080.0]
+        );
+    }
+
+    #[test]
+    fn png_sequences_are_numbered_in_order() {
+        let directory =
+            std::env::temp_dir().join(format!("vizza-recording-{}", std::process::id()));
+        let writer = FrameWriter::start(
+            &config(directory.to_str().unwrap(), 16, 16, 30.0),
+            RecordingFormat::PngSequence,
+        )
+        .unwrap();
+        for shade in [0u8, 255] {
+            writer
+                .send(image::RgbaImage::from_pixel(
+                    16,
+                    16,
+                    image::Rgba([shade, 0, 0, 255]),
+                ))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        let second = image::open(directory.join("frame_000001.png"))
+            .unwrap()
+            .into_rgba8();
+        assert_eq!(second.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert!(directory.join("frame_000000.png").exists());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,358 @@
+//! Recording a session's input and playing it back frame for frame.
+//!
+//! A replay starts from the simulation's settings and grows its world from
+//! the seed among them, then notes every input by the frame it arrived
+//! before: setting changes, preset applies, camera moves and mouse presses.
+//! It also keeps how long each frame was, so playback can take the same
+//! steps rather than whatever the render loop manages that day. With the
+//! same seed, the same steps and the same input, the simulation goes the
+//! same way again, as long as nothing it does depends on the order the GPU
+//! happens to run work in. Only simulations whose
+//! [`supports_replay`](crate::simulations::traits::Simulation::supports_replay)
+//! says so can be recorded.
+//!
+//! Unlike a [`Macro`](super::macros::Macro), which repeats actions on a
+//! clock against whatever is running, a replay brings back its start and
+//! shuts out live mouse input while it plays.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use wgpu::{Device, Queue};
+
+use super::SimulationManager;
+use super::macros::MacroAction;
+use super::preset_manager::sanitize_filename;
+use super::settings_codec::SharedConfiguration;
+use crate::commands::get_settings_dir;
+use crate::error::{AppError, AppResult, SimulationError};
+
+/// Bump when a change would stop older replays from parsing
+pub const REPLAY_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "input")]
+pub enum ReplayInput {
+    /// Anything a macro records
+    Action(MacroAction),
+    MousePress {
+        screen_x: f32,
+        screen_y: f32,
+        mouse_button: u32,
+    },
+    WorldMousePress {
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+    },
+    MouseRelease {
+        mouse_button: u32,
+    },
+}
+
+impl From<MacroAction> for ReplayInput {
+    fn from(action: MacroAction) -> Self {
+        ReplayInput::Action(action)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    /// The frame the input arrived before
+    pub frame: usize,
+    pub input: ReplayInput,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub name: String,
+    /// Settings, seed included, and camera the recording started from
+    pub configuration: SharedConfiguration,
+    /// Seconds each frame stepped the simulation by
+    pub frame_seconds: Vec<f32>,
+    pub events: Vec<ReplayEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayInfo {
+    pub name: String,
+    pub simulation_type: String,
+    pub frames: usize,
+    pub duration_seconds: f32,
+}
+
+impl Replay {
+    pub fn duration_seconds(&self) -> f32 {
+        self.frame_seconds.iter().sum()
+    }
+
+    pub fn info(&self) -> ReplayInfo {
+        ReplayInfo {
+            name: self.name.clone(),
+            simulation_type: self.configuration.simulation_type.clone(),
+            frames: self.frame_seconds.len(),
+            duration_seconds: self.duration_seconds(),
+        }
+    }
+
+    pub fn save(&self) -> AppResult<PathBuf> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize replay: {}", e)))?;
+        std::fs::create_dir_all(replays_dir())?;
+        let path = replay_path(&self.name);
+        std::fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    pub fn load(name: &str) -> AppResult<Self> {
+        let path = replay_path(name);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            SimulationError::InvalidParameter(format!("Failed to read replay '{}': {}", name, e))
+        })?;
+        let replay: Self = toml::from_str(&content)
+            .map_err(|e| AppError::Unknown(format!("Failed to parse {}: {}", path.display(), e)))?;
+        if replay.version > REPLAY_VERSION {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Replay '{}' is version {}, newer than this build reads",
+                name, replay.version
+            ))
+            .into());
+        }
+        Ok(replay)
+    }
+
+    pub fn delete(name: &str) -> AppResult<()> {
+        std::fs::remove_file(replay_path(name))?;
+        Ok(())
+    }
+
+    /// Names of the saved replays, sorted
+    pub fn list() -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(replays_dir()) else {
+            return vec![];
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("toml"))
+            .filter_map(|path| {
+                // The file name is sanitized, the name inside is what the user typed
+                let content = std::fs::read_to_string(&path).ok()?;
+                toml::from_str::<Replay>(&content)
+                    .map(|replay| replay.name)
+                    .ok()
+            })
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReplayRecorder {
+    recording: Option<Replay>,
+}
+
+impl ReplayRecorder {
+    pub fn start(&mut self, name: &str, configuration: SharedConfiguration) -> AppResult<()> {
+        if name.trim().is_empty() {
+            return Err(SimulationError::InvalidParameter(
+                "Replay name cannot be empty".to_string(),
+            )
+            .into());
+        }
+        self.recording = Some(Replay {
+            version: REPLAY_VERSION,
+            name: name.to_string(),
+            configuration,
+            frame_seconds: Vec::new(),
+            events: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// The recorded replay, or `None` if nothing was being recorded
+    pub fn stop(&mut self) -> Option<Replay> {
+        self.recording.take()
+    }
+
+    pub fn record(&mut self, input: ReplayInput) {
+        if let Some(recording) = &mut self.recording {
+            recording.events.push(ReplayEvent {
+                frame: recording.frame_seconds.len(),
+                input,
+            });
+        }
+    }
+
+    /// Note a frame that stepped the simulation by `delta_time`
+    pub fn end_frame(&mut self, delta_time: f32) {
+        if let Some(recording) = &mut self.recording {
+            recording.frame_seconds.push(delta_time);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    frame: usize,
+    next_event: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayProgress {
+    pub name: String,
+    pub frame: usize,
+    pub frames: usize,
+}
+
+impl ReplayPlayer {
+    /// Bring back the replay's start in the running simulation and get
+    /// ready to play it from the first frame
+    pub fn start(
+        replay: Replay,
+        manager: &mut SimulationManager,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> AppResult<Self> {
+        manager.apply_shared_configuration(&replay.configuration, device, queue)?;
+        manager.reset_runtime_state(device, queue)?;
+        Ok(Self {
+            replay,
+            frame: 0,
+            next_event: 0,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.replay.name
+    }
+
+    pub fn progress(&self) -> ReplayProgress {
+        ReplayProgress {
+            name: self.replay.name.clone(),
+            frame: self.frame,
+            frames: self.replay.frame_seconds.len(),
+        }
+    }
+
+    /// The input due before the next frame and how long that frame is, or
+    /// `None` once every frame has played
+    pub fn next_frame(&mut self) -> Option<(Vec<ReplayInput>, f32)> {
+        let delta_time = *self.replay.frame_seconds.get(self.frame)?;
+        let due = self.replay.events[self.next_event..]
+            .iter()
+            .take_while(|event| event.frame <= self.frame)
+            .map(|event| event.input.clone())
+            .collect::<Vec<_>>();
+        self.next_event += due.len();
+        self.frame += 1;
+        Some((due, delta_time))
+    }
+}
+
+fn replays_dir() -> PathBuf {
+    get_settings_dir().join("replays")
+}
+
+fn replay_path(name: &str) -> PathBuf {
+    replays_dir().join(format!("{}.toml", sanitize_filename(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configuration() -> SharedConfiguration {
+        SharedConfiguration {
+            simulation_type: "slime_mold".to_string(),
+            preset: None,
+            settings: Some(serde_json::json!({ "random_seed": 7 })),
+            camera: None,
+            color_scheme: None,
+        }
+    }
+
+    #[test]
+    fn inputs_are_kept_with_the_frame_they_arrived_before() {
+        let mut recorder = ReplayRecorder::default();
+        recorder.record(MacroAction::ResetCamera.into());
+        recorder.end_frame(0.016);
+        assert!(recorder.stop().is_none());
+
+        recorder.start("Swirl", configuration()).unwrap();
+        recorder.record(ReplayInput::MousePress {
+            screen_x: 10.0,
+            screen_y: 20.0,
+            mouse_button: 0,
+        });
+        recorder.end_frame(0.016);
+        recorder.end_frame(0.017);
+        recorder.record(ReplayInput::MouseRelease { mouse_button: 0 });
+        recorder.end_frame(0.015);
+        let replay = recorder.stop().unwrap();
+        assert_eq!(replay.frame_seconds, vec![0.016, 0.017, 0.015]);
+        assert_eq!(
+            replay
+                .events
+                .iter()
+                .map(|event| event.frame)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+    }
+
+    #[test]
+    fn playback_hands_out_each_frame_with_its_input() {
+        let mut recorder = ReplayRecorder::default();
+        recorder.start("Swirl", configuration()).unwrap();
+        recorder.record(MacroAction::ZoomCamera { delta: 0.5 }.into());
+        recorder.record(ReplayInput::MouseRelease { mouse_button: 1 });
+        recorder.end_frame(0.02);
+        recorder.end_frame(0.03);
+        let mut player = ReplayPlayer {
+            replay: recorder.stop().unwrap(),
+            frame: 0,
+            next_event: 0,
+        };
+
+        let (inputs, delta_time) = player.next_frame().unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(delta_time, 0.02);
+        assert_eq!(player.next_frame(), Some((vec![], 0.03)));
+        assert_eq!(player.next_frame(), None);
+        assert_eq!(player.progress().frame, 2);
+    }
+
+    #[test]
+    fn replays_round_trip_through_toml() {
+        let replay = Replay {
+            version: REPLAY_VERSION,
+            name: "Swirl".to_string(),
+            configuration: configuration(),
+            frame_seconds: vec![0.25, 0.5],
+            events: vec![
+                ReplayEvent {
+                    frame: 0,
+                    input: ReplayInput::Action(MacroAction::UpdateSetting {
+                        name: "decay_rate".to_string(),
+                        value: serde_json::json!(0.25),
+                    }),
+                },
+                ReplayEvent {
+                    frame: 1,
+                    input: ReplayInput::WorldMousePress {
+                        world_x: -0.5,
+                        world_y: 0.25,
+                        mouse_button: 2,
+                    },
+                },
+            ],
+        };
+        let content = toml::to_string_pretty(&replay).unwrap();
+        assert_eq!(toml::from_str::<Replay>(&content).unwrap(), replay);
+        assert_eq!(replay.info().duration_seconds, 0.75);
+    }
+}
@@ -0,0 +1,217 @@
+//! Scene files: a workspace together with the simulation's exact state.
+//!
+//! A [`Workspace`] brings back how a simulation looks; a scene brings back
+//! where it was too, by adding a [`SimulationSnapshot`] of its live buffers
+//! and textures. Scenes are TOML files with the `.vizza` extension, saved
+//! wherever the user picks.
+//!
+//! Every scene records the version it was written in. Files from newer
+//! builds are refused rather than half read; when the layout changes, older
+//! files are brought up to date in [`migrate`] before they are parsed.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use wgpu::{Device, Queue, SurfaceConfiguration};
+
+use super::SimulationManager;
+use super::workspace::{Workspace, WorkspaceInfo};
+use crate::error::{AppError, AppResult, SimulationError};
+use crate::simulations::shared::SimulationSnapshot;
+
+/// Bump when a change would stop older files from parsing, and teach
+/// [`migrate`] the step up
+pub const SCENE_VERSION: u32 = 1;
+
+pub const SCENE_EXTENSION: &str = "vizza";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scene {
+    pub version: u32,
+    /// Settings, camera, color scheme, master effects, palette cycling and
+    /// canvas
+    pub workspace: Workspace,
+    pub snapshot: SimulationSnapshot,
+}
+
+impl Scene {
+    /// The running simulation as it is now, named after the file it's for
+    pub fn capture(
+        manager: &SimulationManager,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        path: &Path,
+    ) -> AppResult<Self> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Self {
+            version: SCENE_VERSION,
+            workspace: Workspace::capture_as(manager, &name)?,
+            snapshot: manager.simulation_snapshot(device, queue)?,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize scene: {}", e)))?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> AppResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            SimulationError::InvalidParameter(format!(
+                "Failed to read scene {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::parse(&content)
+            .map_err(|e| AppError::Unknown(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let mut table: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+        let version = table
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .ok_or("Not a scene file: it has no version")?;
+        if version > SCENE_VERSION as i64 {
+            return Err(format!(
+                "Scene version {} is newer than this build reads",
+                version
+            ));
+        }
+        migrate(&mut table, version);
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| e.to_string())
+    }
+
+    pub fn info(&self) -> WorkspaceInfo {
+        WorkspaceInfo {
+            name: self.workspace.name.clone(),
+            saved_at: self.workspace.saved_at.clone(),
+            simulation_type: self.snapshot.simulation_type.clone(),
+        }
+    }
+
+    /// Bring the scene back into the running simulation, which has to be the
+    /// one the scene was saved from
+    pub fn restore(
+        &self,
+        manager: &mut SimulationManager,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<()> {
+        let running = manager
+            .current_simulation
+            .as_ref()
+            .ok_or(SimulationError::NotRunning)?
+            .type_name();
+        if running != self.snapshot.simulation_type {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Scene is of {}, but {} is running",
+                self.snapshot.simulation_type, running
+            ))
+            .into());
+        }
+        self.workspace
+            .restore(manager, device, queue, surface_config)?;
+        manager.load_simulation_snapshot(&self.snapshot, device, queue)?;
+        Ok(())
+    }
+}
+
+/// Bring a scene written in `version` up to [`SCENE_VERSION`], one version
+/// at a time. The layout hasn't changed yet, so only the number moves.
+fn migrate(table: &mut toml::Table, version: i64) {
+    if version < SCENE_VERSION as i64 {
+        table.insert(
+            "version".to_string(),
+            toml::Value::Integer(SCENE_VERSION as i64),
+        );
+    }
+}
+
+/// `path` with the scene extension, added if it has none
+pub fn scene_path(path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.extension().is_some() {
+        path
+    } else {
+        path.with_extension(SCENE_EXTENSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::color_cycle::ColorCycle;
+    use crate::simulation::master_effects::MasterEffects;
+    use crate::simulation::settings_codec::SharedConfiguration;
+    use crate::simulations::shared::snapshot::{ResourceContents, SNAPSHOT_VERSION, encode_bytes};
+
+    fn scene() -> Scene {
+        Scene {
+            version: SCENE_VERSION,
+            workspace: Workspace {
+                name: "Coral".to_string(),
+                saved_at: "2025-03-09T14:05:07Z".to_string(),
+                configuration: SharedConfiguration {
+                    simulation_type: "gray_scott".to_string(),
+                    preset: None,
+                    settings: Some(serde_json::json!({ "feed_rate": 0.055 })),
+                    camera: None,
+                    color_scheme: None,
+                },
+                master_effects: MasterEffects::default(),
+                color_cycle: ColorCycle::default(),
+                canvas: None,
+            },
+            snapshot: SimulationSnapshot {
+                version: SNAPSHOT_VERSION,
+                simulation_type: "gray_scott".to_string(),
+                settings: serde_json::json!({ "feed_rate": 0.055 }),
+                camera: None,
+                resources: vec![ResourceContents::Buffer {
+                    size: 8,
+                    data: encode_bytes(&[3; 8]).unwrap(),
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let scene = scene();
+        let content = toml::to_string_pretty(&scene).unwrap();
+        let parsed = Scene::parse(&content).unwrap();
+        assert_eq!(parsed.workspace, scene.workspace);
+        assert_eq!(parsed.snapshot.resources, scene.snapshot.resources);
+        assert_eq!(parsed.info().simulation_type, "gray_scott");
+    }
+
+    #[test]
+    fn newer_and_unversioned_files_are_refused() {
+        let mut newer = scene();
+        newer.version = SCENE_VERSION + 1;
+        let content = toml::to_string_pretty(&newer).unwrap();
+        assert!(Scene::parse(&content).unwrap_err().contains("newer"));
+
+        let workspace = toml::to_string_pretty(&scene().workspace).unwrap();
+        assert!(Scene::parse(&workspace).is_err());
+    }
+
+    #[test]
+    fn paths_get_the_scene_extension() {
+        assert_eq!(scene_path("/tmp/coral"), PathBuf::from("/tmp/coral.vizza"));
+        assert_eq!(
+            scene_path("/tmp/coral.vizza"),
+            PathBuf::from("/tmp/coral.vizza")
+        );
+    }
+}
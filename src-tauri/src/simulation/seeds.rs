@@ -0,0 +1,65 @@
+//! Named seeds for worlds grown from random numbers.
+//!
+//! The stochastic simulations keep the seed their world starts from in one
+//! of their settings, so a preset already brings that world back exactly. A
+//! seed can also be given a name: any text that isn't a plain number is
+//! hashed into one, so "tidal pools" grows the same world on every machine
+//! and is easier to pass around than 2735913241.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seed {
+    /// The text the seed was made from, when it wasn't a number
+    pub name: Option<String>,
+    pub value: u32,
+}
+
+impl Seed {
+    /// A seed from a number, or from a name hashed into one. Names ignore
+    /// case and the spaces around them.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        if let Ok(value) = text.parse::<u32>() {
+            return Self { name: None, value };
+        }
+        Self {
+            name: Some(text.to_string()),
+            value: hash_name(&text.to_lowercase()),
+        }
+    }
+}
+
+/// 32-bit FNV-1a, chosen over the standard hasher because it can't change
+/// between Rust versions and take every named world with it
+fn hash_name(name: &str) -> u32 {
+    name.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_used_as_they_are() {
+        assert_eq!(
+            Seed::parse(" 1234 "),
+            Seed {
+                name: None,
+                value: 1234
+            }
+        );
+    }
+
+    #[test]
+    fn names_always_hash_to_the_same_seed() {
+        let seed = Seed::parse("Tidal Pools");
+        assert_eq!(seed.name.as_deref(), Some("Tidal Pools"));
+        assert_eq!(seed.value, Seed::parse("  tidal pools").value);
+        assert_ne!(seed.value, Seed::parse("tidal pool").value);
+        // Pinned, since saved names have to keep growing the same worlds
+        assert_eq!(hash_name("a"), 0xe40c_292c);
+    }
+}
@@ -0,0 +1,290 @@
+//! Compact encoding of a simulation configuration for sharing.
+//!
+//! A [`SharedConfiguration`] is serialized to JSON, deflated and base64url
+//! encoded, giving a string that survives URLs and chat clients. Share codes
+//! prefix that payload with a format version (`VZ1:...`) so older builds can
+//! tell a code from a newer release apart from a corrupt one. `vizza://` deep
+//! links carry a share code in their query.
+
+use base64::Engine;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+use crate::error::{AppError, AppResult};
+use crate::simulations::shared::ColorScheme;
+
+pub const DEEP_LINK_SCHEME: &str = "vizza";
+
+/// Bump when the payload stops being readable by older builds
+pub const SHARE_CODE_VERSION: u32 = 1;
+const SHARE_CODE_PREFIX: &str = "VZ";
+
+/// Refuse to inflate payloads beyond this, a settings blob is a few KB at most
+const MAX_DECODED_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraView {
+    pub position: [f32; 2],
+    pub zoom: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedColorScheme {
+    pub name: String,
+    #[serde(default)]
+    pub reversed: bool,
+    /// Base64 LUT bytes, included for custom schemes the recipient won't have
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl SharedColorScheme {
+    pub fn embed(color_scheme: &ColorScheme) -> String {
+        base64::engine::general_purpose::STANDARD.encode(color_scheme.clone().into_bytes())
+    }
+
+    pub fn embedded_color_scheme(&self) -> Option<AppResult<ColorScheme>> {
+        let data = self.data.as_ref()?;
+        Some(
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| AppError::Unknown(format!("Invalid color scheme data: {}", e)))
+                .and_then(|bytes| Ok(ColorScheme::from_bytes(self.name.clone(), &bytes)?)),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedConfiguration {
+    pub simulation_type: String,
+    /// Name of a preset to apply, used when `settings` is absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// Full settings, as returned by the simulation's `get_settings`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraView>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_scheme: Option<SharedColorScheme>,
+}
+
+impl SharedConfiguration {
+    pub fn encode(&self) -> AppResult<String> {
+        let json = serde_json::to_vec(self)?;
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&json)?;
+        let compressed = encoder.finish()?;
+        Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(compressed))
+    }
+
+    pub fn decode(payload: &str) -> AppResult<Self> {
+        let compressed = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(payload.trim())
+            .map_err(|e| AppError::Unknown(format!("Invalid configuration encoding: {}", e)))?;
+
+        let mut json = Vec::new();
+        DeflateDecoder::new(compressed.as_slice())
+            .take(MAX_DECODED_BYTES)
+            .read_to_end(&mut json)
+            .map_err(|e| AppError::Unknown(format!("Invalid configuration data: {}", e)))?;
+
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// A versioned, copy-pasteable code such as `VZ1:jZDBC...`
+    pub fn to_share_code(&self) -> AppResult<String> {
+        Ok(format!(
+            "{}{}:{}",
+            SHARE_CODE_PREFIX,
+            SHARE_CODE_VERSION,
+            self.encode()?
+        ))
+    }
+
+    pub fn from_share_code(code: &str) -> AppResult<Self> {
+        let invalid = || AppError::Unknown("Not a Vizza share code".to_string());
+        let (header, payload) = code.trim().split_once(':').ok_or_else(invalid)?;
+        let version: u32 = header
+            .strip_prefix(SHARE_CODE_PREFIX)
+            .and_then(|version| version.parse().ok())
+            .ok_or_else(invalid)?;
+
+        if version > SHARE_CODE_VERSION {
+            return Err(AppError::Unknown(format!(
+                "Share code version {} is from a newer version of Vizza (this one reads up to {})",
+                version, SHARE_CODE_VERSION
+            )));
+        }
+        Self::decode(payload)
+    }
+
+    /// `vizza://open?config=<share code>`
+    pub fn to_deep_link(&self) -> AppResult<String> {
+        Ok(format!(
+            "{}://open?config={}",
+            DEEP_LINK_SCHEME,
+            self.to_share_code()?
+        ))
+    }
+
+    /// Parse a deep link, either carrying an encoded configuration or the
+    /// readable `vizza://open?simulation=slime_mold&preset=Net` form
+    pub fn from_deep_link(url: &tauri::Url) -> AppResult<Self> {
+        if url.scheme() != DEEP_LINK_SCHEME {
+            return Err(AppError::Unknown(format!(
+                "Not a {} link: {}",
+                DEEP_LINK_SCHEME, url
+            )));
+        }
+
+        let query = |key: &str| {
+            url.query_pairs()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        if let Some(code) = query("config") {
+            return Self::from_share_code(&code);
+        }
+
+        let simulation_type = query("simulation").ok_or_else(|| {
+            AppError::Unknown(format!("Link does not name a simulation: {}", url))
+        })?;
+        Ok(Self {
+            simulation_type,
+            preset: query("preset"),
+            settings: None,
+            camera: None,
+            color_scheme: None,
+        })
+    }
+}
+
+/// Overlay `overrides` onto `base`, recursing into objects. Shared settings
+/// are merged over the running simulation's own so that codes from builds with
+/// fewer or extra settings still apply.
+pub fn merge_settings(base: &mut serde_json::Value, overrides: &serde_json::Value) {
+    match (base, overrides) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key) {
+                    Some(existing) => merge_settings(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> SharedConfiguration {
+        SharedConfiguration {
+            simulation_type: "gray_scott".to_string(),
+            preset: None,
+            settings: Some(serde_json::json!({ "feed_rate": 0.055, "kill_rate": 0.062 })),
+            camera: Some(CameraView {
+                position: [0.25, -0.5],
+                zoom: 2.0,
+            }),
+            color_scheme: Some(SharedColorScheme {
+                name: "MATPLOTLIB_viridis".to_string(),
+                reversed: true,
+                data: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_payload() {
+        let config = example();
+        let payload = config.encode().unwrap();
+        assert!(
+            payload
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+        assert_eq!(SharedConfiguration::decode(&payload).unwrap(), config);
+    }
+
+    #[test]
+    fn round_trips_through_deep_link() {
+        let config = example();
+        let link = tauri::Url::parse(&config.to_deep_link().unwrap()).unwrap();
+        assert_eq!(SharedConfiguration::from_deep_link(&link).unwrap(), config);
+    }
+
+    #[test]
+    fn parses_readable_links() {
+        let link =
+            tauri::Url::parse("vizza://open?simulation=slime_mold&preset=Net%20Maker").unwrap();
+        let config = SharedConfiguration::from_deep_link(&link).unwrap();
+        assert_eq!(config.simulation_type, "slime_mold");
+        assert_eq!(config.preset.as_deref(), Some("Net Maker"));
+
+        let other = tauri::Url::parse("https://example.com/?simulation=flow").unwrap();
+        assert!(SharedConfiguration::from_deep_link(&other).is_err());
+    }
+
+    #[test]
+    fn share_codes_are_versioned() {
+        let config = example();
+        let code = config.to_share_code().unwrap();
+        assert!(code.starts_with("VZ1:"));
+        assert_eq!(SharedConfiguration::from_share_code(&code).unwrap(), config);
+
+        let newer = code.replacen("VZ1:", "VZ99:", 1);
+        let error = SharedConfiguration::from_share_code(&newer).unwrap_err();
+        assert!(error.to_string().contains("newer version"));
+
+        assert!(SharedConfiguration::from_share_code("hello").is_err());
+        assert!(SharedConfiguration::from_share_code("XX1:abc").is_err());
+    }
+
+    #[test]
+    fn embedded_color_schemes_round_trip() {
+        let mut color_scheme = ColorScheme {
+            name: "Mine".to_string(),
+            red: [0; 256],
+            green: [128; 256],
+            blue: [255; 256],
+        };
+        color_scheme.red[10] = 42;
+        let shared = SharedColorScheme {
+            name: "Mine".to_string(),
+            reversed: false,
+            data: Some(SharedColorScheme::embed(&color_scheme)),
+        };
+        let decoded = shared.embedded_color_scheme().unwrap().unwrap();
+        assert_eq!(decoded.red, color_scheme.red);
+        assert_eq!(decoded.blue, color_scheme.blue);
+    }
+
+    #[test]
+    fn merged_settings_keep_unshared_fields() {
+        let mut base = serde_json::json!({ "a": 1, "nested": { "x": 1, "y": 2 } });
+        merge_settings(
+            &mut base,
+            &serde_json::json!({ "nested": { "y": 3 }, "from_newer_build": true }),
+        );
+        assert_eq!(
+            base,
+            serde_json::json!({ "a": 1, "nested": { "x": 1, "y": 3 }, "from_newer_build": true })
+        );
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(SharedConfiguration::decode("not base64!").is_err());
+        assert!(SharedConfiguration::decode("AAAA").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! How far apart two sets of a simulation's settings are, for finding the
+//! presets closest to whatever is on screen.
+//!
+//! Each setting contributes a difference between 0 and 1. Numbers with a
+//! range in the simulation's setting rules are compared across that range,
+//! other numbers relative to their size, and anything else is either the same
+//! or not. Nested settings and arrays, like a force matrix, count as one
+//! setting averaged over their parts. The distance is the root mean square of
+//! the differences, so 0 is identical and 1 differs in everything.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::preset_manager::AnyPresetManager;
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+
+/// Settings that change what surrounds a simulation rather than how it
+/// behaves
+const IGNORED_SETTINGS: &[&str] = &["background_layer"];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarPreset {
+    pub name: String,
+    /// 0 for identical settings, up to 1
+    pub distance: f64,
+    pub built_in: bool,
+}
+
+pub fn settings_distance(a: &Value, b: &Value, validator: &SettingValidator) -> f64 {
+    let (Value::Object(a), Value::Object(b)) = (a, b) else {
+        return difference(a, b, None);
+    };
+    let differences: Vec<f64> = a
+        .iter()
+        .filter(|(key, _)| !IGNORED_SETTINGS.contains(&key.as_str()))
+        .filter_map(|(key, value)| {
+            let other = b.get(key)?;
+            Some(difference(value, other, validator.rule(key)))
+        })
+        .collect();
+    if differences.is_empty() {
+        return 0.0;
+    }
+    let mean_square = differences.iter().map(|d| d * d).sum::<f64>() / differences.len() as f64;
+    mean_square.sqrt()
+}
+
+/// The presets of `presets` nearest to `settings`, closest first
+pub fn nearest_presets(
+    settings: &Value,
+    presets: &dyn AnyPresetManager,
+    validator: &SettingValidator,
+    limit: usize,
+) -> Vec<SimilarPreset> {
+    let mut similar: Vec<SimilarPreset> = presets
+        .get_preset_names()
+        .into_iter()
+        .filter_map(|name| {
+            let preset_settings = presets.preset_settings_json(&name)?;
+            Some(SimilarPreset {
+                distance: settings_distance(settings, &preset_settings, validator),
+                built_in: presets.is_built_in_preset(&name),
+                name,
+            })
+        })
+        .collect();
+    similar.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    similar.truncate(limit);
+    similar
+}
+
+fn difference(a: &Value, b: &Value, rule: Option<Rule>) -> f64 {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            let (Some(a), Some(b)) = (a.as_f64(), b.as_f64()) else {
+                return 1.0;
+            };
+            let span = match rule {
+                Some(Rule::Range { min, max }) => max - min,
+                Some(Rule::Count { min, max }) => (max - min) as f64,
+                _ => a.abs().max(b.abs()),
+            };
+            if span <= 0.0 {
+                return 0.0;
+            }
+            ((a - b).abs() / span).min(1.0)
+        }
+        (Value::Object(a), Value::Object(b)) => mean(
+            a.iter()
+                .filter_map(|(key, value)| Some(difference(value, b.get(key)?, None))),
+        ),
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            mean(a.iter().zip(b).map(|(a, b)| difference(a, b, None)))
+        }
+        (a, b) => {
+            if a == b {
+                0.0
+            } else {
+                1.0
+            }
+        }
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const RULES: SettingValidator =
+        SettingValidator::new(&[("feed_rate", Rule::Range { min: 0.0, max: 0.1 })], &[]);
+
+    #[test]
+    fn ranges_scale_the_difference() {
+        let a = json!({ "feed_rate": 0.05 });
+        let b = json!({ "feed_rate": 0.06 });
+        assert!((settings_distance(&a, &b, &RULES) - 0.1).abs() < 1e-9);
+        assert_eq!(settings_distance(&a, &a, &RULES), 0.0);
+
+        // Without a range, numbers are compared relative to their size
+        let c = json!({ "speed": 50.0 });
+        let d = json!({ "speed": 100.0 });
+        assert_eq!(settings_distance(&c, &d, &RULES), 0.5);
+    }
+
+    #[test]
+    fn closer_settings_rank_first() {
+        let current = json!({ "feed_rate": 0.05, "mode": "Spots", "matrix": [0.0, 1.0] });
+        let near = json!({ "feed_rate": 0.05, "mode": "Spots", "matrix": [0.0, 0.5] });
+        let far = json!({ "feed_rate": 0.0, "mode": "Waves", "matrix": [1.0, -1.0] });
+        assert!(
+            settings_distance(&current, &near, &RULES) < settings_distance(&current, &far, &RULES)
+        );
+        assert!(settings_distance(&current, &far, &RULES) <= 1.0);
+
+        // A background image says nothing about the simulation
+        let with_background = json!({ "feed_rate": 0.05, "background_layer": { "enabled": true } });
+        let without = json!({ "feed_rate": 0.05, "background_layer": { "enabled": false } });
+        assert_eq!(settings_distance(&with_background, &without, &RULES), 0.0);
+    }
+}
@@ -0,0 +1,139 @@
+//! Progressive supersampling of a paused view.
+//!
+//! While the simulation is paused and nothing about the view changes, each
+//! frame renders it again with the camera nudged by a fraction of a pixel,
+//! and the master bus averages the frames in an accumulation texture. Once
+//! enough samples are in, the still is frozen and the simulation isn't
+//! rendered again until the view changes. Any change to the settings or the
+//! camera starts the accumulation over.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{SimulationError, SimulationResult};
+
+pub const MAX_SAMPLES: u32 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Supersampling {
+    pub enabled: bool,
+    /// Frames averaged into the finished still
+    pub samples: u32,
+}
+
+impl Default for Supersampling {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            samples: 64,
+        }
+    }
+}
+
+impl Supersampling {
+    pub fn validate(&self) -> SimulationResult<()> {
+        if !(1..=MAX_SAMPLES).contains(&self.samples) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Supersampling needs between 1 and {} samples",
+                MAX_SAMPLES
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What the paused view looks like: the simulation's settings, and where its
+/// camera is and is heading
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewFingerprint {
+    pub settings: Value,
+    pub camera: [f32; 6],
+}
+
+#[derive(Debug, Default)]
+pub struct Supersampler {
+    config: Supersampling,
+    // View the accumulated samples belong to
+    view: Option<ViewFingerprint>,
+}
+
+impl Supersampler {
+    pub fn config(&self) -> &Supersampling {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: Supersampling) -> SimulationResult<()> {
+        config.validate()?;
+        self.config = config;
+        self.view = None;
+        Ok(())
+    }
+
+    /// Forget the accumulated view, as when the simulation moves on
+    pub fn clear(&mut self) {
+        self.view = None;
+    }
+
+    /// Note the view for this frame. Returns true when it differs from the
+    /// one the accumulated samples were taken of.
+    pub fn view_changed(&mut self, view: ViewFingerprint) -> bool {
+        if self.view.as_ref() == Some(&view) {
+            return false;
+        }
+        self.view = Some(view);
+        true
+    }
+}
+
+/// Sub-pixel offset for sample `index`, within half a pixel of the center.
+/// The first sample is the unjittered view; the rest follow the 2,3 Halton
+/// sequence, which covers the pixel evenly at any sample count.
+pub fn jitter(index: u32) -> [f32; 2] {
+    if index == 0 {
+        return [0.0, 0.0];
+    }
+    [halton(index, 2) - 0.5, halton(index, 3) - 0.5]
+}
+
+fn halton(mut index: u32, base: u32) -> f32 {
+    let mut result = 0.0;
+    let mut fraction = 1.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_stays_within_the_pixel() {
+        assert_eq!(jitter(0), [0.0, 0.0]);
+        assert_eq!(jitter(1), [0.0, 1.0 / 3.0 - 0.5]);
+        assert_eq!(jitter(2), [-0.25, 2.0 / 3.0 - 0.5]);
+        assert!((1..MAX_SAMPLES).all(|index| {
+            jitter(index)
+                .iter()
+                .all(|offset| (-0.5..0.5).contains(offset))
+        }));
+    }
+
+    #[test]
+    fn restarts_when_the_view_changes() {
+        let mut supersampler = Supersampler::default();
+        let view = |zoom| ViewFingerprint {
+            settings: serde_json::json!({ "speed": 1.0 }),
+            camera: [0.0, 0.0, zoom, 0.0, 0.0, zoom],
+        };
+        assert!(supersampler.view_changed(view(1.0)));
+        assert!(!supersampler.view_changed(view(1.0)));
+        assert!(supersampler.view_changed(view(2.0)));
+        supersampler.clear();
+        assert!(supersampler.view_changed(view(2.0)));
+    }
+}
@@ -0,0 +1,137 @@
+//! Gliding from one set of settings to another instead of jumping.
+//!
+//! When a preset is applied with a transition, everything it changes takes
+//! effect at once except the settings the simulation's rules give a range.
+//! Those start back at the values they had before and ease over to the
+//! preset's values. Counts, switches and choices have no in-between, and
+//! many of them rebuild buffers, so they snap as they always did.
+
+use serde_json::Value;
+
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+
+/// Longest transition a preset may ask for, in seconds
+pub const MAX_TRANSITION_SECONDS: f32 = 60.0;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Glide {
+    setting: String,
+    from: f64,
+    to: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingTransition {
+    glides: Vec<Glide>,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl SettingTransition {
+    /// A transition over `duration` seconds for the ranged settings that
+    /// differ between `from` and `to`, or `None` when none of them do
+    pub fn new(
+        from: &Value,
+        to: &Value,
+        validator: &SettingValidator,
+        duration: f32,
+    ) -> Option<Self> {
+        let (Value::Object(from_fields), Value::Object(to_fields)) = (from, to) else {
+            return None;
+        };
+        let glides: Vec<Glide> = to_fields
+            .iter()
+            .filter(|(setting, _)| matches!(validator.rule(setting), Some(Rule::Range { .. })))
+            .filter_map(|(setting, to)| {
+                let from = from_fields.get(setting)?.as_f64()?;
+                let to = to.as_f64()?;
+                (from != to).then(|| Glide {
+                    setting: setting.clone(),
+                    from,
+                    to,
+                })
+            })
+            .collect();
+        if glides.is_empty() || duration <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            glides,
+            duration,
+            elapsed: 0.0,
+        })
+    }
+
+    /// Move on by `delta_time` seconds. Returns the settings to update.
+    pub fn advance(&mut self, delta_time: f32) -> Vec<(String, f64)> {
+        self.elapsed = (self.elapsed + delta_time).min(self.duration);
+        let t = (self.elapsed / self.duration) as f64;
+        // Ease in and out, so the change doesn't start or stop with a jolt
+        let eased = t * t * (3.0 - 2.0 * t);
+        self.glides
+            .iter()
+            .map(|glide| {
+                let value = if self.is_finished() {
+                    glide.to
+                } else {
+                    glide.from + (glide.to - glide.from) * eased
+                };
+                (glide.setting.clone(), value)
+            })
+            .collect()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const RULES: SettingValidator = SettingValidator::new(
+        &[
+            (
+                "speed",
+                Rule::Range {
+                    min: 0.0,
+                    max: 10.0,
+                },
+            ),
+            ("decay", Rule::Range { min: 0.0, max: 1.0 }),
+            ("count", Rule::Count { min: 1, max: 100 }),
+        ],
+        &[],
+    );
+
+    #[test]
+    fn only_ranged_settings_that_change_glide() {
+        let from = json!({ "speed": 1.0, "decay": 0.5, "count": 10, "shape": "Circle" });
+        let to = json!({ "speed": 5.0, "decay": 0.5, "count": 20, "shape": "Square" });
+        let mut transition = SettingTransition::new(&from, &to, &RULES, 1.0).unwrap();
+        let updates = transition.advance(0.0);
+        assert_eq!(updates, vec![("speed".to_string(), 1.0)]);
+
+        assert!(SettingTransition::new(&from, &from, &RULES, 1.0).is_none());
+        assert!(SettingTransition::new(&from, &to, &RULES, 0.0).is_none());
+    }
+
+    #[test]
+    fn a_transition_eases_from_start_to_end() {
+        let from = json!({ "speed": 0.0 });
+        let to = json!({ "speed": 8.0 });
+        let mut transition = SettingTransition::new(&from, &to, &RULES, 2.0).unwrap();
+
+        let quarter = transition.advance(0.5)[0].1;
+        assert!(quarter > 0.0 && quarter < 2.0, "{}", quarter);
+        let half = transition.advance(0.5)[0].1;
+        assert!((half - 4.0).abs() < 1e-9);
+        assert!(!transition.is_finished());
+
+        // Overshooting the end lands exactly on the target
+        assert_eq!(transition.advance(5.0)[0].1, 8.0);
+        assert!(transition.is_finished());
+    }
+}
@@ -0,0 +1,256 @@
+//! Hot reloading of presets and color schemes edited outside the app.
+//!
+//! The user presets folder of every simulation and the custom LUT folder are
+//! polled for files being added, removed or rewritten. A changed presets
+//! folder is read back into its preset manager, and a changed LUT folder
+//! refreshes the color scheme list and, if the running simulation's own
+//! color scheme was the file that changed, re-applies it. Either way the
+//! frontend is told with an event, so lists update without a restart.
+//!
+//! Polling a handful of small folders once a second is cheap, and it behaves
+//! the same on every platform and file system.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::SimulationManager;
+use crate::simulations::shared::ColorSchemeManager;
+
+/// Emitted with a [`PresetsChanged`] when a simulation's preset files change
+pub const PRESETS_CHANGED_EVENT: &str = "presets-changed";
+
+/// Emitted with a [`ColorSchemesChanged`] when the custom LUT files change
+pub const COLOR_SCHEMES_CHANGED_EVENT: &str = "color-schemes-changed";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetsChanged {
+    pub simulation_type: String,
+    pub presets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorSchemesChanged {
+    /// Names of the color schemes whose files were added, changed or removed
+    pub changed: Vec<String>,
+    pub color_schemes: Vec<String>,
+}
+
+/// The files with one extension in a folder, with enough about each to notice
+/// it being rewritten
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct FolderSnapshot {
+    files: BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl FolderSnapshot {
+    /// A missing or unreadable folder reads as empty
+    fn read(dir: &Path, extension: &str) -> Self {
+        let files = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some(extension))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((entry.path(), (metadata.modified().ok(), metadata.len())))
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// Files added, removed or rewritten since `earlier`
+    fn changed_since(&self, earlier: &Self) -> Vec<PathBuf> {
+        let added_or_rewritten = self
+            .files
+            .iter()
+            .filter(|(path, file)| earlier.files.get(*path) != Some(file))
+            .map(|(path, _)| path.clone());
+        let removed = earlier
+            .files
+            .keys()
+            .filter(|path| !self.files.contains_key(*path))
+            .cloned();
+        added_or_rewritten.chain(removed).collect()
+    }
+}
+
+struct WatchedFolder {
+    dir: PathBuf,
+    extension: &'static str,
+    snapshot: FolderSnapshot,
+}
+
+impl WatchedFolder {
+    fn new(dir: PathBuf, extension: &'static str) -> Self {
+        let snapshot = FolderSnapshot::read(&dir, extension);
+        Self {
+            dir,
+            extension,
+            snapshot,
+        }
+    }
+
+    /// What changed since the last poll. The new state is recorded before
+    /// anything is reloaded, so a file still being written shows up again on
+    /// the next poll.
+    fn poll(&mut self) -> Vec<PathBuf> {
+        let current = FolderSnapshot::read(&self.dir, self.extension);
+        let changed = current.changed_since(&self.snapshot);
+        self.snapshot = current;
+        changed
+    }
+}
+
+/// Watch the preset and LUT folders for as long as the app runs
+pub async fn watch(app: AppHandle) {
+    let manager = app
+        .state::<Arc<tokio::sync::Mutex<SimulationManager>>>()
+        .inner()
+        .clone();
+    let mut preset_folders: Vec<(String, WatchedFolder)> = manager
+        .lock()
+        .await
+        .preset_manager
+        .user_preset_dirs()
+        .into_iter()
+        .map(|(simulation_type, dir)| (simulation_type, WatchedFolder::new(dir, "toml")))
+        .collect();
+    let mut lut_folder = match ColorSchemeManager::lut_dir() {
+        Ok(dir) => WatchedFolder::new(dir, "lut"),
+        Err(e) => {
+            tracing::warn!("Not watching custom color schemes: {}", e);
+            return;
+        }
+    };
+
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+
+        for (simulation_type, folder) in &mut preset_folders {
+            if !folder.poll().is_empty() {
+                reload_presets(&app, &manager, simulation_type).await;
+            }
+        }
+
+        let changed = lut_folder.poll();
+        if !changed.is_empty() {
+            let changed = changed
+                .iter()
+                .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+                .collect();
+            reload_color_schemes(&app, &manager, changed).await;
+        }
+    }
+}
+
+async fn reload_presets(
+    app: &AppHandle,
+    manager: &tokio::sync::Mutex<SimulationManager>,
+    simulation_type: &str,
+) {
+    let presets = {
+        let mut sim_manager = manager.lock().await;
+        if let Err(e) = sim_manager
+            .preset_manager
+            .reload_user_presets(simulation_type)
+        {
+            tracing::warn!("Could not reload {} presets: {}", simulation_type, e);
+            return;
+        }
+        sim_manager
+            .preset_manager
+            .get_manager(simulation_type)
+            .map(|presets| presets.get_preset_names())
+            .unwrap_or_default()
+    };
+
+    let payload = PresetsChanged {
+        simulation_type: simulation_type.to_string(),
+        presets,
+    };
+    if let Err(e) = app.emit(PRESETS_CHANGED_EVENT, &payload) {
+        tracing::error!("Failed to emit {} event: {}", PRESETS_CHANGED_EVENT, e);
+    }
+}
+
+async fn reload_color_schemes(
+    app: &AppHandle,
+    manager: &tokio::sync::Mutex<SimulationManager>,
+    changed: Vec<String>,
+) {
+    let (device, queue) = {
+        let gpu_context = app.state::<Arc<tokio::sync::Mutex<crate::GpuContext>>>();
+        let gpu_ctx = gpu_context.lock().await;
+        (gpu_ctx.device.clone(), gpu_ctx.queue.clone())
+    };
+
+    let color_schemes = {
+        let mut sim_manager = manager.lock().await;
+        // Custom color schemes are read from disk whenever they're applied,
+        // so applying the current one again picks up the new file
+        if let Some((current, _)) = sim_manager.current_color_scheme()
+            && changed.contains(&current)
+            && let Err(e) = sim_manager.apply_color_scheme(&current, &device, &queue)
+        {
+            tracing::warn!("Could not reload color scheme '{}': {}", current, e);
+        }
+        sim_manager.get_available_color_schemes()
+    };
+    tracing::info!("Reloaded custom color schemes: {}", changed.join(", "));
+
+    let payload = ColorSchemesChanged {
+        changed,
+        color_schemes,
+    };
+    if let Err(e) = app.emit(COLOR_SCHEMES_CHANGED_EVENT, &payload) {
+        tracing::error!(
+            "Failed to emit {} event: {}",
+            COLOR_SCHEMES_CHANGED_EVENT,
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn added_rewritten_and_removed_files_are_noticed() {
+        let dir = std::env::temp_dir().join(format!("vizza-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let preset = dir.join("Calm.toml");
+        std::fs::write(&preset, "name = \"Calm\"").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a preset").unwrap();
+
+        let mut folder = WatchedFolder::new(dir.clone(), "toml");
+        assert!(folder.poll().is_empty());
+
+        let added = dir.join("Stormy.toml");
+        std::fs::write(&added, "name = \"Stormy\"").unwrap();
+        assert_eq!(folder.poll(), vec![added.clone()]);
+
+        std::fs::write(&preset, "name = \"Calm\"\nspeed = 2").unwrap();
+        assert_eq!(folder.poll(), vec![preset.clone()]);
+
+        std::fs::remove_file(&added).unwrap();
+        assert_eq!(folder.poll(), vec![added]);
+        assert!(folder.poll().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_missing_folder_is_empty() {
+        let snapshot = FolderSnapshot::read(Path::new("/no/such/vizza/folder"), "lut");
+        assert_eq!(snapshot, FolderSnapshot::default());
+    }
+}
@@ -0,0 +1,161 @@
+//! Periodic health checks of the running simulation.
+//!
+//! Aggressive settings can push a simulation into NaNs, a single collapsed
+//! point or runaway velocities, and from then on it just renders a blank or
+//! frozen frame. Every few seconds the watchdog reduces the simulation's
+//! [`HealthProbe`](crate::simulations::shared::HealthProbe)s on the GPU. A
+//! problem that persists is reported to the frontend and, when enabled in the
+//! app settings, the simulation is asked to recover.
+
+use serde::Serialize;
+use std::sync::Arc;
+use wgpu::{Device, Queue};
+
+use crate::commands::AppSettings;
+use crate::simulations::shared::HealthIssue;
+use crate::simulations::shared::health::HealthReducer;
+use crate::simulations::traits::{Simulation, SimulationType};
+
+/// Emitted with a [`HealthWarning`] when a check finds a problem
+pub const HEALTH_WARNING_EVENT: &str = "simulation-health-warning";
+
+const CHECK_INTERVAL_SECONDS: f32 = 2.0;
+
+/// Checks in a row a problem has to show up in before it's reported, so the
+/// first frames after a reset or a big setting change don't trip it
+const CONFIRMING_CHECKS: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    pub enabled: bool,
+    pub auto_recover: bool,
+}
+
+impl WatchdogConfig {
+    pub fn from_app_settings(settings: &AppSettings) -> Self {
+        Self {
+            enabled: settings.watchdog_enabled,
+            auto_recover: settings.watchdog_auto_recover,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthWarning {
+    pub simulation_type: String,
+    pub issues: Vec<HealthIssue>,
+    /// Whether the simulation was reset or had settings clamped
+    pub recovered: bool,
+}
+
+#[derive(Debug)]
+pub struct Watchdog {
+    config: WatchdogConfig,
+    since_check: f32,
+    strikes: u32,
+    // Created on the first check, so a disabled watchdog costs nothing
+    reducer: Option<HealthReducer>,
+    warnings: Vec<HealthWarning>,
+}
+
+impl Watchdog {
+    pub fn new(config: WatchdogConfig) -> Self {
+        Self {
+            config,
+            since_check: 0.0,
+            strikes: 0,
+            reducer: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: WatchdogConfig) {
+        self.config = config;
+        self.reset();
+    }
+
+    /// Forget problems seen so far, e.g. when a new simulation starts
+    pub fn reset(&mut self) {
+        self.since_check = 0.0;
+        self.strikes = 0;
+    }
+
+    /// Check `simulation` if a check is due, and recover it if configured to.
+    /// Returns whether a problem was reported.
+    pub fn update(
+        &mut self,
+        simulation: &mut SimulationType,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        delta_time: f32,
+    ) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        self.since_check += delta_time;
+        if self.since_check < CHECK_INTERVAL_SECONDS {
+            return false;
+        }
+        self.since_check = 0.0;
+
+        let issues = self.inspect(simulation, device, queue);
+        if issues.is_empty() {
+            self.strikes = 0;
+            return false;
+        }
+        self.strikes += 1;
+        if self.strikes < CONFIRMING_CHECKS {
+            return false;
+        }
+        self.strikes = 0;
+
+        tracing::warn!(
+            "{} simulation is unhealthy: {:?}",
+            simulation.type_name(),
+            issues
+        );
+        let recovered = self.config.auto_recover
+            && match simulation.recover_from_instability(&issues, device, queue) {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::error!("Failed to recover simulation: {}", e);
+                    false
+                }
+            };
+        self.warnings.push(HealthWarning {
+            simulation_type: simulation.type_name().to_string(),
+            issues,
+            recovered,
+        });
+        true
+    }
+
+    /// Drain the warnings raised since the last call
+    pub fn take_warnings(&mut self) -> Vec<HealthWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    fn inspect(
+        &mut self,
+        simulation: &SimulationType,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> Vec<HealthIssue> {
+        let probes = simulation.health_probes();
+        if probes.is_empty() {
+            return Vec::new();
+        }
+
+        let reducer = self
+            .reducer
+            .get_or_insert_with(|| HealthReducer::new(device));
+        let mut issues = Vec::new();
+        for probe in &probes {
+            match reducer.measure(device, queue, probe) {
+                Ok(stats) => issues.extend(stats.issues(probe.label, probe.check)),
+                Err(e) => tracing::debug!("Skipping health check of {}: {}", probe.label, e),
+            }
+        }
+        issues
+    }
+}
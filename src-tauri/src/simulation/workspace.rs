@@ -0,0 +1,219 @@
+//! Named workspaces: everything that makes up the current look, in one file.
+//!
+//! A preset only holds a simulation's settings. A workspace adds the camera
+//! and color scheme (through the same [`SharedConfiguration`] share codes
+//! use, so custom schemes travel with it), the master effects, palette
+//! cycling and the output canvas. Background layers are part of the
+//! simulation settings and come along with them.
+//!
+//! Workspaces are TOML files in the `workspaces` folder of the settings
+//! directory, named after the workspace.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use wgpu::{Device, Queue, SurfaceConfiguration};
+
+use super::SimulationManager;
+use super::canvas::Canvas;
+use super::color_cycle::ColorCycle;
+use super::master_effects::MasterEffects;
+use super::settings_codec::SharedConfiguration;
+use crate::commands::get_settings_dir;
+use crate::error::{AppError, AppResult, SimulationError};
+
+const MAX_NAME_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    /// RFC 3339, UTC
+    pub saved_at: String,
+    pub configuration: SharedConfiguration,
+    #[serde(default)]
+    pub master_effects: MasterEffects,
+    #[serde(default)]
+    pub color_cycle: ColorCycle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canvas: Option<Canvas>,
+}
+
+/// A workspace as listed for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceInfo {
+    pub name: String,
+    pub saved_at: String,
+    pub simulation_type: String,
+}
+
+impl Workspace {
+    /// The running simulation's look, under `name`
+    pub fn capture(manager: &SimulationManager, name: &str) -> AppResult<Self> {
+        validate_name(name)?;
+        Self::capture_as(manager, name)
+    }
+
+    /// The running simulation's look under a name that isn't a workspace
+    /// file's, such as a scene file's
+    pub fn capture_as(manager: &SimulationManager, name: &str) -> AppResult<Self> {
+        Ok(Self {
+            name: name.to_string(),
+            saved_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            configuration: manager.shared_configuration()?,
+            master_effects: manager.master_bus.effects().clone(),
+            color_cycle: *manager.color_cycler.cycle(),
+            canvas: manager.master_bus.canvas(),
+        })
+    }
+
+    /// Write the workspace, replacing one of the same name
+    pub fn save(&self) -> AppResult<()> {
+        let path = workspace_path(&self.name)?;
+        std::fs::create_dir_all(workspaces_dir())?;
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| AppError::Unknown(format!("Failed to serialize workspace: {}", e)))?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn load(name: &str) -> AppResult<Self> {
+        let path = workspace_path(name)?;
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            SimulationError::InvalidParameter(format!("Failed to read workspace '{}': {}", name, e))
+        })?;
+        toml::from_str(&content)
+            .map_err(|e| AppError::Unknown(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+
+    pub fn delete(name: &str) -> AppResult<()> {
+        std::fs::remove_file(workspace_path(name)?)?;
+        Ok(())
+    }
+
+    /// Every saved workspace, by name. Files that don't parse are skipped.
+    pub fn list() -> Vec<WorkspaceInfo> {
+        let Ok(files) = std::fs::read_dir(workspaces_dir()) else {
+            return vec![];
+        };
+        let mut workspaces: Vec<WorkspaceInfo> = files
+            .filter_map(|file| file.ok())
+            .map(|file| file.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("toml"))
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(&path).ok()?;
+                match toml::from_str::<Self>(&content) {
+                    Ok(workspace) => Some(WorkspaceInfo {
+                        name: workspace.name,
+                        saved_at: workspace.saved_at,
+                        simulation_type: workspace.configuration.simulation_type,
+                    }),
+                    Err(e) => {
+                        tracing::warn!("Skipping workspace {}: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        workspaces.sort_by_key(|workspace| workspace.name.to_lowercase());
+        workspaces
+    }
+
+    /// Bring the workspace back. The effects, cycling and canvas apply
+    /// straight away; the simulation configuration is applied if its
+    /// simulation is running, and otherwise left as the pending shared
+    /// configuration for the frontend to apply once it has started the right
+    /// simulation. Returns whether the configuration was applied.
+    pub fn restore(
+        &self,
+        manager: &mut SimulationManager,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+    ) -> AppResult<bool> {
+        manager.set_master_effects(self.master_effects.clone(), device, queue, surface_config)?;
+        manager.set_color_cycle(self.color_cycle)?;
+        manager.set_canvas(self.canvas, device, queue, surface_config)?;
+
+        let is_running = manager
+            .current_simulation
+            .as_ref()
+            .is_some_and(|simulation| simulation.type_name() == self.configuration.simulation_type);
+        if is_running {
+            manager.apply_shared_configuration(&self.configuration, device, queue)?;
+        } else {
+            manager.pending_shared_configuration = Some(self.configuration.clone());
+        }
+        Ok(is_running)
+    }
+}
+
+fn workspaces_dir() -> PathBuf {
+    get_settings_dir().join("workspaces")
+}
+
+/// The name is also the file name, so it's kept to characters that are safe
+/// in one on every platform
+fn validate_name(name: &str) -> AppResult<()> {
+    let valid = !name.trim().is_empty()
+        && name.len() <= MAX_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+    if !valid {
+        return Err(SimulationError::InvalidParameter(format!(
+            "Workspace names need 1 to {} letters, digits, spaces, '-' or '_', not '{}'",
+            MAX_NAME_LENGTH, name
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+fn workspace_path(name: &str) -> AppResult<PathBuf> {
+    validate_name(name)?;
+    Ok(workspaces_dir().join(format!("{}.toml", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_stay_inside_the_workspaces_folder() {
+        assert!(workspace_path("Night drive 2").is_ok());
+        assert!(workspace_path("../settings").is_err());
+        assert!(workspace_path("a/b").is_err());
+        assert!(workspace_path("   ").is_err());
+        assert!(workspace_path(&"x".repeat(MAX_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let workspace = Workspace {
+            name: "Bloom".to_string(),
+            saved_at: "2025-03-09T14:05:07Z".to_string(),
+            configuration: SharedConfiguration {
+                simulation_type: "gray_scott".to_string(),
+                preset: None,
+                settings: Some(serde_json::json!({ "feed_rate": 0.055 })),
+                camera: None,
+                color_scheme: None,
+            },
+            master_effects: MasterEffects::default(),
+            color_cycle: ColorCycle::default(),
+            canvas: Some(Canvas {
+                width: 1080,
+                height: 1920,
+            }),
+        };
+        let content = toml::to_string_pretty(&workspace).unwrap();
+        assert_eq!(toml::from_str::<Workspace>(&content).unwrap(), workspace);
+
+        // Workspaces from before a field existed still load
+        let minimal =
+            "name = \"Old\"\nsaved_at = \"\"\n[configuration]\nsimulation_type = \"flow\"\n";
+        let old: Workspace = toml::from_str(minimal).unwrap();
+        assert_eq!(old.canvas, None);
+        assert_eq!(old.color_cycle, ColorCycle::default());
+    }
+}
@@ -0,0 +1,274 @@
+//! # Flock
+//!
+//! Reynolds' boids: each boid sees only the flockmates within
+//! `perception_radius` and its field of view, and steers by three rules at
+//! once. Separation turns it away from any flying too close, alignment
+//! turns it to fly the way they fly, and cohesion turns it toward the middle
+//! of them. Each rule asks for a velocity at full speed and steers toward
+//! it with at most `max_force`, and the three are weighed against each
+//! other by their weights.
+//!
+//! Finding the flockmates is what costs. The box is cut into cells at least
+//! `perception_radius` across, each boid is filed into its cell every
+//! frame, and a boid only looks in its own cell and the eight around it.
+//!
+//! The boids are laid out here and uploaded; from then on `flock.wgsl` runs
+//! them on the GPU. A copy of the steering step is kept here for the tests.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use std::f32::consts::TAU;
+
+#[cfg(test)]
+use super::settings::Edges;
+use super::settings::Spawn;
+
+/// Boids filed in one grid cell at most; any more in a crowded cell go
+/// unseen by their neighbors for that frame
+pub const CELL_CAPACITY: u32 = 32;
+
+/// Most cells in the grid. Past it cells grow beyond the perception radius
+/// rather than the grid growing further.
+pub const MAX_GRID_CELLS: u32 = 65_536;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct Boid {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    /// Flockmates it saw last frame, for coloring
+    pub neighbors: f32,
+    pub _pad: f32,
+}
+
+impl Boid {
+    pub fn new(position: [f32; 2], velocity: [f32; 2]) -> Self {
+        Self {
+            position,
+            velocity,
+            neighbors: 0.0,
+            _pad: 0.0,
+        }
+    }
+}
+
+/// Cells across and up a box `2 * half_width` wide and 2 high, with every
+/// cell at least `perception_radius` across so a boid's flockmates are all
+/// in the cells next to its own
+pub fn grid_size(perception_radius: f32, half_width: f32) -> (u32, u32) {
+    let radius = perception_radius.max(1e-3);
+    let mut width = ((2.0 * half_width / radius).floor() as u32).max(1);
+    let mut height = ((2.0 / radius).floor() as u32).max(1);
+    if width * height > MAX_GRID_CELLS {
+        let shrink = ((width * height) as f32 / MAX_GRID_CELLS as f32).sqrt();
+        width = ((width as f32 / shrink).floor() as u32).max(1);
+        height = ((height as f32 / shrink).floor() as u32).max(1);
+    }
+    (width, height)
+}
+
+/// `count` boids in a box `2 * half_width` wide and 2 high, flying between
+/// `min_speed` and `max_speed`
+pub fn seed_boids(
+    count: usize,
+    spawn: Spawn,
+    half_width: f32,
+    (min_speed, max_speed): (f32, f32),
+    rng: &mut impl Rng,
+) -> Vec<Boid> {
+    let max_speed = max_speed.max(min_speed);
+    let shared_heading = rng.random_range(0.0..TAU);
+    let ring_radius = 0.6 * half_width.min(1.0);
+    (0..count)
+        .map(|_| {
+            let speed = if max_speed > min_speed {
+                rng.random_range(min_speed..max_speed)
+            } else {
+                max_speed
+            };
+            let (position, heading) = match spawn {
+                Spawn::Scattered => (
+                    [
+                        rng.random_range(-half_width..half_width),
+                        rng.random_range(-1.0..1.0),
+                    ],
+                    rng.random_range(0.0..TAU),
+                ),
+                Spawn::Flock => {
+                    // Evenly over a disc, not bunched at its middle
+                    let radius = 0.15 * rng.random::<f32>().sqrt();
+                    let angle = rng.random_range(0.0..TAU);
+                    (
+                        [radius * angle.cos(), radius * angle.sin()],
+                        shared_heading + rng.random_range(-0.2..0.2),
+                    )
+                }
+                Spawn::Ring => {
+                    let angle = rng.random_range(0.0..TAU);
+                    let radius = ring_radius + rng.random_range(-0.03..0.03);
+                    // Counterclockwise round the ring
+                    (
+                        [radius * angle.cos(), radius * angle.sin()],
+                        angle + std::f32::consts::FRAC_PI_2,
+                    )
+                }
+            };
+            Boid::new(position, [speed * heading.cos(), speed * heading.sin()])
+        })
+        .collect()
+}
+
+/// How boids steer, from the settings of the same names, with the field of
+/// view as the cosine of its half angle
+#[cfg(test)]
+pub struct Flocking {
+    pub min_speed: f32,
+    pub max_speed: f32,
+    pub max_force: f32,
+    pub perception_radius: f32,
+    pub separation_radius: f32,
+    pub view_cos: f32,
+    pub separation_weight: f32,
+    pub alignment_weight: f32,
+    pub cohesion_weight: f32,
+    pub edges: Edges,
+}
+
+#[cfg(test)]
+fn length(v: [f32; 2]) -> f32 {
+    v[0].hypot(v[1])
+}
+
+/// The steering force that turns `velocity` toward full speed along
+/// `direction`
+#[cfg(test)]
+fn steer_toward(direction: [f32; 2], velocity: [f32; 2], flocking: &Flocking) -> [f32; 2] {
+    let len = length(direction);
+    if len < 1e-6 {
+        return [0.0; 2];
+    }
+    let desired = direction.map(|d| d / len * flocking.max_speed);
+    let steer = [desired[0] - velocity[0], desired[1] - velocity[1]];
+    let force = length(steer);
+    if force > flocking.max_force {
+        steer.map(|s| s / force * flocking.max_force)
+    } else {
+        steer
+    }
+}
+
+/// Boid `index` of `boids` after `dt` seconds flying among the rest, as
+/// `update` in `flock.wgsl` does without the grid or the cursor
+#[cfg(test)]
+pub fn step(boids: &[Boid], index: usize, flocking: &Flocking, dt: f32, half_width: f32) -> Boid {
+    let boid = boids[index];
+    let wrap = flocking.edges == Edges::Wrap;
+    let mut seen = 0u32;
+    let mut velocity_sum = [0.0f32; 2];
+    let mut offset_sum = [0.0f32; 2];
+    let mut separation = [0.0f32; 2];
+    for (other_index, other) in boids.iter().enumerate() {
+        if other_index == index {
+            continue;
+        }
+        let mut offset = [
+            other.position[0] - boid.position[0],
+            other.position[1] - boid.position[1],
+        ];
+        if wrap {
+            offset[0] -= 2.0 * half_width * (offset[0] / (2.0 * half_width)).round();
+            offset[1] -= 2.0 * (offset[1] / 2.0).round();
+        }
+        let distance = length(offset);
+        if distance > flocking.perception_radius || distance < 1e-6 {
+            continue;
+        }
+        let speed = length(boid.velocity).max(1e-6);
+        let facing =
+            (offset[0] * boid.velocity[0] + offset[1] * boid.velocity[1]) / (distance * speed);
+        if facing < flocking.view_cos {
+            continue;
+        }
+        seen += 1;
+        velocity_sum = [
+            velocity_sum[0] + other.velocity[0],
+            velocity_sum[1] + other.velocity[1],
+        ];
+        offset_sum = [offset_sum[0] + offset[0], offset_sum[1] + offset[1]];
+        if distance < flocking.separation_radius {
+            let push = 1.0 - distance / flocking.separation_radius;
+            separation = [
+                separation[0] - offset[0] / distance * push,
+                separation[1] - offset[1] / distance * push,
+            ];
+        }
+    }
+
+    let mut acceleration = [0.0f32; 2];
+    let mut add = |force: [f32; 2], weight: f32| {
+        acceleration[0] += force[0] * weight;
+        acceleration[1] += force[1] * weight;
+    };
+    if seen > 0 {
+        add(
+            steer_toward(separation, boid.velocity, flocking),
+            flocking.separation_weight,
+        );
+        add(
+            steer_toward(velocity_sum, boid.velocity, flocking),
+            flocking.alignment_weight,
+        );
+        add(
+            steer_toward(offset_sum, boid.velocity, flocking),
+            flocking.cohesion_weight,
+        );
+    }
+    if !wrap {
+        let margin = flocking.perception_radius.max(0.05);
+        let bounds = [half_width, 1.0];
+        let mut away = [0.0f32; 2];
+        for axis in 0..2 {
+            let inside = bounds[axis] - boid.position[axis].abs();
+            if inside < margin {
+                away[axis] = -boid.position[axis].signum() * (1.0 - inside / margin);
+            }
+        }
+        add(steer_toward(away, boid.velocity, flocking), 2.0);
+    }
+
+    let mut velocity = [
+        boid.velocity[0] + acceleration[0] * dt,
+        boid.velocity[1] + acceleration[1] * dt,
+    ];
+    let speed = length(velocity);
+    if speed < 1e-6 {
+        velocity = [flocking.min_speed, 0.0];
+    } else {
+        let clamped = speed.clamp(flocking.min_speed, flocking.max_speed);
+        velocity = velocity.map(|v| v / speed * clamped);
+    }
+
+    let mut position = [
+        boid.position[0] + velocity[0] * dt,
+        boid.position[1] + velocity[1] * dt,
+    ];
+    let bounds = [half_width, 1.0];
+    for axis in 0..2 {
+        let bound = bounds[axis];
+        if position[axis].abs() > bound {
+            if wrap {
+                position[axis] -= 2.0 * bound * position[axis].signum();
+            } else {
+                position[axis] = bound.copysign(position[axis]);
+                velocity[axis] = -velocity[axis];
+            }
+        }
+    }
+
+    Boid {
+        position,
+        velocity,
+        neighbors: seen as f32,
+        _pad: 0.0,
+    }
+}
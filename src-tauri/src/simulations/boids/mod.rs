@@ -0,0 +1,82 @@
+pub mod flock;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::BoidsModel;
+
+use crate::simulation::preset_manager::{BoidsPresetManager, Preset};
+
+/// Initialize Boids presets with built-in configurations
+pub fn init_presets(preset_manager: &mut BoidsPresetManager) {
+    use settings::{ColorMode, Edges, Settings, Spawn};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Starlings".to_string(),
+        Settings {
+            boid_count: 60_000,
+            spawn: Spawn::Flock,
+            max_speed: 0.5,
+            min_speed: 0.25,
+            field_of_view: 2.8,
+            alignment_weight: 2.0,
+            cohesion_weight: 1.2,
+            boid_size: 0.005,
+            trail_persistence: 0.5,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Milling".to_string(),
+        Settings {
+            spawn: Spawn::Ring,
+            edges: Edges::Avoid,
+            perception_radius: 0.08,
+            separation_weight: 1.0,
+            alignment_weight: 0.6,
+            cohesion_weight: 1.6,
+            color_mode: ColorMode::Heading,
+            trail_persistence: 0.6,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Loose Swarm".to_string(),
+        Settings {
+            boid_count: 8000,
+            max_force: 3.0,
+            field_of_view: std::f32::consts::PI,
+            separation_weight: 2.5,
+            alignment_weight: 0.1,
+            cohesion_weight: 0.4,
+            color_mode: ColorMode::Speed,
+            trail_persistence: 0.0,
+            boid_size: 0.012,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Crowded Schools".to_string(),
+        Settings {
+            boid_count: 150_000,
+            perception_radius: 0.02,
+            separation_radius: 0.006,
+            boid_size: 0.004,
+            color_mode: ColorMode::Neighbors,
+            trail_persistence: 0.2,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
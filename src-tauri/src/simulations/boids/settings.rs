@@ -0,0 +1,301 @@
+//! # Boids Settings Module
+//!
+//! How many boids fly and where they start, how far they see, how hard
+//! each of the three flocking rules steers them, what happens at the edges
+//! of the box, and how they and their trails are drawn.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Constraint, Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Spawn {
+    /// Anywhere in the box, flying every which way
+    #[default]
+    Scattered,
+    /// One tight flock in the middle, all heading the same way
+    Flock,
+    /// Round a ring, circling it
+    Ring,
+}
+
+impl FromStr for Spawn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "scattered" => Ok(Spawn::Scattered),
+            "flock" => Ok(Spawn::Flock),
+            "ring" => Ok(Spawn::Ring),
+            _ => Err(format!(
+                "Invalid Spawn: '{}'. Expected 'Scattered', 'Flock', or 'Ring'",
+                s
+            )),
+        }
+    }
+}
+
+/// What the box's edges do to a boid reaching them
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Edges {
+    /// Fly out of one side and in at the other
+    #[default]
+    Wrap,
+    /// Turn back before reaching the walls
+    Avoid,
+}
+
+impl FromStr for Edges {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wrap" => Ok(Edges::Wrap),
+            "avoid" => Ok(Edges::Avoid),
+            _ => Err(format!(
+                "Invalid Edges: '{}'. Expected 'Wrap' or 'Avoid'",
+                s
+            )),
+        }
+    }
+}
+
+impl From<Edges> for u32 {
+    fn from(edges: Edges) -> Self {
+        match edges {
+            Edges::Wrap => 0,
+            Edges::Avoid => 1,
+        }
+    }
+}
+
+/// What picks each boid's color from the color scheme
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorMode {
+    #[default]
+    Heading,
+    Speed,
+    /// How many flockmates it can see
+    Neighbors,
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "heading" => Ok(ColorMode::Heading),
+            "speed" => Ok(ColorMode::Speed),
+            "neighbors" => Ok(ColorMode::Neighbors),
+            _ => Err(format!(
+                "Invalid ColorMode: '{}'. Expected 'Heading', 'Speed', or 'Neighbors'",
+                s
+            )),
+        }
+    }
+}
+
+impl From<ColorMode> for u32 {
+    fn from(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Heading => 0,
+            ColorMode::Speed => 1,
+            ColorMode::Neighbors => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Boids in the sky; fewer fly if the GPU can't hold them
+    pub boid_count: u32,
+    pub spawn: Spawn,
+    pub random_seed: u32,
+
+    /// Speeds a boid keeps between, in box heights per second
+    pub min_speed: f32,
+    pub max_speed: f32,
+    /// Most a boid's velocity can change each second, in box heights per
+    /// second squared
+    pub max_force: f32,
+    /// How far a boid sees its flockmates, in box heights
+    pub perception_radius: f32,
+    /// Radians either side of its heading a boid sees; pi sees all round
+    pub field_of_view: f32,
+    /// Flockmates nearer than this are steered away from, in box heights
+    pub separation_radius: f32,
+
+    /// Keep clear of crowding flockmates
+    pub separation_weight: f32,
+    /// Fly the way nearby flockmates fly
+    pub alignment_weight: f32,
+    /// Head for the middle of nearby flockmates
+    pub cohesion_weight: f32,
+
+    pub edges: Edges,
+    /// How hard the cursor's obstacle turns boids aside, or its lure pulls
+    /// them in
+    pub cursor_weight: f32,
+
+    /// Length of each boid as drawn, in box heights
+    pub boid_size: f32,
+    pub color_mode: ColorMode,
+    /// Share of the trail left after a second; 0 leaves no trails
+    pub trail_persistence: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            boid_count: 20_000,
+            spawn: Spawn::Scattered,
+            random_seed: 0,
+            min_speed: 0.15,
+            max_speed: 0.4,
+            max_force: 1.5,
+            perception_radius: 0.05,
+            field_of_view: 2.4,
+            separation_radius: 0.015,
+            separation_weight: 1.5,
+            alignment_weight: 1.0,
+            cohesion_weight: 0.8,
+            edges: Edges::Wrap,
+            cursor_weight: 4.0,
+            boid_size: 0.008,
+            color_mode: ColorMode::Heading,
+            trail_persistence: 0.3,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "boid_count",
+            Rule::Count {
+                min: 10,
+                max: 500_000,
+            },
+        ),
+        ("spawn", Rule::OneOf(&["Scattered", "Flock", "Ring"])),
+        (
+            "random_seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+        ("min_speed", Rule::Range { min: 0.0, max: 2.0 }),
+        (
+            "max_speed",
+            Rule::Range {
+                min: 0.01,
+                max: 2.0,
+            },
+        ),
+        (
+            "max_force",
+            Rule::Range {
+                min: 0.01,
+                max: 20.0,
+            },
+        ),
+        (
+            "perception_radius",
+            Rule::Range {
+                min: 0.005,
+                max: 0.3,
+            },
+        ),
+        (
+            "field_of_view",
+            Rule::Range {
+                min: 0.1,
+                max: 3.15,
+            },
+        ),
+        (
+            "separation_radius",
+            Rule::Range {
+                min: 0.001,
+                max: 0.3,
+            },
+        ),
+        (
+            "separation_weight",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        (
+            "alignment_weight",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        (
+            "cohesion_weight",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        ("edges", Rule::OneOf(&["Wrap", "Avoid"])),
+        (
+            "cursor_weight",
+            Rule::Range {
+                min: 0.0,
+                max: 20.0,
+            },
+        ),
+        (
+            "boid_size",
+            Rule::Range {
+                min: 0.001,
+                max: 0.05,
+            },
+        ),
+        (
+            "color_mode",
+            Rule::OneOf(&["Heading", "Speed", "Neighbors"]),
+        ),
+        (
+            "trail_persistence",
+            Rule::Range {
+                min: 0.0,
+                max: 0.99,
+            },
+        ),
+    ],
+    &[
+        Constraint::Ordered {
+            lower: "min_speed",
+            upper: "max_speed",
+        },
+        Constraint::Ordered {
+            lower: "separation_radius",
+            upper: "perception_radius",
+        },
+    ],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("spawn", SettingCategory::Generators),
+    ("random_seed", SettingCategory::Generators),
+    ("color_mode", SettingCategory::Colors),
+    ("trail_persistence", SettingCategory::Colors),
+]);
@@ -0,0 +1,70 @@
+// Shared by every boids pass, each of which binds `params`. The box is
+// `aspect` wide either side of the origin and 1 high either side, the
+// neighbor grid laid over it row by row from the bottom.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    boid_count: u32,
+    grid_width: u32,
+    grid_height: u32,
+    cell_capacity: u32,
+    dt: f32,
+    min_speed: f32,
+    max_speed: f32,
+    max_force: f32,
+    perception_radius: f32,
+    separation_radius: f32,
+    // Cosine of the field of view's half angle
+    view_cos: f32,
+    separation_weight: f32,
+    alignment_weight: f32,
+    cohesion_weight: f32,
+    // 0 wraps round, 1 turns back from the walls
+    edges: u32,
+    edge_margin: f32,
+    cursor: vec2<f32>,
+    cursor_radius: f32,
+    // 0 off, 1 an obstacle, 2 a lure
+    cursor_mode: u32,
+    cursor_weight: f32,
+    boid_size: f32,
+    _pad: f32,
+    color_mode: u32,
+}
+
+struct Boid {
+    position: vec2<f32>,
+    velocity: vec2<f32>,
+    neighbors: f32,
+    _pad: f32,
+}
+
+const PI: f32 = 3.14159265359;
+const TAU: f32 = 6.28318530718;
+
+fn half_extent() -> vec2<f32> {
+    return vec2<f32>(params.aspect, 1.0);
+}
+
+fn cell_of(position: vec2<f32>) -> vec2<i32> {
+    let grid = vec2<f32>(f32(params.grid_width), f32(params.grid_height));
+    let cell = vec2<i32>(floor((position + half_extent()) / (2.0 * half_extent()) * grid));
+    return clamp(cell, vec2<i32>(0), vec2<i32>(i32(params.grid_width) - 1, i32(params.grid_height) - 1));
+}
+
+fn cell_index(cell: vec2<i32>) -> u32 {
+    return u32(cell.y) * params.grid_width + u32(cell.x);
+}
+
+// The shortest way from one point to another, round the box's seams when
+// the box wraps
+fn offset_between(start: vec2<f32>, end: vec2<f32>) -> vec2<f32> {
+    var offset = end - start;
+    if (params.edges == 0u) {
+        let size = 2.0 * half_extent();
+        offset -= size * round(offset / size);
+    }
+    return offset;
+}
@@ -0,0 +1,151 @@
+// Filing boids into the neighbor grid, then steering each by what it sees
+// in its own cell and the eight around it. flock.rs explains the rules and
+// keeps a copy of the steering for the tests.
+//
+// `counts` is cleared before `populate` runs. A boid takes the next slot
+// in its cell, and one arriving once the cell is full isn't filed.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> boids_in: array<Boid>;
+@group(0) @binding(2) var<storage, read_write> boids_out: array<Boid>;
+@group(0) @binding(3) var<storage, read_write> cells: array<u32>;
+@group(0) @binding(4) var<storage, read_write> counts: array<atomic<u32>>;
+
+@compute @workgroup_size(64)
+fn populate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if (index >= params.boid_count) {
+        return;
+    }
+    let cell = cell_index(cell_of(boids_in[index].position));
+    let slot = atomicAdd(&counts[cell], 1u);
+    if (slot < params.cell_capacity) {
+        cells[cell * params.cell_capacity + slot] = index;
+    }
+}
+
+// The force turning `velocity` toward full speed along `direction`
+fn steer_toward(direction: vec2<f32>, velocity: vec2<f32>) -> vec2<f32> {
+    let len = length(direction);
+    if (len < 1e-6) {
+        return vec2<f32>(0.0);
+    }
+    let steer = direction / len * params.max_speed - velocity;
+    let force = length(steer);
+    if (force > params.max_force) {
+        return steer / force * params.max_force;
+    }
+    return steer;
+}
+
+@compute @workgroup_size(64)
+fn update(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if (index >= params.boid_count) {
+        return;
+    }
+    var boid = boids_in[index];
+    let wrap = params.edges == 0u;
+    let speed = max(length(boid.velocity), 1e-6);
+    let home = cell_of(boid.position);
+    let grid = vec2<i32>(i32(params.grid_width), i32(params.grid_height));
+    // Wrapping round a grid under three cells across would visit a cell twice
+    let wrap_cells = wrap && all(grid >= vec2<i32>(3));
+
+    var seen = 0u;
+    var velocity_sum = vec2<f32>(0.0);
+    var offset_sum = vec2<f32>(0.0);
+    var separation = vec2<f32>(0.0);
+    for (var dy = -1; dy <= 1; dy++) {
+        for (var dx = -1; dx <= 1; dx++) {
+            var cell = home + vec2<i32>(dx, dy);
+            if (wrap_cells) {
+                cell = (cell + grid) % grid;
+            } else if (any(cell < vec2<i32>(0)) || any(cell >= grid)) {
+                continue;
+            }
+            let cell_id = cell_index(cell);
+            let filed = min(atomicLoad(&counts[cell_id]), params.cell_capacity);
+            for (var slot = 0u; slot < filed; slot++) {
+                let other_index = cells[cell_id * params.cell_capacity + slot];
+                if (other_index == index) {
+                    continue;
+                }
+                let other = boids_in[other_index];
+                let offset = offset_between(boid.position, other.position);
+                let distance = length(offset);
+                if (distance > params.perception_radius || distance < 1e-6) {
+                    continue;
+                }
+                if (dot(offset, boid.velocity) / (distance * speed) < params.view_cos) {
+                    continue;
+                }
+                seen += 1u;
+                velocity_sum += other.velocity;
+                offset_sum += offset;
+                if (distance < params.separation_radius) {
+                    separation -= offset / distance * (1.0 - distance / params.separation_radius);
+                }
+            }
+        }
+    }
+
+    var acceleration = vec2<f32>(0.0);
+    if (seen > 0u) {
+        acceleration += steer_toward(separation, boid.velocity) * params.separation_weight;
+        acceleration += steer_toward(velocity_sum, boid.velocity) * params.alignment_weight;
+        acceleration += steer_toward(offset_sum, boid.velocity) * params.cohesion_weight;
+    }
+    if (!wrap) {
+        // Turn back harder the nearer the wall
+        let inside = half_extent() - abs(boid.position);
+        let near = max(1.0 - inside / params.edge_margin, vec2<f32>(0.0));
+        let away = -sign(boid.position) * select(vec2<f32>(0.0), near, inside < vec2<f32>(params.edge_margin));
+        acceleration += steer_toward(away, boid.velocity) * 2.0;
+    }
+
+    // Away from the obstacle as they near its rim, or toward the lure once
+    // they're within four radii of it
+    let to_cursor = offset_between(boid.position, params.cursor);
+    let cursor_distance = length(to_cursor);
+    if (params.cursor_mode == 1u) {
+        let beyond = cursor_distance - params.cursor_radius;
+        if (beyond < params.perception_radius) {
+            let push = 1.0 - max(beyond, 0.0) / params.perception_radius;
+            acceleration += steer_toward(-to_cursor, boid.velocity) * params.cursor_weight * push;
+        }
+    } else if (params.cursor_mode == 2u && cursor_distance < 4.0 * params.cursor_radius) {
+        acceleration += steer_toward(to_cursor, boid.velocity) * params.cursor_weight * 0.5;
+    }
+
+    var velocity = boid.velocity + acceleration * params.dt;
+    let new_speed = length(velocity);
+    if (new_speed < 1e-6) {
+        velocity = vec2<f32>(params.min_speed, 0.0);
+    } else {
+        velocity *= clamp(new_speed, params.min_speed, params.max_speed) / new_speed;
+    }
+
+    var position = boid.position + velocity * params.dt;
+    let bounds = half_extent();
+    if (wrap) {
+        position -= 2.0 * bounds * sign(position) * vec2<f32>(abs(position) > bounds);
+    } else {
+        let outside = abs(position) > bounds;
+        position = clamp(position, -bounds, bounds);
+        velocity = select(velocity, -velocity, outside);
+    }
+    // Nothing flies through the obstacle
+    if (params.cursor_mode == 1u) {
+        let from_cursor = offset_between(params.cursor, position);
+        let distance = length(from_cursor);
+        if (distance < params.cursor_radius && distance > 1e-6) {
+            position += from_cursor / distance * (params.cursor_radius - distance);
+        }
+    }
+
+    boid.position = position;
+    boid.velocity = velocity;
+    boid.neighbors = f32(seen);
+    boids_out[index] = boid;
+}
@@ -0,0 +1,6 @@
+pub const FLOCK_SHADER: &str = concat!(include_str!("common.wgsl"), include_str!("flock.wgsl"));
+pub const RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("common.wgsl"),
+    include_str!("render.wgsl")
+);
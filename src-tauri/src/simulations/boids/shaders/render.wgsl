@@ -0,0 +1,102 @@
+// The boids are drawn into a trail texture covering the box, over what's
+// left of last frame's once `trails.wgsl` has faded it, and the trail is
+// then drawn to the screen through the camera. Each boid is a small
+// arrowhead colored by its heading, speed or how many flockmates it sees.
+//
+// The trail is opaque, faded toward the sky it was cleared to.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> boids: array<Boid>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+@group(1) @binding(0) var trail_texture: texture_2d<f32>;
+@group(1) @binding(1) var trail_sampler: sampler;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+fn from_ndc(ndc: vec2<f32>) -> vec2<f32> {
+    let world = ndc / params.view_zoom + params.view_center;
+    return vec2<f32>(world.x * params.aspect, world.y);
+}
+
+struct FullscreenOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// One triangle covering the target
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return FullscreenOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+fn trail_uv(ndc: vec2<f32>) -> vec2<f32> {
+    return vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+}
+
+@fragment
+fn fs_present(input: FullscreenOutput) -> @location(0) vec4<f32> {
+    let point = from_ndc(input.ndc);
+    let box_ndc = vec2<f32>(point.x / params.aspect, point.y);
+    let trail = textureSample(trail_texture, trail_sampler, trail_uv(box_ndc));
+    if (abs(box_ndc.x) > 1.0 || abs(box_ndc.y) > 1.0) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    return vec4<f32>(trail.rgb, 1.0);
+}
+
+struct BoidOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) color: vec3<f32>,
+}
+
+fn color_position(boid: Boid) -> f32 {
+    switch (params.color_mode) {
+        case 1u: {
+            let range = max(params.max_speed - params.min_speed, 1e-3);
+            return (length(boid.velocity) - params.min_speed) / range;
+        }
+        case 2u: {
+            return min(boid.neighbors / 16.0, 1.0);
+        }
+        default: {
+            return atan2(boid.velocity.y, boid.velocity.x) / TAU + 0.5;
+        }
+    }
+}
+
+// An arrowhead pointing the way the boid flies, in the trail texture's
+// coordinates rather than the camera's
+@vertex
+fn vs_boid(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> BoidOutput {
+    let boid = boids[instance_index];
+    let corners = array<vec2<f32>, 3>(
+        vec2<f32>(0.6, 0.0),
+        vec2<f32>(-0.4, 0.3),
+        vec2<f32>(-0.4, -0.3),
+    );
+    let speed = length(boid.velocity);
+    let direction = select(vec2<f32>(1.0, 0.0), boid.velocity / speed, speed > 1e-6);
+    let normal = vec2<f32>(-direction.y, direction.x);
+    let local = corners[vertex_index] * params.boid_size;
+    let point = boid.position + direction * local.x + normal * local.y;
+    let ndc = vec2<f32>(point.x / params.aspect, point.y);
+    return BoidOutput(vec4<f32>(ndc, 0.0, 1.0), lut_color(color_position(boid)));
+}
+
+@fragment
+fn fs_boid(input: BoidOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(input.color, 1.0);
+}
@@ -0,0 +1,936 @@
+//! # Boids Simulation Module
+//!
+//! A flock of boids steering by separation, alignment and cohesion, leaving
+//! fading trails; see [`flock`] for the rules. The left button holds up an
+//! obstacle the flock parts around and the right button dangles a lure it
+//! chases.
+//!
+//! ## Technical Overview
+//!
+//! Everything runs on the GPU once seeded. Each frame:
+//! 1. The grid's counts are cleared and every boid is filed into its cell.
+//! 2. Every boid looks through the cells around its own for flockmates,
+//!    steers, and flies on into the other of two boid buffers.
+//! 3. Last frame's trail is faded by the shared [`Trails`] and the boids are
+//!    drawn over it as arrowheads.
+//! 4. The trail is drawn to the screen through the camera.
+//!
+//! The trail covers the box rather than the screen, so panning and zooming
+//! move over the trail instead of smearing it.
+//!
+//! [`flock`]: super::flock
+//! [`Trails`]: crate::simulations::shared::Trails
+
+use bytemuck::{Pod, Zeroable};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, BlendState, Buffer,
+    BufferDescriptor, BufferUsages, CommandEncoder, ComputePipeline, ComputePipelineDescriptor,
+    Device, PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, Sampler,
+    ShaderModule, ShaderStages, SurfaceConfiguration, TextureFormat, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_budget::GpuReservation;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, FadeCurve, PingPongParticleBuffers, Trails,
+};
+use crate::simulations::traits::{FAST_FORWARD_STEP_TIME, Simulation};
+
+use super::flock::{self, Boid, CELL_CAPACITY};
+use super::settings::{ColorMode, Edges, Settings, Spawn};
+use super::shaders::{FLOCK_SHADER, RENDER_SHADER};
+use super::state::State;
+
+/// Longest step taken in one frame, so a stall doesn't scatter the flock
+const MAX_FRAME_TIME: f32 = 1.0 / 30.0;
+
+/// Room for trails fading to almost nothing
+const TRAIL_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The box's background, which the trails fade into
+const SKY: wgpu::Color = wgpu::Color {
+    r: 0.015,
+    g: 0.015,
+    b: 0.015,
+    a: 1.0,
+};
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    boid_count: u32,
+    grid_width: u32,
+    grid_height: u32,
+    cell_capacity: u32,
+    dt: f32,
+    min_speed: f32,
+    max_speed: f32,
+    max_force: f32,
+    perception_radius: f32,
+    separation_radius: f32,
+    view_cos: f32,
+    separation_weight: f32,
+    alignment_weight: f32,
+    cohesion_weight: f32,
+    edges: u32,
+    edge_margin: f32,
+    cursor: [f32; 2],
+    cursor_radius: f32,
+    cursor_mode: u32,
+    cursor_weight: f32,
+    boid_size: f32,
+    _pad: f32,
+    color_mode: u32,
+}
+
+/// What the cursor holds up while a button is down
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cursor {
+    Off,
+    Obstacle([f32; 2]),
+    Lure([f32; 2]),
+}
+
+/// Everything the flock's bind groups point at besides the flock itself
+#[derive(Debug)]
+struct Resources {
+    flock_bind_group_layout: BindGroupLayout,
+    render_bind_group_layout: BindGroupLayout,
+    trail_bind_group_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    trail_sampler: Sampler,
+}
+
+/// The neighbor grid on the GPU and the bind groups over it and the boids,
+/// remade when the count or the grid changes. Bind groups come in pairs,
+/// one for each boid buffer being the current one.
+#[derive(Debug)]
+struct Flock {
+    boid_count: u32,
+    grid_width: u32,
+    grid_height: u32,
+    counts: Buffer,
+    flock_bind_groups: [BindGroup; 2],
+    render_bind_groups: [BindGroup; 2],
+    _memory: GpuReservation,
+}
+
+impl Flock {
+    /// A grid over `boids`, which must have just been reallocated so the
+    /// first buffer is the current one
+    fn new(
+        device: &Device,
+        boids: &PingPongParticleBuffers<Boid>,
+        boid_count: u32,
+        (grid_width, grid_height): (u32, u32),
+        resources: &Resources,
+    ) -> Self {
+        let cell_count = (grid_width * grid_height) as u64;
+        let cells_size = cell_count * CELL_CAPACITY as u64 * std::mem::size_of::<u32>() as u64;
+        let counts_size = cell_count * std::mem::size_of::<u32>() as u64;
+        let memory = GpuReservation::new(cells_size + counts_size);
+
+        let cells = device.create_buffer(&BufferDescriptor {
+            label: Some("Boids Grid Cells Buffer"),
+            size: cells_size,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let counts = device.create_buffer(&BufferDescriptor {
+            label: Some("Boids Grid Counts Buffer"),
+            size: counts_size,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Index 0 reads the current buffer before any swap
+        let (first, second) = (boids.current_buffer(), boids.inactive_buffer());
+        let flock_bind_group = |label, from: &Buffer, to: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.flock_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, from),
+                    resource_helpers::buffer_entry(2, to),
+                    resource_helpers::buffer_entry(3, &cells),
+                    resource_helpers::buffer_entry(4, &counts),
+                ],
+            })
+        };
+        let flock_bind_groups = [
+            flock_bind_group("Boids Flock Bind Group A", first, second),
+            flock_bind_group("Boids Flock Bind Group B", second, first),
+        ];
+
+        let render_bind_group = |label, boids: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.render_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, boids),
+                    resource_helpers::buffer_entry(2, &resources.lut_buffer),
+                ],
+            })
+        };
+        let render_bind_groups = [
+            render_bind_group("Boids Render Bind Group A", first),
+            render_bind_group("Boids Render Bind Group B", second),
+        ];
+
+        Self {
+            boid_count,
+            grid_width,
+            grid_height,
+            counts,
+            flock_bind_groups,
+            render_bind_groups,
+            _memory: memory,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BoidsModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    populate_pipeline: ComputePipeline,
+    update_pipeline: ComputePipeline,
+    boid_pipeline: RenderPipeline,
+    present_pipeline: RenderPipeline,
+    resources: Resources,
+    boids: PingPongParticleBuffers<Boid>,
+    flock: Flock,
+    trails: Trails,
+
+    cursor: Cursor,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl BoidsModel {
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let flock_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Boids Flock Shader"),
+            source: wgpu::ShaderSource::Wgsl(FLOCK_SHADER.into()),
+        });
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Boids Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Boids Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("Boids LUT Buffer for {}", state.color_scheme_name)),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let trail_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Boids Trail Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let flock_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Boids Flock Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, false),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, false),
+                resource_helpers::storage_buffer_entry(4, ShaderStages::COMPUTE, false),
+            ],
+        });
+
+        let render_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Boids Render Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(
+                        0,
+                        ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    ),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::VERTEX, true),
+                    resource_helpers::storage_buffer_entry(
+                        2,
+                        ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                        true,
+                    ),
+                ],
+            });
+
+        let trail_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Boids Trail Bind Group Layout"),
+            entries: &[
+                resource_helpers::texture_entry(
+                    0,
+                    ShaderStages::FRAGMENT,
+                    wgpu::TextureSampleType::Float { filterable: true },
+                    wgpu::TextureViewDimension::D2,
+                ),
+                resource_helpers::sampler_entry(
+                    1,
+                    ShaderStages::FRAGMENT,
+                    wgpu::SamplerBindingType::Filtering,
+                ),
+            ],
+        });
+
+        let compute_pipeline = |label: &str, entry_point| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(&format!("Boids {} Pipeline", label)),
+                layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                    label: Some(&format!("Boids {} Pipeline Layout", label)),
+                    bind_group_layouts: &[&flock_bind_group_layout],
+                    push_constant_ranges: &[],
+                })),
+                module: &flock_module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let populate_pipeline = compute_pipeline("Populate", "populate");
+        let update_pipeline = compute_pipeline("Update", "update");
+
+        let boid_pipeline = create_pipeline(
+            device,
+            "Boids Boid Pipeline",
+            &[&render_bind_group_layout],
+            &render_module,
+            ("vs_boid", "fs_boid"),
+            TRAIL_FORMAT,
+        );
+        let present_pipeline = create_pipeline(
+            device,
+            "Boids Present Pipeline",
+            &[&render_bind_group_layout, &trail_bind_group_layout],
+            &render_module,
+            ("vs_fullscreen", "fs_present"),
+            surface_config.format,
+        );
+
+        let resources = Resources {
+            flock_bind_group_layout,
+            render_bind_group_layout,
+            trail_bind_group_layout,
+            params_buffer,
+            lut_buffer,
+            trail_sampler,
+        };
+        // Replaced by rebuild_flock once the model exists
+        let boids = PingPongParticleBuffers::uninitialized(
+            device,
+            ["Boids Buffer A", "Boids Buffer B"],
+            BufferUsages::empty(),
+            1,
+        );
+        let flock = Flock::new(device, &boids, 1, (1, 1), &resources);
+        let trails = Trails::new(
+            device,
+            "Boids Trails",
+            (surface_config.width, surface_config.height),
+            1.0,
+            TRAIL_FORMAT,
+            wgpu::FilterMode::Linear,
+        )
+        .with_max_fade(1.0);
+
+        let mut model = Self {
+            settings,
+            state,
+            populate_pipeline,
+            update_pipeline,
+            boid_pipeline,
+            present_pipeline,
+            resources,
+            boids,
+            flock,
+            trails,
+            cursor: Cursor::Off,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.rebuild_flock(device, queue)?;
+        Ok(model)
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// Remake the boids and grid for the window and settings, and seed them
+    fn rebuild_flock(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let grid = flock::grid_size(self.settings.perception_radius, self.aspect());
+        let boid_count = self
+            .boids
+            .reallocate(device, self.settings.boid_count.max(1) as usize);
+        self.flock = Flock::new(
+            device,
+            &self.boids,
+            boid_count as u32,
+            grid,
+            &self.resources,
+        );
+        self.state.boid_count = self.flock.boid_count;
+        self.state.grid_width = self.flock.grid_width;
+        self.state.grid_height = self.flock.grid_height;
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Lay the boids out from the seed and wipe the trails
+    fn seed(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) {
+        let mut rng = StdRng::seed_from_u64(self.settings.random_seed as u64);
+        let boids = flock::seed_boids(
+            self.flock.boid_count as usize,
+            self.settings.spawn,
+            self.aspect(),
+            (self.settings.min_speed, self.settings.max_speed),
+            &mut rng,
+        );
+        queue.write_buffer(self.boids.current_buffer(), 0, bytemuck::cast_slice(&boids));
+        self.trails.clear(device, queue, SKY);
+    }
+
+    fn write_params(&self, queue: &Arc<Queue>, dt: f32) {
+        let settings = &self.settings;
+        let (cursor_mode, cursor) = match self.cursor {
+            Cursor::Off => (0, [0.0; 2]),
+            Cursor::Obstacle(center) => (1, center),
+            Cursor::Lure(center) => (2, center),
+        };
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            boid_count: self.flock.boid_count,
+            grid_width: self.flock.grid_width,
+            grid_height: self.flock.grid_height,
+            cell_capacity: CELL_CAPACITY,
+            dt,
+            min_speed: settings.min_speed.min(settings.max_speed),
+            max_speed: settings.max_speed,
+            max_force: settings.max_force,
+            perception_radius: settings.perception_radius,
+            separation_radius: settings.separation_radius.max(1e-4),
+            view_cos: settings.field_of_view.min(std::f32::consts::PI).cos(),
+            separation_weight: settings.separation_weight,
+            alignment_weight: settings.alignment_weight,
+            cohesion_weight: settings.cohesion_weight,
+            edges: settings.edges.into(),
+            edge_margin: settings.perception_radius.max(0.05),
+            cursor,
+            cursor_radius: self.state.cursor_size.max(1e-3),
+            cursor_mode,
+            cursor_weight: settings.cursor_weight,
+            boid_size: settings.boid_size,
+            _pad: 0.0,
+            color_mode: settings.color_mode.into(),
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
+    /// File the boids into the grid and fly them on a step
+    fn encode_step(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.clear_buffer(&self.flock.counts, 0, None);
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Boids Step Pass"),
+                timestamp_writes: None,
+            });
+            let workgroups = self.flock.boid_count.div_ceil(64);
+            compute_pass.set_bind_group(
+                0,
+                self.boids.get_bind_group(
+                    &self.flock.flock_bind_groups[0],
+                    &self.flock.flock_bind_groups[1],
+                ),
+                &[],
+            );
+            compute_pass.set_pipeline(&self.populate_pipeline);
+            compute_pass.dispatch_workgroups(workgroups, 1, 1);
+            compute_pass.set_pipeline(&self.update_pipeline);
+            compute_pass.dispatch_workgroups(workgroups, 1, 1);
+        }
+        self.boids.swap();
+    }
+
+    /// Fade the trail by `dt` seconds' worth and draw the boids over it.
+    /// Over the opaque sky every frame takes the same share of what's left,
+    /// so the trails keep `trail_persistence` of themselves a second
+    /// whatever the frame rate.
+    fn encode_trail(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        dt: f32,
+    ) {
+        {
+            let mut render_pass = self.trails.begin_pass(
+                device,
+                queue,
+                encoder,
+                FadeCurve::Linear,
+                self.settings.trail_persistence.clamp(0.0, 1.0).powf(dt),
+                SKY,
+            );
+            render_pass.set_pipeline(&self.boid_pipeline);
+            render_pass.set_bind_group(
+                0,
+                self.boids.get_bind_group(
+                    &self.flock.render_bind_groups[0],
+                    &self.flock.render_bind_groups[1],
+                ),
+                &[],
+            );
+            render_pass.draw(0..3, 0..self.flock.boid_count);
+        }
+        self.trails.swap();
+    }
+
+    fn encode_present(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        surface_view: &TextureView,
+    ) {
+        let trail_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Boids Trail Bind Group"),
+            layout: &self.resources.trail_bind_group_layout,
+            entries: &[
+                resource_helpers::texture_view_entry(0, self.trails.view()),
+                resource_helpers::sampler_bind_entry(1, &self.resources.trail_sampler),
+            ],
+        });
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Boids Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_bind_group(
+            0,
+            self.boids.get_bind_group(
+                &self.flock.render_bind_groups[0],
+                &self.flock.render_bind_groups[1],
+            ),
+            &[],
+        );
+        render_pass.set_bind_group(1, &trail_bind_group, &[]);
+        render_pass.set_pipeline(&self.present_pipeline);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+fn create_pipeline(
+    device: &Device,
+    label: &str,
+    bind_group_layouts: &[&BindGroupLayout],
+    module: &ShaderModule,
+    (vertex_entry, fragment_entry): (&str, &str),
+    format: TextureFormat,
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(&format!("{} Layout", label)),
+            bind_group_layouts,
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex_entry),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for BoidsModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        let dt = delta_time.min(MAX_FRAME_TIME);
+        self.write_params(queue, dt);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Boids Render"),
+        });
+        self.encode_step(&mut encoder);
+        self.encode_trail(device, queue, &mut encoder, dt);
+        self.encode_present(device, &mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.write_params(queue, 0.0);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Boids Render Paused"),
+        });
+        self.encode_present(device, &mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn fast_forward(
+        &mut self,
+        steps: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // The trails are left as they were; they fade out soon enough
+        self.write_params(queue, FAST_FORWARD_STEP_TIME);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Boids Fast Forward"),
+        });
+        for _ in 0..steps {
+            self.encode_step(&mut encoder);
+        }
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.trails
+            .resize(device, (new_config.width, new_config.height));
+        self.rebuild_flock(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let center = [world_x * self.aspect(), world_y];
+        // Left holds up an obstacle, right dangles a lure
+        self.cursor = match mouse_button {
+            0 => Cursor::Obstacle(center),
+            2 => Cursor::Lure(center),
+            _ => return Ok(()),
+        };
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.cursor = Cursor::Off;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.boid_count != self.settings.boid_count
+            || old_settings.perception_radius != self.settings.perception_radius
+        {
+            self.rebuild_flock(device, queue)?;
+        } else if old_settings.spawn != self.settings.spawn
+            || old_settings.random_seed != self.settings.random_seed
+        {
+            self.reset_runtime_state(device, queue)?;
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.seed(device, queue);
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.spawn = match rng.random_range(0..3) {
+            0 => Spawn::Scattered,
+            1 => Spawn::Flock,
+            _ => Spawn::Ring,
+        };
+        self.settings.max_speed = rng.random_range(0.2..0.8);
+        self.settings.min_speed = self.settings.max_speed * rng.random_range(0.2..0.6);
+        self.settings.max_force = rng.random_range(0.5..4.0);
+        self.settings.field_of_view = rng.random_range(1.5..std::f32::consts::PI);
+        self.settings.separation_weight = rng.random_range(0.5..3.0);
+        self.settings.alignment_weight = rng.random_range(0.2..2.5);
+        self.settings.cohesion_weight = rng.random_range(0.2..2.0);
+        self.settings.edges = if rng.random_bool(0.5) {
+            Edges::Wrap
+        } else {
+            Edges::Avoid
+        };
+        self.settings.color_mode = match rng.random_range(0..3) {
+            0 => ColorMode::Heading,
+            1 => ColorMode::Speed,
+            _ => ColorMode::Neighbors,
+        };
+        self.settings.trail_persistence = rng.random_range(0.0..0.6);
+        self.settings.random_seed = rng.random();
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "boid_count" => {
+                self.settings.boid_count = number(setting_name, &value)? as u32;
+                self.rebuild_flock(device, queue)?;
+            }
+            "spawn" => {
+                self.settings.spawn = value
+                    .as_str()
+                    .unwrap_or("Scattered")
+                    .parse()
+                    .map_err(|e| format!("Invalid spawn: {}", e))?;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "random_seed" => {
+                self.settings.random_seed = number(setting_name, &value)? as u32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "min_speed" => self.settings.min_speed = number(setting_name, &value)? as f32,
+            "max_speed" => self.settings.max_speed = number(setting_name, &value)? as f32,
+            "max_force" => self.settings.max_force = number(setting_name, &value)? as f32,
+            "perception_radius" => {
+                self.settings.perception_radius = number(setting_name, &value)? as f32;
+                self.rebuild_flock(device, queue)?;
+            }
+            "field_of_view" => {
+                self.settings.field_of_view = number(setting_name, &value)? as f32;
+            }
+            "separation_radius" => {
+                self.settings.separation_radius = number(setting_name, &value)? as f32;
+            }
+            "separation_weight" => {
+                self.settings.separation_weight = number(setting_name, &value)? as f32;
+            }
+            "alignment_weight" => {
+                self.settings.alignment_weight = number(setting_name, &value)? as f32;
+            }
+            "cohesion_weight" => {
+                self.settings.cohesion_weight = number(setting_name, &value)? as f32;
+            }
+            "edges" => {
+                self.settings.edges = value
+                    .as_str()
+                    .unwrap_or("Wrap")
+                    .parse()
+                    .map_err(|e| format!("Invalid edges: {}", e))?;
+            }
+            "cursor_weight" => {
+                self.settings.cursor_weight = number(setting_name, &value)? as f32;
+            }
+            "boid_size" => self.settings.boid_size = number(setting_name, &value)? as f32,
+            "color_mode" => {
+                self.settings.color_mode = value
+                    .as_str()
+                    .unwrap_or("Heading")
+                    .parse()
+                    .map_err(|e| format!("Invalid color mode: {}", e))?;
+            }
+            "trail_persistence" => {
+                self.settings.trail_persistence = number(setting_name, &value)? as f32;
+            }
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "cursor_size" => {
+                self.state.cursor_size = number(state_name, &value)?.clamp(0.01, 0.5) as f32;
+            }
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
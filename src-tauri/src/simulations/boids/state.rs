@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Boids flying, which is fewer than asked for if the GPU couldn't hold
+    // them all, and the neighbor grid's size in cells
+    pub boid_count: u32,
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Radius in box heights of the obstacle the left button holds up, or
+    // the lure the right button dangles
+    pub cursor_size: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            boid_count: 0,
+            grid_width: 0,
+            grid_height: 0,
+            cursor_size: 0.1,
+            color_scheme_name: "MATPLOTLIB_twilight".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::flock::{self, Boid, Flocking, MAX_GRID_CELLS, grid_size, seed_boids};
+use super::settings::{ColorMode, Edges, SETTING_RULES, Settings, Spawn};
+use super::shaders::{FLOCK_SHADER, RENDER_SHADER};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn flocking(settings: &Settings) -> Flocking {
+    Flocking {
+        min_speed: settings.min_speed,
+        max_speed: settings.max_speed,
+        max_force: settings.max_force,
+        perception_radius: settings.perception_radius,
+        separation_radius: settings.separation_radius,
+        view_cos: settings.field_of_view.cos(),
+        separation_weight: settings.separation_weight,
+        alignment_weight: settings.alignment_weight,
+        cohesion_weight: settings.cohesion_weight,
+        edges: settings.edges,
+    }
+}
+
+/// Every boid moved on by one step, all reading the same frame
+fn step_all(boids: &[Boid], flocking: &Flocking, half_width: f32) -> Vec<Boid> {
+    (0..boids.len())
+        .map(|index| flock::step(boids, index, flocking, 1.0 / 60.0, half_width))
+        .collect()
+}
+
+fn distance(a: &Boid, b: &Boid) -> f32 {
+    (a.position[0] - b.position[0]).hypot(a.position[1] - b.position[1])
+}
+
+#[test]
+fn grid_cells_are_no_smaller_than_the_perception_radius() {
+    for (radius, half_width) in [(0.05, 1.0), (0.05, 1.778), (0.3, 0.5), (0.2, 2.4)] {
+        let (width, height) = grid_size(radius, half_width);
+        assert!(
+            2.0 * half_width / width as f32 >= radius,
+            "{} {}",
+            radius,
+            half_width
+        );
+        assert!(2.0 / height as f32 >= radius, "{} {}", radius, half_width);
+    }
+    assert_eq!(grid_size(0.05, 1.0), (40, 40));
+    // Past the cap the cells grow instead
+    let (width, height) = grid_size(0.005, 1.778);
+    assert!(width * height <= MAX_GRID_CELLS);
+    assert!(width > height);
+}
+
+#[test]
+fn boids_start_inside_the_box_at_a_speed_they_keep() {
+    let half_width = 1.5;
+    for spawn in [Spawn::Scattered, Spawn::Flock, Spawn::Ring] {
+        let boids = seed_boids(
+            400,
+            spawn,
+            half_width,
+            (0.1, 0.3),
+            &mut StdRng::seed_from_u64(3),
+        );
+        assert_eq!(boids.len(), 400);
+        for boid in &boids {
+            let [x, y] = boid.position;
+            assert!(x.abs() <= half_width && y.abs() <= 1.0, "{:?}", spawn);
+            let speed = boid.velocity[0].hypot(boid.velocity[1]);
+            assert!((0.1..=0.3 + 1e-6).contains(&speed), "{:?} {}", spawn, speed);
+        }
+    }
+    let seeded = |seed| {
+        seed_boids(
+            50,
+            Spawn::Scattered,
+            1.0,
+            (0.1, 0.3),
+            &mut StdRng::seed_from_u64(seed),
+        )
+    };
+    assert_eq!(seeded(1), seeded(1));
+    assert_ne!(seeded(1), seeded(2));
+}
+
+#[test]
+fn a_lone_boid_flies_straight_on() {
+    let flocking = flocking(&Settings::default());
+    let boids = [Boid::new([0.0, 0.0], [0.2, 0.0])];
+    let moved = flock::step(&boids, 0, &flocking, 0.5, 1.0);
+    assert_eq!(moved.velocity, [0.2, 0.0]);
+    assert!((moved.position[0] - 0.1).abs() < 1e-6);
+    assert_eq!(moved.neighbors, 0.0);
+}
+
+#[test]
+fn crowding_boids_spread_apart() {
+    let settings = Settings {
+        alignment_weight: 0.0,
+        cohesion_weight: 0.0,
+        field_of_view: std::f32::consts::PI,
+        ..Settings::default()
+    };
+    let flocking = flocking(&settings);
+    let mut boids = vec![
+        Boid::new([0.0, 0.0], [0.2, 0.0]),
+        Boid::new([0.0, 0.005], [0.2, 0.0]),
+    ];
+    let start = distance(&boids[0], &boids[1]);
+    for _ in 0..30 {
+        boids = step_all(&boids, &flocking, 1.0);
+    }
+    assert!(distance(&boids[0], &boids[1]) > start * 2.0);
+}
+
+#[test]
+fn neighbors_come_to_fly_the_same_way() {
+    let settings = Settings {
+        separation_weight: 0.0,
+        cohesion_weight: 0.0,
+        perception_radius: 0.2,
+        field_of_view: std::f32::consts::PI,
+        ..Settings::default()
+    };
+    let flocking = flocking(&settings);
+    let mut boids = vec![
+        Boid::new([0.0, 0.0], [0.3, 0.0]),
+        Boid::new([0.0, 0.02], [0.3 * 0.8f32.cos(), 0.3 * 0.8f32.sin()]),
+    ];
+    let heading = |boid: &Boid| boid.velocity[1].atan2(boid.velocity[0]);
+    for _ in 0..60 {
+        boids = step_all(&boids, &flocking, 1.0);
+    }
+    assert!((heading(&boids[0]) - heading(&boids[1])).abs() < 0.1);
+    assert_eq!(boids[0].neighbors, 1.0);
+}
+
+#[test]
+fn boids_stay_in_the_box() {
+    let half_width = 1.2;
+    for edges in [Edges::Wrap, Edges::Avoid] {
+        let flocking = flocking(&Settings {
+            edges,
+            max_speed: 1.0,
+            min_speed: 0.8,
+            ..Settings::default()
+        });
+        let mut boids = seed_boids(
+            60,
+            Spawn::Scattered,
+            half_width,
+            (0.8, 1.0),
+            &mut StdRng::seed_from_u64(9),
+        );
+        for _ in 0..240 {
+            boids = step_all(&boids, &flocking, half_width);
+            for boid in &boids {
+                let [x, y] = boid.position;
+                assert!(x.abs() <= half_width && y.abs() <= 1.0, "{:?}", edges);
+            }
+        }
+    }
+}
+
+#[test]
+fn default_settings_keep_to_the_rules() {
+    let settings = serde_json::to_value(Settings::default()).unwrap();
+    for (name, value) in settings.as_object().unwrap() {
+        let validated = SETTING_RULES
+            .validate(name, value.clone(), || settings.clone())
+            .unwrap();
+        assert!(!validated.clamped, "{} = {}", name, value);
+    }
+}
+
+#[test]
+fn enums_parse_from_their_names() {
+    for spawn in [Spawn::Scattered, Spawn::Flock, Spawn::Ring] {
+        assert_eq!(format!("{:?}", spawn).parse::<Spawn>(), Ok(spawn));
+    }
+    for mode in [ColorMode::Heading, ColorMode::Speed, ColorMode::Neighbors] {
+        assert_eq!(format!("{:?}", mode).parse::<ColorMode>(), Ok(mode));
+    }
+    assert_eq!("avoid".parse::<Edges>(), Ok(Edges::Avoid));
+    assert!("bounce".parse::<Edges>().is_err());
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Boids Flock Shader", FLOCK_SHADER),
+        ("Boids Render Shader", RENDER_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
@@ -0,0 +1,180 @@
+//! # Colony
+//!
+//! Bacteria that swim by run and tumble, as E. coli does: each swims
+//! straight for a while, then tumbles to a new heading at random. They
+//! can't sense which way the nutrient rises, only whether it's been rising
+//! lately, so they put off tumbling while things improve and tumble sooner
+//! when they don't. That bias alone walks the colony up the gradient.
+//!
+//! Eating earns energy and living spends it. A bacterium divides in two
+//! once it's saved enough and dies if it runs out.
+//!
+//! The bacteria and nutrient are laid out here and uploaded; from then on
+//! `colony.wgsl` and `nutrient.wgsl` run them on the GPU. A copy of the
+//! swimming step is kept here for the tests.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use std::f32::consts::TAU;
+
+use super::settings::{NutrientLayout, Placement};
+
+/// Energy a bacterium starts with when seeded or born
+pub const NEWBORN_ENERGY: f32 = 0.5;
+
+/// Most diffusion passes run in a frame, however fast the nutrient spreads
+pub const MAX_DIFFUSION_ITERATIONS: u32 = 8;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct Bacterium {
+    pub position: [f32; 2],
+    pub heading: f32,
+    /// Nutrient as recently remembered, to compare against what's here now
+    pub memory: f32,
+    pub energy: f32,
+    /// Seconds since it was born or seeded
+    pub age: f32,
+    pub _pad0: f32,
+    pub _pad1: f32,
+}
+
+impl Bacterium {
+    pub fn new(position: [f32; 2], heading: f32) -> Self {
+        Self {
+            position,
+            heading,
+            memory: 0.0,
+            energy: NEWBORN_ENERGY,
+            age: 0.0,
+            _pad0: 0.0,
+            _pad1: 0.0,
+        }
+    }
+}
+
+/// Cells across and up a box `2 * half_width` wide and 2 high, with
+/// `resolution` cells up it
+pub fn grid_size(resolution: u32, half_width: f32) -> (u32, u32) {
+    let height = resolution.max(1);
+    let width = ((height as f32 * half_width).ceil() as u32).max(1);
+    (width, height)
+}
+
+/// Passes needed for the nutrient to spread by `diffusion` over `dt`
+/// seconds on a grid `height` cells high, and how far each pass blends a
+/// cell toward its neighbors. Each pass blends at most fully, past which
+/// it would overshoot.
+pub fn diffusion_steps(diffusion: f32, dt: f32, height: u32) -> (u32, f32) {
+    let cell = 2.0 / height.max(1) as f32;
+    let total = 4.0 * diffusion.max(0.0) * dt.max(0.0) / (cell * cell);
+    let iterations = (total.ceil() as u32).clamp(1, MAX_DIFFUSION_ITERATIONS);
+    (iterations, (total / iterations as f32).min(1.0))
+}
+
+/// Nutrient for each cell of a `width` by `height` grid, row by row from
+/// the bottom
+pub fn seed_nutrient(
+    layout: NutrientLayout,
+    (width, height): (u32, u32),
+    rng: &mut impl Rng,
+) -> Vec<f32> {
+    let (width, height) = (width as usize, height as usize);
+    match layout {
+        NutrientLayout::Empty => vec![0.0; width * height],
+        NutrientLayout::Uniform => vec![0.5; width * height],
+        NutrientLayout::Gradient => (0..width * height)
+            .map(|i| (i % width) as f32 / (width - 1).max(1) as f32)
+            .collect(),
+        NutrientLayout::Patches => {
+            let mut cells = vec![0.0f32; width * height];
+            for _ in 0..rng.random_range(6..=10) {
+                let center = [
+                    rng.random_range(0.0..width as f32),
+                    rng.random_range(0.0..height as f32),
+                ];
+                let radius = rng.random_range(0.06..0.15) * height as f32;
+                for (i, cell) in cells.iter_mut().enumerate() {
+                    let dx = (i % width) as f32 + 0.5 - center[0];
+                    let dy = (i / width) as f32 + 0.5 - center[1];
+                    let falloff = 1.0 - (dx * dx + dy * dy) / (radius * radius);
+                    *cell = cell.max(falloff);
+                }
+            }
+            cells
+        }
+    }
+}
+
+/// `count` bacteria in a box `2 * half_width` wide and 2 high
+pub fn seed_bacteria(
+    count: usize,
+    placement: Placement,
+    half_width: f32,
+    rng: &mut impl Rng,
+) -> Vec<Bacterium> {
+    (0..count)
+        .map(|_| {
+            let position = match placement {
+                Placement::Center => {
+                    // Evenly over a disc, not bunched at its middle
+                    let radius = 0.1 * rng.random::<f32>().sqrt();
+                    let angle = rng.random_range(0.0..TAU);
+                    [radius * angle.cos(), radius * angle.sin()]
+                }
+                Placement::Scattered => [
+                    rng.random_range(-half_width..half_width),
+                    rng.random_range(-1.0..1.0),
+                ],
+                Placement::Edge => [-half_width + 0.05, rng.random_range(-0.9..0.9)],
+            };
+            Bacterium::new(position, rng.random_range(0.0..TAU))
+        })
+        .collect()
+}
+
+/// How a bacterium swims, from the settings of the same names
+#[cfg(test)]
+pub struct Swimming {
+    pub run_speed: f32,
+    pub tumble_rate: f32,
+    pub chemotaxis: f32,
+    pub memory_time: f32,
+    pub rotational_diffusion: f32,
+    pub half_saturation: f32,
+}
+
+/// Move a bacterium on by `dt` seconds with `nutrient` where it is, as
+/// `update_bacteria` in `colony.wgsl` does
+#[cfg(test)]
+pub fn swim(
+    bacterium: &mut Bacterium,
+    nutrient: f32,
+    swimming: &Swimming,
+    dt: f32,
+    half_width: f32,
+    rng: &mut impl Rng,
+) {
+    let change =
+        (nutrient - bacterium.memory) / (nutrient + bacterium.memory + swimming.half_saturation);
+    let rate = swimming.tumble_rate * (-swimming.chemotaxis * change).exp();
+    if rng.random::<f32>() < 1.0 - (-rate * dt).exp() {
+        bacterium.heading = rng.random::<f32>() * TAU;
+    }
+    bacterium.heading +=
+        (rng.random::<f32>() - 0.5) * (24.0 * swimming.rotational_diffusion * dt).sqrt();
+    bacterium.memory += (nutrient - bacterium.memory) * (dt / swimming.memory_time).min(1.0);
+
+    let step = swimming.run_speed * dt;
+    let [x, y] = &mut bacterium.position;
+    *x += step * bacterium.heading.cos();
+    *y += step * bacterium.heading.sin();
+    if x.abs() > half_width {
+        *x = (2.0 * half_width).copysign(*x) - *x;
+        bacterium.heading = std::f32::consts::PI - bacterium.heading;
+    }
+    if y.abs() > 1.0 {
+        *y = 2.0f32.copysign(*y) - *y;
+        bacterium.heading = -bacterium.heading;
+    }
+}
@@ -0,0 +1,93 @@
+pub mod colony;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::ChemotaxisModel;
+
+use crate::simulation::preset_manager::{ChemotaxisPresetManager, Preset};
+
+/// Initialize Chemotaxis presets with built-in configurations
+pub fn init_presets(preset_manager: &mut ChemotaxisPresetManager) {
+    use settings::{NutrientLayout, Placement, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Gradient Climb".to_string(),
+        Settings {
+            nutrient_layout: NutrientLayout::Gradient,
+            placement: Placement::Edge,
+            initial_bacteria: 3000,
+            chemotaxis: 10.0,
+            consumption_rate: 0.2,
+            metabolism: 0.05,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Scattered Grazers".to_string(),
+        Settings {
+            placement: Placement::Scattered,
+            initial_bacteria: 6000,
+            run_speed: 0.08,
+            tumble_rate: 2.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Expanding Ring".to_string(),
+        Settings {
+            nutrient_layout: NutrientLayout::Uniform,
+            placement: Placement::Center,
+            initial_bacteria: 1000,
+            max_bacteria: 80000,
+            chemotaxis: 12.0,
+            consumption_rate: 0.8,
+            growth_yield: 3.0,
+            diffusion: 0.0005,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Blind Swimmers".to_string(),
+        Settings {
+            placement: Placement::Scattered,
+            chemotaxis: 0.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Starving Colony".to_string(),
+        Settings {
+            nutrient_layout: NutrientLayout::Patches,
+            placement: Placement::Scattered,
+            initial_bacteria: 12000,
+            nutrient_decay: 0.1,
+            metabolism: 0.3,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Empty Dish".to_string(),
+        Settings {
+            nutrient_layout: NutrientLayout::Empty,
+            initial_bacteria: 500,
+            metabolism: 0.02,
+            diffusion: 0.005,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,271 @@
+//! # Chemotaxis Settings Module
+//!
+//! Where the nutrient starts and how it spreads, how the bacteria swim and
+//! steer, and how fast they eat, grow and starve.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use crate::simulations::shared::{BackgroundLayer, Boundary, BoundaryConditions};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum NutrientLayout {
+    /// Nothing to eat until some is painted
+    Empty,
+    /// The same everywhere
+    Uniform,
+    /// Round patches scattered at random
+    #[default]
+    Patches,
+    /// Rising from nothing on the left to full on the right
+    Gradient,
+}
+
+impl FromStr for NutrientLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "empty" => Ok(NutrientLayout::Empty),
+            "uniform" => Ok(NutrientLayout::Uniform),
+            "patches" => Ok(NutrientLayout::Patches),
+            "gradient" => Ok(NutrientLayout::Gradient),
+            _ => Err(format!(
+                "Invalid NutrientLayout: '{}'. Expected 'Empty', 'Uniform', 'Patches', or 'Gradient'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Placement {
+    /// A tight colony in the middle
+    #[default]
+    Center,
+    /// Spread evenly over the box
+    Scattered,
+    /// A line down the left wall
+    Edge,
+}
+
+impl FromStr for Placement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "center" => Ok(Placement::Center),
+            "scattered" => Ok(Placement::Scattered),
+            "edge" => Ok(Placement::Edge),
+            _ => Err(format!(
+                "Invalid Placement: '{}'. Expected 'Center', 'Scattered', or 'Edge'",
+                s
+            )),
+        }
+    }
+}
+
+/// What the left mouse button paints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BrushTool {
+    #[default]
+    Nutrient,
+    Bacteria,
+}
+
+impl FromStr for BrushTool {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "nutrient" => Ok(BrushTool::Nutrient),
+            "bacteria" => Ok(BrushTool::Bacteria),
+            _ => Err(format!(
+                "Invalid BrushTool: '{}'. Expected 'Nutrient' or 'Bacteria'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub nutrient_layout: NutrientLayout,
+    pub seed: u32,
+    /// Nutrient cells up the height of the box
+    pub field_resolution: u32,
+    /// How fast the nutrient spreads, in box heights squared per second
+    pub diffusion: f32,
+    /// Share of the nutrient that spoils each second
+    pub nutrient_decay: f32,
+
+    pub initial_bacteria: u32,
+    /// Room for this many bacteria; growth stops once it's full
+    pub max_bacteria: u32,
+    pub placement: Placement,
+
+    /// Swimming speed between tumbles, in box heights per second
+    pub run_speed: f32,
+    /// Tumbles per second while the nutrient isn't changing
+    pub tumble_rate: f32,
+    /// How strongly rising nutrient puts tumbles off, and falling nutrient
+    /// brings them on. 0 leaves the bacteria swimming at random.
+    pub chemotaxis: f32,
+    /// Seconds the bacteria remember the nutrient over, to tell whether
+    /// it's rising
+    pub memory_time: f32,
+    /// Wander in heading while running, in radians squared per second
+    pub rotational_diffusion: f32,
+
+    /// Nutrient each bacterium eats per second when there's plenty
+    pub consumption_rate: f32,
+    /// Nutrient at which the bacteria eat at half their fastest
+    pub half_saturation: f32,
+    /// Energy gained per unit of nutrient eaten; a bacterium divides at 1
+    pub growth_yield: f32,
+    /// Energy spent each second just staying alive; at 0 a bacterium dies
+    pub metabolism: f32,
+
+    /// Length of each bacterium as drawn, in box heights
+    pub rod_length: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+
+    // What the nutrient does at the box's edges
+    #[serde(default = "default_boundaries")]
+    pub boundaries: BoundaryConditions,
+}
+
+/// Walled in on every side, so no nutrient leaks out
+fn default_boundaries() -> BoundaryConditions {
+    BoundaryConditions::uniform(Boundary::Reflective)
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            nutrient_layout: NutrientLayout::Patches,
+            seed: 0,
+            field_resolution: 192,
+            diffusion: 0.002,
+            nutrient_decay: 0.0,
+            initial_bacteria: 2000,
+            max_bacteria: 40000,
+            placement: Placement::Center,
+            run_speed: 0.12,
+            tumble_rate: 1.0,
+            chemotaxis: 6.0,
+            memory_time: 1.0,
+            rotational_diffusion: 0.2,
+            consumption_rate: 0.4,
+            half_saturation: 0.2,
+            growth_yield: 2.0,
+            metabolism: 0.1,
+            rod_length: 0.012,
+            background_layer: BackgroundLayer::default(),
+            boundaries: default_boundaries(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "nutrient_layout",
+            Rule::OneOf(&["Empty", "Uniform", "Patches", "Gradient"]),
+        ),
+        (
+            "seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+        ("field_resolution", Rule::Count { min: 32, max: 1024 }),
+        (
+            "diffusion",
+            Rule::Range {
+                min: 0.0,
+                max: 0.05,
+            },
+        ),
+        ("nutrient_decay", Rule::Range { min: 0.0, max: 0.9 }),
+        (
+            "initial_bacteria",
+            Rule::Count {
+                min: 1,
+                max: 200000,
+            },
+        ),
+        (
+            "max_bacteria",
+            Rule::Count {
+                min: 100,
+                max: 200000,
+            },
+        ),
+        ("placement", Rule::OneOf(&["Center", "Scattered", "Edge"])),
+        ("run_speed", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "tumble_rate",
+            Rule::Range {
+                min: 0.0,
+                max: 20.0,
+            },
+        ),
+        (
+            "chemotaxis",
+            Rule::Range {
+                min: 0.0,
+                max: 50.0,
+            },
+        ),
+        (
+            "memory_time",
+            Rule::Range {
+                min: 0.05,
+                max: 10.0,
+            },
+        ),
+        ("rotational_diffusion", Rule::Range { min: 0.0, max: 5.0 }),
+        ("consumption_rate", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "half_saturation",
+            Rule::Range {
+                min: 0.01,
+                max: 2.0,
+            },
+        ),
+        (
+            "growth_yield",
+            Rule::Range {
+                min: 0.0,
+                max: 20.0,
+            },
+        ),
+        ("metabolism", Rule::Range { min: 0.0, max: 2.0 }),
+        (
+            "rod_length",
+            Rule::Range {
+                min: 0.002,
+                max: 0.05,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("nutrient_layout", SettingCategory::Generators),
+    ("seed", SettingCategory::Generators),
+    ("initial_bacteria", SettingCategory::Generators),
+    ("placement", SettingCategory::Generators),
+]);
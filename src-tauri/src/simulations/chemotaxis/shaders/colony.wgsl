@@ -0,0 +1,128 @@
+// The bacteria: eating, swimming by run and tumble, dying, dividing into
+// free slots, and being dropped in by the brush. colony.rs explains the
+// swimming and keeps a copy of it for the tests.
+//
+// A slot holds a bacterium while its `occupied` flag is set. Births claim
+// a free slot by swapping its flag from 0 to 1, so two can't land in the
+// same one.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> bacteria: array<Bacterium>;
+@group(0) @binding(2) var<storage, read_write> occupied: array<atomic<u32>>;
+@group(0) @binding(3) var<storage, read> nutrient: array<f32>;
+@group(0) @binding(4) var<storage, read_write> eaten: array<atomic<u32>>;
+// The number alive, counted afresh each frame
+@group(0) @binding(5) var<storage, read_write> population: atomic<u32>;
+
+// Tries at finding a free slot before giving up until next frame
+const CLAIM_ATTEMPTS: u32 = 4u;
+
+fn claim_slot(seed: u32) -> i32 {
+    var state = seed;
+    for (var attempt = 0u; attempt < CLAIM_ATTEMPTS; attempt++) {
+        state = hash(state);
+        let slot = state % params.capacity;
+        if (atomicCompareExchangeWeak(&occupied[slot], 0u, 1u).exchanged) {
+            return i32(slot);
+        }
+    }
+    return -1;
+}
+
+@compute @workgroup_size(64)
+fn update_bacteria(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if (index >= params.capacity || atomicLoad(&occupied[index]) == 0u) {
+        return;
+    }
+    var bacterium = bacteria[index];
+    var state = hash(index ^ hash(params.frame));
+
+    // Eat, faster the more there is but never more than a full rate
+    let cell = cell_index(cell_of(bacterium.position));
+    let here = max(nutrient[cell], 0.0);
+    let bite = min(
+        params.consumption_rate * here / (here + params.half_saturation) * params.dt,
+        here
+    );
+    atomicAdd(&eaten[cell], u32(bite * EAT_SCALE));
+    bacterium.energy += bite * params.growth_yield - params.metabolism * params.dt;
+    bacterium.age += params.dt;
+    if (bacterium.energy <= 0.0) {
+        atomicStore(&occupied[index], 0u);
+        return;
+    }
+
+    // Tumble less while the nutrient's been rising, more while it's falling
+    let change = (here - bacterium.memory) / (here + bacterium.memory + params.half_saturation);
+    let rate = params.tumble_rate * exp(-params.chemotaxis * change);
+    if (random(&state) < 1.0 - exp(-rate * params.dt)) {
+        bacterium.heading = random(&state) * TAU;
+    }
+    bacterium.heading += (random(&state) - 0.5) * sqrt(24.0 * params.rotational_diffusion * params.dt);
+    bacterium.memory += (here - bacterium.memory) * min(params.dt / params.memory_time, 1.0);
+
+    // Run, bouncing off the walls
+    let step = params.run_speed * params.dt;
+    var position = bacterium.position + step * vec2<f32>(cos(bacterium.heading), sin(bacterium.heading));
+    if (abs(position.x) > params.aspect) {
+        position.x = sign(position.x) * 2.0 * params.aspect - position.x;
+        bacterium.heading = PI - bacterium.heading;
+    }
+    if (abs(position.y) > 1.0) {
+        position.y = sign(position.y) * 2.0 - position.y;
+        bacterium.heading = -bacterium.heading;
+    }
+    bacterium.position = position;
+    bacterium.heading = bacterium.heading - TAU * floor(bacterium.heading / TAU);
+
+    bacteria[index] = bacterium;
+    atomicAdd(&population, 1u);
+}
+
+// Bacteria with enough energy split it with a daughter swimming off the
+// other way. Without a free slot they wait and try again next frame.
+@compute @workgroup_size(64)
+fn divide(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if (index >= params.capacity || atomicLoad(&occupied[index]) == 0u) {
+        return;
+    }
+    var bacterium = bacteria[index];
+    if (bacterium.energy < 1.0) {
+        return;
+    }
+    let slot = claim_slot(index * 2654435761u + params.frame * 40503u);
+    if (slot < 0) {
+        return;
+    }
+    bacterium.energy *= 0.5;
+    bacterium.age = 0.0;
+    var daughter = bacterium;
+    daughter.heading = bacterium.heading + PI;
+    bacteria[slot] = daughter;
+    bacteria[index] = bacterium;
+    atomicAdd(&population, 1u);
+}
+
+// New bacteria scattered over the brush
+@compute @workgroup_size(64)
+fn spawn(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.spawn_count) {
+        return;
+    }
+    var state = hash(id.x * 747796405u + params.frame);
+    let slot = claim_slot(state);
+    if (slot < 0) {
+        return;
+    }
+    let radius = params.brush_radius * sqrt(random(&state));
+    let angle = random(&state) * TAU;
+    let offset = radius * vec2<f32>(cos(angle), sin(angle));
+    let position = clamp(
+        params.brush_center + offset,
+        vec2<f32>(-params.aspect, -1.0),
+        vec2<f32>(params.aspect, 1.0)
+    );
+    bacteria[slot] = Bacterium(position, random(&state) * TAU, 0.0, 0.5, 0.0, 0.0, 0.0);
+}
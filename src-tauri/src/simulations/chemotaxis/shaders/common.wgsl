@@ -0,0 +1,84 @@
+// Shared by every chemotaxis pass, each of which binds `params`. The box
+// is `aspect` wide either side of the origin and 1 high either side, the
+// nutrient grid laid over it row by row from the bottom.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    grid_width: u32,
+    grid_height: u32,
+    capacity: u32,
+    frame: u32,
+    dt: f32,
+    run_speed: f32,
+    tumble_rate: f32,
+    chemotaxis: f32,
+    memory_time: f32,
+    rotational_diffusion: f32,
+    consumption_rate: f32,
+    half_saturation: f32,
+    growth_yield: f32,
+    metabolism: f32,
+    // How far each diffusion pass blends a cell toward its neighbors, and
+    // the share of nutrient kept through the pass
+    diffusion_blend: f32,
+    decay_keep: f32,
+    brush_center: vec2<f32>,
+    brush_radius: f32,
+    brush_strength: f32,
+    brush_erase: u32,
+    spawn_count: u32,
+    rod_length: f32,
+    // Box heights per pixel, for antialiasing
+    pixel_size: f32,
+    // Edge conditions for the nutrient, see boundary.wgsl
+    boundaries: u32,
+    _pad: u32,
+}
+
+struct Bacterium {
+    position: vec2<f32>,
+    heading: f32,
+    memory: f32,
+    energy: f32,
+    age: f32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+const PI: f32 = 3.14159265359;
+const TAU: f32 = 6.28318530718;
+
+// Nutrient eaten is added up per cell in fixed point, there being no
+// atomic floats
+const EAT_SCALE: f32 = 1048576.0;
+
+fn cell_size() -> f32 {
+    return 2.0 / f32(params.grid_height);
+}
+
+fn cell_of(position: vec2<f32>) -> vec2<i32> {
+    let cell = vec2<i32>(floor((position + vec2<f32>(params.aspect, 1.0)) / cell_size()));
+    return clamp(cell, vec2<i32>(0), vec2<i32>(i32(params.grid_width) - 1, i32(params.grid_height) - 1));
+}
+
+fn cell_index(cell: vec2<i32>) -> u32 {
+    return u32(cell.y) * params.grid_width + u32(cell.x);
+}
+
+fn hash(seed: u32) -> u32 {
+    var x = seed;
+    x ^= x >> 16u;
+    x *= 0x7feb352du;
+    x ^= x >> 15u;
+    x *= 0x846ca68bu;
+    x ^= x >> 16u;
+    return x;
+}
+
+// Uniform in [0, 1), advancing `state` for the next draw
+fn random(state: ptr<function, u32>) -> f32 {
+    *state = hash(*state);
+    return f32(*state >> 8u) / 16777216.0;
+}
@@ -0,0 +1,11 @@
+pub const COLONY_SHADER: &str = concat!(include_str!("common.wgsl"), include_str!("colony.wgsl"));
+pub const NUTRIENT_SHADER: &str = concat!(
+    include_str!("../../shared/boundary.wgsl"),
+    include_str!("common.wgsl"),
+    include_str!("nutrient.wgsl")
+);
+pub const RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("common.wgsl"),
+    include_str!("render.wgsl")
+);
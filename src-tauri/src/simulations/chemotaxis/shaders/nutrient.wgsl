@@ -0,0 +1,67 @@
+// The nutrient field: spreading, spoiling and being eaten, and painted by
+// the brush. Diffusion is the same blend toward the four neighbors as
+// slime mold's trail diffusion, run as many passes a frame as the spread
+// needs to stay stable. Neighbors past the edges are read through the
+// boundary conditions, with no nutrient beyond absorbing ones.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> nutrient_in: array<f32>;
+@group(0) @binding(2) var<storage, read_write> nutrient_out: array<f32>;
+@group(0) @binding(3) var<storage, read_write> eaten: array<atomic<u32>>;
+@group(0) @binding(4) var<storage, read> boundary_mask: array<u32>;
+
+fn grid_bounds() -> vec2<u32> {
+    return vec2<u32>(params.grid_width, params.grid_height);
+}
+
+fn nutrient_at(center: vec2<i32>, offset: vec2<i32>) -> f32 {
+    let cell = boundary_cell(params.boundaries, center + offset, center, grid_bounds());
+    if (cell.x < 0) {
+        return 0.0;
+    }
+    return nutrient_in[cell_index(cell)];
+}
+
+@compute @workgroup_size(8, 8)
+fn diffuse(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height) {
+        return;
+    }
+    let cell = vec2<i32>(id.xy);
+    let index = cell_index(cell);
+    let center = nutrient_in[index];
+    // Only the first pass of a frame finds anything eaten
+    let bitten = f32(atomicExchange(&eaten[index], 0u)) / EAT_SCALE;
+    // Cells walled off by the mask keep what they hold
+    if (boundary_walled(params.boundaries, cell, grid_bounds())) {
+        nutrient_out[index] = center;
+        return;
+    }
+    let neighbors = nutrient_at(cell, vec2<i32>(-1, 0))
+        + nutrient_at(cell, vec2<i32>(1, 0))
+        + nutrient_at(cell, vec2<i32>(0, -1))
+        + nutrient_at(cell, vec2<i32>(0, 1));
+    let blended = center * (1.0 - params.diffusion_blend) + neighbors * (params.diffusion_blend * 0.25);
+    nutrient_out[index] = max(blended * params.decay_keep - bitten, 0.0);
+}
+
+// Lays nutrient down toward the brush strength, or wipes it away, falling
+// off toward the brush's edge
+@compute @workgroup_size(8, 8)
+fn paint(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height) {
+        return;
+    }
+    let center = (vec2<f32>(id.xy) + 0.5) * cell_size() - vec2<f32>(params.aspect, 1.0);
+    let distance = length(center - params.brush_center);
+    if (distance > params.brush_radius) {
+        return;
+    }
+    let factor = 1.0 - distance / params.brush_radius;
+    let index = cell_index(vec2<i32>(id.xy));
+    var target_value = params.brush_strength;
+    if (params.brush_erase != 0u) {
+        target_value = 0.0;
+    }
+    nutrient_in[index] = mix(nutrient_in[index], target_value, factor);
+}
@@ -0,0 +1,113 @@
+// Draws the nutrient as a dim wash of the color scheme, brighter where
+// there's more, with the bacteria over it as little rods pointing the way
+// they swim, brighter the more energy they've saved.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> bacteria: array<Bacterium>;
+@group(0) @binding(2) var<storage, read> occupied: array<u32>;
+@group(0) @binding(3) var<storage, read> nutrient: array<f32>;
+@group(0) @binding(4) var<storage, read> lut_data: array<u32>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+fn to_ndc(point: vec2<f32>) -> vec2<f32> {
+    return (vec2<f32>(point.x / params.aspect, point.y) - params.view_center) * params.view_zoom;
+}
+
+fn from_ndc(ndc: vec2<f32>) -> vec2<f32> {
+    let world = ndc / params.view_zoom + params.view_center;
+    return vec2<f32>(world.x * params.aspect, world.y);
+}
+
+fn nutrient_cell(cell: vec2<i32>) -> f32 {
+    let clamped = clamp(cell, vec2<i32>(0), vec2<i32>(i32(params.grid_width) - 1, i32(params.grid_height) - 1));
+    return nutrient[cell_index(clamped)];
+}
+
+// Blended between the four nearest cell centers
+fn nutrient_at(point: vec2<f32>) -> f32 {
+    let grid = (point + vec2<f32>(params.aspect, 1.0)) / cell_size() - 0.5;
+    let base = vec2<i32>(floor(grid));
+    let t = fract(grid);
+    let bottom = mix(nutrient_cell(base), nutrient_cell(base + vec2<i32>(1, 0)), t.x);
+    let top = mix(nutrient_cell(base + vec2<i32>(0, 1)), nutrient_cell(base + vec2<i32>(1, 1)), t.x);
+    return mix(bottom, top, t.y);
+}
+
+struct FieldOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// One triangle covering the screen
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> FieldOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return FieldOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+@fragment
+fn fs_field(input: FieldOutput) -> @location(0) vec4<f32> {
+    let point = from_ndc(input.ndc);
+    if (abs(point.x) > params.aspect || abs(point.y) > 1.0) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    let amount = clamp(nutrient_at(point), 0.0, 1.0);
+    return vec4<f32>(lut_color(amount * 0.55) * mix(0.15, 0.7, amount), 1.0);
+}
+
+struct RodOutput {
+    @builtin(position) position: vec4<f32>,
+    // Along and across the rod, in box heights from its middle
+    @location(0) local: vec2<f32>,
+    @location(1) @interpolate(flat) energy: f32,
+}
+
+// A rectangle around the rod, a pixel wider all round for the soft edge.
+// Empty slots collapse to nothing.
+@vertex
+fn vs_bacterium(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> RodOutput {
+    if (occupied[instance_index] == 0u) {
+        return RodOutput(vec4<f32>(0.0, 0.0, 0.0, 1.0), vec2<f32>(0.0), 0.0);
+    }
+    let bacterium = bacteria[instance_index];
+    let corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let extent = vec2<f32>(0.5 * params.rod_length, 0.2 * params.rod_length) + params.pixel_size;
+    let local = corners[vertex_index] * extent;
+    let direction = vec2<f32>(cos(bacterium.heading), sin(bacterium.heading));
+    let normal = vec2<f32>(-direction.y, direction.x);
+    let point = bacterium.position + direction * local.x + normal * local.y;
+    return RodOutput(vec4<f32>(to_ndc(point), 0.0, 1.0), local, bacterium.energy);
+}
+
+@fragment
+fn fs_bacterium(input: RodOutput) -> @location(0) vec4<f32> {
+    let radius = 0.2 * params.rod_length;
+    let half_span = max(0.5 * params.rod_length - radius, 0.0);
+    let along = clamp(input.local.x, -half_span, half_span);
+    let distance = length(input.local - vec2<f32>(along, 0.0)) - radius;
+    let half_pixel = params.pixel_size * 0.5;
+    let coverage = 1.0 - smoothstep(-half_pixel, half_pixel, distance);
+    if (coverage <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(lut_color(0.6 + 0.4 * clamp(input.energy, 0.0, 1.0)), coverage);
+}
@@ -0,0 +1,1092 @@
+//! # Chemotaxis Simulation Module
+//!
+//! Bacteria swimming by run and tumble through a nutrient field the mouse
+//! paints, eating it down as the colony grows; see [`colony`] for how they
+//! swim, eat and divide.
+//!
+//! ## Technical Overview
+//!
+//! Everything runs on the GPU once seeded. Each frame:
+//! 1. Every bacterium eats from its cell, spends energy living, dies or
+//!    swims on, and is counted.
+//! 2. Those with energy to spare divide into free slots.
+//! 3. A few diffusion passes ping-pong the nutrient between two buffers,
+//!    the first taking out what was eaten.
+//! 4. The field is drawn as a wash of the color scheme and the bacteria as
+//!    rods over it.
+//!
+//! The count of the living is copied back without waiting on it, so the
+//! population shown is a frame or two behind.
+//!
+//! [`colony`]: super::colony
+
+use bytemuck::{Pod, Zeroable};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, BlendState, Buffer,
+    BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, ShaderModule,
+    ShaderStages, SurfaceConfiguration, TextureFormat, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::ping_pong_buffers::PingPongBuffers;
+use crate::simulations::shared::{
+    BackgroundLayer, BoundaryConditions, ColorScheme, ColorSchemeManager,
+};
+use crate::simulations::traits::{FAST_FORWARD_STEP_TIME, Simulation};
+
+use super::colony::{self, Bacterium};
+use super::settings::{BrushTool, NutrientLayout, Placement, Settings};
+use super::shaders::{COLONY_SHADER, NUTRIENT_SHADER, RENDER_SHADER};
+use super::state::State;
+
+/// Longest step taken in one frame, so a stall doesn't send the bacteria
+/// leaping across the nutrient
+const MAX_FRAME_TIME: f32 = 1.0 / 30.0;
+
+/// Bacteria dropped each time the bacteria brush is dragged
+const BACTERIA_PER_DAB: u32 = 24;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    grid_width: u32,
+    grid_height: u32,
+    capacity: u32,
+    frame: u32,
+    dt: f32,
+    run_speed: f32,
+    tumble_rate: f32,
+    chemotaxis: f32,
+    memory_time: f32,
+    rotational_diffusion: f32,
+    consumption_rate: f32,
+    half_saturation: f32,
+    growth_yield: f32,
+    metabolism: f32,
+    diffusion_blend: f32,
+    decay_keep: f32,
+    brush_center: [f32; 2],
+    brush_radius: f32,
+    brush_strength: f32,
+    brush_erase: u32,
+    spawn_count: u32,
+    rod_length: f32,
+    pixel_size: f32,
+    boundaries: u32,
+    _pad: u32,
+}
+
+/// Where the brush lands and what it does there
+struct Dab {
+    center: [f32; 2],
+    erase: bool,
+    spawn_count: u32,
+}
+
+/// Everything the colony's bind groups point at besides the colony itself
+#[derive(Debug)]
+struct Resources {
+    colony_bind_group_layout: BindGroupLayout,
+    nutrient_bind_group_layout: BindGroupLayout,
+    render_bind_group_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    population_buffer: Buffer,
+    population_staging_buffer: Buffer,
+}
+
+/// The nutrient grid and the bacteria's slots on the GPU, remade when the
+/// grid changes size or the colony's room does. Bind groups come in pairs,
+/// one for each nutrient buffer being the current one.
+#[derive(Debug)]
+struct Colony {
+    width: u32,
+    height: u32,
+    capacity: u32,
+    nutrient: PingPongBuffers,
+    bacteria: Buffer,
+    occupied: Buffer,
+    eaten: Buffer,
+    boundary_mask: Buffer,
+    colony_bind_groups: [BindGroup; 2],
+    diffuse_bind_groups: [BindGroup; 2],
+    render_bind_groups: [BindGroup; 2],
+}
+
+impl Colony {
+    fn new(
+        device: &Device,
+        (width, height): (u32, u32),
+        capacity: u32,
+        resources: &Resources,
+    ) -> Self {
+        let cell_bytes = (width * height) as u64 * std::mem::size_of::<f32>() as u64;
+        let nutrient = PingPongBuffers::new(
+            device,
+            cell_bytes,
+            BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            "Chemotaxis Nutrient Buffer",
+        );
+        let eaten = device.create_buffer(&BufferDescriptor {
+            label: Some("Chemotaxis Eaten Buffer"),
+            size: cell_bytes,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let boundary_mask = device.create_buffer(&BufferDescriptor {
+            label: Some("Chemotaxis Boundary Mask Buffer"),
+            size: cell_bytes,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bacteria = device.create_buffer(&BufferDescriptor {
+            label: Some("Chemotaxis Bacteria Buffer"),
+            size: capacity as u64 * std::mem::size_of::<Bacterium>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let occupied = device.create_buffer(&BufferDescriptor {
+            label: Some("Chemotaxis Occupied Buffer"),
+            size: capacity as u64 * std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Index 0 reads the current buffer before any swap
+        let (first, second) = (nutrient.current_buffer(), nutrient.inactive_buffer());
+        let colony_bind_group = |label, nutrient: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.colony_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, &bacteria),
+                    resource_helpers::buffer_entry(2, &occupied),
+                    resource_helpers::buffer_entry(3, nutrient),
+                    resource_helpers::buffer_entry(4, &eaten),
+                    resource_helpers::buffer_entry(5, &resources.population_buffer),
+                ],
+            })
+        };
+        let colony_bind_groups = [
+            colony_bind_group("Chemotaxis Colony Bind Group A", first),
+            colony_bind_group("Chemotaxis Colony Bind Group B", second),
+        ];
+
+        let diffuse_bind_group = |label, from: &Buffer, to: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.nutrient_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, from),
+                    resource_helpers::buffer_entry(2, to),
+                    resource_helpers::buffer_entry(3, &eaten),
+                    resource_helpers::buffer_entry(4, &boundary_mask),
+                ],
+            })
+        };
+        let diffuse_bind_groups = [
+            diffuse_bind_group("Chemotaxis Diffuse Bind Group A", first, second),
+            diffuse_bind_group("Chemotaxis Diffuse Bind Group B", second, first),
+        ];
+
+        let render_bind_group = |label, nutrient: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.render_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, &bacteria),
+                    resource_helpers::buffer_entry(2, &occupied),
+                    resource_helpers::buffer_entry(3, nutrient),
+                    resource_helpers::buffer_entry(4, &resources.lut_buffer),
+                ],
+            })
+        };
+        let render_bind_groups = [
+            render_bind_group("Chemotaxis Render Bind Group A", first),
+            render_bind_group("Chemotaxis Render Bind Group B", second),
+        ];
+
+        Self {
+            width,
+            height,
+            capacity,
+            nutrient,
+            bacteria,
+            occupied,
+            eaten,
+            boundary_mask,
+            colony_bind_groups,
+            diffuse_bind_groups,
+            render_bind_groups,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ChemotaxisModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    update_pipeline: ComputePipeline,
+    divide_pipeline: ComputePipeline,
+    spawn_pipeline: ComputePipeline,
+    diffuse_pipeline: ComputePipeline,
+    paint_pipeline: ComputePipeline,
+    field_pipeline: RenderPipeline,
+    bacterium_pipeline: RenderPipeline,
+    resources: Resources,
+    colony: Colony,
+
+    // Frames run, to give every frame its own random numbers
+    frame: u32,
+    // Set once the population copied back last is ready to read; None
+    // while no copy is on its way
+    population_ready: Option<Arc<AtomicBool>>,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl ChemotaxisModel {
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let colony_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Chemotaxis Colony Shader"),
+            source: wgpu::ShaderSource::Wgsl(COLONY_SHADER.into()),
+        });
+        let nutrient_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Chemotaxis Nutrient Shader"),
+            source: wgpu::ShaderSource::Wgsl(NUTRIENT_SHADER.into()),
+        });
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Chemotaxis Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Chemotaxis Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Chemotaxis LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let population_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Chemotaxis Population Buffer"),
+            size: std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let population_staging_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Chemotaxis Population Staging Buffer"),
+            size: std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let colony_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Chemotaxis Colony Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, true),
+                    resource_helpers::storage_buffer_entry(4, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(5, ShaderStages::COMPUTE, false),
+                ],
+            });
+
+        let nutrient_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Chemotaxis Nutrient Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(4, ShaderStages::COMPUTE, true),
+                ],
+            });
+
+        let render_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Chemotaxis Render Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(
+                        0,
+                        ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    ),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::VERTEX, true),
+                    resource_helpers::storage_buffer_entry(2, ShaderStages::VERTEX, true),
+                    resource_helpers::storage_buffer_entry(3, ShaderStages::FRAGMENT, true),
+                    resource_helpers::storage_buffer_entry(4, ShaderStages::FRAGMENT, true),
+                ],
+            });
+
+        let compute_pipeline = |label: &str, layout: &BindGroupLayout, module, entry_point| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(&format!("Chemotaxis {} Pipeline", label)),
+                layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                    label: Some(&format!("Chemotaxis {} Pipeline Layout", label)),
+                    bind_group_layouts: &[layout],
+                    push_constant_ranges: &[],
+                })),
+                module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let update_pipeline = compute_pipeline(
+            "Update",
+            &colony_bind_group_layout,
+            &colony_module,
+            "update_bacteria",
+        );
+        let divide_pipeline = compute_pipeline(
+            "Divide",
+            &colony_bind_group_layout,
+            &colony_module,
+            "divide",
+        );
+        let spawn_pipeline =
+            compute_pipeline("Spawn", &colony_bind_group_layout, &colony_module, "spawn");
+        let diffuse_pipeline = compute_pipeline(
+            "Diffuse",
+            &nutrient_bind_group_layout,
+            &nutrient_module,
+            "diffuse",
+        );
+        let paint_pipeline = compute_pipeline(
+            "Paint",
+            &nutrient_bind_group_layout,
+            &nutrient_module,
+            "paint",
+        );
+
+        let field_pipeline = create_pipeline(
+            device,
+            "Chemotaxis Field Pipeline",
+            &render_bind_group_layout,
+            &render_module,
+            ("vs_fullscreen", "fs_field"),
+            surface_config.format,
+            BlendState::REPLACE,
+        );
+        let bacterium_pipeline = create_pipeline(
+            device,
+            "Chemotaxis Bacterium Pipeline",
+            &render_bind_group_layout,
+            &render_module,
+            ("vs_bacterium", "fs_bacterium"),
+            surface_config.format,
+            BlendState::ALPHA_BLENDING,
+        );
+
+        let resources = Resources {
+            colony_bind_group_layout,
+            nutrient_bind_group_layout,
+            render_bind_group_layout,
+            params_buffer,
+            lut_buffer,
+            population_buffer,
+            population_staging_buffer,
+        };
+        // Replaced by rebuild_colony once the model exists
+        let colony = Colony::new(device, (1, 1), 1, &resources);
+
+        let mut model = Self {
+            settings,
+            state,
+            update_pipeline,
+            divide_pipeline,
+            spawn_pipeline,
+            diffuse_pipeline,
+            paint_pipeline,
+            field_pipeline,
+            bacterium_pipeline,
+            resources,
+            colony,
+            frame: 0,
+            population_ready: None,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.rebuild_colony(device, queue)?;
+        Ok(model)
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// Remake the grid and slots for the window and settings, and seed them
+    fn rebuild_colony(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let (width, height) = colony::grid_size(self.settings.field_resolution, self.aspect());
+        let capacity = self.settings.max_bacteria.max(1);
+        self.colony = Colony::new(device, (width, height), capacity, &self.resources);
+        self.state.grid_width = width;
+        self.state.grid_height = height;
+        if let Err(e) = self.write_boundary_mask(&self.settings.boundaries, queue) {
+            tracing::warn!("Chemotaxis grid remade without its boundary mask: {}", e);
+        }
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Upload the walls of `boundaries`' mask to the grid, if it has one
+    fn write_boundary_mask(
+        &self,
+        boundaries: &BoundaryConditions,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let (width, height) = (self.colony.width, self.colony.height);
+        if let Some(cells) = boundaries.mask_cells(width, height, true)? {
+            queue.write_buffer(&self.colony.boundary_mask, 0, bytemuck::cast_slice(&cells));
+        }
+        Ok(())
+    }
+
+    /// Lay the nutrient out and drop the starting bacteria in, from the seed
+    fn seed(&mut self, queue: &Arc<Queue>) {
+        let mut rng = StdRng::seed_from_u64(self.settings.seed as u64);
+        let cells = (self.colony.width * self.colony.height) as usize;
+        let nutrient = colony::seed_nutrient(
+            self.settings.nutrient_layout,
+            (self.colony.width, self.colony.height),
+            &mut rng,
+        );
+        queue.write_buffer(
+            self.colony.nutrient.current_buffer(),
+            0,
+            bytemuck::cast_slice(&nutrient),
+        );
+        queue.write_buffer(
+            &self.colony.eaten,
+            0,
+            bytemuck::cast_slice(&vec![0u32; cells]),
+        );
+
+        let count = self.settings.initial_bacteria.min(self.colony.capacity) as usize;
+        let bacteria =
+            colony::seed_bacteria(count, self.settings.placement, self.aspect(), &mut rng);
+        let mut occupied = vec![0u32; self.colony.capacity as usize];
+        occupied[..count].fill(1);
+        if !bacteria.is_empty() {
+            queue.write_buffer(&self.colony.bacteria, 0, bytemuck::cast_slice(&bacteria));
+        }
+        queue.write_buffer(&self.colony.occupied, 0, bytemuck::cast_slice(&occupied));
+
+        self.state.population = count as u32;
+        self.state.time = 0.0;
+    }
+
+    /// Write the params for a frame `dt` long, with the brush at `dab` if
+    /// it's down, and return the diffusion passes the frame needs
+    fn write_params(&self, queue: &Arc<Queue>, dt: f32, dab: Option<&Dab>) -> u32 {
+        let settings = &self.settings;
+        let (iterations, diffusion_blend) =
+            colony::diffusion_steps(settings.diffusion, dt, self.colony.height);
+        let decay_keep = (1.0 - settings.nutrient_decay).powf(dt / iterations as f32);
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            grid_width: self.colony.width,
+            grid_height: self.colony.height,
+            capacity: self.colony.capacity,
+            frame: self.frame,
+            dt,
+            run_speed: settings.run_speed,
+            tumble_rate: settings.tumble_rate,
+            chemotaxis: settings.chemotaxis,
+            memory_time: settings.memory_time.max(1e-3),
+            rotational_diffusion: settings.rotational_diffusion,
+            consumption_rate: settings.consumption_rate,
+            half_saturation: settings.half_saturation.max(1e-3),
+            growth_yield: settings.growth_yield,
+            metabolism: settings.metabolism,
+            diffusion_blend,
+            decay_keep,
+            brush_center: dab.map_or([0.0; 2], |dab| dab.center),
+            brush_radius: self.state.cursor_size.max(1e-3),
+            brush_strength: self.state.cursor_strength,
+            brush_erase: dab.is_some_and(|dab| dab.erase) as u32,
+            spawn_count: dab.map_or(0, |dab| dab.spawn_count),
+            rod_length: settings.rod_length,
+            pixel_size: 2.0 / (self.camera.zoom * self.height.max(1) as f32),
+            // The grid's rows run up the box
+            boundaries: settings.boundaries.packed(true),
+            _pad: 0,
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+        iterations
+    }
+
+    /// Eat, swim, divide and diffuse for `iterations` diffusion passes
+    fn encode_step(&mut self, encoder: &mut wgpu::CommandEncoder, iterations: u32) {
+        encoder.clear_buffer(&self.resources.population_buffer, 0, None);
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Chemotaxis Step Pass"),
+            timestamp_writes: None,
+        });
+        let colony_bind_group =
+            &self.colony.colony_bind_groups[self.colony.nutrient.current_index()];
+        let workgroups = self.colony.capacity.div_ceil(64);
+        compute_pass.set_pipeline(&self.update_pipeline);
+        compute_pass.set_bind_group(0, colony_bind_group, &[]);
+        compute_pass.dispatch_workgroups(workgroups, 1, 1);
+        compute_pass.set_pipeline(&self.divide_pipeline);
+        compute_pass.dispatch_workgroups(workgroups, 1, 1);
+
+        compute_pass.set_pipeline(&self.diffuse_pipeline);
+        for _ in 0..iterations {
+            compute_pass.set_bind_group(
+                0,
+                &self.colony.diffuse_bind_groups[self.colony.nutrient.current_index()],
+                &[],
+            );
+            compute_pass.dispatch_workgroups(
+                self.colony.width.div_ceil(8),
+                self.colony.height.div_ceil(8),
+                1,
+            );
+            self.colony.nutrient.swap();
+        }
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Chemotaxis Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_bind_group(
+            0,
+            &self.colony.render_bind_groups[self.colony.nutrient.current_index()],
+            &[],
+        );
+        render_pass.set_pipeline(&self.field_pipeline);
+        render_pass.draw(0..3, 0..1);
+        render_pass.set_pipeline(&self.bacterium_pipeline);
+        render_pass.draw(0..6, 0..self.colony.capacity);
+    }
+
+    /// Pick up the population if its copy has arrived, and start another
+    /// copy along with `encoder` if none is on its way
+    fn read_population(
+        &mut self,
+        device: &Arc<Device>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> bool {
+        // Only advances the mapping; never waits on the GPU
+        let _ = device.poll(wgpu::wgt::PollType::Poll);
+        if let Some(ready) = &self.population_ready {
+            if !ready.load(Ordering::Acquire) {
+                return false;
+            }
+            let staging = &self.resources.population_staging_buffer;
+            {
+                let data = staging.slice(..).get_mapped_range();
+                self.state.population = *bytemuck::from_bytes::<u32>(&data);
+            }
+            staging.unmap();
+            self.population_ready = None;
+        }
+        encoder.copy_buffer_to_buffer(
+            &self.resources.population_buffer,
+            0,
+            &self.resources.population_staging_buffer,
+            0,
+            std::mem::size_of::<u32>() as u64,
+        );
+        true
+    }
+
+    /// Map the staging buffer once the copy just submitted lands
+    fn start_population_mapping(&mut self) {
+        let ready = Arc::new(AtomicBool::new(false));
+        let flag = ready.clone();
+        self.resources
+            .population_staging_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                flag.store(result.is_ok(), Ordering::Release);
+            });
+        self.population_ready = Some(ready);
+    }
+
+    /// Send the brush down at `dab`: painting or wiping nutrient, or
+    /// dropping bacteria in
+    fn dab(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, dab: Dab) {
+        self.write_params(queue, 0.0, Some(&dab));
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Chemotaxis Brush"),
+        });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Chemotaxis Brush Pass"),
+                timestamp_writes: None,
+            });
+            let current = self.colony.nutrient.current_index();
+            if dab.spawn_count > 0 {
+                compute_pass.set_pipeline(&self.spawn_pipeline);
+                compute_pass.set_bind_group(0, &self.colony.colony_bind_groups[current], &[]);
+                compute_pass.dispatch_workgroups(dab.spawn_count.div_ceil(64), 1, 1);
+            } else {
+                compute_pass.set_pipeline(&self.paint_pipeline);
+                compute_pass.set_bind_group(0, &self.colony.diffuse_bind_groups[current], &[]);
+                compute_pass.dispatch_workgroups(
+                    self.colony.width.div_ceil(8),
+                    self.colony.height.div_ceil(8),
+                    1,
+                );
+            }
+        }
+        queue.submit([encoder.finish()]);
+        // Each dab scatters its bacteria differently
+        self.frame = self.frame.wrapping_add(1);
+    }
+}
+
+fn create_pipeline(
+    device: &Device,
+    label: &str,
+    bind_group_layout: &BindGroupLayout,
+    module: &ShaderModule,
+    (vertex_entry, fragment_entry): (&str, &str),
+    format: TextureFormat,
+    blend: BlendState,
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(&format!("{} Layout", label)),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex_entry),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for ChemotaxisModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        let dt = delta_time.min(MAX_FRAME_TIME);
+        let iterations = self.write_params(queue, dt, None);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Chemotaxis Render"),
+        });
+        self.encode_step(&mut encoder, iterations);
+        let copying = self.read_population(device, &mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        if copying {
+            self.start_population_mapping();
+        }
+
+        self.frame = self.frame.wrapping_add(1);
+        self.state.time += dt;
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.write_params(queue, 0.0, None);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Chemotaxis Render Paused"),
+        });
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn fast_forward(
+        &mut self,
+        steps: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // The params carry the frame number the bacteria draw their random
+        // numbers from, so each step is its own submission
+        let dt = FAST_FORWARD_STEP_TIME;
+        for _ in 0..steps {
+            let iterations = self.write_params(queue, dt, None);
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Chemotaxis Fast Forward"),
+            });
+            self.encode_step(&mut encoder, iterations);
+            queue.submit([encoder.finish()]);
+            self.frame = self.frame.wrapping_add(1);
+            self.state.time += dt;
+        }
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.rebuild_colony(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let center = [world_x * self.aspect(), world_y];
+        // Left paints with the current tool, right wipes nutrient away
+        let dab = match (mouse_button, self.state.brush_tool) {
+            (0, BrushTool::Nutrient) => Dab {
+                center,
+                erase: false,
+                spawn_count: 0,
+            },
+            (0, BrushTool::Bacteria) => Dab {
+                center,
+                erase: false,
+                spawn_count: BACTERIA_PER_DAB,
+            },
+            (2, _) => Dab {
+                center,
+                erase: true,
+                spawn_count: 0,
+            },
+            _ => return Ok(()),
+        };
+        self.dab(device, queue, dab);
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.field_resolution != self.settings.field_resolution
+            || old_settings.max_bacteria != self.settings.max_bacteria
+        {
+            self.rebuild_colony(device, queue)?;
+        } else if old_settings.nutrient_layout != self.settings.nutrient_layout
+            || old_settings.seed != self.settings.seed
+            || old_settings.initial_bacteria != self.settings.initial_bacteria
+            || old_settings.placement != self.settings.placement
+        {
+            self.reset_runtime_state(device, queue)?;
+        }
+        if old_settings.boundaries.mask != self.settings.boundaries.mask {
+            self.write_boundary_mask(&self.settings.boundaries, queue)?;
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.seed(queue);
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.nutrient_layout = match rng.random_range(0..3) {
+            0 => NutrientLayout::Uniform,
+            1 => NutrientLayout::Patches,
+            _ => NutrientLayout::Gradient,
+        };
+        self.settings.placement = match rng.random_range(0..3) {
+            0 => Placement::Center,
+            1 => Placement::Scattered,
+            _ => Placement::Edge,
+        };
+        self.settings.diffusion = rng.random_range(0.0005..0.01);
+        self.settings.run_speed = rng.random_range(0.05..0.3);
+        self.settings.tumble_rate = rng.random_range(0.3..3.0);
+        self.settings.chemotaxis = rng.random_range(0.0..15.0);
+        self.settings.consumption_rate = rng.random_range(0.1..1.0);
+        self.settings.growth_yield = rng.random_range(0.5..4.0);
+        self.settings.metabolism = rng.random_range(0.02..0.3);
+        self.settings.seed = rng.random();
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "nutrient_layout" => {
+                self.settings.nutrient_layout = value
+                    .as_str()
+                    .unwrap_or("Patches")
+                    .parse()
+                    .map_err(|e| format!("Invalid nutrient layout: {}", e))?;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "seed" => {
+                self.settings.seed = number(setting_name, &value)? as u32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "field_resolution" => {
+                self.settings.field_resolution = number(setting_name, &value)? as u32;
+                self.rebuild_colony(device, queue)?;
+            }
+            "diffusion" => self.settings.diffusion = number(setting_name, &value)? as f32,
+            "nutrient_decay" => {
+                self.settings.nutrient_decay = number(setting_name, &value)? as f32;
+            }
+            "initial_bacteria" => {
+                self.settings.initial_bacteria = number(setting_name, &value)? as u32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "max_bacteria" => {
+                self.settings.max_bacteria = number(setting_name, &value)? as u32;
+                self.rebuild_colony(device, queue)?;
+            }
+            "placement" => {
+                self.settings.placement = value
+                    .as_str()
+                    .unwrap_or("Center")
+                    .parse()
+                    .map_err(|e| format!("Invalid placement: {}", e))?;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "run_speed" => self.settings.run_speed = number(setting_name, &value)? as f32,
+            "tumble_rate" => self.settings.tumble_rate = number(setting_name, &value)? as f32,
+            "chemotaxis" => self.settings.chemotaxis = number(setting_name, &value)? as f32,
+            "memory_time" => self.settings.memory_time = number(setting_name, &value)? as f32,
+            "rotational_diffusion" => {
+                self.settings.rotational_diffusion = number(setting_name, &value)? as f32;
+            }
+            "consumption_rate" => {
+                self.settings.consumption_rate = number(setting_name, &value)? as f32;
+            }
+            "half_saturation" => {
+                self.settings.half_saturation = number(setting_name, &value)? as f32;
+            }
+            "growth_yield" => self.settings.growth_yield = number(setting_name, &value)? as f32,
+            "metabolism" => self.settings.metabolism = number(setting_name, &value)? as f32,
+            "rod_length" => self.settings.rod_length = number(setting_name, &value)? as f32,
+            "boundaries" => {
+                let boundaries = BoundaryConditions::from_setting(value)?;
+                self.write_boundary_mask(&boundaries, queue)?;
+                self.settings.boundaries = boundaries;
+            }
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "brush_tool" => {
+                self.state.brush_tool = value
+                    .as_str()
+                    .unwrap_or("Nutrient")
+                    .parse()
+                    .map_err(|e| format!("Invalid brush_tool: {}", e))?;
+            }
+            "cursor_size" => {
+                self.state.cursor_size = number(state_name, &value)?.clamp(0.01, 0.5) as f32;
+            }
+            "cursor_strength" => {
+                self.state.cursor_strength = number(state_name, &value)?.clamp(0.0, 1.0) as f32;
+            }
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
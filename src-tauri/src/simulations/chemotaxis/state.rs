@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use super::settings::BrushTool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Nutrient grid size in cells
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Bacteria alive, as last counted on the GPU, and seconds since the
+    // colony was seeded
+    pub population: u32,
+    pub time: f32,
+
+    // What the left button paints, the brush radius in box heights, and
+    // how much nutrient it lays down
+    pub brush_tool: BrushTool,
+    pub cursor_size: f32,
+    pub cursor_strength: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            grid_width: 0,
+            grid_height: 0,
+            population: 0,
+            time: 0.0,
+            brush_tool: BrushTool::Nutrient,
+            cursor_size: 0.08,
+            cursor_strength: 1.0,
+            color_scheme_name: "KTZ_bw_SeaWeed".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
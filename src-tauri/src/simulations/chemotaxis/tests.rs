@@ -0,0 +1,206 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::colony::{
+    self, Bacterium, MAX_DIFFUSION_ITERATIONS, Swimming, diffusion_steps, grid_size, seed_bacteria,
+    seed_nutrient,
+};
+use super::settings::{BrushTool, NutrientLayout, Placement, Settings};
+use super::shaders::{COLONY_SHADER, NUTRIENT_SHADER, RENDER_SHADER};
+use crate::simulations::shared::BoundaryConditions;
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn swimming(settings: &Settings) -> Swimming {
+    Swimming {
+        run_speed: settings.run_speed,
+        tumble_rate: settings.tumble_rate,
+        chemotaxis: settings.chemotaxis,
+        memory_time: settings.memory_time,
+        rotational_diffusion: settings.rotational_diffusion,
+        half_saturation: settings.half_saturation,
+    }
+}
+
+/// Mean x of bacteria started at the origin after `seconds` swimming up a
+/// nutrient rising from the left wall to the right
+fn mean_x_after(chemotaxis: f32, seconds: f32) -> f32 {
+    let half_width = 1.5;
+    let swimming = swimming(&Settings {
+        chemotaxis,
+        ..Settings::default()
+    });
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut bacteria = seed_bacteria(2000, Placement::Center, half_width, &mut rng);
+    let nutrient = |b: &Bacterium| (b.position[0] + half_width) / (2.0 * half_width);
+    for bacterium in &mut bacteria {
+        bacterium.memory = nutrient(bacterium);
+    }
+    let dt = 1.0 / 60.0;
+    for _ in 0..(seconds / dt) as u32 {
+        for bacterium in &mut bacteria {
+            let here = nutrient(bacterium);
+            colony::swim(bacterium, here, &swimming, dt, half_width, &mut rng);
+        }
+    }
+    bacteria.iter().map(|b| b.position[0]).sum::<f32>() / bacteria.len() as f32
+}
+
+#[test]
+fn grid_covers_the_box() {
+    assert_eq!(grid_size(100, 1.0), (100, 100));
+    assert_eq!(grid_size(100, 1.778), (178, 100));
+    // Cells stay square: the width covers the box as the height does
+    let (width, height) = grid_size(192, 1.6);
+    assert!(width as f32 / height as f32 >= 1.6);
+}
+
+#[test]
+fn diffusion_passes_stay_stable() {
+    let (iterations, blend) = diffusion_steps(0.0, 1.0 / 60.0, 192);
+    assert_eq!(iterations, 1);
+    assert_eq!(blend, 0.0);
+
+    let mut previous = 0;
+    for diffusion in [0.001, 0.005, 0.02, 0.05] {
+        let (iterations, blend) = diffusion_steps(diffusion, 1.0 / 30.0, 512);
+        assert!(blend <= 1.0 && blend > 0.0, "{} {}", diffusion, blend);
+        assert!(iterations >= previous);
+        assert!(iterations <= MAX_DIFFUSION_ITERATIONS);
+        previous = iterations;
+    }
+    assert!(previous > 1);
+}
+
+#[test]
+fn nutrient_layouts_fill_the_grid() {
+    let size = (48, 32);
+    let mut rng = StdRng::seed_from_u64(1);
+    let empty = seed_nutrient(NutrientLayout::Empty, size, &mut rng);
+    assert_eq!(empty.len(), 48 * 32);
+    assert!(empty.iter().all(|&c| c == 0.0));
+
+    let uniform = seed_nutrient(NutrientLayout::Uniform, size, &mut rng);
+    assert!(uniform.iter().all(|&c| c == uniform[0] && c > 0.0));
+
+    let gradient = seed_nutrient(NutrientLayout::Gradient, size, &mut rng);
+    for row in gradient.chunks(48) {
+        assert_eq!(row[0], 0.0);
+        assert_eq!(row[47], 1.0);
+        assert!(row.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
+
+#[test]
+fn patches_repeat_with_the_seed() {
+    let size = (64, 48);
+    let patches = |seed| {
+        seed_nutrient(
+            NutrientLayout::Patches,
+            size,
+            &mut StdRng::seed_from_u64(seed),
+        )
+    };
+    let first = patches(3);
+    assert_eq!(first, patches(3));
+    assert_ne!(first, patches(4));
+    assert!(first.iter().all(|&c| (0.0..=1.0).contains(&c)));
+    // Some ground is covered and some left bare
+    assert!(first.iter().any(|&c| c > 0.5));
+    assert!(first.contains(&0.0));
+}
+
+#[test]
+fn bacteria_start_inside_the_box() {
+    let half_width = 1.4;
+    let mut rng = StdRng::seed_from_u64(2);
+    for placement in [Placement::Center, Placement::Scattered, Placement::Edge] {
+        let bacteria = seed_bacteria(500, placement, half_width, &mut rng);
+        assert_eq!(bacteria.len(), 500);
+        for bacterium in &bacteria {
+            let [x, y] = bacterium.position;
+            assert!(x.abs() <= half_width && y.abs() <= 1.0, "{:?}", placement);
+            if placement == Placement::Center {
+                assert!(x.hypot(y) <= 0.1 + 1e-6);
+            }
+        }
+    }
+}
+
+#[test]
+fn bacteria_stay_in_the_box_while_swimming() {
+    let half_width = 1.2;
+    let swimming = swimming(&Settings {
+        run_speed: 0.6,
+        ..Settings::default()
+    });
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut bacteria = seed_bacteria(200, Placement::Scattered, half_width, &mut rng);
+    for _ in 0..1200 {
+        for bacterium in &mut bacteria {
+            colony::swim(bacterium, 0.3, &swimming, 1.0 / 60.0, half_width, &mut rng);
+            let [x, y] = bacterium.position;
+            assert!(x.abs() <= half_width && y.abs() <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn chemotaxis_climbs_the_gradient() {
+    let steered = mean_x_after(20.0, 10.0);
+    let blind = mean_x_after(0.0, 10.0);
+    // The gradient is shallow, so the drift is slow but well clear of the
+    // scatter of swimming blind
+    assert!(steered > 0.1, "steered {}", steered);
+    assert!(blind.abs() < 0.03, "blind {}", blind);
+}
+
+#[test]
+fn enums_parse_from_their_names() {
+    for layout in [
+        NutrientLayout::Empty,
+        NutrientLayout::Uniform,
+        NutrientLayout::Patches,
+        NutrientLayout::Gradient,
+    ] {
+        assert_eq!(
+            format!("{:?}", layout).parse::<NutrientLayout>(),
+            Ok(layout)
+        );
+    }
+    for placement in [Placement::Center, Placement::Scattered, Placement::Edge] {
+        assert_eq!(
+            format!("{:?}", placement).parse::<Placement>(),
+            Ok(placement)
+        );
+    }
+    assert_eq!("bacteria".parse::<BrushTool>(), Ok(BrushTool::Bacteria));
+    assert!("nothing".parse::<NutrientLayout>().is_err());
+}
+
+#[test]
+fn presets_without_boundaries_stay_walled_in() {
+    let mut saved = serde_json::to_value(Settings::default()).unwrap();
+    saved.as_object_mut().unwrap().remove("boundaries");
+    let settings: Settings = serde_json::from_value(saved).unwrap();
+    assert_eq!(
+        settings.boundaries,
+        BoundaryConditions::preset("walled").unwrap()
+    );
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Chemotaxis Colony Shader", COLONY_SHADER),
+        ("Chemotaxis Nutrient Shader", NUTRIENT_SHADER),
+        ("Chemotaxis Render Shader", RENDER_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
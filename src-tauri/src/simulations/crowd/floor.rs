@@ -0,0 +1,317 @@
+//! # Crowd Floor
+//!
+//! The floor plan: a grid of cells over the box, each open or wall, that
+//! the brush draws on a cell at a time as eikonal's mazes are drawn. The
+//! floor wraps round at every edge, so walking out of one side walks back
+//! in at the other unless a wall is in the way.
+//!
+//! Each way of walking has a flow field on the floor: at every open cell,
+//! the way round the walls toward the edge the walkers leave by. It comes
+//! from the shortest distance to that edge, found by Dijkstra over the
+//! cells and their diagonals, and is worked out again whenever the walls
+//! change.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use rand::Rng;
+
+use super::settings::Scene;
+
+/// A cell walkers can cross
+pub const OPEN: u32 = 0;
+/// A cell walkers are pushed away from
+pub const WALL: u32 = 1;
+
+#[derive(Debug, Clone)]
+pub struct Floor {
+    width: u32,
+    height: u32,
+    half_width: f32,
+    cells: Vec<u32>,
+}
+
+impl Floor {
+    /// An open floor `resolution` cells high over a box `2 * half_width`
+    /// wide and 2 high
+    pub fn new(resolution: u32, half_width: f32) -> Self {
+        let height = resolution.max(1);
+        let width = ((height as f32 * half_width).round() as u32).max(1);
+        Self {
+            width,
+            height,
+            half_width,
+            cells: vec![OPEN; (width * height) as usize],
+        }
+    }
+
+    /// The walls `scene` starts with, its corridors `corridor_width` wide
+    pub fn build(scene: Scene, resolution: u32, half_width: f32, corridor_width: f32) -> Self {
+        let mut floor = Self::new(resolution, half_width);
+        let half = corridor_width * 0.5;
+        // The door through the bottleneck is a third of the corridor
+        let door = corridor_width / 6.0;
+        floor.fill(|[x, y]| match scene {
+            Scene::Corridor | Scene::Counterflow => y.abs() > half,
+            Scene::Bottleneck => y.abs() > half || (x.abs() < 0.03 && y.abs() > door),
+            Scene::Crossing => y.abs() > half && x.abs() > half,
+            Scene::Open => false,
+        });
+        floor
+    }
+
+    /// Make a wall of every cell whose center `is_wall` picks out
+    fn fill(&mut self, is_wall: impl Fn([f32; 2]) -> bool) {
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                let cell = if is_wall(self.center((x, y))) {
+                    WALL
+                } else {
+                    OPEN
+                };
+                self.cells[(y as u32 * self.width + x as u32) as usize] = cell;
+            }
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn half_width(&self) -> f32 {
+        self.half_width
+    }
+
+    /// Box heights across a cell
+    pub fn cell_size(&self) -> f32 {
+        2.0 / self.height as f32
+    }
+
+    /// A word per cell, row by row from the bottom, as the shader reads
+    /// them
+    pub fn cells(&self) -> &[u32] {
+        &self.cells
+    }
+
+    /// The cell over `position`, which needn't be inside the box
+    pub fn cell_of(&self, [x, y]: [f32; 2]) -> (i32, i32) {
+        let size = self.cell_size();
+        (
+            ((x + self.half_width) / size).floor() as i32,
+            ((y + 1.0) / size).floor() as i32,
+        )
+    }
+
+    /// The middle of a cell, which needn't be on the grid
+    pub fn center(&self, (x, y): (i32, i32)) -> [f32; 2] {
+        let size = self.cell_size();
+        [
+            (x as f32 + 0.5) * size - self.half_width,
+            (y as f32 + 0.5) * size - 1.0,
+        ]
+    }
+
+    /// Index of a cell, wrapped onto the grid
+    fn index(&self, (x, y): (i32, i32)) -> usize {
+        let x = x.rem_euclid(self.width as i32) as u32;
+        let y = y.rem_euclid(self.height as i32) as u32;
+        (y * self.width + x) as usize
+    }
+
+    pub fn is_wall(&self, cell: (i32, i32)) -> bool {
+        self.cells[self.index(cell)] == WALL
+    }
+
+    /// Set every cell whose center is within `radius` of `center` to
+    /// `cell`, wrapping round the edges. Returns whether anything changed.
+    pub fn paint(&mut self, center: [f32; 2], radius: f32, cell: u32) -> bool {
+        let (cx, cy) = self.cell_of(center);
+        let reach = (radius / self.cell_size()).ceil() as i32 + 1;
+        let mut changed = false;
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let [x, y] = self.center((cx + dx, cy + dy));
+                if (x - center[0]).hypot(y - center[1]) > radius {
+                    continue;
+                }
+                let index = self.index((cx + dx, cy + dy));
+                changed |= self.cells[index] != cell;
+                self.cells[index] = cell;
+            }
+        }
+        changed
+    }
+
+    /// From the nearest wall within `reach` of `position` to it, if there
+    /// is one: the offset and how far
+    pub fn nearest_wall(&self, position: [f32; 2], reach: f32) -> Option<([f32; 2], f32)> {
+        let size = self.cell_size();
+        let (cx, cy) = self.cell_of(position);
+        let cells = (reach / size).ceil() as i32;
+        let mut nearest: Option<([f32; 2], f32)> = None;
+        for dy in -cells..=cells {
+            for dx in -cells..=cells {
+                let cell = (cx + dx, cy + dy);
+                if !self.is_wall(cell) {
+                    continue;
+                }
+                // The nearest point of the cell's square
+                let [x, y] = self.center(cell);
+                let offset = [
+                    position[0] - position[0].clamp(x - size * 0.5, x + size * 0.5),
+                    position[1] - position[1].clamp(y - size * 0.5, y + size * 0.5),
+                ];
+                let distance = offset[0].hypot(offset[1]);
+                if distance <= reach && nearest.is_none_or(|(_, nearest)| distance < nearest) {
+                    nearest = Some((offset, distance));
+                }
+            }
+        }
+        nearest
+    }
+
+    /// The middle of a random open cell, if there are any
+    pub fn random_open(&self, rng: &mut impl Rng) -> Option<[f32; 2]> {
+        let open = self.cells.iter().filter(|&&cell| cell == OPEN).count();
+        if open == 0 {
+            return None;
+        }
+        let pick = rng.random_range(0..open);
+        let index = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|&(_, &cell)| cell == OPEN)
+            .nth(pick)?
+            .0;
+        let width = self.width as usize;
+        Some(self.center(((index % width) as i32, (index / width) as i32)))
+    }
+
+    /// A random open cell on the edge walkers heading along `heading` come
+    /// in by, or anywhere open if that edge is walled off
+    pub fn random_entrance(&self, heading: [f32; 2], rng: &mut impl Rng) -> Option<[f32; 2]> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let edge: Vec<(i32, i32)> = if heading[0].abs() >= heading[1].abs() {
+            let x = if heading[0] > 0.0 { 0 } else { width - 1 };
+            (0..height).map(|y| (x, y)).collect()
+        } else {
+            let y = if heading[1] > 0.0 { 0 } else { height - 1 };
+            (0..width).map(|x| (x, y)).collect()
+        };
+        let open: Vec<_> = edge
+            .into_iter()
+            .filter(|&cell| !self.is_wall(cell))
+            .collect();
+        if open.is_empty() {
+            return self.random_open(rng);
+        }
+        Some(self.center(open[rng.random_range(0..open.len())]))
+    }
+
+    /// Unit direction at every cell toward the edge walkers heading along
+    /// `heading` leave by, the way round the walls. Cells walled in from
+    /// that edge, and walls, just point along `heading`.
+    pub fn flow_field(&self, heading: [f32; 2]) -> Vec<[f32; 2]> {
+        let distances = self.distances_to_exit(heading);
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut field = vec![heading; self.cells.len()];
+        for y in 0..height {
+            for x in 0..width {
+                let index = (y * width + x) as usize;
+                let here = distances[index];
+                if !here.is_finite() {
+                    continue;
+                }
+                // Walls and the grid's edges don't pull either way
+                let at = |cell: (i32, i32)| {
+                    if cell.0 < 0 || cell.1 < 0 || cell.0 >= width || cell.1 >= height {
+                        return here;
+                    }
+                    let distance = distances[(cell.1 * width + cell.0) as usize];
+                    if distance.is_finite() { distance } else { here }
+                };
+                let slope = [
+                    at((x - 1, y)) - at((x + 1, y)),
+                    at((x, y - 1)) - at((x, y + 1)),
+                ];
+                let length = slope[0].hypot(slope[1]);
+                if length > 1e-6 {
+                    field[index] = [slope[0] / length, slope[1] / length];
+                }
+            }
+        }
+        field
+    }
+
+    /// Cells from each open cell to the nearest open cell on the edge
+    /// walkers heading along `heading` leave by, without wrapping
+    pub fn distances_to_exit(&self, heading: [f32; 2]) -> Vec<f32> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut distances = vec![f32::INFINITY; self.cells.len()];
+        // Non-negative floats order the same as their bits, which do have
+        // an order for the heap
+        let mut heap = BinaryHeap::new();
+        let is_exit = |(x, y): (i32, i32)| {
+            if heading[0].abs() >= heading[1].abs() {
+                x == if heading[0] > 0.0 { width - 1 } else { 0 }
+            } else {
+                y == if heading[1] > 0.0 { height - 1 } else { 0 }
+            }
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let index = (y * width + x) as usize;
+                if is_exit((x, y)) && self.cells[index] == OPEN {
+                    distances[index] = 0.0;
+                    heap.push(Reverse((0.0f32.to_bits(), index)));
+                }
+            }
+        }
+        while let Some(Reverse((bits, index))) = heap.pop() {
+            let distance = f32::from_bits(bits);
+            if distance > distances[index] {
+                continue;
+            }
+            let (x, y) = ((index as i32) % width, (index as i32) / width);
+            for (dx, dy) in [
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (1, -1),
+                (-1, 1),
+                (1, 1),
+            ] {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                    continue;
+                }
+                let diagonal = dx != 0 && dy != 0;
+                // No cutting past the corner of a wall
+                if self.is_wall((nx, ny))
+                    || (diagonal && (self.is_wall((x + dx, y)) || self.is_wall((x, y + dy))))
+                {
+                    continue;
+                }
+                let next = distance
+                    + if diagonal {
+                        std::f32::consts::SQRT_2
+                    } else {
+                        1.0
+                    };
+                let neighbor = (ny * width + nx) as usize;
+                if next < distances[neighbor] {
+                    distances[neighbor] = next;
+                    heap.push(Reverse((next.to_bits(), neighbor)));
+                }
+            }
+        }
+        distances
+    }
+}
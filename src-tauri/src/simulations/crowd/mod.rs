@@ -0,0 +1,76 @@
+pub mod floor;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+pub mod walkers;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::CrowdModel;
+
+use crate::simulation::preset_manager::{CrowdPresetManager, Preset};
+
+/// Initialize Crowd presets with built-in configurations
+pub fn init_presets(preset_manager: &mut CrowdPresetManager) {
+    use settings::{Scene, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Rush Hour".to_string(),
+        Settings {
+            scene: Scene::Bottleneck,
+            agent_count: 700,
+            desired_speed: 0.35,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Lane Forming".to_string(),
+        Settings {
+            scene: Scene::Counterflow,
+            corridor_width: 1.2,
+            agent_count: 500,
+            anisotropy: 0.15,
+            noise: 0.02,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Busy Junction".to_string(),
+        Settings {
+            scene: Scene::Crossing,
+            corridor_width: 0.6,
+            agent_count: 500,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Stop And Go".to_string(),
+        Settings {
+            scene: Scene::Corridor,
+            corridor_width: 0.4,
+            agent_count: 600,
+            relaxation_time: 1.2,
+            anisotropy: 0.05,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Draw Your Own".to_string(),
+        Settings {
+            scene: Scene::Open,
+            agent_count: 300,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,193 @@
+//! # Crowd Settings Module
+//!
+//! The floor plan the crowd starts on, how many walk it and where they're
+//! headed, and the social forces steering them.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Scene {
+    /// A corridor everyone walks down the same way
+    Corridor,
+    /// A corridor walked both ways at once
+    #[default]
+    Counterflow,
+    /// A corridor with a wall across it and a narrow door through
+    Bottleneck,
+    /// Two corridors crossing, each walked its own way
+    Crossing,
+    /// No walls at all, for drawing corridors of your own
+    Open,
+}
+
+impl FromStr for Scene {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "corridor" => Ok(Scene::Corridor),
+            "counterflow" => Ok(Scene::Counterflow),
+            "bottleneck" => Ok(Scene::Bottleneck),
+            "crossing" => Ok(Scene::Crossing),
+            "open" => Ok(Scene::Open),
+            _ => Err(format!(
+                "Invalid Scene: '{}'. Expected 'Corridor', 'Counterflow', 'Bottleneck', 'Crossing', or 'Open'",
+                s
+            )),
+        }
+    }
+}
+
+/// What the left mouse button draws; the right one always erases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BrushTool {
+    #[default]
+    Wall,
+    Erase,
+}
+
+impl FromStr for BrushTool {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wall" => Ok(BrushTool::Wall),
+            "erase" => Ok(BrushTool::Erase),
+            _ => Err(format!(
+                "Invalid BrushTool: '{}'. Expected 'Wall' or 'Erase'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub scene: Scene,
+    /// Width of the scene's corridors, in box heights
+    pub corridor_width: f32,
+    /// Floor cells up the height of the box; walls are drawn a cell at a
+    /// time
+    pub floor_resolution: u32,
+    pub seed: u32,
+
+    pub agent_count: u32,
+    /// Radius of each walker, in box heights
+    pub agent_radius: f32,
+    /// Speed each walker would keep with nobody in the way, in box heights
+    /// per second
+    pub desired_speed: f32,
+    /// Seconds a walker takes to get back up to speed and onto its route
+    pub relaxation_time: f32,
+
+    /// Push between walkers as they touch, in box heights per second
+    /// squared
+    pub repulsion: f32,
+    /// How far the push reaches past touching, in box heights
+    pub repulsion_range: f32,
+    /// Push away from walls as a walker touches one
+    pub wall_repulsion: f32,
+    /// How much more walkers mind those ahead of them than behind: 0 minds
+    /// only those ahead, 1 minds everyone alike
+    pub anisotropy: f32,
+    /// Random jostling, in box heights per second squared
+    pub noise: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            scene: Scene::Counterflow,
+            corridor_width: 0.8,
+            floor_resolution: 96,
+            seed: 0,
+            agent_count: 400,
+            agent_radius: 0.015,
+            desired_speed: 0.25,
+            relaxation_time: 0.5,
+            repulsion: 1.5,
+            repulsion_range: 0.01,
+            wall_repulsion: 3.0,
+            anisotropy: 0.3,
+            noise: 0.05,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "scene",
+            Rule::OneOf(&["Corridor", "Counterflow", "Bottleneck", "Crossing", "Open"]),
+        ),
+        ("corridor_width", Rule::Range { min: 0.2, max: 1.8 }),
+        ("floor_resolution", Rule::Count { min: 24, max: 256 }),
+        (
+            "seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+        ("agent_count", Rule::Count { min: 1, max: 4000 }),
+        (
+            "agent_radius",
+            Rule::Range {
+                min: 0.005,
+                max: 0.05,
+            },
+        ),
+        ("desired_speed", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "relaxation_time",
+            Rule::Range {
+                min: 0.05,
+                max: 5.0,
+            },
+        ),
+        (
+            "repulsion",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        (
+            "repulsion_range",
+            Rule::Range {
+                min: 0.002,
+                max: 0.05,
+            },
+        ),
+        (
+            "wall_repulsion",
+            Rule::Range {
+                min: 0.0,
+                max: 20.0,
+            },
+        ),
+        ("anisotropy", Rule::Range { min: 0.0, max: 1.0 }),
+        ("noise", Rule::Range { min: 0.0, max: 1.0 }),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("scene", SettingCategory::Generators),
+    ("seed", SettingCategory::Generators),
+    ("agent_count", SettingCategory::Generators),
+]);
@@ -0,0 +1,123 @@
+// Draws the floor plan with a fullscreen pass, walls lighter than the
+// open floor, then each walker as a disc colored by how congested it has
+// been, with a dark nose on the side it's walking toward.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    // Box heights per pixel, for antialiasing
+    pixel_size: f32,
+    agent_radius: f32,
+    grid_width: u32,
+    grid_height: u32,
+}
+
+struct Walker {
+    position: vec2<f32>,
+    velocity: vec2<f32>,
+    congestion: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> floor_cells: array<u32>;
+@group(0) @binding(2) var<storage, read> walkers: array<Walker>;
+@group(0) @binding(3) var<storage, read> lut_data: array<u32>;
+
+const WALL: u32 = 1u;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+// The box is aspect wide in each direction; the camera sees 1 to a side
+fn to_ndc(point: vec2<f32>) -> vec2<f32> {
+    return (vec2<f32>(point.x / params.aspect, point.y) - params.view_center) * params.view_zoom;
+}
+
+fn from_ndc(ndc: vec2<f32>) -> vec2<f32> {
+    let view = ndc / params.view_zoom + params.view_center;
+    return vec2<f32>(view.x * params.aspect, view.y);
+}
+
+struct FloorOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// One triangle covering the screen
+@vertex
+fn vs_floor(@builtin(vertex_index) vertex_index: u32) -> FloorOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return FloorOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+@fragment
+fn fs_floor(input: FloorOutput) -> @location(0) vec4<f32> {
+    let point = from_ndc(input.ndc);
+    // The floor wraps, so the view repeats it past the box
+    let size = 2.0 / f32(params.grid_height);
+    let cell = vec2<i32>(floor(vec2<f32>(point.x + params.aspect, point.y + 1.0) / size));
+    let x = u32(((cell.x % i32(params.grid_width)) + i32(params.grid_width)) % i32(params.grid_width));
+    let y = u32(((cell.y % i32(params.grid_height)) + i32(params.grid_height)) % i32(params.grid_height));
+    if (floor_cells[y * params.grid_width + x] == WALL) {
+        return vec4<f32>(vec3<f32>(0.35), 1.0);
+    }
+    return vec4<f32>(vec3<f32>(0.04), 1.0);
+}
+
+struct WalkerOutput {
+    @builtin(position) position: vec4<f32>,
+    // Offset from the walker's middle, in radii
+    @location(0) local: vec2<f32>,
+    @location(1) @interpolate(flat) instance: u32,
+}
+
+// A square around the walker, a pixel wider all round for the soft edge
+@vertex
+fn vs_walker(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> WalkerOutput {
+    let corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let pad = 1.0 + params.pixel_size / params.agent_radius;
+    let local = corners[vertex_index] * pad;
+    let point = walkers[instance_index].position + local * params.agent_radius;
+    return WalkerOutput(vec4<f32>(to_ndc(point), 0.0, 1.0), local, instance_index);
+}
+
+@fragment
+fn fs_walker(input: WalkerOutput) -> @location(0) vec4<f32> {
+    let walker = walkers[input.instance];
+    let distance = (length(input.local) - 1.0) * params.agent_radius;
+    let half_pixel = params.pixel_size * 0.5;
+    let coverage = 1.0 - smoothstep(-half_pixel, half_pixel, distance);
+    if (coverage <= 0.0) {
+        discard;
+    }
+    var color = lut_color(walker.congestion);
+    let speed = length(walker.velocity);
+    if (speed > 1e-4) {
+        let nose = walker.velocity / speed * 0.6;
+        let nose_distance = (length(input.local - nose) - 0.3) * params.agent_radius;
+        let on_nose = 1.0 - smoothstep(-half_pixel, half_pixel, nose_distance);
+        color = mix(color, color * 0.25, on_nose);
+    }
+    return vec4<f32>(color, coverage);
+}
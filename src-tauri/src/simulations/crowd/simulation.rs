@@ -0,0 +1,702 @@
+//! # Crowd Simulation Module
+//!
+//! Walkers steered by social forces down corridors, through doors and
+//! across each other's paths, each colored by how held up it has been
+//! lately so jams show as they form and travel back up the crowd. The
+//! left mouse button draws walls, or erases them with the eraser picked,
+//! and the right button always erases; the walkers find their way round
+//! whatever's drawn.
+//!
+//! The walking runs on the CPU in [`walkers`](super::walkers) over the
+//! floor plan in [`floor`](super::floor). Each frame the walkers are
+//! uploaded for the GPU to draw as discs over a fullscreen pass of the
+//! floor, which is only uploaded again when the walls change.
+
+use bytemuck::{Pod, Zeroable};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, BlendState, Buffer,
+    BufferDescriptor, BufferUsages, Device, PipelineLayoutDescriptor, Queue, RenderPipeline,
+    RenderPipelineDescriptor, ShaderModule, ShaderStages, SurfaceConfiguration, TextureFormat,
+    TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, ParticleBuffer,
+};
+use crate::simulations::traits::Simulation;
+
+use super::floor::{Floor, OPEN, WALL};
+use super::settings::{BrushTool, Scene, Settings};
+use super::shaders::RENDER_SHADER;
+use super::state::State;
+use super::walkers::{Crowd, Forces, headings};
+
+/// Longest step taken in one frame, so a stall doesn't throw walkers
+/// through each other
+const MAX_FRAME_TIME: f32 = 1.0 / 30.0;
+
+/// Longest single step of the social forces; longer frames take several
+const MAX_STEP: f32 = 1.0 / 60.0;
+
+/// Walkers room is made for up front; the buffer grows past this as needed
+const INITIAL_WALKERS: usize = 1024;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    pixel_size: f32,
+    agent_radius: f32,
+    grid_width: u32,
+    grid_height: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct Walker {
+    position: [f32; 2],
+    velocity: [f32; 2],
+    congestion: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+#[derive(Debug)]
+pub struct CrowdModel {
+    pub settings: Settings,
+    pub state: State,
+    floor: Floor,
+    crowd: Crowd,
+    // Seeded from the settings, so a crowd walks the same way every time
+    rng: StdRng,
+
+    // GPU resources
+    floor_pipeline: RenderPipeline,
+    walker_pipeline: RenderPipeline,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    floor_buffer: Buffer,
+    walker_buffer: ParticleBuffer<Walker>,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+
+    // Walkers laid out for this frame, kept to save reallocating
+    walkers: Vec<Walker>,
+
+    // Walls changed since the floor was last uploaded
+    floor_dirty: bool,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl CrowdModel {
+    pub fn new(
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let mut state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Crowd Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Crowd Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("Crowd LUT Buffer for {}", state.color_scheme_name)),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let aspect = surface_config.width as f32 / surface_config.height.max(1) as f32;
+        let floor = build_floor(&settings, aspect, &mut state);
+        let mut rng = StdRng::seed_from_u64(settings.seed as u64);
+        let crowd = spawn_crowd(&settings, &floor, &mut rng);
+
+        let floor_buffer = create_floor_buffer(device, &floor);
+        let walker_buffer = ParticleBuffer::uninitialized(
+            device,
+            "Crowd Walker Buffer",
+            BufferUsages::empty(),
+            INITIAL_WALKERS,
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Crowd Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::FRAGMENT, true),
+                resource_helpers::storage_buffer_entry(
+                    2,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    true,
+                ),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::FRAGMENT, true),
+            ],
+        });
+        let bind_group = create_bind_group(
+            device,
+            &bind_group_layout,
+            [
+                &params_buffer,
+                &floor_buffer,
+                walker_buffer.buffer(),
+                &lut_buffer,
+            ],
+        );
+
+        let floor_pipeline = create_pipeline(
+            device,
+            "Crowd Floor Pipeline",
+            &bind_group_layout,
+            &render_module,
+            ("vs_floor", "fs_floor"),
+            surface_config.format,
+        );
+        let walker_pipeline = create_pipeline(
+            device,
+            "Crowd Walker Pipeline",
+            &bind_group_layout,
+            &render_module,
+            ("vs_walker", "fs_walker"),
+            surface_config.format,
+        );
+
+        Ok(Self {
+            settings,
+            state,
+            floor,
+            crowd,
+            rng,
+            floor_pipeline,
+            walker_pipeline,
+            params_buffer,
+            lut_buffer,
+            floor_buffer,
+            walker_buffer,
+            bind_group_layout,
+            bind_group,
+            walkers: Vec::new(),
+            floor_dirty: true,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        })
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    fn forces(&self) -> Forces {
+        Forces {
+            agent_radius: self.settings.agent_radius,
+            desired_speed: self.settings.desired_speed,
+            relaxation_time: self.settings.relaxation_time,
+            repulsion: self.settings.repulsion,
+            repulsion_range: self.settings.repulsion_range,
+            wall_repulsion: self.settings.wall_repulsion,
+            anisotropy: self.settings.anisotropy,
+            noise: self.settings.noise,
+        }
+    }
+
+    /// Put a fresh crowd down from the seed, on the floor as it is
+    fn respawn(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.settings.seed as u64);
+        self.crowd = spawn_crowd(&self.settings, &self.floor, &mut self.rng);
+        self.update_flow();
+    }
+
+    /// Lay the scene's walls out afresh, losing any drawn, and respawn the
+    /// crowd on them. The floor buffer is remade to fit.
+    fn rebuild_floor(&mut self, device: &Arc<Device>) {
+        self.floor = build_floor(&self.settings, self.aspect(), &mut self.state);
+        self.floor_buffer = create_floor_buffer(device, &self.floor);
+        self.rebind(device);
+        self.floor_dirty = true;
+        self.respawn();
+    }
+
+    fn update_flow(&mut self) {
+        (self.state.flow, self.state.jammed) =
+            self.crowd.flow(&self.floor, self.settings.desired_speed);
+    }
+
+    /// Point the bind group at the buffers again, after one was replaced
+    fn rebind(&mut self, device: &Device) {
+        self.bind_group = create_bind_group(
+            device,
+            &self.bind_group_layout,
+            [
+                &self.params_buffer,
+                &self.floor_buffer,
+                self.walker_buffer.buffer(),
+                &self.lut_buffer,
+            ],
+        );
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            pixel_size: 2.0 / (self.camera.zoom * self.height.max(1) as f32),
+            agent_radius: self.settings.agent_radius,
+            grid_width: self.floor.width(),
+            grid_height: self.floor.height(),
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn draw(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, surface_view: &TextureView) {
+        if self.floor_dirty {
+            queue.write_buffer(
+                &self.floor_buffer,
+                0,
+                bytemuck::cast_slice(self.floor.cells()),
+            );
+            self.floor_dirty = false;
+        }
+        self.walkers.clear();
+        self.walkers
+            .extend(self.crowd.agents.iter().map(|agent| Walker {
+                position: agent.position,
+                velocity: agent.velocity,
+                congestion: agent.congestion,
+                _pad0: 0.0,
+                _pad1: 0.0,
+                _pad2: 0.0,
+            }));
+        if self.walker_buffer.upload(device, queue, &self.walkers) {
+            self.rebind(device);
+        }
+        self.update_params(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Crowd Render"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Crowd Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_pipeline(&self.floor_pipeline);
+            render_pass.draw(0..3, 0..1);
+            render_pass.set_pipeline(&self.walker_pipeline);
+            render_pass.draw(0..6, 0..self.walkers.len() as u32);
+        }
+        queue.submit([encoder.finish()]);
+    }
+}
+
+/// The scene's floor plan in a box `aspect` wide either side, noting its
+/// size in `state`
+fn build_floor(settings: &Settings, aspect: f32, state: &mut State) -> Floor {
+    let floor = Floor::build(
+        settings.scene,
+        settings.floor_resolution,
+        aspect,
+        settings.corridor_width,
+    );
+    state.grid_width = floor.width();
+    state.grid_height = floor.height();
+    floor
+}
+
+fn spawn_crowd(settings: &Settings, floor: &Floor, rng: &mut StdRng) -> Crowd {
+    Crowd::spawn(
+        settings.agent_count as usize,
+        headings(settings.scene),
+        floor,
+        rng,
+    )
+}
+
+fn create_floor_buffer(device: &Device, floor: &Floor) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some("Crowd Floor Buffer"),
+        size: std::mem::size_of_val(floor.cells()) as u64,
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// The params, floor, walker and LUT buffers, bound in that order
+fn create_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    buffers: [&Buffer; 4],
+) -> BindGroup {
+    let [params, floor, walkers, lut] = buffers;
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Crowd Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params),
+            resource_helpers::buffer_entry(1, floor),
+            resource_helpers::buffer_entry(2, walkers),
+            resource_helpers::buffer_entry(3, lut),
+        ],
+    })
+}
+
+fn create_pipeline(
+    device: &Device,
+    label: &str,
+    bind_group_layout: &BindGroupLayout,
+    module: &ShaderModule,
+    (vertex_entry, fragment_entry): (&str, &str),
+    format: TextureFormat,
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(&format!("{} Layout", label)),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex_entry),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for CrowdModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        let dt = delta_time.min(MAX_FRAME_TIME);
+        let steps = (dt / MAX_STEP).ceil().max(1.0) as u32;
+        let forces = self.forces();
+        for _ in 0..steps {
+            self.crowd
+                .step(&self.floor, &forces, dt / steps as f32, &mut self.rng);
+        }
+        self.update_flow();
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        // The floor keeps to the window, so it's laid out again to fit
+        self.rebuild_floor(device);
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Left draws with the current tool, right erases
+        let cell = match (mouse_button, self.state.brush_tool) {
+            (0, BrushTool::Wall) => WALL,
+            (0, BrushTool::Erase) | (2, _) => OPEN,
+            _ => return Ok(()),
+        };
+        let position = [world_x * self.aspect(), world_y];
+        if self.floor.paint(position, self.state.cursor_size, cell) {
+            self.crowd.refresh_fields(&self.floor);
+            self.floor_dirty = true;
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.scene != self.settings.scene
+            || old_settings.floor_resolution != self.settings.floor_resolution
+            || old_settings.corridor_width != self.settings.corridor_width
+        {
+            self.rebuild_floor(device);
+        } else if old_settings.agent_count != self.settings.agent_count
+            || old_settings.seed != self.settings.seed
+        {
+            self.respawn();
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Drawn walls stay; only the walkers start over
+        self.respawn();
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.scene = match rng.random_range(0..4) {
+            0 => Scene::Corridor,
+            1 => Scene::Counterflow,
+            2 => Scene::Bottleneck,
+            _ => Scene::Crossing,
+        };
+        self.settings.corridor_width = rng.random_range(0.4..1.2);
+        self.settings.agent_count = rng.random_range(100..800);
+        self.settings.desired_speed = rng.random_range(0.15..0.4);
+        self.settings.relaxation_time = rng.random_range(0.3..1.0);
+        self.settings.repulsion = rng.random_range(0.8..2.5);
+        self.settings.anisotropy = rng.random_range(0.1..0.6);
+        self.settings.noise = rng.random_range(0.0..0.2);
+        self.settings.seed = rng.random();
+        self.rebuild_floor(device);
+        Ok(())
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "scene" => {
+                self.settings.scene = value
+                    .as_str()
+                    .unwrap_or("Counterflow")
+                    .parse()
+                    .map_err(|e| format!("Invalid scene: {}", e))?;
+                self.rebuild_floor(device);
+            }
+            "corridor_width" => {
+                self.settings.corridor_width = number(setting_name, &value)? as f32;
+                self.rebuild_floor(device);
+            }
+            "floor_resolution" => {
+                self.settings.floor_resolution = number(setting_name, &value)? as u32;
+                self.rebuild_floor(device);
+            }
+            "seed" => {
+                self.settings.seed = number(setting_name, &value)? as u32;
+                self.respawn();
+            }
+            "agent_count" => {
+                self.settings.agent_count = number(setting_name, &value)? as u32;
+                self.respawn();
+            }
+            "agent_radius" => self.settings.agent_radius = number(setting_name, &value)? as f32,
+            "desired_speed" => self.settings.desired_speed = number(setting_name, &value)? as f32,
+            "relaxation_time" => {
+                self.settings.relaxation_time = number(setting_name, &value)? as f32;
+            }
+            "repulsion" => self.settings.repulsion = number(setting_name, &value)? as f32,
+            "repulsion_range" => {
+                self.settings.repulsion_range = number(setting_name, &value)? as f32;
+            }
+            "wall_repulsion" => self.settings.wall_repulsion = number(setting_name, &value)? as f32,
+            "anisotropy" => self.settings.anisotropy = number(setting_name, &value)? as f32,
+            "noise" => self.settings.noise = number(setting_name, &value)? as f32,
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "brush_tool" => {
+                self.state.brush_tool = value
+                    .as_str()
+                    .unwrap_or("Wall")
+                    .parse()
+                    .map_err(|e| format!("Invalid brush_tool: {}", e))?;
+            }
+            "cursor_size" => {
+                self.state.cursor_size = number(state_name, &value)?.clamp(0.005, 0.5) as f32;
+            }
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
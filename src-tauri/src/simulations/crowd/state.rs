@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use super::settings::BrushTool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Floor grid size in cells
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Average walking speed over desired speed, and the share of walkers
+    // stuck in a jam
+    pub flow: f32,
+    pub jammed: f32,
+
+    // What the left button draws, and the brush radius in box heights
+    pub brush_tool: BrushTool,
+    pub cursor_size: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            grid_width: 0,
+            grid_height: 0,
+            flow: 0.0,
+            jammed: 0.0,
+            brush_tool: BrushTool::Wall,
+            cursor_size: 0.03,
+            color_scheme_name: "MATPLOTLIB_turbo".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
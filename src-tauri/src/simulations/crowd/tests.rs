@@ -0,0 +1,188 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::floor::{Floor, OPEN, WALL};
+use super::settings::{BrushTool, Scene, Settings};
+use super::shaders::RENDER_SHADER;
+use super::walkers::{Crowd, Forces, headings};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn forces(settings: &Settings) -> Forces {
+    Forces {
+        agent_radius: settings.agent_radius,
+        desired_speed: settings.desired_speed,
+        relaxation_time: settings.relaxation_time,
+        repulsion: settings.repulsion,
+        repulsion_range: settings.repulsion_range,
+        wall_repulsion: settings.wall_repulsion,
+        anisotropy: settings.anisotropy,
+        noise: settings.noise,
+    }
+}
+
+/// A crowd of `count` walking `scene` for `seconds`
+fn walk(scene: Scene, count: usize, seconds: f32) -> (Floor, Crowd, Settings) {
+    let settings = Settings {
+        scene,
+        ..Settings::default()
+    };
+    let floor = Floor::build(
+        scene,
+        settings.floor_resolution,
+        1.5,
+        settings.corridor_width,
+    );
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut crowd = Crowd::spawn(count, headings(scene), &floor, &mut rng);
+    let forces = forces(&settings);
+    for _ in 0..(seconds * 60.0) as u32 {
+        crowd.step(&floor, &forces, 1.0 / 60.0, &mut rng);
+    }
+    (floor, crowd, settings)
+}
+
+#[test]
+fn scenes_wall_off_what_they_promise() {
+    let corridor = Floor::build(Scene::Corridor, 64, 1.5, 0.8);
+    assert!(corridor.is_wall(corridor.cell_of([0.0, 0.9])));
+    assert!(!corridor.is_wall(corridor.cell_of([0.0, 0.0])));
+    assert!(!corridor.is_wall(corridor.cell_of([1.45, 0.3])));
+
+    let bottleneck = Floor::build(Scene::Bottleneck, 64, 1.5, 0.8);
+    assert!(bottleneck.is_wall(bottleneck.cell_of([0.0, 0.3])));
+    assert!(!bottleneck.is_wall(bottleneck.cell_of([0.0, 0.0])));
+
+    let crossing = Floor::build(Scene::Crossing, 64, 1.5, 0.8);
+    assert!(crossing.is_wall(crossing.cell_of([1.0, 0.8])));
+    assert!(!crossing.is_wall(crossing.cell_of([0.0, 0.8])));
+    assert!(!crossing.is_wall(crossing.cell_of([1.0, 0.0])));
+
+    let open = Floor::build(Scene::Open, 64, 1.5, 0.8);
+    assert!(open.cells().iter().all(|&cell| cell == OPEN));
+    assert_eq!((open.width(), open.height()), (96, 64));
+}
+
+#[test]
+fn painting_walls_reports_changes_and_wraps() {
+    let mut floor = Floor::new(32, 1.0);
+    assert!(floor.paint([0.0, 0.0], 0.1, WALL));
+    assert!(!floor.paint([0.0, 0.0], 0.1, WALL));
+    assert!(floor.is_wall(floor.cell_of([0.05, 0.0])));
+    // A dab over the edge lands on the far side too
+    floor.paint([0.99, 0.0], 0.1, WALL);
+    assert!(floor.is_wall(floor.cell_of([-0.97, 0.0])));
+    assert!(floor.paint([0.0, 0.0], 0.2, OPEN));
+    assert!(!floor.is_wall(floor.cell_of([0.05, 0.0])));
+}
+
+#[test]
+fn nearest_wall_points_away_from_it() {
+    let floor = Floor::build(Scene::Corridor, 64, 1.0, 1.0);
+    let (offset, distance) = floor.nearest_wall([0.0, 0.45], 0.2).unwrap();
+    assert!(offset[1] < 0.0 && offset[0].abs() < 1e-6, "{:?}", offset);
+    assert!((distance - 0.05).abs() < floor.cell_size(), "{}", distance);
+    assert!(floor.nearest_wall([0.0, 0.0], 0.2).is_none());
+}
+
+#[test]
+fn routes_lead_to_the_exit_round_walls() {
+    let corridor = Floor::build(Scene::Corridor, 64, 1.5, 0.8);
+    let east = corridor.flow_field([1.0, 0.0]);
+    let west = corridor.flow_field([-1.0, 0.0]);
+    let middle = |field: &[[f32; 2]], x: f32| {
+        let (cx, cy) = corridor.cell_of([x, 0.0]);
+        field[(cy as u32 * corridor.width() + cx as u32) as usize]
+    };
+    assert!(middle(&east, 0.0)[0] > 0.99);
+    assert!(middle(&west, 0.0)[0] < -0.99);
+
+    // Behind the bottleneck's wall the way on is round to the door
+    let bottleneck = Floor::build(Scene::Bottleneck, 64, 1.5, 0.8);
+    let field = bottleneck.flow_field([1.0, 0.0]);
+    let (cx, cy) = bottleneck.cell_of([-0.06, 0.3]);
+    let direction = field[(cy as u32 * bottleneck.width() + cx as u32) as usize];
+    assert!(direction[1] < -0.5, "{:?}", direction);
+    let distances = bottleneck.distances_to_exit([1.0, 0.0]);
+    let (near, far) = (
+        bottleneck.cell_of([-0.06, 0.0]),
+        bottleneck.cell_of([-0.06, 0.3]),
+    );
+    let at = |(x, y): (i32, i32)| distances[(y as u32 * bottleneck.width() + x as u32) as usize];
+    assert!(at(near) < at(far));
+}
+
+#[test]
+fn walkers_walk_their_way_and_stay_out_of_walls() {
+    let (floor, crowd, settings) = walk(Scene::Counterflow, 200, 4.0);
+    for group in 0..2 {
+        let going: Vec<_> = crowd.agents.iter().filter(|a| a.group == group).collect();
+        let mean = going.iter().map(|a| a.velocity[0]).sum::<f32>() / going.len() as f32;
+        let expected = if group == 0 { 1.0 } else { -1.0 };
+        assert!(
+            mean * expected > 0.5 * settings.desired_speed,
+            "{} {}",
+            group,
+            mean
+        );
+    }
+    for agent in &crowd.agents {
+        let [x, y] = agent.position;
+        assert!(x.abs() <= 1.5 && y.abs() <= 1.0);
+        assert!(!floor.is_wall(floor.cell_of(agent.position)), "{:?}", agent);
+    }
+}
+
+#[test]
+fn crowds_jam_at_a_door_but_not_in_the_open() {
+    let (floor, crowd, settings) = walk(Scene::Bottleneck, 600, 8.0);
+    let (flow, jammed) = crowd.flow(&floor, settings.desired_speed);
+    let (open_floor, sparse, _) = walk(Scene::Corridor, 100, 8.0);
+    let (open_flow, open_jammed) = sparse.flow(&open_floor, settings.desired_speed);
+    assert!(
+        jammed > 0.2 && open_jammed < 0.05,
+        "{} {}",
+        jammed,
+        open_jammed
+    );
+    assert!(flow < open_flow * 0.7, "{} {}", flow, open_flow);
+}
+
+#[test]
+fn walkers_walled_in_by_the_brush_start_over() {
+    let settings = Settings::default();
+    let mut floor = Floor::build(Scene::Corridor, 64, 1.5, 0.8);
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut crowd = Crowd::spawn(100, headings(Scene::Corridor), &floor, &mut rng);
+    floor.paint([0.0, 0.0], 0.5, WALL);
+    crowd.refresh_fields(&floor);
+    crowd.step(&floor, &forces(&settings), 1.0 / 60.0, &mut rng);
+    for agent in &crowd.agents {
+        assert!(!floor.is_wall(floor.cell_of(agent.position)));
+    }
+}
+
+#[test]
+fn enums_parse_from_their_names() {
+    for scene in [
+        Scene::Corridor,
+        Scene::Counterflow,
+        Scene::Bottleneck,
+        Scene::Crossing,
+        Scene::Open,
+    ] {
+        assert_eq!(format!("{:?}", scene).parse::<Scene>(), Ok(scene));
+    }
+    assert_eq!("erase".parse::<BrushTool>(), Ok(BrushTool::Erase));
+    assert!("door".parse::<BrushTool>().is_err());
+}
+
+#[tokio::test]
+async fn shader_compiles() {
+    let harness = KernelHarness::new().await;
+    let _ = harness
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Crowd Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+}
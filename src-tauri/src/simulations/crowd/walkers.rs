@@ -0,0 +1,334 @@
+//! # Crowd Walkers
+//!
+//! Walkers pushed along by social forces, after Helbing and Molnár. Each
+//! one wants to walk its route at its own pace, and speeds up or turns
+//! toward it over the relaxation time. Those close by push it away, more
+//! so when they're ahead of it than behind, and walls push it back from
+//! them. Nobody plans a thing beyond that, yet corridors walked both ways
+//! sort themselves into lanes, and crowds at a door jam in waves that run
+//! back against the flow.
+//!
+//! Every walker belongs to a group that leaves by one edge and comes back
+//! in at the other, so the flow never runs dry.
+
+use rand::Rng;
+
+use super::floor::Floor;
+use super::settings::Scene;
+
+/// Seconds a walker's congestion takes to catch up with how held up it is
+pub const CONGESTION_TIME: f32 = 1.0;
+
+/// Congestion past which a walker counts as jammed
+pub const JAMMED: f32 = 0.6;
+
+/// Push ranges past touching at which walkers stop minding each other
+const CUTOFF_RANGES: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Agent {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    pub group: usize,
+    /// How held up the walker has been lately: 0 walking freely at its
+    /// pace, 1 at a standstill or pushed back
+    pub congestion: f32,
+}
+
+/// The social forces, from the settings of the same names
+#[derive(Debug, Clone, Copy)]
+pub struct Forces {
+    pub agent_radius: f32,
+    pub desired_speed: f32,
+    pub relaxation_time: f32,
+    pub repulsion: f32,
+    pub repulsion_range: f32,
+    pub wall_repulsion: f32,
+    pub anisotropy: f32,
+    pub noise: f32,
+}
+
+impl Forces {
+    /// How far apart walkers can be and still push each other
+    fn cutoff(&self) -> f32 {
+        2.0 * self.agent_radius + CUTOFF_RANGES * self.repulsion_range
+    }
+}
+
+/// Which way each of `scene`'s groups walks
+pub fn headings(scene: Scene) -> Vec<[f32; 2]> {
+    match scene {
+        Scene::Corridor | Scene::Bottleneck => vec![[1.0, 0.0]],
+        Scene::Counterflow | Scene::Open => vec![[1.0, 0.0], [-1.0, 0.0]],
+        Scene::Crossing => vec![[1.0, 0.0], [0.0, 1.0]],
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Crowd {
+    pub agents: Vec<Agent>,
+    headings: Vec<[f32; 2]>,
+    // A flow field over the floor for each group
+    fields: Vec<Vec<[f32; 2]>>,
+    // Walkers sorted into buckets the size of the push's reach, so only
+    // those nearby need checking
+    bucket_starts: Vec<usize>,
+    bucketed: Vec<usize>,
+}
+
+impl Crowd {
+    /// `count` walkers dropped on the open floor, shared out between the
+    /// groups walking along `headings`
+    pub fn spawn(count: usize, headings: Vec<[f32; 2]>, floor: &Floor, rng: &mut impl Rng) -> Self {
+        let groups = headings.len().max(1);
+        let size = floor.cell_size();
+        let agents = (0..count)
+            .filter_map(|index| {
+                let [x, y] = floor.random_open(rng)?;
+                let jitter = [
+                    rng.random_range(-0.5..0.5) * size,
+                    rng.random_range(-0.5..0.5) * size,
+                ];
+                Some(Agent {
+                    position: [x + jitter[0], y + jitter[1]],
+                    velocity: [0.0, 0.0],
+                    group: index % groups,
+                    congestion: 0.0,
+                })
+            })
+            .collect();
+        let mut crowd = Self {
+            agents,
+            headings,
+            fields: Vec::new(),
+            bucket_starts: Vec::new(),
+            bucketed: Vec::new(),
+        };
+        crowd.refresh_fields(floor);
+        crowd
+    }
+
+    /// Work the routes out again after the walls change
+    pub fn refresh_fields(&mut self, floor: &Floor) {
+        self.fields = self
+            .headings
+            .iter()
+            .map(|&heading| floor.flow_field(heading))
+            .collect();
+    }
+
+    /// The way a walker in `group` at `position` wants to go
+    pub fn route(&self, floor: &Floor, group: usize, position: [f32; 2]) -> [f32; 2] {
+        let (x, y) = floor.cell_of(position);
+        let (width, height) = (floor.width() as i32, floor.height() as i32);
+        let index = (y.rem_euclid(height) * width + x.rem_euclid(width)) as usize;
+        self.fields[group][index]
+    }
+
+    /// Average speed along their routes over the desired speed, and the
+    /// share of walkers jammed
+    pub fn flow(&self, floor: &Floor, desired_speed: f32) -> (f32, f32) {
+        if self.agents.is_empty() || desired_speed <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let count = self.agents.len() as f32;
+        let progress: f32 = self
+            .agents
+            .iter()
+            .map(|agent| {
+                dot(
+                    agent.velocity,
+                    self.route(floor, agent.group, agent.position),
+                )
+            })
+            .sum();
+        let jammed = self
+            .agents
+            .iter()
+            .filter(|agent| agent.congestion > JAMMED)
+            .count();
+        (progress / count / desired_speed, jammed as f32 / count)
+    }
+
+    /// Move everyone on by `dt` seconds
+    pub fn step(&mut self, floor: &Floor, forces: &Forces, dt: f32, rng: &mut impl Rng) {
+        if self.agents.is_empty() || dt <= 0.0 {
+            return;
+        }
+        let half_width = floor.half_width();
+        let span = [2.0 * half_width, 2.0];
+        let cutoff = forces.cutoff().max(1e-4);
+        let columns = ((span[0] / cutoff).floor() as usize).max(1);
+        let rows = ((span[1] / cutoff).floor() as usize).max(1);
+        self.bucket(columns, rows, half_width);
+
+        let accelerations: Vec<[f32; 2]> = (0..self.agents.len())
+            .map(|index| self.acceleration(index, floor, forces, (columns, rows), rng))
+            .collect();
+
+        let max_speed = 1.5 * forces.desired_speed.max(1e-3);
+        let settle = (dt / CONGESTION_TIME).min(1.0);
+        for (index, acceleration) in accelerations.into_iter().enumerate() {
+            let agent = self.agents[index];
+            let route = self.route(floor, agent.group, agent.position);
+            let mut velocity = [
+                agent.velocity[0] + acceleration[0] * dt,
+                agent.velocity[1] + acceleration[1] * dt,
+            ];
+            let speed = velocity[0].hypot(velocity[1]);
+            if speed > max_speed {
+                velocity = [
+                    velocity[0] * max_speed / speed,
+                    velocity[1] * max_speed / speed,
+                ];
+            }
+            let position = [
+                wrap(agent.position[0] + velocity[0] * dt, half_width),
+                wrap(agent.position[1] + velocity[1] * dt, 1.0),
+            ];
+            let held_up = if forces.desired_speed > 0.0 {
+                (1.0 - dot(velocity, route) / forces.desired_speed).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let agent = &mut self.agents[index];
+            agent.velocity = velocity;
+            agent.position = position;
+            agent.congestion += (held_up - agent.congestion) * settle;
+
+            // Walled in by a stroke of the brush: start over at the way in
+            if floor.is_wall(floor.cell_of(position))
+                && let Some(entrance) = floor.random_entrance(self.headings[agent.group], rng)
+            {
+                agent.position = entrance;
+                agent.velocity = [0.0, 0.0];
+                agent.congestion = 0.0;
+            }
+        }
+    }
+
+    fn bucket_of(
+        &self,
+        position: [f32; 2],
+        (columns, rows): (usize, usize),
+        half_width: f32,
+    ) -> usize {
+        let column = ((position[0] + half_width) / (2.0 * half_width) * columns as f32) as usize;
+        let row = ((position[1] + 1.0) * 0.5 * rows as f32) as usize;
+        row.min(rows - 1) * columns + column.min(columns - 1)
+    }
+
+    /// Sort the walkers into buckets by counting them into place
+    fn bucket(&mut self, columns: usize, rows: usize, half_width: f32) {
+        let buckets: Vec<usize> = self
+            .agents
+            .iter()
+            .map(|agent| self.bucket_of(agent.position, (columns, rows), half_width))
+            .collect();
+        self.bucket_starts = vec![0; columns * rows + 1];
+        for &bucket in &buckets {
+            self.bucket_starts[bucket + 1] += 1;
+        }
+        for bucket in 0..columns * rows {
+            self.bucket_starts[bucket + 1] += self.bucket_starts[bucket];
+        }
+        let mut next = self.bucket_starts.clone();
+        self.bucketed = vec![0; self.agents.len()];
+        for (index, &bucket) in buckets.iter().enumerate() {
+            self.bucketed[next[bucket]] = index;
+            next[bucket] += 1;
+        }
+    }
+
+    fn acceleration(
+        &self,
+        index: usize,
+        floor: &Floor,
+        forces: &Forces,
+        (columns, rows): (usize, usize),
+        rng: &mut impl Rng,
+    ) -> [f32; 2] {
+        let agent = &self.agents[index];
+        let half_width = floor.half_width();
+        let route = self.route(floor, agent.group, agent.position);
+        let relaxation = forces.relaxation_time.max(1e-3);
+        // Back up to speed along the route
+        let mut acceleration = [
+            (forces.desired_speed * route[0] - agent.velocity[0]) / relaxation,
+            (forces.desired_speed * route[1] - agent.velocity[1]) / relaxation,
+        ];
+
+        let cutoff = forces.cutoff();
+        let range = forces.repulsion_range.max(1e-4);
+        let touching = 2.0 * forces.agent_radius;
+        let bucket = self.bucket_of(agent.position, (columns, rows), half_width);
+        let (column, row) = ((bucket % columns) as i64, (bucket / columns) as i64);
+        for neighbor_row in neighbors(row, rows) {
+            for neighbor_column in neighbors(column, columns) {
+                let bucket = neighbor_row * columns + neighbor_column;
+                for &other in
+                    &self.bucketed[self.bucket_starts[bucket]..self.bucket_starts[bucket + 1]]
+                {
+                    if other == index {
+                        continue;
+                    }
+                    // The nearest way round, the floor wrapping
+                    let position = self.agents[other].position;
+                    let offset = [
+                        wrap(agent.position[0] - position[0], half_width),
+                        wrap(agent.position[1] - position[1], 1.0),
+                    ];
+                    let distance = offset[0].hypot(offset[1]);
+                    if distance >= cutoff || distance < 1e-6 {
+                        continue;
+                    }
+                    let away = [offset[0] / distance, offset[1] / distance];
+                    // Those ahead, along the route, count for the most
+                    let facing = -dot(away, route);
+                    let weight =
+                        forces.anisotropy + (1.0 - forces.anisotropy) * (1.0 + facing) * 0.5;
+                    let push = forces.repulsion * ((touching - distance) / range).exp() * weight;
+                    acceleration[0] += push * away[0];
+                    acceleration[1] += push * away[1];
+                }
+            }
+        }
+
+        if let Some((offset, distance)) = floor.nearest_wall(agent.position, cutoff)
+            && distance > 1e-6
+        {
+            let push =
+                forces.wall_repulsion * ((forces.agent_radius - distance) / range).exp() / distance;
+            acceleration[0] += push * offset[0];
+            acceleration[1] += push * offset[1];
+        }
+
+        if forces.noise > 0.0 {
+            let angle = rng.random_range(0.0..std::f32::consts::TAU);
+            acceleration[0] += forces.noise * angle.cos();
+            acceleration[1] += forces.noise * angle.sin();
+        }
+        acceleration
+    }
+}
+
+/// Buckets next to and including `index` out of `count`, wrapping round,
+/// each only once when there are fewer than three
+fn neighbors(index: i64, count: usize) -> impl Iterator<Item = usize> {
+    let count = count as i64;
+    let range = if count < 3 {
+        0..count
+    } else {
+        index - 1..index + 2
+    };
+    range.map(move |i| i.rem_euclid(count) as usize)
+}
+
+/// `value` wrapped into `-half..half`
+fn wrap(value: f32, half: f32) -> f32 {
+    (value + half).rem_euclid(2.0 * half) - half
+}
+
+fn dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
@@ -0,0 +1,216 @@
+//! # Cluster
+//!
+//! Diffusion-limited aggregation on a square lattice. Walkers start on a
+//! ring just beyond the cluster's farthest cell and wander a cell at a time
+//! up, down, left or right. A walker with a stuck cell among its eight
+//! neighbors sticks where it stands with probability `stickiness` and
+//! starts over on the ring; one that strays too far out starts over too,
+//! since it would take longer to wander back than to start again. Once the
+//! ring no longer fits in the box, walkers start anywhere in it instead.
+//!
+//! Every stuck cell holds the frame it stuck on plus one, so that 0 can
+//! mean empty, and the cluster is colored by when it grew.
+//!
+//! The cluster and walkers are laid out here and uploaded; from then on
+//! `walk.wgsl` runs them on the GPU. A copy of the walk is kept here for the
+//! tests.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use std::f32::consts::TAU;
+
+use super::settings::SeedLayout;
+
+/// Most cells in the lattice. Past it the resolution is lowered to fit.
+pub const MAX_GRID_CELLS: u32 = 1 << 23;
+
+/// Cells the scattered layout seeds
+pub const SCATTERED_SEEDS: usize = 24;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct Walker {
+    pub cell: [i32; 2],
+    /// The walker's own random state, advanced by every draw it makes
+    pub rng: u32,
+    pub _pad: u32,
+}
+
+/// Cells across and up a box `aspect` times as wide as it is high, with
+/// `resolution` cells up it
+pub fn grid_size(resolution: u32, aspect: f32) -> (u32, u32) {
+    let mut height = resolution.max(1);
+    let mut width = ((height as f32 * aspect).round() as u32).max(1);
+    if width as u64 * height as u64 > MAX_GRID_CELLS as u64 {
+        let shrink = ((width as f64 * height as f64) / MAX_GRID_CELLS as f64).sqrt();
+        width = ((width as f64 / shrink).floor() as u32).max(1);
+        height = ((height as f64 / shrink).floor() as u32).max(1);
+    }
+    (width, height)
+}
+
+fn grid_center((width, height): (u32, u32)) -> [f32; 2] {
+    [width as f32 * 0.5, height as f32 * 0.5]
+}
+
+/// How far a cell lies from the middle of the lattice, rounded up to whole
+/// cells as the cluster's reach is kept
+pub fn reach_of(cell: [i32; 2], grid: (u32, u32)) -> u32 {
+    let center = grid_center(grid);
+    let x = cell[0] as f32 + 0.5 - center[0];
+    let y = cell[1] as f32 + 0.5 - center[1];
+    x.hypot(y).ceil() as u32
+}
+
+fn index_of(cell: [i32; 2], (width, _): (u32, u32)) -> usize {
+    cell[1] as usize * width as usize + cell[0] as usize
+}
+
+#[cfg(test)]
+fn in_grid(cell: [i32; 2], (width, height): (u32, u32)) -> bool {
+    cell[0] >= 0 && cell[1] >= 0 && cell[0] < width as i32 && cell[1] < height as i32
+}
+
+/// The lattice with the layout's seed cells stuck on frame zero, and the
+/// reach of the farthest of them
+pub fn seed_cluster(layout: SeedLayout, grid: (u32, u32), rng: &mut impl Rng) -> (Vec<u32>, u32) {
+    let (width, height) = grid;
+    let mut cells = vec![0; width as usize * height as usize];
+    let seeds: Vec<[i32; 2]> = match layout {
+        SeedLayout::Point => vec![[(width / 2) as i32, (height / 2) as i32]],
+        SeedLayout::Scattered => (0..SCATTERED_SEEDS)
+            .map(|_| {
+                [
+                    rng.random_range(0..width) as i32,
+                    rng.random_range(0..height) as i32,
+                ]
+            })
+            .collect(),
+        SeedLayout::Empty => Vec::new(),
+    };
+    let mut reach = 0;
+    for seed in seeds {
+        cells[index_of(seed, grid)] = 1;
+        reach = reach.max(reach_of(seed, grid));
+    }
+    (cells, reach)
+}
+
+/// Radius of the ring walkers start on, in cells, if it fits in the box
+fn spawn_ring(reach: u32, margin: f32, grid: (u32, u32)) -> Option<f32> {
+    let center = grid_center(grid);
+    let radius = reach as f32 + margin;
+    (radius < center[0].min(center[1])).then_some(radius)
+}
+
+/// Where a walker starts over, as `spawn` in `walk.wgsl` picks it: on the
+/// ring `margin` cells beyond the cluster's reach, or anywhere once the
+/// ring is wider than the box
+pub fn spawn_cell(reach: u32, margin: f32, grid: (u32, u32), rng: &mut impl Rng) -> [i32; 2] {
+    let (width, height) = grid;
+    let point = match spawn_ring(reach, margin, grid) {
+        Some(radius) => {
+            let angle = rng.random_range(0.0..TAU);
+            let center = grid_center(grid);
+            [
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]
+        }
+        None => [
+            rng.random_range(0.0..1.0) * width as f32,
+            rng.random_range(0.0..1.0) * height as f32,
+        ],
+    };
+    [
+        (point[0].floor() as i32).clamp(0, width as i32 - 1),
+        (point[1].floor() as i32).clamp(0, height as i32 - 1),
+    ]
+}
+
+/// `count` walkers on the ring round a cluster of the given reach, each
+/// with its own random state
+pub fn seed_walkers(
+    count: usize,
+    reach: u32,
+    margin: f32,
+    grid: (u32, u32),
+    rng: &mut impl Rng,
+) -> Vec<Walker> {
+    (0..count)
+        .map(|_| Walker {
+            cell: spawn_cell(reach, margin, grid, rng),
+            rng: rng.random(),
+            _pad: 0,
+        })
+        .collect()
+}
+
+/// How walkers wander and stick, from the settings of the same names with
+/// the spawn radius in cells
+#[cfg(test)]
+pub struct Walking {
+    pub stickiness: f32,
+    pub margin: f32,
+}
+
+/// The cluster and the reach of its farthest cell as one walker meets them
+#[cfg(test)]
+pub struct Lattice<'a> {
+    pub cells: &'a mut [u32],
+    pub reach: &'a mut u32,
+    pub grid: (u32, u32),
+}
+
+#[cfg(test)]
+fn filled(cell: [i32; 2], lattice: &Lattice) -> bool {
+    in_grid(cell, lattice.grid) && lattice.cells[index_of(cell, lattice.grid)] != 0
+}
+
+#[cfg(test)]
+fn touching(cell: [i32; 2], lattice: &Lattice) -> bool {
+    (-1..=1).any(|dy| {
+        (-1..=1).any(|dx| (dx, dy) != (0, 0) && filled([cell[0] + dx, cell[1] + dy], lattice))
+    })
+}
+
+/// One step of a walker on frame `frame`, as each step of `walk` in
+/// `walk.wgsl` takes it, with `rng` drawing in place of the walker's own
+/// random state
+#[cfg(test)]
+pub fn step(
+    cell: [i32; 2],
+    lattice: &mut Lattice,
+    walking: &Walking,
+    frame: u32,
+    rng: &mut impl Rng,
+) -> [i32; 2] {
+    let respawn = |lattice: &Lattice, rng: &mut _| {
+        spawn_cell(*lattice.reach, walking.margin, lattice.grid, rng)
+    };
+    if filled(cell, lattice) {
+        return respawn(lattice, rng);
+    }
+    if touching(cell, lattice) && rng.random::<f32>() < walking.stickiness {
+        lattice.cells[index_of(cell, lattice.grid)] = frame + 1;
+        *lattice.reach = (*lattice.reach).max(reach_of(cell, lattice.grid));
+        return respawn(lattice, rng);
+    }
+
+    let offset = [[1, 0], [-1, 0], [0, 1], [0, -1]][rng.random_range(0..4)];
+    let next = [cell[0] + offset[0], cell[1] + offset[1]];
+    let cell = if in_grid(next, lattice.grid) && !filled(next, lattice) {
+        next
+    } else {
+        cell
+    };
+
+    if let Some(radius) = spawn_ring(*lattice.reach, walking.margin, lattice.grid) {
+        let center = grid_center(lattice.grid);
+        let out = (cell[0] as f32 + 0.5 - center[0]).hypot(cell[1] as f32 + 0.5 - center[1]);
+        if out > radius + 2.0 * walking.margin {
+            return respawn(lattice, rng);
+        }
+    }
+    cell
+}
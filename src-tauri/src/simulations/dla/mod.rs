@@ -0,0 +1,61 @@
+pub mod cluster;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::DlaModel;
+
+use crate::simulation::preset_manager::{DlaPresetManager, Preset};
+
+/// Initialize DLA presets with built-in configurations
+pub fn init_presets(preset_manager: &mut DlaPresetManager) {
+    use settings::{SeedLayout, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Coral".to_string(),
+        Settings {
+            stickiness: 0.05,
+            walker_count: 100_000,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Lightning".to_string(),
+        Settings {
+            resolution: 1024,
+            walker_count: 200_000,
+            steps_per_frame: 32,
+            spawn_radius: 0.01,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Frost".to_string(),
+        Settings {
+            seed_layout: SeedLayout::Scattered,
+            stickiness: 0.4,
+            walker_count: 150_000,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Blank Canvas".to_string(),
+        Settings {
+            seed_layout: SeedLayout::Empty,
+            show_walkers: true,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,127 @@
+//! # DLA Settings Module
+//!
+//! How fine the lattice is, how many walkers wander it and how fast, how
+//! readily they stick to the cluster and how far out they start, and what
+//! the cluster grows from.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// What the cluster grows from when seeded
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SeedLayout {
+    /// One cell in the middle of the box
+    #[default]
+    Point,
+    /// A few cells anywhere in the box
+    Scattered,
+    /// Nothing, until a click seeds it
+    Empty,
+}
+
+impl FromStr for SeedLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "point" => Ok(SeedLayout::Point),
+            "scattered" => Ok(SeedLayout::Scattered),
+            "empty" => Ok(SeedLayout::Empty),
+            _ => Err(format!(
+                "Invalid SeedLayout: '{}'. Expected 'Point', 'Scattered', or 'Empty'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Cells up the box, with as many more across as the window is wider
+    pub resolution: u32,
+    /// Walkers wandering at once; fewer walk if the GPU can't hold them
+    pub walker_count: u32,
+    /// Lattice steps each walker takes every frame
+    pub steps_per_frame: u32,
+    /// Chance a walker next to the cluster sticks there on each step. Lower
+    /// lets walkers slip further in, growing thicker branches.
+    pub stickiness: f32,
+    /// How far beyond the cluster's farthest cell walkers start, in box
+    /// heights
+    pub spawn_radius: f32,
+    pub seed_layout: SeedLayout,
+    pub random_seed: u32,
+    /// Draw the walkers over the cluster
+    pub show_walkers: bool,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            resolution: 512,
+            walker_count: 50_000,
+            steps_per_frame: 16,
+            stickiness: 1.0,
+            spawn_radius: 0.02,
+            seed_layout: SeedLayout::Point,
+            random_seed: 0,
+            show_walkers: false,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("resolution", Rule::Count { min: 64, max: 2048 }),
+        (
+            "walker_count",
+            Rule::Count {
+                min: 1,
+                max: 1_000_000,
+            },
+        ),
+        ("steps_per_frame", Rule::Count { min: 1, max: 256 }),
+        (
+            "stickiness",
+            Rule::Range {
+                min: 0.01,
+                max: 1.0,
+            },
+        ),
+        (
+            "spawn_radius",
+            Rule::Range {
+                min: 0.005,
+                max: 0.5,
+            },
+        ),
+        ("seed_layout", Rule::OneOf(&["Point", "Scattered", "Empty"])),
+        (
+            "random_seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+        ("show_walkers", Rule::Flag),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("seed_layout", SettingCategory::Generators),
+    ("random_seed", SettingCategory::Generators),
+]);
@@ -0,0 +1,50 @@
+// Shared by every DLA pass, each of which binds `params`. The lattice is
+// laid over the box row by row from the bottom, `grid_height` cells up it,
+// and the box is `aspect` wide either side of the origin and 1 high either
+// side.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    grid_width: u32,
+    grid_height: u32,
+    walker_count: u32,
+    // Frames grown since seeding; cells stuck this frame hold it plus one
+    frame: u32,
+    steps: u32,
+    stickiness: f32,
+    // How far beyond the cluster's reach walkers start, in cells
+    spawn_margin: f32,
+    // 0 off, 1 seeds the cluster, 2 erases it
+    brush_mode: u32,
+    // In cells
+    brush_center: vec2<f32>,
+    brush_radius: f32,
+    _pad: u32,
+}
+
+struct Walker {
+    cell: vec2<i32>,
+    rng: u32,
+    _pad: u32,
+}
+
+const TAU: f32 = 6.28318530718;
+
+fn grid_extent() -> vec2<f32> {
+    return vec2<f32>(f32(params.grid_width), f32(params.grid_height));
+}
+
+fn in_grid(cell: vec2<i32>) -> bool {
+    return all(cell >= vec2<i32>(0)) && all(cell < vec2<i32>(grid_extent()));
+}
+
+fn cell_index(cell: vec2<i32>) -> u32 {
+    return u32(cell.y) * params.grid_width + u32(cell.x);
+}
+
+// From the middle of the lattice to the middle of the cell, in cells
+fn distance_out(cell: vec2<i32>) -> f32 {
+    return length(vec2<f32>(cell) + 0.5 - grid_extent() * 0.5);
+}
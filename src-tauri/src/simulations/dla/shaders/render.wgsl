@@ -0,0 +1,59 @@
+// The lattice is drawn through the camera, each stuck cell colored by when
+// it stuck: the seeds at one end of the color scheme and this frame's
+// growth at the other. Walkers can be drawn over it as single points.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> cluster: array<u32>;
+@group(0) @binding(2) var<storage, read> walkers: array<Walker>;
+@group(0) @binding(3) var<storage, read> lut_data: array<u32>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+struct FullscreenOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// One triangle covering the target
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return FullscreenOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+@fragment
+fn fs_cluster(input: FullscreenOutput) -> @location(0) vec4<f32> {
+    // The box spans -1 to 1 both ways in the camera's coordinates
+    let box_ndc = input.ndc / params.view_zoom + params.view_center;
+    if (abs(box_ndc.x) > 1.0 || abs(box_ndc.y) > 1.0) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    let grid = vec2<i32>(grid_extent());
+    let cell = clamp(vec2<i32>(floor((box_ndc * 0.5 + 0.5) * grid_extent())), vec2<i32>(0), grid - 1);
+    let stuck = cluster[cell_index(cell)];
+    if (stuck == 0u) {
+        return vec4<f32>(vec3<f32>(0.015), 1.0);
+    }
+    let age = f32(stuck - 1u) / max(f32(params.frame), 1.0);
+    return vec4<f32>(lut_color(age), 1.0);
+}
+
+@vertex
+fn vs_walker(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let cell = vec2<f32>(walkers[vertex_index].cell) + 0.5;
+    let box_ndc = cell / grid_extent() * 2.0 - 1.0;
+    return vec4<f32>((box_ndc - params.view_center) * params.view_zoom, 0.0, 1.0);
+}
+
+@fragment
+fn fs_walker() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.45, 0.5, 0.55, 1.0);
+}
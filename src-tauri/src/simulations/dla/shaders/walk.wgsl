@@ -0,0 +1,133 @@
+// Each walker takes `steps` lattice steps a frame, sticking to the cluster
+// or starting over as `cluster.rs` describes. Walkers race one another for
+// the cells they stick to; the loser of a race starts over as if it had
+// stuck, so no cell is claimed twice.
+//
+// `paint` seeds or erases a disc of the cluster under the brush.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> cluster: array<atomic<u32>>;
+@group(0) @binding(2) var<storage, read_write> walkers: array<Walker>;
+// Farthest any stuck cell lies from the middle, in whole cells
+@group(0) @binding(3) var<storage, read_write> reach: atomic<u32>;
+
+fn hash(seed: u32) -> u32 {
+    var x = seed;
+    x ^= x >> 16u;
+    x *= 0x7feb352du;
+    x ^= x >> 15u;
+    x *= 0x846ca68bu;
+    x ^= x >> 16u;
+    return x;
+}
+
+// Uniform in [0, 1), advancing `state` for the next draw
+fn random(state: ptr<function, u32>) -> f32 {
+    *state = hash(*state);
+    return f32(*state >> 8u) / 16777216.0;
+}
+
+fn filled(cell: vec2<i32>) -> bool {
+    return in_grid(cell) && atomicLoad(&cluster[cell_index(cell)]) != 0u;
+}
+
+fn touching(cell: vec2<i32>) -> bool {
+    for (var dy = -1; dy <= 1; dy++) {
+        for (var dx = -1; dx <= 1; dx++) {
+            if ((dx != 0 || dy != 0) && filled(cell + vec2<i32>(dx, dy))) {
+                return true;
+            }
+        }
+    }
+    return false;
+}
+
+fn ring_radius() -> f32 {
+    return f32(atomicLoad(&reach)) + params.spawn_margin;
+}
+
+fn ring_fits(radius: f32) -> bool {
+    let half = grid_extent() * 0.5;
+    return radius < min(half.x, half.y);
+}
+
+fn spawn(state: ptr<function, u32>) -> vec2<i32> {
+    let radius = ring_radius();
+    var point: vec2<f32>;
+    if (ring_fits(radius)) {
+        let angle = random(state) * TAU;
+        point = grid_extent() * 0.5 + radius * vec2<f32>(cos(angle), sin(angle));
+    } else {
+        let x = random(state);
+        point = vec2<f32>(x, random(state)) * grid_extent();
+    }
+    return clamp(vec2<i32>(floor(point)), vec2<i32>(0), vec2<i32>(grid_extent()) - 1);
+}
+
+fn stick(cell: vec2<i32>) {
+    let claimed = atomicCompareExchangeWeak(&cluster[cell_index(cell)], 0u, params.frame + 1u);
+    if (claimed.exchanged) {
+        atomicMax(&reach, u32(ceil(distance_out(cell))));
+    }
+}
+
+@compute @workgroup_size(64)
+fn walk(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if (index >= params.walker_count) {
+        return;
+    }
+    var walker = walkers[index];
+    var state = walker.rng;
+    var cell = walker.cell;
+    var offsets = array<vec2<i32>, 4>(
+        vec2<i32>(1, 0),
+        vec2<i32>(-1, 0),
+        vec2<i32>(0, 1),
+        vec2<i32>(0, -1),
+    );
+
+    for (var step = 0u; step < params.steps; step++) {
+        // Seeded over, or beaten to this cell by another walker
+        if (filled(cell)) {
+            cell = spawn(&state);
+            continue;
+        }
+        if (touching(cell) && random(&state) < params.stickiness) {
+            stick(cell);
+            cell = spawn(&state);
+            continue;
+        }
+
+        let next = cell + offsets[min(u32(random(&state) * 4.0), 3u)];
+        if (in_grid(next) && !filled(next)) {
+            cell = next;
+        }
+
+        let radius = ring_radius();
+        if (ring_fits(radius) && distance_out(cell) > radius + 2.0 * params.spawn_margin) {
+            cell = spawn(&state);
+        }
+    }
+
+    walkers[index] = Walker(cell, state, 0u);
+}
+
+@compute @workgroup_size(8, 8)
+fn paint(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height || params.brush_mode == 0u) {
+        return;
+    }
+    let cell = vec2<i32>(id.xy);
+    if (length(vec2<f32>(cell) + 0.5 - params.brush_center) > params.brush_radius) {
+        return;
+    }
+    let index = cell_index(cell);
+    if (params.brush_mode == 2u) {
+        atomicStore(&cluster[index], 0u);
+    } else if (atomicLoad(&cluster[index]) == 0u) {
+        // Walkers don't run during this pass, so nothing races for the cell
+        atomicStore(&cluster[index], params.frame + 1u);
+        atomicMax(&reach, u32(ceil(distance_out(cell))));
+    }
+}
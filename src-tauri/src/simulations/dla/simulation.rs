@@ -0,0 +1,752 @@
+//! # DLA Simulation Module
+//!
+//! Diffusion-limited aggregation: particles wander at random and stick to
+//! the cluster where they bump into it, growing it into branching,
+//! lightning-like shapes; see [`cluster`] for the rules. The left button
+//! seeds new growth under the cursor and the right button erases it.
+//!
+//! ## Technical Overview
+//!
+//! Everything runs on the GPU once seeded. Each frame:
+//! 1. While a button is down, the cells under the brush are seeded or
+//!    erased.
+//! 2. Every walker takes its steps, racing the others for the cells it
+//!    sticks to.
+//! 3. The lattice is drawn through the camera, colored by when each cell
+//!    stuck, with the walkers over it if they're shown.
+//!
+//! [`cluster`]: super::cluster
+
+use bytemuck::{Pod, Zeroable};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, BlendState, Buffer,
+    BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    PipelineLayoutDescriptor, PrimitiveTopology, Queue, RenderPipeline, RenderPipelineDescriptor,
+    ShaderModule, ShaderStages, SurfaceConfiguration, TextureFormat, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_budget::GpuReservation;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, ParticleBuffer,
+};
+use crate::simulations::traits::Simulation;
+
+use super::cluster::{self, Walker};
+use super::settings::{SeedLayout, Settings};
+use super::shaders::{RENDER_SHADER, WALK_SHADER};
+use super::state::State;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    grid_width: u32,
+    grid_height: u32,
+    walker_count: u32,
+    frame: u32,
+    steps: u32,
+    stickiness: f32,
+    spawn_margin: f32,
+    brush_mode: u32,
+    brush_center: [f32; 2],
+    brush_radius: f32,
+    _pad: u32,
+}
+
+/// What the brush does to the cluster while a button is down, at a point
+/// in cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Brush {
+    Off,
+    Seed([f32; 2]),
+    Erase([f32; 2]),
+}
+
+/// Everything the lattice's bind groups point at besides the lattice itself
+#[derive(Debug)]
+struct Resources {
+    walk_bind_group_layout: BindGroupLayout,
+    render_bind_group_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+}
+
+/// The cluster and its reach on the GPU, and the bind groups over them and
+/// the walkers, remade when the lattice or the walker count changes
+#[derive(Debug)]
+struct Lattice {
+    grid_width: u32,
+    grid_height: u32,
+    walker_count: u32,
+    cluster: Buffer,
+    reach: Buffer,
+    walk_bind_group: BindGroup,
+    render_bind_group: BindGroup,
+    _memory: GpuReservation,
+}
+
+impl Lattice {
+    fn new(
+        device: &Device,
+        walkers: &ParticleBuffer<Walker>,
+        walker_count: u32,
+        (grid_width, grid_height): (u32, u32),
+        resources: &Resources,
+    ) -> Self {
+        let cluster_size =
+            grid_width as u64 * grid_height as u64 * std::mem::size_of::<u32>() as u64;
+        let memory = GpuReservation::new(cluster_size);
+
+        let cluster = device.create_buffer(&BufferDescriptor {
+            label: Some("DLA Cluster Buffer"),
+            size: cluster_size,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let reach = device.create_buffer(&BufferDescriptor {
+            label: Some("DLA Reach Buffer"),
+            size: std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let walk_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("DLA Walk Bind Group"),
+            layout: &resources.walk_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &resources.params_buffer),
+                resource_helpers::buffer_entry(1, &cluster),
+                resource_helpers::buffer_entry(2, walkers.buffer()),
+                resource_helpers::buffer_entry(3, &reach),
+            ],
+        });
+        let render_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("DLA Render Bind Group"),
+            layout: &resources.render_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &resources.params_buffer),
+                resource_helpers::buffer_entry(1, &cluster),
+                resource_helpers::buffer_entry(2, walkers.buffer()),
+                resource_helpers::buffer_entry(3, &resources.lut_buffer),
+            ],
+        });
+
+        Self {
+            grid_width,
+            grid_height,
+            walker_count,
+            cluster,
+            reach,
+            walk_bind_group,
+            render_bind_group,
+            _memory: memory,
+        }
+    }
+
+    fn grid(&self) -> (u32, u32) {
+        (self.grid_width, self.grid_height)
+    }
+}
+
+#[derive(Debug)]
+pub struct DlaModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    walk_pipeline: ComputePipeline,
+    paint_pipeline: ComputePipeline,
+    cluster_pipeline: RenderPipeline,
+    walker_pipeline: RenderPipeline,
+    resources: Resources,
+    walkers: ParticleBuffer<Walker>,
+    lattice: Lattice,
+
+    brush: Brush,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl DlaModel {
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let walk_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("DLA Walk Shader"),
+            source: wgpu::ShaderSource::Wgsl(WALK_SHADER.into()),
+        });
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("DLA Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("DLA Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("DLA LUT Buffer for {}", state.color_scheme_name)),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let walk_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("DLA Walk Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, false),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, false),
+            ],
+        });
+
+        let render_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("DLA Render Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(
+                        0,
+                        ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    ),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::FRAGMENT, true),
+                    resource_helpers::storage_buffer_entry(2, ShaderStages::VERTEX, true),
+                    resource_helpers::storage_buffer_entry(3, ShaderStages::FRAGMENT, true),
+                ],
+            });
+
+        let compute_pipeline = |label: &str, entry_point| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(&format!("DLA {} Pipeline", label)),
+                layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                    label: Some(&format!("DLA {} Pipeline Layout", label)),
+                    bind_group_layouts: &[&walk_bind_group_layout],
+                    push_constant_ranges: &[],
+                })),
+                module: &walk_module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let walk_pipeline = compute_pipeline("Walk", "walk");
+        let paint_pipeline = compute_pipeline("Paint", "paint");
+
+        let cluster_pipeline = create_pipeline(
+            device,
+            "DLA Cluster Pipeline",
+            &render_bind_group_layout,
+            &render_module,
+            ("vs_fullscreen", "fs_cluster"),
+            (PrimitiveTopology::TriangleList, surface_config.format),
+        );
+        let walker_pipeline = create_pipeline(
+            device,
+            "DLA Walker Pipeline",
+            &render_bind_group_layout,
+            &render_module,
+            ("vs_walker", "fs_walker"),
+            (PrimitiveTopology::PointList, surface_config.format),
+        );
+
+        let resources = Resources {
+            walk_bind_group_layout,
+            render_bind_group_layout,
+            params_buffer,
+            lut_buffer,
+        };
+        // Replaced by rebuild_lattice once the model exists
+        let walkers =
+            ParticleBuffer::uninitialized(device, "DLA Walkers Buffer", BufferUsages::empty(), 1);
+        let lattice = Lattice::new(device, &walkers, 1, (1, 1), &resources);
+
+        let mut model = Self {
+            settings,
+            state,
+            walk_pipeline,
+            paint_pipeline,
+            cluster_pipeline,
+            walker_pipeline,
+            resources,
+            walkers,
+            lattice,
+            brush: Brush::Off,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.rebuild_lattice(device, queue)?;
+        Ok(model)
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// Walkers start this many cells beyond the cluster's reach
+    fn spawn_margin(&self) -> f32 {
+        (self.settings.spawn_radius * self.lattice.grid_height as f32).max(2.0)
+    }
+
+    /// Remake the lattice and walkers for the window and settings, and seed
+    /// them
+    fn rebuild_lattice(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let grid = cluster::grid_size(self.settings.resolution, self.aspect());
+        let walker_count = self
+            .walkers
+            .reallocate(device, self.settings.walker_count.max(1) as usize);
+        self.lattice = Lattice::new(
+            device,
+            &self.walkers,
+            walker_count as u32,
+            grid,
+            &self.resources,
+        );
+        self.state.walker_count = self.lattice.walker_count;
+        self.state.grid_width = self.lattice.grid_width;
+        self.state.grid_height = self.lattice.grid_height;
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Lay the seed cells and the walkers out from the seed
+    fn seed(&mut self, queue: &Arc<Queue>) {
+        let mut rng = StdRng::seed_from_u64(self.settings.random_seed as u64);
+        let grid = self.lattice.grid();
+        let (cells, reach) = cluster::seed_cluster(self.settings.seed_layout, grid, &mut rng);
+        let walkers = cluster::seed_walkers(
+            self.lattice.walker_count as usize,
+            reach,
+            self.spawn_margin(),
+            grid,
+            &mut rng,
+        );
+        queue.write_buffer(&self.lattice.cluster, 0, bytemuck::cast_slice(&cells));
+        queue.write_buffer(&self.lattice.reach, 0, bytemuck::bytes_of(&reach));
+        queue.write_buffer(self.walkers.buffer(), 0, bytemuck::cast_slice(&walkers));
+        self.state.frame = 0;
+    }
+
+    fn write_params(&self, queue: &Arc<Queue>) {
+        let (brush_mode, brush_center) = match self.brush {
+            Brush::Off => (0, [0.0; 2]),
+            Brush::Seed(center) => (1, center),
+            Brush::Erase(center) => (2, center),
+        };
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            grid_width: self.lattice.grid_width,
+            grid_height: self.lattice.grid_height,
+            walker_count: self.lattice.walker_count,
+            frame: self.state.frame,
+            steps: self.settings.steps_per_frame.max(1),
+            stickiness: self.settings.stickiness,
+            spawn_margin: self.spawn_margin(),
+            brush_mode,
+            brush_center,
+            // At least the cell clicked on
+            brush_radius: (self.state.cursor_size * self.lattice.grid_height as f32).max(0.75),
+            _pad: 0,
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
+    /// Paint under the brush, if it's down, and walk the walkers if
+    /// `walking`
+    fn encode_step(&self, encoder: &mut wgpu::CommandEncoder, walking: bool) {
+        if self.brush == Brush::Off && !walking {
+            return;
+        }
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("DLA Step Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &self.lattice.walk_bind_group, &[]);
+        if self.brush != Brush::Off {
+            compute_pass.set_pipeline(&self.paint_pipeline);
+            compute_pass.dispatch_workgroups(
+                self.lattice.grid_width.div_ceil(8),
+                self.lattice.grid_height.div_ceil(8),
+                1,
+            );
+        }
+        if walking {
+            compute_pass.set_pipeline(&self.walk_pipeline);
+            compute_pass.dispatch_workgroups(self.lattice.walker_count.div_ceil(64), 1, 1);
+        }
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("DLA Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_bind_group(0, &self.lattice.render_bind_group, &[]);
+        render_pass.set_pipeline(&self.cluster_pipeline);
+        render_pass.draw(0..3, 0..1);
+        if self.settings.show_walkers {
+            render_pass.set_pipeline(&self.walker_pipeline);
+            render_pass.draw(0..self.lattice.walker_count, 0..1);
+        }
+    }
+}
+
+fn create_pipeline(
+    device: &Device,
+    label: &str,
+    bind_group_layout: &BindGroupLayout,
+    module: &ShaderModule,
+    (vertex_entry, fragment_entry): (&str, &str),
+    (topology, format): (PrimitiveTopology, TextureFormat),
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(&format!("{} Layout", label)),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex_entry),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for DlaModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        self.state.frame = self.state.frame.saturating_add(1);
+        self.write_params(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("DLA Render"),
+        });
+        self.encode_step(&mut encoder, true);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.write_params(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("DLA Render Paused"),
+        });
+        // Seeding and erasing still work while paused
+        self.encode_step(&mut encoder, false);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn fast_forward(
+        &mut self,
+        steps: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Each frame needs its own number, so each step is its own submission
+        for _ in 0..steps {
+            self.state.frame = self.state.frame.saturating_add(1);
+            self.write_params(queue);
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("DLA Fast Forward"),
+            });
+            self.encode_step(&mut encoder, true);
+            queue.submit([encoder.finish()]);
+        }
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.rebuild_lattice(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let center = [
+            (world_x * 0.5 + 0.5) * self.lattice.grid_width as f32,
+            (world_y * 0.5 + 0.5) * self.lattice.grid_height as f32,
+        ];
+        // Left seeds new growth, right erases
+        self.brush = match mouse_button {
+            0 => Brush::Seed(center),
+            2 => Brush::Erase(center),
+            _ => return Ok(()),
+        };
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.brush = Brush::Off;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.resolution != self.settings.resolution
+            || old_settings.walker_count != self.settings.walker_count
+        {
+            self.rebuild_lattice(device, queue)?;
+        } else if old_settings.seed_layout != self.settings.seed_layout
+            || old_settings.random_seed != self.settings.random_seed
+        {
+            self.reset_runtime_state(device, queue)?;
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.seed(queue);
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.stickiness = rng.random_range(0.05..1.0);
+        self.settings.spawn_radius = rng.random_range(0.01..0.05);
+        self.settings.seed_layout = if rng.random_bool(0.7) {
+            SeedLayout::Point
+        } else {
+            SeedLayout::Scattered
+        };
+        self.settings.random_seed = rng.random();
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "resolution" => {
+                self.settings.resolution = number(setting_name, &value)? as u32;
+                self.rebuild_lattice(device, queue)?;
+            }
+            "walker_count" => {
+                self.settings.walker_count = number(setting_name, &value)? as u32;
+                self.rebuild_lattice(device, queue)?;
+            }
+            "steps_per_frame" => {
+                self.settings.steps_per_frame = number(setting_name, &value)? as u32;
+            }
+            "stickiness" => self.settings.stickiness = number(setting_name, &value)? as f32,
+            "spawn_radius" => self.settings.spawn_radius = number(setting_name, &value)? as f32,
+            "seed_layout" => {
+                self.settings.seed_layout = value
+                    .as_str()
+                    .unwrap_or("Point")
+                    .parse()
+                    .map_err(|e| format!("Invalid seed layout: {}", e))?;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "random_seed" => {
+                self.settings.random_seed = number(setting_name, &value)? as u32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "show_walkers" => self.settings.show_walkers = value.as_bool().unwrap_or(false),
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "cursor_size" => {
+                self.state.cursor_size = number(state_name, &value)?.clamp(0.002, 0.2) as f32;
+            }
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Walkers wandering, which is fewer than asked for if the GPU couldn't
+    // hold them all, and the lattice's size in cells
+    pub walker_count: u32,
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Frames grown since the cluster was seeded
+    pub frame: u32,
+
+    // Radius in box heights of the brush the left button seeds the cluster
+    // with and the right button erases it with
+    pub cursor_size: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            walker_count: 0,
+            grid_width: 0,
+            grid_height: 0,
+            frame: 0,
+            cursor_size: 0.01,
+            color_scheme_name: "MATPLOTLIB_magma".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::cluster::{
+    self, Lattice, MAX_GRID_CELLS, SCATTERED_SEEDS, Walking, grid_size, reach_of, seed_cluster,
+    seed_walkers, spawn_cell,
+};
+use super::settings::{SETTING_RULES, SeedLayout, Settings};
+use super::shaders::{RENDER_SHADER, WALK_SHADER};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+/// Grows a cluster from `cells` with `walkers` walkers for `frames` frames
+/// of one step each, returning its reach
+fn grow(
+    cells: &mut [u32],
+    reach: u32,
+    grid: (u32, u32),
+    walking: &Walking,
+    walkers: usize,
+    frames: u32,
+) -> u32 {
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut reach = reach;
+    let mut positions: Vec<[i32; 2]> = seed_walkers(walkers, reach, walking.margin, grid, &mut rng)
+        .iter()
+        .map(|walker| walker.cell)
+        .collect();
+    for frame in 1..=frames {
+        for position in &mut positions {
+            let mut lattice = Lattice {
+                cells: &mut *cells,
+                reach: &mut reach,
+                grid,
+            };
+            *position = cluster::step(*position, &mut lattice, walking, frame, &mut rng);
+        }
+    }
+    reach
+}
+
+fn neighbors(cell: [i32; 2], (width, height): (u32, u32)) -> impl Iterator<Item = usize> {
+    (-1..=1)
+        .flat_map(move |dy| (-1..=1).map(move |dx| [cell[0] + dx, cell[1] + dy]))
+        .filter(move |&[x, y]| {
+            [x, y] != cell && x >= 0 && y >= 0 && x < width as i32 && y < height as i32
+        })
+        .map(move |[x, y]| y as usize * width as usize + x as usize)
+}
+
+#[test]
+fn the_lattice_follows_the_window_shape() {
+    assert_eq!(grid_size(512, 1.0), (512, 512));
+    assert_eq!(grid_size(512, 1.5), (768, 512));
+    let (width, height) = grid_size(2048, 4.0);
+    assert!(width * height <= MAX_GRID_CELLS);
+    assert!(width > height * 3);
+}
+
+#[test]
+fn seed_layouts_stick_on_frame_zero() {
+    let grid = (64, 48);
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let (cells, reach) = seed_cluster(SeedLayout::Point, grid, &mut rng);
+    assert_eq!(cells.iter().filter(|&&cell| cell != 0).count(), 1);
+    assert_eq!(cells[24 * 64 + 32], 1);
+    assert_eq!(reach, 1);
+
+    let (cells, reach) = seed_cluster(SeedLayout::Empty, grid, &mut rng);
+    assert!(cells.iter().all(|&cell| cell == 0));
+    assert_eq!(reach, 0);
+
+    let (cells, reach) = seed_cluster(SeedLayout::Scattered, grid, &mut rng);
+    let seeded = cells.iter().filter(|&&cell| cell != 0).count();
+    assert!(seeded > 0 && seeded <= SCATTERED_SEEDS);
+    assert!(cells.iter().all(|&cell| cell <= 1));
+    assert!(reach > 1);
+}
+
+#[test]
+fn walkers_start_on_the_ring_beyond_the_reach() {
+    let grid = (200, 100);
+    let mut rng = StdRng::seed_from_u64(5);
+    for _ in 0..200 {
+        let cell = spawn_cell(10, 6.0, grid, &mut rng);
+        let distance = reach_of(cell, grid);
+        assert!((15..=17).contains(&distance), "{:?}", cell);
+    }
+    // Past the edge of the box, anywhere in it
+    for _ in 0..200 {
+        let [x, y] = spawn_cell(60, 6.0, grid, &mut rng);
+        assert!((0..200).contains(&x) && (0..100).contains(&y));
+    }
+}
+
+#[test]
+fn walkers_grow_a_connected_cluster() {
+    let grid = (64, 64);
+    let (mut cells, reach) = seed_cluster(SeedLayout::Point, grid, &mut StdRng::seed_from_u64(0));
+    let walking = Walking {
+        stickiness: 1.0,
+        margin: 3.0,
+    };
+    let frames = 400;
+    let reach = grow(&mut cells, reach, grid, &walking, 64, frames);
+
+    let stuck: Vec<_> = (0..cells.len())
+        .filter(|&index| cells[index] != 0)
+        .collect();
+    assert!(stuck.len() > 50, "{}", stuck.len());
+    for &index in &stuck {
+        let cell = [(index % 64) as i32, (index / 64) as i32];
+        assert!(cells[index] <= frames + 1);
+        assert!(reach_of(cell, grid) <= reach);
+        // Every cell but the seed stuck next to one no younger than itself
+        if cells[index] > 1 {
+            assert!(
+                neighbors(cell, grid)
+                    .any(|other| cells[other] != 0 && cells[other] <= cells[index]),
+                "{:?}",
+                cell
+            );
+        }
+    }
+}
+
+#[test]
+fn walkers_only_stick_next_to_the_cluster() {
+    let grid = (48, 48);
+    let mut cells = vec![0; 48 * 48];
+    let walking = Walking {
+        stickiness: 1.0,
+        margin: 3.0,
+    };
+    let reach = grow(&mut cells, 0, grid, &walking, 32, 200);
+    assert!(cells.iter().all(|&cell| cell == 0));
+    assert_eq!(reach, 0);
+}
+
+#[test]
+fn default_settings_keep_to_the_rules() {
+    let settings = serde_json::to_value(Settings::default()).unwrap();
+    for (name, value) in settings.as_object().unwrap() {
+        let validated = SETTING_RULES
+            .validate(name, value.clone(), || settings.clone())
+            .unwrap();
+        assert!(!validated.clamped, "{} = {}", name, value);
+    }
+}
+
+#[test]
+fn seed_layouts_parse_from_their_names() {
+    for layout in [SeedLayout::Point, SeedLayout::Scattered, SeedLayout::Empty] {
+        assert_eq!(format!("{:?}", layout).parse::<SeedLayout>(), Ok(layout));
+    }
+    assert!("line".parse::<SeedLayout>().is_err());
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("DLA Walk Shader", WALK_SHADER),
+        ("DLA Render Shader", RENDER_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
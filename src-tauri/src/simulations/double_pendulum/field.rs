@@ -0,0 +1,227 @@
+//! # Field
+//!
+//! A double pendulum is two arms hung one from the other, and for most
+//! starting angles it swings chaotically. Every cell of the field is one
+//! pendulum released from rest, the upper arm's starting angle running
+//! across the field and the lower arm's running up it, so neighboring cells
+//! start almost alike and the picture shows where they stay together and
+//! where they fly apart.
+//!
+//! Angles are measured from straight down. The upper arm is 1 long with a
+//! bob of mass 1 on its end; the lower arm and bob are sized relative to it.
+//! Each cell also carries a shadow pendulum started `perturbation` away in
+//! the upper angle, and how far the two have drifted apart is the cell's
+//! divergence.
+//!
+//! The field is laid out here and uploaded; from then on `step.wgsl` swings
+//! the pendulums on the GPU. A copy of the integration step is kept here for
+//! the tests.
+
+use bytemuck::{Pod, Zeroable};
+
+use super::settings::Settings;
+
+/// Angles and angular velocities of both arms
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct Swing {
+    pub upper_angle: f32,
+    pub lower_angle: f32,
+    pub upper_velocity: f32,
+    pub lower_velocity: f32,
+}
+
+impl Swing {
+    /// Released from rest at the given angles
+    pub fn at_rest(upper_angle: f32, lower_angle: f32) -> Self {
+        Self {
+            upper_angle,
+            lower_angle,
+            upper_velocity: 0.0,
+            lower_velocity: 0.0,
+        }
+    }
+}
+
+/// One cell of the field, laid out as `step.wgsl` reads it
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct Pendulum {
+    pub swing: Swing,
+    pub shadow: Swing,
+}
+
+/// Cells across and up a surface of `width` by `height` pixels with cells
+/// `cell_size` pixels square
+pub fn grid_size(width: u32, height: u32, cell_size: u32) -> (u32, u32) {
+    let cell_size = cell_size.max(1);
+    (
+        width.div_ceil(cell_size).max(1),
+        height.div_ceil(cell_size).max(1),
+    )
+}
+
+/// Starting angles of the cell at `cell`, `spread` radians either side of
+/// `center` up the field and as much further across it as the field is
+/// wider than it is tall, so the angles change as fast both ways
+pub fn starting_angles(
+    cell: (u32, u32),
+    (grid_width, grid_height): (u32, u32),
+    center: [f32; 2],
+    spread: f32,
+) -> [f32; 2] {
+    let across = (cell.0 as f32 + 0.5) / grid_width.max(1) as f32 * 2.0 - 1.0;
+    let up = (cell.1 as f32 + 0.5) / grid_height.max(1) as f32 * 2.0 - 1.0;
+    let aspect = grid_width as f32 / grid_height.max(1) as f32;
+    [
+        center[0] + across * aspect * spread,
+        center[1] + up * spread,
+    ]
+}
+
+/// Every cell released from rest, row by row from the bottom
+pub fn seed_field(
+    grid: (u32, u32),
+    center: [f32; 2],
+    spread: f32,
+    perturbation: f32,
+) -> Vec<Pendulum> {
+    let (grid_width, grid_height) = grid;
+    (0..grid_height)
+        .flat_map(|y| (0..grid_width).map(move |x| (x, y)))
+        .map(|cell| {
+            let [upper, lower] = starting_angles(cell, grid, center, spread);
+            Pendulum {
+                swing: Swing::at_rest(upper, lower),
+                shadow: Swing::at_rest(upper + perturbation, lower),
+            }
+        })
+        .collect()
+}
+
+/// `angle` brought into -pi to pi
+#[cfg(test)]
+pub fn wrap_angle(angle: f32) -> f32 {
+    use std::f32::consts::PI;
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+/// How far apart two swings' arms hang, in radians, from 0 to pi times
+/// root 2
+#[cfg(test)]
+pub fn separation(a: &Swing, b: &Swing) -> f32 {
+    wrap_angle(a.upper_angle - b.upper_angle).hypot(wrap_angle(a.lower_angle - b.lower_angle))
+}
+
+/// The physical constants a pendulum swings under
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Physics {
+    pub gravity: f32,
+    pub damping: f32,
+    /// Lower arm over upper arm
+    pub length_ratio: f32,
+    /// Lower bob over upper bob
+    pub mass_ratio: f32,
+}
+
+impl Physics {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            gravity: settings.gravity,
+            damping: settings.damping,
+            length_ratio: settings.length_ratio,
+            mass_ratio: settings.mass_ratio,
+        }
+    }
+}
+
+#[cfg(test)]
+impl Physics {
+    /// Rates of change of `swing`'s angles and velocities
+    fn derivative(&self, swing: &Swing) -> Swing {
+        let (g, l2, m2) = (self.gravity, self.length_ratio, self.mass_ratio);
+        let (t1, t2, w1, w2) = (
+            swing.upper_angle,
+            swing.lower_angle,
+            swing.upper_velocity,
+            swing.lower_velocity,
+        );
+        let delta = t1 - t2;
+        let denominator = 2.0 + m2 - m2 * (2.0 * delta).cos();
+        let upper = (-g * (2.0 + m2) * t1.sin()
+            - m2 * g * (t1 - 2.0 * t2).sin()
+            - 2.0 * delta.sin() * m2 * (w2 * w2 * l2 + w1 * w1 * delta.cos()))
+            / denominator;
+        let lower = 2.0
+            * delta.sin()
+            * (w1 * w1 * (1.0 + m2) + g * (1.0 + m2) * t1.cos() + w2 * w2 * l2 * m2 * delta.cos())
+            / (l2 * denominator);
+        Swing {
+            upper_angle: w1,
+            lower_angle: w2,
+            upper_velocity: upper - self.damping * w1,
+            lower_velocity: lower - self.damping * w2,
+        }
+    }
+
+    /// One fourth-order Runge-Kutta step of `dt`, as `step.wgsl` takes it
+    pub fn step(&self, swing: &Swing, dt: f32) -> Swing {
+        let along = |s: &Swing, d: &Swing, h: f32| Swing {
+            upper_angle: s.upper_angle + d.upper_angle * h,
+            lower_angle: s.lower_angle + d.lower_angle * h,
+            upper_velocity: s.upper_velocity + d.upper_velocity * h,
+            lower_velocity: s.lower_velocity + d.lower_velocity * h,
+        };
+        let k1 = self.derivative(swing);
+        let k2 = self.derivative(&along(swing, &k1, dt * 0.5));
+        let k3 = self.derivative(&along(swing, &k2, dt * 0.5));
+        let k4 = self.derivative(&along(swing, &k3, dt));
+        let blend = |a: f32, b: f32, c: f32, d: f32| (a + 2.0 * b + 2.0 * c + d) * dt / 6.0;
+        Swing {
+            upper_angle: swing.upper_angle
+                + blend(
+                    k1.upper_angle,
+                    k2.upper_angle,
+                    k3.upper_angle,
+                    k4.upper_angle,
+                ),
+            lower_angle: swing.lower_angle
+                + blend(
+                    k1.lower_angle,
+                    k2.lower_angle,
+                    k3.lower_angle,
+                    k4.lower_angle,
+                ),
+            upper_velocity: swing.upper_velocity
+                + blend(
+                    k1.upper_velocity,
+                    k2.upper_velocity,
+                    k3.upper_velocity,
+                    k4.upper_velocity,
+                ),
+            lower_velocity: swing.lower_velocity
+                + blend(
+                    k1.lower_velocity,
+                    k2.lower_velocity,
+                    k3.lower_velocity,
+                    k4.lower_velocity,
+                ),
+        }
+    }
+
+    /// Kinetic plus potential energy, with the pivot at height 0
+    pub fn energy(&self, swing: &Swing) -> f32 {
+        let (g, l2, m2) = (self.gravity, self.length_ratio, self.mass_ratio);
+        let (t1, t2, w1, w2) = (
+            swing.upper_angle,
+            swing.lower_angle,
+            swing.upper_velocity,
+            swing.lower_velocity,
+        );
+        let kinetic = 0.5 * (1.0 + m2) * w1 * w1
+            + 0.5 * m2 * l2 * l2 * w2 * w2
+            + m2 * l2 * w1 * w2 * (t1 - t2).cos();
+        let potential = -(1.0 + m2) * g * t1.cos() - m2 * g * l2 * t2.cos();
+        kinetic + potential
+    }
+}
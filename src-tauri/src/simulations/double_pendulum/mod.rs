@@ -0,0 +1,69 @@
+pub mod field;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::DoublePendulumModel;
+
+use crate::simulation::preset_manager::{DoublePendulumPresetManager, Preset};
+
+/// Initialize Double Pendulum presets with built-in configurations
+pub fn init_presets(preset_manager: &mut DoublePendulumPresetManager) {
+    use settings::{ColorMode, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Divergence Map".to_string(),
+        Settings {
+            color_mode: ColorMode::Divergence,
+            perturbation: 1e-5,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Edge of Chaos".to_string(),
+        Settings {
+            center_angles: [1.2, -0.4],
+            spread: 0.25,
+            cell_size: 1,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Heavy Lower Bob".to_string(),
+        Settings {
+            mass_ratio: 4.0,
+            length_ratio: 0.6,
+            color_mode: ColorMode::Divergence,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Winding Down".to_string(),
+        Settings {
+            damping: 0.15,
+            steps_per_frame: 8,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Low Gravity".to_string(),
+        Settings {
+            gravity: 1.6,
+            time_step: 0.01,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,175 @@
+//! # Double Pendulum Settings Module
+//!
+//! The pull and drag the pendulums swing under and the shape of their arms,
+//! which starting angles the field covers, how far each frame moves them
+//! on, and how they are colored.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// What picks each pendulum's color from the color scheme
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorMode {
+    /// Which way the lower arm hangs, going once round the scheme
+    #[default]
+    Angle,
+    /// How far it has drifted from its shadow, on a log scale
+    Divergence,
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "angle" => Ok(ColorMode::Angle),
+            "divergence" => Ok(ColorMode::Divergence),
+            _ => Err(format!(
+                "Invalid ColorMode: '{}'. Expected 'Angle' or 'Divergence'",
+                s
+            )),
+        }
+    }
+}
+
+impl From<ColorMode> for u32 {
+    fn from(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Angle => 0,
+            ColorMode::Divergence => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub gravity: f32,
+    /// Share of their angular velocity the arms lose each second
+    pub damping: f32,
+    /// Lower arm's length over the upper arm's
+    pub length_ratio: f32,
+    /// Lower bob's mass over the upper bob's
+    pub mass_ratio: f32,
+
+    /// Starting angles of the upper and lower arm in the middle of the
+    /// field, in radians from straight down
+    pub center_angles: [f32; 2],
+    /// Radians the starting angles run either side of the middle, up the
+    /// field; pi covers every angle
+    pub spread: f32,
+    /// How much further the shadow pendulum's upper arm starts, in radians
+    pub perturbation: f32,
+
+    /// Integration step, in seconds
+    pub time_step: f32,
+    pub steps_per_frame: u32,
+
+    /// Side of each pendulum's cell, in pixels
+    pub cell_size: u32,
+    pub color_mode: ColorMode,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            gravity: 9.81,
+            damping: 0.0,
+            length_ratio: 1.0,
+            mass_ratio: 1.0,
+            center_angles: [0.0, 0.0],
+            spread: std::f32::consts::PI,
+            perturbation: 1e-4,
+            time_step: 0.005,
+            steps_per_frame: 4,
+            cell_size: 2,
+            color_mode: ColorMode::Angle,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting).
+/// `center_upper` and `center_lower` set one of the center angles each.
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "gravity",
+            Rule::Range {
+                min: 0.0,
+                max: 50.0,
+            },
+        ),
+        ("damping", Rule::Range { min: 0.0, max: 2.0 }),
+        (
+            "length_ratio",
+            Rule::Range {
+                min: 0.1,
+                max: 10.0,
+            },
+        ),
+        (
+            "mass_ratio",
+            Rule::Range {
+                min: 0.1,
+                max: 10.0,
+            },
+        ),
+        (
+            "center_upper",
+            Rule::Range {
+                min: -std::f64::consts::PI,
+                max: std::f64::consts::PI,
+            },
+        ),
+        (
+            "center_lower",
+            Rule::Range {
+                min: -std::f64::consts::PI,
+                max: std::f64::consts::PI,
+            },
+        ),
+        (
+            "spread",
+            Rule::Range {
+                min: 1e-4,
+                max: 3.15,
+            },
+        ),
+        (
+            "perturbation",
+            Rule::Range {
+                min: 1e-6,
+                max: 0.1,
+            },
+        ),
+        (
+            "time_step",
+            Rule::Range {
+                min: 0.0005,
+                max: 0.05,
+            },
+        ),
+        ("steps_per_frame", Rule::Count { min: 1, max: 64 }),
+        ("cell_size", Rule::Count { min: 1, max: 16 }),
+        ("color_mode", Rule::OneOf(&["Angle", "Divergence"])),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("center_angles", SettingCategory::Generators),
+    ("spread", SettingCategory::Generators),
+    ("perturbation", SettingCategory::Generators),
+    ("color_mode", SettingCategory::Colors),
+]);
@@ -0,0 +1,36 @@
+// Shared by every double pendulum pass, each of which binds `params`. The
+// field is laid over the box row by row from the bottom, one pendulum to a
+// cell, and the box is -1 to 1 both ways in the camera's coordinates.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    time_step: f32,
+    grid_width: u32,
+    grid_height: u32,
+    steps: u32,
+    // 0 by the lower arm's angle, 1 by divergence from the shadow
+    color_mode: u32,
+    gravity: f32,
+    damping: f32,
+    // Lower arm over upper arm, and lower bob over upper bob
+    length_ratio: f32,
+    mass_ratio: f32,
+    perturbation: f32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+// Upper angle, lower angle, upper angular velocity, lower angular velocity
+struct Pendulum {
+    swing: vec4<f32>,
+    shadow: vec4<f32>,
+}
+
+const PI: f32 = 3.14159265359;
+const TAU: f32 = 6.28318530718;
+
+fn wrap_angle(angle: f32) -> f32 {
+    return angle - TAU * floor((angle + PI) / TAU);
+}
@@ -0,0 +1,6 @@
+pub const STEP_SHADER: &str = concat!(include_str!("common.wgsl"), include_str!("step.wgsl"));
+pub const RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("common.wgsl"),
+    include_str!("render.wgsl")
+);
@@ -0,0 +1,58 @@
+// The field is drawn through the camera, each pendulum colored by which
+// way its lower arm hangs or by how far it has drifted from its shadow.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> pendulums: array<Pendulum>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+struct FullscreenOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// One triangle covering the target
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return FullscreenOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+// Drift from the shadow on a log scale: 0 where they started apart, 1 as
+// far apart as two pendulums can hang
+fn divergence(pendulum: Pendulum) -> f32 {
+    let apart = vec2<f32>(
+        wrap_angle(pendulum.swing.x - pendulum.shadow.x),
+        wrap_angle(pendulum.swing.y - pendulum.shadow.y)
+    );
+    let start = max(params.perturbation, 1e-7);
+    return log(max(length(apart), start) / start) / log(PI * sqrt(2.0) / start);
+}
+
+@fragment
+fn fs_main(input: FullscreenOutput) -> @location(0) vec4<f32> {
+    let box_ndc = input.ndc / params.view_zoom + params.view_center;
+    if (abs(box_ndc.x) > 1.0 || abs(box_ndc.y) > 1.0) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    let grid = vec2<f32>(f32(params.grid_width), f32(params.grid_height));
+    let cell = clamp(vec2<u32>((box_ndc * 0.5 + 0.5) * grid), vec2<u32>(0u), vec2<u32>(grid) - 1u);
+    let pendulum = pendulums[cell.y * params.grid_width + cell.x];
+
+    var position: f32;
+    if (params.color_mode == 0u) {
+        position = wrap_angle(pendulum.swing.y) / TAU + 0.5;
+    } else {
+        position = divergence(pendulum);
+    }
+    return vec4<f32>(lut_color(position), 1.0);
+}
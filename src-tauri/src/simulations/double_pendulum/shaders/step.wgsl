@@ -0,0 +1,56 @@
+// Swings every pendulum and its shadow on by `steps` fourth-order
+// Runge-Kutta steps. A copy of the step is kept in `field.rs`.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> pendulums: array<Pendulum>;
+
+// Rates of change of the angles and velocities, with the upper arm 1 long
+// and its bob of mass 1
+fn derivative(swing: vec4<f32>) -> vec4<f32> {
+    let g = params.gravity;
+    let l2 = params.length_ratio;
+    let m2 = params.mass_ratio;
+    let t1 = swing.x;
+    let t2 = swing.y;
+    let w1 = swing.z;
+    let w2 = swing.w;
+    let delta = t1 - t2;
+    let denominator = 2.0 + m2 - m2 * cos(2.0 * delta);
+    let upper = (-g * (2.0 + m2) * sin(t1)
+        - m2 * g * sin(t1 - 2.0 * t2)
+        - 2.0 * sin(delta) * m2 * (w2 * w2 * l2 + w1 * w1 * cos(delta)))
+        / denominator;
+    let lower = 2.0 * sin(delta)
+        * (w1 * w1 * (1.0 + m2) + g * (1.0 + m2) * cos(t1) + w2 * w2 * l2 * m2 * cos(delta))
+        / (l2 * denominator);
+    return vec4<f32>(w1, w2, upper - params.damping * w1, lower - params.damping * w2);
+}
+
+fn rk4_step(swing: vec4<f32>, dt: f32) -> vec4<f32> {
+    let k1 = derivative(swing);
+    let k2 = derivative(swing + k1 * dt * 0.5);
+    let k3 = derivative(swing + k2 * dt * 0.5);
+    let k4 = derivative(swing + k3 * dt);
+    return swing + (k1 + 2.0 * k2 + 2.0 * k3 + k4) * dt / 6.0;
+}
+
+// Angles kept near zero so they don't lose precision as the arms go round
+fn rewrap(swing: vec4<f32>) -> vec4<f32> {
+    return vec4<f32>(wrap_angle(swing.x), wrap_angle(swing.y), swing.zw);
+}
+
+@compute @workgroup_size(8, 8)
+fn advance(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height) {
+        return;
+    }
+    let index = id.y * params.grid_width + id.x;
+    var pendulum = pendulums[index];
+    for (var i = 0u; i < params.steps; i++) {
+        pendulum.swing = rk4_step(pendulum.swing, params.time_step);
+        pendulum.shadow = rk4_step(pendulum.shadow, params.time_step);
+    }
+    pendulum.swing = rewrap(pendulum.swing);
+    pendulum.shadow = rewrap(pendulum.shadow);
+    pendulums[index] = pendulum;
+}
@@ -0,0 +1,639 @@
+//! # Double Pendulum Simulation Module
+//!
+//! A field of double pendulums, one to each cell, all released from rest at
+//! once with their starting angles running across and up the field; see
+//! [`field`] for the layout. Where the swing is regular neighbors move
+//! together and the field shows smooth bands, and where it is chaotic they
+//! scatter into noise, so the picture maps out which starting angles are
+//! chaotic.
+//!
+//! ## Technical Overview
+//!
+//! The field is laid out on the CPU once and from then on runs on the GPU.
+//! Each frame:
+//! 1. Every pendulum and its shadow take `steps_per_frame` Runge-Kutta
+//!    steps in a compute shader.
+//! 2. The field is drawn through the camera, colored by the lower arm's
+//!    angle or by how far each pendulum has drifted from its shadow.
+//!
+//! [`field`]: super::field
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, Buffer,
+    BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, ShaderStages,
+    SurfaceConfiguration, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_budget::{self, GpuReservation};
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager};
+use crate::simulations::traits::Simulation;
+
+use super::field::{self, Pendulum, Physics};
+use super::settings::{ColorMode, Settings};
+use super::shaders::{RENDER_SHADER, STEP_SHADER};
+use super::state::State;
+
+const PENDULUM_SIZE: u64 = std::mem::size_of::<Pendulum>() as u64;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    time_step: f32,
+    grid_width: u32,
+    grid_height: u32,
+    steps: u32,
+    color_mode: u32,
+    gravity: f32,
+    damping: f32,
+    length_ratio: f32,
+    mass_ratio: f32,
+    perturbation: f32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+#[derive(Debug)]
+pub struct DoublePendulumModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    step_pipeline: ComputePipeline,
+    render_pipeline: RenderPipeline,
+    step_bind_group_layout: BindGroupLayout,
+    render_bind_group_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    pendulums_buffer: Buffer,
+    step_bind_group: BindGroup,
+    render_bind_group: BindGroup,
+    memory: GpuReservation,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl DoublePendulumModel {
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let step_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Double Pendulum Step Shader"),
+            source: wgpu::ShaderSource::Wgsl(STEP_SHADER.into()),
+        });
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Double Pendulum Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Double Pendulum Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Double Pendulum LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let step_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Double Pendulum Step Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+            ],
+        });
+
+        let render_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Double Pendulum Render Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::FRAGMENT),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::FRAGMENT, true),
+                    resource_helpers::storage_buffer_entry(2, ShaderStages::FRAGMENT, true),
+                ],
+            });
+
+        let step_pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Double Pendulum Step Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Double Pendulum Step Pipeline Layout"),
+                bind_group_layouts: &[&step_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            module: &step_module,
+            entry_point: Some("advance"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Double Pendulum Render Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Double Pendulum Render Pipeline Layout"),
+                bind_group_layouts: &[&render_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        // Replaced by rebuild_field once the model exists
+        let (pendulums_buffer, step_bind_group, render_bind_group) = create_field(
+            device,
+            (1, 1),
+            &step_bind_group_layout,
+            &render_bind_group_layout,
+            &params_buffer,
+            &lut_buffer,
+        );
+
+        let mut model = Self {
+            settings,
+            state,
+            step_pipeline,
+            render_pipeline,
+            step_bind_group_layout,
+            render_bind_group_layout,
+            params_buffer,
+            lut_buffer,
+            pendulums_buffer,
+            step_bind_group,
+            render_bind_group,
+            memory: GpuReservation::default(),
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.rebuild_field(device, queue)?;
+        Ok(model)
+    }
+
+    /// Remake the field for the window and cell size, and release it
+    fn rebuild_field(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let requested = field::grid_size(self.width, self.height, self.settings.cell_size);
+        let (grid_width, grid_height) = gpu_budget::fit_resolution(
+            device,
+            "Double Pendulum Field",
+            requested,
+            PENDULUM_SIZE,
+            1,
+            &self.memory,
+        );
+        let (pendulums_buffer, step_bind_group, render_bind_group) = create_field(
+            device,
+            (grid_width, grid_height),
+            &self.step_bind_group_layout,
+            &self.render_bind_group_layout,
+            &self.params_buffer,
+            &self.lut_buffer,
+        );
+        self.pendulums_buffer = pendulums_buffer;
+        self.step_bind_group = step_bind_group;
+        self.render_bind_group = render_bind_group;
+        self.memory = GpuReservation::new(grid_width as u64 * grid_height as u64 * PENDULUM_SIZE);
+        self.state.grid_width = grid_width;
+        self.state.grid_height = grid_height;
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Release every pendulum from rest at its starting angles
+    fn seed(&mut self, queue: &Arc<Queue>) {
+        let pendulums = field::seed_field(
+            (self.state.grid_width, self.state.grid_height),
+            self.settings.center_angles,
+            self.settings.spread,
+            self.settings.perturbation,
+        );
+        queue.write_buffer(&self.pendulums_buffer, 0, bytemuck::cast_slice(&pendulums));
+        self.state.time = 0.0;
+    }
+
+    fn write_params(&self, queue: &Arc<Queue>) {
+        let physics = Physics::from_settings(&self.settings);
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            time_step: self.settings.time_step,
+            grid_width: self.state.grid_width,
+            grid_height: self.state.grid_height,
+            steps: self.settings.steps_per_frame.max(1),
+            color_mode: self.settings.color_mode.into(),
+            gravity: physics.gravity,
+            damping: physics.damping,
+            // A zero length or mass would divide by zero in the shader
+            length_ratio: physics.length_ratio.max(0.01),
+            mass_ratio: physics.mass_ratio.max(0.01),
+            perturbation: self.settings.perturbation,
+            _pad0: 0,
+            _pad1: 0,
+            _pad2: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    /// Seconds the pendulums swing each frame
+    fn frame_time(&self) -> f32 {
+        self.settings.time_step * self.settings.steps_per_frame.max(1) as f32
+    }
+
+    fn encode_step(&self, encoder: &mut wgpu::CommandEncoder, frames: u32) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Double Pendulum Step Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.step_pipeline);
+        compute_pass.set_bind_group(0, &self.step_bind_group, &[]);
+        for _ in 0..frames {
+            compute_pass.dispatch_workgroups(
+                self.state.grid_width.div_ceil(8),
+                self.state.grid_height.div_ceil(8),
+                1,
+            );
+        }
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Double Pendulum Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+/// The pendulum buffer for a field of `size` and the bind groups reading it
+fn create_field(
+    device: &Device,
+    (width, height): (u32, u32),
+    step_layout: &BindGroupLayout,
+    render_layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    lut_buffer: &Buffer,
+) -> (Buffer, BindGroup, BindGroup) {
+    let pendulums_buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("Double Pendulum Pendulums Buffer"),
+        size: width as u64 * height as u64 * PENDULUM_SIZE,
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let step_bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Double Pendulum Step Bind Group"),
+        layout: step_layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, &pendulums_buffer),
+        ],
+    });
+    let render_bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Double Pendulum Render Bind Group"),
+        layout: render_layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, &pendulums_buffer),
+            resource_helpers::buffer_entry(2, lut_buffer),
+        ],
+    });
+    (pendulums_buffer, step_bind_group, render_bind_group)
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for DoublePendulumModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        self.write_params(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Double Pendulum Render"),
+        });
+        self.encode_step(&mut encoder, 1);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        self.state.time += self.frame_time();
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // Nothing swings, but the color mode and camera still update
+        self.write_params(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Double Pendulum Render Paused"),
+        });
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn fast_forward(
+        &mut self,
+        steps: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.write_params(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Double Pendulum Fast Forward"),
+        });
+        self.encode_step(&mut encoder, steps);
+        queue.submit([encoder.finish()]);
+        self.state.time += self.frame_time() * steps as f32;
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.rebuild_field(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        _world_x: f32,
+        _world_y: f32,
+        _mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.cell_size != self.settings.cell_size {
+            self.rebuild_field(device, queue)?;
+        } else if old_settings.center_angles != self.settings.center_angles
+            || old_settings.spread != self.settings.spread
+            || old_settings.perturbation != self.settings.perturbation
+        {
+            self.reset_runtime_state(device, queue)?;
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.seed(queue);
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        let pi = std::f32::consts::PI;
+        self.settings.center_angles = [rng.random_range(-pi..pi), rng.random_range(-pi..pi)];
+        // Narrow spreads show the fine structure around one starting point
+        self.settings.spread = pi * 10f32.powf(rng.random_range(-2.0..0.0));
+        self.settings.length_ratio = rng.random_range(0.5..2.0);
+        self.settings.mass_ratio = rng.random_range(0.5..2.0);
+        self.settings.damping = if rng.random_bool(0.7) {
+            0.0
+        } else {
+            rng.random_range(0.0..0.3)
+        };
+        self.settings.color_mode = if rng.random_bool(0.5) {
+            ColorMode::Angle
+        } else {
+            ColorMode::Divergence
+        };
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "gravity" => self.settings.gravity = number(setting_name, &value)? as f32,
+            "damping" => self.settings.damping = number(setting_name, &value)? as f32,
+            "length_ratio" => self.settings.length_ratio = number(setting_name, &value)? as f32,
+            "mass_ratio" => self.settings.mass_ratio = number(setting_name, &value)? as f32,
+            "time_step" => self.settings.time_step = number(setting_name, &value)? as f32,
+            "steps_per_frame" => {
+                self.settings.steps_per_frame = number(setting_name, &value)? as u32
+            }
+            "color_mode" => {
+                self.settings.color_mode = value
+                    .as_str()
+                    .unwrap_or("Angle")
+                    .parse()
+                    .map_err(|e| format!("Invalid color mode: {}", e))?;
+            }
+            // These change where the pendulums start, so they start again
+            "center_angles" => {
+                self.settings.center_angles = serde_json::from_value(value)?;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "center_upper" => {
+                self.settings.center_angles[0] = number(setting_name, &value)? as f32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "center_lower" => {
+                self.settings.center_angles[1] = number(setting_name, &value)? as f32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "spread" => {
+                self.settings.spread = number(setting_name, &value)? as f32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "perturbation" => {
+                self.settings.perturbation = number(setting_name, &value)? as f32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "cell_size" => {
+                self.settings.cell_size = number(setting_name, &value)? as u32;
+                self.rebuild_field(device, queue)?;
+            }
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // The field's size in pendulums, which is coarser than asked for if the
+    // GPU couldn't hold them all
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Seconds the pendulums have swung since they were released
+    pub time: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            grid_width: 0,
+            grid_height: 0,
+            time: 0.0,
+            color_scheme_name: "MATPLOTLIB_twilight".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::field::{
+    Physics, Swing, grid_size, seed_field, separation, starting_angles, wrap_angle,
+};
+use super::settings::{ColorMode, SETTING_RULES, Settings};
+use super::shaders::{RENDER_SHADER, STEP_SHADER};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+/// Swings `swing` on for `seconds` in steps of `dt`
+fn swing_for(physics: &Physics, swing: Swing, seconds: f32, dt: f32) -> Swing {
+    (0..(seconds / dt) as u32).fold(swing, |swing, _| physics.step(&swing, dt))
+}
+
+#[test]
+fn cells_cover_the_whole_surface() {
+    assert_eq!(grid_size(1920, 1080, 1), (1920, 1080));
+    assert_eq!(grid_size(1920, 1080, 4), (480, 270));
+    assert_eq!(grid_size(1921, 1081, 4), (481, 271));
+    assert_eq!(grid_size(10, 10, 0), (10, 10));
+    assert_eq!(grid_size(0, 0, 8), (1, 1));
+}
+
+#[test]
+fn starting_angles_spread_evenly_about_the_center() {
+    let grid = (200, 100);
+    let center = [0.5, -1.0];
+    let [upper, lower] = starting_angles((99, 49), grid, center, 1.0);
+    let [opposite_upper, opposite_lower] = starting_angles((100, 50), grid, center, 1.0);
+    assert!(((upper + opposite_upper) * 0.5 - center[0]).abs() < 1e-5);
+    assert!(((lower + opposite_lower) * 0.5 - center[1]).abs() < 1e-5);
+
+    // A cell across changes the angle as much as a cell up
+    let across = starting_angles((10, 10), grid, center, 1.0)[0]
+        - starting_angles((9, 10), grid, center, 1.0)[0];
+    let up = starting_angles((10, 10), grid, center, 1.0)[1]
+        - starting_angles((10, 9), grid, center, 1.0)[1];
+    assert!((across - up).abs() < 1e-5);
+
+    // The field's edges reach the spread either side of the center up it
+    let bottom = starting_angles((0, 0), (1, 1000), center, 2.0)[1];
+    assert!((bottom - (center[1] - 2.0)).abs() < 0.01);
+}
+
+#[test]
+fn every_cell_starts_at_rest_beside_its_shadow() {
+    let field = seed_field((16, 8), [0.0, 0.0], 1.0, 1e-3);
+    assert_eq!(field.len(), 16 * 8);
+    for pendulum in &field {
+        assert_eq!(pendulum.swing.upper_velocity, 0.0);
+        assert_eq!(pendulum.swing.lower_velocity, 0.0);
+        assert!((pendulum.shadow.upper_angle - pendulum.swing.upper_angle - 1e-3).abs() < 1e-6);
+        assert_eq!(pendulum.shadow.lower_angle, pendulum.swing.lower_angle);
+    }
+    // Rows run from the bottom
+    assert!(field[0].swing.lower_angle < field[16].swing.lower_angle);
+    assert!(field[0].swing.upper_angle < field[1].swing.upper_angle);
+}
+
+#[test]
+fn angles_wrap_into_a_half_turn_either_way() {
+    use std::f32::consts::PI;
+    assert!((wrap_angle(0.5) - 0.5).abs() < 1e-6);
+    assert!((wrap_angle(2.0 * PI + 0.5) - 0.5).abs() < 1e-5);
+    assert!((wrap_angle(-2.0 * PI - 0.5) + 0.5).abs() < 1e-5);
+    let a = Swing::at_rest(PI - 0.01, 0.0);
+    let b = Swing::at_rest(-PI + 0.01, 0.0);
+    assert!((separation(&a, &b) - 0.02).abs() < 1e-4);
+}
+
+#[test]
+fn hanging_straight_down_stays_put() {
+    let physics = Physics::from_settings(&Settings::default());
+    let swing = swing_for(&physics, Swing::at_rest(0.0, 0.0), 5.0, 0.005);
+    assert_eq!(swing, Swing::at_rest(0.0, 0.0));
+}
+
+#[test]
+fn undamped_swings_keep_their_energy() {
+    let physics = Physics::from_settings(&Settings {
+        mass_ratio: 2.0,
+        length_ratio: 0.7,
+        ..Settings::default()
+    });
+    let start = Swing::at_rest(2.0, -1.0);
+    let end = swing_for(&physics, start, 10.0, 0.002);
+    let (before, after) = (physics.energy(&start), physics.energy(&end));
+    assert!(
+        (before - after).abs() < 1e-2 * before.abs(),
+        "{} -> {}",
+        before,
+        after
+    );
+}
+
+#[test]
+fn damping_drains_energy() {
+    let physics = Physics::from_settings(&Settings {
+        damping: 0.3,
+        ..Settings::default()
+    });
+    let start = Swing::at_rest(1.5, 0.5);
+    let end = swing_for(&physics, start, 10.0, 0.005);
+    assert!(physics.energy(&end) < physics.energy(&start) - 1.0);
+}
+
+#[test]
+fn chaotic_starts_diverge_and_small_swings_do_not() {
+    let physics = Physics::from_settings(&Settings::default());
+    let apart_after = |upper: f32, lower: f32| {
+        let swing = swing_for(&physics, Swing::at_rest(upper, lower), 20.0, 0.005);
+        let shadow = swing_for(&physics, Swing::at_rest(upper + 1e-4, lower), 20.0, 0.005);
+        separation(&swing, &shadow)
+    };
+    assert!(apart_after(0.1, 0.1) < 1e-3);
+    assert!(apart_after(2.5, 2.5) > 0.1);
+}
+
+#[test]
+fn color_modes_parse_from_their_names() {
+    for mode in [ColorMode::Angle, ColorMode::Divergence] {
+        assert_eq!(format!("{:?}", mode).parse::<ColorMode>(), Ok(mode));
+    }
+    assert!("speed".parse::<ColorMode>().is_err());
+}
+
+#[test]
+fn default_settings_keep_to_the_rules() {
+    let settings = serde_json::to_value(Settings::default()).unwrap();
+    for (name, value) in settings.as_object().unwrap() {
+        let validated = SETTING_RULES
+            .validate(name, value.clone(), || settings.clone())
+            .unwrap();
+        assert!(!validated.clamped, "{} = {}", name, value);
+    }
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Double Pendulum Step Shader", STEP_SHADER),
+        ("Double Pendulum Render Shader", RENDER_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
@@ -0,0 +1,334 @@
+//! # Eikonal Mazes
+//!
+//! The grid of walls, sources and the goal the distances are measured
+//! across, uploaded to the GPU a word per cell. Mazes are carved by a
+//! depth-first walk over blocks of `corridor_width` cells with a wall
+//! between each, so every corridor is reachable by exactly one route.
+//!
+//! The GPU relaxes the distance to the nearest source at every cell, over
+//! and over, until it stops changing. The eikonal update takes the nearer
+//! neighbor along each axis and solves `|∇d| = 1` between them, which is
+//! the same upwind step fast marching takes, just without the heap keeping
+//! cells in order. The shortest path is then walked downhill from the goal.
+//! [`relax`] and [`trace_path`] mirror both shaders for the tests.
+//!
+//! The grid doesn't wrap: cells past the edge count as walls.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::settings::Layout;
+#[cfg(test)]
+use super::settings::Metric;
+
+/// A cell anything can pass through
+pub const OPEN: u32 = 0;
+/// A cell nothing passes through, always infinitely far away
+pub const WALL: u32 = 1;
+/// A cell at distance zero
+pub const SOURCE: u32 = 2;
+/// Distance of cells the wavefront hasn't reached
+pub const UNREACHED: f32 = 1.0e30;
+
+#[derive(Debug, Clone)]
+pub struct Maze {
+    width: u32,
+    height: u32,
+    cells: Vec<u32>,
+    goal: Option<(u32, u32)>,
+}
+
+impl Maze {
+    /// An open grid with nothing on it
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![OPEN; (width * height) as usize],
+            goal: None,
+        }
+    }
+
+    /// `layout` laid out on a `width` by `height` grid, with a source near
+    /// one corner or side and the goal across from it
+    pub fn generate(
+        width: u32,
+        height: u32,
+        layout: Layout,
+        corridor_width: u32,
+        obstacle_density: f32,
+        seed: u32,
+    ) -> Self {
+        let mut maze = Self::new(width, height);
+        let mut rng = StdRng::seed_from_u64(seed as u64);
+        let corridor = corridor_width.max(1);
+        let (source, goal) = match layout {
+            Layout::Maze => return Self::carve(width, height, corridor, &mut rng),
+            Layout::Open => ((width / 6, height / 2), (width - 1 - width / 6, height / 2)),
+            Layout::Scatter => {
+                let area = (width * height) as f32;
+                let mut covered = 0.0;
+                while covered < area * obstacle_density.clamp(0.0, 0.9) {
+                    let radius = rng.random_range(corridor as f32..corridor as f32 * 4.0);
+                    let center = (
+                        rng.random_range(0..width) as i32,
+                        rng.random_range(0..height) as i32,
+                    );
+                    maze.paint(center, radius, WALL);
+                    covered += std::f32::consts::PI * radius * radius;
+                }
+                (
+                    (width / 8, height / 8),
+                    (width - 1 - width / 8, height - 1 - height / 8),
+                )
+            }
+        };
+        // Room around both ends so neither is walled in
+        let clearing = corridor as f32 * 2.0;
+        maze.paint((source.0 as i32, source.1 as i32), clearing, OPEN);
+        maze.paint((goal.0 as i32, goal.1 as i32), clearing, OPEN);
+        maze.paint((source.0 as i32, source.1 as i32), 0.5, SOURCE);
+        maze.goal = Some(goal);
+        maze
+    }
+
+    /// Walls everywhere but the corridors a depth-first walk carves between
+    /// blocks, starting in the top left block and ending the goal in the
+    /// bottom right
+    fn carve(width: u32, height: u32, corridor: u32, rng: &mut StdRng) -> Self {
+        let mut maze = Self::new(width, height);
+        maze.cells.fill(WALL);
+        let pitch = corridor + 1;
+        let columns = ((width.saturating_sub(1)) / pitch).max(1);
+        let rows = ((height.saturating_sub(1)) / pitch).max(1);
+        // Top left cell of a block's corridor
+        let origin = |block: u32| (1 + block % columns * pitch, 1 + block / columns * pitch);
+
+        let open_block = |maze: &mut Self, block: u32| {
+            let (x, y) = origin(block);
+            maze.fill_rect(x, y, corridor, corridor, OPEN);
+        };
+        let mut visited = vec![false; (columns * rows) as usize];
+        let mut stack = vec![0];
+        visited[0] = true;
+        open_block(&mut maze, 0);
+        while let Some(&block) = stack.last() {
+            let (column, row) = (block % columns, block / columns);
+            let mut unvisited = Vec::with_capacity(4);
+            if column > 0 {
+                unvisited.push(block - 1);
+            }
+            if column + 1 < columns {
+                unvisited.push(block + 1);
+            }
+            if row > 0 {
+                unvisited.push(block - columns);
+            }
+            if row + 1 < rows {
+                unvisited.push(block + columns);
+            }
+            unvisited.retain(|&next| !visited[next as usize]);
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+            let next = unvisited[rng.random_range(0..unvisited.len())];
+            visited[next as usize] = true;
+            open_block(&mut maze, next);
+            // Knock through the wall between the two blocks
+            let (ax, ay) = origin(block);
+            let (bx, by) = origin(next);
+            let (x, y) = (ax.min(bx), ay.min(by));
+            if ay == by {
+                maze.fill_rect(x + corridor, y, 1, corridor, OPEN);
+            } else {
+                maze.fill_rect(x, y + corridor, corridor, 1, OPEN);
+            }
+            stack.push(next);
+        }
+
+        let (source_x, source_y) = origin(0);
+        maze.fill_rect(source_x, source_y, corridor, corridor, SOURCE);
+        let (goal_x, goal_y) = origin(columns * rows - 1);
+        maze.goal = Some((
+            (goal_x + corridor / 2).min(width - 1),
+            (goal_y + corridor / 2).min(height - 1),
+        ));
+        maze
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, cell: u32) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                self.cells[(row * self.width + column) as usize] = cell;
+            }
+        }
+    }
+
+    /// Set every cell within `radius` of `center` to `cell`. Returns
+    /// whether anything changed.
+    pub fn paint(&mut self, center: (i32, i32), radius: f32, cell: u32) -> bool {
+        let reach = radius.max(0.0).ceil() as i32;
+        let mut changed = false;
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                if (dx * dx + dy * dy) as f32 > radius * radius {
+                    continue;
+                }
+                let (x, y) = (center.0 + dx, center.1 + dy);
+                if let Some(index) = self.index(x, y) {
+                    changed |= self.cells[index] != cell;
+                    self.cells[index] = cell;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Move the goal to `cell`, clearing any wall there
+    pub fn set_goal(&mut self, (x, y): (i32, i32)) -> bool {
+        let Some(index) = self.index(x, y) else {
+            return false;
+        };
+        let goal = (x as u32, y as u32);
+        let changed = self.goal != Some(goal) || self.cells[index] == WALL;
+        if self.cells[index] == WALL {
+            self.cells[index] = OPEN;
+        }
+        self.goal = Some(goal);
+        changed
+    }
+
+    #[cfg(test)]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[cfg(test)]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn goal(&self) -> Option<(u32, u32)> {
+        self.goal
+    }
+
+    /// A word per cell, row by row, as the shaders read them
+    pub fn cells(&self) -> &[u32] {
+        &self.cells
+    }
+
+    /// What's at a cell, with everything off the grid a wall
+    #[cfg(test)]
+    pub fn cell(&self, x: i32, y: i32) -> u32 {
+        self.index(x, y).map_or(WALL, |index| self.cells[index])
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some((y as u32 * self.width + x as u32) as usize)
+    }
+}
+
+/// What one cell relaxes to given the distances around it, as in
+/// march.wgsl
+#[cfg(test)]
+fn update(maze: &Maze, metric: Metric, distances: &[f32], x: i32, y: i32) -> f32 {
+    let at = |dx: i32, dy: i32| match maze.index(x + dx, y + dy) {
+        Some(index) if maze.cells[index] != WALL => distances[index],
+        _ => UNREACHED,
+    };
+    let horizontal = at(-1, 0).min(at(1, 0));
+    let vertical = at(0, -1).min(at(0, 1));
+    let nearest = horizontal.min(vertical);
+    if nearest >= UNREACHED {
+        return UNREACHED;
+    }
+    match metric {
+        Metric::Taxicab => nearest + 1.0,
+        Metric::Octile => {
+            let mut distance = nearest + 1.0;
+            for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                // No cutting past the corner of a wall
+                if maze.cell(x + dx, y) != WALL && maze.cell(x, y + dy) != WALL {
+                    distance = distance.min(at(dx, dy) + std::f32::consts::SQRT_2);
+                }
+            }
+            distance
+        }
+        Metric::Eikonal => {
+            let gap = horizontal - vertical;
+            if gap.abs() >= 1.0 {
+                nearest + 1.0
+            } else {
+                (horizontal + vertical + (2.0 - gap * gap).sqrt()) * 0.5
+            }
+        }
+    }
+}
+
+/// One relaxation pass over the whole grid, reading `distances` and
+/// returning the next
+#[cfg(test)]
+pub fn relax(maze: &Maze, metric: Metric, distances: &[f32]) -> Vec<f32> {
+    (0..maze.height as i32)
+        .flat_map(|y| (0..maze.width as i32).map(move |x| (x, y)))
+        .map(|(x, y)| match maze.cell(x, y) {
+            WALL => UNREACHED,
+            SOURCE => 0.0,
+            _ => update(maze, metric, distances, x, y),
+        })
+        .collect()
+}
+
+/// Cells from the goal downhill to a source, as in path.wgsl. Empty when
+/// there's no goal or the wavefront hasn't reached it.
+#[cfg(test)]
+pub fn trace_path(maze: &Maze, metric: Metric, distances: &[f32]) -> Vec<(i32, i32)> {
+    let Some((x, y)) = maze.goal else {
+        return Vec::new();
+    };
+    let mut cell = (x as i32, y as i32);
+    let mut distance = distances[maze.index(cell.0, cell.1).unwrap()];
+    if distance >= UNREACHED {
+        return Vec::new();
+    }
+    let mut path = vec![cell];
+    while distance > 0.0 {
+        let mut next = cell;
+        for (dx, dy) in [
+            (-1, 0),
+            (1, 0),
+            (0, -1),
+            (0, 1),
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+            (1, 1),
+        ] {
+            let diagonal = dx != 0 && dy != 0;
+            if diagonal
+                && (metric == Metric::Taxicab
+                    || maze.cell(cell.0 + dx, cell.1) == WALL
+                    || maze.cell(cell.0, cell.1 + dy) == WALL)
+            {
+                continue;
+            }
+            if let Some(index) = maze.index(cell.0 + dx, cell.1 + dy)
+                && maze.cells[index] != WALL
+                && distances[index] < distance
+            {
+                distance = distances[index];
+                next = (cell.0 + dx, cell.1 + dy);
+            }
+        }
+        if next == cell {
+            break;
+        }
+        cell = next;
+        path.push(cell);
+    }
+    path
+}
@@ -0,0 +1,90 @@
+pub mod maze;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::EikonalModel;
+
+use crate::simulation::preset_manager::{EikonalPresetManager, Preset};
+
+/// Initialize Eikonal presets with built-in configurations
+pub fn init_presets(preset_manager: &mut EikonalPresetManager) {
+    use settings::{Layout, Metric, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Labyrinth".to_string(),
+        Settings {
+            cell_size: 3,
+            corridor_width: 2,
+            band_spacing: 40.0,
+            wave_speed: 160.0,
+            iterations_per_frame: 48,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Wide Halls".to_string(),
+        Settings {
+            cell_size: 2,
+            corridor_width: 12,
+            band_spacing: 16.0,
+            wave_speed: 120.0,
+            iterations_per_frame: 48,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Boulder Field".to_string(),
+        Settings {
+            layout: Layout::Scatter,
+            cell_size: 3,
+            corridor_width: 4,
+            obstacle_density: 0.35,
+            band_spacing: 12.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Taxicab Boulders".to_string(),
+        Settings {
+            layout: Layout::Scatter,
+            metric: Metric::Taxicab,
+            corridor_width: 3,
+            obstacle_density: 0.45,
+            band_spacing: 10.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Octile Maze".to_string(),
+        Settings {
+            metric: Metric::Octile,
+            corridor_width: 5,
+            band_spacing: 20.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Blank Canvas".to_string(),
+        Settings {
+            layout: Layout::Open,
+            band_spacing: 8.0,
+            front_width: 2.0,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,207 @@
+//! # Eikonal Settings Module
+//!
+//! How the maze is laid out, how distance is measured through it, how fast
+//! the wavefront spreads, and how the distances are drawn.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Layout {
+    /// No walls, just the sources and the goal
+    Open,
+    /// A maze with exactly one way between any two corridors
+    #[default]
+    Maze,
+    /// Round boulders dropped at random
+    Scatter,
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(Layout::Open),
+            "maze" => Ok(Layout::Maze),
+            "scatter" => Ok(Layout::Scatter),
+            _ => Err(format!(
+                "Invalid Layout: '{}'. Expected 'Open', 'Maze', or 'Scatter'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Metric {
+    /// Straight-line distance around the walls, from the eikonal equation
+    #[default]
+    Eikonal,
+    /// Steps between cells sharing an edge, a breadth-first flood
+    Taxicab,
+    /// Steps to any of the eight neighbors, diagonals costing √2
+    Octile,
+}
+
+impl Metric {
+    pub fn shader_index(self) -> u32 {
+        match self {
+            Metric::Eikonal => 0,
+            Metric::Taxicab => 1,
+            Metric::Octile => 2,
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "eikonal" => Ok(Metric::Eikonal),
+            "taxicab" => Ok(Metric::Taxicab),
+            "octile" => Ok(Metric::Octile),
+            _ => Err(format!(
+                "Invalid Metric: '{}'. Expected 'Eikonal', 'Taxicab', or 'Octile'",
+                s
+            )),
+        }
+    }
+}
+
+/// What the left mouse button draws; the right one always erases
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum BrushTool {
+    #[default]
+    Wall,
+    Erase,
+    /// Cells the distances are measured from
+    Source,
+    /// The cell the shortest path is traced back from
+    Goal,
+}
+
+impl FromStr for BrushTool {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wall" => Ok(BrushTool::Wall),
+            "erase" => Ok(BrushTool::Erase),
+            "source" => Ok(BrushTool::Source),
+            "goal" => Ok(BrushTool::Goal),
+            _ => Err(format!(
+                "Invalid BrushTool: '{}'. Expected 'Wall', 'Erase', 'Source', or 'Goal'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub layout: Layout,
+    /// Pixels on each side of a cell
+    pub cell_size: u32,
+    /// Cells across each corridor of the maze, and the smallest boulders
+    pub corridor_width: u32,
+    /// Share of the grid the scattered boulders cover
+    pub obstacle_density: f32,
+    pub seed: u32,
+
+    pub metric: Metric,
+    /// Relaxation passes over the grid each frame, which bounds how fast
+    /// the distances can settle
+    pub iterations_per_frame: u32,
+    /// Cells of distance the wavefront covers each second
+    pub wave_speed: f32,
+
+    /// Cells of distance between each repeat of the color scheme
+    pub band_spacing: f32,
+    /// Cells behind the wavefront that are lit up
+    pub front_width: f32,
+    /// Whether the shortest path from the goal is drawn once reached
+    pub show_path: bool,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            layout: Layout::Maze,
+            cell_size: 4,
+            corridor_width: 3,
+            obstacle_density: 0.3,
+            seed: 0,
+            metric: Metric::Eikonal,
+            iterations_per_frame: 24,
+            wave_speed: 80.0,
+            band_spacing: 24.0,
+            front_width: 6.0,
+            show_path: true,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("layout", Rule::OneOf(&["Open", "Maze", "Scatter"])),
+        ("cell_size", Rule::Count { min: 1, max: 32 }),
+        ("corridor_width", Rule::Count { min: 1, max: 32 }),
+        ("obstacle_density", Rule::Range { min: 0.0, max: 0.6 }),
+        (
+            "seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+        ("metric", Rule::OneOf(&["Eikonal", "Taxicab", "Octile"])),
+        ("iterations_per_frame", Rule::Count { min: 1, max: 256 }),
+        (
+            "wave_speed",
+            Rule::Range {
+                min: 1.0,
+                max: 2000.0,
+            },
+        ),
+        (
+            "band_spacing",
+            Rule::Range {
+                min: 1.0,
+                max: 500.0,
+            },
+        ),
+        (
+            "front_width",
+            Rule::Range {
+                min: 0.0,
+                max: 100.0,
+            },
+        ),
+        ("show_path", Rule::Flag),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("layout", SettingCategory::Generators),
+    ("obstacle_density", SettingCategory::Generators),
+    ("seed", SettingCategory::Generators),
+    ("band_spacing", SettingCategory::Colors),
+    ("front_width", SettingCategory::Colors),
+    ("show_path", SettingCategory::Colors),
+]);
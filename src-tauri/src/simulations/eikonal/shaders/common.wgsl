@@ -0,0 +1,36 @@
+// Shared by every eikonal pass, each of which binds `params` and `walls`.
+// Cells past the edge of the grid count as walls.
+
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    metric: u32, // 0 = Eikonal, 1 = Taxicab, 2 = Octile
+    has_goal: u32,
+    goal_x: u32,
+    goal_y: u32,
+    // Marks the path cells walked this frame, so last frame's needn't be
+    // cleared
+    path_stamp: u32,
+    show_path: u32,
+    front: f32,
+    band_spacing: f32,
+    front_width: f32,
+    _pad0: u32,
+}
+
+const OPEN: u32 = 0u;
+const WALL: u32 = 1u;
+const SOURCE: u32 = 2u;
+const UNREACHED: f32 = 1.0e30;
+
+fn in_grid(cell: vec2<i32>) -> bool {
+    return cell.x >= 0 && cell.y >= 0 && cell.x < i32(params.grid_width) && cell.y < i32(params.grid_height);
+}
+
+fn cell_index(cell: vec2<i32>) -> u32 {
+    return u32(cell.y) * params.grid_width + u32(cell.x);
+}
+
+fn is_wall(cell: vec2<i32>) -> bool {
+    return !in_grid(cell) || walls[cell_index(cell)] == WALL;
+}
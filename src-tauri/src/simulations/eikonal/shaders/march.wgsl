@@ -0,0 +1,68 @@
+// One relaxation pass, one invocation per cell: each cell takes the
+// distance its neighbors imply, with sources pinned at zero and walls at
+// UNREACHED. Repeated, the distances settle to those fast marching would
+// find. maze.rs keeps a copy of the update for the tests.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> walls: array<u32>;
+@group(0) @binding(2) var<storage, read> distances_in: array<f32>;
+@group(0) @binding(3) var<storage, read_write> distances_out: array<f32>;
+
+const SQRT_2: f32 = 1.41421356237;
+
+fn distance_at(cell: vec2<i32>) -> f32 {
+    if (is_wall(cell)) {
+        return UNREACHED;
+    }
+    return distances_in[cell_index(cell)];
+}
+
+fn update(cell: vec2<i32>) -> f32 {
+    let horizontal = min(distance_at(cell + vec2<i32>(-1, 0)), distance_at(cell + vec2<i32>(1, 0)));
+    let vertical = min(distance_at(cell + vec2<i32>(0, -1)), distance_at(cell + vec2<i32>(0, 1)));
+    let nearest = min(horizontal, vertical);
+    if (nearest >= UNREACHED) {
+        return UNREACHED;
+    }
+
+    if (params.metric == 1u) {
+        return nearest + 1.0;
+    }
+    if (params.metric == 2u) {
+        var distance = nearest + 1.0;
+        for (var dy = -1; dy <= 1; dy += 2) {
+            for (var dx = -1; dx <= 1; dx += 2) {
+                // No cutting past the corner of a wall
+                if (!is_wall(cell + vec2<i32>(dx, 0)) && !is_wall(cell + vec2<i32>(0, dy))) {
+                    distance = min(distance, distance_at(cell + vec2<i32>(dx, dy)) + SQRT_2);
+                }
+            }
+        }
+        return distance;
+    }
+
+    // Solve |∇d| = 1 between the nearer neighbor along each axis, unless
+    // one is so much nearer the front can only have come from it
+    let gap = horizontal - vertical;
+    if (abs(gap) >= 1.0) {
+        return nearest + 1.0;
+    }
+    return (horizontal + vertical + sqrt(2.0 - gap * gap)) * 0.5;
+}
+
+@compute @workgroup_size(8, 8)
+fn march(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height) {
+        return;
+    }
+    let cell = vec2<i32>(id.xy);
+    let index = cell_index(cell);
+    let kind = walls[index];
+    if (kind == WALL) {
+        distances_out[index] = UNREACHED;
+    } else if (kind == SOURCE) {
+        distances_out[index] = 0.0;
+    } else {
+        distances_out[index] = update(cell);
+    }
+}
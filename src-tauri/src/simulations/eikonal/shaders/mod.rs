@@ -0,0 +1,8 @@
+pub const MARCH_SHADER: &str = concat!(include_str!("common.wgsl"), include_str!("march.wgsl"));
+pub const PATH_SHADER: &str = concat!(include_str!("common.wgsl"), include_str!("path.wgsl"));
+pub const PAINT_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("common.wgsl"),
+    include_str!("paint.wgsl")
+);
+pub const RENDER_INFINITE_SHADER: &str = crate::simulations::shared::INFINITE_RENDER_SHADER;
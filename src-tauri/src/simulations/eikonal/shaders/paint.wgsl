@@ -0,0 +1,61 @@
+// Colors the display texture a texel per cell. Reached cells take the
+// color scheme in repeating bands of distance, those just behind the
+// wavefront are lit up, and the path, sources, goal and walls are drawn
+// over them in fixed colors.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> walls: array<u32>;
+@group(0) @binding(2) var<storage, read> distances: array<f32>;
+@group(0) @binding(3) var<storage, read> path: array<u32>;
+@group(0) @binding(4) var<storage, read> lut_data: array<u32>;
+@group(0) @binding(5) var output_texture: texture_storage_2d<rgba8unorm, write>;
+
+const WALL_COLOR: vec3<f32> = vec3<f32>(0.35, 0.36, 0.4);
+const SOURCE_COLOR: vec3<f32> = vec3<f32>(1.0, 1.0, 1.0);
+const GOAL_COLOR: vec3<f32> = vec3<f32>(1.0, 0.15, 0.1);
+const PATH_COLOR: vec3<f32> = vec3<f32>(1.0, 1.0, 1.0);
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+fn cell_color(cell: vec2<i32>) -> vec3<f32> {
+    let index = cell_index(cell);
+    let kind = walls[index];
+    if (kind == WALL) {
+        return WALL_COLOR;
+    }
+    if (params.has_goal == 1u && cell.x == i32(params.goal_x) && cell.y == i32(params.goal_y)) {
+        return GOAL_COLOR;
+    }
+    if (kind == SOURCE) {
+        return SOURCE_COLOR;
+    }
+    if (params.show_path == 1u && path[index] == params.path_stamp) {
+        return PATH_COLOR;
+    }
+
+    let distance = distances[index];
+    if (distance >= UNREACHED || distance > params.front) {
+        return vec3<f32>(0.0);
+    }
+    var color = lut_color(fract(distance / params.band_spacing));
+    if (params.front_width > 0.0) {
+        let glow = 1.0 - smoothstep(0.0, params.front_width, params.front - distance);
+        color = mix(color, vec3<f32>(1.0), glow * 0.6);
+    }
+    return color;
+}
+
+@compute @workgroup_size(8, 8)
+fn paint(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height) {
+        return;
+    }
+    textureStore(output_texture, vec2<i32>(id.xy), vec4<f32>(cell_color(vec2<i32>(id.xy)), 1.0));
+}
@@ -0,0 +1,53 @@
+// Walks the shortest path from the goal down to a source, always stepping
+// to the neighbor with the lowest distance, and stamps every cell on the
+// way. A single invocation does the whole walk; it only starts once the
+// wavefront has reached the goal.
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> walls: array<u32>;
+@group(0) @binding(2) var<storage, read> distances: array<f32>;
+@group(0) @binding(3) var<storage, read_write> path: array<u32>;
+
+@compute @workgroup_size(1)
+fn trace(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (params.has_goal == 0u || params.show_path == 0u) {
+        return;
+    }
+    var cell = vec2<i32>(i32(params.goal_x), i32(params.goal_y));
+    var distance = distances[cell_index(cell)];
+    if (distance >= UNREACHED || distance > params.front) {
+        return;
+    }
+
+    // Distances fall every step, so no walk is longer than the grid
+    let max_steps = params.grid_width * params.grid_height;
+    path[cell_index(cell)] = params.path_stamp;
+    for (var step = 0u; step < max_steps && distance > 0.0; step++) {
+        var next = cell;
+        for (var dy = -1; dy <= 1; dy++) {
+            for (var dx = -1; dx <= 1; dx++) {
+                let diagonal = dx != 0 && dy != 0;
+                if ((dx == 0 && dy == 0) || (diagonal && params.metric == 1u)) {
+                    continue;
+                }
+                if (diagonal && (is_wall(cell + vec2<i32>(dx, 0)) || is_wall(cell + vec2<i32>(0, dy)))) {
+                    continue;
+                }
+                let neighbor = cell + vec2<i32>(dx, dy);
+                if (is_wall(neighbor)) {
+                    continue;
+                }
+                let neighbor_distance = distances[cell_index(neighbor)];
+                if (neighbor_distance < distance) {
+                    distance = neighbor_distance;
+                    next = neighbor;
+                }
+            }
+        }
+        if (all(next == cell)) {
+            break;
+        }
+        cell = next;
+        path[cell_index(cell)] = params.path_stamp;
+    }
+}
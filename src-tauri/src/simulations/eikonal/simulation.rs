@@ -0,0 +1,953 @@
+//! # Eikonal Simulation Module
+//!
+//! Distance wavefronts spreading from sources through a maze, drawn as
+//! bands of equal distance, with the shortest path from the goal traced
+//! back once the front reaches it; see [`maze`] for the layouts and how
+//! the distances are found.
+//!
+//! ## Technical Overview
+//!
+//! The maze lives on the CPU, where the mouse draws on it, and is uploaded
+//! whenever it changes. Each frame:
+//! 1. A batch of relaxation passes ping-pongs the distances between two
+//!    buffers, each cell taking the distance its neighbors imply.
+//! 2. The front moves out at the wave speed, held back to what the passes
+//!    so far can have settled.
+//! 3. A single invocation walks downhill from the goal, stamping the path.
+//! 4. A compute pass paints the cells into the display texture, which is
+//!    drawn through the camera with infinite tiling.
+//!
+//! Any edit to the maze starts the flood over from the sources.
+//!
+//! [`maze`]: super::maze
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor,
+    Buffer, BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    FilterMode, PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, Sampler,
+    SamplerDescriptor, ShaderStages, SurfaceConfiguration, Texture, TextureFormat, TextureView,
+    TextureViewDescriptor,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::ping_pong_buffers::PingPongBuffers;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager};
+use crate::simulations::traits::Simulation;
+
+use super::maze::{Maze, OPEN, SOURCE, UNREACHED, WALL};
+use super::settings::{BrushTool, Layout, Metric, Settings};
+use super::shaders::{MARCH_SHADER, PAINT_SHADER, PATH_SHADER, RENDER_INFINITE_SHADER};
+use super::state::State;
+
+/// Distance a relaxation pass is sure to settle: a cell a step along each
+/// axis from a settled one needs both before it's right, so a diagonal
+/// front moves √2 in two passes
+const SETTLED_PER_ITERATION: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    metric: u32, // 0 = Eikonal, 1 = Taxicab, 2 = Octile
+    has_goal: u32,
+    goal_x: u32,
+    goal_y: u32,
+    path_stamp: u32,
+    show_path: u32,
+    front: f32,
+    band_spacing: f32,
+    front_width: f32,
+    _pad0: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct RenderParams {
+    filtering_mode: u32, // 0 = nearest, 1 = linear, 2 = lanczos
+    _pad1: u32,
+    _pad2: u32,
+    _pad3: u32,
+}
+
+/// Everything the grid's bind groups point at besides the grid itself
+#[derive(Debug)]
+struct Resources {
+    march_bind_group_layout: BindGroupLayout,
+    path_bind_group_layout: BindGroupLayout,
+    paint_bind_group_layout: BindGroupLayout,
+    render_infinite_bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    texture_render_params_buffer: Buffer,
+}
+
+/// The maze, distances and path on the GPU and the texture they're painted
+/// into, remade when the grid changes size. Bind groups come in pairs, one
+/// for each distance buffer being the current one.
+#[derive(Debug)]
+struct Grid {
+    width: u32,
+    height: u32,
+    walls: Buffer,
+    distances: PingPongBuffers,
+    _path: Buffer,
+    _display_texture: Texture,
+    march_bind_groups: [BindGroup; 2],
+    path_bind_groups: [BindGroup; 2],
+    paint_bind_groups: [BindGroup; 2],
+    render_infinite_bind_group: BindGroup,
+}
+
+impl Grid {
+    fn new(device: &Device, (width, height): (u32, u32), resources: &Resources) -> Self {
+        let cell_bytes = (width * height) as u64 * std::mem::size_of::<u32>() as u64;
+        let walls = device.create_buffer(&BufferDescriptor {
+            label: Some("Eikonal Walls Buffer"),
+            size: cell_bytes,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let distances = PingPongBuffers::new(
+            device,
+            cell_bytes,
+            BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            "Eikonal Distances Buffer",
+        );
+        let path = device.create_buffer(&BufferDescriptor {
+            label: Some("Eikonal Path Buffer"),
+            size: cell_bytes,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let display_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Eikonal Display Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let display_view = display_texture.create_view(&TextureViewDescriptor::default());
+
+        // Index 0 reads the current buffer before any swap
+        let (first, second) = (distances.current_buffer(), distances.inactive_buffer());
+        let march_bind_group = |label, from: &Buffer, to: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.march_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, &walls),
+                    resource_helpers::buffer_entry(2, from),
+                    resource_helpers::buffer_entry(3, to),
+                ],
+            })
+        };
+        let march_bind_groups = [
+            march_bind_group("Eikonal March Bind Group A", first, second),
+            march_bind_group("Eikonal March Bind Group B", second, first),
+        ];
+
+        let path_bind_group = |label, distances: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.path_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, &walls),
+                    resource_helpers::buffer_entry(2, distances),
+                    resource_helpers::buffer_entry(3, &path),
+                ],
+            })
+        };
+        let path_bind_groups = [
+            path_bind_group("Eikonal Path Bind Group A", first),
+            path_bind_group("Eikonal Path Bind Group B", second),
+        ];
+
+        let paint_bind_group = |label, distances: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.paint_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, &walls),
+                    resource_helpers::buffer_entry(2, distances),
+                    resource_helpers::buffer_entry(3, &path),
+                    resource_helpers::buffer_entry(4, &resources.lut_buffer),
+                    resource_helpers::texture_view_entry(5, &display_view),
+                ],
+            })
+        };
+        let paint_bind_groups = [
+            paint_bind_group("Eikonal Paint Bind Group A", first),
+            paint_bind_group("Eikonal Paint Bind Group B", second),
+        ];
+
+        let render_infinite_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Eikonal Render Infinite Bind Group"),
+            layout: &resources.render_infinite_bind_group_layout,
+            entries: &[
+                resource_helpers::texture_view_entry(0, &display_view),
+                resource_helpers::sampler_bind_entry(1, &resources.sampler),
+                resource_helpers::buffer_entry(2, &resources.texture_render_params_buffer),
+            ],
+        });
+
+        Self {
+            width,
+            height,
+            walls,
+            distances,
+            _path: path,
+            _display_texture: display_texture,
+            march_bind_groups,
+            path_bind_groups,
+            paint_bind_groups,
+            render_infinite_bind_group,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EikonalModel {
+    pub settings: Settings,
+    pub state: State,
+    maze: Maze,
+    // Whether the maze needs uploading and the flood starting over
+    dirty: bool,
+    // Frames the path has been walked, to tell this frame's path cells
+    // from stale ones
+    path_stamp: u32,
+
+    // GPU resources
+    march_pipeline: ComputePipeline,
+    path_pipeline: ComputePipeline,
+    paint_pipeline: ComputePipeline,
+    render_infinite_pipeline: RenderPipeline,
+    resources: Resources,
+    grid: Grid,
+    camera_bind_group: BindGroup,
+
+    // Camera for infinite rendering
+    pub camera: Camera,
+
+    // Surface size the grid is laid over
+    width: u32,
+    height: u32,
+}
+
+impl EikonalModel {
+    /// Calculate the number of tiles needed for infinite rendering based on zoom level
+    fn calculate_tile_count(&self) -> u32 {
+        let zoom = self.camera.zoom;
+        // Each tile covers 2.0 world units, so we need enough tiles to cover the visible area
+        let visible_world_size = 2.0 / zoom;
+        let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
+        let min_tiles = if zoom < 0.1 { 7 } else { 5 };
+        tiles_needed.max(min_tiles).min(1024)
+    }
+
+    fn grid_size(&self) -> (u32, u32) {
+        let cell_size = self.settings.cell_size.max(1);
+        (
+            (self.width / cell_size).max(1),
+            (self.height / cell_size).max(1),
+        )
+    }
+
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let march_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Eikonal March Shader"),
+            source: wgpu::ShaderSource::Wgsl(MARCH_SHADER.into()),
+        });
+        let path_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Eikonal Path Shader"),
+            source: wgpu::ShaderSource::Wgsl(PATH_SHADER.into()),
+        });
+        let paint_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Eikonal Paint Shader"),
+            source: wgpu::ShaderSource::Wgsl(PAINT_SHADER.into()),
+        });
+        let render_infinite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Eikonal Render Infinite Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_INFINITE_SHADER.into()),
+        });
+
+        // Nearest filtering keeps the cells crisp when zoomed in
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Eikonal Sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Eikonal Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Eikonal LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let render_params = RenderParams {
+            filtering_mode: app_settings.texture_filtering.into(),
+            _pad1: 0,
+            _pad2: 0,
+            _pad3: 0,
+        };
+        let texture_render_params_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Eikonal Texture Render Params Buffer"),
+                contents: bytemuck::cast_slice(&[render_params]),
+                usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            });
+
+        let march_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Eikonal March Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, false),
+            ],
+        });
+
+        let path_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Eikonal Path Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, false),
+            ],
+        });
+
+        let paint_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Eikonal Paint Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(4, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_texture_entry(
+                    5,
+                    ShaderStages::COMPUTE,
+                    wgpu::StorageTextureAccess::WriteOnly,
+                    TextureFormat::Rgba8Unorm,
+                ),
+            ],
+        });
+
+        let render_infinite_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Eikonal Render Infinite Bind Group Layout"),
+                entries: &[
+                    resource_helpers::texture_entry(
+                        0,
+                        ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        1,
+                        ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                    resource_helpers::uniform_buffer_entry(2, ShaderStages::FRAGMENT),
+                ],
+            });
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX,
+                )],
+            });
+
+        let compute_pipeline = |label: &str, layout: &BindGroupLayout, module, entry_point| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(&format!("Eikonal {} Pipeline", label)),
+                layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                    label: Some(&format!("Eikonal {} Pipeline Layout", label)),
+                    bind_group_layouts: &[layout],
+                    push_constant_ranges: &[],
+                })),
+                module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let march_pipeline =
+            compute_pipeline("March", &march_bind_group_layout, &march_module, "march");
+        let path_pipeline =
+            compute_pipeline("Path", &path_bind_group_layout, &path_module, "trace");
+        let paint_pipeline =
+            compute_pipeline("Paint", &paint_bind_group_layout, &paint_module, "paint");
+
+        let render_infinite_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Eikonal Render Infinite Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Eikonal Render Infinite Pipeline Layout"),
+                bind_group_layouts: &[
+                    &render_infinite_bind_group_layout,
+                    &camera_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_infinite_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_infinite_module,
+                entry_point: Some("fs_main_texture"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[resource_helpers::buffer_entry(0, camera.buffer())],
+        });
+
+        let resources = Resources {
+            march_bind_group_layout,
+            path_bind_group_layout,
+            paint_bind_group_layout,
+            render_infinite_bind_group_layout,
+            sampler,
+            params_buffer,
+            lut_buffer,
+            texture_render_params_buffer,
+        };
+        // Replaced by resize_grid once the model exists
+        let grid = Grid::new(device, (1, 1), &resources);
+
+        let mut model = Self {
+            settings,
+            state,
+            maze: Maze::new(1, 1),
+            dirty: true,
+            path_stamp: 0,
+            march_pipeline,
+            path_pipeline,
+            paint_pipeline,
+            render_infinite_pipeline,
+            resources,
+            grid,
+            camera_bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.resize_grid(device, queue)?;
+        Ok(model)
+    }
+
+    /// Remake the grid for the surface size and cell size, with a fresh
+    /// maze on it
+    fn resize_grid(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let (width, height) = self.grid_size();
+        self.grid = Grid::new(device, (width, height), &self.resources);
+        self.state.grid_width = width;
+        self.state.grid_height = height;
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Lay the maze out again from the settings
+    fn regenerate(&mut self) {
+        self.maze = Maze::generate(
+            self.grid.width,
+            self.grid.height,
+            self.settings.layout,
+            self.settings.corridor_width,
+            self.settings.obstacle_density,
+            self.settings.seed,
+        );
+        self.dirty = true;
+    }
+
+    /// Upload the maze if it changed, and send the flood out from the
+    /// sources again
+    fn restart_flood_if_dirty(&mut self, queue: &Arc<Queue>) {
+        if !self.dirty {
+            return;
+        }
+        queue.write_buffer(&self.grid.walls, 0, bytemuck::cast_slice(self.maze.cells()));
+        let unreached = vec![UNREACHED; (self.grid.width * self.grid.height) as usize];
+        queue.write_buffer(
+            self.grid.distances.current_buffer(),
+            0,
+            bytemuck::cast_slice(&unreached),
+        );
+        self.state.front = 0.0;
+        self.state.iterations = 0;
+        self.dirty = false;
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let goal = self.maze.goal();
+        let (goal_x, goal_y) = goal.unwrap_or((0, 0));
+        let params = Params {
+            grid_width: self.grid.width,
+            grid_height: self.grid.height,
+            metric: self.settings.metric.shader_index(),
+            has_goal: goal.is_some() as u32,
+            goal_x,
+            goal_y,
+            path_stamp: self.path_stamp,
+            show_path: self.settings.show_path as u32,
+            front: self.state.front,
+            band_spacing: self.settings.band_spacing.max(1.0),
+            front_width: self.settings.front_width,
+            _pad0: 0,
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
+    fn encode_march(&mut self, encoder: &mut wgpu::CommandEncoder, iterations: u32) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Eikonal March Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.march_pipeline);
+        for _ in 0..iterations {
+            compute_pass.set_bind_group(
+                0,
+                &self.grid.march_bind_groups[self.grid.distances.current_index()],
+                &[],
+            );
+            compute_pass.dispatch_workgroups(
+                self.grid.width.div_ceil(8),
+                self.grid.height.div_ceil(8),
+                1,
+            );
+            self.grid.distances.swap();
+        }
+    }
+
+    fn encode_path_and_paint(&self, encoder: &mut wgpu::CommandEncoder) {
+        let current = self.grid.distances.current_index();
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Eikonal Path and Paint Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.path_pipeline);
+        compute_pass.set_bind_group(0, &self.grid.path_bind_groups[current], &[]);
+        compute_pass.dispatch_workgroups(1, 1, 1);
+
+        compute_pass.set_pipeline(&self.paint_pipeline);
+        compute_pass.set_bind_group(0, &self.grid.paint_bind_groups[current], &[]);
+        compute_pass.dispatch_workgroups(
+            self.grid.width.div_ceil(8),
+            self.grid.height.div_ceil(8),
+            1,
+        );
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let tile_count = self.calculate_tile_count();
+        let total_instances = tile_count * tile_count;
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Eikonal Infinite Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.render_infinite_pipeline);
+        render_pass.set_bind_group(0, &self.grid.render_infinite_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+        render_pass.draw(0..6, 0..total_instances);
+    }
+
+    /// Run `iterations` relaxation passes, then trace, paint and draw
+    fn draw(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        iterations: u32,
+        label: &str,
+    ) {
+        self.path_stamp = self.path_stamp.wrapping_add(1).max(1);
+        self.update_params(queue);
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) });
+        if iterations > 0 {
+            self.encode_march(&mut encoder, iterations);
+        }
+        self.encode_path_and_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+    }
+
+    /// The maze cell under a world position, wrapped to the base tile
+    fn cell_at(&self, world_x: f32, world_y: f32) -> (i32, i32) {
+        // Wrap world coords to the base tile, then flip Y into grid rows
+        let wrapped_x = (world_x + 1.0).rem_euclid(2.0) - 1.0;
+        let wrapped_y = (world_y + 1.0).rem_euclid(2.0) - 1.0;
+        (
+            ((wrapped_x + 1.0) * 0.5 * self.grid.width as f32).floor() as i32,
+            ((1.0 - wrapped_y) * 0.5 * self.grid.height as f32).floor() as i32,
+        )
+    }
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for EikonalModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.restart_flood_if_dirty(queue);
+        let iterations = self.settings.iterations_per_frame.max(1);
+        self.state.iterations = self.state.iterations.saturating_add(iterations);
+        // The front never runs ahead of the distances the passes have settled
+        let settled = self.state.iterations as f32 * SETTLED_PER_ITERATION;
+        self.state.front = (self.state.front + delta_time * self.settings.wave_speed).min(settled);
+
+        self.camera.update(delta_time);
+        self.camera.upload_to_gpu(queue);
+        self.draw(device, queue, surface_view, iterations, "Eikonal Render");
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // Edits and coloring changes still show while paused
+        self.restart_flood_if_dirty(queue);
+        self.draw(device, queue, surface_view, 0, "Eikonal Render Paused");
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.resize_grid(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Left draws with the current tool, right erases
+        let tool = match mouse_button {
+            0 => self.state.brush_tool,
+            2 => BrushTool::Erase,
+            _ => return Ok(()),
+        };
+        let cell = self.cell_at(world_x, world_y);
+        let radius = self.state.cursor_size;
+        let changed = match tool {
+            BrushTool::Wall => self.maze.paint(cell, radius, WALL),
+            BrushTool::Erase => self.maze.paint(cell, radius, OPEN),
+            BrushTool::Source => self.maze.paint(cell, radius, SOURCE),
+            BrushTool::Goal => self.maze.set_goal(cell),
+        };
+        self.dirty |= changed;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.cell_size != self.settings.cell_size {
+            self.resize_grid(device, queue)?;
+        } else if old_settings.layout != self.settings.layout
+            || old_settings.corridor_width != self.settings.corridor_width
+            || old_settings.obstacle_density != self.settings.obstacle_density
+            || old_settings.seed != self.settings.seed
+        {
+            self.regenerate();
+        } else if old_settings.metric != self.settings.metric {
+            self.dirty = true;
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.regenerate();
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.layout = match rng.random_range(0..3) {
+            0 => Layout::Open,
+            1 => Layout::Maze,
+            _ => Layout::Scatter,
+        };
+        self.settings.metric = match rng.random_range(0..3) {
+            0 => Metric::Eikonal,
+            1 => Metric::Taxicab,
+            _ => Metric::Octile,
+        };
+        self.settings.corridor_width = rng.random_range(1..8);
+        self.settings.obstacle_density = rng.random_range(0.1..0.45);
+        self.settings.band_spacing = rng.random_range(6.0..60.0);
+        self.settings.seed = rng.random();
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "layout" => {
+                self.settings.layout = value
+                    .as_str()
+                    .unwrap_or("Maze")
+                    .parse()
+                    .map_err(|e| format!("Invalid layout: {}", e))?;
+                self.regenerate();
+            }
+            "cell_size" => {
+                self.settings.cell_size = number(setting_name, &value)? as u32;
+                self.resize_grid(device, queue)?;
+            }
+            "corridor_width" => {
+                self.settings.corridor_width = number(setting_name, &value)? as u32;
+                self.regenerate();
+            }
+            "obstacle_density" => {
+                self.settings.obstacle_density = number(setting_name, &value)? as f32;
+                if self.settings.layout == Layout::Scatter {
+                    self.regenerate();
+                }
+            }
+            "seed" => {
+                self.settings.seed = number(setting_name, &value)? as u32;
+                self.regenerate();
+            }
+            "metric" => {
+                self.settings.metric = value
+                    .as_str()
+                    .unwrap_or("Eikonal")
+                    .parse()
+                    .map_err(|e| format!("Invalid metric: {}", e))?;
+                self.dirty = true;
+            }
+            "iterations_per_frame" => {
+                self.settings.iterations_per_frame = number(setting_name, &value)? as u32;
+            }
+            "wave_speed" => self.settings.wave_speed = number(setting_name, &value)? as f32,
+            "band_spacing" => self.settings.band_spacing = number(setting_name, &value)? as f32,
+            "front_width" => self.settings.front_width = number(setting_name, &value)? as f32,
+            "show_path" => self.settings.show_path = value.as_bool().unwrap_or(true),
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "brush_tool" => {
+                self.state.brush_tool = value
+                    .as_str()
+                    .unwrap_or("Wall")
+                    .parse()
+                    .map_err(|e| format!("Invalid brush_tool: {}", e))?;
+            }
+            "cursor_size" => {
+                self.state.cursor_size = number(state_name, &value)?.clamp(0.0, 64.0) as f32;
+            }
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
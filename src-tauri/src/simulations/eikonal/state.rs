@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use super::settings::BrushTool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Grid size in cells
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Distance the wavefront has spread since the flood last started, and
+    // relaxation passes run in that time
+    pub front: f32,
+    pub iterations: u32,
+
+    // What the mouse draws, and the brush radius in cells
+    pub brush_tool: BrushTool,
+    pub cursor_size: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            grid_width: 0,
+            grid_height: 0,
+            front: 0.0,
+            iterations: 0,
+            brush_tool: BrushTool::Wall,
+            cursor_size: 3.0,
+            color_scheme_name: "MATPLOTLIB_viridis".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use super::maze::{Maze, SOURCE, UNREACHED, WALL, relax, trace_path};
+use super::settings::{Layout, Metric};
+use super::shaders::{MARCH_SHADER, PAINT_SHADER, PATH_SHADER, RENDER_INFINITE_SHADER};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+/// Relax until nothing changes, as a long enough flood does on the GPU
+fn settle(maze: &Maze, metric: Metric) -> Vec<f32> {
+    let mut distances = vec![UNREACHED; (maze.width() * maze.height()) as usize];
+    loop {
+        let next = relax(maze, metric, &distances);
+        if next == distances {
+            return distances;
+        }
+        distances = next;
+    }
+}
+
+fn at(maze: &Maze, distances: &[f32], x: u32, y: u32) -> f32 {
+    distances[(y * maze.width() + x) as usize]
+}
+
+#[test]
+fn open_ground_distances_follow_each_metric() {
+    let mut maze = Maze::new(41, 41);
+    maze.paint((20, 20), 0.0, SOURCE);
+
+    let taxicab = settle(&maze, Metric::Taxicab);
+    assert_eq!(at(&maze, &taxicab, 30, 27), 17.0);
+
+    let octile = settle(&maze, Metric::Octile);
+    let expected = 3.0 + 7.0 * std::f32::consts::SQRT_2;
+    assert!((at(&maze, &octile, 30, 27) - expected).abs() < 1e-4);
+
+    // Along the axes the eikonal update is exact, and off them a single
+    // point source comes out a little long within a few cells of it
+    let eikonal = settle(&maze, Metric::Eikonal);
+    assert_eq!(at(&maze, &eikonal, 40, 20), 20.0);
+    for (x, y) in [(30, 27), (35, 35), (5, 32), (22, 1)] {
+        let exact = ((x as f32 - 20.0).powi(2) + (y as f32 - 20.0).powi(2)).sqrt();
+        let distance = at(&maze, &eikonal, x, y);
+        assert!(distance >= exact - 1e-3, "{} under {}", distance, exact);
+        assert!(distance < exact * 1.08, "{} over {}", distance, exact);
+    }
+}
+
+#[test]
+fn walls_turn_the_front_and_shut_out_enclosures() {
+    let mut maze = Maze::new(30, 30);
+    maze.paint((2, 15), 0.0, SOURCE);
+    // A wall down the middle with a gap at the top
+    for y in 4..30 {
+        maze.paint((15, y), 0.0, WALL);
+    }
+    // A sealed box in the top right
+    for i in 20..=26 {
+        for (x, y) in [(i, 0), (i, 6), (20, i - 20), (26, i - 20)] {
+            maze.paint((x, y), 0.0, WALL);
+        }
+    }
+
+    let distances = settle(&maze, Metric::Taxicab);
+    // Around the end of the wall rather than through it
+    assert_eq!(at(&maze, &distances, 16, 15), (13 + 12 + 1 + 12) as f32);
+    assert_eq!(at(&maze, &distances, 15, 15), UNREACHED);
+    assert_eq!(at(&maze, &distances, 23, 3), UNREACHED);
+}
+
+#[test]
+fn mazes_connect_every_corridor_and_reach_the_goal() {
+    for (corridor, seed) in [(1, 3), (3, 7), (6, 11)] {
+        let maze = Maze::generate(61, 45, Layout::Maze, corridor, 0.0, seed);
+        let distances = settle(&maze, Metric::Taxicab);
+        for (index, &cell) in maze.cells().iter().enumerate() {
+            if cell != WALL {
+                assert!(distances[index] < UNREACHED, "{} cut off", index);
+            }
+        }
+        let (goal_x, goal_y) = maze.goal().unwrap();
+        assert!(at(&maze, &distances, goal_x, goal_y) > 0.0);
+    }
+}
+
+#[test]
+fn mazes_follow_their_seed() {
+    let a = Maze::generate(50, 40, Layout::Maze, 2, 0.0, 1);
+    let b = Maze::generate(50, 40, Layout::Maze, 2, 0.0, 1);
+    let c = Maze::generate(50, 40, Layout::Maze, 2, 0.0, 2);
+    assert_eq!(a.cells(), b.cells());
+    assert_ne!(a.cells(), c.cells());
+}
+
+#[test]
+fn paths_run_downhill_from_goal_to_source() {
+    for metric in [Metric::Eikonal, Metric::Taxicab, Metric::Octile] {
+        for layout in [Layout::Maze, Layout::Scatter, Layout::Open] {
+            let maze = Maze::generate(64, 48, layout, 3, 0.3, 5);
+            let distances = settle(&maze, metric);
+            let path = trace_path(&maze, metric, &distances);
+            let (goal_x, goal_y) = maze.goal().unwrap();
+            assert_eq!(path[0], (goal_x as i32, goal_y as i32));
+
+            let &(end_x, end_y) = path.last().unwrap();
+            assert_eq!(maze.cell(end_x, end_y), SOURCE, "{:?} {:?}", metric, layout);
+            for step in path.windows(2) {
+                let [(ax, ay), (bx, by)] = [step[0], step[1]];
+                assert!((ax - bx).abs() <= 1 && (ay - by).abs() <= 1);
+                if metric == Metric::Taxicab {
+                    assert_eq!((ax - bx).abs() + (ay - by).abs(), 1);
+                }
+                assert_ne!(maze.cell(bx, by), WALL);
+                let before = at(&maze, &distances, ax as u32, ay as u32);
+                let after = at(&maze, &distances, bx as u32, by as u32);
+                assert!(after < before);
+            }
+            if metric == Metric::Taxicab {
+                let goal_distance = at(&maze, &distances, goal_x, goal_y);
+                assert_eq!(path.len() as f32 - 1.0, goal_distance);
+            }
+        }
+    }
+}
+
+#[test]
+fn unreached_goals_and_missing_goals_give_no_path() {
+    let mut maze = Maze::new(10, 10);
+    maze.paint((1, 1), 0.0, SOURCE);
+    assert!(trace_path(&maze, Metric::Eikonal, &settle(&maze, Metric::Eikonal)).is_empty());
+
+    maze.set_goal((8, 8));
+    let unreached = vec![UNREACHED; 100];
+    assert!(trace_path(&maze, Metric::Eikonal, &unreached).is_empty());
+}
+
+#[test]
+fn the_brush_paints_a_disc_and_the_goal_clears_its_wall() {
+    let mut maze = Maze::new(20, 20);
+    assert!(maze.paint((10, 10), 3.0, WALL));
+    assert!(!maze.paint((10, 10), 3.0, WALL));
+    assert_eq!(maze.cell(13, 10), WALL);
+    assert_eq!(maze.cell(12, 12), WALL);
+    assert_ne!(maze.cell(13, 13), WALL);
+    // Off the edge is walled but never painted
+    assert!(!maze.paint((-5, -5), 2.0, SOURCE));
+
+    assert!(maze.set_goal((10, 10)));
+    assert_ne!(maze.cell(10, 10), WALL);
+    assert!(!maze.set_goal((10, 10)));
+    assert!(!maze.set_goal((25, 3)));
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Eikonal March Shader", MARCH_SHADER),
+        ("Eikonal Path Shader", PATH_SHADER),
+        ("Eikonal Paint Shader", PAINT_SHADER),
+        ("Eikonal Render Infinite Shader", RENDER_INFINITE_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
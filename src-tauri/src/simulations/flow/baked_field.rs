@@ -0,0 +1,277 @@
+//! Flow vector fields baked to disk, so a field generated from noise or an
+//! image can be frozen and loaded again later.
+//!
+//! A `.vzfield` file is a 16 byte header followed by the vectors:
+//!
+//! | bytes | content                          |
+//! |-------|----------------------------------|
+//! | 0-3   | magic `VZFF`                     |
+//! | 4-7   | format version, u32              |
+//! | 8-11  | width, u32                       |
+//! | 12-15 | height, u32                      |
+//! | 16-   | `width * height` (x, y) f32 pairs |
+//!
+//! All numbers are little endian. Vectors are stored row by row from world
+//! y = -1 up, and the first and last sample of each row and column sit on
+//! the edges of the world, like the grid `flow_vector_compute.wgsl` fills.
+
+use std::path::PathBuf;
+
+use super::simulation::FlowVector;
+use crate::commands::get_settings_dir;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulation::preset_manager::sanitize_filename;
+
+const MAGIC: &[u8; 4] = b"VZFF";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 16;
+const EXTENSION: &str = "vzfield";
+
+/// Largest side a field file may declare, so a corrupt header can't ask for
+/// gigabytes
+const MAX_SIZE: u32 = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowField {
+    pub width: u32,
+    pub height: u32,
+    /// Direction and magnitude of the flow at each sample
+    pub vectors: Vec<[f32; 2]>,
+}
+
+impl FlowField {
+    /// Take the directions of a square `grid` x `grid` buffer of flow vectors
+    pub fn from_flow_vectors(grid: u32, flow_vectors: &[FlowVector]) -> SimulationResult<Self> {
+        if flow_vectors.len() != (grid * grid) as usize {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Expected {} flow vectors for a {}x{} grid, got {}",
+                grid * grid,
+                grid,
+                grid,
+                flow_vectors.len()
+            )));
+        }
+        Ok(Self {
+            width: grid,
+            height: grid,
+            vectors: flow_vectors.iter().map(|v| v.direction).collect(),
+        })
+    }
+
+    /// The field resampled onto a `grid` x `grid` buffer of flow vectors
+    pub fn to_flow_vectors(&self, grid: u32) -> Vec<FlowVector> {
+        let resampled = self.resample(grid, grid);
+        let world = |i: u32| i as f32 / (grid - 1).max(1) as f32 * 2.0 - 1.0;
+        (0..grid)
+            .flat_map(|y| (0..grid).map(move |x| (x, y)))
+            .zip(resampled.vectors)
+            .map(|((x, y), direction)| FlowVector {
+                position: [world(x), world(y)],
+                direction,
+            })
+            .collect()
+    }
+
+    /// Bilinearly resample to `width` x `height`, keeping the corner samples
+    /// on the corners
+    pub fn resample(&self, width: u32, height: u32) -> Self {
+        if width == self.width && height == self.height {
+            return self.clone();
+        }
+        let scale = |size: u32, source: u32| {
+            if size > 1 {
+                (source - 1) as f32 / (size - 1) as f32
+            } else {
+                0.0
+            }
+        };
+        let (scale_x, scale_y) = (scale(width, self.width), scale(height, self.height));
+
+        let mut vectors = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            let sy = y as f32 * scale_y;
+            let (y0, ty) = (sy.floor() as u32, sy.fract());
+            let y1 = (y0 + 1).min(self.height - 1);
+            for x in 0..width {
+                let sx = x as f32 * scale_x;
+                let (x0, tx) = (sx.floor() as u32, sx.fract());
+                let x1 = (x0 + 1).min(self.width - 1);
+
+                let at = |x: u32, y: u32| self.vectors[(y * self.width + x) as usize];
+                let lerp = |a: [f32; 2], b: [f32; 2], t: f32| {
+                    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+                };
+                let bottom = lerp(at(x0, y0), at(x1, y0), tx);
+                let top = lerp(at(x0, y1), at(x1, y1), tx);
+                vectors.push(lerp(bottom, top, ty));
+            }
+        }
+        Self {
+            width,
+            height,
+            vectors,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.vectors.len() * 8);
+        bytes.extend_from_slice(MAGIC);
+        for value in [VERSION, self.width, self.height] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in self.vectors.iter().flatten() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SimulationResult<Self> {
+        let invalid = |message: &str| {
+            Err(SimulationError::InvalidParameter(format!(
+                "Invalid flow field file: {}",
+                message
+            )))
+        };
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+            return invalid("missing header");
+        }
+        let u32_at =
+            |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        if u32_at(4) != VERSION {
+            return invalid(&format!("unsupported version {}", u32_at(4)));
+        }
+        let (width, height) = (u32_at(8), u32_at(12));
+        if !(1..=MAX_SIZE).contains(&width) || !(1..=MAX_SIZE).contains(&height) {
+            return invalid(&format!("unsupported size {}x{}", width, height));
+        }
+        let data = &bytes[HEADER_LEN..];
+        if data.len() != (width * height) as usize * 8 {
+            return invalid("vector data doesn't match the size");
+        }
+
+        let vectors: Vec<[f32; 2]> = data
+            .chunks_exact(8)
+            .map(|chunk| {
+                [
+                    f32::from_le_bytes(chunk[0..4].try_into().unwrap()),
+                    f32::from_le_bytes(chunk[4..8].try_into().unwrap()),
+                ]
+            })
+            .collect();
+        if !vectors.iter().flatten().all(|v| v.is_finite()) {
+            return invalid("vectors must be finite");
+        }
+        Ok(Self {
+            width,
+            height,
+            vectors,
+        })
+    }
+
+    /// Write the field to the baked field folder, returning its path
+    pub fn save(&self, name: &str) -> SimulationResult<PathBuf> {
+        let path = field_path(name)?;
+        std::fs::create_dir_all(fields_dir())
+            .and_then(|_| std::fs::write(&path, self.to_bytes()))
+            .map_err(|e| {
+                SimulationError::InvalidParameter(format!(
+                    "Failed to write {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(path)
+    }
+
+    pub fn load(name: &str) -> SimulationResult<Self> {
+        let bytes = std::fs::read(field_path(name)?).map_err(|e| {
+            SimulationError::InvalidParameter(format!(
+                "Failed to read flow field '{}': {}",
+                name, e
+            ))
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn delete(name: &str) -> SimulationResult<()> {
+        std::fs::remove_file(field_path(name)?).map_err(|e| {
+            SimulationError::InvalidParameter(format!(
+                "Failed to delete flow field '{}': {}",
+                name, e
+            ))
+        })
+    }
+
+    /// Names of the baked fields on disk, sorted
+    pub fn list() -> Vec<String> {
+        let Ok(files) = std::fs::read_dir(fields_dir()) else {
+            return vec![];
+        };
+        let mut names: Vec<String> = files
+            .filter_map(|file| file.ok())
+            .map(|file| file.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(EXTENSION))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+fn fields_dir() -> PathBuf {
+    get_settings_dir().join("flow").join("fields")
+}
+
+fn field_path(name: &str) -> SimulationResult<PathBuf> {
+    let name = sanitize_filename(name.trim());
+    if name.is_empty() || name.starts_with('.') {
+        return Err(SimulationError::InvalidParameter(format!(
+            "Invalid flow field name '{}'",
+            name
+        )));
+    }
+    Ok(fields_dir().join(format!("{}.{}", name, EXTENSION)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field() -> FlowField {
+        FlowField {
+            width: 2,
+            height: 2,
+            vectors: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]],
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes = field().to_bytes();
+        assert_eq!(bytes.len(), HEADER_LEN + 4 * 8);
+        assert_eq!(FlowField::from_bytes(&bytes).unwrap(), field());
+
+        assert!(FlowField::from_bytes(&bytes[..bytes.len() - 4]).is_err());
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 2;
+        assert!(FlowField::from_bytes(&wrong_version).is_err());
+        assert!(FlowField::from_bytes(b"VZFF").is_err());
+    }
+
+    #[test]
+    fn resampling_keeps_the_corners() {
+        let resampled = field().resample(3, 3);
+        assert_eq!(resampled.vectors[0], [0.0, 0.0]);
+        assert_eq!(resampled.vectors[4], [0.5, 0.5]);
+        assert_eq!(resampled.vectors[8], [1.0, 1.0]);
+
+        let flow_vectors = field().to_flow_vectors(3);
+        assert_eq!(flow_vectors[0].position, [-1.0, -1.0]);
+        assert_eq!(flow_vectors[5].position, [1.0, 0.0]);
+        assert_eq!(flow_vectors[5].direction, [1.0, 0.5]);
+        assert_eq!(
+            FlowField::from_flow_vectors(3, &flow_vectors).unwrap(),
+            field().resample(3, 3)
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Emitters are named spawn points that keep releasing particles into the
+//! flow whether or not the cursor is painting. They are stored in
+//! [`Settings`](super::settings::Settings), so presets save them, and draw
+//! their particles from the brush pool when the brush isn't using it.
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SimulationError, SimulationResult};
+
+/// Size of the emitter array in `particle_update.wgsl`
+pub const MAX_EMITTERS: usize = 16;
+
+/// Same cap as brush and autospawn tickets
+const MAX_TICKETS_PER_FRAME: u32 = 100000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Emitter {
+    pub name: String,
+    /// World space, -1 to 1 on both axes
+    pub position: [f32; 2],
+    /// Particles per second
+    pub rate: u32,
+    /// Launch direction in degrees, counterclockwise from +x
+    pub direction: f32,
+    /// Speed along `direction` on top of the flow, fading out as the
+    /// particle ages. 0 leaves the particles to the flow.
+    pub launch_speed: f32,
+    /// Radius of the spawn area in world units
+    pub radius: f32,
+    /// Color scheme position (0-1) for the particles and their trails, or
+    /// `None` to color them like every other particle
+    pub color: Option<f32>,
+    pub enabled: bool,
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self {
+            name: "Emitter".to_string(),
+            position: [0.0, 0.0],
+            rate: 200,
+            direction: 0.0,
+            launch_speed: 0.0,
+            radius: 0.05,
+            color: None,
+            enabled: true,
+        }
+    }
+}
+
+impl Emitter {
+    fn validate(&self) -> SimulationResult<()> {
+        let invalid = |message: String| Err(SimulationError::InvalidParameter(message));
+        if self.name.trim().is_empty() {
+            return invalid("Emitter name cannot be empty".to_string());
+        }
+        if !self.position.iter().all(|v| (-1.0..=1.0).contains(v)) {
+            return invalid(format!(
+                "Emitter '{}' position must be within -1 to 1",
+                self.name
+            ));
+        }
+        if !self.direction.is_finite() || !self.launch_speed.is_finite() {
+            return invalid(format!(
+                "Emitter '{}' direction and launch speed must be finite",
+                self.name
+            ));
+        }
+        if !(0.0..=2.0).contains(&self.radius) {
+            return invalid(format!(
+                "Emitter '{}' radius must be between 0 and 2",
+                self.name
+            ));
+        }
+        if self.color.is_some_and(|c| !(0.0..=1.0).contains(&c)) {
+            return invalid(format!(
+                "Emitter '{}' color must be between 0 and 1",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn add_emitter(emitters: &mut Vec<Emitter>, emitter: Emitter) -> SimulationResult<()> {
+    emitter.validate()?;
+    if emitters.len() >= MAX_EMITTERS {
+        return Err(SimulationError::InvalidParameter(format!(
+            "At most {} emitters are supported",
+            MAX_EMITTERS
+        )));
+    }
+    if emitters.iter().any(|e| e.name == emitter.name) {
+        return Err(SimulationError::InvalidParameter(format!(
+            "An emitter named '{}' already exists",
+            emitter.name
+        )));
+    }
+    emitters.push(emitter);
+    Ok(())
+}
+
+/// Replace the emitter called `name`, which may rename it
+pub fn update_emitter(
+    emitters: &mut [Emitter],
+    name: &str,
+    emitter: Emitter,
+) -> SimulationResult<()> {
+    emitter.validate()?;
+    if emitter.name != name && emitters.iter().any(|e| e.name == emitter.name) {
+        return Err(SimulationError::InvalidParameter(format!(
+            "An emitter named '{}' already exists",
+            emitter.name
+        )));
+    }
+    let existing = emitters
+        .iter_mut()
+        .find(|e| e.name == name)
+        .ok_or_else(|| SimulationError::InvalidParameter(format!("No emitter named '{}'", name)))?;
+    *existing = emitter;
+    Ok(())
+}
+
+pub fn remove_emitter(emitters: &mut Vec<Emitter>, name: &str) -> SimulationResult<()> {
+    let index = emitters
+        .iter()
+        .position(|e| e.name == name)
+        .ok_or_else(|| SimulationError::InvalidParameter(format!("No emitter named '{}'", name)))?;
+    emitters.remove(index);
+    Ok(())
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable, Default)]
+pub struct GpuEmitter {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    pub radius: f32,
+    /// Negative for no color of its own
+    pub color: f32,
+    pub allowed: u32,
+    pub spawned: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable, Default)]
+pub struct GpuEmitters {
+    pub count: u32,
+    /// Sum of `allowed` over all emitters
+    pub total_allowed: u32,
+    pub _pad0: u32,
+    pub _pad1: u32,
+    pub emitters: [GpuEmitter; MAX_EMITTERS],
+}
+
+/// Turns emitter rates into whole spawn tickets each frame, carrying the
+/// fractions over like the brush and autospawn accumulators do
+#[derive(Debug, Default)]
+pub struct EmitterTickets {
+    accumulators: Vec<f32>,
+}
+
+impl EmitterTickets {
+    pub fn reset(&mut self) {
+        self.accumulators.clear();
+    }
+
+    pub fn next_frame(&mut self, emitters: &[Emitter], delta_time: f32) -> GpuEmitters {
+        let emitters = &emitters[..emitters.len().min(MAX_EMITTERS)];
+        self.accumulators.resize(emitters.len(), 0.0);
+
+        let mut gpu = GpuEmitters {
+            count: emitters.len() as u32,
+            ..Default::default()
+        };
+        for (i, emitter) in emitters.iter().enumerate() {
+            let rate = if emitter.enabled {
+                emitter.rate as f32
+            } else {
+                0.0
+            };
+            self.accumulators[i] += rate * delta_time;
+            let allowed = (self.accumulators[i].floor() as u32).min(MAX_TICKETS_PER_FRAME);
+            self.accumulators[i] = self.accumulators[i].fract();
+
+            let angle = emitter.direction.to_radians();
+            gpu.emitters[i] = GpuEmitter {
+                position: emitter.position,
+                velocity: [
+                    angle.cos() * emitter.launch_speed,
+                    angle.sin() * emitter.launch_speed,
+                ],
+                radius: emitter.radius,
+                color: emitter.color.unwrap_or(-1.0),
+                allowed,
+                spawned: 0,
+            };
+            gpu.total_allowed += allowed;
+        }
+        gpu
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emitter(name: &str) -> Emitter {
+        Emitter {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn emitter_names_are_unique() {
+        let mut emitters = vec![];
+        add_emitter(&mut emitters, emitter("left")).unwrap();
+        add_emitter(&mut emitters, emitter("right")).unwrap();
+        assert!(add_emitter(&mut emitters, emitter("left")).is_err());
+        assert!(update_emitter(&mut emitters, "left", emitter("right")).is_err());
+
+        update_emitter(&mut emitters, "left", emitter("top")).unwrap();
+        remove_emitter(&mut emitters, "right").unwrap();
+        assert_eq!(emitters, vec![emitter("top")]);
+        assert!(remove_emitter(&mut emitters, "right").is_err());
+    }
+
+    #[test]
+    fn tickets_carry_fractions_between_frames() {
+        let mut emitters = vec![
+            Emitter {
+                rate: 90,
+                ..emitter("a")
+            },
+            Emitter {
+                enabled: false,
+                ..emitter("b")
+            },
+        ];
+        emitters[0].color = Some(0.5);
+
+        let mut tickets = EmitterTickets::default();
+        let spawned: u32 = (0..60)
+            .map(|_| tickets.next_frame(&emitters, 1.0 / 60.0).emitters[0].allowed)
+            .sum();
+        assert!((89..=90).contains(&spawned));
+
+        let gpu = tickets.next_frame(&emitters, 1.0 / 60.0);
+        assert_eq!(gpu.count, 2);
+        assert_eq!(gpu.emitters[1].allowed, 0);
+        assert_eq!(gpu.emitters[0].color, 0.5);
+        assert_eq!(gpu.emitters[1].color, -1.0);
+    }
+}
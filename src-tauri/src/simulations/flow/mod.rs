@@ -1,3 +1,5 @@
+pub mod baked_field;
+pub mod emitters;
 pub mod settings;
 pub mod shaders;
 pub mod simulation;
@@ -24,7 +26,7 @@ pub fn init_presets(preset_manager: &mut FlowPresetManager) {
 
     // Load user presets from TOML files
     if let Err(e) = preset_manager.load_user_presets() {
-        eprintln!("Warning: Could not load user presets: {}", e);
+        tracing::warn!("Could not load user presets: {}", e);
     }
 
     let preset_count = preset_manager.get_preset_names().len();
@@ -1,4 +1,8 @@
-use crate::simulations::shared::ImageFitMode;
+use super::emitters::Emitter;
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use crate::simulations::shared::{BackgroundLayer, ImageFitMode};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
@@ -6,6 +10,8 @@ use std::fmt::{self, Display};
 pub enum VectorFieldType {
     Noise,
     Image,
+    /// A field loaded from a `.vzfield` file, see [`super::baked_field`]
+    Baked,
 }
 
 impl Display for VectorFieldType {
@@ -16,6 +22,7 @@ impl Display for VectorFieldType {
             match self {
                 Self::Noise => "Noise",
                 Self::Image => "Image",
+                Self::Baked => "Baked",
             }
         )
     }
@@ -222,6 +229,10 @@ pub struct Settings {
     pub image_mirror_vertical: bool,
     pub image_invert_tone: bool,
 
+    // Baked vector field, used when vector_field_type is Baked
+    #[serde(default)]
+    pub baked_field: Option<String>,
+
     // Particle parameters
     pub total_pool_size: u32, // Total number of particles (autospawn + brush)
     pub particle_lifetime: f32,
@@ -240,6 +251,14 @@ pub struct Settings {
     pub trail_deposition_rate: f32,
     pub trail_diffusion_rate: f32,
     pub trail_wash_out_rate: f32,
+
+    // Emitters
+    #[serde(default)]
+    pub emitters: Vec<Emitter>,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
 }
 
 impl Default for Settings {
@@ -260,6 +279,7 @@ impl Default for Settings {
             image_mirror_horizontal: false,
             image_mirror_vertical: false,
             image_invert_tone: false,
+            baked_field: None,
 
             // Particle parameters
             total_pool_size: 100000,
@@ -279,6 +299,100 @@ impl Default for Settings {
             trail_deposition_rate: 1.0,
             trail_diffusion_rate: 0.0,
             trail_wash_out_rate: 0.1,
+
+            // Emitters
+            emitters: Vec::new(),
+            background_layer: BackgroundLayer::default(),
         }
     }
 }
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "noise_type",
+            Rule::OneOf(&[
+                "OpenSimplex",
+                "Worley",
+                "Value",
+                "FBM",
+                "FBMBillow",
+                "FBMClouds",
+                "FBMRidged",
+                "Billow",
+                "RidgedMulti",
+                "Cylinders",
+                "Checkerboard",
+            ]),
+        ),
+        (
+            "noise_scale",
+            Rule::Range {
+                min: 0.001,
+                max: 10.0,
+            },
+        ),
+        (
+            "noise_dt_multiplier",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        (
+            "vector_magnitude",
+            Rule::Range {
+                min: 0.001,
+                max: 5.0,
+            },
+        ),
+        (
+            "particle_lifetime",
+            Rule::Range {
+                min: 0.1,
+                max: 60.0,
+            },
+        ),
+        (
+            "particle_speed",
+            Rule::Range {
+                min: 0.001,
+                max: 100.0,
+            },
+        ),
+        ("particle_size", Rule::Count { min: 1, max: 50 }),
+        ("trail_decay_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        ("trail_deposition_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        ("trail_diffusion_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        ("trail_wash_out_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "particle_shape",
+            Rule::OneOf(&["Circle", "Square", "Triangle", "Flower", "Star", "Diamond"]),
+        ),
+        ("particle_autospawn", Rule::Flag),
+        ("autospawn_rate", Rule::Count { min: 0, max: 10000 }),
+        ("brush_spawn_rate", Rule::Count { min: 1, max: 10000 }),
+        ("trail_map_filtering", Rule::OneOf(&["Nearest", "Linear"])),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("vector_field_type", SettingCategory::Generators),
+    ("noise_type", SettingCategory::Generators),
+    ("noise_seed", SettingCategory::Generators),
+    ("noise_scale", SettingCategory::Generators),
+    ("noise_x", SettingCategory::Generators),
+    ("noise_y", SettingCategory::Generators),
+    ("image_fit_mode", SettingCategory::Generators),
+    ("image_mirror_horizontal", SettingCategory::Generators),
+    ("image_mirror_vertical", SettingCategory::Generators),
+    ("image_invert_tone", SettingCategory::Generators),
+    ("baked_field", SettingCategory::Generators),
+    ("emitters", SettingCategory::Generators),
+    ("foreground_color_mode", SettingCategory::Colors),
+]);
@@ -1,5 +1,13 @@
-pub const PARTICLE_UPDATE_SHADER: &str = include_str!("particle_update.wgsl");
-pub const PARTICLE_RENDER_SHADER: &str = include_str!("particle_render.wgsl");
+pub const PARTICLE_UPDATE_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("../../shared/global_force.wgsl"),
+    include_str!("../../shared/environment_field.wgsl"),
+    include_str!("particle_update.wgsl")
+);
+pub const PARTICLE_RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("particle_render.wgsl")
+);
 pub const TRAIL_DECAY_DIFFUSION_SHADER: &str = include_str!("trail_decay_diffusion.wgsl");
 pub const TRAIL_RENDER_SHADER: &str = include_str!("trail_render.wgsl");
 pub const BACKGROUND_RENDER_SHADER: &str = include_str!("background_render.wgsl");
@@ -4,8 +4,8 @@ struct Particle {
     lut_index: u32, // 0-255 LUT stop index
     is_alive: u32, // 0=dead, 1=alive
     spawn_type: u32, // 0=autospawn, 1=brush
-    pad0: u32,
-    pad1: u32,
+    emitter: u32, // Index + 1 of the emitter that spawned it, 0=none
+    dyed: u32, // 1 when lut_index holds the emitter's color
 }
 
 struct SimParams {
@@ -59,15 +59,6 @@ struct CameraUniform {
 @group(0) @binding(2) var<storage, read> lut_data: array<u32>;
 @group(1) @binding(0) var<uniform> camera: CameraUniform;
 
-// Convert from sRGB (gamma-corrected) to linear RGB
-fn srgb_to_linear(srgb: f32) -> f32 {
-    if (srgb <= 0.04045) {
-        return srgb / 12.92;
-    } else {
-        return pow((srgb + 0.055) / 1.055, 2.4);
-    }
-}
-
 // Get color from LUT
 fn get_lut_color(intensity: f32) -> vec3<f32> {
     let lut_index = clamp(intensity * 255.0, 0.0, 255.0);
@@ -190,7 +181,9 @@ fn fs_main(@location(0) uv: vec2<f32>, @location(1) particle_index: u32) -> @loc
     // Calculate color based on display mode
     var color_intensity = 0.0;
     
-    if (sim_params.display_mode == 0u) { // Age mode
+    if (particle.dyed == 1u) { // Emitter color
+        color_intensity = f32(particle.lut_index) / 255.0;
+    } else if (sim_params.display_mode == 0u) { // Age mode
         // Use particle age as the color intensity to create a gradient effect
         let age_ratio = particle.age / sim_params.particle_lifetime;
         color_intensity = 1.0 - age_ratio; // Younger particles = higher intensity
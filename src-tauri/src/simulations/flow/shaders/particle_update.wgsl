@@ -4,8 +4,8 @@ struct Particle {
     lut_index: u32, // 0-255 LUT stop index
     is_alive: u32, // 0=dead, 1=alive
     spawn_type: u32, // 0=autospawn, 1=brush
-    pad0: u32,
-    pad1: u32,
+    emitter: u32, // Index + 1 of the emitter that spawned it, 0=none
+    dyed: u32, // 1 when lut_index holds the emitter's color
 }
 
 struct FlowVector {
@@ -66,6 +66,74 @@ struct SpawnControl {
 }
 @group(0) @binding(5) var<storage, read_write> spawn_control: SpawnControl;
 
+// Persistent emitters with per-frame quotas, controlled by CPU
+const MAX_EMITTERS: u32 = 16u;
+struct Emitter {
+    position: vec2<f32>,
+    velocity: vec2<f32>, // Launch velocity, fades out with age
+    radius: f32,
+    color: f32, // LUT position, negative for none
+    allowed: u32,
+    spawned: atomic<u32>,
+}
+struct Emitters {
+    count: u32,
+    total_allowed: u32,
+    _pad0: u32,
+    _pad1: u32,
+    emitters: array<Emitter, MAX_EMITTERS>,
+}
+@group(0) @binding(6) var<storage, read_write> emitters: Emitters;
+@group(0) @binding(7) var<uniform> global_force: GlobalForce;
+@group(0) @binding(8) var<storage, read> environment_field: EnvironmentField;
+
+struct EmitterSpawn {
+    emitter: u32, // Index + 1 of the emitter, 0 if nothing was spawned
+    position: vec2<f32>,
+}
+
+// Try to spawn a dead brush pool particle at one of the emitters
+fn spawn_from_emitter(particle_index: u32) -> EmitterSpawn {
+    var result = EmitterSpawn(0u, vec2<f32>(0.0, 0.0));
+    let count = min(emitters.count, MAX_EMITTERS);
+    if (count == 0u || emitters.total_allowed == 0u) {
+        return result;
+    }
+
+    // Same probabilistic spread as brush spawning, at the emitters' combined rate
+    let dt = sim_params.delta_time;
+    let expected_spawns = f32(emitters.total_allowed);
+    let pool = max(1u, sim_params.brush_pool_size);
+    let expected_alive = min(expected_spawns / max(dt, 1e-6) * sim_params.particle_lifetime, f32(pool));
+    let estimated_dead = max(1.0, f32(pool) - expected_alive);
+    let p = clamp(expected_spawns / estimated_dead, 0.0, 1.0);
+    let frame_idx = floor(sim_params.time / max(dt, 1e-6));
+    let seed = f32(particle_index) * 1.61803 + frame_idx;
+    let randv = fract(sin(seed) * 43758.5453);
+    if (randv >= p) {
+        return result;
+    }
+
+    // Start at a different emitter per particle so none is starved
+    let first = particle_index % count;
+    for (var i = 0u; i < count; i++) {
+        let index = (first + i) % count;
+        let ticket = atomicAdd(&emitters.emitters[index].spawned, 1u);
+        if (ticket < emitters.emitters[index].allowed) {
+            let radius = emitters.emitters[index].radius;
+            let seed1 = f32(particle_index) * 0.4321 + sim_params.time * 0.1;
+            let seed2 = f32(particle_index) * 0.8765 + sim_params.time * 0.05;
+            let angle = fract(sin(seed1) * 43758.5453) * 2.0 * 3.14159;
+            let distance = sqrt(fract(cos(seed2) * 43758.5453));
+            result.emitter = index + 1u;
+            result.position = emitters.emitters[index].position
+                + vec2<f32>(cos(angle), sin(angle)) * radius * distance;
+            return result;
+        }
+    }
+    return result;
+}
+
 // O(1) bilinear sample of flow direction from a uniform grid
 fn sample_flow_vector(pos: vec2<f32>) -> vec2<f32> {
     let grid = f32(sim_params.flow_field_resolution);
@@ -98,15 +166,6 @@ fn world_to_trail_coords(world_pos: vec2<f32>) -> vec2<f32> {
     return vec2<f32>(x, y);
 }
 
-// Convert from sRGB (gamma-corrected) to linear RGB
-fn srgb_to_linear(srgb: f32) -> f32 {
-    if (srgb <= 0.04045) {
-        return srgb / 12.92;
-    } else {
-        return pow((srgb + 0.055) / 1.055, 2.4);
-    }
-}
-
 // Get color from LUT
 fn get_lut_color(intensity: f32) -> vec3<f32> {
     let lut_index = clamp(intensity * 255.0, 0.0, 255.0);
@@ -293,7 +352,8 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             return;
         }
     } else {
-        // Brush particles (spawn_type == 1u)
+        // Brush particles (spawn_type == 1u), which emitters spawn from too
+        var brush_spawned = false;
         if (sim_params.mouse_button_down == 1u && particle.is_alive == 0u) {
             // Left click is held - probabilistic spawn so total matches brush_spawn_rate
             // Expected spawns this frame = rate * dt
@@ -309,18 +369,8 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             let seed = f32(particle_index) * 3.14159 + frame_idx;
             let randv = fract(sin(seed) * 43758.5453);
 
-            if (randv < p) {
-                // Claim a brush ticket to cap spawns this frame
-                let ticket = atomicAdd(&spawn_control.brush_count, 1u);
-                if (ticket >= spawn_control.brush_allowed) {
-                    // Quota exhausted: do not spawn
-                    particle.position = vec2<f32>(0.0, 0.0);
-                    particle.age = 0.0;
-                    particle.lut_index = 0u;
-                    particle.is_alive = 0u;
-                    particles[particle_index] = particle;
-                    return;
-                }
+            // Claim a brush ticket to cap spawns this frame
+            if (randv < p && atomicAdd(&spawn_control.brush_count, 1u) < spawn_control.brush_allowed) {
                 // Spawn at cursor with random offset (spray can effect)
                 let radius = sim_params.cursor_size;
                 let seed1 = f32(particle_index) * 0.1234 + sim_params.time * 0.1;
@@ -331,9 +381,17 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
                 let offset_y = sin(angle) * radius * distance;
                 spawn_x = sim_params.cursor_x + offset_x;
                 spawn_y = sim_params.cursor_y + offset_y;
+                particle.emitter = 0u;
+                brush_spawned = true;
                 should_reset = true; // Force spawn
-            } else {
-                // Don't spawn yet - keep particle dead
+            }
+        }
+
+        if (particle.is_alive == 0u && !brush_spawned) {
+            // Not spawned by the brush this frame - emitters may still claim it
+            let from_emitter = spawn_from_emitter(particle_index);
+            if (from_emitter.emitter == 0u) {
+                // Keep particle dead
                 particle.position = vec2<f32>(0.0, 0.0);
                 particle.age = 0.0;
                 particle.lut_index = 0u;
@@ -341,6 +399,10 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
                 particles[particle_index] = particle;
                 return;
             }
+            spawn_x = from_emitter.position.x;
+            spawn_y = from_emitter.position.y;
+            particle.emitter = from_emitter.emitter;
+            should_reset = true; // Force spawn
         } else if (particle.is_alive == 1u) {
             // Particle is alive - continue normally
             if (should_reset) {
@@ -356,14 +418,6 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
                 spawn_x = particle.position.x;
                 spawn_y = particle.position.y;
             }
-        } else {
-            // Particle is dead and left click is not held - keep dead
-            particle.position = vec2<f32>(0.0, 0.0);
-            particle.age = 0.0;
-            particle.lut_index = 0u;
-            particle.is_alive = 0u;
-            particles[particle_index] = particle;
-            return;
         }
     }
     
@@ -399,6 +453,20 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         let normalized_angle = (direction_angle + 3.14159) / (2.0 * 3.14159); // Normalize to [0, 1]
         particle.lut_index = u32(clamp(normalized_angle * 255.0, 0.0, 255.0));
     }
+
+    // Emitter particles may carry the emitter's color and launch velocity
+    var launch_velocity = vec2<f32>(0.0, 0.0);
+    particle.dyed = 0u;
+    if (particle.spawn_type == 1u && particle.emitter > 0u && particle.emitter <= min(emitters.count, MAX_EMITTERS)) {
+        let index = particle.emitter - 1u;
+        let dye = emitters.emitters[index].color;
+        if (dye >= 0.0) {
+            particle.lut_index = u32(clamp(dye * 255.0, 0.0, 255.0));
+            particle.dyed = 1u;
+        }
+        let age_ratio = clamp(particle.age / sim_params.particle_lifetime, 0.0, 1.0);
+        launch_velocity = emitters.emitters[index].velocity * (1.0 - age_ratio);
+    }
     
     // Apply cursor interaction if active (right click destroys particles)
     if (sim_params.mouse_button_down == 2u) {
@@ -420,7 +488,10 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
     
     // Move particle along flow direction using delta time
-    particle.position += direction * sim_params.particle_speed * sim_params.delta_time;
+    // Wind, gravity and the environment field drift particles across the flow
+    let drift = global_force_at(global_force, particle.position)
+        + environment_bias(particle.position);
+    particle.position += (direction * sim_params.particle_speed + launch_velocity + drift) * sim_params.delta_time;
     
     // Wrap around edges
     particle.position.x = fract(particle.position.x * 0.5 + 0.5) * 2.0 - 1.0;
@@ -429,7 +500,9 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     // Deposit trail at particle position with LUT color based on display mode
     var trail_color_intensity = 0.0;
     
-    if (sim_params.display_mode == 0u) { // Age mode
+    if (particle.dyed == 1u) { // Emitter color
+        trail_color_intensity = f32(particle.lut_index) / 255.0;
+    } else if (sim_params.display_mode == 0u) { // Age mode
         let age_ratio = particle.age / sim_params.particle_lifetime;
         trail_color_intensity = 1.0 - age_ratio; // Younger particles = higher intensity
     } else if (sim_params.display_mode == 1u) { // Random mode
@@ -1,3 +1,5 @@
+use super::baked_field::FlowField;
+use super::emitters::{self, Emitter, EmitterTickets, GpuEmitters};
 use super::settings::{
     BackgroundColorMode, ForegroundColorMode, NoiseType, Settings, VectorFieldType,
 };
@@ -8,10 +10,12 @@ use super::shaders::{
 };
 use crate::commands::AppSettings;
 use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::environment_field;
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use crate::simulations::shared::{
-    AverageColorResources, BindGroupBuilder, ColorSchemeManager, CommonBindGroupLayouts,
-    ComputePipelineBuilder, PostProcessingResources, PostProcessingState, ShaderManager,
+    AverageColorResources, BackgroundLayer, BindGroupBuilder, ColorSchemeManager,
+    CommonBindGroupLayouts, ComputePipelineBuilder, EnvironmentField, GlobalForceUniform,
+    ParticleBuffer, PostProcessingResources, PostProcessingState, RewindResource, ShaderManager,
 };
 use crate::simulations::traits::Simulation;
 use bytemuck::{Pod, Zeroable};
@@ -41,8 +45,8 @@ pub struct Particle {
     pub lut_index: u32,
     pub is_alive: u32,   // 0=dead, 1=alive
     pub spawn_type: u32, // 0=autospawn, 1=brush
-    pub _pad0: u32,      // Padding to keep 32-byte stride aligned with WGSL
-    pub _pad1: u32,
+    pub emitter: u32,    // Index + 1 of the emitter that spawned it, 0=none
+    pub dyed: u32,       // 1 when lut_index holds the emitter's color
 }
 
 #[repr(C)]
@@ -145,12 +149,15 @@ pub struct FlowModel {
     pub common_layouts: CommonBindGroupLayouts,
 
     // GPU resources
-    pub particle_buffer: wgpu::Buffer,
+    pub particle_buffer: ParticleBuffer<Particle>,
     pub flow_vector_buffer: wgpu::Buffer,
     pub sim_params_buffer: wgpu::Buffer,
     pub lut_buffer: wgpu::Buffer,
     pub background_color_buffer: wgpu::Buffer,
     pub spawn_control_buffer: wgpu::Buffer,
+    pub emitter_buffer: wgpu::Buffer,
+    pub global_force_buffer: wgpu::Buffer,
+    pub environment_field_buffer: wgpu::Buffer,
 
     // Trail system
     pub trail_texture: wgpu::Texture,
@@ -190,6 +197,7 @@ pub struct FlowModel {
     pub delta_time: f32,
     pub autospawn_accumulator: f32,
     pub brush_spawn_accumulator: f32,
+    pub emitter_tickets: EmitterTickets,
     pub noise_dt_multiplier: f32, // Multiplier for time when calculating noise position
     pub particles: Vec<Particle>,
     pub flow_vectors: Vec<FlowVector>,
@@ -279,22 +287,25 @@ impl FlowModel {
         // Update params with current time
         self.update_flow_vector_params(queue);
 
-        // Dispatch compute shader
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Flow Vector Compute Encoder"),
-        });
-
-        {
-            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("Flow Vector Compute Pass"),
-                timestamp_writes: None,
+        // Dispatch compute shader, unless a baked field was uploaded in place
+        // of the generated one
+        if self.settings.vector_field_type != VectorFieldType::Baked {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Flow Vector Compute Encoder"),
             });
-            compute_pass.set_pipeline(&self.flow_vector_compute_pipeline);
-            compute_pass.set_bind_group(0, &self.flow_vector_compute_bind_group, &[]);
-            compute_pass.dispatch_workgroups(128u32.div_ceil(16), 128u32.div_ceil(16), 1);
-        }
 
-        queue.submit(std::iter::once(encoder.finish()));
+            {
+                let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("Flow Vector Compute Pass"),
+                    timestamp_writes: None,
+                });
+                compute_pass.set_pipeline(&self.flow_vector_compute_pipeline);
+                compute_pass.set_bind_group(0, &self.flow_vector_compute_bind_group, &[]);
+                compute_pass.dispatch_workgroups(128u32.div_ceil(16), 128u32.div_ceil(16), 1);
+            }
+
+            queue.submit(std::iter::once(encoder.finish()));
+        }
 
         // Update sim params with new flow field resolution
         let sim_params = self.create_runtime_sim_params();
@@ -339,8 +350,8 @@ impl FlowModel {
                     lut_index: 0,                    // No color
                     is_alive: 0,                     // Dead particles are inactive
                     spawn_type: 0,                   // Autospawn particles
-                    _pad0: 0,
-                    _pad1: 0,
+                    emitter: 0,
+                    dyed: 0,
                 };
                 particles.push(particle);
             }
@@ -353,8 +364,8 @@ impl FlowModel {
                     lut_index: 0,                    // No color
                     is_alive: 0,                     // Dead particles are inactive
                     spawn_type: 1,                   // Brush particles
-                    _pad0: 0,
-                    _pad1: 0,
+                    emitter: 0,
+                    dyed: 0,
                 };
                 particles.push(particle);
             }
@@ -364,9 +375,10 @@ impl FlowModel {
         let flow_vectors = Vec::new();
 
         // Create GPU buffers
-        let particle_buffer = resource_helpers::create_storage_buffer_with_data(
+        let particle_buffer = ParticleBuffer::new(
             device,
             "Particle Buffer",
+            wgpu::BufferUsages::empty(),
             &particles,
         );
 
@@ -414,6 +426,26 @@ impl FlowModel {
             &[spawn_control_init],
         );
 
+        // Create emitter buffer, filled every frame from the settings
+        let emitter_buffer = resource_helpers::create_storage_buffer_with_data(
+            device,
+            "Emitter Buffer",
+            &[GpuEmitters::default()],
+        );
+
+        let global_force_buffer = resource_helpers::create_uniform_buffer_with_data(
+            device,
+            "Flow Global Force Buffer",
+            &[GlobalForceUniform::default()],
+        );
+        // Zeroed until a field is set, which reads as no strength
+        let environment_field_buffer = resource_helpers::create_storage_buffer(
+            device,
+            "Flow Environment Field Buffer",
+            environment_field::BUFFER_SIZE,
+            false,
+        );
+
         // Create background color buffer (will be updated based on background setting)
         let background_color_buffer = resource_helpers::create_uniform_buffer_with_data(
             device,
@@ -466,6 +498,9 @@ impl FlowModel {
                     ),
                     resource_helpers::storage_buffer_entry(4, wgpu::ShaderStages::COMPUTE, true),
                     resource_helpers::storage_buffer_entry(5, wgpu::ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(6, wgpu::ShaderStages::COMPUTE, false),
+                    resource_helpers::uniform_buffer_entry(7, wgpu::ShaderStages::COMPUTE),
+                    resource_helpers::storage_buffer_entry(8, wgpu::ShaderStages::COMPUTE, true),
                 ],
             });
 
@@ -476,12 +511,15 @@ impl FlowModel {
             .build();
 
         let particle_update_bind_group = BindGroupBuilder::new(device, &compute_bind_group_layout)
-            .add_buffer(0, &particle_buffer)
+            .add_buffer(0, particle_buffer.buffer())
             .add_buffer(1, &flow_vector_buffer)
             .add_buffer(2, &sim_params_buffer)
             .add_texture_view(3, &trail_texture_view)
             .add_buffer(4, &lut_buffer)
             .add_buffer(5, &spawn_control_buffer)
+            .add_buffer(6, &emitter_buffer)
+            .add_buffer(7, &global_force_buffer)
+            .add_buffer(8, &environment_field_buffer)
             .with_label("Particle Update Bind Group".to_string())
             .build();
 
@@ -627,7 +665,7 @@ impl FlowModel {
             label: Some("Particle Render Bind Group"),
             layout: &render_bind_group_layout,
             entries: &[
-                resource_helpers::buffer_entry(0, &particle_buffer),
+                resource_helpers::buffer_entry(0, particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &sim_params_buffer),
                 resource_helpers::buffer_entry(2, &lut_buffer),
             ],
@@ -1127,6 +1165,8 @@ impl FlowModel {
             vector_field_type: match settings.vector_field_type {
                 VectorFieldType::Noise => 0,
                 VectorFieldType::Image => 1,
+                // Not generated, the compute pass is skipped
+                VectorFieldType::Baked => 0,
             },
             noise_type: 0, // Will be set based on settings
             noise_scale: settings.noise_scale as f32,
@@ -1260,7 +1300,7 @@ impl FlowModel {
         // Use the same camera for both offscreen and infinite rendering
 
         // Create the FlowModel instance
-        let flow_model = Self {
+        let mut flow_model = Self {
             settings: settings.clone(),
             state: super::state::State {
                 time: 0.0,
@@ -1284,6 +1324,8 @@ impl FlowModel {
                 cursor_world_y: 0.0,
                 cursor_size: 0.33,
                 mouse_button_down: 0,
+                global_force: Default::default(),
+                environment_field: Default::default(),
                 flow_field_resolution: DEFAULT_FLOW_FIELD_RESOLUTION,
                 shape_drawing_enabled: false,
                 camera_position: [0.0, 0.0],
@@ -1299,6 +1341,9 @@ impl FlowModel {
             lut_buffer,
             background_color_buffer,
             spawn_control_buffer,
+            emitter_buffer,
+            global_force_buffer,
+            environment_field_buffer,
 
             trail_texture,
             trail_texture_view,
@@ -1317,6 +1362,7 @@ impl FlowModel {
             delta_time: 0.016,
             autospawn_accumulator: 0.0,
             brush_spawn_accumulator: 0.0,
+            emitter_tickets: EmitterTickets::default(),
             noise_dt_multiplier: settings.noise_dt_multiplier,
             particles,
             flow_vectors,
@@ -1386,6 +1432,7 @@ impl FlowModel {
 
         // Update background color buffer to reflect the default white background
         flow_model.update_background_color(queue);
+        flow_model.restore_baked_field(queue);
 
         Ok(flow_model)
     }
@@ -1561,6 +1608,8 @@ impl FlowModel {
             vector_field_type: match self.settings.vector_field_type {
                 VectorFieldType::Noise => 0,
                 VectorFieldType::Image => 1,
+                // Not generated, the compute pass is skipped
+                VectorFieldType::Baked => 0,
             },
             noise_type,
             noise_scale: self.settings.noise_scale as f32,
@@ -1805,6 +1854,23 @@ impl Simulation for FlowModel {
             bytemuck::cast_slice(&[spawn_control]),
         );
 
+        // Emitter tickets, also reset every frame
+        let gpu_emitters = self
+            .emitter_tickets
+            .next_frame(&self.settings.emitters, delta_time);
+        queue.write_buffer(
+            &self.emitter_buffer,
+            0,
+            bytemuck::cast_slice(&[gpu_emitters]),
+        );
+
+        self.state.global_force.advance(delta_time);
+        queue.write_buffer(
+            &self.global_force_buffer,
+            0,
+            bytemuck::cast_slice(&[self.state.global_force.uniform()]),
+        );
+
         // Run particle update compute pass
         let mut compute_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Flow Particle Update Encoder"),
@@ -2090,15 +2156,21 @@ impl Simulation for FlowModel {
                     ),
                     resource_helpers::storage_buffer_entry(4, wgpu::ShaderStages::COMPUTE, true),
                     resource_helpers::storage_buffer_entry(5, wgpu::ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(6, wgpu::ShaderStages::COMPUTE, false),
+                    resource_helpers::uniform_buffer_entry(7, wgpu::ShaderStages::COMPUTE),
+                    resource_helpers::storage_buffer_entry(8, wgpu::ShaderStages::COMPUTE, true),
                 ],
             }),
             entries: &[
-                resource_helpers::buffer_entry(0, &self.particle_buffer),
+                resource_helpers::buffer_entry(0, self.particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &self.flow_vector_buffer),
                 resource_helpers::buffer_entry(2, &self.sim_params_buffer),
                 resource_helpers::texture_view_entry(3, &self.trail_texture_view),
                 resource_helpers::buffer_entry(4, &self.lut_buffer),
                 resource_helpers::buffer_entry(5, &self.spawn_control_buffer),
+                resource_helpers::buffer_entry(6, &self.emitter_buffer),
+                resource_helpers::buffer_entry(7, &self.global_force_buffer),
+                resource_helpers::buffer_entry(8, &self.environment_field_buffer),
             ],
         });
 
@@ -2609,6 +2681,14 @@ impl Simulation for FlowModel {
         serde_json::to_value(&self.settings).unwrap_or_default()
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
     fn get_state(&self) -> serde_json::Value {
         serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
     }
@@ -2682,6 +2762,10 @@ impl Simulation for FlowModel {
         })
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        vec![self.particle_buffer.rewind_resource()]
+    }
+
     fn save_preset(&self, _preset_name: &str) -> crate::error::SimulationResult<()> {
         Ok(())
     }
@@ -2706,6 +2790,7 @@ impl Simulation for FlowModel {
             // Update GPU buffers after applying new settings
             self.update_background_color(queue);
             self.write_sim_params(queue);
+            self.restore_baked_field(queue);
         }
         Ok(())
     }
@@ -2729,8 +2814,8 @@ impl Simulation for FlowModel {
                     lut_index: 0,                         // No color
                     is_alive: 0,                          // Dead particles are inactive
                     spawn_type: 0,                        // Autospawn particles
-                    _pad0: 0,
-                    _pad1: 0,
+                    emitter: 0,
+                    dyed: 0,
                 };
                 particles.push(particle);
             }
@@ -2743,14 +2828,18 @@ impl Simulation for FlowModel {
                     lut_index: 0,                         // No color
                     is_alive: 0,                          // Dead particles are inactive
                     spawn_type: 1,                        // Brush particles
-                    _pad0: 0,
-                    _pad1: 0,
+                    emitter: 0,
+                    dyed: 0,
                 };
                 particles.push(particle);
             }
         });
 
-        queue.write_buffer(&self.particle_buffer, 0, bytemuck::cast_slice(&particles));
+        queue.write_buffer(
+            self.particle_buffer.buffer(),
+            0,
+            bytemuck::cast_slice(&particles),
+        );
         self.particles = particles;
 
         // Reset trail map - clear texture with zeros
@@ -2951,7 +3040,7 @@ impl FlowModel {
 
         // Update particle buffer with dead particles
         queue.write_buffer(
-            &self.particle_buffer,
+            self.particle_buffer.buffer(),
             0,
             bytemuck::cast_slice(&self.particles),
         );
@@ -2970,6 +3059,112 @@ impl FlowModel {
         Ok(())
     }
 
+    pub fn add_emitter(&mut self, emitter: Emitter) -> crate::error::SimulationResult<()> {
+        emitters::add_emitter(&mut self.settings.emitters, emitter)
+    }
+
+    pub fn update_emitter(
+        &mut self,
+        name: &str,
+        emitter: Emitter,
+    ) -> crate::error::SimulationResult<()> {
+        emitters::update_emitter(&mut self.settings.emitters, name, emitter)
+    }
+
+    pub fn remove_emitter(&mut self, name: &str) -> crate::error::SimulationResult<()> {
+        emitters::remove_emitter(&mut self.settings.emitters, name)?;
+        // Accumulators are per index, which just shifted
+        self.emitter_tickets.reset();
+        Ok(())
+    }
+
+    /// Read the current flow vectors back from the GPU
+    pub fn bake_flow_field(
+        &self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> crate::error::SimulationResult<FlowField> {
+        let size = self.flow_vector_buffer.size();
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Flow Vector Staging Buffer"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Flow Field Bake Encoder"),
+        });
+        encoder.copy_buffer_to_buffer(&self.flow_vector_buffer, 0, &staging_buffer, 0, size);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = staging_buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+        device
+            .poll(wgpu::wgt::PollType::Wait)
+            .map_err(|e| crate::error::SimulationError::Gpu(Box::new(e)))?;
+        receiver
+            .recv()
+            .map_err(|e| crate::error::SimulationError::Gpu(Box::new(e)))?
+            .map_err(|e| crate::error::SimulationError::Gpu(Box::new(e)))?;
+
+        let flow_vectors: Vec<FlowVector> = {
+            let data = buffer_slice.get_mapped_range();
+            bytemuck::cast_slice(&data).to_vec()
+        };
+        staging_buffer.unmap();
+
+        FlowField::from_flow_vectors(DEFAULT_FLOW_FIELD_RESOLUTION, &flow_vectors)
+    }
+
+    /// Rasterize `field` and upload it for the particle update
+    pub fn set_environment_field(
+        &mut self,
+        queue: &Arc<Queue>,
+        field: EnvironmentField,
+    ) -> crate::error::SimulationResult<()> {
+        queue.write_buffer(&self.environment_field_buffer, 0, &field.to_gpu_bytes()?);
+        self.state.environment_field = field;
+        Ok(())
+    }
+
+    /// Replace the generated flow vectors with the baked field called `name`
+    pub fn load_baked_field(
+        &mut self,
+        queue: &Arc<Queue>,
+        name: &str,
+    ) -> crate::error::SimulationResult<()> {
+        let field = FlowField::load(name)?;
+        let flow_vectors = field.to_flow_vectors(DEFAULT_FLOW_FIELD_RESOLUTION);
+        queue.write_buffer(
+            &self.flow_vector_buffer,
+            0,
+            bytemuck::cast_slice(&flow_vectors),
+        );
+        self.settings.vector_field_type = VectorFieldType::Baked;
+        self.settings.baked_field = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Upload the baked field the settings ask for. A preset can outlive the
+    /// field file it names, so a missing field falls back to noise.
+    fn restore_baked_field(&mut self, queue: &Arc<Queue>) {
+        if self.settings.vector_field_type != VectorFieldType::Baked {
+            return;
+        }
+        let result = match self.settings.baked_field.clone() {
+            Some(name) => self.load_baked_field(queue, &name),
+            None => Err(crate::error::SimulationError::InvalidParameter(
+                "No baked flow field selected".to_string(),
+            )),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Falling back to a noise flow field: {}", e);
+            self.settings.vector_field_type = VectorFieldType::Noise;
+        }
+    }
+
     fn update_trail_sampler(&mut self, device: &Arc<Device>) {
         self.trail_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("Trail Sampler"),
@@ -1,4 +1,5 @@
 use super::settings::{BackgroundColorMode, ForegroundColorMode, TrailMapFiltering};
+use crate::simulations::shared::{EnvironmentField, GlobalForce};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +33,11 @@ pub struct State {
     pub cursor_size: f32,
     pub mouse_button_down: u32, // 0 = not held, 1 = left click held, 2 = right click held
 
+    // Wind and gravity, a drift on top of the flow
+    pub global_force: GlobalForce,
+    // Bias field particles drift along
+    pub environment_field: EnvironmentField,
+
     // Flow vector generation
     pub flow_field_resolution: u32,
 
@@ -71,6 +77,8 @@ impl Default for State {
             cursor_world_y: 0.0,
             cursor_size: 100.0,
             mouse_button_down: 0,
+            global_force: GlobalForce::default(),
+            environment_field: EnvironmentField::default(),
             flow_field_resolution: 128,
             shape_drawing_enabled: false,
             camera_position: [0.0, 0.0],
@@ -359,6 +359,26 @@ impl FlowFieldValidator {
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
+
+        let emitter_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Emitter Buffer"),
+            size: std::mem::size_of::<super::emitters::GpuEmitters>() as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let global_force_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Global Force Buffer"),
+            size: std::mem::size_of::<crate::simulations::shared::GlobalForceUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let environment_field_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Environment Field Buffer"),
+            size: crate::simulations::shared::environment_field::BUFFER_SIZE,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
         let _bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Test Compute Bind Group"),
             layout: &pipeline.get_bind_group_layout(0),
@@ -369,6 +389,9 @@ impl FlowFieldValidator {
                 resource_helpers::texture_view_entry(3, &trail_texture_view),
                 resource_helpers::buffer_entry(4, &lut_buffer),
                 resource_helpers::buffer_entry(5, &spawn_control_buffer),
+                resource_helpers::buffer_entry(6, &emitter_buffer),
+                resource_helpers::buffer_entry(7, &global_force_buffer),
+                resource_helpers::buffer_entry(8, &environment_field_buffer),
             ],
         });
 
@@ -31,21 +31,11 @@ struct GradientParams {
 
 // Color space interpolation functions
 fn rgb_to_linear(rgb: f32) -> f32 {
-    let normalized = rgb / 255.0;
-    return select(
-        normalized / 12.92,
-        pow((normalized + 0.055) / 1.055, 2.4),
-        normalized > 0.04045
-    );
+    return srgb_to_linear(rgb / 255.0);
 }
 
 fn linear_to_rgb(linear: f32) -> f32 {
-    let result = select(
-        linear * 12.92,
-        1.055 * pow(linear, 1.0 / 2.4) - 0.055,
-        linear > 0.0031308
-    );
-    return clamp(result * 255.0, 0.0, 255.0);
+    return clamp(linear_to_srgb(linear) * 255.0, 0.0, 255.0);
 }
 
 fn linear_rgb_to_xyz(r: f32, g: f32, b: f32) -> vec3<f32> {
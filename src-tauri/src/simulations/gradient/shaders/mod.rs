@@ -1,2 +1,5 @@
 // Shader module for gradient simulation
-pub const GRADIENT_SHADER: &str = include_str!("gradient.wgsl");
+pub const GRADIENT_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("gradient.wgsl")
+);
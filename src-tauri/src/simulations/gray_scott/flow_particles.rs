@@ -0,0 +1,98 @@
+//! Particles streaming over the pattern along the slope of V, each colored
+//! by the V it's passing over. They live in the plane's own -1 to 1 square
+//! and wrap round its edges as the chemicals do, so they're drawn again in
+//! every tile of the plane the camera can see.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SimulationError, SimulationResult};
+
+pub const MAX_PARTICLES: u32 = 200_000;
+
+/// Tiles drawn across and down at most; zoomed further out the particles
+/// are too small to see anyway
+pub const MAX_TILES_ACROSS: u32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlowParticles {
+    pub enabled: bool,
+    pub count: u32,
+    /// World units per second where V is steepest; on flat ground they
+    /// barely move
+    pub speed: f32,
+    /// Degrees the flow is turned from straight up the slope: 0 climbs
+    /// toward high V, 90 circles along its contours, 180 runs downhill
+    pub swirl: f32,
+    /// Seconds a particle streams, on average, before starting afresh
+    /// somewhere else
+    pub lifetime: f32,
+    /// Pixels across each particle
+    pub size: f32,
+    /// How much each particle adds to the color beneath it
+    pub brightness: f32,
+}
+
+impl Default for FlowParticles {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            count: 20_000,
+            speed: 0.15,
+            swirl: 75.0,
+            lifetime: 5.0,
+            size: 2.0,
+            brightness: 0.5,
+        }
+    }
+}
+
+impl FlowParticles {
+    pub fn validate(&self) -> SimulationResult<()> {
+        let invalid = |message: &str| Err(SimulationError::InvalidParameter(message.to_string()));
+        if !(1..=MAX_PARTICLES).contains(&self.count) {
+            return invalid("Flow particle count must be between 1 and 200000");
+        }
+        if !(0.0..=2.0).contains(&self.speed) {
+            return invalid("Flow particle speed must be between 0 and 2");
+        }
+        if !(-180.0..=180.0).contains(&self.swirl) {
+            return invalid("Flow particle swirl must be between -180 and 180 degrees");
+        }
+        if !(0.1..=60.0).contains(&self.lifetime) {
+            return invalid("Flow particle lifetime must be between 0.1 and 60 seconds");
+        }
+        if !(0.5..=16.0).contains(&self.size) {
+            return invalid("Flow particle size must be between 0.5 and 16 pixels");
+        }
+        if !(0.0..=4.0).contains(&self.brightness) {
+            return invalid("Flow particle brightness must be between 0 and 4");
+        }
+        Ok(())
+    }
+}
+
+/// The first tile the camera sees, counting tiles two world units wide
+/// from the one centered on the origin, and how many it sees across and
+/// down. The camera shows `1 / zoom` world units either side of `center`.
+pub fn visible_tiles(center: [f32; 2], zoom: f32) -> ([i32; 2], [u32; 2]) {
+    let reach = 1.0 / zoom.max(1e-3);
+    let mut start = [0; 2];
+    let mut count = [0; 2];
+    for axis in 0..2 {
+        // Tile n covers 2n - 1 to 2n + 1
+        let first = ((center[axis] - reach + 1.0) / 2.0).floor();
+        let last = ((center[axis] + reach + 1.0) / 2.0).floor();
+        let across = (last - first) as u32 + 1;
+        if across > MAX_TILES_ACROSS {
+            // Too many to draw: keep those nearest the middle of the view
+            let middle = ((center[axis] + 1.0) / 2.0).floor() as i32;
+            start[axis] = middle - (MAX_TILES_ACROSS / 2) as i32;
+            count[axis] = MAX_TILES_ACROSS;
+        } else {
+            start[axis] = first as i32;
+            count[axis] = across;
+        }
+    }
+    (start, count)
+}
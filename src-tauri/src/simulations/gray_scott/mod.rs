@@ -1,8 +1,12 @@
+pub mod flow_particles;
 pub mod settings;
 pub mod shaders;
 pub mod simulation;
 pub mod state;
+pub mod surface;
 
+#[cfg(test)]
+mod reference;
 #[cfg(test)]
 mod tests;
 
@@ -12,35 +16,66 @@ use crate::simulation::preset_manager::{GrayScottPresetManager, Preset};
 
 /// Initialize Gray-Scott presets with built-in configurations
 pub fn init_presets(preset_manager: &mut GrayScottPresetManager) {
+    use flow_particles::FlowParticles;
     use settings::Settings;
+    use surface::Surface;
     // Add default presets
     let all_presets = [
-        ("Brain Coral", (0.0545, 0.062)),
-        ("Fingerprint", (0.0545, 0.062)),
-        ("Mitosis", (0.0367, 0.0649)),
-        ("Ripples", (0.018, 0.051)),
-        ("Soliton Collapse", (0.022, 0.06)),
-        ("U-Skate World", (0.062, 0.061)),
-        ("Undulating", (0.026, 0.051)),
-        ("Worms", (0.078, 0.061)),
-        ("Custom", (0.035, 0.058)),
+        ("Brain Coral", Surface::Plane, (0.0545, 0.062)),
+        ("Fingerprint", Surface::Plane, (0.0545, 0.062)),
+        ("Mitosis", Surface::Plane, (0.0367, 0.0649)),
+        ("Ripples", Surface::Plane, (0.018, 0.051)),
+        ("Soliton Collapse", Surface::Plane, (0.022, 0.06)),
+        ("U-Skate World", Surface::Plane, (0.062, 0.061)),
+        ("Undulating", Surface::Plane, (0.026, 0.051)),
+        ("Worms", Surface::Plane, (0.078, 0.061)),
+        ("Custom", Surface::Plane, (0.035, 0.058)),
+        ("Coral Planet", Surface::Sphere, (0.0545, 0.062)),
+        ("Mitosis Torus", Surface::Torus, (0.0367, 0.0649)),
     ];
 
-    for (preset_name, (feed_rate, kill_rate)) in all_presets {
+    let classic = |surface, (feed_rate, kill_rate)| Settings {
+        feed_rate,
+        kill_rate,
+        // Use canonical Gray-Scott diffusion coefficients for classic behavior
+        diffusion_rate_u: 0.16,
+        diffusion_rate_v: 0.08,
+        timestep: 1.0,
+
+        // Optimization defaults
+        max_timestep: 2.0,
+        stability_factor: 0.8,
+        enable_adaptive_timestep: false,
+
+        grid_resolution: Default::default(),
+        lut_blend: Default::default(),
+        background_layer: Default::default(),
+        surface,
+        ..Settings::default()
+    };
+
+    for (preset_name, surface, rates) in all_presets {
+        preset_manager.add_preset(Preset::new(
+            preset_name.to_string(),
+            classic(surface, rates),
+        ));
+    }
+
+    // Patterns with flow particles streaming over them
+    let flowing = [
+        ("Mitosis Currents", (0.0367, 0.0649), 40_000, 90.0),
+        ("Worm Streams", (0.078, 0.061), 30_000, 20.0),
+    ];
+    for (preset_name, rates, count, swirl) in flowing {
         let settings = Settings {
-            feed_rate,
-            kill_rate,
-            // Use canonical Gray-Scott diffusion coefficients for classic behavior
-            diffusion_rate_u: 0.16,
-            diffusion_rate_v: 0.08,
-            timestep: 1.0,
-
-            // Optimization defaults
-            max_timestep: 2.0,
-            stability_factor: 0.8,
-            enable_adaptive_timestep: false,
+            flow_particles: FlowParticles {
+                enabled: true,
+                count,
+                swirl,
+                ..FlowParticles::default()
+            },
+            ..classic(Surface::Plane, rates)
         };
-
         preset_manager.add_preset(Preset::new(preset_name.to_string(), settings));
     }
 
@@ -49,7 +84,7 @@ pub fn init_presets(preset_manager: &mut GrayScottPresetManager) {
 
     // Load user presets from TOML files
     if let Err(e) = preset_manager.load_user_presets() {
-        eprintln!("Warning: Could not load user presets: {}", e);
+        tracing::warn!("Could not load user presets: {}", e);
     }
 
     let preset_count = preset_manager.get_preset_names().len();
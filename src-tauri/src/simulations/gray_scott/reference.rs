@@ -0,0 +1,101 @@
+//! A CPU copy of one step of `reaction_diffusion.wgsl`, for the tests to
+//! hold the shader to. It covers every surface and edge condition but not
+//! the mask, so `mask_target` must be 0.
+
+use super::simulation::SimulationParams;
+use super::surface::{sphere_neighbor, torus_stencil};
+use crate::simulations::shared::boundary;
+
+/// U and V past an absorbing edge
+const AMBIENT: [f32; 2] = [1.0, 0.0];
+
+/// `uvs`, U and V of every cell row by row, one step on. `walls` is the
+/// boundary mask, read only when `params.boundaries` turns it on.
+pub fn step(uvs: &[[f32; 2]], params: &SimulationParams, walls: Option<&[u32]>) -> Vec<[f32; 2]> {
+    assert_eq!(params.mask_target, 0, "The reference step has no mask");
+    let (width, height) = (params.width, params.height);
+    let timestep = if params.enable_adaptive_timestep != 0 {
+        adaptive_timestep(params)
+    } else {
+        params.timestep
+    };
+
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let here = uvs[(y * width + x) as usize];
+            let cell = [x as i32, y as i32];
+            if params.surface == 0 && boundary::walled(params.boundaries, cell, width, walls) {
+                return here;
+            }
+            let laplacian = laplacian(uvs, params, walls, x, y);
+            let reaction = here[0] * here[1] * here[1];
+            let delta_u =
+                params.delta_u * laplacian[0] - reaction + params.feed_rate * (1.0 - here[0]);
+            let delta_v = params.delta_v * laplacian[1] + reaction
+                - (params.kill_rate + params.feed_rate) * here[1];
+            [
+                (here[0] + delta_u * timestep).clamp(0.0, 1.0),
+                (here[1] + delta_v * timestep).clamp(0.0, 1.0),
+            ]
+        })
+        .collect()
+}
+
+fn adaptive_timestep(params: &SimulationParams) -> f32 {
+    let diffusion_limit = 0.25 / (params.delta_u + params.delta_v);
+    let reaction_limit = 1.0 / (1.0 + params.feed_rate + params.kill_rate);
+    diffusion_limit.min(reaction_limit) * params.stability_factor
+}
+
+fn laplacian(
+    uvs: &[[f32; 2]],
+    params: &SimulationParams,
+    walls: Option<&[u32]>,
+    x: u32,
+    y: u32,
+) -> [f32; 2] {
+    let (width, height) = (params.width, params.height);
+    let at = |(x, y): (u32, u32)| uvs[(y * width + x) as usize];
+    let here = at((x, y));
+    let offsets = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+    match params.surface {
+        1 => {
+            let face_size = width / 3;
+            let sum = offsets
+                .iter()
+                .map(|&(dx, dy)| at(sphere_neighbor(x, y, dx, dy, face_size)))
+                .fold([0.0; 2], |sum, uv| [sum[0] + uv[0], sum[1] + uv[1]]);
+            [sum[0] - 4.0 * here[0], sum[1] - 4.0 * here[1]]
+        }
+        2 => {
+            let stencil = torus_stencil(y, width, height, params.tube_ratio);
+            let left = at(((x + width - 1) % width, y));
+            let right = at(((x + 1) % width, y));
+            let up = at((x, (y + height - 1) % height));
+            let down = at((x, (y + 1) % height));
+            let channel = |c: usize| {
+                stencil.along_ring * (left[c] + right[c] - 2.0 * here[c])
+                    + stencil.previous_row * (up[c] - here[c])
+                    + stencil.next_row * (down[c] - here[c])
+            };
+            [channel(0), channel(1)]
+        }
+        _ => {
+            let center = [x as i32, y as i32];
+            let sum = offsets
+                .iter()
+                .map(|&(dx, dy)| {
+                    let cell = [center[0] + dx, center[1] + dy];
+                    match boundary::resolve(params.boundaries, cell, center, (width, height), walls)
+                    {
+                        Some([nx, ny]) => at((nx as u32, ny as u32)),
+                        None => AMBIENT,
+                    }
+                })
+                .fold([0.0; 2], |sum, uv| [sum[0] + uv[0], sum[1] + uv[1]]);
+            [sum[0] - 4.0 * here[0], sum[1] - 4.0 * here[1]]
+        }
+    }
+}
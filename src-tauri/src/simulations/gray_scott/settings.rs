@@ -1,3 +1,9 @@
+use super::flow_particles::FlowParticles;
+use super::surface::Surface;
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use crate::simulations::shared::{BackgroundLayer, BoundaryConditions, GridResolution, LutBlend};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +18,44 @@ pub struct Settings {
     pub max_timestep: f32,
     pub stability_factor: f32,
     pub enable_adaptive_timestep: bool,
+
+    // Simulation grid size, the window's unless fixed
+    #[serde(default)]
+    pub grid_resolution: GridResolution,
+
+    // Second color scheme, blended in by V
+    #[serde(default)]
+    pub lut_blend: LutBlend,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+
+    // What the grid is wrapped around; spheres and tori are drawn in 3D
+    #[serde(default)]
+    pub surface: Surface,
+    // Torus tube radius over ring radius
+    #[serde(default = "default_tube_ratio")]
+    pub tube_ratio: f32,
+    // Radians per second the 3D view circles the surface
+    #[serde(default = "default_spin_speed")]
+    pub spin_speed: f32,
+
+    // Particles streaming along the slope of V over the plane
+    #[serde(default)]
+    pub flow_particles: FlowParticles,
+
+    // What the plane's edges do; spheres and tori have none
+    #[serde(default)]
+    pub boundaries: BoundaryConditions,
+}
+
+fn default_tube_ratio() -> f32 {
+    0.4
+}
+
+fn default_spin_speed() -> f32 {
+    0.1
 }
 
 impl Default for Settings {
@@ -27,10 +71,21 @@ impl Default for Settings {
             max_timestep: 4.0,
             stability_factor: 0.9,
             enable_adaptive_timestep: false,
+
+            grid_resolution: GridResolution::Window,
+            lut_blend: LutBlend::default(),
+            background_layer: BackgroundLayer::default(),
+            surface: Surface::default(),
+            tube_ratio: default_tube_ratio(),
+            spin_speed: default_spin_speed(),
+            flow_particles: FlowParticles::default(),
+            boundaries: BoundaryConditions::default(),
         }
     }
 }
 
+impl PresetSchema for Settings {}
+
 impl Settings {
     /// Randomize all settings within reasonable bounds
     pub fn randomize(&mut self) {
@@ -44,3 +99,32 @@ impl Settings {
         self.timestep = rng.random_range(0.5..2.0);
     }
 }
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("feed_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        ("kill_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "timestep",
+            Rule::Range {
+                min: 0.1,
+                max: 10.0,
+            },
+        ),
+        ("surface", Rule::OneOf(&["Plane", "Sphere", "Torus"])),
+        ("tube_ratio", Rule::Range { min: 0.1, max: 0.9 }),
+        (
+            "spin_speed",
+            Rule::Range {
+                min: -2.0,
+                max: 2.0,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories =
+    SettingCategories::new(&[("lut_blend", SettingCategory::Colors)]);
@@ -0,0 +1,288 @@
+use crate::simulations::gray_scott::flow_particles::{FlowParticles, visible_tiles};
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{Device, Queue};
+
+pub const FLOW_PARTICLES_COMPUTE_SHADER: &str = include_str!("flow_particles_compute.wgsl");
+pub const FLOW_PARTICLES_RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("flow_particles_render.wgsl")
+);
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+pub struct FlowParams {
+    pub width: u32,
+    pub height: u32,
+    pub count: u32,
+    pub frame: u32,
+    pub delta_time: f32,
+    pub speed: f32,
+    pub swirl: f32,
+    pub lifetime: f32,
+    pub size: f32,
+    pub brightness: f32,
+    pub viewport_width: f32,
+    pub viewport_height: f32,
+    pub tile_start_x: i32,
+    pub tile_start_y: i32,
+    pub tile_columns: u32,
+    pub _pad0: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+pub struct FlowParticle {
+    pub position: [f32; 2],
+    pub age: f32,
+    pub value: f32,
+}
+
+/// Flow particles streaming over the plane: a compute pass moving them
+/// along the slope of V and a render pass adding them over the pattern
+#[derive(Debug)]
+pub struct FlowLayer {
+    compute_pipeline: wgpu::ComputePipeline,
+    render_pipeline: wgpu::RenderPipeline,
+    compute_bind_group_layout: wgpu::BindGroupLayout,
+    render_bind_group_layout: wgpu::BindGroupLayout,
+    params_buffer: wgpu::Buffer,
+    particle_buffer: wgpu::Buffer,
+    count: u32,
+    frame: u32,
+    // Tiles drawn this frame, as worked out by the last params upload
+    tile_count: u32,
+}
+
+impl FlowLayer {
+    pub fn new(
+        device: &Arc<Device>,
+        format: wgpu::TextureFormat,
+        camera_bind_group_layout: &wgpu::BindGroupLayout,
+        count: u32,
+    ) -> Self {
+        let compute_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Gray-Scott Flow Particles Compute Shader"),
+            source: wgpu::ShaderSource::Wgsl(FLOW_PARTICLES_COMPUTE_SHADER.into()),
+        });
+        let render_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Gray-Scott Flow Particles Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(FLOW_PARTICLES_RENDER_SHADER.into()),
+        });
+
+        let compute_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Flow Particles Compute Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, wgpu::ShaderStages::COMPUTE),
+                    resource_helpers::storage_buffer_entry(1, wgpu::ShaderStages::COMPUTE, false),
+                    resource_helpers::texture_entry(
+                        2,
+                        wgpu::ShaderStages::COMPUTE,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                ],
+            });
+        let render_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Flow Particles Render Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, wgpu::ShaderStages::VERTEX),
+                    resource_helpers::storage_buffer_entry(1, wgpu::ShaderStages::VERTEX, true),
+                    resource_helpers::storage_buffer_entry(2, wgpu::ShaderStages::VERTEX, true),
+                ],
+            });
+
+        let compute_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Flow Particles Compute Pipeline Layout"),
+                bind_group_layouts: &[&compute_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Flow Particles Compute Pipeline"),
+            layout: Some(&compute_pipeline_layout),
+            module: &compute_shader,
+            entry_point: Some("main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Flow Particles Render Pipeline Layout"),
+                bind_group_layouts: &[&render_bind_group_layout, camera_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        // Particles add light to the pattern beneath them
+        let additive = wgpu::BlendState {
+            color: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::Zero,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+        };
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Flow Particles Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &render_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(additive),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Flow Particles Params Buffer"),
+            size: std::mem::size_of::<FlowParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            compute_pipeline,
+            render_pipeline,
+            compute_bind_group_layout,
+            render_bind_group_layout,
+            params_buffer,
+            particle_buffer: create_particle_buffer(device, count),
+            count,
+            frame: 0,
+            tile_count: 1,
+        }
+    }
+
+    /// Scatter a fresh set of `count` particles, if that's a different
+    /// number from now
+    pub fn set_count(&mut self, device: &Arc<Device>, count: u32) {
+        if count != self.count {
+            self.count = count;
+            self.particle_buffer = create_particle_buffer(device, count);
+        }
+    }
+
+    /// Upload what both passes need this frame
+    pub fn write_params(
+        &mut self,
+        queue: &Arc<Queue>,
+        settings: &FlowParticles,
+        camera: &Camera,
+        grid: (u32, u32),
+        delta_time: f32,
+    ) {
+        let (tile_start, tiles) = visible_tiles(camera.position, camera.zoom);
+        self.tile_count = tiles[0] * tiles[1];
+        self.frame = self.frame.wrapping_add(1);
+        let params = FlowParams {
+            width: grid.0,
+            height: grid.1,
+            count: self.count,
+            frame: self.frame,
+            delta_time,
+            speed: settings.speed,
+            swirl: settings.swirl.to_radians(),
+            lifetime: settings.lifetime,
+            size: settings.size,
+            brightness: settings.brightness,
+            viewport_width: camera.viewport_width.max(1.0),
+            viewport_height: camera.viewport_height.max(1.0),
+            tile_start_x: tile_start[0],
+            tile_start_y: tile_start[1],
+            tile_columns: tiles[0],
+            _pad0: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+    }
+
+    /// Move the particles over `field_view`, the current U and V
+    pub fn step(
+        &self,
+        device: &Arc<Device>,
+        encoder: &mut wgpu::CommandEncoder,
+        field_view: &wgpu::TextureView,
+    ) {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Flow Particles Compute Bind Group"),
+            layout: &self.compute_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &self.params_buffer),
+                resource_helpers::buffer_entry(1, &self.particle_buffer),
+                resource_helpers::texture_view_entry(2, field_view),
+            ],
+        });
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Gray-Scott Flow Particles Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.compute_pipeline);
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.dispatch_workgroups(self.count.div_ceil(64), 1, 1);
+    }
+
+    /// Add the particles over what `render_pass` has drawn so far
+    pub fn draw(
+        &self,
+        device: &Arc<Device>,
+        render_pass: &mut wgpu::RenderPass,
+        lut_buffer: &wgpu::Buffer,
+        camera_bind_group: &wgpu::BindGroup,
+    ) {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Flow Particles Render Bind Group"),
+            layout: &self.render_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &self.params_buffer),
+                resource_helpers::buffer_entry(1, &self.particle_buffer),
+                resource_helpers::buffer_entry(2, lut_buffer),
+            ],
+        });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_bind_group(1, camera_bind_group, &[]);
+        render_pass.draw(0..6, 0..self.count * self.tile_count);
+    }
+}
+
+/// `count` particles scattered over the plane, part way through their
+/// lives so they don't all start afresh at once
+fn create_particle_buffer(device: &Device, count: u32) -> wgpu::Buffer {
+    let mut rng = rand::rng();
+    let particles: Vec<FlowParticle> = (0..count.max(1))
+        .map(|_| FlowParticle {
+            position: [rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0)],
+            age: rng.random(),
+            value: 0.0,
+        })
+        .collect();
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Flow Particles Buffer"),
+        contents: bytemuck::cast_slice(&particles),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+    })
+}
@@ -0,0 +1,98 @@
+// Moves the flow particles along the slope of V, turned by the swirl, and
+// notes the V each one lands on. Particles start afresh somewhere random
+// when their time is up, so they don't all pile up on the peaks.
+
+struct FlowParams {
+    width: u32,
+    height: u32,
+    count: u32,
+    frame: u32,
+    delta_time: f32,
+    speed: f32,
+    // Radians, counterclockwise from straight up the slope
+    swirl: f32,
+    lifetime: f32,
+    size: f32,
+    brightness: f32,
+    viewport_width: f32,
+    viewport_height: f32,
+    tile_start_x: i32,
+    tile_start_y: i32,
+    tile_columns: u32,
+    _pad0: u32,
+}
+
+struct Particle {
+    position: vec2<f32>,
+    // Share of its life gone, 0 to 1
+    age: f32,
+    value: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: FlowParams;
+@group(0) @binding(1) var<storage, read_write> particles: array<Particle>;
+@group(0) @binding(2) var field_texture: texture_2d<f32>;
+
+// Slope, per world unit, at which a particle moves at nearly full speed;
+// shallower than this it slows to a crawl
+const SLOPE_SOFTENING: f32 = 2.0;
+
+fn hash(seed: u32) -> u32 {
+    var x = seed;
+    x = ((x >> 16u) ^ x) * 0x45d9f3bu;
+    x = ((x >> 16u) ^ x) * 0x45d9f3bu;
+    x = (x >> 16u) ^ x;
+    return x;
+}
+
+fn random_float(seed: u32) -> f32 {
+    return f32(hash(seed)) / f32(0xffffffffu);
+}
+
+// V at a texel, the plane wrapping round at its edges
+fn field(texel: vec2<i32>) -> f32 {
+    let size = vec2<i32>(i32(params.width), i32(params.height));
+    let wrapped = ((texel % size) + size) % size;
+    return textureLoad(field_texture, wrapped, 0).y;
+}
+
+// Texture rows run from the top of the plane down
+fn texel_at(position: vec2<f32>) -> vec2<i32> {
+    let size = vec2<f32>(f32(params.width), f32(params.height));
+    return vec2<i32>(floor(vec2<f32>(position.x + 1.0, 1.0 - position.y) * 0.5 * size));
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let index = global_id.x;
+    if (index >= params.count) {
+        return;
+    }
+    var particle = particles[index];
+
+    // Each lives a little longer or shorter than the rest, so they don't
+    // all start afresh together
+    let life = params.lifetime * (0.5 + random_float(index * 0x9e3779b9u));
+    particle.age += params.delta_time / life;
+    if (particle.age >= 1.0) {
+        let seed = hash(index ^ hash(params.frame));
+        particle.position = vec2<f32>(random_float(seed), random_float(seed + 1u)) * 2.0 - 1.0;
+        particle.age = fract(particle.age);
+    }
+
+    let texel = texel_at(particle.position);
+    let slope_per_texel = vec2<f32>(
+        field(texel + vec2<i32>(1, 0)) - field(texel - vec2<i32>(1, 0)),
+        field(texel - vec2<i32>(0, 1)) - field(texel + vec2<i32>(0, 1))
+    ) * 0.5;
+    // A texel is 2 / width of the plane across and 2 / height down
+    let slope = slope_per_texel * vec2<f32>(f32(params.width), f32(params.height)) * 0.5;
+    let turn = vec2<f32>(cos(params.swirl), sin(params.swirl));
+    let turned = vec2<f32>(slope.x * turn.x - slope.y * turn.y, slope.x * turn.y + slope.y * turn.x);
+    let velocity = turned / (length(slope) + SLOPE_SOFTENING) * params.speed;
+
+    let moved = particle.position + velocity * params.delta_time;
+    particle.position = fract((moved + 1.0) * 0.5) * 2.0 - 1.0;
+    particle.value = field(texel_at(particle.position));
+    particles[index] = particle;
+}
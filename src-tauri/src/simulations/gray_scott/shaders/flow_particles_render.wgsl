@@ -0,0 +1,104 @@
+// Draws each flow particle as a soft dot in every tile of the plane the
+// camera sees, colored by the V beneath it and added over the pattern,
+// fading in as it starts and out as its time runs out.
+
+struct FlowParams {
+    width: u32,
+    height: u32,
+    count: u32,
+    frame: u32,
+    delta_time: f32,
+    speed: f32,
+    swirl: f32,
+    lifetime: f32,
+    // Pixels across each particle
+    size: f32,
+    brightness: f32,
+    viewport_width: f32,
+    viewport_height: f32,
+    // The first tile drawn and how many there are in each row of them
+    tile_start_x: i32,
+    tile_start_y: i32,
+    tile_columns: u32,
+    _pad0: u32,
+}
+
+struct Particle {
+    position: vec2<f32>,
+    age: f32,
+    value: f32,
+}
+
+struct CameraUniform {
+    transform_matrix: mat4x4<f32>,
+    position: vec2<f32>,
+    zoom: f32,
+    aspect_ratio: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: FlowParams;
+@group(0) @binding(1) var<storage, read> particles: array<Particle>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+@group(1) @binding(0) var<uniform> camera: CameraUniform;
+
+// V seldom climbs past a half, so it's stretched over the whole scheme
+const VALUE_SCALE: f32 = 2.0;
+const PI: f32 = 3.14159265;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) corner: vec2<f32>,
+    @location(1) color: vec3<f32>,
+}
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    let particle = particles[instance_index % params.count];
+    let tile = instance_index / params.count;
+    let tile_offset = vec2<f32>(
+        f32(params.tile_start_x + i32(tile % params.tile_columns)),
+        f32(params.tile_start_y + i32(tile / params.tile_columns))
+    ) * 2.0;
+
+    let corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let center = camera.transform_matrix * vec4<f32>(particle.position + tile_offset, 0.0, 1.0);
+    // Half the size in pixels, as a share of the half viewport
+    let radius = params.size / vec2<f32>(params.viewport_width, params.viewport_height);
+
+    var output: VertexOutput;
+    output.position = center + vec4<f32>(corner * radius * center.w, 0.0, 0.0);
+    output.corner = corner;
+    output.color = lut_color(particle.value * VALUE_SCALE) * params.brightness
+        * sin(PI * clamp(particle.age, 0.0, 1.0));
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = length(input.corner);
+    if (distance > 1.0) {
+        discard;
+    }
+    return vec4<f32>(input.color * (1.0 - smoothstep(0.4, 1.0, distance)), 0.0);
+}
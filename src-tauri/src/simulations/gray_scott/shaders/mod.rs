@@ -1,6 +1,15 @@
+pub mod flow_layer;
 pub mod noise_seed;
 pub mod paint_compute;
 
-pub const REACTION_DIFFUSION_SHADER: &str = include_str!("reaction_diffusion.wgsl");
+pub const REACTION_DIFFUSION_SHADER: &str = concat!(
+    include_str!("../../shared/boundary.wgsl"),
+    include_str!("reaction_diffusion.wgsl")
+);
 pub const RENDER_INFINITE_SHADER: &str = crate::simulations::shared::INFINITE_RENDER_SHADER;
 pub const BACKGROUND_RENDER_SHADER: &str = include_str!("background_render.wgsl");
+pub const SURFACE_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("../../shared/lut_blend.wgsl"),
+    include_str!("surface.wgsl")
+);
@@ -20,6 +20,14 @@ struct SimulationParams {
     max_timestep: f32,
     stability_factor: f32,
     enable_adaptive_timestep: u32,
+
+    // Surface the grid is laid over (0 = plane, 1 = sphere, 2 = torus),
+    // see surface.rs
+    surface: u32,
+    tube_ratio: f32,
+    // Edge conditions on the plane, see boundary.wgsl
+    boundaries: u32,
+    _pad1: u32,
 }
 
 
@@ -34,6 +42,8 @@ struct UVPair {
 @group(0) @binding(2) var<uniform> params: SimulationParams;
 // Optional image-driven nutrient pattern (bound only when used)
 @group(0) @binding(3) var<storage, read> gradient_map: array<f32>;
+// Walls from the boundary conditions' mask
+@group(0) @binding(4) var<storage, read> boundary_mask: array<u32>;
 
 fn get_index(x: i32, y: i32) -> u32 {
     let width = i32(params.width);
@@ -44,43 +54,142 @@ fn get_index(x: i32, y: i32) -> u32 {
 }
 
 
-fn get_laplacian(x: i32, y: i32) -> vec2<f32> {
+const FRAC_PI_4: f32 = 0.78539816;
+const TAU: f32 = 6.28318530718;
+
+// A cube face's outward normal, then the directions its atlas columns and
+// rows run
+fn cube_face(face: u32) -> mat3x3<f32> {
+    switch (face) {
+        case 0u: {
+            return mat3x3<f32>(vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 0.0, -1.0), vec3<f32>(0.0, -1.0, 0.0));
+        }
+        case 1u: {
+            return mat3x3<f32>(vec3<f32>(-1.0, 0.0, 0.0), vec3<f32>(0.0, 0.0, 1.0), vec3<f32>(0.0, -1.0, 0.0));
+        }
+        case 2u: {
+            return mat3x3<f32>(vec3<f32>(0.0, 1.0, 0.0), vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 0.0, 1.0));
+        }
+        case 3u: {
+            return mat3x3<f32>(vec3<f32>(0.0, -1.0, 0.0), vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 0.0, -1.0));
+        }
+        case 4u: {
+            return mat3x3<f32>(vec3<f32>(0.0, 0.0, 1.0), vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, -1.0, 0.0));
+        }
+        default: {
+            return mat3x3<f32>(vec3<f32>(0.0, 0.0, -1.0), vec3<f32>(-1.0, 0.0, 0.0), vec3<f32>(0.0, -1.0, 0.0));
+        }
+    }
+}
+
+// The point on a face at `st` (-1 to 1 across it, spaced by angle), carrying
+// on in the face's plane past its edges
+fn cube_point(face: u32, st: vec2<f32>) -> vec3<f32> {
+    let axes = cube_face(face);
+    let ab = tan(st * FRAC_PI_4);
+    return axes[0] + ab.x * axes[1] + ab.y * axes[2];
+}
+
+// The atlas texel a direction from the sphere's center falls in
+fn sphere_texel(point: vec3<f32>, face_size: i32) -> vec2<i32> {
+    let a = abs(point);
+    var face: u32;
+    if (a.x >= a.y && a.x >= a.z) {
+        face = select(1u, 0u, point.x > 0.0);
+    } else if (a.y >= a.z) {
+        face = select(3u, 2u, point.y > 0.0);
+    } else {
+        face = select(5u, 4u, point.z > 0.0);
+    }
+    let axes = cube_face(face);
+    let depth = dot(point, axes[0]);
+    let st = atan(vec2<f32>(dot(point, axes[1]), dot(point, axes[2])) / depth) / FRAC_PI_4;
+    let local = clamp(vec2<i32>((st + 1.0) * 0.5 * f32(face_size)), vec2<i32>(0), vec2<i32>(face_size - 1));
+    return vec2<i32>(i32(face % 3u), i32(face / 3u)) * face_size + local;
+}
+
+// The texel `offset` away on the sphere, stepping off the face in 3D and
+// onto whichever face that lands on at an edge
+fn sphere_neighbor(texel: vec2<i32>, offset: vec2<i32>, face_size: i32) -> vec2<i32> {
+    let tile = texel / face_size;
+    let local = texel - tile * face_size + offset;
+    if (all(local >= vec2<i32>(0)) && all(local < vec2<i32>(face_size))) {
+        return texel + offset;
+    }
+    let face = u32(tile.y * 3 + tile.x);
+    let st = (vec2<f32>(local) + 0.5) / f32(face_size) * 2.0 - 1.0;
+    return sphere_texel(cube_point(face, st), face_size);
+}
+
+fn sphere_laplacian(x: i32, y: i32) -> vec2<f32> {
+    let face_size = i32(params.width / 3u);
+    let texel = vec2<i32>(x, y);
+    var laplacian = textureLoad(uvs_in, texel).xy * -4.0;
+    laplacian += textureLoad(uvs_in, sphere_neighbor(texel, vec2<i32>(-1, 0), face_size)).xy;
+    laplacian += textureLoad(uvs_in, sphere_neighbor(texel, vec2<i32>(1, 0), face_size)).xy;
+    laplacian += textureLoad(uvs_in, sphere_neighbor(texel, vec2<i32>(0, -1), face_size)).xy;
+    laplacian += textureLoad(uvs_in, sphere_neighbor(texel, vec2<i32>(0, 1), face_size)).xy;
+    return laplacian;
+}
+
+// Distance from the torus' center over the ring's radius, halfway down a row
+fn torus_reach(row: f32) -> f32 {
+    return 1.0 + params.tube_ratio * cos(TAU * (row + 0.5) / f32(params.height));
+}
+
+// Columns run around the ring and rows around the tube. Each neighbor is
+// weighted by how long the cell really is against the shortest cell side
+// on the torus, as in surface.rs' torus_stencil.
+fn torus_laplacian(x: i32, y: i32) -> vec2<f32> {
     let width = i32(params.width);
     let height = i32(params.height);
-    let wrapped_x = (x + width) % width;
-    let wrapped_y = (y + height) % height;
-    
-    let current_sample = textureLoad(uvs_in, vec2<i32>(wrapped_x, wrapped_y));
-    let current = current_sample.xy; // Extract only the first two components (RG -> UV)
-    
-    var laplacian = vec2<f32>(0.0);
-    
-    // 5-point stencil: center + 4 cardinal neighbors
-    // Center weight: -4 (for 5-point stencil)
-    laplacian -= current * 4.0;
-    
-    // Cardinal directions (weight 1.0 each) - batch the lookups for better performance
-    let left_x = (x - 1 + width) % width;
-    let right_x = (x + 1 + width) % width;
-    let up_y = (y - 1 + height) % height;
-    let down_y = (y + 1 + height) % height;
-    
-    let left_sample = textureLoad(uvs_in, vec2<i32>(left_x, wrapped_y));
-    let right_sample = textureLoad(uvs_in, vec2<i32>(right_x, wrapped_y));
-    let up_sample = textureLoad(uvs_in, vec2<i32>(wrapped_x, up_y));
-    let down_sample = textureLoad(uvs_in, vec2<i32>(wrapped_x, down_y));
-    
-    let left = left_sample.xy;
-    let right = right_sample.xy;
-    let up = up_sample.xy;
-    let down = down_sample.xy;
-    
-    laplacian += left;
-    laplacian += right;
-    laplacian += up;
-    laplacian += down;
-    
-    return laplacian;
+    let ring_step = 1.0 / f32(params.width);
+    let tube_step = params.tube_ratio / f32(params.height);
+    let shortest = min((1.0 - params.tube_ratio) * ring_step, tube_step);
+
+    let here = torus_reach(f32(y));
+    let ring_scale = shortest / (here * ring_step);
+    let tube_scale = shortest / tube_step;
+    let along_ring = ring_scale * ring_scale;
+    let previous_row = tube_scale * tube_scale * torus_reach(f32(y) - 0.5) / here;
+    let next_row = tube_scale * tube_scale * torus_reach(f32(y) + 0.5) / here;
+
+    let current = textureLoad(uvs_in, vec2<i32>(x, y)).xy;
+    let left = textureLoad(uvs_in, vec2<i32>((x - 1 + width) % width, y)).xy;
+    let right = textureLoad(uvs_in, vec2<i32>((x + 1) % width, y)).xy;
+    let up = textureLoad(uvs_in, vec2<i32>(x, (y - 1 + height) % height)).xy;
+    let down = textureLoad(uvs_in, vec2<i32>(x, (y + 1) % height)).xy;
+    return along_ring * (left + right - 2.0 * current)
+        + previous_row * (up - current)
+        + next_row * (down - current);
+}
+
+// U and V of a neighbor on the plane, read through the edge conditions.
+// Past an absorbing edge lies the unreacted state.
+fn plane_neighbor(cell: vec2<i32>, offset: vec2<i32>) -> vec2<f32> {
+    let size = vec2<u32>(params.width, params.height);
+    let neighbor = boundary_cell(params.boundaries, cell + offset, cell, size);
+    if (neighbor.x < 0) {
+        return vec2<f32>(1.0, 0.0);
+    }
+    return textureLoad(uvs_in, neighbor).xy;
+}
+
+fn get_laplacian(x: i32, y: i32) -> vec2<f32> {
+    if (params.surface == 1u) {
+        return sphere_laplacian(x, y);
+    }
+    if (params.surface == 2u) {
+        return torus_laplacian(x, y);
+    }
+
+    let cell = vec2<i32>(x, y);
+    let current = textureLoad(uvs_in, cell).xy;
+    return plane_neighbor(cell, vec2<i32>(-1, 0))
+        + plane_neighbor(cell, vec2<i32>(1, 0))
+        + plane_neighbor(cell, vec2<i32>(0, -1))
+        + plane_neighbor(cell, vec2<i32>(0, 1))
+        - current * 4.0;
 }
 
 fn hash(n: u32) -> f32 {
@@ -267,12 +376,14 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         return;
     }
     
-    let width = i32(params.width);
-    let height = i32(params.height);
-    let wrapped_x = (x + width) % width;
-    let wrapped_y = (y + height) % height;
-    
-    let uv_sample = textureLoad(uvs_in, vec2<i32>(wrapped_x, wrapped_y));
+    let cell = vec2<i32>(x, y);
+    let uv_sample = textureLoad(uvs_in, cell);
+    // Cells walled off by the mask stay as they are
+    let size = vec2<u32>(params.width, params.height);
+    if (params.surface == 0u && boundary_walled(params.boundaries, cell, size)) {
+        textureStore(uvs_out, cell, uv_sample);
+        return;
+    }
     let uv = uv_sample.xy; // Extract only the first two components (RG -> UV)
     let reaction_rate = uv.x * uv.y * uv.y;
     
@@ -334,5 +445,5 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let new_u = clamp(uv.x + delta_u * effective_timestep, 0.0, 1.0);
     let new_v = clamp(uv.y + delta_v * effective_timestep, 0.0, 1.0);
     
-    textureStore(uvs_out, vec2<i32>(wrapped_x, wrapped_y), vec4<f32>(new_u, new_v, 0.0, 0.0));
+    textureStore(uvs_out, cell, vec4<f32>(new_u, new_v, 0.0, 0.0));
 } 
\ No newline at end of file
@@ -0,0 +1,59 @@
+// Draws the sphere or torus the reaction runs on, colored by the simulation
+// texture the same way infinite_render.wgsl colors the plane, and lit from
+// just above the camera.
+
+struct OrbitCameraUniform {
+    view_projection: mat4x4<f32>,
+    eye: vec3<f32>,
+    _pad: f32,
+}
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+@group(0) @binding(0) var<uniform> camera: OrbitCameraUniform;
+@group(0) @binding(1) var simulation_data: texture_2d<f32>;
+@group(0) @binding(2) var simulation_sampler: sampler;
+@group(0) @binding(3) var<storage, read> lut_data: array<u32>;
+// Second color scheme, following V (see lut_blend.wgsl)
+@group(0) @binding(4) var<uniform> lut_blend: LutBlend;
+@group(0) @binding(5) var<storage, read> secondary_lut_data: array<u32>;
+
+const AMBIENT: f32 = 0.2;
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_projection * vec4<f32>(in.position, 1.0);
+    out.world_position = in.position;
+    out.normal = in.normal;
+    out.uv = in.uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(simulation_data, simulation_sampler, in.uv);
+    let index = u32(clamp(sample.x * 255.0, 0.0, 255.0));
+    let color = vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[index + 256u]) / 255.0),
+        srgb_to_linear(f32(lut_data[index + 512u]) / 255.0)
+    );
+    let blended = blend_luts(color, sample.x, sample.y);
+
+    let to_camera = camera.eye - in.world_position;
+    let light = normalize(to_camera + vec3<f32>(0.0, length(to_camera) * 0.5, 0.0));
+    let diffuse = max(dot(normalize(in.normal), light), 0.0);
+    return vec4<f32>(blended * (AMBIENT + (1.0 - AMBIENT) * diffuse), 1.0);
+}
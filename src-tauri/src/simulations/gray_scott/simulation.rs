@@ -1,21 +1,32 @@
 use crate::error::{SimulationError, SimulationResult};
 use crate::simulations::gray_scott::state::{MaskPattern, MaskTarget};
-use crate::simulations::shared::ImageFitMode;
+use crate::simulations::shared::{
+    BackgroundLayer, BoundaryConditions, ColorSchemeManager, GridResolution, ImageFitMode,
+    LutBlend,
+};
 use bytemuck::{Pod, Zeroable};
 use serde_json::Value;
 use std::sync::Arc;
 use wgpu::util::DeviceExt;
 use wgpu::{Device, Queue, SurfaceConfiguration, TextureView};
 
+use super::flow_particles::FlowParticles;
 use super::settings::Settings;
+use super::shaders::flow_layer::FlowLayer;
 use super::shaders::noise_seed::NoiseSeedCompute;
 use super::shaders::paint_compute::PaintCompute;
-use super::shaders::{BACKGROUND_RENDER_SHADER, REACTION_DIFFUSION_SHADER, RENDER_INFINITE_SHADER};
+use super::shaders::{
+    BACKGROUND_RENDER_SHADER, REACTION_DIFFUSION_SHADER, RENDER_INFINITE_SHADER, SURFACE_SHADER,
+};
 use super::state::State;
+use super::surface::{self, Surface, SurfaceVertex};
 use crate::simulations::shared::camera::Camera;
 use crate::simulations::shared::coordinates::TextureCoords;
+use crate::simulations::shared::field_image::image_to_field;
 use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::orbit_camera::OrbitCamera;
 use crate::simulations::shared::ping_pong_textures::PingPongTextures;
+use crate::simulations::shared::rewind::RewindResource;
 use crate::simulations::shared::{
     BindGroupBuilder, CommonBindGroupLayouts, RenderPipelineBuilder, ShaderManager,
 };
@@ -44,6 +55,13 @@ pub struct SimulationParams {
     pub max_timestep: f32,
     pub stability_factor: f32,
     pub enable_adaptive_timestep: u32,
+
+    // Surface the grid is laid over
+    pub surface: u32,
+    pub tube_ratio: f32,
+    // Edge conditions on the plane
+    pub boundaries: u32,
+    pub _pad1: u32,
 }
 
 // Uniform used by the render shader (matches simulations/shared/infinite_render.wgsl SimulationParams)
@@ -106,6 +124,72 @@ pub struct BlurFilterState {
     pub sigma: f32,
 }
 
+/// The sphere or torus, ready to draw
+#[derive(Debug)]
+struct SurfaceMesh {
+    vertices: wgpu::Buffer,
+    indices: wgpu::Buffer,
+    index_count: u32,
+}
+
+impl SurfaceMesh {
+    /// The mesh for `surface` over a grid `width` texels wide, none for the
+    /// plane
+    fn new(device: &Device, surface: Surface, tube_ratio: f32, width: u32) -> Option<Self> {
+        let (vertices, indices) = match surface {
+            Surface::Plane => return None,
+            Surface::Sphere => surface::sphere_mesh(width / 3, 32),
+            Surface::Torus => surface::torus_mesh(tube_ratio, 192, (192.0 * tube_ratio) as u32),
+        };
+        Some(Self {
+            vertices: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("GrayScott Surface Vertices"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            }),
+            indices: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("GrayScott Surface Indices"),
+                contents: bytemuck::cast_slice(&indices),
+                usage: wgpu::BufferUsages::INDEX,
+            }),
+            index_count: indices.len() as u32,
+        })
+    }
+}
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+fn create_depth_view(device: &Device, width: u32, height: u32) -> TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("GrayScott Surface Depth"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+// Row 0 of the grid is drawn along the top of the screen
+const ROWS_UP: bool = false;
+
+fn create_boundary_mask_buffer(device: &Device, width: u32, height: u32) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("GrayScott Boundary Mask Buffer"),
+        size: (width as u64 * height as u64 * std::mem::size_of::<u32>() as u64).max(4),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
 #[derive(Debug)]
 pub struct GrayScottModel {
     // Presentation
@@ -115,8 +199,17 @@ pub struct GrayScottModel {
     background_render_pipeline: wgpu::RenderPipeline,
     render_bind_group_layout: wgpu::BindGroupLayout,
     camera_bind_group_layout: wgpu::BindGroupLayout,
+    // Spheres and tori, drawn as meshes around which the orbit camera turns
+    pub orbit_camera: OrbitCamera,
+    surface_pipeline: wgpu::RenderPipeline,
+    surface_bind_group_layout: wgpu::BindGroupLayout,
+    surface_mesh: Option<SurfaceMesh>,
+    depth_view: TextureView,
     // Color scheme and render params
     lut_buffer: wgpu::Buffer,
+    // Second color scheme blended in by V, and how
+    secondary_lut_buffer: wgpu::Buffer,
+    lut_blend_buffer: wgpu::Buffer,
     background_color_buffer: wgpu::Buffer,
     texture_render_params_buffer: wgpu::Buffer,
     sampler: wgpu::Sampler,
@@ -134,6 +227,8 @@ pub struct GrayScottModel {
     compute_pipeline: wgpu::ComputePipeline,
     noise_seed_compute: NoiseSeedCompute,
     paint_compute: PaintCompute,
+    // Particles streaming over the plane, when turned on
+    flow_layer: FlowLayer,
     last_frame_time: std::time::Instant,
 
     // Background parameters
@@ -144,6 +239,8 @@ pub struct GrayScottModel {
     // Mask image buffer and state
     mask_image_buffer: Option<wgpu::Buffer>,
     mask_image_original: Option<image::DynamicImage>,
+    // Walls from the boundary conditions' mask, a cell each
+    boundary_mask_buffer: wgpu::Buffer,
 
     // Webcam capture for live mask
     pub webcam_capture: crate::simulations::shared::WebcamCapture,
@@ -161,6 +258,9 @@ impl GrayScottModel {
         color_scheme_manager: &crate::simulations::shared::ColorSchemeManager,
         app_settings: &crate::commands::app_settings::AppSettings,
     ) -> SimulationResult<Self> {
+        let (width, height) = settings
+            .surface
+            .grid_size(width, height, settings.tube_ratio);
         let vec_capacity = (width * height) as usize;
         let mut uvs: Vec<UVPair> = std::iter::repeat_n(
             UVPair {
@@ -254,6 +354,11 @@ impl GrayScottModel {
             max_timestep: settings.max_timestep,
             stability_factor: settings.stability_factor,
             enable_adaptive_timestep: settings.enable_adaptive_timestep as u32,
+
+            surface: settings.surface.shader_index(),
+            tube_ratio: settings.tube_ratio,
+            boundaries: settings.boundaries.packed(ROWS_UP),
+            _pad1: 0,
         };
 
         let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -309,6 +414,7 @@ impl GrayScottModel {
                 ),
                 resource_helpers::uniform_buffer_entry(2, wgpu::ShaderStages::COMPUTE),
                 resource_helpers::storage_buffer_entry(3, wgpu::ShaderStages::COMPUTE, true), // Optional gradient map buffer
+                resource_helpers::storage_buffer_entry(4, wgpu::ShaderStages::COMPUTE, true),
             ],
         });
 
@@ -344,6 +450,8 @@ impl GrayScottModel {
             mapped_at_creation: false,
         });
 
+        let boundary_mask_buffer = create_boundary_mask_buffer(device, width, height);
+
         // Create bind groups for both textures (input/output swapped)
         let bind_groups = [
             device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -354,6 +462,7 @@ impl GrayScottModel {
                     resource_helpers::texture_view_entry(1, &simulation_textures.views()[1]), // output
                     resource_helpers::buffer_entry(2, &params_buffer),
                     resource_helpers::buffer_entry(3, &gradient_buffer),
+                    resource_helpers::buffer_entry(4, &boundary_mask_buffer),
                 ],
             }),
             device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -364,6 +473,7 @@ impl GrayScottModel {
                     resource_helpers::texture_view_entry(1, &simulation_textures.views()[0]), // output
                     resource_helpers::buffer_entry(2, &params_buffer),
                     resource_helpers::buffer_entry(3, &gradient_buffer),
+                    resource_helpers::buffer_entry(4, &boundary_mask_buffer),
                 ],
             }),
         ];
@@ -380,6 +490,20 @@ impl GrayScottModel {
             contents: bytemuck::cast_slice(&lut_u32),
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
         });
+        let secondary_lut = settings
+            .lut_blend
+            .secondary_lut(color_scheme_manager)
+            .unwrap_or_else(|_| color_scheme_manager.get_default());
+        let secondary_lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GrayScott Secondary Color Scheme Buffer"),
+            contents: bytemuck::cast_slice(&secondary_lut.to_u32_buffer()),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        });
+        let lut_blend_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GrayScott LUT Blend Buffer"),
+            contents: bytemuck::bytes_of(&settings.lut_blend.uniform()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
         let background_color_buffer =
             device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("GrayScott Background Color Buffer"),
@@ -428,6 +552,8 @@ impl GrayScottModel {
                     resource_helpers::storage_buffer_entry(5, wgpu::ShaderStages::FRAGMENT, true),
                     resource_helpers::uniform_buffer_entry(6, wgpu::ShaderStages::FRAGMENT),
                     resource_helpers::uniform_buffer_entry(7, wgpu::ShaderStages::FRAGMENT),
+                    resource_helpers::uniform_buffer_entry(8, wgpu::ShaderStages::FRAGMENT),
+                    resource_helpers::storage_buffer_entry(9, wgpu::ShaderStages::FRAGMENT, true),
                 ],
             });
         let camera_bind_group_layout = common_layouts.camera.clone();
@@ -477,7 +603,85 @@ impl GrayScottModel {
             })])
             .with_label("GrayScott Background Render".to_string())
             .build();
+
+        let surface_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("GrayScott Surface Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(
+                        0,
+                        wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ),
+                    resource_helpers::texture_entry(
+                        1,
+                        wgpu::ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        2,
+                        wgpu::ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                    resource_helpers::storage_buffer_entry(3, wgpu::ShaderStages::FRAGMENT, true),
+                    resource_helpers::uniform_buffer_entry(4, wgpu::ShaderStages::FRAGMENT),
+                    resource_helpers::storage_buffer_entry(5, wgpu::ShaderStages::FRAGMENT, true),
+                ],
+            });
+        let surface_shader =
+            shader_manager.load_shader(device, "gray_scott_surface", SURFACE_SHADER);
+        let surface_pipeline = RenderPipelineBuilder::new(Arc::clone(device))
+            .with_shader(surface_shader)
+            .with_bind_group_layouts(vec![surface_bind_group_layout.clone()])
+            .with_vertex_buffer_layouts(vec![wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<SurfaceVertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &[
+                    wgpu::VertexAttribute {
+                        offset: 0,
+                        shader_location: 0,
+                        format: wgpu::VertexFormat::Float32x3, // position
+                    },
+                    wgpu::VertexAttribute {
+                        offset: 12,
+                        shader_location: 1,
+                        format: wgpu::VertexFormat::Float32x3, // normal
+                    },
+                    wgpu::VertexAttribute {
+                        offset: 24,
+                        shader_location: 2,
+                        format: wgpu::VertexFormat::Float32x2, // uv
+                    },
+                ],
+            }])
+            .with_depth_stencil(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_fragment_targets(vec![Some(wgpu::ColorTargetState {
+                format: surface_config.format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })])
+            .with_label("GrayScott Surface Render".to_string())
+            .build();
+        let surface_mesh = SurfaceMesh::new(device, settings.surface, settings.tube_ratio, width);
+        let orbit_camera = OrbitCamera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        );
+        let depth_view = create_depth_view(device, surface_config.width, surface_config.height);
         let noise_seed_compute = NoiseSeedCompute::new(device);
+        let flow_layer = FlowLayer::new(
+            device,
+            surface_config.format,
+            &camera_bind_group_layout,
+            settings.flow_particles.count,
+        );
 
         // Create background parameters
         let background_params = BackgroundParams {
@@ -531,7 +735,14 @@ impl GrayScottModel {
             background_render_pipeline,
             render_bind_group_layout,
             camera_bind_group_layout,
+            orbit_camera,
+            surface_pipeline,
+            surface_bind_group_layout,
+            surface_mesh,
+            depth_view,
             lut_buffer,
+            secondary_lut_buffer,
+            lut_blend_buffer,
             background_color_buffer,
             texture_render_params_buffer,
             sampler,
@@ -547,6 +758,7 @@ impl GrayScottModel {
             compute_pipeline,
             noise_seed_compute,
             paint_compute: PaintCompute::new(device),
+            flow_layer,
             last_frame_time: std::time::Instant::now(),
             state,
             background_bind_group,
@@ -559,14 +771,22 @@ impl GrayScottModel {
             },
             mask_image_buffer: Some(gradient_buffer),
             mask_image_original: None,
+            boundary_mask_buffer,
             webcam_capture: crate::simulations::shared::WebcamCapture::new(),
         };
+        if let Err(e) = simulation.write_boundary_mask(&simulation.settings.boundaries, queue) {
+            tracing::warn!("Gray-Scott starting without its boundary mask: {}", e);
+        }
 
         Ok(simulation)
     }
 
     pub fn update_settings(&mut self, new_settings: Settings, queue: &Arc<Queue>) {
         self.settings = new_settings;
+        self.write_lut_blend(queue);
+        if let Err(e) = self.write_boundary_mask(&self.settings.boundaries, queue) {
+            tracing::warn!("Gray-Scott keeping its previous boundary mask: {}", e);
+        }
 
         // Update params buffer
         let params = SimulationParams {
@@ -588,6 +808,11 @@ impl GrayScottModel {
             max_timestep: self.settings.max_timestep,
             stability_factor: self.settings.stability_factor,
             enable_adaptive_timestep: self.settings.enable_adaptive_timestep as u32,
+
+            surface: self.settings.surface.shader_index(),
+            tube_ratio: self.settings.tube_ratio,
+            boundaries: self.settings.boundaries.packed(ROWS_UP),
+            _pad1: 0,
         };
 
         queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
@@ -621,6 +846,39 @@ impl GrayScottModel {
         );
     }
 
+    /// Upload the walls of `boundaries`' mask, if it has one
+    fn write_boundary_mask(
+        &self,
+        boundaries: &BoundaryConditions,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        if let Some(cells) = boundaries.mask_cells(self.width, self.height, ROWS_UP)? {
+            queue.write_buffer(&self.boundary_mask_buffer, 0, bytemuck::cast_slice(&cells));
+        }
+        Ok(())
+    }
+
+    /// Upload the second color scheme and how it's blended in
+    fn write_lut_blend(&self, queue: &Arc<Queue>) {
+        let lut_blend = &self.settings.lut_blend;
+        queue.write_buffer(
+            &self.lut_blend_buffer,
+            0,
+            bytemuck::bytes_of(&lut_blend.uniform()),
+        );
+        match lut_blend.secondary_lut(&ColorSchemeManager::new()) {
+            Ok(lut) => queue.write_buffer(
+                &self.secondary_lut_buffer,
+                0,
+                bytemuck::cast_slice(&lut.to_u32_buffer()),
+            ),
+            Err(e) => tracing::warn!(
+                "Keeping the previous secondary color scheme for Gray-Scott: {}",
+                e
+            ),
+        }
+    }
+
     /// Update simulation parameters when state changes
     pub fn update_simulation_params(&mut self, queue: &Arc<Queue>) -> SimulationResult<()> {
         // Update params buffer
@@ -643,6 +901,11 @@ impl GrayScottModel {
             max_timestep: self.settings.max_timestep,
             stability_factor: self.settings.stability_factor,
             enable_adaptive_timestep: self.settings.enable_adaptive_timestep as u32,
+
+            surface: self.settings.surface.shader_index(),
+            tube_ratio: self.settings.tube_ratio,
+            boundaries: self.settings.boundaries.packed(ROWS_UP),
+            _pad1: 0,
         };
 
         queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
@@ -710,10 +973,21 @@ impl GrayScottModel {
         self.surface_config = new_config.clone();
         self.camera
             .resize(new_config.width as f32, new_config.height as f32);
-
-        // Use full surface resolution; ensure a minimum size
-        let new_sim_width = new_config.width.max(256);
-        let new_sim_height = new_config.height.max(256);
+        self.orbit_camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.depth_view = create_depth_view(device, new_config.width, new_config.height);
+
+        // Use the grid resolution setting (full surface resolution unless
+        // fixed); ensure a minimum size, then fit the surface's layout in it
+        let (grid_width, grid_height) = self
+            .settings
+            .grid_resolution
+            .resolve(new_config.width, new_config.height);
+        let (new_sim_width, new_sim_height) = self.settings.surface.grid_size(
+            grid_width.max(256),
+            grid_height.max(256),
+            self.settings.tube_ratio,
+        );
 
         // Only recreate buffers if dimensions actually changed
         if new_sim_width != self.width || new_sim_height != self.height {
@@ -731,11 +1005,54 @@ impl GrayScottModel {
 
             // Recreate simulation buffers with new dimensions
             Self::recreate_simulation_buffers(self, device, queue)?;
+            self.rebuild_surface_mesh(device);
         }
 
         Ok(())
     }
 
+    /// Wrap the grid around a different surface, or reshape the torus
+    pub fn set_surface(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface: Surface,
+        tube_ratio: f32,
+    ) -> SimulationResult<()> {
+        if surface != self.settings.surface {
+            self.reset_camera();
+        }
+        self.settings.surface = surface;
+        self.settings.tube_ratio = tube_ratio;
+        let surface_config = self.surface_config.clone();
+        self.resize(device, queue, &surface_config)?;
+        self.rebuild_surface_mesh(device);
+        Ok(())
+    }
+
+    fn rebuild_surface_mesh(&mut self, device: &Device) {
+        self.surface_mesh = SurfaceMesh::new(
+            device,
+            self.settings.surface,
+            self.settings.tube_ratio,
+            self.width,
+        );
+    }
+
+    /// Switch to a new grid resolution, rebuilding the field if its size
+    /// changes
+    pub fn set_grid_resolution(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        grid_resolution: GridResolution,
+    ) -> SimulationResult<()> {
+        grid_resolution.validate(device.limits().max_texture_dimension_2d)?;
+        self.settings.grid_resolution = grid_resolution;
+        let surface_config = self.surface_config.clone();
+        self.resize(device, queue, &surface_config)
+    }
+
     /// Recreate simulation textures with new dimensions
     pub fn recreate_simulation_buffers(
         &mut self,
@@ -799,6 +1116,8 @@ impl GrayScottModel {
             mapped_at_creation: false,
         });
 
+        let new_boundary_mask_buffer = create_boundary_mask_buffer(device, self.width, self.height);
+
         // Update params buffer with new dimensions
         let params = SimulationParams {
             feed_rate: self.settings.feed_rate,
@@ -821,6 +1140,11 @@ impl GrayScottModel {
             max_timestep: self.settings.max_timestep,
             stability_factor: self.settings.stability_factor,
             enable_adaptive_timestep: self.settings.enable_adaptive_timestep as u32,
+
+            surface: self.settings.surface.shader_index(),
+            tube_ratio: self.settings.tube_ratio,
+            boundaries: self.settings.boundaries.packed(ROWS_UP),
+            _pad1: 0,
         };
 
         queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
@@ -871,6 +1195,7 @@ impl GrayScottModel {
                 ),
                 resource_helpers::uniform_buffer_entry(2, wgpu::ShaderStages::COMPUTE),
                 resource_helpers::storage_buffer_entry(3, wgpu::ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(4, wgpu::ShaderStages::COMPUTE, true),
             ],
         });
 
@@ -883,6 +1208,7 @@ impl GrayScottModel {
                     resource_helpers::texture_view_entry(1, &new_simulation_textures.views()[1]), // output
                     resource_helpers::buffer_entry(2, &self.params_buffer),
                     resource_helpers::buffer_entry(3, &new_gradient_buffer),
+                    resource_helpers::buffer_entry(4, &new_boundary_mask_buffer),
                 ],
             }),
             device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -893,6 +1219,7 @@ impl GrayScottModel {
                     resource_helpers::texture_view_entry(1, &new_simulation_textures.views()[0]), // output
                     resource_helpers::buffer_entry(2, &self.params_buffer),
                     resource_helpers::buffer_entry(3, &new_gradient_buffer),
+                    resource_helpers::buffer_entry(4, &new_boundary_mask_buffer),
                 ],
             }),
         ];
@@ -900,7 +1227,11 @@ impl GrayScottModel {
         // Replace old textures with new ones
         self.simulation_textures = new_simulation_textures;
         self.mask_image_buffer = Some(new_gradient_buffer);
+        self.boundary_mask_buffer = new_boundary_mask_buffer;
         self.bind_groups = new_bind_groups;
+        if let Err(e) = self.write_boundary_mask(&self.settings.boundaries, queue) {
+            tracing::warn!("Gray-Scott boundary mask not redrawn after resize: {}", e);
+        }
 
         // If we have a gradient image, reprocess it for the new resolution
         if self.mask_image_original.is_some() {
@@ -972,6 +1303,40 @@ impl GrayScottModel {
         Ok(())
     }
 
+    /// Seed the reaction from an image stretched to the grid. Bright areas
+    /// start with V like the initial center seed, black stays at rest.
+    pub fn seed_from_image(&mut self, queue: &Arc<Queue>, image: &image::DynamicImage) {
+        let field = image_to_field(image, self.width, self.height);
+        // The textures are Rgba16Float: U, V and two unused channels
+        let uvs: Vec<u16> = field
+            .iter()
+            .flat_map(|&seed| [1.0 - 0.5 * seed, 0.99 * seed, 0.0, 0.0])
+            .map(|value| half::f16::from_f32(value).to_bits())
+            .collect();
+
+        for texture in self.simulation_textures.textures() {
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                bytemuck::cast_slice(&uvs),
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(self.width * 8), // 4 f16 values * 2 bytes each
+                    rows_per_image: Some(self.height),
+                },
+                wgpu::Extent3d {
+                    width: self.width,
+                    height: self.height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+    }
+
     /// Load an external image, convert to grayscale in [0,1], fit to sim size, and upload
     pub fn load_nutrient_image(
         &mut self,
@@ -981,7 +1346,15 @@ impl GrayScottModel {
         let img = image::open(image_path).map_err(|e| {
             SimulationError::InvalidParameter(format!("Failed to open image: {}", e))
         })?;
+        self.load_nutrient_image_from_data(queue, img)
+    }
 
+    /// Use an already decoded image as the nutrient map
+    pub fn load_nutrient_image_from_data(
+        &mut self,
+        queue: &Arc<Queue>,
+        img: image::DynamicImage,
+    ) -> SimulationResult<()> {
         // Store the original image and reprocess with current fit mode
         self.mask_image_original = Some(img);
         self.reprocess_nutrient_image_with_current_fit_mode(queue)?;
@@ -1149,7 +1522,7 @@ impl GrayScottModel {
         &mut self,
         setting_name: &str,
         value: Value,
-        _device: &Arc<Device>,
+        device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
         match setting_name {
@@ -1232,6 +1605,45 @@ impl GrayScottModel {
                     self.state.cursor_strength = v as f32;
                 }
             }
+            "grid_resolution" => {
+                let grid_resolution =
+                    serde_json::from_value(value).map_err(SimulationError::Serialization)?;
+                self.set_grid_resolution(device, queue, grid_resolution)?;
+            }
+            "lut_blend" => {
+                let lut_blend: LutBlend =
+                    serde_json::from_value(value).map_err(SimulationError::Serialization)?;
+                lut_blend.validate()?;
+                self.settings.lut_blend = lut_blend;
+                self.write_lut_blend(queue);
+            }
+            "surface" => {
+                let surface: Surface =
+                    serde_json::from_value(value).map_err(SimulationError::Serialization)?;
+                self.set_surface(device, queue, surface, self.settings.tube_ratio)?;
+            }
+            "tube_ratio" => {
+                if let Some(v) = value.as_f64() {
+                    self.set_surface(device, queue, self.settings.surface, v as f32)?;
+                }
+            }
+            "spin_speed" => {
+                if let Some(v) = value.as_f64() {
+                    self.settings.spin_speed = v as f32;
+                }
+            }
+            "boundaries" => {
+                let boundaries = BoundaryConditions::from_setting(value)?;
+                self.write_boundary_mask(&boundaries, queue)?;
+                self.settings.boundaries = boundaries;
+            }
+            "flow_particles" => {
+                let flow_particles: FlowParticles =
+                    serde_json::from_value(value).map_err(SimulationError::Serialization)?;
+                flow_particles.validate()?;
+                self.flow_layer.set_count(device, flow_particles.count);
+                self.settings.flow_particles = flow_particles;
+            }
             _ => {}
         }
 
@@ -1255,6 +1667,11 @@ impl GrayScottModel {
             max_timestep: self.settings.max_timestep,
             stability_factor: self.settings.stability_factor,
             enable_adaptive_timestep: self.settings.enable_adaptive_timestep as u32,
+
+            surface: self.settings.surface.shader_index(),
+            tube_ratio: self.settings.tube_ratio,
+            boundaries: self.settings.boundaries.packed(ROWS_UP),
+            _pad1: 0,
         };
 
         queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
@@ -1281,33 +1698,19 @@ impl GrayScottModel {
 
         // Update camera for smooth movement
         self.camera.update(delta_time);
+        if self.settings.surface.is_3d() {
+            self.orbit_camera
+                .spin(self.settings.spin_speed * delta_time);
+        }
+        self.orbit_camera.update(delta_time);
 
         // Run compute pass
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Gray Scott Compute Encoder"),
         });
-
-        {
-            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("Gray Scott Compute Pass"),
-                timestamp_writes: None,
-            });
-
-            compute_pass.set_pipeline(&self.compute_pipeline);
-            compute_pass.set_bind_group(
-                0,
-                self.simulation_textures
-                    .get_bind_group(&self.bind_groups[0], &self.bind_groups[1]),
-                &[],
-            );
-            compute_pass.dispatch_workgroups(self.width, self.height, 1);
-        }
-
+        self.encode_steps(&mut encoder, 1);
         queue.submit(std::iter::once(encoder.finish()));
 
-        // Swap textures for next frame
-        self.simulation_textures.swap();
-
         // Render background and infinite tiling
         self.camera.upload_to_gpu(&self.queue);
         let camera_bind_group = BindGroupBuilder::new(&self.device, &self.camera_bind_group_layout)
@@ -1328,6 +1731,8 @@ impl GrayScottModel {
                 resource_helpers::buffer_entry(5, &self.lut_buffer),
                 resource_helpers::buffer_entry(6, &self.params_buffer),
                 resource_helpers::buffer_entry(7, &self.render_params_buffer),
+                resource_helpers::buffer_entry(8, &self.lut_blend_buffer),
+                resource_helpers::buffer_entry(9, &self.secondary_lut_buffer),
             ],
         });
 
@@ -1337,6 +1742,19 @@ impl GrayScottModel {
                 label: Some("Gray Scott Render Encoder"),
             });
 
+        // The particles move over the field just worked out
+        let flowing = self.settings.flow_particles.enabled && !self.settings.surface.is_3d();
+        if flowing {
+            self.flow_layer.write_params(
+                queue,
+                &self.settings.flow_particles,
+                &self.camera,
+                (self.width, self.height),
+                delta_time,
+            );
+            self.flow_layer.step(device, &mut encoder, &texture_view);
+        }
+
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Gray Scott Render Pass"),
@@ -1361,25 +1779,89 @@ impl GrayScottModel {
             render_pass.draw(0..6, 0..1);
 
             // Infinite tiling
-            let tile_count = {
-                // match shader logic: see infinite_render.wgsl calculate_tile_count
-                let zoom = self.camera.zoom;
-                let visible_world_size = 2.0 / zoom;
-                let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
-                let min_tiles = if zoom < 0.1 { 7 } else { 5 };
-                tiles_needed.max(min_tiles).min(1024)
-            };
-            let total_instances = tile_count * tile_count;
-            render_pass.set_pipeline(&self.render_infinite_pipeline);
-            render_pass.set_bind_group(0, &render_bind_group, &[]);
-            render_pass.set_bind_group(1, &camera_bind_group, &[]);
-            render_pass.draw(0..6, 0..total_instances);
+            if !self.settings.surface.is_3d() {
+                let tile_count = {
+                    // match shader logic: see infinite_render.wgsl calculate_tile_count
+                    let zoom = self.camera.zoom;
+                    let visible_world_size = 2.0 / zoom;
+                    let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
+                    let min_tiles = if zoom < 0.1 { 7 } else { 5 };
+                    tiles_needed.max(min_tiles).min(1024)
+                };
+                let total_instances = tile_count * tile_count;
+                render_pass.set_pipeline(&self.render_infinite_pipeline);
+                render_pass.set_bind_group(0, &render_bind_group, &[]);
+                render_pass.set_bind_group(1, &camera_bind_group, &[]);
+                render_pass.draw(0..6, 0..total_instances);
+            }
+            if flowing {
+                self.flow_layer.draw(
+                    device,
+                    &mut render_pass,
+                    &self.lut_buffer,
+                    &camera_bind_group,
+                );
+            }
         }
+        self.draw_surface(&mut encoder, surface_view, &texture_view);
 
         self.queue.submit(std::iter::once(encoder.finish()));
         Ok(())
     }
 
+    /// Draw the sphere or torus over the background, when there is one
+    fn draw_surface(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        texture_view: &TextureView,
+    ) {
+        let Some(mesh) = &self.surface_mesh else {
+            return;
+        };
+        self.orbit_camera.upload_to_gpu(&self.queue);
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("GrayScott Surface Bind Group"),
+            layout: &self.surface_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, self.orbit_camera.buffer()),
+                resource_helpers::texture_view_entry(1, texture_view),
+                resource_helpers::sampler_bind_entry(2, &self.sampler),
+                resource_helpers::buffer_entry(3, &self.lut_buffer),
+                resource_helpers::buffer_entry(4, &self.lut_blend_buffer),
+                resource_helpers::buffer_entry(5, &self.secondary_lut_buffer),
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Gray Scott Surface Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Discard,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_pipeline(&self.surface_pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_vertex_buffer(0, mesh.vertices.slice(..));
+        render_pass.set_index_buffer(mesh.indices.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+    }
+
     pub fn start_webcam_capture(&mut self, device_index: i32) -> SimulationResult<()> {
         if self.mask_image_buffer.is_none() {
             return Err(SimulationError::InvalidParameter(
@@ -1463,7 +1945,30 @@ impl GrayScottModel {
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
-        // texture_x and texture_y are in [0,1] range
+        // texture_x and texture_y are in [0,1] range. On a sphere or torus
+        // they're still the screen's, and the surface under them is painted.
+        let (texture_x, texture_y) = if self.settings.surface.is_3d() {
+            let ndc = [texture_x * 2.0 - 1.0, 1.0 - texture_y * 2.0];
+            let (origin, direction) = self.orbit_camera.ray(ndc);
+            let Some(point) = surface::pick(
+                self.settings.surface,
+                self.settings.tube_ratio,
+                origin,
+                direction,
+            ) else {
+                return Ok(());
+            };
+            let [x, y] = surface::texture_position(
+                self.settings.surface,
+                self.settings.tube_ratio,
+                point,
+                self.width,
+                self.height,
+            );
+            (x, y)
+        } else {
+            (texture_x, texture_y)
+        };
         self.update_cursor_position(texture_x, texture_y, queue)?;
 
         let texture_coords = TextureCoords::new(texture_x, texture_y);
@@ -1508,20 +2013,55 @@ impl GrayScottModel {
         Ok(())
     }
 
+    /// Pans the plane, or turns the view around a sphere or torus
     pub fn pan_camera(&mut self, delta_x: f32, delta_y: f32) {
-        self.camera.pan(delta_x, delta_y);
+        if self.settings.surface.is_3d() {
+            self.orbit_camera.orbit(delta_x, delta_y);
+        } else {
+            self.camera.pan(delta_x, delta_y);
+        }
     }
 
     pub fn zoom_camera(&mut self, delta: f32) {
-        self.camera.zoom(delta);
+        if self.settings.surface.is_3d() {
+            self.orbit_camera.zoom(delta);
+        } else {
+            self.camera.zoom(delta);
+        }
     }
 
     pub fn zoom_camera_to_cursor(&mut self, delta: f32, cursor_x: f32, cursor_y: f32) {
-        self.camera.zoom_to_cursor(delta, cursor_x, cursor_y);
+        if self.settings.surface.is_3d() {
+            self.orbit_camera.zoom(delta);
+        } else {
+            self.camera.zoom_to_cursor(delta, cursor_x, cursor_y);
+        }
     }
 
+    /// Resets both views. In 3D the plane's camera stays at rest, so the
+    /// cursor can be mapped from the screen onto the surface.
     pub fn reset_camera(&mut self) {
         self.camera.reset();
+        self.orbit_camera.reset();
+    }
+
+    /// React and diffuse `steps` times, swapping the textures after each
+    fn encode_steps(&mut self, encoder: &mut wgpu::CommandEncoder, steps: u32) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Gray Scott Compute Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.compute_pipeline);
+        for _ in 0..steps {
+            compute_pass.set_bind_group(
+                0,
+                self.simulation_textures
+                    .get_bind_group(&self.bind_groups[0], &self.bind_groups[1]),
+                &[],
+            );
+            compute_pass.dispatch_workgroups(self.width, self.height, 1);
+            self.simulation_textures.swap();
+        }
     }
 
     pub(crate) fn toggle_gui(&mut self) -> bool {
@@ -1537,8 +2077,8 @@ impl GrayScottModel {
 impl crate::simulations::traits::Simulation for GrayScottModel {
     fn render_frame_paused(
         &mut self,
-        _device: &Arc<Device>,
-        _queue: &Arc<Queue>,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
         surface_view: &TextureView,
     ) -> SimulationResult<()> {
         // Calculate delta time
@@ -1548,6 +2088,7 @@ impl crate::simulations::traits::Simulation for GrayScottModel {
 
         // Update camera for smooth movement
         self.camera.update(delta_time);
+        self.orbit_camera.update(delta_time);
 
         // Skip compute pass - just render current state
         // Render the current state - pass the current texture (which contains the latest results)
@@ -1571,6 +2112,8 @@ impl crate::simulations::traits::Simulation for GrayScottModel {
                 resource_helpers::buffer_entry(5, &self.lut_buffer),
                 resource_helpers::buffer_entry(6, &self.params_buffer),
                 resource_helpers::buffer_entry(7, &self.render_params_buffer),
+                resource_helpers::buffer_entry(8, &self.lut_blend_buffer),
+                resource_helpers::buffer_entry(9, &self.secondary_lut_buffer),
             ],
         });
 
@@ -1580,6 +2123,18 @@ impl crate::simulations::traits::Simulation for GrayScottModel {
                 label: Some("Gray Scott Render Encoder (Paused)"),
             });
 
+        // Still where they were, but the view of them may have moved
+        let flowing = self.settings.flow_particles.enabled && !self.settings.surface.is_3d();
+        if flowing {
+            self.flow_layer.write_params(
+                queue,
+                &self.settings.flow_particles,
+                &self.camera,
+                (self.width, self.height),
+                0.0,
+            );
+        }
+
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Gray Scott Render Pass (Paused)"),
@@ -1602,19 +2157,30 @@ impl crate::simulations::traits::Simulation for GrayScottModel {
             render_pass.set_bind_group(1, &camera_bind_group, &[]);
             render_pass.draw(0..6, 0..1);
 
-            let tile_count = {
-                let zoom = self.camera.zoom;
-                let visible_world_size = 2.0 / zoom;
-                let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
-                let min_tiles = if zoom < 0.1 { 7 } else { 5 };
-                tiles_needed.max(min_tiles).min(1024)
-            };
-            let total_instances = tile_count * tile_count;
-            render_pass.set_pipeline(&self.render_infinite_pipeline);
-            render_pass.set_bind_group(0, &render_bind_group, &[]);
-            render_pass.set_bind_group(1, &camera_bind_group, &[]);
-            render_pass.draw(0..6, 0..total_instances);
+            if !self.settings.surface.is_3d() {
+                let tile_count = {
+                    let zoom = self.camera.zoom;
+                    let visible_world_size = 2.0 / zoom;
+                    let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
+                    let min_tiles = if zoom < 0.1 { 7 } else { 5 };
+                    tiles_needed.max(min_tiles).min(1024)
+                };
+                let total_instances = tile_count * tile_count;
+                render_pass.set_pipeline(&self.render_infinite_pipeline);
+                render_pass.set_bind_group(0, &render_bind_group, &[]);
+                render_pass.set_bind_group(1, &camera_bind_group, &[]);
+                render_pass.draw(0..6, 0..total_instances);
+            }
+            if flowing {
+                self.flow_layer.draw(
+                    device,
+                    &mut render_pass,
+                    &self.lut_buffer,
+                    &camera_bind_group,
+                );
+            }
         }
+        self.draw_surface(&mut encoder, surface_view, &texture_view);
 
         self.queue.submit(std::iter::once(encoder.finish()));
         Ok(())
@@ -1630,6 +2196,20 @@ impl crate::simulations::traits::Simulation for GrayScottModel {
         self.render_frame(device, queue, surface_view, delta_time)
     }
 
+    fn fast_forward(
+        &mut self,
+        steps: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Gray Scott Fast Forward Encoder"),
+        });
+        self.encode_steps(&mut encoder, steps);
+        queue.submit(std::iter::once(encoder.finish()));
+        Ok(())
+    }
+
     fn resize(
         &mut self,
         device: &Arc<Device>,
@@ -1836,6 +2416,14 @@ impl crate::simulations::traits::Simulation for GrayScottModel {
         serde_json::to_value(&self.settings).unwrap_or_else(|_| serde_json::json!({}))
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
     fn get_state(&self) -> serde_json::Value {
         serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
     }
@@ -1892,15 +2480,37 @@ impl crate::simulations::traits::Simulation for GrayScottModel {
         })
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        self.simulation_textures
+            .textures()
+            .iter()
+            .map(RewindResource::Texture)
+            .collect()
+    }
+
     fn apply_settings(
         &mut self,
         settings: serde_json::Value,
-        _device: &Arc<Device>,
+        device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
         let new_settings: Settings =
             serde_json::from_value(settings).map_err(SimulationError::Serialization)?;
+        new_settings.lut_blend.validate()?;
+        new_settings.flow_particles.validate()?;
+        self.flow_layer
+            .set_count(device, new_settings.flow_particles.count);
+        let grid_resolution = new_settings.grid_resolution;
+        if new_settings.surface != self.settings.surface {
+            self.reset_camera();
+        }
         self.update_settings(new_settings, queue);
+        // A preset saved on a GPU with larger textures falls back to the window
+        if let Err(e) = self.set_grid_resolution(device, queue, grid_resolution) {
+            tracing::warn!("Using the window size for the Gray-Scott grid: {}", e);
+            self.set_grid_resolution(device, queue, GridResolution::Window)?;
+        }
+        self.rebuild_surface_mesh(device);
         Ok(())
     }
 
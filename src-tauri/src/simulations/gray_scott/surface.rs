@@ -0,0 +1,335 @@
+//! Surfaces the reaction can run on besides the flat, wrapping plane, and
+//! how the grid is laid over them.
+//!
+//! - A sphere is a cube blown up into a ball, so there are no poles where
+//!   cells pinch together. The grid holds the cube's six faces in a 3 x 2
+//!   atlas, each face cut at equal angles so its cells stay close to one
+//!   size. Neighbors over a face's edge are found by stepping off the face
+//!   in 3D and seeing which face that lands on.
+//! - A torus keeps the plane's wrapping grid, with columns running around
+//!   the ring and rows around the tube. Cells on the outside of the ring
+//!   are longer than those on the inside, so the diffusion stencil is
+//!   weighted by how long each cell really is, keeping patterns one size
+//!   all over.
+//!
+//! `reaction_diffusion.wgsl` finds neighbors and weights the same way, and
+//! `surface.wgsl` draws the meshes built here. Both shapes are sized to fit
+//! in a unit ball.
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_4, TAU};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Surface {
+    #[default]
+    Plane,
+    Sphere,
+    Torus,
+}
+
+impl Surface {
+    /// What `reaction_diffusion.wgsl` calls this surface
+    pub fn shader_index(self) -> u32 {
+        match self {
+            Surface::Plane => 0,
+            Surface::Sphere => 1,
+            Surface::Torus => 2,
+        }
+    }
+
+    /// Whether the surface is drawn as a mesh with the orbit camera
+    pub fn is_3d(self) -> bool {
+        self != Surface::Plane
+    }
+
+    /// The grid to run on given room for `width` x `height` cells: six
+    /// square faces for the sphere, and cells that start out square on the
+    /// inside of the ring for the torus
+    pub fn grid_size(self, width: u32, height: u32, tube_ratio: f32) -> (u32, u32) {
+        match self {
+            Surface::Plane => (width, height),
+            Surface::Sphere => {
+                let face = (width / 3).min(height / 2).max(1);
+                (face * 3, face * 2)
+            }
+            Surface::Torus => {
+                // Columns over rows is the inner circumference over the tube's
+                let aspect = (1.0 - tube_ratio) / tube_ratio;
+                let rows = (width as f32 / aspect).min(height as f32);
+                let columns = rows * aspect;
+                ((columns as u32).max(1), (rows as u32).max(1))
+            }
+        }
+    }
+}
+
+/// Distance from the torus' center to the middle of its tube, and the
+/// tube's radius, for a torus whose outside edge is 1 from the center
+pub fn torus_radii(tube_ratio: f32) -> (f32, f32) {
+    let ring = 1.0 / (1.0 + tube_ratio);
+    (ring, ring * tube_ratio)
+}
+
+// Each cube face as its outward normal, then the directions its atlas
+// columns and rows run (rows counting down the atlas)
+const FACES: [[[f32; 3]; 3]; 6] = [
+    [[1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, -1.0, 0.0]],
+    [[-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, -1.0, 0.0]],
+    [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+    [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]],
+    [[0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, -1.0, 0.0]],
+    [[0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, -1.0, 0.0]],
+];
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = dot(v, v).sqrt().max(f32::EPSILON);
+    [v[0] / length, v[1] / length, v[2] / length]
+}
+
+/// The point on `face` of the cube at `s` across and `t` down, both -1 to 1
+/// over the face and spaced by angle. Values past 1 carry on in the face's
+/// plane.
+pub fn cube_point(face: u32, s: f32, t: f32) -> [f32; 3] {
+    let [normal, across, down] = FACES[face as usize];
+    let (a, b) = ((s * FRAC_PI_4).tan(), (t * FRAC_PI_4).tan());
+    std::array::from_fn(|i| normal[i] + a * across[i] + b * down[i])
+}
+
+/// Which face `point` is seen through from the center, and where on it
+pub fn cube_coords(point: [f32; 3]) -> (u32, f32, f32) {
+    let [x, y, z] = point.map(f32::abs);
+    let face = if x >= y && x >= z {
+        if point[0] > 0.0 { 0 } else { 1 }
+    } else if y >= z {
+        if point[1] > 0.0 { 2 } else { 3 }
+    } else if point[2] > 0.0 {
+        4
+    } else {
+        5
+    };
+    let [normal, across, down] = FACES[face as usize];
+    let depth = dot(point, normal);
+    let s = (dot(point, across) / depth).atan() / FRAC_PI_4;
+    let t = (dot(point, down) / depth).atan() / FRAC_PI_4;
+    (face, s, t)
+}
+
+/// The atlas texel `point` falls in, for faces `face_size` texels wide
+pub fn sphere_texel(point: [f32; 3], face_size: u32) -> (u32, u32) {
+    let (face, s, t) = cube_coords(point);
+    let local = |c: f32| (((c + 1.0) * 0.5 * face_size as f32) as u32).min(face_size - 1);
+    (
+        face % 3 * face_size + local(s),
+        face / 3 * face_size + local(t),
+    )
+}
+
+// The stencils below run in reaction_diffusion.wgsl; these copies are
+// kept to test them against
+
+/// Face and position on it of the middle of atlas texel (`x`, `y`), which
+/// may lie up to a texel off the face in either direction
+#[cfg(test)]
+fn texel_coords(x: i32, y: i32, face: u32, face_size: u32) -> (f32, f32) {
+    let origin = |index: u32| (index * face_size) as i32;
+    let coord = |texel: i32| (texel as f32 + 0.5) / face_size as f32 * 2.0 - 1.0;
+    (coord(x - origin(face % 3)), coord(y - origin(face / 3)))
+}
+
+/// The texel next to (`x`, `y`) on the sphere, `dx` columns and `dy` rows
+/// over, crossing onto the next face at an edge
+#[cfg(test)]
+pub fn sphere_neighbor(x: u32, y: u32, dx: i32, dy: i32, face_size: u32) -> (u32, u32) {
+    let face = y / face_size * 3 + x / face_size;
+    let (i, j) = ((x % face_size) as i32 + dx, (y % face_size) as i32 + dy);
+    if (0..face_size as i32).contains(&i) && (0..face_size as i32).contains(&j) {
+        return ((x as i32 + dx) as u32, (y as i32 + dy) as u32);
+    }
+    let (s, t) = texel_coords(x as i32 + dx, y as i32 + dy, face, face_size);
+    sphere_texel(cube_point(face, s, t), face_size)
+}
+
+/// Weights of a torus cell's neighbors in the diffusion stencil
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TorusStencil {
+    /// Each of the two cells along the ring, in the same row
+    pub along_ring: f32,
+    /// The cell in the row above
+    pub previous_row: f32,
+    /// The cell in the row below
+    pub next_row: f32,
+}
+
+/// Stencil weights for cells in `row`. Distances are measured against the
+/// shortest cell side anywhere on the torus, so no cell is pulled on much
+/// harder than on the plane and a pattern keeps the size it would have on a
+/// plane of those cells.
+#[cfg(test)]
+pub fn torus_stencil(row: u32, width: u32, height: u32, tube_ratio: f32) -> TorusStencil {
+    // Distance from the center over the ring's radius, at a row's middle
+    let reach = |row: f32| 1.0 + tube_ratio * (TAU * (row + 0.5) / height as f32).cos();
+    let ring_step = 1.0 / width as f32;
+    let tube_step = tube_ratio / height as f32;
+    let shortest = ((1.0 - tube_ratio) * ring_step).min(tube_step);
+
+    let here = reach(row as f32);
+    let tube_weight = (shortest / tube_step).powi(2);
+    // Flux to the next row passes through the edge between them, half a row over
+    let edge = |offset: f32| reach(row as f32 + offset) / here;
+    TorusStencil {
+        along_ring: (shortest / (here * ring_step)).powi(2),
+        previous_row: tube_weight * edge(-0.5),
+        next_row: tube_weight * edge(0.5),
+    }
+}
+
+/// A mesh vertex: where it is, which way it faces and where it reads the
+/// simulation texture
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub struct SurfaceVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+}
+
+/// Triangles over a `columns` x `rows` patch of vertices laid out row by row
+fn grid_indices(first: u32, columns: u32, rows: u32, indices: &mut Vec<u32>) {
+    for row in 0..rows - 1 {
+        for column in 0..columns - 1 {
+            let corner = first + row * columns + column;
+            indices.extend_from_slice(&[
+                corner,
+                corner + columns,
+                corner + 1,
+                corner + 1,
+                corner + columns,
+                corner + columns + 1,
+            ]);
+        }
+    }
+}
+
+/// The unit sphere, each cube face split `subdivisions` times each way.
+/// Faces read only their own tile of the atlas, staying half a texel
+/// inside so neighboring tiles don't bleed in.
+pub fn sphere_mesh(face_size: u32, subdivisions: u32) -> (Vec<SurfaceVertex>, Vec<u32>) {
+    let steps = subdivisions.max(1);
+    let margin = 0.5 / face_size as f32;
+    let mut vertices = Vec::with_capacity((6 * (steps + 1) * (steps + 1)) as usize);
+    let mut indices = Vec::new();
+    for face in 0..6 {
+        grid_indices(vertices.len() as u32, steps + 1, steps + 1, &mut indices);
+        for row in 0..=steps {
+            for column in 0..=steps {
+                let (u, v) = (column as f32 / steps as f32, row as f32 / steps as f32);
+                let position = normalize(cube_point(face, u * 2.0 - 1.0, v * 2.0 - 1.0));
+                let (u, v) = (u.clamp(margin, 1.0 - margin), v.clamp(margin, 1.0 - margin));
+                vertices.push(SurfaceVertex {
+                    position,
+                    normal: position,
+                    uv: [((face % 3) as f32 + u) / 3.0, ((face / 3) as f32 + v) / 2.0],
+                });
+            }
+        }
+    }
+    (vertices, indices)
+}
+
+/// A torus around the y axis with a tube `tube_ratio` of its ring's radius,
+/// split into `ring_segments` around the ring and `tube_segments` around
+/// the tube. The texture wraps both ways, columns following the ring.
+pub fn torus_mesh(
+    tube_ratio: f32,
+    ring_segments: u32,
+    tube_segments: u32,
+) -> (Vec<SurfaceVertex>, Vec<u32>) {
+    let (ring, tube) = torus_radii(tube_ratio);
+    let (ring_segments, tube_segments) = (ring_segments.max(3), tube_segments.max(3));
+    let mut vertices = Vec::with_capacity(((ring_segments + 1) * (tube_segments + 1)) as usize);
+    let mut indices = Vec::new();
+    grid_indices(0, ring_segments + 1, tube_segments + 1, &mut indices);
+    for j in 0..=tube_segments {
+        let v = j as f32 / tube_segments as f32;
+        let (sin_phi, cos_phi) = (v * TAU).sin_cos();
+        for i in 0..=ring_segments {
+            let u = i as f32 / ring_segments as f32;
+            let (sin_theta, cos_theta) = (u * TAU).sin_cos();
+            let reach = ring + tube * cos_phi;
+            vertices.push(SurfaceVertex {
+                position: [reach * cos_theta, tube * sin_phi, reach * sin_theta],
+                normal: [cos_phi * cos_theta, sin_phi, cos_phi * sin_theta],
+                uv: [u, v],
+            });
+        }
+    }
+    (vertices, indices)
+}
+
+/// Signed distance from `point` to the surface
+fn distance(surface: Surface, tube_ratio: f32, point: [f32; 3]) -> f32 {
+    match surface {
+        Surface::Torus => {
+            let (ring, tube) = torus_radii(tube_ratio);
+            let across = point[0].hypot(point[2]) - ring;
+            across.hypot(point[1]) - tube
+        }
+        _ => dot(point, point).sqrt() - 1.0,
+    }
+}
+
+/// Where a ray from `origin` heading along unit `direction` first meets
+/// the surface, marching toward it by the distance that's known to be clear
+pub fn pick(
+    surface: Surface,
+    tube_ratio: f32,
+    origin: [f32; 3],
+    direction: [f32; 3],
+) -> Option<[f32; 3]> {
+    let mut traveled = 0.0;
+    for _ in 0..128 {
+        let point = std::array::from_fn(|i| origin[i] + direction[i] * traveled);
+        let gap = distance(surface, tube_ratio, point);
+        if gap < 1e-4 {
+            return Some(point);
+        }
+        traveled += gap;
+        if traveled > 100.0 {
+            break;
+        }
+    }
+    None
+}
+
+/// Where `point` on the surface falls in the simulation texture, 0 to 1
+/// each way
+pub fn texture_position(
+    surface: Surface,
+    tube_ratio: f32,
+    point: [f32; 3],
+    width: u32,
+    height: u32,
+) -> [f32; 2] {
+    match surface {
+        Surface::Sphere => {
+            let (x, y) = sphere_texel(point, width / 3);
+            [
+                (x as f32 + 0.5) / width as f32,
+                (y as f32 + 0.5) / height as f32,
+            ]
+        }
+        Surface::Torus => {
+            let (ring, _) = torus_radii(tube_ratio);
+            let theta = point[2].atan2(point[0]);
+            let phi = point[1].atan2(point[0].hypot(point[2]) - ring);
+            [(theta / TAU).rem_euclid(1.0), (phi / TAU).rem_euclid(1.0)]
+        }
+        Surface::Plane => [point[0] * 0.5 + 0.5, 0.5 - point[1] * 0.5],
+    }
+}
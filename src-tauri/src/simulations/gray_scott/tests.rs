@@ -5,9 +5,19 @@
 //! both the computational correctness and the integration between different
 //! components of the simulation system.
 
+use super::flow_particles::{FlowParticles, MAX_TILES_ACROSS, visible_tiles};
+use super::reference;
+use super::settings::Settings;
+use super::shaders::flow_layer::{
+    FLOW_PARTICLES_COMPUTE_SHADER, FLOW_PARTICLES_RENDER_SHADER, FlowParams, FlowParticle,
+};
 use super::shaders::{BACKGROUND_RENDER_SHADER, REACTION_DIFFUSION_SHADER};
 use super::simulation::{BackgroundParams, SimulationParams};
+use super::surface::*;
+use crate::simulations::shared::boundary::BoundaryMask;
 use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::kernel_test::{KernelHarness, assert_close};
+use crate::simulations::shared::{Boundary, BoundaryConditions, RewindResource};
 use std::mem;
 use wgpu::util::DeviceExt;
 
@@ -70,6 +80,29 @@ impl GrayScottValidator {
         Ok(())
     }
 
+    /// Validates that the flow particle compute and render shaders compile
+    /// without errors
+    fn validate_flow_particle_shader_compilation(&self) -> Result<(), String> {
+        for (label, source) in [
+            (
+                "Gray-Scott Flow Particles Compute Shader",
+                FLOW_PARTICLES_COMPUTE_SHADER,
+            ),
+            (
+                "Gray-Scott Flow Particles Render Shader",
+                FLOW_PARTICLES_RENDER_SHADER,
+            ),
+        ] {
+            let _ = self
+                .device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some(label),
+                    source: wgpu::ShaderSource::Wgsl(source.into()),
+                });
+        }
+        Ok(())
+    }
+
     /// Validates that the reaction diffusion shader can bind to the Rust structs
     fn validate_reaction_diffusion_shader_binding(&self) -> Result<(), String> {
         // Create dummy data
@@ -94,6 +127,11 @@ impl GrayScottValidator {
             max_timestep: 2.0,
             stability_factor: 0.8,
             enable_adaptive_timestep: 1,
+
+            surface: 0,
+            tube_ratio: 0.4,
+            boundaries: 0,
+            _pad1: 0,
         };
 
         // Create buffers
@@ -148,6 +186,11 @@ impl GrayScottValidator {
             max_timestep: 2.0,
             stability_factor: 0.8,
             enable_adaptive_timestep: 1,
+
+            surface: 0,
+            tube_ratio: 0.4,
+            boundaries: 0,
+            _pad1: 0,
         };
 
         // Create buffers
@@ -264,6 +307,9 @@ async fn test_gray_scott_shader_compilation() {
     validator
         .validate_background_render_shader_compilation()
         .expect("Background render shader compilation failed");
+    validator
+        .validate_flow_particle_shader_compilation()
+        .expect("Flow particle shader compilation failed");
 
     // Print struct sizes for debugging
     validator.print_struct_sizes();
@@ -346,6 +392,11 @@ fn test_struct_layout_consistency() {
             max_timestep: 2.0,
             stability_factor: 0.8,
             enable_adaptive_timestep: 1,
+
+            surface: 0,
+            tube_ratio: 0.4,
+            boundaries: 0,
+            _pad1: 0,
         };
 
         let dummy_background_params = BackgroundParams {
@@ -396,3 +447,376 @@ fn test_struct_layout_consistency() {
         );
     });
 }
+
+fn angle_between(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let length = |v: [f32; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    let dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    (dot / (length(a) * length(b))).clamp(-1.0, 1.0).acos()
+}
+
+/// Center of an atlas texel on the cube
+fn sphere_texel_center(x: u32, y: u32, face_size: u32) -> [f32; 3] {
+    let face = y / face_size * 3 + x / face_size;
+    let coord = |texel: u32| (texel as f32 + 0.5) / face_size as f32 * 2.0 - 1.0;
+    cube_point(face, coord(x % face_size), coord(y % face_size))
+}
+
+#[test]
+fn sphere_texels_round_trip() {
+    let face_size = 24;
+    for y in 0..face_size * 2 {
+        for x in 0..face_size * 3 {
+            let center = sphere_texel_center(x, y, face_size);
+            assert_eq!(sphere_texel(center, face_size), (x, y));
+        }
+    }
+}
+
+#[test]
+fn sphere_neighbors_are_adjacent_and_mutual() {
+    let face_size = 24;
+    let cell = std::f32::consts::FRAC_PI_2 / face_size as f32;
+    for y in 0..face_size * 2 {
+        for x in 0..face_size * 3 {
+            let here = sphere_texel_center(x, y, face_size);
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let (nx, ny) = sphere_neighbor(x, y, dx, dy, face_size);
+                assert_ne!((nx, ny), (x, y));
+                let there = sphere_texel_center(nx, ny, face_size);
+                assert!(
+                    angle_between(here, there) < 1.5 * cell,
+                    "({}, {}) and its neighbor ({}, {}) are far apart",
+                    x,
+                    y,
+                    nx,
+                    ny
+                );
+                let back = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                    .iter()
+                    .any(|&(bx, by)| sphere_neighbor(nx, ny, bx, by, face_size) == (x, y));
+                assert!(
+                    back,
+                    "({}, {}) neighbors ({}, {}) but not back",
+                    x, y, nx, ny
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn surface_grids_fit_their_room() {
+    assert_eq!(Surface::Plane.grid_size(640, 480, 0.4), (640, 480));
+    assert_eq!(Surface::Sphere.grid_size(640, 480, 0.4), (639, 426));
+    let (columns, rows) = Surface::Torus.grid_size(640, 480, 0.4);
+    assert!(columns <= 640 && rows <= 480);
+    // Inside the ring, cells are as long as they are tall
+    let ratio = (columns as f32 * 0.4) / (rows as f32 * 0.6);
+    assert!((ratio - 1.0).abs() < 0.01, "ratio {}", ratio);
+}
+
+#[test]
+fn torus_stencil_conserves_and_stays_stable() {
+    let (tube_ratio, width, height) = (0.4, 300, 200);
+    let reach = |row: u32| {
+        1.0 + tube_ratio * (std::f32::consts::TAU * (row as f32 + 0.5) / height as f32).cos()
+    };
+    for row in 0..height {
+        let stencil = torus_stencil(row, width, height, tube_ratio);
+        let total = 2.0 * stencil.along_ring + stencil.previous_row + stencil.next_row;
+        assert!(total <= 4.05, "row {} pulls {}", row, total);
+        // What leaves one row for the next arrives there, counted per area
+        let next = (row + 1) % height;
+        let arriving = torus_stencil(next, width, height, tube_ratio).previous_row * reach(next);
+        assert!((stencil.next_row * reach(row) - arriving).abs() < 1e-4);
+    }
+    // The ring's inside is where cells are shortest
+    let inside = torus_stencil(height / 2, width, height, tube_ratio);
+    let outside = torus_stencil(0, width, height, tube_ratio);
+    assert!(inside.along_ring > 0.99 && outside.along_ring < inside.along_ring);
+}
+
+#[test]
+fn picking_finds_the_facing_side() {
+    for surface in [Surface::Sphere, Surface::Torus] {
+        let hit = pick(surface, 0.4, [0.0, 0.0, 3.0], [0.0, 0.0, -1.0]).unwrap();
+        assert!((hit[2] - 1.0).abs() < 1e-3, "{:?} hit {:?}", surface, hit);
+    }
+    // Straight down through the torus' hole
+    assert!(pick(Surface::Torus, 0.4, [0.0, 3.0, 0.0], [0.0, -1.0, 0.0]).is_none());
+    assert!(pick(Surface::Sphere, 0.4, [0.0, 3.0, 0.0], [1.0, 0.0, 0.0]).is_none());
+}
+
+#[test]
+fn meshes_read_where_picking_lands() {
+    let (width, height) = Surface::Torus.grid_size(600, 400, 0.3);
+    let (vertices, indices) = torus_mesh(0.3, 32, 16);
+    assert_eq!(indices.len(), 32 * 16 * 6);
+    for vertex in vertices.iter().filter(|v| v.uv[0] < 1.0 && v.uv[1] < 1.0) {
+        let [u, v] = texture_position(Surface::Torus, 0.3, vertex.position, width, height);
+        assert!((u - vertex.uv[0]).abs() < 1e-4 && (v - vertex.uv[1]).abs() < 1e-4);
+    }
+
+    let face_size = 32;
+    let (vertices, indices) = sphere_mesh(face_size, 8);
+    assert_eq!(indices.len(), 6 * 8 * 8 * 6);
+    let texel = 1.0 / (face_size * 2) as f32;
+    for (index, vertex) in vertices.iter().enumerate() {
+        let length = vertex.position.iter().map(|c| c * c).sum::<f32>().sqrt();
+        assert!((length - 1.0).abs() < 1e-5);
+        // Vertices on a cube edge belong to more than one face
+        let (face, s, t) = cube_coords(vertex.position);
+        if face as usize != index / 81 || s.abs() > 0.999 || t.abs() > 0.999 {
+            continue;
+        }
+        let [u, v] = texture_position(
+            Surface::Sphere,
+            0.3,
+            vertex.position,
+            face_size * 3,
+            face_size * 2,
+        );
+        assert!((u - vertex.uv[0]).abs() * 1.5 <= texel * 1.01);
+        assert!((v - vertex.uv[1]).abs() <= texel * 1.01);
+    }
+}
+
+#[test]
+fn flow_particle_structs_match_their_shaders() {
+    // FlowParams is four rows of four scalars; a particle is one
+    assert_eq!(mem::size_of::<FlowParams>(), 64);
+    assert_eq!(mem::size_of::<FlowParticle>(), 16);
+}
+
+#[test]
+fn visible_tiles_cover_the_view() {
+    for (center, zoom) in [
+        ([0.0, 0.0], 1.0),
+        ([0.3, -0.7], 1.0),
+        ([5.2, 3.9], 2.5),
+        ([-1.0, 1.0], 0.6),
+        ([0.0, 0.0], 0.45),
+    ] {
+        let (start, count) = visible_tiles(center, zoom);
+        let reach = 1.0 / zoom;
+        for step in 0..=20 {
+            for axis in 0..2 {
+                let edge = center[axis] - reach + 2.0 * reach * step as f32 / 20.0;
+                let tile = ((edge + 1.0) / 2.0).floor() as i32;
+                assert!(
+                    tile >= start[axis] && tile < start[axis] + count[axis] as i32,
+                    "{} not drawn at {:?} zoom {}",
+                    edge,
+                    center,
+                    zoom
+                );
+            }
+        }
+    }
+    // Zoomed in, only the tile under the camera
+    assert_eq!(visible_tiles([2.1, -1.8], 8.0), ([1, -1], [1, 1]));
+    // Zoomed far out, no more than a few around the middle
+    let (start, count) = visible_tiles([4.0, 0.0], 0.02);
+    assert_eq!(count, [MAX_TILES_ACROSS; 2]);
+    assert_eq!(start[0] + (MAX_TILES_ACROSS / 2) as i32, 2);
+}
+
+#[test]
+fn flow_particles_are_off_and_valid_by_default() {
+    let defaults = FlowParticles::default();
+    assert!(!defaults.enabled);
+    assert!(defaults.validate().is_ok());
+    assert!(
+        FlowParticles {
+            count: 0,
+            ..defaults.clone()
+        }
+        .validate()
+        .is_err()
+    );
+    assert!(
+        FlowParticles {
+            swirl: 270.0,
+            ..defaults.clone()
+        }
+        .validate()
+        .is_err()
+    );
+
+    // Presets saved before there were flow particles still load, without them
+    let mut saved = serde_json::to_value(Settings::default()).unwrap();
+    saved.as_object_mut().unwrap().remove("flow_particles");
+    let settings: Settings = serde_json::from_value(saved).unwrap();
+    assert_eq!(settings.flow_particles, defaults);
+}
+
+#[test]
+fn presets_without_boundaries_keep_wrapping() {
+    let mut saved = serde_json::to_value(Settings::default()).unwrap();
+    saved.as_object_mut().unwrap().remove("boundaries");
+    let settings: Settings = serde_json::from_value(saved).unwrap();
+    assert_eq!(
+        settings.boundaries,
+        BoundaryConditions::preset("wrap").unwrap()
+    );
+    // The reaction shader reads 0 as wrapping at every edge
+    assert_eq!(settings.boundaries.packed(false), 0);
+}
+
+/// What the simulation's Rgba16Float textures hold of `value`
+fn to_f16(value: f32) -> f32 {
+    half::f16::from_f32(value).to_f32()
+}
+
+fn reference_params(width: u32, height: u32, surface: u32, boundaries: u32) -> SimulationParams {
+    SimulationParams {
+        feed_rate: 0.055,
+        kill_rate: 0.062,
+        delta_u: 0.2097,
+        delta_v: 0.105,
+        timestep: 1.0,
+        width,
+        height,
+        mask_pattern: 0,
+        mask_target: 0,
+        mask_strength: 0.0,
+        mask_mirror_horizontal: 0,
+        mask_mirror_vertical: 0,
+        mask_invert_tone: 0,
+        max_timestep: 1.0,
+        stability_factor: 0.8,
+        enable_adaptive_timestep: 0,
+        surface,
+        tube_ratio: 0.4,
+        boundaries,
+        _pad1: 0,
+    }
+}
+
+/// Ripples of V over a reaction otherwise at rest, so every term is busy
+fn rippled_uvs(width: u32, height: u32) -> Vec<[f32; 2]> {
+    (0..width * height)
+        .map(|i| {
+            let (x, y) = ((i % width) as f32, (i / width) as f32);
+            let v = 0.5 * (0.5 + 0.5 * (x * 0.9 + y * 1.7).sin()).powi(2);
+            [to_f16(1.0 - v), to_f16(v)]
+        })
+        .collect()
+}
+
+/// One step of `reaction_diffusion.wgsl` from `uvs`
+async fn gpu_reaction_step(
+    params: &SimulationParams,
+    uvs: &[[f32; 2]],
+    walls: &[u32],
+) -> Vec<[f32; 2]> {
+    let harness = KernelHarness::new().await;
+    let pipeline = harness.pipeline(REACTION_DIFFUSION_SHADER, "main");
+    let size = (params.width, params.height);
+    let format = wgpu::TextureFormat::Rgba16Float;
+    let texels: Vec<u16> = uvs
+        .iter()
+        .flat_map(|uv| [uv[0], uv[1], 0.0, 0.0])
+        .map(|value| half::f16::from_f32(value).to_bits())
+        .collect();
+    let uvs_in = harness.texture(size, format, bytemuck::cast_slice(&texels));
+    let uvs_out = harness.texture(size, format, &vec![0; texels.len() * 2]);
+    let params_buffer = harness.buffer(bytemuck::bytes_of(params), wgpu::BufferUsages::UNIFORM);
+    let gradient_map = harness.buffer(
+        bytemuck::cast_slice(&vec![0.0f32; uvs.len()]),
+        wgpu::BufferUsages::STORAGE,
+    );
+    let walls_buffer = harness.buffer(bytemuck::cast_slice(walls), wgpu::BufferUsages::STORAGE);
+
+    let in_view = uvs_in.create_view(&Default::default());
+    let out_view = uvs_out.create_view(&Default::default());
+    harness.dispatch(
+        &pipeline,
+        &[
+            resource_helpers::texture_view_entry(0, &in_view),
+            resource_helpers::texture_view_entry(1, &out_view),
+            resource_helpers::buffer_entry(2, &params_buffer),
+            resource_helpers::buffer_entry(3, &gradient_map),
+            resource_helpers::buffer_entry(4, &walls_buffer),
+        ],
+        (params.width, params.height, 1),
+    );
+
+    harness
+        .read(RewindResource::Texture(&uvs_out))
+        .chunks_exact(8)
+        .map(|texel| {
+            let channel =
+                |i: usize| half::f16::from_le_bytes([texel[i * 2], texel[i * 2 + 1]]).to_f32();
+            [channel(0), channel(1)]
+        })
+        .collect()
+}
+
+/// Run one step on both paths and compare them to within a couple of steps
+/// of half precision
+async fn assert_reaction_step_matches(params: SimulationParams, walls: Vec<u32>) {
+    let uvs = rippled_uvs(params.width, params.height);
+    let mask = Some(walls.as_slice());
+    let cpu: Vec<f32> = reference::step(&uvs, &params, mask)
+        .iter()
+        .flat_map(|uv| [to_f16(uv[0]), to_f16(uv[1])])
+        .collect();
+    let gpu: Vec<f32> = gpu_reaction_step(&params, &uvs, &walls)
+        .await
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+    assert_close("Reaction step", &gpu, &cpu, 1e-3);
+}
+
+#[test]
+fn reference_step_leaves_the_rest_state_alone() {
+    for surface in 0..3 {
+        let params = reference_params(12, 8, surface, 0);
+        let rest = vec![[1.0, 0.0]; 96];
+        assert_eq!(reference::step(&rest, &params, None), rest);
+    }
+}
+
+#[tokio::test]
+async fn reaction_step_matches_the_reference_on_the_plane() {
+    let (width, height) = (16, 12);
+    let conditions = BoundaryConditions {
+        left: Boundary::Reflective,
+        right: Boundary::Absorbing,
+        bottom: Boundary::Periodic,
+        top: Boundary::Reflective,
+        mask: BoundaryMask::Image {
+            path: "walls.png".to_string(),
+            absorbing: false,
+        },
+    };
+    // A block of wall in the middle of the grid
+    let walls: Vec<u32> = (0..width * height)
+        .map(|i| !((5..9).contains(&(i % width)) && (4..7).contains(&(i / width))) as u32)
+        .collect();
+    let params = reference_params(width, height, 0, conditions.packed(false));
+    assert_reaction_step_matches(params, walls.clone()).await;
+
+    // Wrapping everywhere, with the step size worked out by the shader
+    let params = SimulationParams {
+        enable_adaptive_timestep: 1,
+        ..reference_params(width, height, 0, 0)
+    };
+    assert_reaction_step_matches(params, walls).await;
+}
+
+#[tokio::test]
+async fn reaction_step_matches_the_reference_on_the_torus() {
+    let params = reference_params(20, 10, 2, 0);
+    assert_reaction_step_matches(params, vec![1; 200]).await;
+}
+
+#[tokio::test]
+async fn reaction_step_matches_the_reference_on_the_sphere() {
+    // Faces 4 texels square in the 3 by 2 atlas
+    let params = reference_params(12, 8, 1, 0);
+    assert_reaction_step_matches(params, vec![1; 96]).await;
+}
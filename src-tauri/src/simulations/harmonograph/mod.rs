@@ -0,0 +1,97 @@
+pub mod pendulums;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::HarmonographModel;
+
+use crate::simulation::preset_manager::{HarmonographPresetManager, Preset};
+
+/// Initialize Harmonograph presets with built-in configurations
+pub fn init_presets(preset_manager: &mut HarmonographPresetManager) {
+    use settings::{Axis, ColorBy, Oscillator, Settings};
+
+    let damped = |axis, frequency, phase, damping| Oscillator {
+        damping,
+        ..Oscillator::new(axis, frequency, phase)
+    };
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Three Four Lissajous".to_string(),
+        Settings {
+            oscillators: vec![
+                damped(Axis::X, 3.0, 90.0, 0.004),
+                damped(Axis::Y, 4.0, 0.0, 0.004),
+            ],
+            draw_speed: 2.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Unison Spiral".to_string(),
+        Settings {
+            oscillators: vec![
+                damped(Axis::X, 1.0, 90.0, 0.03),
+                damped(Axis::Y, 1.0, 0.0, 0.03),
+            ],
+            draw_speed: 3.0,
+            pen_width: 2.0,
+            color_by: ColorBy::Direction,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Drifting Figure Eight".to_string(),
+        Settings {
+            oscillators: vec![
+                damped(Axis::X, 1.0, 0.0, 0.006),
+                damped(Axis::Y, 2.0, 0.0, 0.006),
+                damped(Axis::X, 1.003, 90.0, 0.01),
+                damped(Axis::Y, 2.004, 45.0, 0.01),
+            ],
+            color_by: ColorBy::Speed,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Rotary Rosette".to_string(),
+        Settings {
+            oscillators: vec![
+                damped(Axis::X, 2.0, 90.0, 0.005),
+                damped(Axis::Y, 2.0, 0.0, 0.005),
+                Oscillator {
+                    amplitude: 0.6,
+                    ..damped(Axis::Rotary, 3.01, 0.0, 0.008)
+                },
+            ],
+            ink: 0.1,
+            bloom_intensity: 0.8,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Endless Figures".to_string(),
+        Settings {
+            draw_speed: 12.0,
+            auto_restart: true,
+            fade: 0.2,
+            ink: 0.3,
+            color_period: 20.0,
+            bloom_intensity: 1.0,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
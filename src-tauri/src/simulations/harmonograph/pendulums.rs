@@ -0,0 +1,114 @@
+//! # Harmonograph Pendulums
+//!
+//! A harmonograph's pen is swung by pendulums, each a damped sine wave:
+//! some swing it side to side, some up and down, and a rotary one swings
+//! the paper round in a circle under it. Their swings add, so frequencies
+//! in small whole ratios trace Lissajous figures, a slight detuning turns
+//! the figure slowly as it goes round, and the damping draws it spiralling
+//! inward until the pen comes to rest.
+//!
+//! Positions are worked out in double precision, the phases running into
+//! the thousands of radians over a long drawing.
+
+use rand::Rng;
+use std::f64::consts::TAU;
+
+use super::settings::{Axis, MAX_OSCILLATORS, MIN_OSCILLATORS, Oscillator};
+
+/// Radius the figure is scaled to fill at the start, in box heights
+pub const FIGURE_RADIUS: f32 = 0.9;
+
+/// Points traced for each swing of the fastest pendulum, so curves stay
+/// smooth however fast the pen goes
+const POINTS_PER_SWING: f64 = 96.0;
+
+/// Furthest the pen can reach along either axis at the start, adding up
+/// every pendulum swinging that way
+fn reach(oscillators: &[Oscillator]) -> f64 {
+    let along = |axis: Axis| -> f64 {
+        oscillators
+            .iter()
+            .filter(|o| o.axis == axis || o.axis == Axis::Rotary)
+            .map(|o| o.amplitude.abs() as f64)
+            .sum()
+    };
+    along(Axis::X).max(along(Axis::Y)).max(1e-6)
+}
+
+/// Where the pen is `time` seconds into the drawing, scaled so the figure
+/// starts out [`FIGURE_RADIUS`] across
+pub fn pen_position(oscillators: &[Oscillator], time: f64) -> [f32; 2] {
+    let scale = FIGURE_RADIUS as f64 / reach(oscillators);
+    let mut position = [0.0f64; 2];
+    for o in oscillators {
+        let angle = TAU * o.frequency as f64 * time + (o.phase as f64).to_radians();
+        let swing = o.amplitude as f64 * (-(o.damping as f64) * time).exp();
+        match o.axis {
+            Axis::X => position[0] += swing * angle.sin(),
+            Axis::Y => position[1] += swing * angle.sin(),
+            Axis::Rotary => {
+                position[0] += swing * angle.sin();
+                position[1] += swing * angle.cos();
+            }
+        }
+    }
+    [(position[0] * scale) as f32, (position[1] * scale) as f32]
+}
+
+/// How much of their first swing the pendulums still have `time` seconds
+/// in, all together
+pub fn swing(oscillators: &[Oscillator], time: f64) -> f32 {
+    let (left, first) = oscillators.iter().fold((0.0, 0.0), |(left, first), o| {
+        let amplitude = o.amplitude.abs() as f64;
+        (
+            left + amplitude * (-(o.damping as f64) * time).exp(),
+            first + amplitude,
+        )
+    });
+    if first > 0.0 {
+        (left / first) as f32
+    } else {
+        0.0
+    }
+}
+
+/// The pen's path from `from` to `to` seconds in, both ends included, as
+/// points close enough together to join with straight lines
+pub fn trace(oscillators: &[Oscillator], from: f64, to: f64, points: &mut Vec<[f32; 2]>) {
+    points.clear();
+    let fastest = oscillators
+        .iter()
+        .map(|o| o.frequency.abs() as f64)
+        .fold(0.0, f64::max);
+    let steps = ((to - from) * fastest * POINTS_PER_SWING).ceil().max(1.0) as usize;
+    points.extend((0..=steps).map(|i| {
+        let time = from + (to - from) * i as f64 / steps as f64;
+        pen_position(oscillators, time)
+    }));
+}
+
+/// `count` pendulums at frequencies in small whole ratios to each other,
+/// each a little out of tune, half across and half up and down with now
+/// and then a rotary one
+pub fn random_oscillators(count: usize, rng: &mut impl Rng) -> Vec<Oscillator> {
+    let count = count.clamp(MIN_OSCILLATORS, MAX_OSCILLATORS);
+    let base = rng.random_range(0.5..1.5);
+    (0..count)
+        .map(|index| {
+            let axis = match index % 2 {
+                0 if index > 0 && rng.random_bool(0.3) => Axis::Rotary,
+                0 => Axis::X,
+                _ => Axis::Y,
+            };
+            let ratio = rng.random_range(1..=4) as f32;
+            let detune = 1.0 + rng.random_range(-0.005..0.005);
+            Oscillator {
+                axis,
+                frequency: base * ratio * detune,
+                phase: rng.random_range(0.0..360.0),
+                amplitude: rng.random_range(0.5..1.0),
+                damping: rng.random_range(0.004..0.03),
+            }
+        })
+        .collect()
+}
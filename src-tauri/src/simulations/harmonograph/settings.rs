@@ -0,0 +1,251 @@
+//! # Harmonograph Settings Module
+//!
+//! The pendulums swinging the pen and how quickly they die down, how fast
+//! and how heavily the pen draws, and how the ink is colored and glows.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Pendulums a harmonograph can have
+pub const MIN_OSCILLATORS: usize = 2;
+pub const MAX_OSCILLATORS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Axis {
+    /// Swings the pen side to side
+    #[default]
+    X,
+    /// Swings the pen up and down
+    Y,
+    /// Swings the paper round in a circle under the pen
+    Rotary,
+}
+
+impl FromStr for Axis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "x" => Ok(Axis::X),
+            "y" => Ok(Axis::Y),
+            "rotary" => Ok(Axis::Rotary),
+            _ => Err(format!(
+                "Invalid Axis: '{}'. Expected 'X', 'Y', or 'Rotary'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Oscillator {
+    pub axis: Axis,
+    /// Swings per second of drawing
+    pub frequency: f32,
+    /// Degrees into its swing at the start
+    pub phase: f32,
+    /// How far it swings, relative to the others
+    pub amplitude: f32,
+    /// Share of its swing lost each second, as the rate of an exponential
+    pub damping: f32,
+}
+
+impl Oscillator {
+    pub fn new(axis: Axis, frequency: f32, phase: f32) -> Self {
+        Self {
+            axis,
+            frequency,
+            phase,
+            amplitude: 1.0,
+            damping: 0.01,
+        }
+    }
+}
+
+/// The lateral harmonograph most figures are drawn on: two pendulums on
+/// each axis, the second of each a little out of tune so the figure turns
+/// as it shrinks. Fewer than four drops the second of each.
+pub fn lateral(count: usize) -> Vec<Oscillator> {
+    let all = [
+        Oscillator::new(Axis::X, 2.0, 90.0),
+        Oscillator::new(Axis::Y, 3.0, 0.0),
+        Oscillator::new(Axis::X, 3.006, 0.0),
+        Oscillator::new(Axis::Y, 2.0, 270.0),
+    ];
+    all[..count.clamp(MIN_OSCILLATORS, MAX_OSCILLATORS)].to_vec()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorBy {
+    /// Through the color scheme as the drawing goes on, every
+    /// `color_period` seconds
+    #[default]
+    Time,
+    /// By how fast the pen is moving, slow to fast
+    Speed,
+    /// By which way the pen is moving
+    Direction,
+}
+
+impl FromStr for ColorBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "time" => Ok(ColorBy::Time),
+            "speed" => Ok(ColorBy::Speed),
+            "direction" => Ok(ColorBy::Direction),
+            _ => Err(format!(
+                "Invalid ColorBy: '{}'. Expected 'Time', 'Speed', or 'Direction'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub oscillators: Vec<Oscillator>,
+    /// Seconds of drawing each second
+    pub draw_speed: f32,
+    /// Start a new figure, with new frequencies, once the pen has all but
+    /// stopped
+    pub auto_restart: bool,
+
+    /// Width of the pen, in pixels of the paper
+    pub pen_width: f32,
+    /// Ink laid down each pass of the pen, before the exposure
+    pub ink: f32,
+    /// Share of the ink fading from the paper each second
+    pub fade: f32,
+    /// Pixels across the square paper the figure is drawn on
+    pub paper_resolution: u32,
+
+    pub color_by: ColorBy,
+    /// Seconds of drawing for one trip through the color scheme
+    pub color_period: f32,
+    /// How bright the ink looks; heavily inked paper saturates toward white
+    pub exposure: f32,
+    /// Brightness of the ink above which it glows
+    pub bloom_threshold: f32,
+    pub bloom_intensity: f32,
+    /// Glow radius in pixels of the screen
+    pub bloom_radius: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            oscillators: lateral(MAX_OSCILLATORS),
+            draw_speed: 4.0,
+            auto_restart: false,
+            pen_width: 1.5,
+            ink: 0.15,
+            fade: 0.0,
+            paper_resolution: 2048,
+            color_by: ColorBy::Time,
+            color_period: 60.0,
+            exposure: 1.0,
+            bloom_threshold: 0.8,
+            bloom_intensity: 0.5,
+            bloom_radius: 12.0,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "oscillator_count",
+            Rule::Count {
+                min: MIN_OSCILLATORS as u64,
+                max: MAX_OSCILLATORS as u64,
+            },
+        ),
+        (
+            "draw_speed",
+            Rule::Range {
+                min: 0.0,
+                max: 100.0,
+            },
+        ),
+        ("auto_restart", Rule::Flag),
+        (
+            "pen_width",
+            Rule::Range {
+                min: 0.5,
+                max: 16.0,
+            },
+        ),
+        (
+            "ink",
+            Rule::Range {
+                min: 0.001,
+                max: 5.0,
+            },
+        ),
+        ("fade", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "paper_resolution",
+            Rule::Count {
+                min: 256,
+                max: 4096,
+            },
+        ),
+        ("color_by", Rule::OneOf(&["Time", "Speed", "Direction"])),
+        (
+            "color_period",
+            Rule::Range {
+                min: 0.1,
+                max: 1000.0,
+            },
+        ),
+        (
+            "exposure",
+            Rule::Range {
+                min: 0.01,
+                max: 20.0,
+            },
+        ),
+        (
+            "bloom_threshold",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        ("bloom_intensity", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "bloom_radius",
+            Rule::Range {
+                min: 0.0,
+                max: 128.0,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("oscillators", SettingCategory::Generators),
+    ("ink", SettingCategory::Colors),
+    ("color_by", SettingCategory::Colors),
+    ("color_period", SettingCategory::Colors),
+    ("exposure", SettingCategory::Colors),
+    ("bloom_threshold", SettingCategory::Colors),
+    ("bloom_intensity", SettingCategory::Colors),
+    ("bloom_radius", SettingCategory::Colors),
+]);
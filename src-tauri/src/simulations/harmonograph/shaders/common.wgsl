@@ -0,0 +1,33 @@
+// Shared by the pen and composite passes: the paper is a square covering
+// -1 to 1 on both axes, drawn on in its own coordinates and looked at
+// through the camera.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    // Box heights per screen pixel
+    pixel_size: f32,
+    // Width of the pen, in box heights
+    pen_width: f32,
+    ink: f32,
+    exposure: f32,
+    bloom_threshold: f32,
+    bloom_intensity: f32,
+    // In screen pixels
+    bloom_radius: f32,
+    // Pixels across the paper
+    paper_size: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+    _pad3: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+
+// One triangle covering the target
+fn fullscreen_ndc(vertex_index: u32) -> vec2<f32> {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return uv * 2.0 - 1.0;
+}
@@ -0,0 +1,65 @@
+// Shows the paper through the camera, the ink's brightness rolled off so
+// heavily inked lines saturate toward white, with the brightest glowing
+// out over their surroundings.
+
+@group(0) @binding(1) var paper_texture: texture_2d<f32>;
+@group(0) @binding(2) var paper_sampler: sampler;
+
+const BLOOM_TAPS: u32 = 48u;
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let ndc = fullscreen_ndc(vertex_index);
+    return VertexOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+// Where on the paper a point in the box lies; paper rows run top down
+fn paper_uv(point: vec2<f32>) -> vec2<f32> {
+    return vec2<f32>(point.x + 1.0, 1.0 - point.y) * 0.5;
+}
+
+// Ink under a screen pixel, from four taps so thin lines survive the paper
+// being shown smaller than it's drawn
+fn ink(uv: vec2<f32>) -> vec3<f32> {
+    let quarter = params.pixel_size * 0.125;
+    var sum = vec3<f32>(0.0);
+    for (var i = 0u; i < 4u; i++) {
+        let offset = vec2<f32>(f32(i & 1u) * 2.0 - 1.0, f32(i >> 1u) * 2.0 - 1.0) * quarter;
+        sum += textureSampleLevel(paper_texture, paper_sampler, uv + offset, 0.0).rgb;
+    }
+    return sum * 0.25 * params.exposure;
+}
+
+// Bright ink gathered over a disc, golden angle spiral taps
+fn bloom(uv: vec2<f32>) -> vec3<f32> {
+    var sum = vec3<f32>(0.0);
+    for (var i = 0u; i < BLOOM_TAPS; i++) {
+        let radius = sqrt((f32(i) + 0.5) / f32(BLOOM_TAPS)) * params.bloom_radius;
+        let angle = f32(i) * GOLDEN_ANGLE;
+        // Screen pixels to box heights, then to the paper's 0 to 1
+        let offset = vec2<f32>(cos(angle), sin(angle)) * radius * params.pixel_size * 0.5;
+        let sample = textureSampleLevel(paper_texture, paper_sampler, uv + offset, 0.0).rgb;
+        sum += max(sample * params.exposure - vec3<f32>(params.bloom_threshold), vec3<f32>(0.0));
+    }
+    return sum / f32(BLOOM_TAPS);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let view = input.ndc / params.view_zoom + params.view_center;
+    let uv = paper_uv(vec2<f32>(view.x * params.aspect, view.y));
+    if (any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0))) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    var color = ink(uv);
+    if (params.bloom_intensity > 0.0 && params.bloom_radius > 0.0) {
+        color += bloom(uv) * params.bloom_intensity;
+    }
+    return vec4<f32>(vec3<f32>(1.0) - exp(-color), 1.0);
+}
@@ -0,0 +1,7 @@
+pub const PEN_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("common.wgsl"),
+    include_str!("pen.wgsl")
+);
+pub const COMPOSITE_SHADER: &str =
+    concat!(include_str!("common.wgsl"), include_str!("composite.wgsl"));
@@ -0,0 +1,73 @@
+// Draws onto the paper, once `trails.wgsl` has faded it: each stretch of
+// the pen's path since the last frame as a quad the width of the pen, its
+// ink added to whatever the pen has laid down before.
+
+struct Segment {
+    start: vec2<f32>,
+    end: vec2<f32>,
+    // Where its ink falls in the color scheme
+    shade: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+@group(0) @binding(1) var<storage, read> segments: array<Segment>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+struct SegmentOutput {
+    @builtin(position) position: vec4<f32>,
+    // Distance from the middle of the line, in box heights
+    @location(0) across: f32,
+    @location(1) shade: f32,
+}
+
+@vertex
+fn vs_segment(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> SegmentOutput {
+    let segment = segments[instance_index];
+    let along = segment.end - segment.start;
+    let span = length(along);
+    var direction = vec2<f32>(1.0, 0.0);
+    if (span > 1e-7) {
+        direction = along / span;
+    }
+    let normal = vec2<f32>(-direction.y, direction.x);
+
+    // Two triangles, widened a paper pixel past the pen for antialiasing
+    let corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let half_width = params.pen_width * 0.5 + 2.0 / params.paper_size;
+    let point = mix(segment.start, segment.end, corner.x) + normal * corner.y * half_width;
+
+    var output: SegmentOutput;
+    output.position = vec4<f32>(point, 0.0, 1.0);
+    output.across = corner.y * half_width;
+    output.shade = segment.shade;
+    return output;
+}
+
+@fragment
+fn fs_segment(input: SegmentOutput) -> @location(0) vec4<f32> {
+    let texel = 2.0 / params.paper_size;
+    let coverage = clamp((params.pen_width * 0.5 - abs(input.across)) / texel + 0.5, 0.0, 1.0);
+    return vec4<f32>(lut_color(input.shade) * params.ink * coverage, 0.0);
+}
@@ -0,0 +1,801 @@
+//! # Harmonograph Simulation Module
+//!
+//! A pen swung by two to four damped pendulums drawing Lissajous figures
+//! that turn and spiral inward as the swing dies down. Ink builds up where
+//! the pen passes again and again, so the figure's crossings and turning
+//! points glow brightest.
+//!
+//! ## Technical Overview
+//!
+//! The pen's path is worked out on the CPU in
+//! [`pendulums`](super::pendulums), a few hundred points a swing. Each
+//! frame the stretch drawn since the last is uploaded as segments:
+//! 1. A pen pass draws onto a square floating point paper, kept as
+//!    [`Trails`] over black so fading the ink darkens it, adding each
+//!    segment's ink over what's left.
+//! 2. A composite pass shows the paper through the camera, with a roll off
+//!    toward white and bloom gathered from the brightest ink.
+//!
+//! The paper is only cleared when a figure starts over, so zooming and
+//! panning look over a drawing without disturbing it.
+//!
+//! [`Trails`]: crate::simulations::shared::Trails
+
+use bytemuck::{Pod, Zeroable};
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, BlendComponent,
+    BlendFactor, BlendOperation, BlendState, Buffer, BufferDescriptor, BufferUsages, Device,
+    PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, Sampler,
+    ShaderModule, ShaderStages, SurfaceConfiguration, TextureFormat, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, FadeCurve, Trails,
+};
+use crate::simulations::traits::Simulation;
+
+use super::pendulums::{FIGURE_RADIUS, random_oscillators, swing, trace};
+use super::settings::{ColorBy, MAX_OSCILLATORS, MIN_OSCILLATORS, Oscillator, Settings, lateral};
+use super::shaders::{COMPOSITE_SHADER, PEN_SHADER};
+use super::state::State;
+
+/// Ink piles up well past white where the pen crosses itself, so the
+/// paper is kept in floating point
+const PAPER_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Longest stretch of drawing taken in one frame, so a stall doesn't send
+/// the segment buffer soaring
+const MAX_FRAME_TIME: f32 = 0.25;
+
+/// Share of the first swing left by which an automatic restart begins the
+/// next figure
+const RESTART_SWING: f32 = 0.02;
+
+/// Segments room is made for up front; the buffer grows past this as needed
+const INITIAL_SEGMENTS: usize = 1024;
+
+/// Adds ink to what's already on the paper
+const INK_BLEND: BlendState = BlendState {
+    color: BlendComponent {
+        src_factor: BlendFactor::One,
+        dst_factor: BlendFactor::One,
+        operation: BlendOperation::Add,
+    },
+    alpha: BlendComponent {
+        src_factor: BlendFactor::One,
+        dst_factor: BlendFactor::One,
+        operation: BlendOperation::Add,
+    },
+};
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    pixel_size: f32,
+    pen_width: f32,
+    ink: f32,
+    exposure: f32,
+    bloom_threshold: f32,
+    bloom_intensity: f32,
+    bloom_radius: f32,
+    paper_size: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+    _pad3: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct Segment {
+    start: [f32; 2],
+    end: [f32; 2],
+    shade: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+/// Between two and four pendulums, topping up from the lateral ones if
+/// there are too few
+fn fitted(mut oscillators: Vec<Oscillator>) -> Vec<Oscillator> {
+    oscillators.truncate(MAX_OSCILLATORS);
+    if oscillators.len() < MIN_OSCILLATORS {
+        let missing = lateral(MIN_OSCILLATORS).split_off(oscillators.len());
+        oscillators.extend(missing);
+    }
+    oscillators
+}
+
+#[derive(Debug)]
+pub struct HarmonographModel {
+    pub settings: Settings,
+    pub state: State,
+    // Kept in double precision so the pen picks up each frame exactly where
+    // it left off, however long the drawing has run
+    pen_time: f64,
+
+    // GPU resources
+    segment_pipeline: RenderPipeline,
+    composite_pipeline: RenderPipeline,
+    pen_bind_group_layout: BindGroupLayout,
+    composite_bind_group_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    segment_buffer: Buffer,
+    segment_capacity: usize,
+    sampler: Sampler,
+    paper: Trails,
+    pen_bind_group: BindGroup,
+
+    // The pen's path and the segments drawn along it this frame, kept to
+    // save reallocating
+    points: Vec<[f32; 2]>,
+    segments: Vec<Segment>,
+
+    // The paper wants wiping before the pen next draws
+    clear_paper: bool,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl HarmonographModel {
+    pub fn new(
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        mut settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        settings.oscillators = fitted(settings.oscillators);
+        let state = State {
+            swing: swing(&settings.oscillators, 0.0),
+            ..State::default()
+        };
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let pen_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Harmonograph Pen Shader"),
+            source: wgpu::ShaderSource::Wgsl(PEN_SHADER.into()),
+        });
+        let composite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Harmonograph Composite Shader"),
+            source: wgpu::ShaderSource::Wgsl(COMPOSITE_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Harmonograph Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Harmonograph LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let segment_buffer = create_segment_buffer(device, INITIAL_SEGMENTS);
+        let sampler = resource_helpers::create_linear_sampler(
+            device,
+            "Harmonograph Paper Sampler",
+            wgpu::FilterMode::Linear,
+        );
+        let paper = create_paper(device, settings.paper_resolution);
+
+        let pen_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Harmonograph Pen Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::VERTEX, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::FRAGMENT, true),
+            ],
+        });
+        let composite_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Harmonograph Composite Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::FRAGMENT),
+                    resource_helpers::texture_entry(
+                        1,
+                        ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        2,
+                        ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                ],
+            });
+
+        let segment_pipeline = create_pipeline(
+            device,
+            "Harmonograph Segment Pipeline",
+            &pen_bind_group_layout,
+            &pen_module,
+            ("vs_segment", "fs_segment"),
+            (PAPER_FORMAT, INK_BLEND),
+        );
+        let composite_pipeline = create_pipeline(
+            device,
+            "Harmonograph Composite Pipeline",
+            &composite_bind_group_layout,
+            &composite_module,
+            ("vs_main", "fs_main"),
+            (surface_config.format, BlendState::REPLACE),
+        );
+
+        let pen_bind_group = create_pen_bind_group(
+            device,
+            &pen_bind_group_layout,
+            &params_buffer,
+            &segment_buffer,
+            &lut_buffer,
+        );
+
+        Ok(Self {
+            settings,
+            state,
+            pen_time: 0.0,
+            segment_pipeline,
+            composite_pipeline,
+            pen_bind_group_layout,
+            composite_bind_group_layout,
+            params_buffer,
+            lut_buffer,
+            segment_buffer,
+            segment_capacity: INITIAL_SEGMENTS,
+            sampler,
+            paper,
+            pen_bind_group,
+            points: Vec::new(),
+            segments: Vec::new(),
+            clear_paper: true,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        })
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// Start the figure over on clean paper
+    fn restart_figure(&mut self) {
+        self.pen_time = 0.0;
+        self.state.pen_time = 0.0;
+        self.state.swing = swing(&self.settings.oscillators, 0.0);
+        self.clear_paper = true;
+    }
+
+    fn rebuild_paper(&mut self, device: &Arc<Device>) {
+        let resolution = self.settings.paper_resolution;
+        self.paper.resize(device, (resolution, resolution));
+        self.clear_paper = true;
+    }
+
+    /// Lay out the segments the pen draws over the next `delta_time`
+    /// seconds, starting the next figure if the pen has all but stopped
+    fn advance(&mut self, delta_time: f32) {
+        self.segments.clear();
+        let from = self.pen_time;
+        let to = from + (self.settings.draw_speed * delta_time.min(MAX_FRAME_TIME)) as f64;
+        if to > from {
+            let oscillators = &self.settings.oscillators;
+            trace(oscillators, from, to, &mut self.points);
+            let step = (to - from) / (self.points.len() - 1) as f64;
+            // A pen swung flat out by every pendulum at once, for scaling
+            // the speed between 0 and 1
+            let top_speed = FIGURE_RADIUS
+                * std::f32::consts::TAU
+                * oscillators
+                    .iter()
+                    .map(|o| o.frequency.abs())
+                    .fold(0.0, f32::max)
+                    .max(1e-6);
+            let color_by = self.settings.color_by;
+            let color_period = self.settings.color_period.max(1e-3) as f64;
+            self.segments
+                .extend(self.points.windows(2).enumerate().map(|(i, pair)| {
+                    let [start, end] = [pair[0], pair[1]];
+                    let (dx, dy) = (end[0] - start[0], end[1] - start[1]);
+                    let shade = match color_by {
+                        ColorBy::Time => {
+                            let time = from + (i as f64 + 0.5) * step;
+                            (time / color_period).fract() as f32
+                        }
+                        ColorBy::Speed => (dx.hypot(dy) / step as f32 / top_speed).min(1.0),
+                        ColorBy::Direction => dy.atan2(dx) / std::f32::consts::TAU + 0.5,
+                    };
+                    Segment {
+                        start,
+                        end,
+                        shade,
+                        _pad0: 0.0,
+                        _pad1: 0.0,
+                        _pad2: 0.0,
+                    }
+                }));
+        }
+        self.pen_time = to;
+        self.state.pen_time = to as f32;
+        self.state.swing = swing(&self.settings.oscillators, to);
+
+        if self.settings.auto_restart && self.state.swing < RESTART_SWING {
+            self.settings.oscillators =
+                random_oscillators(self.settings.oscillators.len(), &mut rand::rng());
+            self.state.figures += 1;
+            self.restart_figure();
+        }
+    }
+
+    /// Grow the segment buffer to fit this frame's segments
+    fn reserve_segments(&mut self, device: &Arc<Device>) {
+        if self.segments.len() <= self.segment_capacity {
+            return;
+        }
+        self.segment_capacity = self.segments.len().next_power_of_two();
+        self.segment_buffer = create_segment_buffer(device, self.segment_capacity);
+        self.pen_bind_group = create_pen_bind_group(
+            device,
+            &self.pen_bind_group_layout,
+            &self.params_buffer,
+            &self.segment_buffer,
+            &self.lut_buffer,
+        );
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let paper_size = self.settings.paper_resolution as f32;
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            pixel_size: 2.0 / (self.camera.zoom * self.height.max(1) as f32),
+            pen_width: self.settings.pen_width * 2.0 / paper_size,
+            ink: self.settings.ink,
+            exposure: self.settings.exposure,
+            bloom_threshold: self.settings.bloom_threshold,
+            bloom_intensity: self.settings.bloom_intensity,
+            bloom_radius: self.settings.bloom_radius,
+            paper_size,
+            _pad0: 0.0,
+            _pad1: 0.0,
+            _pad2: 0.0,
+            _pad3: 0.0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    /// Draw this frame's segments onto the paper, fading it by `fade_time`
+    /// seconds' worth first, then show the paper
+    fn draw(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        fade_time: f32,
+    ) {
+        self.reserve_segments(device);
+        if !self.segments.is_empty() {
+            queue.write_buffer(
+                &self.segment_buffer,
+                0,
+                bytemuck::cast_slice(&self.segments),
+            );
+        }
+        self.update_params(queue);
+        if self.clear_paper {
+            self.paper.clear(device, queue, wgpu::Color::BLACK);
+            self.clear_paper = false;
+        }
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Harmonograph Render"),
+        });
+        let keep = (1.0 - self.settings.fade.clamp(0.0, 1.0)).powf(fade_time.max(0.0));
+        if keep < 1.0 || !self.segments.is_empty() {
+            {
+                // Fading over black takes the same share off every channel
+                let mut render_pass = self.paper.begin_pass(
+                    device,
+                    queue,
+                    &mut encoder,
+                    FadeCurve::Linear,
+                    keep,
+                    wgpu::Color::BLACK,
+                );
+                if !self.segments.is_empty() {
+                    render_pass.set_pipeline(&self.segment_pipeline);
+                    render_pass.set_bind_group(0, &self.pen_bind_group, &[]);
+                    render_pass.draw(0..6, 0..self.segments.len() as u32);
+                }
+            }
+            self.paper.swap();
+        }
+        let composite_bind_group = create_composite_bind_group(
+            device,
+            &self.composite_bind_group_layout,
+            &self.params_buffer,
+            self.paper.view(),
+            &self.sampler,
+        );
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Harmonograph Composite Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&self.composite_pipeline);
+            render_pass.set_bind_group(0, &composite_bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+        queue.submit([encoder.finish()]);
+    }
+}
+
+/// A square of paper `resolution` pixels across, losing all its ink a frame
+/// at fade 1
+fn create_paper(device: &Device, resolution: u32) -> Trails {
+    Trails::new(
+        device,
+        "Harmonograph Paper",
+        (resolution, resolution),
+        1.0,
+        PAPER_FORMAT,
+        wgpu::FilterMode::Nearest,
+    )
+    .with_max_fade(1.0)
+}
+
+fn create_segment_buffer(device: &Device, capacity: usize) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some("Harmonograph Segment Buffer"),
+        size: (capacity * std::mem::size_of::<Segment>()) as u64,
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+fn create_pen_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    segment_buffer: &Buffer,
+    lut_buffer: &Buffer,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Harmonograph Pen Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, segment_buffer),
+            resource_helpers::buffer_entry(2, lut_buffer),
+        ],
+    })
+}
+
+fn create_composite_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    paper_view: &TextureView,
+    sampler: &Sampler,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Harmonograph Composite Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::texture_view_entry(1, paper_view),
+            resource_helpers::sampler_bind_entry(2, sampler),
+        ],
+    })
+}
+
+fn create_pipeline(
+    device: &Device,
+    label: &str,
+    bind_group_layout: &BindGroupLayout,
+    module: &ShaderModule,
+    (vertex_entry, fragment_entry): (&str, &str),
+    (format, blend): (TextureFormat, BlendState),
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(&format!("{} Layout", label)),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex_entry),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for HarmonographModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        self.advance(delta_time);
+        self.draw(device, queue, surface_view, delta_time);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.segments.clear();
+        self.draw(device, queue, surface_view, 0.0);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        // The paper is its own size whatever the window's, so only the view
+        // of it changes
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        _world_x: f32,
+        _world_y: f32,
+        _mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // The pendulums swing on their own; there's nothing to grab
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut new_settings: Settings = serde_json::from_value(settings)?;
+        new_settings.oscillators = fitted(new_settings.oscillators);
+        let resolution_changed = new_settings.paper_resolution != self.settings.paper_resolution;
+        self.settings = new_settings;
+        if resolution_changed {
+            self.rebuild_paper(device);
+        }
+        self.restart_figure();
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.figures = 1;
+        self.restart_figure();
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.settings.oscillators =
+            random_oscillators(self.settings.oscillators.len(), &mut rand::rng());
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "oscillator_count" => {
+                let count = number(setting_name, &value)? as usize;
+                self.settings.oscillators = lateral(count);
+                self.restart_figure();
+            }
+            "oscillators" => {
+                let oscillators: Vec<Oscillator> = serde_json::from_value(value)?;
+                self.settings.oscillators = fitted(oscillators);
+                self.restart_figure();
+            }
+            "draw_speed" => self.settings.draw_speed = number(setting_name, &value)? as f32,
+            "auto_restart" => self.settings.auto_restart = value.as_bool().unwrap_or(false),
+            "pen_width" => self.settings.pen_width = number(setting_name, &value)? as f32,
+            "ink" => self.settings.ink = number(setting_name, &value)? as f32,
+            "fade" => self.settings.fade = number(setting_name, &value)? as f32,
+            "paper_resolution" => {
+                let resolution = number(setting_name, &value)? as u32;
+                if resolution != self.settings.paper_resolution {
+                    self.settings.paper_resolution = resolution;
+                    self.rebuild_paper(device);
+                    self.restart_figure();
+                }
+            }
+            "color_by" => {
+                self.settings.color_by =
+                    value.as_str().ok_or("color_by must be a string")?.parse()?;
+            }
+            "color_period" => self.settings.color_period = number(setting_name, &value)? as f32,
+            "exposure" => self.settings.exposure = number(setting_name, &value)? as f32,
+            "bloom_threshold" => {
+                self.settings.bloom_threshold = number(setting_name, &value)? as f32
+            }
+            "bloom_intensity" => {
+                self.settings.bloom_intensity = number(setting_name, &value)? as f32
+            }
+            "bloom_radius" => self.settings.bloom_radius = number(setting_name, &value)? as f32,
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
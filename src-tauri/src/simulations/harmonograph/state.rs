@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Seconds the pen has been drawing the current figure
+    pub pen_time: f32,
+    // How much of its first swing the pen still has, 1 to 0
+    pub swing: f32,
+    // Figures drawn since the last reset, counting the current one
+    pub figures: u32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            pen_time: 0.0,
+            swing: 1.0,
+            figures: 1,
+            color_scheme_name: "MATPLOTLIB_plasma".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::pendulums::{FIGURE_RADIUS, pen_position, random_oscillators, swing, trace};
+use super::settings::{
+    Axis, ColorBy, MAX_OSCILLATORS, MIN_OSCILLATORS, Oscillator, Settings, lateral,
+};
+use super::shaders::{COMPOSITE_SHADER, PEN_SHADER};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn undamped(axis: Axis, frequency: f32, phase: f32) -> Oscillator {
+    Oscillator {
+        damping: 0.0,
+        ..Oscillator::new(axis, frequency, phase)
+    }
+}
+
+#[test]
+fn lateral_harmonograph_keeps_between_two_and_four_pendulums() {
+    assert_eq!(lateral(0).len(), MIN_OSCILLATORS);
+    assert_eq!(lateral(3).len(), 3);
+    assert_eq!(lateral(9).len(), MAX_OSCILLATORS);
+    let axes: Vec<Axis> = lateral(2).iter().map(|o| o.axis).collect();
+    assert_eq!(axes, [Axis::X, Axis::Y]);
+    assert_eq!(Settings::default().oscillators.len(), MAX_OSCILLATORS);
+}
+
+#[test]
+fn pen_stays_within_the_figure() {
+    let mut oscillators = lateral(4);
+    oscillators.push(Oscillator {
+        amplitude: 0.7,
+        ..Oscillator::new(Axis::Rotary, 1.3, 40.0)
+    });
+    let mut furthest = 0.0f32;
+    for step in 0..20_000 {
+        let [x, y] = pen_position(&oscillators, step as f64 * 0.01);
+        assert!(x.abs() <= FIGURE_RADIUS + 1e-5 && y.abs() <= FIGURE_RADIUS + 1e-5);
+        furthest = furthest.max(x.abs()).max(y.abs());
+    }
+    // Scaled to fill the figure, not shrunk well inside it
+    assert!(furthest > FIGURE_RADIUS * 0.8);
+}
+
+#[test]
+fn damping_winds_the_swing_down() {
+    let oscillators = lateral(4);
+    assert!((swing(&oscillators, 0.0) - 1.0).abs() < 1e-6);
+    // Every lateral pendulum loses a hundredth of its swing each second
+    let later = swing(&oscillators, 100.0);
+    assert!((later - (-1.0f32).exp()).abs() < 1e-5);
+    assert!(swing(&oscillators, 500.0) < later);
+
+    let [x, y] = pen_position(&oscillators, 1000.0);
+    assert!(x.hypot(y) < FIGURE_RADIUS * 0.01);
+}
+
+#[test]
+fn whole_ratios_without_damping_close_the_figure() {
+    // Two across to three up and down comes round again every second
+    let oscillators = [undamped(Axis::X, 2.0, 90.0), undamped(Axis::Y, 3.0, 0.0)];
+    for step in 0..50 {
+        let time = step as f64 * 0.037;
+        let [a, b] = [
+            pen_position(&oscillators, time),
+            pen_position(&oscillators, time + 1.0),
+        ];
+        assert!((a[0] - b[0]).abs() < 1e-5 && (a[1] - b[1]).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn unison_a_quarter_turn_apart_draws_a_circle() {
+    let oscillators = [undamped(Axis::X, 1.0, 90.0), undamped(Axis::Y, 1.0, 0.0)];
+    for step in 0..100 {
+        let [x, y] = pen_position(&oscillators, step as f64 * 0.013);
+        assert!((x.hypot(y) - FIGURE_RADIUS).abs() < 1e-5);
+    }
+    // A rotary pendulum alone draws the same circle
+    let rotary = [undamped(Axis::Rotary, 1.0, 0.0)];
+    let [x, y] = pen_position(&rotary, 0.3);
+    assert!((x.hypot(y) - FIGURE_RADIUS).abs() < 1e-5);
+}
+
+#[test]
+fn trace_is_fine_enough_to_join_with_straight_lines() {
+    let oscillators = lateral(4);
+    let mut points = Vec::new();
+    trace(&oscillators, 10.0, 12.5, &mut points);
+    assert_eq!(points[0], pen_position(&oscillators, 10.0));
+    assert_eq!(*points.last().unwrap(), pen_position(&oscillators, 12.5));
+    // No stretch longer than a small part of the fastest swing's circuit
+    let circuit = FIGURE_RADIUS * std::f32::consts::TAU;
+    for pair in points.windows(2) {
+        let step = (pair[1][0] - pair[0][0]).hypot(pair[1][1] - pair[0][1]);
+        assert!(step < circuit / 30.0, "step {}", step);
+    }
+
+    // Even a sliver of drawing has both ends
+    trace(&oscillators, 3.0, 3.0001, &mut points);
+    assert_eq!(points.len(), 2);
+}
+
+#[test]
+fn random_oscillators_are_nearly_whole_ratios_on_both_axes() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for count in MIN_OSCILLATORS..=MAX_OSCILLATORS {
+        for _ in 0..50 {
+            let oscillators = random_oscillators(count, &mut rng);
+            assert_eq!(oscillators.len(), count);
+            assert_eq!(oscillators[0].axis, Axis::X);
+            assert_eq!(oscillators[1].axis, Axis::Y);
+            let slowest = oscillators
+                .iter()
+                .map(|o| o.frequency)
+                .fold(f32::MAX, f32::min);
+            for o in &oscillators {
+                assert!(o.damping > 0.0 && o.amplitude > 0.0);
+            }
+            // Some whole multiple of the slowest puts every one at a whole
+            // multiple of the same base, give or take the detuning
+            let whole = (1..=4).any(|multiple| {
+                oscillators.iter().all(|o| {
+                    let ratio = o.frequency / slowest * multiple as f32;
+                    (ratio - ratio.round()).abs() < 0.05
+                })
+            });
+            assert!(whole, "{:?}", oscillators);
+        }
+    }
+}
+
+#[test]
+fn enums_parse_case_insensitively() {
+    assert_eq!("rotary".parse::<Axis>().unwrap(), Axis::Rotary);
+    assert_eq!("Y".parse::<Axis>().unwrap(), Axis::Y);
+    assert!("z".parse::<Axis>().is_err());
+    assert_eq!("SPEED".parse::<ColorBy>().unwrap(), ColorBy::Speed);
+    assert!("hue".parse::<ColorBy>().is_err());
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Harmonograph Pen Shader", PEN_SHADER),
+        ("Harmonograph Composite Shader", COMPOSITE_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
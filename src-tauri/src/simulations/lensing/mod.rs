@@ -0,0 +1,102 @@
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::LensingModel;
+
+use crate::simulation::preset_manager::{LensingPresetManager, Preset};
+
+/// Initialize Gravitational Lensing presets with built-in configurations
+pub fn init_presets(preset_manager: &mut LensingPresetManager) {
+    use settings::{Mass, Settings, mass_ring};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Face On".to_string(),
+        Settings {
+            disk_tilt: 20.0,
+            doppler: 0.1,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Einstein Ring".to_string(),
+        Settings {
+            masses: mass_ring(1, 0.0, 0.7),
+            horizon_size: 0.0,
+            show_disk: false,
+            star_density: 0.6,
+            nebula_strength: 0.6,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Binary Black Hole".to_string(),
+        Settings {
+            masses: mass_ring(2, 0.7, 0.35),
+            orbit_speed: 0.05,
+            disk_outer: 1.2,
+            disk_brightness: 2.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Galaxy Cluster".to_string(),
+        Settings {
+            masses: vec![
+                Mass {
+                    position: [0.0, 0.0],
+                    einstein_radius: 0.6,
+                },
+                Mass {
+                    position: [-1.1, 0.5],
+                    einstein_radius: 0.25,
+                },
+                Mass {
+                    position: [0.9, -0.6],
+                    einstein_radius: 0.3,
+                },
+                Mass {
+                    position: [0.6, 0.9],
+                    einstein_radius: 0.15,
+                },
+                Mass {
+                    position: [-0.5, -1.0],
+                    einstein_radius: 0.2,
+                },
+            ],
+            horizon_size: 0.0,
+            show_disk: false,
+            star_density: 0.8,
+            nebula_strength: 0.8,
+            bloom_intensity: 0.3,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Quasar".to_string(),
+        Settings {
+            disk_inner: 0.45,
+            disk_outer: 2.4,
+            disk_brightness: 5.0,
+            disk_spin: 1.2,
+            doppler: 0.8,
+            bloom_threshold: 0.6,
+            bloom_intensity: 1.2,
+            bloom_radius: 32.0,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,255 @@
+//! # Gravitational Lensing Settings Module
+//!
+//! The masses bending the light and where they sit, what lies behind them,
+//! the accretion disks around them and how brightly those glow.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Masses the shader has room for
+pub const MAX_MASSES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Mass {
+    /// Where it sits in the lens plane
+    pub position: [f32; 2],
+    /// Radius of the ring a source straight behind it is spread into. Its
+    /// square is proportional to the mass.
+    pub einstein_radius: f32,
+}
+
+/// `count` masses evenly spaced on a circle of `radius`, the first to the
+/// right, or a single one in the middle
+pub fn mass_ring(count: usize, radius: f32, einstein_radius: f32) -> Vec<Mass> {
+    if count == 1 {
+        return vec![Mass {
+            position: [0.0, 0.0],
+            einstein_radius,
+        }];
+    }
+    (0..count)
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / count as f32;
+            Mass {
+                position: [radius * angle.cos(), radius * angle.sin()],
+                einstein_radius,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LensedBackground {
+    /// Procedural stars over faint nebulae
+    #[default]
+    Starfield,
+    /// The last loaded image, mirrored out past its edges
+    Image,
+}
+
+impl FromStr for LensedBackground {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "starfield" => Ok(LensedBackground::Starfield),
+            "image" => Ok(LensedBackground::Image),
+            _ => Err(format!(
+                "Invalid LensedBackground: '{}'. Expected 'starfield' or 'image'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Up to [`MAX_MASSES`]
+    pub masses: Vec<Mass>,
+    /// Turns per second the masses orbit their shared center of mass
+    pub orbit_speed: f32,
+
+    pub background: LensedBackground,
+    /// Height of the loaded image in the source plane, relative to the
+    /// height of the view
+    pub image_scale: f32,
+    /// Share of the starfield's cells holding a star
+    pub star_density: f32,
+    pub star_brightness: f32,
+    pub nebula_strength: f32,
+
+    /// Radius of the black shadow, in Einstein radii. Zero lenses without
+    /// hiding anything, like a star or a galaxy would.
+    pub horizon_size: f32,
+    pub show_disk: bool,
+    /// Edges of the accretion disks, in Einstein radii
+    pub disk_inner: f32,
+    pub disk_outer: f32,
+    /// Degrees the disks are tipped away from facing the viewer
+    pub disk_tilt: f32,
+    pub disk_brightness: f32,
+    /// Radians per second the inner edge of each disk turns, with the outer
+    /// parts slower as in an orbit
+    pub disk_spin: f32,
+    /// How much brighter the side turning toward the viewer is
+    pub doppler: f32,
+
+    /// Linear brightness above which the image glows
+    pub bloom_threshold: f32,
+    pub bloom_intensity: f32,
+    /// Glow radius in pixels
+    pub bloom_radius: f32,
+
+    /// Half the height of the plane shown at zoom 1
+    pub extent: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            masses: mass_ring(1, 0.0, 0.5),
+            orbit_speed: 0.0,
+            background: LensedBackground::Starfield,
+            image_scale: 1.0,
+            star_density: 0.35,
+            star_brightness: 1.0,
+            nebula_strength: 0.35,
+            horizon_size: 0.4,
+            show_disk: true,
+            disk_inner: 0.55,
+            disk_outer: 1.5,
+            disk_tilt: 80.0,
+            disk_brightness: 2.5,
+            disk_spin: 0.6,
+            doppler: 0.5,
+            bloom_threshold: 0.8,
+            bloom_intensity: 0.6,
+            bloom_radius: 18.0,
+            extent: 2.0,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "mass_count",
+            Rule::Count {
+                min: 1,
+                max: MAX_MASSES as u64,
+            },
+        ),
+        (
+            "einstein_radius",
+            Rule::Range {
+                min: 0.01,
+                max: 5.0,
+            },
+        ),
+        (
+            "orbit_speed",
+            Rule::Range {
+                min: -2.0,
+                max: 2.0,
+            },
+        ),
+        ("background", Rule::OneOf(&["Starfield", "Image"])),
+        (
+            "image_scale",
+            Rule::Range {
+                min: 0.1,
+                max: 10.0,
+            },
+        ),
+        ("star_density", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "star_brightness",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        ("nebula_strength", Rule::Range { min: 0.0, max: 2.0 }),
+        ("horizon_size", Rule::Range { min: 0.0, max: 1.0 }),
+        ("show_disk", Rule::Flag),
+        ("disk_inner", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "disk_outer",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        (
+            "disk_tilt",
+            Rule::Range {
+                min: 0.0,
+                max: 89.0,
+            },
+        ),
+        (
+            "disk_brightness",
+            Rule::Range {
+                min: 0.0,
+                max: 20.0,
+            },
+        ),
+        (
+            "disk_spin",
+            Rule::Range {
+                min: -10.0,
+                max: 10.0,
+            },
+        ),
+        ("doppler", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "bloom_threshold",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        ("bloom_intensity", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "bloom_radius",
+            Rule::Range {
+                min: 0.0,
+                max: 128.0,
+            },
+        ),
+        (
+            "extent",
+            Rule::Range {
+                min: 0.1,
+                max: 20.0,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized).
+/// Star density places the stars, so it generates rather than colors.
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("masses", SettingCategory::Generators),
+    ("background", SettingCategory::Colors),
+    ("star_density", SettingCategory::Generators),
+    ("star_brightness", SettingCategory::Colors),
+    ("nebula_strength", SettingCategory::Colors),
+    ("disk_brightness", SettingCategory::Colors),
+    ("doppler", SettingCategory::Colors),
+    ("bloom_threshold", SettingCategory::Colors),
+    ("bloom_intensity", SettingCategory::Colors),
+    ("bloom_radius", SettingCategory::Colors),
+]);
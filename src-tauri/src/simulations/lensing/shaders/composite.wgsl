@@ -0,0 +1,74 @@
+// Draws the lensed scene onto the surface with the brightest parts, mostly
+// the inner disks, glowing out over their surroundings.
+
+struct Params {
+    masses: array<vec4<f32>, 8>,
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    mass_count: u32,
+    background: u32,
+    image_aspect: f32,
+    image_scale: f32,
+    star_density: f32,
+    star_brightness: f32,
+    nebula_strength: f32,
+    horizon_size: f32,
+    disk_inner: f32,
+    disk_outer: f32,
+    disk_tilt: f32,
+    disk_brightness: f32,
+    disk_angle: f32,
+    doppler: f32,
+    show_disk: u32,
+    bloom_threshold: f32,
+    bloom_intensity: f32,
+    bloom_radius: f32,
+    surface_width: f32,
+    surface_height: f32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var scene_texture: texture_2d<f32>;
+@group(0) @binding(2) var scene_sampler: sampler;
+
+const BLOOM_TAPS: u32 = 48u;
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return VertexOutput(vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0), vec2<f32>(uv.x, 1.0 - uv.y));
+}
+
+// Bright parts of the scene gathered over a disc, golden angle spiral taps
+fn bloom(uv: vec2<f32>) -> vec3<f32> {
+    let resolution = vec2<f32>(params.surface_width, params.surface_height);
+    var sum = vec3<f32>(0.0);
+    for (var i = 0u; i < BLOOM_TAPS; i++) {
+        let radius = sqrt((f32(i) + 0.5) / f32(BLOOM_TAPS)) * params.bloom_radius;
+        let angle = f32(i) * GOLDEN_ANGLE;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * radius / resolution;
+        let sample = textureSampleLevel(scene_texture, scene_sampler, uv + offset, 0.0).rgb;
+        sum += max(sample - vec3<f32>(params.bloom_threshold), vec3<f32>(0.0));
+    }
+    return sum / f32(BLOOM_TAPS);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSampleLevel(scene_texture, scene_sampler, input.uv, 0.0).rgb;
+    if (params.bloom_intensity > 0.0 && params.bloom_radius > 0.0) {
+        color += bloom(input.uv) * params.bloom_intensity;
+    }
+    return vec4<f32>(min(color, vec3<f32>(1.0)), 1.0);
+}
@@ -0,0 +1,5 @@
+pub const SCENE_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("scene.wgsl")
+);
+pub const COMPOSITE_SHADER: &str = include_str!("composite.wgsl");
@@ -0,0 +1,194 @@
+// Traces each pixel back through the lens plane with the thin lens
+// approximation: light passing a mass at distance d is bent toward it by
+// einstein_radius^2 / d, so the pixel shows whatever lies at the deflected
+// point of the source plane behind. Each mass's accretion disk is drawn
+// twice, once bent like the background, which wraps its far side up over
+// the shadow, and once straight for the near half passing in front.
+
+struct Params {
+    masses: array<vec4<f32>, 8>, // xy position, z Einstein radius
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    mass_count: u32,
+    background: u32, // 0 starfield, 1 image
+    image_aspect: f32,
+    image_scale: f32,
+    star_density: f32,
+    star_brightness: f32,
+    nebula_strength: f32,
+    horizon_size: f32,
+    disk_inner: f32,
+    disk_outer: f32,
+    disk_tilt: f32, // radians
+    disk_brightness: f32,
+    disk_angle: f32, // radians turned at the inner edge
+    doppler: f32,
+    show_disk: u32,
+    bloom_threshold: f32,
+    bloom_intensity: f32,
+    bloom_radius: f32,
+    surface_width: f32,
+    surface_height: f32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> lut_data: array<u32>;
+@group(0) @binding(2) var background_texture: texture_2d<f32>;
+@group(0) @binding(3) var background_sampler: sampler;
+
+// Starfield cells per plane unit in the coarsest layer
+const STAR_SCALE: f32 = 6.0;
+const STAR_LAYERS: u32 = 3u;
+// Radius of a star in plane units, before widening to a pixel
+const STAR_RADIUS: f32 = 0.006;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// One triangle covering the screen
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return VertexOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+fn hash3(p: vec2<f32>) -> vec3<f32> {
+    var q = fract(vec3<f32>(p.xyx) * vec3<f32>(0.1031, 0.1030, 0.0973));
+    q += dot(q, q.yxz + 33.33);
+    return fract((q.xxy + q.yzz) * q.zyx);
+}
+
+fn value_noise(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = hash3(cell).x;
+    let b = hash3(cell + vec2<f32>(1.0, 0.0)).x;
+    let c = hash3(cell + vec2<f32>(0.0, 1.0)).x;
+    let d = hash3(cell + vec2<f32>(1.0, 1.0)).x;
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+fn fbm(p: vec2<f32>) -> f32 {
+    var sum = 0.0;
+    var amplitude = 0.5;
+    var q = p;
+    for (var i = 0u; i < 5u; i++) {
+        sum += amplitude * value_noise(q);
+        q = q * 2.03 + vec2<f32>(17.1, 9.7);
+        amplitude *= 0.5;
+    }
+    return sum;
+}
+
+fn starfield(source: vec2<f32>) -> vec3<f32> {
+    // Stars smaller than a pixel are widened to one and dimmed to match, so
+    // they don't flicker as they move
+    let radius = max(STAR_RADIUS, params.plane_per_pixel * 0.7);
+    let dimming = (STAR_RADIUS * STAR_RADIUS) / (radius * radius);
+    var color = vec3<f32>(0.0);
+    for (var layer = 0u; layer < STAR_LAYERS; layer++) {
+        let scale = STAR_SCALE * f32(1u << layer);
+        let cell = floor(source * scale);
+        let h = hash3(cell + f32(layer) * 113.0);
+        if (h.z < params.star_density) {
+            // Kept clear of the cell's edges so no star is cut off
+            let star = (cell + 0.2 + 0.6 * h.xy) / scale;
+            let falloff = exp(-dot(source - star, source - star) / (radius * radius));
+            let brightness = (0.3 + 2.7 * h.x * h.x) / f32(1u << layer);
+            let tint = mix(vec3<f32>(1.0, 0.78, 0.6), vec3<f32>(0.7, 0.8, 1.0), h.y);
+            color += tint * brightness * falloff * dimming;
+        }
+    }
+    let cloud = fbm(source * 0.6);
+    let nebula = lut_color(cloud) * cloud * cloud * params.nebula_strength * 0.5;
+    return color * params.star_brightness + nebula;
+}
+
+fn lensed_background(source: vec2<f32>) -> vec3<f32> {
+    if (params.background == 1u) {
+        let height = 2.0 * params.extent * params.image_scale;
+        let uv = vec2<f32>(
+            source.x / (height * params.image_aspect) + 0.5,
+            0.5 - source.y / height
+        );
+        return textureSampleLevel(background_texture, background_sampler, uv, 0.0).rgb;
+    }
+    return starfield(source);
+}
+
+// Light given off by a disk at `offset` from its mass, in Einstein radii.
+// The disk is tipped back about the horizontal, so its near half is below.
+fn disk_emission(offset: vec2<f32>, mass: u32) -> vec3<f32> {
+    let on_disk = vec2<f32>(offset.x, offset.y / max(cos(params.disk_tilt), 0.02));
+    let r = length(on_disk);
+    if (r <= params.disk_inner || r >= params.disk_outer) {
+        return vec3<f32>(0.0);
+    }
+    let edges = smoothstep(params.disk_inner, params.disk_inner * 1.15, r)
+        * (1.0 - smoothstep(params.disk_outer * 0.7, params.disk_outer, r));
+
+    // Inner parts orbit faster, winding the streaks into spirals
+    let inner = max(params.disk_inner, 0.05);
+    let phase = atan2(on_disk.y, on_disk.x) - params.disk_angle * pow(r / inner, -1.5);
+    let swirl = vec2<f32>(cos(phase), sin(phase)) * (1.5 + r * 2.0) + f32(mass) * 31.0;
+    let streaks = 0.45 + 0.55 * fbm(swirl + vec2<f32>(r * 6.0, 0.0));
+
+    // Hotter toward the middle, and brighter on the side moving toward us
+    let heat = pow(inner / r, 0.75);
+    let beaming = 1.0 + params.doppler * on_disk.x / r;
+    return lut_color(heat) * heat * heat * streaks * edges * beaming * params.disk_brightness;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let world = input.ndc / params.view_zoom + params.view_center;
+    let image = vec2<f32>(world.x * params.aspect, world.y) * params.extent;
+
+    var source = image;
+    var shadowed = false;
+    for (var i = 0u; i < params.mass_count; i++) {
+        let mass = params.masses[i];
+        let offset = image - mass.xy;
+        let distance_squared = max(dot(offset, offset), 1e-12);
+        source -= mass.z * mass.z * offset / distance_squared;
+        if (sqrt(distance_squared) < params.horizon_size * mass.z) {
+            shadowed = true;
+        }
+    }
+
+    var color = vec3<f32>(0.0);
+    if (!shadowed) {
+        color = lensed_background(source);
+    }
+    if (params.show_disk != 0u) {
+        for (var i = 0u; i < params.mass_count; i++) {
+            let mass = params.masses[i];
+            if (!shadowed) {
+                color += disk_emission((source - mass.xy) / mass.z, i);
+            }
+            let direct = (image - mass.xy) / mass.z;
+            if (direct.y < 0.0) {
+                color += disk_emission(direct, i);
+            }
+        }
+    }
+    return vec4<f32>(color, 1.0);
+}
@@ -0,0 +1,902 @@
+//! # Gravitational Lensing Simulation Module
+//!
+//! Masses bending the light from whatever lies behind them: a starfield or
+//! a loaded image is pulled into arcs and Einstein rings around each one,
+//! and black holes among them hide a shadow inside a glowing accretion disk.
+//!
+//! ## Technical Overview
+//!
+//! Nothing is stepped from frame to frame; every frame is traced afresh:
+//! 1. A fullscreen pass follows each pixel's ray back through the lens plane
+//!    with the thin lens approximation and shades what it lands on, into a
+//!    floating point scene texture so the disks can be brighter than white.
+//! 2. A composite pass draws the scene onto the surface with bloom gathered
+//!    from its brightest parts.
+//!
+//! Masses can orbit their shared center of mass, and dragging one with the
+//! left button moves it.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, Buffer,
+    BufferDescriptor, BufferUsages, Device, PipelineLayoutDescriptor, Queue, RenderPipeline,
+    RenderPipelineDescriptor, Sampler, ShaderStages, SurfaceConfiguration, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager};
+use crate::simulations::traits::Simulation;
+
+use super::settings::{LensedBackground, MAX_MASSES, Mass, Settings, mass_ring};
+use super::shaders::{COMPOSITE_SHADER, SCENE_SHADER};
+use super::state::State;
+
+/// The disks outshine white, so the scene is kept in floating point until
+/// the bloom has been gathered
+const SCENE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+/// Loaded images are shrunk to fit
+const MAX_IMAGE_SIZE: u32 = 4096;
+/// Screen pixels from a mass's center that still pick it up
+const GRAB_RADIUS: f32 = 12.0;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    masses: [[f32; 4]; MAX_MASSES],
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    mass_count: u32,
+    background: u32,
+    image_aspect: f32,
+    image_scale: f32,
+    star_density: f32,
+    star_brightness: f32,
+    nebula_strength: f32,
+    horizon_size: f32,
+    disk_inner: f32,
+    disk_outer: f32,
+    disk_tilt: f32,
+    disk_brightness: f32,
+    disk_angle: f32,
+    doppler: f32,
+    show_disk: u32,
+    bloom_threshold: f32,
+    bloom_intensity: f32,
+    bloom_radius: f32,
+    surface_width: f32,
+    surface_height: f32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+/// Center of mass, each mass weighted by the square of its Einstein radius
+pub fn center_of_mass(masses: &[Mass]) -> [f32; 2] {
+    let (mut x, mut y, mut total) = (0.0, 0.0, 0.0);
+    for mass in masses {
+        let weight = mass.einstein_radius * mass.einstein_radius;
+        x += mass.position[0] * weight;
+        y += mass.position[1] * weight;
+        total += weight;
+    }
+    if total > 0.0 {
+        [x / total, y / total]
+    } else {
+        [0.0, 0.0]
+    }
+}
+
+/// `masses` turned `angle` radians about their center of mass
+pub fn orbit(masses: &[Mass], angle: f32) -> Vec<Mass> {
+    let center = center_of_mass(masses);
+    let (sin, cos) = angle.sin_cos();
+    masses
+        .iter()
+        .map(|mass| {
+            let x = mass.position[0] - center[0];
+            let y = mass.position[1] - center[1];
+            Mass {
+                position: [center[0] + x * cos - y * sin, center[1] + x * sin + y * cos],
+                ..*mass
+            }
+        })
+        .collect()
+}
+
+/// The mass closest to `point` if it's within `radius` or within its own
+/// shadow
+pub fn mass_near(
+    masses: &[Mass],
+    point: [f32; 2],
+    radius: f32,
+    horizon_size: f32,
+) -> Option<usize> {
+    masses
+        .iter()
+        .map(|mass| {
+            let distance = (mass.position[0] - point[0]).hypot(mass.position[1] - point[1]);
+            (distance, radius.max(mass.einstein_radius * horizon_size))
+        })
+        .enumerate()
+        .filter(|&(_, (distance, reach))| distance <= reach)
+        .min_by(|a, b| a.1.0.total_cmp(&b.1.0))
+        .map(|(i, _)| i)
+}
+
+#[derive(Debug)]
+pub struct LensingModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    scene_pipeline: RenderPipeline,
+    composite_pipeline: RenderPipeline,
+    scene_bind_group_layout: BindGroupLayout,
+    composite_bind_group_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    sampler: Sampler,
+    image_sampler: Sampler,
+    image_view: TextureView,
+    image_aspect: f32,
+    scene_view: TextureView,
+    scene_bind_group: BindGroup,
+    composite_bind_group: BindGroup,
+
+    // The camera picks the part of the lens plane shown; it never draws
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl LensingModel {
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let scene_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Lensing Scene Shader"),
+            source: wgpu::ShaderSource::Wgsl(SCENE_SHADER.into()),
+        });
+        let composite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Lensing Composite Shader"),
+            source: wgpu::ShaderSource::Wgsl(COMPOSITE_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Lensing Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Lensing LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let sampler = resource_helpers::create_linear_sampler(
+            device,
+            "Lensing Scene Sampler",
+            wgpu::FilterMode::Linear,
+        );
+        // Mirrored, so rays bent out past the image's edges don't meet a seam
+        let image_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Lensing Image Sampler"),
+            address_mode_u: wgpu::AddressMode::MirrorRepeat,
+            address_mode_v: wgpu::AddressMode::MirrorRepeat,
+            address_mode_w: wgpu::AddressMode::MirrorRepeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        // Stands in until an image is loaded
+        let image_view = create_image_view(device, queue, 1, 1, &[0, 0, 0, 255]);
+
+        let scene_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Lensing Scene Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::FRAGMENT),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::FRAGMENT, true),
+                resource_helpers::texture_entry(
+                    2,
+                    ShaderStages::FRAGMENT,
+                    wgpu::TextureSampleType::Float { filterable: true },
+                    wgpu::TextureViewDimension::D2,
+                ),
+                resource_helpers::sampler_entry(
+                    3,
+                    ShaderStages::FRAGMENT,
+                    wgpu::SamplerBindingType::Filtering,
+                ),
+            ],
+        });
+        let composite_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Lensing Composite Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::FRAGMENT),
+                    resource_helpers::texture_entry(
+                        1,
+                        ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        2,
+                        ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                ],
+            });
+
+        let scene_pipeline = create_fullscreen_pipeline(
+            device,
+            "Lensing Scene Pipeline",
+            &scene_module,
+            &scene_bind_group_layout,
+            SCENE_FORMAT,
+        );
+        let composite_pipeline = create_fullscreen_pipeline(
+            device,
+            "Lensing Composite Pipeline",
+            &composite_module,
+            &composite_bind_group_layout,
+            surface_config.format,
+        );
+
+        let scene_view = create_scene_view(device, surface_config.width, surface_config.height);
+        let scene_bind_group = create_scene_bind_group(
+            device,
+            &scene_bind_group_layout,
+            &params_buffer,
+            &lut_buffer,
+            &image_view,
+            &image_sampler,
+        );
+        let composite_bind_group = create_composite_bind_group(
+            device,
+            &composite_bind_group_layout,
+            &params_buffer,
+            &scene_view,
+            &sampler,
+        );
+
+        Ok(Self {
+            settings,
+            state,
+            scene_pipeline,
+            composite_pipeline,
+            scene_bind_group_layout,
+            composite_bind_group_layout,
+            params_buffer,
+            lut_buffer,
+            sampler,
+            image_sampler,
+            image_view,
+            image_aspect: 1.0,
+            scene_view,
+            scene_bind_group,
+            composite_bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        })
+    }
+
+    pub fn load_image_from_path(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        path: &str,
+    ) -> SimulationResult<()> {
+        let img = image::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
+        self.load_image_from_data(device, queue, img)
+    }
+
+    /// Lens `img` in place of the starfield
+    pub fn load_image_from_data(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        img: image::DynamicImage,
+    ) -> SimulationResult<()> {
+        let img = if img.width() > MAX_IMAGE_SIZE || img.height() > MAX_IMAGE_SIZE {
+            img.thumbnail(MAX_IMAGE_SIZE, MAX_IMAGE_SIZE)
+        } else {
+            img
+        };
+        let rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        if width == 0 || height == 0 {
+            return Err("Image has no pixels".into());
+        }
+
+        self.image_view = create_image_view(device, queue, width, height, &rgba);
+        self.image_aspect = width as f32 / height as f32;
+        self.scene_bind_group = create_scene_bind_group(
+            device,
+            &self.scene_bind_group_layout,
+            &self.params_buffer,
+            &self.lut_buffer,
+            &self.image_view,
+            &self.image_sampler,
+        );
+        self.state.image_loaded = true;
+        self.settings.background = LensedBackground::Image;
+        Ok(())
+    }
+
+    /// Where a world position lands in the lens plane. World space is
+    /// stretched over the screen, so x is widened by the aspect ratio to
+    /// keep pixels square in the plane.
+    fn world_to_plane(&self, world: [f32; 2]) -> [f32; 2] {
+        [
+            world[0] * self.aspect() * self.settings.extent,
+            world[1] * self.settings.extent,
+        ]
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// Plane distance across one screen pixel
+    fn plane_per_pixel(&self) -> f32 {
+        2.0 * self.settings.extent * self.aspect() / (self.camera.zoom * self.width.max(1) as f32)
+    }
+
+    /// How far the masses have orbited so far
+    fn orbit_angle(&self) -> f32 {
+        std::f32::consts::TAU * self.settings.orbit_speed * self.state.time
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let mut masses = [[0.0; 4]; MAX_MASSES];
+        let orbiting = orbit(&self.settings.masses, self.orbit_angle());
+        for (slot, mass) in masses.iter_mut().zip(&orbiting) {
+            *slot = [
+                mass.position[0],
+                mass.position[1],
+                mass.einstein_radius.max(1e-4),
+                0.0,
+            ];
+        }
+        let show_image =
+            self.settings.background == LensedBackground::Image && self.state.image_loaded;
+        let inner = self.settings.disk_inner.max(0.05);
+        let params = Params {
+            masses,
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            extent: self.settings.extent,
+            plane_per_pixel: self.plane_per_pixel(),
+            mass_count: orbiting.len().min(MAX_MASSES) as u32,
+            background: show_image as u32,
+            image_aspect: self.image_aspect,
+            image_scale: self.settings.image_scale,
+            star_density: self.settings.star_density,
+            star_brightness: self.settings.star_brightness,
+            nebula_strength: self.settings.nebula_strength,
+            horizon_size: self.settings.horizon_size,
+            disk_inner: self.settings.disk_inner,
+            disk_outer: self.settings.disk_outer,
+            disk_tilt: self.settings.disk_tilt.to_radians(),
+            disk_brightness: self.settings.disk_brightness,
+            // Wrapped at a whole number of turns of the outer edge so the
+            // angle stays small enough to keep its precision
+            disk_angle: (self.settings.disk_spin * self.state.time)
+                % (std::f32::consts::TAU * (self.settings.disk_outer / inner).powf(1.5)),
+            doppler: self.settings.doppler,
+            show_disk: self.settings.show_disk as u32,
+            bloom_threshold: self.settings.bloom_threshold,
+            bloom_intensity: self.settings.bloom_intensity,
+            bloom_radius: self.settings.bloom_radius,
+            surface_width: self.width as f32,
+            surface_height: self.height as f32,
+            _pad0: 0,
+            _pad1: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn draw(
+        &self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.update_params(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Lensing Render"),
+        });
+        for (label, pipeline, bind_group, target) in [
+            (
+                "Lensing Scene Pass",
+                &self.scene_pipeline,
+                &self.scene_bind_group,
+                &self.scene_view,
+            ),
+            (
+                "Lensing Composite Pass",
+                &self.composite_pipeline,
+                &self.composite_bind_group,
+                surface_view,
+            ),
+        ] {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+}
+
+fn create_fullscreen_pipeline(
+    device: &Device,
+    label: &str,
+    module: &wgpu::ShaderModule,
+    bind_group_layout: &BindGroupLayout,
+    format: wgpu::TextureFormat,
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn create_scene_view(device: &Device, width: u32, height: u32) -> TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Lensing Scene Texture"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: SCENE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// An sRGB texture holding `rgba`, which is `width` by `height`
+fn create_image_view(
+    device: &Device,
+    queue: &Queue,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+) -> TextureView {
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Lensing Image Texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        rgba,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * width),
+            rows_per_image: Some(height),
+        },
+        size,
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn create_scene_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    lut_buffer: &Buffer,
+    image_view: &TextureView,
+    image_sampler: &Sampler,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Lensing Scene Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, lut_buffer),
+            resource_helpers::texture_view_entry(2, image_view),
+            resource_helpers::sampler_bind_entry(3, image_sampler),
+        ],
+    })
+}
+
+fn create_composite_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    scene_view: &TextureView,
+    sampler: &Sampler,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Lensing Composite Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::texture_view_entry(1, scene_view),
+            resource_helpers::sampler_bind_entry(2, sampler),
+        ],
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for LensingModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        self.state.time += delta_time;
+        self.draw(device, queue, surface_view)
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.draw(device, queue, surface_view)
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.scene_view = create_scene_view(device, self.width, self.height);
+        self.composite_bind_group = create_composite_bind_group(
+            device,
+            &self.composite_bind_group_layout,
+            &self.params_buffer,
+            &self.scene_view,
+            &self.sampler,
+        );
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        if mouse_button != 0 {
+            return Ok(());
+        }
+        let point = self.world_to_plane([world_x, world_y]);
+        // Masses are picked where they're drawn, part way round their orbit
+        let angle = self.orbit_angle();
+        let dragging = match self.state.dragging {
+            Some(mass) => Some(mass),
+            None => mass_near(
+                &orbit(&self.settings.masses, angle),
+                point,
+                GRAB_RADIUS * self.plane_per_pixel(),
+                self.settings.horizon_size.max(0.2),
+            ),
+        };
+        if let Some(mass) = dragging.filter(|&m| m < self.settings.masses.len()) {
+            // Undo the orbit so the mass ends up under the cursor
+            let center = center_of_mass(&self.settings.masses);
+            let (sin, cos) = (-angle).sin_cos();
+            let (x, y) = (point[0] - center[0], point[1] - center[1]);
+            self.settings.masses[mass].position =
+                [center[0] + x * cos - y * sin, center[1] + x * sin + y * cos];
+            self.state.dragging = Some(mass);
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.dragging = None;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut new_settings: Settings = serde_json::from_value(settings)?;
+        new_settings.masses.truncate(MAX_MASSES);
+        self.settings = new_settings;
+        self.state.dragging = None;
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.time = 0.0;
+        self.state.dragging = None;
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        let count = rng.random_range(1..=3);
+        self.settings.masses = mass_ring(count, rng.random_range(0.6..1.2), 0.0)
+            .into_iter()
+            .map(|mass| Mass {
+                einstein_radius: rng.random_range(0.25..0.6),
+                ..mass
+            })
+            .collect();
+        self.settings.orbit_speed = if count > 1 {
+            rng.random_range(-0.08..0.08)
+        } else {
+            0.0
+        };
+        self.settings.disk_tilt = rng.random_range(60.0..86.0);
+        self.settings.disk_inner = rng.random_range(0.45..0.8);
+        self.settings.disk_outer = self.settings.disk_inner + rng.random_range(0.5..1.5);
+        self.settings.doppler = rng.random_range(0.2..0.8);
+        self.settings.nebula_strength = rng.random_range(0.0..0.7);
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "mass_count" => {
+                let count = (number(setting_name, &value)? as usize).clamp(1, MAX_MASSES);
+                let einstein_radius = self
+                    .settings
+                    .masses
+                    .first()
+                    .map_or(0.5, |mass| mass.einstein_radius);
+                self.settings.masses = mass_ring(count, 1.0, einstein_radius);
+                self.state.dragging = None;
+            }
+            "einstein_radius" => {
+                let einstein_radius = number(setting_name, &value)? as f32;
+                for mass in &mut self.settings.masses {
+                    mass.einstein_radius = einstein_radius;
+                }
+            }
+            "masses" => {
+                let mut masses: Vec<Mass> = serde_json::from_value(value)?;
+                masses.truncate(MAX_MASSES);
+                self.settings.masses = masses;
+                self.state.dragging = None;
+            }
+            "orbit_speed" => self.settings.orbit_speed = number(setting_name, &value)? as f32,
+            "background" => {
+                self.settings.background = value
+                    .as_str()
+                    .ok_or("background must be a string")?
+                    .parse()?;
+            }
+            "image_scale" => self.settings.image_scale = number(setting_name, &value)? as f32,
+            "star_density" => self.settings.star_density = number(setting_name, &value)? as f32,
+            "star_brightness" => {
+                self.settings.star_brightness = number(setting_name, &value)? as f32
+            }
+            "nebula_strength" => {
+                self.settings.nebula_strength = number(setting_name, &value)? as f32
+            }
+            "horizon_size" => self.settings.horizon_size = number(setting_name, &value)? as f32,
+            "show_disk" => self.settings.show_disk = value.as_bool().unwrap_or(true),
+            "disk_inner" => self.settings.disk_inner = number(setting_name, &value)? as f32,
+            "disk_outer" => self.settings.disk_outer = number(setting_name, &value)? as f32,
+            "disk_tilt" => self.settings.disk_tilt = number(setting_name, &value)? as f32,
+            "disk_brightness" => {
+                self.settings.disk_brightness = number(setting_name, &value)? as f32
+            }
+            "disk_spin" => self.settings.disk_spin = number(setting_name, &value)? as f32,
+            "doppler" => self.settings.doppler = number(setting_name, &value)? as f32,
+            "bloom_threshold" => {
+                self.settings.bloom_threshold = number(setting_name, &value)? as f32
+            }
+            "bloom_intensity" => {
+                self.settings.bloom_intensity = number(setting_name, &value)? as f32
+            }
+            "bloom_radius" => self.settings.bloom_radius = number(setting_name, &value)? as f32,
+            "extent" => self.settings.extent = number(setting_name, &value)? as f32,
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Seconds simulated, which turns the disks and orbits
+    pub time: f32,
+
+    // Whether an image has been loaded to lens
+    pub image_loaded: bool,
+
+    // Mass being dragged, if any
+    pub dragging: Option<usize>,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            image_loaded: false,
+            dragging: None,
+            color_scheme_name: "MATPLOTLIB_inferno".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
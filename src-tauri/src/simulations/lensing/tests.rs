@@ -0,0 +1,96 @@
+use super::settings::{Mass, mass_ring};
+use super::shaders::{COMPOSITE_SHADER, SCENE_SHADER};
+use super::simulation::{center_of_mass, mass_near, orbit};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn close(a: [f32; 2], b: [f32; 2]) -> bool {
+    (a[0] - b[0]).abs() < 1e-5 && (a[1] - b[1]).abs() < 1e-5
+}
+
+#[test]
+fn ring_of_one_sits_in_the_middle() {
+    let single = mass_ring(1, 2.0, 0.4);
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].position, [0.0, 0.0]);
+
+    let ring = mass_ring(4, 2.0, 0.4);
+    assert!(close(ring[0].position, [2.0, 0.0]));
+    assert!(close(ring[1].position, [0.0, 2.0]));
+    assert!(ring.iter().all(|mass| mass.einstein_radius == 0.4));
+    assert!(close(center_of_mass(&ring), [0.0, 0.0]));
+}
+
+#[test]
+fn heavier_masses_pull_the_center_toward_them() {
+    let masses = [
+        Mass {
+            position: [0.0, 0.0],
+            einstein_radius: 2.0,
+        },
+        Mass {
+            position: [5.0, 0.0],
+            einstein_radius: 1.0,
+        },
+    ];
+    // Weighted by the squared Einstein radius, 4 to 1
+    assert!(close(center_of_mass(&masses), [1.0, 0.0]));
+    assert!(close(center_of_mass(&[]), [0.0, 0.0]));
+}
+
+#[test]
+fn orbiting_turns_about_the_center_of_mass() {
+    let masses = [
+        Mass {
+            position: [1.0, 1.0],
+            einstein_radius: 1.0,
+        },
+        Mass {
+            position: [3.0, 1.0],
+            einstein_radius: 1.0,
+        },
+    ];
+    let quarter = orbit(&masses, std::f32::consts::FRAC_PI_2);
+    assert!(close(quarter[0].position, [2.0, 0.0]));
+    assert!(close(quarter[1].position, [2.0, 2.0]));
+    assert!(close(center_of_mass(&quarter), center_of_mass(&masses)));
+
+    let back = orbit(&quarter, -std::f32::consts::FRAC_PI_2);
+    for (a, b) in back.iter().zip(&masses) {
+        assert!(close(a.position, b.position));
+    }
+}
+
+#[test]
+fn grabbing_picks_the_nearest_mass_in_reach() {
+    let masses = [
+        Mass {
+            position: [0.0, 0.0],
+            einstein_radius: 1.0,
+        },
+        Mass {
+            position: [2.0, 0.0],
+            einstein_radius: 0.1,
+        },
+    ];
+    assert_eq!(mass_near(&masses, [1.9, 0.0], 0.2, 0.4), Some(1));
+    // Anywhere over a mass's shadow picks it up
+    assert_eq!(mass_near(&masses, [0.35, 0.0], 0.05, 0.4), Some(0));
+    assert_eq!(mass_near(&masses, [1.0, 0.0], 0.05, 0.4), None);
+    assert_eq!(mass_near(&[], [0.0, 0.0], 10.0, 0.4), None);
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Lensing Scene Shader", SCENE_SHADER),
+        ("Lensing Composite Shader", COMPOSITE_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
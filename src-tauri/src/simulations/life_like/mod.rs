@@ -0,0 +1,129 @@
+pub mod rule;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::LifeLikeModel;
+
+use crate::simulation::preset_manager::{LifeLikePresetManager, Preset};
+
+/// Initialize Life-like presets with built-in configurations
+pub fn init_presets(preset_manager: &mut LifeLikePresetManager) {
+    use crate::simulations::shared::GridTopology;
+    use settings::{LifeColorMode, Settings, SoupSymmetry};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "HighLife".to_string(),
+        Settings {
+            rule: "B36/S23".to_string(),
+            color_mode: LifeColorMode::Trail,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Day & Night".to_string(),
+        Settings {
+            rule: "B3678/S34678".to_string(),
+            soup_density: 0.5,
+            soup_size: 1.0,
+            color_mode: LifeColorMode::Age,
+            history_span: 200.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Seeds".to_string(),
+        Settings {
+            rule: "B2/S".to_string(),
+            generations_per_second: 15.0,
+            soup_density: 0.5,
+            soup_size: 0.05,
+            color_mode: LifeColorMode::Trail,
+            history_span: 20.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Brian's Brain".to_string(),
+        Settings {
+            rule: "B2/S/C3".to_string(),
+            soup_density: 0.2,
+            soup_size: 0.3,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Star Wars".to_string(),
+        Settings {
+            rule: "B2/S345/C4".to_string(),
+            soup_size: 0.4,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Maze".to_string(),
+        Settings {
+            rule: "B3/S12345".to_string(),
+            soup_density: 0.3,
+            soup_size: 0.1,
+            soup_symmetry: SoupSymmetry::Quad,
+            color_mode: LifeColorMode::Age,
+            history_span: 300.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Hex Life".to_string(),
+        Settings {
+            rule: "B2/S34".to_string(),
+            cell_size: 6,
+            topology: GridTopology::Hex,
+            soup_density: 0.3,
+            color_mode: LifeColorMode::Trail,
+            history_span: 40.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Bugs".to_string(),
+        Settings {
+            rule: "R5,C0,M1,S34..58,B34..45,NM".to_string(),
+            generations_per_second: 20.0,
+            cell_size: 2,
+            soup_density: 0.5,
+            soup_size: 0.6,
+            color_mode: LifeColorMode::Trail,
+            history_span: 30.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Soup Search".to_string(),
+        Settings {
+            soup_size: 0.08,
+            soup_density: 0.5,
+            soup_symmetry: SoupSymmetry::Mirror,
+            soup_interval: 1500,
+            generations_per_second: 120.0,
+            color_mode: LifeColorMode::Trail,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
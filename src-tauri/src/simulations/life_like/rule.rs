@@ -0,0 +1,383 @@
+//! # Life-like Rules
+//!
+//! Which neighbor counts bring a dead cell to life and keep a live one alive,
+//! how many states a cell goes through, and how far its neighborhood reaches.
+//!
+//! Three notations are read:
+//!
+//! - B/S, like `B3/S23` for Conway's Life or `23/3` in the older S/B order.
+//!   A `V` at the end counts the four orthogonal neighbors instead of eight.
+//! - Generations, which adds a state count: `B2/S/C3` or `/2/3`. Cells that
+//!   stop surviving spend the states past 1 dying before they're dead, and
+//!   only live cells count as neighbors.
+//! - Larger than Life, like `R5,C0,M1,S34..58,B34..45,NM`: the neighborhood
+//!   radius `R`, states `C` (0 and 2 both mean plain life), whether the cell
+//!   counts itself `M`, survival and birth counts `S` and `B` as numbers or
+//!   ranges separated by commas, and `NM` (square) or `NN` (diamond)
+//!   neighborhoods.
+
+use rand::Rng;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+pub const MAX_RADIUS: u32 = 7;
+/// The state is kept in a byte on the GPU
+pub const MAX_STATES: u32 = 255;
+/// Bits in each of the birth and survival masks the shader reads, enough for
+/// every count of the largest neighborhood
+pub const MASK_WORDS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Every cell within the radius in both directions
+    Moore,
+    /// Cells within the radius in steps along the axes
+    VonNeumann,
+}
+
+impl Neighborhood {
+    /// Cells around the center, not counting it
+    pub fn size(self, radius: u32) -> u32 {
+        match self {
+            Neighborhood::Moore => (2 * radius + 1).pow(2) - 1,
+            Neighborhood::VonNeumann => 2 * radius * (radius + 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LifeRule {
+    birth: BTreeSet<u32>,
+    survival: BTreeSet<u32>,
+    states: u32,
+    radius: u32,
+    neighborhood: Neighborhood,
+    // Whether a live cell counts itself among its neighbors
+    include_center: bool,
+}
+
+impl LifeRule {
+    pub fn new(
+        birth: BTreeSet<u32>,
+        survival: BTreeSet<u32>,
+        states: u32,
+        radius: u32,
+        neighborhood: Neighborhood,
+        include_center: bool,
+    ) -> Result<Self, String> {
+        if !(1..=MAX_RADIUS).contains(&radius) {
+            return Err(format!(
+                "The radius must be 1 to {}, got {}",
+                MAX_RADIUS, radius
+            ));
+        }
+        if !(2..=MAX_STATES).contains(&states) {
+            return Err(format!(
+                "A rule needs 2 to {} states, got {}",
+                MAX_STATES, states
+            ));
+        }
+        let max_count = neighborhood.size(radius) + include_center as u32;
+        if let Some(count) = birth.iter().chain(&survival).find(|&&c| c > max_count) {
+            return Err(format!(
+                "{} neighbors is more than the {} the neighborhood holds",
+                count, max_count
+            ));
+        }
+        Ok(Self {
+            birth,
+            survival,
+            states,
+            radius,
+            neighborhood,
+            include_center,
+        })
+    }
+
+    pub fn states(&self) -> u32 {
+        self.states
+    }
+
+    pub fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    pub fn include_center(&self) -> bool {
+        self.include_center
+    }
+
+    /// Birth counts as bits in the first [`MASK_WORDS`] words, survival
+    /// counts in the next
+    pub fn to_gpu_words(&self) -> Vec<u32> {
+        let mut words = vec![0u32; 2 * MASK_WORDS];
+        for &count in &self.birth {
+            words[count as usize / 32] |= 1 << (count % 32);
+        }
+        for &count in &self.survival {
+            words[MASK_WORDS + count as usize / 32] |= 1 << (count % 32);
+        }
+        words
+    }
+
+    /// A random radius 1 rule. Most random rules either die out or fill the
+    /// grid, so births stay above 1 and each count is only picked now and
+    /// then.
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let birth = loop {
+            let birth: BTreeSet<u32> = (2..=8).filter(|_| rng.random_bool(0.25)).collect();
+            if !birth.is_empty() {
+                break birth;
+            }
+        };
+        let survival = (0..=8).filter(|_| rng.random_bool(0.35)).collect();
+        let states = if rng.random_bool(0.3) {
+            rng.random_range(3..=8)
+        } else {
+            2
+        };
+        Self::new(birth, survival, states, 1, Neighborhood::Moore, false)
+            .expect("radius 1 counts are in range")
+    }
+
+    // Short B/S notation only covers radius 1 without the center
+    fn has_short_notation(&self) -> bool {
+        self.radius == 1 && !self.include_center
+    }
+}
+
+impl Default for LifeRule {
+    /// Conway's Life
+    fn default() -> Self {
+        "B3/S23".parse().expect("Conway's Life parses")
+    }
+}
+
+impl fmt::Display for LifeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.has_short_notation() {
+            let digits = |counts: &BTreeSet<u32>| -> String {
+                counts.iter().map(|c| c.to_string()).collect()
+            };
+            write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))?;
+            if self.states > 2 {
+                write!(f, "/C{}", self.states)?;
+            }
+            if self.neighborhood == Neighborhood::VonNeumann {
+                write!(f, "V")?;
+            }
+            return Ok(());
+        }
+        write!(
+            f,
+            "R{},C{},M{},S{},B{},N{}",
+            self.radius,
+            if self.states > 2 { self.states } else { 0 },
+            self.include_center as u32,
+            ranges(&self.survival),
+            ranges(&self.birth),
+            match self.neighborhood {
+                Neighborhood::Moore => 'M',
+                Neighborhood::VonNeumann => 'N',
+            }
+        )
+    }
+}
+
+/// Counts as `a..b` runs and single numbers separated by commas
+fn ranges(counts: &BTreeSet<u32>) -> String {
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+    for &count in counts {
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == count => *end = count,
+            _ => runs.push((count, count)),
+        }
+    }
+    runs.iter()
+        .map(|&(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}..{}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl FromStr for LifeRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let upper = s.to_ascii_uppercase();
+        let is_larger_than_life =
+            upper.starts_with('R') && upper[1..].starts_with(|c: char| c.is_ascii_digit());
+        if is_larger_than_life {
+            parse_larger_than_life(&upper)
+        } else {
+            parse_short(&upper)
+        }
+        .map_err(|e| format!("Invalid rule '{}': {}", s, e))
+    }
+}
+
+fn parse_short(s: &str) -> Result<LifeRule, String> {
+    let (body, neighborhood) = match s.strip_suffix('V') {
+        Some(body) => (body, Neighborhood::VonNeumann),
+        None => (s, Neighborhood::Moore),
+    };
+    let parts: Vec<&str> = body.split('/').collect();
+    let lettered = parts
+        .iter()
+        .any(|part| part.starts_with(|c: char| c.is_ascii_alphabetic()));
+
+    let (mut birth, mut survival, mut states) = (None, None, None);
+    if lettered {
+        for part in &parts {
+            let mut chars = part.chars();
+            let key = chars.next().ok_or("empty part between '/'")?;
+            let value = chars.as_str();
+            let slot = match key {
+                'B' => &mut birth,
+                'S' => &mut survival,
+                'C' => {
+                    states = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("'{}' is not a state count", value))?,
+                    );
+                    continue;
+                }
+                _ => return Err(format!("unknown part '{}'", part)),
+            };
+            *slot = Some(digits(value)?);
+        }
+    } else {
+        // S/B, with Generations adding the state count as S/B/C
+        match parts.as_slice() {
+            [s, b] => {
+                survival = Some(digits(s)?);
+                birth = Some(digits(b)?);
+            }
+            [s, b, c] => {
+                survival = Some(digits(s)?);
+                birth = Some(digits(b)?);
+                states = Some(
+                    c.parse()
+                        .map_err(|_| format!("'{}' is not a state count", c))?,
+                );
+            }
+            _ => return Err("expected B.../S... or S/B".to_string()),
+        }
+    }
+
+    LifeRule::new(
+        birth.ok_or("no birth part")?,
+        survival.unwrap_or_default(),
+        states.unwrap_or(2),
+        1,
+        neighborhood,
+        false,
+    )
+}
+
+fn digits(s: &str) -> Result<BTreeSet<u32>, String> {
+    s.chars()
+        .map(|c| {
+            c.to_digit(10)
+                .ok_or_else(|| format!("'{}' is not a neighbor count", c))
+        })
+        .collect()
+}
+
+fn parse_larger_than_life(s: &str) -> Result<LifeRule, String> {
+    let mut radius = None;
+    let mut states = 2;
+    let mut include_center = false;
+    let mut neighborhood = Neighborhood::Moore;
+    let mut birth = BTreeSet::new();
+    let mut survival = BTreeSet::new();
+    // Bare counts after S or B belong to the last of them
+    let mut counting = None;
+
+    for token in s.split(',') {
+        let Some(key) = token.chars().next() else {
+            return Err("empty item between ','".to_string());
+        };
+        if key.is_ascii_digit() {
+            match counting {
+                Some('S') => add_counts(&mut survival, token)?,
+                Some(_) => add_counts(&mut birth, token)?,
+                None => return Err(format!("'{}' doesn't follow S or B", token)),
+            }
+            continue;
+        }
+        let value = &token[1..];
+        let number = || {
+            value
+                .parse::<u32>()
+                .map_err(|_| format!("'{}' needs a number", token))
+        };
+        counting = None;
+        match key {
+            'R' => radius = Some(number()?),
+            // C0 is the usual way to write plain life
+            'C' => states = number()?.max(2),
+            'M' => include_center = number()? == 1,
+            'S' => {
+                add_counts(&mut survival, value)?;
+                counting = Some('S');
+            }
+            'B' => {
+                add_counts(&mut birth, value)?;
+                counting = Some('B');
+            }
+            'N' => {
+                neighborhood = match value {
+                    "M" => Neighborhood::Moore,
+                    "N" => Neighborhood::VonNeumann,
+                    _ => return Err(format!("unknown neighborhood '{}'", token)),
+                };
+            }
+            _ => return Err(format!("unknown item '{}'", token)),
+        }
+    }
+
+    LifeRule::new(
+        birth,
+        survival,
+        states,
+        radius.ok_or("no radius")?,
+        neighborhood,
+        include_center,
+    )
+}
+
+/// Add `n`, `a..b` or `a-b` to `counts`. An empty value adds nothing, as in
+/// `S` for no survival.
+fn add_counts(counts: &mut BTreeSet<u32>, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Ok(());
+    }
+    let parse = |n: &str| {
+        n.parse::<u32>()
+            .map_err(|_| format!("'{}' is not a neighbor count", n))
+    };
+    let (start, end) = match value.split_once("..").or_else(|| value.split_once('-')) {
+        Some((start, end)) => (parse(start)?, parse(end)?),
+        None => {
+            let count = parse(value)?;
+            (count, count)
+        }
+    };
+    if start > end {
+        return Err(format!("the range {} is backwards", value));
+    }
+    counts.extend(start..=end);
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! # Life-like Settings Module
+//!
+//! The rule, how fast generations pass, the random soup the grid starts
+//! from and how cells are colored by their history.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use crate::simulations::shared::{BackgroundLayer, GridTopology};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Mirroring applied to the random soup, which tends to give patterns that
+/// last longer and look more deliberate
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SoupSymmetry {
+    #[default]
+    None,
+    /// Left half mirrored to the right
+    Mirror,
+    /// One quarter mirrored across both axes
+    Quad,
+}
+
+impl FromStr for SoupSymmetry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(SoupSymmetry::None),
+            "mirror" => Ok(SoupSymmetry::Mirror),
+            "quad" => Ok(SoupSymmetry::Quad),
+            _ => Err(format!(
+                "Invalid SoupSymmetry: '{}'. Expected 'none', 'mirror' or 'quad'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LifeColorMode {
+    /// Live cells at the top of the color scheme, dying ones stepping down
+    #[default]
+    State,
+    /// Live cells move down the color scheme the longer they survive
+    Age,
+    /// Cells that have died leave a trail fading to the bottom
+    Trail,
+}
+
+impl FromStr for LifeColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "state" => Ok(LifeColorMode::State),
+            "age" => Ok(LifeColorMode::Age),
+            "trail" => Ok(LifeColorMode::Trail),
+            _ => Err(format!(
+                "Invalid LifeColorMode: '{}'. Expected 'state', 'age' or 'trail'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// B/S like `B3/S23`, Generations like `B2/S/C3` or Larger than Life
+    /// like `R5,C0,M1,S34..58,B34..45,NM`; see [`super::rule`]
+    pub rule: String,
+    pub generations_per_second: f32,
+    /// Screen pixels per grid column
+    pub cell_size: u32,
+    /// Square, hex or triangle cells. Hex and triangle grids only take
+    /// radius 1 rules, counting the cells that touch each one.
+    #[serde(default)]
+    pub topology: GridTopology,
+
+    /// Chance each cell of the soup starts alive
+    pub soup_density: f32,
+    /// Share of the grid's width and height the soup covers, centered
+    pub soup_size: f32,
+    pub soup_symmetry: SoupSymmetry,
+    /// The same seed always lays down the same soup
+    pub soup_seed: u32,
+    /// Generations before moving on to the next seed, 0 to stay on this one
+    pub soup_interval: u32,
+
+    pub color_mode: LifeColorMode,
+    /// Generations an age or trail takes to reach the end of its colors
+    pub history_span: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rule: "B3/S23".to_string(),
+            generations_per_second: 30.0,
+            cell_size: 3,
+            topology: GridTopology::Square,
+            soup_density: 0.35,
+            soup_size: 0.5,
+            soup_symmetry: SoupSymmetry::None,
+            soup_seed: 1,
+            soup_interval: 0,
+            color_mode: LifeColorMode::State,
+            history_span: 60.0,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting).
+/// The rule string is checked by parsing it.
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "generations_per_second",
+            Rule::Range {
+                min: 1.0,
+                max: 240.0,
+            },
+        ),
+        ("cell_size", Rule::Count { min: 1, max: 16 }),
+        ("topology", Rule::OneOf(&["Square", "Hex", "Triangle"])),
+        ("soup_density", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "soup_size",
+            Rule::Range {
+                min: 0.01,
+                max: 1.0,
+            },
+        ),
+        ("soup_symmetry", Rule::OneOf(&["None", "Mirror", "Quad"])),
+        (
+            "soup_seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+        (
+            "soup_interval",
+            Rule::Count {
+                min: 0,
+                max: 100_000,
+            },
+        ),
+        ("color_mode", Rule::OneOf(&["State", "Age", "Trail"])),
+        (
+            "history_span",
+            Rule::Range {
+                min: 1.0,
+                max: 10_000.0,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("rule", SettingCategory::Generators),
+    ("topology", SettingCategory::Generators),
+    ("soup_density", SettingCategory::Generators),
+    ("soup_size", SettingCategory::Generators),
+    ("soup_symmetry", SettingCategory::Generators),
+    ("soup_seed", SettingCategory::Generators),
+    ("soup_interval", SettingCategory::Generators),
+    ("color_mode", SettingCategory::Colors),
+    ("history_span", SettingCategory::Colors),
+]);
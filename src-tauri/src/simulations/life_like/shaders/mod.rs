@@ -0,0 +1,10 @@
+pub const STEP_SHADER: &str = concat!(
+    include_str!("../../shared/grid_topology.wgsl"),
+    include_str!("step.wgsl")
+);
+pub const PAINT_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("../../shared/grid_topology.wgsl"),
+    include_str!("paint.wgsl")
+);
+pub const RENDER_INFINITE_SHADER: &str = crate::simulations::shared::INFINITE_RENDER_SHADER;
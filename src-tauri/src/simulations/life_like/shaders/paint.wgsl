@@ -0,0 +1,82 @@
+// Colors the display texture from the grid by state, age or trail. Square
+// grids get a texel per cell; hex and triangle grids are drawn at screen
+// resolution, each texel taking the color of the cell it falls in.
+
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    states: u32,
+    radius: u32,
+    neighborhood: u32,
+    include_center: u32,
+    color_mode: u32, // 0 = State, 1 = Age, 2 = Trail
+    history_span: f32,
+    topology: u32,
+    display_width: u32,
+    display_height: u32,
+    _pad0: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> cells: array<u32>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+@group(0) @binding(3) var output_texture: texture_storage_2d<rgba8unorm, write>;
+
+const STATE_MASK: u32 = 0xffu;
+// Cells that have never been alive
+const HISTORY_MAX: u32 = 0xffffffu;
+
+fn lut_color(position: f32) -> vec4<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec4<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0),
+        1.0
+    );
+}
+
+// Dying states step down from just under the live color
+fn dying_position(state: u32) -> f32 {
+    return 1.0 - f32(state - 1u) / f32(max(params.states - 1u, 1u));
+}
+
+@compute @workgroup_size(8, 8)
+fn paint(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.display_width || id.y >= params.display_height) {
+        return;
+    }
+
+    let grid_size = vec2<u32>(params.grid_width, params.grid_height);
+    let display_size = vec2<f32>(f32(params.display_width), f32(params.display_height));
+    let point = (vec2<f32>(id.xy) + 0.5) / display_size * vec2<f32>(grid_size);
+    let cell = topology_wrap(topology_cell_at(params.topology, point), grid_size);
+    let word = cells[cell.y * params.grid_width + cell.x];
+    let state = word & STATE_MASK;
+    let history = word >> 8u;
+    let faded = clamp(f32(history) / params.history_span, 0.0, 1.0);
+
+    var position = 0.0;
+    if (params.color_mode == 0u) {
+        if (state == 1u) {
+            position = 1.0;
+        } else if (state > 1u) {
+            position = dying_position(state);
+        }
+    } else if (params.color_mode == 1u) {
+        if (state == 1u) {
+            // Newborn cells are brightest, long-lived ones settle lower
+            position = 1.0 - 0.75 * faded;
+        } else if (state > 1u) {
+            position = 0.25 * dying_position(state);
+        }
+    } else {
+        if (state == 1u) {
+            position = 1.0;
+        } else if (history != HISTORY_MAX) {
+            position = 0.75 * (1.0 - faded);
+        }
+    }
+
+    textureStore(output_texture, vec2<i32>(id.xy), lut_color(position));
+}
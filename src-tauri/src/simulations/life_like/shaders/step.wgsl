@@ -0,0 +1,106 @@
+// One generation of a life-like rule, one invocation per cell. The grid
+// wraps at the edges. Square grids count every cell within the radius; hex
+// and triangle grids count the cells touching each one.
+//
+// Each cell holds its state in the low byte and its history above it: how
+// many generations a live cell has survived, or how many have passed since a
+// dead or dying one was last alive.
+
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    states: u32,
+    radius: u32,
+    neighborhood: u32, // 0 = Moore, 1 = von Neumann
+    include_center: u32,
+    color_mode: u32, // 0 = State, 1 = Age, 2 = Trail
+    history_span: f32,
+    topology: u32,
+    display_width: u32,
+    display_height: u32,
+    _pad0: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> cells_in: array<u32>;
+@group(0) @binding(2) var<storage, read_write> cells_out: array<u32>;
+// Birth count bits in the first MASK_WORDS words, survival in the rest
+@group(0) @binding(3) var<storage, read> rule: array<u32>;
+
+const STATE_MASK: u32 = 0xffu;
+const HISTORY_MAX: u32 = 0xffffffu;
+const MASK_WORDS: u32 = 8u;
+
+fn is_alive(cell: vec2<i32>) -> u32 {
+    let wrapped = topology_wrap(cell, vec2<u32>(params.grid_width, params.grid_height));
+    let word = cells_in[wrapped.y * params.grid_width + wrapped.x];
+    return select(0u, 1u, (word & STATE_MASK) == 1u);
+}
+
+fn has_count(offset: u32, count: u32) -> bool {
+    return ((rule[offset + count / 32u] >> (count % 32u)) & 1u) == 1u;
+}
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height) {
+        return;
+    }
+
+    let cell = vec2<i32>(id.xy);
+    var count = 0u;
+    if (params.topology == TOPOLOGY_SQUARE) {
+        let radius = i32(params.radius);
+        for (var dy = -radius; dy <= radius; dy++) {
+            for (var dx = -radius; dx <= radius; dx++) {
+                if (dx == 0 && dy == 0) {
+                    continue;
+                }
+                if (params.neighborhood == 1u && abs(dx) + abs(dy) > radius) {
+                    continue;
+                }
+                count += is_alive(cell + vec2<i32>(dx, dy));
+            }
+        }
+    } else {
+        // The von Neumann neighborhood keeps to the cells sharing an edge
+        let neighbors = select(
+            topology_neighbor_count(params.topology),
+            topology_edge_neighbor_count(params.topology),
+            params.neighborhood == 1u
+        );
+        for (var i = 0u; i < neighbors; i++) {
+            count += is_alive(topology_neighbor(params.topology, cell, i));
+        }
+    }
+
+    let index = id.y * params.grid_width + id.x;
+    let word = cells_in[index];
+    let state = word & STATE_MASK;
+    let history = word >> 8u;
+    let aged = min(history + 1u, HISTORY_MAX);
+    if (params.include_center == 1u && state == 1u) {
+        count += 1u;
+    }
+
+    var next_state = 0u;
+    var next_history = aged;
+    if (state == 0u) {
+        if (has_count(0u, count)) {
+            next_state = 1u;
+            next_history = 0u;
+        }
+    } else if (state == 1u) {
+        if (has_count(MASK_WORDS, count)) {
+            next_state = 1u;
+        } else {
+            // Dying starts the count since the cell was last alive
+            next_state = select(0u, 2u, params.states > 2u);
+            next_history = 0u;
+        }
+    } else {
+        next_state = select(state + 1u, 0u, state + 1u >= params.states);
+    }
+
+    cells_out[index] = (next_history << 8u) | next_state;
+}
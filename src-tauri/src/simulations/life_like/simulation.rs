@@ -0,0 +1,1006 @@
+//! # Life-like Simulation Module
+//!
+//! Conway's Life and its relatives on a square, hex or triangle grid that
+//! wraps at the edges; see [`grid_topology`] for how the shapes are laid out.
+//!
+//! ## Technical Overview
+//!
+//! Each frame runs as many generations as the speed calls for, then paints
+//! and draws the grid:
+//! 1. Each generation is a compute pass reading one cell buffer and writing
+//!    the other, which then swap.
+//! 2. The latest generation is colored into the display texture by state,
+//!    by how long live cells have lasted or by how recently cells died.
+//!    Hex and triangle cells are drawn out at about the surface size so
+//!    their edges show.
+//! 3. The display texture is drawn through the camera with infinite tiling.
+//!
+//! The grid starts from a soup: a patch of random cells drawn from a seed,
+//! so an interesting start can be found again. With a soup interval set,
+//! the seed moves on after that many generations, searching through soups
+//! on its own.
+
+use bytemuck::{Pod, Zeroable};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor,
+    Buffer, BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    FilterMode, PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, Sampler,
+    SamplerDescriptor, ShaderStages, SurfaceConfiguration, Texture, TextureFormat, TextureView,
+    TextureViewDescriptor,
+};
+
+use crate::commands::AppSettings;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::grid_topology;
+use crate::simulations::shared::ping_pong_buffers::PingPongBuffers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, GridTopology, RewindResource,
+};
+use crate::simulations::traits::Simulation;
+
+use super::rule::{LifeRule, Neighborhood};
+use super::settings::{LifeColorMode, Settings, SoupSymmetry};
+use super::shaders::{PAINT_SHADER, RENDER_INFINITE_SHADER, STEP_SHADER};
+use super::state::State;
+
+/// Generations run in a single frame however far behind the speed falls
+const MAX_GENERATIONS_PER_FRAME: u32 = 16;
+/// A live cell with no history yet
+pub const ALIVE: u32 = 1;
+/// A dead cell that has never been alive: state 0 under the largest history
+pub const NEVER_ALIVE: u32 = 0xff_ffff << 8;
+/// Cells drawn around the cursor in each direction
+const BRUSH_RADIUS: i32 = 2;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    states: u32,
+    radius: u32,
+    neighborhood: u32, // 0 = Moore, 1 = von Neumann
+    include_center: u32,
+    color_mode: u32, // 0 = State, 1 = Age, 2 = Trail
+    history_span: f32,
+    topology: u32,
+    display_width: u32,
+    display_height: u32,
+    _pad0: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct RenderParams {
+    filtering_mode: u32, // 0 = nearest, 1 = linear, 2 = lanczos
+    _pad1: u32,
+    _pad2: u32,
+    _pad3: u32,
+}
+
+/// Everything the grid's bind groups point at besides the grid itself
+#[derive(Debug)]
+struct Resources {
+    step_bind_group_layout: BindGroupLayout,
+    paint_bind_group_layout: BindGroupLayout,
+    render_infinite_bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+    params_buffer: Buffer,
+    rule_buffer: Buffer,
+    lut_buffer: Buffer,
+    texture_render_params_buffer: Buffer,
+}
+
+/// The cells and the texture they're painted into, remade when the grid
+/// changes size. Bind groups come in pairs, one for each buffer being the
+/// current generation.
+#[derive(Debug)]
+struct Grid {
+    width: u32,
+    height: u32,
+    display_width: u32,
+    display_height: u32,
+    cells: PingPongBuffers,
+    _display_texture: Texture,
+    step_bind_groups: [BindGroup; 2],
+    paint_bind_groups: [BindGroup; 2],
+    render_infinite_bind_group: BindGroup,
+}
+
+impl Grid {
+    fn new(
+        device: &Device,
+        (width, height): (u32, u32),
+        (display_width, display_height): (u32, u32),
+        resources: &Resources,
+    ) -> Self {
+        let cells = PingPongBuffers::new(
+            device,
+            (width * height) as u64 * std::mem::size_of::<u32>() as u64,
+            BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+            "Life-like Cells Buffer",
+        );
+
+        let display_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Life-like Display Texture"),
+            size: wgpu::Extent3d {
+                width: display_width,
+                height: display_height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let display_view = display_texture.create_view(&TextureViewDescriptor::default());
+
+        // Index 0 reads the current buffer before any swap
+        let (first, second) = (cells.current_buffer(), cells.inactive_buffer());
+        let step_bind_group = |label, from: &Buffer, to: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.step_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, from),
+                    resource_helpers::buffer_entry(2, to),
+                    resource_helpers::buffer_entry(3, &resources.rule_buffer),
+                ],
+            })
+        };
+        let step_bind_groups = [
+            step_bind_group("Life-like Step Bind Group A", first, second),
+            step_bind_group("Life-like Step Bind Group B", second, first),
+        ];
+
+        let paint_bind_group = |label, cells: &Buffer| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(label),
+                layout: &resources.paint_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, cells),
+                    resource_helpers::buffer_entry(2, &resources.lut_buffer),
+                    resource_helpers::texture_view_entry(3, &display_view),
+                ],
+            })
+        };
+        let paint_bind_groups = [
+            paint_bind_group("Life-like Paint Bind Group A", first),
+            paint_bind_group("Life-like Paint Bind Group B", second),
+        ];
+
+        let render_infinite_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Life-like Render Infinite Bind Group"),
+            layout: &resources.render_infinite_bind_group_layout,
+            entries: &[
+                resource_helpers::texture_view_entry(0, &display_view),
+                resource_helpers::sampler_bind_entry(1, &resources.sampler),
+                resource_helpers::buffer_entry(2, &resources.texture_render_params_buffer),
+            ],
+        });
+
+        Self {
+            width,
+            height,
+            display_width,
+            display_height,
+            cells,
+            _display_texture: display_texture,
+            step_bind_groups,
+            paint_bind_groups,
+            render_infinite_bind_group,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LifeLikeModel {
+    pub settings: Settings,
+    pub state: State,
+    rule: LifeRule,
+    // Fractional generations carried between frames
+    pending_generations: f32,
+
+    // GPU resources
+    step_pipeline: ComputePipeline,
+    paint_pipeline: ComputePipeline,
+    render_infinite_pipeline: RenderPipeline,
+    resources: Resources,
+    grid: Grid,
+    camera_bind_group: BindGroup,
+
+    // Camera for infinite rendering
+    pub camera: Camera,
+
+    // Surface size the grid is laid over
+    width: u32,
+    height: u32,
+}
+
+/// The soup `settings` lays down on a `width` by `height` grid, the same for
+/// the same seed
+pub fn soup(settings: &Settings, width: u32, height: u32) -> Vec<u32> {
+    let mut rng = StdRng::seed_from_u64(settings.soup_seed as u64);
+    let density = settings.soup_density.clamp(0.0, 1.0) as f64;
+    let size = settings.soup_size.clamp(0.0, 1.0);
+    let soup_width = ((width as f32 * size).round() as u32).clamp(1, width);
+    let soup_height = ((height as f32 * size).round() as u32).clamp(1, height);
+    let (left, top) = ((width - soup_width) / 2, (height - soup_height) / 2);
+
+    // Cells drawn at random, the rest mirrored from them
+    let (drawn_width, drawn_height) = match settings.soup_symmetry {
+        SoupSymmetry::None => (soup_width, soup_height),
+        SoupSymmetry::Mirror => (soup_width.div_ceil(2), soup_height),
+        SoupSymmetry::Quad => (soup_width.div_ceil(2), soup_height.div_ceil(2)),
+    };
+    let drawn: Vec<bool> = (0..drawn_width * drawn_height)
+        .map(|_| rng.random_bool(density))
+        .collect();
+
+    let mut cells = vec![NEVER_ALIVE; (width * height) as usize];
+    for y in 0..soup_height {
+        for x in 0..soup_width {
+            let (drawn_x, drawn_y) = match settings.soup_symmetry {
+                SoupSymmetry::None => (x, y),
+                SoupSymmetry::Mirror => (x.min(soup_width - 1 - x), y),
+                SoupSymmetry::Quad => (x.min(soup_width - 1 - x), y.min(soup_height - 1 - y)),
+            };
+            if drawn[(drawn_y * drawn_width + drawn_x) as usize] {
+                cells[((top + y) * width + left + x) as usize] = ALIVE;
+            }
+        }
+    }
+    cells
+}
+
+impl LifeLikeModel {
+    /// Calculate the number of tiles needed for infinite rendering based on zoom level
+    fn calculate_tile_count(&self) -> u32 {
+        let zoom = self.camera.zoom;
+        // Each tile covers 2.0 world units, so we need enough tiles to cover the visible area
+        let visible_world_size = 2.0 / zoom;
+        let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
+        let min_tiles = if zoom < 0.1 { 7 } else { 5 };
+        tiles_needed.max(min_tiles).min(1024)
+    }
+
+    fn grid_size(&self) -> (u32, u32) {
+        self.settings
+            .topology
+            .grid_size(self.width, self.height, self.settings.cell_size)
+    }
+
+    /// A texel per cell for square grids. Other shapes only show at several
+    /// texels per cell, so they're painted at about the surface size.
+    fn display_size(&self, (width, height): (u32, u32)) -> (u32, u32) {
+        let topology = self.settings.topology;
+        if topology == GridTopology::Square {
+            return (width, height);
+        }
+        let column_width = self.settings.cell_size.max(1);
+        let row_height = topology.row_height() * column_width as f32;
+        (
+            width * column_width,
+            ((height as f32 * row_height).round() as u32).max(1),
+        )
+    }
+
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let rule: LifeRule = settings
+            .rule
+            .parse()
+            .map_err(SimulationError::InvalidParameter)?;
+        check_topology(&rule, settings.topology)?;
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let step_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Life-like Step Shader"),
+            source: wgpu::ShaderSource::Wgsl(STEP_SHADER.into()),
+        });
+        let paint_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Life-like Paint Shader"),
+            source: wgpu::ShaderSource::Wgsl(PAINT_SHADER.into()),
+        });
+        let render_infinite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Life-like Render Infinite Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_INFINITE_SHADER.into()),
+        });
+
+        // Nearest filtering keeps the cells crisp when zoomed in
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Life-like Sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Life-like Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let rule_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Life-like Rule Buffer"),
+            contents: bytemuck::cast_slice(&rule.to_gpu_words()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Life-like LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let render_params = RenderParams {
+            filtering_mode: app_settings.texture_filtering.into(),
+            _pad1: 0,
+            _pad2: 0,
+            _pad3: 0,
+        };
+        let texture_render_params_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Life-like Texture Render Params Buffer"),
+                contents: bytemuck::cast_slice(&[render_params]),
+                usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            });
+
+        let step_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Life-like Step Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, false),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, true),
+            ],
+        });
+
+        let paint_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Life-like Paint Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_texture_entry(
+                    3,
+                    ShaderStages::COMPUTE,
+                    wgpu::StorageTextureAccess::WriteOnly,
+                    TextureFormat::Rgba8Unorm,
+                ),
+            ],
+        });
+
+        let render_infinite_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Life-like Render Infinite Bind Group Layout"),
+                entries: &[
+                    resource_helpers::texture_entry(
+                        0,
+                        ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        1,
+                        ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                    resource_helpers::uniform_buffer_entry(2, ShaderStages::FRAGMENT),
+                ],
+            });
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX,
+                )],
+            });
+
+        let step_pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Life-like Step Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Life-like Step Pipeline Layout"),
+                bind_group_layouts: &[&step_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            module: &step_module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let paint_pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Life-like Paint Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Life-like Paint Pipeline Layout"),
+                bind_group_layouts: &[&paint_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            module: &paint_module,
+            entry_point: Some("paint"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let render_infinite_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Life-like Render Infinite Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Life-like Render Infinite Pipeline Layout"),
+                bind_group_layouts: &[
+                    &render_infinite_bind_group_layout,
+                    &camera_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_infinite_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_infinite_module,
+                entry_point: Some("fs_main_texture"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[resource_helpers::buffer_entry(0, camera.buffer())],
+        });
+
+        let resources = Resources {
+            step_bind_group_layout,
+            paint_bind_group_layout,
+            render_infinite_bind_group_layout,
+            sampler,
+            params_buffer,
+            rule_buffer,
+            lut_buffer,
+            texture_render_params_buffer,
+        };
+        // Replaced by resize_grid once the model exists
+        let grid = Grid::new(device, (1, 1), (1, 1), &resources);
+
+        let mut model = Self {
+            settings,
+            state,
+            rule,
+            pending_generations: 0.0,
+            step_pipeline,
+            paint_pipeline,
+            render_infinite_pipeline,
+            resources,
+            grid,
+            camera_bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.resize_grid(device, queue)?;
+        Ok(model)
+    }
+
+    /// Remake the grid for the surface size and cell size, and start over
+    /// on it
+    fn resize_grid(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let (width, height) = self.grid_size();
+        let display_size = self.display_size((width, height));
+        self.grid = Grid::new(device, (width, height), display_size, &self.resources);
+        self.state.grid_width = width;
+        self.state.grid_height = height;
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Lay the current seed's soup into both cell buffers
+    fn seed_soup(&mut self, queue: &Arc<Queue>) {
+        let cells = soup(&self.settings, self.grid.width, self.grid.height);
+        let bytes = bytemuck::cast_slice(&cells);
+        queue.write_buffer(self.grid.cells.current_buffer(), 0, bytes);
+        queue.write_buffer(self.grid.cells.inactive_buffer(), 0, bytes);
+        self.state.soup_generation = 0;
+    }
+
+    fn set_rule(&mut self, rule: LifeRule, queue: &Arc<Queue>) {
+        queue.write_buffer(
+            &self.resources.rule_buffer,
+            0,
+            bytemuck::cast_slice(&rule.to_gpu_words()),
+        );
+        self.settings.rule = rule.to_string();
+        self.rule = rule;
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let params = Params {
+            grid_width: self.grid.width,
+            grid_height: self.grid.height,
+            states: self.rule.states(),
+            radius: self.rule.radius(),
+            neighborhood: match self.rule.neighborhood() {
+                Neighborhood::Moore => 0,
+                Neighborhood::VonNeumann => 1,
+            },
+            include_center: self.rule.include_center() as u32,
+            color_mode: match self.settings.color_mode {
+                LifeColorMode::State => 0,
+                LifeColorMode::Age => 1,
+                LifeColorMode::Trail => 2,
+            },
+            history_span: self.settings.history_span.max(1.0),
+            topology: self.settings.topology.shader_index(),
+            display_width: self.grid.display_width,
+            display_height: self.grid.display_height,
+            _pad0: 0,
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
+    fn encode_paint(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Life-like Paint Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.paint_pipeline);
+        compute_pass.set_bind_group(
+            0,
+            &self.grid.paint_bind_groups[self.grid.cells.current_index()],
+            &[],
+        );
+        compute_pass.dispatch_workgroups(
+            self.grid.display_width.div_ceil(8),
+            self.grid.display_height.div_ceil(8),
+            1,
+        );
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let tile_count = self.calculate_tile_count();
+        let total_instances = tile_count * tile_count;
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Life-like Infinite Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.render_infinite_pipeline);
+        render_pass.set_bind_group(0, &self.grid.render_infinite_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+        render_pass.draw(0..6, 0..total_instances);
+    }
+}
+
+/// Hex and triangle grids count the cells touching each one, which leaves
+/// no room for a wider radius
+fn check_topology(rule: &LifeRule, topology: GridTopology) -> SimulationResult<()> {
+    if topology != GridTopology::Square && rule.radius() > 1 {
+        return Err(SimulationError::InvalidParameter(format!(
+            "{:?} grids only take radius 1 rules, and {} has radius {}",
+            topology,
+            rule,
+            rule.radius()
+        )));
+    }
+    Ok(())
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for LifeLikeModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        let interval = self.settings.soup_interval as u64;
+        if interval > 0 && self.state.soup_generation >= interval {
+            self.settings.soup_seed = self.settings.soup_seed.wrapping_add(1);
+            self.seed_soup(queue);
+        }
+
+        self.pending_generations += delta_time * self.settings.generations_per_second;
+        let generations = (self.pending_generations as u32).min(MAX_GENERATIONS_PER_FRAME);
+        // Whatever couldn't run this frame is dropped rather than piling up
+        self.pending_generations = self.pending_generations.fract();
+        self.state.generation += generations as u64;
+        self.state.soup_generation += generations as u64;
+        self.update_params(queue);
+
+        self.camera.update(delta_time);
+        self.camera.upload_to_gpu(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Life-like Render"),
+        });
+
+        if generations > 0 {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Life-like Step Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.step_pipeline);
+            for _ in 0..generations {
+                compute_pass.set_bind_group(
+                    0,
+                    &self.grid.step_bind_groups[self.grid.cells.current_index()],
+                    &[],
+                );
+                compute_pass.dispatch_workgroups(
+                    self.grid.width.div_ceil(8),
+                    self.grid.height.div_ceil(8),
+                    1,
+                );
+                self.grid.cells.swap();
+            }
+        }
+        self.encode_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // Repainted so color changes show while paused
+        self.update_params(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Life-like Render Paused"),
+        });
+        self.encode_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.resize_grid(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Left brings the cells under the cursor to life, right kills them
+        let word = match mouse_button {
+            0 => ALIVE,
+            2 => NEVER_ALIVE,
+            _ => return Ok(()),
+        };
+
+        // Wrap world coords to the base tile, then flip Y into grid rows
+        let wrapped_x = (world_x + 1.0).rem_euclid(2.0) - 1.0;
+        let wrapped_y = (world_y + 1.0).rem_euclid(2.0) - 1.0;
+        let (width, height) = (self.grid.width, self.grid.height);
+        let (x, y) = self.settings.topology.cell_at((
+            (wrapped_x + 1.0) * 0.5 * width as f32,
+            (1.0 - wrapped_y) * 0.5 * height as f32,
+        ));
+
+        for dy in -BRUSH_RADIUS..=BRUSH_RADIUS {
+            for dx in -BRUSH_RADIUS..=BRUSH_RADIUS {
+                let (cell_x, cell_y) = grid_topology::wrap(x + dx, y + dy, width, height);
+                let offset = (cell_y * width + cell_x) as u64 * std::mem::size_of::<u32>() as u64;
+                queue.write_buffer(
+                    self.grid.cells.current_buffer(),
+                    offset,
+                    bytemuck::bytes_of(&word),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    // Integer cells stepped from a copy of the last generation, and
+    // generations counted from the frame times
+    fn supports_replay(&self) -> bool {
+        true
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        vec![
+            RewindResource::Buffer(self.grid.cells.current_buffer()),
+            RewindResource::Buffer(self.grid.cells.inactive_buffer()),
+        ]
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let rule: LifeRule = new_settings
+            .rule
+            .parse()
+            .map_err(SimulationError::InvalidParameter)?;
+        check_topology(&rule, new_settings.topology)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+        self.set_rule(rule, queue);
+
+        if old_settings.cell_size != self.settings.cell_size
+            || old_settings.topology != self.settings.topology
+        {
+            self.resize_grid(device, queue)?;
+        } else if old_settings.soup_seed != self.settings.soup_seed
+            || old_settings.soup_density != self.settings.soup_density
+            || old_settings.soup_size != self.settings.soup_size
+            || old_settings.soup_symmetry != self.settings.soup_symmetry
+        {
+            self.seed_soup(queue);
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.seed_soup(queue);
+        self.pending_generations = 0.0;
+        self.state.generation = 0;
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.set_rule(LifeRule::random(&mut rng), queue);
+        self.settings.soup_seed = rng.random();
+        self.settings.soup_density = rng.random_range(0.15..0.5);
+        self.settings.soup_symmetry = match rng.random_range(0..3) {
+            0 => SoupSymmetry::None,
+            1 => SoupSymmetry::Mirror,
+            _ => SoupSymmetry::Quad,
+        };
+        self.settings.color_mode = match rng.random_range(0..3) {
+            0 => LifeColorMode::State,
+            1 => LifeColorMode::Age,
+            _ => LifeColorMode::Trail,
+        };
+        // A new rule is best watched from a fresh soup
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "rule" => {
+                // The grid carries on under the new rule
+                let rule: LifeRule = value
+                    .as_str()
+                    .ok_or("rule must be a string")?
+                    .parse()
+                    .map_err(SimulationError::InvalidParameter)?;
+                check_topology(&rule, self.settings.topology)?;
+                self.set_rule(rule, queue);
+            }
+            "generations_per_second" => {
+                self.settings.generations_per_second = number(setting_name, &value)? as f32
+            }
+            "cell_size" => {
+                self.settings.cell_size = number(setting_name, &value)? as u32;
+                self.resize_grid(device, queue)?;
+            }
+            "topology" => {
+                let topology: GridTopology = value
+                    .as_str()
+                    .unwrap_or("Square")
+                    .parse()
+                    .map_err(|e| format!("Invalid topology: {}", e))?;
+                check_topology(&self.rule, topology)?;
+                self.settings.topology = topology;
+                self.resize_grid(device, queue)?;
+            }
+            "soup_density" => {
+                self.settings.soup_density = number(setting_name, &value)? as f32;
+                self.seed_soup(queue);
+            }
+            "soup_size" => {
+                self.settings.soup_size = number(setting_name, &value)? as f32;
+                self.seed_soup(queue);
+            }
+            "soup_symmetry" => {
+                self.settings.soup_symmetry = value
+                    .as_str()
+                    .unwrap_or("None")
+                    .parse()
+                    .map_err(|e| format!("Invalid soup_symmetry: {}", e))?;
+                self.seed_soup(queue);
+            }
+            "soup_seed" => {
+                self.settings.soup_seed = number(setting_name, &value)? as u32;
+                self.seed_soup(queue);
+            }
+            "soup_interval" => self.settings.soup_interval = number(setting_name, &value)? as u32,
+            "color_mode" => {
+                self.settings.color_mode = value
+                    .as_str()
+                    .unwrap_or("State")
+                    .parse()
+                    .map_err(|e| format!("Invalid color_mode: {}", e))?;
+            }
+            "history_span" => self.settings.history_span = number(setting_name, &value)? as f32,
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
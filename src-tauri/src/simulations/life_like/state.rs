@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Grid size in cells
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Generations since the grid was reset, and since the current soup was
+    // laid down
+    pub generation: u64,
+    pub soup_generation: u64,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            grid_width: 0,
+            grid_height: 0,
+            generation: 0,
+            soup_generation: 0,
+            color_scheme_name: "MATPLOTLIB_inferno".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::rule::{LifeRule, Neighborhood};
+use super::settings::{Settings, SoupSymmetry};
+use super::shaders::{PAINT_SHADER, RENDER_INFINITE_SHADER, STEP_SHADER};
+use super::simulation::{ALIVE, NEVER_ALIVE, soup};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+#[test]
+fn notations_agree_on_conways_life() {
+    let bs: LifeRule = "B3/S23".parse().unwrap();
+    assert_eq!(bs, LifeRule::default());
+    assert_eq!("23/3".parse::<LifeRule>().unwrap(), bs);
+    assert_eq!("s23/b3".parse::<LifeRule>().unwrap(), bs);
+    assert_eq!("R1,C0,M0,S2..3,B3..3,NM".parse::<LifeRule>().unwrap(), bs);
+    assert_eq!(bs.to_string(), "B3/S23");
+}
+
+#[test]
+fn generations_and_larger_rules_round_trip() {
+    let brain: LifeRule = "/2/3".parse().unwrap();
+    assert_eq!(brain.states(), 3);
+    assert_eq!(brain.to_string(), "B2/S/C3");
+
+    let von_neumann: LifeRule = "B1/S1V".parse().unwrap();
+    assert_eq!(von_neumann.neighborhood(), Neighborhood::VonNeumann);
+    assert_eq!(von_neumann.to_string(), "B1/S1V");
+
+    let bugs: LifeRule = "R5,C0,M1,S34..58,B34..45,NM".parse().unwrap();
+    assert_eq!(bugs.radius(), 5);
+    assert!(bugs.include_center());
+    assert_eq!(bugs.to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+
+    let lists: LifeRule = "R2,C4,S2-3,7,B5,NN".parse().unwrap();
+    assert_eq!(lists.to_string(), "R2,C4,M0,S2..3,7,B5,NN");
+    assert_eq!(lists.to_string().parse::<LifeRule>().unwrap(), lists);
+}
+
+#[test]
+fn bad_rules_are_rejected() {
+    for rule in [
+        "",
+        "B9/S23",
+        "B3/S23/C1",
+        "B3/X2",
+        "R8,C0,S1,B1,NM",
+        "R1,C0,S2..3,B9,NM",
+        "R2,S5..3,B3,NM",
+        "R2,3,B3",
+    ] {
+        assert!(
+            rule.parse::<LifeRule>().is_err(),
+            "{} should not parse",
+            rule
+        );
+    }
+}
+
+#[test]
+fn rule_masks_hold_every_count() {
+    let rule: LifeRule = "R7,C0,M1,S225,B0,NM".parse().unwrap();
+    let words = rule.to_gpu_words();
+    assert_eq!(words[0], 1);
+    assert_eq!(words[8 + 225 / 32], 1 << (225 % 32));
+}
+
+#[test]
+fn random_rules_parse_back() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..100 {
+        let rule = LifeRule::random(&mut rng);
+        assert_eq!(rule.to_string().parse::<LifeRule>().unwrap(), rule);
+    }
+}
+
+#[test]
+fn soups_follow_their_seed_and_symmetry() {
+    let settings = Settings {
+        soup_size: 0.5,
+        soup_density: 0.5,
+        ..Settings::default()
+    };
+    let (width, height) = (40, 30);
+    let cells = soup(&settings, width, height);
+    assert_eq!(cells, soup(&settings, width, height));
+    let other = Settings {
+        soup_seed: settings.soup_seed + 1,
+        ..settings.clone()
+    };
+    assert_ne!(cells, soup(&other, width, height));
+
+    // Everything outside the centered 20 by 15 patch stays empty
+    for y in 0..height {
+        for x in 0..width {
+            let inside = (10..30).contains(&x) && (7..22).contains(&y);
+            let cell = cells[(y * width + x) as usize];
+            assert!(cell == NEVER_ALIVE || (inside && cell == ALIVE));
+        }
+    }
+
+    let quad = Settings {
+        soup_symmetry: SoupSymmetry::Quad,
+        ..settings
+    };
+    let cells = soup(&quad, width, height);
+    let at = |x: u32, y: u32| cells[(y * width + x) as usize];
+    for y in 7..22 {
+        for x in 10..30 {
+            assert_eq!(at(x, y), at(10 + 29 - x, y));
+            assert_eq!(at(x, y), at(x, 7 + 21 - y));
+        }
+    }
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Life-like Step Shader", STEP_SHADER),
+        ("Life-like Paint Shader", PAINT_SHADER),
+        ("Life-like Render Infinite Shader", RENDER_INFINITE_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
@@ -0,0 +1,68 @@
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::MagneticPendulumModel;
+
+use crate::simulation::preset_manager::{MagneticPendulumPresetManager, Preset};
+
+/// Initialize Magnetic Pendulum presets with built-in configurations
+pub fn init_presets(preset_manager: &mut MagneticPendulumPresetManager) {
+    use settings::{Settings, magnet_ring};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Four Magnets".to_string(),
+        Settings {
+            magnets: magnet_ring(4, 1.0),
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Six Magnets".to_string(),
+        Settings {
+            magnets: magnet_ring(6, 1.2),
+            magnet_strength: 0.8,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Low Friction".to_string(),
+        Settings {
+            friction: 0.08,
+            max_steps: 5000,
+            shading: 0.85,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Off Center".to_string(),
+        Settings {
+            magnets: vec![[-0.9, 0.4], [0.7, 0.8], [0.3, -1.0], [-0.2, -0.1]],
+            spring: 0.3,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Boundary Detail".to_string(),
+        Settings {
+            extent: 0.6,
+            max_steps: 4000,
+            tiles_per_frame: 6,
+            show_magnets: false,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,153 @@
+//! # Magnetic Pendulum Settings Module
+//!
+//! Where the magnets sit and how strongly they pull, the pendulum's spring
+//! and friction, how long each trajectory is followed, and how the basin
+//! map is worked out and shaded.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+
+/// Magnets the shader has room for
+pub const MAX_MAGNETS: usize = 8;
+
+/// `count` magnets evenly spaced on a circle of `radius`, the first straight
+/// up
+pub fn magnet_ring(count: usize, radius: f32) -> Vec<[f32; 2]> {
+    (0..count)
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / count as f32;
+            [-radius * angle.sin(), radius * angle.cos()]
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Positions in the plane the pendulum swings over, up to
+    /// [`MAX_MAGNETS`]
+    pub magnets: Vec<[f32; 2]>,
+    pub magnet_strength: f32,
+    /// Height of the pendulum bob above the magnets, which softens their
+    /// pull close up
+    pub magnet_height: f32,
+    /// Pull back toward the middle from the pendulum's own weight
+    pub spring: f32,
+    pub friction: f32,
+
+    /// Integration step, in the pendulum's own time
+    pub time_step: f32,
+    /// Steps followed before giving up and taking the nearest magnet
+    pub max_steps: u32,
+    /// How close and slow the bob must be over a magnet to count as caught
+    pub capture_radius: f32,
+
+    /// Half the height of the plane shown at zoom 1
+    pub extent: f32,
+    /// Share of the screen resolution the map is worked out at
+    pub resolution_scale: f32,
+    /// 64 pixel tiles refined each frame after the quick first pass
+    pub tiles_per_frame: u32,
+    /// How much slow captures darken, showing the bands between basins
+    pub shading: f32,
+    pub show_magnets: bool,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            magnets: magnet_ring(3, 1.0),
+            magnet_strength: 1.0,
+            magnet_height: 0.25,
+            spring: 0.5,
+            friction: 0.2,
+            time_step: 0.02,
+            max_steps: 2000,
+            capture_radius: 0.1,
+            extent: 2.0,
+            resolution_scale: 1.0,
+            tiles_per_frame: 12,
+            shading: 0.7,
+            show_magnets: true,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting).
+/// `magnet_count` lays the magnets out on a ring again.
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "magnet_count",
+            Rule::Count {
+                min: 1,
+                max: MAX_MAGNETS as u64,
+            },
+        ),
+        (
+            "magnet_strength",
+            Rule::Range {
+                min: 0.01,
+                max: 10.0,
+            },
+        ),
+        (
+            "magnet_height",
+            Rule::Range {
+                min: 0.01,
+                max: 2.0,
+            },
+        ),
+        ("spring", Rule::Range { min: 0.0, max: 5.0 }),
+        ("friction", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "time_step",
+            Rule::Range {
+                min: 0.001,
+                max: 0.1,
+            },
+        ),
+        (
+            "max_steps",
+            Rule::Count {
+                min: 10,
+                max: 20_000,
+            },
+        ),
+        (
+            "capture_radius",
+            Rule::Range {
+                min: 0.01,
+                max: 1.0,
+            },
+        ),
+        (
+            "extent",
+            Rule::Range {
+                min: 0.1,
+                max: 20.0,
+            },
+        ),
+        ("resolution_scale", Rule::Range { min: 0.1, max: 1.0 }),
+        ("tiles_per_frame", Rule::Count { min: 1, max: 256 }),
+        ("shading", Rule::Range { min: 0.0, max: 1.0 }),
+        ("show_magnets", Rule::Flag),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("magnets", SettingCategory::Generators),
+    ("shading", SettingCategory::Colors),
+    ("show_magnets", SettingCategory::Colors),
+]);
@@ -0,0 +1,124 @@
+// Follows a pendulum released from rest over each pixel until a magnet
+// catches it, storing which magnet it was and how many steps it took. The
+// preview pass works out one pixel in every PREVIEW_STRIDE square and fills
+// the square with it; the refine pass then goes over 64 pixel tiles at full
+// resolution, a few each frame.
+
+struct Params {
+    magnets: array<vec4<f32>, 8>, // xy in the plane
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    map_width: u32,
+    map_height: u32,
+    surface_width: u32,
+    surface_height: u32,
+    magnet_count: u32,
+    magnet_strength: f32,
+    magnet_height: f32,
+    spring: f32,
+    friction: f32,
+    time_step: f32,
+    max_steps: u32,
+    capture_radius: f32,
+    first_tile: u32,
+    tiles_across: u32,
+    shading: f32,
+    show_magnets: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> basins: array<u32>;
+
+const TILE_SIZE: u32 = 64u;
+const PREVIEW_STRIDE: u32 = 4u;
+// The bob has to have nearly stopped over a magnet, not just be passing by
+const CAPTURE_SPEED: f32 = 0.5;
+
+// Where a point on the map sits in the plane, through the camera's view
+fn plane_position(pixel: vec2<f32>) -> vec2<f32> {
+    let size = vec2<f32>(f32(params.map_width), f32(params.map_height));
+    let ndc = vec2<f32>(pixel.x / size.x * 2.0 - 1.0, 1.0 - pixel.y / size.y * 2.0);
+    let world = ndc / params.view_zoom + params.view_center;
+    return vec2<f32>(world.x * params.aspect, world.y) * params.extent;
+}
+
+fn acceleration(position: vec2<f32>, velocity: vec2<f32>) -> vec2<f32> {
+    var force = -params.spring * position - params.friction * velocity;
+    let height_squared = params.magnet_height * params.magnet_height;
+    for (var i = 0u; i < params.magnet_count; i++) {
+        let offset = params.magnets[i].xy - position;
+        let distance_squared = dot(offset, offset) + height_squared;
+        force += params.magnet_strength * offset / (distance_squared * sqrt(distance_squared));
+    }
+    return force;
+}
+
+fn nearest_magnet(position: vec2<f32>) -> u32 {
+    var nearest = 0u;
+    var nearest_distance = 1e30;
+    for (var i = 0u; i < params.magnet_count; i++) {
+        let d = distance(position, params.magnets[i].xy);
+        if (d < nearest_distance) {
+            nearest = i;
+            nearest_distance = d;
+        }
+    }
+    return nearest;
+}
+
+// Magnet index + 1 in the low byte, steps taken above it
+fn basin(start: vec2<f32>) -> u32 {
+    var position = start;
+    var velocity = vec2<f32>(0.0);
+    for (var step = 0u; step < params.max_steps; step++) {
+        // Semi-implicit Euler keeps the swing from gaining energy
+        velocity += acceleration(position, velocity) * params.time_step;
+        position += velocity * params.time_step;
+        if (length(velocity) < CAPTURE_SPEED) {
+            let magnet = nearest_magnet(position);
+            if (distance(position, params.magnets[magnet].xy) < params.capture_radius) {
+                return (step << 8u) | (magnet + 1u);
+            }
+        }
+    }
+    // Still swinging when time ran out
+    return (params.max_steps << 8u) | (nearest_magnet(position) + 1u);
+}
+
+@compute @workgroup_size(8, 8)
+fn preview(@builtin(global_invocation_id) id: vec3<u32>) {
+    let origin = id.xy * PREVIEW_STRIDE;
+    if (origin.x >= params.map_width || origin.y >= params.map_height) {
+        return;
+    }
+    let word = basin(plane_position(vec2<f32>(origin) + f32(PREVIEW_STRIDE) * 0.5));
+    for (var dy = 0u; dy < PREVIEW_STRIDE; dy++) {
+        for (var dx = 0u; dx < PREVIEW_STRIDE; dx++) {
+            let pixel = origin + vec2<u32>(dx, dy);
+            if (pixel.x < params.map_width && pixel.y < params.map_height) {
+                basins[pixel.y * params.map_width + pixel.x] = word;
+            }
+        }
+    }
+}
+
+// Dispatched 8 workgroups high and 8 wide for each tile
+@compute @workgroup_size(8, 8)
+fn refine(
+    @builtin(workgroup_id) group: vec3<u32>,
+    @builtin(local_invocation_id) local: vec3<u32>,
+) {
+    let groups_per_tile = TILE_SIZE / 8u;
+    let tile = params.first_tile + group.x / groups_per_tile;
+    let tile_origin = vec2<u32>(tile % params.tiles_across, tile / params.tiles_across) * TILE_SIZE;
+    let pixel = tile_origin + vec2<u32>(group.x % groups_per_tile, group.y) * 8u + local.xy;
+    if (pixel.x >= params.map_width || pixel.y >= params.map_height) {
+        return;
+    }
+    basins[pixel.y * params.map_width + pixel.x] = basin(plane_position(vec2<f32>(pixel) + 0.5));
+}
@@ -0,0 +1,5 @@
+pub const BASINS_SHADER: &str = include_str!("basins.wgsl");
+pub const RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("render.wgsl")
+);
@@ -0,0 +1,95 @@
+// Draws the basin map over the screen, each basin in its magnet's color
+// from the scheme and darker the longer the pendulum took to settle, with
+// rings marking the magnets.
+
+struct Params {
+    magnets: array<vec4<f32>, 8>, // xy in the plane
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    map_width: u32,
+    map_height: u32,
+    surface_width: u32,
+    surface_height: u32,
+    magnet_count: u32,
+    magnet_strength: f32,
+    magnet_height: f32,
+    spring: f32,
+    friction: f32,
+    time_step: f32,
+    max_steps: u32,
+    capture_radius: f32,
+    first_tile: u32,
+    tiles_across: u32,
+    shading: f32,
+    show_magnets: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> basins: array<u32>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+// Marker sizes in screen pixels
+const MARKER_RADIUS: f32 = 5.0;
+const MARKER_RING: f32 = 2.0;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+}
+
+// One triangle covering the screen
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return VertexOutput(vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0));
+}
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+fn magnet_color(magnet: u32) -> vec3<f32> {
+    return lut_color((f32(magnet) + 0.5) / f32(max(params.magnet_count, 1u)));
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let surface_size = vec2<f32>(f32(params.surface_width), f32(params.surface_height));
+    let map_size = vec2<f32>(f32(params.map_width), f32(params.map_height));
+    let map_point = input.position.xy / surface_size * map_size;
+    let pixel = min(vec2<u32>(map_point), vec2<u32>(params.map_width, params.map_height) - 1u);
+
+    let word = basins[pixel.y * params.map_width + pixel.x];
+    let caught_by = word & 0xffu;
+    var color = vec3<f32>(0.0);
+    if (caught_by > 0u) {
+        let settle = sqrt(f32(word >> 8u) / f32(max(params.max_steps, 1u)));
+        color = magnet_color(caught_by - 1u) * (1.0 - params.shading * settle);
+    }
+
+    if (params.show_magnets != 0u) {
+        // Same mapping the basins were worked out with
+        let ndc = vec2<f32>(map_point.x / map_size.x * 2.0 - 1.0, 1.0 - map_point.y / map_size.y * 2.0);
+        let world = ndc / params.view_zoom + params.view_center;
+        let plane = vec2<f32>(world.x * params.aspect, world.y) * params.extent;
+        for (var i = 0u; i < params.magnet_count; i++) {
+            let pixels = distance(plane, params.magnets[i].xy) / params.plane_per_pixel;
+            if (pixels < MARKER_RADIUS) {
+                color = magnet_color(i);
+            } else if (pixels < MARKER_RADIUS + MARKER_RING) {
+                color = vec3<f32>(1.0);
+            }
+        }
+    }
+
+    return vec4<f32>(color, 1.0);
+}
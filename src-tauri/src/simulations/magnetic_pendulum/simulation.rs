@@ -0,0 +1,754 @@
+//! # Magnetic Pendulum Simulation Module
+//!
+//! A pendulum swinging over a handful of magnets ends up over one of them,
+//! but which one depends so sensitively on where it was let go that the
+//! basins of attraction interleave as a fractal. Every pixel is one release
+//! point, colored by the magnet that catches it.
+//!
+//! ## Technical Overview
+//!
+//! Each pixel's trajectory runs to the end in a compute shader, which is far
+//! too much work for one frame, so the map fills in progressively:
+//! 1. Whenever the view, magnets or physics change, a preview pass works out
+//!    one pixel in every 4x4 square and fills the square, so there's
+//!    something to look at straight away, including while dragging.
+//! 2. Later frames refine the map in 64 pixel tiles at full resolution,
+//!    a few tiles each frame, until the whole view is done.
+//! 3. The map is drawn straight over the screen with the magnets marked.
+//!
+//! The map is worked out for the camera's view, so zooming in recomputes
+//! finer detail instead of magnifying pixels. Dragging a magnet with the
+//! left button moves it and starts over.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, Buffer,
+    BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, ShaderStages,
+    SurfaceConfiguration, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager};
+use crate::simulations::traits::Simulation;
+
+use super::settings::{MAX_MAGNETS, Settings, magnet_ring};
+use super::shaders::{BASINS_SHADER, RENDER_SHADER};
+use super::state::State;
+
+/// Side of a refined tile in map pixels, matching `basins.wgsl`
+pub const TILE_SIZE: u32 = 64;
+/// Side of the squares the preview pass fills with one pixel's basin
+const PREVIEW_STRIDE: u32 = 4;
+/// Screen pixels from a magnet's center that still pick it up
+const GRAB_RADIUS: f32 = 12.0;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    magnets: [[f32; 4]; MAX_MAGNETS],
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    map_width: u32,
+    map_height: u32,
+    surface_width: u32,
+    surface_height: u32,
+    magnet_count: u32,
+    magnet_strength: f32,
+    magnet_height: f32,
+    spring: f32,
+    friction: f32,
+    time_step: f32,
+    max_steps: u32,
+    capture_radius: f32,
+    first_tile: u32,
+    tiles_across: u32,
+    shading: f32,
+    show_magnets: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+/// The camera's view as the basin map sees it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct View {
+    pub center: [f32; 2],
+    pub zoom: f32,
+    /// Surface width over height
+    pub aspect: f32,
+    /// Half the height of the plane shown at zoom 1
+    pub extent: f32,
+}
+
+impl View {
+    /// Where a world position lands in the plane the pendulum swings over.
+    /// World space is stretched over the screen, so x is widened by the
+    /// aspect ratio to keep pixels square in the plane.
+    pub fn plane_position(&self, world: [f32; 2]) -> [f32; 2] {
+        [world[0] * self.aspect * self.extent, world[1] * self.extent]
+    }
+
+    /// Plane distance across one of `surface_width` screen pixels
+    pub fn plane_per_pixel(&self, surface_width: u32) -> f32 {
+        2.0 * self.extent * self.aspect / (self.zoom * surface_width.max(1) as f32)
+    }
+}
+
+/// Tiles across a `width` by `height` map, and in total
+pub fn tile_grid(width: u32, height: u32) -> (u32, u32) {
+    let across = width.div_ceil(TILE_SIZE);
+    (across, across * height.div_ceil(TILE_SIZE))
+}
+
+/// The magnet closest to `point` if it's within `radius`
+pub fn magnet_near(magnets: &[[f32; 2]], point: [f32; 2], radius: f32) -> Option<usize> {
+    magnets
+        .iter()
+        .map(|m| (m[0] - point[0]).hypot(m[1] - point[1]))
+        .enumerate()
+        .filter(|&(_, distance)| distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+#[derive(Debug)]
+pub struct MagneticPendulumModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    preview_pipeline: ComputePipeline,
+    refine_pipeline: ComputePipeline,
+    render_pipeline: RenderPipeline,
+    compute_bind_group_layout: BindGroupLayout,
+    render_bind_group_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    basins_buffer: Buffer,
+    compute_bind_group: BindGroup,
+    render_bind_group: BindGroup,
+
+    // The camera picks the part of the plane being mapped; it never draws
+    pub camera: Camera,
+    // View the map is being worked out for
+    view: View,
+
+    // Progress through the map since it last changed
+    needs_preview: bool,
+    next_tile: u32,
+
+    width: u32,
+    height: u32,
+}
+
+impl MagneticPendulumModel {
+    pub fn new(
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let basins_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Magnetic Pendulum Basins Shader"),
+            source: wgpu::ShaderSource::Wgsl(BASINS_SHADER.into()),
+        });
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Magnetic Pendulum Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Magnetic Pendulum Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Magnetic Pendulum LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let compute_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Magnetic Pendulum Compute Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+                ],
+            });
+
+        let render_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Magnetic Pendulum Render Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::FRAGMENT),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::FRAGMENT, true),
+                    resource_helpers::storage_buffer_entry(2, ShaderStages::FRAGMENT, true),
+                ],
+            });
+
+        let compute_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Magnetic Pendulum Compute Pipeline Layout"),
+            bind_group_layouts: &[&compute_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let compute_pipeline = |label, entry_point| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&compute_pipeline_layout),
+                module: &basins_module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let preview_pipeline = compute_pipeline("Magnetic Pendulum Preview Pipeline", "preview");
+        let refine_pipeline = compute_pipeline("Magnetic Pendulum Refine Pipeline", "refine");
+
+        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Magnetic Pendulum Render Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Magnetic Pendulum Render Pipeline Layout"),
+                bind_group_layouts: &[&render_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let (map_width, map_height) = map_size(&settings, surface_config);
+        let (basins_buffer, compute_bind_group, render_bind_group) = create_map(
+            device,
+            (map_width, map_height),
+            &compute_bind_group_layout,
+            &render_bind_group_layout,
+            &params_buffer,
+            &lut_buffer,
+        );
+
+        let view = View {
+            center: camera.get_target_position(),
+            zoom: camera.get_target_zoom(),
+            aspect: surface_config.width as f32 / surface_config.height.max(1) as f32,
+            extent: settings.extent,
+        };
+
+        let mut model = Self {
+            settings,
+            state,
+            preview_pipeline,
+            refine_pipeline,
+            render_pipeline,
+            compute_bind_group_layout,
+            render_bind_group_layout,
+            params_buffer,
+            lut_buffer,
+            basins_buffer,
+            compute_bind_group,
+            render_bind_group,
+            camera,
+            view,
+            needs_preview: true,
+            next_tile: 0,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.state.map_width = map_width;
+        model.state.map_height = map_height;
+        Ok(model)
+    }
+
+    /// Start working the map out again from the preview
+    fn restart(&mut self) {
+        self.needs_preview = true;
+        self.next_tile = 0;
+        self.state.progress = 0.0;
+    }
+
+    /// Follow the camera, starting over when it has moved
+    fn refresh_view(&mut self) {
+        let view = View {
+            center: self.camera.get_target_position(),
+            zoom: self.camera.get_target_zoom(),
+            aspect: self.width as f32 / self.height.max(1) as f32,
+            extent: self.settings.extent,
+        };
+        if view != self.view {
+            self.view = view;
+            self.restart();
+        }
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let mut magnets = [[0.0; 4]; MAX_MAGNETS];
+        for (slot, magnet) in magnets.iter_mut().zip(&self.settings.magnets) {
+            slot[0] = magnet[0];
+            slot[1] = magnet[1];
+        }
+        let (tiles_across, _) = tile_grid(self.state.map_width, self.state.map_height);
+        let params = Params {
+            magnets,
+            view_center: self.view.center,
+            view_zoom: self.view.zoom,
+            aspect: self.view.aspect,
+            extent: self.view.extent,
+            plane_per_pixel: self.view.plane_per_pixel(self.width),
+            map_width: self.state.map_width,
+            map_height: self.state.map_height,
+            surface_width: self.width,
+            surface_height: self.height,
+            magnet_count: self.settings.magnets.len().min(MAX_MAGNETS) as u32,
+            magnet_strength: self.settings.magnet_strength,
+            magnet_height: self.settings.magnet_height,
+            spring: self.settings.spring,
+            friction: self.settings.friction,
+            time_step: self.settings.time_step,
+            max_steps: self.settings.max_steps,
+            capture_radius: self.settings.capture_radius,
+            first_tile: self.next_tile,
+            tiles_across,
+            shading: self.settings.shading,
+            show_magnets: self.settings.show_magnets as u32,
+            _pad0: 0,
+            _pad1: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    /// Work out the preview, or the next few tiles
+    fn encode_basins(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        let (_, total_tiles) = tile_grid(self.state.map_width, self.state.map_height);
+        if !self.needs_preview && self.next_tile >= total_tiles {
+            return;
+        }
+
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Magnetic Pendulum Basins Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &self.compute_bind_group, &[]);
+        if self.needs_preview {
+            // The whole frame goes to the preview
+            compute_pass.set_pipeline(&self.preview_pipeline);
+            let stride = PREVIEW_STRIDE * 8;
+            compute_pass.dispatch_workgroups(
+                self.state.map_width.div_ceil(stride),
+                self.state.map_height.div_ceil(stride),
+                1,
+            );
+            self.needs_preview = false;
+        } else {
+            let tiles = self
+                .settings
+                .tiles_per_frame
+                .max(1)
+                .min(total_tiles - self.next_tile);
+            let groups_per_tile = TILE_SIZE / 8;
+            compute_pass.set_pipeline(&self.refine_pipeline);
+            compute_pass.dispatch_workgroups(tiles * groups_per_tile, groups_per_tile, 1);
+            self.next_tile += tiles;
+            self.state.progress = self.next_tile as f32 / total_tiles as f32;
+        }
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Magnetic Pendulum Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    fn resize_map(&mut self, device: &Arc<Device>) {
+        let (width, height) = map_size_for(&self.settings, self.width, self.height);
+        let (basins_buffer, compute_bind_group, render_bind_group) = create_map(
+            device,
+            (width, height),
+            &self.compute_bind_group_layout,
+            &self.render_bind_group_layout,
+            &self.params_buffer,
+            &self.lut_buffer,
+        );
+        self.basins_buffer = basins_buffer;
+        self.compute_bind_group = compute_bind_group;
+        self.render_bind_group = render_bind_group;
+        self.state.map_width = width;
+        self.state.map_height = height;
+        self.restart();
+    }
+}
+
+fn map_size(settings: &Settings, surface_config: &SurfaceConfiguration) -> (u32, u32) {
+    map_size_for(settings, surface_config.width, surface_config.height)
+}
+
+fn map_size_for(settings: &Settings, width: u32, height: u32) -> (u32, u32) {
+    let scale = settings.resolution_scale.clamp(0.1, 1.0);
+    (
+        ((width as f32 * scale) as u32).max(1),
+        ((height as f32 * scale) as u32).max(1),
+    )
+}
+
+/// The basin buffer for a map of `size` and the bind groups reading it
+fn create_map(
+    device: &Device,
+    (width, height): (u32, u32),
+    compute_layout: &BindGroupLayout,
+    render_layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    lut_buffer: &Buffer,
+) -> (Buffer, BindGroup, BindGroup) {
+    // Zeroed, which the render shader shows as not yet worked out
+    let basins_buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("Magnetic Pendulum Basins Buffer"),
+        size: (width * height) as u64 * std::mem::size_of::<u32>() as u64,
+        usage: BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+    let compute_bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Magnetic Pendulum Compute Bind Group"),
+        layout: compute_layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, &basins_buffer),
+        ],
+    });
+    let render_bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Magnetic Pendulum Render Bind Group"),
+        layout: render_layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, &basins_buffer),
+            resource_helpers::buffer_entry(2, lut_buffer),
+        ],
+    });
+    (basins_buffer, compute_bind_group, render_bind_group)
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for MagneticPendulumModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        self.refresh_view();
+        // Written before the passes are encoded, so they see this frame's
+        // first tile
+        self.update_params(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Magnetic Pendulum Render"),
+        });
+        self.encode_basins(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // Nothing more is worked out, but shading and markers still update
+        self.update_params(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Magnetic Pendulum Render Paused"),
+        });
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.resize_map(device);
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        if mouse_button != 0 {
+            return Ok(());
+        }
+        let point = self.view.plane_position([world_x, world_y]);
+        let dragging = match self.state.dragging {
+            Some(magnet) => Some(magnet),
+            None => {
+                let radius = GRAB_RADIUS * self.view.plane_per_pixel(self.width);
+                magnet_near(&self.settings.magnets, point, radius)
+            }
+        };
+        if let Some(magnet) = dragging.filter(|&m| m < self.settings.magnets.len()) {
+            self.settings.magnets[magnet] = point;
+            self.state.dragging = Some(magnet);
+            self.restart();
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.dragging = None;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut new_settings: Settings = serde_json::from_value(settings)?;
+        new_settings.magnets.truncate(MAX_MAGNETS);
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+        if old_settings.resolution_scale != self.settings.resolution_scale {
+            self.resize_map(device);
+        }
+        self.state.dragging = None;
+        self.restart();
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.dragging = None;
+        self.restart();
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        let count = rng.random_range(3..=6);
+        // A loose ring keeps the magnets apart without being too regular
+        self.settings.magnets = magnet_ring(count, 1.0)
+            .into_iter()
+            .map(|[x, y]| {
+                let scale = rng.random_range(0.6..1.4);
+                [x * scale, y * scale]
+            })
+            .collect();
+        self.settings.spring = rng.random_range(0.2..0.8);
+        self.settings.friction = rng.random_range(0.08..0.3);
+        self.settings.magnet_height = rng.random_range(0.15..0.4);
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "magnet_count" => {
+                let count = number(setting_name, &value)? as usize;
+                self.settings.magnets = magnet_ring(count.clamp(1, MAX_MAGNETS), 1.0);
+            }
+            "magnets" => {
+                let mut magnets: Vec<[f32; 2]> = serde_json::from_value(value)?;
+                magnets.truncate(MAX_MAGNETS);
+                self.settings.magnets = magnets;
+            }
+            "magnet_strength" => {
+                self.settings.magnet_strength = number(setting_name, &value)? as f32
+            }
+            "magnet_height" => self.settings.magnet_height = number(setting_name, &value)? as f32,
+            "spring" => self.settings.spring = number(setting_name, &value)? as f32,
+            "friction" => self.settings.friction = number(setting_name, &value)? as f32,
+            "time_step" => self.settings.time_step = number(setting_name, &value)? as f32,
+            "max_steps" => self.settings.max_steps = number(setting_name, &value)? as u32,
+            "capture_radius" => self.settings.capture_radius = number(setting_name, &value)? as f32,
+            "extent" => self.settings.extent = number(setting_name, &value)? as f32,
+            "resolution_scale" => {
+                self.settings.resolution_scale = number(setting_name, &value)? as f32;
+                self.resize_map(device);
+            }
+            // These only change how the map is drawn
+            "tiles_per_frame" => {
+                self.settings.tiles_per_frame = number(setting_name, &value)? as u32;
+                return Ok(());
+            }
+            "shading" => {
+                self.settings.shading = number(setting_name, &value)? as f32;
+                return Ok(());
+            }
+            "show_magnets" => {
+                self.settings.show_magnets = value.as_bool().unwrap_or(true);
+                return Ok(());
+            }
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        self.restart();
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
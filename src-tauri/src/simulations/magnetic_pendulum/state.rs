@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Size of the basin map in pixels
+    pub map_width: u32,
+    pub map_height: u32,
+
+    // Share of the map refined at full resolution since it last changed
+    pub progress: f32,
+
+    // Magnet being dragged, if any
+    pub dragging: Option<usize>,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            map_width: 0,
+            map_height: 0,
+            progress: 0.0,
+            dragging: None,
+            color_scheme_name: "MATPLOTLIB_Spectral".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use super::settings::magnet_ring;
+use super::shaders::{BASINS_SHADER, RENDER_SHADER};
+use super::simulation::{TILE_SIZE, View, magnet_near, tile_grid};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+#[test]
+fn ring_spaces_magnets_evenly_from_the_top() {
+    for count in 1..=8 {
+        let magnets = magnet_ring(count, 1.5);
+        assert_eq!(magnets.len(), count);
+        assert!(magnets[0][0].abs() < 1e-6 && (magnets[0][1] - 1.5).abs() < 1e-6);
+        for magnet in &magnets {
+            assert!((magnet[0].hypot(magnet[1]) - 1.5).abs() < 1e-5);
+        }
+        if count > 1 {
+            let side = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).hypot(a[1] - b[1]);
+            let first = side(magnets[0], magnets[1]);
+            for i in 0..count {
+                let next = magnets[(i + 1) % count];
+                assert!((side(magnets[i], next) - first).abs() < 1e-4);
+            }
+        }
+    }
+}
+
+#[test]
+fn grabbing_picks_the_nearest_magnet_in_reach() {
+    let magnets = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+    assert_eq!(magnet_near(&magnets, [0.6, 0.1], 0.5), Some(1));
+    assert_eq!(magnet_near(&magnets, [0.1, 0.45], 0.6), Some(0));
+    assert_eq!(magnet_near(&magnets, [0.5, 0.5], 0.2), None);
+    assert_eq!(magnet_near(&[], [0.0, 0.0], 10.0), None);
+}
+
+#[test]
+fn view_keeps_plane_pixels_square() {
+    let view = View {
+        center: [0.0, 0.0],
+        zoom: 2.0,
+        aspect: 16.0 / 9.0,
+        extent: 3.0,
+    };
+    // The right and top edges of world space at zoom 1
+    assert_eq!(view.plane_position([1.0, 0.0]), [16.0 / 9.0 * 3.0, 0.0]);
+    assert_eq!(view.plane_position([0.0, 1.0]), [0.0, 3.0]);
+
+    // At zoom 2 the screen shows half the world in each direction, so a
+    // pixel across is a pixel down
+    let (width, height) = (1600, 900);
+    let across = 2.0 * view.plane_position([0.5, 0.0])[0] / width as f32;
+    let down = 2.0 * view.plane_position([0.0, 0.5])[1] / height as f32;
+    assert!((across - down).abs() < 1e-6);
+    assert!((view.plane_per_pixel(width) - across).abs() < 1e-6);
+}
+
+#[test]
+fn tiles_cover_the_whole_map() {
+    assert_eq!(tile_grid(1, 1), (1, 1));
+    assert_eq!(tile_grid(TILE_SIZE, TILE_SIZE), (1, 1));
+    assert_eq!(tile_grid(TILE_SIZE + 1, TILE_SIZE * 2), (2, 4));
+    let (across, total) = tile_grid(1920, 1080);
+    assert!(across * TILE_SIZE >= 1920);
+    assert!(total / across * TILE_SIZE >= 1080);
+    assert_eq!(total % across, 0);
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Magnetic Pendulum Basins Shader", BASINS_SHADER),
+        ("Magnetic Pendulum Render Shader", RENDER_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
@@ -1,4 +1,5 @@
 pub mod shaders;
 pub mod simulation;
+pub mod time_of_day;
 
 pub use simulation::MainMenuModel;
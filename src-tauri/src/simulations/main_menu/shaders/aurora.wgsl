@@ -0,0 +1,30 @@
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    var intensity = 0.0;
+
+    // Three overlapping curtains, each with a lower edge that wanders along x
+    for (var i = 0; i < 3; i = i + 1) {
+        let layer = f32(i);
+        let edge = 0.45 + 0.12 * layer
+            + 0.12 * simplex_noise_3d(vec3<f32>(uv.x * 1.5 + layer * 3.1, layer, time * 4.0));
+        let distance_to_edge = uv.y - edge;
+
+        // Bright sharp lower edge, long soft fade upwards
+        let falloff = select(
+            exp(-distance_to_edge * 4.0),
+            exp(distance_to_edge * 30.0),
+            distance_to_edge < 0.0
+        );
+
+        // Vertical rays drifting sideways
+        let rays = 0.5 + 0.5 * simplex_noise_3d(vec3<f32>(uv.x * 14.0 + layer * 7.0, uv.y * 0.5, time * 6.0 + layer));
+        intensity += falloff * rays * (0.6 - 0.15 * layer);
+    }
+
+    let shimmer = 0.5 + 0.5 * multivariate_fbm(vec3<f32>(uv.x * 3.0, uv.y * 3.0, time), time);
+    let value = clamp(intensity * (0.7 + 0.3 * shimmer), 0.0, 1.0);
+    let color = lookup_lut(value);
+
+    let vignette = 1.0 - length(uv - 0.5) * 0.6;
+    return vec4<f32>(color * vignette, 1.0);
+}
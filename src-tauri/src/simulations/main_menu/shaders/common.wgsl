@@ -1,3 +1,6 @@
+// Shared by every main menu background. Each background file is appended to
+// this one and provides its own fs_main.
+
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) uv: vec2<f32>,
@@ -130,18 +133,9 @@ fn multivariate_fbm(p: vec3<f32>, t: f32) -> f32 {
     return sum;
 }
 
-// Convert from sRGB (gamma-corrected) to linear RGB
-fn srgb_to_linear(srgb: f32) -> f32 {
-    if (srgb <= 0.04045) {
-        return srgb / 12.92;
-    } else {
-        return pow((srgb + 0.055) / 1.055, 2.4);
-    }
-}
-
 // LUT lookup function
 fn lookup_lut(value: f32) -> vec3<f32> {
-    let index = u32(value * 255.0);
+    let index = u32(clamp(value, 0.0, 1.0) * 255.0);
     let r_srgb = f32(lut_data[index]) / 255.0;
     let g_srgb = f32(lut_data[256 + index]) / 255.0;
     let b_srgb = f32(lut_data[512 + index]) / 255.0;
@@ -152,35 +146,3 @@ fn lookup_lut(value: f32) -> vec3<f32> {
         srgb_to_linear(b_srgb)
     );
 }
-
-@fragment
-fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
-    // Move the pole to the top of the screen (Y=1.0 is top in screen coordinates)
-    let pole = vec2<f32>(0.5, 0.7);
-    let delta = uv - pole;
-    let angle = atan2(delta.y, delta.x);
-    let radius = length(delta);
-    let swirl = sin(angle * 3.0 + time + radius * 4.0) * 0.5 + 0.5;
-
-    // Multivariate FBM noise
-    let pos_3d = vec3<f32>(uv.x * 4.0, uv.y * 4.0, time * 0.5);
-    let noise1 = multivariate_fbm(pos_3d, time);
-    let noise2 = multivariate_fbm(pos_3d * 1.7 + vec3<f32>(time * 0.3, time * 0.2, time * 0.4), time * 0.7);
-    let noise3 = multivariate_fbm(pos_3d * 0.8 - vec3<f32>(time * 0.2, time * 0.3, time * 0.1), time * 1.3);
-    let combined_noise = (noise1 + noise2 + noise3) / 3.0;
-    let animated_pattern = combined_noise * swirl;
-
-    // Normalize the pattern to 0-1 range and apply LUT
-    let normalized_value = (animated_pattern + 1.0) * 0.5; // Convert from [-1,1] to [0,1]
-    let color = lookup_lut(normalized_value);
-
-    // Pulsing
-    let pulse = sin(time * 2.0) * 0.1 + 0.9;
-    let final_color = color * pulse;
-
-    // Vignette
-    let vignette = 1.0 - length(uv - 0.5) * 0.8;
-    let final_result = final_color * vignette;
-
-    return vec4<f32>(final_result, 1.0);
-} 
\ No newline at end of file
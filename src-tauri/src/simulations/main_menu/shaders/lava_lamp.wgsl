@@ -0,0 +1,29 @@
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let t = time * 10.0;
+
+    // Metaballs rising and sinking at different rates
+    var field = 0.0;
+    for (var i = 0; i < 8; i = i + 1) {
+        let blob = f32(i);
+        let center = vec2<f32>(
+            0.5 + 0.35 * sin(t * (0.21 + 0.04 * blob) + blob * 1.7),
+            0.5 + 0.45 * sin(t * (0.13 + 0.03 * blob) + blob * 2.3)
+        );
+        let radius = 0.07 + 0.03 * sin(blob * 4.1 + t * 0.3);
+        let offset = uv - center;
+        field += radius * radius / (dot(offset, offset) + 0.0001);
+    }
+
+    // Wobble the blob edges
+    field *= 1.0 + 0.15 * simplex_noise_3d(vec3<f32>(uv.x * 3.0, uv.y * 3.0, t * 0.1));
+
+    let inside = smoothstep(0.9, 1.1, field);
+    let glow = clamp(field * 0.3, 0.0, 1.0) * 0.45;
+    let core = 0.6 + 0.4 * clamp((field - 1.1) * 0.25, 0.0, 1.0);
+    let value = mix(glow, core, inside);
+    let color = lookup_lut(value);
+
+    let vignette = 1.0 - length(uv - 0.5) * 0.8;
+    return vec4<f32>(color * vignette, 1.0);
+}
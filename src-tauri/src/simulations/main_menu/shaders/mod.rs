@@ -1 +1,20 @@
-pub const COMBINED_SHADER: &str = include_str!("combined.wgsl");
+use crate::commands::app_settings::MainMenuBackground;
+use crate::simulations::shared::COLOR_SHADER;
+
+pub const COMMON_SHADER: &str = include_str!("common.wgsl");
+pub const FBM_SWIRL_SHADER: &str = include_str!("fbm_swirl.wgsl");
+pub const AURORA_SHADER: &str = include_str!("aurora.wgsl");
+pub const STARFIELD_SHADER: &str = include_str!("starfield.wgsl");
+pub const LAVA_LAMP_SHADER: &str = include_str!("lava_lamp.wgsl");
+
+/// Full shader source for a background: the shared color, vertex, noise and
+/// LUT helpers followed by that background's fragment stage.
+pub fn background_shader(background: MainMenuBackground) -> String {
+    let fragment = match background {
+        MainMenuBackground::FbmSwirl => FBM_SWIRL_SHADER,
+        MainMenuBackground::Aurora => AURORA_SHADER,
+        MainMenuBackground::Starfield => STARFIELD_SHADER,
+        MainMenuBackground::LavaLamp => LAVA_LAMP_SHADER,
+    };
+    format!("{}\n{}\n{}", COLOR_SHADER, COMMON_SHADER, fragment)
+}
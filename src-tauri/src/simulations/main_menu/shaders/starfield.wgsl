@@ -0,0 +1,42 @@
+fn hash_2d(p: vec2<f32>) -> vec2<f32> {
+    let q = vec2<f32>(dot(p, vec2<f32>(127.1, 311.7)), dot(p, vec2<f32>(269.5, 183.3)));
+    return fract(sin(q) * 43758.5453);
+}
+
+// One layer of stars on a jittered grid, some cells left empty
+fn star_layer(position: vec2<f32>, cell_size: f32, layer: f32) -> f32 {
+    let grid = position / cell_size + vec2<f32>(layer * 17.0, layer * 31.0);
+    let cell = floor(grid);
+    let local = fract(grid);
+    let rnd = hash_2d(cell + layer * 101.0);
+
+    let present = step(0.75, fract(rnd.x * 13.0 + rnd.y * 7.0));
+    let star_center = 0.2 + rnd * 0.6;
+    let size = 0.03 + 0.05 * rnd.y;
+    let twinkle = 0.6 + 0.4 * sin(time * (20.0 + 40.0 * rnd.y) + rnd.x * 6.2831);
+
+    return present * smoothstep(size, 0.0, length(local - star_center)) * twinkle;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Use pixel coordinates so stars stay round regardless of aspect ratio
+    let pixel = in.clip_position.xy;
+
+    var stars = 0.0;
+    for (var i = 0; i < 4; i = i + 1) {
+        let layer = f32(i);
+        // Nearer layers have bigger cells and drift faster
+        let cell_size = 24.0 + 18.0 * layer;
+        let drift = vec2<f32>(time * (60.0 + 80.0 * layer), time * 10.0);
+        stars += star_layer(pixel + drift, cell_size, layer) * (0.5 + 0.2 * layer);
+    }
+
+    // Faint nebula behind the stars
+    let nebula = multivariate_fbm(vec3<f32>(in.uv.x * 2.0, in.uv.y * 2.0, time * 0.3), time * 0.5);
+    let background = clamp((nebula + 1.0) * 0.5, 0.0, 1.0) * 0.35;
+
+    let value = clamp(background + stars, 0.0, 1.0);
+    let color = lookup_lut(value);
+    return vec4<f32>(color, 1.0);
+}
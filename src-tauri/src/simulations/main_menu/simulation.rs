@@ -1,23 +1,42 @@
-use crate::commands::app_settings::AppSettings;
-use crate::error::SimulationResult;
+use super::time_of_day::TimeOfDay;
+use crate::commands::app_settings::{AppSettings, MainMenuBackground};
+use crate::error::{SimulationError, SimulationResult};
 use crate::simulations::shared::{
-    BindGroupBuilder, ColorSchemeManager, CommonBindGroupLayouts, RenderPipelineBuilder,
+    BindGroupBuilder, ColorScheme, ColorSchemeManager, CommonBindGroupLayouts,
+    RenderPipelineBuilder,
 };
 use crate::simulations::traits::Simulation;
+use rand::seq::IndexedRandom;
 use serde_json::Value;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use wgpu::util::DeviceExt;
-use wgpu::{BindGroup, Buffer, Device, Queue, RenderPipeline, SurfaceConfiguration, TextureView};
+use wgpu::{
+    BindGroup, BindGroupLayout, Buffer, Device, Queue, RenderPipeline, SurfaceConfiguration,
+    TextureView,
+};
+
+/// How often to check whether the time-of-day period has changed
+const TIME_OF_DAY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
 
 #[derive(Debug)]
 pub struct MainMenuModel {
     render_pipeline: RenderPipeline,
     time_buffer: Buffer,
     time_bind_group: BindGroup,
+    time_bind_group_layout: BindGroupLayout,
+    lut_buffer: Buffer,
     lut_bind_group: BindGroup,
+    lut_bind_group_layout: BindGroupLayout,
+    surface_format: wgpu::TextureFormat,
     start_time: Instant,
     gui_visible: bool,
+    background: MainMenuBackground,
+    time_of_day_themes: bool,
+    // Period the current LUT was picked for, None when not themed
+    time_of_day: Option<TimeOfDay>,
+    last_time_of_day_check: Instant,
+    color_scheme_manager: ColorSchemeManager,
     // App settings for consistency
     _app_settings: AppSettings,
 }
@@ -47,8 +66,14 @@ impl MainMenuModel {
             .with_label("Main Menu Time Bind Group".to_string())
             .build();
 
-        // Create LUT buffer from a random LUT and create bind group
-        let lut_data = color_scheme_manager.get_random_lut()?;
+        // Create LUT buffer from a themed or random LUT and create bind group
+        let time_of_day = _app_settings
+            .main_menu_time_of_day_themes
+            .then(TimeOfDay::now);
+        let lut_data = match time_of_day {
+            Some(period) => Self::time_of_day_lut(color_scheme_manager, period)?,
+            None => color_scheme_manager.get_random_lut()?,
+        };
         let lut_data_u32 = lut_data.to_u32_buffer();
         let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Main Menu LUT Buffer"),
@@ -63,23 +88,59 @@ impl MainMenuModel {
             .with_label("Main Menu LUT Bind Group".to_string())
             .build();
 
-        // Create shaders using GPU utilities
-        let combined_shader = Arc::new(device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("main_menu_combined"),
+        let background = _app_settings.main_menu_background;
+        let render_pipeline = Self::create_render_pipeline(
+            device,
+            surface_config.format,
+            &time_bind_group_layout,
+            &lut_bind_group_layout,
+            background,
+        );
+
+        let start_time = Instant::now();
+
+        Ok(Self {
+            render_pipeline,
+            time_buffer,
+            time_bind_group,
+            time_bind_group_layout,
+            lut_buffer,
+            lut_bind_group,
+            lut_bind_group_layout,
+            surface_format: surface_config.format,
+            start_time,
+            gui_visible: false,
+            background,
+            time_of_day_themes: _app_settings.main_menu_time_of_day_themes,
+            time_of_day,
+            last_time_of_day_check: Instant::now(),
+            color_scheme_manager: color_scheme_manager.clone(),
+            _app_settings: _app_settings.clone(),
+        })
+    }
+
+    fn create_render_pipeline(
+        device: &Arc<Device>,
+        format: wgpu::TextureFormat,
+        time_bind_group_layout: &BindGroupLayout,
+        lut_bind_group_layout: &BindGroupLayout,
+        background: MainMenuBackground,
+    ) -> RenderPipeline {
+        let shader = Arc::new(device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("main_menu_background"),
             source: wgpu::ShaderSource::Wgsl(
-                crate::simulations::main_menu::shaders::COMBINED_SHADER.into(),
+                crate::simulations::main_menu::shaders::background_shader(background).into(),
             ),
         }));
 
-        // Create render pipeline using GPU utilities
-        let render_pipeline = RenderPipelineBuilder::new(device.clone())
-            .with_shader(combined_shader)
+        RenderPipelineBuilder::new(device.clone())
+            .with_shader(shader)
             .with_bind_group_layouts(vec![
                 time_bind_group_layout.clone(),
                 lut_bind_group_layout.clone(),
             ])
             .with_fragment_targets(vec![Some(wgpu::ColorTargetState {
-                format: surface_config.format,
+                format,
                 blend: Some(wgpu::BlendState::REPLACE),
                 write_mask: wgpu::ColorWrites::ALL,
             })])
@@ -93,19 +154,66 @@ impl MainMenuModel {
                 conservative: false,
             })
             .with_label("Main Menu Background Render Pipeline".to_string())
-            .build();
+            .build()
+    }
 
-        let start_time = Instant::now();
+    fn time_of_day_lut(
+        color_scheme_manager: &ColorSchemeManager,
+        period: TimeOfDay,
+    ) -> SimulationResult<ColorScheme> {
+        let name = period
+            .color_schemes()
+            .choose(&mut rand::rng())
+            .copied()
+            .unwrap_or("MATPLOTLIB_viridis");
+        Ok(color_scheme_manager.get(name)?)
+    }
 
-        Ok(Self {
-            render_pipeline,
-            time_buffer,
-            time_bind_group,
-            lut_bind_group,
-            start_time,
-            gui_visible: false,
-            _app_settings: _app_settings.clone(),
-        })
+    fn set_background(&mut self, device: &Arc<Device>, background: MainMenuBackground) {
+        if background == self.background {
+            return;
+        }
+        self.background = background;
+        self.render_pipeline = Self::create_render_pipeline(
+            device,
+            self.surface_format,
+            &self.time_bind_group_layout,
+            &self.lut_bind_group_layout,
+            background,
+        );
+    }
+
+    fn set_time_of_day_themes(&mut self, enabled: bool) {
+        self.time_of_day_themes = enabled;
+        // Forces a theme pick on the next frame when enabled
+        self.time_of_day = None;
+    }
+
+    /// Swap to a LUT for the current part of the day when it changes
+    fn update_time_of_day_theme(&mut self, queue: &Arc<Queue>) -> SimulationResult<()> {
+        if !self.time_of_day_themes {
+            return Ok(());
+        }
+        if self.time_of_day.is_some()
+            && self.last_time_of_day_check.elapsed() < TIME_OF_DAY_CHECK_INTERVAL
+        {
+            return Ok(());
+        }
+        self.last_time_of_day_check = Instant::now();
+
+        let period = TimeOfDay::now();
+        if self.time_of_day == Some(period) {
+            return Ok(());
+        }
+
+        let lut = Self::time_of_day_lut(&self.color_scheme_manager, period)?;
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&lut.to_u32_buffer()),
+        );
+        self.time_of_day = Some(period);
+        Ok(())
     }
 
     fn get_time(&self) -> f32 {
@@ -121,6 +229,8 @@ impl Simulation for MainMenuModel {
         queue: &Arc<Queue>,
         surface_view: &TextureView,
     ) -> SimulationResult<()> {
+        self.update_time_of_day_theme(queue)?;
+
         // For static rendering, just render with current time (don't advance animation)
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Main Menu Background Static Encoder"),
@@ -160,6 +270,8 @@ impl Simulation for MainMenuModel {
         surface_view: &TextureView,
         _delta_time: f32,
     ) -> SimulationResult<()> {
+        self.update_time_of_day_theme(queue)?;
+
         // Update the time buffer
         let time_seconds = self.get_time();
         queue.write_buffer(&self.time_buffer, 0, bytemuck::cast_slice(&[time_seconds]));
@@ -207,12 +319,26 @@ impl Simulation for MainMenuModel {
 
     fn update_setting(
         &mut self,
-        _setting_name: &str,
-        _value: Value,
-        _device: &Arc<Device>,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
         _queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
-        // No configurable settings for this simulation
+        match setting_name {
+            "background" => {
+                let background: MainMenuBackground = serde_json::from_value(value)?;
+                self.set_background(device, background);
+            }
+            "time_of_day_themes" => {
+                let enabled = value.as_bool().ok_or_else(|| {
+                    SimulationError::invalid_setting(setting_name, "expected a boolean")
+                })?;
+                self.set_time_of_day_themes(enabled);
+            }
+            _ => {
+                tracing::warn!("Unknown setting for MainMenu: {}", setting_name);
+            }
+        }
         Ok(())
     }
 
@@ -234,8 +360,10 @@ impl Simulation for MainMenuModel {
     }
 
     fn get_settings(&self) -> Value {
-        // No settings for this simulation
-        serde_json::json!({})
+        serde_json::json!({
+            "background": self.background,
+            "time_of_day_themes": self.time_of_day_themes
+        })
     }
 
     fn get_state(&self) -> Value {
@@ -280,11 +408,15 @@ impl Simulation for MainMenuModel {
 
     fn apply_settings(
         &mut self,
-        _settings: serde_json::Value,
-        _device: &Arc<Device>,
-        _queue: &Arc<Queue>,
+        settings: serde_json::Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
-        // No settings for this simulation
+        if let Some(settings) = settings.as_object() {
+            for (name, value) in settings {
+                self.update_setting(name, value.clone(), device, queue)?;
+            }
+        }
         Ok(())
     }
 
@@ -0,0 +1,86 @@
+//! Time-of-day color themes for the main menu background.
+
+use chrono::Timelike;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl TimeOfDay {
+    pub fn from_hour(hour: u32) -> Self {
+        match hour {
+            5..=7 => TimeOfDay::Dawn,
+            8..=16 => TimeOfDay::Day,
+            17..=20 => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    /// The period for the current local time
+    pub fn now() -> Self {
+        Self::from_hour(chrono::Local::now().hour())
+    }
+
+    /// Color schemes that suit this part of the day; one is picked at random
+    pub fn color_schemes(self) -> &'static [&'static str] {
+        match self {
+            TimeOfDay::Dawn => &[
+                "KTZ_bw_Sunrise",
+                "KTZ_bw_Sakura",
+                "KTZ_bw_PinkShui",
+                "MATPLOTLIB_spring",
+            ],
+            TimeOfDay::Day => &[
+                "MATPLOTLIB_viridis",
+                "KTZ_bw_Lagoon",
+                "ZELDA_Aqua",
+                "MATPLOTLIB_summer",
+            ],
+            TimeOfDay::Dusk => &[
+                "KTZ_Campfire",
+                "KTZ_bw_Ember",
+                "MATPLOTLIB_magma",
+                "MATPLOTLIB_inferno",
+            ],
+            TimeOfDay::Night => &[
+                "KTZ_bw_CityNight",
+                "KTZ_bw_Nebula",
+                "KTZ_bw_Moon",
+                "MATPLOTLIB_twilight",
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_hour_has_a_period() {
+        assert_eq!(TimeOfDay::from_hour(0), TimeOfDay::Night);
+        assert_eq!(TimeOfDay::from_hour(5), TimeOfDay::Dawn);
+        assert_eq!(TimeOfDay::from_hour(12), TimeOfDay::Day);
+        assert_eq!(TimeOfDay::from_hour(17), TimeOfDay::Dusk);
+        assert_eq!(TimeOfDay::from_hour(23), TimeOfDay::Night);
+    }
+
+    #[test]
+    fn test_theme_color_schemes_exist() {
+        let manager = crate::simulations::shared::ColorSchemeManager::new();
+        for period in [
+            TimeOfDay::Dawn,
+            TimeOfDay::Day,
+            TimeOfDay::Dusk,
+            TimeOfDay::Night,
+        ] {
+            for name in period.color_schemes() {
+                assert!(manager.get(name).is_ok(), "Missing color scheme {}", name);
+            }
+        }
+    }
+}
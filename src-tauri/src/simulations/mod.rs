@@ -18,15 +18,33 @@
 //! The unified interface enables users to seamlessly transition between
 //! different types of complex system exploration.
 
+pub mod boids;
+pub mod chemotaxis;
+pub mod crowd;
+pub mod dla;
+pub mod double_pendulum;
+pub mod eikonal;
 pub mod flow;
 pub mod gradient;
 pub mod gray_scott;
+pub mod harmonograph;
+pub mod lensing;
+pub mod life_like;
+pub mod magnetic_pendulum;
 pub mod main_menu;
 pub mod moire;
 pub mod particle_life;
 pub mod pellets;
+pub mod percolation;
+pub mod phyllotaxis;
 pub mod primordial_particles;
+pub mod quasicrystal;
 pub mod shared;
 pub mod slime_mold;
+pub mod slime_mold_3d;
+pub mod softbody;
+pub mod stippling;
 pub mod traits;
+pub mod turmites;
 pub mod voronoi_ca;
+pub mod vortex;
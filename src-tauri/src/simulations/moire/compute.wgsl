@@ -46,15 +46,6 @@ fn rotate2d(v: vec2<f32>, angle: f32) -> vec2<f32> {
     );
 }
 
-// Convert from sRGB (gamma-corrected) to linear RGB
-fn srgb_to_linear(srgb: f32) -> f32 {
-    if (srgb <= 0.04045) {
-        return srgb / 12.92;
-    } else {
-        return pow((srgb + 0.055) / 1.055, 2.4);
-    }
-}
-
 // Get color from LUT
 fn get_lut_color(intensity: f32) -> vec3<f32> {
     let idx = clamp(i32(intensity * 255.0), 0, 255);
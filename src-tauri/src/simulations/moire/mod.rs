@@ -55,4 +55,7 @@ pub fn init_presets(preset_manager: &mut MoirePresetManager) {
             ..Settings::default()
         },
     ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
 }
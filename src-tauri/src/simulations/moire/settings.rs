@@ -12,7 +12,10 @@
 //! and color processing. The interaction between these systems creates
 //! emergent visual complexity from relatively simple parameters.
 
-use crate::simulations::shared::ImageFitMode;
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use crate::simulations::shared::{BackgroundLayer, ImageFitMode};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
@@ -118,6 +121,10 @@ pub struct Settings {
     pub image_mirror_vertical: bool,
     pub image_invert_tone: bool,
     pub image_interference_mode: ImageInterferenceMode,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
 }
 
 impl Default for Settings {
@@ -146,6 +153,76 @@ impl Default for Settings {
             image_mirror_vertical: false,
             image_invert_tone: true,
             image_interference_mode: ImageInterferenceMode::Modulate,
+            background_layer: BackgroundLayer::default(),
         }
     }
 }
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting).
+/// Moiré reads numbers without checking their type, so every numeric setting
+/// needs a rule here.
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("speed", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "base_freq",
+            Rule::Range {
+                min: 0.1,
+                max: 20.0,
+            },
+        ),
+        ("moire_amount", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "moire_rotation",
+            Rule::Range {
+                min: -std::f64::consts::TAU,
+                max: std::f64::consts::TAU,
+            },
+        ),
+        (
+            "moire_scale",
+            Rule::Range {
+                min: 0.1,
+                max: 10.0,
+            },
+        ),
+        ("moire_interference", Rule::Range { min: 0.0, max: 1.0 }),
+        ("radial_swirl_strength", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "radial_starburst_count",
+            Rule::Range {
+                min: 0.0,
+                max: 128.0,
+            },
+        ),
+        (
+            "radial_center_brightness",
+            Rule::Range { min: 0.0, max: 2.0 },
+        ),
+        ("advect_strength", Rule::Range { min: 0.0, max: 5.0 }),
+        (
+            "advect_speed",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        ("curl", Rule::Range { min: 0.0, max: 1.0 }),
+        ("decay", Rule::Range { min: 0.0, max: 1.0 }),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("generator_type", SettingCategory::Generators),
+    ("base_freq", SettingCategory::Generators),
+    ("image_mode_enabled", SettingCategory::Generators),
+    ("image_fit_mode", SettingCategory::Generators),
+    ("image_mirror_horizontal", SettingCategory::Generators),
+    ("image_mirror_vertical", SettingCategory::Generators),
+    ("image_invert_tone", SettingCategory::Generators),
+    ("image_interference_mode", SettingCategory::Generators),
+]);
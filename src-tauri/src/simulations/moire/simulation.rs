@@ -27,7 +27,9 @@ use crate::error::{ColorSchemeError, SimulationResult};
 use crate::simulations::shared::camera::Camera;
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use crate::simulations::shared::ping_pong_textures::PingPongTextures;
-use crate::simulations::shared::{ColorScheme, ColorSchemeManager, ImageFitMode};
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, ImageFitMode, RewindResource,
+};
 use crate::simulations::traits::Simulation;
 
 use super::settings::Settings;
@@ -1248,6 +1250,22 @@ impl Simulation for MoireModel {
         serde_json::to_value(&self.settings).unwrap_or_default()
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        self.simulation_textures
+            .textures()
+            .iter()
+            .map(RewindResource::Texture)
+            .collect()
+    }
+
     fn apply_settings(
         &mut self,
         settings: serde_json::Value,
@@ -4,6 +4,8 @@ pub mod shaders;
 pub mod simulation;
 pub mod state;
 
+#[cfg(test)]
+mod reference;
 #[cfg(test)]
 mod tests;
 
@@ -27,7 +29,7 @@ pub fn init_presets(preset_manager: &mut ParticleLifePresetManager) {
 
     // Load user presets from TOML files
     if let Err(e) = preset_manager.load_user_presets() {
-        eprintln!("Warning: Could not load user presets: {}", e);
+        tracing::warn!("Could not load user presets: {}", e);
     }
 
     let preset_count = preset_manager.get_preset_names().len();
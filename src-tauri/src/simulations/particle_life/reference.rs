@@ -0,0 +1,108 @@
+//! A CPU copy of one step of `compute.wgsl`, for the tests to hold the
+//! shader to. It leaves out the cursor, Brownian motion and the global
+//! force, so those must be off.
+//!
+//! The shader moves particles in place, so a particle late in the dispatch
+//! may feel a neighbor that has already moved. The copy always reads where
+//! particles were at the start of the step; kept small, steps come out the
+//! same to well within the tests' tolerance.
+
+use super::simulation::SimParams;
+use super::state::Particle;
+
+/// `particles` one step on, pulled on by `force_matrix`, one row per species
+pub fn step(particles: &[Particle], params: &SimParams, force_matrix: &[f32]) -> Vec<Particle> {
+    assert!(
+        params.cursor_active == 0 && params.brownian_motion == 0.0,
+        "The reference step has no cursor or Brownian motion"
+    );
+    let wrap = params.wrap_edges == 1;
+    let count = params.particle_count as usize;
+
+    particles[..count]
+        .iter()
+        .enumerate()
+        .map(|(index, particle)| {
+            let mut force = [0.0f32; 2];
+            for (other_index, other) in particles[..count].iter().enumerate() {
+                if other_index == index {
+                    continue;
+                }
+                let delta = wrapped_distance(particle.position, other.position, wrap);
+                let distance_sq = delta[0] * delta[0] + delta[1] * delta[1];
+                if distance_sq > params.max_distance * params.max_distance {
+                    continue;
+                }
+                let distance = distance_sq.sqrt();
+                if distance < 0.001 {
+                    continue;
+                }
+                let matrix_index =
+                    (particle.species * params.species_count + other.species) as usize;
+                let attraction = force_matrix.get(matrix_index).copied().unwrap_or(0.0);
+                let magnitude = force_at(distance, attraction, params);
+                force[0] += delta[0] / distance * magnitude;
+                force[1] += delta[1] / distance * magnitude;
+            }
+
+            let dt = params.dt;
+            let friction = params.friction.powf(dt * 60.0);
+            let mut velocity = [
+                (particle.velocity[0] + force[0] * dt) * friction,
+                (particle.velocity[1] + force[1] * dt) * friction,
+            ];
+            let mut position = [
+                particle.position[0] + velocity[0] * dt,
+                particle.position[1] + velocity[1] * dt,
+            ];
+            for (position, velocity) in position.iter_mut().zip(&mut velocity) {
+                if wrap {
+                    *position = (*position + 1.0).rem_euclid(2.0) - 1.0;
+                } else if *position < -1.0 {
+                    *position = -1.0;
+                    *velocity = -*velocity * 0.8;
+                } else if *position >= 1.0 {
+                    *position = 1.0 - 0.001;
+                    *velocity = -*velocity * 0.8;
+                }
+            }
+
+            Particle {
+                position,
+                velocity,
+                ..*particle
+            }
+        })
+        .collect()
+}
+
+/// Shortest way from `a` to `b`, across the edges if they wrap
+fn wrapped_distance(a: [f32; 2], b: [f32; 2], wrap: bool) -> [f32; 2] {
+    let mut delta = [b[0] - a[0], b[1] - a[1]];
+    if wrap {
+        for component in &mut delta {
+            if *component > 1.0 {
+                *component -= 2.0;
+            } else if *component < -1.0 {
+                *component += 2.0;
+            }
+        }
+    }
+    delta
+}
+
+/// Linear repulsion up close, then the species' attraction rising and
+/// falling back to nothing at `max_distance`
+fn force_at(distance: f32, attraction: f32, params: &SimParams) -> f32 {
+    let (max_distance, beta) = (params.max_distance, params.beta);
+    let beta_distance = beta * max_distance;
+    if distance < beta_distance {
+        (distance.max(0.001) / beta_distance - 1.0) * params.max_force
+    } else if distance <= max_distance {
+        attraction
+            * (1.0 - (1.0 + beta - 2.0 * distance / max_distance) / (1.0 - beta))
+            * params.max_force
+    } else {
+        0.0
+    }
+}
@@ -1,4 +1,8 @@
 use super::matrix_operations;
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Constraint, Rule, SettingValidator};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
@@ -41,6 +45,10 @@ pub struct Settings {
     /// Controls the amount of random thermal motion applied to particles
     /// Higher values create more chaotic, jittery movement
     pub brownian_motion: f32,
+
+    /// Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -152,10 +160,13 @@ impl Default for Settings {
             min_distance: 0.001,
             max_distance: 0.05,
             brownian_motion: 0.5,
+            background_layer: BackgroundLayer::default(),
         }
     }
 }
 
+impl PresetSchema for Settings {}
+
 impl Settings {
     /// Create a new settings instance with the specified number of species
     pub fn with_species_count(species_count: u32) -> Self {
@@ -740,3 +751,37 @@ impl Settings {
         matrix_operations::flip_sign(&mut self.force_matrix);
     }
 }
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("species_count", Rule::Count { min: 2, max: 8 }),
+        (
+            "particle_count",
+            Rule::Count {
+                min: 1,
+                max: 100000,
+            },
+        ),
+        ("brownian_motion", Rule::Range { min: 0.0, max: 1.0 }),
+        ("wrap_edges", Rule::Flag),
+        (
+            "cursor_strength",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        ("traces_enabled", Rule::Flag),
+    ],
+    &[Constraint::Ordered {
+        lower: "min_distance",
+        upper: "max_distance",
+    }],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("species_count", SettingCategory::Generators),
+    ("force_matrix", SettingCategory::Generators),
+]);
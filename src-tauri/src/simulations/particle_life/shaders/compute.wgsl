@@ -26,14 +26,19 @@ struct SimParams {
     cursor_strength: f32,  // Cursor force strength
     cursor_active: u32,  // Whether cursor interaction is active (0 = inactive, 1 = attract, 2 = repel)
     brownian_motion: f32,  // Brownian motion strength (0.0-1.0)
+    particle_size: f32,
     aspect_ratio: f32,  // Screen aspect ratio for cursor distance calculation
-    _pad1: u32,
-    _pad2: u32,
+    cursor_mode: u32,  // CURSOR_MODE_* from cursor_force.wgsl
+    cursor_curve: u32,  // CURSOR_CURVE_* of the current mode
+    cursor_direction_x: f32,  // Direction the cursor is moving, for push mode
+    cursor_direction_y: f32,
+    cursor_time: f32,  // Animates turbulence
 }
 
 @group(0) @binding(0) var<storage, read_write> particles: array<Particle>;
 @group(0) @binding(1) var<uniform> params: SimParams;
 @group(0) @binding(2) var<storage, read> force_matrix: array<f32>;
+@group(0) @binding(3) var<uniform> global_force: GlobalForce;
 
 // Simple random number generator
 var<private> rng_state: u32;
@@ -189,12 +194,24 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             let distance_to_cursor = sqrt(distance_to_cursor_sq);
             let direction_to_cursor = delta_to_cursor / distance_to_cursor;
             
-            // Calculate cursor force strength based on distance (stronger when closer)
-            let distance_factor = 1.0 - (distance_to_cursor / params.cursor_size);
+            // Calculate cursor force strength based on distance and the mode's curve
+            let distance_factor = cursor_falloff(distance_to_cursor / params.cursor_size, params.cursor_curve);
             let cursor_force_strength = params.cursor_strength * distance_factor;
             
             // Apply force based on cursor mode (attract or repel)
-            if (params.cursor_active == 1u) {
+            if (params.cursor_mode != CURSOR_MODE_RADIAL) {
+                let flip = select(1.0, -1.0, params.cursor_active == 2u);
+                let cursor_direction = vec2<f32>(params.cursor_direction_x, params.cursor_direction_y);
+                force += cursor_shape_force(
+                    params.cursor_mode,
+                    delta_to_cursor,
+                    particle.position,
+                    cursor_direction,
+                    params.cursor_size,
+                    params.cursor_time,
+                    flip,
+                ) * cursor_force_strength;
+            } else if (params.cursor_active == 1u) {
                 // Attract particles to cursor with swirling effect
                 force += direction_to_cursor * cursor_force_strength;
                 
@@ -239,6 +256,9 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         force += brownian_force;
     }
     
+    // Wind and gravity
+    force += global_force_at(global_force, particle.position);
+
     // Update velocity with force and friction
     // Using the same time stepping as standalone version
     let dt = params.dt;
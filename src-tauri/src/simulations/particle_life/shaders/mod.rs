@@ -1,12 +1,17 @@
-pub const COMPUTE_SHADER: &str = include_str!("compute.wgsl");
+pub const COMPUTE_SHADER: &str = concat!(
+    include_str!("../../shared/cursor_force.wgsl"),
+    include_str!("../../shared/global_force.wgsl"),
+    include_str!("compute.wgsl")
+);
 pub const INIT_SHADER: &str = include_str!("init.wgsl");
 pub const FORCE_UPDATE_SHADER: &str = include_str!("force_update.wgsl");
 pub const FORCE_RANDOMIZE_SHADER: &str = include_str!("force_randomize.wgsl");
 pub const VERTEX_SHADER: &str = include_str!("vertex.wgsl");
 pub const FRAGMENT_SHADER: &str = include_str!("fragment.wgsl");
-pub const FADE_VERTEX_SHADER: &str = include_str!("fade_vertex.wgsl");
-pub const FADE_FRAGMENT_SHADER: &str = include_str!("fade_fragment.wgsl");
 pub const BACKGROUND_RENDER_SHADER: &str = include_str!("background_render.wgsl");
 pub const INFINITE_RENDER_SHADER: &str = crate::simulations::shared::INFINITE_RENDER_SHADER;
 pub const POST_EFFECT_SHADER: &str = include_str!("post_effect.wgsl");
-pub const TILE_RENDER_SHADER: &str = include_str!("tile_render.wgsl");
+pub const TILE_RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("tile_render.wgsl")
+);
@@ -28,7 +28,11 @@ struct SimParams {
     brownian_motion: f32,
     particle_size: f32,
     aspect_ratio: f32,
-    _pad1: u32,
+    cursor_mode: u32,
+    cursor_curve: u32,
+    cursor_direction_x: f32,
+    cursor_direction_y: f32,
+    cursor_time: f32,
 }
 
 struct TileParams {
@@ -205,15 +209,6 @@ fn vs_main(
     return output;
 }
 
-// Convert linear RGB to sRGB for proper display
-fn linear_to_srgb(linear: f32) -> f32 {
-    if (linear <= 0.0031308) {
-        return linear * 12.92;
-    } else {
-        return 1.055 * pow(linear, 1.0 / 2.4) - 0.055;
-    }
-}
-
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     // Create circular particles with sharp edges
@@ -239,11 +234,6 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     
     // Apply tile fade factor for smooth transitions
     let faded_color = base_color * input.tile_fade_factor;
-    let final_color = vec3<f32>(
-        linear_to_srgb(faded_color.r),
-        linear_to_srgb(faded_color.g),
-        linear_to_srgb(faded_color.b)
-    );
-    
-    return vec4<f32>(final_color, input.tile_fade_factor);
+    // Output stays linear like fragment.wgsl; the sRGB target does the encoding
+    return vec4<f32>(faded_color, input.tile_fade_factor);
 } 
\ No newline at end of file
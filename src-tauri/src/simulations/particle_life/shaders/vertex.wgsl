@@ -27,7 +27,11 @@ struct SimParams {
     brownian_motion: f32,
     particle_size: f32, // Add particle size parameter
     aspect_ratio: f32,  // Screen aspect ratio for cursor distance calculation
-    _pad1: u32,
+    cursor_mode: u32,
+    cursor_curve: u32,
+    cursor_direction_x: f32,
+    cursor_direction_y: f32,
+    cursor_time: f32,
 }
 
 struct CameraUniform {
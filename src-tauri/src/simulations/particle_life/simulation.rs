@@ -1,8 +1,11 @@
 use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::BackgroundLayer;
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use crate::simulations::shared::{
     BindGroupBuilder, BackgroundColorMode, ColorSchemeManager, ComputePipelineBuilder, PositionGenerator,
-    camera::Camera,
+    GpuReservation, HealthCheck, HealthIssue, HealthProbe, FadeCurve, GraphNode, NodeKind, ParticleBuffer, RenderGraph, RewindResource, Trails,
+    camera::Camera, gpu_budget,
+    gpu_tier::GpuTier,
     post_processing::{PostProcessingResources, PostProcessingState},
 };
 use bytemuck::{Pod, Zeroable};
@@ -50,15 +53,6 @@ pub struct ForceRandomizeParams {
     pub max_force: f32,
 }
 
-#[repr(C)]
-#[derive(Debug, Copy, Clone, Pod, Zeroable)]
-pub struct FadeUniforms {
-    pub fade_amount: f32, // Amount to subtract from alpha each frame (0.0 = no fade, higher = faster fade)
-    pub _pad1: f32,       // Padding for 16-byte alignment
-    pub _pad2: f32,       // Padding for 16-byte alignment
-    pub _pad3: f32,       // Padding for 16-byte alignment
-}
-
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Pod, Zeroable)]
 pub struct BackgroundParams {
@@ -96,7 +90,11 @@ pub struct SimParams {
     pub brownian_motion: f32, // Brownian motion strength (0.0-1.0)
     pub particle_size: f32, // Particle size in world space units
     pub aspect_ratio: f32,  // Screen aspect ratio for cursor distance calculation
-    pub _pad1: u32,
+    pub cursor_mode: u32,   // See `CursorMode::gpu_id`
+    pub cursor_curve: u32,  // See `StrengthCurve::gpu_id`
+    pub cursor_direction_x: f32, // Direction the cursor is moving, for push mode
+    pub cursor_direction_y: f32,
+    pub cursor_time: f32, // Animates turbulence
 }
 
 #[repr(C)]
@@ -155,7 +153,45 @@ impl SimParams {
             brownian_motion: settings.brownian_motion,
             particle_size: state.particle_size,
             aspect_ratio,
-            _pad1: 0,
+            cursor_mode: state.cursor_force.mode.gpu_id(),
+            cursor_curve: state.cursor_force.curve().gpu_id(),
+            cursor_direction_x: 0.0,
+            cursor_direction_y: 0.0,
+            cursor_time: state.cursor_force.time(),
+        }
+    }
+}
+
+/// Floats in a [`Particle`]: position, velocity, species and padding
+const PARTICLE_FLOATS: u32 = (std::mem::size_of::<Particle>() / std::mem::size_of::<f32>()) as u32;
+
+/// Far faster than any preset moves, in world units per second
+const MAX_HEALTHY_VELOCITY: f32 = 100.0;
+
+/// The passes of a Particle Life frame, in the order its render graph runs them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticleLifeNode {
+    Compute,
+    Background,
+    Trails,
+    TrailBlit,
+    Particles,
+    Blur,
+    PostEffect,
+    InfiniteRender,
+}
+
+impl GraphNode for ParticleLifeNode {
+    fn name(self) -> &'static str {
+        match self {
+            ParticleLifeNode::Compute => "compute",
+            ParticleLifeNode::Background => "background",
+            ParticleLifeNode::Trails => "trails",
+            ParticleLifeNode::TrailBlit => "trail_blit",
+            ParticleLifeNode::Particles => "particles",
+            ParticleLifeNode::Blur => "blur",
+            ParticleLifeNode::PostEffect => "post_effect",
+            ParticleLifeNode::InfiniteRender => "infinite_render",
         }
     }
 }
@@ -164,9 +200,10 @@ impl SimParams {
 #[derive(Debug)]
 pub struct ParticleLifeModel {
     // GPU resources
-    pub particle_buffer: wgpu::Buffer,
+    pub particle_buffer: ParticleBuffer<Particle>,
     pub sim_params_buffer: wgpu::Buffer,
     pub force_matrix_buffer: wgpu::Buffer,
+    pub global_force_buffer: wgpu::Buffer,
     pub lut_buffer: Arc<wgpu::Buffer>,
     pub lut_size_buffer: wgpu::Buffer,
     pub color_mode_buffer: wgpu::Buffer,
@@ -214,25 +251,12 @@ pub struct ParticleLifeModel {
     // Trail render pipeline for trail texture (uses surface format)
     pub trail_render_pipeline: wgpu::RenderPipeline,
 
-    // Fade pipeline for traces
-    pub fade_pipeline: wgpu::RenderPipeline,
-    pub fade_bind_group_layout: wgpu::BindGroupLayout,
-    pub fade_bind_group: wgpu::BindGroup,
-    pub fade_uniforms_buffer: wgpu::Buffer,
-
-    // Trail textures for persistent trails (ping-pong buffers)
-    pub trail_texture_a: wgpu::Texture,
-    pub trail_texture_view_a: wgpu::TextureView,
-    pub trail_texture_b: wgpu::Texture,
-    pub trail_texture_view_b: wgpu::TextureView,
-    pub current_trail_is_a: bool, // true = A is current write target, B is read source
-
-    // Blit pipeline to copy trail texture to surface
-    pub blit_pipeline: wgpu::RenderPipeline,
-    // Display blit pipeline to copy trail texture to display texture (Rgba8Unorm format)
-    pub display_blit_pipeline: wgpu::RenderPipeline,
+    // Fading trails the particles are drawn into when traces are on
+    pub trails: Trails,
     pub blit_bind_group_layout: wgpu::BindGroupLayout,
-    pub blit_bind_group: wgpu::BindGroup,
+
+    // The passes making up a frame
+    pub render_graph: RenderGraph<ParticleLifeNode>,
 
     // Background render pipeline for offscreen rendering
     pub background_render_pipeline: wgpu::RenderPipeline,
@@ -251,6 +275,8 @@ pub struct ParticleLifeModel {
     // MSAA texture for anti-aliasing particle rendering
     pub msaa_texture: wgpu::Texture,
     pub msaa_view: wgpu::TextureView,
+    // 1 on low-end GPUs, where the multisampled target is not worth its memory
+    msaa_sample_count: u32,
 
     // Post effect texture for post-processing
     pub post_effect_texture: wgpu::Texture,
@@ -272,7 +298,6 @@ pub struct ParticleLifeModel {
     // Samplers
     pub blit_sampler: wgpu::Sampler,
     pub post_effect_sampler: wgpu::Sampler,
-    pub display_sampler: wgpu::Sampler,
 
     // Simulation state and settings
     pub settings: Settings,
@@ -296,6 +321,8 @@ pub struct ParticleLifeModel {
     pub cursor_active_mode: u32, // 0=inactive, 1=attract, 2=repel
     pub cursor_world_x: f32,
     pub cursor_world_y: f32,
+    /// Unit direction the cursor last moved in while pressed
+    pub cursor_world_direction: [f32; 2],
 
     // Adaptive resolution tracking
     pub current_resolution_scale: f32,
@@ -431,7 +458,7 @@ impl ParticleLifeModel {
                     depth_or_array_layers: 1,
                 },
                 mip_level_count: 1,
-                sample_count: 4, // 4x MSAA
+                sample_count: self.msaa_sample_count,
                 dimension: wgpu::TextureDimension::D2,
                 format: wgpu::TextureFormat::Rgba8Unorm,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -442,55 +469,7 @@ impl ParticleLifeModel {
                 .create_view(&wgpu::TextureViewDescriptor::default());
 
             // Recreate trail textures with new dimensions
-            self.trail_texture_a = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("Trail Texture A"),
-                size: wgpu::Extent3d {
-                    width: new_width,
-                    height: new_height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8Unorm,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                    | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
-
-            self.trail_texture_view_a =
-                self.trail_texture_a
-                    .create_view(&wgpu::TextureViewDescriptor {
-                        label: Some("Trail Texture View A"),
-                        dimension: Some(wgpu::TextureViewDimension::D2),
-                        format: Some(wgpu::TextureFormat::Rgba8Unorm),
-                        ..Default::default()
-                    });
-
-            self.trail_texture_b = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("Trail Texture B"),
-                size: wgpu::Extent3d {
-                    width: new_width,
-                    height: new_height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8Unorm,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                    | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
-
-            self.trail_texture_view_b =
-                self.trail_texture_b
-                    .create_view(&wgpu::TextureViewDescriptor {
-                        label: Some("Trail Texture View B"),
-                        dimension: Some(wgpu::TextureViewDimension::D2),
-                        format: Some(wgpu::TextureFormat::Rgba8Unorm),
-                        ..Default::default()
-                    });
+            self.trails.resize(device, (new_width, new_height));
 
             // Update dimensions
             self.width = new_width;
@@ -579,7 +558,7 @@ impl ParticleLifeModel {
         device: &Arc<Device>,
         queue: &Arc<Queue>,
         surface_config: &SurfaceConfiguration,
-        _adapter_info: &wgpu::AdapterInfo,
+        adapter_info: &wgpu::AdapterInfo,
         particle_count: usize,
         settings: Settings,
         app_settings: &crate::commands::app_settings::AppSettings,
@@ -589,6 +568,19 @@ impl ParticleLifeModel {
         let width = surface_config.width;
         let height = surface_config.height;
 
+        let msaa_sample_count =
+            GpuTier::detect(adapter_info, &device.limits()).msaa_sample_count();
+
+        // Spawn fewer particles rather than fail when the buffer wouldn't fit
+        let particle_count = gpu_budget::fit_count(
+            device,
+            "Particle Life particles",
+            particle_count,
+            std::mem::size_of::<Particle>() as u64,
+            1,
+            &GpuReservation::default(),
+        );
+
         // Use a proper default LUT name instead of hardcoding
         let default_color_scheme = "MATPLOTLIB_ocean";
 
@@ -654,8 +646,12 @@ impl ParticleLifeModel {
             dt: 0.016,
             cursor_size: 0.5,
             cursor_strength: 5.0,
+            cursor_force: Default::default(),
+            global_force: Default::default(),
             traces_enabled: false,
             trace_fade: 0.48,
+            trace_fade_curve: FadeCurve::Linear,
+            trace_resolution_scale: 1.0,
             edge_fade_strength: 1.0,
             position_generator: PositionGenerator::Random,
             type_generator: TypeGenerator::Random,
@@ -668,26 +664,13 @@ impl ParticleLifeModel {
             trail_map_filtering: super::settings::TrailMapFiltering::Nearest,
         };
 
-        // Check buffer size limits
-        let max_storage_buffer_size = device.limits().max_storage_buffer_binding_size as u64;
-        let particle_buffer_size = (particle_count * std::mem::size_of::<Particle>()) as u64;
-
-        if particle_buffer_size > max_storage_buffer_size {
-            return Err(SimulationError::BufferTooLarge {
-                requested: particle_buffer_size,
-                max_available: max_storage_buffer_size,
-            });
-        }
-
         // Create empty particle buffer (will be initialized on GPU)
-        let particle_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Particle Buffer"),
-            size: particle_buffer_size,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::VERTEX
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let particle_buffer = ParticleBuffer::uninitialized(
+            device,
+            "Particle Buffer",
+            wgpu::BufferUsages::VERTEX,
+            particle_count,
+        );
 
         // Create simulation parameters buffer
         let sim_params = SimParams::new(width, height, particle_count as u32, &settings, &state);
@@ -705,6 +688,12 @@ impl ParticleLifeModel {
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
         });
 
+        let global_force_buffer = resource_helpers::create_uniform_buffer_with_data(
+            device,
+            "Global Force Buffer",
+            &[state.global_force.uniform()],
+        );
+
         let lut_data_u32 = state
             .species_colors
             .iter()
@@ -731,6 +720,7 @@ impl ParticleLifeModel {
                     resource_helpers::storage_buffer_entry(0, wgpu::ShaderStages::COMPUTE, false),
                     resource_helpers::uniform_buffer_entry(1, wgpu::ShaderStages::COMPUTE),
                     resource_helpers::storage_buffer_entry(2, wgpu::ShaderStages::COMPUTE, true),
+                    resource_helpers::uniform_buffer_entry(3, wgpu::ShaderStages::COMPUTE),
                 ],
             });
 
@@ -741,9 +731,10 @@ impl ParticleLifeModel {
             .build();
 
         let compute_bind_group = BindGroupBuilder::new(device, &compute_bind_group_layout)
-            .add_buffer(0, &particle_buffer)
+            .add_buffer(0, particle_buffer.buffer())
             .add_buffer(1, &sim_params_buffer)
             .add_buffer(2, &force_matrix_buffer)
+            .add_buffer(3, &global_force_buffer)
             .with_label("Particle Life Compute Bind Group".to_string())
             .build();
 
@@ -789,7 +780,7 @@ impl ParticleLifeModel {
         });
 
         let init_bind_group = BindGroupBuilder::new(device, &init_bind_group_layout)
-            .add_buffer(0, &particle_buffer)
+            .add_buffer(0, particle_buffer.buffer())
             .add_buffer(1, &init_params_buffer)
             .with_label("Particle Life Init Bind Group".to_string())
             .build();
@@ -1073,7 +1064,7 @@ impl ParticleLifeModel {
                 },
                 depth_stencil: None,
                 multisample: wgpu::MultisampleState {
-                    count: 4, // 4x MSAA
+                    count: msaa_sample_count,
                     mask: !0,
                     alpha_to_coverage_enabled: false,
                 },
@@ -1158,7 +1149,7 @@ impl ParticleLifeModel {
             label: Some("Render Bind Group"),
             layout: &render_bind_group_layout_particles,
             entries: &[
-                resource_helpers::buffer_entry(0, &particle_buffer),
+                resource_helpers::buffer_entry(0, particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &sim_params_buffer),
             ],
         });
@@ -1289,177 +1280,7 @@ impl ParticleLifeModel {
             ],
         });
 
-        // Create fade pipeline for traces
-        let fade_vertex_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Fade Vertex Shader"),
-            source: wgpu::ShaderSource::Wgsl(shaders::FADE_VERTEX_SHADER.into()),
-        });
-
-        let fade_fragment_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Fade Fragment Shader"),
-            source: wgpu::ShaderSource::Wgsl(shaders::FADE_FRAGMENT_SHADER.into()),
-        });
-
-        let fade_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Fade Bind Group Layout"),
-                entries: &[
-                    resource_helpers::uniform_buffer_entry(0, wgpu::ShaderStages::FRAGMENT),
-                    resource_helpers::texture_entry(
-                        1,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::TextureSampleType::Float { filterable: true },
-                        wgpu::TextureViewDimension::D2,
-                    ),
-                    resource_helpers::sampler_entry(
-                        2,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::SamplerBindingType::Filtering,
-                    ),
-                ],
-            });
-
-        let fade_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Fade Pipeline Layout"),
-            bind_group_layouts: &[&fade_bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let fade_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Fade Pipeline"),
-            layout: Some(&fade_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &fade_vertex_shader,
-                entry_point: Some("main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &fade_fragment_shader,
-                entry_point: Some("main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Rgba8Unorm, // Use RGBA format to match trail texture
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
-
-        // Create fade uniforms buffer
-        let fade_uniforms = FadeUniforms {
-            fade_amount: 0.01,
-            _pad1: 0.0,
-            _pad2: 0.0,
-            _pad3: 0.0,
-        };
-
-        let fade_uniforms_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Fade Uniforms Buffer"),
-            contents: bytemuck::cast_slice(&[fade_uniforms]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-
-        // Create temporary placeholder texture and sampler for fade bind group
-        let placeholder_texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Placeholder Texture"),
-            size: wgpu::Extent3d {
-                width: 1,
-                height: 1,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-
-        let placeholder_texture_view =
-            placeholder_texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-        let placeholder_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Placeholder Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: app_settings.texture_filtering.into(),
-            min_filter: app_settings.texture_filtering.into(),
-            mipmap_filter: app_settings.texture_filtering.into(),
-            ..Default::default()
-        });
-
-        // Create a temporary fade bind group with placeholder resources - will be updated later with display texture
-        let fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Fade Bind Group"),
-            layout: &fade_bind_group_layout,
-            entries: &[
-                resource_helpers::buffer_entry(0, &fade_uniforms_buffer),
-                resource_helpers::texture_view_entry(1, &placeholder_texture_view),
-                resource_helpers::sampler_bind_entry(2, &placeholder_sampler),
-            ],
-        });
-
-        // Trail textures for persistent trails (ping-pong buffers)
-        let trail_texture_a = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Trail Texture A"),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm, // Use RGBA format for proper alpha support
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-
-        let trail_texture_view_a = trail_texture_a.create_view(&wgpu::TextureViewDescriptor {
-            label: Some("Trail Texture View A"),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            format: Some(wgpu::TextureFormat::Rgba8Unorm), // Use RGBA format for proper alpha support
-            ..Default::default()
-        });
-
-        let trail_texture_b = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Trail Texture B"),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm, // Use RGBA format for proper alpha support
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-
-        let trail_texture_view_b = trail_texture_b.create_view(&wgpu::TextureViewDescriptor {
-            label: Some("Trail Texture View B"),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            format: Some(wgpu::TextureFormat::Rgba8Unorm), // Use RGBA format for proper alpha support
-            ..Default::default()
-        });
-
-        // Blit pipeline to copy trail texture to surface
+        // Layout for sampling the display texture
         let blit_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Blit Bind Group Layout"),
@@ -1478,108 +1299,15 @@ impl ParticleLifeModel {
                 ],
             });
 
-        let blit_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Blit Pipeline Layout"),
-            bind_group_layouts: &[&blit_bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        // Create blit shaders
-        let blit_vertex_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Blit Vertex Shader"),
-            source: wgpu::ShaderSource::Wgsl(shaders::FADE_VERTEX_SHADER.into()), // Reuse fade vertex shader
-        });
-
-        let blit_fragment_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Blit Fragment Shader"),
-            source: wgpu::ShaderSource::Wgsl(
-                r#"
-                @group(0) @binding(0) var trail_texture: texture_2d<f32>;
-                @group(0) @binding(1) var trail_sampler: sampler;
-
-                struct VertexOutput {
-                    @builtin(position) position: vec4<f32>,
-                    @location(0) uv: vec2<f32>,
-                }
-
-                @fragment
-                fn main(input: VertexOutput) -> @location(0) vec4<f32> {
-                    return textureSample(trail_texture, trail_sampler, input.uv);
-                }
-                "#
-                .into(),
-            ),
-        });
-
-        let blit_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Blit Pipeline"),
-            layout: Some(&blit_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &blit_vertex_shader,
-                entry_point: Some("main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &blit_fragment_shader,
-                entry_point: Some("main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_config.format,
-                    blend: None, // No blending for blit
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
-
-        // Create display blit pipeline for copying trail texture to display texture (Rgba8Unorm format)
-        let display_blit_pipeline =
-            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("Display Blit Pipeline"),
-                layout: Some(&blit_pipeline_layout),
-                vertex: wgpu::VertexState {
-                    module: &blit_vertex_shader,
-                    entry_point: Some("main"),
-                    buffers: &[],
-                    compilation_options: Default::default(),
-                },
-                fragment: Some(wgpu::FragmentState {
-                    module: &blit_fragment_shader,
-                    entry_point: Some("main"),
-                    targets: &[Some(wgpu::ColorTargetState {
-                        format: wgpu::TextureFormat::Rgba8Unorm,
-                        blend: None, // No blending for blit
-                        write_mask: wgpu::ColorWrites::ALL,
-                    })],
-                    compilation_options: Default::default(),
-                }),
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    strip_index_format: None,
-                    front_face: wgpu::FrontFace::Ccw,
-                    cull_mode: None,
-                    unclipped_depth: false,
-                    polygon_mode: wgpu::PolygonMode::Fill,
-                    conservative: false,
-                },
-                depth_stencil: None,
-                multisample: wgpu::MultisampleState::default(),
-                multiview: None,
-                cache: None,
-            });
+        // Trails the particles are drawn into when traces are on
+        let trails = Trails::new(
+            device,
+            "Particle Life Trails",
+            (width, height),
+            state.trace_resolution_scale,
+            wgpu::TextureFormat::Rgba8Unorm,
+            app_settings.texture_filtering.into(),
+        );
 
         // Create sampler for blit
         let blit_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -1605,28 +1333,6 @@ impl ParticleLifeModel {
             ..Default::default()
         });
 
-        // Create sampler for display
-        let display_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Display Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: app_settings.texture_filtering.into(),
-            min_filter: app_settings.texture_filtering.into(),
-            mipmap_filter: app_settings.texture_filtering.into(),
-            ..Default::default()
-        });
-
-        // Blit bind group (initially uses texture A)
-        let blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Blit Bind Group"),
-            layout: &blit_bind_group_layout,
-            entries: &[
-                resource_helpers::texture_view_entry(0, &trail_texture_view_a),
-                resource_helpers::sampler_bind_entry(1, &blit_sampler),
-            ],
-        });
-
         // Create background render shader
         let background_render_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Background Render Shader"),
@@ -1736,7 +1442,7 @@ impl ParticleLifeModel {
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
-            sample_count: 4, // 4x MSAA
+            sample_count: msaa_sample_count,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8Unorm,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -1980,9 +1686,10 @@ impl ParticleLifeModel {
             });
 
         let mut result = Self {
-            particle_buffer: particle_buffer.clone(),
+            particle_buffer,
             sim_params_buffer: sim_params_buffer.clone(),
             force_matrix_buffer,
+            global_force_buffer,
             lut_buffer,
             lut_size_buffer,
             color_mode_buffer,
@@ -2039,19 +1746,9 @@ impl ParticleLifeModel {
             offscreen_render_pipeline,
             display_render_pipeline,
             trail_render_pipeline,
-            fade_pipeline,
-            fade_bind_group_layout,
-            fade_bind_group,
-            fade_uniforms_buffer,
-            trail_texture_a,
-            trail_texture_view_a,
-            trail_texture_b,
-            trail_texture_view_b,
-            current_trail_is_a: true,
-            blit_pipeline,
-            display_blit_pipeline,
+            trails,
+            render_graph: Self::create_render_graph(),
             blit_bind_group_layout,
-            blit_bind_group,
             background_render_pipeline,
             background_bind_group_layout,
             background_bind_group,
@@ -2062,6 +1759,7 @@ impl ParticleLifeModel {
             display_bind_group,
             msaa_texture,
             msaa_view,
+            msaa_sample_count,
             post_effect_texture,
             post_effect_view,
             post_effect_bind_group,
@@ -2075,7 +1773,6 @@ impl ParticleLifeModel {
             texture_render_params_buffer,
             blit_sampler,
             post_effect_sampler,
-            display_sampler,
             settings,
             state,
             gui_visible: true,
@@ -2087,6 +1784,7 @@ impl ParticleLifeModel {
             cursor_active_mode: 0,
             cursor_world_x: 0.0,
             cursor_world_y: 0.0,
+            cursor_world_direction: [0.0, 0.0],
             current_resolution_scale: 1.0,
             last_zoom_level: 1.0,
             base_surface_width: surface_config.width,
@@ -2119,36 +1817,9 @@ impl ParticleLifeModel {
         result.initialize_particles_gpu(device, queue)?;
 
         // Initialize trail texture with background color
-        let background_color = match color_mode {
-            BackgroundColorMode::Gray18 => wgpu::Color {
-                r: 0.18,
-                g: 0.18,
-                b: 0.18,
-                a: 1.0,
-            },
-            BackgroundColorMode::White => wgpu::Color::WHITE,
-            BackgroundColorMode::Black => wgpu::Color::BLACK,
-            BackgroundColorMode::ColorScheme => {
-                if !result.state.species_colors.is_empty() {
-                    // Background is appended at the end (index = species_count)
-                    let bg_index = (result.settings.species_count as usize)
-                        .min(result.state.species_colors.len() - 1);
-                    let [r, g, b, a] = result.state.species_colors[bg_index];
-                    wgpu::Color {
-                        r: r.into(),
-                        g: g.into(),
-                        b: b.into(),
-                        a: a.into(),
-                    }
-                } else {
-                    wgpu::Color::BLACK
-                }
-            }
-        };
-        result.clear_trail_texture(device, queue, background_color);
-
-        // Update fade bind group with display texture
-        result.update_fade_bind_group(device);
+        result
+            .trails
+            .clear(device, queue, result.background_clear_color());
 
         Ok(result)
     }
@@ -2166,6 +1837,8 @@ impl ParticleLifeModel {
         sim_params.cursor_x = self.cursor_world_x;
         sim_params.cursor_y = self.cursor_world_y;
         sim_params.cursor_active = self.cursor_active_mode;
+        sim_params.cursor_direction_x = self.cursor_world_direction[0];
+        sim_params.cursor_direction_y = self.cursor_world_direction[1];
         if self.cursor_active_mode > 0 {
             sim_params.cursor_strength =
                 self.state.cursor_strength * self.settings.max_force * 10.0;
@@ -2376,9 +2049,10 @@ impl ParticleLifeModel {
             label: Some("Particle Life Compute Bind Group"),
             layout: &self.compute_pipeline.get_bind_group_layout(0),
             entries: &[
-                resource_helpers::buffer_entry(0, &self.particle_buffer),
+                resource_helpers::buffer_entry(0, self.particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &self.sim_params_buffer),
                 resource_helpers::buffer_entry(2, &self.force_matrix_buffer),
+                resource_helpers::buffer_entry(3, &self.global_force_buffer),
             ],
         });
     }
@@ -2542,75 +2216,36 @@ impl ParticleLifeModel {
         self.state.species_colors.len() as u32
     }
 
-    /// Update fade uniforms for trace rendering
-    fn update_fade_uniforms(&self, queue: &Arc<Queue>, fade_amount: f32) {
-        let fade_uniforms = FadeUniforms {
-            fade_amount,
-            _pad1: 0.0,
-            _pad2: 0.0,
-            _pad3: 0.0,
-        };
-
-        queue.write_buffer(
-            &self.fade_uniforms_buffer,
-            0,
-            bytemuck::cast_slice(&[fade_uniforms]),
-        );
-    }
-
-    fn update_fade_bind_group(&mut self, device: &Arc<Device>) {
-        // Read from the previous trail texture (opposite of current write target)
-        let read_texture_view = self.get_read_trail_texture_view();
-
-        self.fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Fade Bind Group"),
-            layout: &self.fade_bind_group_layout,
-            entries: &[
-                resource_helpers::buffer_entry(0, &self.fade_uniforms_buffer),
-                resource_helpers::texture_view_entry(1, read_texture_view),
-                resource_helpers::sampler_bind_entry(2, &self.display_sampler),
-            ],
-        });
-    }
-
-    /// Get the trail texture view to write to (render target)
-    fn get_write_trail_texture_view(&self) -> &wgpu::TextureView {
-        if self.current_trail_is_a {
-            &self.trail_texture_view_a
-        } else {
-            &self.trail_texture_view_b
-        }
-    }
-
-    /// Get the trail texture view to read from (for fade shader)
-    fn get_read_trail_texture_view(&self) -> &wgpu::TextureView {
-        if self.current_trail_is_a {
-            &self.trail_texture_view_b // Read from B when writing to A
-        } else {
-            &self.trail_texture_view_a // Read from A when writing to B
+    /// Background color the trails fade over
+    fn background_clear_color(&self) -> wgpu::Color {
+        match self.state.background_color_mode {
+            BackgroundColorMode::Gray18 => wgpu::Color {
+                r: 0.18,
+                g: 0.18,
+                b: 0.18,
+                a: 1.0,
+            },
+            BackgroundColorMode::White => wgpu::Color::WHITE,
+            BackgroundColorMode::Black => wgpu::Color::BLACK,
+            BackgroundColorMode::ColorScheme => {
+                if !self.state.species_colors.is_empty() {
+                    // Background is appended at the end (index = species_count)
+                    let bg_index = (self.settings.species_count as usize)
+                        .min(self.state.species_colors.len() - 1);
+                    let [r, g, b, a] = self.state.species_colors[bg_index];
+                    wgpu::Color {
+                        r: r.into(),
+                        g: g.into(),
+                        b: b.into(),
+                        a: a.into(),
+                    }
+                } else {
+                    wgpu::Color::BLACK
+                }
+            }
         }
     }
 
-    /// Swap the ping-pong trail textures after each frame
-    fn swap_trail_textures(&mut self) {
-        self.current_trail_is_a = !self.current_trail_is_a;
-    }
-
-    /// Update blit bind group to read from the current completed trail texture
-    fn update_blit_bind_group(&mut self, device: &Arc<Device>) {
-        // For blitting, we want to read from the texture we just finished writing to
-        let read_texture_view = self.get_write_trail_texture_view();
-
-        self.blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Blit Bind Group"),
-            layout: &self.blit_bind_group_layout,
-            entries: &[
-                resource_helpers::texture_view_entry(0, read_texture_view),
-                resource_helpers::sampler_bind_entry(1, &self.display_sampler),
-            ],
-        });
-    }
-
     /// Update background color based on color mode
     pub fn update_background_params(&mut self, queue: &Arc<Queue>) {
         // Get background color based on color mode
@@ -2647,48 +2282,7 @@ impl ParticleLifeModel {
         queue: &Arc<Queue>,
         background_color: wgpu::Color,
     ) {
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Clear Trail Texture Encoder"),
-        });
-
-        // Clear both trail textures
-        {
-            let _render_pass_a = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Clear Trail Texture A Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.trail_texture_view_a,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(background_color),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-        }
-
-        {
-            let _render_pass_b = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Clear Trail Texture B Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.trail_texture_view_b,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(background_color),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-        }
-
-        queue.submit(std::iter::once(encoder.finish()));
+        self.trails.clear(device, queue, background_color);
     }
 
     /// Calculate which tiles are visible based on camera position and zoom
@@ -2725,6 +2319,242 @@ impl ParticleLifeModel {
         );
     }
 
+    fn create_render_graph() -> RenderGraph<ParticleLifeNode> {
+        RenderGraph::new("Particle Life")
+            .with_node(ParticleLifeNode::Compute, NodeKind::Compute)
+            .with_node(ParticleLifeNode::Background, NodeKind::Offscreen)
+            .with_node(ParticleLifeNode::Trails, NodeKind::Offscreen)
+            .with_node(ParticleLifeNode::TrailBlit, NodeKind::Offscreen)
+            .with_node(ParticleLifeNode::Particles, NodeKind::Offscreen)
+            .with_node(ParticleLifeNode::Blur, NodeKind::Post)
+            .with_node(ParticleLifeNode::PostEffect, NodeKind::Post)
+            .with_node(ParticleLifeNode::InfiniteRender, NodeKind::Composite)
+    }
+
+    /// Render a frame through the render graph, stepping particles and
+    /// trails only while `running`
+    fn render_graph_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        running: bool,
+    ) -> SimulationResult<()> {
+        // Paused trails show the latest frame as it is
+        let traces_enabled = self.state.traces_enabled;
+        self.render_graph
+            .set_enabled(ParticleLifeNode::Compute, running);
+        self.render_graph
+            .set_enabled(ParticleLifeNode::Trails, running && traces_enabled);
+        self.render_graph
+            .set_enabled(ParticleLifeNode::TrailBlit, traces_enabled);
+        self.render_graph
+            .set_enabled(ParticleLifeNode::Particles, !traces_enabled);
+        // Only a paused frame is blurred
+        self.render_graph.set_enabled(
+            ParticleLifeNode::Blur,
+            !running && self.post_processing_state.blur_filter.enabled,
+        );
+        // Skip expensive post-effect pass when using default parameters
+        self.render_graph
+            .set_enabled(ParticleLifeNode::PostEffect, self.needs_post_effects());
+
+        let mut graph = std::mem::take(&mut self.render_graph);
+        let result = graph.execute(device, queue, |node, encoder| {
+            self.encode_render_node(node, device, queue, encoder, surface_view, running)
+        });
+        self.render_graph = graph;
+        result
+    }
+
+    fn encode_render_node(
+        &mut self,
+        node: ParticleLifeNode,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        running: bool,
+    ) -> SimulationResult<()> {
+        let particle_count = self.state.particle_count as u32;
+        match node {
+            ParticleLifeNode::Compute => {
+                let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("Particle Life Compute Pass"),
+                    timestamp_writes: None,
+                });
+
+                compute_pass.set_pipeline(&self.compute_pipeline);
+                compute_pass.set_bind_group(0, &self.compute_bind_group, &[]);
+
+                let workgroup_size = 64;
+                let num_workgroups = self.state.particle_count.div_ceil(workgroup_size);
+                compute_pass.dispatch_workgroups(num_workgroups as u32, 1, 1);
+            }
+            ParticleLifeNode::Background => {
+                let mut background_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Background Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &self.display_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT), // Clear to transparent, background shader will fill
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                background_pass.set_pipeline(&self.background_render_pipeline);
+                background_pass.set_bind_group(0, &self.background_bind_group, &[]);
+                background_pass.draw(0..6, 0..1); // Fullscreen triangle
+            }
+            ParticleLifeNode::Trails => {
+                // Fade the last frame's trails, then draw this frame's particles over them
+                {
+                    let mut trail_render_pass = self.trails.begin_pass(
+                        device,
+                        queue,
+                        encoder,
+                        self.state.trace_fade_curve,
+                        self.state.trace_fade,
+                        self.background_clear_color(),
+                    );
+
+                    trail_render_pass.set_pipeline(&self.trail_render_pipeline);
+                    trail_render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+                    trail_render_pass.set_bind_group(1, &self.lut_bind_group, &[]);
+                    trail_render_pass.set_bind_group(2, &self.camera_bind_group, &[]);
+                    trail_render_pass.draw(0..6, 0..particle_count);
+                }
+                self.trails.swap();
+            }
+            ParticleLifeNode::TrailBlit => self.trails.blit(device, encoder, &self.display_view),
+            ParticleLifeNode::Particles => {
+                // Without trails, particles go straight onto the background
+                let mut particle_render_pass =
+                    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: Some("Particle Render Pass"),
+                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                            view: &self.display_view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Load, // Preserve background
+                                store: wgpu::StoreOp::Store,
+                            },
+                            depth_slice: None,
+                        })],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None,
+                    });
+
+                particle_render_pass.set_pipeline(&self.display_render_pipeline);
+                particle_render_pass.set_bind_group(0, &self.render_bind_group, &[]);
+                particle_render_pass.set_bind_group(1, &self.lut_bind_group, &[]);
+                particle_render_pass.set_bind_group(2, &self.camera_bind_group, &[]);
+                particle_render_pass.draw(0..6, 0..particle_count);
+            }
+            ParticleLifeNode::Blur => {
+                // Blur from display_view into intermediate_view
+                self.apply_post_processing(
+                    device,
+                    queue,
+                    &self.display_view,
+                    &self.post_processing_resources.intermediate_view,
+                    encoder,
+                )?;
+
+                // Copy the blurred result back to the display texture
+                encoder.copy_texture_to_texture(
+                    wgpu::TexelCopyTextureInfo {
+                        texture: &self.post_processing_resources.intermediate_texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    wgpu::TexelCopyTextureInfo {
+                        texture: &self.display_texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    wgpu::Extent3d {
+                        width: self.width,
+                        height: self.height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+            ParticleLifeNode::PostEffect => {
+                let mut post_effect_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Post Effect Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &self.post_effect_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                post_effect_pass.set_pipeline(&self.post_effect_pipeline);
+                post_effect_pass.set_bind_group(0, &self.post_effect_bind_group, &[]);
+                post_effect_pass.draw(0..6, 0..1);
+            }
+            ParticleLifeNode::InfiniteRender => {
+                let tile_count = Self::calculate_tile_count(self.camera.zoom);
+                let total_instances = (tile_count * tile_count) as u32;
+                // A running frame draws over the surface and shows the
+                // display texture as it is
+                let load = if running {
+                    wgpu::LoadOp::Load
+                } else {
+                    wgpu::LoadOp::Clear(wgpu::Color::BLACK)
+                };
+
+                let mut surface_render_pass =
+                    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: Some("Surface Render Pass"),
+                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                            view: surface_view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load,
+                                store: wgpu::StoreOp::Store,
+                            },
+                            depth_slice: None,
+                        })],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None,
+                    });
+
+                // Use display texture directly when post-effects are disabled for better performance
+                surface_render_pass.set_pipeline(&self.render_infinite_pipeline);
+                let bind_group = if !running && self.needs_post_effects() {
+                    &self.render_infinite_bind_group
+                } else {
+                    &self.render_infinite_display_bind_group
+                };
+                surface_render_pass.set_bind_group(0, bind_group, &[]);
+                surface_render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+
+                // Draw a fullscreen quad with multiple instances for tiling
+                surface_render_pass.draw(0..6, 0..total_instances);
+            }
+        }
+        Ok(())
+    }
+
     fn apply_post_processing(
         &self,
         device: &Arc<Device>,
@@ -2835,203 +2665,7 @@ impl Simulation for ParticleLifeModel {
         // Update viewport parameters for camera-aware rendering
         self.update_viewport_params(queue);
 
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Particle Life Static Render Encoder"),
-        });
-
-        // Skip compute pass - just render current particle positions
-
-        // Step 1: Render background to display texture (offscreen)
-        {
-            let mut background_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Static Background Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.display_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT), // Clear to transparent, background shader will fill
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            background_pass.set_pipeline(&self.background_render_pipeline);
-            background_pass.set_bind_group(0, &self.background_bind_group, &[]);
-            background_pass.draw(0..6, 0..1); // Fullscreen triangle
-        }
-
-        // Step 2: Render particles to display texture (offscreen)
-        if self.state.traces_enabled {
-            // For static rendering, just render to current trail texture without fade
-            let write_texture_view = self.get_write_trail_texture_view();
-            let mut trail_render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Static Trail Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: write_texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load, // Preserve previous trail content
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            // For static rendering, don't add new trails - just render existing particles
-            trail_render_pass.set_pipeline(&self.trail_render_pipeline);
-            trail_render_pass.set_bind_group(0, &self.render_bind_group, &[]);
-            trail_render_pass.set_bind_group(1, &self.lut_bind_group, &[]);
-
-            let particle_count = self.state.particle_count as u32;
-            trail_render_pass.draw(0..6, 0..particle_count);
-            drop(trail_render_pass);
-
-            // Now blit trail texture to display texture
-            let mut display_render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Static Display Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.display_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load, // Load existing background
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            display_render_pass.set_pipeline(&self.blit_pipeline);
-            display_render_pass.set_bind_group(0, &self.blit_bind_group, &[]);
-            display_render_pass.draw(0..3, 0..1);
-        } else {
-            // When trails are disabled, render particles directly to display texture
-            let mut display_render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Static Display Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.display_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load, // Load existing background
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            display_render_pass.set_pipeline(&self.display_render_pipeline);
-            display_render_pass.set_bind_group(0, &self.render_bind_group, &[]);
-            display_render_pass.set_bind_group(1, &self.lut_bind_group, &[]);
-            display_render_pass.set_bind_group(2, &self.camera_bind_group, &[]);
-
-            let particle_count = self.state.particle_count as u32;
-            display_render_pass.draw(0..6, 0..particle_count);
-        }
-
-        // Step 3: Apply post-processing if enabled
-        if self.post_processing_state.blur_filter.enabled {
-            // Apply post-processing: input from display_view, output to intermediate_view
-            self.apply_post_processing(
-                device,
-                queue,
-                &self.display_view,
-                &self.post_processing_resources.intermediate_view,
-                &mut encoder,
-            )?;
-
-            // Copy the blurred result back to the display texture using the main encoder
-            encoder.copy_texture_to_texture(
-                wgpu::TexelCopyTextureInfo {
-                    texture: &self.post_processing_resources.intermediate_texture,
-                    mip_level: 0,
-                    origin: wgpu::Origin3d::ZERO,
-                    aspect: wgpu::TextureAspect::All,
-                },
-                wgpu::TexelCopyTextureInfo {
-                    texture: &self.display_texture,
-                    mip_level: 0,
-                    origin: wgpu::Origin3d::ZERO,
-                    aspect: wgpu::TextureAspect::All,
-                },
-                wgpu::Extent3d {
-                    width: self.width,
-                    height: self.height,
-                    depth_or_array_layers: 1,
-                },
-            );
-        }
-
-        // Step 4: Render post effects from display texture to post-effect texture (offscreen)
-        // Skip expensive post-effect pass when using default parameters
-        if self.needs_post_effects() {
-            let mut post_effect_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Static Post Effect Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.post_effect_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            post_effect_pass.set_pipeline(&self.post_effect_pipeline);
-            post_effect_pass.set_bind_group(0, &self.post_effect_bind_group, &[]);
-            post_effect_pass.draw(0..6, 0..1);
-        }
-
-        // Step 4: Render texture to surface with infinite renderer
-        // Use display texture directly when post-effects are disabled for better performance
-        {
-            let tile_count = Self::calculate_tile_count(self.camera.zoom);
-            let total_instances = (tile_count * tile_count) as u32;
-
-            let mut surface_render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Static Surface Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: surface_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            surface_render_pass.set_pipeline(&self.render_infinite_pipeline);
-            let bind_group = if self.needs_post_effects() {
-                &self.render_infinite_bind_group
-            } else {
-                &self.render_infinite_display_bind_group
-            };
-            surface_render_pass.set_bind_group(0, bind_group, &[]);
-            surface_render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
-            surface_render_pass.draw(0..6, 0..total_instances);
-        }
-
-        queue.submit(std::iter::once(encoder.finish()));
-        Ok(())
+        self.render_graph_frame(device, queue, surface_view, false)
     }
 
     fn render_frame(
@@ -3051,7 +2685,14 @@ impl Simulation for ParticleLifeModel {
         let delta_time = delta_time.min(1.0); // Max 1 second jump
 
         // Update GPU buffers with current state
+        self.state.cursor_force.advance(delta_time);
+        self.state.global_force.advance(delta_time);
         self.update_sim_params(device, queue);
+        queue.write_buffer(
+            &self.global_force_buffer,
+            0,
+            bytemuck::cast_slice(&[self.state.global_force.uniform()]),
+        );
 
         // Update camera with smoothing using actual delta time
         self.camera.update(delta_time);
@@ -3065,275 +2706,7 @@ impl Simulation for ParticleLifeModel {
         // Update background parameters
         self.update_background_params(queue);
 
-        // Use separate command encoders to avoid texture usage conflicts
-        // First encoder: compute and trail rendering
-        let mut compute_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Particle Life Compute Encoder"),
-        });
-
-        // Single physics step per frame for proper timing
-        {
-            let mut compute_pass =
-                compute_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                    label: Some("Particle Life Compute Pass"),
-                    timestamp_writes: None,
-                });
-
-            compute_pass.set_pipeline(&self.compute_pipeline);
-            compute_pass.set_bind_group(0, &self.compute_bind_group, &[]);
-
-            let workgroup_size = 64;
-            let num_workgroups = self.state.particle_count.div_ceil(workgroup_size);
-            compute_pass.dispatch_workgroups(num_workgroups as u32, 1, 1);
-        }
-
-        // Step 1: Render background to display texture (offscreen)
-        {
-            let mut background_pass =
-                compute_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Background Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.display_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT), // Clear to transparent, background shader will fill
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
-
-            background_pass.set_pipeline(&self.background_render_pipeline);
-            background_pass.set_bind_group(0, &self.background_bind_group, &[]);
-            background_pass.draw(0..6, 0..1); // Fullscreen triangle
-        }
-
-        // Step 2: Render particles to display texture (offscreen)
-        if self.state.traces_enabled {
-            // Update bind groups for ping-pong rendering
-            self.update_fade_bind_group(device);
-
-            // When trails are enabled, render to trail texture first
-            // Use ping-pong textures to avoid read/write conflicts
-            let write_texture_view = self.get_write_trail_texture_view();
-
-            // Get background color for clearing
-            let background_color = match self.state.background_color_mode {
-                BackgroundColorMode::Black => wgpu::Color {
-                    r: 0.0,
-                    g: 0.0,
-                    b: 0.0,
-                    a: 1.0,
-                },
-                BackgroundColorMode::White => wgpu::Color {
-                    r: 1.0,
-                    g: 1.0,
-                    b: 1.0,
-                    a: 1.0,
-                },
-                BackgroundColorMode::Gray18 => wgpu::Color {
-                    r: 0.18,
-                    g: 0.18,
-                    b: 0.18,
-                    a: 1.0,
-                },
-                BackgroundColorMode::ColorScheme => {
-                    if !self.state.species_colors.is_empty() {
-                        // Background is appended at the end (index = species_count)
-                        let bg_index = (self.settings.species_count as usize)
-                            .min(self.state.species_colors.len() - 1);
-                        let bg = self.state.species_colors[bg_index];
-                        wgpu::Color {
-                            r: bg[0] as f64,
-                            g: bg[1] as f64,
-                            b: bg[2] as f64,
-                            a: bg[3] as f64,
-                        }
-                    } else {
-                        wgpu::Color {
-                            r: 0.0,
-                            g: 0.0,
-                            b: 0.0,
-                            a: 1.0,
-                        }
-                    }
-                }
-            };
-
-            let mut trail_render_pass =
-                compute_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Trail Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: write_texture_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(background_color), // Clear with background color
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
-
-            // Always copy previous trail content (with or without fading)
-            // Calculate fade amount: convert trace_fade (0-1) to subtraction amount per frame
-            let fade_amount = if self.state.trace_fade < 1.0 {
-                // Invert trace_fade so 0.0 = fast fade, 1.0 = no fade
-                // Scale to reasonable subtraction range (0.001 to 0.1 per frame)
-                let fade_strength = 1.0 - self.state.trace_fade;
-                fade_strength * 0.1 // Maximum fade of 0.1 alpha per frame
-            } else {
-                0.0 // No fading
-            };
-
-            self.update_fade_uniforms(queue, fade_amount);
-
-            // Apply fade effect - reads from previous texture, writes to current
-            trail_render_pass.set_pipeline(&self.fade_pipeline);
-            trail_render_pass.set_bind_group(0, &self.fade_bind_group, &[]);
-            trail_render_pass.draw(0..3, 0..1);
-
-            // Then render particles on top
-            trail_render_pass.set_pipeline(&self.trail_render_pipeline);
-            trail_render_pass.set_bind_group(0, &self.render_bind_group, &[]);
-            trail_render_pass.set_bind_group(1, &self.lut_bind_group, &[]);
-            trail_render_pass.set_bind_group(2, &self.camera_bind_group, &[]);
-
-            let particle_count = self.state.particle_count as u32;
-            trail_render_pass.draw(0..6, 0..particle_count);
-            drop(trail_render_pass);
-        } else {
-            // When trails are disabled, render particles directly to display texture (preserving background)
-            let mut particle_render_pass =
-                compute_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Particle Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.display_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load, // Preserve background
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
-
-            particle_render_pass.set_pipeline(&self.display_render_pipeline);
-            particle_render_pass.set_bind_group(0, &self.render_bind_group, &[]);
-            particle_render_pass.set_bind_group(1, &self.lut_bind_group, &[]);
-            particle_render_pass.set_bind_group(2, &self.camera_bind_group, &[]);
-
-            let particle_count = self.state.particle_count as u32;
-            particle_render_pass.draw(0..6, 0..particle_count);
-        }
-
-        // Submit the first encoder to ensure trail texture writes are complete
-        queue.submit(std::iter::once(compute_encoder.finish()));
-
-        // Second encoder: blit and final rendering (can now safely read trail texture)
-        let mut render_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Particle Life Render Encoder"),
-        });
-
-        // Blit trail texture to display texture if trails are enabled
-        if self.state.traces_enabled {
-            // Update blit bind group to read from the texture we just wrote to
-            self.update_blit_bind_group(device);
-
-            let mut display_render_pass =
-                render_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Display Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.display_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load, // Load existing background
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
-
-            display_render_pass.set_pipeline(&self.display_blit_pipeline);
-            display_render_pass.set_bind_group(0, &self.blit_bind_group, &[]);
-            display_render_pass.draw(0..3, 0..1);
-        }
-
-        // Step 3: Render post effects from display texture to post-effect texture (offscreen)
-        // Skip expensive post-effect pass when using default parameters
-        if self.needs_post_effects() {
-            let mut post_effect_pass =
-                render_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Post Effect Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.post_effect_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
-
-            post_effect_pass.set_pipeline(&self.post_effect_pipeline);
-            post_effect_pass.set_bind_group(0, &self.post_effect_bind_group, &[]);
-            post_effect_pass.draw(0..6, 0..1);
-        }
-
-        // Step 4: Render texture to surface with infinite renderer
-        // Use display texture directly when post-effects are disabled for better performance
-        {
-            let tile_count = Self::calculate_tile_count(self.camera.zoom);
-            let total_instances = (tile_count * tile_count) as u32;
-
-            let mut surface_render_pass =
-                render_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Surface Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: surface_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
-
-            surface_render_pass.set_pipeline(&self.render_infinite_pipeline);
-            surface_render_pass.set_bind_group(0, &self.render_infinite_display_bind_group, &[]);
-            surface_render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
-
-            // Draw a fullscreen quad with multiple instances for tiling
-            surface_render_pass.draw(0..6, 0..total_instances);
-        }
-
-        queue.submit(std::iter::once(render_encoder.finish()));
-
-        // Swap ping-pong trail textures for next frame (only if trails are enabled)
-        if self.state.traces_enabled {
-            self.swap_trail_textures();
-        }
-
-        Ok(())
+        self.render_graph_frame(device, queue, surface_view, true)
     }
 
     fn resize(
@@ -3497,6 +2870,20 @@ impl Simulation for ParticleLifeModel {
                     self.state.trace_fade = fade as f32;
                 }
             }
+            "trace_fade_curve" => {
+                if let Ok(curve) = serde_json::from_value(value) {
+                    self.state.trace_fade_curve = curve;
+                }
+            }
+            "trace_resolution_scale" => {
+                if let Some(scale) = value.as_f64()
+                    && self.trails.set_resolution_scale(device, scale as f32)
+                {
+                    self.state.trace_resolution_scale = self.trails.resolution_scale();
+                    self.trails
+                        .clear(device, queue, self.background_clear_color());
+                }
+            }
             "edge_fade_strength" => {
                 if let Some(strength) = value.as_f64() {
                     self.state.edge_fade_strength = strength as f32;
@@ -3687,6 +3074,14 @@ impl Simulation for ParticleLifeModel {
         serde_json::to_value(&self.settings).unwrap_or(Value::Null)
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
     fn get_state(&self) -> Value {
         serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
     }
@@ -3712,6 +3107,18 @@ impl Simulation for ParticleLifeModel {
         let sim_x = world_x;
         let sim_y = world_y;
 
+        // Track which way the cursor moves for push mode, a new press has no
+        // direction until the cursor moves
+        if self.cursor_active_mode == 0 {
+            self.cursor_world_direction = [0.0, 0.0];
+        } else {
+            let movement = [sim_x - self.cursor_world_x, sim_y - self.cursor_world_y];
+            let distance = movement[0].hypot(movement[1]);
+            if distance > 1e-6 {
+                self.cursor_world_direction = [movement[0] / distance, movement[1] / distance];
+            }
+        }
+
         // Store cursor values in the model
         self.cursor_active_mode = cursor_mode;
         self.cursor_world_x = sim_x;
@@ -3737,6 +3144,8 @@ impl Simulation for ParticleLifeModel {
         sim_params.cursor_x = sim_x;
         sim_params.cursor_y = sim_y;
         sim_params.cursor_active = cursor_mode;
+        sim_params.cursor_direction_x = self.cursor_world_direction[0];
+        sim_params.cursor_direction_y = self.cursor_world_direction[1];
         if cursor_mode > 0 {
             sim_params.cursor_strength =
                 self.state.cursor_strength * self.settings.max_force * 10.0;
@@ -3761,6 +3170,7 @@ impl Simulation for ParticleLifeModel {
         self.cursor_active_mode = 0;
         self.cursor_world_x = 0.0;
         self.cursor_world_y = 0.0;
+        self.cursor_world_direction = [0.0, 0.0];
 
         // Update sim params immediately with cursor disabled
         let mut sim_params = SimParams::new(
@@ -3807,6 +3217,51 @@ impl Simulation for ParticleLifeModel {
         self.camera.get_state()
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        vec![self.particle_buffer.rewind_resource()]
+    }
+
+    fn health_probes(&self) -> Vec<HealthProbe<'_>> {
+        vec![
+            HealthProbe::vector(
+                "Particle positions",
+                self.particle_buffer.buffer(),
+                PARTICLE_FLOATS,
+                0,
+                HealthCheck::Positions,
+            ),
+            HealthProbe::vector(
+                "Particle velocities",
+                self.particle_buffer.buffer(),
+                PARTICLE_FLOATS,
+                2,
+                HealthCheck::Velocities {
+                    max_magnitude: MAX_HEALTHY_VELOCITY,
+                },
+            ),
+        ]
+    }
+
+    fn recover_from_instability(
+        &mut self,
+        issues: &[HealthIssue],
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Runaway velocities come back unless the forces behind them are weaker
+        if issues
+            .iter()
+            .any(|issue| matches!(issue, HealthIssue::Exploding { .. }))
+        {
+            self.settings.max_force *= 0.5;
+            tracing::info!(
+                "Reduced max force to {} after instability",
+                self.settings.max_force
+            );
+        }
+        self.reset_runtime_state(device, queue)
+    }
+
     fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
         // TODO: Implement preset saving
         Ok(())
@@ -3862,15 +3317,9 @@ impl Simulation for ParticleLifeModel {
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
-        // Update random seed for reset
-        use rand::Rng;
-        let mut rng = rand::rng();
-        self.state.random_seed = rng.random();
-
-        // Update sim params with new random seed
+        // Start over from the stored seed, so the same seed gives the same
+        // world. The reset button draws a new one in reset_particles_gpu.
         self.update_sim_params(device, queue);
-
-        // Re-initialize particles on GPU with new random seed
         self.initialize_particles_gpu(device, queue)?;
 
         // Ensure GPU operations complete
@@ -3988,33 +3437,8 @@ impl ParticleLifeModel {
             return Ok(());
         }
 
-        // Update state
-        self.state.particle_count = new_count as usize;
-
-        // Check buffer size limits
-        let max_storage_buffer_size = device.limits().max_storage_buffer_binding_size as u64;
-        let new_particle_buffer_size =
-            (new_count as usize * std::mem::size_of::<Particle>()) as u64;
-
-        if new_particle_buffer_size > max_storage_buffer_size {
-            return Err(SimulationError::BufferTooLarge {
-                requested: new_particle_buffer_size,
-                max_available: max_storage_buffer_size,
-            });
-        }
-
-        // Create new particle buffer with new size
-        let new_particle_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Particle Buffer"),
-            size: new_particle_buffer_size,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::VERTEX
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        // Replace the buffer
-        self.particle_buffer = new_particle_buffer;
+        // Update state, with fewer particles than asked if the buffer wouldn't fit
+        self.state.particle_count = self.particle_buffer.reallocate(device, new_count as usize);
 
         // Recreate bind groups with new buffer
         self.recreate_bind_groups(device)?;
@@ -4042,9 +3466,10 @@ impl ParticleLifeModel {
             &self.compute_pipeline.get_bind_group_layout(0),
             "Particle Life Compute Bind Group",
             &[
-                &self.particle_buffer,
+                self.particle_buffer.buffer(),
                 &self.sim_params_buffer,
                 &self.force_matrix_buffer,
+                &self.global_force_buffer,
             ],
         );
 
@@ -4054,7 +3479,7 @@ impl ParticleLifeModel {
             device,
             &self.render_particles_bind_group_layout,
             "Particle Life Render Bind Group",
-            &[&self.particle_buffer, &self.sim_params_buffer],
+            &[self.particle_buffer.buffer(), &self.sim_params_buffer],
         );
 
         tracing::info!("Recreating init bind group");
@@ -4063,7 +3488,7 @@ impl ParticleLifeModel {
             device,
             &self.init_bind_group_layout,
             "Particle Life Init Bind Group",
-            &[&self.particle_buffer, &self.init_params_buffer],
+            &[self.particle_buffer.buffer(), &self.init_params_buffer],
         );
 
         tracing::info!("All bind groups recreated successfully");
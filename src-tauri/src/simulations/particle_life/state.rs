@@ -1,5 +1,7 @@
 use super::settings::{MatrixGenerator, TrailMapFiltering, TypeGenerator};
-use crate::simulations::shared::{BackgroundColorMode, PositionGenerator};
+use crate::simulations::shared::{
+    BackgroundColorMode, CursorForceField, FadeCurve, GlobalForce, PositionGenerator,
+};
 use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
@@ -20,8 +22,18 @@ pub struct State {
     pub dt: f32,
     pub cursor_size: f32,
     pub cursor_strength: f32,
+    /// Shape of the force the cursor applies
+    pub cursor_force: CursorForceField,
+    /// Wind and gravity applied to every particle
+    pub global_force: GlobalForce,
     pub traces_enabled: bool,
     pub trace_fade: f32,
+    /// How trace_fade takes trails away frame by frame
+    #[serde(default)]
+    pub trace_fade_curve: FadeCurve,
+    /// Trail texture resolution relative to the display
+    #[serde(default = "default_trace_resolution_scale")]
+    pub trace_resolution_scale: f32,
     pub edge_fade_strength: f32,
     pub position_generator: PositionGenerator,
     pub type_generator: TypeGenerator,
@@ -76,8 +88,12 @@ impl State {
             dt: 0.016,
             cursor_size: 0.5,
             cursor_strength: 5.0,
+            cursor_force: CursorForceField::default(),
+            global_force: GlobalForce::default(),
             traces_enabled: false,
             trace_fade: 0.48,
+            trace_fade_curve: FadeCurve::Linear,
+            trace_resolution_scale: 1.0,
             edge_fade_strength: 1.0,
             position_generator: PositionGenerator::Random,
             type_generator: TypeGenerator::Random,
@@ -102,8 +118,12 @@ impl Default for State {
             dt: 0.016,
             cursor_size: 0.1,
             cursor_strength: 1.0,
+            cursor_force: CursorForceField::default(),
+            global_force: GlobalForce::default(),
             traces_enabled: true,
             trace_fade: 0.95,
+            trace_fade_curve: FadeCurve::Linear,
+            trace_resolution_scale: 1.0,
             edge_fade_strength: 0.1,
             position_generator: PositionGenerator::Random,
             type_generator: TypeGenerator::Random,
@@ -117,3 +137,7 @@ impl Default for State {
         }
     }
 }
+
+fn default_trace_resolution_scale() -> f32 {
+    1.0
+}
@@ -5,15 +5,20 @@
 //! both the computational correctness and the integration between different
 //! components of the simulation system.
 
+use super::reference;
 use super::shaders::{
-    BACKGROUND_RENDER_SHADER, COMPUTE_SHADER, FADE_FRAGMENT_SHADER, FADE_VERTEX_SHADER,
-    FORCE_RANDOMIZE_SHADER, FORCE_UPDATE_SHADER, FRAGMENT_SHADER, INIT_SHADER, VERTEX_SHADER,
+    BACKGROUND_RENDER_SHADER, COMPUTE_SHADER, FORCE_RANDOMIZE_SHADER, FORCE_UPDATE_SHADER,
+    FRAGMENT_SHADER, INIT_SHADER, VERTEX_SHADER,
 };
 use super::simulation::{
-    BackgroundParams, FadeUniforms, ForceRandomizeParams, ForceUpdateParams, InitParams, SimParams,
+    BackgroundParams, ForceRandomizeParams, ForceUpdateParams, InitParams, SimParams,
 };
 use super::state::Particle;
 use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::kernel_test::{KernelHarness, assert_close};
+use crate::simulations::shared::{GlobalForceUniform, RewindResource};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::mem;
 use wgpu::util::DeviceExt;
 
@@ -120,28 +125,6 @@ impl ParticleLifeValidator {
         Ok(())
     }
 
-    /// Validates that the Particle Life fade vertex shader compiles without errors
-    fn validate_fade_vertex_shader_compilation(&self) -> Result<(), String> {
-        let _ = self
-            .device
-            .create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: Some("Particle Life Fade Vertex Shader"),
-                source: wgpu::ShaderSource::Wgsl(FADE_VERTEX_SHADER.into()),
-            });
-        Ok(())
-    }
-
-    /// Validates that the Particle Life fade fragment shader compiles without errors
-    fn validate_fade_fragment_shader_compilation(&self) -> Result<(), String> {
-        let _ = self
-            .device
-            .create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: Some("Particle Life Fade Fragment Shader"),
-                source: wgpu::ShaderSource::Wgsl(FADE_FRAGMENT_SHADER.into()),
-            });
-        Ok(())
-    }
-
     /// Validates that the Particle Life background render shader compiles without errors
     fn validate_background_render_shader_compilation(&self) -> Result<(), String> {
         let _ = self
@@ -185,7 +168,11 @@ impl ParticleLifeValidator {
             brownian_motion: 0.1,
             particle_size: 0.0001,
             aspect_ratio: 1.0,
-            _pad1: 0,
+            cursor_mode: 0,
+            cursor_curve: 0,
+            cursor_direction_x: 0.0,
+            cursor_direction_y: 0.0,
+            cursor_time: 0.0,
         };
 
         // Create buffers
@@ -240,7 +227,11 @@ impl ParticleLifeValidator {
             brownian_motion: 0.1,
             particle_size: 0.01,
             aspect_ratio: 1.0,
-            _pad1: 0,
+            cursor_mode: 0,
+            cursor_curve: 0,
+            cursor_direction_x: 0.0,
+            cursor_direction_y: 0.0,
+            cursor_time: 0.0,
         };
 
         // Create buffers
@@ -510,7 +501,6 @@ impl ParticleLifeValidator {
             "  BackgroundParams: {} bytes",
             mem::size_of::<BackgroundParams>()
         );
-        println!("  FadeUniforms: {} bytes", mem::size_of::<FadeUniforms>());
         println!("  InitParams: {} bytes", mem::size_of::<InitParams>());
         println!(
             "  ForceUpdateParams: {} bytes",
@@ -547,12 +537,6 @@ async fn test_particle_life_shader_compilation() {
     validator
         .validate_fragment_shader_compilation()
         .expect("Fragment shader compilation failed");
-    validator
-        .validate_fade_vertex_shader_compilation()
-        .expect("Fade vertex shader compilation failed");
-    validator
-        .validate_fade_fragment_shader_compilation()
-        .expect("Fade fragment shader compilation failed");
     validator
         .validate_background_render_shader_compilation()
         .expect("Background render shader compilation failed");
@@ -614,7 +598,6 @@ fn test_struct_layout_consistency() {
         let particle_size = mem::size_of::<Particle>();
         let sim_params_size = mem::size_of::<SimParams>();
         let background_params_size = mem::size_of::<BackgroundParams>();
-        let fade_uniforms_size = mem::size_of::<FadeUniforms>();
         let init_params_size = mem::size_of::<InitParams>();
         let force_update_params_size = mem::size_of::<ForceUpdateParams>();
         let force_randomize_params_size = mem::size_of::<ForceRandomizeParams>();
@@ -649,20 +632,17 @@ fn test_struct_layout_consistency() {
             brownian_motion: 0.1,
             particle_size: 0.01,
             aspect_ratio: 1.0,
-            _pad1: 0,
+            cursor_mode: 0,
+            cursor_curve: 0,
+            cursor_direction_x: 0.0,
+            cursor_direction_y: 0.0,
+            cursor_time: 0.0,
         };
 
         let dummy_background_params = BackgroundParams {
             background_color: [0.0, 0.0, 0.0, 1.0], // RGBA black
         };
 
-        let dummy_fade_uniforms = FadeUniforms {
-            fade_amount: 0.01,
-            _pad1: 0.0,
-            _pad2: 0.0,
-            _pad3: 0.0,
-        };
-
         let dummy_init_params = InitParams {
             start_index: 0,
             spawn_count: 100,
@@ -728,15 +708,6 @@ fn test_struct_layout_consistency() {
                     usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
                 });
 
-        let fade_uniforms_buffer =
-            validator
-                .device
-                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: Some("Particle Life Fade Uniforms Buffer"),
-                    contents: bytemuck::cast_slice(&[dummy_fade_uniforms]),
-                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                });
-
         let init_params_buffer =
             validator
                 .device
@@ -783,7 +754,6 @@ fn test_struct_layout_consistency() {
             background_params_buffer.size() as usize,
             background_params_size
         );
-        assert_eq!(fade_uniforms_buffer.size() as usize, fade_uniforms_size);
         assert_eq!(init_params_buffer.size() as usize, init_params_size);
         assert_eq!(
             force_update_params_buffer.size() as usize,
@@ -809,10 +779,6 @@ fn test_struct_layout_consistency() {
             "  BackgroundParams buffer size: {} bytes",
             background_params_buffer.size()
         );
-        println!(
-            "  FadeUniforms buffer size: {} bytes",
-            fade_uniforms_buffer.size()
-        );
         println!(
             "  InitParams buffer size: {} bytes",
             init_params_buffer.size()
@@ -861,3 +827,162 @@ fn test_sim_params_size_and_alignment() {
     assert_eq!(mem::size_of::<SimParams>(), 80);
     assert_eq!(sim_params_bytes.len(), 80);
 }
+
+fn reference_params(particle_count: u32, wrap_edges: bool) -> SimParams {
+    SimParams {
+        particle_count,
+        species_count: 3,
+        max_force: 0.5,
+        max_distance: 0.05,
+        friction: 0.5,
+        wrap_edges: wrap_edges as u32,
+        width: 800.0,
+        height: 600.0,
+        random_seed: 0,
+        dt: 0.001,
+        beta: 0.3,
+        cursor_x: 0.0,
+        cursor_y: 0.0,
+        cursor_size: 0.1,
+        cursor_strength: 0.0,
+        cursor_active: 0,
+        brownian_motion: 0.0,
+        particle_size: 0.01,
+        aspect_ratio: 1.0,
+        cursor_mode: 0,
+        cursor_curve: 0,
+        cursor_direction_x: 0.0,
+        cursor_direction_y: 0.0,
+        cursor_time: 0.0,
+    }
+}
+
+/// Particles at rest within a few interaction distances of `center`
+fn clustered_particles(count: u32, center: [f32; 2], seed: u64) -> Vec<Particle> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|i| Particle {
+            position: [
+                center[0] + rng.random_range(-0.08..0.08),
+                center[1] + rng.random_range(-0.08..0.08),
+            ],
+            velocity: [0.0, 0.0],
+            species: i % 3,
+            _pad: 0,
+        })
+        .collect()
+}
+
+fn force_matrix(seed: u64) -> Vec<f32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..9).map(|_| rng.random_range(-1.0..1.0)).collect()
+}
+
+/// One step of `compute.wgsl` from `particles`
+async fn gpu_particle_step(
+    params: &SimParams,
+    particles: &[Particle],
+    force_matrix: &[f32],
+) -> Vec<Particle> {
+    let harness = KernelHarness::new().await;
+    let pipeline = harness.pipeline(COMPUTE_SHADER, "main");
+    let particle_buffer =
+        harness.buffer(bytemuck::cast_slice(particles), wgpu::BufferUsages::STORAGE);
+    let params_buffer = harness.buffer(bytemuck::bytes_of(params), wgpu::BufferUsages::UNIFORM);
+    let matrix_buffer = harness.buffer(
+        bytemuck::cast_slice(force_matrix),
+        wgpu::BufferUsages::STORAGE,
+    );
+    let global_force = harness.buffer(
+        bytemuck::bytes_of(&GlobalForceUniform::default()),
+        wgpu::BufferUsages::UNIFORM,
+    );
+    harness.dispatch(
+        &pipeline,
+        &[
+            resource_helpers::buffer_entry(0, &particle_buffer),
+            resource_helpers::buffer_entry(1, &params_buffer),
+            resource_helpers::buffer_entry(2, &matrix_buffer),
+            resource_helpers::buffer_entry(3, &global_force),
+        ],
+        (params.particle_count.div_ceil(64), 1, 1),
+    );
+    harness
+        .read(RewindResource::Buffer(&particle_buffer))
+        .chunks_exact(mem::size_of::<Particle>())
+        .map(bytemuck::pod_read_unaligned)
+        .collect()
+}
+
+/// Run one step on both paths and compare positions and velocities
+async fn assert_particle_step_matches(
+    params: SimParams,
+    particles: Vec<Particle>,
+    force_matrix: Vec<f32>,
+) -> Vec<Particle> {
+    let cpu = reference::step(&particles, &params, &force_matrix);
+    let gpu = gpu_particle_step(&params, &particles, &force_matrix).await;
+    let positions = |particles: &[Particle]| -> Vec<f32> {
+        particles.iter().flat_map(|p| p.position).collect()
+    };
+    let velocities = |particles: &[Particle]| -> Vec<f32> {
+        particles.iter().flat_map(|p| p.velocity).collect()
+    };
+    assert_close("Positions", &positions(&gpu), &positions(&cpu), 1e-6);
+    assert_close("Velocities", &velocities(&gpu), &velocities(&cpu), 1e-5);
+    cpu
+}
+
+#[tokio::test]
+async fn particle_step_matches_the_reference() {
+    let params = reference_params(32, false);
+    let particles = clustered_particles(32, [0.2, -0.1], 7);
+    let moved = assert_particle_step_matches(params, particles, force_matrix(11)).await;
+    // Close enough for most particles to feel their neighbors
+    let pulled = moved.iter().filter(|p| p.velocity != [0.0, 0.0]).count();
+    assert!(pulled > 16, "Only {} particles were pulled on", pulled);
+}
+
+#[tokio::test]
+async fn particle_step_matches_the_reference_across_wrapping_edges() {
+    let params = reference_params(32, true);
+    // Straddling the corner, so neighbors are found across both edges
+    let particles = clustered_particles(32, [1.0, -1.0], 13)
+        .into_iter()
+        .map(|p| Particle {
+            position: p.position.map(|c| (c + 1.0).rem_euclid(2.0) - 1.0),
+            ..p
+        })
+        .collect();
+    assert_particle_step_matches(params, particles, force_matrix(17)).await;
+}
+
+#[tokio::test]
+async fn particle_step_matches_the_reference_bouncing_off_edges() {
+    let params = reference_params(4, false);
+    // Far enough apart not to feel each other, each about to leave
+    let particles: Vec<Particle> = [
+        ([0.9995, 0.5], [1.0, 0.0]),
+        ([-0.9995, -0.5], [-1.0, 0.2]),
+        ([0.5, 0.9995], [0.0, 1.0]),
+        ([-0.5, -0.9995], [0.3, -1.0]),
+    ]
+    .iter()
+    .map(|&(position, velocity)| Particle {
+        position,
+        velocity,
+        species: 0,
+        _pad: 0,
+    })
+    .collect();
+    let moved = assert_particle_step_matches(params, particles.clone(), force_matrix(19)).await;
+    for (before, after) in particles.iter().zip(&moved) {
+        assert!(
+            before
+                .velocity
+                .iter()
+                .zip(after.velocity)
+                .any(|(b, a)| b * a < 0.0)
+        );
+    }
+}
@@ -17,6 +17,10 @@
 //! of the simulation, from basic particle properties to advanced physics
 //! behaviors and visual presentation.
 
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Constraint, Rule, SettingValidator};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -131,6 +135,10 @@ pub struct Settings {
     /// Strength of overlap resolution (0.0 = no separation, 1.0 = maximum separation)
     /// Controls how aggressively overlapping particles are separated
     pub overlap_resolution_strength: f32,
+
+    /// Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
 }
 
 impl Default for Settings {
@@ -150,10 +158,13 @@ impl Default for Settings {
             foreground_color_mode: ForegroundColorMode::Density,
             density_damping_enabled: false,
             overlap_resolution_strength: 0.02,
+            background_layer: BackgroundLayer::default(),
         }
     }
 }
 
+impl PresetSchema for Settings {}
+
 impl Settings {
     /// Randomize all settings within reasonable bounds
     pub fn randomize(&mut self) {
@@ -175,3 +186,44 @@ impl Settings {
         self.overlap_resolution_strength = rng.random_range(0.01..0.2); // Conservative range, max 20%
     }
 }
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("particle_count", Rule::Count { min: 1, max: 50000 }),
+        (
+            "particle_size",
+            Rule::Range {
+                min: 0.0005,
+                max: 1.0,
+            },
+        ),
+        ("density_damping_enabled", Rule::Flag),
+        (
+            "overlap_resolution_strength",
+            Rule::Range { min: 0.0, max: 1.0 },
+        ),
+        (
+            "cursor_size",
+            Rule::Range {
+                min: 0.05,
+                max: 1.0,
+            },
+        ),
+        ("cursor_strength", Rule::Range { min: 0.0, max: 1.0 }),
+    ],
+    &[Constraint::Ordered {
+        lower: "initial_velocity_min",
+        upper: "initial_velocity_max",
+    }],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("particle_count", SettingCategory::Generators),
+    ("initial_velocity_max", SettingCategory::Generators),
+    ("initial_velocity_min", SettingCategory::Generators),
+    ("random_seed", SettingCategory::Generators),
+    ("background_color_mode", SettingCategory::Colors),
+    ("foreground_color_mode", SettingCategory::Colors),
+]);
@@ -39,6 +39,10 @@ struct PhysicsParams {
     density_damping_enabled: u32,
     overlap_resolution_strength: f32,
     frame_index: u32,
+    cursor_mode: u32,  // CURSOR_MODE_* from cursor_force.wgsl
+    cursor_curve: u32,  // CURSOR_CURVE_* of the current mode
+    cursor_time: f32,  // Animates turbulence
+    _pad0: u32,
 }
 
 struct GridParams {
@@ -63,6 +67,7 @@ struct GridCell {
 @group(0) @binding(3) var<uniform> grid_params: GridParams;
 // Atomic per-cell particle counts for deterministic neighbor iteration
 @group(0) @binding(4) var<storage, read> grid_counts: array<atomic<u32>>;
+@group(0) @binding(5) var<uniform> global_force: GlobalForce;
 
 // Convert world position to grid coordinates
 fn world_to_grid(pos: vec2<f32>) -> vec2<u32> {
@@ -94,8 +99,8 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
     var particle = particles[index];
     
-    // Check if mouse is pressed and in attraction mode
-    if (params.mouse_pressed != 0u && params.mouse_mode == 1u) {
+    // Check if mouse is pressed and in attraction mode, the other cursor modes push instead of grabbing
+    if (params.mouse_pressed != 0u && params.mouse_mode == 1u && params.cursor_mode == CURSOR_MODE_RADIAL) {
         let delta = params.mouse_position - particle.position;
         let aspect_corrected_delta = vec2<f32>(delta.x * params.aspect_ratio, delta.y);
         let distance_sq = dot(aspect_corrected_delta, aspect_corrected_delta);
@@ -234,6 +239,9 @@ fn compute_acceleration(particle: Particle, particle_index: u32) -> vec2<f32> {
     if (params.mouse_pressed != 0u) {
         acceleration += compute_mouse_force(particle);
     }
+
+    // Wind and point gravity
+    acceleration += global_force_at(global_force, particle.position);
     
     // Collision forces using spatial grid
     acceleration += compute_collision_forces_grid(particle, particle_index);
@@ -292,7 +300,20 @@ fn compute_mouse_force(particle: Particle) -> vec2<f32> {
         return vec2<f32>(0.0, 0.0);
     }
     
-    let force_strength = params.cursor_strength * (1.0 - distance / params.cursor_size);
+    let force_strength = params.cursor_strength * cursor_falloff(distance / params.cursor_size, params.cursor_curve);
+    if (params.cursor_mode != CURSOR_MODE_RADIAL && params.mouse_mode != 0u) {
+        let flip = select(1.0, -1.0, params.mouse_mode == 2u);
+        return cursor_shape_force(
+            params.cursor_mode,
+            delta,
+            particle.position,
+            params.mouse_velocity,
+            params.cursor_size,
+            params.cursor_time,
+            flip,
+        ) * force_strength;
+    }
+
     let force_direction = normalize(delta);
     
     if (params.mouse_mode == 1u) {
@@ -23,8 +23,9 @@ use crate::error::{SimulationError, SimulationResult};
 use crate::simulations::pellets::settings::{BackgroundColorMode, ForegroundColorMode};
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use crate::simulations::shared::{
-    AverageColorResources, BindGroupBuilder, ColorSchemeManager, ComputePipelineBuilder,
-    RenderPipelineBuilder, camera::Camera,
+    AverageColorResources, BackgroundLayer, BindGroupBuilder, ColorSchemeManager,
+    ComputePipelineBuilder, GraphNode, HealthCheck, HealthIssue, HealthProbe, NodeKind,
+    ParticleBuffer, RenderGraph, RenderPipelineBuilder, RewindResource, Trails, camera::Camera,
 };
 use bytemuck::{Pod, Zeroable};
 use serde_json::Value;
@@ -74,6 +75,10 @@ pub struct PhysicsParams {
     pub density_damping_enabled: u32, // Whether to apply density-based velocity damping
     pub overlap_resolution_strength: f32, // Controls how aggressively overlapping particles are separated
     pub frame_index: u32,
+    pub cursor_mode: u32,  // See `CursorMode::gpu_id`
+    pub cursor_curve: u32, // See `StrengthCurve::gpu_id`
+    pub cursor_time: f32,  // Animates turbulence
+    pub _pad0: u32,
 }
 
 #[repr(C)]
@@ -129,12 +134,43 @@ pub struct GridCell {
     pub particle_indices: [u32; 64], // Max 64 particles per cell
 }
 
+/// The passes of a Pellets frame, in the order its render graph runs them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PelletsNode {
+    Compute,
+    Background,
+    Trails,
+    TrailBlit,
+    Particles,
+    PostEffect,
+    AverageColor,
+    InfiniteRender,
+    Blur,
+}
+
+impl GraphNode for PelletsNode {
+    fn name(self) -> &'static str {
+        match self {
+            PelletsNode::Compute => "compute",
+            PelletsNode::Background => "background",
+            PelletsNode::Trails => "trails",
+            PelletsNode::TrailBlit => "trail_blit",
+            PelletsNode::Particles => "particles",
+            PelletsNode::PostEffect => "post_effect",
+            PelletsNode::AverageColor => "average_color",
+            PelletsNode::InfiniteRender => "infinite_render",
+            PelletsNode::Blur => "blur",
+        }
+    }
+}
+
 // GPU-based physics implementation - no Rapier needed
 
 pub struct PelletsModel {
     // GPU resources
-    pub particle_buffer: wgpu::Buffer,
+    pub particle_buffer: ParticleBuffer<Particle>,
     pub physics_params_buffer: wgpu::Buffer,
+    pub global_force_buffer: wgpu::Buffer,
     pub density_params_buffer: wgpu::Buffer,
     pub render_params_buffer: wgpu::Buffer,
     pub background_params_buffer: wgpu::Buffer,
@@ -174,23 +210,11 @@ pub struct PelletsModel {
     pub density_texture: wgpu::Texture,
     pub density_view: wgpu::TextureView,
 
-    // Trail resources (optional persistent trails)
-    pub trail_texture_a: wgpu::Texture,
-    pub trail_texture_view_a: wgpu::TextureView,
-    pub trail_texture_b: wgpu::Texture,
-    pub trail_texture_view_b: wgpu::TextureView,
-    pub current_trail_is_a: bool,
-    pub trail_sampler: wgpu::Sampler,
-
-    // Trail fade pipeline + uniforms
-    pub trail_fade_pipeline: wgpu::RenderPipeline,
-    pub trail_fade_bind_group_layout: wgpu::BindGroupLayout,
-    pub trail_fade_bind_group: wgpu::BindGroup,
-    pub trail_fade_uniforms_buffer: wgpu::Buffer,
+    // Optional persistent trails the particles are drawn into
+    pub trails: Trails,
 
-    // Trail blit pipeline
-    pub trail_blit_pipeline: wgpu::RenderPipeline,
-    pub trail_blit_bind_group: wgpu::BindGroup,
+    // The passes making up a frame
+    pub render_graph: RenderGraph<PelletsNode>,
 
     // Offscreen render pipelines
     pub background_render_pipeline: wgpu::RenderPipeline,
@@ -245,14 +269,19 @@ impl PelletsModel {
         color_scheme_manager: &ColorSchemeManager,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         // Initialize particles
-        let particles = Self::initialize_particles(settings.particle_count, &settings);
+        let particles = Self::initialize_particles(
+            settings.particle_count,
+            &settings,
+            settings.random_seed as u64,
+        );
 
         // Create buffers
-        let particle_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Pellets Particle Buffer"),
-            contents: bytemuck::cast_slice(&particles),
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-        });
+        let particle_buffer = ParticleBuffer::new(
+            device,
+            "Pellets Particle Buffer",
+            wgpu::BufferUsages::empty(),
+            &particles,
+        );
 
         let camera = Camera::new(
             device,
@@ -353,6 +382,10 @@ impl PelletsModel {
             density_damping_enabled: settings.density_damping_enabled as u32,
             overlap_resolution_strength: settings.overlap_resolution_strength,
             frame_index: 0,
+            cursor_mode: state.cursor_force.mode.gpu_id(),
+            cursor_curve: state.cursor_force.curve().gpu_id(),
+            cursor_time: 0.0,
+            _pad0: 0,
         };
 
         let physics_params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -361,6 +394,12 @@ impl PelletsModel {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
+        let global_force_buffer = resource_helpers::create_uniform_buffer_with_data(
+            device,
+            "Pellets Global Force Buffer",
+            &[state.global_force.uniform()],
+        );
+
         // Create density params buffer
         let density_params = DensityParams {
             particle_count: settings.particle_count,
@@ -439,6 +478,7 @@ impl PelletsModel {
                     resource_helpers::storage_buffer_entry(2, wgpu::ShaderStages::COMPUTE, true),
                     resource_helpers::uniform_buffer_entry(3, wgpu::ShaderStages::COMPUTE),
                     resource_helpers::storage_buffer_entry(4, wgpu::ShaderStages::COMPUTE, true),
+                    resource_helpers::uniform_buffer_entry(5, wgpu::ShaderStages::COMPUTE),
                 ],
             });
 
@@ -466,13 +506,13 @@ impl PelletsModel {
             .build();
 
         let density_bind_group = BindGroupBuilder::new(device, &density_bind_group_layout)
-            .add_buffer(0, &particle_buffer)
+            .add_buffer(0, particle_buffer.buffer())
             .add_buffer(1, &density_params_buffer)
             .with_label("Pellets Density Bind Group".to_string())
             .build();
 
         let render_bind_group = BindGroupBuilder::new(device, &render_bind_group_layout)
-            .add_buffer(0, &particle_buffer)
+            .add_buffer(0, particle_buffer.buffer())
             .add_buffer(1, &render_params_buffer)
             .add_buffer(2, &lut_buffer)
             .with_label("Pellets Render Bind Group".to_string())
@@ -628,7 +668,7 @@ impl PelletsModel {
             &grid_populate_bind_group_layout,
             "Pellets Grid Populate Bind Group",
             &[
-                &particle_buffer,
+                particle_buffer.buffer(),
                 &grid_buffer,
                 &grid_params_buffer,
                 &grid_counts_buffer,
@@ -673,11 +713,12 @@ impl PelletsModel {
             label: Some("Pellets Physics Bind Group"),
             layout: &physics_bind_group_layout,
             entries: &[
-                resource_helpers::buffer_entry(0, &particle_buffer),
+                resource_helpers::buffer_entry(0, particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &physics_params_buffer),
                 resource_helpers::buffer_entry(2, &grid_buffer),
                 resource_helpers::buffer_entry(3, &grid_params_buffer),
                 resource_helpers::buffer_entry(4, &grid_counts_buffer),
+                resource_helpers::buffer_entry(5, &global_force_buffer),
             ],
         });
 
@@ -896,7 +937,7 @@ impl PelletsModel {
             label: Some("Pellets Particle Render Bind Group"),
             layout: &particle_render_pipeline.get_bind_group_layout(0),
             entries: &[
-                resource_helpers::buffer_entry(0, &particle_buffer),
+                resource_helpers::buffer_entry(0, particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &render_params_buffer),
                 resource_helpers::buffer_entry(2, &lut_buffer),
             ],
@@ -1040,188 +1081,16 @@ impl PelletsModel {
                 cache: None,
             });
 
-        // Trail resources
-        let trail_texture_a = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Pellets Trail Texture A"),
-            size: wgpu::Extent3d {
-                width: surface_config.width * 2,
-                height: surface_config.height * 2,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-        let trail_texture_view_a =
-            trail_texture_a.create_view(&wgpu::TextureViewDescriptor::default());
-        let trail_texture_b = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Pellets Trail Texture B"),
-            size: wgpu::Extent3d {
-                width: surface_config.width * 2,
-                height: surface_config.height * 2,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-        let trail_texture_view_b =
-            trail_texture_b.create_view(&wgpu::TextureViewDescriptor::default());
-
-        let trail_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Pellets Trail Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-
-        // Trail fade uniforms buffer
-        #[repr(C)]
-        #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
-        struct TrailFadeUniforms {
-            fade_amount: f32,
-            _pad1: f32,
-            _pad2: f32,
-            _pad3: f32,
-        }
-        let trail_fade_uniforms_buffer =
-            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Pellets Trail Fade Uniforms"),
-                contents: bytemuck::cast_slice(&[TrailFadeUniforms {
-                    fade_amount: 0.01,
-                    _pad1: 0.0,
-                    _pad2: 0.0,
-                    _pad3: 0.0,
-                }]),
-                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            });
-
-        // Trail fade pipeline
-        let trail_fade_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Pellets Trail Fade BGL"),
-                entries: &[
-                    resource_helpers::uniform_buffer_entry(0, wgpu::ShaderStages::FRAGMENT),
-                    resource_helpers::texture_entry(
-                        1,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::TextureSampleType::Float { filterable: true },
-                        wgpu::TextureViewDimension::D2,
-                    ),
-                    resource_helpers::sampler_entry(
-                        2,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::SamplerBindingType::Filtering,
-                    ),
-                ],
-            });
-        let trail_fade_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Pellets Trail Fade Pipeline"),
-            layout: Some(
-                &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                    label: Some("Pellets Trail Fade Pipeline Layout"),
-                    bind_group_layouts: &[&trail_fade_bind_group_layout],
-                    push_constant_ranges: &[],
-                }),
-            ),
-            vertex: wgpu::VertexState {
-                module: &device.create_shader_module(wgpu::ShaderModuleDescriptor {
-                    label: Some("Pellets Trail Fade VS"),
-                    source: wgpu::ShaderSource::Wgsl(
-                        super::shaders::TRAIL_FADE_VERTEX_SHADER.into(),
-                    ),
-                }),
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &device.create_shader_module(wgpu::ShaderModuleDescriptor {
-                    label: Some("Pellets Trail Fade FS"),
-                    source: wgpu::ShaderSource::Wgsl(
-                        super::shaders::TRAIL_FADE_FRAGMENT_SHADER.into(),
-                    ),
-                }),
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Rgba8Unorm,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        // Trail resources, finer than the display so trails stay smooth
+        let trails = Trails::new(
+            device,
+            "Pellets Trails",
+            (surface_config.width, surface_config.height),
+            state.trail_resolution_scale,
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::FilterMode::Linear,
+        );
 
-        // Trail blit pipeline
-        let trail_blit_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Pellets Trail Blit Pipeline"),
-            layout: Some(
-                &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                    label: Some("Pellets Trail Blit Pipeline Layout"),
-                    bind_group_layouts: &[&device.create_bind_group_layout(
-                        &wgpu::BindGroupLayoutDescriptor {
-                            label: Some("Pellets Trail Blit BGL"),
-                            entries: &[
-                                resource_helpers::texture_entry(
-                                    0,
-                                    wgpu::ShaderStages::FRAGMENT,
-                                    wgpu::TextureSampleType::Float { filterable: true },
-                                    wgpu::TextureViewDimension::D2,
-                                ),
-                                resource_helpers::sampler_entry(
-                                    1,
-                                    wgpu::ShaderStages::FRAGMENT,
-                                    wgpu::SamplerBindingType::Filtering,
-                                ),
-                            ],
-                        },
-                    )],
-                    push_constant_ranges: &[],
-                }),
-            ),
-            vertex: wgpu::VertexState {
-                module: &device.create_shader_module(wgpu::ShaderModuleDescriptor {
-                    label: Some("Pellets Trail Blit Shader"),
-                    source: wgpu::ShaderSource::Wgsl(super::shaders::TRAIL_BLIT_SHADER.into()),
-                }),
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &device.create_shader_module(wgpu::ShaderModuleDescriptor {
-                    label: Some("Pellets Trail Blit Shader"),
-                    source: wgpu::ShaderSource::Wgsl(super::shaders::TRAIL_BLIT_SHADER.into()),
-                }),
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Rgba8Unorm,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
         let average_color_uniform_buffer =
             device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("Pellets Average Color Uniform Buffer"),
@@ -1247,49 +1116,10 @@ impl PelletsModel {
         let post_processing_state = PostProcessingState::default();
         let post_processing_resources = PostProcessingResources::new(device, surface_config)?;
 
-        // Create initial trail bind groups BEFORE moving resources into struct
-        let _initial_trail_fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Pellets Trail Fade Bind Group"),
-            layout: &trail_fade_bind_group_layout,
-            entries: &[
-                resource_helpers::buffer_entry(0, &trail_fade_uniforms_buffer),
-                resource_helpers::texture_view_entry(1, &trail_texture_view_b),
-                resource_helpers::sampler_bind_entry(2, &trail_sampler),
-            ],
-        });
-        let _initial_trail_blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Pellets Trail Blit Bind Group"),
-            layout: &trail_blit_pipeline.get_bind_group_layout(0),
-            entries: &[
-                resource_helpers::texture_view_entry(0, &trail_texture_view_a),
-                resource_helpers::sampler_bind_entry(1, &trail_sampler),
-            ],
-        });
-
-        // Placeholders to satisfy struct init; will be replaced below
-        let placeholder_trail_fade_bind_group =
-            device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Pellets Trail Fade Bind Group (placeholder)"),
-                layout: &trail_fade_bind_group_layout,
-                entries: &[
-                    resource_helpers::buffer_entry(0, &trail_fade_uniforms_buffer),
-                    resource_helpers::texture_view_entry(1, &display_view),
-                    resource_helpers::sampler_bind_entry(2, &display_sampler),
-                ],
-            });
-        let placeholder_trail_blit_bind_group =
-            device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Pellets Trail Blit Bind Group (placeholder)"),
-                layout: &trail_blit_pipeline.get_bind_group_layout(0),
-                entries: &[
-                    resource_helpers::texture_view_entry(0, &display_view),
-                    resource_helpers::sampler_bind_entry(1, &display_sampler),
-                ],
-            });
-
         let mut result = PelletsModel {
             particle_buffer,
             physics_params_buffer,
+            global_force_buffer,
             density_params_buffer,
             render_params_buffer,
             background_params_buffer,
@@ -1320,18 +1150,8 @@ impl PelletsModel {
             post_effect_view,
             density_texture,
             density_view,
-            trail_texture_a,
-            trail_texture_view_a,
-            trail_texture_b,
-            trail_texture_view_b,
-            current_trail_is_a: true,
-            trail_sampler,
-            trail_fade_pipeline,
-            trail_fade_bind_group_layout,
-            trail_fade_bind_group: placeholder_trail_fade_bind_group,
-            trail_fade_uniforms_buffer,
-            trail_blit_pipeline,
-            trail_blit_bind_group: placeholder_trail_blit_bind_group,
+            trails,
+            render_graph: Self::create_render_graph(),
             background_render_pipeline,
             background_render_bind_group,
             particle_render_pipeline,
@@ -1360,76 +1180,8 @@ impl PelletsModel {
             post_processing_resources,
         };
 
-        // Now that textures/views are owned by the struct, create correct bind groups
-        result.trail_fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Pellets Trail Fade Bind Group"),
-            layout: &result.trail_fade_bind_group_layout,
-            entries: &[
-                resource_helpers::buffer_entry(0, &result.trail_fade_uniforms_buffer),
-                resource_helpers::texture_view_entry(1, &result.trail_texture_view_b),
-                resource_helpers::sampler_bind_entry(2, &result.trail_sampler),
-            ],
-        });
-        result.trail_blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Pellets Trail Blit Bind Group"),
-            layout: &result.trail_blit_pipeline.get_bind_group_layout(0),
-            entries: &[
-                resource_helpers::texture_view_entry(0, &result.trail_texture_view_a),
-                resource_helpers::sampler_bind_entry(1, &result.trail_sampler),
-            ],
-        });
-
         // Initialize trail textures to transparent
-        {
-            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Pellets Trail Clear Encoder"),
-            });
-            {
-                let _pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Trail Clear A"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &result.trail_texture_view_a,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color {
-                                r: 0.0,
-                                g: 0.0,
-                                b: 0.0,
-                                a: 0.0,
-                            }),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-            }
-            {
-                let _pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Trail Clear B"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &result.trail_texture_view_b,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color {
-                                r: 0.0,
-                                g: 0.0,
-                                b: 0.0,
-                                a: 0.0,
-                            }),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-            }
-            queue.submit(std::iter::once(encoder.finish()));
-        }
+        result.trails.clear(device, queue, wgpu::Color::TRANSPARENT);
 
         // Initialize the background color from the LUT
         result.update_background_color(queue);
@@ -1437,9 +1189,15 @@ impl PelletsModel {
         Ok(result)
     }
 
-    fn initialize_particles(count: u32, settings: &Settings) -> Vec<Particle> {
-        use rand::Rng;
-        let mut rng = rand::rng();
+    /// `count` particles placed from `seed`, so the same seed always gives
+    /// the same start
+    pub(crate) fn initialize_particles(
+        count: u32,
+        settings: &Settings,
+        seed: u64,
+    ) -> Vec<Particle> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
         let mut particles = Vec::with_capacity(count as usize);
 
         if count == 1 {
@@ -1549,21 +1307,12 @@ impl PelletsModel {
         particles
     }
 
-    pub fn step_physics(
-        &mut self,
-        device: &Arc<Device>,
-        queue: &Arc<Queue>,
-    ) -> SimulationResult<()> {
+    pub fn step_physics(&mut self, queue: &Arc<Queue>, encoder: &mut wgpu::CommandEncoder) {
         self.frame_count += 1;
 
         // Update physics parameters
         self.update_physics_params(queue);
 
-        // Dispatch physics compute shader
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Pellets Physics Compute Encoder"),
-        });
-
         // Step 1: Clear the spatial grid
         {
             let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
@@ -1627,8 +1376,24 @@ impl PelletsModel {
             let num_workgroups = self.settings.particle_count.div_ceil(workgroup_size);
             compute_pass.dispatch_workgroups(num_workgroups, 1, 1);
         }
+    }
+
+    /// Place the particles again from the stored seed
+    fn place_particles(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.particles = Self::initialize_particles(
+            self.settings.particle_count,
+            &self.settings,
+            self.settings.random_seed as u64,
+        );
 
-        queue.submit(std::iter::once(encoder.finish()));
+        // Bind groups hold the buffer, so they follow it if it had to grow
+        if self.particle_buffer.upload(device, queue, &self.particles) {
+            self.recreate_bind_groups(device)?;
+        }
         Ok(())
     }
 
@@ -1642,6 +1407,13 @@ impl PelletsModel {
                 self.state.mouse_velocity[1] * decay_factor,
             ];
         }
+        self.state.cursor_force.advance(1.0 / 60.0);
+        self.state.global_force.advance(1.0 / 60.0);
+        queue.write_buffer(
+            &self.global_force_buffer,
+            0,
+            bytemuck::cast_slice(&[self.state.global_force.uniform()]),
+        );
 
         let physics_params = PhysicsParams {
             mouse_position: self.state.mouse_position,
@@ -1666,6 +1438,10 @@ impl PelletsModel {
             },
             overlap_resolution_strength: self.settings.overlap_resolution_strength,
             frame_index: self.frame_count as u32,
+            cursor_mode: self.state.cursor_force.mode.gpu_id(),
+            cursor_curve: self.state.cursor_force.curve().gpu_id(),
+            cursor_time: self.state.cursor_force.time(),
+            _pad0: 0,
         };
 
         queue.write_buffer(
@@ -1720,7 +1496,13 @@ impl PelletsModel {
             // Add particles
             let particles_to_add = new_count - current_count;
             tracing::debug!("Adding {} particles", particles_to_add);
-            let new_particles = Self::initialize_particles(particles_to_add, &self.settings);
+            // Offset from the seed so the new particles don't start where
+            // the first ones did
+            let new_particles = Self::initialize_particles(
+                particles_to_add,
+                &self.settings,
+                self.settings.random_seed as u64 + current_count as u64,
+            );
             self.particles.extend(new_particles);
         } else if new_count < current_count {
             // Remove particles
@@ -1732,25 +1514,9 @@ impl PelletsModel {
         // Update settings
         self.settings.particle_count = new_count;
 
-        // Check if we need to recreate the buffer (if it's too small)
-        let required_buffer_size = self.particles.len() * std::mem::size_of::<Particle>();
-        if self.particle_buffer.size() < required_buffer_size as u64 {
-            // Recreate the particle buffer with the new size
-            self.particle_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Pellets Particle Buffer"),
-                contents: bytemuck::cast_slice(&self.particles),
-                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            });
-
-            // Recreate the bind groups since the buffer changed
+        // Bind groups hold the buffer, so they follow it if it had to grow
+        if self.particle_buffer.upload(device, queue, &self.particles) {
             self.recreate_bind_groups(device)?;
-        } else {
-            // Update existing GPU buffer
-            queue.write_buffer(
-                &self.particle_buffer,
-                0,
-                bytemuck::cast_slice(&self.particles),
-            );
         }
 
         Ok(())
@@ -1767,6 +1533,7 @@ impl PelletsModel {
                     resource_helpers::storage_buffer_entry(2, wgpu::ShaderStages::COMPUTE, true),
                     resource_helpers::uniform_buffer_entry(3, wgpu::ShaderStages::COMPUTE),
                     resource_helpers::storage_buffer_entry(4, wgpu::ShaderStages::COMPUTE, true),
+                    resource_helpers::uniform_buffer_entry(5, wgpu::ShaderStages::COMPUTE),
                 ],
             });
 
@@ -1797,11 +1564,12 @@ impl PelletsModel {
             label: Some("Pellets Physics Bind Group"),
             layout: &physics_bind_group_layout,
             entries: &[
-                resource_helpers::buffer_entry(0, &self.particle_buffer),
+                resource_helpers::buffer_entry(0, self.particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &self.physics_params_buffer),
                 resource_helpers::buffer_entry(2, &self.grid_buffer),
                 resource_helpers::buffer_entry(3, &self.grid_params_buffer),
                 resource_helpers::buffer_entry(4, &self.grid_counts_buffer),
+                resource_helpers::buffer_entry(5, &self.global_force_buffer),
             ],
         });
 
@@ -1810,7 +1578,7 @@ impl PelletsModel {
             label: Some("Pellets Density Bind Group"),
             layout: &density_bind_group_layout,
             entries: &[
-                resource_helpers::buffer_entry(0, &self.particle_buffer),
+                resource_helpers::buffer_entry(0, self.particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &self.density_params_buffer),
             ],
         });
@@ -1820,7 +1588,7 @@ impl PelletsModel {
             label: Some("Pellets Grid Populate Bind Group"),
             layout: &grid_populate_bind_group_layout,
             entries: &[
-                resource_helpers::buffer_entry(0, &self.particle_buffer),
+                resource_helpers::buffer_entry(0, self.particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &self.grid_buffer),
                 resource_helpers::buffer_entry(2, &self.grid_params_buffer),
                 resource_helpers::buffer_entry(3, &self.grid_counts_buffer),
@@ -1849,7 +1617,7 @@ impl PelletsModel {
             label: Some("Pellets Render Bind Group"),
             layout: &render_bind_group_layout,
             entries: &[
-                resource_helpers::buffer_entry(0, &self.particle_buffer),
+                resource_helpers::buffer_entry(0, self.particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &self.render_params_buffer),
                 resource_helpers::buffer_entry(2, &self.lut_buffer),
             ],
@@ -1860,7 +1628,7 @@ impl PelletsModel {
             label: Some("Pellets Particle Render Bind Group"),
             layout: &self.particle_render_pipeline.get_bind_group_layout(0),
             entries: &[
-                resource_helpers::buffer_entry(0, &self.particle_buffer),
+                resource_helpers::buffer_entry(0, self.particle_buffer.buffer()),
                 resource_helpers::buffer_entry(1, &self.render_params_buffer),
                 resource_helpers::buffer_entry(2, &self.lut_buffer),
             ],
@@ -1950,38 +1718,214 @@ impl PelletsModel {
         }
     }
 
-    fn calculate_average_color(&self, device: &Arc<Device>, queue: &Arc<Queue>) {
-        self.average_color_resources.calculate_average_color(
-            device,
-            queue,
-            &self.post_effect_texture,
-        );
+    fn create_render_graph() -> RenderGraph<PelletsNode> {
+        RenderGraph::new("Pellets")
+            .with_node(PelletsNode::Compute, NodeKind::Compute)
+            .with_node(PelletsNode::Background, NodeKind::Offscreen)
+            .with_node(PelletsNode::Trails, NodeKind::Offscreen)
+            .with_node(PelletsNode::TrailBlit, NodeKind::Offscreen)
+            .with_node(PelletsNode::Particles, NodeKind::Offscreen)
+            .with_node(PelletsNode::PostEffect, NodeKind::Post)
+            // Sets the background the infinite render shades with
+            .with_node(PelletsNode::AverageColor, NodeKind::Readback)
+            .with_node(PelletsNode::InfiniteRender, NodeKind::Composite)
+            .with_node(PelletsNode::Blur, NodeKind::Post)
+    }
 
-        // Wait for the GPU work to complete
-        device
-            .poll(wgpu::wgt::PollType::Wait)
-            .expect("Failed to poll device");
+    /// Render a frame through the render graph, stepping physics and
+    /// trails only while `running`
+    fn render_graph_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        running: bool,
+    ) -> SimulationResult<()> {
+        self.update_camera_uniform(queue);
+        self.update_render_params(queue);
+        self.update_background_params(queue);
+        self.update_post_effect_params(queue);
+        self.update_background_color(queue);
 
-        // Read the result and update the background color buffer
-        if let Some(average_color) = self.average_color_resources.get_average_color() {
-            queue.write_buffer(
-                &self.background_color_buffer,
-                0,
-                bytemuck::cast_slice(&[average_color]),
-            );
+        // Paused trails show the latest frame as it is
+        let trails_enabled = self.state.trails_enabled;
+        self.render_graph.set_enabled(PelletsNode::Compute, running);
+        self.render_graph
+            .set_enabled(PelletsNode::Trails, running && trails_enabled);
+        self.render_graph
+            .set_enabled(PelletsNode::TrailBlit, trails_enabled);
+        self.render_graph
+            .set_enabled(PelletsNode::Particles, !trails_enabled);
+        self.render_graph
+            .set_enabled(PelletsNode::AverageColor, running);
+        self.render_graph.set_enabled(
+            PelletsNode::Blur,
+            self.post_processing_state.blur_filter.enabled,
+        );
 
-            // Also update the average color uniform buffer for the infinite render shader
-            queue.write_buffer(
-                &self.average_color_uniform_buffer,
-                0,
-                bytemuck::cast_slice(&[average_color]),
-            );
-        }
-        // Unmap the staging buffer after reading
-        self.average_color_resources.unmap_staging_buffer();
+        let mut graph = std::mem::take(&mut self.render_graph);
+        let result = graph.execute(device, queue, |node, encoder| {
+            self.encode_render_node(node, device, queue, encoder, surface_view)
+        });
+        self.render_graph = graph;
+        result
     }
 
-    fn update_particle_radii(&mut self, queue: &Arc<Queue>) {
+    fn encode_render_node(
+        &mut self,
+        node: PelletsNode,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        match node {
+            PelletsNode::Compute => self.step_physics(queue, encoder),
+            PelletsNode::Background => {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Pellets Background Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &self.display_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    occlusion_query_set: None,
+                    timestamp_writes: None,
+                });
+                render_pass.set_pipeline(&self.background_render_pipeline);
+                render_pass.set_bind_group(0, &self.background_render_bind_group, &[]);
+                render_pass.draw(0..6, 0..1);
+            }
+            PelletsNode::Trails => {
+                // Draw particles over the faded trail texture
+                {
+                    let mut trail_pass = self.trails.begin_pass(
+                        device,
+                        queue,
+                        encoder,
+                        self.state.trail_fade_curve,
+                        self.state.trail_fade,
+                        wgpu::Color::BLACK,
+                    );
+                    trail_pass.set_pipeline(&self.particle_render_pipeline);
+                    trail_pass.set_bind_group(0, &self.particle_render_bind_group, &[]);
+                    trail_pass.draw(0..6, 0..(self.particles.len() * 9) as u32);
+                }
+                self.trails.swap();
+            }
+            PelletsNode::TrailBlit => self.trails.blit(device, encoder, &self.display_view),
+            PelletsNode::Particles => {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Pellets Particle Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &self.display_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    occlusion_query_set: None,
+                    timestamp_writes: None,
+                });
+                render_pass.set_pipeline(&self.particle_render_pipeline);
+                render_pass.set_bind_group(0, &self.particle_render_bind_group, &[]);
+                render_pass.draw(0..6, 0..(self.particles.len() * 9) as u32);
+            }
+            PelletsNode::PostEffect => {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Pellets Post Effect Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &self.post_effect_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    occlusion_query_set: None,
+                    timestamp_writes: None,
+                });
+                render_pass.set_pipeline(&self.post_effect_pipeline);
+                render_pass.set_bind_group(0, &self.post_effect_bind_group, &[]);
+                render_pass.draw(0..6, 0..1);
+            }
+            PelletsNode::AverageColor => self.calculate_average_color(device, queue),
+            PelletsNode::InfiniteRender => {
+                // Tile the post-effect texture across the surface
+                let tile_count = self.calculate_tile_count();
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Pellets Infinite Surface Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: surface_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                        depth_slice: None,
+                    })],
+                    depth_stencil_attachment: None,
+                    occlusion_query_set: None,
+                    timestamp_writes: None,
+                });
+                render_pass.set_pipeline(&self.render_infinite_pipeline);
+                render_pass.set_bind_group(0, &self.render_infinite_bind_group, &[]);
+                render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+                render_pass.draw(0..6, 0..tile_count * tile_count);
+            }
+            PelletsNode::Blur => self.apply_post_processing(
+                device,
+                queue,
+                &self.display_view,
+                &self.post_processing_resources.intermediate_view,
+                encoder,
+            )?,
+        }
+        Ok(())
+    }
+
+    fn calculate_average_color(&self, device: &Arc<Device>, queue: &Arc<Queue>) {
+        self.average_color_resources.calculate_average_color(
+            device,
+            queue,
+            &self.post_effect_texture,
+        );
+
+        // Wait for the GPU work to complete
+        device
+            .poll(wgpu::wgt::PollType::Wait)
+            .expect("Failed to poll device");
+
+        // Read the result and update the background color buffer
+        if let Some(average_color) = self.average_color_resources.get_average_color() {
+            queue.write_buffer(
+                &self.background_color_buffer,
+                0,
+                bytemuck::cast_slice(&[average_color]),
+            );
+
+            // Also update the average color uniform buffer for the infinite render shader
+            queue.write_buffer(
+                &self.average_color_uniform_buffer,
+                0,
+                bytemuck::cast_slice(&[average_color]),
+            );
+        }
+        // Unmap the staging buffer after reading
+        self.average_color_resources.unmap_staging_buffer();
+    }
+
+    fn update_particle_radii(&mut self, queue: &Arc<Queue>) {
         // Update all existing particles' radii to match the new particle size setting
         for particle in &mut self.particles {
             particle.radius = self.settings.particle_size;
@@ -1989,7 +1933,7 @@ impl PelletsModel {
 
         // Update the GPU buffer with the new particle data
         queue.write_buffer(
-            &self.particle_buffer,
+            self.particle_buffer.buffer(),
             0,
             bytemuck::cast_slice(&self.particles),
         );
@@ -2033,6 +1977,7 @@ impl PelletsModel {
         queue: &Arc<Queue>,
         input_texture_view: &wgpu::TextureView,
         output_texture_view: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
     ) -> crate::error::SimulationResult<()> {
         if self.post_processing_state.blur_filter.enabled {
             self.post_processing_resources.update_blur_params(
@@ -2060,30 +2005,24 @@ impl PelletsModel {
                     ),
                 ],
             });
-            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Post Processing Blur Encoder"),
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Post Processing Blur Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: output_texture_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
             });
-            {
-                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Post Processing Blur Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: output_texture_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-                render_pass.set_pipeline(&self.post_processing_resources.blur_pipeline);
-                render_pass.set_bind_group(0, &blur_bind_group, &[]);
-                render_pass.draw(0..6, 0..1);
-            }
-            queue.submit(std::iter::once(encoder.finish()));
+            render_pass.set_pipeline(&self.post_processing_resources.blur_pipeline);
+            render_pass.set_bind_group(0, &blur_bind_group, &[]);
+            render_pass.draw(0..6, 0..1);
         }
         Ok(())
     }
@@ -2108,257 +2047,9 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         surface_view: &TextureView,
         delta_time: f32,
     ) -> SimulationResult<()> {
-        // Step GPU physics simulation
-        self.step_physics(device, queue)?;
-
-        // Update camera with smoothing
+        self.state.simulation_time += delta_time;
         self.camera.update(delta_time);
-
-        // Update uniforms
-        self.update_camera_uniform(queue);
-        self.update_render_params(queue);
-        self.update_background_params(queue);
-        self.update_post_effect_params(queue);
-        self.update_background_color(queue);
-
-        // 1. Render background to display texture (offscreen)
-        let mut offscreen_encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Pellets Offscreen Encoder"),
-            });
-        {
-            let mut render_pass =
-                offscreen_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Background Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.display_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-
-            // Render background
-            render_pass.set_pipeline(&self.background_render_pipeline);
-            render_pass.set_bind_group(0, &self.background_render_bind_group, &[]);
-            render_pass.draw(0..6, 0..1);
-        }
-        queue.submit(std::iter::once(offscreen_encoder.finish()));
-
-        if self.state.trails_enabled {
-            // Trails path: render into ping-pong trail texture with fade, then blit to display
-            // Update fade uniforms from state
-            let fade_strength = (1.0 - self.state.trail_fade).max(0.0);
-            let fade_amount = fade_strength * 0.1;
-            queue.write_buffer(
-                &self.trail_fade_uniforms_buffer,
-                0,
-                bytemuck::bytes_of(&[fade_amount, 0.0f32, 0.0, 0.0]),
-            );
-
-            // Update fade bind group to read from previous trail texture
-            let read_trail_view = if self.current_trail_is_a {
-                &self.trail_texture_view_b
-            } else {
-                &self.trail_texture_view_a
-            };
-            self.trail_fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Pellets Trail Fade Bind Group (frame)"),
-                layout: &self.trail_fade_bind_group_layout,
-                entries: &[
-                    resource_helpers::buffer_entry(0, &self.trail_fade_uniforms_buffer),
-                    resource_helpers::texture_view_entry(1, read_trail_view),
-                    resource_helpers::sampler_bind_entry(2, &self.trail_sampler),
-                ],
-            });
-
-            // Render fade + particles into write trail texture
-            let write_trail_view = if self.current_trail_is_a {
-                &self.trail_texture_view_a
-            } else {
-                &self.trail_texture_view_b
-            };
-            let mut trail_encoder =
-                device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Pellets Trail Encoder"),
-                });
-            {
-                let mut trail_pass = trail_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Trail Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: write_trail_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-
-                // Apply fade from previous trail into current
-                trail_pass.set_pipeline(&self.trail_fade_pipeline);
-                trail_pass.set_bind_group(0, &self.trail_fade_bind_group, &[]);
-                trail_pass.draw(0..3, 0..1);
-
-                // Draw particles over the faded trail texture
-                trail_pass.set_pipeline(&self.particle_render_pipeline);
-                trail_pass.set_bind_group(0, &self.particle_render_bind_group, &[]);
-                trail_pass.draw(0..6, 0..(self.particles.len() * 9) as u32);
-            }
-            queue.submit(std::iter::once(trail_encoder.finish()));
-
-            // Blit the trail texture to the display texture
-            let read_for_blit = write_trail_view;
-            self.trail_blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Pellets Trail Blit Bind Group (frame)"),
-                layout: &self.trail_blit_pipeline.get_bind_group_layout(0),
-                entries: &[
-                    resource_helpers::texture_view_entry(0, read_for_blit),
-                    resource_helpers::sampler_bind_entry(1, &self.trail_sampler),
-                ],
-            });
-
-            let mut blit_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Pellets Trail Blit Encoder"),
-            });
-            {
-                let mut pass = blit_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Trail Blit Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.display_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-                pass.set_pipeline(&self.trail_blit_pipeline);
-                pass.set_bind_group(0, &self.trail_blit_bind_group, &[]);
-                pass.draw(0..3, 0..1);
-            }
-            queue.submit(std::iter::once(blit_encoder.finish()));
-
-            // Swap ping-pong
-            self.current_trail_is_a = !self.current_trail_is_a;
-        } else {
-            // Non-trail path: draw particles to display
-            let mut particle_encoder =
-                device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Pellets Particle Encoder"),
-                });
-            {
-                let mut render_pass =
-                    particle_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                        label: Some("Pellets Particle Render Pass"),
-                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                            view: &self.display_view,
-                            resolve_target: None,
-                            ops: wgpu::Operations {
-                                load: wgpu::LoadOp::Load,
-                                store: wgpu::StoreOp::Store,
-                            },
-                            depth_slice: None,
-                        })],
-                        depth_stencil_attachment: None,
-                        occlusion_query_set: None,
-                        timestamp_writes: None,
-                    });
-                render_pass.set_pipeline(&self.particle_render_pipeline);
-                render_pass.set_bind_group(0, &self.particle_render_bind_group, &[]);
-                render_pass.draw(0..6, 0..(self.particles.len() * 9) as u32);
-            }
-            queue.submit(std::iter::once(particle_encoder.finish()));
-        }
-
-        // 3. Render post effects to post-effect texture (offscreen)
-        let mut post_effect_encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Pellets Post Effect Encoder"),
-            });
-        {
-            let mut render_pass =
-                post_effect_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Post Effect Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.post_effect_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-
-            // Render post effects
-            render_pass.set_pipeline(&self.post_effect_pipeline);
-            render_pass.set_bind_group(0, &self.post_effect_bind_group, &[]);
-            render_pass.draw(0..6, 0..1);
-        }
-        queue.submit(std::iter::once(post_effect_encoder.finish()));
-
-        // 3. Calculate average color from the post-effect texture
-        self.calculate_average_color(device, queue);
-
-        // 4. Render post-effect texture to surface with infinite tiling
-        let tile_count = self.calculate_tile_count();
-        let total_instances = tile_count * tile_count;
-
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Pellets Infinite Surface Encoder"),
-        });
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Pellets Infinite Surface Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: surface_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
-            render_pass.set_pipeline(&self.render_infinite_pipeline);
-            render_pass.set_bind_group(0, &self.render_infinite_bind_group, &[]);
-            render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
-            render_pass.draw(0..6, 0..total_instances);
-        }
-        queue.submit(std::iter::once(encoder.finish()));
-
-        if self.post_processing_state.blur_filter.enabled {
-            self.apply_post_processing(
-                device,
-                queue,
-                &self.display_view,
-                &self.post_processing_resources.intermediate_view,
-            )?;
-            // Copy result back to display_view if needed
-        }
-
-        Ok(())
+        self.render_graph_frame(device, queue, surface_view, true)
     }
 
     fn render_frame_paused(
@@ -2368,237 +2059,8 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         surface_view: &TextureView,
     ) -> SimulationResult<()> {
         // For static rendering, just render without updating physics
-        // Update camera with smoothing
         self.camera.update(0.016); // Assume 60 FPS for now
-
-        self.update_camera_uniform(queue);
-        self.update_render_params(queue);
-        self.update_background_params(queue);
-        self.update_post_effect_params(queue);
-        self.update_background_color(queue);
-
-        // 1. Render background to display texture (offscreen)
-        let mut offscreen_encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Pellets Static Offscreen Encoder"),
-            });
-        {
-            let mut render_pass =
-                offscreen_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Static Background Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.display_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-
-            // Render background
-            render_pass.set_pipeline(&self.background_render_pipeline);
-            render_pass.set_bind_group(0, &self.background_render_bind_group, &[]);
-            render_pass.draw(0..6, 0..1);
-        }
-        queue.submit(std::iter::once(offscreen_encoder.finish()));
-
-        if self.state.trails_enabled {
-            let fade_strength = (1.0 - self.state.trail_fade).max(0.0);
-            let fade_amount = fade_strength * 0.1;
-            queue.write_buffer(
-                &self.trail_fade_uniforms_buffer,
-                0,
-                bytemuck::bytes_of(&[fade_amount, 0.0f32, 0.0, 0.0]),
-            );
-
-            let read_trail_view = if self.current_trail_is_a {
-                &self.trail_texture_view_b
-            } else {
-                &self.trail_texture_view_a
-            };
-            self.trail_fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Pellets Trail Fade Bind Group (static)"),
-                layout: &self.trail_fade_bind_group_layout,
-                entries: &[
-                    resource_helpers::buffer_entry(0, &self.trail_fade_uniforms_buffer),
-                    resource_helpers::texture_view_entry(1, read_trail_view),
-                    resource_helpers::sampler_bind_entry(2, &self.trail_sampler),
-                ],
-            });
-
-            let write_trail_view = if self.current_trail_is_a {
-                &self.trail_texture_view_a
-            } else {
-                &self.trail_texture_view_b
-            };
-            let mut trail_encoder =
-                device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Pellets Static Trail Encoder"),
-                });
-            {
-                let mut trail_pass = trail_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Static Trail Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: write_trail_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-                trail_pass.set_pipeline(&self.trail_fade_pipeline);
-                trail_pass.set_bind_group(0, &self.trail_fade_bind_group, &[]);
-                trail_pass.draw(0..3, 0..1);
-                trail_pass.set_pipeline(&self.particle_render_pipeline);
-                trail_pass.set_bind_group(0, &self.particle_render_bind_group, &[]);
-                trail_pass.draw(0..6, 0..(self.particles.len() * 9) as u32);
-            }
-            queue.submit(std::iter::once(trail_encoder.finish()));
-
-            let read_for_blit = write_trail_view;
-            self.trail_blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Pellets Static Trail Blit Bind Group"),
-                layout: &self.trail_blit_pipeline.get_bind_group_layout(0),
-                entries: &[
-                    resource_helpers::texture_view_entry(0, read_for_blit),
-                    resource_helpers::sampler_bind_entry(1, &self.trail_sampler),
-                ],
-            });
-            let mut blit_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Pellets Static Trail Blit Encoder"),
-            });
-            {
-                let mut pass = blit_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Static Trail Blit Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.display_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-                pass.set_pipeline(&self.trail_blit_pipeline);
-                pass.set_bind_group(0, &self.trail_blit_bind_group, &[]);
-                pass.draw(0..3, 0..1);
-            }
-            queue.submit(std::iter::once(blit_encoder.finish()));
-            self.current_trail_is_a = !self.current_trail_is_a;
-        } else {
-            let mut particle_encoder =
-                device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Pellets Static Particle Encoder"),
-                });
-            {
-                let mut render_pass =
-                    particle_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                        label: Some("Pellets Static Particle Render Pass"),
-                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                            view: &self.display_view,
-                            resolve_target: None,
-                            ops: wgpu::Operations {
-                                load: wgpu::LoadOp::Load,
-                                store: wgpu::StoreOp::Store,
-                            },
-                            depth_slice: None,
-                        })],
-                        depth_stencil_attachment: None,
-                        occlusion_query_set: None,
-                        timestamp_writes: None,
-                    });
-                render_pass.set_pipeline(&self.particle_render_pipeline);
-                render_pass.set_bind_group(0, &self.particle_render_bind_group, &[]);
-                render_pass.draw(0..6, 0..(self.particles.len() * 9) as u32);
-            }
-            queue.submit(std::iter::once(particle_encoder.finish()));
-        }
-
-        // 3. Render post effects to post-effect texture (offscreen)
-        let mut post_effect_encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Pellets Static Post Effect Encoder"),
-            });
-        {
-            let mut render_pass =
-                post_effect_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Pellets Static Post Effect Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.post_effect_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    occlusion_query_set: None,
-                    timestamp_writes: None,
-                });
-
-            // Render post effects
-            render_pass.set_pipeline(&self.post_effect_pipeline);
-            render_pass.set_bind_group(0, &self.post_effect_bind_group, &[]);
-            render_pass.draw(0..6, 0..1);
-        }
-        queue.submit(std::iter::once(post_effect_encoder.finish()));
-
-        // 4. Render post-effect texture to surface with infinite tiling
-        let tile_count = self.calculate_tile_count();
-        let total_instances = tile_count * tile_count;
-
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Pellets Static Infinite Surface Encoder"),
-        });
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Pellets Static Infinite Surface Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: surface_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
-            render_pass.set_pipeline(&self.render_infinite_pipeline);
-            render_pass.set_bind_group(0, &self.render_infinite_bind_group, &[]);
-            render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
-            render_pass.draw(0..6, 0..total_instances);
-        }
-        queue.submit(std::iter::once(encoder.finish()));
-
-        if self.post_processing_state.blur_filter.enabled {
-            self.apply_post_processing(
-                device,
-                queue,
-                &self.display_view,
-                &self.post_processing_resources.intermediate_view,
-            )?;
-            // Copy result back to display_view if needed
-        }
-
-        Ok(())
+        self.render_graph_frame(device, queue, surface_view, false)
     }
 
     fn resize(
@@ -2675,44 +2137,8 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         self.post_effect_view = post_effect_view;
 
         // Recreate trail textures for new dimensions
-        let trail_texture_a = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Pellets Trail Texture A"),
-            size: wgpu::Extent3d {
-                width: new_config.width * 2,
-                height: new_config.height * 2,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-        let trail_texture_view_a =
-            trail_texture_a.create_view(&wgpu::TextureViewDescriptor::default());
-        let trail_texture_b = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Pellets Trail Texture B"),
-            size: wgpu::Extent3d {
-                width: new_config.width * 2,
-                height: new_config.height * 2,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-        let trail_texture_view_b =
-            trail_texture_b.create_view(&wgpu::TextureViewDescriptor::default());
-
-        self.trail_texture_a = trail_texture_a;
-        self.trail_texture_view_a = trail_texture_view_a;
-        self.trail_texture_b = trail_texture_b;
-        self.trail_texture_view_b = trail_texture_view_b;
-        self.current_trail_is_a = true;
+        self.trails
+            .resize(device, (new_config.width, new_config.height));
 
         // Recreate density texture for new dimensions
         let density_texture = device.create_texture(&wgpu::TextureDescriptor {
@@ -2944,7 +2370,7 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         &mut self,
         state_name: &str,
         value: serde_json::Value,
-        _device: &Arc<Device>,
+        device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> crate::error::SimulationResult<()> {
         match state_name {
@@ -3006,6 +2432,19 @@ impl crate::simulations::traits::Simulation for PelletsModel {
                     self.state.trail_fade = fade as f32;
                 }
             }
+            "trail_fade_curve" => {
+                if let Ok(curve) = serde_json::from_value(value) {
+                    self.state.trail_fade_curve = curve;
+                }
+            }
+            "trail_resolution_scale" => {
+                if let Some(scale) = value.as_f64()
+                    && self.trails.set_resolution_scale(device, scale as f32)
+                {
+                    self.state.trail_resolution_scale = self.trails.resolution_scale();
+                    self.trails.clear(device, queue, wgpu::Color::TRANSPARENT);
+                }
+            }
             _ => {
                 tracing::warn!("Unknown state parameter for Pellets: {}", state_name);
             }
@@ -3017,6 +2456,14 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         serde_json::to_value(&self.settings).unwrap_or(Value::Null)
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
     fn get_state(&self) -> Value {
         serde_json::to_value(&self.state).unwrap_or(Value::Null)
     }
@@ -3033,11 +2480,8 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         let clamped_x = world_x.clamp(-1.0, 1.0);
         let clamped_y = (-world_y).clamp(-1.0, 1.0); // Fix Y-axis inversion
 
-        // Calculate mouse velocity based on time difference
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64();
+        // Calculate mouse velocity based on simulation time, which replays step the same way
+        let current_time = self.state.simulation_time as f64;
 
         let time_delta = current_time - self.state.last_mouse_time;
 
@@ -3065,9 +2509,10 @@ impl crate::simulations::traits::Simulation for PelletsModel {
             ];
         }
 
-        // Encode mouse button into mode: 0 none, 1 left(attraction)
+        // Encode mouse button into mode: 0 none, 1 left(attraction), 2 right
         let mode = match mouse_button {
             0 => 1u32, // Left click for attraction
+            2 => 2u32, // Right click reverses the vortex, push and turbulence modes
             _ => 0u32, // Other buttons do nothing
         };
 
@@ -3129,6 +2574,39 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         })
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        vec![self.particle_buffer.rewind_resource()]
+    }
+
+    fn health_probes(&self) -> Vec<HealthProbe<'_>> {
+        // Velocities are capped by the physics shader, so only broken positions matter
+        vec![HealthProbe::vector(
+            "Particle positions",
+            self.particle_buffer.buffer(),
+            (std::mem::size_of::<Particle>() / std::mem::size_of::<f32>()) as u32,
+            0,
+            HealthCheck::Positions,
+        )]
+    }
+
+    fn recover_from_instability(
+        &mut self,
+        issues: &[HealthIssue],
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Gravity is what pulls pellets into one point or flings them beyond
+        // float range, so the same start would end the same way without it weaker
+        if !issues.is_empty() {
+            self.settings.gravitational_constant *= 0.5;
+            tracing::info!(
+                "Reduced gravitational constant to {} after instability",
+                self.settings.gravitational_constant
+            );
+        }
+        self.place_particles(device, queue)
+    }
+
     fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
         // TODO: Implement preset saving
         Ok(())
@@ -3156,29 +2634,7 @@ impl crate::simulations::traits::Simulation for PelletsModel {
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
-        // Reinitialize particles
-        self.particles = Self::initialize_particles(self.settings.particle_count, &self.settings);
-
-        // Check if we need to recreate the buffer (if it's too small)
-        let required_buffer_size = self.particles.len() * std::mem::size_of::<Particle>();
-        if self.particle_buffer.size() < required_buffer_size as u64 {
-            // Recreate the particle buffer with the new size
-            self.particle_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Pellets Particle Buffer"),
-                contents: bytemuck::cast_slice(&self.particles),
-                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            });
-
-            // Recreate the bind groups since the buffer changed
-            self.recreate_bind_groups(device)?;
-        } else {
-            // Update existing GPU buffer
-            queue.write_buffer(
-                &self.particle_buffer,
-                0,
-                bytemuck::cast_slice(&self.particles),
-            );
-        }
+        self.place_particles(device, queue)?;
 
         // Reset camera
         self.camera.reset();
@@ -17,13 +17,14 @@
 //! and simulation execution status, providing the context needed for
 //! responsive and intuitive user experience.
 
+use crate::simulations::shared::{CursorForceField, FadeCurve, GlobalForce};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct State {
     /// Current mouse interaction state
     pub mouse_pressed: bool,
-    /// 0 = no mouse, 1 = attract (left click)
+    /// 0 = no mouse, 1 = attract (left click), 2 = right click
     pub mouse_mode: u32,
     pub mouse_position: [f32; 2],
     pub mouse_velocity: [f32; 2], // Mouse velocity in world units per second
@@ -33,6 +34,10 @@ pub struct State {
     /// Cursor interaction parameters
     pub cursor_size: f32,
     pub cursor_strength: f32,
+    /// Shape of the force the cursor applies
+    pub cursor_force: CursorForceField,
+    /// Wind and gravity applied to every particle
+    pub global_force: GlobalForce,
 
     /// Grabbed particles for drag interaction
     pub grabbed_particles: Vec<usize>, // Indices of particles being dragged
@@ -57,6 +62,12 @@ pub struct State {
     pub trails_enabled: bool,
     /// Trail fade amount control in [0,1]. 0 = fast fade, 1 = no fade
     pub trail_fade: f32,
+    /// How trail_fade takes trails away frame by frame
+    #[serde(default)]
+    pub trail_fade_curve: FadeCurve,
+    /// Trail texture resolution relative to the display
+    #[serde(default = "default_trail_resolution_scale")]
+    pub trail_resolution_scale: f32,
 }
 
 impl Default for State {
@@ -70,6 +81,8 @@ impl Default for State {
             last_mouse_time: 0.0,
             cursor_size: 0.20,
             cursor_strength: 1.0, // Increased for better throwing visibility
+            cursor_force: CursorForceField::default(),
+            global_force: GlobalForce::default(),
             grabbed_particles: Vec::new(),
             current_color_scheme: "MATPLOTLIB_bone".to_string(),
             color_scheme_reversed: true,
@@ -80,10 +93,16 @@ impl Default for State {
             is_running: true,
             trails_enabled: false,
             trail_fade: 0.5,
+            trail_fade_curve: FadeCurve::Linear,
+            trail_resolution_scale: default_trail_resolution_scale(),
         }
     }
 }
 
+fn default_trail_resolution_scale() -> f32 {
+    2.0
+}
+
 impl State {
     /// Reset all state to default values
     pub fn reset(&mut self) {
@@ -699,6 +699,10 @@ fn test_struct_layout_consistency() {
             density_damping_enabled: 1,
             overlap_resolution_strength: 0.02,
             frame_index: 0,
+            cursor_mode: 0,
+            cursor_curve: 0,
+            cursor_time: 0.0,
+            _pad0: 0,
         };
 
         let physics_params_buffer =
@@ -758,6 +762,22 @@ fn test_struct_layout_consistency() {
     });
 }
 
+#[test]
+fn test_particles_start_from_the_seed() {
+    use super::PelletsModel;
+    use crate::simulations::pellets::settings::Settings;
+
+    let settings = Settings::default();
+    let positions = |seed| -> Vec<[f32; 2]> {
+        PelletsModel::initialize_particles(100, &settings, seed)
+            .iter()
+            .map(|particle| particle.position)
+            .collect()
+    };
+    assert_eq!(positions(7), positions(7));
+    assert_ne!(positions(7), positions(8));
+}
+
 #[cfg(test)]
 mod tests {
     use crate::simulation::preset_manager::PelletsPresetManager;
@@ -0,0 +1,402 @@
+//! # Percolation Lattices
+//!
+//! Every site (or every bond between neighboring sites) draws a random
+//! threshold once. At occupation probability `p` the ones below `p` are
+//! open, and open sites joined through open neighbors (or open bonds) form
+//! clusters. As `p` rises past the critical probability a cluster appears
+//! that spans the grid from one side to the other.
+//!
+//! Raising `p` opens sites in threshold order and merges clusters with
+//! union-find, so a sweep costs about as much as a single pass over the
+//! grid (the Newman-Ziff algorithm). Lowering it starts over from empty.
+//!
+//! Invasion percolation grows a single cluster from an injection site
+//! instead, always taking the neighboring site with the lowest threshold,
+//! like a fluid forcing its way into porous rock through the widest pores.
+//!
+//! Neither wraps at the edges: a cluster only spans when it reaches the
+//! opposite side.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::simulations::shared::GridTopology;
+
+/// Which connections carry percolation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Occupation {
+    /// Sites open, and neighboring open sites connect
+    Site,
+    /// Every site is present, and the bonds between neighbors open
+    Bond,
+}
+
+/// The probability where a spanning cluster first appears on an infinite
+/// lattice. Hex cells touch six others like the triangular lattice, and
+/// triangle cells touch three through their edges like the honeycomb.
+pub fn critical_probability(topology: GridTopology, occupation: Occupation) -> f32 {
+    match (topology, occupation) {
+        (GridTopology::Square, Occupation::Site) => 0.592_746,
+        (GridTopology::Square, Occupation::Bond) => 0.5,
+        (GridTopology::Hex, Occupation::Site) => 0.5,
+        (GridTopology::Hex, Occupation::Bond) => 0.347_296,
+        (GridTopology::Triangle, Occupation::Site) => 0.697_043,
+        (GridTopology::Triangle, Occupation::Bond) => 0.652_704,
+    }
+}
+
+// Grid sides a cell touches, so clusters know when they span
+const LEFT: u8 = 1;
+const RIGHT: u8 = 2;
+const TOP: u8 = 4;
+const BOTTOM: u8 = 8;
+
+fn sides(width: u32, height: u32) -> Vec<u8> {
+    (0..width * height)
+        .map(|cell| {
+            let (x, y) = (cell % width, cell / width);
+            let mut sides = 0;
+            if x == 0 {
+                sides |= LEFT;
+            }
+            if x == width - 1 {
+                sides |= RIGHT;
+            }
+            if y == 0 {
+                sides |= TOP;
+            }
+            if y == height - 1 {
+                sides |= BOTTOM;
+            }
+            sides
+        })
+        .collect()
+}
+
+fn spans(sides: u8) -> bool {
+    sides & (LEFT | RIGHT) == LEFT | RIGHT || sides & (TOP | BOTTOM) == TOP | BOTTOM
+}
+
+/// Neighbors of `cell` sharing an edge, without wrapping
+fn edge_neighbors(
+    topology: GridTopology,
+    width: u32,
+    height: u32,
+    cell: u32,
+) -> impl Iterator<Item = u32> {
+    let (x, y) = ((cell % width) as i32, (cell / width) as i32);
+    topology.neighbor_offsets(x, y)[..topology.edge_neighbor_count()]
+        .iter()
+        .filter_map(move |&(dx, dy)| {
+            let (nx, ny) = (x + dx, y + dy);
+            let inside = (0..width as i32).contains(&nx) && (0..height as i32).contains(&ny);
+            inside.then(|| ny as u32 * width + nx as u32)
+        })
+}
+
+#[derive(Debug)]
+pub struct Lattice {
+    width: u32,
+    height: u32,
+    topology: GridTopology,
+    occupation: Occupation,
+    // Sites for site percolation or bonds for bond percolation, in the order
+    // they open, and the thresholds they open at
+    order: Vec<u32>,
+    thresholds: Vec<f32>,
+    bonds: Vec<(u32, u32)>,
+    opened: usize,
+
+    open: Vec<bool>,
+    parent: Vec<u32>,
+    size: Vec<u32>,
+    // Sides each cluster touches, kept at its root
+    sides: Vec<u8>,
+    largest: u32,
+    spanning: bool,
+}
+
+impl Lattice {
+    pub fn new(
+        width: u32,
+        height: u32,
+        topology: GridTopology,
+        occupation: Occupation,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let cells = width * height;
+        let bonds: Vec<(u32, u32)> = match occupation {
+            Occupation::Site => Vec::new(),
+            Occupation::Bond => (0..cells)
+                .flat_map(|cell| {
+                    edge_neighbors(topology, width, height, cell)
+                        .filter(move |&neighbor| neighbor > cell)
+                        .map(move |neighbor| (cell, neighbor))
+                })
+                .collect(),
+        };
+        let count = match occupation {
+            Occupation::Site => cells as usize,
+            Occupation::Bond => bonds.len(),
+        };
+        let drawn: Vec<f32> = (0..count).map(|_| rng.random()).collect();
+        let mut order: Vec<u32> = (0..count as u32).collect();
+        order.sort_by(|&a, &b| drawn[a as usize].total_cmp(&drawn[b as usize]));
+        let thresholds = order.iter().map(|&i| drawn[i as usize]).collect();
+
+        let mut lattice = Self {
+            width,
+            height,
+            topology,
+            occupation,
+            order,
+            thresholds,
+            bonds,
+            opened: 0,
+            open: Vec::new(),
+            parent: Vec::new(),
+            size: Vec::new(),
+            sides: Vec::new(),
+            largest: 0,
+            spanning: false,
+        };
+        lattice.clear();
+        lattice
+    }
+
+    /// Back to nothing open. Bond lattices keep every site, each its own
+    /// cluster.
+    fn clear(&mut self) {
+        let cells = self.width * self.height;
+        let all_sites = self.occupation == Occupation::Bond;
+        self.opened = 0;
+        self.open = vec![all_sites; cells as usize];
+        self.parent = (0..cells).collect();
+        self.size = vec![1; cells as usize];
+        self.sides = sides(self.width, self.height);
+        self.largest = all_sites as u32;
+        self.spanning = all_sites && (self.width == 1 || self.height == 1);
+    }
+
+    /// Open everything with a threshold below `p`. Returns whether that
+    /// changed anything.
+    pub fn open_to(&mut self, p: f32) -> bool {
+        let mut changed = false;
+        if self.opened > 0 && self.thresholds[self.opened - 1] >= p {
+            self.clear();
+            changed = true;
+        }
+        while self.opened < self.order.len() && self.thresholds[self.opened] < p {
+            let next = self.order[self.opened] as usize;
+            self.opened += 1;
+            changed = true;
+            match self.occupation {
+                Occupation::Site => {
+                    let cell = next as u32;
+                    self.open[next] = true;
+                    self.largest = self.largest.max(1);
+                    self.spanning |= spans(self.sides[next]);
+                    for neighbor in edge_neighbors(self.topology, self.width, self.height, cell) {
+                        if self.open[neighbor as usize] {
+                            self.union(cell, neighbor);
+                        }
+                    }
+                }
+                Occupation::Bond => {
+                    let (a, b) = self.bonds[next];
+                    self.union(a, b);
+                }
+            }
+        }
+        changed
+    }
+
+    fn find(&mut self, mut cell: u32) -> u32 {
+        while self.parent[cell as usize] != cell {
+            let grandparent = self.parent[self.parent[cell as usize] as usize];
+            self.parent[cell as usize] = grandparent;
+            cell = grandparent;
+        }
+        cell
+    }
+
+    fn union(&mut self, a: u32, b: u32) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        let (big, small) = if self.size[a as usize] >= self.size[b as usize] {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        self.parent[small] = big as u32;
+        self.size[big] += self.size[small];
+        self.sides[big] |= self.sides[small];
+        self.largest = self.largest.max(self.size[big]);
+        self.spanning |= spans(self.sides[big]);
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The cluster `cell` belongs to, named by one of its cells, or `None`
+    /// while the site is closed
+    pub fn cluster(&mut self, cell: u32) -> Option<u32> {
+        self.open[cell as usize].then(|| self.find(cell))
+    }
+
+    /// Sites in the cluster named by `root`
+    pub fn cluster_size(&self, root: u32) -> u32 {
+        self.size[root as usize]
+    }
+
+    /// Whether the cluster named by `root` reaches across the grid
+    pub fn cluster_spans(&self, root: u32) -> bool {
+        spans(self.sides[root as usize])
+    }
+
+    pub fn largest_cluster(&self) -> u32 {
+        self.largest
+    }
+
+    /// Whether any cluster reaches across the grid
+    pub fn spanning(&self) -> bool {
+        self.spanning
+    }
+
+    /// How much of the lattice is open so far
+    pub fn open_fraction(&self) -> f32 {
+        self.opened as f32 / self.order.len().max(1) as f32
+    }
+}
+
+/// Where invading fluid enters the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Injection {
+    /// Along the whole left side, breaking through at the right
+    LeftSide,
+    /// At one cell, breaking through at any side
+    Cell(u32),
+}
+
+#[derive(Debug)]
+pub struct Invasion {
+    width: u32,
+    height: u32,
+    topology: GridTopology,
+    thresholds: Vec<f32>,
+    // When each cell was invaded, counting from 1, with 0 still dry
+    invaded_at: Vec<u32>,
+    queued: Vec<bool>,
+    // Candidates with the lowest threshold first. Thresholds are positive,
+    // so their bits sort the same way they do.
+    frontier: BinaryHeap<Reverse<(u32, u32)>>,
+    sides: Vec<u8>,
+    goal: u8,
+    invaded: u32,
+    broke_through: bool,
+}
+
+impl Invasion {
+    pub fn new(
+        width: u32,
+        height: u32,
+        topology: GridTopology,
+        seed: u64,
+        injection: Injection,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let cells = width * height;
+        let mut invasion = Self {
+            width,
+            height,
+            topology,
+            thresholds: (0..cells).map(|_| rng.random()).collect(),
+            invaded_at: vec![0; cells as usize],
+            queued: vec![false; cells as usize],
+            frontier: BinaryHeap::new(),
+            sides: sides(width, height),
+            goal: 0,
+            invaded: 0,
+            broke_through: false,
+        };
+        match injection {
+            Injection::LeftSide => {
+                invasion.goal = RIGHT;
+                for y in 0..height {
+                    invasion.enqueue(y * width);
+                }
+            }
+            Injection::Cell(cell) => {
+                invasion.goal = LEFT | RIGHT | TOP | BOTTOM;
+                invasion.enqueue(cell.min(cells - 1));
+            }
+        }
+        invasion
+    }
+
+    fn enqueue(&mut self, cell: u32) {
+        if !self.queued[cell as usize] {
+            self.queued[cell as usize] = true;
+            let key = self.thresholds[cell as usize].to_bits();
+            self.frontier.push(Reverse((key, cell)));
+        }
+    }
+
+    /// Invade up to `steps` more cells, stopping at breakthrough. Returns
+    /// how many were taken.
+    pub fn invade(&mut self, steps: u32) -> u32 {
+        let mut taken = 0;
+        while taken < steps && !self.finished() {
+            let Some(Reverse((_, cell))) = self.frontier.pop() else {
+                break;
+            };
+            self.invaded += 1;
+            self.invaded_at[cell as usize] = self.invaded;
+            self.broke_through |= self.sides[cell as usize] & self.goal != 0;
+            for neighbor in edge_neighbors(self.topology, self.width, self.height, cell) {
+                self.enqueue(neighbor);
+            }
+            taken += 1;
+        }
+        taken
+    }
+
+    /// Broken through, or with nowhere left to go
+    pub fn finished(&self) -> bool {
+        self.broke_through || self.frontier.is_empty()
+    }
+
+    pub fn broke_through(&self) -> bool {
+        self.broke_through
+    }
+
+    /// When `cell` was invaded, counting from 1, or `None` while it's dry
+    pub fn invaded_at(&self, cell: u32) -> Option<u32> {
+        match self.invaded_at[cell as usize] {
+            0 => None,
+            step => Some(step),
+        }
+    }
+
+    pub fn invaded(&self) -> u32 {
+        self.invaded
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
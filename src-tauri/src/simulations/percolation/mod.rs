@@ -0,0 +1,95 @@
+pub mod lattice;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::PercolationModel;
+
+use crate::simulation::preset_manager::{PercolationPresetManager, Preset};
+
+/// Initialize Percolation presets with built-in configurations
+pub fn init_presets(preset_manager: &mut PercolationPresetManager) {
+    use crate::simulations::shared::GridTopology;
+    use settings::{ClusterColoring, InjectionSite, PercolationMode, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Spanning Cluster".to_string(),
+        Settings {
+            coloring: ClusterColoring::Spanning,
+            ramp_spread: 0.05,
+            sweep_seconds: 20.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Cluster Mosaic".to_string(),
+        Settings {
+            coloring: ClusterColoring::Identity,
+            cell_size: 6,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Bond Percolation".to_string(),
+        Settings {
+            mode: PercolationMode::Bond,
+            cell_size: 3,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Hex Critical Point".to_string(),
+        Settings {
+            topology: GridTopology::Hex,
+            cell_size: 6,
+            ramp: false,
+            probability: 0.5,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Honeycomb Bonds".to_string(),
+        Settings {
+            mode: PercolationMode::Bond,
+            topology: GridTopology::Triangle,
+            cell_size: 8,
+            coloring: ClusterColoring::Identity,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Fluid Intrusion".to_string(),
+        Settings {
+            mode: PercolationMode::Invasion,
+            cell_size: 2,
+            invasion_rate: 4000.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Injection Well".to_string(),
+        Settings {
+            mode: PercolationMode::Invasion,
+            injection: InjectionSite::Center,
+            topology: GridTopology::Hex,
+            cell_size: 4,
+            invasion_rate: 800.0,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,199 @@
+//! # Percolation Settings Module
+//!
+//! The lattice and what percolates through it, how the occupation
+//! probability sweeps past the threshold, how fast invasion spreads and how
+//! clusters are colored.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use crate::simulations::shared::{BackgroundLayer, GridTopology};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum PercolationMode {
+    /// Sites open at random, joining open neighbors into clusters
+    #[default]
+    Site,
+    /// Bonds between neighboring sites open at random
+    Bond,
+    /// Fluid pushed in from an injection site, always through the easiest
+    /// opening on its edge
+    Invasion,
+}
+
+impl FromStr for PercolationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "site" => Ok(PercolationMode::Site),
+            "bond" => Ok(PercolationMode::Bond),
+            "invasion" => Ok(PercolationMode::Invasion),
+            _ => Err(format!(
+                "Invalid PercolationMode: '{}'. Expected 'site', 'bond' or 'invasion'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ClusterColoring {
+    /// Larger clusters further up the color scheme, on a log scale up to the
+    /// largest
+    #[default]
+    Size,
+    /// Each cluster its own color
+    Identity,
+    /// Clusters reaching across the grid at the top, the rest dimmed
+    Spanning,
+}
+
+impl FromStr for ClusterColoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "size" => Ok(ClusterColoring::Size),
+            "identity" => Ok(ClusterColoring::Identity),
+            "spanning" => Ok(ClusterColoring::Spanning),
+            _ => Err(format!(
+                "Invalid ClusterColoring: '{}'. Expected 'size', 'identity' or 'spanning'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum InjectionSite {
+    /// The whole left side, until fluid reaches the right
+    #[default]
+    LeftSide,
+    /// The middle of the grid, until fluid reaches any side
+    Center,
+}
+
+impl FromStr for InjectionSite {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "leftside" | "left_side" => Ok(InjectionSite::LeftSide),
+            "center" => Ok(InjectionSite::Center),
+            _ => Err(format!(
+                "Invalid InjectionSite: '{}'. Expected 'left_side' or 'center'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub mode: PercolationMode,
+    pub topology: GridTopology,
+    /// Screen pixels per grid column
+    pub cell_size: u32,
+
+    /// Sweep the probability across the threshold instead of holding it
+    pub ramp: bool,
+    /// Occupation probability while not ramping
+    pub probability: f32,
+    /// How far either side of the threshold a sweep starts and ends
+    pub ramp_spread: f32,
+    pub sweep_seconds: f32,
+    /// Time spent on the finished sweep or invasion before starting over
+    pub hold_seconds: f32,
+
+    /// Cells invaded each second
+    pub invasion_rate: f32,
+    pub injection: InjectionSite,
+
+    pub coloring: ClusterColoring,
+    /// The same seed always gives the same lattice
+    pub seed: u32,
+    /// Move on to the next seed each time a sweep or invasion starts over
+    pub reseed: bool,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            mode: PercolationMode::Site,
+            topology: GridTopology::Square,
+            cell_size: 4,
+            ramp: true,
+            probability: 0.6,
+            ramp_spread: 0.12,
+            sweep_seconds: 12.0,
+            hold_seconds: 3.0,
+            invasion_rate: 1500.0,
+            injection: InjectionSite::LeftSide,
+            coloring: ClusterColoring::Size,
+            seed: 1,
+            reseed: true,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("mode", Rule::OneOf(&["Site", "Bond", "Invasion"])),
+        ("topology", Rule::OneOf(&["Square", "Hex", "Triangle"])),
+        ("cell_size", Rule::Count { min: 1, max: 32 }),
+        ("ramp", Rule::Flag),
+        ("probability", Rule::Range { min: 0.0, max: 1.0 }),
+        ("ramp_spread", Rule::Range { min: 0.0, max: 0.5 }),
+        (
+            "sweep_seconds",
+            Rule::Range {
+                min: 0.5,
+                max: 600.0,
+            },
+        ),
+        (
+            "hold_seconds",
+            Rule::Range {
+                min: 0.0,
+                max: 60.0,
+            },
+        ),
+        (
+            "invasion_rate",
+            Rule::Range {
+                min: 1.0,
+                max: 100_000.0,
+            },
+        ),
+        ("injection", Rule::OneOf(&["LeftSide", "Center"])),
+        ("coloring", Rule::OneOf(&["Size", "Identity", "Spanning"])),
+        (
+            "seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+        ("reseed", Rule::Flag),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("topology", SettingCategory::Generators),
+    ("seed", SettingCategory::Generators),
+    ("reseed", SettingCategory::Generators),
+    ("coloring", SettingCategory::Colors),
+]);
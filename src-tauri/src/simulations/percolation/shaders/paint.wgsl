@@ -0,0 +1,43 @@
+// Colors the display texture from the positions in the color scheme worked
+// out for each cell on the CPU. Square grids get a texel per cell; hex and
+// triangle grids are drawn at screen resolution, each texel taking the
+// color of the cell it falls in.
+
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    topology: u32,
+    display_width: u32,
+    display_height: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> cells: array<u32>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+@group(0) @binding(3) var output_texture: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8)
+fn paint(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.display_width || id.y >= params.display_height) {
+        return;
+    }
+
+    let grid_size = vec2<u32>(params.grid_width, params.grid_height);
+    let display_size = vec2<f32>(f32(params.display_width), f32(params.display_height));
+    let point = (vec2<f32>(id.xy) + 0.5) / display_size * vec2<f32>(grid_size);
+    // The grid doesn't wrap, but texels past the last half cell of a hex or
+    // triangle row still need a cell to show
+    let cell = topology_wrap(topology_cell_at(params.topology, point), grid_size);
+    let index = min(cells[cell.y * params.grid_width + cell.x], 255u);
+
+    let color = vec4<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0),
+        1.0
+    );
+    textureStore(output_texture, vec2<i32>(id.xy), color);
+}
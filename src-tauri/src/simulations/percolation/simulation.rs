@@ -0,0 +1,944 @@
+//! # Percolation Simulation Module
+//!
+//! Site and bond percolation with the occupation probability sweeping
+//! across the critical threshold, and invasion percolation pushing fluid
+//! into the lattice, on square, hex or triangle cells; see [`lattice`] for
+//! how clusters are found.
+//!
+//! ## Technical Overview
+//!
+//! Clusters are tracked on the CPU, since union-find has no good GPU form
+//! and only the newly opened sites change each frame:
+//! 1. The probability moves along its sweep and the lattice opens whatever
+//!    falls below it, or the invasion takes its next cells.
+//! 2. When anything changed, each cell's place in the color scheme is
+//!    worked out from its cluster, or from when it was invaded, and
+//!    uploaded.
+//! 3. A compute pass paints those into the display texture, with hex and
+//!    triangle cells drawn out at about the surface size so their edges
+//!    show, and the texture is drawn through the camera with infinite
+//!    tiling.
+//!
+//! After a sweep or invasion finishes and has been held for a moment, it
+//! starts over, on the next seed if reseeding. Clicking during invasion
+//! starts a new one from the cell under the cursor.
+//!
+//! [`lattice`]: super::lattice
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor,
+    Buffer, BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    FilterMode, PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, Sampler,
+    SamplerDescriptor, ShaderStages, SurfaceConfiguration, Texture, TextureFormat, TextureView,
+    TextureViewDescriptor,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager, GridTopology};
+use crate::simulations::traits::Simulation;
+
+use super::lattice::{Injection, Invasion, Lattice, Occupation, critical_probability};
+use super::settings::{ClusterColoring, InjectionSite, PercolationMode, Settings};
+use super::shaders::{PAINT_SHADER, RENDER_INFINITE_SHADER};
+use super::state::State;
+
+/// Closed sites and dry cells sit at the bottom of the color scheme, and
+/// everything else starts this far up so it stands out from them
+const OPEN_FLOOR: f32 = 0.15;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    topology: u32,
+    display_width: u32,
+    display_height: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct RenderParams {
+    filtering_mode: u32, // 0 = nearest, 1 = linear, 2 = lanczos
+    _pad1: u32,
+    _pad2: u32,
+    _pad3: u32,
+}
+
+/// Everything the grid's bind groups point at besides the grid itself
+#[derive(Debug)]
+struct Resources {
+    paint_bind_group_layout: BindGroupLayout,
+    render_infinite_bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    texture_render_params_buffer: Buffer,
+}
+
+/// The colors uploaded for each cell and the texture they're painted into,
+/// remade when the grid changes size
+#[derive(Debug)]
+struct Grid {
+    width: u32,
+    height: u32,
+    display_width: u32,
+    display_height: u32,
+    cells: Buffer,
+    _display_texture: Texture,
+    paint_bind_group: BindGroup,
+    render_infinite_bind_group: BindGroup,
+}
+
+impl Grid {
+    fn new(
+        device: &Device,
+        (width, height): (u32, u32),
+        (display_width, display_height): (u32, u32),
+        resources: &Resources,
+    ) -> Self {
+        let cells = device.create_buffer(&BufferDescriptor {
+            label: Some("Percolation Cells Buffer"),
+            size: (width * height) as u64 * std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let display_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Percolation Display Texture"),
+            size: wgpu::Extent3d {
+                width: display_width,
+                height: display_height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let display_view = display_texture.create_view(&TextureViewDescriptor::default());
+
+        let paint_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Percolation Paint Bind Group"),
+            layout: &resources.paint_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &resources.params_buffer),
+                resource_helpers::buffer_entry(1, &cells),
+                resource_helpers::buffer_entry(2, &resources.lut_buffer),
+                resource_helpers::texture_view_entry(3, &display_view),
+            ],
+        });
+
+        let render_infinite_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Percolation Render Infinite Bind Group"),
+            layout: &resources.render_infinite_bind_group_layout,
+            entries: &[
+                resource_helpers::texture_view_entry(0, &display_view),
+                resource_helpers::sampler_bind_entry(1, &resources.sampler),
+                resource_helpers::buffer_entry(2, &resources.texture_render_params_buffer),
+            ],
+        });
+
+        Self {
+            width,
+            height,
+            display_width,
+            display_height,
+            cells,
+            _display_texture: display_texture,
+            paint_bind_group,
+            render_infinite_bind_group,
+        }
+    }
+}
+
+/// What's running on the grid
+#[derive(Debug)]
+enum Process {
+    Sweep(Lattice),
+    Invasion(Invasion),
+}
+
+#[derive(Debug)]
+pub struct PercolationModel {
+    pub settings: Settings,
+    pub state: State,
+    process: Process,
+    // Fractional cells carried between frames while invading
+    pending_invasion: f32,
+    // Seconds since the sweep or invasion finished
+    held: f32,
+    // Whether the cell colors need working out again
+    dirty: bool,
+
+    // GPU resources
+    paint_pipeline: ComputePipeline,
+    render_infinite_pipeline: RenderPipeline,
+    resources: Resources,
+    grid: Grid,
+    camera_bind_group: BindGroup,
+
+    // Camera for infinite rendering
+    pub camera: Camera,
+
+    // Surface size the grid is laid over
+    width: u32,
+    height: u32,
+}
+
+/// Position in the color scheme of each cell, as a LUT index
+fn lut_index(position: f32) -> u32 {
+    (position.clamp(0.0, 1.0) * 255.0).round() as u32
+}
+
+/// LUT indices for the clusters of `lattice` colored by `coloring`, with
+/// closed sites at 0
+pub fn cluster_colors(lattice: &mut Lattice, coloring: ClusterColoring) -> Vec<u32> {
+    let log_largest = (lattice.largest_cluster().max(2) as f32).ln();
+    (0..lattice.width() * lattice.height())
+        .map(|cell| {
+            let Some(root) = lattice.cluster(cell) else {
+                return 0;
+            };
+            let position = match coloring {
+                ClusterColoring::Size => {
+                    let size = lattice.cluster_size(root) as f32;
+                    OPEN_FLOOR + (1.0 - OPEN_FLOOR) * size.ln() / log_largest
+                }
+                ClusterColoring::Identity => {
+                    // Scatter neighboring roots across the scheme
+                    let hash = root.wrapping_mul(0x9e37_79b9) >> 8;
+                    OPEN_FLOOR + (1.0 - OPEN_FLOOR) * hash as f32 / (1 << 24) as f32
+                }
+                ClusterColoring::Spanning if lattice.cluster_spans(root) => 1.0,
+                ClusterColoring::Spanning => 0.35,
+            };
+            lut_index(position).max(1)
+        })
+        .collect()
+}
+
+/// LUT indices for `invasion`, rising with when each cell was invaded so
+/// the advancing front is brightest, with dry cells at 0
+pub fn invasion_colors(invasion: &Invasion) -> Vec<u32> {
+    let invaded = invasion.invaded().max(1) as f32;
+    (0..invasion.width() * invasion.height())
+        .map(|cell| match invasion.invaded_at(cell) {
+            Some(step) => {
+                let position = OPEN_FLOOR + (1.0 - OPEN_FLOOR) * step as f32 / invaded;
+                lut_index(position).max(1)
+            }
+            None => 0,
+        })
+        .collect()
+}
+
+fn occupation(mode: PercolationMode) -> Occupation {
+    match mode {
+        PercolationMode::Bond => Occupation::Bond,
+        _ => Occupation::Site,
+    }
+}
+
+impl PercolationModel {
+    /// Calculate the number of tiles needed for infinite rendering based on zoom level
+    fn calculate_tile_count(&self) -> u32 {
+        let zoom = self.camera.zoom;
+        // Each tile covers 2.0 world units, so we need enough tiles to cover the visible area
+        let visible_world_size = 2.0 / zoom;
+        let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
+        let min_tiles = if zoom < 0.1 { 7 } else { 5 };
+        tiles_needed.max(min_tiles).min(1024)
+    }
+
+    fn grid_size(&self) -> (u32, u32) {
+        self.settings
+            .topology
+            .grid_size(self.width, self.height, self.settings.cell_size)
+    }
+
+    /// A texel per cell for square grids. Other shapes only show at several
+    /// texels per cell, so they're painted at about the surface size.
+    fn display_size(&self, (width, height): (u32, u32)) -> (u32, u32) {
+        let topology = self.settings.topology;
+        if topology == GridTopology::Square {
+            return (width, height);
+        }
+        let column_width = self.settings.cell_size.max(1);
+        let row_height = topology.row_height() * column_width as f32;
+        (
+            width * column_width,
+            ((height as f32 * row_height).round() as u32).max(1),
+        )
+    }
+
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let paint_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Percolation Paint Shader"),
+            source: wgpu::ShaderSource::Wgsl(PAINT_SHADER.into()),
+        });
+        let render_infinite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Percolation Render Infinite Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_INFINITE_SHADER.into()),
+        });
+
+        // Nearest filtering keeps the cells crisp when zoomed in
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Percolation Sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Percolation Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Percolation LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let render_params = RenderParams {
+            filtering_mode: app_settings.texture_filtering.into(),
+            _pad1: 0,
+            _pad2: 0,
+            _pad3: 0,
+        };
+        let texture_render_params_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Percolation Texture Render Params Buffer"),
+                contents: bytemuck::cast_slice(&[render_params]),
+                usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            });
+
+        let paint_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Percolation Paint Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_texture_entry(
+                    3,
+                    ShaderStages::COMPUTE,
+                    wgpu::StorageTextureAccess::WriteOnly,
+                    TextureFormat::Rgba8Unorm,
+                ),
+            ],
+        });
+
+        let render_infinite_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Percolation Render Infinite Bind Group Layout"),
+                entries: &[
+                    resource_helpers::texture_entry(
+                        0,
+                        ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        1,
+                        ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                    resource_helpers::uniform_buffer_entry(2, ShaderStages::FRAGMENT),
+                ],
+            });
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX,
+                )],
+            });
+
+        let paint_pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Percolation Paint Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Percolation Paint Pipeline Layout"),
+                bind_group_layouts: &[&paint_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            module: &paint_module,
+            entry_point: Some("paint"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let render_infinite_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Percolation Render Infinite Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Percolation Render Infinite Pipeline Layout"),
+                bind_group_layouts: &[
+                    &render_infinite_bind_group_layout,
+                    &camera_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_infinite_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_infinite_module,
+                entry_point: Some("fs_main_texture"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[resource_helpers::buffer_entry(0, camera.buffer())],
+        });
+
+        let resources = Resources {
+            paint_bind_group_layout,
+            render_infinite_bind_group_layout,
+            sampler,
+            params_buffer,
+            lut_buffer,
+            texture_render_params_buffer,
+        };
+        // Replaced by resize_grid once the model exists
+        let grid = Grid::new(device, (1, 1), (1, 1), &resources);
+        let process = Process::Sweep(Lattice::new(1, 1, settings.topology, Occupation::Site, 0));
+
+        let mut model = Self {
+            settings,
+            state,
+            process,
+            pending_invasion: 0.0,
+            held: 0.0,
+            dirty: true,
+            paint_pipeline,
+            render_infinite_pipeline,
+            resources,
+            grid,
+            camera_bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.resize_grid(device, queue)?;
+        Ok(model)
+    }
+
+    /// Remake the grid for the surface size and cell size, and start over
+    /// on it
+    fn resize_grid(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let (width, height) = self.grid_size();
+        let display_size = self.display_size((width, height));
+        self.grid = Grid::new(device, (width, height), display_size, &self.resources);
+        self.state.grid_width = width;
+        self.state.grid_height = height;
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Start the sweep or invasion over on the current seed
+    fn restart(&mut self) {
+        let (width, height) = (self.grid.width, self.grid.height);
+        let injection = match self.settings.injection {
+            InjectionSite::LeftSide => Injection::LeftSide,
+            InjectionSite::Center => Injection::Cell(height / 2 * width + width / 2),
+        };
+        self.start(injection);
+    }
+
+    fn start(&mut self, injection: Injection) {
+        let (width, height) = (self.grid.width, self.grid.height);
+        let (topology, seed) = (self.settings.topology, self.settings.seed as u64);
+        self.process = match self.settings.mode {
+            PercolationMode::Invasion => {
+                Process::Invasion(Invasion::new(width, height, topology, seed, injection))
+            }
+            mode => Process::Sweep(Lattice::new(
+                width,
+                height,
+                topology,
+                occupation(mode),
+                seed,
+            )),
+        };
+        self.state.critical_probability =
+            critical_probability(topology, occupation(self.settings.mode));
+        self.state.cycle_time = 0.0;
+        self.pending_invasion = 0.0;
+        self.held = 0.0;
+        self.dirty = true;
+    }
+
+    /// Where the probability stands this far into the sweep
+    fn probability(&self) -> f32 {
+        if !self.settings.ramp {
+            return self.settings.probability;
+        }
+        let critical = self.state.critical_probability;
+        let spread = self.settings.ramp_spread;
+        let (low, high) = ((critical - spread).max(0.0), (critical + spread).min(1.0));
+        let progress = (self.state.cycle_time / self.settings.sweep_seconds.max(0.1)).min(1.0);
+        low + (high - low) * progress
+    }
+
+    /// Move the sweep or invasion on by `delta_time` seconds
+    fn advance(&mut self, delta_time: f32) {
+        self.state.cycle_time += delta_time;
+        let probability = self.probability();
+        let (changed, finished) = match &mut self.process {
+            Process::Sweep(lattice) => {
+                let changed = lattice.open_to(probability);
+                self.state.open_fraction = lattice.open_fraction();
+                self.state.largest_cluster = lattice.largest_cluster();
+                self.state.spanning = lattice.spanning();
+                // Held probabilities never finish
+                let finished =
+                    self.settings.ramp && self.state.cycle_time >= self.settings.sweep_seconds;
+                (changed, finished)
+            }
+            Process::Invasion(invasion) => {
+                self.pending_invasion += delta_time * self.settings.invasion_rate;
+                let steps = self.pending_invasion as u32;
+                self.pending_invasion -= steps as f32;
+                let changed = invasion.invade(steps) > 0;
+                self.state.invaded = invasion.invaded();
+                self.state.broke_through = invasion.broke_through();
+                (changed, invasion.finished())
+            }
+        };
+        self.state.probability = probability;
+        self.dirty |= changed;
+
+        if finished {
+            self.held += delta_time;
+            if self.held >= self.settings.hold_seconds {
+                if self.settings.reseed {
+                    self.settings.seed = self.settings.seed.wrapping_add(1);
+                }
+                self.restart();
+            }
+        }
+    }
+
+    /// Work out and upload the cell colors, if anything changed
+    fn upload_colors(&mut self, queue: &Arc<Queue>) {
+        if !self.dirty {
+            return;
+        }
+        let colors = match &mut self.process {
+            Process::Sweep(lattice) => cluster_colors(lattice, self.settings.coloring),
+            Process::Invasion(invasion) => invasion_colors(invasion),
+        };
+        queue.write_buffer(&self.grid.cells, 0, bytemuck::cast_slice(&colors));
+        self.dirty = false;
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let params = Params {
+            grid_width: self.grid.width,
+            grid_height: self.grid.height,
+            topology: self.settings.topology.shader_index(),
+            display_width: self.grid.display_width,
+            display_height: self.grid.display_height,
+            _pad0: 0,
+            _pad1: 0,
+            _pad2: 0,
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
+    fn encode_paint(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Percolation Paint Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.paint_pipeline);
+        compute_pass.set_bind_group(0, &self.grid.paint_bind_group, &[]);
+        compute_pass.dispatch_workgroups(
+            self.grid.display_width.div_ceil(8),
+            self.grid.display_height.div_ceil(8),
+            1,
+        );
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let tile_count = self.calculate_tile_count();
+        let total_instances = tile_count * tile_count;
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Percolation Infinite Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.render_infinite_pipeline);
+        render_pass.set_bind_group(0, &self.grid.render_infinite_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+        render_pass.draw(0..6, 0..total_instances);
+    }
+
+    fn draw(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        label: &str,
+    ) {
+        self.upload_colors(queue);
+        self.update_params(queue);
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) });
+        self.encode_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+    }
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for PercolationModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.advance(delta_time);
+        self.camera.update(delta_time);
+        self.camera.upload_to_gpu(queue);
+        self.draw(device, queue, surface_view, "Percolation Render");
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // Colors are still worked out so coloring changes show while paused
+        self.draw(device, queue, surface_view, "Percolation Render Paused");
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.resize_grid(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Left click injects fluid under the cursor; sweeps don't take input
+        if mouse_button != 0 || self.settings.mode != PercolationMode::Invasion {
+            return Ok(());
+        }
+
+        // Wrap world coords to the base tile, then flip Y into grid rows
+        let wrapped_x = (world_x + 1.0).rem_euclid(2.0) - 1.0;
+        let wrapped_y = (world_y + 1.0).rem_euclid(2.0) - 1.0;
+        let (width, height) = (self.grid.width, self.grid.height);
+        let (x, y) = self.settings.topology.cell_at((
+            (wrapped_x + 1.0) * 0.5 * width as f32,
+            (1.0 - wrapped_y) * 0.5 * height as f32,
+        ));
+        let x = x.clamp(0, width as i32 - 1) as u32;
+        let y = y.clamp(0, height as i32 - 1) as u32;
+        self.start(Injection::Cell(y * width + x));
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    // Grown on the CPU from the seed, so only the frame times set its pace
+    fn supports_replay(&self) -> bool {
+        true
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.cell_size != self.settings.cell_size
+            || old_settings.topology != self.settings.topology
+        {
+            self.resize_grid(device, queue)?;
+        } else if old_settings.mode != self.settings.mode
+            || old_settings.seed != self.settings.seed
+            || old_settings.injection != self.settings.injection
+        {
+            self.restart();
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.restart();
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.mode = match rng.random_range(0..3) {
+            0 => PercolationMode::Site,
+            1 => PercolationMode::Bond,
+            _ => PercolationMode::Invasion,
+        };
+        self.settings.coloring = match rng.random_range(0..3) {
+            0 => ClusterColoring::Size,
+            1 => ClusterColoring::Identity,
+            _ => ClusterColoring::Spanning,
+        };
+        self.settings.injection = if rng.random_bool(0.5) {
+            InjectionSite::LeftSide
+        } else {
+            InjectionSite::Center
+        };
+        self.settings.ramp_spread = rng.random_range(0.04..0.2);
+        self.settings.seed = rng.random();
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "mode" => {
+                self.settings.mode = value
+                    .as_str()
+                    .unwrap_or("Site")
+                    .parse()
+                    .map_err(|e| format!("Invalid mode: {}", e))?;
+                self.restart();
+            }
+            "topology" => {
+                self.settings.topology = value
+                    .as_str()
+                    .unwrap_or("Square")
+                    .parse()
+                    .map_err(|e| format!("Invalid topology: {}", e))?;
+                self.resize_grid(device, queue)?;
+            }
+            "cell_size" => {
+                self.settings.cell_size = number(setting_name, &value)? as u32;
+                self.resize_grid(device, queue)?;
+            }
+            "ramp" => self.settings.ramp = value.as_bool().unwrap_or(true),
+            "probability" => self.settings.probability = number(setting_name, &value)? as f32,
+            "ramp_spread" => self.settings.ramp_spread = number(setting_name, &value)? as f32,
+            "sweep_seconds" => self.settings.sweep_seconds = number(setting_name, &value)? as f32,
+            "hold_seconds" => self.settings.hold_seconds = number(setting_name, &value)? as f32,
+            "invasion_rate" => self.settings.invasion_rate = number(setting_name, &value)? as f32,
+            "injection" => {
+                self.settings.injection = value
+                    .as_str()
+                    .unwrap_or("LeftSide")
+                    .parse()
+                    .map_err(|e| format!("Invalid injection: {}", e))?;
+                if self.settings.mode == PercolationMode::Invasion {
+                    self.restart();
+                }
+            }
+            "coloring" => {
+                self.settings.coloring = value
+                    .as_str()
+                    .unwrap_or("Size")
+                    .parse()
+                    .map_err(|e| format!("Invalid coloring: {}", e))?;
+                self.dirty = true;
+            }
+            "seed" => {
+                self.settings.seed = number(setting_name, &value)? as u32;
+                self.restart();
+            }
+            "reseed" => self.settings.reseed = value.as_bool().unwrap_or(true),
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Grid size in cells
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Where the probability stands against the lattice's threshold
+    pub probability: f32,
+    pub critical_probability: f32,
+    pub open_fraction: f32,
+
+    // Clusters at the current probability
+    pub largest_cluster: u32,
+    pub spanning: bool,
+
+    // Cells invaded so far, and whether fluid has broken through
+    pub invaded: u32,
+    pub broke_through: bool,
+
+    // Seconds into the current sweep or invasion
+    pub cycle_time: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            grid_width: 0,
+            grid_height: 0,
+            probability: 0.0,
+            critical_probability: 0.0,
+            open_fraction: 0.0,
+            largest_cluster: 0,
+            spanning: false,
+            invaded: 0,
+            broke_through: false,
+            cycle_time: 0.0,
+            color_scheme_name: "MATPLOTLIB_plasma".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
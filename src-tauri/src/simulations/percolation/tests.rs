@@ -0,0 +1,145 @@
+use super::lattice::{Injection, Invasion, Lattice, Occupation, critical_probability};
+use super::settings::ClusterColoring;
+use super::shaders::{PAINT_SHADER, RENDER_INFINITE_SHADER};
+use super::simulation::{cluster_colors, invasion_colors};
+use crate::simulations::shared::GridTopology;
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+const ALL: [GridTopology; 3] = [
+    GridTopology::Square,
+    GridTopology::Hex,
+    GridTopology::Triangle,
+];
+
+/// Every cluster's size, largest first
+fn cluster_sizes(lattice: &mut Lattice) -> Vec<u32> {
+    let roots: Vec<u32> = (0..lattice.width() * lattice.height())
+        .filter(|&cell| lattice.cluster(cell) == Some(cell))
+        .collect();
+    let mut sizes: Vec<u32> = roots
+        .iter()
+        .map(|&root| lattice.cluster_size(root))
+        .collect();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes
+}
+
+#[test]
+fn sweep_runs_from_empty_to_one_cluster() {
+    for topology in ALL {
+        let mut sites = Lattice::new(40, 30, topology, Occupation::Site, 7);
+        assert!(cluster_sizes(&mut sites).is_empty());
+        sites.open_to(1.0);
+        assert_eq!(cluster_sizes(&mut sites), vec![40 * 30]);
+        assert!(sites.spanning());
+
+        // Bond lattices start with every site alone
+        let mut bonds = Lattice::new(40, 30, topology, Occupation::Bond, 7);
+        assert_eq!(bonds.largest_cluster(), 1);
+        assert_eq!(cluster_sizes(&mut bonds).len(), 40 * 30);
+        bonds.open_to(1.0);
+        assert_eq!(cluster_sizes(&mut bonds), vec![40 * 30]);
+    }
+}
+
+#[test]
+fn lowering_the_probability_matches_a_fresh_lattice() {
+    for occupation in [Occupation::Site, Occupation::Bond] {
+        let mut swept = Lattice::new(50, 50, GridTopology::Hex, occupation, 3);
+        swept.open_to(0.8);
+        swept.open_to(0.45);
+        let mut fresh = Lattice::new(50, 50, GridTopology::Hex, occupation, 3);
+        fresh.open_to(0.45);
+        assert_eq!(cluster_sizes(&mut swept), cluster_sizes(&mut fresh));
+        assert_eq!(swept.largest_cluster(), fresh.largest_cluster());
+        assert_eq!(swept.open_fraction(), fresh.open_fraction());
+    }
+}
+
+#[test]
+fn clusters_span_well_past_the_threshold_only() {
+    for topology in ALL {
+        for occupation in [Occupation::Site, Occupation::Bond] {
+            let critical = critical_probability(topology, occupation);
+            let mut lattice = Lattice::new(120, 120, topology, occupation, 11);
+            lattice.open_to(critical - 0.15);
+            assert!(!lattice.spanning(), "{:?} {:?}", topology, occupation);
+            lattice.open_to(critical + 0.15);
+            assert!(lattice.spanning(), "{:?} {:?}", topology, occupation);
+        }
+    }
+}
+
+#[test]
+fn invasion_grows_one_connected_cluster_to_breakthrough() {
+    let (width, height) = (60, 40);
+    let mut invasion = Invasion::new(width, height, GridTopology::Square, 5, Injection::LeftSide);
+    while !invasion.finished() {
+        invasion.invade(100);
+    }
+    assert!(invasion.broke_through());
+    let reached_right = (0..height).any(|y| invasion.invaded_at(y * width + width - 1).is_some());
+    assert!(reached_right);
+
+    // On a square grid every invaded cell past the first column was reached
+    // from an earlier one beside it
+    for cell in 0..width * height {
+        let (x, y) = (cell % width, cell / width);
+        let Some(step) = invasion.invaded_at(cell) else {
+            continue;
+        };
+        if x == 0 {
+            continue;
+        }
+        let earlier = [(x - 1, y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)]
+            .into_iter()
+            .filter(|&(nx, ny)| nx < width && ny < height)
+            .any(|(nx, ny)| {
+                invasion
+                    .invaded_at(ny * width + nx)
+                    .is_some_and(|other| other < step)
+            });
+        assert!(earlier, "({}, {}) was invaded from nowhere", x, y);
+    }
+}
+
+#[test]
+fn colors_leave_closed_and_dry_cells_at_the_bottom() {
+    let mut lattice = Lattice::new(30, 30, GridTopology::Square, Occupation::Site, 2);
+    lattice.open_to(0.5);
+    for coloring in [
+        ClusterColoring::Size,
+        ClusterColoring::Identity,
+        ClusterColoring::Spanning,
+    ] {
+        let colors = cluster_colors(&mut lattice, coloring);
+        for (cell, &color) in colors.iter().enumerate() {
+            assert_eq!(lattice.cluster(cell as u32).is_some(), color > 0);
+            assert!(color <= 255);
+        }
+    }
+
+    // Too few steps to reach a side from the middle
+    let center = Injection::Cell(30 * 60 + 30);
+    let mut invasion = Invasion::new(60, 60, GridTopology::Triangle, 2, center);
+    assert_eq!(invasion.invade(25), 25);
+    let colors = invasion_colors(&invasion);
+    assert_eq!(colors.iter().filter(|&&color| color > 0).count(), 25);
+    assert_eq!(colors.iter().max(), Some(&255));
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Percolation Paint Shader", PAINT_SHADER),
+        ("Percolation Render Infinite Shader", RENDER_INFINITE_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
@@ -0,0 +1,78 @@
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod spiral;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::PhyllotaxisModel;
+
+use crate::simulation::preset_manager::{PhyllotaxisPresetManager, Preset};
+
+/// Initialize Phyllotaxis presets with built-in configurations
+pub fn init_presets(preset_manager: &mut PhyllotaxisPresetManager) {
+    use settings::{ColorBy, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Sunflower Spirals".to_string(),
+        Settings {
+            max_primordia: 3000,
+            primordium_size: 0.55,
+            color_by: ColorBy::Spirals,
+            spiral_count: 34,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Golden Sweep".to_string(),
+        Settings {
+            sweep_amplitude: 1.0,
+            sweep_period: 90.0,
+            growth_rate: 0.0,
+            max_primordia: 2000,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Rational Spokes".to_string(),
+        Settings {
+            divergence_angle: 144.0,
+            growth_rate: 80.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Pinecone".to_string(),
+        Settings {
+            max_primordia: 400,
+            expansion: 0.65,
+            primordium_size: 0.6,
+            growth_rate: 10.0,
+            color_by: ColorBy::Spirals,
+            spiral_count: 13,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Noble Cousin".to_string(),
+        Settings {
+            // 360° over 2 + the golden ratio, the next hardest angle to
+            // approximate by fractions
+            divergence_angle: 99.501_55,
+            color_by: ColorBy::Spirals,
+            spiral_count: 29,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,150 @@
+//! # Phyllotaxis Settings Module
+//!
+//! The angle between primordia and how it's scrubbed or swept, how fast the
+//! head grows and how big it gets, and how the primordia are colored.
+
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::spiral::GOLDEN_ANGLE;
+use crate::simulation::preset_migration::PresetSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorBy {
+    /// From the youngest at the middle to the oldest at the rim
+    #[default]
+    Age,
+    /// By which of the spiral arms each lies on, `spiral_count` of them
+    Spirals,
+}
+
+impl FromStr for ColorBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "age" => Ok(ColorBy::Age),
+            "spirals" => Ok(ColorBy::Spirals),
+            _ => Err(format!(
+                "Invalid ColorBy: '{}'. Expected 'Age' or 'Spirals'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Degrees each primordium turns from the one before
+    pub divergence_angle: f32,
+    /// Degrees either side of the golden angle dragging across the view
+    /// scrubs through
+    pub scrub_range: f32,
+    /// Degrees the angle sweeps back and forth by on its own; 0 holds it
+    /// still
+    pub sweep_amplitude: f32,
+    /// Seconds for one sweep there and back
+    pub sweep_period: f32,
+
+    /// Primordia formed each second
+    pub growth_rate: f32,
+    /// Primordia on a full head; older ones drop off the rim
+    pub max_primordia: u32,
+    /// Power of its age a primordium's distance from the middle grows by;
+    /// 0.5 keeps them all the same size, as on a sunflower
+    pub expansion: f32,
+    /// Radius of each primordium, as a share of the gap to its neighbors
+    pub primordium_size: f32,
+
+    pub color_by: ColorBy,
+    /// Spiral arms colored apart when coloring by spirals; a Fibonacci
+    /// number picks out the spirals the eye sees
+    pub spiral_count: u32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            divergence_angle: GOLDEN_ANGLE as f32,
+            scrub_range: 2.0,
+            sweep_amplitude: 0.0,
+            sweep_period: 60.0,
+            growth_rate: 40.0,
+            max_primordia: 1500,
+            expansion: 0.5,
+            primordium_size: 0.5,
+            color_by: ColorBy::Age,
+            spiral_count: 34,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "divergence_angle",
+            Rule::Range {
+                min: 0.0,
+                max: 360.0,
+            },
+        ),
+        (
+            "scrub_range",
+            Rule::Range {
+                min: 0.01,
+                max: 180.0,
+            },
+        ),
+        (
+            "sweep_amplitude",
+            Rule::Range {
+                min: 0.0,
+                max: 90.0,
+            },
+        ),
+        (
+            "sweep_period",
+            Rule::Range {
+                min: 1.0,
+                max: 600.0,
+            },
+        ),
+        (
+            "growth_rate",
+            Rule::Range {
+                min: 0.0,
+                max: 2000.0,
+            },
+        ),
+        ("max_primordia", Rule::Count { min: 1, max: 50000 }),
+        ("expansion", Rule::Range { min: 0.2, max: 1.0 }),
+        (
+            "primordium_size",
+            Rule::Range {
+                min: 0.05,
+                max: 2.0,
+            },
+        ),
+        ("color_by", Rule::OneOf(&["Age", "Spirals"])),
+        ("spiral_count", Rule::Count { min: 1, max: 233 }),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized).
+/// `spiral_count` only picks which spirals are colored apart.
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("color_by", SettingCategory::Colors),
+    ("spiral_count", SettingCategory::Colors),
+]);
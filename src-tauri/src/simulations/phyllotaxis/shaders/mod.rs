@@ -0,0 +1,4 @@
+pub const RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("render.wgsl")
+);
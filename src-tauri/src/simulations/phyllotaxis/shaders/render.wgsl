@@ -0,0 +1,78 @@
+// Draws each primordium the CPU lays out as a disc, shaded a little
+// toward its edge so the head looks packed with seeds.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    // Box heights per pixel, for antialiasing
+    pixel_size: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+struct Disc {
+    position: vec2<f32>,
+    radius: f32,
+    // Where the primordium falls in the color scheme
+    color: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> discs: array<Disc>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+// The head is round whatever the window's shape
+fn to_ndc(point: vec2<f32>) -> vec2<f32> {
+    return (vec2<f32>(point.x / params.aspect, point.y) - params.view_center) * params.view_zoom;
+}
+
+struct DiscOutput {
+    @builtin(position) position: vec4<f32>,
+    // Offset from the disc's middle, in box heights
+    @location(0) local: vec2<f32>,
+    @location(1) @interpolate(flat) instance: u32,
+}
+
+// A square around the disc, a pixel wider all round for the soft edge
+@vertex
+fn vs_disc(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> DiscOutput {
+    let corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let disc = discs[instance_index];
+    let local = corners[vertex_index] * (disc.radius + params.pixel_size);
+    return DiscOutput(vec4<f32>(to_ndc(disc.position + local), 0.0, 1.0), local, instance_index);
+}
+
+@fragment
+fn fs_disc(input: DiscOutput) -> @location(0) vec4<f32> {
+    let disc = discs[input.instance];
+    let distance = length(input.local) - disc.radius;
+    let half_pixel = params.pixel_size * 0.5;
+    let coverage = 1.0 - smoothstep(-half_pixel, half_pixel, distance);
+    if (coverage <= 0.0) {
+        discard;
+    }
+    let depth = clamp(1.0 - length(input.local) / max(disc.radius, 1e-6), 0.0, 1.0);
+    let color = lut_color(disc.color) * mix(0.6, 1.0, sqrt(depth));
+    return vec4<f32>(color, coverage);
+}
@@ -0,0 +1,587 @@
+//! # Phyllotaxis Simulation Module
+//!
+//! A sunflower head growing from the middle out, primordium by primordium,
+//! each turned from the last by the divergence angle. Dragging the left
+//! mouse button across the view scrubs the angle either side of the golden
+//! angle, so the spokes every nearby fraction lines the primordia up in
+//! can be seen giving way to the even packing at 137.5°; the right button
+//! snaps back to it. The angle can also sweep back and forth on its own.
+//!
+//! The head is laid out on the CPU in [`spiral`](super::spiral) each frame
+//! and drawn by the GPU as discs.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, BlendState, Buffer,
+    BufferDescriptor, BufferUsages, Device, PipelineLayoutDescriptor, Queue, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, SurfaceConfiguration, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, ParticleBuffer,
+};
+use crate::simulations::traits::Simulation;
+
+use super::settings::{ColorBy, Settings};
+use super::shaders::RENDER_SHADER;
+use super::spiral::{self, GOLDEN_ANGLE, Layout, Primordium};
+use super::state::State;
+
+/// Discs room is made for up front; the buffer grows past this as needed
+const INITIAL_DISCS: usize = 4096;
+
+/// Youngest primordia the packing is measured over, enough to show the
+/// pattern without the cost growing with the head
+const PACKING_SAMPLE: usize = 400;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    pixel_size: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct Disc {
+    position: [f32; 2],
+    radius: f32,
+    color: f32,
+}
+
+#[derive(Debug)]
+pub struct PhyllotaxisModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // Primordia formed so far, fractional while the next one swells in
+    grown: f64,
+    // Seconds into the current sweep
+    sweep_time: f32,
+
+    // GPU resources
+    render_pipeline: RenderPipeline,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    disc_buffer: ParticleBuffer<Disc>,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+
+    // The head and its discs laid out for this frame, kept to save
+    // reallocating
+    primordia: Vec<Primordium>,
+    discs: Vec<Disc>,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl PhyllotaxisModel {
+    pub fn new(
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Phyllotaxis Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Phyllotaxis Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Phyllotaxis LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let disc_buffer = ParticleBuffer::uninitialized(
+            device,
+            "Phyllotaxis Disc Buffer",
+            BufferUsages::empty(),
+            INITIAL_DISCS,
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Phyllotaxis Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ),
+                resource_helpers::storage_buffer_entry(
+                    1,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    true,
+                ),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::FRAGMENT, true),
+            ],
+        });
+        let bind_group = create_bind_group(
+            device,
+            &bind_group_layout,
+            &params_buffer,
+            disc_buffer.buffer(),
+            &lut_buffer,
+        );
+
+        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Phyllotaxis Render Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Phyllotaxis Render Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_module,
+                entry_point: Some("vs_disc"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_module,
+                entry_point: Some("fs_disc"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let mut simulation = Self {
+            // A full head to start with, so there's something to look at
+            grown: settings.max_primordia as f64,
+            settings,
+            state,
+            sweep_time: 0.0,
+            render_pipeline,
+            params_buffer,
+            lut_buffer,
+            disc_buffer,
+            bind_group_layout,
+            bind_group,
+            primordia: Vec::new(),
+            discs: Vec::new(),
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        simulation.lay_out();
+        Ok(simulation)
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// The angle the head grows at right now, the sweep added in
+    fn divergence(&self) -> f64 {
+        let period = self.settings.sweep_period.max(1e-3);
+        let sweep = (std::f32::consts::TAU * self.sweep_time / period).sin();
+        (self.settings.divergence_angle + self.settings.sweep_amplitude * sweep) as f64
+    }
+
+    fn layout(&self) -> Layout {
+        Layout {
+            divergence: self.divergence(),
+            expansion: self.settings.expansion as f64,
+            max_primordia: self.settings.max_primordia,
+            primordium_size: self.settings.primordium_size as f64,
+        }
+    }
+
+    /// Lay the head out as it is now, noting how it's packed
+    fn lay_out(&mut self) {
+        let layout = self.layout();
+        spiral::lay_out(self.grown, &layout, &mut self.primordia);
+        self.state.divergence = layout.divergence as f32;
+        self.state.primordia = self.primordia.len() as u32;
+        self.state.packing = spiral::packing(&self.primordia, &layout, PACKING_SAMPLE);
+    }
+
+    /// Start the head over from a single primordium
+    fn restart(&mut self) {
+        self.grown = 0.0;
+        self.sweep_time = 0.0;
+        self.lay_out();
+    }
+
+    /// Grow the disc buffer to fit the head, in doublings so a growing head
+    /// doesn't remake it every frame. Discs past what the GPU can spare
+    /// room for are left out.
+    fn reserve_discs(&mut self, device: &Arc<Device>) {
+        if self.discs.len() <= self.disc_buffer.capacity() {
+            return;
+        }
+        let capacity = self
+            .disc_buffer
+            .reallocate(device, self.discs.len().next_power_of_two());
+        self.discs.truncate(capacity);
+        self.bind_group = create_bind_group(
+            device,
+            &self.bind_group_layout,
+            &self.params_buffer,
+            self.disc_buffer.buffer(),
+            &self.lut_buffer,
+        );
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            pixel_size: 2.0 / (self.camera.zoom * self.height.max(1) as f32),
+            _pad0: 0.0,
+            _pad1: 0.0,
+            _pad2: 0.0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn draw(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, surface_view: &TextureView) {
+        let spirals = self.settings.spiral_count.max(1) as u64;
+        let color_by = self.settings.color_by;
+        self.discs.clear();
+        self.discs
+            .extend(self.primordia.iter().map(|primordium| Disc {
+                position: primordium.position,
+                radius: primordium.radius,
+                color: match color_by {
+                    ColorBy::Age => primordium.age,
+                    ColorBy::Spirals => {
+                        (primordium.birth % spirals) as f32 / (spirals - 1).max(1) as f32
+                    }
+                },
+            }));
+        self.reserve_discs(device);
+        if !self.discs.is_empty() {
+            queue.write_buffer(
+                self.disc_buffer.buffer(),
+                0,
+                bytemuck::cast_slice(&self.discs),
+            );
+        }
+        self.update_params(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Phyllotaxis Render"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Phyllotaxis Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.draw(0..6, 0..self.discs.len() as u32);
+        }
+        queue.submit([encoder.finish()]);
+    }
+}
+
+fn create_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    disc_buffer: &Buffer,
+    lut_buffer: &Buffer,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Phyllotaxis Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, disc_buffer),
+            resource_helpers::buffer_entry(2, lut_buffer),
+        ],
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for PhyllotaxisModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        self.grown += (self.settings.growth_rate * delta_time) as f64;
+        if self.settings.sweep_amplitude > 0.0 {
+            self.sweep_time =
+                (self.sweep_time + delta_time).rem_euclid(self.settings.sweep_period.max(1e-3));
+        }
+        self.lay_out();
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        _world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Left scrubs across the view, right snaps back to the golden angle
+        let angle = match mouse_button {
+            0 => GOLDEN_ANGLE as f32 + world_x.clamp(-1.0, 1.0) * self.settings.scrub_range,
+            2 => GOLDEN_ANGLE as f32,
+            _ => return Ok(()),
+        };
+        self.settings.divergence_angle = angle.clamp(0.0, 360.0);
+        self.lay_out();
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.settings = serde_json::from_value(settings)?;
+        self.lay_out();
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.restart();
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        // Mostly near the golden angle, where the interesting packings are
+        self.settings.divergence_angle = if rng.random_bool(0.7) {
+            GOLDEN_ANGLE as f32 + rng.random_range(-1.5..1.5)
+        } else {
+            rng.random_range(20.0..180.0)
+        };
+        self.settings.growth_rate = rng.random_range(10.0..150.0);
+        self.settings.max_primordia = rng.random_range(300..4000);
+        self.settings.expansion = rng.random_range(0.4..0.7);
+        self.settings.primordium_size = rng.random_range(0.3..0.7);
+        self.settings.color_by = if rng.random_bool(0.5) {
+            ColorBy::Age
+        } else {
+            ColorBy::Spirals
+        };
+        self.settings.spiral_count = [8, 13, 21, 34, 55][rng.random_range(0..5)];
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "divergence_angle" => {
+                self.settings.divergence_angle = number(setting_name, &value)? as f32;
+            }
+            "scrub_range" => self.settings.scrub_range = number(setting_name, &value)? as f32,
+            "sweep_amplitude" => {
+                self.settings.sweep_amplitude = number(setting_name, &value)? as f32;
+                self.sweep_time = 0.0;
+            }
+            "sweep_period" => self.settings.sweep_period = number(setting_name, &value)? as f32,
+            "growth_rate" => self.settings.growth_rate = number(setting_name, &value)? as f32,
+            "max_primordia" => {
+                self.settings.max_primordia = number(setting_name, &value)? as u32;
+            }
+            "expansion" => self.settings.expansion = number(setting_name, &value)? as f32,
+            "primordium_size" => {
+                self.settings.primordium_size = number(setting_name, &value)? as f32;
+            }
+            "color_by" => {
+                self.settings.color_by = value
+                    .as_str()
+                    .unwrap_or("Age")
+                    .parse()
+                    .map_err(|e| format!("Invalid color_by: {}", e))?;
+            }
+            "spiral_count" => self.settings.spiral_count = number(setting_name, &value)? as u32,
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        self.lay_out();
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
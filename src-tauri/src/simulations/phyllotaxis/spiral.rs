@@ -0,0 +1,123 @@
+//! # Phyllotaxis Spiral
+//!
+//! Vogel's model of a sunflower head. Primordia form one at a time at the
+//! middle, each turned from the last by the divergence angle, and drift
+//! outward as more form behind them. The `k`th youngest sits `k` to the
+//! power of the expansion from the middle, a square root keeping the same
+//! area to each of them all the way out.
+//!
+//! Only at angles whose turns are hard to approximate by fractions do the
+//! primordia fill the head evenly. Any fraction close to the angle lines
+//! them up in as many spokes, with gaps between. The golden angle is the
+//! hardest of all to approximate, and packs them best.
+
+/// 360° over the golden ratio squared, 180° times 3 - √5
+pub const GOLDEN_ANGLE: f64 = 137.507_764_050_037_85;
+
+/// Radius of a full head, in box heights
+const HEAD_RADIUS: f64 = 0.95;
+
+/// Nearest-neighbor gap in a hexagonal packing with one primordium to each
+/// π of area, the best any layout can do
+const HEXAGONAL_GAP: f64 = 1.904_626_703_152_660_2;
+
+/// The youngest primordia, left out of the packing as they're still
+/// crowded together at the middle before the pattern settles
+const CROWDED_MIDDLE: usize = 20;
+
+/// How the head is laid out, from the settings of the same names
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+    /// Degrees each primordium turns from the last
+    pub divergence: f64,
+    pub expansion: f64,
+    pub max_primordia: u32,
+    pub primordium_size: f64,
+}
+
+impl Layout {
+    /// Scale of the head, so the oldest primordia reach its rim
+    pub fn spacing(&self) -> f64 {
+        HEAD_RADIUS / (self.max_primordia.max(1) as f64).powf(self.expansion)
+    }
+
+    /// The gap between neighbors `rank` in from the youngest, for the
+    /// primordia to be sized to and their packing measured by
+    fn local_spacing(&self, rank: f64) -> f64 {
+        self.spacing() * (2.0 * self.expansion).sqrt() * rank.max(1.0).powf(self.expansion - 0.5)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Primordium {
+    pub position: [f32; 2],
+    pub radius: f32,
+    /// 0 just formed at the middle, 1 about to drop off the rim
+    pub age: f32,
+    /// Primordia formed before this one
+    pub birth: u64,
+}
+
+/// The primordia on the head once `grown` have formed, youngest first.
+/// The youngest swells in as it forms and the oldest shrinks away as it
+/// reaches the rim, so the head grows smoothly.
+pub fn lay_out(grown: f64, layout: &Layout, primordia: &mut Vec<Primordium>) {
+    primordia.clear();
+    if grown <= 0.0 {
+        return;
+    }
+    let max = layout.max_primordia as f64;
+    let spacing = layout.spacing();
+    let youngest = grown.ceil() as u64 - 1;
+    for birth in (0..=youngest).rev() {
+        let rank = grown - birth as f64;
+        if rank > max {
+            break;
+        }
+        let angle = (birth as f64 * layout.divergence)
+            .rem_euclid(360.0)
+            .to_radians();
+        let distance = spacing * rank.powf(layout.expansion);
+        let swell = rank.min(max - rank + 1.0).min(1.0);
+        let radius = layout.primordium_size * layout.local_spacing(rank) * swell;
+        primordia.push(Primordium {
+            position: [
+                (distance * angle.cos()) as f32,
+                (distance * angle.sin()) as f32,
+            ],
+            radius: radius as f32,
+            age: (rank / max) as f32,
+            birth,
+        });
+    }
+}
+
+/// How evenly the `count` youngest primordia fill the head: their average
+/// gap to the nearest over the gap in a hexagonal packing, near 1 for the
+/// golden angle and near 0 when they line up in spokes
+pub fn packing(primordia: &[Primordium], layout: &Layout, count: usize) -> f32 {
+    let inner = &primordia[..count.min(primordia.len())];
+    if inner.len() <= CROWDED_MIDDLE + 1 {
+        return 0.0;
+    }
+    let measured = &inner[CROWDED_MIDDLE..];
+    let total: f64 = measured
+        .iter()
+        .enumerate()
+        .map(|(index, primordium)| {
+            let rank = index + CROWDED_MIDDLE;
+            let nearest = inner
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != rank)
+                .map(|(_, other)| {
+                    let dx = other.position[0] - primordium.position[0];
+                    let dy = other.position[1] - primordium.position[1];
+                    dx.hypot(dy)
+                })
+                .fold(f32::INFINITY, f32::min);
+            nearest as f64 / layout.local_spacing(rank as f64 + 1.0)
+        })
+        .sum();
+    (total / measured.len() as f64 / HEXAGONAL_GAP) as f32
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Angle the head is growing at right now, swept or not
+    pub divergence: f32,
+    // Primordia on the head
+    pub primordia: u32,
+    // How evenly the middle of the head is packed, 1 at best
+    pub packing: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            divergence: 0.0,
+            primordia: 0,
+            packing: 0.0,
+            color_scheme_name: "MATPLOTLIB_YlGn".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use super::settings::{ColorBy, Settings};
+use super::shaders::RENDER_SHADER;
+use super::spiral::{GOLDEN_ANGLE, Layout, Primordium, lay_out, packing};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn layout(divergence: f64) -> Layout {
+    let settings = Settings::default();
+    Layout {
+        divergence,
+        expansion: settings.expansion as f64,
+        max_primordia: settings.max_primordia,
+        primordium_size: settings.primordium_size as f64,
+    }
+}
+
+fn head(grown: f64, layout: &Layout) -> Vec<Primordium> {
+    let mut primordia = Vec::new();
+    lay_out(grown, layout, &mut primordia);
+    primordia
+}
+
+#[test]
+fn golden_angle_is_a_turn_over_phi_squared() {
+    let phi = (1.0 + 5.0f64.sqrt()) / 2.0;
+    assert!((GOLDEN_ANGLE - 360.0 / (phi * phi)).abs() < 1e-9);
+    assert!((Settings::default().divergence_angle as f64 - GOLDEN_ANGLE).abs() < 1e-4);
+}
+
+#[test]
+fn primordia_drift_out_on_a_square_root_as_the_head_grows() {
+    let layout = layout(GOLDEN_ANGLE);
+    let primordia = head(900.0, &layout);
+    assert_eq!(primordia.len(), 900);
+    let spacing = layout.spacing();
+    for (rank, primordium) in primordia.iter().enumerate() {
+        let distance = primordium.position[0].hypot(primordium.position[1]) as f64;
+        // The youngest formed a whole primordium ago
+        assert!((distance - spacing * (rank as f64 + 1.0).sqrt()).abs() < 1e-5);
+    }
+    // Youngest first, and each turned from the next by the divergence
+    assert_eq!(primordia[0].birth, 899);
+    let [a, b] = [primordia[10].position, primordia[11].position];
+    let turn = (a[1].atan2(a[0]) - b[1].atan2(b[0]))
+        .to_degrees()
+        .rem_euclid(360.0);
+    assert!((turn as f64 - GOLDEN_ANGLE).abs() < 1e-3, "{}", turn);
+
+    // Growing on moves everyone out along the same ray
+    let later = head(950.0, &layout);
+    let before = primordia.iter().find(|p| p.birth == 500).unwrap();
+    let after = later.iter().find(|p| p.birth == 500).unwrap();
+    let along = before.position[0] * after.position[1] - before.position[1] * after.position[0];
+    assert!(along.abs() < 1e-5);
+    assert!(
+        after.position[0].hypot(after.position[1]) > before.position[0].hypot(before.position[1])
+    );
+}
+
+#[test]
+fn a_full_head_drops_its_oldest_off_the_rim() {
+    let layout = layout(GOLDEN_ANGLE);
+    let full = head(5000.0, &layout);
+    assert_eq!(full.len(), layout.max_primordia as usize);
+    let oldest = full.last().unwrap();
+    assert_eq!(oldest.birth, 5000 - layout.max_primordia as u64);
+    assert!(oldest.position[0].hypot(oldest.position[1]) <= 0.951);
+    assert!(full.iter().all(|p| (0.0..=1.0).contains(&p.age)));
+    assert!(head(0.0, &layout).is_empty());
+}
+
+#[test]
+fn new_primordia_swell_in_gradually() {
+    let layout = layout(GOLDEN_ANGLE);
+    let size = |grown: f64| head(grown, &layout)[0].radius;
+    assert!(size(100.2) < size(100.6));
+    assert!(size(100.6) < head(100.6, &layout)[5].radius);
+}
+
+#[test]
+fn the_golden_angle_packs_best() {
+    let packed = |divergence: f64| {
+        let layout = layout(divergence);
+        packing(&head(1500.0, &layout), &layout, 400)
+    };
+    let golden = packed(GOLDEN_ANGLE);
+    assert!(golden > 0.85, "{}", golden);
+    // Close by, and at fractions of a turn, they line up in spokes
+    for divergence in [137.3, 137.6, 140.0, 144.0, 120.0, 180.0] {
+        let other = packed(divergence);
+        assert!(
+            other < golden - 0.1,
+            "{} packs {} against {}",
+            divergence,
+            other,
+            golden
+        );
+    }
+}
+
+#[test]
+fn packing_is_measured_on_the_counted_primordia() {
+    let layout = layout(GOLDEN_ANGLE);
+    assert_eq!(packing(&head(10.0, &layout), &layout, 400), 0.0);
+    assert!(packing(&head(1500.0, &layout), &layout, 100) > 0.0);
+}
+
+#[test]
+fn color_by_parses_from_its_names() {
+    assert_eq!("spirals".parse::<ColorBy>(), Ok(ColorBy::Spirals));
+    assert_eq!("Age".parse::<ColorBy>(), Ok(ColorBy::Age));
+    assert!("petals".parse::<ColorBy>().is_err());
+}
+
+#[tokio::test]
+async fn shader_compiles() {
+    let harness = KernelHarness::new().await;
+    let _ = harness
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Phyllotaxis Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+}
@@ -24,7 +24,7 @@ pub fn init_presets(preset_manager: &mut PrimordialParticlesPresetManager) {
 
     // Load user presets from TOML files
     if let Err(e) = preset_manager.load_user_presets() {
-        eprintln!("Warning: Could not load user presets: {}", e);
+        tracing::warn!("Could not load user presets: {}", e);
     }
 
     let preset_count = preset_manager.get_preset_names().len();
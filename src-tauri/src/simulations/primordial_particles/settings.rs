@@ -1,3 +1,7 @@
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
 use serde::{Deserialize, Serialize};
 
 /// Settings for the Primordial Particles simulation that can be saved in presets
@@ -24,6 +28,10 @@ pub struct Settings {
 
     /// Wrap particles around screen edges if true
     pub wrap_edges: bool,
+
+    /// Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
 }
 
 impl Default for Settings {
@@ -34,10 +42,13 @@ impl Default for Settings {
             velocity: 0.2,
             radius: 0.1,
             wrap_edges: true,
+            background_layer: BackgroundLayer::default(),
         }
     }
 }
 
+impl PresetSchema for Settings {}
+
 impl Settings {
     /// Get alpha in radians for GPU calculations
     pub fn alpha_radians(&self) -> f32 {
@@ -67,3 +78,34 @@ impl Settings {
         self.wrap_edges = true;
     }
 }
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "particle_count",
+            Rule::Count {
+                min: 1000,
+                max: 100000,
+            },
+        ),
+        ("velocity", Rule::Range { min: 0.1, max: 2.0 }),
+        (
+            "radius",
+            Rule::Range {
+                min: 0.005,
+                max: 0.1,
+            },
+        ),
+        ("wrap_edges", Rule::Flag),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized).
+/// α and β are the motion law, so they decide what grows out of the soup.
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("alpha", SettingCategory::Generators),
+    ("beta", SettingCategory::Generators),
+    ("background_layer", SettingCategory::Colors),
+]);
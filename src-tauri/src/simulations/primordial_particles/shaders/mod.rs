@@ -1,7 +1,13 @@
-pub const PARTICLE_UPDATE_SHADER: &str = include_str!("particle_update.wgsl");
-pub const PARTICLE_RENDER_SHADER: &str = include_str!("particle_render.wgsl");
+pub const PARTICLE_UPDATE_SHADER: &str = concat!(
+    include_str!("../../shared/cursor_force.wgsl"),
+    include_str!("../../shared/global_force.wgsl"),
+    include_str!("../../shared/environment_field.wgsl"),
+    include_str!("particle_update.wgsl")
+);
+pub const PARTICLE_RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("particle_render.wgsl")
+);
 pub const BACKGROUND_RENDER_SHADER: &str = include_str!("background_render.wgsl");
 pub const INIT_SHADER: &str = include_str!("init.wgsl");
 pub const DENSITY_COMPUTE_SHADER: &str = include_str!("density_compute.wgsl");
-pub const FADE_VERTEX_SHADER: &str = include_str!("fade_vertex.wgsl");
-pub const FADE_FRAGMENT_SHADER: &str = include_str!("fade_fragment.wgsl");
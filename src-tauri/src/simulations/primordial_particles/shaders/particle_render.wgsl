@@ -45,15 +45,6 @@ struct VertexOutput {
     @location(2) uv: vec2<f32>,
 }
 
-// Convert from sRGB (gamma-corrected) to linear RGB
-fn srgb_to_linear(srgb: f32) -> f32 {
-    if (srgb <= 0.04045) {
-        return srgb / 12.92;
-    } else {
-        return pow((srgb + 0.055) / 1.055, 2.4);
-    }
-}
-
 // Get color from LUT based on intensity (0-1)
 fn get_lut_color(intensity: f32) -> vec3<f32> {
     let index = u32(clamp(intensity * 255.0, 0.0, 255.0));
@@ -34,8 +34,10 @@ struct SimParams {
     cursor_size: f32,
     cursor_strength: f32,
     aspect_ratio: f32,
-    _pad1: f32,
-    _pad0: f32,
+    cursor_mode: u32,  // CURSOR_MODE_* from cursor_force.wgsl
+    cursor_curve: u32,  // CURSOR_CURVE_* of the current mode
+    cursor_time: f32,  // Animates turbulence
+    _pad0: u32,
 }
 
 @group(0) @binding(0)
@@ -47,6 +49,12 @@ var<storage, read_write> particles_out: array<Particle>;
 @group(0) @binding(2)
 var<uniform> sim_params: SimParams;
 
+@group(0) @binding(3)
+var<uniform> global_force: GlobalForce;
+
+@group(0) @binding(4)
+var<storage, read> environment_field: EnvironmentField;
+
 // Calculate distance between two points with optional wrapping
 fn distance_with_wrapping(p1: vec2<f32>, p2: vec2<f32>) -> f32 {
     var dx = p2.x - p1.x;
@@ -143,12 +151,18 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         let to_cursor = sim_params.mouse_position - particle.position;
         let aspect_corrected = vec2<f32>(to_cursor.x * sim_params.aspect_ratio, to_cursor.y);
         let distance = length(aspect_corrected);
-        if (distance <= sim_params.cursor_size && distance > 1e-6) {
-            let distance_factor = 1.0 - (distance / sim_params.cursor_size);
-            var dir = normalize(vec2<f32>(to_cursor.x, to_cursor.y));
-            if (sim_params.mouse_mode == 2u) { // repel
-                dir = -dir;
-            }
+        if (distance <= sim_params.cursor_size && distance > 1e-6 && sim_params.mouse_mode != 0u) {
+            let distance_factor = cursor_falloff(distance / sim_params.cursor_size, sim_params.cursor_curve);
+            let flip = select(1.0, -1.0, sim_params.mouse_mode == 2u); // repel
+            let dir = cursor_shape_force(
+                sim_params.cursor_mode,
+                to_cursor,
+                particle.position,
+                sim_params.mouse_velocity,
+                sim_params.cursor_size,
+                sim_params.cursor_time,
+                flip,
+            );
             // magnitude scaled by proximity and cursor strength (boosted)
             let mag = sim_params.cursor_strength * distance_factor * 4.0;
             cursor_force = dir * mag;
@@ -200,6 +214,12 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     
     particle.position.x += dx;
     particle.position.y += dy;
+
+    // Wind, gravity and the environment field drift the particle without
+    // changing its heading
+    let drift = global_force_at(global_force, particle.position)
+        + environment_bias(particle.position);
+    particle.position += drift * sim_params.dt;
     
     // Apply edge wrapping if enabled (using [-1,1] world space)
     if (sim_params.wrap_edges == 1u) {
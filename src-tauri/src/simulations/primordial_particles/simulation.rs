@@ -1,11 +1,11 @@
 use crate::error::SimulationResult;
 use crate::simulations::primordial_particles::state::{BackgroundColorMode, ForegroundColorMode};
+use crate::simulations::shared::environment_field;
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use crate::simulations::shared::{
-    ColorSchemeManager, ComputePipelineBuilder,
+    BackgroundLayer, ColorSchemeManager, ComputePipelineBuilder, EnvironmentField, HealthCheck,
+    HealthIssue, HealthProbe, PingPongParticleBuffers, RewindResource, Trails,
     camera::Camera,
-    ping_pong_buffers::PingPongBuffers,
-    ping_pong_render_textures::PingPongRenderTextures,
     post_processing::{PostProcessingResources, PostProcessingState},
 };
 use bytemuck::{Pod, Zeroable};
@@ -41,8 +41,10 @@ pub struct SimParams {
     pub cursor_size: f32,
     pub cursor_strength: f32,
     pub aspect_ratio: f32,
-    pub _pad1: f32,
-    pub _pad0: f32,
+    pub cursor_mode: u32,  // See `CursorMode::gpu_id`
+    pub cursor_curve: u32, // See `StrengthCurve::gpu_id`
+    pub cursor_time: f32,  // Animates turbulence
+    pub _pad0: u32,
 }
 
 #[repr(C)]
@@ -104,8 +106,10 @@ impl Default for SimParams {
             cursor_size: 0.20,
             cursor_strength: 1.0,
             aspect_ratio: 1.0,
-            _pad1: 0.0,
-            _pad0: 0.0,
+            cursor_mode: 0,
+            cursor_curve: 0,
+            cursor_time: 0.0,
+            _pad0: 0,
         }
     }
 }
@@ -114,8 +118,10 @@ impl Default for SimParams {
 #[derive(Debug)]
 pub struct PrimordialParticlesModel {
     // GPU resources
-    pub particle_buffers: PingPongBuffers,
+    pub particle_buffers: PingPongParticleBuffers<super::state::Particle>,
     pub sim_params_buffer: wgpu::Buffer,
+    pub global_force_buffer: wgpu::Buffer,
+    pub environment_field_buffer: wgpu::Buffer,
     pub init_params_buffer: wgpu::Buffer,
     pub render_params_buffer: wgpu::Buffer,
     pub background_params_buffer: wgpu::Buffer,
@@ -165,19 +171,7 @@ pub struct PrimordialParticlesModel {
     pub lut_bind_group_layout: wgpu::BindGroupLayout,
 
     // Trail/trace infrastructure
-    pub trail_textures: PingPongRenderTextures,
-
-    // Fade pipeline for traces
-    pub fade_pipeline: wgpu::RenderPipeline,
-    pub fade_bind_group_layout: wgpu::BindGroupLayout,
-    pub fade_bind_group: wgpu::BindGroup,
-    pub fade_uniforms_buffer: wgpu::Buffer,
-
-    // Blit pipeline to copy trail texture to surface
-    pub blit_pipeline: wgpu::RenderPipeline,
-    pub blit_bind_group_layout: wgpu::BindGroupLayout,
-    pub blit_bind_group: wgpu::BindGroup,
-    pub blit_sampler: wgpu::Sampler,
+    pub trails: Trails,
 
     // Offscreen display for infinite tiling when traces are disabled
     pub display_texture: wgpu::Texture,
@@ -203,27 +197,15 @@ impl PrimordialParticlesModel {
         settings: &Settings,
         state: &State,
     ) -> SimulationResult<Self> {
-        // Create ping-pong particle buffers and initialize both
-        let particle_stride = std::mem::size_of::<super::state::Particle>() as u64;
-        let particle_buffer_size = particle_stride * state.particle_count as u64;
-        let particle_buffers = PingPongBuffers::new(
+        // Create ping-pong particle buffers, both holding the initial particles
+        let particle_buffers = PingPongParticleBuffers::new(
             device,
-            particle_buffer_size,
-            wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::VERTEX
-                | wgpu::BufferUsages::COPY_DST
-                | wgpu::BufferUsages::COPY_SRC,
-            "Primordial Particles Buffer",
-        );
-        queue.write_buffer(
-            particle_buffers.current_buffer(),
-            0,
-            bytemuck::cast_slice(&state.particles),
-        );
-        queue.write_buffer(
-            particle_buffers.inactive_buffer(),
-            0,
-            bytemuck::cast_slice(&state.particles),
+            [
+                "Primordial Particles Buffer A",
+                "Primordial Particles Buffer B",
+            ],
+            wgpu::BufferUsages::VERTEX,
+            &state.particles,
         );
 
         // Create simulation parameters buffer
@@ -246,8 +228,10 @@ impl PrimordialParticlesModel {
             cursor_size: 0.20,
             cursor_strength: 1.0,
             aspect_ratio: surface_config.width as f32 / surface_config.height as f32,
-            _pad1: 0.0,
-            _pad0: 0.0,
+            cursor_mode: state.cursor_force.mode.gpu_id(),
+            cursor_curve: state.cursor_force.curve().gpu_id(),
+            cursor_time: state.cursor_force.time(),
+            _pad0: 0,
         };
 
         let sim_params_buffer = resource_helpers::create_uniform_buffer_with_data(
@@ -256,6 +240,19 @@ impl PrimordialParticlesModel {
             &[sim_params],
         );
 
+        let global_force_buffer = resource_helpers::create_uniform_buffer_with_data(
+            device,
+            "Primordial Particles Global Force Buffer",
+            &[state.global_force.uniform()],
+        );
+        // Zeroed until a field is set, which reads as no strength
+        let environment_field_buffer = resource_helpers::create_storage_buffer(
+            device,
+            "Primordial Particles Environment Field Buffer",
+            environment_field::BUFFER_SIZE,
+            false,
+        );
+
         // Create initialization parameters buffer
         let init_params = InitParams {
             start_index: 0,
@@ -324,6 +321,10 @@ impl PrimordialParticlesModel {
                     resource_helpers::storage_buffer_entry(1, wgpu::ShaderStages::COMPUTE, false),
                     // binding 2: uniforms
                     resource_helpers::uniform_buffer_entry(2, wgpu::ShaderStages::COMPUTE),
+                    // binding 3: global force
+                    resource_helpers::uniform_buffer_entry(3, wgpu::ShaderStages::COMPUTE),
+                    // binding 4: environment field
+                    resource_helpers::storage_buffer_entry(4, wgpu::ShaderStages::COMPUTE, true),
                 ],
             });
 
@@ -335,6 +336,8 @@ impl PrimordialParticlesModel {
                 resource_helpers::buffer_entry(0, particle_buffers.inactive_buffer()),
                 resource_helpers::buffer_entry(1, particle_buffers.current_buffer()),
                 resource_helpers::buffer_entry(2, &sim_params_buffer),
+                resource_helpers::buffer_entry(3, &global_force_buffer),
+                resource_helpers::buffer_entry(4, &environment_field_buffer),
             ],
         });
         let compute_bind_group2 = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -344,6 +347,8 @@ impl PrimordialParticlesModel {
                 resource_helpers::buffer_entry(0, particle_buffers.current_buffer()),
                 resource_helpers::buffer_entry(1, particle_buffers.inactive_buffer()),
                 resource_helpers::buffer_entry(2, &sim_params_buffer),
+                resource_helpers::buffer_entry(3, &global_force_buffer),
+                resource_helpers::buffer_entry(4, &environment_field_buffer),
             ],
         });
 
@@ -692,7 +697,9 @@ impl PrimordialParticlesModel {
         // Infinite tiling pipeline using shared shader
         let infinite_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Primordial Particles Infinite Render Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("../shared/infinite_render.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl(
+                crate::simulations::shared::INFINITE_RENDER_SHADER.into(),
+            ),
         });
 
         // Bind group layout for display texture + sampler + params
@@ -766,249 +773,21 @@ impl PrimordialParticlesModel {
             });
 
         // Create trail textures for persistent trails
-        let trail_textures = PingPongRenderTextures::new(
+        let trails = Trails::new(
             device,
-            surface_config.width,
-            surface_config.height,
-            surface_config.format,
             "Primordial Particles Trail Texture",
-        );
-
-        // Create fade shader modules
-        let fade_vertex_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Primordial Particles Fade Vertex Shader"),
-            source: wgpu::ShaderSource::Wgsl(shaders::FADE_VERTEX_SHADER.into()),
-        });
-
-        let fade_fragment_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Primordial Particles Fade Fragment Shader"),
-            source: wgpu::ShaderSource::Wgsl(shaders::FADE_FRAGMENT_SHADER.into()),
-        });
-
-        // Create fade bind group layout
-        let fade_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Primordial Particles Fade Bind Group Layout"),
-                entries: &[
-                    resource_helpers::uniform_buffer_entry(0, wgpu::ShaderStages::FRAGMENT),
-                    resource_helpers::texture_entry(
-                        1,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::TextureSampleType::Float { filterable: true },
-                        wgpu::TextureViewDimension::D2,
-                    ),
-                    resource_helpers::sampler_entry(
-                        2,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::SamplerBindingType::Filtering,
-                    ),
-                ],
-            });
-
-        // Create fade uniforms buffer
-        let fade_uniforms = [0.01f32, 0.0f32, 0.0f32, 0.0f32]; // fade_amount, _pad1, _pad2, _pad3
-        let fade_uniforms_buffer = resource_helpers::create_uniform_buffer_with_data(
-            device,
-            "Primordial Particles Fade Uniforms Buffer",
-            &fade_uniforms,
-        );
-
-        // Create fade pipeline
-        let fade_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Primordial Particles Fade Pipeline Layout"),
-            bind_group_layouts: &[&fade_bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let fade_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Primordial Particles Fade Pipeline"),
-            layout: Some(&fade_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &fade_vertex_shader,
-                entry_point: Some("main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &fade_fragment_shader,
-                entry_point: Some("main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_config.format, // Use surface format
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        });
-
-        // Create blit sampler
-        let blit_sampler = resource_helpers::create_linear_sampler(
-            device,
-            "Primordial Particles Blit Sampler",
+            (surface_config.width, surface_config.height),
+            state.trace_resolution_scale,
+            surface_config.format,
             wgpu::FilterMode::Linear,
-        );
-
-        // Create blit bind group layout
-        let blit_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Primordial Particles Blit Bind Group Layout"),
-                entries: &[
-                    resource_helpers::texture_entry(
-                        0,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::TextureSampleType::Float { filterable: true },
-                        wgpu::TextureViewDimension::D2,
-                    ),
-                    resource_helpers::sampler_entry(
-                        1,
-                        wgpu::ShaderStages::FRAGMENT,
-                        wgpu::SamplerBindingType::Filtering,
-                    ),
-                ],
-            });
-
-        // Create blit vertex shader (fullscreen triangle)
-        let blit_vertex_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Primordial Particles Blit Vertex Shader"),
-            source: wgpu::ShaderSource::Wgsl(
-                r#"
-                struct VertexOutput {
-                    @builtin(position) position: vec4<f32>,
-                    @location(0) uv: vec2<f32>,
-                }
-
-                @vertex
-                fn main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
-                    var positions = array<vec2<f32>, 3>(
-                        vec2<f32>(-1.0, -1.0),
-                        vec2<f32>(3.0, -1.0),
-                        vec2<f32>(-1.0, 3.0)
-                    );
-                    
-                    var uvs = array<vec2<f32>, 3>(
-                        vec2<f32>(0.0, 1.0),
-                        vec2<f32>(2.0, 1.0),
-                        vec2<f32>(0.0, -1.0)
-                    );
-                    
-                    var output: VertexOutput;
-                    output.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
-                    output.uv = uvs[vertex_index];
-                    return output;
-                }
-                "#
-                .into(),
-            ),
-        });
-
-        // Create blit fragment shader
-        let blit_fragment_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Primordial Particles Blit Fragment Shader"),
-            source: wgpu::ShaderSource::Wgsl(
-                r#"
-                @group(0) @binding(0) var trail_texture: texture_2d<f32>;
-                @group(0) @binding(1) var trail_sampler: sampler;
-
-                struct VertexOutput {
-                    @builtin(position) position: vec4<f32>,
-                    @location(0) uv: vec2<f32>,
-                }
-
-                @fragment
-                fn main(input: VertexOutput) -> @location(0) vec4<f32> {
-                    return textureSample(trail_texture, trail_sampler, input.uv);
-                }
-                "#
-                .into(),
-            ),
-        });
-
-        // Create blit pipeline
-        let blit_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Primordial Particles Blit Pipeline Layout"),
-            bind_group_layouts: &[&blit_bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let blit_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Primordial Particles Blit Pipeline"),
-            layout: Some(&blit_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &blit_vertex_shader,
-                entry_point: Some("main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &blit_fragment_shader,
-                entry_point: Some("main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_config.format, // Use surface format
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        });
-
-        // Create fade bind group (initially uses current texture)
-        let fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Primordial Particles Fade Bind Group"),
-            layout: &fade_bind_group_layout,
-            entries: &[
-                resource_helpers::buffer_entry(0, &fade_uniforms_buffer),
-                resource_helpers::texture_view_entry(1, trail_textures.current_view()),
-                resource_helpers::sampler_bind_entry(2, &blit_sampler),
-            ],
-        });
-
-        // Create blit bind group (initially uses current texture)
-        let blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Primordial Particles Blit Bind Group"),
-            layout: &blit_bind_group_layout,
-            entries: &[
-                resource_helpers::texture_view_entry(0, trail_textures.current_view()),
-                resource_helpers::sampler_bind_entry(1, &blit_sampler),
-            ],
-        });
+        )
+        .with_max_fade(0.05);
 
         let model = Self {
             particle_buffers,
             sim_params_buffer,
+            global_force_buffer,
+            environment_field_buffer,
             init_params_buffer,
             render_params_buffer,
             background_params_buffer,
@@ -1040,15 +819,7 @@ impl PrimordialParticlesModel {
             lut_buffer,
             lut_bind_group,
             lut_bind_group_layout,
-            trail_textures,
-            fade_pipeline,
-            fade_bind_group_layout,
-            fade_bind_group,
-            fade_uniforms_buffer,
-            blit_pipeline,
-            blit_bind_group_layout,
-            blit_bind_group,
-            blit_sampler,
+            trails,
             display_texture,
             display_view,
             display_sampler,
@@ -1125,8 +896,10 @@ impl PrimordialParticlesModel {
             cursor_size: state.cursor_size,
             cursor_strength: state.cursor_strength,
             aspect_ratio: self.camera.viewport_width / self.camera.viewport_height,
-            _pad1: 0.0,
-            _pad0: 0.0,
+            cursor_mode: state.cursor_force.mode.gpu_id(),
+            cursor_curve: state.cursor_force.curve().gpu_id(),
+            cursor_time: state.cursor_force.time(),
+            _pad0: 0,
         };
 
         queue.write_buffer(
@@ -1181,6 +954,13 @@ impl PrimordialParticlesModel {
         self.camera.upload_to_gpu(queue);
 
         // Ensure the GPU sees the latest mouse/cursor and sim parameters before compute
+        self.state.cursor_force.advance(delta_time);
+        self.state.global_force.advance(delta_time);
+        queue.write_buffer(
+            &self.global_force_buffer,
+            0,
+            bytemuck::cast_slice(&[self.state.global_force.uniform()]),
+        );
         self.update_simulation_parameters(queue)?;
 
         // Dispatch compute shader to update particles (ping-pong) - always first
@@ -1237,17 +1017,6 @@ impl PrimordialParticlesModel {
 
         // Render with or without trails
         if self.state.traces_enabled {
-            // Calculate fade amount: convert trace_fade (0-1) to subtraction amount per frame
-            let fade_amount = if self.state.trace_fade < 1.0 {
-                // Invert trace_fade so 0.0 = fast fade, 1.0 = no fade
-                let fade_strength = 1.0 - self.state.trace_fade;
-                fade_strength * 0.05 // Scale to reasonable fade rate
-            } else {
-                0.0 // No fade when trace_fade is 1.0
-            };
-
-            self.update_fade_uniforms(queue, fade_amount);
-
             // Create new encoder for trail rendering
             let mut trail_encoder =
                 device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -1256,27 +1025,14 @@ impl PrimordialParticlesModel {
 
             // Apply fade effect - reads from previous texture, writes to current
             {
-                let mut trail_render_pass =
-                    trail_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                        label: Some("Primordial Particles Trail Render Pass"),
-                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                            view: self.trail_textures.inactive_view(),
-                            resolve_target: None,
-                            ops: wgpu::Operations {
-                                load: wgpu::LoadOp::Clear(self.resolve_background_clear_color()), // Clear with background color
-                                store: wgpu::StoreOp::Store,
-                            },
-                            depth_slice: None,
-                        })],
-                        depth_stencil_attachment: None,
-                        timestamp_writes: None,
-                        occlusion_query_set: None,
-                    });
-
-                // Apply fade effect - reads from previous texture, writes to current
-                trail_render_pass.set_pipeline(&self.fade_pipeline);
-                trail_render_pass.set_bind_group(0, &self.fade_bind_group, &[]);
-                trail_render_pass.draw(0..3, 0..1);
+                let mut trail_render_pass = self.trails.begin_pass(
+                    device,
+                    queue,
+                    &mut trail_encoder,
+                    self.state.trace_fade_curve,
+                    self.state.trace_fade,
+                    self.resolve_background_clear_color(),
+                );
 
                 // Then render particles on top
                 trail_render_pass.set_pipeline(&self.render_pipeline);
@@ -1298,7 +1054,8 @@ impl PrimordialParticlesModel {
 
             // Create a transient bind group sampling from the just-written trail texture
             // This is now safe because we're in a new encoder
-            let trail_read_view = self.trail_textures.inactive_view();
+            self.trails.swap();
+            let trail_read_view = self.trails.view();
             let infinite_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
                 label: Some("Primordial Particles Infinite Trail BG"),
                 layout: &self.infinite_render_bind_group_layout,
@@ -1342,13 +1099,6 @@ impl PrimordialParticlesModel {
 
             // Submit the surface rendering encoder
             queue.submit(std::iter::once(surface_encoder.finish()));
-
-            // Swap trail textures for next frame first so "current" points to the texture we just wrote
-            self.trail_textures.swap();
-
-            // Then update bind groups to reflect the new roles (read from new current)
-            self.update_fade_bind_group(device);
-            self.update_blit_bind_group(device);
         } else {
             // When trails are disabled, render to offscreen display then infinite-tile to surface
             // Create new encoder for offscreen rendering
@@ -1440,6 +1190,17 @@ impl PrimordialParticlesModel {
         Ok(())
     }
 
+    /// Rasterize `field` and upload it for the particle update
+    pub fn set_environment_field(
+        &mut self,
+        queue: &Arc<Queue>,
+        field: EnvironmentField,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(&self.environment_field_buffer, 0, &field.to_gpu_bytes()?);
+        self.state.environment_field = field;
+        Ok(())
+    }
+
     /// Recreate bind groups that reference the particle buffers after buffer recreation
     fn recreate_particle_bind_groups(&mut self, device: &Device) {
         // Recreate compute bind groups for ping-pong
@@ -1450,6 +1211,8 @@ impl PrimordialParticlesModel {
                 resource_helpers::buffer_entry(0, self.particle_buffers.inactive_buffer()),
                 resource_helpers::buffer_entry(1, self.particle_buffers.current_buffer()),
                 resource_helpers::buffer_entry(2, &self.sim_params_buffer),
+                resource_helpers::buffer_entry(3, &self.global_force_buffer),
+                resource_helpers::buffer_entry(4, &self.environment_field_buffer),
             ],
         });
         self.compute_bind_group2 = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -1459,6 +1222,26 @@ impl PrimordialParticlesModel {
                 resource_helpers::buffer_entry(0, self.particle_buffers.current_buffer()),
                 resource_helpers::buffer_entry(1, self.particle_buffers.inactive_buffer()),
                 resource_helpers::buffer_entry(2, &self.sim_params_buffer),
+                resource_helpers::buffer_entry(3, &self.global_force_buffer),
+                resource_helpers::buffer_entry(4, &self.environment_field_buffer),
+            ],
+        });
+
+        // Recreate density bind groups for ping-pong
+        self.density_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Primordial Particles Density Bind Group A"),
+            layout: &self.density_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, self.particle_buffers.current_buffer()),
+                resource_helpers::buffer_entry(1, &self.density_params_buffer),
+            ],
+        });
+        self.density_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Primordial Particles Density Bind Group B"),
+            layout: &self.density_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, self.particle_buffers.inactive_buffer()),
+                resource_helpers::buffer_entry(1, &self.density_params_buffer),
             ],
         });
 
@@ -1524,8 +1307,10 @@ impl PrimordialParticlesModel {
             cursor_size: self.state.cursor_size,
             cursor_strength: self.state.cursor_strength,
             aspect_ratio: self.camera.viewport_width / self.camera.viewport_height,
-            _pad1: 0.0,
-            _pad0: 0.0,
+            cursor_mode: self.state.cursor_force.mode.gpu_id(),
+            cursor_curve: self.state.cursor_force.curve().gpu_id(),
+            cursor_time: self.state.cursor_force.time(),
+            _pad0: 0,
         };
 
         queue.write_buffer(
@@ -1643,47 +1428,6 @@ impl PrimordialParticlesModel {
         Ok(())
     }
 
-    /// Update fade bind group to read from the previous trail texture
-    fn update_fade_bind_group(&mut self, device: &Device) {
-        // Read from the current trail texture (for fade effect)
-        let read_texture_view = self.trail_textures.current_view();
-
-        self.fade_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Primordial Particles Fade Bind Group"),
-            layout: &self.fade_bind_group_layout,
-            entries: &[
-                resource_helpers::buffer_entry(0, &self.fade_uniforms_buffer),
-                resource_helpers::texture_view_entry(1, read_texture_view),
-                resource_helpers::sampler_bind_entry(2, &self.blit_sampler),
-            ],
-        });
-    }
-
-    /// Update blit bind group to read from the current completed trail texture
-    fn update_blit_bind_group(&mut self, device: &Device) {
-        // For blitting, we want to read from the texture we just finished writing to
-        let read_texture_view = self.trail_textures.inactive_view();
-
-        self.blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Primordial Particles Blit Bind Group"),
-            layout: &self.blit_bind_group_layout,
-            entries: &[
-                resource_helpers::texture_view_entry(0, read_texture_view),
-                resource_helpers::sampler_bind_entry(1, &self.blit_sampler),
-            ],
-        });
-    }
-
-    /// Update fade uniforms buffer
-    fn update_fade_uniforms(&self, queue: &Queue, fade_amount: f32) {
-        let fade_uniforms = [fade_amount, 0.0f32, 0.0f32, 0.0f32];
-        queue.write_buffer(
-            &self.fade_uniforms_buffer,
-            0,
-            bytemuck::cast_slice(&fade_uniforms),
-        );
-    }
-
     /// Resolve background color as wgpu::Color for clears
     fn resolve_background_clear_color(&self) -> wgpu::Color {
         match self.state.background_color_mode {
@@ -1744,33 +1488,7 @@ impl PrimordialParticlesModel {
         queue: &Arc<Queue>,
         background_color: wgpu::Color,
     ) {
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Primordial Particles Clear Trail Texture Encoder"),
-        });
-
-        // Clear both trail textures
-        for (i, view) in self.trail_textures.views().iter().enumerate() {
-            let _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some(&format!(
-                    "Clear Trail Texture {} Pass",
-                    if i == 0 { "A" } else { "B" }
-                )),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(background_color),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-        }
-
-        queue.submit(std::iter::once(encoder.finish()));
+        self.trails.clear(device, queue, background_color);
     }
 }
 
@@ -1871,6 +1589,10 @@ impl Simulation for PrimordialParticlesModel {
         self.display_view = display_texture.create_view(&wgpu::TextureViewDescriptor::default());
         self.display_texture = display_texture;
 
+        // Recreate trail textures to match as well
+        self.trails
+            .resize(device, (surface_config.width, surface_config.height));
+
         // Recreate infinite render display bind group (view changed)
         self.render_infinite_display_bind_group =
             device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -1903,33 +1625,13 @@ impl Simulation for PrimordialParticlesModel {
                             self.camera.viewport_height as u32,
                         );
 
-                        // Recreate particle buffers with new size
-                        let particle_stride = std::mem::size_of::<super::state::Particle>() as u64;
-                        let particle_buffer_size = particle_stride * new_count as u64;
-                        self.particle_buffers = PingPongBuffers::new(
-                            device,
-                            particle_buffer_size,
-                            wgpu::BufferUsages::STORAGE
-                                | wgpu::BufferUsages::VERTEX
-                                | wgpu::BufferUsages::COPY_DST
-                                | wgpu::BufferUsages::COPY_SRC,
-                            "Primordial Particles Buffer",
-                        );
-
-                        // Initialize both buffers with new state particles
-                        queue.write_buffer(
-                            self.particle_buffers.current_buffer(),
-                            0,
-                            bytemuck::cast_slice(&self.state.particles),
-                        );
-                        queue.write_buffer(
-                            self.particle_buffers.inactive_buffer(),
-                            0,
-                            bytemuck::cast_slice(&self.state.particles),
-                        );
-
-                        // Recreate bind groups that reference the particle buffers
-                        self.recreate_particle_bind_groups(device);
+                        // Bind groups hold the buffers, so they follow them if they had to grow
+                        if self
+                            .particle_buffers
+                            .upload(device, queue, &self.state.particles)
+                        {
+                            self.recreate_particle_bind_groups(device);
+                        }
 
                         // Update simulation parameters with new particle count
                         self.update_simulation_parameters(queue)?;
@@ -2084,13 +1786,25 @@ impl Simulation for PrimordialParticlesModel {
             "traces_enabled" => {
                 if let Some(v) = value.as_bool() {
                     self.state.traces_enabled = v;
-                    // Note: Trail infrastructure would need to be implemented for this to work
                 }
             }
             "trace_fade" => {
                 if let Some(v) = value.as_f64() {
                     self.state.trace_fade = v as f32;
-                    // Note: Trail infrastructure would need to be implemented for this to work
+                }
+            }
+            "trace_fade_curve" => {
+                if let Ok(curve) = serde_json::from_value(value) {
+                    self.state.trace_fade_curve = curve;
+                }
+            }
+            "trace_resolution_scale" => {
+                if let Some(v) = value.as_f64()
+                    && self.trails.set_resolution_scale(device, v as f32)
+                {
+                    self.state.trace_resolution_scale = self.trails.resolution_scale();
+                    self.trails
+                        .clear(device, queue, self.resolve_background_clear_color());
                 }
             }
             "density_radius" => {
@@ -2153,9 +1867,10 @@ impl Simulation for PrimordialParticlesModel {
             ];
         }
 
-        // Encode mouse button into mode: 0 none, 1 left(attraction)
+        // Encode mouse button into mode: 0 none, 1 left(attraction), 2 right(repulsion)
         let mode = match mouse_button {
             0 => 1u32, // Left click for attraction
+            2 => 2u32, // Right click for repulsion
             _ => 0u32, // Other buttons do nothing
         };
 
@@ -2212,6 +1927,48 @@ impl Simulation for PrimordialParticlesModel {
         })
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        self.particle_buffers.rewind_resources().into()
+    }
+
+    fn health_probes(&self) -> Vec<HealthProbe<'_>> {
+        vec![HealthProbe::vector(
+            "Particle positions",
+            self.particle_buffers.current_buffer(),
+            (std::mem::size_of::<super::state::Particle>() / std::mem::size_of::<f32>()) as u32,
+            0,
+            HealthCheck::Positions,
+        )]
+    }
+
+    fn recover_from_instability(
+        &mut self,
+        issues: &[HealthIssue],
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Steps too long for the edge wrap to bring particles back leave them
+        // out of range, and strong crowding turns every particle into one clump
+        for issue in issues {
+            match issue {
+                HealthIssue::NonFinite { .. } => {
+                    self.settings.velocity *= 0.5;
+                    tracing::info!(
+                        "Reduced velocity to {} after instability",
+                        self.settings.velocity
+                    );
+                }
+                HealthIssue::Collapsed { .. } => {
+                    self.settings.beta *= 0.5;
+                    tracing::info!("Reduced beta to {} after instability", self.settings.beta);
+                }
+                _ => {}
+            }
+        }
+        self.update_simulation_parameters(queue)?;
+        self.reset_runtime_state(device, queue)
+    }
+
     fn save_preset(&self, preset_name: &str) -> SimulationResult<()> {
         // This would typically interact with a preset manager
         // For now, we'll just log that a preset was saved
@@ -2338,6 +2095,14 @@ impl Simulation for PrimordialParticlesModel {
         serde_json::to_value(&self.settings).unwrap_or(Value::Null)
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
     fn get_state(&self) -> Value {
         serde_json::to_value(&self.state).unwrap_or(Value::Null)
     }
@@ -1,3 +1,4 @@
+use crate::simulations::shared::{CursorForceField, EnvironmentField, FadeCurve, GlobalForce};
 use bytemuck::{Pod, Zeroable};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -103,6 +104,12 @@ pub struct State {
     /// Cursor interaction parameters
     pub cursor_size: f32,
     pub cursor_strength: f32,
+    /// Shape of the force the cursor applies
+    pub cursor_force: CursorForceField,
+    /// Wind and gravity, which carry particles along without turning them
+    pub global_force: GlobalForce,
+    /// Bias field particles drift along, uploaded when it changes
+    pub environment_field: EnvironmentField,
 
     /// Grabbed particles for drag interaction
     pub grabbed_particles: Vec<usize>,
@@ -110,6 +117,12 @@ pub struct State {
     /// Trail/trace settings
     pub traces_enabled: bool,
     pub trace_fade: f32,
+    /// How trace_fade takes trails away frame by frame
+    #[serde(default)]
+    pub trace_fade_curve: FadeCurve,
+    /// Trail texture resolution relative to the display
+    #[serde(default = "default_trace_resolution_scale")]
+    pub trace_resolution_scale: f32,
 
     /// Density visualization radius for UI
     pub density_radius: f32,
@@ -127,6 +140,10 @@ pub struct Particle {
     pub grabbed: u32,
 }
 
+fn default_trace_resolution_scale() -> f32 {
+    1.0
+}
+
 impl Default for State {
     fn default() -> Self {
         Self {
@@ -152,11 +169,16 @@ impl Default for State {
             last_mouse_time: 0.0,
             cursor_size: 0.20,
             cursor_strength: 1.0,
+            cursor_force: CursorForceField::default(),
+            global_force: GlobalForce::default(),
+            environment_field: EnvironmentField::default(),
             grabbed_particles: Vec::new(),
 
             // Trail/trace defaults
             traces_enabled: false,
             trace_fade: 0.48,
+            trace_fade_curve: FadeCurve::Linear,
+            trace_resolution_scale: default_trace_resolution_scale(),
 
             // Density visualization defaults
             density_radius: 0.04,
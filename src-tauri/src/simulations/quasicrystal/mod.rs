@@ -0,0 +1,92 @@
+pub mod pattern;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::QuasicrystalModel;
+
+use crate::simulation::preset_manager::{Preset, QuasicrystalPresetManager};
+
+/// Initialize Quasicrystal presets with built-in configurations
+pub fn init_presets(preset_manager: &mut QuasicrystalPresetManager) {
+    use settings::{Pattern, Settings, TileColoring};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Fivefold Waves".to_string(),
+        Settings {
+            symmetry: 5,
+            extent: 8.0,
+            banding: 2.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Fine Rosettes".to_string(),
+        Settings {
+            symmetry: 11,
+            extent: 30.0,
+            banding: 6.0,
+            speed: 0.1,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Penrose".to_string(),
+        Settings {
+            pattern: Pattern::Tiling,
+            symmetry: 5,
+            extent: 8.0,
+            speed: 0.02,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Ammann-Beenker".to_string(),
+        Settings {
+            pattern: Pattern::Tiling,
+            symmetry: 4,
+            extent: 8.0,
+            speed: 0.02,
+            tile_coloring: TileColoring::Orientation,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Twelvefold Tiling".to_string(),
+        Settings {
+            pattern: Pattern::Tiling,
+            symmetry: 6,
+            extent: 10.0,
+            speed: 0.015,
+            edge_width: 0.04,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Turning Heptagrid".to_string(),
+        Settings {
+            pattern: Pattern::Tiling,
+            symmetry: 7,
+            extent: 12.0,
+            speed: 0.01,
+            rotation_speed: 0.05,
+            edge_width: 0.0,
+            tile_coloring: TileColoring::Orientation,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
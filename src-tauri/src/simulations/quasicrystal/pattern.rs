@@ -0,0 +1,135 @@
+//! The geometry both patterns share.
+//!
+//! `symmetry` directions are spread over half a turn, the `j`th at angle
+//! π·j/`symmetry`. The wave pattern adds up one plane wave travelling along
+//! each, which has `2 * symmetry` fold symmetry for odd counts and
+//! `symmetry` fold otherwise.
+//!
+//! The tiling is de Bruijn's multigrid dual. Each direction has a grid of
+//! parallel lines where `dot(p, e_j) + offset_j` is a whole number, and
+//! every crossing of two lines becomes a rhomb with sides along those two
+//! directions. Five directions whose offsets add up to a whole number,
+//! with the alternate signs this file's spacing needs, give the Penrose
+//! tiling.
+//! Moving the offsets shuffles tiles about without ever leaving a gap.
+
+use std::f32::consts::{PI, TAU};
+
+/// Unit vectors along each of the `symmetry` directions
+pub fn directions(symmetry: u32) -> Vec<[f32; 2]> {
+    (0..symmetry)
+        .map(|j| {
+            let angle = PI * j as f32 / symmetry as f32;
+            [angle.cos(), angle.sin()]
+        })
+        .collect()
+}
+
+/// Offsets of each direction's grid at `phase`, a full turn of which
+/// brings them back to where they started.
+///
+/// They're worked out for directions spread over a whole turn, where the
+/// Penrose condition is that they add up to a whole number, then flipped
+/// for the directions [`directions`] turns round by half a turn. An even
+/// share of one on top keeps lines from crossing three at a time where
+/// directions sit a third of a turn apart, which would leave the tiling
+/// overlapping itself. Three directions alone can't meet the condition
+/// without that, so they go without.
+pub fn grid_offsets(symmetry: u32, phase: f32) -> Vec<f32> {
+    let n = symmetry.max(1) as usize;
+    let share = if n == 3 { 0.2 } else { 1.0 / n as f32 };
+    // Both waves sum to zero over the directions for three or more
+    let full_turn: Vec<f32> = (0..n)
+        .map(|m| {
+            let angle = TAU * m as f32 / n as f32;
+            share + 0.3 * (phase + 2.0 * angle).sin() + 0.15 * (0.7 + phase + angle).cos()
+        })
+        .collect();
+    if n.is_multiple_of(2) {
+        return full_turn;
+    }
+    (0..n)
+        .map(|j| {
+            if j % 2 == 0 {
+                full_turn[j / 2]
+            } else {
+                -full_turn[(j + n) / 2]
+            }
+        })
+        .collect()
+}
+
+/// The rhomb a point of the tiling falls in, found the way `render.wgsl`
+/// finds it
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rhomb {
+    /// The two directions its sides run along, lowest first
+    pub directions: (usize, usize),
+    /// Corner the sides start from
+    pub corner: [f32; 2],
+    /// Where the point is across the rhomb along each side, 0 to 1
+    pub along: [f32; 2],
+}
+
+/// Every rhomb of the tiling holding `point`, which is one of them except
+/// exactly on an edge
+#[cfg(test)]
+pub fn rhombs_at(point: [f32; 2], offsets: &[f32]) -> Vec<Rhomb> {
+    let n = offsets.len();
+    let e = directions(n as u32);
+    let dot = |a: [f32; 2], b: [f32; 2]| a[0] * b[0] + a[1] * b[1];
+
+    // Each grid line ends up about n / 2 times further out in the tiling,
+    // so shrinking the point gives the grid cell it's near
+    let shift = (0..n).fold([0.0, 0.0], |sum, j| {
+        [sum[0] + offsets[j] * e[j][0], sum[1] + offsets[j] * e[j][1]]
+    });
+    let scale = 2.0 / n as f32;
+    let grid_point = [(point[0] - shift[0]) * scale, (point[1] - shift[1]) * scale];
+
+    let mut found = Vec::new();
+    for r in 0..n {
+        for s in r + 1..n {
+            let determinant = e[r][0] * e[s][1] - e[r][1] * e[s][0];
+            let near_r = (dot(grid_point, e[r]) + offsets[r]).floor();
+            let near_s = (dot(grid_point, e[s]) + offsets[s]).floor();
+            // One line either side always turns out to be enough
+            for line_r in [near_r - 1.0, near_r, near_r + 1.0] {
+                for line_s in [near_s - 1.0, near_s, near_s + 1.0] {
+                    let a = line_r - offsets[r];
+                    let b = line_s - offsets[s];
+                    let crossing = [
+                        (a * e[s][1] - b * e[r][1]) / determinant,
+                        (b * e[r][0] - a * e[s][0]) / determinant,
+                    ];
+                    let mut corner = [0.0, 0.0];
+                    for j in 0..n {
+                        let index = if j == r {
+                            line_r
+                        } else if j == s {
+                            line_s
+                        } else {
+                            (dot(crossing, e[j]) + offsets[j]).ceil()
+                        };
+                        corner[0] += index * e[j][0];
+                        corner[1] += index * e[j][1];
+                    }
+                    let d = [point[0] - corner[0], point[1] - corner[1]];
+                    let along = [
+                        (d[0] * e[s][1] - d[1] * e[s][0]) / determinant,
+                        (e[r][0] * d[1] - e[r][1] * d[0]) / determinant,
+                    ];
+                    if along.iter().all(|t| (0.0..=1.0).contains(t)) {
+                        found.push(Rhomb {
+                            directions: (r, s),
+                            corner,
+                            along,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    found
+}
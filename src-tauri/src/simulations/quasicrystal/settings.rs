@@ -0,0 +1,181 @@
+//! # Quasicrystal Settings Module
+//!
+//! Which pattern is drawn and with how many directions, how big it is and
+//! how fast it moves, and how the waves or tiles are colored.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Directions the shader has room for
+pub const MAX_SYMMETRY: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Pattern {
+    /// Plane waves along every direction, added up
+    #[default]
+    Waves,
+    /// Rhombs from de Bruijn's multigrid, Penrose's tiling with five
+    /// directions
+    Tiling,
+}
+
+impl Pattern {
+    pub fn shader_index(self) -> u32 {
+        match self {
+            Pattern::Waves => 0,
+            Pattern::Tiling => 1,
+        }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "waves" => Ok(Pattern::Waves),
+            "tiling" => Ok(Pattern::Tiling),
+            _ => Err(format!(
+                "Invalid Pattern: '{}'. Expected 'Waves' or 'Tiling'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TileColoring {
+    /// Rhombs of the same angles share a color
+    #[default]
+    Shape,
+    /// Every pair of directions has its own color, so turned copies of a
+    /// rhomb differ
+    Orientation,
+}
+
+impl TileColoring {
+    pub fn shader_index(self) -> u32 {
+        match self {
+            TileColoring::Shape => 0,
+            TileColoring::Orientation => 1,
+        }
+    }
+}
+
+impl FromStr for TileColoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "shape" => Ok(TileColoring::Shape),
+            "orientation" => Ok(TileColoring::Orientation),
+            _ => Err(format!(
+                "Invalid TileColoring: '{}'. Expected 'Shape' or 'Orientation'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub pattern: Pattern,
+    /// Directions the waves travel or the tiles' sides run, up to
+    /// [`MAX_SYMMETRY`]
+    pub symmetry: u32,
+    /// Half the height of the plane shown at zoom 1, in wavelengths or tile
+    /// sides
+    pub extent: f32,
+
+    /// Turns of the waves' phase, or of the grid offsets, each second
+    pub speed: f32,
+    /// Radians the whole pattern turns each second
+    pub rotation_speed: f32,
+
+    /// Times the color scheme repeats, back and forth, over the range the
+    /// waves add up to
+    pub banding: f32,
+    /// Width of the lines between tiles, as a share of a tile side
+    pub edge_width: f32,
+    pub tile_coloring: TileColoring,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            pattern: Pattern::Waves,
+            symmetry: 7,
+            extent: 12.0,
+            speed: 0.2,
+            rotation_speed: 0.0,
+            banding: 3.0,
+            edge_width: 0.06,
+            tile_coloring: TileColoring::Shape,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("pattern", Rule::OneOf(&["Waves", "Tiling"])),
+        (
+            "symmetry",
+            Rule::Count {
+                min: 3,
+                max: MAX_SYMMETRY as u64,
+            },
+        ),
+        (
+            "extent",
+            Rule::Range {
+                min: 1.0,
+                max: 200.0,
+            },
+        ),
+        (
+            "speed",
+            Rule::Range {
+                min: -5.0,
+                max: 5.0,
+            },
+        ),
+        (
+            "rotation_speed",
+            Rule::Range {
+                min: -2.0,
+                max: 2.0,
+            },
+        ),
+        (
+            "banding",
+            Rule::Range {
+                min: 0.5,
+                max: 16.0,
+            },
+        ),
+        ("edge_width", Rule::Range { min: 0.0, max: 0.5 }),
+        ("tile_coloring", Rule::OneOf(&["Shape", "Orientation"])),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("pattern", SettingCategory::Generators),
+    ("symmetry", SettingCategory::Generators),
+    ("banding", SettingCategory::Colors),
+    ("edge_width", SettingCategory::Colors),
+    ("tile_coloring", SettingCategory::Colors),
+]);
@@ -0,0 +1,183 @@
+// Draws the quasicrystal straight over the screen: either plane waves along
+// every direction added up and read through the color scheme, or the
+// de Bruijn tiling with each rhomb colored by its kind. pattern.rs explains
+// the geometry and keeps a copy of tile_at for the tests.
+
+struct Params {
+    directions: array<vec4<f32>, 8>, // Unit vectors, two to an element
+    offsets: array<vec4<f32>, 4>, // Grid offsets, four to an element
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    rotation: f32,
+    phase: f32,
+    pattern: u32,
+    symmetry: u32,
+    banding: f32,
+    edge_width: f32,
+    tile_coloring: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> lut_data: array<u32>;
+
+const PI: f32 = 3.14159265359;
+const TAU: f32 = 6.28318530718;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// One triangle covering the screen
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    return VertexOutput(vec4<f32>(ndc, 0.0, 1.0), ndc);
+}
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+fn direction(j: u32) -> vec2<f32> {
+    let pair = params.directions[j / 2u];
+    if (j % 2u == 0u) {
+        return pair.xy;
+    }
+    return pair.zw;
+}
+
+fn offset(j: u32) -> f32 {
+    return params.offsets[j / 4u][j % 4u];
+}
+
+// Where the waves' sum falls, folded back and forth `banding` times over
+// the color scheme
+fn waves(point: vec2<f32>) -> vec3<f32> {
+    var sum = 0.0;
+    for (var j = 0u; j < params.symmetry; j++) {
+        sum += cos(TAU * (dot(point, direction(j)) - params.phase));
+    }
+    let along = (sum / f32(params.symmetry) + 1.0) * 0.5 * params.banding;
+    let band = floor(along);
+    var position = along - band;
+    if (u32(band) % 2u == 1u) {
+        position = 1.0 - position;
+    }
+    return lut_color(position);
+}
+
+struct Rhomb {
+    found: bool,
+    r: u32,
+    s: u32,
+    // Where the point is along each side, 0 to 1
+    along: vec2<f32>,
+}
+
+// The rhomb holding `point`. Grid lines land about symmetry / 2 times
+// further out in the tiling, so the grid cell near the shrunk point has
+// the crossing, give or take a line either way along each direction.
+fn tile_at(point: vec2<f32>) -> Rhomb {
+    let n = params.symmetry;
+    var shift = vec2<f32>(0.0);
+    for (var j = 0u; j < n; j++) {
+        shift += offset(j) * direction(j);
+    }
+    let grid_point = (point - shift) * 2.0 / f32(n);
+
+    for (var r = 0u; r < n; r++) {
+        let e_r = direction(r);
+        let near_r = floor(dot(grid_point, e_r) + offset(r));
+        for (var s = r + 1u; s < n; s++) {
+            let e_s = direction(s);
+            let determinant = e_r.x * e_s.y - e_r.y * e_s.x;
+            let near_s = floor(dot(grid_point, e_s) + offset(s));
+            for (var i = -1; i <= 1; i++) {
+                for (var k = -1; k <= 1; k++) {
+                    let line_r = near_r + f32(i);
+                    let line_s = near_s + f32(k);
+                    let a = line_r - offset(r);
+                    let b = line_s - offset(s);
+                    let crossing = vec2<f32>(
+                        a * e_s.y - b * e_r.y,
+                        b * e_r.x - a * e_s.x
+                    ) / determinant;
+                    var corner = line_r * e_r + line_s * e_s;
+                    for (var j = 0u; j < n; j++) {
+                        if (j != r && j != s) {
+                            let e_j = direction(j);
+                            corner += ceil(dot(crossing, e_j) + offset(j)) * e_j;
+                        }
+                    }
+                    let d = point - corner;
+                    let along = vec2<f32>(
+                        d.x * e_s.y - d.y * e_s.x,
+                        e_r.x * d.y - e_r.y * d.x
+                    ) / determinant;
+                    if (all(along >= vec2<f32>(0.0)) && all(along <= vec2<f32>(1.0))) {
+                        return Rhomb(true, r, s, along);
+                    }
+                }
+            }
+        }
+    }
+    return Rhomb(false, 0u, 0u, vec2<f32>(0.0));
+}
+
+fn tiling(point: vec2<f32>) -> vec3<f32> {
+    let rhomb = tile_at(point);
+    if (!rhomb.found) {
+        return vec3<f32>(0.0);
+    }
+    let n = params.symmetry;
+    var position: f32;
+    if (params.tile_coloring == 0u) {
+        // From thinnest to squarest
+        let apart = rhomb.s - rhomb.r;
+        let shape = min(apart, n - apart);
+        position = (f32(shape) - 0.5) / f32(n / 2u);
+    } else {
+        let pair = rhomb.r * n - rhomb.r * (rhomb.r + 1u) / 2u + rhomb.s - rhomb.r - 1u;
+        position = (f32(pair) + 0.5) / f32(n * (n - 1u) / 2u);
+    }
+
+    let color = lut_color(position);
+    if (params.edge_width <= 0.0) {
+        return color;
+    }
+
+    // Distance to the nearest side, a side being one unit long
+    let apart_angle = PI * f32(rhomb.s - rhomb.r) / f32(n);
+    let edge = min(min(rhomb.along.x, 1.0 - rhomb.along.x), min(rhomb.along.y, 1.0 - rhomb.along.y));
+    let distance = edge * sin(apart_angle);
+    let half_width = params.edge_width * 0.5;
+    let line = 1.0 - smoothstep(half_width, half_width + params.plane_per_pixel, distance);
+    return color * (1.0 - line);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let world = input.ndc / params.view_zoom + params.view_center;
+    let unrotated = vec2<f32>(world.x * params.aspect, world.y) * params.extent;
+    let c = cos(params.rotation);
+    let s = sin(params.rotation);
+    let point = vec2<f32>(c * unrotated.x - s * unrotated.y, s * unrotated.x + c * unrotated.y);
+
+    if (params.pattern == 1u) {
+        return vec4<f32>(tiling(point), 1.0);
+    }
+    return vec4<f32>(waves(point), 1.0);
+}
@@ -0,0 +1,478 @@
+//! # Quasicrystal Simulation Module
+//!
+//! Patterns that are ordered without ever repeating, built from a handful
+//! of directions spaced evenly around the circle. Two ways of drawing them:
+//! - Waves: one plane wave travelling along each direction, added up. Seven
+//!   waves give the familiar fourteen-fold rosettes, which flow as the
+//!   waves' phase moves.
+//! - Tiling: de Bruijn's multigrid, which gives the Penrose tiling for five
+//!   directions and its relatives for other counts. Moving the grids'
+//!   offsets flips tiles over one another as the tiling rearranges.
+//!
+//! Everything is worked out per pixel in the fragment shader from a small
+//! uniform, so there's no state on the GPU beyond the color scheme.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayoutDescriptor, Buffer, BufferDescriptor,
+    BufferUsages, Device, PipelineLayoutDescriptor, Queue, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, SurfaceConfiguration, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager};
+use crate::simulations::traits::Simulation;
+
+use super::pattern::{directions, grid_offsets};
+use super::settings::{MAX_SYMMETRY, Pattern, Settings, TileColoring};
+use super::shaders::RENDER_SHADER;
+use super::state::State;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    directions: [[f32; 4]; MAX_SYMMETRY as usize / 2],
+    offsets: [[f32; 4]; MAX_SYMMETRY as usize / 4],
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    extent: f32,
+    plane_per_pixel: f32,
+    rotation: f32,
+    phase: f32,
+    pattern: u32,
+    symmetry: u32,
+    banding: f32,
+    edge_width: f32,
+    tile_coloring: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+#[derive(Debug)]
+pub struct QuasicrystalModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    render_pipeline: RenderPipeline,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    bind_group: BindGroup,
+
+    // The camera picks the part of the plane shown; it never draws
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl QuasicrystalModel {
+    pub fn new(
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Quasicrystal Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Quasicrystal Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Quasicrystal LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Quasicrystal Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::FRAGMENT),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::FRAGMENT, true),
+            ],
+        });
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Quasicrystal Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &params_buffer),
+                resource_helpers::buffer_entry(1, &lut_buffer),
+            ],
+        });
+
+        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Quasicrystal Render Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Quasicrystal Render Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            settings,
+            state,
+            render_pipeline,
+            params_buffer,
+            lut_buffer,
+            bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        })
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let symmetry = self.settings.symmetry.clamp(3, MAX_SYMMETRY);
+        let mut unit_vectors = [[0.0; 4]; MAX_SYMMETRY as usize / 2];
+        for (j, [x, y]) in directions(symmetry).into_iter().enumerate() {
+            unit_vectors[j / 2][j % 2 * 2] = x;
+            unit_vectors[j / 2][j % 2 * 2 + 1] = y;
+        }
+        let mut offsets = [[0.0; 4]; MAX_SYMMETRY as usize / 4];
+        let phase = self.state.phase * std::f32::consts::TAU;
+        for (j, offset) in grid_offsets(symmetry, phase).into_iter().enumerate() {
+            offsets[j / 4][j % 4] = offset;
+        }
+        let aspect = self.width as f32 / self.height.max(1) as f32;
+        let params = Params {
+            directions: unit_vectors,
+            offsets,
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect,
+            extent: self.settings.extent,
+            plane_per_pixel: 2.0 * self.settings.extent
+                / (self.camera.zoom * self.height.max(1) as f32),
+            rotation: self.state.rotation,
+            phase: self.state.phase,
+            pattern: self.settings.pattern.shader_index(),
+            symmetry,
+            banding: self.settings.banding,
+            edge_width: self.settings.edge_width,
+            tile_coloring: self.settings.tile_coloring.shader_index(),
+            _pad0: 0,
+            _pad1: 0,
+            _pad2: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Quasicrystal Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    fn draw(&self, device: &Arc<Device>, queue: &Arc<Queue>, surface_view: &TextureView) {
+        self.update_params(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Quasicrystal Render"),
+        });
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+    }
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for QuasicrystalModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        // Kept to a single turn so precision holds up over long runs
+        self.state.phase = (self.state.phase + self.settings.speed * delta_time).rem_euclid(1.0);
+        self.state.rotation = (self.state.rotation + self.settings.rotation_speed * delta_time)
+            .rem_euclid(std::f32::consts::TAU);
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        _world_x: f32,
+        _world_y: f32,
+        _mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut new_settings: Settings = serde_json::from_value(settings)?;
+        new_settings.symmetry = new_settings.symmetry.clamp(3, MAX_SYMMETRY);
+        self.settings = new_settings;
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.phase = 0.0;
+        self.state.rotation = 0.0;
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.pattern = if rng.random_bool(0.5) {
+            Pattern::Waves
+        } else {
+            Pattern::Tiling
+        };
+        match self.settings.pattern {
+            Pattern::Waves => {
+                self.settings.symmetry = rng.random_range(5..=11);
+                self.settings.extent = rng.random_range(6.0..20.0);
+                self.settings.banding = rng.random_range(1.0..5.0);
+                self.settings.speed = rng.random_range(0.05..0.4);
+            }
+            Pattern::Tiling => {
+                // Larger counts make ever smaller tiles, and cost more
+                self.settings.symmetry = rng.random_range(4..=9);
+                self.settings.extent = rng.random_range(4.0..12.0);
+                self.settings.speed = rng.random_range(0.01..0.08);
+                self.settings.tile_coloring = if rng.random_bool(0.5) {
+                    TileColoring::Shape
+                } else {
+                    TileColoring::Orientation
+                };
+            }
+        }
+        self.settings.rotation_speed = if rng.random_bool(0.3) {
+            rng.random_range(-0.2..0.2)
+        } else {
+            0.0
+        };
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "pattern" => {
+                self.settings.pattern = value
+                    .as_str()
+                    .unwrap_or("Waves")
+                    .parse()
+                    .map_err(|e| format!("Invalid pattern: {}", e))?;
+            }
+            "symmetry" => {
+                let symmetry = number(setting_name, &value)? as u32;
+                self.settings.symmetry = symmetry.clamp(3, MAX_SYMMETRY);
+            }
+            "extent" => self.settings.extent = number(setting_name, &value)? as f32,
+            "speed" => self.settings.speed = number(setting_name, &value)? as f32,
+            "rotation_speed" => self.settings.rotation_speed = number(setting_name, &value)? as f32,
+            "banding" => self.settings.banding = number(setting_name, &value)? as f32,
+            "edge_width" => self.settings.edge_width = number(setting_name, &value)? as f32,
+            "tile_coloring" => {
+                self.settings.tile_coloring = value
+                    .as_str()
+                    .unwrap_or("Shape")
+                    .parse()
+                    .map_err(|e| format!("Invalid tile coloring: {}", e))?;
+            }
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
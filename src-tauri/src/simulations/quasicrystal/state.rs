@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Turns the waves or grid offsets have moved through
+    pub phase: f32,
+    // Radians the pattern has turned
+    pub rotation: f32,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            phase: 0.0,
+            rotation: 0.0,
+            color_scheme_name: "MATPLOTLIB_twilight".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
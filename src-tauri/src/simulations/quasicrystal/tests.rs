@@ -0,0 +1,129 @@
+use super::pattern::{directions, grid_offsets, rhombs_at};
+use super::settings::MAX_SYMMETRY;
+use super::shaders::RENDER_SHADER;
+use crate::simulations::shared::kernel_test::KernelHarness;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn directions_are_unit_and_evenly_spread() {
+    for symmetry in 3..=MAX_SYMMETRY {
+        let e = directions(symmetry);
+        assert_eq!(e.len(), symmetry as usize);
+        let step = std::f32::consts::PI / symmetry as f32;
+        for pair in e.windows(2) {
+            let [a, b] = [pair[0], pair[1]];
+            assert!((a[0].hypot(a[1]) - 1.0).abs() < 1e-6);
+            let between = (a[0] * b[0] + a[1] * b[1]).clamp(-1.0, 1.0).acos();
+            assert!((between - step).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn offsets_meet_the_penrose_condition() {
+    for symmetry in 3..=MAX_SYMMETRY {
+        for phase in [0.0, 0.8, 2.5, 5.9] {
+            let offsets = grid_offsets(symmetry, phase);
+            assert_eq!(offsets.len(), symmetry as usize);
+            // Odd counts flip every other direction round, and its offset
+            // with it
+            let sum: f32 = offsets
+                .iter()
+                .enumerate()
+                .map(|(j, o)| {
+                    if symmetry % 2 == 1 && j % 2 == 1 {
+                        -o
+                    } else {
+                        *o
+                    }
+                })
+                .sum();
+            if symmetry == 3 {
+                assert!((sum - sum.round()).abs() > 0.1);
+            } else {
+                assert!(
+                    (sum - sum.round()).abs() < 1e-5,
+                    "{} add up to {}",
+                    symmetry,
+                    sum
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn offsets_come_back_after_a_turn() {
+    let start = grid_offsets(5, 0.4);
+    let turned = grid_offsets(5, 0.4 + std::f32::consts::TAU);
+    for (a, b) in start.iter().zip(&turned) {
+        assert!((a - b).abs() < 1e-5);
+    }
+    assert_ne!(start, grid_offsets(5, 1.4));
+}
+
+#[test]
+fn rhombs_cover_the_plane_without_overlapping() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let inside = |t: f32| t > 1e-3 && t < 1.0 - 1e-3;
+    let mut samples = 0;
+    let mut misses = 0;
+    for symmetry in 3..=MAX_SYMMETRY {
+        for phase in [0.3, 3.7] {
+            let offsets = grid_offsets(symmetry, phase);
+            for _ in 0..200 {
+                let point = [rng.random_range(-40.0..40.0), rng.random_range(-40.0..40.0)];
+                let rhombs = rhombs_at(point, &offsets);
+                // Points on an edge belong to the rhombs either side
+                let holding = rhombs
+                    .iter()
+                    .filter(|r| r.along.iter().all(|&t| inside(t)))
+                    .count();
+                samples += 1;
+                if rhombs.is_empty() || holding > 1 {
+                    misses += 1;
+                }
+            }
+        }
+    }
+    // Where three lines nearly cross at one point, f32 can put a corner on
+    // the wrong side of the third, as it can in the shader
+    assert!(misses * 1000 < samples, "{} of {} missed", misses, samples);
+}
+
+#[test]
+fn penrose_thick_rhombs_cover_golden_ratio_squared_more() {
+    let mut rng = StdRng::seed_from_u64(8);
+    let offsets = grid_offsets(5, 1.0);
+    let mut thick = 0;
+    let mut thin = 0;
+    for _ in 0..20_000 {
+        let point = [
+            rng.random_range(-150.0..150.0),
+            rng.random_range(-150.0..150.0),
+        ];
+        let rhomb = rhombs_at(point, &offsets)[0];
+        let (r, s) = rhomb.directions;
+        // Directions a fifth of a half turn apart make the thin 36° rhomb
+        if (s - r).min(5 - (s - r)) == 1 {
+            thin += 1;
+        } else {
+            thick += 1;
+        }
+    }
+    let golden = (1.0 + 5.0_f32.sqrt()) / 2.0;
+    let ratio = thick as f32 / thin as f32;
+    assert!((ratio / (golden * golden) - 1.0).abs() < 0.1, "{}", ratio);
+}
+
+#[tokio::test]
+async fn shader_compiles() {
+    let harness = KernelHarness::new().await;
+    let _ = harness
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Quasicrystal Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+}
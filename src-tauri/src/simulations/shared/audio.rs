@@ -0,0 +1,270 @@
+//! Band energies of live audio, for simulations that react to sound.
+//!
+//! Samples come from the webview, which has the microphone (or a loopback
+//! device standing in for system audio), in chunks of mono PCM. Each frame
+//! the latest [`FFT_SIZE`] samples are windowed and transformed on the CPU,
+//! and the spectrum is summed into bass, mid and treble bands. Every band is
+//! divided by a slowly falling peak of its own, so quiet rooms and loud
+//! speakers both swing the full 0-1 range, and falls back at a set release
+//! time rather than flickering with every beat.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Samples per transform, a power of two. About 43 ms at 48 kHz, with bins
+/// 23 Hz apart.
+pub const FFT_SIZE: usize = 2048;
+
+/// Hz edges of the bass, mid and treble bands
+const BAND_EDGES: [f32; 4] = [20.0, 250.0, 4000.0, 16000.0];
+
+/// Seconds without new samples before the input counts as silent
+const INPUT_TIMEOUT: f32 = 0.5;
+
+/// How fast the normalizing peaks fall, as seconds to halve
+const PEAK_HALF_LIFE: f32 = 10.0;
+
+/// RMS below which nothing registers, so hiss isn't stretched to full scale
+const MIN_PEAK: f32 = 0.005;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct AudioBands {
+    pub bass: f32,
+    pub mid: f32,
+    pub treble: f32,
+    /// Loudness across the whole spectrum
+    pub level: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioBand {
+    Bass,
+    Mid,
+    Treble,
+    Level,
+}
+
+impl AudioBand {
+    pub fn of(self, bands: &AudioBands) -> f32 {
+        match self {
+            AudioBand::Bass => bands.bass,
+            AudioBand::Mid => bands.mid,
+            AudioBand::Treble => bands.treble,
+            AudioBand::Level => bands.level,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AudioAnalyzer {
+    samples: VecDeque<f32>,
+    sample_rate: u32,
+    since_input: f32,
+    // Bass, mid, treble and level. Peaks are RMS, levels normalized.
+    peaks: [f32; 4],
+    levels: [f32; 4],
+}
+
+impl Default for AudioAnalyzer {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(FFT_SIZE),
+            sample_rate: 48000,
+            since_input: INPUT_TIMEOUT,
+            peaks: [MIN_PEAK; 4],
+            levels: [0.0; 4],
+        }
+    }
+}
+
+impl AudioAnalyzer {
+    /// Add mono samples in -1 to 1. A new sample rate starts the buffer over.
+    pub fn push_samples(&mut self, samples: &[f32], sample_rate: u32) {
+        if sample_rate != self.sample_rate {
+            self.samples.clear();
+            self.sample_rate = sample_rate;
+        }
+        let keep = samples.len().min(FFT_SIZE);
+        let overflow = (self.samples.len() + keep).saturating_sub(FFT_SIZE);
+        self.samples.drain(..overflow);
+        self.samples.extend(
+            samples[samples.len() - keep..]
+                .iter()
+                .map(|sample| if sample.is_finite() { *sample } else { 0.0 }),
+        );
+        self.since_input = 0.0;
+    }
+
+    pub fn bands(&self) -> AudioBands {
+        let [bass, mid, treble, level] = self.levels;
+        AudioBands {
+            bass,
+            mid,
+            treble,
+            level,
+        }
+    }
+
+    /// Analyze the latest samples, `delta_time` seconds after the last call.
+    /// `gain` scales the normalized bands, and `release` is the seconds a
+    /// band takes to fall most of the way back after a peak.
+    pub fn analyze(&mut self, delta_time: f32, gain: f32, release: f32) -> AudioBands {
+        self.since_input += delta_time;
+        if self.since_input >= INPUT_TIMEOUT {
+            self.samples.clear();
+        }
+
+        let raw = self.measure();
+        let peak_decay = 0.5f32.powf(delta_time / PEAK_HALF_LIFE);
+        let fall = if release > 0.0 {
+            (-delta_time / release * 3.0).exp()
+        } else {
+            0.0
+        };
+        for (i, level) in self.levels.iter_mut().enumerate() {
+            self.peaks[i] = (self.peaks[i] * peak_decay).max(raw[i]).max(MIN_PEAK);
+            let target = (raw[i] / self.peaks[i] * gain).clamp(0.0, 1.0);
+            // Rise at once, fall back at the release rate
+            *level = target.max(*level * fall);
+        }
+        self.bands()
+    }
+
+    /// RMS of the bass, mid and treble parts of the buffered samples, and of
+    /// the whole signal
+    fn measure(&self) -> [f32; 4] {
+        if self.samples.len() < FFT_SIZE {
+            return [0.0; 4];
+        }
+        let window: Vec<f32> = (0..FFT_SIZE)
+            .map(|n| 0.5 - 0.5 * (std::f32::consts::TAU * n as f32 / FFT_SIZE as f32).cos())
+            .collect();
+        let mut re: Vec<f32> = self
+            .samples
+            .iter()
+            .zip(&window)
+            .map(|(sample, weight)| sample * weight)
+            .collect();
+        let mut im = vec![0.0; FFT_SIZE];
+        fft(&mut re, &mut im);
+
+        // By Parseval, power summed over the bins in a band, counting the
+        // mirrored negative frequencies, over N times the window's power
+        // is the mean square of that band in the windowed signal
+        let window_power: f32 = window.iter().map(|w| w * w).sum();
+        let bin_hz = self.sample_rate as f32 / FFT_SIZE as f32;
+        let mut bands = [0.0; 4];
+        for (band, edges) in BAND_EDGES.windows(2).enumerate() {
+            let first = ((edges[0] / bin_hz).ceil() as usize).max(1);
+            let last = ((edges[1] / bin_hz).ceil() as usize).min(FFT_SIZE / 2);
+            let power: f32 = (first..last).map(|k| re[k] * re[k] + im[k] * im[k]).sum();
+            bands[band] = (2.0 * power / (FFT_SIZE as f32 * window_power)).sqrt();
+        }
+        let mean_square = self.samples.iter().map(|s| s * s).sum::<f32>() / FFT_SIZE as f32;
+        bands[3] = mean_square.sqrt();
+        bands
+    }
+}
+
+/// In-place radix-2 FFT. Both slices must have the same power of two length.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    // Bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -std::f32::consts::TAU / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let a = start + k;
+                let b = a + len / 2;
+                let odd_re = re[b] * cos - im[b] * sin;
+                let odd_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - odd_re;
+                im[b] = im[a] - odd_im;
+                re[a] += odd_re;
+                im[a] += odd_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(frequency: f32, amplitude: f32) -> Vec<f32> {
+        (0..FFT_SIZE)
+            .map(|n| amplitude * (std::f32::consts::TAU * frequency * n as f32 / 48000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn fft_finds_a_pure_tone() {
+        let mut re: Vec<f32> = (0..64)
+            .map(|n| (std::f32::consts::TAU * 5.0 * n as f32 / 64.0).cos())
+            .collect();
+        let mut im = vec![0.0; 64];
+        fft(&mut re, &mut im);
+        for k in 0..64 {
+            let magnitude = (re[k] * re[k] + im[k] * im[k]).sqrt();
+            let expected = if k == 5 || k == 59 { 32.0 } else { 0.0 };
+            assert!((magnitude - expected).abs() < 1e-3, "bin {}", k);
+        }
+    }
+
+    #[test]
+    fn tones_land_in_their_band() {
+        let mut analyzer = AudioAnalyzer::default();
+        analyzer.push_samples(&tone(100.0, 0.5), 48000);
+        let raw = analyzer.measure();
+        // A sine's RMS is its amplitude over root two
+        assert!((raw[0] - 0.5 / 2f32.sqrt()).abs() < 0.02, "{:?}", raw);
+        assert!(raw[1] < 0.05 && raw[2] < 0.01, "{:?}", raw);
+
+        analyzer.push_samples(&tone(8000.0, 0.5), 48000);
+        let bands = analyzer.analyze(0.016, 1.0, 0.0);
+        assert!(bands.treble > 0.95 && bands.bass < 0.05, "{:?}", bands);
+    }
+
+    #[test]
+    fn bands_fall_back_once_the_sound_stops() {
+        let mut analyzer = AudioAnalyzer::default();
+        analyzer.push_samples(&tone(100.0, 0.5), 48000);
+        assert!(analyzer.analyze(0.016, 1.0, 0.5).bass > 0.95);
+
+        analyzer.push_samples(&vec![0.0; FFT_SIZE], 48000);
+        let falling = analyzer.analyze(0.1, 1.0, 0.5).bass;
+        assert!(falling > 0.2 && falling < 0.95, "{}", falling);
+        for _ in 0..20 {
+            analyzer.analyze(0.1, 1.0, 0.5);
+        }
+        assert!(analyzer.bands().bass < 1e-3);
+    }
+
+    #[test]
+    fn quiet_hiss_stays_quiet() {
+        let mut analyzer = AudioAnalyzer::default();
+        analyzer.push_samples(&tone(1000.0, 0.0005), 48000);
+        let bands = analyzer.analyze(0.016, 1.0, 0.0);
+        assert!(bands.mid < 0.1 && bands.level < 0.1, "{:?}", bands);
+    }
+}
@@ -0,0 +1,119 @@
+//! An image drawn behind a simulation, kept in the simulation's settings so
+//! presets carry it. The master bus composites it with whatever the
+//! simulation rendered: behind its transparent parts, or over the whole
+//! frame with one of the blend modes for simulations that render opaque.
+
+use serde::{Deserialize, Serialize};
+
+use super::ImageFitMode;
+use crate::error::{SimulationError, SimulationResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundBlend {
+    /// Shows through where the simulation is transparent
+    #[default]
+    Behind,
+    Screen,
+    Add,
+    Multiply,
+}
+
+impl From<BackgroundBlend> for u32 {
+    fn from(blend: BackgroundBlend) -> Self {
+        match blend {
+            BackgroundBlend::Behind => 1,
+            BackgroundBlend::Screen => 2,
+            BackgroundBlend::Add => 3,
+            BackgroundBlend::Multiply => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundLayer {
+    pub enabled: bool,
+    pub image_path: String,
+    pub fit_mode: ImageFitMode,
+    /// Repeat the image where it doesn't cover the frame
+    pub tile: bool,
+    pub blend: BackgroundBlend,
+    pub opacity: f32,
+}
+
+impl Default for BackgroundLayer {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            image_path: String::new(),
+            fit_mode: ImageFitMode::FitV,
+            tile: false,
+            blend: BackgroundBlend::Behind,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl BackgroundLayer {
+    pub fn is_visible(&self) -> bool {
+        self.enabled && !self.image_path.is_empty() && self.opacity > 0.0
+    }
+
+    pub fn validate(&self) -> SimulationResult<()> {
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err(SimulationError::InvalidParameter(
+                "Background opacity must be between 0 and 1".to_string(),
+            ));
+        }
+        if self.enabled && self.image_path.is_empty() {
+            return Err(SimulationError::InvalidParameter(
+                "Background layer needs an image".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Share of a `frame` sized frame the image covers on each axis, for an
+    /// image of `image` pixels
+    pub fn extent(&self, image: [f32; 2], frame: [f32; 2]) -> [f32; 2] {
+        let scale = match self.fit_mode {
+            ImageFitMode::Stretch => return [1.0, 1.0],
+            ImageFitMode::Center => 1.0,
+            ImageFitMode::FitH => frame[0] / image[0],
+            ImageFitMode::FitV => frame[1] / image[1],
+        };
+        [image[0] * scale / frame[0], image[1] * scale / frame[1]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_modes_scale_to_the_frame() {
+        let mut layer = BackgroundLayer::default();
+        let (image, frame) = ([500.0, 250.0], [1000.0, 1000.0]);
+        assert_eq!(layer.extent(image, frame), [2.0, 1.0]);
+        layer.fit_mode = ImageFitMode::FitH;
+        assert_eq!(layer.extent(image, frame), [1.0, 0.5]);
+        layer.fit_mode = ImageFitMode::Center;
+        assert_eq!(layer.extent(image, frame), [0.5, 0.25]);
+        layer.fit_mode = ImageFitMode::Stretch;
+        assert_eq!(layer.extent(image, frame), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn needs_an_image_to_show() {
+        let mut layer = BackgroundLayer {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(!layer.is_visible());
+        assert!(layer.validate().is_err());
+        layer.image_path = "footage.png".to_string();
+        assert!(layer.is_visible());
+        assert!(layer.validate().is_ok());
+    }
+}
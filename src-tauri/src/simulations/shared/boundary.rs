@@ -0,0 +1,407 @@
+//! What happens at the edges of a grid field: whether stencils reaching
+//! past an edge wrap around to the other side, see the edge cell mirrored
+//! back, or see an ambient value the field drains toward. Each edge is set
+//! on its own, and an image can wall off cells inside the grid as well.
+//!
+//! `boundary.wgsl` resolves neighbors the same way on the GPU from
+//! [`BoundaryConditions::packed`]. Gray-Scott and the chemotaxis nutrient
+//! field read their stencils through it.
+//!
+//! - Periodic edges wrap, so the field lies on a torus.
+//! - Reflective edges mirror the cells inside, so nothing flows across.
+//! - Absorbing edges are held at the field's ambient value (no V for
+//!   Gray-Scott, no nutrient for chemotaxis), so the field leaks away.
+//!
+//! Mask walls reflect or absorb the same way, according to the mask.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+use super::field_image::image_to_field;
+use crate::error::{SimulationError, SimulationResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Boundary {
+    #[default]
+    Periodic,
+    Reflective,
+    Absorbing,
+}
+
+impl Boundary {
+    /// What `boundary.wgsl` calls this condition
+    pub fn shader_index(self) -> u32 {
+        match self {
+            Boundary::Periodic => 0,
+            Boundary::Reflective => 1,
+            Boundary::Absorbing => 2,
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "periodic" => Ok(Boundary::Periodic),
+            "reflective" => Ok(Boundary::Reflective),
+            "absorbing" => Ok(Boundary::Absorbing),
+            _ => Err(format!(
+                "Invalid Boundary: '{}'. Expected 'periodic', 'reflective' or 'absorbing'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BoundaryMask {
+    #[default]
+    None,
+    /// Walls wherever the image at `path`, stretched over the grid, is
+    /// darker than middle gray. Walled cells keep their values and reflect
+    /// unless `absorbing`.
+    Image { path: String, absorbing: bool },
+}
+
+/// Named sets of edge conditions, for the presets menu
+pub const BOUNDARY_PRESETS: &[&str] = &["wrap", "walled", "open", "channel"];
+
+// Bit set in `packed` when the mask is on, past the five two bit fields
+const MASK_BIT: u32 = 1 << 10;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BoundaryConditions {
+    pub left: Boundary,
+    pub right: Boundary,
+    pub bottom: Boundary,
+    pub top: Boundary,
+    pub mask: BoundaryMask,
+}
+
+impl BoundaryConditions {
+    /// All four edges alike, with no mask
+    pub fn uniform(boundary: Boundary) -> Self {
+        Self {
+            left: boundary,
+            right: boundary,
+            bottom: boundary,
+            top: boundary,
+            mask: BoundaryMask::None,
+        }
+    }
+
+    /// The edges of one of [`BOUNDARY_PRESETS`], with no mask. A channel
+    /// wraps left to right between walls at the bottom and top.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "wrap" => Some(Self::uniform(Boundary::Periodic)),
+            "walled" => Some(Self::uniform(Boundary::Reflective)),
+            "open" => Some(Self::uniform(Boundary::Absorbing)),
+            "channel" => Some(Self {
+                bottom: Boundary::Reflective,
+                top: Boundary::Reflective,
+                ..Self::uniform(Boundary::Periodic)
+            }),
+            _ => None,
+        }
+    }
+
+    /// The conditions a `boundaries` setting names: a preset's name, or
+    /// the conditions in full
+    pub fn from_setting(value: Value) -> SimulationResult<Self> {
+        let conditions = match value {
+            Value::String(name) => Self::preset(&name).ok_or_else(|| {
+                SimulationError::InvalidParameter(format!(
+                    "Unknown boundary preset '{}'. Expected one of {}",
+                    name,
+                    BOUNDARY_PRESETS.join(", ")
+                ))
+            })?,
+            value => serde_json::from_value(value).map_err(SimulationError::Serialization)?,
+        };
+        conditions.validate()?;
+        Ok(conditions)
+    }
+
+    pub fn validate(&self) -> SimulationResult<()> {
+        match &self.mask {
+            BoundaryMask::Image { path, .. } if path.is_empty() => Err(
+                SimulationError::InvalidParameter("Boundary masks need an image path".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// The edges seen along the grid's rows, which run up the screen if
+    /// `rows_up` and down it otherwise: left, right, row 0's side and the
+    /// last row's side
+    fn edges(&self, rows_up: bool) -> [Boundary; 4] {
+        if rows_up {
+            [self.left, self.right, self.bottom, self.top]
+        } else {
+            [self.left, self.right, self.top, self.bottom]
+        }
+    }
+
+    /// How `boundary.wgsl` takes the conditions: two bits an edge in the
+    /// order of `edges`, two for what mask walls do, then whether there's
+    /// a mask at all
+    pub fn packed(&self, rows_up: bool) -> u32 {
+        let mut packed = self
+            .edges(rows_up)
+            .iter()
+            .enumerate()
+            .fold(0, |packed, (slot, edge)| {
+                packed | edge.shader_index() << (slot * 2)
+            });
+        if let BoundaryMask::Image { absorbing, .. } = self.mask {
+            let walls = if absorbing {
+                Boundary::Absorbing
+            } else {
+                Boundary::Reflective
+            };
+            packed |= walls.shader_index() << 8 | MASK_BIT;
+        }
+        packed
+    }
+
+    /// The mask over a `width` x `height` grid, row by row, with 1 for
+    /// open cells and 0 for walls, or `None` without one. Images are
+    /// flipped onto grids whose rows run up so they appear upright.
+    pub fn mask_cells(
+        &self,
+        width: u32,
+        height: u32,
+        rows_up: bool,
+    ) -> SimulationResult<Option<Vec<u32>>> {
+        let BoundaryMask::Image { path, .. } = &self.mask else {
+            return Ok(None);
+        };
+        let image = image::open(path).map_err(|e| {
+            SimulationError::InvalidParameter(format!(
+                "Failed to open boundary mask {}: {}",
+                path, e
+            ))
+        })?;
+        let mut field = image_to_field(&image, width, height);
+        if rows_up {
+            field = field
+                .chunks_exact(width as usize)
+                .rev()
+                .flatten()
+                .copied()
+                .collect();
+        }
+        Ok(Some(field.iter().map(|v| (*v >= 0.5) as u32).collect()))
+    }
+}
+
+/// The cell a stencil around `center` reads for its neighbor at `cell`,
+/// as `boundary_cell` in `boundary.wgsl` finds it from `packed`, or `None`
+/// where it reads the ambient value. Only the tests need it on the CPU.
+#[cfg(test)]
+pub fn resolve(
+    packed: u32,
+    cell: [i32; 2],
+    center: [i32; 2],
+    (width, height): (u32, u32),
+    mask: Option<&[u32]>,
+) -> Option<[i32; 2]> {
+    let edge = |slot: u32| (packed >> (slot * 2)) & 3;
+    let x = resolve_axis(cell[0], width as i32, edge(0), edge(1))?;
+    let y = resolve_axis(cell[1], height as i32, edge(2), edge(3))?;
+    if !walled(packed, [x, y], width, mask) {
+        Some([x, y])
+    } else if edge(4) == Boundary::Absorbing.shader_index() {
+        None
+    } else {
+        Some(center)
+    }
+}
+
+/// Whether the mask walls `cell` off, as `boundary_walled` in
+/// `boundary.wgsl` finds it
+#[cfg(test)]
+pub fn walled(packed: u32, [x, y]: [i32; 2], width: u32, mask: Option<&[u32]>) -> bool {
+    packed & MASK_BIT != 0
+        && mask.is_some_and(|mask| mask[(y as u32 * width + x as u32) as usize] == 0)
+}
+
+#[cfg(test)]
+fn resolve_axis(value: i32, size: i32, low: u32, high: u32) -> Option<i32> {
+    let condition = match value {
+        v if v < 0 => low,
+        v if v >= size => high,
+        v => return Some(v),
+    };
+    match condition {
+        0 => Some(value.rem_euclid(size)),
+        1 if value < 0 => Some((-value - 1).min(size - 1)),
+        1 => Some((2 * size - value - 1).max(0)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEIGHBORS: [[i32; 2]; 4] = [[-1, 0], [1, 0], [0, -1], [0, 1]];
+
+    /// One explicit diffusion step of `field` toward `ambient` past
+    /// absorbing edges
+    fn diffuse(field: &[f32], packed: u32, grid: (u32, u32), mask: Option<&[u32]>) -> Vec<f32> {
+        let ambient = 0.0;
+        let index = |[x, y]: [i32; 2]| (y as u32 * grid.0 + x as u32) as usize;
+        (0..grid.1 as i32)
+            .flat_map(|y| (0..grid.0 as i32).map(move |x| [x, y]))
+            .map(|cell| {
+                let here = field[index(cell)];
+                if mask.is_some_and(|mask| mask[index(cell)] == 0) {
+                    return here;
+                }
+                let sum: f32 = NEIGHBORS
+                    .iter()
+                    .map(|[dx, dy]| {
+                        resolve(packed, [cell[0] + dx, cell[1] + dy], cell, grid, mask)
+                            .map_or(ambient, |neighbor| field[index(neighbor)])
+                    })
+                    .sum();
+                here + 0.2 * (sum - 4.0 * here)
+            })
+            .collect()
+    }
+
+    fn total_after(conditions: &BoundaryConditions, steps: usize) -> f32 {
+        let grid = (6, 5);
+        let mut field = vec![0.0; 30];
+        field[0] = 1.0;
+        field[29] = 1.0;
+        for _ in 0..steps {
+            field = diffuse(&field, conditions.packed(true), grid, None);
+        }
+        field.iter().sum()
+    }
+
+    #[test]
+    fn edges_wrap_mirror_or_absorb() {
+        let grid = (4, 3);
+        let wrap = BoundaryConditions::uniform(Boundary::Periodic).packed(true);
+        assert_eq!(resolve(wrap, [-1, 0], [0, 0], grid, None), Some([3, 0]));
+        assert_eq!(resolve(wrap, [4, 3], [3, 2], grid, None), Some([0, 0]));
+
+        let walled = BoundaryConditions::uniform(Boundary::Reflective).packed(true);
+        assert_eq!(resolve(walled, [-1, 1], [0, 1], grid, None), Some([0, 1]));
+        assert_eq!(resolve(walled, [-2, 1], [0, 1], grid, None), Some([1, 1]));
+        assert_eq!(resolve(walled, [2, 3], [2, 2], grid, None), Some([2, 2]));
+
+        let open = BoundaryConditions::uniform(Boundary::Absorbing).packed(true);
+        assert_eq!(resolve(open, [4, 1], [3, 1], grid, None), None);
+        assert_eq!(resolve(open, [2, 1], [3, 1], grid, None), Some([2, 1]));
+    }
+
+    #[test]
+    fn each_edge_keeps_its_own_condition() {
+        let conditions = BoundaryConditions::preset("channel").unwrap();
+        let grid = (4, 3);
+        let packed = conditions.packed(true);
+        assert_eq!(resolve(packed, [-1, 1], [0, 1], grid, None), Some([3, 1]));
+        assert_eq!(resolve(packed, [1, -1], [1, 0], grid, None), Some([1, 0]));
+
+        let conditions = BoundaryConditions {
+            top: Boundary::Absorbing,
+            ..BoundaryConditions::uniform(Boundary::Reflective)
+        };
+        // The top is the last row when rows run up and row 0 otherwise
+        assert_eq!(
+            resolve(conditions.packed(true), [1, 3], [1, 2], grid, None),
+            None
+        );
+        assert_eq!(
+            resolve(conditions.packed(true), [1, -1], [1, 0], grid, None),
+            Some([1, 0])
+        );
+        assert_eq!(
+            resolve(conditions.packed(false), [1, -1], [1, 0], grid, None),
+            None
+        );
+    }
+
+    #[test]
+    fn closed_edges_keep_what_open_ones_lose() {
+        for preset in ["wrap", "walled", "channel"] {
+            let total = total_after(&BoundaryConditions::preset(preset).unwrap(), 50);
+            assert!((total - 2.0).abs() < 1e-4, "{} {}", preset, total);
+        }
+        let open = total_after(&BoundaryConditions::preset("open").unwrap(), 50);
+        assert!(open < 1.0, "{}", open);
+    }
+
+    #[test]
+    fn mask_walls_reflect_or_absorb() {
+        let grid = (3, 1);
+        let mask = [1, 0, 1];
+        let masked = |absorbing| BoundaryConditions {
+            mask: BoundaryMask::Image {
+                path: "mask.png".to_string(),
+                absorbing,
+            },
+            ..BoundaryConditions::uniform(Boundary::Reflective)
+        };
+        let reflecting = masked(false).packed(true);
+        assert_eq!(
+            resolve(reflecting, [1, 0], [0, 0], grid, Some(&mask)),
+            Some([0, 0])
+        );
+        assert_eq!(
+            resolve(reflecting, [2, 0], [2, 0], grid, Some(&mask)),
+            Some([2, 0])
+        );
+        let absorbing = masked(true).packed(true);
+        assert_eq!(resolve(absorbing, [1, 0], [2, 0], grid, Some(&mask)), None);
+
+        // Nothing gets through a reflecting wall
+        let mut field = vec![1.0, 0.0, 0.0];
+        for _ in 0..20 {
+            field = diffuse(&field, reflecting, grid, Some(&mask));
+        }
+        assert_eq!(field, [1.0, 0.0, 0.0]);
+        // Without a mask the walls are ignored
+        let plain = BoundaryConditions::uniform(Boundary::Reflective).packed(true);
+        assert_eq!(
+            resolve(plain, [1, 0], [0, 0], grid, Some(&mask)),
+            Some([1, 0])
+        );
+    }
+
+    #[test]
+    fn settings_name_presets_or_spell_conditions_out() {
+        for name in BOUNDARY_PRESETS {
+            assert!(
+                BoundaryConditions::from_setting(Value::from(*name)).is_ok(),
+                "{}",
+                name
+            );
+        }
+        assert!(BoundaryConditions::from_setting(Value::from("leaky")).is_err());
+
+        let conditions = BoundaryConditions::from_setting(serde_json::json!({
+            "left": "Absorbing",
+            "mask": { "type": "image", "path": "walls.png", "absorbing": false },
+        }))
+        .unwrap();
+        assert_eq!(conditions.left, Boundary::Absorbing);
+        assert_eq!(conditions.right, Boundary::Periodic);
+
+        let pathless =
+            serde_json::json!({ "mask": { "type": "image", "path": "", "absorbing": true } });
+        assert!(BoundaryConditions::from_setting(pathless).is_err());
+        assert_eq!("Reflective".parse::<Boundary>(), Ok(Boundary::Reflective));
+        assert!("sticky".parse::<Boundary>().is_err());
+    }
+}
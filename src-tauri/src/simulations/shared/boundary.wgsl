@@ -0,0 +1,59 @@
+// Edge conditions for grid fields. Mirrors boundary.rs, which documents
+// them; `conditions` is BoundaryConditions::packed. Shaders including this
+// bind `boundary_mask`, 1 for open cells and 0 for walls row by row, which
+// is only read while the mask is on.
+
+const BOUNDARY_PERIODIC: u32 = 0u;
+const BOUNDARY_REFLECTIVE: u32 = 1u;
+const BOUNDARY_ABSORBING: u32 = 2u;
+const BOUNDARY_MASK_BIT: u32 = 1024u;
+
+// Marks a neighbor that reads the ambient value
+const BOUNDARY_AMBIENT: vec2<i32> = vec2<i32>(-1, -1);
+
+// Slots 0 to 3 are the left, right, row 0 and last row edges, 4 the walls
+fn boundary_condition(conditions: u32, slot: u32) -> u32 {
+    return (conditions >> (slot * 2u)) & 3u;
+}
+
+fn boundary_axis(value: i32, size: i32, low: u32, high: u32) -> i32 {
+    var condition = high;
+    if (value < 0) {
+        condition = low;
+    } else if (value < size) {
+        return value;
+    }
+    if (condition == BOUNDARY_PERIODIC) {
+        return ((value % size) + size) % size;
+    }
+    if (condition == BOUNDARY_REFLECTIVE) {
+        if (value < 0) {
+            return min(-value - 1, size - 1);
+        }
+        return max(2 * size - value - 1, 0);
+    }
+    return -1;
+}
+
+// The cell a stencil around `center` reads for its neighbor at `cell`,
+// or BOUNDARY_AMBIENT where it reads the field's ambient value
+fn boundary_cell(conditions: u32, cell: vec2<i32>, center: vec2<i32>, size: vec2<u32>) -> vec2<i32> {
+    let bounds = vec2<i32>(size);
+    let x = boundary_axis(cell.x, bounds.x, boundary_condition(conditions, 0u), boundary_condition(conditions, 1u));
+    let y = boundary_axis(cell.y, bounds.y, boundary_condition(conditions, 2u), boundary_condition(conditions, 3u));
+    if (x < 0 || y < 0) {
+        return BOUNDARY_AMBIENT;
+    }
+    if ((conditions & BOUNDARY_MASK_BIT) != 0u && boundary_mask[u32(y) * size.x + u32(x)] == 0u) {
+        if (boundary_condition(conditions, 4u) == BOUNDARY_ABSORBING) {
+            return BOUNDARY_AMBIENT;
+        }
+        return center;
+    }
+    return vec2<i32>(x, y);
+}
+
+// Whether the mask walls `cell` off, which then holds its value
+fn boundary_walled(conditions: u32, cell: vec2<i32>, size: vec2<u32>) -> bool {
+    return (conditions & BOUNDARY_MASK_BIT) != 0u && boundary_mask[u32(cell.y) * size.x + u32(cell.x)] == 0u;
+}
@@ -63,6 +63,8 @@ pub struct Camera {
     smoothing_factor: f32,
     /// Camera sensitivity multiplier for pan and zoom operations
     sensitivity: f32,
+    /// Sub-pixel offset of the rendered view in NDC, for supersampling
+    jitter: [f32; 2],
 }
 
 impl Camera {
@@ -101,6 +103,7 @@ impl Camera {
             uniform_data,
             smoothing_factor: 0.15, // Smooth camera movement
             sensitivity: 1.0,       // Default sensitivity
+            jitter: [0.0, 0.0],
         })
     }
 
@@ -238,6 +241,22 @@ impl Camera {
         self.update_uniform();
     }
 
+    /// Jump straight to a position and zoom, without smoothing
+    pub fn set_view(&mut self, position: [f32; 2], zoom: f32) {
+        self.position = [position[0].clamp(-2.0, 2.0), position[1].clamp(-2.0, 2.0)];
+        self.target_position = self.position;
+        self.zoom = zoom.clamp(0.005, 50.0);
+        self.target_zoom = self.zoom;
+        self.update_uniform();
+    }
+
+    /// Head for the same view as `leader`, keeping this camera's own smoothing
+    pub fn follow(&mut self, leader: &Camera) {
+        self.target_position = leader.target_position;
+        self.target_zoom = leader.target_zoom;
+        self.smoothing_factor = leader.smoothing_factor;
+    }
+
     /// Update viewport dimensions (call when window is resized)
     pub fn resize(&mut self, width: f32, height: f32) {
         self.viewport_width = width;
@@ -245,12 +264,26 @@ impl Camera {
         self.update_uniform();
     }
 
+    /// Shift the rendered view by a fraction of a pixel without moving the
+    /// camera. Takes effect on the next upload.
+    pub fn set_jitter(&mut self, pixels: [f32; 2]) {
+        self.jitter = [
+            pixels[0] * 2.0 / self.viewport_width,
+            pixels[1] * 2.0 / self.viewport_height,
+        ];
+        self.update_uniform();
+    }
+
     /// Update the uniform data after camera changes
     fn update_uniform(&mut self) {
         let aspect_ratio = self.viewport_width / self.viewport_height;
+        let position = [
+            self.position[0] - self.jitter[0] / self.zoom,
+            self.position[1] - self.jitter[1] / self.zoom,
+        ];
         self.uniform_data = CameraUniform {
-            transform_matrix: Self::create_simple_transform_matrix(self.position, self.zoom),
-            position: self.position,
+            transform_matrix: Self::create_simple_transform_matrix(position, self.zoom),
+            position,
             zoom: self.zoom,
             aspect_ratio,
         };
@@ -0,0 +1,30 @@
+// Color space helpers shared by every shader that reads color schemes.
+// LUTs are stored as sRGB bytes; shaders work in linear RGB and let the
+// surface format handle the final encoding.
+
+// Convert from sRGB (gamma-corrected) to linear RGB
+fn srgb_to_linear(srgb: f32) -> f32 {
+    if (srgb <= 0.04045) {
+        return srgb / 12.92;
+    } else {
+        return pow((srgb + 0.055) / 1.055, 2.4);
+    }
+}
+
+fn srgb_to_linear_rgb(srgb: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(srgb_to_linear(srgb.r), srgb_to_linear(srgb.g), srgb_to_linear(srgb.b));
+}
+
+// Convert linear RGB to sRGB, for targets that are not sRGB-encoded
+fn linear_to_srgb(linear: f32) -> f32 {
+    if (linear <= 0.0031308) {
+        return linear * 12.92;
+    } else {
+        return 1.055 * pow(linear, 1.0 / 2.4) - 0.055;
+    }
+}
+
+fn linear_to_srgb_rgb(linear: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(linear_to_srgb(linear.r), linear_to_srgb(linear.g), linear_to_srgb(linear.b));
+}
+
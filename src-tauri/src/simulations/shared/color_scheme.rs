@@ -1,3 +1,4 @@
+use super::color_space::srgb_to_linear;
 use crate::commands::get_settings_dir;
 use crate::error::{ColorSchemeError, LutResult};
 use include_dir::{Dir, include_dir};
@@ -35,6 +36,14 @@ impl ColorScheme {
         self.blue.reverse();
     }
 
+    /// Shift every entry `offset` places toward the start, wrapping around
+    pub fn rotate(&mut self, offset: usize) {
+        let offset = offset % 256;
+        self.red.rotate_left(offset);
+        self.green.rotate_left(offset);
+        self.blue.rotate_left(offset);
+    }
+
     pub fn from_bytes(name: String, data: &[u8]) -> io::Result<Self> {
         if data.len() != 768 {
             return Err(io::Error::new(
@@ -69,15 +78,6 @@ impl ColorScheme {
             let index = if n == 1 { 0 } else { (i * 255) / (n - 1) };
             let index = index.min(255);
 
-            // Convert from sRGB (gamma-corrected) to linear RGB
-            let srgb_to_linear = |srgb: f32| -> f32 {
-                if srgb <= 0.04045 {
-                    srgb / 12.92
-                } else {
-                    ((srgb + 0.055) / 1.055).powf(2.4)
-                }
-            };
-
             let r_srgb = self.red[index] as f32 / 255.0;
             let g_srgb = self.green[index] as f32 / 255.0;
             let b_srgb = self.blue[index] as f32 / 255.0;
@@ -100,6 +100,134 @@ impl ColorScheme {
     pub fn get_last_color(&self) -> Option<Vec<f32>> {
         self.get_colors(2).last().cloned()
     }
+
+    /// Parse an Adobe/Resolve `.cube` file.
+    ///
+    /// 1D LUTs are used as-is. For 3D LUTs the neutral axis (r = g = b) is
+    /// taken, which is how the LUT maps a grayscale ramp.
+    pub fn from_cube(name: String, content: &str) -> LutResult<Self> {
+        let mut size_1d = None;
+        let mut size_3d = None;
+        let mut entries: Vec<[f32; 3]> = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let first = parts.next().unwrap_or_default();
+            match first {
+                "LUT_1D_SIZE" | "LUT_3D_SIZE" => {
+                    let size = parts
+                        .next()
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .filter(|&v| v >= 2)
+                        .ok_or_else(|| {
+                            ColorSchemeError::FormatError(format!("Invalid {}", first))
+                        })?;
+                    if first == "LUT_1D_SIZE" {
+                        size_1d = Some(size);
+                    } else {
+                        size_3d = Some(size);
+                    }
+                }
+                _ if first.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') => {
+                    let values: Vec<f32> = line
+                        .split_whitespace()
+                        .map(|v| v.parse::<f32>())
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| ColorSchemeError::FormatError(e.to_string()))?;
+                    if values.len() != 3 {
+                        return Err(ColorSchemeError::FormatError(format!(
+                            "Expected 3 values per entry, got {}",
+                            values.len()
+                        )));
+                    }
+                    entries.push([values[0], values[1], values[2]]);
+                }
+                // TITLE, DOMAIN_MIN/MAX and other keywords don't affect the ramp
+                _ => {}
+            }
+        }
+
+        let ramp: Vec<[f32; 3]> = match (size_1d, size_3d) {
+            (Some(size), _) => {
+                if entries.len() != size {
+                    return Err(ColorSchemeError::size_error(size, entries.len()));
+                }
+                entries
+            }
+            (None, Some(size)) => {
+                if entries.len() != size * size * size {
+                    return Err(ColorSchemeError::size_error(
+                        size * size * size,
+                        entries.len(),
+                    ));
+                }
+                // Red varies fastest, then green, then blue
+                (0..size)
+                    .map(|i| entries[i + i * size + i * size * size])
+                    .collect()
+            }
+            (None, None) => {
+                return Err(ColorSchemeError::FormatError(
+                    "Missing LUT_1D_SIZE or LUT_3D_SIZE".to_string(),
+                ));
+            }
+        };
+
+        Ok(Self::from_ramp(name, |t| {
+            let position = t * (ramp.len() - 1) as f32;
+            let index = (position as usize).min(ramp.len() - 2);
+            let fraction = position - index as f32;
+            let (a, b) = (ramp[index], ramp[index + 1]);
+            [
+                a[0] + (b[0] - a[0]) * fraction,
+                a[1] + (b[1] - a[1]) * fraction,
+                a[2] + (b[2] - a[2]) * fraction,
+            ]
+        }))
+    }
+
+    /// Sample a gradient image along its long axis, through the middle
+    pub fn from_gradient_image(name: String, image: &image::DynamicImage) -> Self {
+        let rgb = image.to_rgb8();
+        let (width, height) = rgb.dimensions();
+        let horizontal = width >= height;
+        let length = if horizontal { width } else { height };
+
+        Self::from_ramp(name, |t| {
+            let along = ((t * (length - 1) as f32).round() as u32).min(length - 1);
+            let pixel = if horizontal {
+                rgb.get_pixel(along, height / 2)
+            } else {
+                rgb.get_pixel(width / 2, along)
+            };
+            pixel.0.map(|c| c as f32 / 255.0)
+        })
+    }
+
+    /// Build a LUT by sampling `color_at` (sRGB, 0-1) at 256 even steps
+    fn from_ramp(name: String, color_at: impl Fn(f32) -> [f32; 3]) -> Self {
+        let mut red = [0u8; 256];
+        let mut green = [0u8; 256];
+        let mut blue = [0u8; 256];
+        for i in 0..256 {
+            let [r, g, b] = color_at(i as f32 / 255.0);
+            red[i] = (r.clamp(0.0, 1.0) * 255.0).round() as u8;
+            green[i] = (g.clamp(0.0, 1.0) * 255.0).round() as u8;
+            blue[i] = (b.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+        Self {
+            name,
+            red,
+            green,
+            blue,
+        }
+    }
+
     /// Convert to u32 buffer for GPU usage
     pub fn to_u32_buffer(&self) -> Vec<u32> {
         let mut lut_data_combined = Vec::with_capacity(768);
@@ -169,7 +297,7 @@ impl ColorSchemeManager {
         self.get_custom(name)
     }
 
-    fn lut_dir() -> LutResult<std::path::PathBuf> {
+    pub(crate) fn lut_dir() -> LutResult<std::path::PathBuf> {
         let lut_dir = get_settings_dir().join("LUTs");
         Ok(lut_dir)
     }
@@ -284,6 +412,46 @@ mod tests {
         assert_eq!(u32_buffer.len(), 768); // 768 u32 values
     }
 
+    #[test]
+    fn test_cube_1d_import() {
+        let cube = "TITLE \"ramp\"\nLUT_1D_SIZE 2\n0.0 0.0 0.0\n1.0 0.5 0.0\n";
+        let lut = ColorScheme::from_cube("ramp".to_string(), cube).unwrap();
+        assert_eq!((lut.red[0], lut.green[0], lut.blue[0]), (0, 0, 0));
+        assert_eq!((lut.red[255], lut.green[255], lut.blue[255]), (255, 128, 0));
+        assert_eq!(lut.red[128], 128);
+    }
+
+    #[test]
+    fn test_cube_3d_uses_neutral_axis() {
+        // Identity 2x2x2 cube, red fastest
+        let mut cube = String::from("LUT_3D_SIZE 2\n");
+        for b in 0..2 {
+            for g in 0..2 {
+                for r in 0..2 {
+                    cube.push_str(&format!("{} {} {}\n", r, g, b));
+                }
+            }
+        }
+        let lut = ColorScheme::from_cube("identity".to_string(), &cube).unwrap();
+        assert_eq!(lut.red, lut.green);
+        assert_eq!(lut.green, lut.blue);
+        assert_eq!(lut.red[255], 255);
+
+        assert!(ColorScheme::from_cube("bad".to_string(), "LUT_3D_SIZE 2\n0 0 0\n").is_err());
+    }
+
+    #[test]
+    fn test_gradient_image_import() {
+        let image = image::RgbImage::from_fn(16, 2, |x, _| image::Rgb([(x * 17) as u8, 0, 255]));
+        let lut = ColorScheme::from_gradient_image(
+            "strip".to_string(),
+            &image::DynamicImage::ImageRgb8(image),
+        );
+        assert_eq!(lut.red[0], 0);
+        assert_eq!(lut.red[255], 255);
+        assert_eq!(lut.blue[100], 255);
+    }
+
     #[test]
     fn test_automatic_lut_loading() {
         let manager = ColorSchemeManager::new();
@@ -0,0 +1,611 @@
+//! Color scripts: small functions from a simulation's value to a color.
+//!
+//! A script is a few `let` bindings and a final expression, in a syntax
+//! borrowed from Rhai:
+//!
+//! ```text
+//! let bands = fract(v * 6.0);
+//! if bands < 0.5 { hsv(v, 0.8, 1.0) } else { rgba(0, 0, 0, 0.5) }
+//! ```
+//!
+//! `v` runs from 0 to 1 along the color scheme. A script that ends in a
+//! number picks that place in the current scheme, so `1 - v` reverses it and
+//! `v * v` pushes its colors toward the top. One that ends in `rgb`, `rgba`
+//! or `hsv` gives the color itself, sRGB from 0 to 1; with `rgba` the alpha
+//! mixes it over the scheme's own color at `v`. The script is run once for
+//! each of the scheme's 256 entries whenever it or the scheme changes, so it
+//! never runs on the GPU and costs nothing per frame. There are no loops, so
+//! every script finishes.
+
+use super::ColorScheme;
+use crate::error::{ColorSchemeError, LutResult};
+
+/// Longest script accepted, in bytes
+pub const MAX_SCRIPT_LENGTH: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Number(f64),
+    Color([f64; 4]),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Power,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Symbol(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScript {
+    bindings: Vec<(String, Expr)>,
+    result: Expr,
+}
+
+impl ColorScript {
+    /// Parse `source` and run it over the whole scheme once, so a script
+    /// that calls something unknown fails here rather than later
+    pub fn compile(source: &str) -> LutResult<Self> {
+        if source.len() > MAX_SCRIPT_LENGTH {
+            return Err(script_error(format!(
+                "is longer than {} bytes",
+                MAX_SCRIPT_LENGTH
+            )));
+        }
+        let script = Parser::new(tokenize(source)?).script()?;
+        for i in 0..256 {
+            script.evaluate(i as f64 / 255.0)?;
+        }
+        Ok(script)
+    }
+
+    /// `base` with each entry replaced by what the script makes of it
+    pub fn apply(&self, base: &ColorScheme) -> LutResult<ColorScheme> {
+        let mut scheme = base.clone();
+        for i in 0..256 {
+            let base_color = |index: usize| {
+                [base.red[index], base.green[index], base.blue[index]].map(|c| c as f64 / 255.0)
+            };
+            let rgb = match self.evaluate(i as f64 / 255.0)? {
+                Value::Number(place) => {
+                    let place = if place.is_finite() { place } else { 0.0 };
+                    base_color((place.clamp(0.0, 1.0) * 255.0).round() as usize)
+                }
+                Value::Color([r, g, b, a]) => {
+                    let under = base_color(i);
+                    let a = a.clamp(0.0, 1.0);
+                    [0, 1, 2].map(|c| under[c] + ([r, g, b][c] - under[c]) * a)
+                }
+            };
+            let [r, g, b] = rgb.map(|c| {
+                let c = if c.is_finite() { c } else { 0.0 };
+                (c.clamp(0.0, 1.0) * 255.0).round() as u8
+            });
+            scheme.red[i] = r;
+            scheme.green[i] = g;
+            scheme.blue[i] = b;
+        }
+        Ok(scheme)
+    }
+
+    fn evaluate(&self, v: f64) -> LutResult<Value> {
+        let mut scope = vec![
+            ("v".to_string(), Value::Number(v)),
+            ("pi".to_string(), Value::Number(std::f64::consts::PI)),
+            ("tau".to_string(), Value::Number(std::f64::consts::TAU)),
+        ];
+        for (name, expr) in &self.bindings {
+            let value = evaluate(expr, &scope)?;
+            scope.push((name.clone(), value));
+        }
+        evaluate(&self.result, &scope)
+    }
+}
+
+fn script_error(message: String) -> ColorSchemeError {
+    ColorSchemeError::FormatError(format!("Color script {}", message))
+}
+
+fn tokenize(source: &str) -> LutResult<Vec<Token>> {
+    const SYMBOLS: [&str; 20] = [
+        "<=", ">=", "==", "!=", "+", "-", "*", "/", "%", "^", "(", ")", ",", ";", "=", "<", ">",
+        "{", "}", "!",
+    ];
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if rest.starts_with("//") {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+        } else if c.is_ascii_digit() || c == '.' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| script_error(format!("has a malformed number '{}'", &rest[..end])))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Identifier(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else {
+            return Err(script_error(format!("has an unexpected '{}'", c)));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: &'static str) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> LutResult<()> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(script_error(format!("expected '{}'", symbol)))
+        }
+    }
+
+    fn script(&mut self) -> LutResult<ColorScript> {
+        let mut bindings = Vec::new();
+        while self.peek() == Some(&Token::Identifier("let".to_string())) {
+            self.position += 1;
+            let Some(Token::Identifier(name)) = self.next() else {
+                return Err(script_error("expected a name after 'let'".to_string()));
+            };
+            self.expect("=")?;
+            let value = self.expression()?;
+            self.expect(";")?;
+            bindings.push((name, value));
+        }
+        let result = self.expression()?;
+        // A trailing semicolon is allowed, as in Rhai
+        self.eat(";");
+        if self.peek().is_some() {
+            return Err(script_error(
+                "has more after its final expression".to_string(),
+            ));
+        }
+        Ok(ColorScript { bindings, result })
+    }
+
+    fn expression(&mut self) -> LutResult<Expr> {
+        if self.peek() == Some(&Token::Identifier("if".to_string())) {
+            self.position += 1;
+            let condition = self.expression()?;
+            let then = self.block()?;
+            if self.next() != Some(Token::Identifier("else".to_string())) {
+                return Err(script_error("expected 'else' after an 'if'".to_string()));
+            }
+            let otherwise = if self.peek() == Some(&Token::Identifier("if".to_string())) {
+                self.expression()?
+            } else {
+                self.block()?
+            };
+            return Ok(Expr::If(
+                Box::new(condition),
+                Box::new(then),
+                Box::new(otherwise),
+            ));
+        }
+        self.comparison()
+    }
+
+    fn block(&mut self) -> LutResult<Expr> {
+        self.expect("{")?;
+        let expr = self.expression()?;
+        self.expect("}")?;
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> LutResult<Expr> {
+        let left = self.sum()?;
+        let op = match self.peek() {
+            Some(Token::Symbol("<")) => BinaryOp::Less,
+            Some(Token::Symbol("<=")) => BinaryOp::LessEqual,
+            Some(Token::Symbol(">")) => BinaryOp::Greater,
+            Some(Token::Symbol(">=")) => BinaryOp::GreaterEqual,
+            Some(Token::Symbol("==")) => BinaryOp::Equal,
+            Some(Token::Symbol("!=")) => BinaryOp::NotEqual,
+            _ => return Ok(left),
+        };
+        self.position += 1;
+        let right = self.sum()?;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn sum(&mut self) -> LutResult<Expr> {
+        let mut left = self.product()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Symbol("+")) => BinaryOp::Add,
+                Some(Token::Symbol("-")) => BinaryOp::Subtract,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> LutResult<Expr> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Symbol("*")) => BinaryOp::Multiply,
+                Some(Token::Symbol("/")) => BinaryOp::Divide,
+                Some(Token::Symbol("%")) => BinaryOp::Remainder,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> LutResult<Expr> {
+        if self.eat("-") {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> LutResult<Expr> {
+        let base = self.primary()?;
+        if self.eat("^") {
+            // Right to left, and binding tighter than a leading minus
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary(
+                BinaryOp::Power,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> LutResult<Expr> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expr::Number(number)),
+            Some(Token::Identifier(name)) => {
+                if !self.eat("(") {
+                    return Ok(Expr::Variable(name));
+                }
+                let mut arguments = Vec::new();
+                if !self.eat(")") {
+                    loop {
+                        arguments.push(self.expression()?);
+                        if self.eat(")") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Ok(Expr::Call(name, arguments))
+            }
+            Some(Token::Symbol("(")) => {
+                let expr = self.expression()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Symbol(symbol)) => {
+                Err(script_error(format!("has an unexpected '{}'", symbol)))
+            }
+            None => Err(script_error("ends too soon".to_string())),
+        }
+    }
+}
+
+fn evaluate(expr: &Expr, scope: &[(String, Value)]) -> LutResult<Value> {
+    match expr {
+        Expr::Number(number) => Ok(Value::Number(*number)),
+        Expr::Variable(name) => scope
+            .iter()
+            .rev()
+            .find(|(bound, _)| bound == name)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| script_error(format!("uses '{}' before it's defined", name))),
+        Expr::Negate(inner) => Ok(map(evaluate(inner, scope)?, |x| -x)),
+        Expr::Binary(op, left, right) => {
+            let (left, right) = (evaluate(left, scope)?, evaluate(right, scope)?);
+            Ok(combine(left, right, |a, b| match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Subtract => a - b,
+                BinaryOp::Multiply => a * b,
+                BinaryOp::Divide => a / b,
+                BinaryOp::Remainder => a % b,
+                BinaryOp::Power => a.powf(b),
+                BinaryOp::Less => (a < b) as u8 as f64,
+                BinaryOp::LessEqual => (a <= b) as u8 as f64,
+                BinaryOp::Greater => (a > b) as u8 as f64,
+                BinaryOp::GreaterEqual => (a >= b) as u8 as f64,
+                BinaryOp::Equal => (a == b) as u8 as f64,
+                BinaryOp::NotEqual => (a != b) as u8 as f64,
+            }))
+        }
+        Expr::If(condition, then, otherwise) => match evaluate(condition, scope)? {
+            Value::Number(condition) if condition != 0.0 => evaluate(then, scope),
+            Value::Number(_) => evaluate(otherwise, scope),
+            Value::Color(_) => Err(script_error(
+                "tests a color in an 'if', which needs a number".to_string(),
+            )),
+        },
+        Expr::Call(name, arguments) => {
+            let arguments = arguments
+                .iter()
+                .map(|argument| evaluate(argument, scope))
+                .collect::<LutResult<Vec<_>>>()?;
+            call(name, &arguments)
+        }
+    }
+}
+
+fn map(value: Value, f: impl Fn(f64) -> f64) -> Value {
+    match value {
+        Value::Number(x) => Value::Number(f(x)),
+        Value::Color(color) => Value::Color(color.map(f)),
+    }
+}
+
+/// Numbers spread over every channel of a color they meet
+fn combine(left: Value, right: Value, f: impl Fn(f64, f64) -> f64) -> Value {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => Value::Number(f(a, b)),
+        (Value::Color(a), Value::Number(b)) => Value::Color(a.map(|a| f(a, b))),
+        (Value::Number(a), Value::Color(b)) => Value::Color(b.map(|b| f(a, b))),
+        (Value::Color(a), Value::Color(b)) => Value::Color([0, 1, 2, 3].map(|c| f(a[c], b[c]))),
+    }
+}
+
+fn call(name: &str, arguments: &[Value]) -> LutResult<Value> {
+    let arity = match name {
+        "sin" | "cos" | "tan" | "abs" | "floor" | "ceil" | "round" | "fract" | "sqrt" | "exp"
+        | "ln" => 1,
+        "pow" | "min" | "max" | "step" => 2,
+        "clamp" | "mix" | "smoothstep" | "rgb" | "hsv" => 3,
+        "rgba" => 4,
+        _ => {
+            return Err(script_error(format!(
+                "calls an unknown function '{}'",
+                name
+            )));
+        }
+    };
+    if arguments.len() != arity {
+        return Err(script_error(format!(
+            "calls {} with {} arguments, it takes {}",
+            name,
+            arguments.len(),
+            arity
+        )));
+    }
+    // The functions that only make sense for plain numbers
+    let numbers = || -> LutResult<Vec<f64>> {
+        arguments
+            .iter()
+            .map(|argument| match argument {
+                Value::Number(x) => Ok(*x),
+                Value::Color(_) => Err(script_error(format!(
+                    "passes a color to {}, which takes numbers",
+                    name
+                ))),
+            })
+            .collect()
+    };
+    let a = arguments[0];
+    let value = match name {
+        "sin" => map(a, f64::sin),
+        "cos" => map(a, f64::cos),
+        "tan" => map(a, f64::tan),
+        "abs" => map(a, f64::abs),
+        "floor" => map(a, f64::floor),
+        "ceil" => map(a, f64::ceil),
+        "round" => map(a, f64::round),
+        "fract" => map(a, |x| x - x.floor()),
+        "sqrt" => map(a, f64::sqrt),
+        "exp" => map(a, f64::exp),
+        "ln" => map(a, f64::ln),
+        "pow" => combine(a, arguments[1], f64::powf),
+        "min" => combine(a, arguments[1], f64::min),
+        "max" => combine(a, arguments[1], f64::max),
+        "clamp" => combine(combine(a, arguments[1], f64::max), arguments[2], f64::min),
+        "mix" => {
+            let Value::Number(t) = arguments[2] else {
+                return Err(script_error(
+                    "calls mix with a color as its amount".to_string(),
+                ));
+            };
+            combine(a, arguments[1], |a, b| a + (b - a) * t)
+        }
+        "step" => {
+            let n = numbers()?;
+            Value::Number((n[1] >= n[0]) as u8 as f64)
+        }
+        "smoothstep" => {
+            let n = numbers()?;
+            let t = ((n[2] - n[0]) / (n[1] - n[0])).clamp(0.0, 1.0);
+            Value::Number(t * t * (3.0 - 2.0 * t))
+        }
+        "rgb" => {
+            let n = numbers()?;
+            Value::Color([n[0], n[1], n[2], 1.0])
+        }
+        "rgba" => {
+            let n = numbers()?;
+            Value::Color([n[0], n[1], n[2], n[3]])
+        }
+        _ => {
+            let n = numbers()?;
+            let [r, g, b] = hsv_to_rgb(n[0], n[1], n[2]);
+            Value::Color([r, g, b, 1.0])
+        }
+    };
+    Ok(value)
+}
+
+/// Hue wraps around every 1, saturation and value run from 0 to 1
+fn hsv_to_rgb(h: f64, s: f64, v: f64) -> [f64; 3] {
+    let h = (h - h.floor()) * 6.0;
+    let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+    let chroma = v * s;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let [r, g, b] = match h as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    let m = v - chroma;
+    [r + m, g + m, b + m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp() -> ColorScheme {
+        let mut ramp = [0u8; 256];
+        for (i, entry) in ramp.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+        ColorScheme {
+            name: "Ramp".to_string(),
+            red: ramp,
+            green: ramp,
+            blue: ramp,
+        }
+    }
+
+    #[test]
+    fn a_number_picks_a_place_in_the_scheme() {
+        let reversed = ColorScript::compile("1 - v")
+            .unwrap()
+            .apply(&ramp())
+            .unwrap();
+        assert_eq!(reversed.red[0], 255);
+        assert_eq!(reversed.red[255], 0);
+
+        let squared = ColorScript::compile("let t = v; t ^ 2")
+            .unwrap()
+            .apply(&ramp())
+            .unwrap();
+        assert_eq!(squared.green[128], 64);
+    }
+
+    #[test]
+    fn colors_replace_or_mix_over_the_scheme() {
+        let script = ColorScript::compile(
+            "// red below the middle, half white above
+             if v < 0.5 { rgb(1, 0, 0) } else { rgba(1, 1, 1, 0.5) }",
+        )
+        .unwrap();
+        let scheme = script.apply(&ramp()).unwrap();
+        assert_eq!(
+            [scheme.red[10], scheme.green[10], scheme.blue[10]],
+            [255, 0, 0]
+        );
+        assert_eq!(scheme.red[255], 255);
+        // Halfway from 201 to 255
+        assert_eq!(scheme.green[201], 228);
+    }
+
+    #[test]
+    fn operators_follow_the_usual_precedence() {
+        let script = ColorScript::compile("-2 ^ 2 + 3 * 2 - 1").unwrap();
+        assert_eq!(script.evaluate(0.0).unwrap(), Value::Number(1.0));
+        let script = ColorScript::compile("mix(rgb(0, 0, 0), rgb(1, 1, 1), 0.25) * 2").unwrap();
+        assert_eq!(
+            script.evaluate(0.0).unwrap(),
+            Value::Color([0.5, 0.5, 0.5, 2.0])
+        );
+    }
+
+    #[test]
+    fn hsv_goes_round_the_hues() {
+        assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0), [1.0, 0.0, 0.0]);
+        let green = hsv_to_rgb(1.0 / 3.0, 1.0, 1.0);
+        assert!(green[0].abs() < 1e-9 && (green[1] - 1.0).abs() < 1e-9);
+        assert_eq!(hsv_to_rgb(1.0, 1.0, 1.0), hsv_to_rgb(0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn broken_scripts_are_rejected_when_compiled() {
+        for source in [
+            "",
+            "v +",
+            "let = 2; v",
+            "sin(v, 2)",
+            "unknown(v)",
+            "w * 2",
+            "if rgb(1, 1, 1) { v } else { v }",
+            "v v",
+            "v # 2",
+        ] {
+            assert!(ColorScript::compile(source).is_err(), "{}", source);
+        }
+        assert!(ColorScript::compile(&"v".repeat(MAX_SCRIPT_LENGTH + 1)).is_err());
+    }
+}
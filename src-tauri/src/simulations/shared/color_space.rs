@@ -0,0 +1,100 @@
+//! CPU-side color space conversions and output color space selection.
+//!
+//! These mirror `color.wgsl` so that colors computed on the CPU (species
+//! colors, UI swatches, exported frames) match what the shaders produce.
+
+use crate::commands::app_settings::ColorSpace;
+
+/// Convert from sRGB (gamma-corrected) to linear RGB
+pub fn srgb_to_linear(srgb: f32) -> f32 {
+    if srgb <= 0.04045 {
+        srgb / 12.92
+    } else {
+        ((srgb + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert from linear RGB to sRGB (gamma-corrected)
+pub fn linear_to_srgb(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Encode a linear channel as an 8-bit sRGB value, clipping out-of-gamut values
+pub fn linear_to_srgb_u8(linear: f32) -> u8 {
+    (linear_to_srgb(linear).clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Pick a surface format for the requested output color space.
+///
+/// Display P3 output uses a half-float surface, which the Metal, DX12 and
+/// Vulkan backends present as extended-range linear sRGB. Shaders keep
+/// working in linear sRGB either way, so colors only differ where the display
+/// can show more than sRGB. Falls back to an sRGB format when the surface
+/// doesn't offer one.
+pub fn select_surface_format(
+    formats: &[wgpu::TextureFormat],
+    color_space: ColorSpace,
+) -> wgpu::TextureFormat {
+    if color_space == ColorSpace::DisplayP3 {
+        if formats.contains(&wgpu::TextureFormat::Rgba16Float) {
+            return wgpu::TextureFormat::Rgba16Float;
+        }
+        tracing::warn!("Wide-gamut surface format not available, falling back to sRGB");
+    }
+
+    formats
+        .iter()
+        .find(|f| f.is_srgb())
+        .copied()
+        .unwrap_or(formats[0])
+}
+
+/// The color space a surface format actually presents in
+pub fn active_color_space(format: wgpu::TextureFormat) -> ColorSpace {
+    match format {
+        wgpu::TextureFormat::Rgba16Float => ColorSpace::DisplayP3,
+        _ => ColorSpace::Srgb,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srgb_round_trip() {
+        for i in 0..=255u8 {
+            let linear = srgb_to_linear(i as f32 / 255.0);
+            assert_eq!(linear_to_srgb_u8(linear), i);
+        }
+    }
+
+    #[test]
+    fn test_surface_format_fallback() {
+        let formats = [
+            wgpu::TextureFormat::Bgra8Unorm,
+            wgpu::TextureFormat::Bgra8UnormSrgb,
+        ];
+        assert_eq!(
+            select_surface_format(&formats, ColorSpace::DisplayP3),
+            wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+
+        let formats = [
+            wgpu::TextureFormat::Bgra8UnormSrgb,
+            wgpu::TextureFormat::Rgba16Float,
+        ];
+        assert_eq!(
+            select_surface_format(&formats, ColorSpace::DisplayP3),
+            wgpu::TextureFormat::Rgba16Float
+        );
+        assert_eq!(
+            select_surface_format(&formats, ColorSpace::Srgb),
+            wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! Position-based constraint solving for point masses joined by springs,
+//! for simulations with bodies that bend and squash.
+//!
+//! Each step is split into small substeps. A substep moves every point by
+//! its velocity and gravity, pulls the points back toward what the
+//! constraints allow, then takes the velocity as how far each point really
+//! moved. With substeps this small a single pass over the constraints is
+//! as good as more iterations (XPBD's "small steps"), and a constraint's
+//! compliance sets how soft it is independently of the step size.
+
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointMass {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    /// Where the point was at the start of the substep
+    pub previous: [f32; 2],
+    pub mass: f32,
+    /// Pinned points stay wherever they're put
+    pub pinned: bool,
+}
+
+impl PointMass {
+    pub fn new(position: [f32; 2], mass: f32) -> Self {
+        Self {
+            position,
+            velocity: [0.0, 0.0],
+            previous: position,
+            mass,
+            pinned: false,
+        }
+    }
+
+    pub fn inverse_mass(&self) -> f32 {
+        if self.pinned || self.mass <= 0.0 {
+            0.0
+        } else {
+            1.0 / self.mass
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    /// Keeps two points `rest` apart, like a spring
+    Distance {
+        a: usize,
+        b: usize,
+        rest: f32,
+        compliance: f32,
+    },
+    /// Keeps the area inside a closed loop of points, listed
+    /// counterclockwise, at `rest`, like the gas inside a balloon
+    Area {
+        ring: Vec<usize>,
+        rest: f32,
+        compliance: f32,
+    },
+}
+
+impl Constraint {
+    /// A spring between `a` and `b` at its current length
+    pub fn distance(points: &[PointMass], a: usize, b: usize, compliance: f32) -> Self {
+        Self::Distance {
+            a,
+            b,
+            rest: length(sub(points[a].position, points[b].position)),
+            compliance,
+        }
+    }
+
+    /// Pressure holding `ring` at its current area
+    pub fn area(points: &[PointMass], ring: Vec<usize>, compliance: f32) -> Self {
+        let rest = ring_area(points, &ring);
+        Self::Area {
+            ring,
+            rest,
+            compliance,
+        }
+    }
+
+    /// Move the points toward satisfying the constraint over a substep of
+    /// `h` seconds
+    fn project(&self, points: &mut [PointMass], h: f32) {
+        match self {
+            Self::Distance {
+                a,
+                b,
+                rest,
+                compliance,
+            } => {
+                let (a, b) = (*a, *b);
+                let (wa, wb) = (points[a].inverse_mass(), points[b].inverse_mass());
+                let weight = wa + wb;
+                if weight == 0.0 {
+                    return;
+                }
+                let delta = sub(points[a].position, points[b].position);
+                let distance = length(delta);
+                if distance < 1e-9 {
+                    return;
+                }
+                let normal = scale(delta, 1.0 / distance);
+                let lambda = -(distance - rest) / (weight + compliance / (h * h));
+                points[a].position = add(points[a].position, scale(normal, wa * lambda));
+                points[b].position = add(points[b].position, scale(normal, -wb * lambda));
+            }
+            Self::Area {
+                ring,
+                rest,
+                compliance,
+            } => {
+                let count = ring.len();
+                if count < 3 {
+                    return;
+                }
+                // The area's gradient at each point is half the chord
+                // between its neighbors, turned a quarter
+                let gradient = |points: &[PointMass], i: usize| {
+                    let before = points[ring[(i + count - 1) % count]].position;
+                    let after = points[ring[(i + 1) % count]].position;
+                    [0.5 * (after[1] - before[1]), 0.5 * (before[0] - after[0])]
+                };
+                let mut weight = 0.0;
+                for i in 0..count {
+                    let g = gradient(points, i);
+                    weight += points[ring[i]].inverse_mass() * dot(g, g);
+                }
+                if weight == 0.0 {
+                    return;
+                }
+                let lambda = -(ring_area(points, ring) - rest) / (weight + compliance / (h * h));
+                let gradients: Vec<[f32; 2]> = (0..count).map(|i| gradient(points, i)).collect();
+                for (&point, g) in ring.iter().zip(gradients) {
+                    let w = points[point].inverse_mass();
+                    points[point].position = add(points[point].position, scale(g, w * lambda));
+                }
+            }
+        }
+    }
+}
+
+/// How a [`step`] moves the points
+#[derive(Debug, Clone, Copy)]
+pub struct StepSettings {
+    pub gravity: [f32; 2],
+    /// Share of velocity lost each second, 0 to 1
+    pub damping: f32,
+    pub substeps: u32,
+}
+
+/// Advance the points `dt` seconds. `each_substep` runs after the
+/// constraints in every substep, given the share of the step done so far,
+/// for collisions and anything dragging points about.
+pub fn step(
+    points: &mut [PointMass],
+    constraints: &[Constraint],
+    settings: &StepSettings,
+    dt: f32,
+    mut each_substep: impl FnMut(&mut [PointMass], f32),
+) {
+    let substeps = settings.substeps.max(1);
+    let h = dt / substeps as f32;
+    if h <= 0.0 {
+        return;
+    }
+    let keep = (1.0 - settings.damping.clamp(0.0, 1.0)).powf(h);
+    for substep in 0..substeps {
+        for point in points.iter_mut() {
+            point.previous = point.position;
+            if point.inverse_mass() > 0.0 {
+                point.velocity = add(point.velocity, scale(settings.gravity, h));
+                point.position = add(point.position, scale(point.velocity, h));
+            }
+        }
+        for constraint in constraints {
+            constraint.project(points, h);
+        }
+        each_substep(points, (substep + 1) as f32 / substeps as f32);
+        for point in points.iter_mut() {
+            point.velocity = scale(sub(point.position, point.previous), keep / h);
+        }
+    }
+}
+
+/// Push apart two points closer than `minimum`, in proportion to how
+/// easily each moves. Returns whether they touched.
+pub fn separate(points: &mut [PointMass], a: usize, b: usize, minimum: f32) -> bool {
+    let delta = sub(points[a].position, points[b].position);
+    let distance_squared = dot(delta, delta);
+    if distance_squared >= minimum * minimum || distance_squared < 1e-18 {
+        return false;
+    }
+    let (wa, wb) = (points[a].inverse_mass(), points[b].inverse_mass());
+    if wa + wb == 0.0 {
+        return true;
+    }
+    let distance = distance_squared.sqrt();
+    let push = scale(delta, (minimum - distance) / (distance * (wa + wb)));
+    points[a].position = add(points[a].position, scale(push, wa));
+    points[b].position = add(points[b].position, scale(push, -wb));
+    true
+}
+
+/// Signed area inside a loop of points, positive counterclockwise
+pub fn ring_area(points: &[PointMass], ring: &[usize]) -> f32 {
+    let count = ring.len();
+    let mut twice = 0.0;
+    for i in 0..count {
+        let [x0, y0] = points[ring[i]].position;
+        let [x1, y1] = points[ring[(i + 1) % count]].position;
+        twice += x0 * y1 - x1 * y0;
+    }
+    twice * 0.5
+}
+
+/// Points evenly around a circle, counterclockwise from the right
+pub fn circle(center: [f32; 2], radius: f32, count: usize) -> Vec<[f32; 2]> {
+    (0..count)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f32 / count as f32;
+            [
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]
+        })
+        .collect()
+}
+
+fn add(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+fn sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn scale(a: [f32; 2], s: f32) -> [f32; 2] {
+    [a[0] * s, a[1] * s]
+}
+
+fn dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn length(a: [f32; 2]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STILL: StepSettings = StepSettings {
+        gravity: [0.0, 0.0],
+        damping: 0.0,
+        substeps: 8,
+    };
+
+    #[test]
+    fn stiff_springs_return_to_their_rest_length() {
+        let mut points = vec![
+            PointMass::new([0.0, 0.0], 1.0),
+            PointMass::new([1.0, 0.0], 1.0),
+        ];
+        let spring = Constraint::distance(&points, 0, 1, 0.0);
+        points[1].position = [1.5, 0.0];
+        step(&mut points, &[spring], &STILL, 1.0 / 60.0, |_, _| {});
+        let gap = points[1].position[0] - points[0].position[0];
+        assert!((gap - 1.0).abs() < 1e-4, "{}", gap);
+        // Both ends moved the same amount, the middle staying put
+        assert!((points[0].position[0] + points[1].position[0] - 1.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn compliant_springs_give_way_more() {
+        let stretched = |compliance| {
+            let mut points = vec![
+                PointMass::new([0.0, 0.0], 1.0),
+                PointMass::new([0.0, -1.0], 1.0),
+            ];
+            points[0].pinned = true;
+            let spring = Constraint::distance(&points, 0, 1, compliance);
+            let hanging = StepSettings {
+                gravity: [0.0, -9.8],
+                damping: 0.9,
+                ..STILL
+            };
+            for _ in 0..600 {
+                step(
+                    &mut points,
+                    std::slice::from_ref(&spring),
+                    &hanging,
+                    1.0 / 60.0,
+                    |_, _| {},
+                );
+            }
+            -points[1].position[1] - 1.0
+        };
+        let (stiff, soft) = (stretched(1e-6), stretched(1e-2));
+        assert!(stiff < 1e-3, "{}", stiff);
+        assert!(soft > 0.05, "{}", soft);
+        // Hooke's law: stretch is weight times compliance
+        assert!((soft - 9.8 * 1e-2).abs() < 0.01, "{}", soft);
+    }
+
+    #[test]
+    fn pressure_holds_a_squashed_loop_at_its_area() {
+        let ring: Vec<usize> = (0..16).collect();
+        let mut points: Vec<PointMass> = circle([0.0, 0.0], 1.0, 16)
+            .into_iter()
+            .map(|p| PointMass::new(p, 1.0))
+            .collect();
+        let area = Constraint::area(&points, ring.clone(), 0.0);
+        let rest = ring_area(&points, &ring);
+        assert!((rest - PI).abs() < 0.1);
+        for point in &mut points {
+            point.position[1] *= 0.5;
+        }
+        step(&mut points, &[area], &STILL, 1.0 / 60.0, |_, _| {});
+        assert!((ring_area(&points, &ring) - rest).abs() < rest * 0.01);
+    }
+
+    #[test]
+    fn velocity_comes_from_how_far_points_really_moved() {
+        let mut points = vec![PointMass::new([0.0, 0.0], 1.0)];
+        points[0].velocity = [1.0, 0.0];
+        // A wall at x = 0.01 stops it dead
+        step(&mut points, &[], &STILL, 0.1, |points, _| {
+            points[0].position[0] = points[0].position[0].min(0.01);
+        });
+        assert!((points[0].position[0] - 0.01).abs() < 1e-6);
+        assert!(points[0].velocity[0].abs() < 1e-6);
+    }
+
+    #[test]
+    fn pinned_points_never_move() {
+        let mut points = vec![
+            PointMass::new([0.0, 0.0], 1.0),
+            PointMass::new([1.0, 0.0], 1.0),
+        ];
+        points[0].pinned = true;
+        let spring = Constraint::distance(&points, 0, 1, 0.0);
+        let falling = StepSettings {
+            gravity: [0.0, -9.8],
+            ..STILL
+        };
+        for _ in 0..120 {
+            step(
+                &mut points,
+                std::slice::from_ref(&spring),
+                &falling,
+                1.0 / 60.0,
+                |_, _| {},
+            );
+        }
+        assert_eq!(points[0].position, [0.0, 0.0]);
+        let [x, y] = points[1].position;
+        assert!((x.hypot(y) - 1.0).abs() < 1e-3);
+        assert!(y < 0.0);
+    }
+
+    #[test]
+    fn separate_pushes_overlapping_points_to_the_minimum() {
+        let mut points = vec![
+            PointMass::new([0.0, 0.0], 1.0),
+            PointMass::new([0.1, 0.0], 3.0),
+        ];
+        assert!(separate(&mut points, 0, 1, 0.5));
+        let gap = points[1].position[0] - points[0].position[0];
+        assert!((gap - 0.5).abs() < 1e-6);
+        // The light point moved three times as far
+        assert!((-points[0].position[0] - 3.0 * (points[1].position[0] - 0.1)).abs() < 1e-6);
+        assert!(!separate(&mut points, 0, 1, 0.5));
+    }
+}
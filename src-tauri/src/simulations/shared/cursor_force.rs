@@ -0,0 +1,177 @@
+//! Shapes of the force field the cursor applies in the particle simulations.
+//!
+//! Besides the radial attract/repel each simulation already had, the cursor
+//! can stir particles into a vortex, push them along the direction it moves,
+//! or shake them with turbulence. Every mode has its own strength curve, the
+//! falloff from the cursor's center to its edge, so switching modes brings
+//! back the curve last picked for that mode. The WGSL side lives in
+//! `cursor_force.wgsl`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CursorMode {
+    /// Attract with the primary button, repel with the secondary one
+    #[default]
+    Radial,
+    /// Swirl around the cursor, reversed with the secondary button
+    Vortex,
+    /// Push along the cursor's movement, pull against it with the secondary button
+    Push,
+    /// Noise that drifts over time
+    Turbulence,
+}
+
+impl CursorMode {
+    /// Id used by `cursor_force.wgsl`
+    pub fn gpu_id(self) -> u32 {
+        match self {
+            CursorMode::Radial => 0,
+            CursorMode::Vortex => 1,
+            CursorMode::Push => 2,
+            CursorMode::Turbulence => 3,
+        }
+    }
+}
+
+/// Strength from the cursor's center (full strength) to its edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrengthCurve {
+    #[default]
+    Linear,
+    /// Smoothstep, soft at both ends
+    Smooth,
+    /// Full strength up to the edge
+    Constant,
+    /// Quadratic, concentrated near the center
+    Focused,
+}
+
+impl StrengthCurve {
+    /// Id used by `cursor_force.wgsl`
+    pub fn gpu_id(self) -> u32 {
+        match self {
+            StrengthCurve::Linear => 0,
+            StrengthCurve::Smooth => 1,
+            StrengthCurve::Constant => 2,
+            StrengthCurve::Focused => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrengthCurves {
+    pub radial: StrengthCurve,
+    pub vortex: StrengthCurve,
+    pub push: StrengthCurve,
+    pub turbulence: StrengthCurve,
+}
+
+impl Default for StrengthCurves {
+    fn default() -> Self {
+        Self {
+            // Matches the falloff radial mode always had
+            radial: StrengthCurve::Linear,
+            vortex: StrengthCurve::Smooth,
+            push: StrengthCurve::Smooth,
+            turbulence: StrengthCurve::Constant,
+        }
+    }
+}
+
+impl StrengthCurves {
+    pub fn get(&self, mode: CursorMode) -> StrengthCurve {
+        match mode {
+            CursorMode::Radial => self.radial,
+            CursorMode::Vortex => self.vortex,
+            CursorMode::Push => self.push,
+            CursorMode::Turbulence => self.turbulence,
+        }
+    }
+
+    fn get_mut(&mut self, mode: CursorMode) -> &mut StrengthCurve {
+        match mode {
+            CursorMode::Radial => &mut self.radial,
+            CursorMode::Vortex => &mut self.vortex,
+            CursorMode::Push => &mut self.push,
+            CursorMode::Turbulence => &mut self.turbulence,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CursorForceField {
+    pub mode: CursorMode,
+    pub curves: StrengthCurves,
+    /// Seconds of simulation, animates turbulence
+    #[serde(skip)]
+    time: f32,
+}
+
+impl CursorForceField {
+    /// Switch to `mode`, changing its strength curve too if `curve` is given
+    pub fn set_mode(&mut self, mode: CursorMode, curve: Option<StrengthCurve>) {
+        self.mode = mode;
+        if let Some(curve) = curve {
+            *self.curves.get_mut(mode) = curve;
+        }
+    }
+
+    /// The strength curve of the current mode
+    pub fn curve(&self) -> StrengthCurve {
+        self.curves.get(self.mode)
+    }
+
+    pub fn advance(&mut self, delta_time: f32) {
+        // Wrapped so the noise offset keeps its precision
+        self.time = (self.time + delta_time) % 1000.0;
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gpu_ids_match_the_shader() {
+        let shader = include_str!("cursor_force.wgsl");
+        let modes = [
+            ("RADIAL", CursorMode::Radial),
+            ("VORTEX", CursorMode::Vortex),
+            ("PUSH", CursorMode::Push),
+            ("TURBULENCE", CursorMode::Turbulence),
+        ];
+        for (name, mode) in modes {
+            let line = format!("const CURSOR_MODE_{}: u32 = {}u;", name, mode.gpu_id());
+            assert!(shader.contains(&line), "missing `{}`", line);
+        }
+        let curves = [
+            ("LINEAR", StrengthCurve::Linear),
+            ("SMOOTH", StrengthCurve::Smooth),
+            ("CONSTANT", StrengthCurve::Constant),
+            ("FOCUSED", StrengthCurve::Focused),
+        ];
+        for (name, curve) in curves {
+            let line = format!("const CURSOR_CURVE_{}: u32 = {}u;", name, curve.gpu_id());
+            assert!(shader.contains(&line), "missing `{}`", line);
+        }
+    }
+
+    #[test]
+    fn each_mode_keeps_its_own_curve() {
+        let mut field = CursorForceField::default();
+        assert_eq!(field.curve(), StrengthCurve::Linear);
+        field.set_mode(CursorMode::Vortex, Some(StrengthCurve::Focused));
+        field.set_mode(CursorMode::Push, None);
+        assert_eq!(field.curve(), StrengthCurve::Smooth);
+        field.set_mode(CursorMode::Vortex, None);
+        assert_eq!(field.curve(), StrengthCurve::Focused);
+        assert_eq!(field.curves.radial, StrengthCurve::Linear);
+    }
+}
@@ -0,0 +1,89 @@
+// Cursor force field shapes shared by the particle simulations.
+// Prepended to a compute shader; ids match `CursorMode` and `StrengthCurve`.
+
+const CURSOR_MODE_RADIAL: u32 = 0u;
+const CURSOR_MODE_VORTEX: u32 = 1u;
+const CURSOR_MODE_PUSH: u32 = 2u;
+const CURSOR_MODE_TURBULENCE: u32 = 3u;
+
+const CURSOR_CURVE_LINEAR: u32 = 0u;
+const CURSOR_CURVE_SMOOTH: u32 = 1u;
+const CURSOR_CURVE_CONSTANT: u32 = 2u;
+const CURSOR_CURVE_FOCUSED: u32 = 3u;
+
+// Strength at `t`, the distance from the cursor as a fraction of its radius
+fn cursor_falloff(t: f32, curve: u32) -> f32 {
+    if (t > 1.0) {
+        return 0.0;
+    }
+    let inside = 1.0 - t;
+    switch curve {
+        case CURSOR_CURVE_SMOOTH: {
+            return inside * inside * (3.0 - 2.0 * inside);
+        }
+        case CURSOR_CURVE_CONSTANT: {
+            return 1.0;
+        }
+        case CURSOR_CURVE_FOCUSED: {
+            return inside * inside;
+        }
+        default: {
+            return inside;
+        }
+    }
+}
+
+fn cursor_hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+fn cursor_value_noise(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = cursor_hash(cell);
+    let b = cursor_hash(cell + vec2<f32>(1.0, 0.0));
+    let c = cursor_hash(cell + vec2<f32>(0.0, 1.0));
+    let d = cursor_hash(cell + vec2<f32>(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+// Unit-strength force of the vortex, push and turbulence modes; radial mode
+// is left to each simulation. `to_cursor` points from the particle to the
+// cursor, `direction` is the cursor's movement and `flip` is -1.0 for the
+// secondary mouse button, which reverses the field.
+fn cursor_shape_force(
+    mode: u32,
+    to_cursor: vec2<f32>,
+    position: vec2<f32>,
+    direction: vec2<f32>,
+    radius: f32,
+    time: f32,
+    flip: f32,
+) -> vec2<f32> {
+    let distance = length(to_cursor);
+    if (distance < 1e-6) {
+        return vec2<f32>(0.0, 0.0);
+    }
+    let toward = to_cursor / distance;
+    switch mode {
+        case CURSOR_MODE_VORTEX: {
+            return vec2<f32>(-toward.y, toward.x) * flip;
+        }
+        case CURSOR_MODE_PUSH: {
+            if (length(direction) < 1e-6) {
+                return vec2<f32>(0.0, 0.0);
+            }
+            return normalize(direction) * flip;
+        }
+        case CURSOR_MODE_TURBULENCE: {
+            // Eddies a few per cursor radius, drifting over time
+            let p = position / max(radius, 1e-3) * 3.0 + vec2<f32>(time * 0.7, time * 0.4);
+            let angle = cursor_value_noise(p) * 12.566371;
+            return vec2<f32>(cos(angle), sin(angle)) * flip;
+        }
+        default: {
+            return toward * flip;
+        }
+    }
+}
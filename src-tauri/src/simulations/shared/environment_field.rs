@@ -0,0 +1,249 @@
+//! Scalar fields laid over the world that bias particle motion: particles
+//! drift up the slope of the field, toward its high values, or down it with
+//! a negative strength. Flow and Primordial Particles each keep their own
+//! [`EnvironmentField`], rasterized on the CPU into a fixed size grid for
+//! `environment_field.wgsl` whenever it changes.
+//!
+//! The grid covers world space -1 to 1 on both axes, row by row from
+//! y = -1 up. Images are flipped so they appear upright.
+
+use serde::{Deserialize, Serialize};
+
+use super::field_image::image_to_field;
+use crate::error::{SimulationError, SimulationResult};
+
+/// Side of the rasterized grid
+pub const FIELD_SIZE: u32 = 256;
+const HEADER_LEN: usize = 16;
+/// Size of the storage buffer `environment_field.wgsl` binds
+pub const BUFFER_SIZE: u64 = (HEADER_LEN + (FIELD_SIZE * FIELD_SIZE) as usize * 4) as u64;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EnvironmentShape {
+    #[default]
+    None,
+    /// Highest at `center`, falling to 0 at `radius` world units from it
+    Radial { center: [f32; 2], radius: f32 },
+    /// Rising from 0 to 1 across the world toward `angle` radians
+    Linear { angle: f32 },
+    /// Fractal value noise with `scale` features across the world
+    Noise { scale: f32, seed: u32 },
+    /// Luminance of the image at `path`, stretched over the world
+    Image { path: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentField {
+    pub shape: EnvironmentShape,
+    /// Drift in world units per second along a slope of 1 per world unit.
+    /// Negative pushes toward low values.
+    pub strength: f32,
+    pub invert: bool,
+}
+
+impl Default for EnvironmentField {
+    fn default() -> Self {
+        Self {
+            shape: EnvironmentShape::None,
+            strength: 0.2,
+            invert: false,
+        }
+    }
+}
+
+impl EnvironmentField {
+    pub fn validate(&self) -> SimulationResult<()> {
+        let invalid = |message: &str| Err(SimulationError::InvalidParameter(message.to_string()));
+        if !self.strength.is_finite() {
+            return invalid("Environment field strength must be finite");
+        }
+        match &self.shape {
+            EnvironmentShape::Radial { center, radius }
+                if !center.iter().chain([radius]).all(|v| v.is_finite()) || *radius <= 0.0 =>
+            {
+                return invalid("Radial environment fields need a finite center and radius");
+            }
+            EnvironmentShape::Linear { angle } if !angle.is_finite() => {
+                return invalid("Linear environment field angle must be finite");
+            }
+            EnvironmentShape::Noise { scale, .. } if !(0.1..=64.0).contains(scale) => {
+                return invalid("Environment noise scale must be between 0.1 and 64");
+            }
+            EnvironmentShape::Image { path } if path.is_empty() => {
+                return invalid("Image environment fields need an image path");
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Field values, 0-1, on the [`FIELD_SIZE`] grid
+    pub fn rasterize(&self) -> SimulationResult<Vec<f32>> {
+        let size = FIELD_SIZE as usize;
+        let world = |i: usize| i as f32 / (size - 1) as f32 * 2.0 - 1.0;
+        let mut values = match &self.shape {
+            EnvironmentShape::None => vec![0.0; size * size],
+            EnvironmentShape::Image { path } => {
+                let image = image::open(path).map_err(|e| {
+                    SimulationError::InvalidParameter(format!(
+                        "Failed to open environment image {}: {}",
+                        path, e
+                    ))
+                })?;
+                // Image rows run top down, the grid bottom up
+                let rows = image_to_field(&image, FIELD_SIZE, FIELD_SIZE);
+                rows.chunks_exact(size).rev().flatten().copied().collect()
+            }
+            shape => (0..size)
+                .flat_map(|y| (0..size).map(move |x| (world(x), world(y))))
+                .map(|(x, y)| shape_value(shape, x, y))
+                .collect(),
+        };
+        if self.invert && self.shape != EnvironmentShape::None {
+            values.iter_mut().for_each(|v| *v = 1.0 - *v);
+        }
+        Ok(values)
+    }
+
+    /// `EnvironmentField` in `environment_field.wgsl`: the grid size, the
+    /// strength, then the values. An empty field has no strength.
+    pub fn to_gpu_bytes(&self) -> SimulationResult<Vec<u8>> {
+        let values = self.rasterize()?;
+        let strength = if self.shape == EnvironmentShape::None {
+            0.0
+        } else {
+            self.strength
+        };
+        let mut bytes = Vec::with_capacity(BUFFER_SIZE as usize);
+        bytes.extend_from_slice(&FIELD_SIZE.to_le_bytes());
+        bytes.extend_from_slice(&FIELD_SIZE.to_le_bytes());
+        bytes.extend_from_slice(&strength.to_le_bytes());
+        bytes.extend_from_slice(&0.0f32.to_le_bytes());
+        bytes.extend_from_slice(bytemuck::cast_slice(&values));
+        Ok(bytes)
+    }
+}
+
+fn shape_value(shape: &EnvironmentShape, x: f32, y: f32) -> f32 {
+    match shape {
+        EnvironmentShape::Radial { center, radius } => {
+            let distance = ((x - center[0]).powi(2) + (y - center[1]).powi(2)).sqrt();
+            (1.0 - distance / radius).max(0.0)
+        }
+        EnvironmentShape::Linear { angle } => {
+            let along = x * angle.cos() + y * angle.sin();
+            (along * 0.5 + 0.5).clamp(0.0, 1.0)
+        }
+        EnvironmentShape::Noise { scale, seed } => {
+            // Four octaves, normalized back to 0-1
+            let (mut total, mut amplitude, mut frequency) = (0.0, 1.0, *scale * 0.5);
+            for octave in 0..4 {
+                total += value_noise(x * frequency, y * frequency, seed.wrapping_add(octave))
+                    * amplitude;
+                amplitude *= 0.5;
+                frequency *= 2.0;
+            }
+            total / 1.875
+        }
+        EnvironmentShape::None | EnvironmentShape::Image { .. } => 0.0,
+    }
+}
+
+fn lattice_value(x: i32, y: i32, seed: u32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ seed.wrapping_mul(0xcb1a_b31f);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0x5bd1_e995);
+    h ^= h >> 15;
+    h as f32 / u32::MAX as f32
+}
+
+fn value_noise(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+    let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    let bottom = lerp(
+        lattice_value(ix, iy, seed),
+        lattice_value(ix + 1, iy, seed),
+        tx,
+    );
+    let top = lerp(
+        lattice_value(ix, iy + 1, seed),
+        lattice_value(ix + 1, iy + 1, seed),
+        tx,
+    );
+    lerp(bottom, top, ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(values: &[f32], x: u32, y: u32) -> f32 {
+        values[(y * FIELD_SIZE + x) as usize]
+    }
+
+    #[test]
+    fn shapes_rise_where_expected() {
+        let last = FIELD_SIZE - 1;
+        let radial = EnvironmentField {
+            shape: EnvironmentShape::Radial {
+                center: [0.0, 0.0],
+                radius: 1.0,
+            },
+            ..Default::default()
+        }
+        .rasterize()
+        .unwrap();
+        assert!(at(&radial, last / 2, last / 2) > 0.99);
+        assert_eq!(at(&radial, 0, 0), 0.0);
+
+        // Up is +y, the end of the grid
+        let linear = EnvironmentField {
+            shape: EnvironmentShape::Linear {
+                angle: std::f32::consts::FRAC_PI_2,
+            },
+            invert: true,
+            ..Default::default()
+        }
+        .rasterize()
+        .unwrap();
+        assert_eq!(at(&linear, 10, 0), 1.0);
+        assert_eq!(at(&linear, 10, last), 0.0);
+
+        let noise = EnvironmentField {
+            shape: EnvironmentShape::Noise {
+                scale: 4.0,
+                seed: 7,
+            },
+            ..Default::default()
+        }
+        .rasterize()
+        .unwrap();
+        assert!(noise.iter().all(|v| (0.0..=1.0).contains(v)));
+    }
+
+    #[test]
+    fn empty_fields_have_no_strength() {
+        let bytes = EnvironmentField::default().to_gpu_bytes().unwrap();
+        assert_eq!(bytes.len() as u64, BUFFER_SIZE);
+        assert_eq!(bytes[8..12], 0.0f32.to_le_bytes());
+
+        assert!(
+            EnvironmentField {
+                shape: EnvironmentShape::Radial {
+                    center: [0.0, 0.0],
+                    radius: 0.0,
+                },
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
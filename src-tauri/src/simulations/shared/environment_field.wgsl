@@ -0,0 +1,47 @@
+// Environment bias field shared by the particle simulations.
+// Prepended to a compute shader, which binds its own
+// `var<storage, read> environment_field: EnvironmentField`; the layout
+// matches `EnvironmentField::to_gpu_bytes`.
+
+struct EnvironmentField {
+    width: u32,
+    height: u32,
+    strength: f32,
+    _pad0: f32,
+    values: array<f32>,
+}
+
+fn environment_value_at_cell(x: i32, y: i32) -> f32 {
+    let cx = clamp(x, 0, i32(environment_field.width) - 1);
+    let cy = clamp(y, 0, i32(environment_field.height) - 1);
+    return environment_field.values[u32(cy) * environment_field.width + u32(cx)];
+}
+
+// Bilinear value at a world position, -1 to 1 on both axes
+fn environment_value(position: vec2<f32>) -> f32 {
+    let size = vec2<f32>(f32(environment_field.width), f32(environment_field.height));
+    let cell = clamp(position * 0.5 + 0.5, vec2<f32>(0.0), vec2<f32>(1.0)) * (size - 1.0);
+    let base = floor(cell);
+    let t = cell - base;
+    let x = i32(base.x);
+    let y = i32(base.y);
+    let bottom = mix(environment_value_at_cell(x, y), environment_value_at_cell(x + 1, y), t.x);
+    let top = mix(environment_value_at_cell(x, y + 1), environment_value_at_cell(x + 1, y + 1), t.x);
+    return mix(bottom, top, t.y);
+}
+
+// Drift velocity at `position`: the slope of the field times its strength
+fn environment_bias(position: vec2<f32>) -> vec2<f32> {
+    if (environment_field.strength == 0.0) {
+        return vec2<f32>(0.0);
+    }
+    // One grid cell in world units
+    let step = 2.0 / f32(max(environment_field.width, 2u) - 1u);
+    let dx = vec2<f32>(step, 0.0);
+    let dy = vec2<f32>(0.0, step);
+    let slope = vec2<f32>(
+        environment_value(position + dx) - environment_value(position - dx),
+        environment_value(position + dy) - environment_value(position - dy)
+    ) / (2.0 * step);
+    return slope * environment_field.strength;
+}
@@ -0,0 +1,59 @@
+//! Scalar fields stored as 16-bit grayscale images, so a field such as the
+//! slime mold trail map can be saved, edited in other tools and loaded back
+//! later or into a different simulation.
+//!
+//! Row 0 of the image is row 0 of the field, the same order the mask and
+//! position image loaders use.
+
+use image::{DynamicImage, ImageBuffer, Luma};
+
+pub type FieldImage = ImageBuffer<Luma<u16>, Vec<u16>>;
+
+/// `values` are row major and clamped to 0-1
+pub fn field_to_image(width: u32, height: u32, values: &[f32]) -> FieldImage {
+    let pixels = values
+        .iter()
+        .map(|v| (v.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16)
+        .collect();
+    ImageBuffer::from_raw(width, height, pixels).expect("field size matches its dimensions")
+}
+
+/// Luminance of `image` stretched to `width` x `height`, from 0 to 1
+pub fn image_to_field(image: &DynamicImage, width: u32, height: u32) -> Vec<f32> {
+    let gray = image.to_luma16();
+    let gray = if gray.dimensions() == (width, height) {
+        gray
+    } else {
+        image::imageops::resize(&gray, width, height, image::imageops::FilterType::Triangle)
+    };
+    gray.pixels()
+        .map(|p| p[0] as f32 / u16::MAX as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_round_trip_through_images() {
+        let values = [0.0, 0.25, 1.0, 1.5, -1.0, 0.5];
+        let image = field_to_image(3, 2, &values);
+        assert_eq!(image.get_pixel(2, 0)[0], u16::MAX);
+        assert_eq!(image.get_pixel(0, 1)[0], u16::MAX);
+
+        let field = image_to_field(&DynamicImage::ImageLuma16(image), 3, 2);
+        let expected = [0.0, 0.25, 1.0, 1.0, 0.0, 0.5];
+        for (value, expected) in field.iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-4, "{} != {}", value, expected);
+        }
+    }
+
+    #[test]
+    fn images_are_stretched_to_the_field() {
+        let image = DynamicImage::ImageLuma8(ImageBuffer::from_pixel(4, 4, Luma([255u8])));
+        let field = image_to_field(&image, 8, 2);
+        assert_eq!(field.len(), 16);
+        assert!(field.iter().all(|v| (v - 1.0).abs() < 1e-4));
+    }
+}
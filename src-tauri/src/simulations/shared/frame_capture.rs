@@ -0,0 +1,240 @@
+//! Offscreen render target with CPU readback.
+//!
+//! Simulations render straight into whatever view they are handed, so to get
+//! pixels back out we give them an offscreen texture in the surface format and
+//! copy it into a mappable buffer afterwards.
+
+use super::color_space::{linear_to_srgb_u8, srgb_to_linear};
+use crate::error::{SimulationError, SimulationResult};
+use std::sync::Arc;
+use wgpu::{Device, Queue};
+
+#[derive(Debug)]
+pub struct FrameCapture {
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    readback_buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    format: wgpu::TextureFormat,
+}
+
+impl FrameCapture {
+    pub fn new(
+        device: &Arc<Device>,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+        label: &str,
+    ) -> SimulationResult<Self> {
+        if !Self::is_supported_format(format) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Frame capture does not support texture format {:?}",
+                format
+            )));
+        }
+
+        let width = width.max(1);
+        let height = height.max(1);
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("{} Capture Texture", label)),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Rows in a texture->buffer copy must be aligned to 256 bytes
+        let unpadded_bytes_per_row = width * Self::bytes_per_pixel(format);
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} Capture Readback Buffer", label)),
+            size: (padded_bytes_per_row * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
+            texture,
+            view,
+            readback_buffer,
+            width,
+            height,
+            padded_bytes_per_row,
+            format,
+        })
+    }
+
+    pub fn is_supported_format(format: wgpu::TextureFormat) -> bool {
+        matches!(
+            format,
+            wgpu::TextureFormat::Rgba8Unorm
+                | wgpu::TextureFormat::Rgba8UnormSrgb
+                | wgpu::TextureFormat::Bgra8Unorm
+                | wgpu::TextureFormat::Bgra8UnormSrgb
+                | wgpu::TextureFormat::Rgba16Float
+        )
+    }
+
+    fn bytes_per_pixel(format: wgpu::TextureFormat) -> u32 {
+        match format {
+            wgpu::TextureFormat::Rgba16Float => 8,
+            _ => 4,
+        }
+    }
+
+    /// A surface configuration matching the capture texture, for handing to
+    /// simulation constructors and `resize`.
+    pub fn surface_config(&self) -> wgpu::SurfaceConfiguration {
+        wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: self.format,
+            width: self.width,
+            height: self.height,
+            present_mode: wgpu::PresentMode::Fifo,
+            desired_maximum_frame_latency: 2,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+        }
+    }
+
+    /// Copy the capture texture back to the CPU as tightly packed RGBA8.
+    ///
+    /// Half-float captures hold linear color, so they are encoded to sRGB to
+    /// match what an sRGB surface would have shown.
+    pub fn read_rgba(
+        &self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<image::RgbaImage> {
+        let raw = self.read_raw(device, queue)?;
+
+        let pixels = match self.format {
+            wgpu::TextureFormat::Rgba16Float => Self::decode_half_floats(&raw)
+                .enumerate()
+                .map(|(channel, value)| {
+                    // Alpha is linear coverage, not color
+                    if channel % 4 == 3 {
+                        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+                    } else {
+                        linear_to_srgb_u8(value)
+                    }
+                })
+                .collect(),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => {
+                let mut pixels = raw;
+                for pixel in pixels.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+                pixels
+            }
+            _ => raw,
+        };
+
+        image::RgbaImage::from_raw(self.width, self.height, pixels).ok_or_else(|| {
+            SimulationError::InvalidParameter("Captured frame has unexpected size".to_string())
+        })
+    }
+
+    /// Copy the capture texture back to the CPU as linear floating point RGBA.
+    ///
+    /// Half-float captures keep values outside 0-1, 8-bit captures are decoded
+    /// from sRGB.
+    pub fn read_linear(
+        &self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<image::Rgba32FImage> {
+        let pixels: Vec<f32> = if self.format == wgpu::TextureFormat::Rgba16Float {
+            let raw = self.read_raw(device, queue)?;
+            Self::decode_half_floats(&raw).collect()
+        } else {
+            self.read_rgba(device, queue)?
+                .into_raw()
+                .into_iter()
+                .enumerate()
+                .map(|(channel, value)| {
+                    let value = value as f32 / 255.0;
+                    if channel % 4 == 3 {
+                        value
+                    } else {
+                        srgb_to_linear(value)
+                    }
+                })
+                .collect()
+        };
+
+        image::Rgba32FImage::from_raw(self.width, self.height, pixels).ok_or_else(|| {
+            SimulationError::InvalidParameter("Captured frame has unexpected size".to_string())
+        })
+    }
+
+    fn decode_half_floats(raw: &[u8]) -> impl Iterator<Item = f32> + '_ {
+        raw.chunks_exact(2)
+            .map(|bytes| half::f16::from_le_bytes([bytes[0], bytes[1]]).to_f32())
+    }
+
+    /// Copy the texture into the readback buffer and return its rows without padding
+    fn read_raw(&self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<Vec<u8>> {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Frame Capture Copy Encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &self.readback_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(self.padded_bytes_per_row),
+                    rows_per_image: Some(self.height),
+                },
+            },
+            wgpu::Extent3d {
+                width: self.width,
+                height: self.height,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = self.readback_buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+        device
+            .poll(wgpu::wgt::PollType::Wait)
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+        receiver
+            .recv()
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+
+        let row_bytes = (self.width * Self::bytes_per_pixel(self.format)) as usize;
+        let mut pixels = Vec::with_capacity(row_bytes * self.height as usize);
+        {
+            let data = buffer_slice.get_mapped_range();
+            for row in data.chunks(self.padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..row_bytes]);
+            }
+        }
+        self.readback_buffer.unmap();
+
+        Ok(pixels)
+    }
+}
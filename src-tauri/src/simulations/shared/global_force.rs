@@ -0,0 +1,263 @@
+//! Forces that act on every particle no matter where the cursor is: a
+//! constant wind, gusts that swell and fade on top of it, and gravity toward
+//! a point. Each particle simulation keeps its own [`GlobalForce`], whose
+//! parameters can be swept by LFOs. It is resolved on the CPU every frame
+//! into a [`GlobalForceUniform`] for `global_force.wgsl`.
+//!
+//! Simulations with inertia take the result as an acceleration, the others
+//! (Flow, Primordial Particles) as a drift velocity.
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SimulationError, SimulationResult};
+
+pub const MAX_LFOS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForceParameter {
+    WindX,
+    WindY,
+    GustStrength,
+    GravityX,
+    GravityY,
+    GravityStrength,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LfoWaveform {
+    #[default]
+    Sine,
+    Triangle,
+    Square,
+    Saw,
+}
+
+/// Low frequency oscillator added to one [`ForceParameter`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Lfo {
+    pub target: ForceParameter,
+    #[serde(default)]
+    pub waveform: LfoWaveform,
+    /// Hz
+    pub frequency: f32,
+    /// Peak offset added to the target
+    pub amplitude: f32,
+    /// Fraction of a cycle, 0-1
+    #[serde(default)]
+    pub phase: f32,
+}
+
+impl Lfo {
+    /// Offset at `time` seconds, between -amplitude and amplitude
+    pub fn value(&self, time: f32) -> f32 {
+        let cycle = (time * self.frequency + self.phase).rem_euclid(1.0);
+        let wave = match self.waveform {
+            LfoWaveform::Sine => (cycle * std::f32::consts::TAU).sin(),
+            LfoWaveform::Triangle => 1.0 - 4.0 * (cycle - 0.5).abs(),
+            LfoWaveform::Square => {
+                if cycle < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            LfoWaveform::Saw => 2.0 * cycle - 1.0,
+        };
+        wave * self.amplitude
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalForce {
+    /// World units per second squared (or per second as a drift)
+    pub wind: [f32; 2],
+    /// How far gusts swell the wind past its base strength, as a fraction
+    pub gust_strength: f32,
+    /// Gusts per second
+    pub gust_frequency: f32,
+    /// World space point gravity pulls toward
+    pub gravity_center: [f32; 2],
+    /// Negative pushes away from the center
+    pub gravity_strength: f32,
+    pub lfos: Vec<Lfo>,
+    #[serde(skip)]
+    time: f32,
+}
+
+impl Default for GlobalForce {
+    fn default() -> Self {
+        Self {
+            wind: [0.0, 0.0],
+            gust_strength: 0.0,
+            gust_frequency: 0.5,
+            gravity_center: [0.0, 0.0],
+            gravity_strength: 0.0,
+            lfos: Vec::new(),
+            time: 0.0,
+        }
+    }
+}
+
+impl GlobalForce {
+    pub fn validate(&self) -> SimulationResult<()> {
+        let values = [
+            self.wind[0],
+            self.wind[1],
+            self.gust_strength,
+            self.gust_frequency,
+            self.gravity_center[0],
+            self.gravity_center[1],
+            self.gravity_strength,
+        ];
+        let lfo_values = self
+            .lfos
+            .iter()
+            .flat_map(|lfo| [lfo.frequency, lfo.amplitude, lfo.phase]);
+        if !values.into_iter().chain(lfo_values).all(f32::is_finite) {
+            return Err(SimulationError::InvalidParameter(
+                "Global force values must be finite".to_string(),
+            ));
+        }
+        if self.gust_frequency < 0.0 || self.lfos.iter().any(|lfo| lfo.frequency < 0.0) {
+            return Err(SimulationError::InvalidParameter(
+                "Gust and LFO frequencies cannot be negative".to_string(),
+            ));
+        }
+        if self.lfos.len() > MAX_LFOS {
+            return Err(SimulationError::InvalidParameter(format!(
+                "At most {} LFOs are supported",
+                MAX_LFOS
+            )));
+        }
+        Ok(())
+    }
+
+    /// Replace the configuration, keeping the running clock so gusts and
+    /// LFOs don't jump back to their start
+    pub fn set(&mut self, force: GlobalForce) {
+        let time = self.time;
+        *self = force;
+        self.time = time;
+    }
+
+    pub fn advance(&mut self, delta_time: f32) {
+        // Wrapped like the cursor force clock; a 1000 s period is a whole
+        // number of cycles for any frequency with three decimals
+        self.time = (self.time + delta_time) % 1000.0;
+    }
+
+    /// Base value of `parameter` plus its LFOs
+    fn modulated(&self, parameter: ForceParameter) -> f32 {
+        let base = match parameter {
+            ForceParameter::WindX => self.wind[0],
+            ForceParameter::WindY => self.wind[1],
+            ForceParameter::GustStrength => self.gust_strength,
+            ForceParameter::GravityX => self.gravity_center[0],
+            ForceParameter::GravityY => self.gravity_center[1],
+            ForceParameter::GravityStrength => self.gravity_strength,
+        };
+        base + self
+            .lfos
+            .iter()
+            .filter(|lfo| lfo.target == parameter)
+            .map(|lfo| lfo.value(self.time))
+            .sum::<f32>()
+    }
+
+    pub fn uniform(&self) -> GlobalForceUniform {
+        // Two detuned sines so gusts don't repeat too obviously; never below
+        // calm, so they only ever add to the wind
+        let phase = self.time * self.gust_frequency * std::f32::consts::TAU;
+        let gust = (0.7 * phase.sin() + 0.3 * (phase * 2.3).sin()).max(0.0);
+        let wind_scale = 1.0 + self.modulated(ForceParameter::GustStrength) * gust;
+        GlobalForceUniform {
+            wind: [
+                self.modulated(ForceParameter::WindX) * wind_scale,
+                self.modulated(ForceParameter::WindY) * wind_scale,
+            ],
+            gravity_center: [
+                self.modulated(ForceParameter::GravityX),
+                self.modulated(ForceParameter::GravityY),
+            ],
+            gravity_strength: self.modulated(ForceParameter::GravityStrength),
+            _pad0: 0.0,
+            _pad1: 0.0,
+            _pad2: 0.0,
+        }
+    }
+}
+
+/// `GlobalForce` in `global_force.wgsl`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable, Default, PartialEq)]
+pub struct GlobalForceUniform {
+    pub wind: [f32; 2],
+    pub gravity_center: [f32; 2],
+    pub gravity_strength: f32,
+    pub _pad0: f32,
+    pub _pad1: f32,
+    pub _pad2: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lfo_waveforms_span_the_amplitude() {
+        for waveform in [
+            LfoWaveform::Sine,
+            LfoWaveform::Triangle,
+            LfoWaveform::Square,
+            LfoWaveform::Saw,
+        ] {
+            let lfo = Lfo {
+                target: ForceParameter::WindX,
+                waveform,
+                frequency: 1.0,
+                amplitude: 2.0,
+                phase: 0.0,
+            };
+            let values: Vec<f32> = (0..100).map(|i| lfo.value(i as f32 / 100.0)).collect();
+            let max = values.iter().cloned().fold(f32::MIN, f32::max);
+            let min = values.iter().cloned().fold(f32::MAX, f32::min);
+            assert!((1.9..=2.0).contains(&max), "{:?} max {}", waveform, max);
+            assert!((-2.0..=-1.9).contains(&min), "{:?} min {}", waveform, min);
+        }
+    }
+
+    #[test]
+    fn lfos_modulate_their_target_only() {
+        let mut force = GlobalForce {
+            wind: [0.5, 0.0],
+            gravity_strength: 1.0,
+            lfos: vec![Lfo {
+                target: ForceParameter::GravityStrength,
+                waveform: LfoWaveform::Square,
+                frequency: 1.0,
+                amplitude: 0.25,
+                phase: 0.0,
+            }],
+            ..Default::default()
+        };
+        force.advance(0.1);
+        let uniform = force.uniform();
+        assert_eq!(uniform.wind, [0.5, 0.0]);
+        assert_eq!(uniform.gravity_strength, 1.25);
+
+        force.set(GlobalForce::default());
+        assert_eq!(force.uniform(), GlobalForceUniform::default());
+        assert!(
+            GlobalForce {
+                gust_frequency: -1.0,
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
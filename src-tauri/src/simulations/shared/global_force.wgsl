@@ -0,0 +1,26 @@
+// Wind and point gravity shared by the particle simulations.
+// Prepended to a compute shader, which binds its own `GlobalForce` uniform;
+// the layout matches `GlobalForceUniform`.
+
+struct GlobalForce {
+    wind: vec2<f32>,
+    gravity_center: vec2<f32>,
+    gravity_strength: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+// Force at `position`. Gravity keeps the same strength at any distance and
+// eases off inside a small core so particles settle at the center instead of
+// jittering across it.
+fn global_force_at(force: GlobalForce, position: vec2<f32>) -> vec2<f32> {
+    var result = force.wind;
+    let to_center = force.gravity_center - position;
+    let distance = length(to_center);
+    if (force.gravity_strength != 0.0 && distance > 1e-5) {
+        let core = min(distance / 0.05, 1.0);
+        result += to_center / distance * force.gravity_strength * core;
+    }
+    return result;
+}
@@ -0,0 +1,280 @@
+//! Process-wide accounting of large GPU allocations.
+//!
+//! wgpu doesn't tell us how much video memory there is, or warn before an
+//! allocation fails. Instead the big, size-dependent resources (trail maps,
+//! particle buffers, rewind snapshots, offscreen targets) hold a
+//! [`GpuReservation`] for their size, and new allocations are checked against
+//! both the device limits and a memory budget before they are made. When a
+//! request doesn't fit it is shrunk, and a [`GpuMemoryWarning`] is queued for
+//! the render loop to forward to the frontend.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use wgpu::Device;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Requests scaled down to fit are shrunk a little further, like the trail
+/// map limit check always did, to leave room for rounding
+const FIT_MARGIN: f64 = 0.95;
+
+static RESERVED_BYTES: AtomicU64 = AtomicU64::new(0);
+// Zero until `configure` runs, which means unlimited
+static BUDGET_BYTES: AtomicU64 = AtomicU64::new(0);
+static AUTOMATIC_BUDGET_BYTES: AtomicU64 = AtomicU64::new(0);
+static WARNINGS: Mutex<Vec<GpuMemoryWarning>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum GpuMemoryLimit {
+    /// A single buffer would be larger than the device allows
+    DeviceLimit,
+    /// All tracked allocations together would exceed the memory budget
+    Budget,
+}
+
+/// Sent to the frontend as `gpu-memory-warning` when a resource was shrunk
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuMemoryWarning {
+    pub resource: String,
+    pub limit: GpuMemoryLimit,
+    pub requested: u64,
+    pub granted: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct GpuMemoryUsage {
+    pub reserved_bytes: u64,
+    pub budget_bytes: u64,
+}
+
+/// Bytes counted against the budget for as long as this value is alive
+#[derive(Debug, Default)]
+pub struct GpuReservation {
+    bytes: u64,
+}
+
+impl GpuReservation {
+    pub fn new(bytes: u64) -> Self {
+        RESERVED_BYTES.fetch_add(bytes, Ordering::Relaxed);
+        Self { bytes }
+    }
+
+    /// Reserve the size of a single-mip texture
+    pub fn for_texture(texture: &wgpu::Texture) -> Self {
+        let size = texture.size();
+        let bytes_per_texel = texture.format().block_copy_size(None).unwrap_or(4) as u64;
+        Self::new(
+            size.width as u64
+                * size.height as u64
+                * size.depth_or_array_layers as u64
+                * bytes_per_texel,
+        )
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+impl Drop for GpuReservation {
+    fn drop(&mut self) {
+        RESERVED_BYTES.fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// Pick the budget for this adapter. `budget_mb` of zero means automatic.
+pub fn configure(adapter_info: &wgpu::AdapterInfo, budget_mb: u32) {
+    AUTOMATIC_BUDGET_BYTES.store(
+        automatic_budget(adapter_info.device_type),
+        Ordering::Relaxed,
+    );
+    set_budget_mb(budget_mb);
+}
+
+/// Change the budget, e.g. from app settings. Zero means automatic.
+pub fn set_budget_mb(budget_mb: u32) {
+    let bytes = match budget_mb {
+        0 => AUTOMATIC_BUDGET_BYTES.load(Ordering::Relaxed),
+        mb => mb as u64 * MIB,
+    };
+    tracing::info!("GPU memory budget set to {} MiB", bytes / MIB);
+    BUDGET_BYTES.store(bytes, Ordering::Relaxed);
+}
+
+pub fn usage() -> GpuMemoryUsage {
+    GpuMemoryUsage {
+        reserved_bytes: RESERVED_BYTES.load(Ordering::Relaxed),
+        budget_bytes: BUDGET_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Whether `bytes` more would still be within the budget
+pub fn fits(bytes: u64) -> bool {
+    bytes <= available_bytes(0)
+}
+
+/// Largest element count up to `requested` that fits. Each element takes
+/// `bytes_per_item` in each of `copies` same-sized buffers, and `replacing`
+/// is the reservation the new buffers will take over from.
+pub fn fit_count(
+    device: &Device,
+    resource: &str,
+    requested: usize,
+    bytes_per_item: u64,
+    copies: u64,
+    replacing: &GpuReservation,
+) -> usize {
+    let (max_items, limit) = max_items(
+        buffer_limit(device),
+        available_bytes(replacing.bytes()),
+        bytes_per_item,
+        copies,
+    );
+    if requested as u64 <= max_items {
+        return requested;
+    }
+
+    let granted = ((max_items as f64 * FIT_MARGIN) as usize).max(1);
+    warn(resource, limit, requested as u64, granted as u64, "items");
+    granted
+}
+
+/// Largest resolution with the aspect ratio of `width` x `height` that fits,
+/// for `copies` buffers of `bytes_per_pixel` per pixel
+pub fn fit_resolution(
+    device: &Device,
+    resource: &str,
+    (width, height): (u32, u32),
+    bytes_per_pixel: u64,
+    copies: u64,
+    replacing: &GpuReservation,
+) -> (u32, u32) {
+    let (max_pixels, limit) = max_items(
+        buffer_limit(device),
+        available_bytes(replacing.bytes()),
+        bytes_per_pixel,
+        copies,
+    );
+    let pixels = width as u64 * height as u64;
+    if pixels <= max_pixels {
+        return (width, height);
+    }
+
+    let scale = (max_pixels as f64 / pixels as f64).sqrt() * FIT_MARGIN;
+    let granted = (
+        ((width as f64 * scale) as u32).max(1),
+        ((height as f64 * scale) as u32).max(1),
+    );
+    warn(
+        resource,
+        limit,
+        pixels,
+        granted.0 as u64 * granted.1 as u64,
+        "pixels",
+    );
+    granted
+}
+
+/// Drain the warnings raised since the last call
+pub fn take_warnings() -> Vec<GpuMemoryWarning> {
+    WARNINGS
+        .lock()
+        .map(|mut warnings| std::mem::take(&mut *warnings))
+        .unwrap_or_default()
+}
+
+/// Queue a warning about `resource` getting less than it asked for
+pub fn warn(resource: &str, limit: GpuMemoryLimit, requested: u64, granted: u64, unit: &str) {
+    let reason = match limit {
+        GpuMemoryLimit::DeviceLimit => "the GPU's buffer size limit",
+        GpuMemoryLimit::Budget => "the GPU memory budget",
+    };
+    let message = format!(
+        "{} reduced from {} to {} {} to stay within {}",
+        resource, requested, granted, unit, reason
+    );
+    tracing::warn!("{}", message);
+    if let Ok(mut warnings) = WARNINGS.lock() {
+        warnings.push(GpuMemoryWarning {
+            resource: resource.to_string(),
+            limit,
+            requested,
+            granted,
+            message,
+        });
+    }
+}
+
+fn buffer_limit(device: &Device) -> u64 {
+    let limits = device.limits();
+    limits
+        .max_buffer_size
+        .min(limits.max_storage_buffer_binding_size as u64)
+}
+
+/// Budget left over, counting `replacing` as already freed
+fn available_bytes(replacing: u64) -> u64 {
+    let budget = BUDGET_BYTES.load(Ordering::Relaxed);
+    if budget == 0 {
+        return u64::MAX;
+    }
+    let reserved = RESERVED_BYTES
+        .load(Ordering::Relaxed)
+        .saturating_sub(replacing);
+    budget.saturating_sub(reserved)
+}
+
+/// How many items fit, and which limit is the tighter one
+fn max_items(
+    buffer_limit: u64,
+    available: u64,
+    bytes_per_item: u64,
+    copies: u64,
+) -> (u64, GpuMemoryLimit) {
+    let bytes_per_item = bytes_per_item.max(1);
+    let by_device = buffer_limit / bytes_per_item;
+    let by_budget = available / bytes_per_item.saturating_mul(copies.max(1));
+    if by_budget < by_device {
+        (by_budget, GpuMemoryLimit::Budget)
+    } else {
+        (by_device, GpuMemoryLimit::DeviceLimit)
+    }
+}
+
+/// Conservative guesses, since only part of what's allocated is tracked
+fn automatic_budget(device_type: wgpu::DeviceType) -> u64 {
+    match device_type {
+        wgpu::DeviceType::DiscreteGpu => 3072 * MIB,
+        wgpu::DeviceType::IntegratedGpu | wgpu::DeviceType::VirtualGpu => 1536 * MIB,
+        wgpu::DeviceType::Cpu | wgpu::DeviceType::Other => 768 * MIB,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_limit_applies_per_buffer() {
+        let (items, limit) = max_items(1000, u64::MAX, 4, 3);
+        assert_eq!(items, 250);
+        assert_eq!(limit, GpuMemoryLimit::DeviceLimit);
+    }
+
+    #[test]
+    fn budget_applies_to_all_copies() {
+        let (items, limit) = max_items(1000, 1200, 4, 3);
+        assert_eq!(items, 100);
+        assert_eq!(limit, GpuMemoryLimit::Budget);
+    }
+
+    #[test]
+    fn integrated_gpus_get_a_smaller_budget() {
+        assert!(
+            automatic_budget(wgpu::DeviceType::IntegratedGpu)
+                < automatic_budget(wgpu::DeviceType::DiscreteGpu)
+        );
+    }
+}
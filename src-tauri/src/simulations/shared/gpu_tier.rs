@@ -0,0 +1,84 @@
+//! Coarse classification of the GPU, so simulations can pick lighter
+//! variants on hardware that can't comfortably run the full ones.
+
+use serde::Serialize;
+
+/// Storage buffers smaller than this can't hold the default particle and
+/// agent counts, which is typical of older integrated GPUs
+pub(crate) const REDUCED_STORAGE_BUFFER_SIZE: u32 = 256 * 1024 * 1024;
+
+/// Largest texture side below which the device is treated as downlevel
+const REDUCED_TEXTURE_DIMENSION: u32 = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum GpuTier {
+    Full,
+    /// Smaller default buffers and no multisampling
+    Reduced,
+}
+
+impl GpuTier {
+    pub fn detect(adapter_info: &wgpu::AdapterInfo, limits: &wgpu::Limits) -> Self {
+        let software = adapter_info.device_type == wgpu::DeviceType::Cpu;
+        let downlevel_backend = adapter_info.backend == wgpu::Backend::Gl;
+        let small_buffers = limits.max_storage_buffer_binding_size < REDUCED_STORAGE_BUFFER_SIZE;
+        let small_textures = limits.max_texture_dimension_2d < REDUCED_TEXTURE_DIMENSION;
+
+        if software || downlevel_backend || small_buffers || small_textures {
+            GpuTier::Reduced
+        } else {
+            GpuTier::Full
+        }
+    }
+
+    pub fn msaa_sample_count(self) -> u32 {
+        match self {
+            GpuTier::Full => 4,
+            GpuTier::Reduced => 1,
+        }
+    }
+
+    /// Default particle or agent count to start a simulation with
+    pub fn default_count(self, full: usize) -> usize {
+        match self {
+            GpuTier::Full => full,
+            GpuTier::Reduced => (full / 10).max(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(device_type: wgpu::DeviceType, backend: wgpu::Backend) -> wgpu::AdapterInfo {
+        wgpu::AdapterInfo {
+            name: "Test Adapter".to_string(),
+            vendor: 0,
+            device: 0,
+            device_type,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend,
+        }
+    }
+
+    #[test]
+    fn discrete_gpu_with_default_limits_is_full() {
+        let info = adapter(wgpu::DeviceType::DiscreteGpu, wgpu::Backend::Vulkan);
+        let limits = wgpu::Limits {
+            max_storage_buffer_binding_size: 2_147_483_647,
+            ..wgpu::Limits::default()
+        };
+        assert_eq!(GpuTier::detect(&info, &limits), GpuTier::Full);
+    }
+
+    #[test]
+    fn downlevel_limits_are_reduced() {
+        let info = adapter(wgpu::DeviceType::IntegratedGpu, wgpu::Backend::Vulkan);
+        let tier = GpuTier::detect(&info, &wgpu::Limits::downlevel_defaults());
+        assert_eq!(tier, GpuTier::Reduced);
+        assert_eq!(tier.msaa_sample_count(), 1);
+        assert_eq!(tier.default_count(10_000_000), 1_000_000);
+    }
+}
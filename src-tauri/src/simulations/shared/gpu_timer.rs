@@ -0,0 +1,162 @@
+//! Timestamps the GPU writes as it reaches them in its queue.
+//!
+//! Each timestamp is an empty compute pass, so one can go between any two
+//! passes of an encoder, or be submitted on its own between submissions, and
+//! only `TIMESTAMP_QUERY` is needed. A query set holds a limited number of
+//! them, so written timestamps are read back in batches. Reading waits for
+//! the GPU, so do it between the pieces of work being timed rather than
+//! inside one.
+
+use wgpu::{Device, Queue};
+
+use crate::error::{SimulationError, SimulationResult};
+
+#[derive(Debug)]
+pub struct GpuTimer {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick
+    period: f64,
+    capacity: u32,
+    written: u32,
+    /// Read back so far, in milliseconds
+    timestamps: Vec<f64>,
+}
+
+impl GpuTimer {
+    /// A timer holding up to `capacity` timestamps between read backs, or
+    /// None when the device can't write timestamps
+    pub fn new(device: &Device, queue: &Queue, capacity: u32, label: &str) -> Option<Self> {
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+        let capacity = capacity.clamp(1, wgpu::QUERY_SET_MAX_QUERIES);
+        let size = capacity as u64 * wgpu::QUERY_SIZE as u64;
+        Some(Self {
+            query_set: device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some(label),
+                ty: wgpu::QueryType::Timestamp,
+                count: capacity,
+            }),
+            resolve_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(&format!("{} Resolve", label)),
+                size,
+                usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+            readback_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(&format!("{} Readback", label)),
+                size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            period: queue.get_timestamp_period() as f64,
+            capacity,
+            written: 0,
+            timestamps: Vec::new(),
+        })
+    }
+
+    /// Timestamps that can still be written before the next read back
+    pub fn remaining(&self) -> u32 {
+        self.capacity - self.written
+    }
+
+    /// Submit a timestamp written when the GPU reaches it. A full timer is
+    /// read back first, waiting for the GPU.
+    pub fn mark(&mut self, device: &Device, queue: &Queue) -> SimulationResult<()> {
+        if self.remaining() == 0 {
+            self.read_back(device, queue)?;
+        }
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("GPU Timer Timestamp"),
+        });
+        self.encode_mark(&mut encoder);
+        queue.submit(std::iter::once(encoder.finish()));
+        Ok(())
+    }
+
+    /// Record a timestamp in `encoder`, written when the GPU reaches it.
+    /// Reading back can't happen mid-encoder, so this writes nothing when
+    /// the timer is full; check `remaining` first.
+    pub fn encode_mark(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if self.remaining() == 0 {
+            return;
+        }
+        encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("GPU Timer Timestamp"),
+            timestamp_writes: Some(wgpu::ComputePassTimestampWrites {
+                query_set: &self.query_set,
+                beginning_of_pass_write_index: Some(self.written),
+                end_of_pass_write_index: None,
+            }),
+        });
+        self.written += 1;
+    }
+
+    /// Read back the timestamps written since the last read back, freeing
+    /// the query set for more
+    pub fn read_back(&mut self, device: &Device, queue: &Queue) -> SimulationResult<()> {
+        if self.written == 0 {
+            return Ok(());
+        }
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("GPU Timer Resolve"),
+        });
+        encoder.resolve_query_set(&self.query_set, 0..self.written, &self.resolve_buffer, 0);
+        let size = self.written as u64 * wgpu::QUERY_SIZE as u64;
+        encoder.copy_buffer_to_buffer(&self.resolve_buffer, 0, &self.readback_buffer, 0, size);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = self.readback_buffer.slice(..size);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+        device
+            .poll(wgpu::wgt::PollType::Wait)
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+        receiver
+            .recv()
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+
+        {
+            let data = buffer_slice.get_mapped_range();
+            let ticks: &[u64] = bytemuck::cast_slice(&data);
+            self.timestamps.extend(
+                ticks
+                    .iter()
+                    .map(|tick| *tick as f64 * self.period / 1_000_000.0),
+            );
+        }
+        self.readback_buffer.unmap();
+        self.written = 0;
+        Ok(())
+    }
+
+    /// Every timestamp written, in milliseconds on the GPU's clock
+    pub fn finish(mut self, device: &Device, queue: &Queue) -> SimulationResult<Vec<f64>> {
+        self.read_back(device, queue)?;
+        Ok(self.timestamps)
+    }
+}
+
+/// Milliseconds between each pair of timestamps
+pub fn pair_durations(timestamps: &[f64]) -> Vec<f64> {
+    timestamps
+        .chunks_exact(2)
+        .map(|pair| (pair[1] - pair[0]).max(0.0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_become_durations() {
+        assert_eq!(pair_durations(&[1.0, 3.5, 4.0, 4.0, 9.0]), [2.5, 0.0]);
+        // A clock that stepped back doesn't give negative times
+        assert_eq!(pair_durations(&[5.0, 4.0]), [0.0]);
+    }
+}
@@ -97,6 +97,11 @@ impl RenderPipelineBuilder {
         self
     }
 
+    pub fn with_depth_stencil(mut self, depth_stencil: wgpu::DepthStencilState) -> Self {
+        self.depth_stencil = Some(depth_stencil);
+        self
+    }
+
     pub fn with_fragment_targets(mut self, targets: Vec<Option<ColorTargetState>>) -> Self {
         self.fragment_targets = targets;
         self
@@ -369,7 +374,7 @@ pub mod resource_helpers {
         })
     }
 
-    /// Create an empty storage buffer
+    /// Create an empty storage buffer that can be copied out for readback
     pub fn create_storage_buffer(
         device: &Device,
         label: &str,
@@ -379,7 +384,7 @@ pub mod resource_helpers {
         device.create_buffer(&wgpu::BufferDescriptor {
             label: Some(label),
             size,
-            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
             mapped_at_creation,
         })
     }
@@ -0,0 +1,90 @@
+//! Size of the field a grid based simulation (Gray-Scott, Slime Mold) runs
+//! on. By default the grid follows the window, one cell per pixel. A fixed
+//! size decouples the two: the display samples the field at whatever size
+//! the window is, so a 4096x4096 pattern keeps its detail in a small window
+//! and isn't rebuilt when the window is resized.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SimulationError, SimulationResult};
+
+/// Smallest fixed grid, the same floor Gray-Scott puts on window sized grids
+pub const MIN_FIXED_SIZE: u32 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum GridResolution {
+    #[default]
+    Window,
+    Fixed {
+        width: u32,
+        height: u32,
+    },
+}
+
+impl GridResolution {
+    /// Grid size for a window of `window_width` x `window_height` pixels
+    pub fn resolve(self, window_width: u32, window_height: u32) -> (u32, u32) {
+        match self {
+            Self::Window => (window_width, window_height),
+            Self::Fixed { width, height } => (width, height),
+        }
+    }
+
+    /// `max_dimension` is the device's largest 2D texture side
+    pub fn validate(self, max_dimension: u32) -> SimulationResult<()> {
+        let Self::Fixed { width, height } = self else {
+            return Ok(());
+        };
+        let range = MIN_FIXED_SIZE..=max_dimension;
+        if !range.contains(&width) || !range.contains(&height) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Grid resolution {}x{} is outside {}-{} on this GPU",
+                width, height, MIN_FIXED_SIZE, max_dimension
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_grids_ignore_the_window() {
+        let fixed = GridResolution::Fixed {
+            width: 4096,
+            height: 2048,
+        };
+        assert_eq!(fixed.resolve(800, 600), (4096, 2048));
+        assert_eq!(GridResolution::Window.resolve(800, 600), (800, 600));
+
+        assert!(fixed.validate(8192).is_ok());
+        assert!(fixed.validate(2048).is_err());
+        assert!(
+            GridResolution::Fixed {
+                width: 64,
+                height: 512
+            }
+            .validate(8192)
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn serializes_with_a_mode_tag() {
+        let json = serde_json::to_value(GridResolution::Fixed {
+            width: 1024,
+            height: 1024,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"mode": "fixed", "width": 1024, "height": 1024})
+        );
+        let window: GridResolution =
+            serde_json::from_value(serde_json::json!({"mode": "window"})).unwrap();
+        assert_eq!(window, GridResolution::Window);
+    }
+}
@@ -0,0 +1,298 @@
+//! Shape of the cells in a grid based automaton, which cells neighbor each
+//! one and which cell a point falls in. Grids wrap at the edges.
+//! `grid_topology.wgsl` does the same indexing on the GPU, so a shader and
+//! the CPU side always agree on where a cell is.
+//!
+//! Cells are addressed by column and row on every topology:
+//!
+//! - Square cells are one column wide and one row tall.
+//! - Hex grids shift odd rows half a column right, with rows packed closer
+//!   together so the hexagons come out regular.
+//! - Triangle grids alternate triangles pointing up (where `x + y` is even)
+//!   and down. Each is two columns wide at its base, so neighbors in a row
+//!   overlap by half.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GridTopology {
+    #[default]
+    Square,
+    Hex,
+    Triangle,
+}
+
+// Neighbors sharing an edge come first in each table
+const SQUARE: [(i32, i32); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (-1, -1),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+];
+const HEX_EVEN_ROW: [(i32, i32); 6] = [(-1, 0), (1, 0), (-1, -1), (0, -1), (-1, 1), (0, 1)];
+const HEX_ODD_ROW: [(i32, i32); 6] = [(-1, 0), (1, 0), (0, -1), (1, -1), (0, 1), (1, 1)];
+const TRIANGLE_UP: [(i32, i32); 12] = [
+    (-1, 0),
+    (1, 0),
+    (0, 1),
+    (-2, 0),
+    (2, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-2, 1),
+    (-1, 1),
+    (1, 1),
+    (2, 1),
+];
+const TRIANGLE_DOWN: [(i32, i32); 12] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (-2, 0),
+    (2, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (-2, -1),
+    (-1, -1),
+    (1, -1),
+    (2, -1),
+];
+
+const SQRT_3: f32 = 1.732_050_8;
+
+impl GridTopology {
+    /// What `grid_topology.wgsl` calls this topology
+    pub fn shader_index(self) -> u32 {
+        match self {
+            GridTopology::Square => 0,
+            GridTopology::Hex => 1,
+            GridTopology::Triangle => 2,
+        }
+    }
+
+    /// Height of a row in column widths, so cells keep their true shape
+    pub fn row_height(self) -> f32 {
+        match self {
+            GridTopology::Square => 1.0,
+            GridTopology::Hex => SQRT_3 / 2.0,
+            GridTopology::Triangle => SQRT_3,
+        }
+    }
+
+    /// Columns and rows that fit `width` by `height` pixels with columns
+    /// `column_width` pixels wide. Hex rows and triangle rows and columns
+    /// come in pairs, since the pattern only wraps cleanly on even counts.
+    pub fn grid_size(self, width: u32, height: u32, column_width: u32) -> (u32, u32) {
+        let column_width = column_width.max(1);
+        let columns = width / column_width;
+        let rows = (height as f32 / (column_width as f32 * self.row_height())) as u32;
+        match self {
+            GridTopology::Square => (columns.max(1), rows.max(1)),
+            GridTopology::Hex => (columns.max(1), (rows & !1).max(2)),
+            GridTopology::Triangle => ((columns & !1).max(2), (rows & !1).max(2)),
+        }
+    }
+
+    /// Offsets to every cell touching `(x, y)`, those sharing an edge first
+    pub fn neighbor_offsets(self, x: i32, y: i32) -> &'static [(i32, i32)] {
+        match self {
+            GridTopology::Square => &SQUARE,
+            GridTopology::Hex if y & 1 == 0 => &HEX_EVEN_ROW,
+            GridTopology::Hex => &HEX_ODD_ROW,
+            GridTopology::Triangle if (x + y) & 1 == 0 => &TRIANGLE_UP,
+            GridTopology::Triangle => &TRIANGLE_DOWN,
+        }
+    }
+
+    /// How many of [`neighbor_offsets`](Self::neighbor_offsets) share an
+    /// edge with the cell
+    pub fn edge_neighbor_count(self) -> usize {
+        match self {
+            GridTopology::Square => 4,
+            GridTopology::Hex => 6,
+            GridTopology::Triangle => 3,
+        }
+    }
+
+    /// The cells touching `(x, y)` on a `width` by `height` grid
+    pub fn neighbors(
+        self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> impl Iterator<Item = (u32, u32)> {
+        self.neighbor_offsets(x as i32, y as i32)
+            .iter()
+            .map(move |&(dx, dy)| wrap(x as i32 + dx, y as i32 + dy, width, height))
+    }
+
+    /// The cell `point` falls in, given in columns across and rows down.
+    /// Not wrapped to the grid.
+    pub fn cell_at(self, point: (f32, f32)) -> (i32, i32) {
+        let (x, y) = point;
+        match self {
+            GridTopology::Square => (x.floor() as i32, y.floor() as i32),
+            GridTopology::Hex => {
+                // Closest center of the rows around the point, with rows
+                // measured at their on-screen height
+                let row = y.floor() as i32;
+                let mut best = (0, 0);
+                let mut best_distance = f32::MAX;
+                for candidate_row in row - 1..=row + 1 {
+                    let shift = (candidate_row & 1) as f32 * 0.5;
+                    let column = (x - shift).floor() as i32;
+                    let (center_x, center_y) = self.cell_center(column, candidate_row);
+                    let dx = x - center_x;
+                    let dy = (y - center_y) * self.row_height();
+                    let distance = dx * dx + dy * dy;
+                    if distance < best_distance {
+                        best = (column, candidate_row);
+                        best_distance = distance;
+                    }
+                }
+                best
+            }
+            GridTopology::Triangle => {
+                // Between two triangles overlapping this half column, split
+                // by the slanted edge they share
+                let row = y.floor() as i32;
+                let depth = y - row as f32;
+                let left = (x - 0.5).floor() as i32;
+                let across = x - 0.5 - left as f32;
+                let edge = if (left + row) & 1 == 0 {
+                    depth
+                } else {
+                    1.0 - depth
+                };
+                (left + (across >= edge) as i32, row)
+            }
+        }
+    }
+
+    /// Where the middle of cell `(x, y)` is, in columns across and rows down
+    pub fn cell_center(self, x: i32, y: i32) -> (f32, f32) {
+        match self {
+            GridTopology::Square => (x as f32 + 0.5, y as f32 + 0.5),
+            GridTopology::Hex => (x as f32 + 0.5 + (y & 1) as f32 * 0.5, y as f32 + 0.5),
+            GridTopology::Triangle => {
+                let pointing_up = (x + y) & 1 == 0;
+                let depth = if pointing_up { 2.0 / 3.0 } else { 1.0 / 3.0 };
+                (x as f32 + 0.5, y as f32 + depth)
+            }
+        }
+    }
+}
+
+/// `(x, y)` wrapped onto a `width` by `height` grid
+pub fn wrap(x: i32, y: i32, width: u32, height: u32) -> (u32, u32) {
+    (
+        x.rem_euclid(width as i32) as u32,
+        y.rem_euclid(height as i32) as u32,
+    )
+}
+
+impl FromStr for GridTopology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "square" => Ok(GridTopology::Square),
+            "hex" => Ok(GridTopology::Hex),
+            "triangle" => Ok(GridTopology::Triangle),
+            _ => Err(format!(
+                "Invalid GridTopology: '{}'. Expected 'square', 'hex' or 'triangle'",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [GridTopology; 3] = [
+        GridTopology::Square,
+        GridTopology::Hex,
+        GridTopology::Triangle,
+    ];
+
+    #[test]
+    fn neighbors_are_mutual() {
+        for topology in ALL {
+            let (width, height) = topology.grid_size(160, 120, 10);
+            for y in 0..height {
+                for x in 0..width {
+                    for (nx, ny) in topology.neighbors(x, y, width, height) {
+                        assert!(
+                            topology
+                                .neighbors(nx, ny, width, height)
+                                .any(|cell| cell == (x, y)),
+                            "{:?}: ({}, {}) neighbors ({}, {}) but not back",
+                            topology,
+                            x,
+                            y,
+                            nx,
+                            ny
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn points_land_in_the_cell_around_them() {
+        for topology in ALL {
+            for y in -3..5 {
+                for x in -3..5 {
+                    let (center_x, center_y) = topology.cell_center(x, y);
+                    assert_eq!(topology.cell_at((center_x, center_y)), (x, y));
+                    // A little way off center is still the same cell
+                    assert_eq!(
+                        topology.cell_at((center_x + 0.1, center_y - 0.1)),
+                        (x, y),
+                        "{:?}",
+                        topology
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn edge_neighbors_are_the_nearest() {
+        for topology in ALL {
+            for (x, y) in [(2, 2), (3, 2), (2, 3), (3, 3)] {
+                let (center_x, center_y) = topology.cell_center(x, y);
+                let distance = |&(dx, dy): &(i32, i32)| {
+                    let (nx, ny) = topology.cell_center(x + dx, y + dy);
+                    let scaled = (ny - center_y) * topology.row_height();
+                    ((nx - center_x).powi(2) + scaled.powi(2)).sqrt()
+                };
+                let offsets = topology.neighbor_offsets(x, y);
+                let (edges, corners) = offsets.split_at(topology.edge_neighbor_count());
+                let farthest_edge = edges.iter().map(distance).fold(0.0, f32::max);
+                let nearest_corner = corners.iter().map(distance).fold(f32::MAX, f32::min);
+                assert!(farthest_edge < nearest_corner + 1e-4, "{:?}", topology);
+            }
+        }
+    }
+
+    #[test]
+    fn wrapping_grids_come_in_pairs() {
+        assert_eq!(GridTopology::Square.grid_size(105, 75, 10), (10, 7));
+        let (_, rows) = GridTopology::Hex.grid_size(105, 75, 10);
+        assert_eq!(rows % 2, 0);
+        let (columns, rows) = GridTopology::Triangle.grid_size(105, 75, 10);
+        assert_eq!((columns % 2, rows % 2), (0, 0));
+    }
+}
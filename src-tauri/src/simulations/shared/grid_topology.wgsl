@@ -0,0 +1,118 @@
+// Cell shapes for grid based automata: which cells touch which, and which
+// cell a point falls in. Mirrors grid_topology.rs, which documents the
+// layouts. Cells are addressed by column and row; neighbors come back
+// unwrapped, so pass them through topology_wrap.
+
+const TOPOLOGY_SQUARE: u32 = 0u;
+const TOPOLOGY_HEX: u32 = 1u;
+const TOPOLOGY_TRIANGLE: u32 = 2u;
+
+fn topology_neighbor_count(topology: u32) -> u32 {
+    switch (topology) {
+        case 1u: { return 6u; }
+        case 2u: { return 12u; }
+        default: { return 8u; }
+    }
+}
+
+// The first this many neighbors share an edge with the cell
+fn topology_edge_neighbor_count(topology: u32) -> u32 {
+    switch (topology) {
+        case 1u: { return 6u; }
+        case 2u: { return 3u; }
+        default: { return 4u; }
+    }
+}
+
+fn topology_neighbor(topology: u32, cell: vec2<i32>, index: u32) -> vec2<i32> {
+    if (topology == TOPOLOGY_HEX) {
+        var even_row = array<vec2<i32>, 6>(
+            vec2<i32>(-1, 0), vec2<i32>(1, 0), vec2<i32>(-1, -1),
+            vec2<i32>(0, -1), vec2<i32>(-1, 1), vec2<i32>(0, 1)
+        );
+        var odd_row = array<vec2<i32>, 6>(
+            vec2<i32>(-1, 0), vec2<i32>(1, 0), vec2<i32>(0, -1),
+            vec2<i32>(1, -1), vec2<i32>(0, 1), vec2<i32>(1, 1)
+        );
+        if ((cell.y & 1) == 0) {
+            return cell + even_row[index];
+        }
+        return cell + odd_row[index];
+    }
+    if (topology == TOPOLOGY_TRIANGLE) {
+        // Written for a triangle pointing up; flipped for one pointing down
+        var up = array<vec2<i32>, 12>(
+            vec2<i32>(-1, 0), vec2<i32>(1, 0), vec2<i32>(0, 1),
+            vec2<i32>(-2, 0), vec2<i32>(2, 0),
+            vec2<i32>(-1, -1), vec2<i32>(0, -1), vec2<i32>(1, -1),
+            vec2<i32>(-2, 1), vec2<i32>(-1, 1), vec2<i32>(1, 1), vec2<i32>(2, 1)
+        );
+        var offset = up[index];
+        if (((cell.x + cell.y) & 1) == 1) {
+            offset.y = -offset.y;
+        }
+        return cell + offset;
+    }
+    var square = array<vec2<i32>, 8>(
+        vec2<i32>(0, -1), vec2<i32>(1, 0), vec2<i32>(0, 1), vec2<i32>(-1, 0),
+        vec2<i32>(-1, -1), vec2<i32>(1, -1), vec2<i32>(1, 1), vec2<i32>(-1, 1)
+    );
+    return cell + square[index];
+}
+
+fn topology_wrap(cell: vec2<i32>, size: vec2<u32>) -> vec2<u32> {
+    let bounds = vec2<i32>(size);
+    return vec2<u32>(((cell % bounds) + bounds) % bounds);
+}
+
+// Height of a row in column widths
+fn topology_row_height(topology: u32) -> f32 {
+    switch (topology) {
+        case 1u: { return 0.8660254; }
+        case 2u: { return 1.7320508; }
+        default: { return 1.0; }
+    }
+}
+
+fn topology_cell_center(topology: u32, cell: vec2<i32>) -> vec2<f32> {
+    let corner = vec2<f32>(cell);
+    if (topology == TOPOLOGY_HEX) {
+        return corner + vec2<f32>(0.5 + f32(cell.y & 1) * 0.5, 0.5);
+    }
+    if (topology == TOPOLOGY_TRIANGLE) {
+        let pointing_up = ((cell.x + cell.y) & 1) == 0;
+        return corner + vec2<f32>(0.5, select(1.0 / 3.0, 2.0 / 3.0, pointing_up));
+    }
+    return corner + vec2<f32>(0.5, 0.5);
+}
+
+// The cell a point in columns across and rows down falls in, unwrapped
+fn topology_cell_at(topology: u32, point: vec2<f32>) -> vec2<i32> {
+    let row = i32(floor(point.y));
+    if (topology == TOPOLOGY_HEX) {
+        // Closest center of the rows around the point
+        var best = vec2<i32>(0, row);
+        var best_distance = 1e30;
+        for (var candidate_row = row - 1; candidate_row <= row + 1; candidate_row++) {
+            let shift = f32(candidate_row & 1) * 0.5;
+            let candidate = vec2<i32>(i32(floor(point.x - shift)), candidate_row);
+            var offset = point - topology_cell_center(topology, candidate);
+            offset.y *= topology_row_height(topology);
+            let distance = dot(offset, offset);
+            if (distance < best_distance) {
+                best = candidate;
+                best_distance = distance;
+            }
+        }
+        return best;
+    }
+    if (topology == TOPOLOGY_TRIANGLE) {
+        // Two triangles overlap each half column, split by a slanted edge
+        let depth = point.y - f32(row);
+        let left = i32(floor(point.x - 0.5));
+        let across = point.x - 0.5 - f32(left);
+        let edge = select(1.0 - depth, depth, ((left + row) & 1) == 0);
+        return vec2<i32>(left + select(0, 1, across >= edge), row);
+    }
+    return vec2<i32>(floor(point));
+}
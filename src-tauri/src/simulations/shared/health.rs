@@ -0,0 +1,395 @@
+//! Cheap GPU statistics over simulation buffers, for spotting states a
+//! simulation won't come back from on its own.
+//!
+//! A simulation describes the buffers worth watching as [`HealthProbe`]s
+//! through [`Simulation::health_probes`](crate::simulations::traits::Simulation::health_probes).
+//! [`HealthReducer`] boils each one down to a handful of numbers on the GPU,
+//! and only those numbers are read back.
+
+use bytemuck::{Pod, Zeroable};
+use serde::Serialize;
+use std::sync::Arc;
+use wgpu::{Buffer, ComputePipeline, Device, Queue};
+
+use super::gpu_utils::resource_helpers;
+use crate::error::{SimulationError, SimulationResult};
+
+const HEALTH_SHADER: &str = include_str!("health.wgsl");
+
+/// Matches `WORKGROUP_COUNT` in health.wgsl
+const WORKGROUP_COUNT: u32 = 256;
+const STATS_LEN: usize = 8;
+const STATS_SIZE: u64 = (STATS_LEN * std::mem::size_of::<u32>()) as u64;
+
+/// Positions spread over less than this have all ended up in the same spot
+const COLLAPSED_EXTENT: f32 = 1e-4;
+
+/// What a probe's values are, and so what counts as broken for them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HealthCheck {
+    /// Broken when every element sits at the same point
+    Positions,
+    /// Broken when any element is faster than `max_magnitude`
+    Velocities { max_magnitude: f32 },
+    /// Broken when every value is zero
+    Field,
+}
+
+/// A vector or scalar read from each element of a storage buffer
+#[derive(Debug, Clone, Copy)]
+pub struct HealthProbe<'a> {
+    pub label: &'static str,
+    pub buffer: &'a Buffer,
+    /// Floats per element
+    pub stride: u32,
+    /// First float of the watched value within an element
+    pub offset: u32,
+    /// 1 for a scalar, 2 for a 2D vector
+    pub components: u32,
+    pub check: HealthCheck,
+}
+
+impl<'a> HealthProbe<'a> {
+    /// The 2D vector at `offset` in elements of `stride` floats
+    pub fn vector(
+        label: &'static str,
+        buffer: &'a Buffer,
+        stride: u32,
+        offset: u32,
+        check: HealthCheck,
+    ) -> Self {
+        Self {
+            label,
+            buffer,
+            stride,
+            offset,
+            components: 2,
+            check,
+        }
+    }
+
+    /// A buffer of plain floats, such as a trail map
+    pub fn field(label: &'static str, buffer: &'a Buffer) -> Self {
+        Self {
+            label,
+            buffer,
+            stride: 1,
+            offset: 0,
+            components: 1,
+            check: HealthCheck::Field,
+        }
+    }
+
+    fn element_count(&self) -> u32 {
+        let element_bytes = self.stride.max(1) as u64 * std::mem::size_of::<f32>() as u64;
+        (self.buffer.size() / element_bytes) as u32
+    }
+}
+
+/// A problem found in one probe
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum HealthIssue {
+    NonFinite { field: String, count: u32 },
+    Collapsed { field: String },
+    Exploding { field: String, max_magnitude: f32 },
+    AllZero { field: String },
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct HealthParams {
+    element_count: u32,
+    stride: u32,
+    offset: u32,
+    components: u32,
+}
+
+/// What the reduction found in one probe
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthStats {
+    pub non_finite: u32,
+    pub nonzero: u32,
+    pub finite: u32,
+    pub max_magnitude: f32,
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+impl HealthStats {
+    fn from_raw(raw: [u32; STATS_LEN]) -> Self {
+        Self {
+            non_finite: raw[0],
+            nonzero: raw[1],
+            max_magnitude: f32::from_bits(raw[2]),
+            min: [from_ordered_bits(raw[3]), from_ordered_bits(raw[4])],
+            max: [from_ordered_bits(raw[5]), from_ordered_bits(raw[6])],
+            finite: raw[7],
+        }
+    }
+
+    pub fn issues(&self, label: &str, check: HealthCheck) -> Vec<HealthIssue> {
+        let field = label.to_string();
+        let mut issues = Vec::new();
+        if self.non_finite > 0 {
+            issues.push(HealthIssue::NonFinite {
+                field: field.clone(),
+                count: self.non_finite,
+            });
+        }
+        if self.finite == 0 {
+            return issues;
+        }
+
+        match check {
+            HealthCheck::Positions => {
+                let extent = (self.max[0] - self.min[0]).max(self.max[1] - self.min[1]);
+                if self.finite > 1 && extent < COLLAPSED_EXTENT {
+                    issues.push(HealthIssue::Collapsed { field });
+                }
+            }
+            HealthCheck::Velocities { max_magnitude } => {
+                if self.max_magnitude > max_magnitude {
+                    issues.push(HealthIssue::Exploding {
+                        field,
+                        max_magnitude: self.max_magnitude,
+                    });
+                }
+            }
+            HealthCheck::Field => {
+                if self.nonzero == 0 {
+                    issues.push(HealthIssue::AllZero { field });
+                }
+            }
+        }
+        issues
+    }
+}
+
+/// Inverse of `ordered_bits` in health.wgsl
+fn from_ordered_bits(bits: u32) -> f32 {
+    if bits & 0x8000_0000 != 0 {
+        f32::from_bits(bits & 0x7fff_ffff)
+    } else {
+        f32::from_bits(!bits)
+    }
+}
+
+fn initial_stats() -> [u32; STATS_LEN] {
+    // Min slots start at the largest ordered value so any float replaces them
+    [0, 0, 0, u32::MAX, u32::MAX, 0, 0, 0]
+}
+
+#[derive(Debug)]
+pub struct HealthReducer {
+    pipeline: ComputePipeline,
+    params_buffer: Buffer,
+    stats_buffer: Buffer,
+    staging_buffer: Buffer,
+}
+
+impl HealthReducer {
+    pub fn new(device: &Arc<Device>) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Health Shader"),
+            source: wgpu::ShaderSource::Wgsl(HEALTH_SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Health Pipeline"),
+            layout: None,
+            module: &shader,
+            entry_point: Some("main"),
+            cache: None,
+            compilation_options: Default::default(),
+        });
+
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Health Params Buffer"),
+            size: std::mem::size_of::<HealthParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let stats_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Health Stats Buffer"),
+            size: STATS_SIZE,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Health Staging Buffer"),
+            size: STATS_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            pipeline,
+            params_buffer,
+            stats_buffer,
+            staging_buffer,
+        }
+    }
+
+    /// Reduce `probe` on the GPU and wait for the result
+    pub fn measure(
+        &self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        probe: &HealthProbe,
+    ) -> SimulationResult<HealthStats> {
+        if probe.buffer.size() > device.limits().max_storage_buffer_binding_size as u64 {
+            return Err(SimulationError::InvalidParameter(format!(
+                "{} buffer is too large to check",
+                probe.label
+            )));
+        }
+
+        let params = HealthParams {
+            element_count: probe.element_count(),
+            stride: probe.stride.max(1),
+            offset: probe.offset,
+            components: probe.components,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+        queue.write_buffer(
+            &self.stats_buffer,
+            0,
+            bytemuck::cast_slice(&initial_stats()),
+        );
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Health Bind Group"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                resource_helpers::buffer_entry(0, probe.buffer),
+                resource_helpers::buffer_entry(1, &self.stats_buffer),
+                resource_helpers::buffer_entry(2, &self.params_buffer),
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Health Encoder"),
+        });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Health Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            compute_pass.dispatch_workgroups(WORKGROUP_COUNT, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&self.stats_buffer, 0, &self.staging_buffer, 0, STATS_SIZE);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = self.staging_buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+        device
+            .poll(wgpu::wgt::PollType::Wait)
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+        receiver
+            .recv()
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+
+        let raw: [u32; STATS_LEN] = {
+            let data = buffer_slice.get_mapped_range();
+            *bytemuck::from_bytes(&data)
+        };
+        self.staging_buffer.unmap();
+
+        Ok(HealthStats::from_raw(raw))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ordered_bits(value: f32) -> u32 {
+        let bits = value.to_bits();
+        if bits & 0x8000_0000 != 0 {
+            !bits
+        } else {
+            bits | 0x8000_0000
+        }
+    }
+
+    fn stats(min: [f32; 2], max: [f32; 2], max_magnitude: f32, nonzero: u32) -> HealthStats {
+        HealthStats {
+            non_finite: 0,
+            nonzero,
+            finite: 100,
+            max_magnitude,
+            min,
+            max,
+        }
+    }
+
+    #[test]
+    fn ordered_bits_round_trip_and_keep_order() {
+        for value in [-3.5f32, -0.25, 0.0, 0.25, 1e9] {
+            assert_eq!(from_ordered_bits(ordered_bits(value)), value);
+        }
+        assert!(ordered_bits(-1.0) < ordered_bits(-0.5));
+        assert!(ordered_bits(-0.5) < ordered_bits(0.5));
+    }
+
+    #[test]
+    fn untouched_stats_decode_to_an_empty_range() {
+        let decoded = HealthStats::from_raw(initial_stats());
+        assert_eq!(decoded.finite, 0);
+        assert!(
+            decoded
+                .issues("Particles", HealthCheck::Positions)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn checks_flag_their_failure_modes() {
+        let spread = stats([-1.0, -1.0], [1.0, 1.0], 0.5, 100);
+        assert!(
+            spread
+                .issues("Particles", HealthCheck::Positions)
+                .is_empty()
+        );
+
+        let collapsed = stats([0.3, 0.3], [0.3, 0.3], 0.5, 100);
+        assert_eq!(
+            collapsed.issues("Particles", HealthCheck::Positions),
+            vec![HealthIssue::Collapsed {
+                field: "Particles".to_string()
+            }]
+        );
+
+        let fast = HealthCheck::Velocities {
+            max_magnitude: 10.0,
+        };
+        assert!(spread.issues("Velocities", fast).is_empty());
+        assert_eq!(
+            stats([0.0; 2], [0.0; 2], 50.0, 100)
+                .issues("Velocities", fast)
+                .len(),
+            1
+        );
+
+        let empty = stats([0.0; 2], [0.0; 2], 0.0, 0);
+        assert_eq!(empty.issues("Trails", HealthCheck::Field).len(), 1);
+
+        let broken = HealthStats {
+            non_finite: 3,
+            ..spread
+        };
+        assert_eq!(
+            broken.issues("Particles", HealthCheck::Positions),
+            vec![HealthIssue::NonFinite {
+                field: "Particles".to_string(),
+                count: 3
+            }]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+// Reduces one vector per element of a storage buffer to a few statistics the
+// watchdog uses to spot broken simulation states
+
+struct Params {
+    element_count: u32,
+    // Floats per element, and the first float of the watched vector in it
+    stride: u32,
+    offset: u32,
+    // 1 for scalar fields, 2 for positions and velocities
+    components: u32,
+}
+
+// The grid-stride loop below covers any element count with a fixed dispatch
+const WORKGROUP_SIZE: u32 = 256u;
+const WORKGROUP_COUNT: u32 = 256u;
+
+@group(0) @binding(0) var<storage, read> values: array<f32>;
+// [non_finite, nonzero, max_magnitude, min_x, min_y, max_x, max_y, finite]
+@group(0) @binding(1) var<storage, read_write> stats: array<atomic<u32>, 8>;
+@group(0) @binding(2) var<uniform> params: Params;
+
+fn is_finite(value: f32) -> bool {
+    return (bitcast<u32>(value) & 0x7f800000u) != 0x7f800000u;
+}
+
+// Maps floats to integers with the same ordering, for atomic min and max
+fn ordered_bits(value: f32) -> u32 {
+    let bits = bitcast<u32>(value);
+    if ((bits & 0x80000000u) != 0u) {
+        return ~bits;
+    }
+    return bits | 0x80000000u;
+}
+
+@compute @workgroup_size(256)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    var non_finite = 0u;
+    var nonzero = 0u;
+    var finite = 0u;
+    var max_magnitude = 0.0;
+    var lowest = vec2<f32>(3.4e38);
+    var highest = vec2<f32>(-3.4e38);
+
+    for (var i = id.x; i < params.element_count; i += WORKGROUP_SIZE * WORKGROUP_COUNT) {
+        let base = i * params.stride + params.offset;
+        var value = vec2<f32>(values[base], 0.0);
+        if (params.components > 1u) {
+            value.y = values[base + 1u];
+        }
+
+        if (!is_finite(value.x) || !is_finite(value.y)) {
+            non_finite += 1u;
+            continue;
+        }
+        finite += 1u;
+        if (any(value != vec2<f32>(0.0))) {
+            nonzero += 1u;
+        }
+        max_magnitude = max(max_magnitude, length(value));
+        lowest = min(lowest, value);
+        highest = max(highest, value);
+    }
+
+    if (non_finite > 0u) {
+        atomicAdd(&stats[0], non_finite);
+    }
+    if (finite == 0u) {
+        return;
+    }
+    atomicAdd(&stats[1], nonzero);
+    // Non-negative floats already order like their bits
+    atomicMax(&stats[2], bitcast<u32>(max_magnitude));
+    atomicMin(&stats[3], ordered_bits(lowest.x));
+    atomicMin(&stats[4], ordered_bits(lowest.y));
+    atomicMax(&stats[5], ordered_bits(highest.x));
+    atomicMax(&stats[6], ordered_bits(highest.y));
+    atomicAdd(&stats[7], finite);
+}
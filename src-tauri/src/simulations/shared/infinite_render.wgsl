@@ -35,6 +35,12 @@ var<uniform> params: SimulationParams;
 @group(0) @binding(7)
 var<uniform> render_params: RenderParams;
 
+// Second color scheme, following V (see lut_blend.wgsl)
+@group(0) @binding(8)
+var<uniform> lut_blend: LutBlend;
+@group(0) @binding(9)
+var<storage, read> secondary_lut_data: array<u32>;
+
 @group(1) @binding(0)
 var<uniform> camera: CameraUniform;
 
@@ -210,27 +216,21 @@ fn lanczos_kernel(x: f32, a: f32) -> f32 {
     return (sin(x_pi) * sin(x_pi_a)) / (x_pi * x_pi_a);
 }
 
-// Convert from sRGB (gamma-corrected) to linear RGB
-fn srgb_to_linear(srgb: f32) -> f32 {
-    if (srgb <= 0.04045) {
-        return srgb / 12.92;
-    } else {
-        return pow((srgb + 0.055) / 1.055, 2.4);
-    }
-}
-
 // Fragment shader for texture-based simulations (Gray Scott)
 fn fs_main_storage(in: VertexOutput) -> vec4<f32> {
     var u_interpolated: f32;
+    var v_interpolated: f32;
     
     if (render_params.filtering_mode == 0u) {
         // Nearest neighbor
         let sample = textureSample(simulation_data, simulation_sampler, in.uv);
         u_interpolated = sample.x; // R channel contains u value
+        v_interpolated = sample.y;
     } else if (render_params.filtering_mode == 1u) {
         // Linear (bilinear interpolation)
         let sample = textureSample(simulation_data, simulation_sampler, in.uv);
         u_interpolated = sample.x; // R channel contains u value
+        v_interpolated = sample.y;
     } else {
         // Lanczos filtering
         let tex_dims = textureDimensions(simulation_data);
@@ -241,6 +241,7 @@ fn fs_main_storage(in: VertexOutput) -> vec4<f32> {
         let radius = i32(lanczos_a);
         
         var u_sum = 0.0;
+        var v_sum = 0.0;
         var weight_sum = 0.0;
         
         // Sample in a radius around the target pixel
@@ -271,12 +272,14 @@ fn fs_main_storage(in: VertexOutput) -> vec4<f32> {
                 let u = sample.x; // R channel contains u value
                 
                 u_sum += u * weight;
+                v_sum += sample.y * weight;
                 weight_sum += weight;
             }
         }
         
         // Normalize by total weight
         u_interpolated = u_sum / weight_sum;
+        v_interpolated = v_sum / weight_sum;
     }
     
     // Use interpolated u value for LUT lookup
@@ -291,7 +294,10 @@ fn fs_main_storage(in: VertexOutput) -> vec4<f32> {
     let b = srgb_to_linear(b_srgb);
     let a = 1.0;
     
-    let base_color = vec4<f32>(r, g, b, a);
+    let base_color = vec4<f32>(
+        blend_luts(vec3<f32>(r, g, b), u_interpolated, v_interpolated),
+        a
+    );
     
     if (base_color.a <= 0.0) {
         discard;
@@ -0,0 +1,168 @@
+//! Running one compute kernel on a headless device, so tests can hold it to
+//! the CPU copy kept beside the simulation.
+//!
+//! Pipelines are built with an automatic layout, so a test only binds what
+//! the kernel reads, by binding number.
+
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+use super::rewind::RewindResource;
+use super::snapshot;
+
+pub struct KernelHarness {
+    pub device: Arc<wgpu::Device>,
+    pub queue: Arc<wgpu::Queue>,
+}
+
+impl KernelHarness {
+    pub async fn new() -> Self {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            })
+            .await
+            .expect("Failed to find an appropriate adapter");
+
+        // Some adapters only read storage textures in float formats with this
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("Kernel Test Device"),
+                required_features: adapter.features()
+                    & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to create device");
+
+        Self {
+            device: Arc::new(device),
+            queue: Arc::new(queue),
+        }
+    }
+
+    pub fn pipeline(&self, source: &str, entry_point: &str) -> wgpu::ComputePipeline {
+        let module = self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Kernel Test Shader"),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+        self.device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Kernel Test Pipeline"),
+                layout: None,
+                module: &module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+    }
+
+    pub fn buffer(&self, contents: &[u8], usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        self.device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Kernel Test Buffer"),
+                contents,
+                usage: usage | wgpu::BufferUsages::COPY_SRC,
+            })
+    }
+
+    /// A 2D texture `width` by `height` holding `contents`, packed row
+    /// after row
+    pub fn texture(
+        &self,
+        (width, height): (u32, u32),
+        format: wgpu::TextureFormat,
+        contents: &[u8],
+    ) -> wgpu::Texture {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Kernel Test Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::STORAGE_BINDING
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        self.queue.write_texture(
+            texture.as_image_copy(),
+            contents,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(contents.len() as u32 / height),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+        texture
+    }
+
+    /// Dispatch `pipeline` once over `workgroups` with `entries` bound to
+    /// group 0
+    pub fn dispatch(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        entries: &[wgpu::BindGroupEntry<'_>],
+        workgroups: (u32, u32, u32),
+    ) {
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Kernel Test Bind Group"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Kernel Test Encoder"),
+            });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Kernel Test Pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+        }
+        self.queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    pub fn read(&self, resource: RewindResource<'_>) -> Vec<u8> {
+        snapshot::read_resource(&self.device, &self.queue, resource)
+            .expect("Failed to read back kernel output")
+    }
+}
+
+/// Panic naming the first of `gpu`'s values more than `tolerance` from
+/// `cpu`'s
+pub fn assert_close(what: &str, gpu: &[f32], cpu: &[f32], tolerance: f32) {
+    assert_eq!(gpu.len(), cpu.len(), "{} lengths differ", what);
+    if let Some((index, (g, c))) = gpu
+        .iter()
+        .zip(cpu)
+        .enumerate()
+        .find(|(_, (g, c))| (*g - *c).abs() > tolerance || g.is_nan() != c.is_nan())
+    {
+        panic!(
+            "{} differs at {}: GPU gave {}, CPU gave {} (tolerance {})",
+            what, index, g, c, tolerance
+        );
+    }
+}
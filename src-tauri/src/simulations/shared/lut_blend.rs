@@ -0,0 +1,116 @@
+//! A second color scheme blended per pixel with a simulation's main one,
+//! driven by a second signal the simulation renders alongside the main
+//! signal (Gray-Scott's V next to U). `lut_blend.wgsl` does the blending; the
+//! render shader binds the secondary LUT as `secondary_lut_data` and the
+//! [`LutBlendUniform`] as `lut_blend`.
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+
+use super::{ColorScheme, ColorSchemeManager};
+use crate::error::{LutResult, SimulationError, SimulationResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LutBlendMode {
+    /// Only the main color scheme
+    #[default]
+    Off,
+    /// Both schemes follow the main signal; the second signal fades between them
+    Crossfade,
+    /// The secondary scheme, following the second signal, is multiplied in
+    Multiply,
+    /// The secondary scheme, following the second signal, is screened in
+    Screen,
+    /// The secondary scheme, following the second signal, is added on top
+    Add,
+}
+
+impl From<LutBlendMode> for u32 {
+    fn from(mode: LutBlendMode) -> Self {
+        match mode {
+            LutBlendMode::Off => 0,
+            LutBlendMode::Crossfade => 1,
+            LutBlendMode::Multiply => 2,
+            LutBlendMode::Screen => 3,
+            LutBlendMode::Add => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LutBlend {
+    pub mode: LutBlendMode,
+    pub color_scheme: String,
+    pub color_scheme_reversed: bool,
+    /// How much of the secondary scheme shows, 0-1
+    pub amount: f32,
+}
+
+impl Default for LutBlend {
+    fn default() -> Self {
+        Self {
+            mode: LutBlendMode::Off,
+            color_scheme: "MATPLOTLIB_viridis".to_string(),
+            color_scheme_reversed: false,
+            amount: 1.0,
+        }
+    }
+}
+
+/// `LutBlend` in `lut_blend.wgsl`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct LutBlendUniform {
+    pub mode: u32,
+    pub amount: f32,
+    pub _pad0: u32,
+    pub _pad1: u32,
+}
+
+impl LutBlend {
+    pub fn validate(&self) -> SimulationResult<()> {
+        if !(0.0..=1.0).contains(&self.amount) {
+            return Err(SimulationError::InvalidParameter(
+                "LUT blend amount must be between 0 and 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn uniform(&self) -> LutBlendUniform {
+        LutBlendUniform {
+            mode: self.mode.into(),
+            amount: self.amount,
+            _pad0: 0,
+            _pad1: 0,
+        }
+    }
+
+    /// The secondary scheme, reversed if asked for
+    pub fn secondary_lut(&self, manager: &ColorSchemeManager) -> LutResult<ColorScheme> {
+        let mut scheme = manager.get(&self.color_scheme)?;
+        if self.color_scheme_reversed {
+            scheme.reverse();
+        }
+        Ok(scheme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn off_by_default() {
+        let blend: LutBlend = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(blend.uniform().mode, 0);
+        assert!(blend.validate().is_ok());
+
+        let blend: LutBlend =
+            serde_json::from_value(serde_json::json!({"mode": "screen", "amount": 2.0})).unwrap();
+        assert_eq!(blend.uniform().mode, 3);
+        assert!(blend.validate().is_err());
+    }
+}
@@ -0,0 +1,44 @@
+// Blends a second color scheme into a simulation's main one.
+// Prepended to a render shader, which binds its own
+// `var<uniform> lut_blend: LutBlend` and
+// `var<storage, read> secondary_lut_data: array<u32>`; the layout matches
+// `LutBlendUniform`.
+
+struct LutBlend {
+    mode: u32, // 0=off, 1=crossfade, 2=multiply, 3=screen, 4=add
+    amount: f32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+fn secondary_lut_color(t: f32) -> vec3<f32> {
+    let idx = u32(clamp(t * 255.0, 0.0, 255.0));
+    return vec3<f32>(
+        srgb_to_linear(f32(secondary_lut_data[idx]) / 255.0),
+        srgb_to_linear(f32(secondary_lut_data[idx + 256u]) / 255.0),
+        srgb_to_linear(f32(secondary_lut_data[idx + 512u]) / 255.0)
+    );
+}
+
+// `color` is the main scheme at `primary`; both signals run 0-1
+fn blend_luts(color: vec3<f32>, primary: f32, secondary: f32) -> vec3<f32> {
+    let amount = clamp(lut_blend.amount, 0.0, 1.0);
+    switch (lut_blend.mode) {
+        case 1u: {
+            return mix(color, secondary_lut_color(primary), clamp(secondary, 0.0, 1.0) * amount);
+        }
+        case 2u: {
+            return mix(color, color * secondary_lut_color(secondary), amount);
+        }
+        case 3u: {
+            let screened = 1.0 - (1.0 - color) * (1.0 - secondary_lut_color(secondary));
+            return mix(color, screened, amount);
+        }
+        case 4u: {
+            return min(color + secondary_lut_color(secondary) * amount, vec3<f32>(1.0));
+        }
+        default: {
+            return color;
+        }
+    }
+}
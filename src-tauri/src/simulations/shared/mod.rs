@@ -18,29 +18,82 @@
 //! management. Each area provides both basic functionality and
 //! advanced features for sophisticated simulation experiences.
 
+pub mod audio;
 pub mod average_color;
+pub mod background_layer;
+pub mod boundary;
 pub mod camera;
 pub mod color_scheme;
+pub mod color_script;
+pub mod color_space;
+pub mod constraints;
 pub mod coordinates;
+pub mod cursor_force;
+pub mod environment_field;
+pub mod field_image;
+pub mod frame_capture;
+pub mod global_force;
+pub mod gpu_budget;
+pub mod gpu_tier;
+pub mod gpu_timer;
 pub mod gpu_utils;
+pub mod grid_resolution;
+pub mod grid_topology;
+pub mod health;
+#[cfg(test)]
+pub mod kernel_test;
+pub mod lut_blend;
+pub mod orbit_camera;
+pub mod particle_system;
 pub mod ping_pong_buffers;
 pub mod ping_pong_render_textures;
 pub mod ping_pong_textures;
 pub mod position_generators;
 pub mod post_processing;
+pub mod randomize;
+pub mod render_graph;
+pub mod rewind;
+pub mod snapshot;
+pub mod trails;
 pub mod types;
+pub mod validation;
 pub mod webcam;
 
 pub use average_color::AverageColorResources;
+pub use background_layer::BackgroundLayer;
+pub use boundary::{Boundary, BoundaryConditions};
 pub use color_scheme::{ColorScheme, ColorSchemeManager, SimulationColorSchemeManager};
+pub use color_script::ColorScript;
+pub use cursor_force::{CursorForceField, CursorMode, StrengthCurve};
+pub use environment_field::EnvironmentField;
+pub use frame_capture::FrameCapture;
+pub use global_force::{GlobalForce, GlobalForceUniform};
+pub use gpu_budget::GpuReservation;
+pub use gpu_timer::GpuTimer;
 pub use gpu_utils::{
     BindGroupBuilder, CommonBindGroupLayouts, ComputePipelineBuilder, RenderPipelineBuilder,
     ShaderManager,
 };
+pub use grid_resolution::GridResolution;
+pub use grid_topology::GridTopology;
+pub use health::{HealthCheck, HealthIssue, HealthProbe};
+pub use lut_blend::LutBlend;
+pub use particle_system::{ParticleBuffer, PingPongParticleBuffers};
 pub use position_generators::{PositionGenerator, SlimeMoldPositionGenerator};
 pub use post_processing::{PostProcessingResources, PostProcessingState};
+pub use randomize::{RandomizeOptions, SettingCategories};
+pub use render_graph::{GraphNode, NodeKind, ProfiledGraph, RenderGraph};
+pub use rewind::{RewindBuffer, RewindConfig, RewindHistory, RewindResource};
+pub use snapshot::SimulationSnapshot;
+pub use trails::{FadeCurve, Trails};
 pub use types::{BackgroundColorMode, ImageFitMode};
+pub use validation::{SettingValidator, ValidationError};
 pub use webcam::WebcamCapture;
 
-pub const INFINITE_RENDER_SHADER: &str = include_str!("infinite_render.wgsl");
+pub const INFINITE_RENDER_SHADER: &str = concat!(
+    include_str!("color.wgsl"),
+    include_str!("lut_blend.wgsl"),
+    include_str!("infinite_render.wgsl")
+);
 pub const AVERAGE_COLOR_SHADER: &str = include_str!("average_color.wgsl");
+pub const COLOR_SHADER: &str = include_str!("color.wgsl");
@@ -0,0 +1,295 @@
+//! Perspective camera circling a point, for simulations drawn as 3D
+//! meshes. Dragging turns it around the target and zooming moves it nearer
+//! or further, both eased in the way [`Camera`](super::camera::Camera)
+//! eases its pans.
+
+use bytemuck::{Pod, Zeroable};
+use std::f32::consts::FRAC_PI_2;
+use std::sync::Arc;
+use wgpu::{Device, Queue};
+
+/// GPU-compatible orbit camera data
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable, Debug)]
+pub struct OrbitCameraUniform {
+    /// World to clip space, column major
+    pub view_projection: [f32; 16],
+    /// Where the camera is, for lighting
+    pub eye: [f32; 3],
+    pub _pad: f32,
+}
+
+/// Where an orbit camera is looking from, as angles around its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitView {
+    /// Angle around the vertical axis, 0 looking down -z
+    pub yaw: f32,
+    /// Angle above the horizon
+    pub pitch: f32,
+    /// Distance from the target
+    pub distance: f32,
+}
+
+const FIELD_OF_VIEW: f32 = 0.8;
+const NEAR: f32 = 0.05;
+const FAR: f32 = 100.0;
+const MIN_DISTANCE: f32 = 1.2;
+const MAX_DISTANCE: f32 = 20.0;
+// Keep short of straight up or down, where the view would spin about
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.05;
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = dot(v, v).sqrt().max(f32::EPSILON);
+    [v[0] / length, v[1] / length, v[2] / length]
+}
+
+impl Default for OrbitView {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.35,
+            distance: 3.0,
+        }
+    }
+}
+
+impl OrbitView {
+    /// Camera position, around a target at the origin
+    pub fn eye(&self) -> [f32; 3] {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        [
+            self.distance * cos_pitch * sin_yaw,
+            self.distance * sin_pitch,
+            self.distance * cos_pitch * cos_yaw,
+        ]
+    }
+
+    /// Directions to the camera's right, up and forward
+    fn axes(&self) -> [[f32; 3]; 3] {
+        let forward = normalize(sub([0.0; 3], self.eye()));
+        let right = normalize(cross(forward, [0.0, 1.0, 0.0]));
+        let up = cross(right, forward);
+        [right, up, forward]
+    }
+
+    /// World to clip space for a viewport `aspect_ratio` wide per unit of
+    /// height, with depth running 0 to 1 as wgpu expects
+    pub fn view_projection(&self, aspect_ratio: f32) -> [f32; 16] {
+        let [right, up, forward] = self.axes();
+        let eye = self.eye();
+        let focal = 1.0 / (FIELD_OF_VIEW * 0.5).tan();
+        let depth_scale = FAR / (FAR - NEAR);
+        // Each row of the combined matrix is a projection row times the view
+        let rows = [
+            (right.map(|c| c * focal / aspect_ratio), 0.0),
+            (up.map(|c| c * focal), 0.0),
+            (forward.map(|c| c * depth_scale), -NEAR * depth_scale),
+            (forward, 0.0),
+        ];
+        let mut matrix = [0.0; 16];
+        for (row, (axis, offset)) in rows.iter().enumerate() {
+            for column in 0..3 {
+                matrix[column * 4 + row] = axis[column];
+            }
+            matrix[12 + row] = offset - dot(*axis, eye);
+        }
+        matrix
+    }
+
+    /// The ray from the camera through `ndc`, as its origin and unit
+    /// direction
+    pub fn ray(&self, ndc: [f32; 2], aspect_ratio: f32) -> ([f32; 3], [f32; 3]) {
+        let [right, up, forward] = self.ray_basis(aspect_ratio);
+        let direction = std::array::from_fn(|i| forward[i] + right[i] * ndc[0] + up[i] * ndc[1]);
+        (self.eye(), normalize(direction))
+    }
+
+    /// Right, up and forward scaled so that the ray through `ndc` heads
+    /// along `forward + ndc.x * right + ndc.y * up`, for shaders that cast
+    /// their own rays
+    pub fn ray_basis(&self, aspect_ratio: f32) -> [[f32; 3]; 3] {
+        let [right, up, forward] = self.axes();
+        let spread = (FIELD_OF_VIEW * 0.5).tan();
+        [
+            right.map(|c| c * spread * aspect_ratio),
+            up.map(|c| c * spread),
+            forward,
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub struct OrbitCamera {
+    /// Where the camera is now
+    pub view: OrbitView,
+    /// Where it's easing toward
+    target: OrbitView,
+    pub viewport_width: f32,
+    pub viewport_height: f32,
+    buffer: wgpu::Buffer,
+    /// Smoothing factor for camera movement (0.0 = no smoothing, 1.0 = instant)
+    smoothing_factor: f32,
+    sensitivity: f32,
+}
+
+impl OrbitCamera {
+    pub fn new(device: &Arc<Device>, viewport_width: f32, viewport_height: f32) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Orbit Camera Uniform Buffer"),
+            size: std::mem::size_of::<OrbitCameraUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            view: OrbitView::default(),
+            target: OrbitView::default(),
+            viewport_width,
+            viewport_height,
+            buffer,
+            smoothing_factor: 0.15,
+            sensitivity: 1.0,
+        }
+    }
+
+    /// Ease toward the target view (call this every frame)
+    pub fn update(&mut self, delta_time: f32) {
+        let smoothing = (self.smoothing_factor * delta_time * 60.0).min(1.0);
+        self.view.yaw += (self.target.yaw - self.view.yaw) * smoothing;
+        self.view.pitch += (self.target.pitch - self.view.pitch) * smoothing;
+        self.view.distance += (self.target.distance - self.view.distance) * smoothing;
+    }
+
+    /// Turn around the target, by pan deltas as [`Camera::pan`] gets them
+    ///
+    /// [`Camera::pan`]: super::camera::Camera::pan
+    pub fn orbit(&mut self, delta_x: f32, delta_y: f32) {
+        let speed = 0.1 * self.sensitivity;
+        self.target.yaw -= delta_x * speed;
+        self.target.pitch = (self.target.pitch - delta_y * speed).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Turn around the vertical axis at once, without easing
+    pub fn spin(&mut self, angle: f32) {
+        self.view.yaw += angle;
+        self.target.yaw += angle;
+    }
+
+    /// Move nearer for positive `delta`, further for negative
+    pub fn zoom(&mut self, delta: f32) {
+        let factor = 1.0 + delta * self.sensitivity * 0.3;
+        self.target.distance =
+            (self.target.distance / factor.max(0.1)).clamp(MIN_DISTANCE, MAX_DISTANCE);
+    }
+
+    pub fn reset(&mut self) {
+        self.view = OrbitView::default();
+        self.target = self.view;
+    }
+
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.viewport_width = width;
+        self.viewport_height = height;
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.viewport_width / self.viewport_height.max(1.0)
+    }
+
+    pub fn set_smoothing_factor(&mut self, factor: f32) {
+        self.smoothing_factor = factor.clamp(0.0, 1.0);
+    }
+
+    pub fn set_sensitivity(&mut self, sensitivity: f32) {
+        self.sensitivity = sensitivity.max(0.1);
+    }
+
+    /// The ray under a point in normalized device coordinates
+    pub fn ray(&self, ndc: [f32; 2]) -> ([f32; 3], [f32; 3]) {
+        self.view.ray(ndc, self.aspect_ratio())
+    }
+
+    pub fn upload_to_gpu(&self, queue: &Queue) {
+        let uniform = OrbitCameraUniform {
+            view_projection: self.view.view_projection(self.aspect_ratio()),
+            eye: self.view.eye(),
+            _pad: 0.0,
+        };
+        queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(&uniform));
+    }
+
+    pub fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+    }
+
+    pub fn get_state(&self) -> serde_json::Value {
+        serde_json::json!({
+            "yaw": self.view.yaw,
+            "pitch": self.view.pitch,
+            "distance": self.view.distance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(matrix: &[f32; 16], point: [f32; 3]) -> [f32; 3] {
+        let clip: [f32; 4] = std::array::from_fn(|row| {
+            (0..3).map(|c| matrix[c * 4 + row] * point[c]).sum::<f32>() + matrix[12 + row]
+        });
+        [clip[0] / clip[3], clip[1] / clip[3], clip[2] / clip[3]]
+    }
+
+    #[test]
+    fn target_lands_mid_screen_in_front() {
+        let view = OrbitView {
+            yaw: 0.7,
+            pitch: -0.3,
+            distance: 4.0,
+        };
+        let [x, y, z] = project(&view.view_projection(1.5), [0.0; 3]);
+        assert!(x.abs() < 1e-5 && y.abs() < 1e-5);
+        assert!(z > 0.0 && z < 1.0);
+    }
+
+    #[test]
+    fn rays_pass_through_what_they_point_at() {
+        let view = OrbitView::default();
+        let aspect_ratio = 16.0 / 9.0;
+        let matrix = view.view_projection(aspect_ratio);
+        for ndc in [[0.0, 0.0], [0.5, -0.25], [-0.9, 0.8]] {
+            let (origin, direction) = view.ray(ndc, aspect_ratio);
+            let point = std::array::from_fn(|i| origin[i] + direction[i] * 2.5);
+            let [x, y, _] = project(&matrix, point);
+            assert!((x - ndc[0]).abs() < 1e-4 && (y - ndc[1]).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn nearer_points_are_shallower() {
+        let view = OrbitView::default();
+        let matrix = view.view_projection(1.0);
+        let eye = view.eye();
+        let near = project(&matrix, eye.map(|c| c * 0.5));
+        let far = project(&matrix, eye.map(|c| -c));
+        assert!(near[2] < far[2]);
+    }
+}
@@ -0,0 +1,295 @@
+//! Pieces the particle simulations share, so each one only says what its
+//! particles are and how they move.
+//!
+//! [`ParticleBuffer`] holds a simulation's particles on the GPU. It makes
+//! the storage buffer every particle kernel reads and writes, copyable so
+//! rewind, snapshots and health probes can read it, counts it against the
+//! GPU memory budget, and grows or replaces it when the particle count
+//! changes. Whenever the buffer is replaced the caller is told, since any
+//! bind group holding the old one has to be made again.
+//! [`PingPongParticleBuffers`] is the same for simulations that read last
+//! step's particles while writing this step's.
+//!
+//! Particle Life, Pellets, Flow and Primordial Particles keep their
+//! particles here, as do Boids, the DLA walkers, the 3D slime mold's agents
+//! and the turmites' ants. Crowd, Stippling and Phyllotaxis move theirs on
+//! the CPU and upload them each frame. Shapes laid out afresh every frame
+//! only to be drawn, like Softbody's capsules and the harmonograph's pen
+//! segments, aren't particles and keep plain buffers.
+//!
+//! The rest of what the particle simulations have in common lives beside it:
+//! trails in [`trails`](super::trails), cursor forces in
+//! [`cursor_force`](super::cursor_force) and the infinite tiled display in
+//! `infinite_render.wgsl`. Each still has its own init and update shaders,
+//! since their particles differ in layout and in how they move.
+
+use bytemuck::Pod;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{Device, Queue};
+
+use super::gpu_budget::{self, GpuReservation};
+use super::rewind::RewindResource;
+
+#[derive(Debug)]
+pub struct ParticleBuffer<T> {
+    buffer: wgpu::Buffer,
+    label: &'static str,
+    usage: wgpu::BufferUsages,
+    memory: GpuReservation,
+    particle: PhantomData<T>,
+}
+
+impl<T: Pod> ParticleBuffer<T> {
+    /// Usage every particle buffer has, to which `extra_usage` adds, e.g.
+    /// `VERTEX` for drawing straight from it
+    const BASE_USAGE: wgpu::BufferUsages = wgpu::BufferUsages::STORAGE
+        .union(wgpu::BufferUsages::COPY_SRC)
+        .union(wgpu::BufferUsages::COPY_DST);
+
+    /// A buffer holding `particles`
+    pub fn new(
+        device: &Arc<Device>,
+        label: &'static str,
+        extra_usage: wgpu::BufferUsages,
+        particles: &[T],
+    ) -> Self {
+        let usage = Self::BASE_USAGE | extra_usage;
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(label),
+            contents: bytemuck::cast_slice(particles),
+            usage,
+        });
+        Self {
+            memory: GpuReservation::new(buffer.size()),
+            buffer,
+            label,
+            usage,
+            particle: PhantomData,
+        }
+    }
+
+    /// A buffer for `len` particles, left for a shader to fill
+    pub fn uninitialized(
+        device: &Arc<Device>,
+        label: &'static str,
+        extra_usage: wgpu::BufferUsages,
+        len: usize,
+    ) -> Self {
+        let usage = Self::BASE_USAGE | extra_usage;
+        let buffer = Self::create(device, label, usage, len);
+        Self {
+            memory: GpuReservation::new(buffer.size()),
+            buffer,
+            label,
+            usage,
+            particle: PhantomData,
+        }
+    }
+
+    fn create(device: &Device, label: &str, usage: wgpu::BufferUsages, len: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size: (len.max(1) * std::mem::size_of::<T>()) as u64,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    pub fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+    }
+
+    /// Particles the buffer has room for
+    pub fn capacity(&self) -> usize {
+        (self.buffer.size() / std::mem::size_of::<T>() as u64) as usize
+    }
+
+    pub fn rewind_resource(&self) -> RewindResource<'_> {
+        RewindResource::Buffer(&self.buffer)
+    }
+
+    /// Write `particles` from the start, growing the buffer if they don't
+    /// fit. Returns whether the buffer was replaced.
+    pub fn upload(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, particles: &[T]) -> bool {
+        let bytes: &[u8] = bytemuck::cast_slice(particles);
+        if (bytes.len() as u64) <= self.buffer.size() {
+            queue.write_buffer(&self.buffer, 0, bytes);
+            return false;
+        }
+        self.buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(self.label),
+            contents: bytes,
+            usage: self.usage,
+        });
+        self.memory = GpuReservation::new(self.buffer.size());
+        true
+    }
+
+    /// Replace the buffer with one for `len` particles, left for a shader
+    /// to fill, or for fewer if the GPU can't spare room for that many.
+    /// Returns how many it has room for; the buffer is always replaced.
+    pub fn reallocate(&mut self, device: &Arc<Device>, len: usize) -> usize {
+        let len = gpu_budget::fit_count(
+            device,
+            self.label,
+            len,
+            std::mem::size_of::<T>() as u64,
+            1,
+            &self.memory,
+        );
+        self.buffer = Self::create(device, self.label, self.usage, len);
+        self.memory = GpuReservation::new(self.buffer.size());
+        len
+    }
+}
+
+#[derive(Debug)]
+pub struct PingPongParticleBuffers<T> {
+    buffers: [ParticleBuffer<T>; 2],
+    /// Index of the buffer holding the latest particles
+    current: usize,
+}
+
+impl<T: Pod> PingPongParticleBuffers<T> {
+    /// Two buffers, labeled `labels`, both holding `particles`
+    pub fn new(
+        device: &Arc<Device>,
+        labels: [&'static str; 2],
+        extra_usage: wgpu::BufferUsages,
+        particles: &[T],
+    ) -> Self {
+        Self {
+            buffers: labels.map(|label| ParticleBuffer::new(device, label, extra_usage, particles)),
+            current: 0,
+        }
+    }
+
+    /// Two buffers, labeled `labels`, for `len` particles each, left for a
+    /// shader or an upload to fill
+    pub fn uninitialized(
+        device: &Arc<Device>,
+        labels: [&'static str; 2],
+        extra_usage: wgpu::BufferUsages,
+        len: usize,
+    ) -> Self {
+        Self {
+            buffers: labels
+                .map(|label| ParticleBuffer::uninitialized(device, label, extra_usage, len)),
+            current: 0,
+        }
+    }
+
+    /// The buffer holding the latest particles
+    pub fn current_buffer(&self) -> &wgpu::Buffer {
+        self.buffers[self.current].buffer()
+    }
+
+    /// The buffer the next step writes into
+    pub fn inactive_buffer(&self) -> &wgpu::Buffer {
+        self.buffers[1 - self.current].buffer()
+    }
+
+    pub fn swap(&mut self) {
+        self.current = 1 - self.current;
+    }
+
+    /// Of `a` and `b`, the bind group made with the buffers in the order
+    /// they have now
+    pub fn get_bind_group<'a>(
+        &self,
+        a: &'a wgpu::BindGroup,
+        b: &'a wgpu::BindGroup,
+    ) -> &'a wgpu::BindGroup {
+        if self.current == 0 { a } else { b }
+    }
+
+    pub fn rewind_resources(&self) -> [RewindResource<'_>; 2] {
+        [
+            self.buffers[0].rewind_resource(),
+            self.buffers[1].rewind_resource(),
+        ]
+    }
+
+    /// Write `particles` into both buffers, growing them if they don't fit.
+    /// Returns whether the buffers were replaced.
+    pub fn upload(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, particles: &[T]) -> bool {
+        let replaced = self.buffers[0].upload(device, queue, particles);
+        self.buffers[1].upload(device, queue, particles) || replaced
+    }
+
+    /// Replace both buffers with ones for `len` particles, as
+    /// [`ParticleBuffer::reallocate`] does, the first becoming the current
+    /// one. Returns how many both have room for.
+    pub fn reallocate(&mut self, device: &Arc<Device>, len: usize) -> usize {
+        self.current = 0;
+        let len = self.buffers[0].reallocate(device, len);
+        self.buffers[1].reallocate(device, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulations::shared::kernel_test::KernelHarness;
+
+    #[tokio::test]
+    async fn uploads_grow_the_buffer_only_when_needed() {
+        let harness = KernelHarness::new().await;
+        let (device, queue) = (&harness.device, &harness.queue);
+        let mut particles = ParticleBuffer::new(
+            device,
+            "Test Particles",
+            wgpu::BufferUsages::empty(),
+            &[1u32; 8],
+        );
+        assert_eq!(particles.buffer().size(), 32);
+
+        assert!(!particles.upload(device, queue, &[2; 4]));
+        assert_eq!(particles.buffer().size(), 32);
+        assert!(particles.upload(device, queue, &[3; 16]));
+        assert_eq!(particles.buffer().size(), 64);
+
+        let read: Vec<u32> = harness
+            .read(particles.rewind_resource())
+            .chunks_exact(4)
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+        assert_eq!(read, vec![3; 16]);
+
+        assert_eq!(particles.reallocate(device, 100), 100);
+        assert_eq!(particles.buffer().size(), 400);
+        assert_eq!(particles.capacity(), 100);
+    }
+
+    #[tokio::test]
+    async fn ping_pong_buffers_swap_and_grow_together() {
+        let harness = KernelHarness::new().await;
+        let (device, queue) = (&harness.device, &harness.queue);
+        let mut particles = PingPongParticleBuffers::new(
+            device,
+            ["Test Particles A", "Test Particles B"],
+            wgpu::BufferUsages::empty(),
+            &[1u32; 4],
+        );
+        let first = particles.current_buffer().clone();
+        particles.swap();
+        assert_ne!(particles.current_buffer(), &first);
+        assert_eq!(particles.inactive_buffer(), &first);
+
+        assert!(particles.upload(device, queue, &[2; 8]));
+        for resource in particles.rewind_resources() {
+            let read: Vec<u32> = harness
+                .read(resource)
+                .chunks_exact(4)
+                .map(bytemuck::pod_read_unaligned)
+                .collect();
+            assert_eq!(read, vec![2; 8]);
+        }
+
+        assert_eq!(particles.reallocate(device, 16), 16);
+        assert_eq!(particles.current_buffer().size(), 64);
+        assert_eq!(particles.inactive_buffer().size(), 64);
+    }
+}
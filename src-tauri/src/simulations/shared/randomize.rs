@@ -0,0 +1,275 @@
+//! Ways of randomizing settings short of rolling all of them again.
+//!
+//! A simulation's own randomizer is the source of every random value, so the
+//! ranges it keeps to still hold. [`randomized`] then decides how much of that
+//! fresh roll replaces the current settings: a subtle randomize moves each
+//! number only a little of the way toward its new value and keeps the rest,
+//! and a category limits the change to the settings the simulation files
+//! under it, leaving everything else as it was. A subtle randomize keeps the
+//! seed, since a seed moved a little grows a world as different as any other.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Most of the way a number moves toward its new value in a subtle randomize
+pub const SUBTLE_STRENGTH: f64 = 0.15;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RandomizeMode {
+    /// Small steps from the current settings
+    Subtle,
+    /// Every setting rolled again
+    #[default]
+    Wild,
+}
+
+/// What part of a simulation a setting controls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettingCategory {
+    /// How it's colored and lit
+    Colors,
+    /// How it moves and changes
+    Physics,
+    /// What it starts from: seeds, layouts, rules and initial conditions
+    Generators,
+}
+
+/// Each simulation's settings by category, as listed next to its setting
+/// rules. Settings not listed count as physics.
+#[derive(Debug, Clone, Copy)]
+pub struct SettingCategories {
+    categories: &'static [(&'static str, SettingCategory)],
+}
+
+impl SettingCategories {
+    pub const NONE: SettingCategories = SettingCategories::new(&[]);
+
+    pub const fn new(categories: &'static [(&'static str, SettingCategory)]) -> Self {
+        Self { categories }
+    }
+
+    pub fn category(&self, setting: &str) -> SettingCategory {
+        self.categories
+            .iter()
+            .find(|(name, _)| *name == setting)
+            .map_or(SettingCategory::Physics, |(_, category)| *category)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RandomizeOptions {
+    pub mode: RandomizeMode,
+    /// Only randomize the settings in this category, or all of them
+    pub category: Option<SettingCategory>,
+}
+
+impl RandomizeOptions {
+    /// The simulation's own randomize, with nothing held back
+    pub fn is_full(&self) -> bool {
+        self.mode == RandomizeMode::Wild && self.category.is_none()
+    }
+}
+
+/// `current` with the settings `options` covers taken from `rolled`, a fresh
+/// pass of the simulation's randomizer. `seed` names the simulation's seed
+/// setting, if it has one.
+pub fn randomized(
+    current: &Value,
+    rolled: &Value,
+    options: RandomizeOptions,
+    categories: &SettingCategories,
+    seed: Option<&str>,
+    rng: &mut impl Rng,
+) -> Value {
+    let (Value::Object(fields), Value::Object(rolled_fields)) = (current, rolled) else {
+        return rolled.clone();
+    };
+    // One step for every number, so settings that have to stay in order do
+    let step = rng.random_range(0.0..=SUBTLE_STRENGTH);
+    let settings = fields
+        .iter()
+        .map(|(key, value)| {
+            let covered = options
+                .category
+                .is_none_or(|category| categories.category(key) == category);
+            let value = match rolled_fields.get(key) {
+                Some(rolled_value) if covered => match options.mode {
+                    RandomizeMode::Wild => rolled_value.clone(),
+                    RandomizeMode::Subtle if seed == Some(key.as_str()) => value.clone(),
+                    RandomizeMode::Subtle => nudged(value, rolled_value, step),
+                },
+                _ => value.clone(),
+            };
+            (key.clone(), value)
+        })
+        .collect();
+    Value::Object(settings)
+}
+
+/// Numbers in `value` moved `step` of the way to `rolled`. Anything else
+/// stays, since a switch or a choice can't change only a little.
+fn nudged(value: &Value, rolled: &Value, step: f64) -> Value {
+    match (value, rolled) {
+        (Value::Object(fields), Value::Object(rolled_fields)) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, field)| {
+                    let field = match rolled_fields.get(key) {
+                        Some(rolled_field) => nudged(field, rolled_field, step),
+                        None => field.clone(),
+                    };
+                    (key.clone(), field)
+                })
+                .collect(),
+        ),
+        (Value::Array(items), Value::Array(rolled_items)) if items.len() == rolled_items.len() => {
+            Value::Array(
+                items
+                    .iter()
+                    .zip(rolled_items)
+                    .map(|(item, rolled_item)| nudged(item, rolled_item, step))
+                    .collect(),
+            )
+        }
+        (Value::Number(number), Value::Number(rolled_number)) => {
+            let (Some(from), Some(to)) = (number.as_f64(), rolled_number.as_f64()) else {
+                return value.clone();
+            };
+            let moved = from + (to - from) * step;
+            // Counts and indices have to stay whole
+            if number.is_f64() {
+                Value::from(moved)
+            } else if number.is_u64() {
+                Value::from(moved.round().max(0.0) as u64)
+            } else {
+                Value::from(moved.round() as i64)
+            }
+        }
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use serde_json::json;
+
+    const CATEGORIES: SettingCategories = SettingCategories::new(&[
+        ("color_mode", SettingCategory::Colors),
+        ("seed", SettingCategory::Generators),
+    ]);
+
+    fn current() -> Value {
+        json!({ "speed": 1.0, "count": 100, "color_mode": "Age", "seed": 1, "wrap": false })
+    }
+
+    fn rolled() -> Value {
+        json!({ "speed": 3.0, "count": 200, "color_mode": "Speed", "seed": 9, "wrap": true })
+    }
+
+    #[test]
+    fn wild_takes_the_whole_roll() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let options = RandomizeOptions::default();
+        assert!(options.is_full());
+        assert_eq!(
+            randomized(
+                &current(),
+                &rolled(),
+                options,
+                &CATEGORIES,
+                Some("seed"),
+                &mut rng
+            ),
+            rolled()
+        );
+    }
+
+    #[test]
+    fn subtle_moves_numbers_a_little_and_keeps_choices() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let options = RandomizeOptions {
+            mode: RandomizeMode::Subtle,
+            category: None,
+        };
+        for _ in 0..20 {
+            let settings = randomized(
+                &current(),
+                &rolled(),
+                options,
+                &CATEGORIES,
+                Some("seed"),
+                &mut rng,
+            );
+            let speed = settings["speed"].as_f64().unwrap();
+            assert!((1.0..=1.0 + 2.0 * SUBTLE_STRENGTH).contains(&speed));
+            let count = settings["count"].as_u64().unwrap();
+            assert!((100..=115).contains(&count));
+            assert_eq!(settings["color_mode"], json!("Age"));
+            assert_eq!(settings["seed"], json!(1));
+            assert_eq!(settings["wrap"], json!(false));
+        }
+    }
+
+    #[test]
+    fn subtle_keeps_ordered_settings_in_order() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let options = RandomizeOptions {
+            mode: RandomizeMode::Subtle,
+            category: None,
+        };
+        let current = json!({ "speed_min": 0.0, "speed_max": 1.0 });
+        let rolled = json!({ "speed_min": 10.0, "speed_max": 10.0 });
+        for _ in 0..20 {
+            let settings = randomized(
+                &current,
+                &rolled,
+                options,
+                &CATEGORIES,
+                Some("seed"),
+                &mut rng,
+            );
+            assert!(settings["speed_min"].as_f64() <= settings["speed_max"].as_f64());
+        }
+    }
+
+    #[test]
+    fn a_category_leaves_the_other_settings_alone() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let only = |category| RandomizeOptions {
+            mode: RandomizeMode::Wild,
+            category: Some(category),
+        };
+
+        let colors = randomized(
+            &current(),
+            &rolled(),
+            only(SettingCategory::Colors),
+            &CATEGORIES,
+            Some("seed"),
+            &mut rng,
+        );
+        assert_eq!(
+            colors,
+            json!({ "speed": 1.0, "count": 100, "color_mode": "Speed", "seed": 1, "wrap": false })
+        );
+
+        // Anything not listed is physics
+        let physics = randomized(
+            &current(),
+            &rolled(),
+            only(SettingCategory::Physics),
+            &CATEGORIES,
+            Some("seed"),
+            &mut rng,
+        );
+        assert_eq!(
+            physics,
+            json!({ "speed": 3.0, "count": 200, "color_mode": "Age", "seed": 1, "wrap": true })
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! A simulation's frame as an ordered list of typed passes.
+//!
+//! Instead of a hand-written run of encoders in `render_frame`, a simulation
+//! declares its passes once as variants of its own [`GraphNode`] enum,
+//! switches them on and off as its settings change, and runs the graph each
+//! frame. The graph records every enabled node into one encoder and submits
+//! it once, so passes see each other's writes in graph order as they would
+//! within any encoder. The exception is a [`NodeKind::Readback`] node, which
+//! waits on the GPU for an earlier node's result: the graph submits what came
+//! before it first, so the node can submit and read back its own work. What
+//! a node encodes stays with the simulation, which is handed each node in
+//! turn and matches on it, so a pass the graph doesn't know can't be named.
+//!
+//! While profiling, the graph puts a GPU timestamp either side of every node
+//! it runs, so any simulation on a graph reports the same per-pass timings.
+
+use serde::Serialize;
+use wgpu::{CommandEncoder, Device, Queue};
+
+use super::gpu_timer::{GpuTimer, pair_durations};
+use crate::error::SimulationResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// Steps the simulation on the GPU
+    Compute,
+    /// Draws into the simulation's own textures
+    Offscreen,
+    /// Works over an image an earlier node finished
+    Post,
+    /// Reads an earlier node's result back on the CPU, so the graph submits
+    /// what came before it first
+    Readback,
+    /// Draws onto the surface
+    Composite,
+}
+
+/// A pass a simulation's graph can hold, normally a variant of a fieldless
+/// enum listing the simulation's passes
+pub trait GraphNode: Copy + PartialEq + std::fmt::Debug {
+    /// How the pass is named in profiles and benchmark reports
+    fn name(self) -> &'static str;
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderNode<N> {
+    pub node: N,
+    pub kind: NodeKind,
+    pub enabled: bool,
+}
+
+/// GPU time a node took each frame it ran while profiling
+#[derive(Debug, Clone)]
+pub struct PassTimes {
+    pub name: &'static str,
+    pub kind: NodeKind,
+    pub durations_ms: Vec<f64>,
+}
+
+/// Profiling of a simulation's graph, whatever nodes it holds
+pub trait ProfiledGraph {
+    /// Time every node run from now on with `timer`
+    fn start_profiling(&mut self, timer: GpuTimer);
+
+    /// Read back the timestamps so far if the next frame might not fit,
+    /// which waits for the GPU. Call between frames, as timestamps are
+    /// recorded into the frame's encoder and can't be read back inside it.
+    /// Counts every node, as the simulation may switch some on while
+    /// rendering.
+    fn make_room_for_frame(&mut self, device: &Device, queue: &Queue) -> SimulationResult<()>;
+
+    /// Stop profiling and collect the timings of each node that ran, in
+    /// graph order
+    fn finish_profiling(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+    ) -> SimulationResult<Vec<PassTimes>>;
+}
+
+#[derive(Debug)]
+struct Profiler {
+    timer: GpuTimer,
+    /// Index of the node behind each pair of timestamps
+    timed_nodes: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub struct RenderGraph<N> {
+    label: &'static str,
+    nodes: Vec<RenderNode<N>>,
+    profiler: Option<Profiler>,
+}
+
+impl<N> Default for RenderGraph<N> {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl<N> RenderGraph<N> {
+    pub fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            nodes: Vec::new(),
+            profiler: None,
+        }
+    }
+}
+
+impl<N: GraphNode> RenderGraph<N> {
+    /// Add an enabled node, run after those added before it
+    pub fn with_node(mut self, node: N, kind: NodeKind) -> Self {
+        debug_assert!(
+            self.nodes.iter().all(|existing| existing.node != node),
+            "{} has {:?} twice",
+            self.label,
+            node
+        );
+        self.nodes.push(RenderNode {
+            node,
+            kind,
+            enabled: true,
+        });
+        self
+    }
+
+    pub fn set_enabled(&mut self, node: N, enabled: bool) {
+        match self.nodes.iter_mut().find(|existing| existing.node == node) {
+            Some(existing) => existing.enabled = enabled,
+            None => tracing::warn!("{} has no {:?} node", self.label, node),
+        }
+    }
+
+    /// Record the enabled nodes in order, `encode` adding each one to the
+    /// encoder it is handed, and submit them. If a node fails, what the
+    /// nodes before it recorded is still submitted.
+    pub fn execute(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        mut encode: impl FnMut(N, &mut CommandEncoder) -> SimulationResult<()>,
+    ) -> SimulationResult<()> {
+        let label = self.label;
+        let create_encoder = || {
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) })
+        };
+        let mut encoder = create_encoder();
+        let mut result = Ok(());
+        for (index, node) in self.nodes.iter().enumerate() {
+            if !node.enabled {
+                continue;
+            }
+            // Room was made before the frame, so a node is timed by both of
+            // its timestamps or neither
+            let mut profiler = self
+                .profiler
+                .as_mut()
+                .filter(|profiler| profiler.timer.remaining() >= 2);
+            if let Some(profiler) = &mut profiler {
+                profiler.timer.encode_mark(&mut encoder);
+            }
+            if node.kind == NodeKind::Readback {
+                let earlier = std::mem::replace(&mut encoder, create_encoder());
+                queue.submit(std::iter::once(earlier.finish()));
+            }
+            result = encode(node.node, &mut encoder);
+            if let Some(profiler) = profiler {
+                profiler.timer.encode_mark(&mut encoder);
+                profiler.timed_nodes.push(index);
+            }
+            if result.is_err() {
+                break;
+            }
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+        result
+    }
+}
+
+impl<N: GraphNode> ProfiledGraph for RenderGraph<N> {
+    fn start_profiling(&mut self, timer: GpuTimer) {
+        self.profiler = Some(Profiler {
+            timer,
+            timed_nodes: Vec::new(),
+        });
+    }
+
+    fn make_room_for_frame(&mut self, device: &Device, queue: &Queue) -> SimulationResult<()> {
+        let needed = 2 * self.nodes.len() as u32;
+        match &mut self.profiler {
+            Some(profiler) if profiler.timer.remaining() < needed => {
+                profiler.timer.read_back(device, queue)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn finish_profiling(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+    ) -> SimulationResult<Vec<PassTimes>> {
+        let Some(profiler) = self.profiler.take() else {
+            return Ok(Vec::new());
+        };
+        let durations = pair_durations(&profiler.timer.finish(device, queue)?);
+        Ok(collect_pass_times(
+            &self.nodes,
+            &profiler.timed_nodes,
+            &durations,
+        ))
+    }
+}
+
+fn collect_pass_times<N: GraphNode>(
+    nodes: &[RenderNode<N>],
+    timed_nodes: &[usize],
+    durations: &[f64],
+) -> Vec<PassTimes> {
+    let mut passes: Vec<PassTimes> = nodes
+        .iter()
+        .map(|node| PassTimes {
+            name: node.node.name(),
+            kind: node.kind,
+            durations_ms: Vec::new(),
+        })
+        .collect();
+    for (index, duration) in timed_nodes.iter().zip(durations) {
+        passes[*index].durations_ms.push(*duration);
+    }
+    passes.retain(|pass| !pass.durations_ms.is_empty());
+    passes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum TestNode {
+        Physics,
+        Trails,
+        Blur,
+        InfiniteRender,
+    }
+
+    impl GraphNode for TestNode {
+        fn name(self) -> &'static str {
+            match self {
+                TestNode::Physics => "physics",
+                TestNode::Trails => "trails",
+                TestNode::Blur => "blur",
+                TestNode::InfiniteRender => "infinite_render",
+            }
+        }
+    }
+
+    #[test]
+    fn disabled_nodes_are_skipped_in_order() {
+        let mut graph = RenderGraph::new("Test Graph")
+            .with_node(TestNode::Physics, NodeKind::Compute)
+            .with_node(TestNode::Trails, NodeKind::Offscreen)
+            .with_node(TestNode::Blur, NodeKind::Post)
+            .with_node(TestNode::InfiniteRender, NodeKind::Composite);
+        graph.set_enabled(TestNode::Trails, false);
+        let enabled = |graph: &RenderGraph<TestNode>| -> Vec<TestNode> {
+            graph
+                .nodes
+                .iter()
+                .filter(|node| node.enabled)
+                .map(|node| node.node)
+                .collect()
+        };
+        assert_eq!(
+            enabled(&graph),
+            [TestNode::Physics, TestNode::Blur, TestNode::InfiniteRender]
+        );
+
+        graph.set_enabled(TestNode::Trails, true);
+        assert_eq!(enabled(&graph).len(), 4);
+    }
+
+    #[test]
+    fn timings_are_grouped_by_node_in_graph_order() {
+        let graph = RenderGraph::new("Test Graph")
+            .with_node(TestNode::Physics, NodeKind::Compute)
+            .with_node(TestNode::Trails, NodeKind::Offscreen)
+            .with_node(TestNode::InfiniteRender, NodeKind::Composite);
+        // Two frames, the second without trails
+        let passes = collect_pass_times(&graph.nodes, &[0, 1, 2, 0, 2], &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let times: Vec<_> = passes
+            .iter()
+            .map(|pass| (pass.name, pass.durations_ms.clone()))
+            .collect();
+        assert_eq!(
+            times,
+            [
+                ("physics", vec![1.0, 4.0]),
+                ("trails", vec![2.0]),
+                ("infinite_render", vec![3.0, 5.0]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! GPU-side history of simulation state for rewinding.
+//!
+//! Every snapshot interval the buffers and textures a simulation reports
+//! through [`Simulation::rewind_resources`](crate::simulations::traits::Simulation::rewind_resources)
+//! are copied into textures and buffers owned by the [`RewindBuffer`]. The
+//! copies never leave VRAM, so recording costs a copy command per resource and
+//! no readback. Only the last `duration_seconds` of snapshots are kept, the
+//! oldest allocations are reused for new snapshots once the buffer is full.
+//!
+//! Restoring a snapshot copies it back over the live resources. While paused,
+//! the history can be scrubbed back and forth freely; once the simulation
+//! steps again, snapshots newer than the restored one are discarded.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use wgpu::{Device, Queue};
+
+use crate::commands::app_settings::AppSettings;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::gpu_budget::{self, GpuMemoryLimit, GpuReservation};
+
+/// A piece of live simulation state that should be captured in snapshots.
+///
+/// Sources must have `COPY_SRC` and `COPY_DST` usage.
+#[derive(Debug, Clone, Copy)]
+pub enum RewindResource<'a> {
+    Buffer(&'a wgpu::Buffer),
+    Texture(&'a wgpu::Texture),
+}
+
+impl RewindResource<'_> {
+    fn size_bytes(self) -> u64 {
+        match self {
+            RewindResource::Buffer(buffer) => buffer.size(),
+            RewindResource::Texture(texture) => {
+                let size = texture.size();
+                let bytes_per_texel = texture.format().block_copy_size(None).unwrap_or(4) as u64;
+                size.width as u64
+                    * size.height as u64
+                    * size.depth_or_array_layers as u64
+                    * bytes_per_texel
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum SnapshotCopy {
+    Buffer(wgpu::Buffer),
+    Texture(wgpu::Texture),
+}
+
+impl SnapshotCopy {
+    fn new(device: &Device, resource: RewindResource) -> Self {
+        match resource {
+            RewindResource::Buffer(buffer) => {
+                SnapshotCopy::Buffer(device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Rewind Snapshot Buffer"),
+                    size: buffer.size(),
+                    usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }))
+            }
+            RewindResource::Texture(texture) => {
+                SnapshotCopy::Texture(device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Rewind Snapshot Texture"),
+                    size: texture.size(),
+                    mip_level_count: texture.mip_level_count(),
+                    sample_count: texture.sample_count(),
+                    dimension: texture.dimension(),
+                    format: texture.format(),
+                    usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                }))
+            }
+        }
+    }
+
+    /// Whether this copy can hold the contents of `resource`
+    fn matches(&self, resource: RewindResource) -> bool {
+        match (self, resource) {
+            (SnapshotCopy::Buffer(copy), RewindResource::Buffer(buffer)) => {
+                copy.size() == buffer.size()
+            }
+            (SnapshotCopy::Texture(copy), RewindResource::Texture(texture)) => {
+                copy.size() == texture.size()
+                    && copy.format() == texture.format()
+                    && copy.mip_level_count() == texture.mip_level_count()
+            }
+            _ => false,
+        }
+    }
+
+    /// Record a copy from `from` into `to`, which must be the same kind and size
+    fn encode_copy(encoder: &mut wgpu::CommandEncoder, from: RewindResource, to: RewindResource) {
+        match (from, to) {
+            (RewindResource::Buffer(from), RewindResource::Buffer(to)) => {
+                encoder.copy_buffer_to_buffer(from, 0, to, 0, from.size());
+            }
+            (RewindResource::Texture(from), RewindResource::Texture(to)) => {
+                for mip_level in 0..from.mip_level_count() {
+                    encoder.copy_texture_to_texture(
+                        wgpu::TexelCopyTextureInfo {
+                            texture: from,
+                            mip_level,
+                            origin: wgpu::Origin3d::ZERO,
+                            aspect: wgpu::TextureAspect::All,
+                        },
+                        wgpu::TexelCopyTextureInfo {
+                            texture: to,
+                            mip_level,
+                            origin: wgpu::Origin3d::ZERO,
+                            aspect: wgpu::TextureAspect::All,
+                        },
+                        from.size().mip_level_size(mip_level, from.dimension()),
+                    );
+                }
+            }
+            _ => unreachable!("snapshot copies are created to match their source"),
+        }
+    }
+
+    fn as_resource(&self) -> RewindResource<'_> {
+        match self {
+            SnapshotCopy::Buffer(buffer) => RewindResource::Buffer(buffer),
+            SnapshotCopy::Texture(texture) => RewindResource::Texture(texture),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    /// Simulated seconds since the history was cleared
+    time: f64,
+    copies: Vec<SnapshotCopy>,
+    memory: GpuReservation,
+}
+
+impl Snapshot {
+    fn matches(&self, resources: &[RewindResource]) -> bool {
+        self.copies.len() == resources.len()
+            && self
+                .copies
+                .iter()
+                .zip(resources)
+                .all(|(copy, resource)| copy.matches(*resource))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RewindConfig {
+    pub enabled: bool,
+    pub duration_seconds: f32,
+    pub snapshot_interval_seconds: f32,
+}
+
+impl RewindConfig {
+    pub fn from_app_settings(app_settings: &AppSettings) -> Self {
+        Self {
+            enabled: app_settings.rewind_enabled,
+            duration_seconds: app_settings.rewind_duration_seconds,
+            snapshot_interval_seconds: app_settings.rewind_snapshot_interval_seconds,
+        }
+    }
+
+    /// Number of snapshots needed to cover the configured duration
+    pub fn capacity(&self) -> usize {
+        let interval = self.snapshot_interval_seconds.max(MIN_SNAPSHOT_INTERVAL) as f64;
+        ((self.duration_seconds.max(0.0) as f64 / interval).ceil() as usize).max(1)
+    }
+}
+
+/// Snapshotting more often than this would copy every frame at high frame rates
+const MIN_SNAPSHOT_INTERVAL: f32 = 0.05;
+
+/// Summary of the recorded history for the frontend's scrub bar
+#[derive(Debug, Clone, Serialize)]
+pub struct RewindHistory {
+    pub enabled: bool,
+    /// Seconds before the present of each snapshot, oldest first
+    pub snapshot_ages: Vec<f32>,
+    /// Index of the snapshot currently shown, if the history is being scrubbed
+    pub cursor: Option<usize>,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug)]
+pub struct RewindBuffer {
+    config: RewindConfig,
+    snapshots: VecDeque<Snapshot>,
+    elapsed: f64,
+    since_last_snapshot: f64,
+    cursor: Option<usize>,
+    // Set once the memory budget stopped the history from growing
+    budget_limited: bool,
+}
+
+impl RewindBuffer {
+    pub fn new(config: RewindConfig) -> Self {
+        Self {
+            config,
+            snapshots: VecDeque::new(),
+            elapsed: 0.0,
+            since_last_snapshot: 0.0,
+            cursor: None,
+            budget_limited: false,
+        }
+    }
+
+    pub fn set_config(&mut self, config: RewindConfig) {
+        self.config = config;
+        self.budget_limited = false;
+        if !config.enabled {
+            self.clear();
+            return;
+        }
+        let excess = self.snapshots.len().saturating_sub(config.capacity());
+        self.snapshots.drain(..excess);
+        self.cursor = self.cursor.map(|cursor| cursor.saturating_sub(excess));
+    }
+
+    /// Drop all snapshots, e.g. when the simulation is restarted
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.budget_limited = false;
+        self.elapsed = 0.0;
+        self.since_last_snapshot = 0.0;
+        self.cursor = None;
+    }
+
+    /// Advance the clock by one simulated frame and report whether a snapshot
+    /// should be taken
+    pub fn tick(&mut self, delta_time: f32) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        // Stepping after a scrub branches off a new timeline
+        if let Some(cursor) = self.cursor.take() {
+            self.snapshots.truncate(cursor + 1);
+        }
+        self.elapsed += delta_time.max(0.0) as f64;
+        self.since_last_snapshot += delta_time.max(0.0) as f64;
+
+        let due = self.snapshots.is_empty()
+            || self.since_last_snapshot
+                >= self
+                    .config
+                    .snapshot_interval_seconds
+                    .max(MIN_SNAPSHOT_INTERVAL) as f64;
+        if due {
+            self.since_last_snapshot = 0.0;
+        }
+        due
+    }
+
+    /// Copy the given resources into a new snapshot
+    pub fn record(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        resources: &[RewindResource],
+    ) {
+        if !self.config.enabled || resources.is_empty() {
+            return;
+        }
+
+        // Buffers were reallocated (resize, particle count change), history no longer applies
+        if self
+            .snapshots
+            .back()
+            .is_some_and(|snapshot| !snapshot.matches(resources))
+        {
+            tracing::debug!("Simulation resources changed, clearing rewind history");
+            self.snapshots.clear();
+        }
+
+        // Keep fewer snapshots than configured rather than run out of GPU memory
+        let snapshot_bytes: u64 = resources.iter().map(|resource| resource.size_bytes()).sum();
+        let full = self.snapshots.len() >= self.config.capacity();
+        if !full
+            && !self.budget_limited
+            && !self.snapshots.is_empty()
+            && !gpu_budget::fits(snapshot_bytes)
+        {
+            self.budget_limited = true;
+            gpu_budget::warn(
+                "Rewind history",
+                GpuMemoryLimit::Budget,
+                self.config.capacity() as u64,
+                self.snapshots.len() as u64,
+                "snapshots",
+            );
+        }
+        let reuse_oldest = full || self.budget_limited;
+
+        let mut snapshot = if reuse_oldest {
+            self.snapshots.pop_front()
+        } else {
+            None
+        }
+        .filter(|snapshot| snapshot.matches(resources))
+        .unwrap_or_else(|| Snapshot {
+            time: 0.0,
+            copies: resources
+                .iter()
+                .map(|resource| SnapshotCopy::new(device, *resource))
+                .collect(),
+            memory: GpuReservation::new(snapshot_bytes),
+        });
+        snapshot.time = self.elapsed;
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Rewind Snapshot Encoder"),
+        });
+        for (resource, copy) in resources.iter().zip(&snapshot.copies) {
+            SnapshotCopy::encode_copy(&mut encoder, *resource, copy.as_resource());
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Restore the snapshot closest to `seconds` before the one currently
+    /// shown, returning its index
+    pub fn rewind(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        seconds: f32,
+        resources: &[RewindResource],
+    ) -> SimulationResult<usize> {
+        let now = self.elapsed;
+        let times: Vec<f64> = self
+            .snapshots
+            .iter()
+            .map(|snapshot| snapshot.time)
+            .collect();
+        let index = snapshot_at_or_before(&times, now - seconds.max(0.0) as f64)
+            .ok_or_else(|| SimulationError::InvalidParameter("No rewind history".to_string()))?;
+        self.restore(device, queue, index, resources)?;
+        Ok(index)
+    }
+
+    /// Copy snapshot `index` (0 is the oldest) back over the live resources
+    pub fn restore(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        index: usize,
+        resources: &[RewindResource],
+    ) -> SimulationResult<()> {
+        let snapshot = self.snapshots.get(index).ok_or_else(|| {
+            SimulationError::InvalidParameter(format!(
+                "Rewind snapshot {} does not exist ({} recorded)",
+                index,
+                self.snapshots.len()
+            ))
+        })?;
+
+        if !snapshot.matches(resources) {
+            self.clear();
+            return Err(SimulationError::InvalidParameter(
+                "Simulation resources changed since the snapshot was taken".to_string(),
+            ));
+        }
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Rewind Restore Encoder"),
+        });
+        for (copy, resource) in snapshot.copies.iter().zip(resources) {
+            SnapshotCopy::encode_copy(&mut encoder, copy.as_resource(), *resource);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+
+        self.elapsed = snapshot.time;
+        self.since_last_snapshot = 0.0;
+        self.cursor = Some(index);
+        Ok(())
+    }
+
+    pub fn history(&self) -> RewindHistory {
+        let now = self
+            .snapshots
+            .back()
+            .map_or(self.elapsed, |snapshot| snapshot.time.max(self.elapsed));
+        RewindHistory {
+            enabled: self.config.enabled,
+            snapshot_ages: self
+                .snapshots
+                .iter()
+                .map(|snapshot| (now - snapshot.time) as f32)
+                .collect(),
+            cursor: self.cursor,
+            memory_bytes: self
+                .snapshots
+                .iter()
+                .map(|snapshot| snapshot.memory.bytes())
+                .sum(),
+        }
+    }
+}
+
+/// Index of the latest time not after `target`, or the oldest if all are later
+fn snapshot_at_or_before(times: &[f64], target: f64) -> Option<usize> {
+    if times.is_empty() {
+        return None;
+    }
+    Some(times.iter().rposition(|&time| time <= target).unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(duration_seconds: f32, snapshot_interval_seconds: f32) -> RewindConfig {
+        RewindConfig {
+            enabled: true,
+            duration_seconds,
+            snapshot_interval_seconds,
+        }
+    }
+
+    #[test]
+    fn capacity_covers_duration() {
+        assert_eq!(config(10.0, 0.5).capacity(), 20);
+        assert_eq!(config(10.0, 3.0).capacity(), 4);
+        assert_eq!(config(0.0, 1.0).capacity(), 1);
+        // Intervals are clamped so a zero interval can't ask for unbounded memory
+        assert_eq!(config(1.0, 0.0).capacity(), 20);
+    }
+
+    #[test]
+    fn snapshots_are_due_every_interval() {
+        let mut rewind = RewindBuffer::new(config(10.0, 1.0));
+        // The first frame always snapshots so there is something to go back to
+        assert!(rewind.tick(0.016));
+        rewind.snapshots.push_back(Snapshot {
+            time: rewind.elapsed,
+            copies: Vec::new(),
+            memory: GpuReservation::default(),
+        });
+        assert!(!rewind.tick(0.5));
+        assert!(!rewind.tick(0.4));
+        assert!(rewind.tick(0.2));
+        assert!(!rewind.tick(0.2));
+    }
+
+    #[test]
+    fn disabled_buffer_never_snapshots() {
+        let mut rewind = RewindBuffer::new(RewindConfig {
+            enabled: false,
+            ..config(10.0, 1.0)
+        });
+        assert!(!rewind.tick(5.0));
+        assert_eq!(rewind.elapsed, 0.0);
+    }
+
+    #[test]
+    fn rewind_picks_latest_snapshot_before_target() {
+        let times = [0.0, 1.0, 2.0, 3.0];
+        assert_eq!(snapshot_at_or_before(&times, 2.5), Some(2));
+        assert_eq!(snapshot_at_or_before(&times, 3.0), Some(3));
+        assert_eq!(snapshot_at_or_before(&times, -4.0), Some(0));
+        assert_eq!(snapshot_at_or_before(&[], 1.0), None);
+    }
+
+    #[test]
+    fn shrinking_duration_drops_oldest() {
+        let mut rewind = RewindBuffer::new(config(10.0, 1.0));
+        for time in 0..10 {
+            rewind.snapshots.push_back(Snapshot {
+                time: time as f64,
+                copies: Vec::new(),
+                memory: GpuReservation::default(),
+            });
+        }
+        rewind.cursor = Some(8);
+        rewind.set_config(config(3.0, 1.0));
+        let times: Vec<f64> = rewind.snapshots.iter().map(|s| s.time).collect();
+        assert_eq!(times, vec![7.0, 8.0, 9.0]);
+        assert_eq!(rewind.cursor, Some(1));
+
+        rewind.set_config(RewindConfig {
+            enabled: false,
+            ..config(3.0, 1.0)
+        });
+        assert!(rewind.snapshots.is_empty());
+    }
+}
@@ -0,0 +1,449 @@
+//! Exact copies of simulation state, read back from the GPU.
+//!
+//! Where [`rewind`](super::rewind) keeps its snapshots in VRAM, a
+//! [`SimulationSnapshot`] brings the same resources, the ones a simulation
+//! reports through
+//! [`Simulation::rewind_resources`](crate::simulations::traits::Simulation::rewind_resources),
+//! back to the CPU so they can be saved and loaded in a later session. Each
+//! resource's bytes are deflated and base64 encoded, so a snapshot is plain
+//! JSON.
+//!
+//! Loading writes the bytes straight over the live resources, which only
+//! works if they are laid out the same way: the settings are applied first so
+//! buffers sized by a count are remade to match, but a texture sized by the
+//! window won't fit after the window has changed size.
+
+use base64::Engine;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::sync::Arc;
+use wgpu::{Device, Queue};
+
+use super::rewind::RewindResource;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulation::settings_codec::CameraView;
+
+/// Bump when snapshots stop being readable by older builds
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The running simulation's settings and live state at one moment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationSnapshot {
+    pub version: u32,
+    pub simulation_type: String,
+    /// Full settings, as returned by the simulation's `get_settings`
+    pub settings: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraView>,
+    /// In the order the simulation lists its rewind resources
+    pub resources: Vec<ResourceContents>,
+}
+
+/// The contents of one buffer or texture, deflated and base64 encoded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ResourceContents {
+    Buffer {
+        size: u64,
+        data: String,
+    },
+    /// Every mip level, each packed row after row without padding
+    Texture {
+        width: u32,
+        height: u32,
+        depth_or_array_layers: u32,
+        mip_level_count: u32,
+        format: String,
+        data: String,
+    },
+}
+
+impl ResourceContents {
+    fn describe(resource: RewindResource<'_>, data: String) -> Self {
+        match resource {
+            RewindResource::Buffer(buffer) => ResourceContents::Buffer {
+                size: buffer.size(),
+                data,
+            },
+            RewindResource::Texture(texture) => {
+                let size = texture.size();
+                ResourceContents::Texture {
+                    width: size.width,
+                    height: size.height,
+                    depth_or_array_layers: size.depth_or_array_layers,
+                    mip_level_count: texture.mip_level_count(),
+                    format: format!("{:?}", texture.format()),
+                    data,
+                }
+            }
+        }
+    }
+
+    fn data(&self) -> &str {
+        match self {
+            ResourceContents::Buffer { data, .. } | ResourceContents::Texture { data, .. } => data,
+        }
+    }
+
+    /// Whether these contents were read from a resource laid out like
+    /// `resource`
+    fn fits(&self, resource: RewindResource<'_>) -> bool {
+        // Compare everything but the data itself
+        let live = Self::describe(resource, String::new());
+        match (self, &live) {
+            (
+                ResourceContents::Buffer { size, .. },
+                ResourceContents::Buffer { size: live, .. },
+            ) => size == live,
+            (
+                ResourceContents::Texture {
+                    width,
+                    height,
+                    depth_or_array_layers,
+                    mip_level_count,
+                    format,
+                    ..
+                },
+                ResourceContents::Texture {
+                    width: live_width,
+                    height: live_height,
+                    depth_or_array_layers: live_layers,
+                    mip_level_count: live_mips,
+                    format: live_format,
+                    ..
+                },
+            ) => {
+                (
+                    width,
+                    height,
+                    depth_or_array_layers,
+                    mip_level_count,
+                    format,
+                ) == (live_width, live_height, live_layers, live_mips, live_format)
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn encode_bytes(bytes: &[u8]) -> SimulationResult<String> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+    encoder
+        .write_all(bytes)
+        .and_then(|_| encoder.finish())
+        .map(|compressed| base64::engine::general_purpose::STANDARD.encode(compressed))
+        .map_err(|e| SimulationError::Gpu(Box::new(e)))
+}
+
+/// The bytes `data` encodes, which must come to exactly `expected` of them
+pub fn decode_bytes(data: &str, expected: u64) -> SimulationResult<Vec<u8>> {
+    let invalid = |message: String| SimulationError::InvalidParameter(message);
+    let compressed = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| invalid(format!("Invalid snapshot encoding: {}", e)))?;
+    let mut bytes = Vec::new();
+    // One byte over is enough to tell the data is too long
+    DeflateDecoder::new(compressed.as_slice())
+        .take(expected + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| invalid(format!("Invalid snapshot data: {}", e)))?;
+    if bytes.len() as u64 != expected {
+        return Err(invalid(format!(
+            "Snapshot data holds {} bytes where {} were expected",
+            bytes.len(),
+            expected
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Texel size and row layout of each mip level of `texture`
+struct MipLayout {
+    extent: wgpu::Extent3d,
+    row_bytes: u32,
+    padded_row_bytes: u32,
+    rows: u32,
+}
+
+impl MipLayout {
+    fn of(texture: &wgpu::Texture) -> Vec<Self> {
+        let bytes_per_texel = texture.format().block_copy_size(None).unwrap_or(4);
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        (0..texture.mip_level_count())
+            .map(|mip_level| {
+                let extent = texture
+                    .size()
+                    .mip_level_size(mip_level, texture.dimension());
+                let row_bytes = extent.width * bytes_per_texel;
+                Self {
+                    extent,
+                    row_bytes,
+                    padded_row_bytes: row_bytes.div_ceil(align) * align,
+                    rows: extent.height * extent.depth_or_array_layers,
+                }
+            })
+            .collect()
+    }
+
+    fn packed_size(&self) -> u64 {
+        self.row_bytes as u64 * self.rows as u64
+    }
+
+    fn padded_size(&self) -> u64 {
+        self.padded_row_bytes as u64 * self.rows as u64
+    }
+}
+
+/// `padded` with the padding at the end of each `padded_row_bytes` row cut
+/// back to `row_bytes`
+pub fn unpad_rows(padded: &[u8], row_bytes: usize, padded_row_bytes: usize) -> Vec<u8> {
+    padded
+        .chunks(padded_row_bytes)
+        .flat_map(|row| &row[..row_bytes.min(row.len())])
+        .copied()
+        .collect()
+}
+
+/// Bytes a buffer or texture's contents take packed without row padding
+fn packed_size(resource: RewindResource<'_>) -> u64 {
+    match resource {
+        RewindResource::Buffer(buffer) => buffer.size(),
+        RewindResource::Texture(texture) => MipLayout::of(texture)
+            .iter()
+            .map(MipLayout::packed_size)
+            .sum(),
+    }
+}
+
+/// Copy `resources` back from the GPU and encode them
+pub fn read_resources(
+    device: &Arc<Device>,
+    queue: &Arc<Queue>,
+    resources: &[RewindResource<'_>],
+) -> SimulationResult<Vec<ResourceContents>> {
+    resources
+        .iter()
+        .map(|&resource| {
+            let bytes = read_resource(device, queue, resource)?;
+            Ok(ResourceContents::describe(resource, encode_bytes(&bytes)?))
+        })
+        .collect()
+}
+
+/// The packed bytes of one buffer or texture, as [`read_resources`] encodes
+/// them
+pub fn read_resource(
+    device: &Arc<Device>,
+    queue: &Arc<Queue>,
+    resource: RewindResource<'_>,
+) -> SimulationResult<Vec<u8>> {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Snapshot Readback Encoder"),
+    });
+    let (staging_size, layouts) = match resource {
+        RewindResource::Buffer(buffer) => (buffer.size(), Vec::new()),
+        RewindResource::Texture(texture) => {
+            let layouts = MipLayout::of(texture);
+            (layouts.iter().map(MipLayout::padded_size).sum(), layouts)
+        }
+    };
+    let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Snapshot Staging Buffer"),
+        size: staging_size.max(wgpu::COPY_BUFFER_ALIGNMENT),
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    match resource {
+        RewindResource::Buffer(buffer) => {
+            encoder.copy_buffer_to_buffer(buffer, 0, &staging_buffer, 0, buffer.size());
+        }
+        RewindResource::Texture(texture) => {
+            let mut offset = 0;
+            for (mip_level, layout) in layouts.iter().enumerate() {
+                encoder.copy_texture_to_buffer(
+                    wgpu::TexelCopyTextureInfo {
+                        texture,
+                        mip_level: mip_level as u32,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    wgpu::TexelCopyBufferInfo {
+                        buffer: &staging_buffer,
+                        layout: wgpu::TexelCopyBufferLayout {
+                            offset,
+                            bytes_per_row: Some(layout.padded_row_bytes),
+                            rows_per_image: Some(layout.extent.height),
+                        },
+                    },
+                    layout.extent,
+                );
+                offset += layout.padded_size();
+            }
+        }
+    }
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let buffer_slice = staging_buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+    device
+        .poll(wgpu::wgt::PollType::Wait)
+        .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+    receiver
+        .recv()
+        .map_err(|e| SimulationError::Gpu(Box::new(e)))?
+        .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+
+    let bytes = {
+        let data = buffer_slice.get_mapped_range();
+        match resource {
+            RewindResource::Buffer(buffer) => data[..buffer.size() as usize].to_vec(),
+            RewindResource::Texture(_) => {
+                let mut bytes = Vec::new();
+                let mut offset = 0;
+                for layout in &layouts {
+                    let end = offset + layout.padded_size() as usize;
+                    bytes.extend(unpad_rows(
+                        &data[offset..end],
+                        layout.row_bytes as usize,
+                        layout.padded_row_bytes as usize,
+                    ));
+                    offset = end;
+                }
+                bytes
+            }
+        }
+    };
+    staging_buffer.unmap();
+    Ok(bytes)
+}
+
+/// Write `contents` over the live `resources`, checking first that every one
+/// of them fits
+pub fn write_resources(
+    queue: &Arc<Queue>,
+    resources: &[RewindResource<'_>],
+    contents: &[ResourceContents],
+) -> SimulationResult<()> {
+    if resources.len() != contents.len() {
+        return Err(SimulationError::InvalidParameter(format!(
+            "Snapshot holds {} resources but the simulation has {}",
+            contents.len(),
+            resources.len()
+        )));
+    }
+    if let Some(index) = resources
+        .iter()
+        .zip(contents)
+        .position(|(resource, contents)| !contents.fits(*resource))
+    {
+        return Err(SimulationError::InvalidParameter(format!(
+            "Snapshot resource {} no longer matches the simulation's, which has changed size or format since",
+            index
+        )));
+    }
+
+    // Decode everything before writing anything, so bad data changes nothing
+    let decoded = resources
+        .iter()
+        .zip(contents)
+        .map(|(resource, contents)| decode_bytes(contents.data(), packed_size(*resource)))
+        .collect::<SimulationResult<Vec<_>>>()?;
+
+    for (resource, bytes) in resources.iter().zip(decoded) {
+        match *resource {
+            RewindResource::Buffer(buffer) => queue.write_buffer(buffer, 0, &bytes),
+            RewindResource::Texture(texture) => {
+                let mut offset = 0;
+                for (mip_level, layout) in MipLayout::of(texture).iter().enumerate() {
+                    let end = offset + layout.packed_size() as usize;
+                    queue.write_texture(
+                        wgpu::TexelCopyTextureInfo {
+                            texture,
+                            mip_level: mip_level as u32,
+                            origin: wgpu::Origin3d::ZERO,
+                            aspect: wgpu::TextureAspect::All,
+                        },
+                        &bytes[offset..end],
+                        wgpu::TexelCopyBufferLayout {
+                            offset: 0,
+                            bytes_per_row: Some(layout.row_bytes),
+                            rows_per_image: Some(layout.extent.height),
+                        },
+                        layout.extent,
+                    );
+                    offset = end;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_survive_encoding() {
+        let bytes: Vec<u8> = (0..10_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let encoded = encode_bytes(&bytes).unwrap();
+        assert_eq!(decode_bytes(&encoded, bytes.len() as u64).unwrap(), bytes);
+        assert_eq!(
+            decode_bytes(&encode_bytes(&[]).unwrap(), 0).unwrap(),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[test]
+    fn decoding_rejects_the_wrong_length() {
+        let encoded = encode_bytes(&[1, 2, 3, 4]).unwrap();
+        assert!(decode_bytes(&encoded, 3).is_err());
+        assert!(decode_bytes(&encoded, 5).is_err());
+        assert!(decode_bytes("not base64!", 4).is_err());
+    }
+
+    #[test]
+    fn row_padding_is_cut_away() {
+        let padded = [1, 2, 3, 0, 0, 4, 5, 6, 0, 0];
+        assert_eq!(unpad_rows(&padded, 3, 5), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(unpad_rows(&padded, 5, 5), padded.to_vec());
+    }
+
+    #[test]
+    fn snapshots_round_trip_through_json() {
+        let snapshot = SimulationSnapshot {
+            version: SNAPSHOT_VERSION,
+            simulation_type: "gray_scott".to_string(),
+            settings: serde_json::json!({ "feed_rate": 0.055 }),
+            camera: Some(CameraView {
+                position: [0.25, -0.5],
+                zoom: 2.0,
+            }),
+            resources: vec![
+                ResourceContents::Buffer {
+                    size: 4,
+                    data: encode_bytes(&[1, 2, 3, 4]).unwrap(),
+                },
+                ResourceContents::Texture {
+                    width: 2,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                    mip_level_count: 1,
+                    format: "Rgba8Unorm".to_string(),
+                    data: encode_bytes(&[0; 8]).unwrap(),
+                },
+            ],
+        };
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["resources"][0]["kind"], "Buffer");
+        assert_eq!(json["resources"][1]["kind"], "Texture");
+        let restored: SimulationSnapshot = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.resources, snapshot.resources);
+        assert_eq!(restored.camera, snapshot.camera);
+    }
+}
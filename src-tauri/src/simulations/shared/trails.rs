@@ -0,0 +1,408 @@
+//! Trails left behind by the particle simulations, the boids and the
+//! harmonograph's pen.
+//!
+//! Each frame fades what the trail texture held the frame before into the
+//! other texture of a ping-pong pair, the simulation draws its particles
+//! over that, and the result is blitted onto its display texture or
+//! sampled directly. Over an opaque background the fade moves the color
+//! itself toward the background by the alpha taken off. How
+//! quickly trails go is a persistence from 0 (gone the next frame) to 1
+//! (never), shaped by a [`FadeCurve`]. The textures can be kept at a
+//! different resolution than the display, finer for smoother trails or
+//! coarser for cheaper ones. The WGSL side lives in `trails.wgsl`.
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use wgpu::util::DeviceExt;
+use wgpu::{CommandEncoder, Device, Queue, TextureView};
+
+use super::gpu_budget::GpuReservation;
+use super::gpu_utils::resource_helpers;
+use super::ping_pong_render_textures::PingPongRenderTextures;
+
+/// Most alpha a trail loses in a frame, at persistence 0
+pub const DEFAULT_MAX_FADE: f32 = 0.1;
+
+pub const MIN_RESOLUTION_SCALE: f32 = 0.25;
+pub const MAX_RESOLUTION_SCALE: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FadeCurve {
+    /// The same alpha off every frame, so trails end after a fixed length
+    #[default]
+    Linear,
+    /// A share of what's left off every frame, so trails thin out slowly
+    Exponential,
+    /// Trails stay until they are cleared
+    None,
+}
+
+impl FadeCurve {
+    /// Id used by `trails.wgsl`
+    pub fn gpu_id(self) -> u32 {
+        match self {
+            FadeCurve::Linear => 0,
+            FadeCurve::Exponential => 1,
+            FadeCurve::None => 2,
+        }
+    }
+}
+
+/// Alpha taken off each frame for `persistence`, 1 keeping trails forever
+pub fn fade_amount(persistence: f32, max_fade: f32) -> f32 {
+    (1.0 - persistence).clamp(0.0, 1.0) * max_fade
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct FadeUniforms {
+    amount: f32,
+    curve: u32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+pub struct Trails {
+    label: &'static str,
+    format: wgpu::TextureFormat,
+    textures: PingPongRenderTextures,
+    _memory: [GpuReservation; 2],
+    /// Display size the trails cover, before scaling
+    size: (u32, u32),
+    resolution_scale: f32,
+    max_fade: f32,
+    sampler: wgpu::Sampler,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniforms_buffer: wgpu::Buffer,
+    fade_pipeline: wgpu::RenderPipeline,
+    blit_pipeline: wgpu::RenderPipeline,
+}
+
+impl Trails {
+    /// Trails over a `size` display, `resolution_scale` times as fine, kept
+    /// in and blitted onto `format` textures so the simulation's own
+    /// pipelines can draw into them
+    pub fn new(
+        device: &Device,
+        label: &'static str,
+        size: (u32, u32),
+        resolution_scale: f32,
+        format: wgpu::TextureFormat,
+        filter: wgpu::FilterMode,
+    ) -> Self {
+        let resolution_scale = resolution_scale.clamp(MIN_RESOLUTION_SCALE, MAX_RESOLUTION_SCALE);
+        let (width, height) = scaled(size, resolution_scale);
+        let textures = PingPongRenderTextures::new(device, width, height, format, label);
+        let memory = reserve(&textures);
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(&format!("{} Sampler", label)),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: filter,
+            min_filter: filter,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let uniforms_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} Fade Uniforms", label)),
+            contents: bytemuck::bytes_of(&FadeUniforms {
+                amount: 0.0,
+                curve: FadeCurve::None.gpu_id(),
+                _pad1: 0.0,
+                _pad2: 0.0,
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} Bind Group Layout", label)),
+            entries: &[
+                resource_helpers::texture_entry(
+                    0,
+                    wgpu::ShaderStages::FRAGMENT,
+                    wgpu::TextureSampleType::Float { filterable: true },
+                    wgpu::TextureViewDimension::D2,
+                ),
+                resource_helpers::sampler_entry(
+                    1,
+                    wgpu::ShaderStages::FRAGMENT,
+                    wgpu::SamplerBindingType::Filtering,
+                ),
+                resource_helpers::uniform_buffer_entry(2, wgpu::ShaderStages::FRAGMENT),
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&format!("{} Pipeline Layout", label)),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&format!("{} Shader", label)),
+            source: wgpu::ShaderSource::Wgsl(include_str!("trails.wgsl").into()),
+        });
+
+        let pipeline = |name: &str, entry_point: &str| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(&format!("{} {} Pipeline", label, name)),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let fade_pipeline = pipeline("Fade", "fs_fade");
+        let blit_pipeline = pipeline("Blit", "fs_blit");
+
+        Self {
+            label,
+            format,
+            textures,
+            _memory: memory,
+            size,
+            resolution_scale,
+            max_fade: DEFAULT_MAX_FADE,
+            sampler,
+            bind_group_layout,
+            uniforms_buffer,
+            fade_pipeline,
+            blit_pipeline,
+        }
+    }
+
+    /// Lose at most `max_fade` alpha a frame instead of [`DEFAULT_MAX_FADE`]
+    pub fn with_max_fade(mut self, max_fade: f32) -> Self {
+        self.max_fade = max_fade;
+        self
+    }
+
+    pub fn resolution_scale(&self) -> f32 {
+        self.resolution_scale
+    }
+
+    /// Cover a display of a new size. The trails start over, cleared.
+    pub fn resize(&mut self, device: &Device, size: (u32, u32)) {
+        self.size = size;
+        let (width, height) = scaled(size, self.resolution_scale);
+        self.textures = PingPongRenderTextures::new(device, width, height, self.format, self.label);
+        self._memory = reserve(&self.textures);
+    }
+
+    /// Keep the trails `scale` times as fine as the display. Returns whether
+    /// the textures were made again, in which case the trails start over.
+    pub fn set_resolution_scale(&mut self, device: &Device, scale: f32) -> bool {
+        let scale = scale.clamp(MIN_RESOLUTION_SCALE, MAX_RESOLUTION_SCALE);
+        if scale == self.resolution_scale {
+            return false;
+        }
+        self.resolution_scale = scale;
+        self.resize(device, self.size);
+        true
+    }
+
+    /// Fill both textures with `color`
+    pub fn clear(&self, device: &Device, queue: &Queue, color: wgpu::Color) {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some(&format!("{} Clear Encoder", self.label)),
+        });
+        for view in self.textures.views() {
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&format!("{} Clear Pass", self.label)),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Start this frame's trails: the last frame's, faded over `background`.
+    /// Draw the new frame into the pass returned, then [`swap`](Self::swap).
+    pub fn begin_pass<'encoder>(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &'encoder mut CommandEncoder,
+        curve: FadeCurve,
+        persistence: f32,
+        background: wgpu::Color,
+    ) -> wgpu::RenderPass<'encoder> {
+        queue.write_buffer(
+            &self.uniforms_buffer,
+            0,
+            bytemuck::bytes_of(&FadeUniforms {
+                amount: fade_amount(persistence, self.max_fade),
+                curve: curve.gpu_id(),
+                _pad1: 0.0,
+                _pad2: 0.0,
+            }),
+        );
+        let bind_group = self.bind_group(device, self.textures.current_view());
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&format!("{} Pass", self.label)),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: self.textures.inactive_view(),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(background),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.fade_pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+        pass
+    }
+
+    /// Make the frame just drawn the one to fade from and blit
+    pub fn swap(&mut self) {
+        self.textures.swap();
+    }
+
+    /// The latest trails, for simulations that sample them directly
+    pub fn view(&self) -> &TextureView {
+        self.textures.current_view()
+    }
+
+    /// Draw the latest trails over `target`
+    pub fn blit(&self, device: &Device, encoder: &mut CommandEncoder, target: &TextureView) {
+        let bind_group = self.bind_group(device, self.textures.current_view());
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&format!("{} Blit Pass", self.label)),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.blit_pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+
+    fn bind_group(&self, device: &Device, view: &TextureView) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&format!("{} Bind Group", self.label)),
+            layout: &self.bind_group_layout,
+            entries: &[
+                resource_helpers::texture_view_entry(0, view),
+                resource_helpers::sampler_bind_entry(1, &self.sampler),
+                resource_helpers::buffer_entry(2, &self.uniforms_buffer),
+            ],
+        })
+    }
+}
+
+/// Count both textures against the GPU memory budget
+fn reserve(textures: &PingPongRenderTextures) -> [GpuReservation; 2] {
+    textures
+        .textures()
+        .each_ref()
+        .map(GpuReservation::for_texture)
+}
+
+fn scaled((width, height): (u32, u32), scale: f32) -> (u32, u32) {
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulations::shared::kernel_test::KernelHarness;
+    use crate::simulations::shared::rewind::RewindResource;
+
+    #[test]
+    fn persistence_maps_onto_the_fade_per_frame() {
+        assert_eq!(fade_amount(1.0, DEFAULT_MAX_FADE), 0.0);
+        assert_eq!(fade_amount(0.0, DEFAULT_MAX_FADE), DEFAULT_MAX_FADE);
+        assert_eq!(fade_amount(1.5, DEFAULT_MAX_FADE), 0.0);
+        assert_eq!(scaled((100, 50), 0.25), (25, 13));
+        assert_eq!(scaled((1, 1), 0.25), (1, 1));
+    }
+
+    /// Alpha of the trail after one faded frame from an opaque one
+    async fn faded_alpha(curve: FadeCurve, persistence: f32) -> u8 {
+        let harness = KernelHarness::new().await;
+        let (device, queue) = (&harness.device, &harness.queue);
+        let mut trails = Trails::new(
+            device,
+            "Test Trails",
+            (4, 4),
+            1.0,
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::FilterMode::Nearest,
+        );
+        trails.clear(device, queue, wgpu::Color::WHITE);
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        drop(trails.begin_pass(
+            device,
+            queue,
+            &mut encoder,
+            curve,
+            persistence,
+            wgpu::Color::TRANSPARENT,
+        ));
+        queue.submit(std::iter::once(encoder.finish()));
+        trails.swap();
+
+        let texture = trails.textures.current_texture();
+        let texels = harness.read(RewindResource::Texture(texture));
+        texels[3]
+    }
+
+    #[tokio::test]
+    async fn curves_fade_differently() {
+        // Half persistence takes 0.05 off: linearly, or as a share of 1
+        assert_eq!(faded_alpha(FadeCurve::Linear, 0.5).await, 242);
+        assert_eq!(faded_alpha(FadeCurve::Exponential, 0.5).await, 242);
+        assert_eq!(faded_alpha(FadeCurve::None, 0.0).await, 255);
+        assert_eq!(faded_alpha(FadeCurve::Linear, 1.0).await, 255);
+    }
+}
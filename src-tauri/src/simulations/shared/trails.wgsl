@@ -0,0 +1,56 @@
+// Trail fading and blitting, for `trails.rs`
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+struct FadeUniforms {
+    // Alpha taken off each frame, before the curve shapes it
+    amount: f32,
+    // 0 linear, 1 exponential, 2 none
+    curve: u32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+@group(0) @binding(0) var trail_texture: texture_2d<f32>;
+@group(0) @binding(1) var trail_sampler: sampler;
+@group(0) @binding(2) var<uniform> fade: FadeUniforms;
+
+// One triangle over the whole target
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0)
+    );
+    var uvs = array<vec2<f32>, 3>(
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(2.0, 1.0),
+        vec2<f32>(0.0, -1.0)
+    );
+    var out: VertexOutput;
+    out.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
+    out.uv = uvs[vertex_index];
+    return out;
+}
+
+@fragment
+fn fs_fade(input: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(trail_texture, trail_sampler, input.uv);
+    var alpha = color.a;
+    if (fade.curve == 0u) {
+        alpha = alpha - fade.amount;
+    } else if (fade.curve == 1u && fade.amount > 0.0) {
+        // At least one step of the 8-bit target, or faint trails never go
+        alpha = min(alpha * (1.0 - fade.amount), alpha - 1.0 / 255.0);
+    }
+    return vec4<f32>(color.rgb, max(alpha, 0.0));
+}
+
+@fragment
+fn fs_blit(input: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(trail_texture, trail_sampler, input.uv);
+}
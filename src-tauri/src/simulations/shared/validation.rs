@@ -0,0 +1,302 @@
+//! Checks applied to setting updates before they reach a simulation.
+//!
+//! Each simulation describes its settings in a [`SettingValidator`] table:
+//! the range a number may take, whether it has to be a whole number, which
+//! names an enum setting accepts, and pairs of settings that have to stay in
+//! order. A value the table doesn't allow is either clamped into place or
+//! rejected with a [`ValidationError`] the frontend can show next to the
+//! field. Settings missing from the table are passed through untouched.
+
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rule {
+    /// A number in `min..=max`, clamped when it falls outside
+    Range {
+        min: f64,
+        max: f64,
+    },
+    /// A whole number in `min..=max`. Counts usually resize buffers, so a
+    /// value outside is rejected rather than quietly replaced.
+    Count {
+        min: u64,
+        max: u64,
+    },
+    Flag,
+    /// One of the listed names
+    OneOf(&'static [&'static str]),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constraint {
+    /// `lower` must not exceed `upper`. Whichever of the two is being
+    /// updated gets clamped against the current value of the other.
+    Ordered {
+        lower: &'static str,
+        upper: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error)]
+#[serde(tag = "kind")]
+pub enum ValidationError {
+    #[error("'{setting}' expects {expected}, got {value}")]
+    WrongType {
+        setting: String,
+        expected: &'static str,
+        value: Value,
+    },
+    #[error("'{setting}' must be between {min} and {max}, got {value}")]
+    OutOfRange {
+        setting: String,
+        min: f64,
+        max: f64,
+        value: f64,
+    },
+    #[error("'{setting}' must be one of {}, got '{value}'", allowed.join(", "))]
+    NotAllowed {
+        setting: String,
+        allowed: &'static [&'static str],
+        value: String,
+    },
+}
+
+/// A value that passed validation, possibly after clamping
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated {
+    pub value: Value,
+    pub clamped: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SettingValidator {
+    rules: &'static [(&'static str, Rule)],
+    constraints: &'static [Constraint],
+}
+
+impl SettingValidator {
+    /// For simulations without settings worth checking
+    pub const NONE: SettingValidator = SettingValidator::new(&[], &[]);
+
+    pub const fn new(
+        rules: &'static [(&'static str, Rule)],
+        constraints: &'static [Constraint],
+    ) -> Self {
+        Self { rules, constraints }
+    }
+
+    /// Check `value` for `setting`. `current_settings` is only called when a
+    /// constraint needs the value of another setting.
+    pub fn validate(
+        &self,
+        setting: &str,
+        value: Value,
+        current_settings: impl FnOnce() -> Value,
+    ) -> Result<Validated, ValidationError> {
+        let mut validated = match self.rule(setting) {
+            Some(rule) => check_rule(setting, rule, value)?,
+            None => Validated {
+                value,
+                clamped: false,
+            },
+        };
+
+        // A setting takes part in at most one constraint
+        let paired = self.constraints.iter().find_map(|constraint| {
+            let Constraint::Ordered { lower, upper } = *constraint;
+            if setting == lower {
+                Some((upper, true))
+            } else if setting == upper {
+                Some((lower, false))
+            } else {
+                None
+            }
+        });
+        let Some((other, is_lower)) = paired else {
+            return Ok(validated);
+        };
+        let (Some(number), Some(limit)) = (
+            validated.value.as_f64(),
+            current_settings().get(other).and_then(Value::as_f64),
+        ) else {
+            return Ok(validated);
+        };
+
+        let allowed = if is_lower {
+            number.min(limit)
+        } else {
+            number.max(limit)
+        };
+        if allowed != number {
+            validated = Validated {
+                value: Value::from(allowed),
+                clamped: true,
+            };
+        }
+        Ok(validated)
+    }
+
+    pub fn rule(&self, setting: &str) -> Option<Rule> {
+        self.rules
+            .iter()
+            .find(|(name, _)| *name == setting)
+            .map(|(_, rule)| *rule)
+    }
+}
+
+fn check_rule(setting: &str, rule: Rule, value: Value) -> Result<Validated, ValidationError> {
+    let wrong_type = |expected: &'static str, value: Value| ValidationError::WrongType {
+        setting: setting.to_string(),
+        expected,
+        value,
+    };
+
+    match rule {
+        Rule::Range { min, max } => {
+            let Some(number) = value.as_f64().filter(|number| number.is_finite()) else {
+                return Err(wrong_type("a number", value));
+            };
+            let allowed = number.clamp(min, max);
+            if allowed == number {
+                Ok(Validated {
+                    value,
+                    clamped: false,
+                })
+            } else {
+                Ok(Validated {
+                    value: Value::from(allowed),
+                    clamped: true,
+                })
+            }
+        }
+        Rule::Count { min, max } => {
+            // The frontend sends whole numbers as floats now and then
+            let count = value.as_u64().or_else(|| {
+                value
+                    .as_f64()
+                    .filter(|number| *number >= 0.0 && number.fract() == 0.0)
+                    .map(|number| number as u64)
+            });
+            let Some(count) = count else {
+                return Err(wrong_type("a whole number", value));
+            };
+            if !(min..=max).contains(&count) {
+                return Err(ValidationError::OutOfRange {
+                    setting: setting.to_string(),
+                    min: min as f64,
+                    max: max as f64,
+                    value: count as f64,
+                });
+            }
+            Ok(Validated {
+                value: Value::from(count),
+                clamped: false,
+            })
+        }
+        Rule::Flag => {
+            if !value.is_boolean() {
+                return Err(wrong_type("true or false", value));
+            }
+            Ok(Validated {
+                value,
+                clamped: false,
+            })
+        }
+        Rule::OneOf(allowed) => {
+            let Some(name) = value.as_str() else {
+                return Err(wrong_type("a name", value));
+            };
+            if !allowed.contains(&name) {
+                return Err(ValidationError::NotAllowed {
+                    setting: setting.to_string(),
+                    allowed,
+                    value: name.to_string(),
+                });
+            }
+            Ok(Validated {
+                value,
+                clamped: false,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const VALIDATOR: SettingValidator = SettingValidator::new(
+        &[
+            ("strength", Rule::Range { min: 0.0, max: 1.0 }),
+            ("count", Rule::Count { min: 2, max: 8 }),
+            ("shape", Rule::OneOf(&["Circle", "Square"])),
+        ],
+        &[Constraint::Ordered {
+            lower: "speed_min",
+            upper: "speed_max",
+        }],
+    );
+
+    fn no_settings() -> Value {
+        panic!("settings should not be needed")
+    }
+
+    #[test]
+    fn ranges_clamp_and_counts_reject() {
+        let clamped = VALIDATOR
+            .validate("strength", json!(1.5), no_settings)
+            .unwrap();
+        assert_eq!(clamped.value, json!(1.0));
+        assert!(clamped.clamped);
+
+        let count = VALIDATOR
+            .validate("count", json!(4.0), no_settings)
+            .unwrap();
+        assert_eq!(count.value, json!(4));
+        assert!(matches!(
+            VALIDATOR.validate("count", json!(9), no_settings),
+            Err(ValidationError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            VALIDATOR.validate("count", json!(2.5), no_settings),
+            Err(ValidationError::WrongType { .. })
+        ));
+        assert!(matches!(
+            VALIDATOR.validate("strength", json!("high"), no_settings),
+            Err(ValidationError::WrongType { .. })
+        ));
+        assert!(matches!(
+            VALIDATOR.validate("shape", json!("Hexagon"), no_settings),
+            Err(ValidationError::NotAllowed { .. })
+        ));
+
+        // Settings without a rule are left to the simulation
+        let unknown = VALIDATOR
+            .validate("anything", json!("goes"), no_settings)
+            .unwrap();
+        assert_eq!(unknown.value, json!("goes"));
+    }
+
+    #[test]
+    fn ordered_settings_are_clamped_against_each_other() {
+        let current = || json!({ "speed_min": 20.0, "speed_max": 60.0 });
+
+        let lower = VALIDATOR
+            .validate("speed_min", json!(80.0), current)
+            .unwrap();
+        assert_eq!(lower.value, json!(60.0));
+        assert!(lower.clamped);
+
+        let upper = VALIDATOR
+            .validate("speed_max", json!(10.0), current)
+            .unwrap();
+        assert_eq!(upper.value, json!(20.0));
+
+        let fine = VALIDATOR
+            .validate("speed_max", json!(40.0), current)
+            .unwrap();
+        assert!(!fine.clamped);
+    }
+}
@@ -10,6 +10,7 @@ pub struct BindGroupManager {
     pub render_bind_group: BindGroup,
     pub camera_bind_group: BindGroup,
     pub gradient_bind_group: BindGroup,
+    pub agent_render_bind_group: BindGroup,
 }
 
 impl BindGroupManager {
@@ -21,6 +22,7 @@ impl BindGroupManager {
         render_bind_group_layout: &BindGroupLayout,
         camera_bind_group_layout: &BindGroupLayout,
         gradient_bind_group_layout: &BindGroupLayout,
+        agent_render_bind_group_layout: &BindGroupLayout,
         agent_buffer: &Buffer,
         trail_map_buffer: &Buffer,
         trail_map_buffer_b: &Buffer,
@@ -33,6 +35,7 @@ impl BindGroupManager {
         cursor_buffer: &Buffer,
         background_color_buffer: &Buffer,
         average_color_uniform_buffer: &Buffer,
+        agent_render_params_buffer: &Buffer,
     ) -> Self {
         Self {
             compute_bind_group: Self::create_compute_bind_group(
@@ -81,6 +84,13 @@ impl BindGroupManager {
                 mask_buffer,
                 sim_size_buffer,
             ),
+            agent_render_bind_group: Self::create_agent_render_bind_group(
+                device,
+                agent_render_bind_group_layout,
+                agent_buffer,
+                agent_render_params_buffer,
+                lut_buffer,
+            ),
         }
     }
 
@@ -226,4 +236,31 @@ impl BindGroupManager {
             ],
         })
     }
+
+    fn create_agent_render_bind_group(
+        device: &Device,
+        layout: &BindGroupLayout,
+        agent_buffer: &Buffer,
+        agent_render_params_buffer: &Buffer,
+        lut_buffer: &Buffer,
+    ) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Agent Render Bind Group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: agent_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: agent_render_params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: lut_buffer.as_entire_binding(),
+                },
+            ],
+        })
+    }
 }
@@ -16,6 +16,7 @@ pub struct PipelineManager {
     pub render_pipeline: RenderPipeline,
     pub render_infinite_pipeline: RenderPipeline,
     pub background_render_pipeline: RenderPipeline,
+    pub agent_render_pipeline: RenderPipeline,
     pub compute_bind_group_layout: BindGroupLayout,
     pub display_bind_group_layout: BindGroupLayout,
     pub render_bind_group_layout: BindGroupLayout,
@@ -23,6 +24,7 @@ pub struct PipelineManager {
     pub gradient_bind_group_layout: BindGroupLayout,
     pub background_bind_group_layout: BindGroupLayout,
     pub average_color_bind_group_layout: BindGroupLayout,
+    pub agent_render_bind_group_layout: BindGroupLayout,
 }
 
 impl PipelineManager {
@@ -124,6 +126,19 @@ impl PipelineManager {
                 ],
             });
 
+        let agent_render_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Agent Render Bind Group Layout"),
+                entries: &[
+                    resource_helpers::storage_buffer_entry(0, wgpu::ShaderStages::VERTEX, true),
+                    resource_helpers::uniform_buffer_entry(
+                        1,
+                        wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ),
+                    resource_helpers::storage_buffer_entry(2, wgpu::ShaderStages::VERTEX, true),
+                ],
+            });
+
         let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
             label: Some("Compute Pipeline"),
             layout: Some(
@@ -383,6 +398,53 @@ impl PipelineManager {
                 cache: None,
             });
 
+        // Blended over the display texture, before it is tiled to the surface
+        let agent_render_pipeline =
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Agent Render Pipeline"),
+                layout: Some(
+                    &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                        label: Some("Agent Render Pipeline Layout"),
+                        bind_group_layouts: &[&agent_render_bind_group_layout],
+                        push_constant_ranges: &[],
+                    }),
+                ),
+                vertex: wgpu::VertexState {
+                    module: &shader_manager.agent_render_shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader_manager.agent_render_shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    // Rotated agents face either way
+                    cull_mode: None,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+                cache: None,
+            });
+
         Self {
             compute_pipeline,
             decay_pipeline,
@@ -395,6 +457,7 @@ impl PipelineManager {
             render_pipeline,
             render_infinite_pipeline,
             background_render_pipeline,
+            agent_render_pipeline,
             compute_bind_group_layout,
             display_bind_group_layout,
             render_bind_group_layout,
@@ -402,6 +465,7 @@ impl PipelineManager {
             gradient_bind_group_layout,
             background_bind_group_layout,
             average_color_bind_group_layout,
+            agent_render_bind_group_layout,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::simulations::shared::AVERAGE_COLOR_SHADER;
 use crate::simulations::slime_mold::shaders::{
-    BACKGROUND_RENDER_SHADER, COMPUTE_SHADER, DISPLAY_SHADER, GRADIENT_SHADER,
+    AGENT_RENDER_SHADER, BACKGROUND_RENDER_SHADER, COMPUTE_SHADER, DISPLAY_SHADER, GRADIENT_SHADER,
     QUAD_INFINITE_SHADER, QUAD_SHADER,
 };
 use crate::simulations::slime_mold::workgroup_optimizer::WorkgroupConfig;
@@ -15,6 +15,7 @@ pub struct ShaderManager {
     pub gradient_shader: ShaderModule,
     pub background_render_shader: ShaderModule,
     pub average_color_shader: ShaderModule,
+    pub agent_render_shader: ShaderModule,
 }
 
 impl ShaderManager {
@@ -58,6 +59,11 @@ impl ShaderManager {
                 workgroup_config.compute_1d,
                 workgroup_config.compute_2d,
             ),
+            agent_render_shader: Self::create_shader(
+                device,
+                "Agent Render Shader",
+                AGENT_RENDER_SHADER,
+            ),
         }
     }
 
@@ -1,4 +1,8 @@
-use crate::simulations::shared::ImageFitMode;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Constraint, Rule, SettingValidator};
+use crate::simulations::shared::{BackgroundLayer, GridResolution, ImageFitMode};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::ops::Range;
@@ -69,6 +73,106 @@ pub struct Settings {
     ///
     /// Defaults to BackgroundMode::Black.
     pub background_mode: BackgroundMode,
+    /// Size of the trail map, or the window's.
+    ///
+    /// Defaults to GridResolution::Window.
+    #[serde(default)]
+    pub grid_resolution: GridResolution,
+    /// How the agents themselves are drawn.
+    ///
+    /// Defaults to hidden, showing only the trail map.
+    #[serde(default)]
+    pub agent_display: AgentDisplay,
+
+    /// Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AgentDisplayMode {
+    #[default]
+    Hidden,
+    OverTrails,
+    /// Agents on the background, without the trail map
+    AgentsOnly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AgentShape {
+    /// Pointing along the heading
+    #[default]
+    Triangle,
+    Dot,
+}
+
+impl From<AgentShape> for u32 {
+    fn from(shape: AgentShape) -> Self {
+        match shape {
+            AgentShape::Triangle => 0,
+            AgentShape::Dot => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AgentColorMode {
+    /// Hue wheel around the heading
+    #[default]
+    Heading,
+    /// Color scheme from the minimum to the maximum speed
+    Speed,
+}
+
+impl From<AgentColorMode> for u32 {
+    fn from(mode: AgentColorMode) -> Self {
+        match mode {
+            AgentColorMode::Heading => 0,
+            AgentColorMode::Speed => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentDisplay {
+    pub mode: AgentDisplayMode,
+    pub shape: AgentShape,
+    pub color_mode: AgentColorMode,
+    /// Length of an agent in trail map cells
+    pub size: f32,
+    /// 0-1
+    pub opacity: f32,
+}
+
+impl Default for AgentDisplay {
+    fn default() -> Self {
+        Self {
+            mode: AgentDisplayMode::Hidden,
+            shape: AgentShape::Triangle,
+            color_mode: AgentColorMode::Heading,
+            size: 4.0,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl AgentDisplay {
+    pub fn validate(&self) -> SimulationResult<()> {
+        if !(0.5..=64.0).contains(&self.size) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Agent size {} is outside 0.5-64",
+                self.size
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err(SimulationError::InvalidParameter(format!(
+                "Agent opacity {} is outside 0-1",
+                self.opacity
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -181,10 +285,15 @@ impl Default for Settings {
             decay_frequency: 1,
             random_seed: 0,
             background_mode: BackgroundMode::Black,
+            grid_resolution: GridResolution::Window,
+            agent_display: AgentDisplay::default(),
+            background_layer: BackgroundLayer::default(),
         }
     }
 }
 
+impl PresetSchema for Settings {}
+
 impl Settings {
     /// Randomize all settings within reasonable bounds
     pub fn randomize(&mut self) {
@@ -214,3 +323,105 @@ impl Settings {
         self.random_seed = rng.random();
     }
 }
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting).
+/// The UI shows angles in degrees but sends radians.
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "pheromone_decay_rate",
+            Rule::Range {
+                min: 0.0,
+                max: 10000.0,
+            },
+        ),
+        (
+            "pheromone_deposition_rate",
+            Rule::Range {
+                min: 0.0,
+                max: 100.0,
+            },
+        ),
+        (
+            "pheromone_diffusion_rate",
+            Rule::Range {
+                min: 0.0,
+                max: 100.0,
+            },
+        ),
+        (
+            "agent_speed_min",
+            Rule::Range {
+                min: 0.0,
+                max: 500.0,
+            },
+        ),
+        (
+            "agent_speed_max",
+            Rule::Range {
+                min: 0.0,
+                max: 500.0,
+            },
+        ),
+        (
+            "agent_turn_rate",
+            Rule::Range {
+                min: 0.0,
+                max: std::f64::consts::TAU,
+            },
+        ),
+        (
+            "agent_jitter",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        (
+            "agent_sensor_angle",
+            Rule::Range {
+                min: 0.0,
+                max: std::f64::consts::PI,
+            },
+        ),
+        (
+            "agent_sensor_distance",
+            Rule::Range {
+                min: 0.0,
+                max: 500.0,
+            },
+        ),
+        (
+            "cursor_size",
+            Rule::Range {
+                min: 10.0,
+                max: 500.0,
+            },
+        ),
+        (
+            "cursor_strength",
+            Rule::Range {
+                min: 0.0,
+                max: 50.0,
+            },
+        ),
+        ("trailMapFiltering", Rule::OneOf(&["Nearest", "Linear"])),
+    ],
+    &[Constraint::Ordered {
+        lower: "agent_speed_min",
+        upper: "agent_speed_max",
+    }],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized).
+/// The agent display only changes how agents are drawn, so it counts as color.
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    (
+        "agent_possible_starting_headings",
+        SettingCategory::Generators,
+    ),
+    ("position_image_fit_mode", SettingCategory::Generators),
+    ("random_seed", SettingCategory::Generators),
+    ("background_mode", SettingCategory::Colors),
+    ("agent_display", SettingCategory::Colors),
+]);
@@ -0,0 +1,111 @@
+// Draws every agent as an instanced triangle or dot over the display texture,
+// in the same trail map space the display pass maps onto it
+
+const TAU: f32 = 6.28318530718;
+
+struct AgentRenderParams {
+    sim_width: f32,
+    sim_height: f32,
+    size: f32,
+    opacity: f32,
+    shape: u32, // 0=triangle, 1=dot
+    color_mode: u32, // 0=heading, 1=speed
+    speed_min: f32,
+    speed_max: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+    @location(1) color: vec3<f32>,
+};
+
+// x, y, angle, speed as in compute.wgsl
+@group(0) @binding(0)
+var<storage, read> agents: array<vec4<f32>>;
+
+@group(0) @binding(1)
+var<uniform> params: AgentRenderParams;
+
+@group(0) @binding(2)
+var<storage, read> lut_data: array<u32>;
+
+fn get_lut_color(intensity: f32) -> vec3<f32> {
+    let idx = clamp(i32(intensity * 255.0), 0, 255);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[idx]) / 255.0),
+        srgb_to_linear(f32(lut_data[256 + idx]) / 255.0),
+        srgb_to_linear(f32(lut_data[512 + idx]) / 255.0)
+    );
+}
+
+fn hue_to_rgb(hue: f32) -> vec3<f32> {
+    let k = (vec3<f32>(0.0, 2.0, 4.0) / 6.0 + hue) * 6.0 % 6.0;
+    return clamp(abs(k - 3.0) - 1.0, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    let agent = agents[instance_index];
+
+    // Unit shape pointing along +x; the triangle leaves its second half degenerate
+    var triangle = array<vec2<f32>, 6>(
+        vec2<f32>(0.5, 0.0),
+        vec2<f32>(-0.5, 0.35),
+        vec2<f32>(-0.5, -0.35),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(0.0, 0.0),
+    );
+    var quad = array<vec2<f32>, 6>(
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, -0.5),
+        vec2<f32>(-0.5, 0.5),
+        vec2<f32>(-0.5, 0.5),
+        vec2<f32>(0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+    );
+    var local = quad[vertex_index];
+    if (params.shape == 0u) {
+        local = triangle[vertex_index];
+    }
+
+    let heading = vec2<f32>(cos(agent.z), sin(agent.z));
+    let rotated = vec2<f32>(
+        local.x * heading.x - local.y * heading.y,
+        local.x * heading.y + local.y * heading.x
+    );
+    let cell = agent.xy + rotated * params.size;
+
+    var color: vec3<f32>;
+    if (params.color_mode == 0u) {
+        let hue = fract(agent.z / TAU);
+        color = srgb_to_linear_rgb(hue_to_rgb(hue));
+    } else {
+        let speed_range = max(0.0001, params.speed_max - params.speed_min);
+        color = get_lut_color(clamp((agent.w - params.speed_min) / speed_range, 0.0, 1.0));
+    }
+
+    var out: VertexOutput;
+    // Trail map row 0 is the top row of the display texture
+    out.position = vec4<f32>(
+        cell.x / params.sim_width * 2.0 - 1.0,
+        1.0 - cell.y / params.sim_height * 2.0,
+        0.0,
+        1.0
+    );
+    out.local = local;
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    if (params.shape == 1u && length(in.local) > 0.5) {
+        discard;
+    }
+    return vec4<f32>(in.color, params.opacity);
+}
@@ -89,15 +89,6 @@ fn sample_gradient_map_smooth(pos: vec2<f32>) -> f32 {
     return mix(v0, v1, dy);
 }
 
-// Convert from sRGB (gamma-corrected) to linear RGB
-fn srgb_to_linear(srgb: f32) -> f32 {
-    if (srgb <= 0.04045) {
-        return srgb / 12.92;
-    } else {
-        return pow((srgb + 0.055) / 1.055, 2.4);
-    }
-}
-
 // Get color from color scheme (planar LUT)
 fn get_lut_color(intensity: f32) -> vec3<f32> {
     let idx = clamp(i32(intensity * 255.0), 0, 255);
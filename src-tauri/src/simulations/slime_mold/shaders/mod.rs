@@ -1,6 +1,13 @@
 pub const COMPUTE_SHADER: &str = include_str!("compute.wgsl");
-pub const DISPLAY_SHADER: &str = include_str!("display.wgsl");
+pub const DISPLAY_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("display.wgsl")
+);
 pub const GRADIENT_SHADER: &str = include_str!("gradient.wgsl");
 pub const QUAD_SHADER: &str = include_str!("quad.wgsl");
 pub const QUAD_INFINITE_SHADER: &str = crate::simulations::shared::INFINITE_RENDER_SHADER;
 pub const BACKGROUND_RENDER_SHADER: &str = include_str!("background_render.wgsl");
+pub const AGENT_RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("agent_render.wgsl")
+);
@@ -8,14 +8,17 @@ use wgpu::{Device, Queue, SurfaceConfiguration, TextureView};
 
 use super::buffer_pool::BufferPool;
 use super::render::{bind_group_manager::BindGroupManager, pipeline_manager::PipelineManager};
-use super::settings::Settings;
+use super::settings::{AgentDisplay, AgentDisplayMode, Settings};
 use super::state::{MaskPattern, MaskTarget, State as SlimeMoldState};
 use super::workgroup_optimizer::WorkgroupConfig;
 use crate::simulations::shared::ImageFitMode;
+use crate::simulations::shared::field_image::{FieldImage, field_to_image, image_to_field};
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use crate::simulations::shared::post_processing::{PostProcessingResources, PostProcessingState};
 use crate::simulations::shared::{
-    ColorScheme, ColorSchemeManager, camera::Camera, ping_pong_buffers::PingPongBuffers,
+    BackgroundLayer, ColorScheme, ColorSchemeManager, GpuReservation, GridResolution, HealthCheck,
+    HealthIssue, HealthProbe, RewindResource, camera::Camera, gpu_budget,
+    ping_pong_buffers::PingPongBuffers,
 };
 
 #[repr(C)]
@@ -109,6 +112,43 @@ pub struct BackgroundParams {
     pub _pad0: u32,
 }
 
+/// `AgentRenderParams` in `agent_render.wgsl`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub struct AgentRenderParams {
+    pub sim_width: f32,
+    pub sim_height: f32,
+    pub size: f32,
+    pub opacity: f32,
+
+    pub shape: u32,
+    pub color_mode: u32,
+    pub speed_min: f32,
+    pub speed_max: f32,
+}
+
+impl AgentRenderParams {
+    pub fn new(settings: &Settings, width: u32, height: u32) -> Self {
+        let display = &settings.agent_display;
+        Self {
+            sim_width: width as f32,
+            sim_height: height as f32,
+            size: display.size,
+            opacity: display.opacity,
+            shape: display.shape.into(),
+            color_mode: display.color_mode.into(),
+            speed_min: settings.agent_speed_min,
+            speed_max: settings.agent_speed_max,
+        }
+    }
+}
+
+/// Each agent is a vec4 of position, heading and speed
+const AGENT_SIZE_BYTES: u64 = 4 * std::mem::size_of::<f32>() as u64;
+/// Both trail map buffers, the mask and the RGBA8 display texture are screen sized
+const TRAIL_MAP_COPIES: u64 = 4;
+const TRAIL_MAP_BYTES_PER_PIXEL: u64 = TRAIL_MAP_COPIES * std::mem::size_of::<f32>() as u64;
+
 #[derive(Debug)]
 /// SlimeMoldModel manages simulation-specific GPU resources and logic
 /// while using Tauri's shared GPU context (device, queue, surface config)
@@ -140,6 +180,9 @@ pub struct SlimeMoldModel {
     pub current_trail_map_size: u64,
     pub current_mask_buffer_size: u64,
     pub current_agent_buffer_size: u64,
+    // Held against the GPU memory budget
+    trail_map_memory: GpuReservation,
+    agent_memory: GpuReservation,
 
     // Dimension tracking for resize scaling
     pub current_width: u32,
@@ -152,6 +195,8 @@ pub struct SlimeMoldModel {
     // Resize debouncing
     pub last_resize_time: std::time::Instant,
     pub resize_debounce_threshold: std::time::Duration,
+    /// Last surface size, for resolving a window sized grid
+    pub window_size: (u32, u32),
 
     // Add cursor interaction state to SlimeMoldModel
     pub cursor_active_mode: u32, // 0=inactive, 1=attract, 2=repel
@@ -171,6 +216,7 @@ pub struct SlimeMoldModel {
     pub average_color_staging_buffer: wgpu::Buffer,
     pub average_color_bind_group: wgpu::BindGroup,
     pub average_color_uniform_buffer: wgpu::Buffer,
+    pub agent_render_params_buffer: wgpu::Buffer,
     pub color_scheme_manager: Arc<ColorSchemeManager>,
     pub post_processing_state: PostProcessingState,
     pub post_processing_resources: PostProcessingResources,
@@ -218,34 +264,31 @@ impl SlimeMoldModel {
         app_settings: &AppSettings,
         color_scheme_manager: &ColorSchemeManager,
     ) -> SimulationResult<Self> {
-        let physical_width = surface_config.width;
-        let physical_height = surface_config.height;
+        let (physical_width, physical_height) = settings
+            .grid_resolution
+            .resolve(surface_config.width, surface_config.height);
 
-        // Check if the trail map buffer size would exceed GPU limits
-        let max_storage_buffer_size = device.limits().max_storage_buffer_binding_size as u64;
-        let trail_map_size = (physical_width * physical_height) as usize;
-        let trail_map_size_bytes = (trail_map_size * std::mem::size_of::<f32>()) as u64;
-
-        // If buffer would be too large, scale down the resolution
-        let (effective_width, effective_height) = if trail_map_size_bytes > max_storage_buffer_size
-        {
-            let scale_factor =
-                (max_storage_buffer_size as f64 / trail_map_size_bytes as f64).sqrt();
-            let new_width = (physical_width as f64 * scale_factor * 0.95) as u32; // 95% to be safe
-            let new_height = (physical_height as f64 * scale_factor * 0.95) as u32;
-            tracing::warn!(
-                "Trail map buffer size {} bytes exceeds GPU limit {} bytes. Scaling down from {}x{} to {}x{}",
-                trail_map_size_bytes,
-                max_storage_buffer_size,
-                physical_width,
-                physical_height,
-                new_width,
-                new_height
-            );
-            (new_width, new_height)
-        } else {
-            (physical_width, physical_height)
-        };
+        // Scale down the resolution if the trail map wouldn't fit in GPU memory
+        let (effective_width, effective_height) = gpu_budget::fit_resolution(
+            device,
+            "Slime mold trail map",
+            (physical_width, physical_height),
+            std::mem::size_of::<f32>() as u64,
+            TRAIL_MAP_COPIES,
+            &GpuReservation::default(),
+        );
+        let trail_map_memory = GpuReservation::new(
+            effective_width as u64 * effective_height as u64 * TRAIL_MAP_BYTES_PER_PIXEL,
+        );
+        let agent_count = gpu_budget::fit_count(
+            device,
+            "Slime mold agents",
+            agent_count,
+            AGENT_SIZE_BYTES,
+            1,
+            &GpuReservation::default(),
+        );
+        let agent_memory = GpuReservation::new(agent_count as u64 * AGENT_SIZE_BYTES);
 
         // Create simulation-specific buffers
         let agent_buffer = create_agent_buffer(device, agent_count);
@@ -443,6 +486,17 @@ impl SlimeMoldModel {
                 usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
             });
 
+        let agent_render_params_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Slime Mold Agent Render Params Buffer"),
+                contents: bytemuck::bytes_of(&AgentRenderParams::new(
+                    &settings,
+                    physical_width,
+                    physical_height,
+                )),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+
         // Create bind group manager
         let bind_group_manager = BindGroupManager::new(
             device,
@@ -451,6 +505,7 @@ impl SlimeMoldModel {
             &pipeline_manager.render_bind_group_layout,
             &pipeline_manager.camera_bind_group_layout,
             &pipeline_manager.gradient_bind_group_layout,
+            &pipeline_manager.agent_render_bind_group_layout,
             &agent_buffer,
             trail_map_buffers.current_buffer(),
             trail_map_buffers.inactive_buffer(),
@@ -463,6 +518,7 @@ impl SlimeMoldModel {
             &cursor_buffer,
             &background_color_buffer,
             &average_color_uniform_buffer,
+            &agent_render_params_buffer,
         );
 
         // Create background bind group
@@ -509,12 +565,15 @@ impl SlimeMoldModel {
             current_trail_map_size: trail_map_size_bytes,
             current_mask_buffer_size: trail_map_size_bytes,
             current_agent_buffer_size: agent_buffer_size_bytes,
+            trail_map_memory,
+            agent_memory,
             current_width: effective_width,
             current_height: effective_height,
             gui_visible: true,
             camera,
             last_resize_time: std::time::Instant::now(),
             resize_debounce_threshold: std::time::Duration::from_millis(500),
+            window_size: (surface_config.width, surface_config.height),
             cursor_active_mode: 0,
             cursor_world_x: 0.0,
             cursor_world_y: 0.0,
@@ -530,6 +589,7 @@ impl SlimeMoldModel {
             average_color_staging_buffer,
             average_color_bind_group,
             average_color_uniform_buffer,
+            agent_render_params_buffer,
             color_scheme_manager: Arc::new(color_scheme_manager.clone()),
             post_processing_state,
             post_processing_resources,
@@ -552,7 +612,7 @@ impl SlimeMoldModel {
         }
 
         // Initialize agents using GPU compute shader instead of CPU
-        simulation.reset_agents(device, queue)?;
+        simulation.randomize_agents(device, queue)?;
 
         Ok(simulation)
     }
@@ -564,34 +624,11 @@ impl SlimeMoldModel {
         queue: &Arc<Queue>,
         new_config: &SurfaceConfiguration,
     ) -> SimulationResult<()> {
-        let physical_width = new_config.width;
-        let physical_height = new_config.height;
-
-        // Check if the trail map buffer size would exceed GPU limits
-        let max_storage_buffer_size = device.limits().max_storage_buffer_binding_size as u64;
-        let trail_map_size = (physical_width * physical_height) as usize;
-        let trail_map_size_bytes = (trail_map_size * std::mem::size_of::<f32>()) as u64;
+        self.window_size = (new_config.width, new_config.height);
+        // A fixed grid ignores the window, but post-processing still follows it
+        self.post_processing_resources.resize(device, new_config)?;
 
-        // If buffer would be too large, scale down the resolution
-        let (effective_width, effective_height) = if trail_map_size_bytes > max_storage_buffer_size
-        {
-            let scale_factor =
-                (max_storage_buffer_size as f64 / trail_map_size_bytes as f64).sqrt();
-            let new_width = (physical_width as f64 * scale_factor * 0.95) as u32; // 95% to be safe
-            let new_height = (physical_height as f64 * scale_factor * 0.95) as u32;
-            tracing::warn!(
-                "Trail map buffer size {} bytes exceeds GPU limit {} bytes. Scaling down from {}x{} to {}x{}",
-                trail_map_size_bytes,
-                max_storage_buffer_size,
-                physical_width,
-                physical_height,
-                new_width,
-                new_height
-            );
-            (new_width, new_height)
-        } else {
-            (physical_width, physical_height)
-        };
+        let (effective_width, effective_height) = self.fit_grid(device);
 
         // Early return if dimensions haven't changed significantly
         let width_diff = effective_width.abs_diff(self.current_width);
@@ -614,6 +651,53 @@ impl SlimeMoldModel {
         }
         self.last_resize_time = now;
 
+        self.resize_grid(device, queue, effective_width, effective_height);
+        tracing::info!("Slime mold resize completed successfully");
+        Ok(())
+    }
+
+    /// Grid size from the grid resolution setting and window, scaled down
+    /// if the trail map wouldn't fit in GPU memory
+    fn fit_grid(&self, device: &Arc<Device>) -> (u32, u32) {
+        let (window_width, window_height) = self.window_size;
+        gpu_budget::fit_resolution(
+            device,
+            "Slime mold trail map",
+            self.settings
+                .grid_resolution
+                .resolve(window_width, window_height),
+            std::mem::size_of::<f32>() as u64,
+            TRAIL_MAP_COPIES,
+            &self.trail_map_memory,
+        )
+    }
+
+    /// Switch to a new grid resolution, rescaling the trail map and agents
+    /// if its size changes
+    pub fn set_grid_resolution(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        grid_resolution: GridResolution,
+    ) -> SimulationResult<()> {
+        grid_resolution.validate(device.limits().max_texture_dimension_2d)?;
+        self.settings.grid_resolution = grid_resolution;
+        let (effective_width, effective_height) = self.fit_grid(device);
+        if (effective_width, effective_height) != (self.current_width, self.current_height) {
+            self.resize_grid(device, queue, effective_width, effective_height);
+        }
+        Ok(())
+    }
+
+    /// Rebuild the trail map, mask, agents and display texture at a new grid
+    /// size, scaling the old contents
+    fn resize_grid(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        effective_width: u32,
+        effective_height: u32,
+    ) {
         tracing::info!(
             "Resizing slime mold from {}x{} to {}x{}",
             self.current_width,
@@ -627,15 +711,6 @@ impl SlimeMoldModel {
         let trail_map_size_bytes = (trail_map_size * std::mem::size_of::<f32>()) as u64;
         let agent_buffer_size_bytes = (self.agent_count * 4 * std::mem::size_of::<f32>()) as u64;
 
-        // Validate buffer sizes to prevent overruns
-        if trail_map_size_bytes > max_storage_buffer_size {
-            return Err(format!(
-                "New trail map buffer size {} bytes exceeds GPU limit {} bytes",
-                trail_map_size_bytes, max_storage_buffer_size
-            )
-            .into());
-        }
-
         // Store old buffers for scaling
         let old_trail_map_buffers = std::mem::replace(
             &mut self.trail_map_buffers,
@@ -774,6 +849,9 @@ impl SlimeMoldModel {
         );
 
         // Update current sizes and dimensions
+        self.trail_map_memory = GpuReservation::new(
+            effective_width as u64 * effective_height as u64 * TRAIL_MAP_BYTES_PER_PIXEL,
+        );
         self.current_trail_map_size = trail_map_size_bytes;
         self.current_mask_buffer_size = trail_map_size_bytes;
         self.current_agent_buffer_size = agent_buffer_size_bytes;
@@ -826,11 +904,6 @@ impl SlimeMoldModel {
         // Resize camera
         self.camera
             .resize(effective_width as f32, effective_height as f32);
-
-        self.post_processing_resources.resize(device, new_config)?;
-
-        tracing::info!("Slime mold resize completed successfully");
-        Ok(())
     }
 
     /// Render a single frame of the simulation
@@ -925,23 +998,30 @@ impl SlimeMoldModel {
         queue.submit(std::iter::once(background_encoder.finish()));
 
         // 2. Generate main simulation content to offscreen texture
-        let mut display_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Slime Mold Display Encoder"),
-        });
-        {
-            let mut compute_pass =
-                display_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                    label: Some("Slime Mold Display Pass"),
-                    timestamp_writes: None,
+        let agent_display_mode = self.settings.agent_display.mode;
+        if agent_display_mode != AgentDisplayMode::AgentsOnly {
+            let mut display_encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Slime Mold Display Encoder"),
                 });
-            compute_pass.set_pipeline(&self.pipeline_manager.display_pipeline);
-            compute_pass.set_bind_group(0, &self.bind_group_manager.display_bind_group, &[]);
-            let (workgroups_x, workgroups_y) = self
-                .workgroup_config
-                .workgroups_2d(self.display_texture.width(), self.display_texture.height());
-            compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+            {
+                let mut compute_pass =
+                    display_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                        label: Some("Slime Mold Display Pass"),
+                        timestamp_writes: None,
+                    });
+                compute_pass.set_pipeline(&self.pipeline_manager.display_pipeline);
+                compute_pass.set_bind_group(0, &self.bind_group_manager.display_bind_group, &[]);
+                let (workgroups_x, workgroups_y) = self
+                    .workgroup_config
+                    .workgroups_2d(self.display_texture.width(), self.display_texture.height());
+                compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+            }
+            queue.submit(std::iter::once(display_encoder.finish()));
+        }
+        if agent_display_mode != AgentDisplayMode::Hidden {
+            self.render_agents(device, queue);
         }
-        queue.submit(std::iter::once(display_encoder.finish()));
 
         // 2. Render offscreen texture to surface with infinite tiling
         let tile_count = self.calculate_tile_count();
@@ -987,6 +1067,44 @@ impl SlimeMoldModel {
         Ok(())
     }
 
+    /// Draw the agents over the display texture
+    fn render_agents(&self, device: &Arc<Device>, queue: &Arc<Queue>) {
+        queue.write_buffer(
+            &self.agent_render_params_buffer,
+            0,
+            bytemuck::bytes_of(&AgentRenderParams::new(
+                &self.settings,
+                self.current_width,
+                self.current_height,
+            )),
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Slime Mold Agent Render Encoder"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Slime Mold Agent Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.display_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&self.pipeline_manager.agent_render_pipeline);
+            render_pass.set_bind_group(0, &self.bind_group_manager.agent_render_bind_group, &[]);
+            render_pass.draw(0..6, 0..self.agent_count as u32);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
     /// Run the compute passes for the simulation
     fn run_compute_passes(&mut self, encoder: &mut wgpu::CommandEncoder) {
         // Mask pass (if enabled)
@@ -1096,16 +1214,79 @@ impl SlimeMoldModel {
         );
     }
 
-    /// Reset agents to random positions using GPU compute shader
-    pub fn reset_agents(
+    /// Read the trail map back from the GPU as an image, one pixel per cell
+    pub fn export_trail_map(
+        &self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<FieldImage> {
+        let trail_map = self.trail_map_buffers.current_buffer();
+        let size =
+            (self.current_width * self.current_height) as u64 * std::mem::size_of::<f32>() as u64;
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Trail Map Staging Buffer"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Trail Map Export Encoder"),
+        });
+        encoder.copy_buffer_to_buffer(trail_map, 0, &staging_buffer, 0, size);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = staging_buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+        device
+            .poll(wgpu::wgt::PollType::Wait)
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+        receiver
+            .recv()
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?
+            .map_err(|e| SimulationError::Gpu(Box::new(e)))?;
+
+        let image = {
+            let data = buffer_slice.get_mapped_range();
+            field_to_image(
+                self.current_width,
+                self.current_height,
+                bytemuck::cast_slice(&data),
+            )
+        };
+        staging_buffer.unmap();
+        Ok(image)
+    }
+
+    /// Replace the trail map with an image stretched to the grid
+    pub fn import_trail_map(&self, queue: &Arc<Queue>, image: &image::DynamicImage) {
+        let trail_map = image_to_field(image, self.current_width, self.current_height);
+        queue.write_buffer(
+            self.trail_map_buffers.current_buffer(),
+            0,
+            bytemuck::cast_slice(&trail_map),
+        );
+    }
+
+    /// Place the agents afresh with a new random seed
+    pub fn randomize_agents(
         &mut self,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
-        // Update the random seed to ensure different randomization
         self.settings.random_seed = rand::random::<u32>();
+        self.reset_agents(device, queue)
+    }
 
-        // Update the sim size buffer with the new random seed
+    /// Place the agents again from the stored seed, so the same seed always
+    /// gives the same start
+    pub fn reset_agents(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // The reset shader derives each agent from the seed in the sim size buffer
         let sim_size = SimSizeUniform::new(
             self.current_width,
             self.current_height,
@@ -1462,6 +1643,26 @@ impl SlimeMoldModel {
                     self.update_display_sampler(device);
                 }
             }
+            "grid_resolution" => {
+                let grid_resolution =
+                    serde_json::from_value(value).map_err(|e| SimulationError::InvalidSetting {
+                        setting_name: setting_name.to_string(),
+                        message: e.to_string(),
+                    })?;
+                self.set_grid_resolution(device, queue, grid_resolution)?;
+            }
+            "agent_display" => {
+                let invalid = |message: String| SimulationError::InvalidSetting {
+                    setting_name: setting_name.to_string(),
+                    message,
+                };
+                let agent_display: AgentDisplay =
+                    serde_json::from_value(value).map_err(|e| invalid(e.to_string()))?;
+                agent_display
+                    .validate()
+                    .map_err(|e| invalid(e.to_string()))?;
+                self.settings.agent_display = agent_display;
+            }
             _ => {
                 return Err(format!("Unknown setting: {}", setting_name).into());
             }
@@ -1489,7 +1690,14 @@ impl SlimeMoldModel {
         queue: &Arc<Queue>,
         surface_config: &SurfaceConfiguration,
     ) -> SimulationResult<()> {
-        self.agent_count = count as usize;
+        self.agent_count = gpu_budget::fit_count(
+            device,
+            "Slime mold agents",
+            count as usize,
+            AGENT_SIZE_BYTES,
+            1,
+            &self.agent_memory,
+        );
 
         // Recreate the agent buffer with new count
         let agent_buffer_size_bytes = (self.agent_count * 4 * std::mem::size_of::<f32>()) as u64;
@@ -1513,8 +1721,10 @@ impl SlimeMoldModel {
         );
 
         // Create new agent buffer with new count
-        let physical_width = surface_config.width;
-        let physical_height = surface_config.height;
+        let (physical_width, physical_height) = self
+            .settings
+            .grid_resolution
+            .resolve(surface_config.width, surface_config.height);
 
         self.agent_buffer = create_agent_buffer_pooled(
             &mut self.buffer_pool,
@@ -1527,6 +1737,7 @@ impl SlimeMoldModel {
         );
 
         self.current_agent_buffer_size = agent_buffer_size_bytes;
+        self.agent_memory = GpuReservation::new(agent_buffer_size_bytes);
 
         // Recreate bind groups with new agent buffer
         self.recreate_bind_groups(device);
@@ -1546,6 +1757,7 @@ impl SlimeMoldModel {
             &self.pipeline_manager.render_bind_group_layout,
             &self.pipeline_manager.camera_bind_group_layout,
             &self.pipeline_manager.gradient_bind_group_layout,
+            &self.pipeline_manager.agent_render_bind_group_layout,
             &self.agent_buffer,
             self.trail_map_buffers.current_buffer(),
             self.trail_map_buffers.inactive_buffer(),
@@ -1558,6 +1770,7 @@ impl SlimeMoldModel {
             &self.cursor_buffer,
             &self.background_color_buffer,
             &self.average_color_uniform_buffer,
+            &self.agent_render_params_buffer,
         );
     }
 
@@ -1801,14 +2014,23 @@ impl SlimeMoldModel {
     /// Load an external image for position generation, convert to grayscale, fit to current sim size
     pub fn load_position_image_from_path(
         &mut self,
-        _device: &Arc<Device>,
+        device: &Arc<Device>,
         queue: &Arc<Queue>,
         image_path: &str,
     ) -> SimulationResult<()> {
         let img = image::open(image_path).map_err(|e| {
             SimulationError::InvalidParameter(format!("Failed to open image: {}", e))
         })?;
+        self.load_position_image_from_data(device, queue, img)
+    }
 
+    /// Use an already decoded image for position generation
+    pub fn load_position_image_from_data(
+        &mut self,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        img: image::DynamicImage,
+    ) -> SimulationResult<()> {
         // Store original image for reprocessing
         self.position_image_original = Some(img.clone());
 
@@ -2362,11 +2584,14 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
         self.camera.update(0.016); // Assume 60 FPS for now
         self.camera.upload_to_gpu(queue);
 
+        // With agents only the display texture still holds the last frame
+        let redraw_trails = self.settings.agent_display.mode != AgentDisplayMode::AgentsOnly;
+
         // Generate display texture first
         let mut display_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Slime Mold Static Display Encoder"),
         });
-        {
+        if redraw_trails {
             let mut compute_pass =
                 display_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                     label: Some("Slime Mold Static Display Pass"),
@@ -2388,7 +2613,7 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
         // Skip compute passes for simulation - just render current state
 
         // First render to display texture
-        {
+        if redraw_trails {
             let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("Slime Mold Static Display Pass"),
                 timestamp_writes: None,
@@ -2400,6 +2625,13 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
                 .workgroups_2d(self.display_texture.width(), self.display_texture.height());
             compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
         }
+        if redraw_trails && self.settings.agent_display.mode == AgentDisplayMode::OverTrails {
+            queue.submit(std::iter::once(encoder.finish()));
+            self.render_agents(device, queue);
+            encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Slime Mold Static Render Encoder"),
+            });
+        }
 
         // Then render display texture to surface with 3x3 instanced rendering
         {
@@ -2639,6 +2871,14 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
         serde_json::to_value(&self.settings).unwrap_or_else(|_| serde_json::json!({}))
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
     fn get_state(&self) -> serde_json::Value {
         serde_json::json!({
             "agent_count": self.agent_count,
@@ -2751,6 +2991,38 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
         })
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        vec![
+            RewindResource::Buffer(&self.agent_buffer),
+            RewindResource::Buffer(self.trail_map_buffers.current_buffer()),
+            RewindResource::Buffer(self.trail_map_buffers.inactive_buffer()),
+        ]
+    }
+
+    fn health_probes(&self) -> Vec<HealthProbe<'_>> {
+        vec![
+            HealthProbe::vector(
+                "Agent positions",
+                &self.agent_buffer,
+                (AGENT_SIZE_BYTES / std::mem::size_of::<f32>() as u64) as u32,
+                0,
+                HealthCheck::Positions,
+            ),
+            HealthProbe::field("Trail map", self.trail_map_buffers.current_buffer()),
+        ]
+    }
+
+    fn recover_from_instability(
+        &mut self,
+        _issues: &[HealthIssue],
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // The stored seed would lead back to the same state, so scatter the
+        // agents from a new one and let the trail map fade on its own
+        self.randomize_agents(device, queue)
+    }
+
     fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
         // This would need to be implemented with the preset manager
         // For now, we'll return an error indicating it needs to be implemented
@@ -2765,9 +3037,10 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
 
     fn reset_runtime_state(
         &mut self,
-        _device: &Arc<Device>,
+        device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
+        self.reset_agents(device, queue)?;
         self.reset_trails(queue);
         Ok(())
     }
@@ -2793,7 +3066,7 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
     fn apply_settings(
         &mut self,
         settings: serde_json::Value,
-        _device: &Arc<Device>,
+        device: &Arc<Device>,
         queue: &Arc<Queue>,
     ) -> SimulationResult<()> {
         let new_settings: Settings =
@@ -2801,7 +3074,20 @@ impl crate::simulations::traits::Simulation for SlimeMoldModel {
                 setting_name: "settings".to_string(),
                 message: e.to_string(),
             })?;
+        new_settings
+            .agent_display
+            .validate()
+            .map_err(|e| SimulationError::InvalidSetting {
+                setting_name: "agent_display".to_string(),
+                message: e.to_string(),
+            })?;
+        let grid_resolution = new_settings.grid_resolution;
         self.update_settings(new_settings, queue);
+        // A preset saved on a GPU with larger textures falls back to the window
+        if let Err(e) = self.set_grid_resolution(device, queue, grid_resolution) {
+            tracing::warn!("Using the window size for the slime mold grid: {}", e);
+            self.set_grid_resolution(device, queue, GridResolution::Window)?;
+        }
         Ok(())
     }
 
@@ -5,7 +5,9 @@
 //! both the computational correctness and the integration between different
 //! components of the simulation system.
 
-use super::shaders::{BACKGROUND_RENDER_SHADER, COMPUTE_SHADER, DISPLAY_SHADER, QUAD_SHADER};
+use super::shaders::{
+    AGENT_RENDER_SHADER, BACKGROUND_RENDER_SHADER, COMPUTE_SHADER, DISPLAY_SHADER, QUAD_SHADER,
+};
 use super::simulation::{BackgroundParams, SimSizeUniform};
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use std::mem;
@@ -103,6 +105,17 @@ impl SlimeMoldValidator {
         Ok(())
     }
 
+    /// Validates that the Slime Mold agent render shader compiles without errors
+    fn validate_agent_render_shader_compilation(&self) -> Result<(), String> {
+        let _ = self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Slime Mold Agent Render Shader"),
+                source: wgpu::ShaderSource::Wgsl(AGENT_RENDER_SHADER.into()),
+            });
+        Ok(())
+    }
+
     /// Validates that the compute shader can bind to the Rust structs
     fn validate_compute_shader_binding(&self) -> Result<(), String> {
         // Create dummy data - agents are stored as 4 floats each (x, y, angle, speed)
@@ -359,6 +372,9 @@ async fn test_slime_mold_shader_compilation() {
     validator
         .validate_background_render_shader_compilation()
         .expect("Background render shader compilation failed");
+    validator
+        .validate_agent_render_shader_compilation()
+        .expect("Agent render shader compilation failed");
 
     // Print struct sizes for debugging
     validator.print_struct_sizes();
@@ -0,0 +1,85 @@
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+pub mod volume;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::SlimeMold3dModel;
+
+use crate::simulation::preset_manager::{Preset, SlimeMold3dPresetManager};
+
+/// Initialize 3D Slime Mold presets with built-in configurations
+pub fn init_presets(preset_manager: &mut SlimeMold3dPresetManager) {
+    use settings::{Settings, Spawn};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Tangled Web".to_string(),
+        Settings {
+            agent_count: 2_000_000,
+            agent_sensor_distance: 9.0,
+            agent_turn_rate: 0.4,
+            density_threshold: 20.0,
+            color_range: 400.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Coral".to_string(),
+        Settings {
+            spawn: Spawn::Shell,
+            agent_speed: 0.6,
+            agent_sensor_angle: 0.8,
+            agent_sensor_distance: 4.0,
+            decay_rate: 0.02,
+            diffusion_rate: 0.15,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Nebula".to_string(),
+        Settings {
+            spawn: Spawn::Cube,
+            diffusion_rate: 0.7,
+            density: 0.1,
+            density_threshold: 5.0,
+            brightness: 1.5,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Fine Filaments".to_string(),
+        Settings {
+            volume_resolution: 192,
+            agent_speed: 1.5,
+            agent_sensor_angle: 0.3,
+            agent_sensor_distance: 12.0,
+            agent_turn_rate: 0.2,
+            diffusion_rate: 0.1,
+            ray_steps: 320,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Big Bang".to_string(),
+        Settings {
+            spawn: Spawn::Center,
+            agent_speed: 2.0,
+            agent_jitter: 0.02,
+            steps_per_frame: 2,
+            auto_rotate: 0.2,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,221 @@
+//! # 3D Slime Mold Settings Module
+//!
+//! How many agents roam the volume and how they sense, turn and lay down
+//! trail, how the trail spreads and fades, and how the raymarched volume
+//! looks.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Where the agents start out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Spawn {
+    /// Scattered through a ball at the middle of the volume
+    #[default]
+    Sphere,
+    /// On the surface of that ball, heading inward
+    Shell,
+    /// Scattered through the whole volume
+    Cube,
+    /// All at the center, heading every which way
+    Center,
+}
+
+impl FromStr for Spawn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sphere" => Ok(Spawn::Sphere),
+            "shell" => Ok(Spawn::Shell),
+            "cube" => Ok(Spawn::Cube),
+            "center" => Ok(Spawn::Center),
+            _ => Err(format!(
+                "Invalid Spawn: '{}'. Expected 'Sphere', 'Shell', 'Cube', or 'Center'",
+                s
+            )),
+        }
+    }
+}
+
+impl From<Spawn> for u32 {
+    fn from(spawn: Spawn) -> Self {
+        match spawn {
+            Spawn::Sphere => 0,
+            Spawn::Shell => 1,
+            Spawn::Cube => 2,
+            Spawn::Center => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Agents roaming the volume; fewer are used if the GPU can't hold them
+    pub agent_count: u32,
+    /// Voxels along each edge of the cubic trail volume
+    pub volume_resolution: u32,
+    pub spawn: Spawn,
+    pub random_seed: u32,
+    /// Simulation steps each frame
+    pub steps_per_frame: u32,
+
+    /// Voxels an agent moves each step
+    pub agent_speed: f32,
+    /// Radians between an agent's heading and its side sensors
+    pub agent_sensor_angle: f32,
+    /// How far ahead the sensors reach, in voxels
+    pub agent_sensor_distance: f32,
+    /// Radians an agent turns toward a stronger scent each step
+    pub agent_turn_rate: f32,
+    /// Radians of random wobble added to each turn
+    pub agent_jitter: f32,
+
+    /// Trail each agent lays down each step
+    pub deposit: f32,
+    /// Share of the trail lost each step
+    pub decay_rate: f32,
+    /// How far each step the trail blurs into its neighbors, 0 to 1
+    pub diffusion_rate: f32,
+
+    /// How quickly the trail hides what's behind it
+    pub density: f32,
+    /// Trail fainter than this is left out, clearing the haze between
+    /// strands
+    pub density_threshold: f32,
+    /// Trail at the top of the color scheme
+    pub color_range: f32,
+    pub brightness: f32,
+    /// Samples taken along each ray through the volume
+    pub ray_steps: u32,
+    /// Radians a second the camera circles the volume on its own
+    pub auto_rotate: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            agent_count: 1_000_000,
+            volume_resolution: 128,
+            spawn: Spawn::Sphere,
+            random_seed: 0,
+            steps_per_frame: 1,
+            agent_speed: 1.0,
+            agent_sensor_angle: 0.5,
+            agent_sensor_distance: 6.0,
+            agent_turn_rate: 0.3,
+            agent_jitter: 0.05,
+            deposit: 1.0,
+            decay_rate: 0.05,
+            diffusion_rate: 0.3,
+            density: 0.3,
+            density_threshold: 10.0,
+            color_range: 200.0,
+            brightness: 1.0,
+            ray_steps: 192,
+            auto_rotate: 0.1,
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "agent_count",
+            Rule::Count {
+                min: 1_000,
+                max: 8_000_000,
+            },
+        ),
+        ("volume_resolution", Rule::Count { min: 32, max: 256 }),
+        ("spawn", Rule::OneOf(&["Sphere", "Shell", "Cube", "Center"])),
+        ("steps_per_frame", Rule::Count { min: 1, max: 8 }),
+        (
+            "agent_speed",
+            Rule::Range {
+                min: 0.05,
+                max: 5.0,
+            },
+        ),
+        (
+            "agent_sensor_angle",
+            Rule::Range {
+                min: 0.05,
+                max: 1.5,
+            },
+        ),
+        (
+            "agent_sensor_distance",
+            Rule::Range {
+                min: 1.0,
+                max: 32.0,
+            },
+        ),
+        ("agent_turn_rate", Rule::Range { min: 0.0, max: 1.5 }),
+        ("agent_jitter", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "deposit",
+            Rule::Range {
+                min: 0.01,
+                max: 10.0,
+            },
+        ),
+        ("decay_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        ("diffusion_rate", Rule::Range { min: 0.0, max: 1.0 }),
+        (
+            "density",
+            Rule::Range {
+                min: 0.01,
+                max: 20.0,
+            },
+        ),
+        (
+            "density_threshold",
+            Rule::Range {
+                min: 0.0,
+                max: 100.0,
+            },
+        ),
+        (
+            "color_range",
+            Rule::Range {
+                min: 0.1,
+                max: 1000.0,
+            },
+        ),
+        (
+            "brightness",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        ("ray_steps", Rule::Count { min: 32, max: 1024 }),
+        (
+            "auto_rotate",
+            Rule::Range {
+                min: -2.0,
+                max: 2.0,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("volume_resolution", SettingCategory::Generators),
+    ("spawn", SettingCategory::Generators),
+    ("random_seed", SettingCategory::Generators),
+    ("density", SettingCategory::Colors),
+    ("density_threshold", SettingCategory::Colors),
+    ("color_range", SettingCategory::Colors),
+    ("brightness", SettingCategory::Colors),
+    ("ray_steps", SettingCategory::Colors),
+]);
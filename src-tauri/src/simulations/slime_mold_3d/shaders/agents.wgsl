@@ -0,0 +1,138 @@
+// Spawns the agents and moves them on. Each step an agent smells the trail
+// ahead of it and on a cone around its heading, turns toward the strongest
+// scent, moves forward and adds to the trail where it lands.
+
+// Agents are dispatched in rows of workgroups, as a million of them need
+// more workgroups than one row allows
+fn agent_index(id: vec3<u32>, workgroups: vec3<u32>) -> u32 {
+    return id.y * workgroups.x * 64u + id.x;
+}
+
+@compute @workgroup_size(64)
+fn init_agents(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+    let index = agent_index(id, workgroups);
+    if index >= params.agent_count {
+        return;
+    }
+    var seed = pcg(index ^ pcg(params.seed));
+    let size = f32(params.resolution);
+    let center = vec3<f32>(size * 0.5);
+    // Radius of the ball the sphere and shell spawns fill
+    let radius = size * 0.3;
+    let direction = random_direction(&seed);
+
+    var agent: Agent;
+    switch params.spawn {
+        // Shell
+        case 1u: {
+            agent.position = center + direction * radius;
+            agent.heading = -direction;
+        }
+        // Cube
+        case 2u: {
+            agent.position = vec3<f32>(random(&seed), random(&seed), random(&seed)) * size;
+            agent.heading = random_direction(&seed);
+        }
+        // Center
+        case 3u: {
+            agent.position = center + direction * 0.5;
+            agent.heading = direction;
+        }
+        // Sphere, spread evenly through the ball
+        default: {
+            agent.position = center + direction * radius * pow(random(&seed), 1.0 / 3.0);
+            agent.heading = random_direction(&seed);
+        }
+    }
+    agents[index] = agent;
+}
+
+// Trail at a point in voxels; outside the volume there's none
+fn sense(position: vec3<f32>) -> f32 {
+    let size = f32(params.resolution);
+    if any(position < vec3<f32>(0.0)) || any(position >= vec3<f32>(size)) {
+        return 0.0;
+    }
+    return textureLoad(trail_in, vec3<i32>(position), 0).r;
+}
+
+// `current` turned toward `wanted` by at most `angle`, both unit length
+fn turn_toward(current: vec3<f32>, wanted: vec3<f32>, angle: f32) -> vec3<f32> {
+    let cos_between = clamp(dot(current, wanted), -1.0, 1.0);
+    if acos(cos_between) <= angle {
+        return wanted;
+    }
+    let across = normalize(wanted - current * cos_between);
+    return current * cos(angle) + across * sin(angle);
+}
+
+@compute @workgroup_size(64)
+fn update_agents(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+    let index = agent_index(id, workgroups);
+    if index >= params.agent_count {
+        return;
+    }
+    var agent = agents[index];
+    var seed = pcg(index * 1664525u + pcg(params.step ^ params.seed));
+    let heading = agent.heading;
+
+    // Two directions square to the heading, spun by a random angle each step
+    // so the side sensors don't favor any way round
+    let helper = select(vec3<f32>(0.0, 1.0, 0.0), vec3<f32>(1.0, 0.0, 0.0), abs(heading.y) > 0.9);
+    let side_u = normalize(cross(heading, helper));
+    let side_v = cross(heading, side_u);
+    let spin = random(&seed) * TAU;
+
+    var strongest = sense(agent.position + heading * params.sensor_distance);
+    var wanted = heading;
+    for (var i = 0u; i < 3u; i++) {
+        let around = spin + f32(i) * TAU / 3.0;
+        let side = side_u * cos(around) + side_v * sin(around);
+        let direction = heading * cos(params.sensor_angle) + side * sin(params.sensor_angle);
+        let scent = sense(agent.position + direction * params.sensor_distance);
+        if scent > strongest {
+            strongest = scent;
+            wanted = direction;
+        }
+    }
+    var new_heading = turn_toward(heading, wanted, params.turn_rate);
+    new_heading = normalize(new_heading + random_direction(&seed) * params.jitter);
+
+    if params.cursor_mode != 0u {
+        let offset = params.cursor - agent.position;
+        let distance = length(offset);
+        if distance < params.cursor_radius && distance > 1e-3 {
+            let toward = select(-1.0, 1.0, params.cursor_mode == 1u);
+            let falloff = 1.0 - distance / params.cursor_radius;
+            let pull = offset / distance * toward * params.cursor_strength * falloff;
+            new_heading = normalize(new_heading + pull);
+        }
+    }
+
+    // Bounce off the walls of the volume
+    let size = f32(params.resolution);
+    var position = agent.position + new_heading * params.speed;
+    for (var axis = 0; axis < 3; axis++) {
+        if position[axis] < 0.0 {
+            position[axis] = -position[axis];
+            new_heading[axis] = abs(new_heading[axis]);
+        } else if position[axis] >= size {
+            position[axis] = 2.0 * size - position[axis] - 1e-3;
+            new_heading[axis] = -abs(new_heading[axis]);
+        }
+    }
+    position = clamp(position, vec3<f32>(0.0), vec3<f32>(size - 1e-3));
+
+    agent.position = position;
+    agent.heading = new_heading;
+    agents[index] = agent;
+
+    let voxel = vec3<u32>(position);
+    atomicAdd(&deposits[voxel_index(voxel)], u32(params.deposit * DEPOSIT_SCALE));
+}
@@ -0,0 +1,69 @@
+// Shared by the agent and trail passes. The trail is a cube of voxels
+// `resolution` across, and agents keep their positions in voxels of it.
+
+struct Params {
+    resolution: u32,
+    agent_count: u32,
+    seed: u32,
+    // Steps taken since the agents were spawned
+    step: u32,
+    // In voxels a step
+    speed: f32,
+    sensor_angle: f32,
+    sensor_distance: f32,
+    turn_rate: f32,
+    jitter: f32,
+    deposit: f32,
+    // Share of the trail kept through a step
+    keep: f32,
+    diffusion: f32,
+    spawn: u32,
+    // 0 inactive, 1 attracting, 2 repelling
+    cursor_mode: u32,
+    // In voxels
+    cursor_radius: f32,
+    cursor_strength: f32,
+    cursor: vec3<f32>,
+    _pad0: f32,
+}
+
+struct Agent {
+    position: vec3<f32>,
+    _pad0: f32,
+    // Unit length
+    heading: vec3<f32>,
+    _pad1: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> agents: array<Agent>;
+@group(0) @binding(2) var trail_in: texture_3d<f32>;
+// Trail laid down this step, in fixed point so agents can add to it at once
+@group(0) @binding(3) var<storage, read_write> deposits: array<atomic<u32>>;
+@group(0) @binding(4) var trail_out: texture_storage_3d<r32float, write>;
+
+const TAU: f32 = 6.28318530718;
+const DEPOSIT_SCALE: f32 = 256.0;
+
+fn pcg(value: u32) -> u32 {
+    let state = value * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+// Uniform in 0 to 1, moving the seed on
+fn random(seed: ptr<function, u32>) -> f32 {
+    *seed = pcg(*seed);
+    return f32(*seed) / 4294967295.0;
+}
+
+fn random_direction(seed: ptr<function, u32>) -> vec3<f32> {
+    let z = random(seed) * 2.0 - 1.0;
+    let angle = random(seed) * TAU;
+    let ring = sqrt(max(1.0 - z * z, 0.0));
+    return vec3<f32>(ring * cos(angle), ring * sin(angle), z);
+}
+
+fn voxel_index(voxel: vec3<u32>) -> u32 {
+    return (voxel.z * params.resolution + voxel.y) * params.resolution + voxel.x;
+}
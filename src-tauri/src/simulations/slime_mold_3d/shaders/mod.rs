@@ -0,0 +1,6 @@
+pub const AGENTS_SHADER: &str = concat!(include_str!("common.wgsl"), include_str!("agents.wgsl"));
+pub const TRAIL_SHADER: &str = concat!(include_str!("common.wgsl"), include_str!("trail.wgsl"));
+pub const RENDER_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("render.wgsl")
+);
@@ -0,0 +1,113 @@
+// Raymarches the trail volume, the cube from -1 to 1 on every axis, from
+// front to back: every sample glows in the color scheme's color for its
+// strength and hides what's behind it by its density.
+
+struct RenderParams {
+    eye: vec3<f32>,
+    // Voxels along each edge
+    resolution: f32,
+    // A pixel's ray heads along forward + ndc.x * right + ndc.y * up
+    right: vec3<f32>,
+    density: f32,
+    up: vec3<f32>,
+    density_threshold: f32,
+    forward: vec3<f32>,
+    color_range: f32,
+    brightness: f32,
+    steps: u32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: RenderParams;
+@group(0) @binding(1) var trail: texture_3d<f32>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // One triangle covering the screen
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    var output: VertexOutput;
+    output.position = vec4<f32>(ndc, 0.0, 1.0);
+    output.ndc = ndc;
+    return output;
+}
+
+// Where a ray enters and leaves the volume; entry past exit means a miss
+fn box_span(origin: vec3<f32>, direction: vec3<f32>) -> vec2<f32> {
+    let inverse = 1.0 / direction;
+    let a = (vec3<f32>(-1.0) - origin) * inverse;
+    let b = (vec3<f32>(1.0) - origin) * inverse;
+    let near = min(a, b);
+    let far = max(a, b);
+    return vec2<f32>(max(max(max(near.x, near.y), near.z), 0.0), min(min(far.x, far.y), far.z));
+}
+
+// The trail blended between the eight voxels around a point in voxels
+fn sample_trail(position: vec3<f32>) -> f32 {
+    let last = vec3<i32>(i32(params.resolution) - 1);
+    let shifted = position - 0.5;
+    let base = floor(shifted);
+    let blend = shifted - base;
+    let corner = vec3<i32>(base);
+    var total = 0.0;
+    for (var i = 0; i < 8; i++) {
+        let offset = vec3<i32>(i & 1, (i >> 1u) & 1, (i >> 2u) & 1);
+        let voxel = clamp(corner + offset, vec3<i32>(0), last);
+        let weights = select(1.0 - blend, blend, vec3<bool>(offset == vec3<i32>(1)));
+        total += textureLoad(trail, voxel, 0).r * weights.x * weights.y * weights.z;
+    }
+    return total;
+}
+
+fn hash(pixel: vec2<u32>) -> f32 {
+    var value = pixel.x * 1973u + pixel.y * 9277u;
+    value = (value ^ (value >> 16u)) * 0x45d9f3bu;
+    value = value ^ (value >> 16u);
+    return f32(value & 0xffffu) / 65535.0;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let direction = normalize(params.forward + params.right * input.ndc.x + params.up * input.ndc.y);
+    let span = box_span(params.eye, direction);
+    if span.x >= span.y {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    // Steps are the same length whichever way the ray crosses, starting a
+    // little way along at random so the samples don't band
+    let step = 2.0 * sqrt(3.0) / f32(max(params.steps, 1u));
+    var t = span.x + step * hash(vec2<u32>(input.position.xy));
+    var color = vec3<f32>(0.0);
+    var transmittance = 1.0;
+    loop {
+        if t >= span.y || transmittance < 0.01 {
+            break;
+        }
+        let point = params.eye + direction * t;
+        let strength = sample_trail((point * 0.5 + 0.5) * params.resolution);
+        let dense = max(strength - params.density_threshold, 0.0);
+        let alpha = 1.0 - exp(-dense * params.density * step);
+        let glow = lut_color(strength / params.color_range) * params.brightness;
+        color += transmittance * alpha * glow;
+        transmittance *= 1.0 - alpha;
+        t += step;
+    }
+    return vec4<f32>(color, 1.0);
+}
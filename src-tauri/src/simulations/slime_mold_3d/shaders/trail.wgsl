@@ -0,0 +1,27 @@
+// Spreads and fades the trail: each voxel blurs toward the average of its
+// neighborhood, takes in what the agents laid down this step and loses a
+// share of the lot.
+
+@compute @workgroup_size(4, 4, 4)
+fn diffuse(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = params.resolution;
+    if any(id >= vec3<u32>(size)) {
+        return;
+    }
+    let voxel = vec3<i32>(id);
+    let last = vec3<i32>(i32(size) - 1);
+    var total = 0.0;
+    for (var z = -1; z <= 1; z++) {
+        for (var y = -1; y <= 1; y++) {
+            for (var x = -1; x <= 1; x++) {
+                let neighbor = clamp(voxel + vec3<i32>(x, y, z), vec3<i32>(0), last);
+                total += textureLoad(trail_in, neighbor, 0).r;
+            }
+        }
+    }
+    let here = textureLoad(trail_in, voxel, 0).r;
+    let blurred = mix(here, total / 27.0, params.diffusion);
+    let deposited = f32(atomicExchange(&deposits[voxel_index(id)], 0u)) / DEPOSIT_SCALE;
+    let trail = (blurred + deposited) * params.keep;
+    textureStore(trail_out, voxel, vec4<f32>(trail, 0.0, 0.0, 0.0));
+}
@@ -0,0 +1,809 @@
+//! # 3D Slime Mold Simulation Module
+//!
+//! Physarum agents roaming a cube instead of a plane. Each agent smells the
+//! trail ahead of it and around a cone on its heading, turns toward the
+//! strongest scent and lays down more trail as it goes, so the agents pull
+//! together into a branching web of tubes that threads through the volume.
+//!
+//! ## Technical Overview
+//!
+//! The trail is a cubic `R32Float` 3D texture, ping-ponged between two
+//! copies. Each simulation step runs two compute passes:
+//! 1. The agent pass senses the current trail, turns and moves each agent,
+//!    bouncing it off the walls, and adds its deposit to a buffer of
+//!    fixed point atomics, one per voxel.
+//! 2. The trail pass blurs every voxel toward its neighborhood, takes in
+//!    and clears the deposits, fades the result and writes it to the other
+//!    copy of the trail.
+//!
+//! The trail is then raymarched from the orbit camera, front to back,
+//! glowing in the color scheme by strength and hiding what's behind it by
+//! density. Dragging turns the camera around the volume, and clicking lures
+//! agents toward, or with the right button away from, the point where the
+//! cursor's ray passes nearest the middle.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferDescriptor, BufferUsages, ComputePipeline,
+    Device, PipelineLayoutDescriptor, Queue, RenderPipeline, ShaderModule, ShaderStages,
+    SurfaceConfiguration, TextureFormat, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::gpu_budget;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::orbit_camera::OrbitCamera;
+use crate::simulations::shared::{ColorScheme, ColorSchemeManager, GpuReservation, ParticleBuffer};
+use crate::simulations::traits::Simulation;
+
+use super::settings::Settings;
+use super::shaders::{AGENTS_SHADER, RENDER_SHADER, TRAIL_SHADER};
+use super::state::State;
+use super::volume::{Agent, agent_dispatch, cursor_point, edge_for_voxels, to_voxels};
+
+const TRAIL_FORMAT: TextureFormat = TextureFormat::R32Float;
+
+/// Two copies of the trail and the deposit buffer, each four bytes a voxel
+const VOLUME_COPIES: u64 = 3;
+
+/// Voxels each workgroup of the trail pass covers along each axis
+const TRAIL_WORKGROUP_SIZE: u32 = 4;
+
+/// Reach of the cursor's lure, as a share of the volume's edge
+const CURSOR_RADIUS: f32 = 0.2;
+/// How hard the cursor pulls agents round, against their unit heading
+const CURSOR_STRENGTH: f32 = 0.5;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    resolution: u32,
+    agent_count: u32,
+    seed: u32,
+    step: u32,
+    speed: f32,
+    sensor_angle: f32,
+    sensor_distance: f32,
+    turn_rate: f32,
+    jitter: f32,
+    deposit: f32,
+    keep: f32,
+    diffusion: f32,
+    spawn: u32,
+    cursor_mode: u32,
+    cursor_radius: f32,
+    cursor_strength: f32,
+    cursor: [f32; 3],
+    _pad0: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct RenderParams {
+    eye: [f32; 3],
+    resolution: f32,
+    right: [f32; 3],
+    density: f32,
+    up: [f32; 3],
+    density_threshold: f32,
+    forward: [f32; 3],
+    color_range: f32,
+    brightness: f32,
+    steps: u32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+/// Everything sized by the volume's resolution, and the bind groups over it
+/// and the agents, made again whenever either changes
+#[derive(Debug)]
+struct Volume {
+    resolution: u32,
+    agent_count: u32,
+    // The first reads the first trail and writes the second, the other the
+    // other way round
+    compute_bind_groups: [BindGroup; 2],
+    // Each shows its trail
+    render_bind_groups: [BindGroup; 2],
+    memory: GpuReservation,
+}
+
+impl Volume {
+    /// Sized to fit the GPU, `replacing` being the volume it's made to
+    /// take over from
+    fn new(
+        device: &Arc<Device>,
+        layouts: &Layouts,
+        agents: &ParticleBuffer<Agent>,
+        agent_count: u32,
+        requested_resolution: u32,
+        replacing: &GpuReservation,
+    ) -> Self {
+        let requested_voxels = (requested_resolution as usize).pow(3);
+        let voxels = gpu_budget::fit_count(
+            device,
+            "3D slime mold volume",
+            requested_voxels,
+            std::mem::size_of::<f32>() as u64,
+            VOLUME_COPIES,
+            replacing,
+        );
+        let resolution = edge_for_voxels(voxels)
+            .min(device.limits().max_texture_dimension_3d)
+            .max(1);
+        let voxels = (resolution as u64).pow(3);
+        let memory =
+            GpuReservation::new(voxels * std::mem::size_of::<f32>() as u64 * VOLUME_COPIES);
+
+        let deposit_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("3D Slime Mold Deposit Buffer"),
+            size: voxels * std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let trail_views = [0, 1].map(|i| create_trail_view(device, resolution, i));
+
+        let compute_bind_groups = [0, 1].map(|i| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(&format!("3D Slime Mold Compute Bind Group {}", i)),
+                layout: &layouts.compute,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &layouts.params_buffer),
+                    resource_helpers::buffer_entry(1, agents.buffer()),
+                    resource_helpers::texture_view_entry(2, &trail_views[i]),
+                    resource_helpers::buffer_entry(3, &deposit_buffer),
+                    resource_helpers::texture_view_entry(4, &trail_views[1 - i]),
+                ],
+            })
+        });
+        let render_bind_groups = [0, 1].map(|i| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(&format!("3D Slime Mold Render Bind Group {}", i)),
+                layout: &layouts.render,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &layouts.render_params_buffer),
+                    resource_helpers::texture_view_entry(1, &trail_views[i]),
+                    resource_helpers::buffer_entry(2, &layouts.lut_buffer),
+                ],
+            })
+        });
+
+        Self {
+            resolution,
+            agent_count,
+            compute_bind_groups,
+            render_bind_groups,
+            memory,
+        }
+    }
+}
+
+/// The layouts and buffers a [`Volume`] binds, which outlive it
+#[derive(Debug)]
+struct Layouts {
+    compute: BindGroupLayout,
+    render: BindGroupLayout,
+    params_buffer: Buffer,
+    render_params_buffer: Buffer,
+    lut_buffer: Buffer,
+}
+
+#[derive(Debug)]
+pub struct SlimeMold3dModel {
+    pub settings: Settings,
+    pub state: State,
+
+    // GPU resources
+    init_pipeline: ComputePipeline,
+    update_pipeline: ComputePipeline,
+    diffuse_pipeline: ComputePipeline,
+    render_pipeline: RenderPipeline,
+    layouts: Layouts,
+    agents: ParticleBuffer<Agent>,
+    volume: Volume,
+    // Which copy of the trail is the latest
+    current_trail: usize,
+    // The agents want spawning before the next step
+    respawn: bool,
+
+    // 0 inactive, 1 attracting, 2 repelling
+    cursor_mode: u32,
+    // In voxels
+    cursor: [f32; 3],
+
+    pub camera: OrbitCamera,
+}
+
+impl SlimeMold3dModel {
+    pub fn new(
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+        let camera = OrbitCamera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        );
+
+        let agents_module = create_module(device, "3D Slime Mold Agents Shader", AGENTS_SHADER);
+        let trail_module = create_module(device, "3D Slime Mold Trail Shader", TRAIL_SHADER);
+        let render_module = create_module(device, "3D Slime Mold Render Shader", RENDER_SHADER);
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("3D Slime Mold Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let render_params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("3D Slime Mold Render Params Buffer"),
+            size: std::mem::size_of::<RenderParams>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "3D Slime Mold LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let trail_texture = wgpu::TextureSampleType::Float { filterable: false };
+        let compute = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("3D Slime Mold Compute Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+                resource_helpers::texture_entry(
+                    2,
+                    ShaderStages::COMPUTE,
+                    trail_texture,
+                    wgpu::TextureViewDimension::D3,
+                ),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, false),
+                // The shared helper only makes 2D storage textures
+                BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: TRAIL_FORMAT,
+                        view_dimension: wgpu::TextureViewDimension::D3,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let render = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("3D Slime Mold Render Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::FRAGMENT),
+                resource_helpers::texture_entry(
+                    1,
+                    ShaderStages::FRAGMENT,
+                    trail_texture,
+                    wgpu::TextureViewDimension::D3,
+                ),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::FRAGMENT, true),
+            ],
+        });
+
+        let compute_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("3D Slime Mold Compute Pipeline Layout"),
+            bind_group_layouts: &[&compute],
+            push_constant_ranges: &[],
+        });
+        let compute_pipeline = |label: &str, module: &ShaderModule, entry_point: &str| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&compute_layout),
+                module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let init_pipeline =
+            compute_pipeline("3D Slime Mold Init Pipeline", &agents_module, "init_agents");
+        let update_pipeline = compute_pipeline(
+            "3D Slime Mold Update Pipeline",
+            &agents_module,
+            "update_agents",
+        );
+        let diffuse_pipeline =
+            compute_pipeline("3D Slime Mold Diffuse Pipeline", &trail_module, "diffuse");
+        let render_pipeline =
+            create_render_pipeline(device, &render, &render_module, surface_config.format);
+
+        let layouts = Layouts {
+            compute,
+            render,
+            params_buffer,
+            render_params_buffer,
+            lut_buffer,
+        };
+        let mut agents = ParticleBuffer::uninitialized(
+            device,
+            "3D Slime Mold Agent Buffer",
+            BufferUsages::empty(),
+            1,
+        );
+        let agent_count = agents.reallocate(device, settings.agent_count as usize) as u32;
+        let volume = Volume::new(
+            device,
+            &layouts,
+            &agents,
+            agent_count,
+            settings.volume_resolution,
+            &GpuReservation::default(),
+        );
+
+        Ok(Self {
+            state: State {
+                agent_count: volume.agent_count,
+                ..state
+            },
+            settings,
+            init_pipeline,
+            update_pipeline,
+            diffuse_pipeline,
+            render_pipeline,
+            layouts,
+            agents,
+            volume,
+            current_trail: 0,
+            respawn: true,
+            cursor_mode: 0,
+            cursor: [0.0; 3],
+            camera,
+        })
+    }
+
+    /// Make the volume again at the current settings' size, empty, and
+    /// spawn the agents into it. New textures and buffers start out zeroed,
+    /// which is the quickest way to clear them.
+    fn rebuild_volume(&mut self, device: &Arc<Device>) {
+        let agent_count =
+            self.agents
+                .reallocate(device, self.settings.agent_count as usize) as u32;
+        self.volume = Volume::new(
+            device,
+            &self.layouts,
+            &self.agents,
+            agent_count,
+            self.settings.volume_resolution,
+            &self.volume.memory,
+        );
+        self.state.agent_count = self.volume.agent_count;
+        self.state.steps = 0;
+        self.current_trail = 0;
+        self.respawn = true;
+    }
+
+    fn params(&self) -> Params {
+        let settings = &self.settings;
+        let resolution = self.volume.resolution;
+        Params {
+            resolution,
+            agent_count: self.volume.agent_count,
+            seed: settings.random_seed,
+            step: self.state.steps as u32,
+            speed: settings.agent_speed,
+            sensor_angle: settings.agent_sensor_angle,
+            sensor_distance: settings.agent_sensor_distance,
+            turn_rate: settings.agent_turn_rate,
+            jitter: settings.agent_jitter,
+            deposit: settings.deposit,
+            keep: 1.0 - settings.decay_rate.clamp(0.0, 1.0),
+            diffusion: settings.diffusion_rate.clamp(0.0, 1.0),
+            spawn: settings.spawn.into(),
+            cursor_mode: self.cursor_mode,
+            cursor_radius: CURSOR_RADIUS * resolution as f32,
+            cursor_strength: CURSOR_STRENGTH,
+            cursor: self.cursor,
+            _pad0: 0.0,
+        }
+    }
+
+    /// Move the agents on and spread the trail, `steps` times over
+    fn step(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, steps: u32) {
+        let [agent_x, agent_y] = agent_dispatch(self.volume.agent_count);
+        let voxel_groups = self.volume.resolution.div_ceil(TRAIL_WORKGROUP_SIZE);
+        for _ in 0..steps {
+            // Each step's params are written before its own submission, so
+            // every step sees its own step count
+            let params = self.params();
+            queue.write_buffer(&self.layouts.params_buffer, 0, bytemuck::bytes_of(&params));
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("3D Slime Mold Step"),
+            });
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("3D Slime Mold Compute Pass"),
+                    timestamp_writes: None,
+                });
+                pass.set_bind_group(0, &self.volume.compute_bind_groups[self.current_trail], &[]);
+                if self.respawn {
+                    pass.set_pipeline(&self.init_pipeline);
+                    pass.dispatch_workgroups(agent_x, agent_y, 1);
+                    self.respawn = false;
+                }
+                pass.set_pipeline(&self.update_pipeline);
+                pass.dispatch_workgroups(agent_x, agent_y, 1);
+                pass.set_pipeline(&self.diffuse_pipeline);
+                pass.dispatch_workgroups(voxel_groups, voxel_groups, voxel_groups);
+            }
+            queue.submit([encoder.finish()]);
+            self.current_trail = 1 - self.current_trail;
+            self.state.steps += 1;
+        }
+    }
+
+    fn draw(&self, device: &Arc<Device>, queue: &Arc<Queue>, surface_view: &TextureView) {
+        let view = self.camera.view;
+        let [right, up, forward] = view.ray_basis(self.camera.aspect_ratio());
+        let render_params = RenderParams {
+            eye: view.eye(),
+            resolution: self.volume.resolution as f32,
+            right,
+            density: self.settings.density,
+            up,
+            density_threshold: self.settings.density_threshold,
+            forward,
+            color_range: self.settings.color_range.max(1e-3),
+            brightness: self.settings.brightness,
+            steps: self.settings.ray_steps,
+            _pad0: 0.0,
+            _pad1: 0.0,
+        };
+        queue.write_buffer(
+            &self.layouts.render_params_buffer,
+            0,
+            bytemuck::bytes_of(&render_params),
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("3D Slime Mold Render"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("3D Slime Mold Raymarch Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, &self.volume.render_bind_groups[self.current_trail], &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+        queue.submit([encoder.finish()]);
+    }
+
+    fn randomize(&mut self) {
+        let mut rng = rand::rng();
+        let settings = &mut self.settings;
+        settings.random_seed = rng.random();
+        settings.agent_speed = rng.random_range(0.5..2.0);
+        settings.agent_sensor_angle = rng.random_range(0.2..1.0);
+        settings.agent_sensor_distance = rng.random_range(2.0..12.0);
+        settings.agent_turn_rate = rng.random_range(0.1..0.8);
+        settings.agent_jitter = rng.random_range(0.0..0.2);
+        settings.decay_rate = rng.random_range(0.01..0.15);
+        settings.diffusion_rate = rng.random_range(0.05..0.6);
+    }
+}
+
+fn create_module(device: &Device, label: &str, source: &str) -> ShaderModule {
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    })
+}
+
+/// One copy of the trail, starting out empty
+fn create_trail_view(device: &Device, resolution: u32, index: usize) -> TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("3D Slime Mold Trail Texture {}", index)),
+            size: wgpu::Extent3d {
+                width: resolution,
+                height: resolution,
+                depth_or_array_layers: resolution,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            format: TRAIL_FORMAT,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn create_render_pipeline(
+    device: &Device,
+    bind_group_layout: &BindGroupLayout,
+    module: &ShaderModule,
+    format: TextureFormat,
+) -> RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("3D Slime Mold Render Pipeline"),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("3D Slime Mold Render Pipeline Layout"),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for SlimeMold3dModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.spin(self.settings.auto_rotate * delta_time);
+        self.camera.update(delta_time);
+        self.step(device, queue, self.settings.steps_per_frame.max(1));
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // The camera can still be turned while the agents are held
+        self.camera.update(1.0 / 60.0);
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        // The volume is its own size whatever the window's
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        Ok(())
+    }
+
+    /// `ndc_x` and `ndc_y` are the cursor in normalized device coordinates,
+    /// there being no plane to map it onto
+    fn handle_mouse_interaction(
+        &mut self,
+        ndc_x: f32,
+        ndc_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let (origin, direction) = self.camera.ray([ndc_x, ndc_y]);
+        let Some(point) = cursor_point(origin, direction) else {
+            self.cursor_mode = 0;
+            return Ok(());
+        };
+        self.cursor = to_voxels(point, self.volume.resolution);
+        self.cursor_mode = match mouse_button {
+            0 => 1,
+            2 => 2,
+            _ => 0,
+        };
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.cursor_mode = 0;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.settings = serde_json::from_value(settings)?;
+        // A web grown under other rules would only confuse the agents, so
+        // they start over whether or not the volume changed size
+        self.rebuild_volume(device);
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.rebuild_volume(device);
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.randomize();
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.layouts.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let settings = &mut self.settings;
+        match setting_name {
+            "agent_count" => {
+                settings.agent_count = number(setting_name, &value)? as u32;
+                self.rebuild_volume(device);
+            }
+            "volume_resolution" => {
+                settings.volume_resolution = number(setting_name, &value)? as u32;
+                self.rebuild_volume(device);
+            }
+            "spawn" => {
+                settings.spawn = value.as_str().ok_or("spawn must be a string")?.parse()?;
+                self.rebuild_volume(device);
+            }
+            "random_seed" => {
+                settings.random_seed = number(setting_name, &value)? as u32;
+                self.rebuild_volume(device);
+            }
+            "steps_per_frame" => settings.steps_per_frame = number(setting_name, &value)? as u32,
+            "agent_speed" => settings.agent_speed = number(setting_name, &value)? as f32,
+            "agent_sensor_angle" => {
+                settings.agent_sensor_angle = number(setting_name, &value)? as f32
+            }
+            "agent_sensor_distance" => {
+                settings.agent_sensor_distance = number(setting_name, &value)? as f32
+            }
+            "agent_turn_rate" => settings.agent_turn_rate = number(setting_name, &value)? as f32,
+            "agent_jitter" => settings.agent_jitter = number(setting_name, &value)? as f32,
+            "deposit" => settings.deposit = number(setting_name, &value)? as f32,
+            "decay_rate" => settings.decay_rate = number(setting_name, &value)? as f32,
+            "diffusion_rate" => settings.diffusion_rate = number(setting_name, &value)? as f32,
+            "density" => settings.density = number(setting_name, &value)? as f32,
+            "density_threshold" => {
+                settings.density_threshold = number(setting_name, &value)? as f32
+            }
+            "color_range" => settings.color_range = number(setting_name, &value)? as f32,
+            "brightness" => settings.brightness = number(setting_name, &value)? as f32,
+            "ray_steps" => settings.ray_steps = number(setting_name, &value)? as u32,
+            "auto_rotate" => settings.auto_rotate = number(setting_name, &value)? as f32,
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Agents actually roaming, which may be fewer than asked for if the GPU
+    // can't hold them all
+    pub agent_count: u32,
+    // Simulation steps taken since the agents were last spawned
+    pub steps: u64,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            agent_count: 0,
+            steps: 0,
+            color_scheme_name: "MATPLOTLIB_inferno".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
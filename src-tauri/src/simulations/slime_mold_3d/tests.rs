@@ -0,0 +1,108 @@
+use super::settings::{SETTING_RULES, Settings, Spawn};
+use super::shaders::{AGENTS_SHADER, RENDER_SHADER, TRAIL_SHADER};
+use super::volume::{
+    AGENT_WORKGROUP_SIZE, agent_dispatch, box_span, cursor_point, edge_for_voxels, to_voxels,
+};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn inside(point: [f32; 3]) -> bool {
+    point.iter().all(|c| c.abs() <= 1.0 + 1e-5)
+}
+
+#[test]
+fn rays_through_the_volume_enter_and_leave_it() {
+    let (near, far) = box_span([0.0, 0.0, 4.0], [0.0, 0.0, -1.0]).unwrap();
+    assert!((near - 3.0).abs() < 1e-5 && (far - 5.0).abs() < 1e-5);
+
+    // Corner to corner is the longest way through
+    let diagonal = 1.0 / 3.0f32.sqrt();
+    let (near, far) = box_span([-3.0; 3], [diagonal; 3]).unwrap();
+    assert!((far - near - 2.0 * 3.0f32.sqrt()).abs() < 1e-4);
+
+    // From inside, the ray starts where it is
+    let (near, far) = box_span([0.5, 0.0, 0.0], [1.0, 0.0, 0.0]).unwrap();
+    assert_eq!(near, 0.0);
+    assert!((far - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn rays_beside_or_away_from_the_volume_miss() {
+    assert!(box_span([0.0, 2.0, 4.0], [0.0, 0.0, -1.0]).is_none());
+    assert!(box_span([0.0, 0.0, 4.0], [0.0, 0.0, 1.0]).is_none());
+    assert!(box_span([3.0, 3.0, 0.0], [0.0, 0.0, 1.0]).is_none());
+    assert!(cursor_point([0.0, 2.0, 4.0], [0.0, 0.0, -1.0]).is_none());
+}
+
+#[test]
+fn cursor_lands_nearest_the_middle_inside_the_volume() {
+    // Straight through the middle
+    let point = cursor_point([0.0, 0.0, 4.0], [0.0, 0.0, -1.0]).unwrap();
+    assert!(point.iter().all(|c| c.abs() < 1e-5));
+
+    // Glancing past it, still somewhere in the volume
+    let direction = {
+        let length = (0.3f32 * 0.3 + 1.0).sqrt();
+        [0.3 / length, 0.0, -1.0 / length]
+    };
+    let point = cursor_point([-1.2, 0.5, 4.0], direction).unwrap();
+    assert!(inside(point), "{:?}", point);
+
+    assert_eq!(to_voxels([-1.0, 0.0, 1.0], 128), [0.0, 64.0, 128.0]);
+}
+
+#[test]
+fn volume_edges_never_hold_more_than_allowed() {
+    assert_eq!(edge_for_voxels(128 * 128 * 128), 128);
+    assert_eq!(edge_for_voxels(128 * 128 * 128 - 1), 127);
+    assert_eq!(edge_for_voxels(1000), 10);
+    assert_eq!(edge_for_voxels(999), 9);
+    assert_eq!(edge_for_voxels(0), 0);
+}
+
+#[test]
+fn agent_dispatch_covers_every_agent_within_the_row_limit() {
+    for count in [1, 64, 65, 1_000_000, 8_000_000] {
+        let [x, y] = agent_dispatch(count);
+        assert!(x <= 65535 && y >= 1);
+        let covered = x as u64 * y as u64 * AGENT_WORKGROUP_SIZE as u64;
+        assert!(covered >= count as u64);
+        // No more than one row's worth to spare
+        assert!(covered - (count as u64) < x as u64 * AGENT_WORKGROUP_SIZE as u64);
+    }
+}
+
+#[test]
+fn default_settings_keep_to_the_rules() {
+    let settings = serde_json::to_value(Settings::default()).unwrap();
+    for (name, value) in settings.as_object().unwrap() {
+        let validated = SETTING_RULES
+            .validate(name, value.clone(), || settings.clone())
+            .unwrap();
+        assert!(!validated.clamped, "{} = {}", name, value);
+    }
+}
+
+#[test]
+fn spawn_parses_case_insensitively() {
+    assert_eq!("shell".parse::<Spawn>().unwrap(), Spawn::Shell);
+    assert_eq!("CENTER".parse::<Spawn>().unwrap(), Spawn::Center);
+    assert!("torus".parse::<Spawn>().is_err());
+    assert_eq!(u32::from(Spawn::Cube), 2);
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("3D Slime Mold Agents Shader", AGENTS_SHADER),
+        ("3D Slime Mold Trail Shader", TRAIL_SHADER),
+        ("3D Slime Mold Render Shader", RENDER_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
@@ -0,0 +1,67 @@
+//! The cube the agents roam, from -1 to 1 on every axis, and the sums the
+//! CPU does about it: how big it can be, how the agent pass is dispatched
+//! and where the cursor's ray reaches into it.
+
+/// An agent as laid out in `common.wgsl`: a position and a unit heading,
+/// each padded out to 16 bytes. Only the GPU reads or writes one.
+pub type Agent = [f32; 8];
+
+/// Agents each workgroup of the agent pass moves
+pub const AGENT_WORKGROUP_SIZE: u32 = 64;
+
+/// Most workgroups a dispatch can have along one dimension
+const MAX_WORKGROUPS: u32 = 65535;
+
+/// Edge of the largest cubic volume with no more than `voxels` voxels
+pub fn edge_for_voxels(voxels: usize) -> u32 {
+    let mut edge = (voxels as f64).cbrt() as u64;
+    while (edge + 1).pow(3) <= voxels as u64 {
+        edge += 1;
+    }
+    while edge.pow(3) > voxels as u64 {
+        edge -= 1;
+    }
+    edge as u32
+}
+
+/// Workgroups along x and y covering `count` agents, since a million
+/// agents need more workgroups than one dimension allows
+pub fn agent_dispatch(count: u32) -> [u32; 2] {
+    let groups = count.div_ceil(AGENT_WORKGROUP_SIZE).max(1);
+    let width = groups.min(MAX_WORKGROUPS);
+    [width, groups.div_ceil(width)]
+}
+
+/// How far along a ray it enters and leaves the volume, or `None` if it
+/// misses. The ray starts at `origin`, so the entry is never behind it.
+pub fn box_span(origin: [f32; 3], direction: [f32; 3]) -> Option<(f32, f32)> {
+    let mut near = 0.0f32;
+    let mut far = f32::INFINITY;
+    for axis in 0..3 {
+        if direction[axis].abs() < f32::EPSILON {
+            if origin[axis].abs() > 1.0 {
+                return None;
+            }
+            continue;
+        }
+        let a = (-1.0 - origin[axis]) / direction[axis];
+        let b = (1.0 - origin[axis]) / direction[axis];
+        near = near.max(a.min(b));
+        far = far.min(a.max(b));
+    }
+    (near <= far).then_some((near, far))
+}
+
+/// The point where a ray through the volume passes nearest its center,
+/// kept inside the volume, for placing the cursor at a sensible depth
+pub fn cursor_point(origin: [f32; 3], direction: [f32; 3]) -> Option<[f32; 3]> {
+    let (near, far) = box_span(origin, direction)?;
+    let nearest: f32 = -(0..3).map(|i| origin[i] * direction[i]).sum::<f32>();
+    let t = nearest.clamp(near, far);
+    Some(std::array::from_fn(|i| origin[i] + direction[i] * t))
+}
+
+/// A point in the volume in voxels of a volume `resolution` across
+pub fn to_voxels(point: [f32; 3], resolution: u32) -> [f32; 3] {
+    point.map(|c| (c * 0.5 + 0.5) * resolution as f32)
+}
@@ -0,0 +1,394 @@
+//! # Soft Bodies
+//!
+//! Bodies built from point masses and the constraints between them, in a
+//! box `2 * half_width` wide and 2 high centered on the origin:
+//! - Blobs are a loop of points joined in a ring, with pressure holding the
+//!   area inside and a hub point on soft spokes so there's something in
+//!   the middle to fill when drawn as metaballs.
+//! - Ropes are a chain of points with the top one pinned.
+//! - Jelly boxes are a grid of points with springs along the rows and
+//!   columns and softer ones across each square's diagonals.
+//!
+//! [`constraints`](crate::simulations::shared::constraints) does the
+//! solving; this module lays the bodies out, keeps them in the box and
+//! apart from each other, and lets the mouse drag points about.
+
+use std::ops::Range;
+
+use crate::simulations::shared::constraints::{
+    self, Constraint, PointMass, StepSettings, circle, separate,
+};
+
+use super::settings::Scene;
+
+/// Top of the ropes, just under the ceiling
+const ROPE_TOP: f32 = 0.95;
+/// Radians from hanging straight down the ropes start at
+const ROPE_ANGLE: f32 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyKind {
+    Blob,
+    Rope,
+    JellyBox,
+}
+
+#[derive(Debug, Clone)]
+pub struct Body {
+    pub kind: BodyKind,
+    pub points: Range<usize>,
+    /// Springs drawn in outline mode
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// A point being dragged by the mouse
+#[derive(Debug, Clone, Copy)]
+struct Grab {
+    point: usize,
+    target: [f32; 2],
+    was_pinned: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct World {
+    pub points: Vec<PointMass>,
+    pub bodies: Vec<Body>,
+    constraints: Vec<Constraint>,
+    /// Each constraint's compliance as a multiple of the stiffness setting's
+    compliance_scales: Vec<f32>,
+    /// Rest length or area of each blob's constraints as made, by
+    /// constraint index, for pressure to inflate from
+    inflatable: Vec<(usize, f32)>,
+    /// Points' collision radii, and which body each belongs to
+    radii: Vec<f32>,
+    owners: Vec<usize>,
+    /// Radius of each point's metaball
+    pub splat_radii: Vec<f32>,
+    half_width: f32,
+    grab: Option<Grab>,
+}
+
+/// Compliance of an ordinary spring at `stiffness`, from 1e-2 when floppy
+/// down to 1e-7 when it barely stretches
+pub fn compliance(stiffness: f32) -> f32 {
+    10f32.powf(-7.0 + 5.0 * (1.0 - stiffness.clamp(0.0, 1.0)))
+}
+
+impl World {
+    /// The bodies of `scene`, `body_count` of each kind it has, built from
+    /// `resolution` points along each
+    pub fn build(
+        scene: Scene,
+        body_count: u32,
+        resolution: u32,
+        stiffness: f32,
+        half_width: f32,
+    ) -> Self {
+        let mut world = Self {
+            points: Vec::new(),
+            bodies: Vec::new(),
+            constraints: Vec::new(),
+            compliance_scales: Vec::new(),
+            inflatable: Vec::new(),
+            radii: Vec::new(),
+            owners: Vec::new(),
+            splat_radii: Vec::new(),
+            half_width,
+            grab: None,
+        };
+        let count = body_count.max(1) as usize;
+        let kinds: Vec<BodyKind> = match scene {
+            Scene::Blobs => vec![BodyKind::Blob; count * 3],
+            Scene::Ropes => vec![BodyKind::Rope; count * 3],
+            Scene::JellyBoxes => vec![BodyKind::JellyBox; count * 2],
+            Scene::Playground => [BodyKind::Rope, BodyKind::Blob, BodyKind::JellyBox]
+                .into_iter()
+                .cycle()
+                .take(count * 3)
+                .collect(),
+        };
+
+        let slot_width = 2.0 * half_width / kinds.len() as f32;
+        let size = (0.42 * slot_width).min(0.22);
+        let resolution = resolution.max(4) as usize;
+        for (slot, kind) in kinds.into_iter().enumerate() {
+            let x = -half_width + (slot as f32 + 0.5) * slot_width;
+            // Staggered so neighbors don't land in step
+            let lift = if slot % 2 == 0 { 0.0 } else { 0.25 };
+            match kind {
+                BodyKind::Blob => world.add_blob([x, 0.2 + lift], size, resolution),
+                BodyKind::Rope => world.add_rope([x, ROPE_TOP], 0.9 + lift, resolution),
+                BodyKind::JellyBox => {
+                    world.add_jelly_box([x, -0.35 + lift], size * 1.6, resolution / 3 + 2)
+                }
+            }
+        }
+        world.set_stiffness(stiffness);
+        world
+    }
+
+    fn add_point(&mut self, position: [f32; 2], radius: f32, splat_radius: f32) -> usize {
+        self.points.push(PointMass::new(position, 1.0));
+        self.radii.push(radius);
+        self.owners.push(self.bodies.len());
+        self.splat_radii.push(splat_radius);
+        self.points.len() - 1
+    }
+
+    fn add_spring(&mut self, a: usize, b: usize, compliance_scale: f32) {
+        self.constraints
+            .push(Constraint::distance(&self.points, a, b, 0.0));
+        self.compliance_scales.push(compliance_scale);
+    }
+
+    fn add_blob(&mut self, center: [f32; 2], radius: f32, count: usize) {
+        let start = self.points.len();
+        let spacing = std::f32::consts::TAU * radius / count as f32;
+        let ring: Vec<usize> = circle(center, radius, count)
+            .into_iter()
+            .map(|position| self.add_point(position, spacing * 0.5, spacing * 1.4))
+            .collect();
+        let hub = self.add_point(center, spacing * 0.5, radius);
+
+        let mut edges = Vec::with_capacity(count);
+        for i in 0..count {
+            let (a, b) = (ring[i], ring[(i + 1) % count]);
+            self.add_spring(a, b, 1.0);
+            // Spokes only keep the hub near the middle
+            self.add_spring(hub, a, 200.0);
+            edges.push((a, b));
+        }
+        self.constraints
+            .push(Constraint::area(&self.points, ring, 0.0));
+        self.compliance_scales.push(0.1);
+
+        let first = self.constraints.len() - 2 * count - 1;
+        for index in first..self.constraints.len() {
+            let rest = match &self.constraints[index] {
+                Constraint::Distance { rest, .. } | Constraint::Area { rest, .. } => *rest,
+            };
+            self.inflatable.push((index, rest));
+        }
+
+        self.bodies.push(Body {
+            kind: BodyKind::Blob,
+            points: start..self.points.len(),
+            edges,
+        });
+    }
+
+    fn add_rope(&mut self, top: [f32; 2], length: f32, count: usize) {
+        let start = self.points.len();
+        let spacing = length / (count - 1) as f32;
+        let mut edges = Vec::with_capacity(count - 1);
+        for i in 0..count {
+            let t = i as f32 / (count - 1) as f32;
+            // Held out to one side so it swings when let go
+            let position = [
+                top[0] + length * t * ROPE_ANGLE.sin(),
+                top[1] - length * t * ROPE_ANGLE.cos(),
+            ];
+            let point = self.add_point(position, spacing * 0.5, spacing * 1.2);
+            if i == 0 {
+                self.points[point].pinned = true;
+            } else {
+                self.add_spring(point - 1, point, 0.05);
+                edges.push((point - 1, point));
+            }
+        }
+        self.bodies.push(Body {
+            kind: BodyKind::Rope,
+            points: start..self.points.len(),
+            edges,
+        });
+    }
+
+    fn add_jelly_box(&mut self, center: [f32; 2], size: f32, side: usize) {
+        let start = self.points.len();
+        let spacing = size / (side - 1) as f32;
+        let corner = [center[0] - size * 0.5, center[1] - size * 0.5];
+        for row in 0..side {
+            for column in 0..side {
+                let position = [
+                    corner[0] + column as f32 * spacing,
+                    corner[1] + row as f32 * spacing,
+                ];
+                self.add_point(position, spacing * 0.5, spacing * 1.1);
+            }
+        }
+        let at = |row: usize, column: usize| start + row * side + column;
+        let mut edges = Vec::new();
+        for row in 0..side {
+            for column in 0..side {
+                if column + 1 < side {
+                    self.add_spring(at(row, column), at(row, column + 1), 1.0);
+                    edges.push((at(row, column), at(row, column + 1)));
+                }
+                if row + 1 < side {
+                    self.add_spring(at(row, column), at(row + 1, column), 1.0);
+                    edges.push((at(row, column), at(row + 1, column)));
+                }
+                if row + 1 < side && column + 1 < side {
+                    self.add_spring(at(row, column), at(row + 1, column + 1), 4.0);
+                    self.add_spring(at(row, column + 1), at(row + 1, column), 4.0);
+                }
+            }
+        }
+        self.bodies.push(Body {
+            kind: BodyKind::JellyBox,
+            points: start..self.points.len(),
+            edges,
+        });
+    }
+
+    pub fn set_stiffness(&mut self, stiffness: f32) {
+        let base = compliance(stiffness);
+        for (constraint, &scale) in self.constraints.iter_mut().zip(&self.compliance_scales) {
+            match constraint {
+                Constraint::Distance { compliance, .. } | Constraint::Area { compliance, .. } => {
+                    *compliance = base * scale;
+                }
+            }
+        }
+    }
+
+    /// Scale the area every blob holds, 1 being the size it was made. The
+    /// skin stretches to fit, since a ring of springs fighting the gas
+    /// inside would never settle.
+    pub fn set_pressure(&mut self, pressure: f32) {
+        for &(index, made) in &self.inflatable {
+            match &mut self.constraints[index] {
+                Constraint::Distance { rest, .. } => *rest = made * pressure.sqrt(),
+                Constraint::Area { rest, .. } => *rest = made * pressure,
+            }
+        }
+    }
+
+    /// A box `2 * half_width` wide, pulling in anything now outside it
+    pub fn set_half_width(&mut self, half_width: f32) {
+        self.half_width = half_width;
+    }
+
+    pub fn half_width(&self) -> f32 {
+        self.half_width
+    }
+
+    /// Move everything on by `dt` seconds, keeping points in the box and
+    /// out of other bodies. `friction` is the share of sliding stopped by
+    /// each touch of the walls.
+    pub fn step(&mut self, settings: &StepSettings, friction: f32, dt: f32) {
+        let grab = self.grab;
+        let start = grab.map(|grab| self.points[grab.point].position);
+        let half_width = self.half_width;
+        let (radii, owners) = (&self.radii, &self.owners);
+        constraints::step(
+            &mut self.points,
+            &self.constraints,
+            settings,
+            dt,
+            |points, done| {
+                // The grabbed point slides to the cursor over the frame, so
+                // it leaves with the cursor's speed when let go
+                if let (Some(grab), Some(start)) = (grab, start) {
+                    points[grab.point].position = [
+                        start[0] + (grab.target[0] - start[0]) * done,
+                        start[1] + (grab.target[1] - start[1]) * done,
+                    ];
+                }
+                separate_bodies(points, radii, owners);
+                for (point, &radius) in points.iter_mut().zip(radii) {
+                    keep_in_box(point, radius, half_width, friction);
+                }
+            },
+        );
+    }
+
+    /// The nearest point within `reach` of `position`
+    pub fn nearest(&self, position: [f32; 2], reach: f32) -> Option<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                let dx = point.position[0] - position[0];
+                let dy = point.position[1] - position[1];
+                (index, dx * dx + dy * dy)
+            })
+            .filter(|&(_, distance_squared)| distance_squared <= reach * reach)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Start dragging the nearest point within `reach`, or keep dragging
+    /// the one already held. Returns whether a point is held.
+    pub fn drag(&mut self, position: [f32; 2], reach: f32) -> bool {
+        if let Some(grab) = &mut self.grab {
+            grab.target = position;
+            return true;
+        }
+        let Some(point) = self.nearest(position, reach) else {
+            return false;
+        };
+        self.grab = Some(Grab {
+            point,
+            target: position,
+            was_pinned: self.points[point].pinned,
+        });
+        // Held points go where they're put, whatever pulls on them
+        self.points[point].pinned = true;
+        true
+    }
+
+    /// Let go of the dragged point, which keeps the speed it was moving at
+    pub fn release(&mut self) {
+        if let Some(grab) = self.grab.take() {
+            self.points[grab.point].pinned = grab.was_pinned;
+        }
+    }
+
+    pub fn grabbed(&self) -> Option<usize> {
+        self.grab.map(|grab| grab.point)
+    }
+
+    /// Pin the nearest point within `reach` where it is, or free it if
+    /// already pinned
+    pub fn toggle_pin(&mut self, position: [f32; 2], reach: f32) -> bool {
+        let Some(point) = self.nearest(position, reach) else {
+            return false;
+        };
+        let point = &mut self.points[point];
+        point.pinned = !point.pinned;
+        point.velocity = [0.0, 0.0];
+        true
+    }
+}
+
+/// Points of different bodies are kept apart; a body's own points are held
+/// in shape by its springs
+fn separate_bodies(points: &mut [PointMass], radii: &[f32], owners: &[usize]) {
+    for a in 0..points.len() {
+        for b in a + 1..points.len() {
+            if owners[a] != owners[b] {
+                separate(points, a, b, radii[a] + radii[b]);
+            }
+        }
+    }
+}
+
+/// Push a point back inside the box, taking off some of its sliding along
+/// whichever wall it touched
+fn keep_in_box(point: &mut PointMass, radius: f32, half_width: f32, friction: f32) {
+    if point.pinned {
+        return;
+    }
+    let limits = [half_width - radius, 1.0 - radius];
+    for (axis, limit) in limits.into_iter().enumerate() {
+        let limit = limit.max(0.0);
+        let value = point.position[axis];
+        if value.abs() > limit {
+            point.position[axis] = limit.copysign(value);
+            let along = 1 - axis;
+            let slid = point.position[along] - point.previous[along];
+            point.position[along] -= slid * friction;
+        }
+    }
+}
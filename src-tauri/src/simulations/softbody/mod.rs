@@ -0,0 +1,92 @@
+pub mod bodies;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::SoftbodyModel;
+
+use crate::simulation::preset_manager::{Preset, SoftbodyPresetManager};
+
+/// Initialize Soft Body presets with built-in configurations
+pub fn init_presets(preset_manager: &mut SoftbodyPresetManager) {
+    use settings::{RenderMode, Scene, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Blob Party".to_string(),
+        Settings {
+            scene: Scene::Blobs,
+            body_count: 3,
+            resolution: 20,
+            stiffness: 0.6,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Hanging Ropes".to_string(),
+        Settings {
+            scene: Scene::Ropes,
+            body_count: 2,
+            resolution: 24,
+            stiffness: 0.9,
+            damping: 0.1,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Jelly Stack".to_string(),
+        Settings {
+            scene: Scene::JellyBoxes,
+            body_count: 3,
+            resolution: 15,
+            stiffness: 0.45,
+            friction: 0.6,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Metaball Goo".to_string(),
+        Settings {
+            scene: Scene::Blobs,
+            body_count: 2,
+            resolution: 24,
+            stiffness: 0.4,
+            pressure: 0.8,
+            render_mode: RenderMode::Metaball,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Floaty Moon".to_string(),
+        Settings {
+            gravity: 0.3,
+            damping: 0.05,
+            friction: 0.1,
+            pressure: 1.3,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Stiff Crates".to_string(),
+        Settings {
+            scene: Scene::JellyBoxes,
+            body_count: 2,
+            stiffness: 0.97,
+            substeps: 24,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,174 @@
+//! # Soft Body Settings Module
+//!
+//! Which bodies are dropped into the box, how stiff and springy they are,
+//! the forces acting on them, and how they're drawn.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Scene {
+    /// Pressurized blobs
+    Blobs,
+    /// Ropes hanging from pinned ends
+    Ropes,
+    /// A stack of jelly boxes
+    JellyBoxes,
+    /// One of everything
+    #[default]
+    Playground,
+}
+
+impl FromStr for Scene {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "blobs" => Ok(Scene::Blobs),
+            "ropes" => Ok(Scene::Ropes),
+            "jellyboxes" => Ok(Scene::JellyBoxes),
+            "playground" => Ok(Scene::Playground),
+            _ => Err(format!(
+                "Invalid Scene: '{}'. Expected 'Blobs', 'Ropes', 'JellyBoxes', or 'Playground'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum RenderMode {
+    /// The springs as lines and the points as dots
+    #[default]
+    Outline,
+    /// Each body as one smooth filled shape, from metaballs around its
+    /// points
+    Metaball,
+}
+
+impl RenderMode {
+    pub fn shader_index(self) -> u32 {
+        match self {
+            RenderMode::Outline => 0,
+            RenderMode::Metaball => 1,
+        }
+    }
+}
+
+impl FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "outline" => Ok(RenderMode::Outline),
+            "metaball" => Ok(RenderMode::Metaball),
+            _ => Err(format!(
+                "Invalid RenderMode: '{}'. Expected 'Outline' or 'Metaball'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub scene: Scene,
+    /// Times each kind of body appears in the scene
+    pub body_count: u32,
+    /// Points around each blob, along each rope, or along each side of a
+    /// jelly box
+    pub resolution: u32,
+
+    /// 0 for springs that sag and wobble, 1 for ones that barely stretch
+    pub stiffness: f32,
+    /// How hard the gas inside each blob pushes out, 1 holding it at the
+    /// size it was made
+    pub pressure: f32,
+    /// Box heights per second squared, downward
+    pub gravity: f32,
+    /// Share of velocity lost each second
+    pub damping: f32,
+    /// Share of sliding stopped by each touch of the walls
+    pub friction: f32,
+    pub substeps: u32,
+
+    pub render_mode: RenderMode,
+    /// Width of the springs and dots in outline mode, in box heights
+    pub line_width: f32,
+    /// How far out the metaballs' surface sits, larger pulling bodies in
+    /// tighter
+    pub metaball_threshold: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            scene: Scene::Playground,
+            body_count: 1,
+            resolution: 16,
+            stiffness: 0.7,
+            pressure: 1.0,
+            gravity: 1.5,
+            damping: 0.2,
+            friction: 0.3,
+            substeps: 12,
+            render_mode: RenderMode::Outline,
+            line_width: 0.006,
+            metaball_threshold: 1.0,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting)
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "scene",
+            Rule::OneOf(&["Blobs", "Ropes", "JellyBoxes", "Playground"]),
+        ),
+        ("body_count", Rule::Count { min: 1, max: 6 }),
+        ("resolution", Rule::Count { min: 4, max: 40 }),
+        ("stiffness", Rule::Range { min: 0.0, max: 1.0 }),
+        ("pressure", Rule::Range { min: 0.2, max: 3.0 }),
+        (
+            "gravity",
+            Rule::Range {
+                min: -10.0,
+                max: 10.0,
+            },
+        ),
+        ("damping", Rule::Range { min: 0.0, max: 1.0 }),
+        ("friction", Rule::Range { min: 0.0, max: 1.0 }),
+        ("substeps", Rule::Count { min: 1, max: 64 }),
+        ("render_mode", Rule::OneOf(&["Outline", "Metaball"])),
+        (
+            "line_width",
+            Rule::Range {
+                min: 0.001,
+                max: 0.05,
+            },
+        ),
+        ("metaball_threshold", Rule::Range { min: 0.2, max: 4.0 }),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("scene", SettingCategory::Generators),
+    ("body_count", SettingCategory::Generators),
+    ("render_mode", SettingCategory::Colors),
+    ("line_width", SettingCategory::Colors),
+    ("metaball_threshold", SettingCategory::Colors),
+]);
@@ -0,0 +1,146 @@
+// Draws the soft bodies from capsules the CPU lays out each frame: a
+// segment with a radius, a dot being one whose ends meet. In outline mode
+// the capsules are the springs and points themselves. In metaball mode each
+// point's capsule is splatted additively into a field texture instead, and
+// a fullscreen pass fills wherever the field passes the threshold.
+
+struct Params {
+    view_center: vec2<f32>,
+    view_zoom: f32,
+    aspect: f32,
+    // Box heights per pixel, for antialiasing
+    pixel_size: f32,
+    threshold: f32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+struct Capsule {
+    a: vec2<f32>,
+    b: vec2<f32>,
+    radius: f32,
+    // Where the body falls in the color scheme; below 0 for the walls and
+    // above 1 for pinned points
+    color: f32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> capsules: array<Capsule>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+
+@group(1) @binding(0) var field_texture: texture_2d<f32>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+fn body_color(position: f32) -> vec3<f32> {
+    if (position < 0.0) {
+        return vec3<f32>(0.3);
+    }
+    if (position > 1.0) {
+        return vec3<f32>(1.0);
+    }
+    return lut_color(position);
+}
+
+// The box is aspect wide in each direction; the camera sees 1 to a side
+fn to_ndc(point: vec2<f32>) -> vec2<f32> {
+    return (vec2<f32>(point.x / params.aspect, point.y) - params.view_center) * params.view_zoom;
+}
+
+struct CapsuleOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) point: vec2<f32>,
+    @location(1) @interpolate(flat) instance: u32,
+}
+
+// A rectangle around the capsule, a pixel wider all round for the soft edge
+@vertex
+fn vs_capsule(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> CapsuleOutput {
+    let capsule = capsules[instance_index];
+    let corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+
+    let pad = capsule.radius + params.pixel_size;
+    let along = capsule.b - capsule.a;
+    let span = length(along);
+    var direction = vec2<f32>(1.0, 0.0);
+    if (span > 1e-6) {
+        direction = along / span;
+    }
+    let normal = vec2<f32>(-direction.y, direction.x);
+    let point = capsule.a
+        + direction * ((span + 2.0 * pad) * corner.x - pad)
+        + normal * pad * (2.0 * corner.y - 1.0);
+    return CapsuleOutput(vec4<f32>(to_ndc(point), 0.0, 1.0), point, instance_index);
+}
+
+fn segment_distance(point: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+    let along = b - a;
+    let t = clamp(dot(point - a, along) / max(dot(along, along), 1e-12), 0.0, 1.0);
+    return length(point - a - along * t);
+}
+
+@fragment
+fn fs_capsule(input: CapsuleOutput) -> @location(0) vec4<f32> {
+    let capsule = capsules[input.instance];
+    let distance = segment_distance(input.point, capsule.a, capsule.b) - capsule.radius;
+    let half_pixel = params.pixel_size * 0.5;
+    let coverage = 1.0 - smoothstep(-half_pixel, half_pixel, distance);
+    if (coverage <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(body_color(capsule.color), coverage);
+}
+
+// Adds up each point's falloff, and its color weighted by it, so the
+// composite can take the average color wherever bodies run together
+@fragment
+fn fs_splat(input: CapsuleOutput) -> @location(0) vec4<f32> {
+    let capsule = capsules[input.instance];
+    let d = length(input.point - capsule.a) / capsule.radius;
+    if (d >= 1.0) {
+        discard;
+    }
+    let falloff = (1.0 - d * d) * (1.0 - d * d);
+    return vec4<f32>(falloff, falloff * capsule.color, 0.0, 0.0);
+}
+
+// One triangle covering the screen
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_metaball(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let field = textureLoad(field_texture, vec2<i32>(position.xy), 0);
+    let width = max(fwidth(field.r), 1e-4);
+    let coverage = smoothstep(params.threshold - width, params.threshold + width, field.r);
+    if (coverage <= 0.0) {
+        discard;
+    }
+    // Darker toward the surface, so the bodies look rounded
+    let depth = smoothstep(params.threshold, params.threshold * 2.5, field.r);
+    let color = lut_color(field.g / max(field.r, 1e-6)) * mix(0.55, 1.0, depth);
+    return vec4<f32>(color, coverage);
+}
@@ -0,0 +1,817 @@
+//! # Soft Body Simulation Module
+//!
+//! Blobs, ropes and jelly boxes made of point masses and springs, falling
+//! about a box the shape of the window. The mouse picks points up and
+//! flings them with the left button and pins or unpins them with the
+//! right.
+//!
+//! The physics runs on the CPU in [`bodies`](super::bodies); each frame the
+//! springs and points are laid out as capsules for the GPU to draw, either
+//! as outlines or, in metaball mode, splatted into a field texture that a
+//! fullscreen pass fills in wherever it's dense enough.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::ops::Range;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, BlendComponent,
+    BlendFactor, BlendOperation, BlendState, Buffer, BufferDescriptor, BufferUsages, Device,
+    PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, ShaderModule,
+    ShaderStages, SurfaceConfiguration, TextureFormat, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::constraints::StepSettings;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager};
+use crate::simulations::traits::Simulation;
+
+use super::bodies::{BodyKind, World};
+use super::settings::{RenderMode, Scene, Settings};
+use super::shaders::RENDER_SHADER;
+use super::state::State;
+
+/// Longest step taken in one frame, so a stall doesn't throw the bodies
+/// through each other
+const MAX_FRAME_TIME: f32 = 1.0 / 30.0;
+
+/// How close the mouse has to be to a point to pick it up or pin it, in
+/// box heights at the starting zoom
+const GRAB_REACH: f32 = 0.08;
+
+/// Capsules room is made for up front; the buffer grows past this as needed
+const INITIAL_CAPSULES: usize = 1024;
+
+const FIELD_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Capsule colors outside the color scheme, picked out by the shader
+const WALL_COLOR: f32 = -1.0;
+const PINNED_COLOR: f32 = 2.0;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    view_center: [f32; 2],
+    view_zoom: f32,
+    aspect: f32,
+    pixel_size: f32,
+    threshold: f32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct Capsule {
+    a: [f32; 2],
+    b: [f32; 2],
+    radius: f32,
+    color: f32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+impl Capsule {
+    fn new(a: [f32; 2], b: [f32; 2], radius: f32, color: f32) -> Self {
+        Self {
+            a,
+            b,
+            radius,
+            color,
+            _pad0: 0.0,
+            _pad1: 0.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SoftbodyModel {
+    pub settings: Settings,
+    pub state: State,
+    world: World,
+
+    // GPU resources
+    capsule_pipeline: RenderPipeline,
+    splat_pipeline: RenderPipeline,
+    metaball_pipeline: RenderPipeline,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    capsule_buffer: Buffer,
+    capsule_capacity: usize,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    field_layout: BindGroupLayout,
+    field_view: TextureView,
+    field_bind_group: BindGroup,
+
+    // Capsules laid out for this frame, kept to save reallocating
+    capsules: Vec<Capsule>,
+
+    // The right button pins once per press, however long it's held
+    pinning: bool,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+impl SoftbodyModel {
+    pub fn new(
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let mut state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let render_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Softbody Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Softbody Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Softbody LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let capsule_buffer = create_capsule_buffer(device, INITIAL_CAPSULES);
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Softbody Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ),
+                resource_helpers::storage_buffer_entry(
+                    1,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    true,
+                ),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::FRAGMENT, true),
+            ],
+        });
+        let bind_group = create_bind_group(
+            device,
+            &bind_group_layout,
+            &params_buffer,
+            &capsule_buffer,
+            &lut_buffer,
+        );
+
+        let field_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Softbody Field Bind Group Layout"),
+            entries: &[resource_helpers::texture_entry(
+                0,
+                ShaderStages::FRAGMENT,
+                wgpu::TextureSampleType::Float { filterable: false },
+                wgpu::TextureViewDimension::D2,
+            )],
+        });
+        let (field_view, field_bind_group) = create_field(
+            device,
+            &field_layout,
+            surface_config.width,
+            surface_config.height,
+        );
+
+        let alpha_blend = Some(BlendState::ALPHA_BLENDING);
+        let additive = BlendComponent {
+            src_factor: BlendFactor::One,
+            dst_factor: BlendFactor::One,
+            operation: BlendOperation::Add,
+        };
+        let capsule_pipeline = create_pipeline(
+            device,
+            "Softbody Capsule Pipeline",
+            &[&bind_group_layout],
+            &render_module,
+            ("vs_capsule", "fs_capsule"),
+            surface_config.format,
+            alpha_blend,
+        );
+        let splat_pipeline = create_pipeline(
+            device,
+            "Softbody Splat Pipeline",
+            &[&bind_group_layout],
+            &render_module,
+            ("vs_capsule", "fs_splat"),
+            FIELD_FORMAT,
+            Some(BlendState {
+                color: additive,
+                alpha: additive,
+            }),
+        );
+        let metaball_pipeline = create_pipeline(
+            device,
+            "Softbody Metaball Pipeline",
+            &[&bind_group_layout, &field_layout],
+            &render_module,
+            ("vs_fullscreen", "fs_metaball"),
+            surface_config.format,
+            alpha_blend,
+        );
+
+        let aspect = surface_config.width as f32 / surface_config.height.max(1) as f32;
+        let world = build_world(&settings, aspect, &mut state);
+
+        Ok(Self {
+            settings,
+            state,
+            world,
+            capsule_pipeline,
+            splat_pipeline,
+            metaball_pipeline,
+            params_buffer,
+            lut_buffer,
+            capsule_buffer,
+            capsule_capacity: INITIAL_CAPSULES,
+            bind_group_layout,
+            bind_group,
+            field_layout,
+            field_view,
+            field_bind_group,
+            capsules: Vec::new(),
+            pinning: false,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        })
+    }
+
+    fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    fn rebuild_world(&mut self) {
+        self.world = build_world(&self.settings, self.aspect(), &mut self.state);
+    }
+
+    /// Capsules for this frame: the splats for the field texture, then
+    /// everything drawn straight onto the screen
+    fn lay_out_capsules(&mut self) -> (Range<u32>, Range<u32>) {
+        self.capsules.clear();
+        let metaball = self.settings.render_mode == RenderMode::Metaball;
+        let body_count = self.world.bodies.len().max(1) as f32;
+        let dot_radius = self.settings.line_width;
+
+        if metaball {
+            for (index, body) in self.world.bodies.iter().enumerate() {
+                if body.kind == BodyKind::Rope {
+                    continue;
+                }
+                let color = (index as f32 + 0.5) / body_count;
+                for point in body.points.clone() {
+                    let position = self.world.points[point].position;
+                    let radius = self.world.splat_radii[point];
+                    self.capsules
+                        .push(Capsule::new(position, position, radius, color));
+                }
+            }
+        }
+        let splats = 0..self.capsules.len() as u32;
+
+        let (x, y) = (self.world.half_width(), 1.0);
+        let corners = [[-x, -y], [x, -y], [x, y], [-x, y]];
+        for i in 0..4 {
+            self.capsules.push(Capsule::new(
+                corners[i],
+                corners[(i + 1) % 4],
+                self.settings.line_width,
+                WALL_COLOR,
+            ));
+        }
+        for (index, body) in self.world.bodies.iter().enumerate() {
+            let color = (index as f32 + 0.5) / body_count;
+            // Ropes are too thin to fill, so they stay as lines
+            let filled = metaball && body.kind != BodyKind::Rope;
+            if !filled {
+                for &(a, b) in &body.edges {
+                    self.capsules.push(Capsule::new(
+                        self.world.points[a].position,
+                        self.world.points[b].position,
+                        self.settings.line_width * 0.5,
+                        color,
+                    ));
+                }
+            }
+            for point in body.points.clone() {
+                let point = &self.world.points[point];
+                // Only the pins show over the filled bodies
+                if filled && !point.pinned {
+                    continue;
+                }
+                let color = if point.pinned { PINNED_COLOR } else { color };
+                self.capsules.push(Capsule::new(
+                    point.position,
+                    point.position,
+                    dot_radius,
+                    color,
+                ));
+            }
+        }
+        let overlay = splats.end..self.capsules.len() as u32;
+        (splats, overlay)
+    }
+
+    /// Grow the capsule buffer to fit this frame's capsules
+    fn reserve_capsules(&mut self, device: &Arc<Device>) {
+        if self.capsules.len() <= self.capsule_capacity {
+            return;
+        }
+        self.capsule_capacity = self.capsules.len().next_power_of_two();
+        self.capsule_buffer = create_capsule_buffer(device, self.capsule_capacity);
+        self.bind_group = create_bind_group(
+            device,
+            &self.bind_group_layout,
+            &self.params_buffer,
+            &self.capsule_buffer,
+            &self.lut_buffer,
+        );
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let params = Params {
+            view_center: self.camera.position,
+            view_zoom: self.camera.zoom,
+            aspect: self.aspect(),
+            pixel_size: 2.0 / (self.camera.zoom * self.height.max(1) as f32),
+            threshold: self.settings.metaball_threshold,
+            _pad0: 0.0,
+            _pad1: 0.0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn draw(&mut self, device: &Arc<Device>, queue: &Arc<Queue>, surface_view: &TextureView) {
+        let (splats, overlay) = self.lay_out_capsules();
+        self.reserve_capsules(device);
+        queue.write_buffer(
+            &self.capsule_buffer,
+            0,
+            bytemuck::cast_slice(&self.capsules),
+        );
+        self.update_params(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Softbody Render"),
+        });
+        let metaball = self.settings.render_mode == RenderMode::Metaball;
+        if metaball {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Softbody Splat Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.field_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&self.splat_pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.draw(0..6, splats);
+        }
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Softbody Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            if metaball {
+                render_pass.set_pipeline(&self.metaball_pipeline);
+                render_pass.set_bind_group(1, &self.field_bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+            render_pass.set_pipeline(&self.capsule_pipeline);
+            render_pass.draw(0..6, overlay);
+        }
+        queue.submit([encoder.finish()]);
+    }
+}
+
+/// The bodies `settings` asks for in a box `aspect` wide either side,
+/// noting their size in `state`
+fn build_world(settings: &Settings, aspect: f32, state: &mut State) -> World {
+    let mut world = World::build(
+        settings.scene,
+        settings.body_count,
+        settings.resolution,
+        settings.stiffness,
+        aspect,
+    );
+    world.set_pressure(settings.pressure);
+    state.point_count = world.points.len();
+    state.body_count = world.bodies.len();
+    state.grabbing = false;
+    world
+}
+
+fn create_capsule_buffer(device: &Device, capacity: usize) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some("Softbody Capsule Buffer"),
+        size: (capacity * std::mem::size_of::<Capsule>()) as u64,
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+fn create_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    capsule_buffer: &Buffer,
+    lut_buffer: &Buffer,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Softbody Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, capsule_buffer),
+            resource_helpers::buffer_entry(2, lut_buffer),
+        ],
+    })
+}
+
+/// The texture the metaballs are splatted into, the size of the surface
+fn create_field(
+    device: &Device,
+    layout: &BindGroupLayout,
+    width: u32,
+    height: u32,
+) -> (TextureView, BindGroup) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Softbody Field Texture"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FIELD_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Softbody Field Bind Group"),
+        layout,
+        entries: &[resource_helpers::texture_view_entry(0, &view)],
+    });
+    (view, bind_group)
+}
+
+fn create_pipeline(
+    device: &Device,
+    label: &str,
+    bind_group_layouts: &[&BindGroupLayout],
+    module: &ShaderModule,
+    (vertex_entry, fragment_entry): (&str, &str),
+    format: TextureFormat,
+    blend: Option<BlendState>,
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(&format!("{} Layout", label)),
+            bind_group_layouts,
+            push_constant_ranges: &[],
+        })),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some(vertex_entry),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for SoftbodyModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.camera.update(delta_time);
+        let step_settings = StepSettings {
+            gravity: [0.0, -self.settings.gravity],
+            damping: self.settings.damping,
+            substeps: self.settings.substeps,
+        };
+        self.world.step(
+            &step_settings,
+            self.settings.friction,
+            delta_time.min(MAX_FRAME_TIME),
+        );
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.draw(device, queue, surface_view);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        // The box keeps to the window, walls moving in on anything outside
+        self.world.set_half_width(self.aspect());
+        (self.field_view, self.field_bind_group) = create_field(
+            device,
+            &self.field_layout,
+            new_config.width,
+            new_config.height,
+        );
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let position = [world_x * self.aspect(), world_y];
+        let reach = GRAB_REACH / self.camera.zoom;
+        match mouse_button {
+            0 => {
+                self.world.drag(position, reach);
+                self.state.grabbing = self.world.grabbed().is_some();
+            }
+            2 if !self.pinning => {
+                self.pinning = true;
+                self.world.toggle_pin(position, reach);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.world.release();
+        self.state.grabbing = false;
+        self.pinning = false;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.settings = serde_json::from_value(settings)?;
+        self.rebuild_world();
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.pinning = false;
+        self.rebuild_world();
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.scene = match rng.random_range(0..4) {
+            0 => Scene::Blobs,
+            1 => Scene::Ropes,
+            2 => Scene::JellyBoxes,
+            _ => Scene::Playground,
+        };
+        self.settings.body_count = rng.random_range(1..=3);
+        self.settings.stiffness = rng.random_range(0.3..0.95);
+        self.settings.pressure = rng.random_range(0.7..1.6);
+        self.settings.gravity = rng.random_range(0.5..3.0);
+        self.settings.damping = rng.random_range(0.05..0.4);
+        self.settings.friction = rng.random_range(0.0..0.6);
+        self.settings.render_mode = if rng.random_bool(0.5) {
+            RenderMode::Outline
+        } else {
+            RenderMode::Metaball
+        };
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "scene" => {
+                self.settings.scene = value
+                    .as_str()
+                    .unwrap_or("Playground")
+                    .parse()
+                    .map_err(|e| format!("Invalid scene: {}", e))?;
+                self.rebuild_world();
+            }
+            "body_count" => {
+                self.settings.body_count = number(setting_name, &value)? as u32;
+                self.rebuild_world();
+            }
+            "resolution" => {
+                self.settings.resolution = number(setting_name, &value)? as u32;
+                self.rebuild_world();
+            }
+            "stiffness" => {
+                self.settings.stiffness = number(setting_name, &value)? as f32;
+                self.world.set_stiffness(self.settings.stiffness);
+            }
+            "pressure" => {
+                self.settings.pressure = number(setting_name, &value)? as f32;
+                self.world.set_pressure(self.settings.pressure);
+            }
+            "gravity" => self.settings.gravity = number(setting_name, &value)? as f32,
+            "damping" => self.settings.damping = number(setting_name, &value)? as f32,
+            "friction" => self.settings.friction = number(setting_name, &value)? as f32,
+            "substeps" => self.settings.substeps = number(setting_name, &value)? as u32,
+            "render_mode" => {
+                self.settings.render_mode = value
+                    .as_str()
+                    .unwrap_or("Outline")
+                    .parse()
+                    .map_err(|e| format!("Invalid render mode: {}", e))?;
+            }
+            "line_width" => self.settings.line_width = number(setting_name, &value)? as f32,
+            "metaball_threshold" => {
+                self.settings.metaball_threshold = number(setting_name, &value)? as f32;
+            }
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Size of the world being simulated
+    pub point_count: usize,
+    pub body_count: usize,
+
+    // Whether the mouse is holding a point
+    pub grabbing: bool,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            point_count: 0,
+            body_count: 0,
+            grabbing: false,
+            color_scheme_name: "MATPLOTLIB_plasma".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
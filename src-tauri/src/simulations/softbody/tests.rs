@@ -0,0 +1,217 @@
+use super::bodies::{BodyKind, World};
+use super::settings::{Scene, Settings};
+use super::shaders::RENDER_SHADER;
+use crate::simulations::shared::constraints::{StepSettings, ring_area};
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+fn step_settings(settings: &Settings) -> StepSettings {
+    StepSettings {
+        gravity: [0.0, -settings.gravity],
+        damping: settings.damping,
+        substeps: settings.substeps,
+    }
+}
+
+fn run(world: &mut World, settings: &Settings, seconds: f32) {
+    let steps = (seconds * 60.0) as u32;
+    for _ in 0..steps {
+        world.step(&step_settings(settings), settings.friction, 1.0 / 60.0);
+    }
+}
+
+#[test]
+fn every_scene_settles_inside_the_box() {
+    // Damped harder than usual so the ropes stop swinging in time
+    let settings = Settings {
+        damping: 0.6,
+        ..Settings::default()
+    };
+    for scene in [
+        Scene::Blobs,
+        Scene::Ropes,
+        Scene::JellyBoxes,
+        Scene::Playground,
+    ] {
+        let mut world = World::build(scene, 2, settings.resolution, settings.stiffness, 1.6);
+        run(&mut world, &settings, 8.0);
+        for point in &world.points {
+            let [x, y] = point.position;
+            assert!(x.abs() <= 1.6 && y.abs() <= 1.0, "{:?} {:?}", scene, point);
+            assert!(x.is_finite() && y.is_finite());
+            // Come to rest rather than jittering about
+            assert!(
+                point.velocity[0].hypot(point.velocity[1]) < 0.2,
+                "{:?}",
+                scene
+            );
+        }
+    }
+}
+
+#[test]
+fn scenes_hold_the_bodies_they_promise() {
+    let kinds = |scene| {
+        World::build(scene, 2, 12, 0.5, 1.0)
+            .bodies
+            .iter()
+            .map(|body| body.kind)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(kinds(Scene::Blobs), vec![BodyKind::Blob; 6]);
+    assert_eq!(kinds(Scene::JellyBoxes), vec![BodyKind::JellyBox; 4]);
+    let playground = kinds(Scene::Playground);
+    for kind in [BodyKind::Blob, BodyKind::Rope, BodyKind::JellyBox] {
+        assert_eq!(playground.iter().filter(|&&k| k == kind).count(), 2);
+    }
+}
+
+#[test]
+fn blobs_rest_on_the_floor_near_the_area_they_hold() {
+    let settings = Settings {
+        damping: 0.6,
+        ..Settings::default()
+    };
+    for pressure in [0.6, 1.0, 1.8] {
+        let mut world = World::build(Scene::Blobs, 1, 20, settings.stiffness, 2.0);
+        world.set_pressure(pressure);
+        // Long enough to drop and stop bouncing, the fuller blobs being
+        // springier
+        run(&mut world, &settings, 8.0);
+        let body = &world.bodies[0];
+        // The hub is the last point; the rest go round the ring
+        let ring: Vec<usize> = (body.points.start..body.points.end - 1).collect();
+        let made = World::build(Scene::Blobs, 1, 20, settings.stiffness, 2.0);
+        let made_area = ring_area(&made.points, &ring);
+        let area = ring_area(&world.points, &ring);
+        assert!(
+            (area / (made_area * pressure) - 1.0).abs() < 0.15,
+            "{} holds {} of {}",
+            pressure,
+            area,
+            made_area * pressure
+        );
+        let lowest = ring
+            .iter()
+            .map(|&i| world.points[i].position[1])
+            .fold(f32::MAX, f32::min);
+        assert!(lowest < -0.95, "{} rests at {}", pressure, lowest);
+    }
+}
+
+#[test]
+fn ropes_hang_from_their_pins_without_stretching_much() {
+    let settings = Settings {
+        damping: 0.8,
+        ..Settings::default()
+    };
+    let mut world = World::build(Scene::Ropes, 1, 16, settings.stiffness, 1.5);
+    let top = world.points[0].position;
+    let length: f32 = world.bodies[0]
+        .edges
+        .iter()
+        .map(|&(a, b)| {
+            let [ax, ay] = world.points[a].position;
+            let [bx, by] = world.points[b].position;
+            (ax - bx).hypot(ay - by)
+        })
+        .sum();
+    run(&mut world, &settings, 8.0);
+
+    assert_eq!(world.points[0].position, top);
+    let rope = world.bodies[0].points.clone();
+    let bottom = world.points[rope.end - 1].position;
+    // Hangs straight down once the swing dies away
+    assert!((bottom[0] - top[0]).abs() < 0.05, "{:?}", bottom);
+    let hanging = top[1] - bottom[1];
+    assert!(
+        hanging > length * 0.95 && hanging < length * 1.05,
+        "{}",
+        hanging
+    );
+}
+
+#[test]
+fn dragged_points_follow_the_cursor_and_fly_off_when_let_go() {
+    let settings = Settings {
+        gravity: 0.0,
+        ..Settings::default()
+    };
+    let mut world = World::build(Scene::JellyBoxes, 1, 12, settings.stiffness, 2.0);
+    let corner = world.points[0].position;
+    assert!(!world.drag([5.0, 5.0], 0.1));
+    assert!(world.drag(corner, 0.1));
+    assert_eq!(world.grabbed(), Some(0));
+
+    // Swept right at 1.2 box heights a second
+    let mut target = corner;
+    for _ in 0..20 {
+        target[0] += 0.02;
+        world.drag(target, 0.1);
+        world.step(&step_settings(&settings), settings.friction, 1.0 / 60.0);
+        assert!((world.points[0].position[0] - target[0]).abs() < 1e-5);
+    }
+    world.release();
+    assert_eq!(world.grabbed(), None);
+    assert!(!world.points[0].pinned);
+    assert!((world.points[0].velocity[0] - 1.2).abs() < 0.05);
+    world.step(&step_settings(&settings), settings.friction, 1.0 / 60.0);
+    assert!(world.points[0].position[0] > target[0]);
+}
+
+#[test]
+fn toggling_a_pin_holds_a_point_in_place() {
+    let settings = Settings::default();
+    let mut world = World::build(Scene::Blobs, 1, 12, settings.stiffness, 2.0);
+    let point = world.points[3].position;
+    assert!(world.toggle_pin(point, 0.01));
+    run(&mut world, &settings, 1.0);
+    assert_eq!(world.points[3].position, point);
+    assert!(world.toggle_pin(point, 0.01));
+    run(&mut world, &settings, 1.0);
+    assert!(world.points[3].position[1] < point[1] - 0.1);
+}
+
+#[test]
+fn bodies_stay_out_of_each_other() {
+    let settings = Settings::default();
+    let mut world = World::build(Scene::JellyBoxes, 3, 12, settings.stiffness, 0.6);
+    run(&mut world, &settings, 6.0);
+    // No point ends up inside another box's outline
+    for (i, body) in world.bodies.iter().enumerate() {
+        let xs = body.points.clone().map(|p| world.points[p].position[0]);
+        let ys = body.points.clone().map(|p| world.points[p].position[1]);
+        let (min_x, max_x) = (
+            xs.clone().fold(f32::MAX, f32::min),
+            xs.fold(f32::MIN, f32::max),
+        );
+        let (min_y, max_y) = (
+            ys.clone().fold(f32::MAX, f32::min),
+            ys.fold(f32::MIN, f32::max),
+        );
+        let shrink = 0.2 * (max_x - min_x).min(max_y - min_y);
+        for (j, other) in world.bodies.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            for p in other.points.clone() {
+                let [x, y] = world.points[p].position;
+                let inside = x > min_x + shrink
+                    && x < max_x - shrink
+                    && y > min_y + shrink
+                    && y < max_y - shrink;
+                assert!(!inside, "box {} point {} inside box {}", j, p, i);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn shader_compiles() {
+    let harness = KernelHarness::new().await;
+    let _ = harness
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Softbody Render Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_SHADER.into()),
+        });
+}
@@ -0,0 +1,75 @@
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+pub mod stipple;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::StipplingModel;
+
+use crate::simulation::preset_manager::{Preset, StipplingPresetManager};
+
+/// Initialize Stippling presets with built-in configurations
+pub fn init_presets(preset_manager: &mut StipplingPresetManager) {
+    use settings::{Settings, StippleColoring};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Fine Engraving".to_string(),
+        Settings {
+            point_count: 20_000,
+            resolution: 1024,
+            point_size: 0.5,
+            size_variation: 0.3,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Bold Dots".to_string(),
+        Settings {
+            point_count: 1500,
+            point_size: 1.0,
+            size_variation: 0.8,
+            contrast: 1.4,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Pointillism".to_string(),
+        Settings {
+            point_count: 12_000,
+            point_size: 1.1,
+            size_variation: 0.0,
+            contrast: 0.6,
+            coloring: StippleColoring::Image,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Density Map".to_string(),
+        Settings {
+            point_count: 6000,
+            coloring: StippleColoring::Density,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Slow Settle".to_string(),
+        Settings {
+            relaxation: 0.2,
+            steps_per_second: 8.0,
+            max_steps: 400,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,162 @@
+//! # Stippling Settings Module
+//!
+//! How many dots there are and how the image is read into darkness, how
+//! fast the dots relax into place, and how they're sized and colored.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum StippleColoring {
+    /// Every dot in the color at the top of the color scheme, on paper in
+    /// the color at the bottom
+    #[default]
+    Ink,
+    /// Dots placed along the color scheme by how dark their cell is
+    Density,
+    /// Dots in the image's own color where they sit
+    Image,
+}
+
+impl StippleColoring {
+    pub fn shader_index(self) -> u32 {
+        match self {
+            StippleColoring::Ink => 0,
+            StippleColoring::Density => 1,
+            StippleColoring::Image => 2,
+        }
+    }
+}
+
+impl FromStr for StippleColoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ink" => Ok(StippleColoring::Ink),
+            "density" => Ok(StippleColoring::Density),
+            "image" => Ok(StippleColoring::Image),
+            _ => Err(format!(
+                "Invalid StippleColoring: '{}'. Expected 'Ink', 'Density' or 'Image'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Dots placed over the image
+    pub point_count: u32,
+    /// Longer side of the density map the dots relax over, in pixels
+    pub resolution: u32,
+    /// Power the image's darkness is raised to, so higher values leave
+    /// midtones sparser
+    pub contrast: f32,
+    /// Stipple the light areas instead of the dark ones
+    pub invert: bool,
+
+    /// Share of the way to its cell's center each dot moves per step
+    pub relaxation: f32,
+    pub steps_per_second: f32,
+    /// Steps taken before the dots are left where they are
+    pub max_steps: u32,
+
+    /// Dot diameter as a share of the average spacing between dots
+    pub point_size: f32,
+    /// How much smaller dots in lighter areas are drawn
+    pub size_variation: f32,
+    pub coloring: StippleColoring,
+
+    /// The same seed always scatters the dots the same way
+    pub seed: u32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            point_count: 4000,
+            resolution: 512,
+            contrast: 1.0,
+            invert: false,
+            relaxation: 0.7,
+            steps_per_second: 15.0,
+            max_steps: 200,
+            point_size: 0.6,
+            size_variation: 0.5,
+            coloring: StippleColoring::Ink,
+            seed: 1,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "point_count",
+            Rule::Count {
+                min: 16,
+                max: 50_000,
+            },
+        ),
+        ("resolution", Rule::Count { min: 64, max: 2048 }),
+        ("contrast", Rule::Range { min: 0.2, max: 5.0 }),
+        ("invert", Rule::Flag),
+        (
+            "relaxation",
+            Rule::Range {
+                min: 0.05,
+                max: 1.0,
+            },
+        ),
+        (
+            "steps_per_second",
+            Rule::Range {
+                min: 1.0,
+                max: 120.0,
+            },
+        ),
+        (
+            "max_steps",
+            Rule::Count {
+                min: 1,
+                max: 10_000,
+            },
+        ),
+        (
+            "point_size",
+            Rule::Range {
+                min: 0.05,
+                max: 1.5,
+            },
+        ),
+        ("size_variation", Rule::Range { min: 0.0, max: 1.0 }),
+        ("coloring", Rule::OneOf(&["Ink", "Density", "Image"])),
+        (
+            "seed",
+            Rule::Count {
+                min: 0,
+                max: u32::MAX as u64,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("point_count", SettingCategory::Generators),
+    ("seed", SettingCategory::Generators),
+    ("coloring", SettingCategory::Colors),
+]);
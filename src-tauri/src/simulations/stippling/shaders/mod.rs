@@ -0,0 +1,4 @@
+pub const STIPPLE_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("stipple.wgsl")
+);
@@ -0,0 +1,94 @@
+// Stipple dots drawn as round, antialiased discs over a sheet of paper the
+// size of the image. Dot positions and radii are in world units, the
+// radius measured vertically and narrowed across so dots stay round on a
+// stretched screen.
+
+struct CameraUniform {
+    transform_matrix: mat4x4<f32>,
+    position: vec2<f32>,
+    zoom: f32,
+    aspect_ratio: f32,
+}
+
+struct Dot {
+    position: vec2<f32>,
+    radius: f32,
+    density: f32,
+    color: vec4<f32>, // linear image color
+}
+
+struct Params {
+    sheet_half_size: vec2<f32>,
+    coloring: u32, // 0 = ink, 1 = density, 2 = image
+    _pad0: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> dots: array<Dot>;
+@group(0) @binding(2) var<storage, read> lut_data: array<u32>;
+@group(1) @binding(0) var<uniform> camera: CameraUniform;
+
+const CORNERS = array<vec2<f32>, 6>(
+    vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(-1.0, 1.0),
+    vec2<f32>(-1.0, 1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0)
+);
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+@vertex
+fn vs_paper(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let corner = CORNERS[vertex_index] * params.sheet_half_size;
+    return camera.transform_matrix * vec4<f32>(corner, 0.0, 1.0);
+}
+
+@fragment
+fn fs_paper() -> @location(0) vec4<f32> {
+    return vec4<f32>(lut_color(0.0), 1.0);
+}
+
+struct DotOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) offset: vec2<f32>,
+    @location(1) @interpolate(flat) color: vec3<f32>,
+}
+
+@vertex
+fn vs_dot(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> DotOutput {
+    let dot = dots[instance_index];
+    let corner = CORNERS[vertex_index];
+    let extent = vec2<f32>(dot.radius / camera.aspect_ratio, dot.radius);
+
+    var out: DotOutput;
+    out.position = camera.transform_matrix * vec4<f32>(dot.position + corner * extent, 0.0, 1.0);
+    out.offset = corner;
+    if (params.coloring == 1u) {
+        // Kept off the paper's end of the scheme so light dots still show
+        out.color = lut_color(0.25 + 0.75 * dot.density);
+    } else if (params.coloring == 2u) {
+        out.color = dot.color.rgb;
+    } else {
+        out.color = lut_color(1.0);
+    }
+    return out;
+}
+
+@fragment
+fn fs_dot(in: DotOutput) -> @location(0) vec4<f32> {
+    let distance = length(in.offset);
+    let edge = fwidth(distance);
+    let coverage = 1.0 - smoothstep(1.0 - edge, 1.0, distance);
+    if (coverage <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(in.color, coverage);
+}
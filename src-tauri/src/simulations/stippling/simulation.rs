@@ -0,0 +1,776 @@
+//! # Stippling Simulation Module
+//!
+//! An image redrawn in dots, which settle into place by weighted Voronoi
+//! relaxation; see [`stipple`] for how.
+//!
+//! ## Technical Overview
+//!
+//! Relaxation runs on the CPU at a steady number of steps per second until
+//! it's taken enough of them. Between steps each dot is drawn part way from
+//! where it was to where the step moved it, so the dots flow into place
+//! instead of jumping. The dots are uploaded every frame and drawn as
+//! instanced discs over a sheet of paper sized to the image.
+//!
+//! Until an image is loaded a shaded sphere is stippled. The finished dots
+//! can be exported to SVG.
+//!
+//! [`stipple`]: super::stipple
+
+use bytemuck::{Pod, Zeroable};
+use image::DynamicImage;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor, Buffer,
+    BufferDescriptor, BufferUsages, Device, PipelineLayoutDescriptor, Queue, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, SurfaceConfiguration, TextureView,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::color_space::srgb_to_linear;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, ParticleBuffer,
+};
+use crate::simulations::traits::Simulation;
+
+use super::settings::{Settings, StippleColoring};
+use super::shaders::STIPPLE_SHADER;
+use super::state::State;
+use super::stipple::{self, DensityMap, SvgDot};
+
+/// Loaded images are shrunk to this size on their longer side, well past
+/// the finest density map
+const MAX_IMAGE_SIZE: u32 = 4096;
+
+/// Relaxation steps caught up on in one frame at most, so a slow frame
+/// doesn't lead to a slower one
+const MAX_STEPS_PER_FRAME: u32 = 4;
+
+/// Where dots colored by density start along the color scheme, so the
+/// lightest stay clear of the paper color
+const DENSITY_COLOR_FLOOR: f32 = 0.25;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    sheet_half_size: [f32; 2],
+    coloring: u32,
+    _pad0: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct GpuDot {
+    position: [f32; 2],
+    radius: f32,
+    density: f32,
+    color: [f32; 4],
+}
+
+#[derive(Debug)]
+pub struct StipplingModel {
+    pub settings: Settings,
+    pub state: State,
+    // Loaded image, if any, kept to read into a new density map when the
+    // resolution or contrast changes
+    image: Option<DynamicImage>,
+    map: DensityMap,
+    dots: Vec<[f32; 2]>,
+    // Where each dot was before the last step
+    previous: Vec<[f32; 2]>,
+    cell_density: Vec<f32>,
+    rng: StdRng,
+    // Seconds since the last step
+    since_step: f32,
+    // CPU copy of the color scheme, for export
+    color_scheme: ColorScheme,
+
+    // GPU resources
+    paper_pipeline: RenderPipeline,
+    dot_pipeline: RenderPipeline,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    dot_buffer: ParticleBuffer<GpuDot>,
+    camera_bind_group: BindGroup,
+
+    pub camera: Camera,
+
+    width: u32,
+    height: u32,
+}
+
+/// Half the width and height, in world units, of a sheet `map_aspect`
+/// wide per unit of height, fit to a screen `screen_aspect` wide per unit of
+/// height. World space is stretched over the screen, so a square sheet on
+/// a wide screen is narrower than it is tall in world units.
+pub fn sheet_half_size(map_aspect: f32, screen_aspect: f32) -> [f32; 2] {
+    let height = (screen_aspect / map_aspect).min(1.0);
+    [height * map_aspect / screen_aspect, height]
+}
+
+fn create_bind_group(
+    device: &Device,
+    layout: &BindGroupLayout,
+    params_buffer: &Buffer,
+    dot_buffer: &Buffer,
+    lut_buffer: &Buffer,
+) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Stippling Bind Group"),
+        layout,
+        entries: &[
+            resource_helpers::buffer_entry(0, params_buffer),
+            resource_helpers::buffer_entry(1, dot_buffer),
+            resource_helpers::buffer_entry(2, lut_buffer),
+        ],
+    })
+}
+
+impl StipplingModel {
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        _app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Stippling Shader"),
+            source: wgpu::ShaderSource::Wgsl(STIPPLE_SHADER.into()),
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Stippling Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let color_scheme = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Stippling LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let dot_buffer = ParticleBuffer::uninitialized(
+            device,
+            "Stippling Dot Buffer",
+            BufferUsages::empty(),
+            settings.point_count as usize,
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Stippling Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::VERTEX, true),
+                resource_helpers::storage_buffer_entry(
+                    2,
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                    true,
+                ),
+            ],
+        });
+        let bind_group = create_bind_group(
+            device,
+            &bind_group_layout,
+            &params_buffer,
+            dot_buffer.buffer(),
+            &lut_buffer,
+        );
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX,
+                )],
+            });
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[resource_helpers::buffer_entry(0, camera.buffer())],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Stippling Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout, &camera_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |label: &str, vertex: &str, fragment: &str, blend| {
+            device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &module,
+                    entry_point: Some(vertex),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &module,
+                    entry_point: Some(fragment),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_config.format,
+                        blend: Some(blend),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let paper_pipeline = pipeline(
+            "Stippling Paper Pipeline",
+            "vs_paper",
+            "fs_paper",
+            wgpu::BlendState::REPLACE,
+        );
+        let dot_pipeline = pipeline(
+            "Stippling Dot Pipeline",
+            "vs_dot",
+            "fs_dot",
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
+
+        let map = DensityMap::sphere(settings.resolution, settings.contrast, settings.invert);
+        let rng = StdRng::seed_from_u64(settings.seed as u64);
+
+        let mut model = Self {
+            settings,
+            state,
+            image: None,
+            map,
+            dots: Vec::new(),
+            previous: Vec::new(),
+            cell_density: Vec::new(),
+            rng,
+            since_step: 0.0,
+            color_scheme,
+            paper_pipeline,
+            dot_pipeline,
+            bind_group_layout,
+            bind_group,
+            params_buffer,
+            lut_buffer,
+            dot_buffer,
+            camera_bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.rebuild_map();
+        model.reset_runtime_state(device, queue)?;
+        Ok(model)
+    }
+
+    pub fn load_image_from_path(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        path: &str,
+    ) -> SimulationResult<()> {
+        let img = image::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
+        self.load_image_from_data(device, queue, img)
+    }
+
+    /// Stipple `img` from a fresh scatter of dots
+    pub fn load_image_from_data(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        img: DynamicImage,
+    ) -> SimulationResult<()> {
+        if img.width() == 0 || img.height() == 0 {
+            return Err("Image has no pixels".into());
+        }
+        let img = if img.width() > MAX_IMAGE_SIZE || img.height() > MAX_IMAGE_SIZE {
+            img.thumbnail(MAX_IMAGE_SIZE, MAX_IMAGE_SIZE)
+        } else {
+            img
+        };
+        self.image = Some(img);
+        self.state.image_loaded = true;
+        self.rebuild_map();
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Read the image, or the sphere, into a density map at the current
+    /// resolution and contrast
+    fn rebuild_map(&mut self) {
+        let (resolution, contrast, invert) = (
+            self.settings.resolution,
+            self.settings.contrast,
+            self.settings.invert,
+        );
+        self.map = match &self.image {
+            Some(image) => DensityMap::from_image(image, resolution, contrast, invert),
+            None => DensityMap::sphere(resolution, contrast, invert),
+        };
+        self.state.map_width = self.map.width;
+        self.state.map_height = self.map.height;
+    }
+
+    /// Take relaxation steps for `delta_time` seconds' worth
+    fn advance(&mut self, delta_time: f32) {
+        if self.state.step >= self.settings.max_steps {
+            return;
+        }
+        let interval = 1.0 / self.settings.steps_per_second.max(0.01);
+        self.since_step += delta_time;
+        let mut taken = 0;
+        while self.since_step >= interval
+            && self.state.step < self.settings.max_steps
+            && taken < MAX_STEPS_PER_FRAME
+        {
+            self.previous.clone_from(&self.dots);
+            let relaxation = stipple::relax(
+                &self.map,
+                &mut self.dots,
+                self.settings.relaxation,
+                &mut self.rng,
+            );
+            self.cell_density = relaxation.cell_density;
+            self.state.mean_movement = relaxation.mean_movement;
+            self.state.step += 1;
+            self.since_step -= interval;
+            taken += 1;
+        }
+        // Behind by more than a frame's catching up; let the rest go
+        self.since_step = self.since_step.min(interval);
+    }
+
+    /// How far the dots are drawn from where they were toward where the
+    /// last step put them
+    fn step_progress(&self) -> f32 {
+        if self.state.step >= self.settings.max_steps {
+            return 1.0;
+        }
+        (self.since_step * self.settings.steps_per_second).clamp(0.0, 1.0)
+    }
+
+    fn sheet_half_size(&self) -> [f32; 2] {
+        let map_aspect = self.map.width as f32 / self.map.height as f32;
+        let screen_aspect = self.width as f32 / self.height.max(1) as f32;
+        sheet_half_size(map_aspect, screen_aspect)
+    }
+
+    fn radius(&self, index: usize) -> f32 {
+        let spacing = stipple::spacing(&self.map, self.dots.len());
+        let density = self.cell_density.get(index).copied().unwrap_or(1.0);
+        stipple::dot_radius(
+            self.settings.point_size,
+            self.settings.size_variation,
+            spacing,
+            density,
+        )
+    }
+
+    fn upload(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) {
+        let half = self.sheet_half_size();
+        let (map_width, map_height) = (self.map.width as f32, self.map.height as f32);
+        let to_world = |p: [f32; 2]| {
+            [
+                (p[0] / map_width * 2.0 - 1.0) * half[0],
+                (1.0 - p[1] / map_height * 2.0) * half[1],
+            ]
+        };
+        let pixel_to_world = 2.0 * half[1] / map_height;
+        let progress = self.step_progress();
+
+        let dots: Vec<GpuDot> = self
+            .dots
+            .iter()
+            .zip(&self.previous)
+            .enumerate()
+            .map(|(index, (dot, previous))| {
+                let position = [
+                    previous[0] + (dot[0] - previous[0]) * progress,
+                    previous[1] + (dot[1] - previous[1]) * progress,
+                ];
+                let [r, g, b] = self.map.color_at(position);
+                GpuDot {
+                    position: to_world(position),
+                    radius: self.radius(index) * pixel_to_world,
+                    density: self.cell_density.get(index).copied().unwrap_or(1.0),
+                    color: [
+                        srgb_to_linear(r as f32 / 255.0),
+                        srgb_to_linear(g as f32 / 255.0),
+                        srgb_to_linear(b as f32 / 255.0),
+                        1.0,
+                    ],
+                }
+            })
+            .collect();
+
+        if self.dot_buffer.upload(device, queue, &dots) {
+            self.bind_group = create_bind_group(
+                device,
+                &self.bind_group_layout,
+                &self.params_buffer,
+                self.dot_buffer.buffer(),
+                &self.lut_buffer,
+            );
+        }
+
+        let params = Params {
+            sheet_half_size: half,
+            coloring: self.settings.coloring.shader_index(),
+            _pad0: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn draw(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        label: &str,
+    ) {
+        self.upload(device, queue);
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Stippling Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+            render_pass.set_pipeline(&self.paper_pipeline);
+            render_pass.draw(0..6, 0..1);
+            render_pass.set_pipeline(&self.dot_pipeline);
+            render_pass.draw(0..6, 0..self.dots.len() as u32);
+        }
+        queue.submit([encoder.finish()]);
+    }
+
+    fn scheme_color(&self, position: f32) -> [u8; 3] {
+        let index = (position.clamp(0.0, 1.0) * 255.0) as usize;
+        [
+            self.color_scheme.red[index],
+            self.color_scheme.green[index],
+            self.color_scheme.blue[index],
+        ]
+    }
+
+    /// The dots where the last step put them, as an SVG the size of the
+    /// density map
+    pub fn export_svg(&self) -> String {
+        let dots: Vec<SvgDot> = self
+            .dots
+            .iter()
+            .enumerate()
+            .map(|(index, &position)| {
+                let density = self.cell_density.get(index).copied().unwrap_or(1.0);
+                let color = match self.settings.coloring {
+                    StippleColoring::Ink => self.scheme_color(1.0),
+                    StippleColoring::Density => self
+                        .scheme_color(DENSITY_COLOR_FLOOR + (1.0 - DENSITY_COLOR_FLOOR) * density),
+                    StippleColoring::Image => self.map.color_at(position),
+                };
+                SvgDot {
+                    position,
+                    radius: self.radius(index),
+                    color,
+                }
+            })
+            .collect();
+        stipple::to_svg(
+            self.map.width,
+            self.map.height,
+            self.scheme_color(0.0),
+            &dots,
+        )
+    }
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for StipplingModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.advance(delta_time);
+        self.camera.update(delta_time);
+        self.camera.upload_to_gpu(queue);
+        self.draw(device, queue, surface_view, "Stippling Render");
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        self.draw(device, queue, surface_view, "Stippling Render Paused");
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        Ok(())
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        _world_x: f32,
+        _world_y: f32,
+        _mouse_button: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        let map_changed = old_settings.resolution != self.settings.resolution
+            || old_settings.contrast != self.settings.contrast
+            || old_settings.invert != self.settings.invert;
+        if map_changed {
+            self.rebuild_map();
+        }
+        if map_changed
+            || old_settings.point_count != self.settings.point_count
+            || old_settings.seed != self.settings.seed
+        {
+            self.reset_runtime_state(device, queue)?;
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.rng = StdRng::seed_from_u64(self.settings.seed as u64);
+        self.dots = stipple::scatter(&self.map, self.settings.point_count as usize, &mut self.rng);
+        self.previous.clone_from(&self.dots);
+        self.cell_density = self
+            .dots
+            .iter()
+            .map(|&dot| {
+                let (x, y) = self.map.pixel(dot);
+                self.map.at(x, y)
+            })
+            .collect();
+        self.since_step = 0.0;
+        self.state.step = 0;
+        self.state.mean_movement = 0.0;
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.point_count = rng.random_range(1000..12_000);
+        self.settings.contrast = rng.random_range(0.6..2.5);
+        self.settings.point_size = rng.random_range(0.3..1.0);
+        self.settings.size_variation = rng.random_range(0.0..1.0);
+        self.settings.coloring = match rng.random_range(0..3) {
+            0 => StippleColoring::Ink,
+            1 => StippleColoring::Density,
+            _ => StippleColoring::Image,
+        };
+        self.settings.seed = rng.random();
+        self.rebuild_map();
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        self.color_scheme = color_scheme.clone();
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "point_count" => {
+                self.settings.point_count = number(setting_name, &value)? as u32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "resolution" => {
+                self.settings.resolution = number(setting_name, &value)? as u32;
+                self.rebuild_map();
+                self.reset_runtime_state(device, queue)?;
+            }
+            "contrast" => {
+                self.settings.contrast = number(setting_name, &value)? as f32;
+                self.rebuild_map();
+                self.reset_runtime_state(device, queue)?;
+            }
+            "invert" => {
+                self.settings.invert = value.as_bool().unwrap_or(false);
+                self.rebuild_map();
+                self.reset_runtime_state(device, queue)?;
+            }
+            "relaxation" => self.settings.relaxation = number(setting_name, &value)? as f32,
+            "steps_per_second" => {
+                self.settings.steps_per_second = number(setting_name, &value)? as f32;
+            }
+            "max_steps" => self.settings.max_steps = number(setting_name, &value)? as u32,
+            "point_size" => self.settings.point_size = number(setting_name, &value)? as f32,
+            "size_variation" => {
+                self.settings.size_variation = number(setting_name, &value)? as f32;
+            }
+            "coloring" => {
+                self.settings.coloring = value
+                    .as_str()
+                    .unwrap_or("Ink")
+                    .parse()
+                    .map_err(|e| format!("Invalid coloring: {}", e))?;
+            }
+            "seed" => {
+                self.settings.seed = number(setting_name, &value)? as u32;
+                self.reset_runtime_state(device, queue)?;
+            }
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Size of the density map the dots relax over
+    pub map_width: u32,
+    pub map_height: u32,
+
+    // Relaxation steps taken, and how far dots moved on the last of them,
+    // in map pixels
+    pub step: u32,
+    pub mean_movement: f32,
+
+    // Whether an image has been loaded to stipple
+    pub image_loaded: bool,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            map_width: 0,
+            map_height: 0,
+            step: 0,
+            mean_movement: 0.0,
+            image_loaded: false,
+            color_scheme_name: "MATPLOTLIB_binary".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Weighted Voronoi stippling, after Secord (2002).
+//!
+//! Dots start scattered over a density map, darker areas drawing more of
+//! them. Each relaxation step gives every dot the pixels nearer to it than
+//! to any other, its Voronoi cell, and moves it toward that cell's center
+//! of darkness. Repeating this is Lloyd's relaxation weighted by density:
+//! dots even out their spacing while bunching where the image is dark.
+//!
+//! Positions are in density map pixels, with (0, 0) at the top left corner
+//! of the first pixel and pixel centers at half steps.
+
+use image::DynamicImage;
+use rand::Rng;
+
+use crate::simulations::shared::field_image::image_to_field;
+
+/// How dark each pixel is, from 0 to 1, alongside the color it came from
+#[derive(Debug, Clone)]
+pub struct DensityMap {
+    pub width: u32,
+    pub height: u32,
+    pub values: Vec<f32>,
+    pub colors: Vec<[u8; 3]>,
+}
+
+impl DensityMap {
+    /// Darkness of `image` shrunk so its longer side is at most `max_side`.
+    /// `contrast` is applied as a power, so above 1 only the darkest areas
+    /// keep many dots. `invert` stipples the light areas instead.
+    pub fn from_image(image: &DynamicImage, max_side: u32, contrast: f32, invert: bool) -> Self {
+        let (width, height) = fit(image.width(), image.height(), max_side);
+        let luminance = image_to_field(image, width, height);
+        let rgb = image
+            .resize_exact(width, height, image::imageops::FilterType::Triangle)
+            .to_rgb8();
+        Self {
+            width,
+            height,
+            values: luminance
+                .iter()
+                .map(|&l| darkness(l, contrast, invert))
+                .collect(),
+            colors: rgb.pixels().map(|p| p.0).collect(),
+        }
+    }
+
+    /// A lit sphere on a pale ground, stippled until an image is loaded
+    pub fn sphere(size: u32, contrast: f32, invert: bool) -> Self {
+        let size = size.max(1);
+        let mut values = Vec::with_capacity((size * size) as usize);
+        let mut colors = Vec::with_capacity((size * size) as usize);
+        for y in 0..size {
+            for x in 0..size {
+                let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let (u, v) = (u / 0.8, v / 0.8);
+                let r2 = u * u + v * v;
+                let luminance = if r2 < 1.0 {
+                    // Lit from the upper left
+                    let normal = [u, -v, (1.0 - r2).sqrt()];
+                    let light = [-0.5, 0.6, 0.62];
+                    let lit: f32 = normal.iter().zip(light).map(|(n, l)| n * l).sum();
+                    0.05 + 0.9 * lit.max(0.0)
+                } else {
+                    // The sphere's shadow falls down and to the right
+                    let (su, sv) = (u - 0.35, v - 0.75);
+                    let shadow = (su * su / 1.2 + sv * sv * 6.0).min(1.0);
+                    0.6 + 0.35 * shadow
+                };
+                let gray = (luminance.clamp(0.0, 1.0) * 255.0) as u8;
+                values.push(darkness(luminance, contrast, invert));
+                colors.push([gray; 3]);
+            }
+        }
+        Self {
+            width: size,
+            height: size,
+            values,
+            colors,
+        }
+    }
+
+    pub fn at(&self, x: u32, y: u32) -> f32 {
+        self.values[(y * self.width + x) as usize]
+    }
+
+    /// The pixel `position` falls in, clamped to the map
+    pub fn pixel(&self, position: [f32; 2]) -> (u32, u32) {
+        (
+            (position[0].max(0.0) as u32).min(self.width - 1),
+            (position[1].max(0.0) as u32).min(self.height - 1),
+        )
+    }
+
+    pub fn color_at(&self, position: [f32; 2]) -> [u8; 3] {
+        let (x, y) = self.pixel(position);
+        self.colors[(y * self.width + x) as usize]
+    }
+}
+
+/// Size `width` x `height` scaled so neither side is over `max_side`,
+/// keeping its proportions
+pub fn fit(width: u32, height: u32, max_side: u32) -> (u32, u32) {
+    let longest = width.max(height).max(1);
+    if longest <= max_side {
+        return (width.max(1), height.max(1));
+    }
+    let scale = max_side as f32 / longest as f32;
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+fn darkness(luminance: f32, contrast: f32, invert: bool) -> f32 {
+    let dark = if invert { luminance } else { 1.0 - luminance };
+    dark.clamp(0.0, 1.0).powf(contrast)
+}
+
+/// A random spot on `map`, likelier the darker it is. Maps with nothing
+/// dark on them take any spot.
+pub fn random_spot(map: &DensityMap, rng: &mut impl Rng) -> [f32; 2] {
+    let spot = |rng: &mut dyn rand::RngCore| {
+        [
+            rng.random_range(0.0..map.width as f32),
+            rng.random_range(0.0..map.height as f32),
+        ]
+    };
+    // Rejection sampling, given up on for nearly blank maps
+    for _ in 0..256 {
+        let position = spot(rng);
+        let (x, y) = map.pixel(position);
+        if rng.random::<f32>() < map.at(x, y) {
+            return position;
+        }
+    }
+    spot(rng)
+}
+
+/// `count` dots scattered by darkness
+pub fn scatter(map: &DensityMap, count: usize, rng: &mut impl Rng) -> Vec<[f32; 2]> {
+    (0..count).map(|_| random_spot(map, rng)).collect()
+}
+
+/// Average spacing between `count` dots spread evenly over `map`
+pub fn spacing(map: &DensityMap, count: usize) -> f32 {
+    ((map.width * map.height) as f32 / count.max(1) as f32).sqrt()
+}
+
+/// Dots bucketed on a coarse grid so the nearest can be found without
+/// checking them all
+struct DotGrid {
+    cell: f32,
+    columns: i32,
+    rows: i32,
+    buckets: Vec<Vec<u32>>,
+}
+
+impl DotGrid {
+    fn new(map: &DensityMap, dots: &[[f32; 2]]) -> Self {
+        let cell = spacing(map, dots.len()).max(1.0);
+        let columns = (map.width as f32 / cell).ceil().max(1.0) as i32;
+        let rows = (map.height as f32 / cell).ceil().max(1.0) as i32;
+        let mut grid = Self {
+            cell,
+            columns,
+            rows,
+            buckets: vec![Vec::new(); (columns * rows) as usize],
+        };
+        for (index, dot) in dots.iter().enumerate() {
+            let (column, row) = grid.bucket(*dot);
+            grid.buckets[(row * columns + column) as usize].push(index as u32);
+        }
+        grid
+    }
+
+    fn bucket(&self, position: [f32; 2]) -> (i32, i32) {
+        (
+            ((position[0] / self.cell) as i32).clamp(0, self.columns - 1),
+            ((position[1] / self.cell) as i32).clamp(0, self.rows - 1),
+        )
+    }
+
+    /// Index of the dot nearest `position`, searching outward a ring of
+    /// buckets at a time until no closer dot could be further out
+    fn nearest(&self, dots: &[[f32; 2]], position: [f32; 2]) -> Option<u32> {
+        let (column, row) = self.bucket(position);
+        let mut best: Option<(u32, f32)> = None;
+        let max_ring = self.columns.max(self.rows);
+        for ring in 0..=max_ring {
+            if let Some((_, distance2)) = best {
+                // Anything in this ring is at least ring - 1 buckets away
+                let reach = (ring - 1).max(0) as f32 * self.cell;
+                if reach * reach > distance2 {
+                    break;
+                }
+            }
+            for r in (row - ring).max(0)..=(row + ring).min(self.rows - 1) {
+                for c in (column - ring).max(0)..=(column + ring).min(self.columns - 1) {
+                    // Only the edge of the ring, the inside was searched already
+                    if (r - row).abs() != ring && (c - column).abs() != ring {
+                        continue;
+                    }
+                    for &index in &self.buckets[(r * self.columns + c) as usize] {
+                        let dot = dots[index as usize];
+                        let (dx, dy) = (dot[0] - position[0], dot[1] - position[1]);
+                        let distance2 = dx * dx + dy * dy;
+                        if best.is_none_or(|(_, d)| distance2 < d) {
+                            best = Some((index, distance2));
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(index, _)| index)
+    }
+}
+
+/// What a relaxation step found
+#[derive(Debug, Clone, Default)]
+pub struct Relaxation {
+    /// Average darkness over each dot's cell, from 0 to 1
+    pub cell_density: Vec<f32>,
+    /// How far dots moved on average, in pixels
+    pub mean_movement: f32,
+}
+
+/// One step of weighted Lloyd relaxation, moving each dot `amount` of the
+/// way toward the center of darkness of its cell. Dots whose cells have no
+/// darkness at all are moved to a random dark spot instead.
+pub fn relax(
+    map: &DensityMap,
+    dots: &mut [[f32; 2]],
+    amount: f32,
+    rng: &mut impl Rng,
+) -> Relaxation {
+    let grid = DotGrid::new(map, dots);
+    // Per dot: weight, weighted x, weighted y, pixel count
+    let mut sums = vec![[0.0_f64; 4]; dots.len()];
+    for y in 0..map.height {
+        for x in 0..map.width {
+            let position = [x as f32 + 0.5, y as f32 + 0.5];
+            let Some(index) = grid.nearest(dots, position) else {
+                continue;
+            };
+            let weight = map.at(x, y) as f64;
+            let sum = &mut sums[index as usize];
+            sum[0] += weight;
+            sum[1] += weight * position[0] as f64;
+            sum[2] += weight * position[1] as f64;
+            sum[3] += 1.0;
+        }
+    }
+
+    let mut moved = 0.0;
+    let cell_density = dots
+        .iter_mut()
+        .zip(&sums)
+        .map(|(dot, &[weight, wx, wy, pixels])| {
+            let target = if weight > 0.0 {
+                [(wx / weight) as f32, (wy / weight) as f32]
+            } else {
+                random_spot(map, rng)
+            };
+            let step = [(target[0] - dot[0]) * amount, (target[1] - dot[1]) * amount];
+            moved += step[0].hypot(step[1]);
+            *dot = [dot[0] + step[0], dot[1] + step[1]];
+            if pixels > 0.0 {
+                (weight / pixels) as f32
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    Relaxation {
+        cell_density,
+        mean_movement: moved / dots.len().max(1) as f32,
+    }
+}
+
+/// Radius of a dot, as a share `point_size` of the dot spacing, shrinking
+/// in lighter cells by up to `size_variation`
+pub fn dot_radius(point_size: f32, size_variation: f32, spacing: f32, density: f32) -> f32 {
+    let shade = 1.0 - size_variation + size_variation * density.clamp(0.0, 1.0).sqrt();
+    0.5 * point_size * spacing * shade
+}
+
+/// A dot as written out to SVG, in map pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgDot {
+    pub position: [f32; 2],
+    pub radius: f32,
+    pub color: [u8; 3],
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// An SVG of `dots` on a `width` x `height` sheet of `paper`
+pub fn to_svg(width: u32, height: u32, paper: [u8; 3], dots: &[SvgDot]) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"{w}\" height=\"{h}\">\n\
+         <rect width=\"{w}\" height=\"{h}\" fill=\"{paper}\"/>\n",
+        w = width,
+        h = height,
+        paper = hex(paper),
+    );
+    for dot in dots {
+        svg.push_str(&format!(
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.3}\" fill=\"{}\"/>\n",
+            dot.position[0],
+            dot.position[1],
+            dot.radius,
+            hex(dot.color),
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
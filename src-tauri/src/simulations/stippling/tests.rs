@@ -0,0 +1,143 @@
+use super::shaders::STIPPLE_SHADER;
+use super::simulation::sheet_half_size;
+use super::stipple::{DensityMap, SvgDot, dot_radius, fit, relax, scatter, spacing, to_svg};
+use crate::simulations::shared::kernel_test::KernelHarness;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// A map dark on its left half and blank on its right
+fn half_dark(width: u32, height: u32) -> DensityMap {
+    let values = (0..width * height)
+        .map(|i| if i % width < width / 2 { 1.0 } else { 0.0 })
+        .collect();
+    DensityMap {
+        width,
+        height,
+        values,
+        colors: vec![[0; 3]; (width * height) as usize],
+    }
+}
+
+fn uniform(width: u32, height: u32) -> DensityMap {
+    DensityMap {
+        width,
+        height,
+        values: vec![1.0; (width * height) as usize],
+        colors: vec![[0; 3]; (width * height) as usize],
+    }
+}
+
+#[test]
+fn images_fit_inside_the_map_size() {
+    assert_eq!(fit(4000, 2000, 512), (512, 256));
+    assert_eq!(fit(300, 900, 600), (200, 600));
+    assert_eq!(fit(100, 50, 512), (100, 50));
+}
+
+#[test]
+fn dots_land_only_on_darkness() {
+    let map = half_dark(64, 32);
+    let dots = scatter(&map, 500, &mut StdRng::seed_from_u64(1));
+    assert_eq!(dots.len(), 500);
+    assert!(dots.iter().all(|dot| dot[0] < 32.0));
+}
+
+#[test]
+fn relaxation_evens_out_dots_on_a_flat_map() {
+    let map = uniform(64, 64);
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut dots = scatter(&map, 64, &mut rng);
+    let mut movement = Vec::new();
+    for _ in 0..40 {
+        movement.push(relax(&map, &mut dots, 1.0, &mut rng).mean_movement);
+    }
+    assert!(movement.last().unwrap() < &(movement[0] * 0.2));
+
+    // Settled dots keep about the average spacing from their neighbors
+    let nearest = |i: usize| {
+        dots.iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, other)| (other[0] - dots[i][0]).hypot(other[1] - dots[i][1]))
+            .fold(f32::MAX, f32::min)
+    };
+    let expected = spacing(&map, dots.len());
+    for i in 0..dots.len() {
+        assert!(nearest(i) > expected * 0.5, "dot {} crowded", i);
+    }
+}
+
+#[test]
+fn a_dot_moves_to_its_cells_center_of_darkness() {
+    let map = half_dark(40, 10);
+    let mut dots = vec![[30.0, 5.0]];
+    let relaxation = relax(&map, &mut dots, 1.0, &mut StdRng::seed_from_u64(3));
+    // Its cell is the whole map, dark from x = 0 to 20
+    assert!((dots[0][0] - 10.0).abs() < 1e-3 && (dots[0][1] - 5.0).abs() < 1e-3);
+    assert!((relaxation.cell_density[0] - 0.5).abs() < 1e-6);
+
+    let mut dots = vec![[30.0, 5.0]];
+    relax(&map, &mut dots, 0.5, &mut StdRng::seed_from_u64(3));
+    assert!((dots[0][0] - 20.0).abs() < 1e-3);
+}
+
+#[test]
+fn dots_stranded_on_blank_paper_are_moved_to_darkness() {
+    let map = half_dark(40, 10);
+    // The second dot's cell is all on the blank half
+    let mut dots = vec![[5.0, 5.0], [38.0, 5.0]];
+    relax(&map, &mut dots, 1.0, &mut StdRng::seed_from_u64(4));
+    assert!(dots[1][0] < 20.0);
+}
+
+#[test]
+fn lighter_cells_get_smaller_dots() {
+    assert_eq!(dot_radius(1.0, 0.0, 10.0, 0.1), 5.0);
+    assert_eq!(dot_radius(1.0, 1.0, 10.0, 1.0), 5.0);
+    assert_eq!(dot_radius(1.0, 1.0, 10.0, 0.25), 2.5);
+    assert_eq!(dot_radius(0.5, 0.0, 10.0, 0.0), 2.5);
+}
+
+#[test]
+fn sheets_fit_the_screen_at_their_own_proportions() {
+    // A square sheet on a wide screen fills the height
+    assert_eq!(sheet_half_size(1.0, 2.0), [0.5, 1.0]);
+    // A wide sheet on a square screen fills the width
+    assert_eq!(sheet_half_size(2.0, 1.0), [1.0, 0.5]);
+    assert_eq!(sheet_half_size(1.5, 1.5), [1.0, 1.0]);
+}
+
+#[test]
+fn svg_has_a_circle_per_dot_on_paper() {
+    let dots = [
+        SvgDot {
+            position: [1.5, 2.0],
+            radius: 0.75,
+            color: [0, 0, 0],
+        },
+        SvgDot {
+            position: [10.0, 4.25],
+            radius: 1.0,
+            color: [255, 16, 0],
+        },
+    ];
+    let svg = to_svg(20, 10, [255, 255, 255], &dots);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("viewBox=\"0 0 20 10\""));
+    assert!(svg.contains("<rect width=\"20\" height=\"10\" fill=\"#ffffff\"/>"));
+    assert!(svg.contains("<circle cx=\"1.50\" cy=\"2.00\" r=\"0.750\" fill=\"#000000\"/>"));
+    assert!(svg.contains("fill=\"#ff1000\""));
+    assert_eq!(svg.matches("<circle").count(), 2);
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[tokio::test]
+async fn shader_compiles() {
+    let harness = KernelHarness::new().await;
+    let _ = harness
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Stippling Shader"),
+            source: wgpu::ShaderSource::Wgsl(STIPPLE_SHADER.into()),
+        });
+}
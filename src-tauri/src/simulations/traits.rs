@@ -18,8 +18,13 @@
 //! It also provides comprehensive user interaction capabilities that work
 //! consistently across all simulation types.
 
-use crate::error::SimulationResult;
-use crate::simulations::shared::BackgroundColorMode;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_tier::GpuTier;
+use crate::simulations::shared::{
+    BackgroundColorMode, BackgroundLayer, HealthIssue, HealthProbe, ProfiledGraph, RewindResource,
+    SettingCategories, SettingValidator,
+};
 use serde_json::Value;
 use std::sync::Arc;
 use wgpu::{Device, Queue, SurfaceConfiguration, TextureView};
@@ -38,6 +43,24 @@ macro_rules! delegate_to_simulation {
             SimulationType::VoronoiCA(simulation) => simulation.$method(),
             SimulationType::Moire(simulation) => simulation.$method(),
             SimulationType::PrimordialParticles(simulation) => simulation.$method(),
+            SimulationType::Turmites(simulation) => simulation.$method(),
+            SimulationType::LifeLike(simulation) => simulation.$method(),
+            SimulationType::Lensing(simulation) => simulation.$method(),
+            SimulationType::MagneticPendulum(simulation) => simulation.$method(),
+            SimulationType::Percolation(simulation) => simulation.$method(),
+            SimulationType::Crowd(simulation) => simulation.$method(),
+            SimulationType::Phyllotaxis(simulation) => simulation.$method(),
+            SimulationType::Harmonograph(simulation) => simulation.$method(),
+            SimulationType::SlimeMold3d(simulation) => simulation.$method(),
+            SimulationType::Chemotaxis(simulation) => simulation.$method(),
+            SimulationType::Softbody(simulation) => simulation.$method(),
+            SimulationType::Eikonal(simulation) => simulation.$method(),
+            SimulationType::Quasicrystal(simulation) => simulation.$method(),
+            SimulationType::Stippling(simulation) => simulation.$method(),
+            SimulationType::Vortex(simulation) => simulation.$method(),
+            SimulationType::Boids(simulation) => simulation.$method(),
+            SimulationType::Dla(simulation) => simulation.$method(),
+            SimulationType::DoublePendulum(simulation) => simulation.$method(),
         }
     };
     ($self:expr, $method:ident, $($arg:expr),+) => {
@@ -52,12 +75,34 @@ macro_rules! delegate_to_simulation {
             SimulationType::VoronoiCA(simulation) => simulation.$method($($arg),+),
             SimulationType::Moire(simulation) => simulation.$method($($arg),+),
             SimulationType::PrimordialParticles(simulation) => simulation.$method($($arg),+),
+            SimulationType::Turmites(simulation) => simulation.$method($($arg),+),
+            SimulationType::LifeLike(simulation) => simulation.$method($($arg),+),
+            SimulationType::Lensing(simulation) => simulation.$method($($arg),+),
+            SimulationType::MagneticPendulum(simulation) => simulation.$method($($arg),+),
+            SimulationType::Percolation(simulation) => simulation.$method($($arg),+),
+            SimulationType::Crowd(simulation) => simulation.$method($($arg),+),
+            SimulationType::Phyllotaxis(simulation) => simulation.$method($($arg),+),
+            SimulationType::Harmonograph(simulation) => simulation.$method($($arg),+),
+            SimulationType::SlimeMold3d(simulation) => simulation.$method($($arg),+),
+            SimulationType::Chemotaxis(simulation) => simulation.$method($($arg),+),
+            SimulationType::Softbody(simulation) => simulation.$method($($arg),+),
+            SimulationType::Eikonal(simulation) => simulation.$method($($arg),+),
+            SimulationType::Quasicrystal(simulation) => simulation.$method($($arg),+),
+            SimulationType::Stippling(simulation) => simulation.$method($($arg),+),
+            SimulationType::Vortex(simulation) => simulation.$method($($arg),+),
+            SimulationType::Boids(simulation) => simulation.$method($($arg),+),
+            SimulationType::Dla(simulation) => simulation.$method($($arg),+),
+            SimulationType::DoublePendulum(simulation) => simulation.$method($($arg),+),
         }
     };
 }
 
 /// Common interface for all simulation types
 ///
+/// Simulated time each fast-forward step stands for in simulations that move
+/// by the frame's time, in seconds
+pub const FAST_FORWARD_STEP_TIME: f32 = 1.0 / 60.0;
+
 /// This trait defines the contract that all simulations must implement.
 /// It provides a unified way to interact with different simulation types
 /// while maintaining clear separation between settings (presettable) and state (runtime).
@@ -79,6 +124,21 @@ pub trait Simulation {
         surface_view: &TextureView,
     ) -> SimulationResult<()>;
 
+    /// Run `steps` updates of the simulation without drawing any of them, to
+    /// jump ahead to a developed state
+    ///
+    /// Each step should advance the simulation as one frame does, taking
+    /// [`FAST_FORWARD_STEP_TIME`] as the frame's time where that matters.
+    fn fast_forward(
+        &mut self,
+        _steps: u32,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Default implementation: no compute-only step to run
+        Err(SimulationError::UnsupportedOperation)
+    }
+
     /// Handle window resize events
     fn resize(
         &mut self,
@@ -166,6 +226,53 @@ pub trait Simulation {
         serde_json::json!({})
     }
 
+    /// GPU buffers and textures holding the simulation's state, for the rewind history
+    ///
+    /// Each resource needs `COPY_SRC` and `COPY_DST` usage.
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        // Default implementation: nothing to snapshot
+        Vec::new()
+    }
+
+    /// Buffers the watchdog should check for broken states
+    ///
+    /// Each buffer needs `STORAGE` usage.
+    fn health_probes(&self) -> Vec<HealthProbe<'_>> {
+        // Default implementation: nothing is watched
+        Vec::new()
+    }
+
+    /// Get back to a working state after the watchdog found `issues`
+    fn recover_from_instability(
+        &mut self,
+        _issues: &[HealthIssue],
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Default implementation: start over with the same settings
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Whether the same settings, seed, input and frame times always give the
+    /// same frames, so a recorded replay plays back exactly
+    ///
+    /// Work the GPU schedules in a different order each run, such as racing
+    /// writes or atomically filled lists, rules this out.
+    fn supports_replay(&self) -> bool {
+        // Default implementation: not known to replay exactly
+        false
+    }
+
+    /// Image drawn behind the simulation, saved with its settings
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        // Default implementation: no background layer
+        None
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        None
+    }
+
     /// Save the current settings as a preset
     ///
     /// This should only save settings, not runtime state.
@@ -239,6 +346,24 @@ pub enum SimulationType {
     VoronoiCA(Box<crate::simulations::voronoi_ca::simulation::VoronoiCASimulation>),
     Moire(Box<crate::simulations::moire::MoireModel>),
     PrimordialParticles(Box<crate::simulations::primordial_particles::PrimordialParticlesModel>),
+    Turmites(Box<crate::simulations::turmites::TurmitesModel>),
+    LifeLike(Box<crate::simulations::life_like::LifeLikeModel>),
+    Lensing(Box<crate::simulations::lensing::LensingModel>),
+    MagneticPendulum(Box<crate::simulations::magnetic_pendulum::MagneticPendulumModel>),
+    Percolation(Box<crate::simulations::percolation::PercolationModel>),
+    Softbody(Box<crate::simulations::softbody::SoftbodyModel>),
+    Chemotaxis(Box<crate::simulations::chemotaxis::ChemotaxisModel>),
+    Crowd(Box<crate::simulations::crowd::CrowdModel>),
+    Phyllotaxis(Box<crate::simulations::phyllotaxis::PhyllotaxisModel>),
+    Harmonograph(Box<crate::simulations::harmonograph::HarmonographModel>),
+    SlimeMold3d(Box<crate::simulations::slime_mold_3d::SlimeMold3dModel>),
+    Eikonal(Box<crate::simulations::eikonal::EikonalModel>),
+    Quasicrystal(Box<crate::simulations::quasicrystal::QuasicrystalModel>),
+    Stippling(Box<crate::simulations::stippling::StipplingModel>),
+    Vortex(Box<crate::simulations::vortex::VortexModel>),
+    Boids(Box<crate::simulations::boids::BoidsModel>),
+    Dla(Box<crate::simulations::dla::DlaModel>),
+    DoublePendulum(Box<crate::simulations::double_pendulum::DoublePendulumModel>),
 }
 
 impl SimulationType {
@@ -252,6 +377,7 @@ impl SimulationType {
         color_scheme_manager: &crate::simulations::shared::ColorSchemeManager,
         app_settings: &crate::commands::AppSettings,
     ) -> Result<Self, Box<dyn std::error::Error>> {
+        let gpu_tier = GpuTier::detect(adapter_info, &device.limits());
         match simulation_type {
             "slime_mold" => {
                 let settings = crate::simulations::slime_mold::settings::Settings::default();
@@ -260,7 +386,7 @@ impl SimulationType {
                     queue,
                     surface_config,
                     adapter_info,
-                    10_000_000,
+                    gpu_tier.default_count(10_000_000),
                     settings,
                     app_settings,
                     color_scheme_manager,
@@ -270,9 +396,12 @@ impl SimulationType {
             "gray_scott" => {
                 let settings = crate::simulations::gray_scott::settings::Settings::default();
 
-                // Use full surface resolution for simulation
-                let sim_width = surface_config.width.max(256);
-                let sim_height = surface_config.height.max(256);
+                // Use full surface resolution unless the grid resolution is fixed
+                let (grid_width, grid_height) = settings
+                    .grid_resolution
+                    .resolve(surface_config.width, surface_config.height);
+                let sim_width = grid_width.max(256);
+                let sim_height = grid_height.max(256);
 
                 let simulation = crate::simulations::gray_scott::GrayScottModel::new(
                     device,
@@ -384,6 +513,240 @@ impl SimulationType {
                 )?;
                 Ok(SimulationType::PrimordialParticles(Box::new(simulation)))
             }
+            "turmites" => {
+                let settings = crate::simulations::turmites::settings::Settings::default();
+
+                let simulation = crate::simulations::turmites::TurmitesModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Turmites(Box::new(simulation)))
+            }
+            "life_like" => {
+                let settings = crate::simulations::life_like::settings::Settings::default();
+
+                let simulation = crate::simulations::life_like::LifeLikeModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::LifeLike(Box::new(simulation)))
+            }
+            "lensing" => {
+                let settings = crate::simulations::lensing::settings::Settings::default();
+
+                let simulation = crate::simulations::lensing::LensingModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Lensing(Box::new(simulation)))
+            }
+            "magnetic_pendulum" => {
+                let settings = crate::simulations::magnetic_pendulum::settings::Settings::default();
+
+                let simulation = crate::simulations::magnetic_pendulum::MagneticPendulumModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::MagneticPendulum(Box::new(simulation)))
+            }
+            "percolation" => {
+                let settings = crate::simulations::percolation::settings::Settings::default();
+
+                let simulation = crate::simulations::percolation::PercolationModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Percolation(Box::new(simulation)))
+            }
+            "crowd" => {
+                let settings = crate::simulations::crowd::settings::Settings::default();
+
+                let simulation = crate::simulations::crowd::CrowdModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Crowd(Box::new(simulation)))
+            }
+            "phyllotaxis" => {
+                let settings = crate::simulations::phyllotaxis::settings::Settings::default();
+
+                let simulation = crate::simulations::phyllotaxis::PhyllotaxisModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Phyllotaxis(Box::new(simulation)))
+            }
+            "harmonograph" => {
+                let settings = crate::simulations::harmonograph::settings::Settings::default();
+
+                let simulation = crate::simulations::harmonograph::HarmonographModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Harmonograph(Box::new(simulation)))
+            }
+            "slime_mold_3d" => {
+                let settings = crate::simulations::slime_mold_3d::settings::Settings::default();
+
+                let simulation = crate::simulations::slime_mold_3d::SlimeMold3dModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::SlimeMold3d(Box::new(simulation)))
+            }
+            "chemotaxis" => {
+                let settings = crate::simulations::chemotaxis::settings::Settings::default();
+
+                let simulation = crate::simulations::chemotaxis::ChemotaxisModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Chemotaxis(Box::new(simulation)))
+            }
+            "softbody" => {
+                let settings = crate::simulations::softbody::settings::Settings::default();
+
+                let simulation = crate::simulations::softbody::SoftbodyModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Softbody(Box::new(simulation)))
+            }
+            "eikonal" => {
+                let settings = crate::simulations::eikonal::settings::Settings::default();
+
+                let simulation = crate::simulations::eikonal::EikonalModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Eikonal(Box::new(simulation)))
+            }
+            "quasicrystal" => {
+                let settings = crate::simulations::quasicrystal::settings::Settings::default();
+
+                let simulation = crate::simulations::quasicrystal::QuasicrystalModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Quasicrystal(Box::new(simulation)))
+            }
+            "stippling" => {
+                let settings = crate::simulations::stippling::settings::Settings::default();
+
+                let simulation = crate::simulations::stippling::StipplingModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Stippling(Box::new(simulation)))
+            }
+            "vortex" => {
+                let settings = crate::simulations::vortex::settings::Settings::default();
+
+                let simulation = crate::simulations::vortex::VortexModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Vortex(Box::new(simulation)))
+            }
+            "boids" => {
+                let settings = crate::simulations::boids::settings::Settings::default();
+
+                let simulation = crate::simulations::boids::BoidsModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Boids(Box::new(simulation)))
+            }
+            "dla" => {
+                let settings = crate::simulations::dla::settings::Settings::default();
+
+                let simulation = crate::simulations::dla::DlaModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::Dla(Box::new(simulation)))
+            }
+            "double_pendulum" => {
+                let settings = crate::simulations::double_pendulum::settings::Settings::default();
+
+                let simulation = crate::simulations::double_pendulum::DoublePendulumModel::new(
+                    device,
+                    queue,
+                    surface_config,
+                    settings,
+                    app_settings,
+                    color_scheme_manager,
+                )?;
+                Ok(SimulationType::DoublePendulum(Box::new(simulation)))
+            }
             _ => Err(format!("Unknown simulation type: {}", simulation_type).into()),
         }
     }
@@ -395,6 +758,260 @@ impl SimulationType {
     ) -> SimulationResult<()> {
         delegate_to_simulation!(self, reset_runtime_state, device, queue)
     }
+
+    /// The identifier used for this simulation type in commands and preset directories
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            SimulationType::SlimeMold(_) => "slime_mold",
+            SimulationType::GrayScott(_) => "gray_scott",
+            SimulationType::ParticleLife(_) => "particle_life",
+            SimulationType::Pellets(_) => "pellets",
+            SimulationType::Flow(_) => "flow",
+            SimulationType::MainMenu(_) => "main_menu",
+            SimulationType::Gradient(_) => "gradient",
+            SimulationType::Moire(_) => "moire",
+            SimulationType::VoronoiCA(_) => "voronoi_ca",
+            SimulationType::PrimordialParticles(_) => "primordial_particles",
+            SimulationType::Turmites(_) => "turmites",
+            SimulationType::LifeLike(_) => "life_like",
+            SimulationType::Lensing(_) => "lensing",
+            SimulationType::MagneticPendulum(_) => "magnetic_pendulum",
+            SimulationType::Percolation(_) => "percolation",
+            SimulationType::Crowd(_) => "crowd",
+            SimulationType::Phyllotaxis(_) => "phyllotaxis",
+            SimulationType::Harmonograph(_) => "harmonograph",
+            SimulationType::SlimeMold3d(_) => "slime_mold_3d",
+            SimulationType::Chemotaxis(_) => "chemotaxis",
+            SimulationType::Softbody(_) => "softbody",
+            SimulationType::Eikonal(_) => "eikonal",
+            SimulationType::Quasicrystal(_) => "quasicrystal",
+            SimulationType::Stippling(_) => "stippling",
+            SimulationType::Vortex(_) => "vortex",
+            SimulationType::Boids(_) => "boids",
+            SimulationType::Dla(_) => "dla",
+            SimulationType::DoublePendulum(_) => "double_pendulum",
+        }
+    }
+
+    /// The limits [`update_setting`](Simulation::update_setting) values are
+    /// checked against before they reach the simulation
+    pub fn setting_validator(&self) -> &'static SettingValidator {
+        match self {
+            SimulationType::SlimeMold(_) => {
+                &crate::simulations::slime_mold::settings::SETTING_RULES
+            }
+            SimulationType::GrayScott(_) => {
+                &crate::simulations::gray_scott::settings::SETTING_RULES
+            }
+            SimulationType::ParticleLife(_) => {
+                &crate::simulations::particle_life::settings::SETTING_RULES
+            }
+            SimulationType::Pellets(_) => &crate::simulations::pellets::settings::SETTING_RULES,
+            SimulationType::Flow(_) => &crate::simulations::flow::settings::SETTING_RULES,
+            SimulationType::Moire(_) => &crate::simulations::moire::settings::SETTING_RULES,
+            SimulationType::PrimordialParticles(_) => {
+                &crate::simulations::primordial_particles::settings::SETTING_RULES
+            }
+            SimulationType::Turmites(_) => &crate::simulations::turmites::settings::SETTING_RULES,
+            SimulationType::LifeLike(_) => &crate::simulations::life_like::settings::SETTING_RULES,
+            SimulationType::Lensing(_) => &crate::simulations::lensing::settings::SETTING_RULES,
+            SimulationType::MagneticPendulum(_) => {
+                &crate::simulations::magnetic_pendulum::settings::SETTING_RULES
+            }
+            SimulationType::Percolation(_) => {
+                &crate::simulations::percolation::settings::SETTING_RULES
+            }
+            SimulationType::Crowd(_) => &crate::simulations::crowd::settings::SETTING_RULES,
+            SimulationType::Phyllotaxis(_) => {
+                &crate::simulations::phyllotaxis::settings::SETTING_RULES
+            }
+            SimulationType::Harmonograph(_) => {
+                &crate::simulations::harmonograph::settings::SETTING_RULES
+            }
+            SimulationType::SlimeMold3d(_) => {
+                &crate::simulations::slime_mold_3d::settings::SETTING_RULES
+            }
+            SimulationType::Chemotaxis(_) => {
+                &crate::simulations::chemotaxis::settings::SETTING_RULES
+            }
+            SimulationType::Softbody(_) => &crate::simulations::softbody::settings::SETTING_RULES,
+            SimulationType::Eikonal(_) => &crate::simulations::eikonal::settings::SETTING_RULES,
+            SimulationType::Quasicrystal(_) => {
+                &crate::simulations::quasicrystal::settings::SETTING_RULES
+            }
+            SimulationType::Stippling(_) => &crate::simulations::stippling::settings::SETTING_RULES,
+            SimulationType::Vortex(_) => &crate::simulations::vortex::settings::SETTING_RULES,
+            SimulationType::Boids(_) => &crate::simulations::boids::settings::SETTING_RULES,
+            SimulationType::Dla(_) => &crate::simulations::dla::settings::SETTING_RULES,
+            SimulationType::DoublePendulum(_) => {
+                &crate::simulations::double_pendulum::settings::SETTING_RULES
+            }
+            _ => &SettingValidator::NONE,
+        }
+    }
+
+    /// Which settings [`randomized`](crate::simulations::shared::randomize::randomized)
+    /// counts as colors, physics or generators
+    pub fn setting_categories(&self) -> &'static SettingCategories {
+        match self {
+            SimulationType::SlimeMold(_) => {
+                &crate::simulations::slime_mold::settings::SETTING_CATEGORIES
+            }
+            SimulationType::GrayScott(_) => {
+                &crate::simulations::gray_scott::settings::SETTING_CATEGORIES
+            }
+            SimulationType::ParticleLife(_) => {
+                &crate::simulations::particle_life::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Pellets(_) => {
+                &crate::simulations::pellets::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Flow(_) => &crate::simulations::flow::settings::SETTING_CATEGORIES,
+            SimulationType::Moire(_) => &crate::simulations::moire::settings::SETTING_CATEGORIES,
+            SimulationType::PrimordialParticles(_) => {
+                &crate::simulations::primordial_particles::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Turmites(_) => {
+                &crate::simulations::turmites::settings::SETTING_CATEGORIES
+            }
+            SimulationType::LifeLike(_) => {
+                &crate::simulations::life_like::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Lensing(_) => {
+                &crate::simulations::lensing::settings::SETTING_CATEGORIES
+            }
+            SimulationType::MagneticPendulum(_) => {
+                &crate::simulations::magnetic_pendulum::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Percolation(_) => {
+                &crate::simulations::percolation::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Crowd(_) => &crate::simulations::crowd::settings::SETTING_CATEGORIES,
+            SimulationType::Phyllotaxis(_) => {
+                &crate::simulations::phyllotaxis::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Harmonograph(_) => {
+                &crate::simulations::harmonograph::settings::SETTING_CATEGORIES
+            }
+            SimulationType::SlimeMold3d(_) => {
+                &crate::simulations::slime_mold_3d::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Chemotaxis(_) => {
+                &crate::simulations::chemotaxis::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Softbody(_) => {
+                &crate::simulations::softbody::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Eikonal(_) => {
+                &crate::simulations::eikonal::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Quasicrystal(_) => {
+                &crate::simulations::quasicrystal::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Stippling(_) => {
+                &crate::simulations::stippling::settings::SETTING_CATEGORIES
+            }
+            SimulationType::Vortex(_) => &crate::simulations::vortex::settings::SETTING_CATEGORIES,
+            SimulationType::Boids(_) => &crate::simulations::boids::settings::SETTING_CATEGORIES,
+            SimulationType::Dla(_) => &crate::simulations::dla::settings::SETTING_CATEGORIES,
+            SimulationType::DoublePendulum(_) => {
+                &crate::simulations::double_pendulum::settings::SETTING_CATEGORIES
+            }
+            _ => &SettingCategories::NONE,
+        }
+    }
+
+    /// The setting holding the seed the simulation's world grows from, for
+    /// simulations whose worlds start from random numbers
+    pub fn seed_setting(&self) -> Option<&'static str> {
+        match self {
+            SimulationType::SlimeMold(_)
+            | SimulationType::SlimeMold3d(_)
+            | SimulationType::ParticleLife(_)
+            | SimulationType::Pellets(_)
+            | SimulationType::Boids(_)
+            | SimulationType::Dla(_) => Some("random_seed"),
+            SimulationType::Flow(_) => Some("noise_seed"),
+            SimulationType::LifeLike(_) => Some("soup_seed"),
+            SimulationType::Percolation(_)
+            | SimulationType::Crowd(_)
+            | SimulationType::Chemotaxis(_)
+            | SimulationType::Eikonal(_)
+            | SimulationType::Stippling(_) => Some("seed"),
+            _ => None,
+        }
+    }
+
+    /// The pan/zoom camera, for simulations that have one
+    pub fn camera(&self) -> Option<&Camera> {
+        match self {
+            SimulationType::SlimeMold(simulation) => Some(&simulation.camera),
+            SimulationType::GrayScott(simulation) => Some(&simulation.camera),
+            SimulationType::ParticleLife(simulation) => Some(&simulation.camera),
+            SimulationType::Flow(simulation) => Some(&simulation.camera),
+            SimulationType::Pellets(simulation) => Some(&simulation.camera),
+            SimulationType::VoronoiCA(simulation) => Some(&simulation.camera),
+            SimulationType::PrimordialParticles(simulation) => Some(&simulation.camera),
+            SimulationType::Turmites(simulation) => Some(&simulation.camera),
+            SimulationType::LifeLike(simulation) => Some(&simulation.camera),
+            SimulationType::Lensing(simulation) => Some(&simulation.camera),
+            SimulationType::MagneticPendulum(simulation) => Some(&simulation.camera),
+            SimulationType::Percolation(simulation) => Some(&simulation.camera),
+            SimulationType::Crowd(simulation) => Some(&simulation.camera),
+            SimulationType::Phyllotaxis(simulation) => Some(&simulation.camera),
+            SimulationType::Harmonograph(simulation) => Some(&simulation.camera),
+            SimulationType::Chemotaxis(simulation) => Some(&simulation.camera),
+            SimulationType::Softbody(simulation) => Some(&simulation.camera),
+            SimulationType::Eikonal(simulation) => Some(&simulation.camera),
+            SimulationType::Quasicrystal(simulation) => Some(&simulation.camera),
+            SimulationType::Stippling(simulation) => Some(&simulation.camera),
+            SimulationType::Vortex(simulation) => Some(&simulation.camera),
+            SimulationType::Boids(simulation) => Some(&simulation.camera),
+            SimulationType::Dla(simulation) => Some(&simulation.camera),
+            SimulationType::DoublePendulum(simulation) => Some(&simulation.camera),
+            _ => None, // No camera for other simulations
+        }
+    }
+
+    pub fn camera_mut(&mut self) -> Option<&mut Camera> {
+        match self {
+            SimulationType::SlimeMold(simulation) => Some(&mut simulation.camera),
+            SimulationType::GrayScott(simulation) => Some(&mut simulation.camera),
+            SimulationType::ParticleLife(simulation) => Some(&mut simulation.camera),
+            SimulationType::Flow(simulation) => Some(&mut simulation.camera),
+            SimulationType::Pellets(simulation) => Some(&mut simulation.camera),
+            SimulationType::VoronoiCA(simulation) => Some(&mut simulation.camera),
+            SimulationType::PrimordialParticles(simulation) => Some(&mut simulation.camera),
+            SimulationType::Turmites(simulation) => Some(&mut simulation.camera),
+            SimulationType::LifeLike(simulation) => Some(&mut simulation.camera),
+            SimulationType::Lensing(simulation) => Some(&mut simulation.camera),
+            SimulationType::MagneticPendulum(simulation) => Some(&mut simulation.camera),
+            SimulationType::Percolation(simulation) => Some(&mut simulation.camera),
+            SimulationType::Crowd(simulation) => Some(&mut simulation.camera),
+            SimulationType::Phyllotaxis(simulation) => Some(&mut simulation.camera),
+            SimulationType::Harmonograph(simulation) => Some(&mut simulation.camera),
+            SimulationType::Chemotaxis(simulation) => Some(&mut simulation.camera),
+            SimulationType::Softbody(simulation) => Some(&mut simulation.camera),
+            SimulationType::Eikonal(simulation) => Some(&mut simulation.camera),
+            SimulationType::Quasicrystal(simulation) => Some(&mut simulation.camera),
+            SimulationType::Stippling(simulation) => Some(&mut simulation.camera),
+            SimulationType::Vortex(simulation) => Some(&mut simulation.camera),
+            SimulationType::Boids(simulation) => Some(&mut simulation.camera),
+            SimulationType::Dla(simulation) => Some(&mut simulation.camera),
+            SimulationType::DoublePendulum(simulation) => Some(&mut simulation.camera),
+            _ => None, // No camera for other simulations
+        }
+    }
+
+    /// The passes a frame is made of, for simulations rendering through a
+    /// render graph
+    pub fn render_graph_mut(&mut self) -> Option<&mut dyn ProfiledGraph> {
+        match self {
+            SimulationType::ParticleLife(simulation) => Some(&mut simulation.render_graph),
+            SimulationType::Pellets(simulation) => Some(&mut simulation.render_graph),
+            _ => None,
+        }
+    }
 }
 
 impl Simulation for SimulationType {
@@ -417,6 +1034,15 @@ impl Simulation for SimulationType {
         delegate_to_simulation!(self, render_frame_paused, device, queue, surface_view)
     }
 
+    fn fast_forward(
+        &mut self,
+        steps: u32,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        delegate_to_simulation!(self, fast_forward, steps, device, queue)
+    }
+
     fn resize(
         &mut self,
         device: &Arc<Device>,
@@ -438,6 +1064,32 @@ impl Simulation for SimulationType {
             SimulationType::PrimordialParticles(simulation) => {
                 simulation.resize(device, queue, new_config)
             }
+            SimulationType::Turmites(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::LifeLike(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Lensing(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::MagneticPendulum(simulation) => {
+                simulation.resize(device, queue, new_config)
+            }
+            SimulationType::Percolation(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Crowd(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Phyllotaxis(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Harmonograph(simulation) => {
+                simulation.resize(device, queue, new_config)
+            }
+            SimulationType::SlimeMold3d(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Chemotaxis(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Softbody(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Eikonal(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Quasicrystal(simulation) => {
+                simulation.resize(device, queue, new_config)
+            }
+            SimulationType::Stippling(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Vortex(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Boids(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::Dla(simulation) => simulation.resize(device, queue, new_config),
+            SimulationType::DoublePendulum(simulation) => {
+                simulation.resize(device, queue, new_config)
+            }
         }
     }
 
@@ -516,6 +1168,35 @@ impl Simulation for SimulationType {
         delegate_to_simulation!(self, get_camera_state)
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        delegate_to_simulation!(self, rewind_resources)
+    }
+
+    fn health_probes(&self) -> Vec<HealthProbe<'_>> {
+        delegate_to_simulation!(self, health_probes)
+    }
+
+    fn recover_from_instability(
+        &mut self,
+        issues: &[HealthIssue],
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        delegate_to_simulation!(self, recover_from_instability, issues, device, queue)
+    }
+
+    fn supports_replay(&self) -> bool {
+        delegate_to_simulation!(self, supports_replay)
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        delegate_to_simulation!(self, background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        delegate_to_simulation!(self, background_layer_mut)
+    }
+
     fn save_preset(&self, preset_name: &str) -> SimulationResult<()> {
         delegate_to_simulation!(self, save_preset, preset_name)
     }
@@ -0,0 +1,83 @@
+pub mod rule;
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::TurmitesModel;
+
+use crate::simulation::preset_manager::{Preset, TurmitesPresetManager};
+
+/// Initialize Turmites presets with built-in configurations
+pub fn init_presets(preset_manager: &mut TurmitesPresetManager) {
+    use settings::{AntSpawn, Settings, TurmiteColorMode};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Four Ants".to_string(),
+        Settings {
+            ant_count: 4,
+            spawn: AntSpawn::Center,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Symmetric Bloom".to_string(),
+        Settings {
+            rule: "LLRR".to_string(),
+            steps_per_frame: 200,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Square Filler".to_string(),
+        Settings {
+            rule: "LRRRRRLLR".to_string(),
+            steps_per_frame: 500,
+            cell_size: 2,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Growing Triangle".to_string(),
+        Settings {
+            rule: "RRLLLRLLLRRR".to_string(),
+            steps_per_frame: 500,
+            cell_size: 2,
+            color_mode: TurmiteColorMode::Age,
+            age_span: 1200.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Fibonacci Spiral".to_string(),
+        Settings {
+            rule: "{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}".to_string(),
+            steps_per_frame: 200,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Ant Colony".to_string(),
+        Settings {
+            ant_count: 32,
+            spawn: AntSpawn::Random,
+            steps_per_frame: 100,
+            cell_size: 2,
+            color_mode: TurmiteColorMode::Age,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
@@ -0,0 +1,382 @@
+//! # Turmite Rules
+//!
+//! A turmite is an ant with an internal state. Each step it reads the color
+//! of the cell it stands on, and its rule, looked up by state and color, says
+//! which color to paint the cell, which way to turn and which state to move
+//! to. It then walks forward one cell.
+//!
+//! Rules are written in one of two notations:
+//!
+//! - Langton's ant notation, one turn per color like `RL` or `LLRR`. The ant
+//!   has a single state, turns as its current cell's letter says and moves
+//!   the cell on to the next color.
+//! - The table notation Golly uses, like `{{{1,2,0},{0,8,0}}}`: a list of
+//!   states, each a list of `{color to write, turn, next state}` for every
+//!   color. Turns are 1 (none), 2 (right), 4 (u-turn) and 8 (left).
+
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+pub const MAX_STATES: u32 = 8;
+pub const MAX_COLORS: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    None,
+    Right,
+    /// A u-turn
+    Back,
+    Left,
+}
+
+impl Turn {
+    const ALL: [Turn; 4] = [Turn::None, Turn::Right, Turn::Back, Turn::Left];
+
+    /// Clockwise quarter turns, as the step shader applies them
+    pub fn quarter_turns(self) -> u32 {
+        match self {
+            Turn::None => 0,
+            Turn::Right => 1,
+            Turn::Back => 2,
+            Turn::Left => 3,
+        }
+    }
+
+    fn letter(self) -> char {
+        match self {
+            Turn::None => 'N',
+            Turn::Right => 'R',
+            Turn::Back => 'U',
+            Turn::Left => 'L',
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter.to_ascii_uppercase() {
+            'N' => Some(Turn::None),
+            'R' => Some(Turn::Right),
+            'U' => Some(Turn::Back),
+            'L' => Some(Turn::Left),
+            _ => None,
+        }
+    }
+
+    fn golly_code(self) -> u32 {
+        match self {
+            Turn::None => 1,
+            Turn::Right => 2,
+            Turn::Back => 4,
+            Turn::Left => 8,
+        }
+    }
+
+    fn from_golly_code(code: u32) -> Option<Self> {
+        match code {
+            1 => Some(Turn::None),
+            2 => Some(Turn::Right),
+            4 => Some(Turn::Back),
+            8 => Some(Turn::Left),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub write: u32,
+    pub turn: Turn,
+    pub next_state: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurmiteRule {
+    states: u32,
+    colors: u32,
+    // Indexed by state * colors + color
+    transitions: Vec<Transition>,
+}
+
+impl TurmiteRule {
+    /// Langton's ant with a turn for each color
+    pub fn ant(turns: &[Turn]) -> Result<Self, String> {
+        let colors = turns.len() as u32;
+        if !(1..=MAX_COLORS).contains(&colors) {
+            return Err(format!(
+                "An ant needs 1 to {} turns, got {}",
+                MAX_COLORS, colors
+            ));
+        }
+        let transitions = turns
+            .iter()
+            .enumerate()
+            .map(|(color, &turn)| Transition {
+                write: (color as u32 + 1) % colors,
+                turn,
+                next_state: 0,
+            })
+            .collect();
+        Ok(Self {
+            states: 1,
+            colors,
+            transitions,
+        })
+    }
+
+    /// A rule from `transitions[state][color]`
+    pub fn from_table(table: Vec<Vec<Transition>>) -> Result<Self, String> {
+        let states = table.len() as u32;
+        if !(1..=MAX_STATES).contains(&states) {
+            return Err(format!(
+                "A turmite needs 1 to {} states, got {}",
+                MAX_STATES, states
+            ));
+        }
+        let colors = table[0].len() as u32;
+        if !(1..=MAX_COLORS).contains(&colors) {
+            return Err(format!(
+                "A turmite needs 1 to {} colors, got {}",
+                MAX_COLORS, colors
+            ));
+        }
+        if let Some(state) = table.iter().position(|row| row.len() as u32 != colors) {
+            return Err(format!(
+                "State {} has {} colors where state 0 has {}",
+                state,
+                table[state].len(),
+                colors
+            ));
+        }
+        let transitions: Vec<Transition> = table.into_iter().flatten().collect();
+        if let Some(transition) = transitions
+            .iter()
+            .find(|t| t.write >= colors || t.next_state >= states)
+        {
+            return Err(format!(
+                "Transition {{{},{},{}}} goes past {} colors or {} states",
+                transition.write,
+                transition.turn.golly_code(),
+                transition.next_state,
+                colors,
+                states
+            ));
+        }
+        Ok(Self {
+            states,
+            colors,
+            transitions,
+        })
+    }
+
+    pub fn states(&self) -> u32 {
+        self.states
+    }
+
+    pub fn colors(&self) -> u32 {
+        self.colors
+    }
+
+    pub fn transition(&self, state: u32, color: u32) -> Transition {
+        self.transitions[(state * self.colors + color) as usize]
+    }
+
+    /// Whether the rule can be written in Langton's ant notation
+    pub fn is_ant(&self) -> bool {
+        self.states == 1
+            && self
+                .transitions
+                .iter()
+                .enumerate()
+                .all(|(color, t)| t.write == (color as u32 + 1) % self.colors)
+    }
+
+    /// One word per transition, in the order the step shader looks them up:
+    /// the color to write in the low byte, the quarter turns in the next and
+    /// the next state in the third
+    pub fn to_gpu_words(&self) -> Vec<u32> {
+        self.transitions
+            .iter()
+            .map(|t| t.write | (t.turn.quarter_turns() << 8) | (t.next_state << 16))
+            .collect()
+    }
+
+    /// Mostly ants, which usually build something worth watching; otherwise
+    /// a small turmite
+    pub fn random(rng: &mut impl Rng) -> Self {
+        if rng.random_bool(0.7) {
+            let colors = rng.random_range(2..=8);
+            loop {
+                let turns: Vec<Turn> = (0..colors)
+                    .map(|_| {
+                        if rng.random_bool(0.1) {
+                            Turn::ALL[rng.random_range(0..4)]
+                        } else if rng.random_bool(0.5) {
+                            Turn::Left
+                        } else {
+                            Turn::Right
+                        }
+                    })
+                    .collect();
+                // Without both turns the ant only walks in circles or lines
+                if turns.contains(&Turn::Left) && turns.contains(&Turn::Right) {
+                    return Self::ant(&turns).expect("ant sizes are in range");
+                }
+            }
+        }
+        let states = rng.random_range(2..=3);
+        let colors = rng.random_range(2..=3);
+        let table = (0..states)
+            .map(|_| {
+                (0..colors)
+                    .map(|_| Transition {
+                        write: rng.random_range(0..colors),
+                        turn: Turn::ALL[rng.random_range(0..4)],
+                        next_state: rng.random_range(0..states),
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_table(table).expect("turmite sizes are in range")
+    }
+}
+
+impl Default for TurmiteRule {
+    /// Langton's ant
+    fn default() -> Self {
+        Self::ant(&[Turn::Right, Turn::Left]).expect("ant sizes are in range")
+    }
+}
+
+impl fmt::Display for TurmiteRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ant() {
+            return self
+                .transitions
+                .iter()
+                .try_for_each(|t| write!(f, "{}", t.turn.letter()));
+        }
+        write!(f, "{{")?;
+        for state in 0..self.states {
+            if state > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{{")?;
+            for color in 0..self.colors {
+                if color > 0 {
+                    write!(f, ",")?;
+                }
+                let t = self.transition(state, color);
+                write!(
+                    f,
+                    "{{{},{},{}}}",
+                    t.write,
+                    t.turn.golly_code(),
+                    t.next_state
+                )?;
+            }
+            write!(f, "}}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl FromStr for TurmiteRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('{') {
+            return parse_table(s);
+        }
+        let turns = s
+            .chars()
+            .map(|letter| {
+                Turn::from_letter(letter).ok_or_else(|| {
+                    format!(
+                        "Invalid turn '{}' in rule '{}'. Expected L, R, N or U",
+                        letter, s
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::ant(&turns)
+    }
+}
+
+enum Node {
+    Number(u32),
+    List(Vec<Node>),
+}
+
+fn parse_table(s: &str) -> Result<TurmiteRule, String> {
+    let mut chars = s.chars().filter(|c| !c.is_whitespace()).peekable();
+    let root = parse_node(&mut chars)?;
+    if chars.next().is_some() {
+        return Err(format!("Unexpected text after the table in '{}'", s));
+    }
+
+    let invalid = || format!("Expected {{{{{{write,turn,next}},...}},...}}, got '{}'", s);
+    let Node::List(states) = root else {
+        return Err(invalid());
+    };
+    let table = states
+        .into_iter()
+        .map(|state| {
+            let Node::List(colors) = state else {
+                return Err(invalid());
+            };
+            colors
+                .into_iter()
+                .map(|transition| match transition {
+                    Node::List(fields) => match fields.as_slice() {
+                        [
+                            Node::Number(write),
+                            Node::Number(turn),
+                            Node::Number(next_state),
+                        ] => Ok(Transition {
+                            write: *write,
+                            turn: Turn::from_golly_code(*turn).ok_or_else(|| {
+                                format!("Invalid turn {}. Expected 1, 2, 4 or 8", turn)
+                            })?,
+                            next_state: *next_state,
+                        }),
+                        _ => Err(invalid()),
+                    },
+                    Node::Number(_) => Err(invalid()),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    TurmiteRule::from_table(table)
+}
+
+fn parse_node(chars: &mut std::iter::Peekable<impl Iterator<Item = char>>) -> Result<Node, String> {
+    match chars.peek() {
+        Some('{') => {
+            chars.next();
+            let mut items = Vec::new();
+            loop {
+                items.push(parse_node(chars)?);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some('}') => return Ok(Node::List(items)),
+                    Some(c) => return Err(format!("Expected ',' or '}}', got '{}'", c)),
+                    None => return Err("Missing '}'".to_string()),
+                }
+            }
+        }
+        Some(c) if c.is_ascii_digit() => {
+            let mut digits = String::new();
+            while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                digits.push(*c);
+                chars.next();
+            }
+            digits
+                .parse()
+                .map(Node::Number)
+                .map_err(|_| format!("Number {} is too large", digits))
+        }
+        Some(c) => Err(format!("Expected a number or '{{', got '{}'", c)),
+        None => Err("Unexpected end of rule".to_string()),
+    }
+}
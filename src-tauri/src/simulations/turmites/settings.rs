@@ -0,0 +1,139 @@
+//! # Turmites Settings Module
+//!
+//! The rule the ants follow, how many there are and where they start, how
+//! fast they walk and how the grid they leave behind is colored.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Where the ants are put down when the grid is cleared
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum AntSpawn {
+    /// All on the center cell, facing different ways
+    #[default]
+    Center,
+    /// Spaced around a circle
+    Ring,
+    Random,
+}
+
+impl FromStr for AntSpawn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "center" => Ok(AntSpawn::Center),
+            "ring" => Ok(AntSpawn::Ring),
+            "random" => Ok(AntSpawn::Random),
+            _ => Err(format!(
+                "Invalid AntSpawn: '{}'. Expected 'center', 'ring' or 'random'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TurmiteColorMode {
+    /// Each cell color gets its own place along the color scheme
+    #[default]
+    State,
+    /// Cells fade down the color scheme after an ant leaves them
+    Age,
+}
+
+impl FromStr for TurmiteColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "state" => Ok(TurmiteColorMode::State),
+            "age" => Ok(TurmiteColorMode::Age),
+            _ => Err(format!(
+                "Invalid TurmiteColorMode: '{}'. Expected 'state' or 'age'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Langton's ant turns like `RL`, or a turmite table like
+    /// `{{{1,2,0},{0,8,0}}}`; see [`super::rule`]
+    pub rule: String,
+    pub ant_count: u32,
+    pub spawn: AntSpawn,
+    /// Steps each ant takes per frame
+    pub steps_per_frame: u32,
+    /// Screen pixels per grid cell
+    pub cell_size: u32,
+
+    pub color_mode: TurmiteColorMode,
+    /// Frames a visited cell takes to fade to the bottom of the color scheme
+    pub age_span: f32,
+    pub show_ants: bool,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rule: "RL".to_string(),
+            ant_count: 1,
+            spawn: AntSpawn::Center,
+            steps_per_frame: 50,
+            cell_size: 4,
+            color_mode: TurmiteColorMode::State,
+            age_span: 600.0,
+            show_ants: true,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+/// Limits for [`update_setting`](crate::simulations::traits::Simulation::update_setting).
+/// The rule string is checked by parsing it.
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        ("ant_count", Rule::Count { min: 1, max: 256 }),
+        ("spawn", Rule::OneOf(&["Center", "Ring", "Random"])),
+        (
+            "steps_per_frame",
+            Rule::Count {
+                min: 1,
+                max: 10_000,
+            },
+        ),
+        ("cell_size", Rule::Count { min: 1, max: 16 }),
+        ("color_mode", Rule::OneOf(&["State", "Age"])),
+        (
+            "age_span",
+            Rule::Range {
+                min: 1.0,
+                max: 3600.0,
+            },
+        ),
+        ("show_ants", Rule::Flag),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("rule", SettingCategory::Generators),
+    ("ant_count", SettingCategory::Generators),
+    ("spawn", SettingCategory::Generators),
+    ("color_mode", SettingCategory::Colors),
+    ("age_span", SettingCategory::Colors),
+    ("show_ants", SettingCategory::Colors),
+]);
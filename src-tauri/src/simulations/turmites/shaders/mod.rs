@@ -0,0 +1,6 @@
+pub const STEP_SHADER: &str = include_str!("step.wgsl");
+pub const PAINT_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("paint.wgsl")
+);
+pub const RENDER_INFINITE_SHADER: &str = crate::simulations::shared::INFINITE_RENDER_SHADER;
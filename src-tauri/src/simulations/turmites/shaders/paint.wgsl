@@ -0,0 +1,73 @@
+// Colors the display texture from the grid, then marks where the ants are.
+
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    colors: u32,
+    states: u32,
+    ant_count: u32,
+    steps: u32,
+    frame: u32,
+    color_mode: u32, // 0 = State, 1 = Age
+    age_span: f32,
+    show_ants: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+struct Ant {
+    x: u32,
+    y: u32,
+    direction: u32,
+    state: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> cells: array<u32>;
+@group(0) @binding(2) var<storage, read> ants: array<Ant>;
+@group(0) @binding(3) var<storage, read> lut_data: array<u32>;
+@group(0) @binding(4) var output_texture: texture_storage_2d<rgba8unorm, write>;
+
+const COLOR_MASK: u32 = 0xffu;
+// Visit frames are stored in 24 bits
+const FRAME_MASK: u32 = 0xffffffu;
+
+fn lut_color(position: f32) -> vec4<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec4<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0),
+        1.0
+    );
+}
+
+@compute @workgroup_size(8, 8)
+fn paint(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.grid_width || id.y >= params.grid_height) {
+        return;
+    }
+
+    let cell = cells[id.y * params.grid_width + id.x];
+    var position = 0.0;
+    if (params.color_mode == 0u) {
+        let color = (cell & COLOR_MASK) % params.colors;
+        position = f32(color) / f32(max(params.colors - 1u, 1u));
+    } else if (cell != 0u) {
+        // Never-visited cells stay at the bottom, visited ones only get close
+        let age = (params.frame - (cell >> 8u)) & FRAME_MASK;
+        position = max(1.0 - f32(age) / params.age_span, 1.0 / 255.0);
+    }
+
+    textureStore(output_texture, vec2<i32>(id.xy), lut_color(position));
+}
+
+@compute @workgroup_size(64)
+fn mark_ants(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (params.show_ants == 0u || id.x >= params.ant_count) {
+        return;
+    }
+
+    let ant = ants[id.x];
+    textureStore(output_texture, vec2<i32>(i32(ant.x), i32(ant.y)), vec4<f32>(1.0, 0.0, 0.0, 1.0));
+}
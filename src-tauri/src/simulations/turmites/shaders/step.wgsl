@@ -0,0 +1,80 @@
+// Walks every ant through its steps for the frame. One invocation per ant.
+
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    colors: u32,
+    states: u32,
+    ant_count: u32,
+    steps: u32,
+    frame: u32,
+    color_mode: u32, // 0 = State, 1 = Age
+    age_span: f32,
+    show_ants: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+struct Ant {
+    x: u32,
+    y: u32,
+    direction: u32, // Clockwise from north
+    state: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+// Color in the low byte, the frame the cell was last visited in above it
+@group(0) @binding(1) var<storage, read_write> cells: array<atomic<u32>>;
+@group(0) @binding(2) var<storage, read_write> ants: array<Ant>;
+// Color to write in the low byte, quarter turns in the next, next state in the third
+@group(0) @binding(3) var<storage, read> rule: array<u32>;
+
+const COLOR_MASK: u32 = 0xffu;
+
+fn forward(direction: u32) -> vec2<i32> {
+    switch direction {
+        case 0u: { return vec2<i32>(0, -1); }
+        case 1u: { return vec2<i32>(1, 0); }
+        case 2u: { return vec2<i32>(0, 1); }
+        default: { return vec2<i32>(-1, 0); }
+    }
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.ant_count) {
+        return;
+    }
+
+    var ant = ants[id.x];
+    let visited = params.frame << 8u;
+    let size = vec2<i32>(i32(params.grid_width), i32(params.grid_height));
+
+    for (var i = 0u; i < params.steps; i++) {
+        let index = ant.y * params.grid_width + ant.x;
+
+        // Another ant can write the cell between our read and write, in
+        // which case the transition is looked up again for its new color
+        var transition = 0u;
+        var old = atomicLoad(&cells[index]);
+        loop {
+            let color = (old & COLOR_MASK) % params.colors;
+            transition = rule[ant.state * params.colors + color];
+            let result = atomicCompareExchangeWeak(&cells[index], old, visited | (transition & COLOR_MASK));
+            if (result.exchanged) {
+                break;
+            }
+            old = result.old_value;
+        }
+
+        ant.direction = (ant.direction + (transition >> 8u)) & 3u;
+        ant.state = ((transition >> 16u) & COLOR_MASK) % params.states;
+
+        // The grid wraps around at the edges
+        let next = (vec2<i32>(i32(ant.x), i32(ant.y)) + forward(ant.direction) + size) % size;
+        ant.x = u32(next.x);
+        ant.y = u32(next.y);
+    }
+
+    ants[id.x] = ant;
+}
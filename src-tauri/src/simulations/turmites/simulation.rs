@@ -0,0 +1,949 @@
+//! # Turmites Simulation Module
+//!
+//! Langton's ant and its generalizations on a grid that wraps at the edges.
+//!
+//! ## Technical Overview
+//!
+//! Each frame runs three compute passes and a render pass:
+//! 1. Every ant takes its steps, one invocation per ant. Cells are updated
+//!    with compare-and-swap so ants meeting on a cell don't lose steps.
+//! 2. The grid is colored into the display texture by cell color or by how
+//!    long ago an ant last passed.
+//! 3. The ants are marked on top.
+//! 4. The display texture is drawn through the camera with infinite tiling.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor,
+    Buffer, BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    FilterMode, PipelineLayoutDescriptor, Queue, RenderPipeline, RenderPipelineDescriptor, Sampler,
+    SamplerDescriptor, ShaderStages, SurfaceConfiguration, Texture, TextureFormat, TextureView,
+    TextureViewDescriptor,
+};
+
+use crate::commands::AppSettings;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::{
+    BackgroundLayer, ColorScheme, ColorSchemeManager, ParticleBuffer, RewindResource,
+};
+use crate::simulations::traits::Simulation;
+
+use super::rule::{MAX_COLORS, MAX_STATES, TurmiteRule};
+use super::settings::{AntSpawn, Settings, TurmiteColorMode};
+use super::shaders::{PAINT_SHADER, RENDER_INFINITE_SHADER, STEP_SHADER};
+use super::state::State;
+
+/// The largest `ant_count` allowed by the setting rules
+const MAX_ANTS: u32 = 256;
+/// Cells store the frame they were visited in above their color byte
+const FRAME_MASK: u32 = 0xff_ffff;
+/// Cells painted around the cursor in each direction
+const BRUSH_RADIUS: i32 = 2;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    grid_width: u32,
+    grid_height: u32,
+    colors: u32,
+    states: u32,
+    ant_count: u32,
+    steps: u32,
+    frame: u32,
+    color_mode: u32, // 0 = State, 1 = Age
+    age_span: f32,
+    show_ants: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Pod, Zeroable)]
+pub struct Ant {
+    pub x: u32,
+    pub y: u32,
+    /// Clockwise from north
+    pub direction: u32,
+    pub state: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct RenderParams {
+    filtering_mode: u32, // 0 = nearest, 1 = linear, 2 = lanczos
+    _pad1: u32,
+    _pad2: u32,
+    _pad3: u32,
+}
+
+/// Everything the grid's bind groups point at besides the grid itself
+#[derive(Debug)]
+struct Resources {
+    step_bind_group_layout: BindGroupLayout,
+    paint_bind_group_layout: BindGroupLayout,
+    render_infinite_bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+    params_buffer: Buffer,
+    ants_buffer: ParticleBuffer<Ant>,
+    rule_buffer: Buffer,
+    lut_buffer: Buffer,
+    texture_render_params_buffer: Buffer,
+}
+
+/// The cells and the texture they're painted into, remade when the grid
+/// changes size
+#[derive(Debug)]
+struct Grid {
+    width: u32,
+    height: u32,
+    cells_buffer: Buffer,
+    _display_texture: Texture,
+    step_bind_group: BindGroup,
+    paint_bind_group: BindGroup,
+    render_infinite_bind_group: BindGroup,
+}
+
+impl Grid {
+    fn new(device: &Device, width: u32, height: u32, resources: &Resources) -> Self {
+        let cells_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Turmites Cells Buffer"),
+            size: (width * height) as u64 * std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let display_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Turmites Display Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let display_view = display_texture.create_view(&TextureViewDescriptor::default());
+
+        let step_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Turmites Step Bind Group"),
+            layout: &resources.step_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &resources.params_buffer),
+                resource_helpers::buffer_entry(1, &cells_buffer),
+                resource_helpers::buffer_entry(2, resources.ants_buffer.buffer()),
+                resource_helpers::buffer_entry(3, &resources.rule_buffer),
+            ],
+        });
+
+        let paint_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Turmites Paint Bind Group"),
+            layout: &resources.paint_bind_group_layout,
+            entries: &[
+                resource_helpers::buffer_entry(0, &resources.params_buffer),
+                resource_helpers::buffer_entry(1, &cells_buffer),
+                resource_helpers::buffer_entry(2, resources.ants_buffer.buffer()),
+                resource_helpers::buffer_entry(3, &resources.lut_buffer),
+                resource_helpers::texture_view_entry(4, &display_view),
+            ],
+        });
+
+        let render_infinite_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Turmites Render Infinite Bind Group"),
+            layout: &resources.render_infinite_bind_group_layout,
+            entries: &[
+                resource_helpers::texture_view_entry(0, &display_view),
+                resource_helpers::sampler_bind_entry(1, &resources.sampler),
+                resource_helpers::buffer_entry(2, &resources.texture_render_params_buffer),
+            ],
+        });
+
+        Self {
+            width,
+            height,
+            cells_buffer,
+            _display_texture: display_texture,
+            step_bind_group,
+            paint_bind_group,
+            render_infinite_bind_group,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TurmitesModel {
+    pub settings: Settings,
+    pub state: State,
+    rule: TurmiteRule,
+    // Counts from 1 so a cell visited in frame 0 can't look unvisited
+    frame: u32,
+
+    // GPU resources
+    step_pipeline: ComputePipeline,
+    paint_pipeline: ComputePipeline,
+    mark_ants_pipeline: ComputePipeline,
+    render_infinite_pipeline: RenderPipeline,
+    resources: Resources,
+    grid: Grid,
+    camera_bind_group: BindGroup,
+
+    // Camera for infinite rendering
+    pub camera: Camera,
+
+    // Surface size the grid is laid over
+    width: u32,
+    height: u32,
+}
+
+/// Where `settings` puts its ants on a `width` by `height` grid
+pub fn spawn_ants(settings: &Settings, width: u32, height: u32, rng: &mut impl Rng) -> Vec<Ant> {
+    let count = settings.ant_count.min(MAX_ANTS);
+    let (center_x, center_y) = (width / 2, height / 2);
+    (0..count)
+        .map(|i| match settings.spawn {
+            AntSpawn::Center => Ant {
+                x: center_x,
+                y: center_y,
+                direction: i % 4,
+                state: 0,
+            },
+            AntSpawn::Ring => {
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                let radius = width.min(height) as f32 * 0.2;
+                let x = center_x as f32 + angle.sin() * radius;
+                let y = center_y as f32 - angle.cos() * radius;
+                Ant {
+                    x: (x.round() as u32).min(width - 1),
+                    y: (y.round() as u32).min(height - 1),
+                    // Facing along the ring
+                    direction: ((angle / std::f32::consts::FRAC_PI_2).round() as u32 + 1) % 4,
+                    state: 0,
+                }
+            }
+            AntSpawn::Random => Ant {
+                x: rng.random_range(0..width),
+                y: rng.random_range(0..height),
+                direction: rng.random_range(0..4),
+                state: 0,
+            },
+        })
+        .collect()
+}
+
+impl TurmitesModel {
+    /// Calculate the number of tiles needed for infinite rendering based on zoom level
+    fn calculate_tile_count(&self) -> u32 {
+        let zoom = self.camera.zoom;
+        // Each tile covers 2.0 world units, so we need enough tiles to cover the visible area
+        let visible_world_size = 2.0 / zoom;
+        let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
+        let min_tiles = if zoom < 0.1 { 7 } else { 5 };
+        tiles_needed.max(min_tiles).min(1024)
+    }
+
+    fn grid_size(&self) -> (u32, u32) {
+        let cell_size = self.settings.cell_size.max(1);
+        (
+            (self.width / cell_size).max(1),
+            (self.height / cell_size).max(1),
+        )
+    }
+
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let rule: TurmiteRule = settings
+            .rule
+            .parse()
+            .map_err(SimulationError::InvalidParameter)?;
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let step_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Turmites Step Shader"),
+            source: wgpu::ShaderSource::Wgsl(STEP_SHADER.into()),
+        });
+        let paint_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Turmites Paint Shader"),
+            source: wgpu::ShaderSource::Wgsl(PAINT_SHADER.into()),
+        });
+        let render_infinite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Turmites Render Infinite Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_INFINITE_SHADER.into()),
+        });
+
+        // Nearest filtering keeps the cells crisp when zoomed in
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Turmites Sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Turmites Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let ants_buffer = ParticleBuffer::uninitialized(
+            device,
+            "Turmites Ants Buffer",
+            BufferUsages::empty(),
+            MAX_ANTS as usize,
+        );
+
+        let rule_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Turmites Rule Buffer"),
+            size: (MAX_STATES * MAX_COLORS) as u64 * std::mem::size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&rule_buffer, 0, bytemuck::cast_slice(&rule.to_gpu_words()));
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Turmites LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let render_params = RenderParams {
+            filtering_mode: app_settings.texture_filtering.into(),
+            _pad1: 0,
+            _pad2: 0,
+            _pad3: 0,
+        };
+        let texture_render_params_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Turmites Texture Render Params Buffer"),
+                contents: bytemuck::cast_slice(&[render_params]),
+                usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            });
+
+        let step_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Turmites Step Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, false),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, true),
+            ],
+        });
+
+        let paint_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Turmites Paint Bind Group Layout"),
+            entries: &[
+                resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, true),
+                resource_helpers::storage_texture_entry(
+                    4,
+                    ShaderStages::COMPUTE,
+                    wgpu::StorageTextureAccess::WriteOnly,
+                    TextureFormat::Rgba8Unorm,
+                ),
+            ],
+        });
+
+        let render_infinite_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Turmites Render Infinite Bind Group Layout"),
+                entries: &[
+                    resource_helpers::texture_entry(
+                        0,
+                        ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        1,
+                        ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                    resource_helpers::uniform_buffer_entry(2, ShaderStages::FRAGMENT),
+                ],
+            });
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX,
+                )],
+            });
+
+        let step_pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Turmites Step Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Turmites Step Pipeline Layout"),
+                bind_group_layouts: &[&step_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            module: &step_module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let paint_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Turmites Paint Pipeline Layout"),
+            bind_group_layouts: &[&paint_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let paint_pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Turmites Paint Pipeline"),
+            layout: Some(&paint_pipeline_layout),
+            module: &paint_module,
+            entry_point: Some("paint"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let mark_ants_pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Turmites Mark Ants Pipeline"),
+            layout: Some(&paint_pipeline_layout),
+            module: &paint_module,
+            entry_point: Some("mark_ants"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let render_infinite_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Turmites Render Infinite Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Turmites Render Infinite Pipeline Layout"),
+                bind_group_layouts: &[
+                    &render_infinite_bind_group_layout,
+                    &camera_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_infinite_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_infinite_module,
+                entry_point: Some("fs_main_texture"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[resource_helpers::buffer_entry(0, camera.buffer())],
+        });
+
+        let resources = Resources {
+            step_bind_group_layout,
+            paint_bind_group_layout,
+            render_infinite_bind_group_layout,
+            sampler,
+            params_buffer,
+            ants_buffer,
+            rule_buffer,
+            lut_buffer,
+            texture_render_params_buffer,
+        };
+        // Replaced by resize_grid once the model exists
+        let grid = Grid::new(device, 1, 1, &resources);
+
+        let mut model = Self {
+            settings,
+            state,
+            rule,
+            frame: 0,
+            step_pipeline,
+            paint_pipeline,
+            mark_ants_pipeline,
+            render_infinite_pipeline,
+            resources,
+            grid,
+            camera_bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.resize_grid(device, queue)?;
+        Ok(model)
+    }
+
+    /// Remake the grid for the surface size and cell size, and start over
+    /// on it
+    fn resize_grid(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let (width, height) = self.grid_size();
+        self.grid = Grid::new(device, width, height, &self.resources);
+        self.state.grid_width = width;
+        self.state.grid_height = height;
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn respawn_ants(&mut self, queue: &Arc<Queue>) {
+        let ants = spawn_ants(
+            &self.settings,
+            self.grid.width,
+            self.grid.height,
+            &mut rand::rng(),
+        );
+        queue.write_buffer(
+            self.resources.ants_buffer.buffer(),
+            0,
+            bytemuck::cast_slice(&ants),
+        );
+    }
+
+    fn set_rule(&mut self, rule: TurmiteRule, queue: &Arc<Queue>) {
+        queue.write_buffer(
+            &self.resources.rule_buffer,
+            0,
+            bytemuck::cast_slice(&rule.to_gpu_words()),
+        );
+        self.settings.rule = rule.to_string();
+        self.rule = rule;
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>) {
+        let params = Params {
+            grid_width: self.grid.width,
+            grid_height: self.grid.height,
+            colors: self.rule.colors(),
+            states: self.rule.states(),
+            ant_count: self.settings.ant_count.min(MAX_ANTS),
+            steps: self.settings.steps_per_frame,
+            frame: self.frame,
+            color_mode: match self.settings.color_mode {
+                TurmiteColorMode::State => 0,
+                TurmiteColorMode::Age => 1,
+            },
+            age_span: self.settings.age_span.max(1.0),
+            show_ants: self.settings.show_ants as u32,
+            _pad0: 0,
+            _pad1: 0,
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
+    fn encode_paint(&self, encoder: &mut wgpu::CommandEncoder) {
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Turmites Paint Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.paint_pipeline);
+            compute_pass.set_bind_group(0, &self.grid.paint_bind_group, &[]);
+            compute_pass.dispatch_workgroups(
+                self.grid.width.div_ceil(8),
+                self.grid.height.div_ceil(8),
+                1,
+            );
+        }
+
+        // A pass of its own so the ants land on top of the painted cells
+        if self.settings.show_ants {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Turmites Mark Ants Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.mark_ants_pipeline);
+            compute_pass.set_bind_group(0, &self.grid.paint_bind_group, &[]);
+            compute_pass.dispatch_workgroups(
+                self.settings.ant_count.min(MAX_ANTS).div_ceil(64),
+                1,
+                1,
+            );
+        }
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let tile_count = self.calculate_tile_count();
+        let total_instances = tile_count * tile_count;
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Turmites Infinite Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.render_infinite_pipeline);
+        render_pass.set_bind_group(0, &self.grid.render_infinite_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+        render_pass.draw(0..6, 0..total_instances);
+    }
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for TurmitesModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        self.frame = self.frame % FRAME_MASK + 1;
+        self.state.steps += self.settings.steps_per_frame as u64;
+        self.update_params(queue);
+
+        self.camera.update(delta_time);
+        self.camera.upload_to_gpu(queue);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Turmites Render"),
+        });
+
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Turmites Step Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.step_pipeline);
+            compute_pass.set_bind_group(0, &self.grid.step_bind_group, &[]);
+            compute_pass.dispatch_workgroups(
+                self.settings.ant_count.min(MAX_ANTS).div_ceil(64),
+                1,
+                1,
+            );
+        }
+        self.encode_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // Repainted so color changes show while paused
+        self.update_params(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Turmites Render Paused"),
+        });
+        self.encode_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.resize_grid(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Left paints the cells under the cursor in the first color after
+        // the background, right clears them
+        let color = match mouse_button {
+            0 => 1 % self.rule.colors(),
+            2 => 0,
+            _ => return Ok(()),
+        };
+        let word = if color == 0 {
+            0
+        } else {
+            (self.frame << 8) | color
+        };
+
+        // Wrap world coords to the base tile, then flip Y into grid rows
+        let wrapped_x = (world_x + 1.0).rem_euclid(2.0) - 1.0;
+        let wrapped_y = (world_y + 1.0).rem_euclid(2.0) - 1.0;
+        let (width, height) = (self.grid.width as i32, self.grid.height as i32);
+        let x = ((wrapped_x + 1.0) * 0.5 * width as f32) as i32;
+        let y = ((1.0 - wrapped_y) * 0.5 * height as f32) as i32;
+
+        for dy in -BRUSH_RADIUS..=BRUSH_RADIUS {
+            for dx in -BRUSH_RADIUS..=BRUSH_RADIUS {
+                let cell_x = (x + dx).rem_euclid(width);
+                let cell_y = (y + dy).rem_euclid(height);
+                let offset = (cell_y * width + cell_x) as u64 * std::mem::size_of::<u32>() as u64;
+                queue.write_buffer(&self.grid.cells_buffer, offset, bytemuck::bytes_of(&word));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        vec![
+            RewindResource::Buffer(&self.grid.cells_buffer),
+            self.resources.ants_buffer.rewind_resource(),
+        ]
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let rule: TurmiteRule = new_settings
+            .rule
+            .parse()
+            .map_err(SimulationError::InvalidParameter)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+        self.set_rule(rule, queue);
+
+        if old_settings.cell_size != self.settings.cell_size {
+            self.resize_grid(device, queue)?;
+        } else if old_settings.ant_count != self.settings.ant_count
+            || old_settings.spawn != self.settings.spawn
+        {
+            self.respawn_ants(queue);
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Turmites Clear Grid"),
+        });
+        encoder.clear_buffer(&self.grid.cells_buffer, 0, None);
+        queue.submit([encoder.finish()]);
+
+        self.respawn_ants(queue);
+        self.frame = 0;
+        self.state.steps = 0;
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.set_rule(TurmiteRule::random(&mut rng), queue);
+        self.settings.ant_count = if rng.random_bool(0.5) {
+            1
+        } else {
+            rng.random_range(2..=8)
+        };
+        self.settings.spawn = match rng.random_range(0..3) {
+            0 => AntSpawn::Center,
+            1 => AntSpawn::Ring,
+            _ => AntSpawn::Random,
+        };
+        self.settings.color_mode = if rng.random_bool(0.5) {
+            TurmiteColorMode::State
+        } else {
+            TurmiteColorMode::Age
+        };
+        // A new rule is best watched growing from an empty grid
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "rule" => {
+                let rule: TurmiteRule = value
+                    .as_str()
+                    .ok_or("rule must be a string")?
+                    .parse()
+                    .map_err(SimulationError::InvalidParameter)?;
+                self.set_rule(rule, queue);
+            }
+            "ant_count" => {
+                self.settings.ant_count = number(setting_name, &value)? as u32;
+                self.respawn_ants(queue);
+            }
+            "spawn" => {
+                self.settings.spawn = value
+                    .as_str()
+                    .unwrap_or("Center")
+                    .parse()
+                    .map_err(|e| format!("Invalid spawn: {}", e))?;
+                self.respawn_ants(queue);
+            }
+            "steps_per_frame" => {
+                self.settings.steps_per_frame = number(setting_name, &value)? as u32
+            }
+            "cell_size" => {
+                self.settings.cell_size = number(setting_name, &value)? as u32;
+                self.resize_grid(device, queue)?;
+            }
+            "color_mode" => {
+                self.settings.color_mode = value
+                    .as_str()
+                    .unwrap_or("State")
+                    .parse()
+                    .map_err(|e| format!("Invalid color_mode: {}", e))?;
+            }
+            "age_span" => self.settings.age_span = number(setting_name, &value)? as f32,
+            "show_ants" => self.settings.show_ants = value.as_bool().unwrap_or(true),
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Grid size in cells
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Steps each ant has taken since the grid was cleared
+    pub steps: u64,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            grid_width: 0,
+            grid_height: 0,
+            steps: 0,
+            color_scheme_name: "MATPLOTLIB_viridis".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
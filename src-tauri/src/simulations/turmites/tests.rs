@@ -0,0 +1,111 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::rule::{Transition, TurmiteRule, Turn};
+use super::settings::{AntSpawn, Settings};
+use super::shaders::{PAINT_SHADER, RENDER_INFINITE_SHADER, STEP_SHADER};
+use super::simulation::spawn_ants;
+use crate::simulations::shared::kernel_test::KernelHarness;
+
+#[test]
+fn ant_notation_matches_the_table() {
+    let ant: TurmiteRule = "RL".parse().unwrap();
+    let table: TurmiteRule = "{{{1,2,0},{0,8,0}}}".parse().unwrap();
+    assert_eq!(ant, table);
+    assert_eq!(ant, TurmiteRule::default());
+    assert_eq!(table.to_string(), "RL");
+
+    let llrr: TurmiteRule = " llrr ".parse().unwrap();
+    assert_eq!(llrr.colors(), 4);
+    assert_eq!(
+        llrr.transition(0, 3),
+        Transition {
+            write: 0,
+            turn: Turn::Right,
+            next_state: 0,
+        }
+    );
+}
+
+#[test]
+fn tables_round_trip() {
+    let text = "{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}";
+    let rule: TurmiteRule = text.parse().unwrap();
+    assert_eq!(rule.states(), 2);
+    assert_eq!(rule.colors(), 2);
+    assert!(!rule.is_ant());
+    assert_eq!(rule.to_string(), text);
+    assert_eq!(
+        "{ {{1, 8, 1}, {1, 8, 1}},\n {{1, 2, 1}, {0, 1, 0}} }"
+            .parse::<TurmiteRule>()
+            .unwrap(),
+        rule
+    );
+
+    // Right is one clockwise quarter turn, left three
+    assert_eq!(
+        rule.to_gpu_words(),
+        vec![0x1_0301, 0x1_0301, 0x1_0101, 0x0_0000]
+    );
+}
+
+#[test]
+fn bad_rules_are_rejected() {
+    for text in [
+        "",
+        "RLX",
+        "{{{1,2,0},{0,8,0}}",
+        "{{{1,3,0},{0,8,0}}}",
+        "{{{2,2,0},{0,8,0}}}",
+        "{{{1,2,1},{0,8,0}}}",
+        "{{{1,2,0},{0,8,0}},{{1,2,0}}}",
+        "{{{1,2,0,0}}}",
+        "RLRLRLRLRLRLRLRLR",
+    ] {
+        assert!(text.parse::<TurmiteRule>().is_err(), "{:?} parsed", text);
+    }
+}
+
+#[test]
+fn random_rules_parse_back() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..200 {
+        let rule = TurmiteRule::random(&mut rng);
+        assert_eq!(rule.to_string().parse::<TurmiteRule>().unwrap(), rule);
+    }
+}
+
+#[test]
+fn ants_spawn_inside_the_grid() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for spawn in [AntSpawn::Center, AntSpawn::Ring, AntSpawn::Random] {
+        let settings = Settings {
+            ant_count: 9,
+            spawn,
+            ..Settings::default()
+        };
+        let ants = spawn_ants(&settings, 40, 30, &mut rng);
+        assert_eq!(ants.len(), 9);
+        assert!(
+            ants.iter()
+                .all(|ant| ant.x < 40 && ant.y < 30 && ant.direction < 4)
+        );
+    }
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Turmites Step Shader", STEP_SHADER),
+        ("Turmites Paint Shader", PAINT_SHADER),
+        ("Turmites Render Infinite Shader", RENDER_INFINITE_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
@@ -7,4 +7,7 @@ pub const GRID_POPULATE_SHADER: &str = include_str!("grid_populate.wgsl");
 pub const JFA_INIT_SHADER: &str = include_str!("jfa_init.wgsl");
 pub const JFA_ITERATION_SHADER: &str = include_str!("jfa_iteration.wgsl");
 pub const VCA_INFINITE_RENDER_SHADER: &str = include_str!("infinite_render.wgsl");
-pub const VORONOI_RENDER_JFA_SHADER: &str = include_str!("voronoi_render_jfa.wgsl");
+pub const VORONOI_RENDER_JFA_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("voronoi_render_jfa.wgsl")
+);
@@ -38,11 +38,6 @@ struct Vertex {
 // A: distance to site (squared)
 @group(0) @binding(3) var jfa_texture: texture_2d<f32>;
 
-fn srgb_to_linear(srgb: f32) -> f32 {
-  if (srgb <= 0.04045) { return srgb / 12.92; }
-  return pow((srgb + 0.055) / 1.055, 2.4);
-}
-
 fn lut_sample(intensity: f32) -> vec3<f32> {
   let lut_index = clamp(intensity * 255.0, 0.0, 255.0);
   let idx = u32(lut_index);
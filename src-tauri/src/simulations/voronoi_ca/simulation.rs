@@ -10,10 +10,12 @@ use wgpu::{
 };
 
 use crate::commands::app_settings::AppSettings;
-use crate::error::SimulationResult;
+use crate::error::{SimulationError, SimulationResult};
+use crate::simulations::shared::BackgroundLayer;
 use crate::simulations::shared::camera::Camera;
 use crate::simulations::shared::gpu_utils::resource_helpers;
 use crate::simulations::shared::ping_pong_textures::PingPongTextures;
+use crate::simulations::shared::rewind::RewindResource;
 use crate::simulations::traits::Simulation;
 
 use super::shaders::{
@@ -146,6 +148,7 @@ pub struct VoronoiCASimulation {
     app_settings: crate::commands::app_settings::AppSettings,
     // VCA settings
     rulestring: String,
+    background_layer: BackgroundLayer,
     // Camera
     pub camera: Camera,
     camera_bind_group: BindGroup,
@@ -456,6 +459,7 @@ impl VoronoiCASimulation {
             contents: bytemuck::cast_slice(&points),
             usage: wgpu::BufferUsages::VERTEX
                 | wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
                 | wgpu::BufferUsages::COPY_DST,
         });
 
@@ -1116,6 +1120,7 @@ impl VoronoiCASimulation {
             border_width: 1.0,
             app_settings: app_settings.clone(),
             rulestring: "B3/S23".to_string(), // Default to Conway's Game of Life
+            background_layer: BackgroundLayer::default(),
             camera,
             camera_bind_group,
             display_texture,
@@ -1179,6 +1184,7 @@ impl VoronoiCASimulation {
             size: (std::mem::size_of::<Vertex>() * new_count as usize) as u64,
             usage: wgpu::BufferUsages::VERTEX
                 | wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
                 | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
@@ -2285,6 +2291,10 @@ impl Simulation for VoronoiCASimulation {
         serde_json::json!({ "position": [self.camera.position[0], self.camera.position[1]], "zoom": self.camera.zoom })
     }
 
+    fn rewind_resources(&self) -> Vec<RewindResource<'_>> {
+        vec![RewindResource::Buffer(&self.vertex_buffer)]
+    }
+
     fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
         Ok(())
     }
@@ -2316,6 +2326,10 @@ impl Simulation for VoronoiCASimulation {
             };
             queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
         }
+        if let Some(layer) = settings.get("background_layer") {
+            self.background_layer =
+                serde_json::from_value(layer.clone()).map_err(SimulationError::Serialization)?;
+        }
 
         Ok(())
     }
@@ -2464,10 +2478,19 @@ impl Simulation for VoronoiCASimulation {
             "color_scheme_reversed": self.color_scheme_reversed,
             "coloring_mode": match self.color_mode { 0 => "Random", 1 => "Density", 2 => "Age", 3 => "Binary", _ => "Random" },
             "borders_enabled": self.borders_enabled,
-            "border_width": self.border_width
+            "border_width": self.border_width,
+            "background_layer": self.background_layer
         })
     }
 
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.background_layer)
+    }
+
     fn get_state(&self) -> Value {
         serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
     }
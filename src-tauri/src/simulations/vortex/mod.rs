@@ -0,0 +1,80 @@
+pub mod settings;
+pub mod shaders;
+pub mod simulation;
+pub mod state;
+pub mod vortices;
+
+#[cfg(test)]
+mod tests;
+
+pub use simulation::VortexModel;
+
+use crate::simulation::preset_manager::{Preset, VortexPresetManager};
+
+/// Initialize Vortex presets with built-in configurations
+pub fn init_presets(preset_manager: &mut VortexPresetManager) {
+    use settings::{DisplayMode, DyePattern, InitialCondition, Settings};
+
+    preset_manager.add_preset(Preset::new("Default".to_string(), Settings::default()));
+
+    preset_manager.add_preset(Preset::new(
+        "Kelvin-Helmholtz".to_string(),
+        Settings {
+            initial_condition: InitialCondition::ShearLayer,
+            vortex_count: 2048,
+            perturbation: 0.01,
+            core_radius: 0.01,
+            dye_pattern: DyePattern::Halves,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Turbulence".to_string(),
+        Settings {
+            initial_condition: InitialCondition::RandomVortices,
+            vortex_count: 512,
+            strength: 0.3,
+            core_radius: 0.02,
+            dye_pattern: DyePattern::Checker,
+            stripes: 12.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Dipole Collisions".to_string(),
+        Settings {
+            initial_condition: InitialCondition::Dipoles,
+            vortex_count: 64,
+            strength: 0.8,
+            core_radius: 0.008,
+            stripes: 16.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Fading Smoke".to_string(),
+        Settings {
+            initial_condition: InitialCondition::RandomVortices,
+            vortex_count: 256,
+            decay: 0.05,
+            dye_dissipation: 0.1,
+            stir_strength: 2.0,
+            ..Settings::default()
+        },
+    ));
+
+    preset_manager.add_preset(Preset::new(
+        "Vorticity View".to_string(),
+        Settings {
+            display_mode: DisplayMode::Vorticity,
+            display_gain: 2.0,
+            ..Settings::default()
+        },
+    ));
+
+    // Capture all the built-in preset names we just added
+    preset_manager.capture_built_in_presets();
+}
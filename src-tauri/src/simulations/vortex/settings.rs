@@ -0,0 +1,262 @@
+//! # Vortex Settings Module
+//!
+//! How many vortices there are and how they start out, how they move and
+//! fade, the dye they stir and how it's shown.
+
+use crate::simulation::preset_migration::PresetSchema;
+use crate::simulations::shared::BackgroundLayer;
+use crate::simulations::shared::randomize::{SettingCategories, SettingCategory};
+use crate::simulations::shared::validation::{Rule, SettingValidator};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum InitialCondition {
+    /// One sheet of vortices across the middle, rolling up into curls
+    ShearLayer,
+    /// Two opposite sheets, the fluid between them running the other way
+    #[default]
+    DoubleShear,
+    /// Vortices scattered at random, turning either way
+    RandomVortices,
+    /// Pairs of opposite vortices, each pair heading off in a line
+    Dipoles,
+}
+
+impl FromStr for InitialCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "shearlayer" => Ok(InitialCondition::ShearLayer),
+            "doubleshear" => Ok(InitialCondition::DoubleShear),
+            "randomvortices" => Ok(InitialCondition::RandomVortices),
+            "dipoles" => Ok(InitialCondition::Dipoles),
+            _ => Err(format!(
+                "Invalid InitialCondition: '{}'. Expected 'ShearLayer', 'DoubleShear', 'RandomVortices' or 'Dipoles'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DyePattern {
+    /// Horizontal bands running through the color scheme
+    #[default]
+    Stripes,
+    Checker,
+    /// The top and bottom halves in two colors
+    Halves,
+}
+
+impl DyePattern {
+    pub fn shader_index(self) -> u32 {
+        match self {
+            DyePattern::Stripes => 0,
+            DyePattern::Checker => 1,
+            DyePattern::Halves => 2,
+        }
+    }
+}
+
+impl FromStr for DyePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "stripes" => Ok(DyePattern::Stripes),
+            "checker" => Ok(DyePattern::Checker),
+            "halves" => Ok(DyePattern::Halves),
+            _ => Err(format!(
+                "Invalid DyePattern: '{}'. Expected 'Stripes', 'Checker' or 'Halves'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Dye,
+    /// How fast the fluid is turning, one way and the other either side of
+    /// the middle of the color scheme
+    Vorticity,
+    Speed,
+}
+
+impl DisplayMode {
+    pub fn shader_index(self) -> u32 {
+        match self {
+            DisplayMode::Dye => 0,
+            DisplayMode::Vorticity => 1,
+            DisplayMode::Speed => 2,
+        }
+    }
+}
+
+impl FromStr for DisplayMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dye" => Ok(DisplayMode::Dye),
+            "vorticity" => Ok(DisplayMode::Vorticity),
+            "speed" => Ok(DisplayMode::Speed),
+            _ => Err(format!(
+                "Invalid DisplayMode: '{}'. Expected 'Dye', 'Vorticity' or 'Speed'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub initial_condition: InitialCondition,
+    /// Vortices laid out at the start
+    pub vortex_count: u32,
+    /// Speed of the fluid they set going, in box heights per second
+    pub strength: f32,
+    /// How far the starting layout is jostled, which decides where the
+    /// first curls appear
+    pub perturbation: f32,
+    /// Radius inside which a vortex's pull levels off instead of growing
+    /// without bound
+    pub core_radius: f32,
+    /// Share of each vortex's circulation lost per second
+    pub decay: f32,
+
+    /// Simulated seconds per second
+    pub time_scale: f32,
+    /// Vortex steps per frame
+    pub substeps: u32,
+
+    pub dye_pattern: DyePattern,
+    /// Bands or squares across the box's height
+    pub stripes: f32,
+    /// Share of the dye fading per second
+    pub dye_dissipation: f32,
+    /// Share of the screen resolution the dye is kept at
+    pub dye_resolution: f32,
+    /// Rows of the grid the vortices' velocity is summed onto for moving
+    /// the dye
+    pub velocity_resolution: u32,
+
+    /// Circulation of the vortex pairs a left drag sheds, per unit of speed
+    pub stir_strength: f32,
+    /// Radius of the dye put down while dragging
+    pub splat_radius: f32,
+
+    pub display_mode: DisplayMode,
+    /// How strongly vorticity or speed is pushed toward the ends of the
+    /// color scheme
+    pub display_gain: f32,
+
+    // Image drawn behind the simulation
+    #[serde(default)]
+    pub background_layer: BackgroundLayer,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            initial_condition: InitialCondition::DoubleShear,
+            vortex_count: 1024,
+            strength: 0.5,
+            perturbation: 0.02,
+            core_radius: 0.015,
+            decay: 0.0,
+            time_scale: 1.0,
+            substeps: 2,
+            dye_pattern: DyePattern::Stripes,
+            stripes: 8.0,
+            dye_dissipation: 0.0,
+            dye_resolution: 1.0,
+            velocity_resolution: 128,
+            stir_strength: 1.0,
+            splat_radius: 0.04,
+            display_mode: DisplayMode::Dye,
+            display_gain: 1.0,
+            background_layer: BackgroundLayer::default(),
+        }
+    }
+}
+
+impl PresetSchema for Settings {}
+
+pub const SETTING_RULES: SettingValidator = SettingValidator::new(
+    &[
+        (
+            "initial_condition",
+            Rule::OneOf(&["ShearLayer", "DoubleShear", "RandomVortices", "Dipoles"]),
+        ),
+        (
+            "vortex_count",
+            Rule::Count {
+                min: 2,
+                max: 16_384,
+            },
+        ),
+        ("strength", Rule::Range { min: 0.0, max: 5.0 }),
+        ("perturbation", Rule::Range { min: 0.0, max: 0.5 }),
+        (
+            "core_radius",
+            Rule::Range {
+                min: 0.001,
+                max: 0.2,
+            },
+        ),
+        ("decay", Rule::Range { min: 0.0, max: 1.0 }),
+        ("time_scale", Rule::Range { min: 0.0, max: 5.0 }),
+        ("substeps", Rule::Count { min: 1, max: 16 }),
+        (
+            "dye_pattern",
+            Rule::OneOf(&["Stripes", "Checker", "Halves"]),
+        ),
+        (
+            "stripes",
+            Rule::Range {
+                min: 1.0,
+                max: 64.0,
+            },
+        ),
+        ("dye_dissipation", Rule::Range { min: 0.0, max: 1.0 }),
+        ("dye_resolution", Rule::Range { min: 0.1, max: 1.0 }),
+        ("velocity_resolution", Rule::Count { min: 16, max: 512 }),
+        (
+            "stir_strength",
+            Rule::Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        ),
+        (
+            "splat_radius",
+            Rule::Range {
+                min: 0.001,
+                max: 0.5,
+            },
+        ),
+        ("display_mode", Rule::OneOf(&["Dye", "Vorticity", "Speed"])),
+        (
+            "display_gain",
+            Rule::Range {
+                min: 0.1,
+                max: 10.0,
+            },
+        ),
+    ],
+    &[],
+);
+
+/// Categories for [`randomized`](crate::simulations::shared::randomize::randomized)
+pub const SETTING_CATEGORIES: SettingCategories = SettingCategories::new(&[
+    ("initial_condition", SettingCategory::Generators),
+    ("vortex_count", SettingCategory::Generators),
+    ("dye_pattern", SettingCategory::Generators),
+    ("stripes", SettingCategory::Generators),
+    ("display_mode", SettingCategory::Colors),
+    ("display_gain", SettingCategory::Colors),
+]);
@@ -0,0 +1,5 @@
+pub const VORTEX_SHADER: &str = concat!(
+    include_str!("../../shared/color.wgsl"),
+    include_str!("vortex.wgsl")
+);
+pub const RENDER_INFINITE_SHADER: &str = crate::simulations::shared::INFINITE_RENDER_SHADER;
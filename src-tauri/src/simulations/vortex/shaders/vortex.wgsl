@@ -0,0 +1,243 @@
+// Vortex particles moving each other and the dye they sit in. The fluid's
+// velocity anywhere is the sum of every vortex's swirl (Biot-Savart), each
+// softened inside its core so it stays finite. The box wraps, so each
+// vortex is felt through its nearest copy, faded out before half a box
+// away where the nearest copy would jump to the other side.
+//
+// Vortices sum each other directly. The dye is moved by a coarse grid the
+// same sums are taken on, sampled smoothly between nodes.
+
+struct Vortex {
+    position: vec2<f32>,
+    circulation: f32,
+    _pad: f32,
+}
+
+struct Params {
+    splats: array<vec4<f32>, 8>, // xy position in the box, z radius, w LUT position
+    box_size: vec2<f32>,
+    dye_size: vec2<u32>,
+    grid_size: vec2<u32>,
+    vortex_capacity: u32,
+    splat_count: u32,
+    step_time: f32,
+    frame_time: f32,
+    core_radius: f32,
+    decay: f32, // circulation kept per step
+    dye_fade: f32, // dye kept per frame
+    dye_pattern: u32,
+    stripes: f32,
+    display_mode: u32,
+    display_gain: f32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> vortices: array<Vortex>;
+@group(0) @binding(2) var<storage, read_write> vortex_velocities: array<vec2<f32>>;
+@group(0) @binding(3) var<storage, read_write> grid_velocities: array<vec2<f32>>;
+@group(0) @binding(4) var dye_in: texture_2d<f32>;
+@group(0) @binding(5) var dye_sampler: sampler;
+@group(0) @binding(6) var dye_out: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(7) var<storage, read> lut_data: array<u32>;
+@group(0) @binding(8) var display: texture_storage_2d<rgba8unorm, write>;
+
+const TAU: f32 = 6.28318530718;
+const TILE: u32 = 64u;
+// Vorticity and speed shown at the top of the color scheme at a gain of 1
+const VORTICITY_RANGE: f32 = 20.0;
+const SPEED_RANGE: f32 = 1.0;
+
+var<workgroup> tile_vortices: array<Vortex, TILE>;
+
+fn lut_color(position: f32) -> vec3<f32> {
+    let index = u32(clamp(position, 0.0, 1.0) * 255.0);
+    return vec3<f32>(
+        srgb_to_linear(f32(lut_data[index]) / 255.0),
+        srgb_to_linear(f32(lut_data[256u + index]) / 255.0),
+        srgb_to_linear(f32(lut_data[512u + index]) / 255.0)
+    );
+}
+
+// The shortest way from b to a across the wrapping box
+fn nearest_offset(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    let d = a - b;
+    return d - params.box_size * round(d / params.box_size);
+}
+
+fn wrap(position: vec2<f32>) -> vec2<f32> {
+    let size = params.box_size;
+    return position - size * floor(position / size + 0.5);
+}
+
+fn induced(at: vec2<f32>, vortex: Vortex) -> vec2<f32> {
+    let d = nearest_offset(at, vortex.position);
+    let r2 = dot(d, d);
+    let reach = 0.5 * min(params.box_size.x, params.box_size.y);
+    let fade = 1.0 - smoothstep(0.7 * reach, reach, sqrt(r2));
+    let core2 = params.core_radius * params.core_radius;
+    return vortex.circulation / TAU * vec2<f32>(-d.y, d.x) / (r2 + core2) * fade;
+}
+
+// Velocity at `at` from every vortex, a tile of them at a time through
+// workgroup memory. Every invocation of the workgroup has to call it.
+fn velocity_at(at: vec2<f32>, local_index: u32) -> vec2<f32> {
+    var velocity = vec2<f32>(0.0);
+    let tiles = (params.vortex_capacity + TILE - 1u) / TILE;
+    for (var t = 0u; t < tiles; t++) {
+        let index = t * TILE + local_index;
+        if (index < params.vortex_capacity) {
+            tile_vortices[local_index] = vortices[index];
+        } else {
+            tile_vortices[local_index] = Vortex(vec2<f32>(0.0), 0.0, 0.0);
+        }
+        workgroupBarrier();
+        for (var i = 0u; i < TILE; i++) {
+            let vortex = tile_vortices[i];
+            if (vortex.circulation != 0.0) {
+                velocity += induced(at, vortex);
+            }
+        }
+        workgroupBarrier();
+    }
+    return velocity;
+}
+
+@compute @workgroup_size(64)
+fn vortex_velocity(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let index = min(id.x, params.vortex_capacity - 1u);
+    let velocity = velocity_at(vortices[index].position, local_index);
+    if (id.x < params.vortex_capacity) {
+        vortex_velocities[id.x] = velocity;
+    }
+}
+
+@compute @workgroup_size(64)
+fn vortex_advect(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.vortex_capacity) {
+        return;
+    }
+    var vortex = vortices[id.x];
+    vortex.position = wrap(vortex.position + vortex_velocities[id.x] * params.step_time);
+    vortex.circulation *= params.decay;
+    vortices[id.x] = vortex;
+}
+
+fn grid_node_position(node: vec2<u32>) -> vec2<f32> {
+    return (vec2<f32>(node) / vec2<f32>(params.grid_size) - 0.5) * params.box_size;
+}
+
+@compute @workgroup_size(64)
+fn grid_velocity(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let node_count = params.grid_size.x * params.grid_size.y;
+    let index = min(id.x, node_count - 1u);
+    let node = vec2<u32>(index % params.grid_size.x, index / params.grid_size.x);
+    let velocity = velocity_at(grid_node_position(node), local_index);
+    if (id.x < node_count) {
+        grid_velocities[id.x] = velocity;
+    }
+}
+
+fn grid_node(node: vec2<i32>) -> vec2<f32> {
+    let size = vec2<i32>(params.grid_size);
+    let wrapped = ((node % size) + size) % size;
+    return grid_velocities[u32(wrapped.y) * params.grid_size.x + u32(wrapped.x)];
+}
+
+// The grid's velocity between nodes, blended from the four around it
+fn grid_sample(position: vec2<f32>) -> vec2<f32> {
+    let point = (position / params.box_size + 0.5) * vec2<f32>(params.grid_size);
+    let base = floor(point);
+    let f = point - base;
+    let node = vec2<i32>(base);
+    let bottom = mix(grid_node(node), grid_node(node + vec2<i32>(1, 0)), f.x);
+    let top = mix(grid_node(node + vec2<i32>(0, 1)), grid_node(node + vec2<i32>(1, 1)), f.x);
+    return mix(bottom, top, f.y);
+}
+
+// Row 0 of the dye is the top of the box
+fn dye_texel_position(texel: vec2<u32>) -> vec2<f32> {
+    let uv = (vec2<f32>(texel) + 0.5) / vec2<f32>(params.dye_size);
+    return vec2<f32>(uv.x - 0.5, 0.5 - uv.y) * params.box_size;
+}
+
+fn dye_uv(position: vec2<f32>) -> vec2<f32> {
+    let scaled = position / params.box_size;
+    return vec2<f32>(scaled.x + 0.5, 0.5 - scaled.y);
+}
+
+@compute @workgroup_size(8, 8)
+fn init_dye(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.dye_size.x || id.y >= params.dye_size.y) {
+        return;
+    }
+    let position = dye_texel_position(id.xy);
+    let across = position / params.box_size.y * params.stripes;
+    var shade: f32;
+    if (params.dye_pattern == 1u) {
+        let checks = floor(across);
+        shade = 0.25 + 0.5 * f32((i32(checks.x) + i32(checks.y)) & 1);
+    } else if (params.dye_pattern == 2u) {
+        shade = select(0.25, 0.75, position.y > 0.0);
+    } else {
+        shade = fract(across.y);
+    }
+    textureStore(dye_out, vec2<i32>(id.xy), vec4<f32>(lut_color(shade), 1.0));
+}
+
+@compute @workgroup_size(8, 8)
+fn advect_dye(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.dye_size.x || id.y >= params.dye_size.y) {
+        return;
+    }
+    let position = dye_texel_position(id.xy);
+
+    // Traced back along the flow to where this texel's dye was a frame ago,
+    // through the midpoint so curls don't unwind
+    let start = grid_sample(position);
+    let midpoint = grid_sample(position - start * params.frame_time * 0.5);
+    let origin = position - midpoint * params.frame_time;
+    var dye = textureSampleLevel(dye_in, dye_sampler, dye_uv(origin), 0.0) * params.dye_fade;
+
+    for (var i = 0u; i < params.splat_count; i++) {
+        let splat = params.splats[i];
+        let d = nearest_offset(position, splat.xy);
+        let amount = exp(-dot(d, d) / (splat.z * splat.z));
+        dye = mix(dye, vec4<f32>(lut_color(splat.w), 1.0), amount);
+    }
+    textureStore(dye_out, vec2<i32>(id.xy), dye);
+}
+
+@compute @workgroup_size(8, 8)
+fn paint(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.dye_size.x || id.y >= params.dye_size.y) {
+        return;
+    }
+    var color: vec3<f32>;
+    if (params.display_mode == 0u) {
+        color = textureLoad(dye_in, vec2<i32>(id.xy), 0).rgb;
+    } else {
+        let position = dye_texel_position(id.xy);
+        let h = params.box_size / vec2<f32>(params.grid_size);
+        if (params.display_mode == 1u) {
+            let dv_dx = grid_sample(position + vec2<f32>(h.x, 0.0)).y
+                - grid_sample(position - vec2<f32>(h.x, 0.0)).y;
+            let du_dy = grid_sample(position + vec2<f32>(0.0, h.y)).x
+                - grid_sample(position - vec2<f32>(0.0, h.y)).x;
+            let vorticity = dv_dx / (2.0 * h.x) - du_dy / (2.0 * h.y);
+            color = lut_color(0.5 + 0.5 * vorticity * params.display_gain / VORTICITY_RANGE);
+        } else {
+            let speed = length(grid_sample(position));
+            color = lut_color(speed * params.display_gain / SPEED_RANGE);
+        }
+    }
+    textureStore(display, vec2<i32>(id.xy), vec4<f32>(color, 1.0));
+}
@@ -0,0 +1,1042 @@
+//! # Vortex Simulation Module
+//!
+//! Smoke stirred by vortex particles instead of solved on a grid. Each
+//! vortex is a point of spin that moves the fluid around it, so curls stay
+//! as sharp as the vortices are small however long they run, where a grid
+//! solver blurs them away into its cells.
+//!
+//! ## Technical Overview
+//!
+//! Each frame, on the GPU:
+//! 1. Over a few substeps, every vortex sums the velocity all the others
+//!    give it, through workgroup memory a tile at a time, and moves with it.
+//! 2. The same sum is taken on a coarse grid over the box.
+//! 3. The dye is carried along that grid's flow, with splats from dragging
+//!    blended in.
+//! 4. The dye, or the grid's vorticity or speed, is painted into the
+//!    display texture, which is drawn through the camera with infinite
+//!    tiling since the box wraps.
+//!
+//! Dragging with the left button sheds pairs of vortices along the drag and
+//! puts down dye; the right button only puts down dye.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+use serde_json::Value;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupLayout, BindGroupLayoutDescriptor,
+    Buffer, BufferDescriptor, BufferUsages, ComputePipeline, ComputePipelineDescriptor, Device,
+    FilterMode, PipelineLayout, PipelineLayoutDescriptor, Queue, RenderPipeline,
+    RenderPipelineDescriptor, Sampler, SamplerDescriptor, ShaderModule, ShaderStages,
+    SurfaceConfiguration, Texture, TextureFormat, TextureView, TextureViewDescriptor,
+};
+
+use crate::commands::AppSettings;
+use crate::error::SimulationResult;
+use crate::simulations::shared::camera::Camera;
+use crate::simulations::shared::gpu_utils::resource_helpers;
+use crate::simulations::shared::ping_pong_textures::PingPongTextures;
+use crate::simulations::shared::{BackgroundLayer, ColorScheme, ColorSchemeManager};
+use crate::simulations::traits::Simulation;
+
+use super::settings::{DyePattern, InitialCondition, Settings};
+use super::shaders::{RENDER_INFINITE_SHADER, VORTEX_SHADER};
+use super::state::State;
+use super::vortices::{self, Vortex};
+
+/// Slots kept after the laid out vortices for pairs shed by stirring,
+/// reused oldest first once they're all taken
+pub const STIR_SLOTS: u32 = 512;
+
+/// Dye splats blended in per frame
+const MAX_SPLATS: usize = 8;
+
+/// Longest step taken in one frame, so a stall doesn't fling vortices
+/// across the box
+const MAX_FRAME_TIME: f32 = 1.0 / 30.0;
+
+/// Time taken to pass between mouse events while dragging, which turns
+/// drag distances into the speed shed pairs carry on at
+const DRAG_EVENT_TIME: f32 = 1.0 / 60.0;
+
+/// How far through the color scheme the stirring dye moves per second
+const DYE_CYCLE_RATE: f32 = 0.1;
+
+const DYE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Params {
+    splats: [[f32; 4]; MAX_SPLATS],
+    box_size: [f32; 2],
+    dye_size: [u32; 2],
+    grid_size: [u32; 2],
+    vortex_capacity: u32,
+    splat_count: u32,
+    step_time: f32,
+    frame_time: f32,
+    core_radius: f32,
+    decay: f32,
+    dye_fade: f32,
+    dye_pattern: u32,
+    stripes: f32,
+    display_mode: u32,
+    display_gain: f32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct RenderParams {
+    filtering_mode: u32, // 0 = nearest, 1 = linear, 2 = lanczos
+    _pad1: u32,
+    _pad2: u32,
+    _pad3: u32,
+}
+
+/// The compute passes, in the order a frame runs them
+#[derive(Debug)]
+struct Pipelines {
+    init_dye: ComputePipeline,
+    vortex_velocity: ComputePipeline,
+    vortex_advect: ComputePipeline,
+    grid_velocity: ComputePipeline,
+    advect_dye: ComputePipeline,
+    paint: ComputePipeline,
+}
+
+impl Pipelines {
+    fn new(device: &Device, layout: &PipelineLayout, module: &ShaderModule) -> Self {
+        let pipeline = |entry_point: &str| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(&format!("Vortex {} Pipeline", entry_point)),
+                layout: Some(layout),
+                module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        Self {
+            init_dye: pipeline("init_dye"),
+            vortex_velocity: pipeline("vortex_velocity"),
+            vortex_advect: pipeline("vortex_advect"),
+            grid_velocity: pipeline("grid_velocity"),
+            advect_dye: pipeline("advect_dye"),
+            paint: pipeline("paint"),
+        }
+    }
+}
+
+/// Everything the field's bind groups point at besides the field itself
+#[derive(Debug)]
+struct Resources {
+    compute_bind_group_layout: BindGroupLayout,
+    render_infinite_bind_group_layout: BindGroupLayout,
+    dye_sampler: Sampler,
+    display_sampler: Sampler,
+    params_buffer: Buffer,
+    lut_buffer: Buffer,
+    texture_render_params_buffer: Buffer,
+}
+
+/// The vortices, the velocity grid and the dye, remade when any of their
+/// sizes change
+#[derive(Debug)]
+struct Field {
+    dye_width: u32,
+    dye_height: u32,
+    grid_width: u32,
+    grid_height: u32,
+    vortex_capacity: u32,
+    vortices: Buffer,
+    _vortex_velocities: Buffer,
+    _grid_velocities: Buffer,
+    dye: PingPongTextures,
+    _display_texture: Texture,
+    // Reading the dye from each texture of the pair and writing the other
+    compute_bind_groups: [BindGroup; 2],
+    render_infinite_bind_group: BindGroup,
+}
+
+impl Field {
+    fn new(
+        device: &Device,
+        (dye_width, dye_height): (u32, u32),
+        (grid_width, grid_height): (u32, u32),
+        vortex_capacity: u32,
+        resources: &Resources,
+    ) -> Self {
+        let vortices = device.create_buffer(&BufferDescriptor {
+            label: Some("Vortex Particles Buffer"),
+            size: vortex_capacity as u64 * std::mem::size_of::<Vortex>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let vortex_velocities = device.create_buffer(&BufferDescriptor {
+            label: Some("Vortex Velocities Buffer"),
+            size: vortex_capacity as u64 * std::mem::size_of::<[f32; 2]>() as u64,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let grid_velocities = device.create_buffer(&BufferDescriptor {
+            label: Some("Vortex Grid Velocities Buffer"),
+            size: (grid_width * grid_height) as u64 * std::mem::size_of::<[f32; 2]>() as u64,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let dye = PingPongTextures::new(device, dye_width, dye_height, DYE_FORMAT, "Vortex Dye");
+
+        let display_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Vortex Display Texture"),
+            size: wgpu::Extent3d {
+                width: dye_width,
+                height: dye_height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let display_view = display_texture.create_view(&TextureViewDescriptor::default());
+
+        let compute_bind_group = |read: usize| {
+            device.create_bind_group(&BindGroupDescriptor {
+                label: Some(&format!("Vortex Compute Bind Group {}", read)),
+                layout: &resources.compute_bind_group_layout,
+                entries: &[
+                    resource_helpers::buffer_entry(0, &resources.params_buffer),
+                    resource_helpers::buffer_entry(1, &vortices),
+                    resource_helpers::buffer_entry(2, &vortex_velocities),
+                    resource_helpers::buffer_entry(3, &grid_velocities),
+                    resource_helpers::texture_view_entry(4, &dye.views()[read]),
+                    resource_helpers::sampler_bind_entry(5, &resources.dye_sampler),
+                    resource_helpers::texture_view_entry(6, &dye.views()[1 - read]),
+                    resource_helpers::buffer_entry(7, &resources.lut_buffer),
+                    resource_helpers::texture_view_entry(8, &display_view),
+                ],
+            })
+        };
+        let compute_bind_groups = [compute_bind_group(0), compute_bind_group(1)];
+
+        let render_infinite_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Vortex Render Infinite Bind Group"),
+            layout: &resources.render_infinite_bind_group_layout,
+            entries: &[
+                resource_helpers::texture_view_entry(0, &display_view),
+                resource_helpers::sampler_bind_entry(1, &resources.display_sampler),
+                resource_helpers::buffer_entry(2, &resources.texture_render_params_buffer),
+            ],
+        });
+
+        Self {
+            dye_width,
+            dye_height,
+            grid_width,
+            grid_height,
+            vortex_capacity,
+            vortices,
+            _vortex_velocities: vortex_velocities,
+            _grid_velocities: grid_velocities,
+            dye,
+            _display_texture: display_texture,
+            compute_bind_groups,
+            render_infinite_bind_group,
+        }
+    }
+
+    /// The bind group reading the current dye and writing the other
+    fn bind_group(&self) -> &BindGroup {
+        self.dye
+            .get_bind_group(&self.compute_bind_groups[0], &self.compute_bind_groups[1])
+    }
+}
+
+#[derive(Debug)]
+pub struct VortexModel {
+    pub settings: Settings,
+    pub state: State,
+    // Dye splats waiting for the next frame, as position, radius and LUT
+    // position
+    splats: Vec<[f32; 4]>,
+    // Stirring slot the next shed pair goes into
+    next_stir_slot: u32,
+
+    // GPU resources
+    pipelines: Pipelines,
+    render_infinite_pipeline: RenderPipeline,
+    resources: Resources,
+    field: Field,
+    camera_bind_group: BindGroup,
+
+    // Camera for infinite rendering
+    pub camera: Camera,
+
+    // Surface size the field is laid over
+    width: u32,
+    height: u32,
+}
+
+/// Velocity grid nodes across and down a box of `box_size`, with
+/// `resolution` rows
+pub fn grid_size(box_size: [f32; 2], resolution: u32) -> (u32, u32) {
+    let columns = (resolution as f32 * box_size[0] / box_size[1]).round() as u32;
+    (columns.max(1), resolution.max(1))
+}
+
+/// Circulation each vortex of a shed pair `spacing` apart needs to carry on
+/// at `speed`
+pub fn dipole_circulation(speed: f32, spacing: f32) -> f32 {
+    // Each vortex moves the other at circulation / (2π spacing)
+    speed * std::f32::consts::TAU * spacing
+}
+
+impl VortexModel {
+    /// Calculate the number of tiles needed for infinite rendering based on zoom level
+    fn calculate_tile_count(&self) -> u32 {
+        let zoom = self.camera.zoom;
+        // Each tile covers 2.0 world units, so we need enough tiles to cover the visible area
+        let visible_world_size = 2.0 / zoom;
+        let tiles_needed = (visible_world_size / 2.0).ceil() as u32 + 6;
+        let min_tiles = if zoom < 0.1 { 7 } else { 5 };
+        tiles_needed.max(min_tiles).min(1024)
+    }
+
+    /// The box is a unit high and as wide as the surface is in proportion
+    fn box_size(&self) -> [f32; 2] {
+        [self.width as f32 / self.height.max(1) as f32, 1.0]
+    }
+
+    fn dye_size(&self) -> (u32, u32) {
+        let scale = self.settings.dye_resolution;
+        (
+            ((self.width as f32 * scale).round() as u32).max(1),
+            ((self.height as f32 * scale).round() as u32).max(1),
+        )
+    }
+
+    pub fn new(
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_config: &SurfaceConfiguration,
+        settings: Settings,
+        app_settings: &AppSettings,
+        color_scheme_manager: &ColorSchemeManager,
+    ) -> SimulationResult<Self> {
+        let state = State::default();
+
+        let camera = Camera::new(
+            device,
+            surface_config.width as f32,
+            surface_config.height as f32,
+        )?;
+
+        let vortex_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Vortex Shader"),
+            source: wgpu::ShaderSource::Wgsl(VORTEX_SHADER.into()),
+        });
+        let render_infinite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Vortex Render Infinite Shader"),
+            source: wgpu::ShaderSource::Wgsl(RENDER_INFINITE_SHADER.into()),
+        });
+
+        // The dye wraps with the box when it's traced back across an edge
+        let dye_sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Vortex Dye Sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            address_mode_w: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+        let display_sampler = resource_helpers::create_linear_sampler(
+            device,
+            "Vortex Display Sampler",
+            FilterMode::Linear,
+        );
+
+        let params_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Vortex Params Buffer"),
+            size: std::mem::size_of::<Params>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lut = color_scheme_manager.get(&state.color_scheme_name)?;
+        let lut_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!(
+                "Vortex LUT Buffer for {}",
+                state.color_scheme_name
+            )),
+            contents: bytemuck::cast_slice(&lut.to_u32_buffer()),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+
+        let render_params = RenderParams {
+            filtering_mode: app_settings.texture_filtering.into(),
+            _pad1: 0,
+            _pad2: 0,
+            _pad3: 0,
+        };
+        let texture_render_params_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Vortex Texture Render Params Buffer"),
+                contents: bytemuck::cast_slice(&[render_params]),
+                usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            });
+
+        let compute_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Vortex Compute Bind Group Layout"),
+                entries: &[
+                    resource_helpers::uniform_buffer_entry(0, ShaderStages::COMPUTE),
+                    resource_helpers::storage_buffer_entry(1, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(2, ShaderStages::COMPUTE, false),
+                    resource_helpers::storage_buffer_entry(3, ShaderStages::COMPUTE, false),
+                    resource_helpers::texture_entry(
+                        4,
+                        ShaderStages::COMPUTE,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        5,
+                        ShaderStages::COMPUTE,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                    resource_helpers::storage_texture_entry(
+                        6,
+                        ShaderStages::COMPUTE,
+                        wgpu::StorageTextureAccess::WriteOnly,
+                        DYE_FORMAT,
+                    ),
+                    resource_helpers::storage_buffer_entry(7, ShaderStages::COMPUTE, true),
+                    resource_helpers::storage_texture_entry(
+                        8,
+                        ShaderStages::COMPUTE,
+                        wgpu::StorageTextureAccess::WriteOnly,
+                        TextureFormat::Rgba8Unorm,
+                    ),
+                ],
+            });
+
+        let render_infinite_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Vortex Render Infinite Bind Group Layout"),
+                entries: &[
+                    resource_helpers::texture_entry(
+                        0,
+                        ShaderStages::FRAGMENT,
+                        wgpu::TextureSampleType::Float { filterable: true },
+                        wgpu::TextureViewDimension::D2,
+                    ),
+                    resource_helpers::sampler_entry(
+                        1,
+                        ShaderStages::FRAGMENT,
+                        wgpu::SamplerBindingType::Filtering,
+                    ),
+                    resource_helpers::uniform_buffer_entry(2, ShaderStages::FRAGMENT),
+                ],
+            });
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[resource_helpers::uniform_buffer_entry(
+                    0,
+                    ShaderStages::VERTEX,
+                )],
+            });
+
+        let compute_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Vortex Compute Pipeline Layout"),
+            bind_group_layouts: &[&compute_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipelines = Pipelines::new(device, &compute_pipeline_layout, &vortex_module);
+
+        let render_infinite_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Vortex Render Infinite Pipeline"),
+            layout: Some(&device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("Vortex Render Infinite Pipeline Layout"),
+                bind_group_layouts: &[
+                    &render_infinite_bind_group_layout,
+                    &camera_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &render_infinite_module,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &render_infinite_module,
+                entry_point: Some("fs_main_texture"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[resource_helpers::buffer_entry(0, camera.buffer())],
+        });
+
+        let resources = Resources {
+            compute_bind_group_layout,
+            render_infinite_bind_group_layout,
+            dye_sampler,
+            display_sampler,
+            params_buffer,
+            lut_buffer,
+            texture_render_params_buffer,
+        };
+        // Replaced by rebuild_field once the model exists
+        let field = Field::new(device, (1, 1), (1, 1), 1, &resources);
+
+        let mut model = Self {
+            settings,
+            state,
+            splats: Vec::with_capacity(MAX_SPLATS),
+            next_stir_slot: 0,
+            pipelines,
+            render_infinite_pipeline,
+            resources,
+            field,
+            camera_bind_group,
+            camera,
+            width: surface_config.width,
+            height: surface_config.height,
+        };
+        model.rebuild_field(device, queue)?;
+        Ok(model)
+    }
+
+    /// Remake the field for the surface size and resolutions, and start
+    /// over on it
+    fn rebuild_field(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) -> SimulationResult<()> {
+        let dye_size = self.dye_size();
+        let grid_size = grid_size(self.box_size(), self.settings.velocity_resolution);
+        let vortex_capacity = self.settings.vortex_count + STIR_SLOTS;
+        self.field = Field::new(
+            device,
+            dye_size,
+            grid_size,
+            vortex_capacity,
+            &self.resources,
+        );
+        self.state.dye_width = dye_size.0;
+        self.state.dye_height = dye_size.1;
+        self.state.grid_width = grid_size.0;
+        self.state.grid_height = grid_size.1;
+        self.state.vortex_capacity = vortex_capacity;
+        self.reset_runtime_state(device, queue)
+    }
+
+    /// Lay the vortices out afresh, with the stirring slots emptied
+    fn seed_vortices(&mut self, queue: &Arc<Queue>) {
+        let mut vortices = vortices::seed(
+            self.settings.initial_condition,
+            self.settings.vortex_count as usize,
+            self.box_size(),
+            self.settings.strength,
+            self.settings.perturbation,
+            &mut rand::rng(),
+        );
+        vortices.resize(self.field.vortex_capacity as usize, Vortex::zeroed());
+        queue.write_buffer(&self.field.vortices, 0, bytemuck::cast_slice(&vortices));
+        self.next_stir_slot = 0;
+    }
+
+    /// Paint the starting dye pattern over whatever dye there was
+    fn init_dye(&mut self, device: &Arc<Device>, queue: &Arc<Queue>) {
+        self.update_params(queue, 0.0);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Vortex Init Dye"),
+        });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Vortex Init Dye Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipelines.init_dye);
+            compute_pass.set_bind_group(0, self.field.bind_group(), &[]);
+            compute_pass.dispatch_workgroups(
+                self.field.dye_width.div_ceil(8),
+                self.field.dye_height.div_ceil(8),
+                1,
+            );
+        }
+        queue.submit([encoder.finish()]);
+        self.field.dye.swap();
+    }
+
+    /// Shed a pair of vortices at `position` heading along `drag`, into the
+    /// oldest stirring slot
+    fn shed_dipole(&mut self, queue: &Arc<Queue>, position: [f32; 2], drag: [f32; 2]) {
+        let distance = drag[0].hypot(drag[1]);
+        let spacing = self.settings.splat_radius;
+        let circulation =
+            dipole_circulation(distance / DRAG_EVENT_TIME, spacing) * self.settings.stir_strength;
+        let pair = vortices::dipole(
+            position,
+            drag[1].atan2(drag[0]),
+            spacing,
+            circulation,
+            self.box_size(),
+        );
+        let slot = self.settings.vortex_count + self.next_stir_slot;
+        queue.write_buffer(
+            &self.field.vortices,
+            slot as u64 * std::mem::size_of::<Vortex>() as u64,
+            bytemuck::cast_slice(&pair),
+        );
+        self.next_stir_slot = (self.next_stir_slot + 2) % STIR_SLOTS;
+    }
+
+    fn update_params(&self, queue: &Arc<Queue>, frame_time: f32) {
+        let mut splats = [[0.0; 4]; MAX_SPLATS];
+        splats[..self.splats.len()].copy_from_slice(&self.splats);
+        let substeps = self.settings.substeps.max(1);
+        let step_time = frame_time / substeps as f32;
+        let params = Params {
+            splats,
+            box_size: self.box_size(),
+            dye_size: [self.field.dye_width, self.field.dye_height],
+            grid_size: [self.field.grid_width, self.field.grid_height],
+            vortex_capacity: self.field.vortex_capacity,
+            splat_count: self.splats.len() as u32,
+            step_time,
+            frame_time,
+            core_radius: self.settings.core_radius,
+            decay: (1.0 - self.settings.decay).max(0.0).powf(step_time),
+            dye_fade: (1.0 - self.settings.dye_dissipation)
+                .max(0.0)
+                .powf(frame_time),
+            dye_pattern: self.settings.dye_pattern.shader_index(),
+            stripes: self.settings.stripes,
+            display_mode: self.settings.display_mode.shader_index(),
+            display_gain: self.settings.display_gain,
+            _pad0: 0,
+            _pad1: 0,
+            _pad2: 0,
+        };
+        queue.write_buffer(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
+    /// Move the vortices, then the dye along the flow they make
+    fn encode_step(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Vortex Step Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, self.field.bind_group(), &[]);
+
+        let vortex_groups = self.field.vortex_capacity.div_ceil(64);
+        for _ in 0..self.settings.substeps.max(1) {
+            compute_pass.set_pipeline(&self.pipelines.vortex_velocity);
+            compute_pass.dispatch_workgroups(vortex_groups, 1, 1);
+            compute_pass.set_pipeline(&self.pipelines.vortex_advect);
+            compute_pass.dispatch_workgroups(vortex_groups, 1, 1);
+        }
+
+        let nodes = self.field.grid_width * self.field.grid_height;
+        compute_pass.set_pipeline(&self.pipelines.grid_velocity);
+        compute_pass.dispatch_workgroups(nodes.div_ceil(64), 1, 1);
+
+        compute_pass.set_pipeline(&self.pipelines.advect_dye);
+        compute_pass.dispatch_workgroups(
+            self.field.dye_width.div_ceil(8),
+            self.field.dye_height.div_ceil(8),
+            1,
+        );
+    }
+
+    fn encode_paint(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Vortex Paint Pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.pipelines.paint);
+        compute_pass.set_bind_group(0, self.field.bind_group(), &[]);
+        compute_pass.dispatch_workgroups(
+            self.field.dye_width.div_ceil(8),
+            self.field.dye_height.div_ceil(8),
+            1,
+        );
+    }
+
+    fn encode_render(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        let tile_count = self.calculate_tile_count();
+        let total_instances = tile_count * tile_count;
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Vortex Infinite Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.render_infinite_pipeline);
+        render_pass.set_bind_group(0, &self.field.render_infinite_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+        render_pass.draw(0..6, 0..total_instances);
+    }
+
+    /// Where a point in world space falls in the box, wrapped to the base
+    /// tile
+    fn box_position(&self, world_x: f32, world_y: f32) -> [f32; 2] {
+        let [width, height] = self.box_size();
+        let wrapped_x = (world_x + 1.0).rem_euclid(2.0) - 1.0;
+        let wrapped_y = (world_y + 1.0).rem_euclid(2.0) - 1.0;
+        [wrapped_x * 0.5 * width, wrapped_y * 0.5 * height]
+    }
+}
+
+fn number(setting_name: &str, value: &Value) -> SimulationResult<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", setting_name).into())
+}
+
+impl Simulation for VortexModel {
+    fn render_frame(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+        delta_time: f32,
+    ) -> SimulationResult<()> {
+        let frame_time = delta_time.min(MAX_FRAME_TIME) * self.settings.time_scale;
+        self.state.time += frame_time;
+        self.camera.update(delta_time);
+        self.camera.upload_to_gpu(queue);
+        self.update_params(queue, frame_time);
+        self.splats.clear();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Vortex Render"),
+        });
+        self.encode_step(&mut encoder);
+        self.field.dye.swap();
+        self.encode_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn render_frame_paused(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        surface_view: &TextureView,
+    ) -> SimulationResult<()> {
+        // Still painted so display changes show while paused
+        self.update_params(queue, 0.0);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Vortex Render Paused"),
+        });
+        self.encode_paint(&mut encoder);
+        self.encode_render(&mut encoder, surface_view);
+        queue.submit([encoder.finish()]);
+        Ok(())
+    }
+
+    fn resize(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        new_config: &SurfaceConfiguration,
+    ) -> SimulationResult<()> {
+        self.width = new_config.width;
+        self.height = new_config.height;
+        self.camera
+            .resize(new_config.width as f32, new_config.height as f32);
+        self.rebuild_field(device, queue)
+    }
+
+    fn handle_mouse_interaction(
+        &mut self,
+        world_x: f32,
+        world_y: f32,
+        mouse_button: u32,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let position = self.box_position(world_x, world_y);
+        let Some(last) = self.state.last_cursor.replace(position) else {
+            return Ok(());
+        };
+
+        // Across the box's edge by the shorter way, so wrapping doesn't
+        // count as a huge drag
+        let box_size = self.box_size();
+        let offset = vortices::wrap([position[0] - last[0], position[1] - last[1]], box_size);
+        if offset[0] == 0.0 && offset[1] == 0.0 {
+            return Ok(());
+        }
+
+        if mouse_button == 0 {
+            self.shed_dipole(queue, position, offset);
+        }
+        if self.splats.len() < MAX_SPLATS {
+            let color = (self.state.time * DYE_CYCLE_RATE).fract();
+            self.splats
+                .push([position[0], position[1], self.settings.splat_radius, color]);
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_release(
+        &mut self,
+        _mouse_button: u32,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.last_cursor = None;
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Value {
+        serde_json::to_value(&self.settings).unwrap_or_default()
+    }
+
+    fn background_layer(&self) -> Option<&BackgroundLayer> {
+        Some(&self.settings.background_layer)
+    }
+
+    fn background_layer_mut(&mut self) -> Option<&mut BackgroundLayer> {
+        Some(&mut self.settings.background_layer)
+    }
+
+    fn apply_settings(
+        &mut self,
+        settings: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let new_settings: Settings = serde_json::from_value(settings)?;
+        let old_settings = std::mem::replace(&mut self.settings, new_settings);
+
+        if old_settings.vortex_count != self.settings.vortex_count
+            || old_settings.dye_resolution != self.settings.dye_resolution
+            || old_settings.velocity_resolution != self.settings.velocity_resolution
+        {
+            self.rebuild_field(device, queue)?;
+        } else if old_settings.initial_condition != self.settings.initial_condition
+            || old_settings.strength != self.settings.strength
+            || old_settings.perturbation != self.settings.perturbation
+            || old_settings.dye_pattern != self.settings.dye_pattern
+            || old_settings.stripes != self.settings.stripes
+        {
+            self.reset_runtime_state(device, queue)?;
+        }
+        Ok(())
+    }
+
+    fn reset_runtime_state(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        self.state.time = 0.0;
+        self.state.last_cursor = None;
+        self.splats.clear();
+        self.seed_vortices(queue);
+        self.init_dye(device, queue);
+        Ok(())
+    }
+
+    fn randomize_settings(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        let mut rng = rand::rng();
+        self.settings.initial_condition = match rng.random_range(0..4) {
+            0 => InitialCondition::ShearLayer,
+            1 => InitialCondition::DoubleShear,
+            2 => InitialCondition::RandomVortices,
+            _ => InitialCondition::Dipoles,
+        };
+        self.settings.dye_pattern = match rng.random_range(0..3) {
+            0 => DyePattern::Stripes,
+            1 => DyePattern::Checker,
+            _ => DyePattern::Halves,
+        };
+        self.settings.strength = rng.random_range(0.2..1.5);
+        self.settings.perturbation = rng.random_range(0.005..0.08);
+        self.settings.stripes = rng.random_range(2..=24) as f32;
+        self.reset_runtime_state(device, queue)
+    }
+
+    fn update_color_scheme(
+        &mut self,
+        color_scheme: &ColorScheme,
+        _device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        // Dye already put down keeps its colors; new dye and the vorticity
+        // and speed views take the new scheme
+        queue.write_buffer(
+            &self.resources.lut_buffer,
+            0,
+            bytemuck::cast_slice(&color_scheme.to_u32_buffer()),
+        );
+        Ok(())
+    }
+
+    fn update_setting(
+        &mut self,
+        setting_name: &str,
+        value: Value,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match setting_name {
+            "initial_condition" => {
+                self.settings.initial_condition =
+                    value
+                        .as_str()
+                        .unwrap_or("DoubleShear")
+                        .parse()
+                        .map_err(|e| format!("Invalid initial_condition: {}", e))?;
+                self.reset_runtime_state(device, queue)?;
+            }
+            "vortex_count" => {
+                self.settings.vortex_count = number(setting_name, &value)? as u32;
+                self.rebuild_field(device, queue)?;
+            }
+            "strength" => {
+                self.settings.strength = number(setting_name, &value)? as f32;
+                self.seed_vortices(queue);
+            }
+            "perturbation" => {
+                self.settings.perturbation = number(setting_name, &value)? as f32;
+                self.seed_vortices(queue);
+            }
+            "core_radius" => self.settings.core_radius = number(setting_name, &value)? as f32,
+            "decay" => self.settings.decay = number(setting_name, &value)? as f32,
+            "time_scale" => self.settings.time_scale = number(setting_name, &value)? as f32,
+            "substeps" => self.settings.substeps = number(setting_name, &value)? as u32,
+            "dye_pattern" => {
+                self.settings.dye_pattern = value
+                    .as_str()
+                    .unwrap_or("Stripes")
+                    .parse()
+                    .map_err(|e| format!("Invalid dye_pattern: {}", e))?;
+                self.init_dye(device, queue);
+            }
+            "stripes" => {
+                self.settings.stripes = number(setting_name, &value)? as f32;
+                self.init_dye(device, queue);
+            }
+            "dye_dissipation" => {
+                self.settings.dye_dissipation = number(setting_name, &value)? as f32;
+            }
+            "dye_resolution" => {
+                self.settings.dye_resolution = number(setting_name, &value)? as f32;
+                self.rebuild_field(device, queue)?;
+            }
+            "velocity_resolution" => {
+                self.settings.velocity_resolution = number(setting_name, &value)? as u32;
+                self.rebuild_field(device, queue)?;
+            }
+            "stir_strength" => self.settings.stir_strength = number(setting_name, &value)? as f32,
+            "splat_radius" => self.settings.splat_radius = number(setting_name, &value)? as f32,
+            "display_mode" => {
+                self.settings.display_mode = value
+                    .as_str()
+                    .unwrap_or("Dye")
+                    .parse()
+                    .map_err(|e| format!("Invalid display_mode: {}", e))?;
+            }
+            "display_gain" => self.settings.display_gain = number(setting_name, &value)? as f32,
+            _ => return Err(format!("Unknown setting: {}", setting_name).into()),
+        }
+        Ok(())
+    }
+
+    fn update_state(
+        &mut self,
+        state_name: &str,
+        value: Value,
+        _device: &Arc<Device>,
+        _queue: &Arc<Queue>,
+    ) -> SimulationResult<()> {
+        match state_name {
+            "color_scheme_name" => {
+                // The LUT itself is uploaded by the simulation manager
+                self.state.color_scheme_name = value
+                    .as_str()
+                    .ok_or("color_scheme_name must be a string")?
+                    .to_string();
+            }
+            "color_scheme_reversed" => {
+                self.state.color_scheme_reversed = value.as_bool().unwrap_or(false);
+            }
+            _ => return Err(format!("Unknown state: {}", state_name).into()),
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> Value {
+        serde_json::to_value(&self.state).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    fn save_preset(&self, _preset_name: &str) -> SimulationResult<()> {
+        // Preset saving is handled by the preset manager
+        Ok(())
+    }
+
+    fn load_preset(&mut self, _preset_name: &str, _queue: &Arc<Queue>) -> SimulationResult<()> {
+        // Preset loading is handled by the preset manager
+        Ok(())
+    }
+}
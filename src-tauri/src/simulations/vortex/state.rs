@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    // Size of the dye texture and the velocity grid
+    pub dye_width: u32,
+    pub dye_height: u32,
+    pub grid_width: u32,
+    pub grid_height: u32,
+
+    // Slots in the vortex buffer, laid out and shed by stirring
+    pub vortex_capacity: u32,
+
+    // Seconds simulated since the last reset
+    pub time: f32,
+
+    // Last cursor position while dragging, in the box
+    pub last_cursor: Option<[f32; 2]>,
+
+    // Color scheme state
+    pub color_scheme_name: String,
+    pub color_scheme_reversed: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            dye_width: 0,
+            dye_height: 0,
+            grid_width: 0,
+            grid_height: 0,
+            vortex_capacity: 0,
+            time: 0.0,
+            last_cursor: None,
+            color_scheme_name: "MATPLOTLIB_twilight".to_string(),
+            color_scheme_reversed: false,
+        }
+    }
+}
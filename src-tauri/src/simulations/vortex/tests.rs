@@ -0,0 +1,126 @@
+use super::settings::InitialCondition;
+use super::shaders::{RENDER_INFINITE_SHADER, VORTEX_SHADER};
+use super::simulation::{dipole_circulation, grid_size};
+use super::vortices::{dipole, seed, wrap};
+use crate::simulations::shared::kernel_test::KernelHarness;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+const BOX: [f32; 2] = [1.6, 1.0];
+
+fn inside(position: [f32; 2]) -> bool {
+    (0..2).all(|axis| position[axis].abs() <= BOX[axis] * 0.5)
+}
+
+fn close(a: [f32; 2], b: [f32; 2]) -> bool {
+    (a[0] - b[0]).abs() < 1e-5 && (a[1] - b[1]).abs() < 1e-5
+}
+
+#[test]
+fn wrap_brings_positions_back_into_the_box() {
+    assert!(close(wrap([0.3, -0.2], BOX), [0.3, -0.2]));
+    assert!(close(wrap([0.9, 0.6], BOX), [-0.7, -0.4]));
+    assert!(close(wrap([-3.3, 2.25], BOX), [-0.1, 0.25]));
+}
+
+#[test]
+fn every_layout_fills_the_box_with_the_count_asked_for() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for condition in [
+        InitialCondition::ShearLayer,
+        InitialCondition::DoubleShear,
+        InitialCondition::RandomVortices,
+        InitialCondition::Dipoles,
+    ] {
+        let vortices = seed(condition, 256, BOX, 0.5, 0.02, &mut rng);
+        assert_eq!(vortices.len(), 256, "{:?}", condition);
+        assert!(
+            vortices.iter().all(|v| inside(v.position)),
+            "{:?}",
+            condition
+        );
+        assert!(
+            vortices.iter().all(|v| v.circulation != 0.0),
+            "{:?}",
+            condition
+        );
+    }
+}
+
+#[test]
+fn balanced_layouts_leave_no_net_circulation() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for condition in [InitialCondition::DoubleShear, InitialCondition::Dipoles] {
+        let vortices = seed(condition, 300, BOX, 0.8, 0.05, &mut rng);
+        let total: f32 = vortices.iter().map(|v| v.circulation).sum();
+        assert!(total.abs() < 1e-4, "{:?} left {}", condition, total);
+    }
+
+    // Random vortices alternate their turning, if not their strength
+    let vortices = seed(
+        InitialCondition::RandomVortices,
+        300,
+        BOX,
+        0.8,
+        0.0,
+        &mut rng,
+    );
+    let positive = vortices.iter().filter(|v| v.circulation > 0.0).count();
+    assert_eq!(positive, 150);
+}
+
+#[test]
+fn a_shear_layer_carries_its_jump_in_speed() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let vortices = seed(InitialCondition::ShearLayer, 100, BOX, 0.5, 0.0, &mut rng);
+    let total: f32 = vortices.iter().map(|v| v.circulation).sum();
+    // The circulation round the layer is the speed jump times its length
+    assert!((total + 0.5 * BOX[0]).abs() < 1e-4);
+    assert!(vortices.iter().all(|v| v.position[1].abs() < 1e-6));
+}
+
+#[test]
+fn dipoles_straddle_their_heading() {
+    let heading = 0.6_f32;
+    let [a, b] = dipole([0.1, -0.2], heading, 0.04, 0.3, BOX);
+    assert_eq!(a.circulation, 0.3);
+    assert_eq!(b.circulation, -0.3);
+
+    let between = [a.position[0] - b.position[0], a.position[1] - b.position[1]];
+    assert!((between[0].hypot(between[1]) - 0.04).abs() < 1e-6);
+    // The line between them is across the heading
+    let along = between[0] * heading.cos() + between[1] * heading.sin();
+    assert!(along.abs() < 1e-6);
+    let middle = [
+        (a.position[0] + b.position[0]) * 0.5,
+        (a.position[1] + b.position[1]) * 0.5,
+    ];
+    assert!((middle[0] - 0.1).abs() < 1e-6 && (middle[1] + 0.2).abs() < 1e-6);
+
+    // Each carries the other off at circulation / (2π spacing)
+    let circulation = dipole_circulation(2.0, 0.04);
+    assert!((circulation / (std::f32::consts::TAU * 0.04) - 2.0).abs() < 1e-5);
+}
+
+#[test]
+fn the_velocity_grid_keeps_the_box_proportions() {
+    assert_eq!(grid_size(BOX, 100), (160, 100));
+    assert_eq!(grid_size([1.0, 1.0], 64), (64, 64));
+    assert_eq!(grid_size([0.01, 1.0], 16), (1, 16));
+}
+
+#[tokio::test]
+async fn shaders_compile() {
+    let harness = KernelHarness::new().await;
+    for (label, source) in [
+        ("Vortex Shader", VORTEX_SHADER),
+        ("Vortex Render Infinite Shader", RENDER_INFINITE_SHADER),
+    ] {
+        let _ = harness
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(label),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+    }
+}
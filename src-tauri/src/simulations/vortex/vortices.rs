@@ -0,0 +1,144 @@
+//! Vortex particles and the arrangements a run starts from.
+//!
+//! Positions are in the simulation's box, centered on the origin and
+//! `box_size` across, which wraps around at its edges. A vortex with
+//! positive circulation turns the fluid around it counterclockwise.
+
+use bytemuck::{Pod, Zeroable};
+use rand::Rng;
+
+use super::settings::InitialCondition;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Pod, Zeroable)]
+pub struct Vortex {
+    pub position: [f32; 2],
+    /// Zero for unused slots, which then move nothing
+    pub circulation: f32,
+    pub _pad: f32,
+}
+
+impl Vortex {
+    pub fn new(position: [f32; 2], circulation: f32) -> Self {
+        Self {
+            position,
+            circulation,
+            _pad: 0.0,
+        }
+    }
+}
+
+/// `position` moved back into the box if it's wandered out
+pub fn wrap(position: [f32; 2], box_size: [f32; 2]) -> [f32; 2] {
+    let wrap_axis = |p: f32, size: f32| (p + size * 0.5).rem_euclid(size) - size * 0.5;
+    [
+        wrap_axis(position[0], box_size[0]),
+        wrap_axis(position[1], box_size[1]),
+    ]
+}
+
+/// Lay out `count` vortices for `condition`. `strength` is the speed the
+/// fluid moves at, roughly: the jump in speed across a shear layer, or the
+/// typical speed among random vortices.
+pub fn seed(
+    condition: InitialCondition,
+    count: usize,
+    box_size: [f32; 2],
+    strength: f32,
+    perturbation: f32,
+    rng: &mut impl Rng,
+) -> Vec<Vortex> {
+    let [width, height] = box_size;
+    let wobble = |rng: &mut dyn rand::RngCore| rng.random_range(-1.0..=1.0) * perturbation;
+    match condition {
+        // A sheet of like vortices is a jump in speed between the fluid on
+        // either side of it, which rolls up into a row of curls. The sheet's
+        // circulation is the jump times its length.
+        InitialCondition::ShearLayer => {
+            let circulation = strength * width / count.max(1) as f32;
+            (0..count)
+                .map(|i| {
+                    let x = (i as f32 + 0.5) / count as f32 * width - width * 0.5;
+                    let y = wobble(rng) + perturbation * (std::f32::consts::TAU * x / width).sin();
+                    Vortex::new(wrap([x, y], box_size), -circulation)
+                })
+                .collect()
+        }
+        // Opposite sheets a half box apart, so the fluid between them runs
+        // one way and the rest the other, with nothing left over to turn the
+        // whole box
+        InitialCondition::DoubleShear => {
+            let per_layer = count / 2;
+            let circulation = strength * width / per_layer.max(1) as f32;
+            (0..per_layer * 2)
+                .map(|i| {
+                    let layer = i % 2;
+                    let x = ((i / 2) as f32 + 0.5) / per_layer as f32 * width - width * 0.5;
+                    let y = if layer == 0 {
+                        height * 0.25
+                    } else {
+                        -height * 0.25
+                    };
+                    let sign = if layer == 0 { -1.0 } else { 1.0 };
+                    Vortex::new(wrap([x, y + wobble(rng)], box_size), sign * circulation)
+                })
+                .collect()
+        }
+        // Equal numbers each way round, so the box as a whole stays still
+        InitialCondition::RandomVortices => {
+            let circulation = strength * (width * height / count.max(1) as f32).sqrt();
+            (0..count)
+                .map(|i| {
+                    let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                    let position = [
+                        rng.random_range(-0.5..0.5) * width,
+                        rng.random_range(-0.5..0.5) * height,
+                    ];
+                    Vortex::new(position, sign * circulation * rng.random_range(0.5..1.5))
+                })
+                .collect()
+        }
+        // Pairs of opposite vortices, which carry each other off in a
+        // straight line until they run into something
+        InitialCondition::Dipoles => {
+            let pairs = count / 2;
+            let circulation = strength * (width * height / pairs.max(1) as f32).sqrt() * 0.5;
+            let mut vortices = Vec::with_capacity(pairs * 2);
+            for _ in 0..pairs {
+                let center = [
+                    rng.random_range(-0.5..0.5) * width,
+                    rng.random_range(-0.5..0.5) * height,
+                ];
+                let heading = rng.random_range(0.0..std::f32::consts::TAU);
+                let spacing = height * 0.02;
+                vortices.extend(dipole(center, heading, spacing, circulation, box_size));
+            }
+            vortices
+        }
+    }
+}
+
+/// Two opposite vortices `spacing` apart, moving off together toward
+/// `heading` radians
+pub fn dipole(
+    center: [f32; 2],
+    heading: f32,
+    spacing: f32,
+    circulation: f32,
+    box_size: [f32; 2],
+) -> [Vortex; 2] {
+    // The pair moves along the perpendicular to the line between them, away
+    // from the side where both push the same way
+    let (sin, cos) = heading.sin_cos();
+    let side = [-sin * spacing * 0.5, cos * spacing * 0.5];
+    [
+        Vortex::new(
+            wrap([center[0] + side[0], center[1] + side[1]], box_size),
+            circulation,
+        ),
+        Vortex::new(
+            wrap([center[0] - side[0], center[1] - side[1]], box_size),
+            -circulation,
+        ),
+    ]
+}
@@ -0,0 +1,59 @@
+/**
+ * Microphone capture for audio-reactive settings
+ * Streams mono sample blocks to the backend, which does the band analysis
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+const BLOCK_SIZE = 2048;
+
+export class AudioCapture {
+    private stream: MediaStream | null = null;
+    private context: AudioContext | null = null;
+    private processor: ScriptProcessorNode | null = null;
+    private source: MediaStreamAudioSourceNode | null = null;
+    private mute: GainNode | null = null;
+
+    get active(): boolean {
+        return this.context !== null;
+    }
+
+    async start(): Promise<void> {
+        if (this.active) return;
+
+        this.stream = await navigator.mediaDevices.getUserMedia({ audio: true, video: false });
+        this.context = new AudioContext();
+        this.source = this.context.createMediaStreamSource(this.stream);
+        this.processor = this.context.createScriptProcessor(BLOCK_SIZE, 1, 1);
+        // The processor only runs while connected to the destination, so route it through silence
+        this.mute = this.context.createGain();
+        this.mute.gain.value = 0;
+
+        const sampleRate = this.context.sampleRate;
+        this.processor.onaudioprocess = (event) => {
+            const samples = Array.from(event.inputBuffer.getChannelData(0));
+            invoke('push_audio_samples', { samples, sampleRate }).catch((e) =>
+                console.error('Failed to push audio samples:', e)
+            );
+        };
+
+        this.source.connect(this.processor);
+        this.processor.connect(this.mute);
+        this.mute.connect(this.context.destination);
+    }
+
+    async stop(): Promise<void> {
+        if (this.processor) this.processor.onaudioprocess = null;
+        this.source?.disconnect();
+        this.processor?.disconnect();
+        this.mute?.disconnect();
+        this.stream?.getTracks().forEach((track) => track.stop());
+        await this.context?.close();
+
+        this.stream = null;
+        this.context = null;
+        this.processor = null;
+        this.source = null;
+        this.mute = null;
+    }
+}